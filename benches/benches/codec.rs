@@ -0,0 +1,89 @@
+//! Benchmarks for decoding received frames with the transport codec.
+//!
+//! Compares `serde_json::from_str` on an owned `String` (how transports
+//! parsed messages before the codec) with `codec::decode` on a `Bytes` frame
+//! and the borrowed `codec::decode_ref` envelope.
+//!
+//! Run with: `cargo bench --package mcpkit-benches --bench codec`
+//!
+//! Add `--features simd-json` to decode with simd-json instead of serde_json.
+
+// Allow missing docs for criterion_group! macro generated functions
+#![allow(missing_docs)]
+
+use criterion::{
+    BatchSize, BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main,
+};
+use mcpkit_core::protocol::{Message, Request, RequestId, Response};
+use mcpkit_transport::{Bytes, codec};
+use serde_json::{Value, json};
+
+/// A `tools/call` request, as a server receives it.
+fn tool_call() -> String {
+    let request = Request::with_params(
+        "tools/call",
+        RequestId::Number(12345),
+        json!({
+            "name": "search_database",
+            "arguments": {
+                "query": "SELECT * FROM users WHERE active = true",
+                "options": {"limit": 100, "fields": ["id", "name", "email"]}
+            }
+        }),
+    );
+    serde_json::to_string(&Message::Request(request)).unwrap()
+}
+
+/// A tool result with `items` text blocks, as a client receives it.
+fn tool_result(items: usize) -> String {
+    let content: Vec<Value> = (0..items)
+        .map(|i| json!({"type": "text", "text": format!("Result item {i}: {}", "x".repeat(80))}))
+        .collect();
+    let response = Response::success(
+        RequestId::Number(1),
+        json!({"content": content, "isError": false}),
+    );
+    serde_json::to_string(&Message::Response(response)).unwrap()
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("codec_decode");
+
+    let frames = [
+        ("tool_call", tool_call()),
+        ("result_10", tool_result(10)),
+        ("result_100", tool_result(100)),
+        ("result_1000", tool_result(1000)),
+    ];
+
+    for (name, frame) in &frames {
+        group.throughput(Throughput::Bytes(frame.len() as u64));
+
+        // Each iteration gets a uniquely owned frame, as a transport would,
+        // and frees it afterwards.
+        group.bench_with_input(BenchmarkId::new("serde_json_str", name), frame, |b, f| {
+            b.iter_batched(
+                || f.clone(),
+                |line| serde_json::from_str::<Message>(&line).unwrap(),
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("codec_bytes", name), frame, |b, f| {
+            b.iter_batched(
+                || Bytes::from(f.clone().into_bytes()),
+                |frame| codec::decode(frame).unwrap(),
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.bench_with_input(BenchmarkId::new("codec_ref", name), frame, |b, f| {
+            b.iter(|| codec::decode_ref(black_box(f.as_bytes())).unwrap().id);
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+//! Benchmarks for resolving the tool named in a `tools/call`.
+//!
+//! Compares comparing the name with each tool in turn, as a generated `match`
+//! on the name does, against a `DispatchTable`, which `#[mcp_server]` uses
+//! from 16 tools up.
+//!
+//! Run with: `cargo bench --package mcpkit-benches --bench tool_lookup`
+
+// Allow missing docs for criterion_group! macro generated functions
+#![allow(missing_docs)]
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use mcpkit_server::router::DispatchTable;
+
+/// `count` tool names, leaked so a table can borrow them for `'static`.
+fn tool_names(count: usize) -> &'static [&'static str] {
+    let names: Vec<&'static str> = (0..count)
+        .map(|i| &*Box::leak(format!("tool_{i:04}").into_boxed_str()))
+        .collect();
+    Box::leak(names.into_boxed_slice())
+}
+
+fn bench_tool_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tool_dispatch");
+
+    for count in [8, 16, 100, 500] {
+        let names = tool_names(count);
+        let table = DispatchTable::new(names);
+        // The last tool is the worst case for a sequential match.
+        let last = names[count - 1];
+        let middle = names[count / 2];
+
+        for (case, name) in [("last", last), ("middle", middle), ("unknown", "missing")] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("match_{case}"), count),
+                &name,
+                |b, name| b.iter(|| names.iter().position(|n| *n == black_box(*name))),
+            );
+            group.bench_with_input(
+                BenchmarkId::new(format!("table_{case}"), count),
+                &name,
+                |b, name| b.iter(|| table.get(black_box(name))),
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_tool_dispatch);
+criterion_main!(benches);
@@ -1,23 +1,18 @@
 //! HTTP handlers for MCP requests.
 
+use crate::SUPPORTED_VERSIONS;
 use crate::error::ExtensionError;
 use crate::state::{HasServerInfo, McpState, OAuthState};
-use crate::{SUPPORTED_VERSIONS, is_supported_version};
+use actix_web::http::StatusCode;
 use actix_web::http::header::ContentType;
 use actix_web::{HttpMessage, HttpRequest, HttpResponse, web};
 use futures::stream::{self, StreamExt};
 use mcpkit_core::auth::VerifiedUser;
-use mcpkit_core::capability::ClientCapabilities;
 use mcpkit_core::protocol::Message;
-use mcpkit_core::protocol_version::ProtocolVersion;
-use mcpkit_server::capability::tasks::{TaskManager, route_task_store};
-use mcpkit_server::context::{Context, NoOpPeer};
-use mcpkit_server::{
-    AugmentedTaskOutcome, PromptHandler, ResourceHandler, ServerHandler, ToolHandler,
-    begin_augmented_task, route_completion, route_logging, route_prompts, route_resources,
-    route_tools,
-};
-use std::sync::Arc;
+use mcpkit_core::types::task::TaskId;
+use mcpkit_server::health::{HealthProbes, ProbeResponse, session_health};
+use mcpkit_server::service::{ServiceOutcome, ServiceRejection, ServiceRequest};
+use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
@@ -32,10 +27,13 @@ use tracing::{debug, info, warn};
 ///   compatibility.
 /// - `mcp-session-id`: Optional. Used to track sessions.
 /// - `Content-Type`: Should be `application/json`.
+/// - `Accept`: Picks JSON or an SSE stream for the response; see
+///   [`ResponseMode::negotiate`](mcpkit_server::service::ResponseMode::negotiate).
 ///
 /// # Response
 ///
-/// Returns a JSON-RPC response for request messages, or 202 Accepted for notifications.
+/// Returns a JSON-RPC response for request messages (as JSON or as an SSE
+/// stream), or 202 Accepted for notifications.
 pub async fn handle_mcp_post<H>(
     req: HttpRequest,
     state: web::Data<McpState<H>>,
@@ -44,304 +42,68 @@ pub async fn handle_mcp_post<H>(
 where
     H: ServerHandler + ToolHandler + ResourceHandler + PromptHandler + Send + Sync + 'static,
 {
-    // Reject disallowed Origins (DNS-rebinding protection) before any work.
-    let origin = req.headers().get("origin").and_then(|v| v.to_str().ok());
-    if !state.origin_validator.is_allowed(origin) {
-        warn!(
-            origin = origin.unwrap_or("none"),
-            "Rejected: origin not allowed"
-        );
-        return Ok(HttpResponse::Forbidden().body("origin not allowed"));
-    }
-
-    // Validate protocol version
-    let version = req
-        .headers()
-        .get("mcp-protocol-version")
-        .and_then(|v| v.to_str().ok());
-
-    if !is_supported_version(version) {
-        let provided = version.unwrap_or("none");
-        warn!(version = provided, "Unsupported protocol version");
-        return Err(ExtensionError::UnsupportedVersion(format!(
-            "{} (supported: {})",
-            provided,
-            SUPPORTED_VERSIONS.join(", ")
-        )));
-    }
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+    };
 
     // The verified user (if any) is supplied by the application's auth
     // middleware via a request extension; mcpkit binds the session to it.
     let user = req.extensions().get::<VerifiedUser>().cloned();
-
-    // Get or create session
-    let session_id = req
-        .headers()
-        .get("mcp-session-id")
-        .and_then(|v| v.to_str().ok())
-        .map(String::from);
-
-    let session_id = match session_id {
-        Some(id) => match state.sessions.touch_verified(&id, user.as_ref()) {
-            Ok(true) => id,
-            // Reject an unknown session id rather than silently proceeding.
-            Ok(false) => {
-                warn!(session_id = %id, "Rejected: unknown session id");
-                return Err(ExtensionError::SessionNotFound(id));
-            }
-            Err(e) => {
-                warn!(session_id = %id, error = %e, "Rejected: session binding violation");
-                return Ok(HttpResponse::Forbidden().body(e.to_string()));
-            }
-        },
-        None => state.sessions.create_for_user(user),
-    };
-
-    debug!(session_id = %session_id, "Processing MCP request");
-
-    // Parse message
-    let msg: Message =
-        serde_json::from_str(&body).map_err(|e| ExtensionError::InvalidMessage(e.to_string()))?;
-
-    // Process message
-    match msg {
-        Message::Request(request) => {
-            info!(
-                method = %request.method,
-                id = ?request.id,
-                session_id = %session_id,
-                "Handling MCP request"
-            );
-
-            // On initialize, negotiate the protocol version and record it (and
-            // the client's capabilities) on the session, so subsequent requests
-            // observe the negotiated values and the session is no longer subject
-            // to the initialization timeout.
-            if request.method.as_ref() == "initialize" {
-                let (version, caps) = negotiate_initialize(request.params.as_ref());
-                state
-                    .sessions
-                    .update(&session_id, |s| s.mark_initialized(version, caps.clone()));
+    let request = ServiceRequest::from_bytes(body)
+        .with_origin(header("origin"))
+        .with_protocol_version(header("mcp-protocol-version"))
+        .with_session_id(header("mcp-session-id"))
+        .with_accept(header("accept"))
+        .with_user(user);
+
+    let reply = state.service().handle(request).await;
+    let sse = reply.sse_body();
+    match reply.outcome {
+        ServiceOutcome::Response {
+            session_id,
+            response,
+        } => {
+            let mut ok = HttpResponse::Ok();
+            ok.insert_header(("mcp-session-id", session_id));
+            if let Some(body) = sse {
+                return Ok(ok.content_type("text/event-stream").body(body));
             }
-
-            // Resolve the session's negotiated values for the request context,
-            // falling back to defaults before initialization completes.
-            let session = state.sessions.get(&session_id);
-            let protocol_version = session
-                .as_ref()
-                .and_then(|s| s.protocol_version)
-                .unwrap_or(ProtocolVersion::LATEST);
-            // This session's task store (per-session isolation for `tasks/*`).
-            let task_store = session.as_ref().map(|s| s.tasks.clone());
-            let client_caps = session
-                .and_then(|s| s.client_capabilities)
-                .unwrap_or_default();
-
-            // Create a basic response using the handler's capabilities
-            let response = create_response_for_request(
-                &state,
-                &request,
-                protocol_version,
-                &client_caps,
-                task_store.as_ref(),
-            )
-            .await;
-
             let body = serde_json::to_string(&Message::Response(response))
                 .map_err(ExtensionError::Serialization)?;
-
-            Ok(HttpResponse::Ok()
-                .content_type(ContentType::json())
-                .insert_header(("mcp-session-id", session_id))
-                .body(body))
-        }
-        Message::Notification(notification) => {
-            debug!(
-                method = %notification.method,
-                session_id = %session_id,
-                "Received notification"
-            );
-            Ok(HttpResponse::Accepted()
-                .insert_header(("mcp-session-id", session_id))
-                .finish())
-        }
-        _ => {
-            warn!("Unexpected message type received");
-            Err(ExtensionError::InvalidMessage(
-                "Expected request or notification".to_string(),
-            ))
+            Ok(ok.content_type(ContentType::json()).body(body))
         }
+        ServiceOutcome::Accepted { session_id } => Ok(HttpResponse::Accepted()
+            .insert_header(("mcp-session-id", session_id))
+            .finish()),
+        ServiceOutcome::Rejected(rejection) => rejection_response(rejection),
+        _ => Err(ExtensionError::Internal(
+            "unexpected service outcome".to_string(),
+        )),
     }
 }
 
-/// Negotiate the protocol version and extract client capabilities from an
-/// `initialize` request's params.
-///
-/// The negotiated version is the highest supported version not exceeding the
-/// client's requested version, falling back to the latest supported version
-/// when the request omits or names an unknown version.
-fn negotiate_initialize(
-    params: Option<&serde_json::Value>,
-) -> (ProtocolVersion, Option<ClientCapabilities>) {
-    let requested = params
-        .and_then(|p| p.get("protocolVersion"))
-        .and_then(serde_json::Value::as_str)
-        .unwrap_or("");
-    let version = ProtocolVersion::negotiate(requested, ProtocolVersion::ALL)
-        .unwrap_or(ProtocolVersion::LATEST);
-    let capabilities = params
-        .and_then(|p| p.get("capabilities"))
-        .and_then(|c| serde_json::from_value::<ClientCapabilities>(c.clone()).ok());
-    (version, capabilities)
-}
-
-/// Create a response for a request.
-///
-/// Routes all MCP methods through the appropriate handler traits.
-async fn create_response_for_request<H>(
-    state: &McpState<H>,
-    request: &mcpkit_core::protocol::Request,
-    protocol_version: ProtocolVersion,
-    client_caps: &ClientCapabilities,
-    task_store: Option<&Arc<TaskManager>>,
-) -> mcpkit_core::protocol::Response
-where
-    H: ServerHandler + ToolHandler + ResourceHandler + PromptHandler + Send + Sync + 'static,
-{
-    use mcpkit_core::error::JsonRpcError;
-    use mcpkit_core::protocol::Response;
-
-    let method = request.method.as_ref();
-    let params = request.params.as_ref();
-
-    // Create a context for the request
-    let req_id = request.id.clone();
-    let server_caps = state.effective_capabilities();
-    let peer = NoOpPeer;
-    let ctx = Context::new(
-        &req_id,
-        None,
-        client_caps,
-        &server_caps,
-        protocol_version,
-        &peer,
-    );
-
-    match method {
-        "ping" => Response::success(request.id.clone(), serde_json::json!({})),
-        "initialize" => {
-            let init_result = serde_json::json!({
-                "protocolVersion": protocol_version.as_str(),
-                "serverInfo": state.server_info,
-                "capabilities": server_caps,
-            });
-            Response::success(request.id.clone(), init_result)
+/// Render a request the service refused.
+fn rejection_response(rejection: ServiceRejection) -> Result<HttpResponse, ExtensionError> {
+    match rejection {
+        ServiceRejection::UnsupportedVersion(provided) => {
+            Err(ExtensionError::UnsupportedVersion(format!(
+                "{} (supported: {})",
+                provided,
+                SUPPORTED_VERSIONS.join(", ")
+            )))
         }
-        _ => {
-            // Task-augmented tools/call and tasks/* are served from this
-            // session's own task store (per-session isolation).
-            if let Some(store) = task_store {
-                if method == "tools/call" {
-                    match begin_augmented_task(
-                        state.handler.clone(),
-                        store,
-                        params,
-                        client_caps.clone(),
-                        server_caps.clone(),
-                        protocol_version,
-                    )
-                    .await
-                    {
-                        AugmentedTaskOutcome::Started(create_result, fut) => {
-                            tokio::spawn(fut);
-                            return Response::success(request.id.clone(), create_result);
-                        }
-                        AugmentedTaskOutcome::Rejected(e) => {
-                            return Response::error(request.id.clone(), e.into());
-                        }
-                        AugmentedTaskOutcome::NotApplicable => {}
-                    }
-                } else if let Some(result) = route_task_store(store, method, params).await {
-                    return match result {
-                        Ok(value) => Response::success(request.id.clone(), value),
-                        Err(e) => Response::error(request.id.clone(), e.into()),
-                    };
-                }
-            }
-
-            // Try routing to tools
-            if let Some(result) = route_tools(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing to resources
-            if let Some(result) = route_resources(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing to prompts
-            if let Some(result) = route_prompts(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing logging/setLevel (gated on the advertised capability)
-            if let Some(result) =
-                route_logging(state.handler.as_ref(), &server_caps, method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing completion/complete (when a completion handler is set)
-            if let Some(result) =
-                route_completion(state.completion.as_deref(), method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Method not found
-            Response::error(
-                request.id.clone(),
-                JsonRpcError::method_not_found(format!("Method '{method}' not found")),
-            )
+        ServiceRejection::SessionNotFound(id) => Err(ExtensionError::SessionNotFound(id)),
+        ServiceRejection::InvalidMessage(message) => Err(ExtensionError::InvalidMessage(message)),
+        ServiceRejection::UnexpectedMessage => Err(ExtensionError::InvalidMessage(
+            "Expected request or notification".to_string(),
+        )),
+        other => {
+            let status = StatusCode::from_u16(other.status_code())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            Ok(HttpResponse::build(status).body(other.to_string()))
         }
     }
 }
@@ -405,8 +167,20 @@ where
         (id, rx)
     };
 
-    // Create the SSE stream
-    let stream = create_sse_stream(id, rx);
+    // Create the SSE stream, ending it on shutdown so graceful shutdown is
+    // not held open by idle SSE connections.
+    state.sse_sessions.start_reaper();
+    let heartbeat = state.sse_sessions.session_config().heartbeat_interval;
+    let stream =
+        futures::StreamExt::take_until(create_sse_stream(id, rx, heartbeat), state.shutdown.wait());
+    #[cfg(feature = "prometheus")]
+    let stream = counted_sse_stream(
+        stream,
+        state
+            .metrics
+            .as_ref()
+            .map(mcpkit_transport::McpMetrics::sse_connection),
+    );
 
     HttpResponse::Ok()
         .content_type("text/event-stream")
@@ -418,6 +192,7 @@ where
 fn create_sse_stream(
     session_id: String,
     rx: tokio::sync::broadcast::Receiver<String>,
+    heartbeat: Option<Duration>,
 ) -> impl futures::Stream<Item = Result<web::Bytes, actix_web::error::Error>> {
     // First, send the connected event
     let connected_event = format!("event: connected\ndata: {session_id}\n\n");
@@ -425,10 +200,22 @@ fn create_sse_stream(
     // Create a stream that first yields the connected event, then messages
     let connected = stream::once(async move { Ok(web::Bytes::from(connected_event)) });
 
-    // Create message stream
-    let messages = stream::unfold(rx, |mut rx| async move {
+    // Create message stream, with a keep-alive comment whenever no message
+    // arrives within the heartbeat interval
+    let messages = stream::unfold(rx, move |mut rx| async move {
         loop {
-            match rx.recv().await {
+            let received = match heartbeat {
+                Some(interval) => {
+                    if let Ok(received) = tokio::time::timeout(interval, rx.recv()).await {
+                        received
+                    } else {
+                        let keepalive = web::Bytes::from_static(b": keepalive\n\n");
+                        return Some((Ok(keepalive), rx));
+                    }
+                }
+                None => rx.recv().await,
+            };
+            match received {
                 Ok(msg) => {
                     let event = format!("event: message\ndata: {msg}\n\n");
                     return Some((
@@ -448,17 +235,171 @@ fn create_sse_stream(
         }
     });
 
-    // Add periodic keep-alive comments
-    let keepalive = stream::unfold((), |()| async {
-        tokio::time::sleep(Duration::from_secs(15)).await;
-        Some((
-            Ok::<_, actix_web::error::Error>(web::Bytes::from_static(b": keepalive\n\n")),
-            (),
-        ))
+    // The connected event first, then messages until the channel closes
+    connected.chain(messages)
+}
+
+/// Handle SSE connections following a single task.
+///
+/// Mounted at `{post_path}/tasks/{task_id}/events`. Streams the task's
+/// `status` and `progress` events (see
+/// [`TaskEvent`](mcpkit_server::capability::tasks::TaskEvent)) and ends after
+/// the event that makes the task terminal.
+///
+/// # Headers
+///
+/// - `mcp-session-id`: Required. Tasks are scoped to the session that
+///   created them.
+/// - `last-event-id`: Optional. Resumes after the given event; without it,
+///   every retained event of the task is sent first.
+///
+/// Answers `404 Not Found` for an unknown session or task.
+pub async fn handle_task_events<H>(
+    req: HttpRequest,
+    path: web::Path<String>,
+    state: web::Data<McpState<H>>,
+) -> HttpResponse
+where
+    H: Send + Sync + 'static,
+{
+    let header = |name: &str| req.headers().get(name).and_then(|v| v.to_str().ok());
+    let origin = header("origin");
+    if !state.origin_validator.is_allowed(origin) {
+        warn!(
+            origin = origin.unwrap_or("none"),
+            "Rejected task SSE: origin not allowed"
+        );
+        return HttpResponse::Forbidden().body("origin not allowed");
+    }
+
+    let user = req.extensions().get::<VerifiedUser>().cloned();
+    let Some(session_id) = header("mcp-session-id") else {
+        return HttpResponse::BadRequest().body("missing mcp-session-id");
+    };
+    let session = match state.sessions.get_verified(session_id, user.as_ref()) {
+        Ok(Some(session)) => session,
+        Ok(None) => return HttpResponse::NotFound().body("unknown session"),
+        Err(e) => {
+            warn!(session_id, error = %e, "Rejected task SSE: session binding violation");
+            return HttpResponse::Forbidden().body(e.to_string());
+        }
+    };
+
+    let task_id = TaskId::new(path.into_inner());
+    if session.tasks.get(&task_id).is_none() {
+        return HttpResponse::NotFound().body("unknown task");
+    }
+    let after = header("last-event-id").and_then(|v| v.parse().ok());
+    debug!(
+        session_id,
+        task_id = task_id.as_str(),
+        ?after,
+        "Following task events"
+    );
+
+    let heartbeat = state.sse_sessions.session_config().heartbeat_interval;
+    let events = Box::pin(session.tasks.follow(task_id, after));
+    // A keep-alive comment whenever no event arrives within the heartbeat
+    // interval.
+    let stream = stream::unfold(events, move |mut events| async move {
+        let next = match heartbeat {
+            Some(interval) => {
+                if let Ok(next) = tokio::time::timeout(interval, events.next()).await {
+                    next
+                } else {
+                    let keepalive = web::Bytes::from_static(b": keepalive\n\n");
+                    return Some((Ok(keepalive), events));
+                }
+            }
+            None => events.next().await,
+        };
+        next.map(|event| {
+            let frame = format!(
+                "id: {}\nevent: {}\ndata: {}\n\n",
+                event.id,
+                event.event_type(),
+                event.data()
+            );
+            (
+                Ok::<_, actix_web::error::Error>(web::Bytes::from(frame)),
+                events,
+            )
+        })
     });
+    let stream = futures::StreamExt::take_until(stream, state.shutdown.wait());
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("Connection", "keep-alive"))
+        .streaming(stream)
+}
+
+/// Keep `connection` (counting the stream as an open SSE connection) alive
+/// until `stream` is dropped.
+#[cfg(feature = "prometheus")]
+fn counted_sse_stream<S: futures::Stream>(
+    stream: S,
+    connection: Option<mcpkit_transport::GaugeGuard>,
+) -> impl futures::Stream<Item = S::Item> {
+    stream.map(move |event| {
+        let _connection = &connection;
+        event
+    })
+}
+
+/// Serve the router's Prometheus metrics in the text exposition format.
+///
+/// Mounted at `/metrics` by [`McpRouter::with_metrics`](crate::McpRouter::with_metrics).
+/// Answers `404 Not Found` if the state has no metrics.
+#[cfg(feature = "prometheus")]
+pub async fn handle_metrics<H>(state: web::Data<McpState<H>>) -> HttpResponse {
+    let Some(metrics) = &state.metrics else {
+        return HttpResponse::NotFound().finish();
+    };
+    metrics.set_active_sessions(state.sessions.session_count());
+    let exporter = mcpkit_transport::MetricsExporter::from_metrics(metrics);
+    match exporter.gather() {
+        Ok(body) => HttpResponse::Ok()
+            .content_type(exporter.content_type())
+            .body(body),
+        Err(e) => {
+            warn!(error = %e, "Failed to encode metrics");
+            HttpResponse::InternalServerError().finish()
+        }
+    }
+}
 
-    // Merge the streams (connected first, then interleave messages and keepalive)
-    connected.chain(stream::select(messages, keepalive))
+/// Answer the liveness probe.
+///
+/// Mounted at `/healthz` by [`McpRouter::with_health_checks`](crate::McpRouter::with_health_checks).
+/// Answers `404 Not Found` if the state has no health probes.
+pub async fn handle_liveness<H>(req: HttpRequest, state: web::Data<McpState<H>>) -> HttpResponse {
+    let Some(probes) = &state.health else {
+        return HttpResponse::NotFound().finish();
+    };
+    probe_response(probes.liveness(HealthProbes::verbose_requested(Some(req.query_string()))))
+}
+
+/// Answer the readiness probe: the configured checks plus session health,
+/// which fails once shutdown begins.
+///
+/// Mounted at `/readyz` by [`McpRouter::with_health_checks`](crate::McpRouter::with_health_checks).
+/// Answers `404 Not Found` if the state has no health probes.
+pub async fn handle_readiness<H>(req: HttpRequest, state: web::Data<McpState<H>>) -> HttpResponse {
+    let Some(probes) = &state.health else {
+        return HttpResponse::NotFound().finish();
+    };
+    let sessions = session_health(state.sessions.session_count(), state.shutdown.is_shutdown());
+    let verbose = HealthProbes::verbose_requested(Some(req.query_string()));
+    probe_response(probes.readiness(sessions, verbose))
+}
+
+fn probe_response(probe: ProbeResponse) -> HttpResponse {
+    let status = StatusCode::from_u16(probe.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    HttpResponse::build(status)
+        .content_type(probe.content_type)
+        .body(probe.body)
 }
 
 /// Handle `.well-known/oauth-protected-resource` requests.
@@ -499,34 +440,3 @@ pub async fn handle_oauth_protected_resource(
         .content_type(ContentType::json())
         .body(body))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::negotiate_initialize;
-    use mcpkit_core::protocol_version::ProtocolVersion;
-
-    #[test]
-    fn negotiate_uses_requested_supported_version() {
-        let params = serde_json::json!({
-            "protocolVersion": "2025-06-18",
-            "capabilities": {}
-        });
-        let (version, caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::V2025_06_18);
-        assert!(caps.is_some());
-    }
-
-    #[test]
-    fn negotiate_defaults_to_latest_when_absent() {
-        let (version, caps) = negotiate_initialize(None);
-        assert_eq!(version, ProtocolVersion::LATEST);
-        assert!(caps.is_none());
-    }
-
-    #[test]
-    fn negotiate_unknown_version_falls_back_to_latest() {
-        let params = serde_json::json!({ "protocolVersion": "2099-01-01" });
-        let (version, _caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::LATEST);
-    }
-}
@@ -10,6 +10,7 @@
 //! - Session management with automatic cleanup
 //! - Protocol version validation
 //! - CORS support
+//! - Prometheus `/metrics` endpoint (`prometheus` feature)
 //!
 //! # HTTP Protocol Requirements
 //!
@@ -89,7 +90,12 @@ mod session;
 mod state;
 
 pub use error::ExtensionError;
-pub use handler::{handle_mcp_post, handle_oauth_protected_resource, handle_sse};
+#[cfg(feature = "prometheus")]
+pub use handler::handle_metrics;
+pub use handler::{
+    handle_liveness, handle_mcp_post, handle_oauth_protected_resource, handle_readiness,
+    handle_sse, handle_task_events,
+};
 pub use router::McpRouter;
 pub use session::{
     DEFAULT_INIT_TIMEOUT, EventStore, EventStoreConfig, Session, SessionManager, SessionStore,
@@ -106,7 +112,9 @@ pub use state::{McpState, OAuthState};
 /// ```
 pub mod prelude {
     pub use crate::error::ExtensionError;
-    pub use crate::handler::{handle_mcp_post, handle_oauth_protected_resource, handle_sse};
+    pub use crate::handler::{
+        handle_mcp_post, handle_oauth_protected_resource, handle_sse, handle_task_events,
+    };
     pub use crate::router::McpRouter;
     pub use crate::session::{
         DEFAULT_INIT_TIMEOUT, EventStore, EventStoreConfig, Session, SessionManager, SessionStore,
@@ -1,12 +1,19 @@
 //! Router builder for MCP endpoints.
 
-use crate::handler::{handle_mcp_post, handle_oauth_protected_resource, handle_sse};
+use crate::handler::{
+    handle_liveness, handle_mcp_post, handle_oauth_protected_resource, handle_readiness,
+    handle_sse, handle_task_events,
+};
 use crate::state::{HasServerInfo, McpState, OAuthState};
 use actix_cors::Cors;
 use actix_web::middleware::Logger;
 use actix_web::{App, HttpServer, web};
 use mcpkit_core::auth::ProtectedResourceMetadata;
-use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use mcpkit_server::health::{HealthChecker, HealthProbes, LIVENESS_PATH, READINESS_PATH};
+use mcpkit_server::service::ResponseMode;
+use mcpkit_server::shutdown::DEFAULT_SHUTDOWN_TIMEOUT;
+use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ShutdownHandle, ToolHandler};
+use mcpkit_transport::TransportLimits;
 use mcpkit_transport::http::OriginValidator;
 use std::sync::Arc;
 
@@ -45,6 +52,7 @@ pub struct McpRouter<H> {
     post_path: String,
     sse_path: String,
     oauth_metadata: Option<ProtectedResourceMetadata>,
+    limits: Option<TransportLimits>,
 }
 
 impl<H> McpRouter<H>
@@ -67,6 +75,7 @@ where
             post_path: "/mcp".to_string(),
             sse_path: "/mcp/sse".to_string(),
             oauth_metadata: None,
+            limits: None,
         }
     }
 
@@ -88,6 +97,113 @@ where
         self
     }
 
+    /// Rate limit requests per session, per method, and per tool.
+    ///
+    /// Buckets are keyed by the `mcp-session-id` header. A request over a
+    /// quota gets a JSON-RPC error whose `data.retry_after_ms` says when to
+    /// retry. Disabled by default.
+    #[must_use]
+    pub fn with_rate_limit(mut self, config: mcpkit_server::RateLimitConfig) -> Self {
+        self.state = self.state.with_rate_limit(config);
+        self
+    }
+
+    /// Check `tools/call` requests against a tool policy.
+    ///
+    /// The policy sees the `VerifiedUser` your auth middleware attached, and a
+    /// denied call gets a JSON-RPC error with code `-32011`. Disabled by
+    /// default.
+    #[must_use]
+    pub fn with_tool_authorizer(mut self, authorizer: mcpkit_server::ToolAuthorizer) -> Self {
+        self.state = self.state.with_tool_authorizer(authorizer);
+        self
+    }
+
+    /// Write an audit record for each tool call and resource read.
+    ///
+    /// Records carry the `mcp-session-id` and the verified user's subject.
+    /// See [`AuditLogger`](mcpkit_server::AuditLogger) for sinks, sampling,
+    /// and redaction. Disabled by default.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: mcpkit_server::AuditLogger) -> Self {
+        self.state = self.state.with_audit_logger(logger);
+        self
+    }
+
+    /// Trace tool, resource, and prompt invocations.
+    ///
+    /// Each call runs in an `mcp.tool`, `mcp.resource`, or `mcp.prompt` span
+    /// carrying the request and session ids, duration, and outcome. See
+    /// [`InvocationTracing`](mcpkit_server::InvocationTracing) for argument
+    /// capture. Arguments are not captured by default.
+    #[must_use]
+    pub fn with_invocation_tracing(mut self, tracing: mcpkit_server::InvocationTracing) -> Self {
+        self.state = self.state.with_invocation_tracing(tracing);
+        self
+    }
+
+    /// Record request counts, errors, and latency percentiles per method and
+    /// per tool into the layer's
+    /// [`ServerMetrics`](mcpkit_server::ServerMetrics).
+    ///
+    /// With [`MetricsLayer::expose_resource`](mcpkit_server::MetricsLayer::expose_resource),
+    /// clients can also read a JSON snapshot from the `metrics://server`
+    /// resource. Disabled by default.
+    #[must_use]
+    pub fn with_server_metrics(mut self, metrics: mcpkit_server::MetricsLayer) -> Self {
+        self.state = self.state.with_server_metrics(metrics);
+        self
+    }
+
+    /// Choose how POSTed requests are answered when the client's `Accept`
+    /// header allows both JSON and SSE.
+    ///
+    /// With [`ResponseMode::Sse`], the response is a `text/event-stream`
+    /// carrying the notifications the handler sent (progress, logs) and then
+    /// the JSON-RPC response. Clients that accept only `application/json`
+    /// still get plain JSON. Defaults to [`ResponseMode::Json`].
+    #[must_use]
+    pub const fn with_response_mode(mut self, mode: ResponseMode) -> Self {
+        self.state.response_mode = mode;
+        self
+    }
+
+    /// Apply [`TransportLimits`] to the MCP endpoints.
+    ///
+    /// Bodies over `max_message_size` are answered with `413 Payload Too
+    /// Large` and requests beyond `max_in_flight` with `503 Service
+    /// Unavailable`. `read_timeout` is not applied; configure it on the
+    /// `HttpServer` (e.g. `client_request_timeout`).
+    #[must_use]
+    pub fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.state = self.state.with_limits(limits.clone());
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Record Prometheus metrics and serve them at `GET /metrics`.
+    ///
+    /// Exports request counts and latencies per JSON-RPC method, error counts
+    /// by JSON-RPC error code, open sessions, open SSE streams, and tool calls
+    /// and failures per tool. Requires the `prometheus` feature.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_metrics(self) -> Self {
+        let metrics =
+            mcpkit_transport::McpMetrics::new().expect("MCP metric names are valid and distinct");
+        self.with_prometheus(metrics)
+    }
+
+    /// Like [`with_metrics`](Self::with_metrics), recording into an existing
+    /// [`McpMetrics`](mcpkit_transport::McpMetrics), e.g. one shared with a
+    /// transport's `TelemetryLayer`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_prometheus(mut self, metrics: mcpkit_transport::McpMetrics) -> Self {
+        self.state.metrics = Some(metrics);
+        self
+    }
+
     /// Set the default task retention (milliseconds) for each session's task
     /// store, applied when a task-augmented `tools/call` omits a `ttl`. Pass
     /// `None` for unlimited retention.
@@ -97,6 +213,51 @@ where
         self
     }
 
+    /// Set session lifetimes, the session cap, and the SSE keep-alive
+    /// interval.
+    ///
+    /// A reaper task, started with the first SSE stream, ends streams idle
+    /// past `idle_timeout` or older than `max_age` and reports each eviction
+    /// to the config's `on_evict` callbacks. The idle timeout also applies to
+    /// HTTP sessions. See [`SessionConfig`](mcpkit_server::SessionConfig) for
+    /// the defaults.
+    #[must_use]
+    pub fn with_session_config(mut self, config: mcpkit_server::SessionConfig) -> Self {
+        self.state = self.state.with_session_config(config);
+        self
+    }
+
+    /// Serve Kubernetes liveness and readiness probes at `/healthz` and
+    /// `/readyz`.
+    ///
+    /// `/healthz` answers `200` while the process responds. `/readyz`
+    /// answers `503` once shutdown begins. Add `?verbose` for a JSON report.
+    /// Use [`with_readiness_checks`](Self::with_readiness_checks) to make
+    /// readiness depend on your own components as well.
+    #[must_use]
+    pub fn with_health_checks(self) -> Self {
+        let checker = HealthChecker::new(&self.state.server_info.name);
+        self.with_readiness_checks(checker)
+    }
+
+    /// Like [`with_health_checks`](Self::with_health_checks), with `/readyz`
+    /// also running `checker`'s checks (e.g. that the database is reachable).
+    #[must_use]
+    pub fn with_readiness_checks(mut self, checker: HealthChecker) -> Self {
+        self.state.health = Some(HealthProbes::new(checker));
+        self
+    }
+
+    /// Get a [`ShutdownHandle`] that stops [`serve`](Self::serve) gracefully.
+    ///
+    /// Once triggered, new requests get `503 Service Unavailable`, open SSE
+    /// streams end, and `serve` returns after in-flight requests finish or
+    /// [`DEFAULT_SHUTDOWN_TIMEOUT`] elapses.
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.state.shutdown.clone()
+    }
+
     /// Enable CORS with permissive defaults.
     ///
     /// For production, you should configure CORS manually with custom settings.
@@ -193,16 +354,42 @@ where
     /// Configure an Actix App with MCP routes.
     ///
     /// This is useful when you need to integrate MCP routes with an existing Actix application.
+    /// Besides the POST and SSE endpoints, each task can be followed as an
+    /// SSE stream at `{post_path}/tasks/{task_id}/events` (see
+    /// [`handle_task_events`]).
     pub fn configure_app(&self) -> impl Fn(&mut web::ServiceConfig) + Clone + 'static {
         let state = self.state.clone();
         let post_path = self.post_path.clone();
         let sse_path = self.sse_path.clone();
+        let task_events_path = format!(
+            "{}/tasks/{{task_id}}/events",
+            self.post_path.trim_end_matches('/')
+        );
         let oauth_metadata = self.oauth_metadata.clone();
+        let max_body = self.limits.as_ref().map(|l| l.max_message_size);
 
         move |cfg: &mut web::ServiceConfig| {
             cfg.app_data(web::Data::new(state.clone()))
                 .route(&post_path, web::post().to(handle_mcp_post::<H>))
-                .route(&sse_path, web::get().to(handle_sse::<H>));
+                .route(&sse_path, web::get().to(handle_sse::<H>))
+                .route(&task_events_path, web::get().to(handle_task_events::<H>));
+
+            if let Some(max_body) = max_body {
+                cfg.app_data(web::PayloadConfig::new(max_body));
+            }
+
+            if state.health.is_some() {
+                cfg.route(LIVENESS_PATH, web::get().to(handle_liveness::<H>))
+                    .route(READINESS_PATH, web::get().to(handle_readiness::<H>));
+            }
+
+            #[cfg(feature = "prometheus")]
+            if state.metrics.is_some() {
+                cfg.route(
+                    "/metrics",
+                    web::get().to(crate::handler::handle_metrics::<H>),
+                );
+            }
 
             // Add OAuth discovery endpoint if configured
             if let Some(metadata) = &oauth_metadata {
@@ -227,52 +414,55 @@ where
     /// McpRouter::new(handler).serve("0.0.0.0:3000").await?;
     /// ```
     ///
-    /// For more control over the server, use [`Self::configure_app`] instead.
+    /// Stops gracefully once the [`shutdown_handle`](Self::shutdown_handle)
+    /// is triggered. For more control over the server, use
+    /// [`Self::configure_app`] instead.
     pub async fn serve(self, addr: &str) -> std::io::Result<()> {
         let enable_cors = self.enable_cors;
         let enable_logging = self.enable_logging;
+        let shutdown = self.shutdown_handle();
         let configure = self.configure_app();
+        let timeout = DEFAULT_SHUTDOWN_TIMEOUT.as_secs();
 
         // Due to Actix's type system, we need to handle middleware combinations explicitly
-        match (enable_cors, enable_logging) {
-            (true, true) => {
-                HttpServer::new(move || {
-                    App::new()
-                        .configure(configure.clone())
-                        .wrap(Cors::permissive())
-                        .wrap(Logger::default())
-                })
-                .bind(addr)?
-                .run()
-                .await
-            }
-            (true, false) => {
-                HttpServer::new(move || {
-                    App::new()
-                        .configure(configure.clone())
-                        .wrap(Cors::permissive())
-                })
-                .bind(addr)?
-                .run()
-                .await
-            }
-            (false, true) => {
-                HttpServer::new(move || {
-                    App::new()
-                        .configure(configure.clone())
-                        .wrap(Logger::default())
-                })
+        let server = match (enable_cors, enable_logging) {
+            (true, true) => HttpServer::new(move || {
+                App::new()
+                    .configure(configure.clone())
+                    .wrap(Cors::permissive())
+                    .wrap(Logger::default())
+            })
+            .shutdown_timeout(timeout)
+            .bind(addr)?
+            .run(),
+            (true, false) => HttpServer::new(move || {
+                App::new()
+                    .configure(configure.clone())
+                    .wrap(Cors::permissive())
+            })
+            .shutdown_timeout(timeout)
+            .bind(addr)?
+            .run(),
+            (false, true) => HttpServer::new(move || {
+                App::new()
+                    .configure(configure.clone())
+                    .wrap(Logger::default())
+            })
+            .shutdown_timeout(timeout)
+            .bind(addr)?
+            .run(),
+            (false, false) => HttpServer::new(move || App::new().configure(configure.clone()))
+                .shutdown_timeout(timeout)
                 .bind(addr)?
-                .run()
-                .await
-            }
-            (false, false) => {
-                HttpServer::new(move || App::new().configure(configure.clone()))
-                    .bind(addr)?
-                    .run()
-                    .await
-            }
-        }
+                .run(),
+        };
+
+        let handle = server.handle();
+        actix_web::rt::spawn(async move {
+            shutdown.wait().await;
+            handle.stop(true).await;
+        });
+        server.await
     }
 }
 
@@ -408,4 +598,132 @@ mod tests {
                 .is_allowed(Some("https://evil.example.com"))
         );
     }
+
+    #[actix_rt::test]
+    async fn with_limits_rejects_oversized_bodies() {
+        let router = McpRouter::new(TestHandler)
+            .with_limits(TransportLimits::new().with_max_message_size(16));
+        let app = actix_web::test::init_service(App::new().configure(router.configure_app())).await;
+        let req = actix_web::test::TestRequest::post()
+            .uri("/mcp")
+            .set_payload(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(
+            resp.status(),
+            actix_web::http::StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[actix_rt::test]
+    async fn with_response_mode_answers_in_sse() {
+        let router = McpRouter::new(TestHandler).with_response_mode(ResponseMode::Sse);
+        let app = actix_web::test::init_service(App::new().configure(router.configure_app())).await;
+        let req = actix_web::test::TestRequest::post()
+            .uri("/mcp")
+            .insert_header(("mcp-protocol-version", "2025-06-18"))
+            .insert_header(("accept", "application/json, text/event-stream"))
+            .set_payload(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "text/event-stream"
+        );
+        let body = actix_web::test::read_body(resp).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with("event: message\ndata: "), "{body}");
+    }
+
+    #[actix_rt::test]
+    async fn task_events_stream_until_terminal_and_resume() {
+        let router = McpRouter::new(TestHandler);
+        let session_id = router.state.sessions.create();
+        let tasks = router.state.sessions.get(&session_id).unwrap().tasks;
+        let handle = tasks.create(None);
+        handle.record_progress(1.0, None, None).unwrap();
+        handle.complete(serde_json::json!({})).unwrap();
+        let path = format!("/mcp/tasks/{}/events", handle.id().as_str());
+        let app = actix_web::test::init_service(App::new().configure(router.configure_app())).await;
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&path)
+            .insert_header(("mcp-session-id", session_id.as_str()))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let body = actix_web::test::read_body(resp).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        let events: Vec<_> = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("event: "))
+            .collect();
+        assert_eq!(events, ["status", "progress", "status"], "{body}");
+
+        let req = actix_web::test::TestRequest::get()
+            .uri(&path)
+            .insert_header(("mcp-session-id", session_id.as_str()))
+            .insert_header(("last-event-id", "2"))
+            .to_request();
+        let body = actix_web::test::call_and_read_body(&app, req).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with("id: 3\nevent: status\n"), "{body}");
+        assert_eq!(body.matches("event: ").count(), 1, "{body}");
+
+        let req = actix_web::test::TestRequest::get()
+            .uri("/mcp/tasks/unknown/events")
+            .insert_header(("mcp-session-id", session_id.as_str()))
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+
+    #[actix_rt::test]
+    async fn health_checks_report_liveness_and_readiness() {
+        let mut checker = HealthChecker::new("test-server");
+        checker.add_check("database", mcpkit_server::ComponentHealth::healthy);
+        let router = McpRouter::new(TestHandler).with_readiness_checks(checker);
+        let shutdown = router.shutdown_handle();
+        let app = actix_web::test::init_service(App::new().configure(router.configure_app())).await;
+        let get = |uri: &str| actix_web::test::TestRequest::get().uri(uri).to_request();
+
+        let resp = actix_web::test::call_service(&app, get("/healthz")).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(actix_web::test::read_body(resp).await, "ok");
+
+        let body = actix_web::test::call_and_read_body(&app, get("/readyz?verbose")).await;
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report["status"], "healthy");
+        assert_eq!(report["components"]["database"]["status"], "healthy");
+
+        shutdown.shutdown();
+        let resp = actix_web::test::call_service(&app, get("/readyz")).await;
+        assert_eq!(resp.status(), 503);
+        assert_eq!(actix_web::test::read_body(resp).await, "not ready");
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[actix_rt::test]
+    async fn with_metrics_serves_request_counts() {
+        let router = McpRouter::new(TestHandler).with_metrics();
+        let app = actix_web::test::init_service(App::new().configure(router.configure_app())).await;
+        let req = actix_web::test::TestRequest::post()
+            .uri("/mcp")
+            .insert_header(("mcp-protocol-version", "2025-06-18"))
+            .set_payload(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+            .to_request();
+        let resp = actix_web::test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let req = actix_web::test::TestRequest::get()
+            .uri("/metrics")
+            .to_request();
+        let body = actix_web::test::call_and_read_body(&app, req).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            body.contains(r#"mcp_requests_total{method="ping"} 1"#),
+            "{body}"
+        );
+        assert!(body.contains("mcp_active_sessions 1"), "{body}");
+    }
 }
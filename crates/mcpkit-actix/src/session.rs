@@ -4,9 +4,11 @@ use dashmap::DashMap;
 use mcpkit_core::auth::{SessionBindingError, VerifiedUser, check_session_binding};
 use mcpkit_core::capability::ClientCapabilities;
 use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_server::service::{SessionSnapshot, SessionStorage};
+use mcpkit_server::session::{EvictionReason, SessionConfig};
 use std::collections::VecDeque;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tokio::sync::broadcast;
@@ -324,18 +326,45 @@ impl EventStore {
     }
 }
 
+/// An SSE session's channel and activity times.
+#[derive(Debug)]
+struct SseChannel {
+    tx: broadcast::Sender<String>,
+    created_at: Instant,
+    last_active: Instant,
+}
+
+impl SseChannel {
+    fn new(tx: broadcast::Sender<String>) -> Self {
+        let now = Instant::now();
+        Self {
+            tx,
+            created_at: now,
+            last_active: now,
+        }
+    }
+}
+
 /// Session manager for SSE connections.
 ///
 /// Manages broadcast channels for pushing messages to SSE clients,
 /// with optional event storage for message resumability.
+///
+/// Sessions are evicted according to a [`SessionConfig`]: by the reaper
+/// task (see [`Self::start_reaper`]) once idle or too old, and when a new
+/// session needs room under the maximum session count. Evicting a session
+/// closes its channel, which ends its SSE stream.
 #[derive(Debug)]
 pub struct SessionManager {
-    sessions: DashMap<String, broadcast::Sender<String>>,
+    sessions: DashMap<String, SseChannel>,
     /// Event stores for each session (for SSE resumability).
     event_stores: DashMap<String, Arc<EventStore>>,
     /// Configuration for event stores.
     event_store_config: EventStoreConfig,
     capacity: usize,
+    /// Session lifetime settings.
+    pub(crate) config: SessionConfig,
+    reaper_started: AtomicBool,
 }
 
 impl Default for SessionManager {
@@ -359,6 +388,8 @@ impl SessionManager {
             event_stores: DashMap::new(),
             event_store_config: EventStoreConfig::default(),
             capacity,
+            config: SessionConfig::default(),
+            reaper_started: AtomicBool::new(false),
         }
     }
 
@@ -370,15 +401,44 @@ impl SessionManager {
             event_stores: DashMap::new(),
             event_store_config: config,
             capacity: DEFAULT_SSE_CAPACITY,
+            config: SessionConfig::default(),
+            reaper_started: AtomicBool::new(false),
         }
     }
 
+    /// Set the session lifetime settings.
+    #[must_use]
+    pub fn with_session_config(mut self, config: SessionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The session lifetime settings.
+    #[must_use]
+    pub const fn session_config(&self) -> &SessionConfig {
+        &self.config
+    }
+
     /// Create a new session and return its ID and receiver.
+    ///
+    /// At the maximum session count, the least recently active session is
+    /// evicted first.
     #[must_use]
     pub fn create_session(&self) -> (String, broadcast::Receiver<String>) {
+        if self.config.at_capacity(self.sessions.len()) {
+            let oldest = self
+                .sessions
+                .iter()
+                .min_by_key(|entry| entry.last_active)
+                .map(|entry| entry.key().clone());
+            if let Some(oldest) = oldest {
+                self.evict(&oldest, EvictionReason::Capacity);
+            }
+        }
+
         let id = uuid::Uuid::new_v4().to_string();
         let (tx, rx) = broadcast::channel(self.capacity);
-        self.sessions.insert(id.clone(), tx);
+        self.sessions.insert(id.clone(), SseChannel::new(tx));
 
         // Create an event store for this session
         let event_store = Arc::new(EventStore::new(self.event_store_config.clone()));
@@ -387,10 +447,20 @@ impl SessionManager {
         (id, rx)
     }
 
-    /// Get a receiver for an existing session.
+    /// Get a receiver for an existing session, marking it active.
     #[must_use]
     pub fn get_receiver(&self, id: &str) -> Option<broadcast::Receiver<String>> {
-        self.sessions.get(id).map(|tx| tx.subscribe())
+        self.sessions.get_mut(id).map(|mut channel| {
+            channel.last_active = Instant::now();
+            channel.tx.subscribe()
+        })
+    }
+
+    /// Mark a session as active.
+    pub fn touch(&self, id: &str) {
+        if let Some(mut channel) = self.sessions.get_mut(id) {
+            channel.last_active = Instant::now();
+        }
     }
 
     /// Get the event store for a session.
@@ -404,8 +474,9 @@ impl SessionManager {
     /// Returns `true` if the message was sent, `false` if the session doesn't exist.
     #[must_use]
     pub fn send_to_session(&self, id: &str, message: String) -> bool {
-        if let Some(tx) = self.sessions.get(id) {
-            let _ = tx.send(message);
+        if let Some(mut channel) = self.sessions.get_mut(id) {
+            channel.last_active = Instant::now();
+            let _ = channel.tx.send(message);
             true
         } else {
             false
@@ -422,7 +493,8 @@ impl SessionManager {
         event_type: impl Into<String>,
         message: String,
     ) -> Option<String> {
-        if let Some(tx) = self.sessions.get(session_id) {
+        if let Some(mut channel) = self.sessions.get_mut(session_id) {
+            channel.last_active = Instant::now();
             let event_id = if let Some(store) = self.event_stores.get(session_id) {
                 store.store_auto_id(event_type, message.clone())
             } else {
@@ -432,7 +504,7 @@ impl SessionManager {
                 event_id
             };
 
-            let _ = tx.send(message);
+            let _ = channel.tx.send(message);
             Some(event_id)
         } else {
             None
@@ -441,21 +513,25 @@ impl SessionManager {
 
     /// Broadcast a message to all sessions.
     pub fn broadcast(&self, message: String) {
-        for entry in &self.sessions {
-            let _ = entry.value().send(message.clone());
+        let now = Instant::now();
+        for mut entry in self.sessions.iter_mut() {
+            entry.last_active = now;
+            let _ = entry.tx.send(message.clone());
         }
     }
 
     /// Broadcast a message to all sessions with storage.
     pub fn broadcast_with_storage(&self, event_type: impl Into<String> + Clone, message: String) {
-        for entry in &self.sessions {
+        let now = Instant::now();
+        for mut entry in self.sessions.iter_mut() {
+            entry.last_active = now;
             let session_id = entry.key();
 
             if let Some(store) = self.event_stores.get(session_id) {
                 store.store_auto_id(event_type.clone(), message.clone());
             }
 
-            let _ = entry.value().send(message.clone());
+            let _ = entry.tx.send(message.clone());
         }
     }
 
@@ -471,6 +547,47 @@ impl SessionManager {
         self.sessions.len()
     }
 
+    /// Evict sessions that are idle or past their maximum age, returning how
+    /// many were evicted.
+    #[must_use]
+    pub fn reap_expired(&self) -> usize {
+        let expired: Vec<(String, EvictionReason)> = self
+            .sessions
+            .iter()
+            .filter_map(|entry| {
+                self.config
+                    .eviction_reason(entry.created_at, entry.last_active)
+                    .map(|reason| (entry.key().clone(), reason))
+            })
+            .collect();
+        for (id, reason) in &expired {
+            self.evict(id, *reason);
+        }
+        expired.len()
+    }
+
+    fn evict(&self, id: &str, reason: EvictionReason) {
+        if self.sessions.remove(id).is_some() {
+            self.event_stores.remove(id);
+            self.config.notify_evicted(id, reason);
+        }
+    }
+
+    /// Start the background task that calls [`Self::reap_expired`] every
+    /// [`reap_interval`](SessionConfig::reap_interval).
+    ///
+    /// Only the first call starts a task; the task stops once the manager is
+    /// dropped. Must be called within a Tokio runtime, such as an actix-web
+    /// worker. The SSE handler calls this when it opens a stream.
+    pub fn start_reaper(self: &Arc<Self>) {
+        if self.reaper_started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let manager = Arc::downgrade(self);
+        let interval = self.config.reap_interval;
+        tokio::spawn(reap_periodically(manager, interval));
+    }
+
     /// Clean up expired events across all sessions.
     pub async fn cleanup_expired_events(&self) {
         for entry in &self.event_stores {
@@ -492,13 +609,26 @@ impl SessionManager {
     }
 }
 
+async fn reap_periodically(manager: Weak<SessionManager>, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        let Some(manager) = manager.upgrade() else {
+            break;
+        };
+        let reaped = manager.reap_expired();
+        if reaped > 0 {
+            tracing::debug!(reaped, "Reaped expired SSE sessions");
+        }
+    }
+}
+
 /// Thread-safe session store with automatic cleanup.
 ///
 /// Stores session metadata for HTTP request handling.
 #[derive(Debug)]
 pub struct SessionStore {
     sessions: DashMap<String, Session>,
-    timeout: Duration,
+    pub(crate) timeout: Duration,
     init_timeout: Duration,
     /// Default task retention (ms) applied to each session's task store; `None`
     /// means unlimited. Configure via `McpRouter::with_task_ttl`.
@@ -650,6 +780,38 @@ impl SessionStore {
     }
 }
 
+impl SessionStorage for SessionStore {
+    fn open_session(&self, user: Option<VerifiedUser>) -> String {
+        self.create_for_user(user)
+    }
+
+    fn resume_session(
+        &self,
+        id: &str,
+        user: Option<&VerifiedUser>,
+    ) -> Result<bool, SessionBindingError> {
+        self.touch_verified(id, user)
+    }
+
+    fn record_negotiated(
+        &self,
+        id: &str,
+        protocol_version: ProtocolVersion,
+        capabilities: Option<ClientCapabilities>,
+    ) {
+        // Initialized sessions are no longer subject to the init timeout.
+        self.update(id, |s| s.mark_initialized(protocol_version, capabilities));
+    }
+
+    fn session_snapshot(&self, id: &str) -> Option<SessionSnapshot> {
+        self.get(id).map(|s| SessionSnapshot {
+            protocol_version: s.protocol_version,
+            client_capabilities: s.client_capabilities,
+            tasks: Some(s.tasks),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -866,4 +1028,32 @@ mod tests {
         assert_eq!(event.event_type, "message");
         assert_eq!(event.data, "test data");
     }
+
+    #[tokio::test]
+    async fn reaper_evicts_idle_sessions_and_ends_their_streams() {
+        let evicted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&evicted);
+        let config = SessionConfig::new()
+            .with_idle_timeout(Duration::from_millis(50))
+            .with_reap_interval(Duration::from_millis(10))
+            .on_evict(move |id, reason| recorded.lock().unwrap().push((id.to_string(), reason)));
+        let manager = Arc::new(SessionManager::new().with_session_config(config));
+
+        let (idle, mut idle_rx) = manager.create_session();
+        let (active, _active_rx) = manager.create_session();
+        manager.start_reaper();
+
+        for _ in 0..10 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            manager.touch(&active);
+        }
+
+        assert!(matches!(
+            idle_rx.recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+        assert!(manager.get_receiver(&idle).is_none());
+        assert!(manager.get_receiver(&active).is_some());
+        assert_eq!(*evicted.lock().unwrap(), vec![(idle, EvictionReason::Idle)]);
+    }
 }
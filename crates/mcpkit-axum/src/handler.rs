@@ -11,30 +11,33 @@
 //! unexpected identity is rejected. Requests without a `VerifiedUser` extension
 //! are treated as anonymous. Token validation stays your application's
 //! responsibility; `VerifiedUser::from_claims` builds one from validated JWT
-//! claims.
+//! claims. Handlers and tool policies see the user (and its roles) through
+//! `ctx.extension::<VerifiedUser>()`.
 
+use crate::SUPPORTED_VERSIONS;
 use crate::error::ExtensionError;
 use crate::session::{EventStore, StoredEvent};
 use crate::state::{HasServerInfo, McpState, OAuthState};
-use crate::{SUPPORTED_VERSIONS, is_supported_version};
-use axum::extract::State;
-use axum::http::{HeaderMap, StatusCode};
-use axum::response::IntoResponse;
+use axum::body::Bytes;
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING, VARY};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, Uri};
 use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
 use futures::stream::Stream;
 use mcpkit_core::auth::VerifiedUser;
-use mcpkit_core::capability::ClientCapabilities;
 use mcpkit_core::protocol::Message;
-use mcpkit_core::protocol_version::ProtocolVersion;
-use mcpkit_server::capability::tasks::{TaskManager, route_task_store};
-use mcpkit_server::context::{Context, NoOpPeer};
+use mcpkit_core::types::task::TaskId;
+use mcpkit_server::health::{HealthProbes, ProbeResponse, session_health};
+use mcpkit_server::service::{ServiceOutcome, ServiceRejection, ServiceRequest};
 use mcpkit_server::{
-    AugmentedTaskOutcome, PromptHandler, ResourceHandler, ServerHandler, ToolHandler,
-    begin_augmented_task, route_completion, route_logging, route_prompts, route_resources,
-    route_tools,
+    Extensions, HttpRequestInfo, PromptHandler, ResourceHandler, ServerHandler, ToolHandler,
 };
+use mcpkit_transport::TransportError;
+use mcpkit_transport::compression::{self, Encoding};
 use std::convert::Infallible;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
@@ -49,330 +52,242 @@ use tracing::{debug, info, warn};
 ///   compatibility.
 /// - `mcp-session-id`: Optional. Used to track sessions.
 /// - `Content-Type`: Should be `application/json`.
+/// - `Accept`: Picks JSON or an SSE stream for the response; see
+///   [`ResponseMode::negotiate`](mcpkit_server::service::ResponseMode::negotiate).
 ///
 /// # Response
 ///
-/// Returns a JSON-RPC response for request messages, or 202 Accepted for notifications.
+/// Returns a JSON-RPC response for request messages (as JSON or as an SSE
+/// stream), or 202 Accepted for notifications.
 pub async fn handle_mcp_post<H>(
     State(state): State<McpState<H>>,
+    method: Method,
+    uri: Uri,
     headers: HeaderMap,
-    user: Option<Extension<VerifiedUser>>,
-    body: String,
+    request_extensions: axum::http::Extensions,
+    body: Bytes,
 ) -> impl IntoResponse
 where
     H: ServerHandler + ToolHandler + ResourceHandler + PromptHandler + Send + Sync + 'static,
 {
-    // The verified user (if any) is supplied by the application's auth middleware
-    // via a request extension; mcpkit binds the session to it.
-    let user = user.map(|Extension(u)| u);
-    // Reject disallowed Origins (DNS-rebinding protection) before any work.
-    let origin = headers.get("origin").and_then(|v| v.to_str().ok());
-    if !state.origin_validator.is_allowed(origin) {
-        warn!(
-            origin = origin.unwrap_or("none"),
-            "Rejected: origin not allowed"
-        );
-        return (StatusCode::FORBIDDEN, "origin not allowed").into_response();
-    }
-
-    // Validate protocol version
-    let version = headers
-        .get("mcp-protocol-version")
-        .and_then(|v| v.to_str().ok());
-
-    if !is_supported_version(version) {
-        let provided = version.unwrap_or("none");
-        warn!(version = provided, "Unsupported protocol version");
-        return ExtensionError::UnsupportedVersion(format!(
-            "{} (supported: {})",
-            provided,
-            SUPPORTED_VERSIONS.join(", ")
-        ))
-        .into_response();
-    }
-
-    // Get or create session
-    let session_id = headers
-        .get("mcp-session-id")
-        .and_then(|v| v.to_str().ok())
-        .map(String::from);
-
-    let session_id = match session_id {
-        Some(id) => match state.sessions.touch_verified(&id, user.as_ref()) {
-            Ok(true) => id,
-            // Reject an unknown session id rather than silently proceeding.
-            Ok(false) => {
-                warn!(session_id = %id, "Rejected: unknown session id");
-                return ExtensionError::SessionNotFound(id).into_response();
-            }
-            Err(e) => {
-                warn!(session_id = %id, error = %e, "Rejected: session binding violation");
-                return (StatusCode::FORBIDDEN, e.to_string()).into_response();
-            }
-        },
-        None => state.sessions.create_for_user(user),
+    let body = match decode_body(&state, &headers, body) {
+        Ok(body) => body,
+        Err(response) => return response,
     };
 
-    debug!(session_id = %session_id, "Processing MCP request");
-
-    // Parse message
-    let msg: Message = match serde_json::from_str(&body) {
-        Ok(m) => m,
-        Err(e) => {
-            warn!(error = %e, "Failed to parse JSON-RPC message");
-            return ExtensionError::InvalidMessage(e.to_string()).into_response();
-        }
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
     };
 
-    // Process message
-    match msg {
-        Message::Request(request) => {
-            info!(
-                method = %request.method,
-                id = ?request.id,
-                session_id = %session_id,
-                "Handling MCP request"
-            );
-
-            // On initialize, negotiate the protocol version and record it (and
-            // the client's capabilities) on the session, so subsequent requests
-            // observe the negotiated values and the session is no longer subject
-            // to the initialization timeout.
-            if request.method.as_ref() == "initialize" {
-                let (version, caps) = negotiate_initialize(request.params.as_ref());
-                state
-                    .sessions
-                    .update(&session_id, |s| s.mark_initialized(version, caps.clone()));
-            }
+    let mut extensions = Extensions::new();
+    if let Some(names) = &state.request_info_headers {
+        let peer_addr = request_extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| *addr);
+        extensions.insert(request_info(&method, &uri, &headers, names, peer_addr));
+    }
+    let user = request_extensions.get::<VerifiedUser>().cloned();
+    if !state.request_extractors.is_empty() {
+        let (mut parts, ()) = axum::http::Request::new(()).into_parts();
+        parts.method = method;
+        parts.uri = uri;
+        parts.headers = headers.clone();
+        parts.extensions = request_extensions;
+        for extractor in &state.request_extractors {
+            extractor(&parts, &mut extensions);
+        }
+    }
 
-            // Resolve the session's negotiated values for the request context,
-            // falling back to defaults before initialization completes.
-            let session = state.sessions.get(&session_id);
-            let protocol_version = session
-                .as_ref()
-                .and_then(|s| s.protocol_version)
-                .unwrap_or(ProtocolVersion::LATEST);
-            // This session's task store (per-session isolation for `tasks/*`).
-            let task_store = session.as_ref().map(|s| s.tasks.clone());
-            let client_caps = session
-                .and_then(|s| s.client_capabilities)
-                .unwrap_or_default();
-
-            // Create a basic response using the handler's capabilities
-            // In a full implementation, this would route to the handler's methods
-            let response = create_response_for_request(
-                &state,
-                &request,
-                protocol_version,
-                &client_caps,
-                task_store.as_ref(),
-            )
-            .await;
-
-            match serde_json::to_string(&Message::Response(response)) {
-                Ok(body) => (
-                    StatusCode::OK,
-                    [
-                        ("content-type", "application/json"),
-                        ("mcp-session-id", session_id.as_str()),
-                    ],
-                    body,
-                )
-                    .into_response(),
+    // The verified user (if any) is supplied by the application's auth middleware
+    // via a request extension; mcpkit binds the session to it.
+    let request = ServiceRequest::from_bytes(Vec::from(body))
+        .with_origin(header("origin"))
+        .with_protocol_version(header("mcp-protocol-version"))
+        .with_session_id(header("mcp-session-id"))
+        .with_accept(header("accept"))
+        .with_user(user)
+        .with_extensions(extensions);
+
+    let reply = state.service().handle(request).await;
+    let sse = reply.sse_body();
+    match reply.outcome {
+        ServiceOutcome::Response {
+            session_id,
+            response,
+        } => {
+            let body = match sse {
+                Some(body) => Ok((body, "text/event-stream")),
+                None => serde_json::to_string(&Message::Response(response))
+                    .map(|body| (body, "application/json")),
+            };
+            match body {
+                Ok((body, content_type)) => {
+                    let (encoding, body) = encode_body(&state, &headers, body);
+                    (
+                        StatusCode::OK,
+                        [
+                            ("content-type", content_type),
+                            ("mcp-session-id", session_id.as_str()),
+                        ],
+                        encoding,
+                        body,
+                    )
+                        .into_response()
+                }
                 Err(e) => ExtensionError::Serialization(e).into_response(),
             }
         }
-        Message::Notification(notification) => {
-            debug!(
-                method = %notification.method,
-                session_id = %session_id,
-                "Received notification"
-            );
-            (
-                StatusCode::ACCEPTED,
-                [("mcp-session-id", session_id.as_str())],
-            )
-                .into_response()
-        }
-        _ => {
-            warn!("Unexpected message type received");
-            ExtensionError::InvalidMessage("Expected request or notification".to_string())
-                .into_response()
-        }
+        ServiceOutcome::Accepted { session_id } => (
+            StatusCode::ACCEPTED,
+            [("mcp-session-id", session_id.as_str())],
+        )
+            .into_response(),
+        ServiceOutcome::Rejected(rejection) => rejection_response(rejection),
+        _ => ExtensionError::Internal("unexpected service outcome".to_string()).into_response(),
     }
 }
 
-/// Negotiate the protocol version and extract client capabilities from an
-/// `initialize` request's params.
+/// Undo the request body's `Content-Encoding`.
 ///
-/// The negotiated version is the highest supported version not exceeding the
-/// client's requested version, falling back to the latest supported version
-/// when the request omits or names an unknown version.
-fn negotiate_initialize(
-    params: Option<&serde_json::Value>,
-) -> (ProtocolVersion, Option<ClientCapabilities>) {
-    let requested = params
-        .and_then(|p| p.get("protocolVersion"))
-        .and_then(serde_json::Value::as_str)
-        .unwrap_or("");
-    let version = ProtocolVersion::negotiate(requested, ProtocolVersion::ALL)
-        .unwrap_or(ProtocolVersion::LATEST);
-    let capabilities = params
-        .and_then(|p| p.get("capabilities"))
-        .and_then(|c| serde_json::from_value::<ClientCapabilities>(c.clone()).ok());
-    (version, capabilities)
-}
-
-/// Create a response for a request.
-///
-/// Routes all MCP methods through the appropriate handler traits.
-async fn create_response_for_request<H>(
+/// Compressed bodies are refused with `415 Unsupported Media Type` unless
+/// compression is enabled, and may not inflate past the message size limit.
+fn decode_body<H>(
     state: &McpState<H>,
-    request: &mcpkit_core::protocol::Request,
-    protocol_version: ProtocolVersion,
-    client_caps: &ClientCapabilities,
-    task_store: Option<&Arc<TaskManager>>,
-) -> mcpkit_core::protocol::Response
-where
-    H: ServerHandler + ToolHandler + ResourceHandler + PromptHandler + Send + Sync + 'static,
-{
-    use mcpkit_core::error::JsonRpcError;
-    use mcpkit_core::protocol::Response;
-
-    let method = request.method.as_ref();
-    let params = request.params.as_ref();
-
-    // Create a context for the request
-    let req_id = request.id.clone();
-    let server_caps = state.effective_capabilities();
-    let peer = NoOpPeer;
-    let ctx = Context::new(
-        &req_id,
-        None,
-        client_caps,
-        &server_caps,
-        protocol_version,
-        &peer,
-    );
-
-    match method {
-        "ping" => Response::success(request.id.clone(), serde_json::json!({})),
-        "initialize" => {
-            let init_result = serde_json::json!({
-                "protocolVersion": protocol_version.as_str(),
-                "serverInfo": state.server_info,
-                "capabilities": server_caps,
-            });
-            Response::success(request.id.clone(), init_result)
+    headers: &HeaderMap,
+    body: Bytes,
+) -> Result<Bytes, Response> {
+    let Some(value) = headers.get(CONTENT_ENCODING) else {
+        return Ok(body);
+    };
+    let encoding = match Encoding::from_header(value.to_str().unwrap_or_default()) {
+        Ok(None) => return Ok(body),
+        Ok(Some(encoding)) if state.compression.is_some() => encoding,
+        Ok(Some(_)) | Err(_) => {
+            let accepted = if state.compression.is_some() {
+                compression::ACCEPT_ENCODING
+            } else {
+                "identity"
+            };
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                [(ACCEPT_ENCODING, accepted)],
+                "unsupported content-encoding",
+            )
+                .into_response());
         }
-        _ => {
-            // Task-augmented tools/call and tasks/* are served from this
-            // session's own task store (per-session isolation).
-            if let Some(store) = task_store {
-                if method == "tools/call" {
-                    match begin_augmented_task(
-                        state.handler.clone(),
-                        store,
-                        params,
-                        client_caps.clone(),
-                        server_caps.clone(),
-                        protocol_version,
-                    )
-                    .await
-                    {
-                        AugmentedTaskOutcome::Started(create_result, fut) => {
-                            tokio::spawn(fut);
-                            return Response::success(request.id.clone(), create_result);
-                        }
-                        AugmentedTaskOutcome::Rejected(e) => {
-                            return Response::error(request.id.clone(), e.into());
-                        }
-                        // Not task-augmented — fall through to the normal path.
-                        AugmentedTaskOutcome::NotApplicable => {}
-                    }
-                } else if let Some(result) = route_task_store(store, method, params).await {
-                    return match result {
-                        Ok(value) => Response::success(request.id.clone(), value),
-                        Err(e) => Response::error(request.id.clone(), e.into()),
-                    };
-                }
-            }
+    };
 
-            // Try routing to tools
-            if let Some(result) = route_tools(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
+    let max = state.limits.limits().max_message_size;
+    match compression::decompress(encoding, &body, max) {
+        Ok(decoded) => {
+            state
+                .compression_stats
+                .record_decompressed(body.len(), decoded.len());
+            #[cfg(feature = "prometheus")]
+            if let Some(metrics) = &state.metrics {
+                metrics.record_compression(decoded.len(), body.len());
             }
+            Ok(decoded.into())
+        }
+        Err(TransportError::MessageTooLarge { .. }) => {
+            Err((StatusCode::PAYLOAD_TOO_LARGE, "message too large").into_response())
+        }
+        Err(e) => Err((StatusCode::BAD_REQUEST, e.to_string()).into_response()),
+    }
+}
 
-            // Try routing to resources
-            if let Some(result) = route_resources(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
+/// Compress a response body when compression is enabled, the client
+/// accepts it, and the body is large enough.
+///
+/// Returns the `Content-Encoding` and `Vary` headers to send with it.
+fn encode_body<H>(state: &McpState<H>, headers: &HeaderMap, body: String) -> (HeaderMap, Vec<u8>) {
+    let mut response_headers = HeaderMap::new();
+    let Some(config) = state.compression else {
+        return (response_headers, body.into_bytes());
+    };
+    response_headers.insert(VARY, HeaderValue::from_static("accept-encoding"));
 
-            // Try routing to prompts
-            if let Some(result) = route_prompts(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
+    let Some(encoding) = headers
+        .get(ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|accept| Encoding::negotiate(accept, config.encoding))
+        .filter(|_| config.should_compress(body.len()))
+    else {
+        return (response_headers, body.into_bytes());
+    };
+    match compression::compress(encoding, body.as_bytes(), config.level) {
+        Ok(encoded) => {
+            state
+                .compression_stats
+                .record_compressed(body.len(), encoded.len());
+            #[cfg(feature = "prometheus")]
+            if let Some(metrics) = &state.metrics {
+                metrics.record_compression(body.len(), encoded.len());
             }
+            response_headers.insert(
+                CONTENT_ENCODING,
+                HeaderValue::from_static(encoding.as_str()),
+            );
+            (response_headers, encoded)
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to compress response");
+            (response_headers, body.into_bytes())
+        }
+    }
+}
 
-            // Try routing logging/setLevel (gated on the advertised capability)
-            if let Some(result) =
-                route_logging(state.handler.as_ref(), &server_caps, method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
+/// Render a request the service refused.
+fn rejection_response(rejection: ServiceRejection) -> Response {
+    match rejection {
+        ServiceRejection::UnsupportedVersion(provided) => {
+            ExtensionError::UnsupportedVersion(format!(
+                "{} (supported: {})",
+                provided,
+                SUPPORTED_VERSIONS.join(", ")
+            ))
+            .into_response()
+        }
+        ServiceRejection::SessionNotFound(id) => {
+            ExtensionError::SessionNotFound(id).into_response()
+        }
+        ServiceRejection::InvalidMessage(message) => {
+            ExtensionError::InvalidMessage(message).into_response()
+        }
+        ServiceRejection::UnexpectedMessage => {
+            ExtensionError::InvalidMessage("Expected request or notification".to_string())
+                .into_response()
+        }
+        other => {
+            let status = StatusCode::from_u16(other.status_code())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (status, other.to_string()).into_response()
+        }
+    }
+}
 
-            // Try routing completion/complete (when a completion handler is set)
-            if let Some(result) =
-                route_completion(state.completion.as_deref(), method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
+/// Capture the request metadata exposed to handlers, copying only the
+/// allow-listed headers.
+fn request_info(
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+    names: &[String],
+    peer_addr: Option<SocketAddr>,
+) -> HttpRequestInfo {
+    let mut info = HttpRequestInfo::new(method.as_str(), uri.path());
+    for name in names {
+        for value in headers.get_all(name.as_str()) {
+            if let Ok(value) = value.to_str() {
+                info = info.with_header(name.as_str(), value);
             }
-
-            // Method not found
-            Response::error(
-                request.id.clone(),
-                JsonRpcError::method_not_found(format!("Method '{method}' not found")),
-            )
         }
     }
+    info.peer_addr = peer_addr;
+    info
 }
 
 /// Handle SSE connections for server-to-client streaming.
@@ -467,11 +382,30 @@ where
         (id, rx, Vec::new())
     };
 
+    state.sse_sessions.start_reaper();
+    let heartbeat = state.sse_sessions.session_config().heartbeat_interval;
     let event_store = state.sse_sessions.get_event_store(&id);
-    let stream = create_sse_stream_with_replay(id, rx, replay_events, event_store);
-    Sse::new(stream)
-        .keep_alive(KeepAlive::default())
-        .into_response()
+    // End the stream on shutdown so graceful shutdown is not held open by
+    // idle SSE connections.
+    let stream = futures::StreamExt::take_until(
+        create_sse_stream_with_replay(id, rx, replay_events, event_store),
+        state.shutdown.wait(),
+    );
+    #[cfg(feature = "prometheus")]
+    let stream = counted_sse_stream(
+        stream,
+        state
+            .metrics
+            .as_ref()
+            .map(mcpkit_transport::McpMetrics::sse_connection),
+    );
+    let sse = Sse::new(stream);
+    match heartbeat {
+        Some(interval) => sse
+            .keep_alive(KeepAlive::new().interval(interval))
+            .into_response(),
+        None => sse.into_response(),
+    }
 }
 
 /// Create an SSE stream with support for event replay.
@@ -484,7 +418,7 @@ where
 /// All events include an `id` field for client-side tracking and reconnection.
 fn create_sse_stream_with_replay(
     session_id: String,
-    mut rx: tokio::sync::broadcast::Receiver<String>,
+    mut rx: mcpkit_server::fanout::Inbox,
     replay_events: Vec<StoredEvent>,
     event_store: Option<Arc<EventStore>>,
 ) -> impl Stream<Item = Result<Event, Infallible>> {
@@ -510,34 +444,176 @@ fn create_sse_stream_with_replay(
             .event("connected")
             .data(&session_id));
 
-        // Stream new messages with event IDs
-        loop {
-            match rx.recv().await {
-                Ok(msg) => {
-                    // Generate event ID for the new message
-                    let event_id = event_store
-                        .as_ref()
-                        .map_or_else(|| format!("evt-{}", uuid::Uuid::new_v4()), |store| store.next_event_id());
-
-                    yield Ok(Event::default()
-                        .id(&event_id)
-                        .event("message")
-                        .data(msg));
-                }
-                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                    warn!(skipped = n, "SSE client lagged, skipped messages");
-                    // Note: Lagged events may be available in the event store
-                    // for replay on reconnection
-                }
-                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-                    debug!("SSE channel closed");
-                    break;
-                }
+        // Stream new messages with event IDs, writing each batch that has
+        // queued up since the last wake-up together
+        while let Some(batch) = rx.recv_batch().await {
+            if batch.dropped > 0 {
+                warn!(skipped = batch.dropped, "SSE client lagged, skipped messages");
+                // Note: Lagged events may be available in the event store
+                // for replay on reconnection
+            }
+            for msg in batch.events {
+                // Generate event ID for the new message
+                let event_id = event_store
+                    .as_ref()
+                    .map_or_else(|| format!("evt-{}", uuid::Uuid::new_v4()), |store| store.next_event_id());
+
+                yield Ok(Event::default()
+                    .id(&event_id)
+                    .event("message")
+                    .data(String::from_utf8_lossy(&msg)));
             }
         }
+        debug!("SSE channel closed");
     }
 }
 
+/// Handle SSE connections following a single task.
+///
+/// Mounted at `{post_path}/tasks/{task_id}/events`. Streams the task's
+/// `status` and `progress` events (see
+/// [`TaskEvent`](mcpkit_server::capability::tasks::TaskEvent)) and ends after the
+/// event that makes the task terminal.
+///
+/// # Headers
+///
+/// - `mcp-session-id`: Required. Tasks are scoped to the session that
+///   created them.
+/// - `last-event-id`: Optional. Resumes after the given event; without it,
+///   every retained event of the task is sent first.
+///
+/// Answers `404 Not Found` for an unknown session or task.
+pub async fn handle_task_events<H>(
+    State(state): State<McpState<H>>,
+    Path(task_id): Path<String>,
+    headers: HeaderMap,
+    user: Option<Extension<VerifiedUser>>,
+) -> Response
+where
+    H: Send + Sync + 'static,
+{
+    let origin = headers.get("origin").and_then(|v| v.to_str().ok());
+    if !state.origin_validator.is_allowed(origin) {
+        warn!(
+            origin = origin.unwrap_or("none"),
+            "Rejected task SSE: origin not allowed"
+        );
+        return (StatusCode::FORBIDDEN, "origin not allowed").into_response();
+    }
+
+    let user = user.map(|Extension(u)| u);
+    let Some(session_id) = headers.get("mcp-session-id").and_then(|v| v.to_str().ok()) else {
+        return (StatusCode::BAD_REQUEST, "missing mcp-session-id").into_response();
+    };
+    let session = match state.sessions.get_verified(session_id, user.as_ref()) {
+        Ok(Some(session)) => session,
+        Ok(None) => return (StatusCode::NOT_FOUND, "unknown session").into_response(),
+        Err(e) => {
+            warn!(session_id, error = %e, "Rejected task SSE: session binding violation");
+            return (StatusCode::FORBIDDEN, e.to_string()).into_response();
+        }
+    };
+
+    let task_id = TaskId::new(task_id);
+    if session.tasks.get(&task_id).is_none() {
+        return (StatusCode::NOT_FOUND, "unknown task").into_response();
+    }
+    let after = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+
+    debug!(
+        session_id,
+        task_id = task_id.as_str(),
+        ?after,
+        "Following task events"
+    );
+    let events = futures::StreamExt::map(session.tasks.follow(task_id, after), |event| {
+        Ok::<_, Infallible>(
+            Event::default()
+                .id(event.id.to_string())
+                .event(event.event_type())
+                .data(event.data().to_string()),
+        )
+    });
+    let stream = futures::StreamExt::take_until(events, state.shutdown.wait());
+    let sse = Sse::new(stream);
+    match state.sse_sessions.session_config().heartbeat_interval {
+        Some(interval) => sse
+            .keep_alive(KeepAlive::new().interval(interval))
+            .into_response(),
+        None => sse.into_response(),
+    }
+}
+
+/// Keep `connection` (counting the stream as an open SSE connection) alive
+/// until `stream` is dropped.
+#[cfg(feature = "prometheus")]
+fn counted_sse_stream<S: Stream>(
+    stream: S,
+    connection: Option<mcpkit_transport::GaugeGuard>,
+) -> impl Stream<Item = S::Item> {
+    futures::StreamExt::map(stream, move |event| {
+        let _connection = &connection;
+        event
+    })
+}
+
+/// Serve the router's Prometheus metrics in the text exposition format.
+///
+/// Mounted at `/metrics` by [`McpRouter::with_metrics`](crate::McpRouter::with_metrics).
+/// Answers `404 Not Found` if the state has no metrics.
+#[cfg(feature = "prometheus")]
+pub async fn handle_metrics<H>(State(state): State<McpState<H>>) -> Response {
+    let Some(metrics) = &state.metrics else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    metrics.set_active_sessions(state.sessions.session_count());
+    let exporter = mcpkit_transport::MetricsExporter::from_metrics(metrics);
+    match exporter.gather() {
+        Ok(body) => (
+            StatusCode::OK,
+            [("content-type", exporter.content_type().to_string())],
+            body,
+        )
+            .into_response(),
+        Err(e) => {
+            warn!(error = %e, "Failed to encode metrics");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Answer the liveness probe.
+///
+/// Mounted at `/healthz` by [`McpRouter::with_health_checks`](crate::McpRouter::with_health_checks).
+/// Answers `404 Not Found` if the state has no health probes.
+pub async fn handle_liveness<H>(State(state): State<McpState<H>>, uri: Uri) -> Response {
+    let Some(probes) = &state.health else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    probe_response(probes.liveness(HealthProbes::verbose_requested(uri.query())))
+}
+
+/// Answer the readiness probe: the configured checks plus session health,
+/// which fails once shutdown begins.
+///
+/// Mounted at `/readyz` by [`McpRouter::with_health_checks`](crate::McpRouter::with_health_checks).
+/// Answers `404 Not Found` if the state has no health probes.
+pub async fn handle_readiness<H>(State(state): State<McpState<H>>, uri: Uri) -> Response {
+    let Some(probes) = &state.health else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let sessions = session_health(state.sessions.session_count(), state.shutdown.is_shutdown());
+    probe_response(probes.readiness(sessions, HealthProbes::verbose_requested(uri.query())))
+}
+
+fn probe_response(probe: ProbeResponse) -> Response {
+    let status = StatusCode::from_u16(probe.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, [("content-type", probe.content_type)], probe.body).into_response()
+}
+
 /// Handle `.well-known/oauth-protected-resource` requests.
 ///
 /// Per RFC 9728, MCP servers MUST implement this endpoint to indicate
@@ -574,34 +650,3 @@ pub async fn handle_oauth_protected_resource(State(state): State<OAuthState>) ->
         Json(state.metadata),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::negotiate_initialize;
-    use mcpkit_core::protocol_version::ProtocolVersion;
-
-    #[test]
-    fn negotiate_uses_requested_supported_version() {
-        let params = serde_json::json!({
-            "protocolVersion": "2025-06-18",
-            "capabilities": {}
-        });
-        let (version, caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::V2025_06_18);
-        assert!(caps.is_some());
-    }
-
-    #[test]
-    fn negotiate_defaults_to_latest_when_absent() {
-        let (version, caps) = negotiate_initialize(None);
-        assert_eq!(version, ProtocolVersion::LATEST);
-        assert!(caps.is_none());
-    }
-
-    #[test]
-    fn negotiate_unknown_version_falls_back_to_latest() {
-        let params = serde_json::json!({ "protocolVersion": "2099-01-01" });
-        let (version, _caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::LATEST);
-    }
-}
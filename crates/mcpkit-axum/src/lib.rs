@@ -10,6 +10,7 @@
 //! - Session management with automatic cleanup
 //! - Protocol version validation
 //! - CORS support
+//! - Prometheus `/metrics` endpoint (`prometheus` feature)
 //!
 //! # HTTP Protocol Requirements
 //!
@@ -100,13 +101,18 @@ mod session;
 mod state;
 
 pub use error::ExtensionError;
-pub use handler::{handle_mcp_post, handle_oauth_protected_resource, handle_sse};
+#[cfg(feature = "prometheus")]
+pub use handler::handle_metrics;
+pub use handler::{
+    handle_liveness, handle_mcp_post, handle_oauth_protected_resource, handle_readiness,
+    handle_sse, handle_task_events,
+};
 pub use router::McpRouter;
 pub use session::{
     DEFAULT_INIT_TIMEOUT, EventStore, EventStoreConfig, Session, SessionManager, SessionStore,
     StoredEvent,
 };
-pub use state::{McpState, OAuthState};
+pub use state::{McpState, OAuthState, RequestExtractor};
 
 /// Prelude module for convenient imports.
 ///
@@ -117,7 +123,9 @@ pub use state::{McpState, OAuthState};
 /// ```
 pub mod prelude {
     pub use crate::error::ExtensionError;
-    pub use crate::handler::{handle_mcp_post, handle_oauth_protected_resource, handle_sse};
+    pub use crate::handler::{
+        handle_mcp_post, handle_oauth_protected_resource, handle_sse, handle_task_events,
+    };
     pub use crate::router::McpRouter;
     pub use crate::session::{
         DEFAULT_INIT_TIMEOUT, EventStore, EventStoreConfig, Session, SessionManager, SessionStore,
@@ -1,14 +1,26 @@
 //! Router builder for MCP endpoints.
 
-use crate::handler::{handle_mcp_post, handle_oauth_protected_resource, handle_sse};
+use crate::handler::{
+    handle_liveness, handle_mcp_post, handle_oauth_protected_resource, handle_readiness,
+    handle_sse, handle_task_events,
+};
 use crate::state::{HasServerInfo, McpState, OAuthState};
 use axum::Router;
-use axum::routing::{get, post};
+use axum::extract::{DefaultBodyLimit, Request};
+use axum::response::IntoResponse;
+use axum::routing::{Route, get, post};
 use mcpkit_core::auth::ProtectedResourceMetadata;
-use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use mcpkit_server::health::{HealthChecker, HealthProbes, LIVENESS_PATH, READINESS_PATH};
+use mcpkit_server::service::ResponseMode;
+use mcpkit_server::shutdown::DEFAULT_SHUTDOWN_TIMEOUT;
+use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ShutdownHandle, ToolHandler};
+use mcpkit_transport::TransportLimits;
 use mcpkit_transport::http::OriginValidator;
+use std::convert::Infallible;
 use std::sync::Arc;
+use tower::{Layer, Service};
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::timeout::RequestBodyTimeoutLayer;
 use tower_http::trace::TraceLayer;
 
 /// Builder for MCP Axum routers.
@@ -34,6 +46,12 @@ use tower_http::trace::TraceLayer;
 /// let router = McpRouter::new(MyHandler)
 ///     .with_tracing()
 ///     .into_router();
+///
+/// // Mounted elsewhere, with middleware on the MCP routes only
+/// let router = McpRouter::new(MyHandler)
+///     .path("/custom/mcp")
+///     .layer(TimeoutLayer::new(Duration::from_secs(30)))
+///     .into_router();
 /// ```
 pub struct McpRouter<H> {
     state: McpState<H>,
@@ -42,8 +60,13 @@ pub struct McpRouter<H> {
     post_path: String,
     sse_path: String,
     oauth_metadata: Option<ProtectedResourceMetadata>,
+    limits: Option<TransportLimits>,
+    layers: Vec<RouteLayer>,
 }
 
+/// A tower layer added with [`McpRouter::layer`], applied to the MCP routes.
+type RouteLayer = Box<dyn FnOnce(Router) -> Router + Send>;
+
 impl<H> McpRouter<H>
 where
     H: ServerHandler
@@ -64,6 +87,8 @@ where
             post_path: "/mcp".to_string(),
             sse_path: "/mcp/sse".to_string(),
             oauth_metadata: None,
+            limits: None,
+            layers: Vec::new(),
         }
     }
 
@@ -97,6 +122,225 @@ where
         self
     }
 
+    /// Set session lifetimes, the session cap, and the SSE keep-alive
+    /// interval.
+    ///
+    /// A reaper task, started with the first SSE stream, ends streams idle
+    /// past `idle_timeout` or older than `max_age` and reports each eviction
+    /// to the config's `on_evict` callbacks. The idle timeout also applies to
+    /// HTTP sessions. See [`SessionConfig`](mcpkit_server::SessionConfig) for
+    /// the defaults.
+    #[must_use]
+    pub fn with_session_config(mut self, config: mcpkit_server::SessionConfig) -> Self {
+        self.state = self.state.with_session_config(config);
+        self
+    }
+
+    /// Make HTTP request metadata available to handlers through
+    /// [`Context::http_request`](mcpkit_server::Context::http_request).
+    ///
+    /// Captures the method, path, peer address, and only the named headers
+    /// (e.g. `["x-tenant-id", "user-agent"]`). Disabled by default.
+    #[must_use]
+    pub fn with_request_info<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.state = self.state.with_request_info(headers);
+        self
+    }
+
+    /// Attach values taken from each HTTP request to the handler's
+    /// [`Context`](mcpkit_server::Context).
+    ///
+    /// `extractor` sees the request's method, URI, headers, and axum
+    /// extensions (including `ConnectInfo` and anything your middleware
+    /// inserted), and inserts typed values that handlers read with
+    /// `ctx.extensions().get::<T>()`. Extractors run in registration order.
+    #[must_use]
+    pub fn with_request_extractor<F>(mut self, extractor: F) -> Self
+    where
+        F: Fn(&axum::http::request::Parts, &mut mcpkit_server::Extensions) + Send + Sync + 'static,
+    {
+        self.state = self.state.with_request_extractor(extractor);
+        self
+    }
+
+    /// Pass the axum request extension of type `T` through to handlers.
+    ///
+    /// Use this for values your middleware already extracted, such as
+    /// authentication claims; handlers read them with
+    /// `ctx.extensions().get::<T>()`.
+    #[must_use]
+    pub fn with_request_extension<T>(mut self) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.state = self.state.with_request_extension::<T>();
+        self
+    }
+
+    /// Rate limit requests per session, per method, and per tool.
+    ///
+    /// Buckets are keyed by the `mcp-session-id` header. A request over a
+    /// quota gets a JSON-RPC error whose `data.retry_after_ms` says when to
+    /// retry. Disabled by default.
+    #[must_use]
+    pub fn with_rate_limit(mut self, config: mcpkit_server::RateLimitConfig) -> Self {
+        self.state = self.state.with_rate_limit(config);
+        self
+    }
+
+    /// Check `tools/call` requests against a tool policy.
+    ///
+    /// The policy sees the `VerifiedUser` your auth middleware attached, and a
+    /// denied call gets a JSON-RPC error with code `-32011`. Disabled by
+    /// default.
+    #[must_use]
+    pub fn with_tool_authorizer(mut self, authorizer: mcpkit_server::ToolAuthorizer) -> Self {
+        self.state = self.state.with_tool_authorizer(authorizer);
+        self
+    }
+
+    /// Write an audit record for each tool call and resource read.
+    ///
+    /// Records carry the `mcp-session-id` and the verified user's subject.
+    /// See [`AuditLogger`](mcpkit_server::AuditLogger) for sinks, sampling,
+    /// and redaction. Disabled by default.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: mcpkit_server::AuditLogger) -> Self {
+        self.state = self.state.with_audit_logger(logger);
+        self
+    }
+
+    /// Trace tool, resource, and prompt invocations.
+    ///
+    /// Each call runs in an `mcp.tool`, `mcp.resource`, or `mcp.prompt` span
+    /// carrying the request and session ids, duration, and outcome. See
+    /// [`InvocationTracing`](mcpkit_server::InvocationTracing) for argument
+    /// capture. Arguments are not captured by default.
+    #[must_use]
+    pub fn with_invocation_tracing(mut self, tracing: mcpkit_server::InvocationTracing) -> Self {
+        self.state = self.state.with_invocation_tracing(tracing);
+        self
+    }
+
+    /// Record request counts, errors, and latency percentiles per method and
+    /// per tool into the layer's
+    /// [`ServerMetrics`](mcpkit_server::ServerMetrics).
+    ///
+    /// With [`MetricsLayer::expose_resource`](mcpkit_server::MetricsLayer::expose_resource),
+    /// clients can also read a JSON snapshot from the `metrics://server`
+    /// resource. Disabled by default.
+    #[must_use]
+    pub fn with_server_metrics(mut self, metrics: mcpkit_server::MetricsLayer) -> Self {
+        self.state = self.state.with_server_metrics(metrics);
+        self
+    }
+
+    /// Choose how POSTed requests are answered when the client's `Accept`
+    /// header allows both JSON and SSE.
+    ///
+    /// With [`ResponseMode::Sse`], the response is a `text/event-stream`
+    /// carrying the notifications the handler sent (progress, logs) and then
+    /// the JSON-RPC response. Clients that accept only `application/json`
+    /// still get plain JSON. Defaults to [`ResponseMode::Json`].
+    #[must_use]
+    pub const fn with_response_mode(mut self, mode: ResponseMode) -> Self {
+        self.state.response_mode = mode;
+        self
+    }
+
+    /// Compress responses for clients that send `Accept-Encoding: gzip` or
+    /// `deflate`, and accept request bodies with those `Content-Encoding`s.
+    ///
+    /// Bodies smaller than [`CompressionConfig::min_size`] are sent as-is,
+    /// and the standalone SSE stream is never compressed. Without this,
+    /// compressed requests are answered with `415 Unsupported Media Type`.
+    ///
+    /// [`CompressionConfig::min_size`]: mcpkit_transport::CompressionConfig::min_size
+    #[must_use]
+    pub const fn with_compression(mut self, config: mcpkit_transport::CompressionConfig) -> Self {
+        self.state.compression = Some(config);
+        self
+    }
+
+    /// Get the bytes saved by compression so far.
+    #[must_use]
+    pub fn compression_stats(&self) -> mcpkit_transport::CompressionSnapshot {
+        self.state.compression_stats.snapshot()
+    }
+
+    /// Apply [`TransportLimits`] to the MCP endpoints.
+    ///
+    /// Bodies over `max_message_size` are answered with `413 Payload Too
+    /// Large`, requests beyond `max_in_flight` with `503 Service
+    /// Unavailable`, and a body that takes longer than `read_timeout` to
+    /// arrive fails the request.
+    #[must_use]
+    pub fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.state = self.state.with_limits(limits.clone());
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Record Prometheus metrics and serve them at `GET /metrics`.
+    ///
+    /// Exports request counts and latencies per JSON-RPC method, error counts
+    /// by JSON-RPC error code, open sessions, open SSE streams, and tool calls
+    /// and failures per tool. Requires the `prometheus` feature.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_metrics(self) -> Self {
+        let metrics =
+            mcpkit_transport::McpMetrics::new().expect("MCP metric names are valid and distinct");
+        self.with_prometheus(metrics)
+    }
+
+    /// Like [`with_metrics`](Self::with_metrics), recording into an existing
+    /// [`McpMetrics`](mcpkit_transport::McpMetrics), e.g. one shared with a
+    /// transport's `TelemetryLayer`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_prometheus(mut self, metrics: mcpkit_transport::McpMetrics) -> Self {
+        self.state.metrics = Some(metrics);
+        self
+    }
+
+    /// Serve Kubernetes liveness and readiness probes at `/healthz` and
+    /// `/readyz`.
+    ///
+    /// `/healthz` answers `200` while the process responds. `/readyz`
+    /// answers `503` once shutdown begins. Add `?verbose` for a JSON report.
+    /// Use [`with_readiness_checks`](Self::with_readiness_checks) to make
+    /// readiness depend on your own components as well.
+    #[must_use]
+    pub fn with_health_checks(self) -> Self {
+        let checker = HealthChecker::new(&self.state.server_info.name);
+        self.with_readiness_checks(checker)
+    }
+
+    /// Like [`with_health_checks`](Self::with_health_checks), with `/readyz`
+    /// also running `checker`'s checks (e.g. that the database is reachable).
+    #[must_use]
+    pub fn with_readiness_checks(mut self, checker: HealthChecker) -> Self {
+        self.state.health = Some(HealthProbes::new(checker));
+        self
+    }
+
+    /// Get a [`ShutdownHandle`] that stops [`serve`](Self::serve) gracefully.
+    ///
+    /// Once triggered, new requests get `503 Service Unavailable`, open SSE
+    /// streams end, and `serve` returns after in-flight requests finish or
+    /// [`DEFAULT_SHUTDOWN_TIMEOUT`] elapses. When serving the output of
+    /// [`into_router`](Self::into_router) yourself, pass
+    /// [`ShutdownHandle::wait`] to `axum::serve(..).with_graceful_shutdown`.
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.state.shutdown.clone()
+    }
+
     /// Enable CORS with permissive defaults.
     ///
     /// For production, you should use `with_cors_layer` with a custom configuration.
@@ -145,6 +389,40 @@ where
         self
     }
 
+    /// Mount the MCP endpoints at `path` instead of `/mcp`.
+    ///
+    /// POST requests are served at `path`, SSE at `{path}/sse`, and task
+    /// events under `{path}/tasks`. Health, metrics, and OAuth discovery
+    /// keep their well-known paths.
+    #[must_use]
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.sse_path = format!("{}/sse", path.trim_end_matches('/'));
+        self.post_path = path;
+        self
+    }
+
+    /// Apply a tower middleware layer (authentication, timeouts, rate
+    /// limits, ...) to the MCP endpoints.
+    ///
+    /// Only the POST, SSE, and task event routes are wrapped; health checks,
+    /// metrics, and OAuth discovery stay reachable without it. Layers run in
+    /// the order they are added, the last one outermost, and inside the
+    /// body limits, CORS, and tracing configured on this builder.
+    #[must_use]
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request>>::Future: Send + 'static,
+    {
+        self.layers
+            .push(Box::new(move |router| router.layer(layer)));
+        self
+    }
+
     /// Set the path for POST requests.
     #[must_use]
     pub fn post_path(mut self, path: impl Into<String>) -> Self {
@@ -190,11 +468,47 @@ where
     }
 
     /// Build the router.
+    ///
+    /// Besides the POST and SSE endpoints, each task can be followed as an
+    /// SSE stream at `{post_path}/tasks/{task_id}/events` (see
+    /// [`handle_task_events`]).
     pub fn into_router(self) -> Router {
-        let mut router = Router::new()
+        let task_events_path = format!(
+            "{}/tasks/{{task_id}}/events",
+            self.post_path.trim_end_matches('/')
+        );
+        let mcp_routes = Router::new()
             .route(&self.post_path, post(handle_mcp_post::<H>))
             .route(&self.sse_path, get(handle_sse::<H>))
-            .with_state(self.state);
+            .route(&task_events_path, get(handle_task_events::<H>))
+            .with_state(self.state.clone());
+        let mcp_routes = self
+            .layers
+            .into_iter()
+            .fold(mcp_routes, |routes, layer| layer(routes));
+
+        let routes = Router::new();
+        let routes = if self.state.health.is_some() {
+            routes
+                .route(LIVENESS_PATH, get(handle_liveness::<H>))
+                .route(READINESS_PATH, get(handle_readiness::<H>))
+        } else {
+            routes
+        };
+        #[cfg(feature = "prometheus")]
+        let routes = if self.state.metrics.is_some() {
+            routes.route("/metrics", get(crate::handler::handle_metrics::<H>))
+        } else {
+            routes
+        };
+        let mut router = mcp_routes.merge(routes.with_state(self.state));
+
+        if let Some(limits) = self.limits {
+            router = router.layer(DefaultBodyLimit::max(limits.max_message_size));
+            if let Some(timeout) = limits.read_timeout {
+                router = router.layer(RequestBodyTimeoutLayer::new(timeout));
+            }
+        }
 
         // Add OAuth discovery endpoint if configured
         if let Some(metadata) = self.oauth_metadata {
@@ -235,13 +549,32 @@ where
     /// McpRouter::new(handler).serve("0.0.0.0:3000").await?;
     /// ```
     ///
-    /// For more control over the server, use [`Self::into_router`] instead.
+    /// Stops gracefully once the [`shutdown_handle`](Self::shutdown_handle)
+    /// is triggered. For more control over the server, use
+    /// [`Self::into_router`] instead.
     pub async fn serve(self, addr: &str) -> std::io::Result<()> {
+        let shutdown = self.shutdown_handle();
         let router = self.into_router();
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, router)
-            .await
-            .map_err(std::io::Error::other)
+        let server = axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown.wait())
+        .into_future();
+
+        // Bound how long open connections may hold the shutdown up.
+        let deadline = async {
+            shutdown.wait().await;
+            tokio::time::sleep(DEFAULT_SHUTDOWN_TIMEOUT).await;
+        };
+        tokio::select! {
+            result = server => result,
+            () = deadline => {
+                tracing::warn!("Shutdown timeout elapsed; dropping open connections");
+                Ok(())
+            }
+        }
     }
 }
 
@@ -416,4 +749,256 @@ mod tests {
             .unwrap();
         assert_ne!(resp.status(), StatusCode::FORBIDDEN);
     }
+
+    #[tokio::test]
+    async fn with_limits_rejects_oversized_bodies() {
+        let router = McpRouter::new(TestHandler)
+            .with_limits(TransportLimits::new().with_max_message_size(16))
+            .into_router();
+        let resp = router.oneshot(post_with_origin(None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn response_mode_is_negotiated_from_accept() {
+        let content_type = |router: Router, accept: &str| {
+            let mut request = post_with_origin(None);
+            request
+                .headers_mut()
+                .insert("accept", accept.parse().unwrap());
+            async move {
+                let resp = router.oneshot(request).await.unwrap();
+                assert_eq!(resp.status(), StatusCode::OK);
+                resp.headers()["content-type"].to_str().unwrap().to_string()
+            }
+        };
+        let both = "application/json, text/event-stream";
+
+        let json = McpRouter::new(TestHandler).into_router();
+        assert_eq!(content_type(json, both).await, "application/json");
+
+        let sse = McpRouter::new(TestHandler)
+            .with_response_mode(ResponseMode::Sse)
+            .into_router();
+        assert_eq!(content_type(sse.clone(), both).await, "text/event-stream");
+        assert_eq!(
+            content_type(sse, "application/json").await,
+            "application/json"
+        );
+    }
+
+    #[tokio::test]
+    async fn shutdown_rejects_new_requests() {
+        let router = McpRouter::new(TestHandler);
+        let shutdown = router.shutdown_handle();
+        let router = router.into_router();
+
+        shutdown.shutdown();
+        let resp = router.oneshot(post_with_origin(None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn with_compression_decodes_requests_and_encodes_responses() {
+        use mcpkit_transport::compression::{compress, decompress};
+        use mcpkit_transport::{CompressionConfig, Encoding};
+
+        let gzipped_post = || {
+            let mut request = post_with_origin(None);
+            let body = compress(
+                Encoding::Gzip,
+                br#"{"jsonrpc":"2.0","method":"ping","id":1}"#,
+                6,
+            )
+            .unwrap();
+            *request.body_mut() = Body::from(body);
+            let headers = request.headers_mut();
+            headers.insert("content-encoding", "gzip".parse().unwrap());
+            headers.insert("accept-encoding", "deflate".parse().unwrap());
+            request
+        };
+
+        let plain = McpRouter::new(TestHandler).into_router();
+        let resp = plain.oneshot(gzipped_post()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let router =
+            McpRouter::new(TestHandler).with_compression(CompressionConfig::new().with_min_size(0));
+        let stats = std::sync::Arc::clone(&router.state.compression_stats);
+        let resp = router.into_router().oneshot(gzipped_post()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()["content-encoding"], "deflate");
+        assert_eq!(resp.headers()["vary"], "accept-encoding");
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = decompress(Encoding::Deflate, &body, 1024).unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response["id"], 1);
+
+        let stats = stats.snapshot();
+        assert_eq!(stats.messages_decompressed, 1);
+        assert_eq!(stats.messages_compressed, 1);
+    }
+
+    #[tokio::test]
+    async fn health_checks_report_liveness_and_readiness() {
+        let probe = |router: Router, path: &'static str| async move {
+            let resp = router
+                .oneshot(Request::get(path).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = resp.status();
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, String::from_utf8(body.to_vec()).unwrap())
+        };
+
+        let unmounted = McpRouter::new(TestHandler).into_router();
+        assert_eq!(probe(unmounted, "/healthz").await.0, StatusCode::NOT_FOUND);
+
+        let mut checker = HealthChecker::new("test-server");
+        checker.add_check("database", mcpkit_server::ComponentHealth::healthy);
+        let router = McpRouter::new(TestHandler).with_readiness_checks(checker);
+        let shutdown = router.shutdown_handle();
+        let router = router.into_router();
+
+        let live = probe(router.clone(), "/healthz").await;
+        assert_eq!(live, (StatusCode::OK, "ok".to_string()));
+        let (status, body) = probe(router.clone(), "/readyz?verbose").await;
+        assert_eq!(status, StatusCode::OK);
+        let report: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(report["components"]["database"]["status"], "healthy");
+        assert_eq!(report["components"]["sessions"]["status"], "healthy");
+
+        shutdown.shutdown();
+        let draining = probe(router.clone(), "/readyz").await;
+        assert_eq!(
+            draining,
+            (StatusCode::SERVICE_UNAVAILABLE, "not ready".to_string())
+        );
+        assert_eq!(probe(router, "/healthz").await.0, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn task_events_stream_until_terminal_and_resume() {
+        let router = McpRouter::new(TestHandler);
+        let session_id = router.state.sessions.create();
+        let tasks = router.state.sessions.get(&session_id).unwrap().tasks;
+        let handle = tasks.create(None);
+        handle.record_progress(1.0, None, None).unwrap();
+        handle.complete(serde_json::json!({})).unwrap();
+        let path = format!("/mcp/tasks/{}/events", handle.id().as_str());
+        let router = router.into_router();
+
+        let follow = |session: &str, last_event_id: Option<&str>| {
+            let mut request = Request::get(&path).header("mcp-session-id", session);
+            if let Some(id) = last_event_id {
+                request = request.header("last-event-id", id);
+            }
+            let request = request.body(Body::empty()).unwrap();
+            let router = router.clone();
+            async move {
+                let resp = router.oneshot(request).await.unwrap();
+                let status = resp.status();
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        let (status, body) = follow(&session_id, None).await;
+        assert_eq!(status, StatusCode::OK);
+        let events: Vec<_> = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("event: "))
+            .collect();
+        assert_eq!(events, ["status", "progress", "status"], "{body}");
+        assert!(body.contains(r#""status":"completed""#), "{body}");
+
+        let (_, resumed) = follow(&session_id, Some("2")).await;
+        assert!(resumed.starts_with("id: 3\n"), "{resumed}");
+        assert_eq!(resumed.matches("event: ").count(), 1, "{resumed}");
+
+        let (status, _) = follow("no-such-session", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let missing = Request::get("/mcp/tasks/unknown/events")
+            .header("mcp-session-id", &session_id)
+            .body(Body::empty())
+            .unwrap();
+        let resp = router.oneshot(missing).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn with_metrics_serves_request_counts() {
+        let router = McpRouter::new(TestHandler).with_metrics().into_router();
+        let resp = router
+            .clone()
+            .oneshot(post_with_origin(None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let metrics = Request::get("/metrics").body(Body::empty()).unwrap();
+        let resp = router.oneshot(metrics).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            body.contains(r#"mcp_requests_total{method="ping"} 1"#),
+            "{body}"
+        );
+        assert!(body.contains("mcp_active_sessions 1"), "{body}");
+    }
+
+    #[tokio::test]
+    async fn layers_wrap_only_the_mcp_routes_at_a_custom_path() {
+        async fn require_key(
+            request: axum::extract::Request,
+            next: axum::middleware::Next,
+        ) -> axum::response::Response {
+            if request.headers().contains_key("x-api-key") {
+                next.run(request).await
+            } else {
+                StatusCode::UNAUTHORIZED.into_response()
+            }
+        }
+
+        let router = McpRouter::new(TestHandler)
+            .path("/custom/mcp")
+            .layer(axum::middleware::from_fn(require_key))
+            .with_health_checks()
+            .into_router();
+        let post = |uri: &str, key: bool| {
+            let mut request = Request::post(uri).header("mcp-protocol-version", "2025-06-18");
+            if key {
+                request = request.header("x-api-key", "secret");
+            }
+            request
+                .body(Body::from(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#))
+                .unwrap()
+        };
+
+        let denied = router.clone().oneshot(post("/custom/mcp", false)).await;
+        assert_eq!(denied.unwrap().status(), StatusCode::UNAUTHORIZED);
+        let allowed = router.clone().oneshot(post("/custom/mcp", true)).await;
+        assert_ne!(allowed.unwrap().status(), StatusCode::UNAUTHORIZED);
+        let sse = Request::get("/custom/mcp/sse").body(Body::empty()).unwrap();
+        let sse = router.clone().oneshot(sse).await.unwrap();
+        assert_eq!(sse.status(), StatusCode::UNAUTHORIZED);
+
+        let moved = router.clone().oneshot(post("/mcp", true)).await;
+        assert_eq!(moved.unwrap().status(), StatusCode::NOT_FOUND);
+        let health = Request::get("/healthz").body(Body::empty()).unwrap();
+        assert_eq!(
+            router.oneshot(health).await.unwrap().status(),
+            StatusCode::OK
+        );
+    }
 }
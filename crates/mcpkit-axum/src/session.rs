@@ -1,15 +1,18 @@
 //! Session management for MCP HTTP connections.
 
+use bytes::Bytes;
 use dashmap::DashMap;
 use mcpkit_core::auth::{SessionBindingError, VerifiedUser, check_session_binding};
 use mcpkit_core::capability::ClientCapabilities;
 use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_server::fanout::{FanOut, FanOutConfig, FanOutStats, Inbox, Outbox};
+use mcpkit_server::service::{SessionSnapshot, SessionStorage};
+use mcpkit_server::session::{EvictionReason, SessionConfig};
 use std::collections::VecDeque;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tokio::sync::broadcast;
 
 /// A single MCP session.
 #[derive(Debug, Clone)]
@@ -335,17 +338,52 @@ impl EventStore {
     }
 }
 
+/// An SSE session's outbox and activity times.
+#[derive(Debug)]
+struct SseChannel {
+    outbox: Outbox,
+    created_at: Instant,
+    last_active: Instant,
+}
+
+impl SseChannel {
+    fn new(outbox: Outbox) -> Self {
+        let now = Instant::now();
+        Self {
+            outbox,
+            created_at: now,
+            last_active: now,
+        }
+    }
+}
+
 /// Session manager for SSE connections.
 ///
-/// Manages broadcast channels for pushing messages to SSE clients,
-/// with optional event storage for message resumability.
+/// Manages the outboxes that push messages to SSE clients, with optional
+/// event storage for message resumability.
+///
+/// Each session's messages go through a [`FanOut`] outbox: a broadcast
+/// shares one buffer between all sessions, a stream takes whatever has
+/// queued up in one batch, and a session that falls behind loses its oldest
+/// messages (counted in [`Self::fanout_stats`]) rather than holding up the
+/// others.
+///
+/// Sessions are evicted according to a [`SessionConfig`]: by the reaper
+/// task (see [`Self::start_reaper`]) once idle or too old, and when a new
+/// session needs room under the maximum session count. Evicting a session
+/// closes its channel, which ends its SSE stream.
 #[derive(Debug)]
 pub struct SessionManager {
-    sessions: DashMap<String, broadcast::Sender<String>>,
+    sessions: DashMap<String, SseChannel>,
     /// Event stores for each session (for SSE resumability).
     event_stores: DashMap<String, Arc<EventStore>>,
     /// Configuration for event stores.
     event_store_config: EventStoreConfig,
+    /// Creates the per-session outboxes and counts their events.
+    fanout: FanOut,
+    /// Session lifetime settings.
+    pub(crate) config: SessionConfig,
+    reaper_started: AtomicBool,
 }
 
 impl Default for SessionManager {
@@ -358,11 +396,7 @@ impl SessionManager {
     /// Create a new session manager.
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            sessions: DashMap::new(),
-            event_stores: DashMap::new(),
-            event_store_config: EventStoreConfig::default(),
-        }
+        Self::with_event_store_config(EventStoreConfig::default())
     }
 
     /// Create a new session manager with custom event store configuration.
@@ -372,15 +406,61 @@ impl SessionManager {
             sessions: DashMap::new(),
             event_stores: DashMap::new(),
             event_store_config: config,
+            fanout: FanOut::default(),
+            config: SessionConfig::default(),
+            reaper_started: AtomicBool::new(false),
         }
     }
 
+    /// Set the session lifetime settings.
+    #[must_use]
+    pub fn with_session_config(mut self, config: SessionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the size of each session's outbox and of the batches its SSE
+    /// stream takes. Applies to sessions created afterwards.
+    #[must_use]
+    pub fn with_fanout_config(mut self, config: FanOutConfig) -> Self {
+        self.fanout = FanOut::new(config);
+        self
+    }
+
+    /// Counts of the messages queued, delivered, and dropped across all
+    /// sessions.
+    #[must_use]
+    pub fn fanout_stats(&self) -> FanOutStats {
+        self.fanout.stats()
+    }
+
+    /// The session lifetime settings.
+    #[must_use]
+    pub const fn session_config(&self) -> &SessionConfig {
+        &self.config
+    }
+
     /// Create a new session and return its ID and receiver.
+    ///
+    /// At the maximum session count, the least recently active session is
+    /// evicted first.
     #[must_use]
-    pub fn create_session(&self) -> (String, broadcast::Receiver<String>) {
+    pub fn create_session(&self) -> (String, Inbox) {
+        if self.config.at_capacity(self.sessions.len()) {
+            let oldest = self
+                .sessions
+                .iter()
+                .min_by_key(|entry| entry.last_active)
+                .map(|entry| entry.key().clone());
+            if let Some(oldest) = oldest {
+                self.evict(&oldest, EvictionReason::Capacity);
+            }
+        }
+
         let id = uuid::Uuid::new_v4().to_string();
-        let (tx, rx) = broadcast::channel(100);
-        self.sessions.insert(id.clone(), tx);
+        let outbox = self.fanout.outbox();
+        let rx = outbox.subscribe();
+        self.sessions.insert(id.clone(), SseChannel::new(outbox));
 
         // Create an event store for this session
         let event_store = Arc::new(EventStore::new(self.event_store_config.clone()));
@@ -389,10 +469,23 @@ impl SessionManager {
         (id, rx)
     }
 
-    /// Get a receiver for an existing session.
+    /// Get a receiver for an existing session, marking it active.
+    ///
+    /// The new receiver takes over the session's queued messages; the one
+    /// it replaces ends.
     #[must_use]
-    pub fn get_receiver(&self, id: &str) -> Option<broadcast::Receiver<String>> {
-        self.sessions.get(id).map(|tx| tx.subscribe())
+    pub fn get_receiver(&self, id: &str) -> Option<Inbox> {
+        self.sessions.get_mut(id).map(|mut channel| {
+            channel.last_active = Instant::now();
+            channel.outbox.subscribe()
+        })
+    }
+
+    /// Mark a session as active.
+    pub fn touch(&self, id: &str) {
+        if let Some(mut channel) = self.sessions.get_mut(id) {
+            channel.last_active = Instant::now();
+        }
     }
 
     /// Get the event store for a session.
@@ -406,9 +499,9 @@ impl SessionManager {
     /// Returns `true` if the message was sent, `false` if the session doesn't exist.
     #[must_use]
     pub fn send_to_session(&self, id: &str, message: String) -> bool {
-        if let Some(tx) = self.sessions.get(id) {
-            // Ignore send errors (no receivers)
-            let _ = tx.send(message);
+        if let Some(mut channel) = self.sessions.get_mut(id) {
+            channel.last_active = Instant::now();
+            channel.outbox.push(message);
             true
         } else {
             false
@@ -428,7 +521,8 @@ impl SessionManager {
         event_type: impl Into<String>,
         message: String,
     ) -> Option<String> {
-        if let Some(tx) = self.sessions.get(session_id) {
+        if let Some(mut channel) = self.sessions.get_mut(session_id) {
+            channel.last_active = Instant::now();
             // Store the event first
             let event_id = if let Some(store) = self.event_stores.get(session_id) {
                 store.store_auto_id(event_type, message.clone())
@@ -441,7 +535,7 @@ impl SessionManager {
             };
 
             // Send the message
-            let _ = tx.send(message);
+            channel.outbox.push(message);
             Some(event_id)
         } else {
             None
@@ -449,9 +543,14 @@ impl SessionManager {
     }
 
     /// Broadcast a message to all sessions.
+    ///
+    /// The message is stored once and shared by every session's outbox.
     pub fn broadcast(&self, message: String) {
-        for entry in &self.sessions {
-            let _ = entry.value().send(message.clone());
+        let now = Instant::now();
+        let payload = Bytes::from(message);
+        for mut entry in self.sessions.iter_mut() {
+            entry.last_active = now;
+            entry.outbox.push(payload.clone());
         }
     }
 
@@ -459,7 +558,10 @@ impl SessionManager {
     ///
     /// Stores the event in each session's event store for resumability.
     pub fn broadcast_with_storage(&self, event_type: impl Into<String> + Clone, message: String) {
-        for entry in &self.sessions {
+        let now = Instant::now();
+        let payload = Bytes::from(message.clone());
+        for mut entry in self.sessions.iter_mut() {
+            entry.last_active = now;
             let session_id = entry.key();
 
             // Store in event store
@@ -468,7 +570,7 @@ impl SessionManager {
             }
 
             // Send
-            let _ = entry.value().send(message.clone());
+            entry.outbox.push(payload.clone());
         }
     }
 
@@ -484,6 +586,47 @@ impl SessionManager {
         self.sessions.len()
     }
 
+    /// Evict sessions that are idle or past their maximum age, returning how
+    /// many were evicted.
+    #[must_use]
+    pub fn reap_expired(&self) -> usize {
+        let expired: Vec<(String, EvictionReason)> = self
+            .sessions
+            .iter()
+            .filter_map(|entry| {
+                self.config
+                    .eviction_reason(entry.created_at, entry.last_active)
+                    .map(|reason| (entry.key().clone(), reason))
+            })
+            .collect();
+        for (id, reason) in &expired {
+            self.evict(id, *reason);
+        }
+        expired.len()
+    }
+
+    fn evict(&self, id: &str, reason: EvictionReason) {
+        if self.sessions.remove(id).is_some() {
+            self.event_stores.remove(id);
+            self.config.notify_evicted(id, reason);
+        }
+    }
+
+    /// Start the background task that calls [`Self::reap_expired`] every
+    /// [`reap_interval`](SessionConfig::reap_interval).
+    ///
+    /// Only the first call starts a task; the task stops once the manager is
+    /// dropped. Must be called within a Tokio runtime. The SSE handler calls
+    /// this when it opens a stream.
+    pub fn start_reaper(self: &Arc<Self>) {
+        if self.reaper_started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let manager = Arc::downgrade(self);
+        let interval = self.config.reap_interval;
+        tokio::spawn(reap_periodically(manager, interval));
+    }
+
     /// Clean up expired events across all sessions.
     pub async fn cleanup_expired_events(&self) {
         for entry in &self.event_stores {
@@ -507,6 +650,19 @@ impl SessionManager {
     }
 }
 
+async fn reap_periodically(manager: Weak<SessionManager>, interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        let Some(manager) = manager.upgrade() else {
+            break;
+        };
+        let reaped = manager.reap_expired();
+        if reaped > 0 {
+            tracing::debug!(reaped, "Reaped expired SSE sessions");
+        }
+    }
+}
+
 /// Default timeout after which a session created but never initialized is
 /// reaped.
 pub const DEFAULT_INIT_TIMEOUT: Duration = Duration::from_secs(30);
@@ -517,7 +673,7 @@ pub const DEFAULT_INIT_TIMEOUT: Duration = Duration::from_secs(30);
 #[derive(Debug)]
 pub struct SessionStore {
     sessions: DashMap<String, Session>,
-    timeout: Duration,
+    pub(crate) timeout: Duration,
     init_timeout: Duration,
     /// Default task retention (ms) applied to each session's task store; `None`
     /// means unlimited. Configure via `McpRouter::with_task_ttl`.
@@ -669,6 +825,38 @@ impl SessionStore {
     }
 }
 
+impl SessionStorage for SessionStore {
+    fn open_session(&self, user: Option<VerifiedUser>) -> String {
+        self.create_for_user(user)
+    }
+
+    fn resume_session(
+        &self,
+        id: &str,
+        user: Option<&VerifiedUser>,
+    ) -> Result<bool, SessionBindingError> {
+        self.touch_verified(id, user)
+    }
+
+    fn record_negotiated(
+        &self,
+        id: &str,
+        protocol_version: ProtocolVersion,
+        capabilities: Option<ClientCapabilities>,
+    ) {
+        // Initialized sessions are no longer subject to the init timeout.
+        self.update(id, |s| s.mark_initialized(protocol_version, capabilities));
+    }
+
+    fn session_snapshot(&self, id: &str) -> Option<SessionSnapshot> {
+        self.get(id).map(|s| SessionSnapshot {
+            protocol_version: s.protocol_version,
+            client_capabilities: s.client_capabilities,
+            tasks: Some(s.tasks),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -798,8 +986,8 @@ mod tests {
         assert!(manager.send_to_session(&id, "test message".to_string()));
 
         // Receive the message
-        let msg = rx.recv().await?;
-        assert_eq!(msg, "test message");
+        let batch = rx.recv_batch().await.ok_or("session closed")?;
+        assert_eq!(batch.events, ["test message"]);
 
         // Remove session
         manager.remove_session(&id);
@@ -932,8 +1120,8 @@ mod tests {
         assert!(event_id.is_some());
 
         // Verify message was received
-        let msg = rx.recv().await?;
-        assert_eq!(msg, "test data");
+        let batch = rx.recv_batch().await.ok_or("session closed")?;
+        assert_eq!(batch.events, ["test data"]);
 
         // Verify event was stored
         let store = manager
@@ -990,4 +1178,75 @@ mod tests {
         assert_eq!(event.event_type, "message");
         assert_eq!(event.data, "test data");
     }
+
+    #[tokio::test]
+    async fn reaper_evicts_idle_sessions_and_ends_their_streams() {
+        let evicted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&evicted);
+        let config = SessionConfig::new()
+            .with_idle_timeout(Duration::from_millis(50))
+            .with_reap_interval(Duration::from_millis(10))
+            .on_evict(move |id, reason| recorded.lock().unwrap().push((id.to_string(), reason)));
+        let manager = Arc::new(SessionManager::new().with_session_config(config));
+
+        let (idle, mut idle_rx) = manager.create_session();
+        let (active, _active_rx) = manager.create_session();
+        manager.start_reaper();
+        manager.start_reaper();
+
+        for _ in 0..10 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert!(manager.send_to_session(&active, "ping".to_string()));
+        }
+
+        assert_eq!(idle_rx.recv_batch().await, None);
+        assert!(manager.get_receiver(&idle).is_none());
+        assert!(manager.get_event_store(&idle).is_none());
+        assert!(manager.get_receiver(&active).is_some());
+        assert_eq!(*evicted.lock().unwrap(), vec![(idle, EvictionReason::Idle)]);
+    }
+
+    #[test]
+    fn full_manager_evicts_least_recently_active() {
+        let evicted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&evicted);
+        let manager = SessionManager::new().with_session_config(
+            SessionConfig::new()
+                .with_max_sessions(2)
+                .on_evict(move |id, reason| {
+                    recorded.lock().unwrap().push((id.to_string(), reason));
+                }),
+        );
+
+        let (first, _) = manager.create_session();
+        let (second, _) = manager.create_session();
+        manager.touch(&first);
+        let (third, _) = manager.create_session();
+
+        assert_eq!(manager.session_count(), 2);
+        assert!(manager.get_receiver(&first).is_some());
+        assert!(manager.get_receiver(&third).is_some());
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            vec![(second, EvictionReason::Capacity)]
+        );
+    }
+
+    #[tokio::test]
+    async fn broadcasts_drop_the_oldest_for_lagging_sessions() {
+        let manager =
+            SessionManager::new().with_fanout_config(FanOutConfig::new().with_capacity(2));
+        let mut sessions: Vec<_> = (0..3).map(|_| manager.create_session().1).collect();
+        for n in 0..3 {
+            manager.broadcast(format!("notification {n}"));
+        }
+
+        for rx in &mut sessions {
+            let batch = rx.recv_batch().await.unwrap();
+            assert_eq!(batch.events, ["notification 1", "notification 2"]);
+            assert_eq!(batch.dropped, 1);
+        }
+        let stats = manager.fanout_stats();
+        assert_eq!((stats.published, stats.delivered, stats.dropped), (9, 6, 3));
+    }
 }
@@ -3,10 +3,18 @@
 use crate::session::{SessionManager, SessionStore};
 use mcpkit_core::auth::ProtectedResourceMetadata;
 use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_server::service::{McpService, ResponseMode, ServiceLimits};
+use mcpkit_server::{RateLimiter, ShutdownHandle};
 use mcpkit_transport::http::OriginValidator;
 use std::fmt;
 use std::sync::Arc;
 
+/// Copies values from an HTTP request into the per-request
+/// [`Extensions`](mcpkit_server::Extensions) that handlers read through
+/// [`Context::extensions`](mcpkit_server::Context::extensions).
+pub type RequestExtractor =
+    Arc<dyn Fn(&axum::http::request::Parts, &mut mcpkit_server::Extensions) + Send + Sync>;
+
 /// Shared state for MCP handlers.
 ///
 /// This struct holds all the shared state needed by MCP HTTP handlers,
@@ -30,6 +38,40 @@ pub struct McpState<H> {
     pub list_page_size: Option<usize>,
     /// Optional completion handler for `completion/complete`.
     pub completion: Option<Arc<dyn mcpkit_server::dispatch::DynCompletionHandler>>,
+    /// Headers to capture into [`HttpRequestInfo`](mcpkit_server::HttpRequestInfo)
+    /// for each request; `None` disables the request-info bridge.
+    pub request_info_headers: Option<Arc<[String]>>,
+    /// Extractors run on each POSTed request, in registration order.
+    pub request_extractors: Vec<RequestExtractor>,
+    /// Per-session request rate limits; `None` disables rate limiting.
+    pub rate_limiter: Option<RateLimiter>,
+    /// Authorization policy for `tools/call`; `None` allows every call.
+    pub tool_authorizer: Option<mcpkit_server::ToolAuthorizer>,
+    /// Audit log for tool calls and resource reads; `None` disables it.
+    pub audit_logger: Option<mcpkit_server::AuditLogger>,
+    /// How tool, resource, and prompt invocations are traced.
+    pub invocation_tracing: mcpkit_server::InvocationTracing,
+    /// Request metrics and the `metrics://server` resource; `None` disables
+    /// them.
+    pub server_metrics: Option<mcpkit_server::MetricsLayer>,
+    /// Message size and in-flight limits applied to each request.
+    pub limits: ServiceLimits,
+    /// How POSTed requests are answered when the client accepts both JSON
+    /// and SSE.
+    pub response_mode: ResponseMode,
+    /// Graceful shutdown trigger; once fired, new requests are rejected and
+    /// SSE streams end.
+    pub shutdown: ShutdownHandle,
+    /// Compression of response bodies, and acceptance of compressed request
+    /// bodies; `None` disables both.
+    pub compression: Option<mcpkit_transport::CompressionConfig>,
+    /// Bytes before and after compression, across all requests.
+    pub compression_stats: Arc<mcpkit_transport::CompressionStats>,
+    /// Answers `/healthz` and `/readyz`; `None` leaves them unmounted.
+    pub health: Option<mcpkit_server::HealthProbes>,
+    /// Prometheus metrics recorded for each request; `None` disables them.
+    #[cfg(feature = "prometheus")]
+    pub metrics: Option<mcpkit_transport::McpMetrics>,
 }
 
 // Manual Clone implementation to avoid requiring H: Clone
@@ -43,6 +85,21 @@ impl<H> Clone for McpState<H> {
             origin_validator: Arc::clone(&self.origin_validator),
             list_page_size: self.list_page_size,
             completion: self.completion.clone(),
+            request_info_headers: self.request_info_headers.clone(),
+            request_extractors: self.request_extractors.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            tool_authorizer: self.tool_authorizer.clone(),
+            audit_logger: self.audit_logger.clone(),
+            invocation_tracing: self.invocation_tracing.clone(),
+            server_metrics: self.server_metrics.clone(),
+            limits: self.limits.clone(),
+            response_mode: self.response_mode,
+            shutdown: self.shutdown.clone(),
+            compression: self.compression,
+            compression_stats: Arc::clone(&self.compression_stats),
+            health: self.health.clone(),
+            #[cfg(feature = "prometheus")]
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -61,7 +118,19 @@ impl<H> fmt::Debug for McpState<H> {
                 "completion",
                 &format_args!("Option<Arc<dyn DynCompletionHandler>>"),
             )
-            .finish()
+            .field("request_info_headers", &self.request_info_headers)
+            .field("request_extractors", &self.request_extractors.len())
+            .field("rate_limiter", &self.rate_limiter)
+            .field("tool_authorizer", &self.tool_authorizer)
+            .field("audit_logger", &self.audit_logger)
+            .field("invocation_tracing", &self.invocation_tracing)
+            .field("server_metrics", &self.server_metrics)
+            .field("limits", &self.limits)
+            .field("response_mode", &self.response_mode)
+            .field("shutdown", &self.shutdown)
+            .field("compression", &self.compression)
+            .field("health", &self.health)
+            .finish_non_exhaustive()
     }
 }
 
@@ -80,6 +149,21 @@ impl<H> McpState<H> {
             origin_validator: Arc::new(OriginValidator::default()),
             list_page_size: None,
             completion: None,
+            request_info_headers: None,
+            request_extractors: Vec::new(),
+            rate_limiter: None,
+            tool_authorizer: None,
+            audit_logger: None,
+            invocation_tracing: mcpkit_server::InvocationTracing::default(),
+            server_metrics: None,
+            limits: ServiceLimits::default(),
+            response_mode: ResponseMode::default(),
+            shutdown: ShutdownHandle::new(),
+            compression: None,
+            compression_stats: Arc::default(),
+            health: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
         }
     }
 
@@ -97,6 +181,21 @@ impl<H> McpState<H> {
             origin_validator: Arc::new(OriginValidator::default()),
             list_page_size: None,
             completion: None,
+            request_info_headers: None,
+            request_extractors: Vec::new(),
+            rate_limiter: None,
+            tool_authorizer: None,
+            audit_logger: None,
+            invocation_tracing: mcpkit_server::InvocationTracing::default(),
+            server_metrics: None,
+            limits: ServiceLimits::default(),
+            response_mode: ResponseMode::default(),
+            shutdown: ShutdownHandle::new(),
+            compression: None,
+            compression_stats: Arc::default(),
+            health: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
         }
     }
 }
@@ -157,6 +256,104 @@ impl<H> McpState<H> {
         self
     }
 
+    /// Expose HTTP request metadata to handlers via
+    /// [`Context::http_request`](mcpkit_server::Context::http_request).
+    ///
+    /// Each request's method, path, peer address (when served with connect
+    /// info), and the named headers are captured into an
+    /// [`HttpRequestInfo`](mcpkit_server::HttpRequestInfo). Only the listed
+    /// headers are copied, so credentials are not exposed unless asked for.
+    #[must_use]
+    pub fn with_request_info<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.request_info_headers = Some(
+            headers
+                .into_iter()
+                .map(|h| h.into().to_ascii_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Run `extractor` on each POSTed request to attach values to the
+    /// handler's [`Context`](mcpkit_server::Context).
+    #[must_use]
+    pub fn with_request_extractor<F>(mut self, extractor: F) -> Self
+    where
+        F: Fn(&axum::http::request::Parts, &mut mcpkit_server::Extensions) + Send + Sync + 'static,
+    {
+        self.request_extractors.push(Arc::new(extractor));
+        self
+    }
+
+    /// Copy the axum request extension of type `T`, when present, into the
+    /// handler's [`Context`](mcpkit_server::Context).
+    #[must_use]
+    pub fn with_request_extension<T>(self) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.with_request_extractor(|parts, extensions| {
+            if let Some(value) = parts.extensions.get::<T>() {
+                extensions.insert(value.clone());
+            }
+        })
+    }
+
+    /// Reject requests over `config`'s quotas, keyed by `mcp-session-id`.
+    #[must_use]
+    pub fn with_rate_limit(mut self, config: mcpkit_server::RateLimitConfig) -> Self {
+        self.rate_limiter = Some(RateLimiter::new(config));
+        self
+    }
+
+    /// Refuse `tools/call` requests the authorizer's policy denies.
+    #[must_use]
+    pub fn with_tool_authorizer(mut self, authorizer: mcpkit_server::ToolAuthorizer) -> Self {
+        self.tool_authorizer = Some(authorizer);
+        self
+    }
+
+    /// Record tool calls and resource reads to `logger`.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: mcpkit_server::AuditLogger) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Trace tool, resource, and prompt invocations with `tracing`.
+    #[must_use]
+    pub fn with_invocation_tracing(mut self, tracing: mcpkit_server::InvocationTracing) -> Self {
+        self.invocation_tracing = tracing;
+        self
+    }
+
+    /// Record every request into `metrics`.
+    #[must_use]
+    pub fn with_server_metrics(mut self, metrics: mcpkit_server::MetricsLayer) -> Self {
+        self.server_metrics = Some(metrics);
+        self
+    }
+
+    /// Reject bodies over `limits.max_message_size` and requests beyond
+    /// `limits.max_in_flight`.
+    #[must_use]
+    pub fn with_limits(mut self, limits: mcpkit_transport::TransportLimits) -> Self {
+        self.limits = ServiceLimits::new(limits);
+        self
+    }
+
+    /// Answer POSTed requests in `mode` when the client accepts both JSON
+    /// and SSE.
+    #[must_use]
+    pub const fn with_response_mode(mut self, mode: ResponseMode) -> Self {
+        self.response_mode = mode;
+        self
+    }
+
     /// Set the default task retention (milliseconds) for each session's task
     /// store, applied when a task-augmented `tools/call` omits a `ttl`. Pass
     /// `None` for unlimited retention. Defaults to
@@ -168,9 +365,62 @@ impl<H> McpState<H> {
         }
         self
     }
+
+    /// Apply `config` to the SSE sessions, and its idle timeout to the HTTP
+    /// sessions.
+    #[must_use]
+    pub fn with_session_config(mut self, config: mcpkit_server::SessionConfig) -> Self {
+        if let Some(store) = Arc::get_mut(&mut self.sessions) {
+            store.timeout = config.idle_timeout;
+        }
+        if let Some(manager) = Arc::get_mut(&mut self.sse_sessions) {
+            manager.config = config;
+        }
+        self
+    }
 }
 
 impl<H: mcpkit_server::ServerHandler> McpState<H> {
+    /// A transport-agnostic [`McpService`] over this state's handler,
+    /// sessions, and settings. The handlers delegate request processing to it.
+    #[must_use]
+    pub fn service(&self) -> McpService<H, Arc<SessionStore>> {
+        let mut service = McpService::from_parts(
+            Arc::clone(&self.handler),
+            Arc::clone(&self.sessions),
+            self.server_info.clone(),
+        )
+        .with_origin_validator(Arc::clone(&self.origin_validator));
+        if let Some(page_size) = self.list_page_size {
+            service = service.with_list_page_size(page_size);
+        }
+        if let Some(completion) = &self.completion {
+            service = service.with_shared_completion(Arc::clone(completion));
+        }
+        if let Some(limiter) = &self.rate_limiter {
+            service = service.with_rate_limiter(limiter.clone());
+        }
+        if let Some(authorizer) = &self.tool_authorizer {
+            service = service.with_tool_authorizer(authorizer.clone());
+        }
+        if let Some(logger) = &self.audit_logger {
+            service = service.with_audit_logger(logger.clone());
+        }
+        service = service.with_invocation_tracing(self.invocation_tracing.clone());
+        if let Some(metrics) = &self.server_metrics {
+            service = service.with_server_metrics(metrics.clone());
+        }
+        service = service
+            .with_limits(self.limits.clone())
+            .with_response_mode(self.response_mode)
+            .with_shutdown(self.shutdown.clone());
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.metrics {
+            service = service.with_metrics(metrics.clone());
+        }
+        service
+    }
+
     /// The handler's advertised capabilities, plus `completions` when a
     /// completion handler is registered on this adapter (the handler itself
     /// cannot know it was registered here, so the adapter advertises it).
@@ -4,7 +4,7 @@
 //! helper, so this guards the adapter "routing split" from forgetting completion.
 
 use axum::extract::State;
-use axum::http::HeaderMap;
+use axum::http::{HeaderMap, Method, Uri};
 use axum::response::IntoResponse;
 use mcpkit_axum::McpState;
 use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
@@ -105,9 +105,16 @@ async fn completion_complete_works_through_axum_adapter() {
     })
     .to_string();
 
-    let response = mcpkit_axum::handle_mcp_post(State(state), HeaderMap::new(), None, body)
-        .await
-        .into_response();
+    let response = mcpkit_axum::handle_mcp_post(
+        State(state),
+        Method::POST,
+        Uri::from_static("/mcp"),
+        HeaderMap::new(),
+        axum::http::Extensions::new(),
+        body.into(),
+    )
+    .await
+    .into_response();
     let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
         .expect("body");
@@ -131,9 +138,16 @@ async fn with_completion_advertises_capability_in_initialize() {
             "params": { "protocolVersion": "2025-11-25", "capabilities": {} }
         })
         .to_string();
-        let response = mcpkit_axum::handle_mcp_post(State(state), HeaderMap::new(), None, body)
-            .await
-            .into_response();
+        let response = mcpkit_axum::handle_mcp_post(
+            State(state),
+            Method::POST,
+            Uri::from_static("/mcp"),
+            HeaderMap::new(),
+            axum::http::Extensions::new(),
+            body.into(),
+        )
+        .await
+        .into_response();
         let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .expect("body");
@@ -164,9 +178,16 @@ async fn completion_complete_without_handler_is_method_not_found() {
     })
     .to_string();
 
-    let response = mcpkit_axum::handle_mcp_post(State(state), HeaderMap::new(), None, body)
-        .await
-        .into_response();
+    let response = mcpkit_axum::handle_mcp_post(
+        State(state),
+        Method::POST,
+        Uri::from_static("/mcp"),
+        HeaderMap::new(),
+        axum::http::Extensions::new(),
+        body.into(),
+    )
+    .await
+    .into_response();
     let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
         .expect("body");
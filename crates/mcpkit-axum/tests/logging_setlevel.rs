@@ -4,7 +4,7 @@
 //! "routing split" forgetting to route logging.
 
 use axum::extract::State;
-use axum::http::HeaderMap;
+use axum::http::{HeaderMap, Method, Uri};
 use axum::response::IntoResponse;
 use mcpkit_axum::McpState;
 use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
@@ -84,9 +84,16 @@ async fn logging_set_level_works_through_axum_adapter() {
     })
     .to_string();
 
-    let response = mcpkit_axum::handle_mcp_post(State(state), HeaderMap::new(), None, body)
-        .await
-        .into_response();
+    let response = mcpkit_axum::handle_mcp_post(
+        State(state),
+        Method::POST,
+        Uri::from_static("/mcp"),
+        HeaderMap::new(),
+        axum::http::Extensions::new(),
+        body.into(),
+    )
+    .await
+    .into_response();
     let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
         .await
         .expect("body");
@@ -0,0 +1,129 @@
+//! `with_rate_limit` rejects requests over a session's quota with a JSON-RPC
+//! error carrying `retry_after_ms`, without affecting other sessions.
+
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderValue, Method, Uri};
+use axum::response::IntoResponse;
+use mcpkit_axum::McpState;
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{GetPromptResult, Prompt, Resource, ResourceContents, Tool, ToolOutput};
+use mcpkit_server::{
+    Context, PromptHandler, Quota, RateLimitConfig, ResourceHandler, ServerHandler, ToolHandler,
+};
+
+struct H;
+
+impl ServerHandler for H {
+    fn server_info(&self) -> ServerInfo {
+        ServerInfo::new("t", "1.0.0")
+    }
+    fn capabilities(&self) -> ServerCapabilities {
+        ServerCapabilities::new().with_tools()
+    }
+}
+impl ToolHandler for H {
+    async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        Ok(vec![Tool::new("send_email"), Tool::new("search")])
+    }
+    async fn call_tool(
+        &self,
+        _name: &str,
+        _args: serde_json::Map<String, serde_json::Value>,
+        _ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        Ok(ToolOutput::text("ok"))
+    }
+}
+impl ResourceHandler for H {
+    async fn list_resources(&self, _ctx: &Context<'_>) -> Result<Vec<Resource>, McpError> {
+        Ok(vec![])
+    }
+    async fn read_resource(
+        &self,
+        _uri: &str,
+        _ctx: &Context<'_>,
+    ) -> Result<Vec<ResourceContents>, McpError> {
+        Ok(vec![])
+    }
+}
+impl PromptHandler for H {
+    async fn list_prompts(&self, _ctx: &Context<'_>) -> Result<Vec<Prompt>, McpError> {
+        Ok(vec![])
+    }
+    async fn get_prompt(
+        &self,
+        _name: &str,
+        _args: Option<serde_json::Map<String, serde_json::Value>>,
+        _ctx: &Context<'_>,
+    ) -> Result<GetPromptResult, McpError> {
+        Ok(GetPromptResult {
+            description: None,
+            messages: vec![],
+            meta: None,
+        })
+    }
+}
+
+/// POST one request; returns the session id and the JSON-RPC response.
+async fn post(
+    state: &McpState<H>,
+    session: Option<&str>,
+    method: &str,
+    params: serde_json::Value,
+) -> (String, serde_json::Value) {
+    let mut headers = HeaderMap::new();
+    if let Some(session) = session {
+        headers.insert("mcp-session-id", HeaderValue::from_str(session).unwrap());
+    }
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })
+        .to_string();
+    let response = mcpkit_axum::handle_mcp_post(
+        State(state.clone()),
+        Method::POST,
+        Uri::from_static("/mcp"),
+        headers,
+        axum::http::Extensions::new(),
+        body.into(),
+    )
+    .await
+    .into_response();
+    let session = response.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    (session, serde_json::from_slice(&bytes).expect("json"))
+}
+
+async fn initialize(state: &McpState<H>) -> String {
+    let params = serde_json::json!({ "protocolVersion": "2025-11-25", "capabilities": {} });
+    post(state, None, "initialize", params).await.0
+}
+
+#[tokio::test]
+async fn tool_quota_is_enforced_per_session() {
+    let state = McpState::new(H)
+        .with_rate_limit(RateLimitConfig::new().tool("send_email", Quota::per_minute(1)));
+    let email = serde_json::json!({ "name": "send_email", "arguments": {} });
+
+    let alice = initialize(&state).await;
+    let (_, first) = post(&state, Some(&alice), "tools/call", email.clone()).await;
+    assert_eq!(first["result"]["content"][0]["text"], "ok", "{first}");
+
+    let (_, second) = post(&state, Some(&alice), "tools/call", email.clone()).await;
+    let error = &second["error"];
+    assert_eq!(error["code"], -32010, "{second}");
+    assert_eq!(error["data"]["method"], "tools/call:send_email");
+    assert!(error["data"]["retry_after_ms"].as_u64().unwrap() > 0);
+
+    // Other tools, and other sessions, are unaffected.
+    let search = serde_json::json!({ "name": "search", "arguments": {} });
+    let (_, other_tool) = post(&state, Some(&alice), "tools/call", search).await;
+    assert!(other_tool.get("error").is_none(), "{other_tool}");
+    let bob = initialize(&state).await;
+    let (_, other_session) = post(&state, Some(&bob), "tools/call", email).await;
+    assert!(other_session.get("error").is_none(), "{other_session}");
+}
@@ -0,0 +1,165 @@
+//! The opt-in request-info bridge: with `with_request_info`, tool handlers see
+//! the HTTP method, path, peer address, and only the allow-listed headers via
+//! `Context::http_request`; without it, they see nothing. Request extractors
+//! attach arbitrary typed values, read through `Context::extensions`.
+
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, HeaderValue, Method, Uri};
+use axum::response::IntoResponse;
+use mcpkit_axum::McpState;
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{GetPromptResult, Prompt, Resource, ResourceContents, Tool, ToolOutput};
+use mcpkit_server::{Context, PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use std::net::SocketAddr;
+
+struct H;
+
+/// Claims an auth middleware would insert into the axum request extensions.
+#[derive(Clone)]
+struct Claims {
+    subject: String,
+}
+
+/// A value a request extractor derives from the headers.
+struct UserAgent(String);
+
+impl ServerHandler for H {
+    fn server_info(&self) -> ServerInfo {
+        ServerInfo::new("t", "1.0.0")
+    }
+    fn capabilities(&self) -> ServerCapabilities {
+        ServerCapabilities::new().with_tools()
+    }
+}
+impl ToolHandler for H {
+    async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        Ok(vec![Tool::new("whoami")])
+    }
+    async fn call_tool(
+        &self,
+        _name: &str,
+        _args: serde_json::Map<String, serde_json::Value>,
+        ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        let info = ctx.http_request().map(|info| {
+            serde_json::json!({
+                "method": info.method,
+                "path": info.path,
+                "tenant": info.header("x-tenant-id"),
+                "authorization": info.header("authorization"),
+                "peer": info.peer_addr.map(|a| a.to_string()),
+            })
+        });
+        let extensions = ctx.extensions();
+        let seen = serde_json::json!({
+            "info": info,
+            "subject": extensions.get::<Claims>().map(|c| c.subject.as_str()),
+            "user_agent": extensions.get::<UserAgent>().map(|ua| ua.0.as_str()),
+        });
+        Ok(ToolOutput::text(seen.to_string()))
+    }
+}
+impl ResourceHandler for H {
+    async fn list_resources(&self, _ctx: &Context<'_>) -> Result<Vec<Resource>, McpError> {
+        Ok(vec![])
+    }
+    async fn read_resource(
+        &self,
+        _uri: &str,
+        _ctx: &Context<'_>,
+    ) -> Result<Vec<ResourceContents>, McpError> {
+        Ok(vec![])
+    }
+}
+impl PromptHandler for H {
+    async fn list_prompts(&self, _ctx: &Context<'_>) -> Result<Vec<Prompt>, McpError> {
+        Ok(vec![])
+    }
+    async fn get_prompt(
+        &self,
+        _name: &str,
+        _args: Option<serde_json::Map<String, serde_json::Value>>,
+        _ctx: &Context<'_>,
+    ) -> Result<GetPromptResult, McpError> {
+        Ok(GetPromptResult {
+            description: None,
+            messages: vec![],
+            meta: None,
+        })
+    }
+}
+
+/// Call `whoami` and return the JSON the tool saw.
+async fn whoami(state: McpState<H>) -> serde_json::Value {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-tenant-id", HeaderValue::from_static("acme"));
+    headers.insert("authorization", HeaderValue::from_static("Bearer secret"));
+    headers.insert("user-agent", HeaderValue::from_static("probe/1.0"));
+    let peer: SocketAddr = "10.1.2.3:4567".parse().unwrap();
+    let mut request_extensions = axum::http::Extensions::new();
+    request_extensions.insert(ConnectInfo(peer));
+    request_extensions.insert(Claims {
+        subject: "alice".to_string(),
+    });
+    let body = serde_json::json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": { "name": "whoami", "arguments": {} }
+    })
+    .to_string();
+
+    let response = mcpkit_axum::handle_mcp_post(
+        State(state),
+        Method::POST,
+        Uri::from_static("/mcp?x=1"),
+        headers,
+        request_extensions,
+        body.into(),
+    )
+    .await
+    .into_response();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&bytes).expect("json");
+    let text = json["result"]["content"][0]["text"]
+        .as_str()
+        .unwrap_or_else(|| panic!("response: {json}"));
+    serde_json::from_str(text).expect("tool output json")
+}
+
+#[tokio::test]
+async fn request_info_exposes_allow_listed_metadata() {
+    let seen = whoami(McpState::new(H).with_request_info(["X-Tenant-Id"])).await["info"].clone();
+
+    assert_eq!(seen["method"], "POST");
+    assert_eq!(seen["path"], "/mcp");
+    assert_eq!(seen["tenant"], "acme");
+    assert_eq!(seen["peer"], "10.1.2.3:4567");
+    // Headers that were not allow-listed are never copied.
+    assert!(seen["authorization"].is_null(), "seen: {seen}");
+}
+
+#[tokio::test]
+async fn request_info_is_absent_by_default() {
+    let seen = whoami(McpState::new(H)).await;
+    assert!(seen["info"].is_null(), "seen: {seen}");
+    assert!(seen["subject"].is_null(), "seen: {seen}");
+    assert!(seen["user_agent"].is_null(), "seen: {seen}");
+}
+
+#[tokio::test]
+async fn request_extractors_attach_typed_values() {
+    let state = McpState::new(H)
+        .with_request_extension::<Claims>()
+        .with_request_extractor(|parts, extensions| {
+            if let Some(ua) = parts.headers.get("user-agent") {
+                extensions.insert(UserAgent(ua.to_str().unwrap_or_default().to_string()));
+            }
+        });
+    let seen = whoami(state).await;
+
+    assert_eq!(seen["subject"], "alice");
+    assert_eq!(seen["user_agent"], "probe/1.0");
+    assert!(seen["info"].is_null(), "seen: {seen}");
+}
@@ -2,7 +2,7 @@
 //! axum adapter, including per-session isolation and cancellation propagation.
 
 use axum::extract::State;
-use axum::http::{HeaderMap, HeaderValue};
+use axum::http::{HeaderMap, HeaderValue, Method, Uri};
 use axum::response::IntoResponse;
 use mcpkit_axum::McpState;
 use mcpkit_core::capability::ServerInfo;
@@ -98,10 +98,16 @@ async fn post(
             HeaderValue::from_str(s).expect("session header"),
         );
     }
-    let response =
-        mcpkit_axum::handle_mcp_post(State(state.clone()), headers, None, body.to_string())
-            .await
-            .into_response();
+    let response = mcpkit_axum::handle_mcp_post(
+        State(state.clone()),
+        Method::POST,
+        Uri::from_static("/mcp"),
+        headers,
+        axum::http::Extensions::new(),
+        body.to_string().into(),
+    )
+    .await
+    .into_response();
     let sid = response
         .headers()
         .get("mcp-session-id")
@@ -0,0 +1,448 @@
+//! Inspector commands.
+//!
+//! The same grammar is accepted after `mcpkit [--server SPEC]` on the command
+//! line and at the `mcp>` prompt of an interactive session:
+//!
+//! ```text
+//! tools list
+//! tools call <NAME> [--arg KEY=VALUE]... [--args JSON]
+//! resources list
+//! resources templates
+//! resources read <URI>
+//! prompts list
+//! prompts get <NAME> [--arg KEY=VALUE]...
+//! ping
+//! info
+//! ```
+//!
+//! Tool argument values are parsed as JSON when they can be (`a=1`,
+//! `flag=true`, `tags=["x"]`) and taken as strings otherwise (`path=/tmp`).
+//! Prompt argument values are always strings.
+
+use std::fmt::Write as _;
+
+use clap::{Arg, ArgAction, ArgMatches};
+use mcpkit_client::Client;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{
+    CallToolResult, Content, GetPromptResult, Prompt, Resource, ResourceContents, ResourceTemplate,
+    Tool,
+};
+use mcpkit_transport::Transport;
+use serde_json::{Map, Value};
+
+/// A request to send to the server under inspection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `tools list`
+    ListTools,
+    /// `tools call <NAME>`
+    CallTool {
+        /// Tool name.
+        name: String,
+        /// Tool arguments.
+        arguments: Map<String, Value>,
+    },
+    /// `resources list`
+    ListResources,
+    /// `resources templates`
+    ListResourceTemplates,
+    /// `resources read <URI>`
+    ReadResource {
+        /// Resource URI.
+        uri: String,
+    },
+    /// `prompts list`
+    ListPrompts,
+    /// `prompts get <NAME>`
+    GetPrompt {
+        /// Prompt name.
+        name: String,
+        /// Prompt arguments.
+        arguments: Map<String, Value>,
+    },
+    /// `ping`
+    Ping,
+    /// `info`: server info, capabilities, and instructions from the handshake.
+    Info,
+}
+
+/// The subcommands making up the inspector's grammar.
+///
+/// The binary mounts these under its top-level command, and the interactive
+/// session parses each line against them.
+#[must_use]
+pub fn subcommands() -> Vec<clap::Command> {
+    vec![
+        clap::Command::new("tools")
+            .about("List and call tools")
+            .subcommand_required(true)
+            .subcommand(clap::Command::new("list").about("List the server's tools"))
+            .subcommand(
+                clap::Command::new("call")
+                    .about("Call a tool")
+                    .arg(Arg::new("name").required(true).help("Tool name"))
+                    .arg(key_value_arg(
+                        "Tool argument; VALUE is parsed as JSON if possible",
+                    ))
+                    .arg(
+                        Arg::new("args")
+                            .long("args")
+                            .value_name("JSON")
+                            .help("Tool arguments as a JSON object, merged under any --arg"),
+                    ),
+            ),
+        clap::Command::new("resources")
+            .about("List and read resources")
+            .subcommand_required(true)
+            .subcommand(clap::Command::new("list").about("List the server's resources"))
+            .subcommand(
+                clap::Command::new("templates").about("List the server's resource templates"),
+            )
+            .subcommand(
+                clap::Command::new("read")
+                    .about("Read a resource")
+                    .arg(Arg::new("uri").required(true).help("Resource URI")),
+            ),
+        clap::Command::new("prompts")
+            .about("List and render prompts")
+            .subcommand_required(true)
+            .subcommand(clap::Command::new("list").about("List the server's prompts"))
+            .subcommand(
+                clap::Command::new("get")
+                    .about("Render a prompt")
+                    .arg(Arg::new("name").required(true).help("Prompt name"))
+                    .arg(key_value_arg("Prompt argument")),
+            ),
+        clap::Command::new("ping").about("Check that the server responds"),
+        clap::Command::new("info").about("Show the server's info and capabilities"),
+    ]
+}
+
+/// The repeatable `--arg KEY=VALUE` option.
+fn key_value_arg(help: &'static str) -> Arg {
+    Arg::new("arg")
+        .long("arg")
+        .short('a')
+        .value_name("KEY=VALUE")
+        .action(ArgAction::Append)
+        .help(help)
+}
+
+impl Command {
+    /// Build a command from the matches of one of [`subcommands`].
+    ///
+    /// Returns `Ok(None)` if `matches` holds no inspector subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if an `--arg` is not `KEY=VALUE` or `--args` is not
+    /// a JSON object.
+    pub fn from_matches(matches: &ArgMatches) -> Result<Option<Self>, String> {
+        let Some((group, matches)) = matches.subcommand() else {
+            return Ok(None);
+        };
+        let command = match (group, matches.subcommand()) {
+            ("tools", Some(("list", _))) => Self::ListTools,
+            ("tools", Some(("call", m))) => Self::CallTool {
+                name: required(m, "name"),
+                arguments: tool_arguments(m)?,
+            },
+            ("resources", Some(("list", _))) => Self::ListResources,
+            ("resources", Some(("templates", _))) => Self::ListResourceTemplates,
+            ("resources", Some(("read", m))) => Self::ReadResource {
+                uri: required(m, "uri"),
+            },
+            ("prompts", Some(("list", _))) => Self::ListPrompts,
+            ("prompts", Some(("get", m))) => Self::GetPrompt {
+                name: required(m, "name"),
+                arguments: key_values(m, |raw| Value::String(raw.to_string()))?,
+            },
+            ("ping", _) => Self::Ping,
+            ("info", _) => Self::Info,
+            _ => return Ok(None),
+        };
+        Ok(Some(command))
+    }
+}
+
+/// A required positional argument (clap has already checked it is present).
+fn required(matches: &ArgMatches, id: &str) -> String {
+    matches.get_one::<String>(id).cloned().unwrap_or_default()
+}
+
+/// Tool arguments from `--args` and `--arg`, the latter taking precedence.
+fn tool_arguments(matches: &ArgMatches) -> Result<Map<String, Value>, String> {
+    let mut arguments = match matches.get_one::<String>("args") {
+        Some(json) => match serde_json::from_str(json) {
+            Ok(Value::Object(map)) => map,
+            Ok(_) => return Err("--args must be a JSON object".to_string()),
+            Err(e) => return Err(format!("invalid --args JSON: {e}")),
+        },
+        None => Map::new(),
+    };
+    arguments.extend(key_values(matches, parse_value)?);
+    Ok(arguments)
+}
+
+/// The `--arg KEY=VALUE` pairs, with values converted by `value`.
+fn key_values(
+    matches: &ArgMatches,
+    value: impl Fn(&str) -> Value,
+) -> Result<Map<String, Value>, String> {
+    let mut map = Map::new();
+    for pair in matches.get_many::<String>("arg").into_iter().flatten() {
+        let (key, raw) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("expected KEY=VALUE, got `{pair}`"))?;
+        map.insert(key.to_string(), value(raw));
+    }
+    Ok(map)
+}
+
+/// Parse a tool argument value: JSON if it parses, a plain string otherwise.
+///
+/// # Example
+///
+/// ```
+/// use mcpkit_cli::command::parse_value;
+/// use serde_json::json;
+///
+/// assert_eq!(parse_value("1"), json!(1));
+/// assert_eq!(parse_value("[1, 2]"), json!([1, 2]));
+/// assert_eq!(parse_value("/tmp/x"), json!("/tmp/x"));
+/// assert_eq!(parse_value("\"true\""), json!("true"));
+/// ```
+#[must_use]
+pub fn parse_value(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// What the server returned for a [`Command`].
+#[derive(Debug, Clone)]
+pub enum Output {
+    /// Result of `tools list`.
+    Tools(Vec<Tool>),
+    /// Result of `tools call`.
+    ToolResult(CallToolResult),
+    /// Result of `resources list`.
+    Resources(Vec<Resource>),
+    /// Result of `resources templates`.
+    ResourceTemplates(Vec<ResourceTemplate>),
+    /// Result of `resources read`.
+    Contents(Vec<ResourceContents>),
+    /// Result of `prompts list`.
+    Prompts(Vec<Prompt>),
+    /// Result of `prompts get`.
+    Prompt(GetPromptResult),
+    /// Result of `ping`.
+    Pong,
+    /// Result of `info`.
+    Info(Value),
+}
+
+/// Send `command` to the server behind `client`.
+///
+/// # Errors
+///
+/// Returns the client's error if the server lacks the capability or the
+/// request fails. A tool that runs but reports an error is not an `Err`; see
+/// [`Output::is_error`].
+pub async fn execute<T: Transport + 'static>(
+    client: &Client<T>,
+    command: &Command,
+) -> Result<Output, McpError> {
+    Ok(match command {
+        Command::ListTools => Output::Tools(client.list_tools().await?),
+        Command::CallTool { name, arguments } => Output::ToolResult(
+            client
+                .call_tool(name, Value::Object(arguments.clone()))
+                .await?,
+        ),
+        Command::ListResources => Output::Resources(client.list_resources().await?),
+        Command::ListResourceTemplates => {
+            Output::ResourceTemplates(client.list_resource_templates().await?)
+        }
+        Command::ReadResource { uri } => Output::Contents(client.read_resource(uri).await?),
+        Command::ListPrompts => Output::Prompts(client.list_prompts().await?),
+        Command::GetPrompt { name, arguments } => {
+            let arguments = (!arguments.is_empty()).then(|| arguments.clone());
+            Output::Prompt(client.get_prompt(name, arguments).await?)
+        }
+        Command::Ping => {
+            client.ping().await?;
+            Output::Pong
+        }
+        Command::Info => Output::Info(serde_json::json!({
+            "serverInfo": client.server_info(),
+            "protocolVersion": client.protocol_version().to_string(),
+            "capabilities": client.server_capabilities(),
+            "instructions": client.instructions(),
+        })),
+    })
+}
+
+impl Output {
+    /// Whether this is a tool result flagged `isError`.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::ToolResult(result) if result.is_error())
+    }
+
+    /// Format for the terminal.
+    ///
+    /// With `json`, the raw result is printed as pretty JSON. Otherwise lists
+    /// are printed one item per line with their descriptions, text content
+    /// is printed as-is, and everything else falls back to JSON.
+    #[must_use]
+    pub fn render(&self, json: bool) -> String {
+        if json {
+            return self.to_json();
+        }
+        match self {
+            Self::Tools(tools) => list(tools.iter().map(|t| (&t.name, t.description.as_deref()))),
+            Self::Resources(resources) => list(
+                resources
+                    .iter()
+                    .map(|r| (&r.uri, r.description.as_deref().or(Some(&r.name)))),
+            ),
+            Self::ResourceTemplates(templates) => list(
+                templates
+                    .iter()
+                    .map(|t| (&t.uri_template, t.description.as_deref())),
+            ),
+            Self::Prompts(prompts) => {
+                list(prompts.iter().map(|p| (&p.name, p.description.as_deref())))
+            }
+            Self::ToolResult(result) => {
+                let mut out = if result.is_error() {
+                    "error: ".to_string()
+                } else {
+                    String::new()
+                };
+                let blocks: Vec<String> = result.content.iter().map(content).collect();
+                out.push_str(&blocks.join("\n"));
+                if let Some(structured) = &result.structured_content {
+                    let _ = write!(out, "\n{}", pretty(structured));
+                }
+                out
+            }
+            Self::Contents(contents) => contents
+                .iter()
+                .map(|c| c.text.clone().unwrap_or_else(|| pretty(c)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Self::Pong => "pong".to_string(),
+            Self::Prompt(_) | Self::Info(_) => self.to_json(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            Self::Tools(v) => pretty(v),
+            Self::ToolResult(v) => pretty(v),
+            Self::Resources(v) => pretty(v),
+            Self::ResourceTemplates(v) => pretty(v),
+            Self::Contents(v) => pretty(v),
+            Self::Prompts(v) => pretty(v),
+            Self::Prompt(v) => pretty(v),
+            Self::Pong => "{}".to_string(),
+            Self::Info(v) => pretty(v),
+        }
+    }
+}
+
+/// One `name  description` line per item, names padded to a common width.
+fn list<'a>(items: impl Iterator<Item = (&'a String, Option<&'a str>)>) -> String {
+    let items: Vec<_> = items.collect();
+    let width = items.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for (name, description) in items {
+        let line = match description {
+            Some(description) => format!("{name:width$}  {description}"),
+            None => name.clone(),
+        };
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// A content block: text as-is, anything else as JSON.
+fn content(block: &Content) -> String {
+    match block.as_text() {
+        Some(text) => text.to_string(),
+        None => pretty(block),
+    }
+}
+
+fn pretty(value: &impl serde::Serialize) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|e| format!("<unserializable: {e}>"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(words: &[&str]) -> Result<Option<Command>, String> {
+        let matches = clap::Command::new("mcpkit")
+            .subcommands(subcommands())
+            .try_get_matches_from(std::iter::once("mcpkit").chain(words.iter().copied()))
+            .map_err(|e| e.to_string())?;
+        Command::from_matches(&matches)
+    }
+
+    #[test]
+    fn tool_call_arguments_merge_args_and_arg() {
+        let command = parse(&[
+            "tools",
+            "call",
+            "add",
+            "--args",
+            r#"{"a": 0, "c": 3}"#,
+            "--arg",
+            "a=1",
+            "-a",
+            "b=two",
+        ])
+        .unwrap();
+        assert_eq!(
+            command,
+            Some(Command::CallTool {
+                name: "add".to_string(),
+                arguments: json!({ "a": 1, "b": "two", "c": 3 })
+                    .as_object()
+                    .cloned()
+                    .unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn prompt_arguments_stay_strings() {
+        let Some(Command::GetPrompt { arguments, .. }) =
+            parse(&["prompts", "get", "greet", "--arg", "count=3"]).unwrap()
+        else {
+            panic!("expected prompts get");
+        };
+        assert_eq!(arguments["count"], json!("3"));
+    }
+
+    #[test]
+    fn malformed_arguments_are_rejected() {
+        assert!(parse(&["tools", "call", "add", "--arg", "a"]).is_err());
+        assert!(parse(&["tools", "call", "add", "--args", "[1]"]).is_err());
+        assert!(parse(&["tools", "frobnicate"]).is_err());
+    }
+
+    #[test]
+    fn lists_render_one_item_per_line() {
+        let output = Output::Tools(vec![
+            Tool::new("add").description("Add two numbers"),
+            Tool::new("subtract"),
+        ]);
+        assert_eq!(output.render(false), "add       Add two numbers\nsubtract");
+        assert!(output.render(true).starts_with('['));
+    }
+}
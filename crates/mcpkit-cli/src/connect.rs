@@ -0,0 +1,191 @@
+//! Connecting to the server under inspection.
+//!
+//! A server is named by a spec string, as passed to `--server`:
+//!
+//! - `http://...` or `https://...` connects with the Streamable HTTP transport
+//! - `ws://...` or `wss://...` connects over WebSocket
+//! - anything else is a command line, split like a POSIX shell would, that is
+//!   spawned and spoken to over stdio
+
+use mcpkit_client::discovery::ServerTransport;
+use mcpkit_client::{Client, ClientBuilder};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::Message;
+use mcpkit_transport::{
+    HttpTransport, HttpTransportConfig, SpawnedTransport, Transport, TransportError,
+    TransportMetadata, WebSocketConfig, WebSocketTransport,
+};
+
+/// Parse a `--server` spec into a transport configuration.
+///
+/// # Errors
+///
+/// Returns a message if the spec is empty or a command line has unbalanced
+/// quotes.
+///
+/// # Example
+///
+/// ```
+/// use mcpkit_cli::parse_server;
+/// use mcpkit_client::discovery::ServerTransport;
+///
+/// let server = parse_server("./my-server --verbose").unwrap();
+/// assert!(matches!(server, ServerTransport::Stdio { ref command, .. } if command == "./my-server"));
+///
+/// let server = parse_server("ws://localhost:3000/mcp").unwrap();
+/// assert!(matches!(server, ServerTransport::WebSocket { .. }));
+/// ```
+pub fn parse_server(spec: &str) -> Result<ServerTransport, String> {
+    let spec = spec.trim();
+    if spec.starts_with("http://") || spec.starts_with("https://") {
+        return Ok(ServerTransport::Http {
+            url: spec.to_string(),
+        });
+    }
+    if spec.starts_with("ws://") || spec.starts_with("wss://") {
+        return Ok(ServerTransport::WebSocket {
+            url: spec.to_string(),
+        });
+    }
+    let words = shlex::split(spec).ok_or_else(|| format!("unbalanced quotes in `{spec}`"))?;
+    stdio_server(words)
+}
+
+/// Build a stdio transport configuration from a command and its arguments.
+///
+/// # Errors
+///
+/// Returns a message if `words` is empty.
+pub fn stdio_server(words: impl IntoIterator<Item = String>) -> Result<ServerTransport, String> {
+    let mut words = words.into_iter();
+    let command = words
+        .next()
+        .ok_or_else(|| "no server command given".to_string())?;
+    Ok(ServerTransport::Stdio {
+        command,
+        args: words.collect(),
+    })
+}
+
+/// Connect to `server` and perform the MCP handshake.
+///
+/// # Errors
+///
+/// Returns an error if the process cannot be spawned, the connection fails,
+/// or the server rejects `initialize`.
+pub async fn connect(server: &ServerTransport) -> Result<Client<CliTransport>, McpError> {
+    ClientBuilder::new()
+        .name("mcpkit-cli")
+        .version(env!("CARGO_PKG_VERSION"))
+        .build(open(server).await?)
+        .await
+}
+
+/// Open a transport to `server` without performing the handshake.
+///
+/// # Errors
+///
+/// Returns an error if the process cannot be spawned or the connection fails.
+pub async fn open(server: &ServerTransport) -> Result<CliTransport, McpError> {
+    Ok(match server {
+        ServerTransport::Stdio { command, args } => {
+            CliTransport::Stdio(Box::new(SpawnedTransport::spawn(command, args).await?))
+        }
+        ServerTransport::Http { url } => CliTransport::Http(Box::new(
+            HttpTransport::connect(HttpTransportConfig::new(url)).await?,
+        )),
+        ServerTransport::WebSocket { url } => CliTransport::WebSocket(Box::new(
+            WebSocketTransport::connect(WebSocketConfig::new(url)).await?,
+        )),
+    })
+}
+
+/// One of the transports the inspector can connect with.
+///
+/// Boxed so the enum stays small whichever transport is in use.
+pub enum CliTransport {
+    /// A spawned server process, spoken to over stdio.
+    Stdio(Box<SpawnedTransport>),
+    /// A Streamable HTTP endpoint.
+    Http(Box<HttpTransport>),
+    /// A WebSocket endpoint.
+    WebSocket(Box<WebSocketTransport>),
+}
+
+impl Transport for CliTransport {
+    type Error = TransportError;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        match self {
+            Self::Stdio(t) => t.send(msg).await,
+            Self::Http(t) => t.send(msg).await,
+            Self::WebSocket(t) => t.send(msg).await,
+        }
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        match self {
+            Self::Stdio(t) => t.recv().await,
+            Self::Http(t) => t.recv().await,
+            Self::WebSocket(t) => t.recv().await,
+        }
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        match self {
+            Self::Stdio(t) => t.close().await,
+            Self::Http(t) => t.close().await,
+            Self::WebSocket(t) => t.close().await,
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        match self {
+            Self::Stdio(t) => t.is_connected(),
+            Self::Http(t) => t.is_connected(),
+            Self::WebSocket(t) => t.is_connected(),
+        }
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        match self {
+            Self::Stdio(t) => t.metadata(),
+            Self::Http(t) => t.metadata(),
+            Self::WebSocket(t) => t.metadata(),
+        }
+    }
+
+    fn connection_epoch(&self) -> u64 {
+        match self {
+            Self::Stdio(t) => t.connection_epoch(),
+            Self::Http(t) => t.connection_epoch(),
+            Self::WebSocket(t) => t.connection_epoch(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_server_picks_transport_from_scheme() {
+        assert!(matches!(
+            parse_server("https://example.com/mcp"),
+            Ok(ServerTransport::Http { url }) if url == "https://example.com/mcp"
+        ));
+        assert!(matches!(
+            parse_server("wss://example.com/mcp"),
+            Ok(ServerTransport::WebSocket { .. })
+        ));
+        match parse_server(r#"node "my server.js" --port 3"#) {
+            Ok(ServerTransport::Stdio { command, args }) => {
+                assert_eq!(command, "node");
+                assert_eq!(args, ["my server.js", "--port", "3"]);
+            }
+            other => panic!("expected a stdio server, got {other:?}"),
+        }
+        assert!(parse_server("  ").is_err());
+        assert!(parse_server("node 'unterminated").is_err());
+    }
+}
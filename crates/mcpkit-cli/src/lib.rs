@@ -0,0 +1,35 @@
+//! Command-line inspector for MCP servers.
+//!
+//! This crate provides the `mcpkit` binary, a debugging tool built on
+//! `mcpkit-client`. It connects to a server over stdio, HTTP, or WebSocket
+//! and runs one command, or opens an interactive session:
+//!
+//! ```text
+//! $ mcpkit connect stdio -- ./my-server
+//! mcp> tools list
+//! mcp> tools call add --arg a=1 --arg b=2
+//! mcp> resources read file:///README.md
+//!
+//! $ mcpkit --server ./my-server tools list
+//! $ mcpkit --server http://localhost:3000/mcp tools call add --arg a=1 --arg b=2
+//! $ mcpkit --server ./my-server conformance
+//! ```
+//!
+//! The library half exposes the pieces the binary is made of, so they can be
+//! reused or tested without spawning a process:
+//!
+//! - [`command`]: the command grammar shared by one-shot invocations and the
+//!   interactive session, and [`execute`](command::execute) to run a
+//!   [`Command`] against a [`Client`](mcpkit_client::Client)
+//! - [`connect`]: turning a server spec into a transport or a connected client
+//! - [`repl`]: the interactive session loop
+
+#![deny(missing_docs)]
+
+pub mod command;
+pub mod connect;
+pub mod repl;
+
+pub use command::{Command, Output};
+pub use connect::{CliTransport, connect, parse_server};
+pub use repl::Repl;
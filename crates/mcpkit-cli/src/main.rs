@@ -0,0 +1,208 @@
+//! `mcpkit`: inspect and debug MCP servers from the command line.
+
+use std::process::ExitCode;
+
+use clap::{Arg, ArgAction, ArgMatches};
+use mcpkit_cli::Repl;
+use mcpkit_cli::command::{self, Command};
+use mcpkit_cli::connect::{connect, open, parse_server, stdio_server};
+use mcpkit_client::discovery::ServerTransport;
+use mcpkit_testing::conformance::{CHECKS, ConformanceSuite};
+use mcpkit_transport::Transport;
+use tokio::io::BufReader;
+use tracing_subscriber::EnvFilter;
+
+fn cli() -> clap::Command {
+    clap::Command::new("mcpkit")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Inspect and debug MCP servers")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("server")
+                .long("server")
+                .short('s')
+                .value_name("SPEC")
+                .env("MCPKIT_SERVER")
+                .global(true)
+                .help("Server to use: an http(s):// or ws(s):// URL, or a command to spawn"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .action(ArgAction::SetTrue)
+                .global(true)
+                .help("Print raw JSON results"),
+        )
+        .subcommand(
+            clap::Command::new("connect")
+                .about("Open an interactive session")
+                .after_help(
+                    "Examples:\n  mcpkit connect stdio -- ./my-server --verbose\n  mcpkit connect http http://localhost:3000/mcp\n  mcpkit --server ./my-server connect",
+                )
+                .arg(
+                    Arg::new("transport")
+                        .value_parser(["stdio", "http", "ws"])
+                        .help("How to reach the server; defaults to --server"),
+                )
+                .arg(
+                    Arg::new("target")
+                        .num_args(1..)
+                        .trailing_var_arg(true)
+                        .allow_hyphen_values(true)
+                        .help("The command to spawn, or the URL to connect to"),
+                ),
+        )
+        .subcommand(
+            clap::Command::new("conformance")
+                .about("Check the server against the MCP spec")
+                .arg(
+                    Arg::new("protocol-version")
+                        .long("protocol-version")
+                        .value_name("VERSION")
+                        .help("Protocol version to request; defaults to the latest"),
+                )
+                .arg(
+                    Arg::new("skip")
+                        .long("skip")
+                        .value_name("CHECK")
+                        .action(ArgAction::Append)
+                        .value_parser(clap::builder::PossibleValuesParser::new(CHECKS))
+                        .help("Skip a check; may be repeated"),
+                ),
+        )
+        .subcommands(command::subcommands())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+
+    let matches = cli().get_matches();
+    match run(&matches).await {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(matches: &ArgMatches) -> Result<ExitCode, String> {
+    let json = matches.get_flag("json");
+
+    if let Some(("connect", connect_matches)) = matches.subcommand() {
+        let server = connect_target(matches, connect_matches)?;
+        let client = connect(&server).await.map_err(|e| e.to_string())?;
+        let info = client.server_info();
+        eprintln!(
+            "Connected to {} {}. Type `help` for commands, `exit` to quit.",
+            info.name, info.version
+        );
+        Repl::new(client)
+            .json(json)
+            .run(BufReader::new(tokio::io::stdin()), tokio::io::stdout())
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let spec = matches
+        .get_one::<String>("server")
+        .ok_or("no server given; pass --server or set MCPKIT_SERVER")?;
+
+    if let Some(("conformance", conformance)) = matches.subcommand() {
+        let mut suite = ConformanceSuite::new();
+        if let Some(version) = conformance.get_one::<String>("protocol-version") {
+            suite = suite.protocol_version(version);
+        }
+        for id in conformance.get_many::<String>("skip").into_iter().flatten() {
+            suite = suite.skip(id);
+        }
+        let transport = open(&parse_server(spec)?)
+            .await
+            .map_err(|e| e.to_string())?;
+        let report = suite.run(&transport).await;
+        let _ = transport.close().await;
+        if json {
+            let report = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+            println!("{report}");
+        } else {
+            println!("{report}");
+        }
+        return Ok(if report.is_conformant() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        });
+    }
+
+    let command = Command::from_matches(matches)?.ok_or("no command given")?;
+    let client = connect(&parse_server(spec)?)
+        .await
+        .map_err(|e| e.to_string())?;
+    let output = command::execute(&client, &command)
+        .await
+        .map_err(|e| e.to_string())?;
+    println!("{}", output.render(json));
+    let _ = client.close().await;
+    Ok(if output.is_error() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// The server named by `connect <transport> <target>...`, or by `--server`.
+fn connect_target(matches: &ArgMatches, connect: &ArgMatches) -> Result<ServerTransport, String> {
+    let target: Vec<String> = connect
+        .get_many::<String>("target")
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect();
+    let url = || match target.as_slice() {
+        [url] => Ok(url.clone()),
+        _ => Err("expected exactly one URL".to_string()),
+    };
+    match connect.get_one::<String>("transport").map(String::as_str) {
+        Some("stdio") => stdio_server(target),
+        Some("http") => Ok(ServerTransport::Http { url: url()? }),
+        Some("ws") => Ok(ServerTransport::WebSocket { url: url()? }),
+        _ => {
+            let spec = matches
+                .get_one::<String>("server")
+                .ok_or("no server given; use `connect stdio -- COMMAND` or --server")?;
+            parse_server(spec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_is_well_formed() {
+        cli().debug_assert();
+    }
+
+    #[test]
+    fn connect_stdio_takes_the_command_after_dashes() {
+        let matches = cli()
+            .try_get_matches_from(["mcpkit", "connect", "stdio", "--", "./srv", "--port", "3"])
+            .unwrap();
+        let Some(("connect", connect)) = matches.subcommand() else {
+            panic!("expected connect");
+        };
+        match connect_target(&matches, connect).unwrap() {
+            ServerTransport::Stdio { command, args } => {
+                assert_eq!(command, "./srv");
+                assert_eq!(args, ["--port", "3"]);
+            }
+            other => panic!("expected stdio, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Interactive inspector session.
+//!
+//! Each line is split like a shell command and parsed with the grammar in
+//! [`command`](crate::command). `help` lists the commands, `exit` or `quit`
+//! (or end of input) ends the session. Errors are printed and the session
+//! carries on.
+
+use mcpkit_client::Client;
+use mcpkit_transport::Transport;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::command::{self, Command};
+
+/// Prompt printed before each line is read.
+pub const PROMPT: &str = "mcp> ";
+
+/// An interactive session with one connected server.
+pub struct Repl<T: Transport + 'static> {
+    client: Client<T>,
+    json: bool,
+}
+
+impl<T: Transport + 'static> Repl<T> {
+    /// Create a session over a connected client.
+    #[must_use]
+    pub const fn new(client: Client<T>) -> Self {
+        Self {
+            client,
+            json: false,
+        }
+    }
+
+    /// Print raw JSON results instead of the human-readable rendering.
+    #[must_use]
+    pub const fn json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// The client the session sends commands through.
+    #[must_use]
+    pub const fn client(&self) -> &Client<T> {
+        &self.client
+    }
+
+    /// Read commands from `input` until `exit` or end of input, writing
+    /// prompts and results to `output`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if reading `input` or writing `output` fails.
+    pub async fn run<R, W>(&self, input: R, mut output: W) -> std::io::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = input.lines();
+        loop {
+            output.write_all(PROMPT.as_bytes()).await?;
+            output.flush().await?;
+            let Some(line) = lines.next_line().await? else {
+                output.write_all(b"\n").await?;
+                break;
+            };
+            let reply = match parse_line(&line) {
+                Ok(Line::Empty) => continue,
+                Ok(Line::Exit) => break,
+                Ok(Line::Command(command)) => {
+                    match command::execute(&self.client, &command).await {
+                        Ok(result) => result.render(self.json),
+                        Err(e) => format!("error: {e}"),
+                    }
+                }
+                Err(message) => message,
+            };
+            output.write_all(reply.trim_end().as_bytes()).await?;
+            output.write_all(b"\n").await?;
+        }
+        output.flush().await
+    }
+}
+
+/// One parsed line of input.
+#[derive(Debug, PartialEq)]
+enum Line {
+    Empty,
+    Exit,
+    Command(Command),
+}
+
+/// The grammar of the prompt: the inspector commands plus `exit`.
+fn grammar() -> clap::Command {
+    clap::Command::new("mcp")
+        .no_binary_name(true)
+        .subcommand_required(true)
+        .disable_version_flag(true)
+        .subcommands(command::subcommands())
+        .subcommand(
+            clap::Command::new("exit")
+                .visible_alias("quit")
+                .about("End the session"),
+        )
+}
+
+/// Parse a line; `Err` holds the message to print (including `help` output).
+fn parse_line(line: &str) -> Result<Line, String> {
+    let words = shlex::split(line).ok_or_else(|| "error: unbalanced quotes".to_string())?;
+    if words.is_empty() {
+        return Ok(Line::Empty);
+    }
+    let matches = grammar()
+        .try_get_matches_from(words)
+        .map_err(|e| e.render().to_string())?;
+    if matches.subcommand_name() == Some("exit") {
+        return Ok(Line::Exit);
+    }
+    match Command::from_matches(&matches) {
+        Ok(Some(command)) => Ok(Line::Command(command)),
+        Ok(None) => Ok(Line::Empty),
+        Err(message) => Err(format!("error: {message}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quoted_words_and_exit() {
+        assert_eq!(parse_line("   "), Ok(Line::Empty));
+        assert_eq!(parse_line("quit"), Ok(Line::Exit));
+        assert_eq!(
+            parse_line(r#"resources read "file:///my notes.txt""#),
+            Ok(Line::Command(Command::ReadResource {
+                uri: "file:///my notes.txt".to_string(),
+            }))
+        );
+        let help = parse_line("help").unwrap_err();
+        assert!(help.contains("tools"), "{help}");
+        assert!(parse_line("tools call 'add").is_err());
+    }
+}
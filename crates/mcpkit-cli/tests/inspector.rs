@@ -0,0 +1,113 @@
+//! Inspector commands and sessions against a mock server over an in-memory
+//! transport.
+
+use std::sync::Arc;
+
+use mcpkit_cli::Repl;
+use mcpkit_cli::command::{self, Command, Output};
+use mcpkit_client::{Client, ClientBuilder};
+use mcpkit_core::types::ToolOutput;
+use mcpkit_server::ServerBuilder;
+use mcpkit_testing::mock::{MockPrompt, MockResource};
+use mcpkit_testing::{MockServer, MockTool};
+use mcpkit_transport::MemoryTransport;
+use serde_json::json;
+
+async fn client() -> Client<MemoryTransport> {
+    let server = MockServer::builder()
+        .name("mock")
+        .tool(
+            MockTool::new("add")
+                .description("Add two numbers")
+                .handler(|args| {
+                    let sum = args["a"].as_f64().unwrap_or(0.0) + args["b"].as_f64().unwrap_or(0.0);
+                    Ok(ToolOutput::text(sum.to_string()))
+                }),
+        )
+        .tool(MockTool::new("fail").returns_error("boom"))
+        .resource(MockResource::new("file:///readme", "readme").content("hello"))
+        .prompt(MockPrompt::new("greet").template("Hi!"))
+        .build();
+    let (client_side, server_side) = MemoryTransport::pair();
+    let server = Arc::new(server);
+    let server = ServerBuilder::new(Arc::clone(&server))
+        .with_tools(Arc::clone(&server))
+        .with_resources(Arc::clone(&server))
+        .with_prompts(server)
+        .build();
+    tokio::spawn(server.serve(server_side));
+    ClientBuilder::new().build(client_side).await.unwrap()
+}
+
+#[tokio::test]
+async fn commands_render_server_results() {
+    let client = client().await;
+
+    let tools = command::execute(&client, &Command::ListTools)
+        .await
+        .unwrap();
+    // The mock server lists tools in no particular order.
+    let mut lines: Vec<_> = tools.render(false).lines().map(str::to_string).collect();
+    lines.sort();
+    assert_eq!(lines, ["add   Add two numbers", "fail"]);
+
+    let sum = command::execute(
+        &client,
+        &Command::CallTool {
+            name: "add".to_string(),
+            arguments: json!({ "a": 1, "b": 2 }).as_object().cloned().unwrap(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(sum.render(false), "3");
+    assert!(!sum.is_error());
+
+    let failed = command::execute(
+        &client,
+        &Command::CallTool {
+            name: "fail".to_string(),
+            arguments: serde_json::Map::new(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(failed.is_error());
+    assert_eq!(failed.render(false), "error: boom");
+
+    let contents = command::execute(
+        &client,
+        &Command::ReadResource {
+            uri: "file:///readme".to_string(),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(contents.render(false), "hello");
+
+    let info = command::execute(&client, &Command::Info).await.unwrap();
+    let Output::Info(info) = info else {
+        panic!("expected info");
+    };
+    assert_eq!(info["serverInfo"]["name"], "mock");
+}
+
+#[tokio::test]
+async fn session_runs_commands_until_exit() {
+    let repl = Repl::new(client().await);
+    let input = b"tools call add --arg a=2 --arg b=5\n\nprompts list\nbogus\nexit\nping\n";
+    let mut output = Vec::new();
+    repl.run(&input[..], &mut output).await.unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("mcp> 7\n"), "{output}");
+    assert!(output.contains("greet"), "{output}");
+    assert!(
+        output.contains("unrecognized subcommand 'bogus'"),
+        "{output}"
+    );
+    assert!(
+        !output.contains("pong"),
+        "session should end at exit: {output}"
+    );
+}
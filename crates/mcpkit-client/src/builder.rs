@@ -3,17 +3,29 @@
 //! The [`ClientBuilder`] provides a fluent API for constructing MCP clients
 //! with customizable options.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use mcpkit_core::capability::{ClientCapabilities, ClientInfo};
 use mcpkit_core::error::McpError;
+use mcpkit_core::types::Tool;
 use mcpkit_transport::Transport;
+use mcpkit_transport::ping::PingConfig;
 
 use crate::client::{Client, initialize};
+use crate::dispatch::DEFAULT_MAX_PENDING_REQUESTS;
+use crate::local_tools::LocalTools;
+use crate::reconnect::ReconnectPolicy;
+use crate::roots::RootsManager;
+use crate::tool_policy::{Decision, ToolConfirmation, ToolGate};
 
 /// Default per-request timeout applied when none is configured.
 pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
 
+/// Default size of the chunks requested by
+/// [`Client::read_resource_streaming`](crate::Client::read_resource_streaming).
+pub const DEFAULT_RESOURCE_CHUNK_SIZE: u64 = 1024 * 1024;
+
 /// Builder for constructing MCP clients.
 ///
 /// Use this builder to configure and create an MCP client connection.
@@ -41,6 +53,15 @@ pub struct ClientBuilder {
     version: String,
     capabilities: ClientCapabilities,
     request_timeout: Duration,
+    method_timeouts: HashMap<String, Duration>,
+    reconnect: ReconnectPolicy,
+    local_tools: LocalTools,
+    roots: Option<RootsManager>,
+    cache_lists: bool,
+    ping: Option<PingConfig>,
+    max_pending_requests: usize,
+    resource_chunk_size: u64,
+    tool_gate: ToolGate,
 }
 
 impl Default for ClientBuilder {
@@ -58,6 +79,15 @@ impl ClientBuilder {
             version: env!("CARGO_PKG_VERSION").to_string(),
             capabilities: ClientCapabilities::default(),
             request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            method_timeouts: HashMap::new(),
+            reconnect: ReconnectPolicy::none(),
+            local_tools: LocalTools::new(),
+            roots: None,
+            cache_lists: false,
+            ping: None,
+            max_pending_requests: DEFAULT_MAX_PENDING_REQUESTS,
+            resource_chunk_size: DEFAULT_RESOURCE_CHUNK_SIZE,
+            tool_gate: ToolGate::default(),
         }
     }
 
@@ -139,6 +169,10 @@ impl ClientBuilder {
     /// [`DEFAULT_REQUEST_TIMEOUT`] (60 seconds). For long-running work, prefer the
     /// Tasks API, or raise this value for clients that issue legitimately slow calls.
     ///
+    /// The remaining time is sent with each request in
+    /// `_meta["mcpkit/timeoutMs"]`, so the server can see it as
+    /// `Context::deadline`.
+    ///
     /// [`TransportErrorKind::Timeout`]: mcpkit_core::error::TransportErrorKind::Timeout
     #[must_use]
     pub const fn request_timeout(mut self, timeout: Duration) -> Self {
@@ -146,6 +180,180 @@ impl ClientBuilder {
         self
     }
 
+    /// Set the timeout of requests for one method, overriding
+    /// [`request_timeout`](Self::request_timeout).
+    ///
+    /// ```rust
+    /// use mcpkit_client::ClientBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let builder = ClientBuilder::new()
+    ///     .request_timeout(Duration::from_secs(10))
+    ///     .method_timeout("tools/call", Duration::from_secs(120));
+    /// ```
+    ///
+    /// A [`CallOptions`](crate::CallOptions) timeout passed to a single call
+    /// takes precedence over both.
+    #[must_use]
+    pub fn method_timeout(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.method_timeouts.insert(method.into(), timeout);
+        self
+    }
+
+    /// Resend requests lost to a transport reconnect, up to `retries` times.
+    ///
+    /// When the transport reconnects (see
+    /// [`Transport::connection_epoch`](mcpkit_transport::Transport::connection_epoch)),
+    /// the client re-runs `initialize` and replays resource subscriptions on its
+    /// own. Requests that were in flight fail with a
+    /// [`TransportErrorKind::Reconnected`] error, which
+    /// [`McpError::is_recoverable`] reports as recoverable. By default (`0`)
+    /// that error is returned to the caller; with retries the request is
+    /// resent instead. The server may have acted on a `tools/call` before the
+    /// connection dropped, so with retries enabled each `tools/call` carries a
+    /// generated idempotency key (`_meta["mcpkit/idempotencyKey"]`); mcpkit
+    /// servers answer a resend with the original result (see
+    /// `mcpkit_server::idempotency`). Only enable this against other servers if
+    /// resending is safe.
+    ///
+    /// [`TransportErrorKind::Reconnected`]: mcpkit_core::error::TransportErrorKind::Reconnected
+    #[must_use]
+    pub const fn retry_on_reconnect(mut self, retries: u32) -> Self {
+        self.reconnect = ReconnectPolicy::none().with_max_replays(retries);
+        self
+    }
+
+    /// Resume the session after a transport reconnect according to `policy`.
+    ///
+    /// The handshake is retried with backoff, idempotent requests that were
+    /// in flight are resent, and other lost requests fail with
+    /// [`TransportErrorKind::Interrupted`]. See [`crate::reconnect`].
+    ///
+    /// [`TransportErrorKind::Interrupted`]: mcpkit_core::error::TransportErrorKind::Interrupted
+    #[must_use]
+    pub const fn reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = policy;
+        self
+    }
+
+    /// Register client-side tools.
+    ///
+    /// Local tools are listed and called through the same
+    /// [`Client::list_tools`] / [`Client::call_tool`] API as server tools, and
+    /// work even when the server lacks the tools capability. The registry's
+    /// [`ToolResolution`](crate::ToolResolution) decides what happens when a
+    /// local tool shares a name with a server tool.
+    #[must_use]
+    pub fn local_tools(mut self, tools: LocalTools) -> Self {
+        self.local_tools = tools;
+        self
+    }
+
+    /// Serve roots from a [`RootsManager`] and notify servers when they change.
+    ///
+    /// Enables the roots capability with change notifications. The client
+    /// answers `roots/list` from the manager instead of
+    /// [`ClientHandler::list_roots`](crate::ClientHandler::list_roots), and
+    /// each change made through a clone of the manager sends
+    /// `notifications/roots/list_changed` to the server.
+    #[must_use]
+    pub fn roots_manager(mut self, roots: RootsManager) -> Self {
+        self.capabilities = self.capabilities.with_roots_and_changes();
+        self.roots = Some(roots);
+        self
+    }
+
+    /// Memoize tool, resource, and prompt lists.
+    ///
+    /// [`Client::list_tools`], [`Client::list_resources`], and
+    /// [`Client::list_prompts`] then fetch each list once and serve it from
+    /// memory until the server sends the matching
+    /// `notifications/*/list_changed` or the session is resumed after a
+    /// reconnect. [`Client::refresh_tools`] and its siblings force a refetch;
+    /// [`Client::list_cache_stats`] reports hits and misses.
+    #[must_use]
+    pub const fn cache_lists(mut self) -> Self {
+        self.cache_lists = true;
+        self
+    }
+
+    /// Ping the server periodically and close the connection once it stops
+    /// answering.
+    ///
+    /// After `config.max_missed` consecutive pings go unanswered, the
+    /// config's [`on_unhealthy`](PingConfig::on_unhealthy) callbacks run and
+    /// the transport is closed. [`Client::connection_healthy`] and
+    /// [`Client::ping_health`] report the supervisor's view in the meantime.
+    #[must_use]
+    pub fn ping_supervision(mut self, config: PingConfig) -> Self {
+        self.ping = Some(config);
+        self
+    }
+
+    /// Cap the number of requests awaiting a response.
+    ///
+    /// Once `max` requests are in flight, further calls fail immediately with
+    /// a [`ResourceExhausted`](mcpkit_core::error::TransportErrorKind::ResourceExhausted)
+    /// transport error instead of queueing. Defaults to
+    /// [`DEFAULT_MAX_PENDING_REQUESTS`].
+    #[must_use]
+    pub const fn max_pending_requests(mut self, max: usize) -> Self {
+        self.max_pending_requests = max;
+        self
+    }
+
+    /// Set the size of the chunks
+    /// [`Client::read_resource_streaming`](crate::Client::read_resource_streaming)
+    /// requests, which bounds the memory a streamed read holds at a time.
+    ///
+    /// Servers cap chunks at
+    /// [`MAX_RESOURCE_CHUNK_SIZE`](mcpkit_core::types::MAX_RESOURCE_CHUNK_SIZE)
+    /// whatever is asked. Defaults to [`DEFAULT_RESOURCE_CHUNK_SIZE`]; zero
+    /// is treated as one byte.
+    #[must_use]
+    pub const fn resource_chunk_size(mut self, size: u64) -> Self {
+        self.resource_chunk_size = if size == 0 { 1 } else { size };
+        self
+    }
+
+    /// Check every call to a server tool against `policy` before sending it.
+    ///
+    /// The policy sees the tool as the server listed it, annotations
+    /// included; see [`tool_policy`](crate::tool_policy) and the ready-made
+    /// [`confirm_destructive`](crate::tool_policy::confirm_destructive).
+    #[must_use]
+    pub fn tool_policy<F>(mut self, policy: F) -> Self
+    where
+        F: Fn(&Tool) -> Decision + Send + Sync + 'static,
+    {
+        self.tool_gate.set_policy(policy);
+        self
+    }
+
+    /// Ask `confirmation` about calls the tool policy decides need
+    /// [`Decision::RequireConfirmation`]. Without one, such calls fail.
+    #[must_use]
+    pub fn tool_confirmation(mut self, confirmation: impl ToolConfirmation) -> Self {
+        self.tool_gate.set_confirmation(confirmation);
+        self
+    }
+
+    /// Connect the client to `transport` without initializing it yet.
+    ///
+    /// The returned [`ConnectedClient`] offers nothing but
+    /// [`initialize`](ConnectedClient::initialize), so no request can be sent
+    /// before the MCP handshake completes. Use this when the handshake should
+    /// happen later than the connection, or to attach a handler in between;
+    /// otherwise [`build`](Self::build) does both at once.
+    #[must_use]
+    pub fn connect<T: Transport + 'static>(self, transport: T) -> ConnectedClient<T> {
+        ConnectedClient {
+            transport,
+            handler: crate::handler::NoOpHandler,
+            builder: self,
+        }
+    }
+
     /// Build and connect the client using the given transport.
     ///
     /// This performs the MCP handshake and returns a connected client.
@@ -154,15 +362,7 @@ impl ClientBuilder {
     ///
     /// Returns an error if the handshake fails or the transport encounters an error.
     pub async fn build<T: Transport + 'static>(self, transport: T) -> Result<Client<T>, McpError> {
-        let client_info = ClientInfo::new(&self.name, &self.version);
-        let init_result = initialize(&transport, &client_info, &self.capabilities).await?;
-        Ok(Client::new(
-            transport,
-            init_result,
-            client_info,
-            self.capabilities,
-            self.request_timeout,
-        ))
+        self.connect(transport).initialize().await
     }
 
     /// Build and connect the client with a custom handler.
@@ -180,16 +380,114 @@ impl ClientBuilder {
         transport: T,
         handler: H,
     ) -> Result<Client<T, H>, McpError> {
-        let client_info = ClientInfo::new(&self.name, &self.version);
-        let init_result = initialize(&transport, &client_info, &self.capabilities).await?;
+        self.connect(transport).handler(handler).initialize().await
+    }
+}
+
+/// A client whose transport is connected but whose MCP handshake has not run.
+///
+/// This is the client-side counterpart of
+/// [`Connection<Connected>`](mcpkit_core::state::Connection): the only way
+/// forward is [`initialize`](Self::initialize), which performs the handshake
+/// and returns a ready [`Client`]. Requests exist only on [`Client`], so
+/// calling `list_tools` before initialization is a compile error rather than
+/// a confusing runtime failure. [`Client::close`] consumes the client, ending
+/// the lifecycle.
+///
+/// # Example
+///
+/// ```no_run
+/// use mcpkit_client::ClientBuilder;
+/// use mcpkit_transport::SpawnedTransport;
+///
+/// # async fn example() -> Result<(), mcpkit_core::error::McpError> {
+/// let transport = SpawnedTransport::spawn("my-server", &[] as &[&str]).await?;
+/// let connected = ClientBuilder::new().name("my-client").connect(transport);
+/// // connected.list_tools() does not exist yet.
+/// let client = connected.initialize().await?;
+/// let tools = client.list_tools().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ConnectedClient<T, H = crate::handler::NoOpHandler> {
+    transport: T,
+    handler: H,
+    builder: ClientBuilder,
+}
+
+impl<T: Transport + 'static, H: crate::handler::ClientHandler + 'static> ConnectedClient<T, H> {
+    /// Handle server-initiated requests (sampling, elicitation, roots) with
+    /// `handler` once initialized.
+    #[must_use]
+    pub fn handler<H2: crate::handler::ClientHandler + 'static>(
+        self,
+        handler: H2,
+    ) -> ConnectedClient<T, H2> {
+        ConnectedClient {
+            transport: self.transport,
+            handler,
+            builder: self.builder,
+        }
+    }
+
+    /// The client information `initialize` will send.
+    #[must_use]
+    pub fn client_info(&self) -> ClientInfo {
+        ClientInfo::new(&self.builder.name, &self.builder.version)
+    }
+
+    /// The capabilities `initialize` will declare.
+    #[must_use]
+    pub const fn capabilities(&self) -> &ClientCapabilities {
+        &self.builder.capabilities
+    }
+
+    /// The connected transport.
+    #[must_use]
+    pub const fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Perform the MCP handshake, returning the ready client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handshake fails or the transport encounters an error.
+    pub async fn initialize(self) -> Result<Client<T, H>, McpError> {
+        let Self {
+            transport,
+            handler,
+            builder,
+        } = self;
+        let client_info = ClientInfo::new(&builder.name, &builder.version);
+        let init_result = initialize(&transport, &client_info, &builder.capabilities).await?;
         Ok(Client::with_handler(
             transport,
             init_result,
             client_info,
-            self.capabilities,
+            builder.capabilities,
             handler,
-            self.request_timeout,
-        ))
+            builder.request_timeout,
+        )
+        .with_local_tools(builder.local_tools)
+        .with_method_timeouts(builder.method_timeouts)
+        .with_reconnect_policy(builder.reconnect)
+        .with_roots_manager(builder.roots)
+        .with_list_cache(builder.cache_lists)
+        .with_ping_supervision(builder.ping)
+        .with_max_pending_requests(builder.max_pending_requests)
+        .with_resource_chunk_size(builder.resource_chunk_size)
+        .with_tool_gate(builder.tool_gate))
+    }
+}
+
+impl<T, H> std::fmt::Debug for ConnectedClient<T, H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectedClient")
+            .field("name", &self.builder.name)
+            .field("version", &self.builder.version)
+            .field("capabilities", &self.builder.capabilities)
+            .finish_non_exhaustive()
     }
 }
 
@@ -218,4 +516,60 @@ mod tests {
         assert!(builder.capabilities.has_sampling());
         assert!(builder.capabilities.has_roots());
     }
+
+    #[test]
+    fn roots_manager_enables_roots_with_changes() {
+        let builder = ClientBuilder::new().roots_manager(RootsManager::new());
+        assert!(builder.roots.is_some());
+        assert!(builder.capabilities.has_roots());
+        assert_eq!(
+            builder
+                .capabilities
+                .roots
+                .as_ref()
+                .and_then(|roots| roots.list_changed),
+            Some(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_defers_the_handshake_to_initialize() {
+        use mcpkit_core::protocol::{Message, Response};
+        use mcpkit_core::state::InitializeResultBuilder;
+        use mcpkit_transport::MemoryTransport;
+
+        let (client_tr, server) = MemoryTransport::pair();
+        let connected = ClientBuilder::new()
+            .name("lazy")
+            .with_sampling()
+            .connect(client_tr);
+        assert_eq!(connected.client_info().name, "lazy");
+        assert!(connected.capabilities().has_sampling());
+
+        // Nothing is sent until `initialize`.
+        let idle = tokio::time::timeout(Duration::from_millis(50), server.recv()).await;
+        assert!(idle.is_err(), "connect must not start the handshake");
+
+        let serve = tokio::spawn(async move {
+            let Ok(Some(Message::Request(request))) = server.recv().await else {
+                panic!("expected initialize");
+            };
+            assert_eq!(request.method.as_ref(), "initialize");
+            let result = InitializeResultBuilder::new("srv", "1.0.0")
+                .with_tools()
+                .build();
+            server
+                .send(Message::Response(Response::success(
+                    request.id,
+                    serde_json::to_value(result).unwrap(),
+                )))
+                .await
+                .unwrap();
+            server
+        });
+        let client = connected.initialize().await.unwrap();
+        assert_eq!(client.server_info().name, "srv");
+        assert!(client.server_capabilities().has_tools());
+        drop(serve.await.unwrap());
+    }
 }
@@ -10,7 +10,10 @@
 //! - Connection lifecycle
 //! - Server-initiated request handling via [`ClientHandler`]
 
+use bytes::Bytes;
 use futures::channel::oneshot;
+use futures::future::Either;
+use futures::{Stream, TryStreamExt, stream};
 use mcpkit_core::capability::{
     ClientCapabilities, ClientInfo, InitializeRequest, InitializeResult, PROTOCOL_VERSION,
     SUPPORTED_PROTOCOL_VERSIONS, ServerCapabilities, ServerInfo, is_version_supported,
@@ -22,30 +25,187 @@ use mcpkit_core::error::{
 use mcpkit_core::protocol::{Message, Notification, Request, RequestId, Response};
 use mcpkit_core::protocol_version::ProtocolVersion;
 use mcpkit_core::types::{
-    CallToolRequest, CallToolResult, CancelTaskRequest, CancelTaskResult, CompleteRequest,
-    CompleteResult, CompletionArgument, CompletionRef, CreateMessageRequest, ElicitRequestParams,
-    GetPromptRequest, GetPromptResult, GetTaskRequest, GetTaskResult, ListPromptsResult,
-    ListResourceTemplatesResult, ListResourcesResult, ListTasksRequest, ListTasksResult,
-    ListToolsResult, Prompt, ReadResourceRequest, ReadResourceResult, Resource, ResourceContents,
-    ResourceTemplate, SubscribeRequest, Task, TaskStatus, Tool, UnsubscribeRequest,
+    ByteRange, CallToolRequest, CallToolResult, CancelTaskRequest, CancelTaskResult,
+    CompleteRequest, CompleteResult, CompletionArgument, CompletionRef, CreateMessageRequest,
+    ElicitRequestParams, GetPromptRequest, GetPromptResult, GetTaskRequest, GetTaskResult,
+    ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, ListTasksRequest,
+    ListTasksResult, ListToolsResult, Meta, Prompt, RANGE_META_KEY, ReadResourceRequest,
+    ReadResourceResult, Resource, ResourceContents, ResourceTemplate, SubscribeRequest, Task,
+    TaskId, TaskStatus, TaskStatusNotificationParams, Tool, UnsubscribeRequest,
 };
 use mcpkit_transport::Transport;
-use std::collections::HashMap;
-use std::sync::Arc;
+use mcpkit_transport::ping::{PingConfig, PingExit, PingHealth, PingSupervisor};
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use tracing::{debug, error, info, trace, warn};
+use web_time::Instant;
 
 // Runtime-agnostic sync primitives
 use async_lock::RwLock;
 
-// Use tokio channels when tokio-runtime is enabled, otherwise use the transport abstraction
-#[cfg(feature = "tokio-runtime")]
+// Tokio's channels are runtime-independent; tasks and timers go through the
+// transport runtime so the client also runs on the browser event loop.
+use mcpkit_transport::runtime;
 use tokio::sync::mpsc;
 
+use crate::builder::DEFAULT_RESOURCE_CHUNK_SIZE;
+use crate::dispatch::{DEFAULT_MAX_PENDING_REQUESTS, PendingRequests};
 use crate::handler::{ClientHandler, RequestContext};
+use crate::list_cache::{ListCache, ListCacheStats, Slot};
+use crate::local_tools::{self, LocalTools, ToolResolution};
+use crate::reconnect::ReconnectPolicy;
+use crate::roots::RootsManager;
+use crate::tool_policy::ToolGate;
+use crate::watch::{ResourceWatcher, ResourceWatchers};
 use mcpkit_core::tasks::{TaskManager, route_task_store};
 
+/// Unanswered-request count above which the router drops ids whose waiters
+/// have given up.
+const UNANSWERED_PRUNE_THRESHOLD: usize = 1024;
+
+/// What the router hands the event worker, in arrival order.
+enum ClientEvent {
+    /// A server notification.
+    Notification(Notification),
+    /// The session was resumed on a reconnected transport.
+    Reconnected,
+    /// The connection is gone.
+    Disconnected,
+}
+
+/// Polling interval for [`Client::await_task`] when the task suggests none.
+pub const DEFAULT_TASK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Client-side state shared between a [`Client`] and its message router.
+#[derive(Clone, Default)]
+struct Shared {
+    /// Roots served for `roots/list` in place of the handler.
+    roots: Arc<OnceLock<RootsManager>>,
+    /// Callers of [`Client::await_task`] waiting on a terminal status.
+    task_watchers: Arc<TaskWatchers>,
+    /// Memoized list results, invalidated by `list_changed` notifications.
+    list_cache: Arc<OnceLock<ListCache>>,
+    /// Open [`ResourceWatcher`]s, woken by `notifications/resources/updated`.
+    resource_watchers: Arc<ResourceWatchers>,
+    /// Tools whose output schemas [`Client::call_tool_typed`] validates
+    /// against, invalidated like the list cache.
+    tool_schemas: Arc<Slot<Tool>>,
+    /// How the session is resumed after a reconnect; unset means
+    /// [`ReconnectPolicy::none`].
+    reconnect: Arc<OnceLock<ReconnectPolicy>>,
+}
+
+impl Shared {
+    /// The policy applied when the transport reconnects.
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.reconnect
+            .get()
+            .copied()
+            .unwrap_or_else(ReconnectPolicy::none)
+    }
+}
+
+/// Waiters for tasks to reach a terminal status, woken by
+/// `notifications/tasks/status`.
+#[derive(Default)]
+struct TaskWatchers {
+    waiters: std::sync::Mutex<HashMap<TaskId, Vec<oneshot::Sender<Task>>>>,
+}
+
+impl TaskWatchers {
+    /// Register interest in `id` reaching a terminal status.
+    fn watch(&self, id: &TaskId) -> oneshot::Receiver<Task> {
+        let (tx, rx) = oneshot::channel();
+        let mut waiters = self
+            .waiters
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        // Drop waiters whose callers gave up.
+        waiters.retain(|_, senders| {
+            senders.retain(|tx| !tx.is_canceled());
+            !senders.is_empty()
+        });
+        waiters.entry(id.clone()).or_default().push(tx);
+        rx
+    }
+
+    /// Wake every waiter on `task` if it has reached a terminal status.
+    fn update(&self, task: &Task) {
+        if !task.status.is_terminal() {
+            return;
+        }
+        let senders = self
+            .waiters
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&task.task_id);
+        for tx in senders.into_iter().flatten() {
+            let _ = tx.send(task.clone());
+        }
+    }
+}
+
+/// What the message router needs to rebuild the session after the transport
+/// reconnects.
+struct Resumption {
+    client_info: ClientInfo,
+    client_caps: Arc<ClientCapabilities>,
+    subscriptions: Arc<RwLock<HashSet<String>>>,
+    session_epoch: Arc<AtomicU64>,
+    next_id: Arc<AtomicU64>,
+    request_timeout: Duration,
+}
+
+/// Per-call options for requests such as [`Client::call_tool_with`].
+///
+/// ```rust
+/// use mcpkit_client::CallOptions;
+/// use std::time::Duration;
+///
+/// let options = CallOptions::timeout(Duration::from_secs(5)).with_idempotency_key("order-42");
+/// assert_eq!(options.timeout, Some(Duration::from_secs(5)));
+/// assert_eq!(options.idempotency_key.as_deref(), Some("order-42"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallOptions {
+    /// How long to wait for the response. Overrides the builder's
+    /// per-method and default timeouts.
+    pub timeout: Option<Duration>,
+    /// Key sent in `_meta["mcpkit/idempotencyKey"]` so the server answers a
+    /// repeated call with the first call's result. Reuse it when retrying a
+    /// call yourself; calls resent after a reconnect get a generated key.
+    pub idempotency_key: Option<String>,
+}
+
+impl CallOptions {
+    /// Options that leave every setting at the client's configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            timeout: None,
+            idempotency_key: None,
+        }
+    }
+
+    /// Options with the given response timeout.
+    #[must_use]
+    pub const fn timeout(timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            idempotency_key: None,
+        }
+    }
+
+    /// Send `key` as the call's idempotency key.
+    #[must_use]
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+}
+
 /// An MCP client connected to a server.
 ///
 /// The client provides methods for interacting with MCP servers:
@@ -92,10 +252,10 @@ pub struct Client<T: Transport, H: ClientHandler = crate::handler::NoOpHandler>
     client_info: ClientInfo,
     /// Client capabilities.
     client_caps: ClientCapabilities,
-    /// Next request ID.
-    next_id: AtomicU64,
-    /// Pending requests awaiting responses.
-    pending: Arc<RwLock<HashMap<RequestId, oneshot::Sender<Response>>>>,
+    /// Next request ID (shared with the router, which replays subscriptions).
+    next_id: Arc<AtomicU64>,
+    /// Requests awaiting responses.
+    pending: Arc<PendingRequests>,
     /// Instructions from the server.
     instructions: Option<String>,
     /// Handler for server-initiated requests.
@@ -104,14 +264,31 @@ pub struct Client<T: Transport, H: ClientHandler = crate::handler::NoOpHandler>
     outgoing_tx: mpsc::Sender<Message>,
     /// Maximum time to wait for a response to a request before timing out.
     request_timeout: Duration,
+    /// Per-method overrides of `request_timeout`.
+    method_timeouts: HashMap<String, Duration>,
+    /// Client-side tools listed and called alongside the server's.
+    local_tools: LocalTools,
+    /// Resource URIs subscribed to, replayed after a reconnect.
+    subscriptions: Arc<RwLock<HashSet<String>>>,
+    /// Bumped by the router each time it resumes the session after a reconnect.
+    session_epoch: Arc<AtomicU64>,
+    /// State shared with the router: the roots manager, task waiters, and
+    /// list cache.
+    shared: Shared,
+    /// Health of the connection as seen by the ping supervisor, if enabled.
+    ping_health: Option<PingHealth>,
+    /// Size of the chunks requested by `read_resource_streaming`.
+    resource_chunk_size: u64,
+    /// Policy and confirmation that calls to server tools are checked
+    /// against.
+    tool_gate: ToolGate,
     /// Flag indicating if the client is running.
     running: Arc<AtomicBool>,
-    /// Handle to the background task.
-    _background_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl<T: Transport + 'static> Client<T, crate::handler::NoOpHandler> {
-    /// Create a new client without a handler (called by builder).
+    /// Create a new client without a handler.
+    #[cfg(test)]
     pub(crate) fn new(
         transport: T,
         init_result: InitializeResult,
@@ -141,9 +318,13 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         request_timeout: Duration,
     ) -> Self {
         let transport = Arc::new(transport);
-        let pending = Arc::new(RwLock::new(HashMap::new()));
+        let pending = Arc::new(PendingRequests::new(DEFAULT_MAX_PENDING_REQUESTS));
         let handler = Arc::new(handler);
         let running = Arc::new(AtomicBool::new(true));
+        let next_id = Arc::new(AtomicU64::new(1));
+        let subscriptions = Arc::new(RwLock::new(HashSet::new()));
+        let session_epoch = Arc::new(AtomicU64::new(0));
+        let shared = Shared::default();
 
         // Parse the negotiated protocol version
         let protocol_version =
@@ -169,19 +350,27 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
             .then(|| Arc::new(TaskManager::new()));
 
         // Start background message routing task
-        let background_handle = Self::spawn_message_router(
+        Self::spawn_message_router(
             Arc::clone(&transport),
             Arc::clone(&pending),
             Arc::clone(&handler),
             Arc::clone(&running),
             outgoing_rx,
-            Arc::new(client_caps.clone()),
+            Resumption {
+                client_info: client_info.clone(),
+                client_caps: Arc::new(client_caps.clone()),
+                subscriptions: Arc::clone(&subscriptions),
+                session_epoch: Arc::clone(&session_epoch),
+                next_id: Arc::clone(&next_id),
+                request_timeout,
+            },
             tasks,
+            shared.clone(),
         );
 
         // Notify handler that connection is established
         let handler_clone = Arc::clone(&handler);
-        tokio::spawn(async move {
+        runtime::spawn(async move {
             handler_clone.on_connected().await;
         });
 
@@ -192,35 +381,151 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
             protocol_version,
             client_info,
             client_caps,
-            next_id: AtomicU64::new(1),
+            next_id,
             pending,
             instructions: init_result.instructions,
             handler,
             outgoing_tx,
             request_timeout,
+            method_timeouts: HashMap::new(),
+            local_tools: LocalTools::new(),
+            subscriptions,
+            session_epoch,
+            shared,
+            ping_health: None,
+            resource_chunk_size: DEFAULT_RESOURCE_CHUNK_SIZE,
+            tool_gate: ToolGate::default(),
             running,
-            _background_handle: Some(background_handle),
         }
     }
 
+    /// Install client-side tools (called by builder).
+    pub(crate) fn with_local_tools(mut self, local_tools: LocalTools) -> Self {
+        self.local_tools = local_tools;
+        self
+    }
+
+    /// Override the request timeout of individual methods (called by builder).
+    pub(crate) fn with_method_timeouts(mut self, timeouts: HashMap<String, Duration>) -> Self {
+        self.method_timeouts = timeouts;
+        self
+    }
+
+    /// Cap the number of requests awaiting a response (called by builder).
+    pub(crate) fn with_max_pending_requests(self, max: usize) -> Self {
+        self.pending.set_max(max);
+        self
+    }
+
+    /// Set the chunk size of streamed resource reads (called by builder).
+    pub(crate) const fn with_resource_chunk_size(mut self, size: u64) -> Self {
+        self.resource_chunk_size = size;
+        self
+    }
+
+    /// Gate calls to server tools (called by builder).
+    pub(crate) fn with_tool_gate(mut self, tool_gate: ToolGate) -> Self {
+        self.tool_gate = tool_gate;
+        self
+    }
+
+    /// Set how the session is resumed after a reconnect (called by builder).
+    pub(crate) fn with_reconnect_policy(self, policy: ReconnectPolicy) -> Self {
+        // The slot is only filled here, once, right after construction.
+        let _ = self.shared.reconnect.set(policy);
+        self
+    }
+
+    /// The policy applied when the transport reconnects.
+    fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.shared.reconnect_policy()
+    }
+
+    /// Serve roots from a manager and attach it for change notifications (called by builder).
+    pub(crate) fn with_roots_manager(self, roots: Option<RootsManager>) -> Self {
+        if let Some(roots) = roots {
+            roots.attach(self.outgoing_tx.clone());
+            // The slot is only filled here, once, right after construction.
+            let _ = self.shared.roots.set(roots);
+        }
+        self
+    }
+
+    /// Memoize list results until the server reports a change (called by builder).
+    pub(crate) fn with_list_cache(self, enabled: bool) -> Self {
+        if enabled {
+            let _ = self.shared.list_cache.set(ListCache::default());
+        }
+        self
+    }
+
+    /// Ping the server in the background and close the connection once it
+    /// stops answering (called by builder).
+    pub(crate) fn with_ping_supervision(mut self, config: Option<PingConfig>) -> Self {
+        let Some(config) = config else {
+            return self;
+        };
+        let supervisor = PingSupervisor::new(config);
+        self.ping_health = Some(supervisor.health());
+
+        let transport = Arc::clone(&self.transport);
+        let pending = Arc::clone(&self.pending);
+        let next_id = Arc::clone(&self.next_id);
+        let running = Arc::clone(&self.running);
+        let outgoing_tx = self.outgoing_tx.clone();
+        runtime::spawn(async move {
+            let ping = || {
+                let id = RequestId::Number(next_id.fetch_add(1, Ordering::SeqCst));
+                send_ping(
+                    id,
+                    outgoing_tx.clone(),
+                    Arc::clone(&pending),
+                    Arc::clone(&running),
+                )
+            };
+            if supervisor.run(ping).await == PingExit::Unhealthy {
+                running.store(false, Ordering::SeqCst);
+                if let Err(e) = transport.close().await {
+                    debug!(error = %e, "Failed to close unhealthy connection");
+                }
+            }
+        });
+        self
+    }
+
     /// Spawn the background message routing task.
     ///
     /// This task:
     /// - Reads incoming messages from the transport
-    /// - Routes responses to pending request channels
-    /// - Delegates server-initiated requests to the handler
-    /// - Handles notifications
+    /// - Routes responses to pending request channels, in whatever order
+    ///   they arrive
+    /// - Queues server-initiated requests to the request worker
+    /// - Queues notifications, in order, to the event worker
+    /// - Resumes the session when the transport reports a reconnection
+    #[allow(clippy::too_many_arguments)]
     fn spawn_message_router(
         transport: Arc<T>,
-        pending: Arc<RwLock<HashMap<RequestId, oneshot::Sender<Response>>>>,
+        pending: Arc<PendingRequests>,
         handler: Arc<H>,
         running: Arc<AtomicBool>,
         mut outgoing_rx: mpsc::Receiver<Message>,
-        client_caps: Arc<ClientCapabilities>,
+        resumption: Resumption,
         tasks: Option<Arc<TaskManager>>,
-    ) -> tokio::task::JoinHandle<()> {
-        tokio::spawn(async move {
+        shared: Shared,
+    ) {
+        runtime::spawn(async move {
             debug!("Starting client message router");
+            let requests = Self::spawn_request_worker(
+                Arc::clone(&handler),
+                Arc::clone(&transport),
+                Arc::clone(&resumption.client_caps),
+                tasks,
+                Arc::clone(&shared.roots),
+            );
+            let events = Self::spawn_event_worker(handler);
+            let mut connection_epoch = transport.connection_epoch();
+            // Requests written to the current connection and not yet answered.
+            let mut unanswered: HashSet<RequestId> = HashSet::new();
 
             loop {
                 if !running.load(Ordering::SeqCst) {
@@ -231,8 +536,18 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                 tokio::select! {
                     // Handle outgoing messages
                     Some(msg) = outgoing_rx.recv() => {
+                        let id = match &msg {
+                            Message::Request(r) => Some(r.id.clone()),
+                            _ => None,
+                        };
                         if let Err(e) = transport.send(msg).await {
                             error!(?e, "Failed to send message");
+                        } else if let Some(id) = id {
+                            if unanswered.len() >= UNANSWERED_PRUNE_THRESHOLD {
+                                // Timed-out requests are never answered.
+                                unanswered.retain(|id| pending.contains(id));
+                            }
+                            unanswered.insert(id);
                         }
                     }
 
@@ -247,31 +562,66 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                                     Message::Notification(n) => format!("Notification({})", n.method),
                                 };
                                 debug!(msg = %msg_id, "Router received message from transport");
+                                if let Message::Response(r) = &message {
+                                    unanswered.remove(&r.id);
+                                }
                                 Self::handle_incoming_message(
                                     message,
                                     &pending,
-                                    &handler,
-                                    &transport,
-                                    &client_caps,
-                                    tasks.as_ref(),
-                                ).await;
+                                    &requests,
+                                    &events,
+                                    &shared,
+                                );
                             }
                             Ok(None) => {
                                 info!("Connection closed by server");
                                 running.store(false, Ordering::SeqCst);
                                 // Drop pending senders so in-flight requests fail
                                 // fast instead of waiting out their timeout.
-                                pending.write().await.clear();
-                                handler.on_disconnected().await;
+                                pending.clear();
+                                let _ = events.send(ClientEvent::Disconnected);
                                 break;
                             }
                             Err(e) => {
+                                let epoch = transport.connection_epoch();
+                                if epoch != connection_epoch {
+                                    connection_epoch = epoch;
+                                    warn!(error = %e, "Transport reconnected, resuming session");
+                                    let policy = shared.reconnect_policy();
+                                    let mut resumed = Self::resume_session(&transport, &pending, &resumption, &mut unanswered).await;
+                                    let mut attempt = 1;
+                                    while let Err(resume_err) = &resumed {
+                                        if attempt >= policy.max_attempts || !running.load(Ordering::SeqCst) {
+                                            break;
+                                        }
+                                        let delay = policy.backoff(attempt);
+                                        warn!(attempt, error = %resume_err, ?delay, "Failed to resume session, retrying");
+                                        runtime::sleep(delay).await;
+                                        attempt += 1;
+                                        resumed = Self::resume_session(&transport, &pending, &resumption, &mut unanswered).await;
+                                    }
+                                    match resumed {
+                                        Ok(()) => {
+                                            // The server may have changed while we were away.
+                                            if let Some(cache) = shared.list_cache.get() {
+                                                cache.clear();
+                                            }
+                                            shared.tool_schemas.invalidate();
+                                            shared.resource_watchers.notify_all();
+                                            let _ = events.send(ClientEvent::Reconnected);
+                                            continue;
+                                        }
+                                        Err(resume_err) => {
+                                            error!(error = %resume_err, "Failed to resume session after reconnect");
+                                        }
+                                    }
+                                }
                                 error!(?e, "Transport error in message router");
                                 running.store(false, Ordering::SeqCst);
                                 // Drop pending senders so in-flight requests fail
                                 // fast instead of waiting out their timeout.
-                                pending.write().await.clear();
-                                handler.on_disconnected().await;
+                                pending.clear();
+                                let _ = events.send(ClientEvent::Disconnected);
                                 break;
                             }
                         }
@@ -279,72 +629,172 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                 }
             }
 
+            shared.resource_watchers.close();
             debug!("Message router stopped");
-        })
+        });
     }
 
-    /// Handle an incoming message from the server.
-    async fn handle_incoming_message(
-        message: Message,
-        pending: &Arc<RwLock<HashMap<RequestId, oneshot::Sender<Response>>>>,
-        handler: &Arc<H>,
+    /// Rebuild the session on a reconnected transport.
+    ///
+    /// Requests written to the old connection are failed (their waiters see
+    /// the bumped session epoch and report a retryable error), the handshake is
+    /// re-run, and resource subscriptions are replayed. Queued requests are
+    /// sent once this returns, after the new `initialize`.
+    async fn resume_session(
         transport: &Arc<T>,
-        client_caps: &Arc<ClientCapabilities>,
-        tasks: Option<&Arc<TaskManager>>,
-    ) {
-        match message {
-            Message::Response(response) => {
-                Self::route_response(response, pending).await;
+        pending: &Arc<PendingRequests>,
+        resumption: &Resumption,
+        unanswered: &mut HashSet<RequestId>,
+    ) -> Result<(), McpError> {
+        resumption.session_epoch.fetch_add(1, Ordering::SeqCst);
+        for id in unanswered.drain() {
+            pending.remove(&id);
+        }
+
+        let handshake = initialize(
+            transport.as_ref(),
+            &resumption.client_info,
+            &resumption.client_caps,
+        );
+        let init_result = runtime::timeout(resumption.request_timeout, handshake)
+            .await
+            .map_err(|_| {
+                McpError::Transport(Box::new(TransportDetails {
+                    kind: TransportErrorKind::Timeout,
+                    message: format!(
+                        "Re-initialization timed out after {:?}",
+                        resumption.request_timeout
+                    ),
+                    context: TransportContext::default(),
+                    source: None,
+                }))
+            })??;
+        info!(
+            server = %init_result.server_info.name,
+            protocol_version = %init_result.protocol_version,
+            "Session re-initialized after reconnect"
+        );
+
+        let uris: Vec<String> = resumption
+            .subscriptions
+            .read()
+            .await
+            .iter()
+            .cloned()
+            .collect();
+        for uri in uris {
+            let id = RequestId::Number(resumption.next_id.fetch_add(1, Ordering::SeqCst));
+            let params = serde_json::to_value(SubscribeRequest { uri: uri.clone() })?;
+            let request = Request::with_params("resources/subscribe", id.clone(), params);
+            let mut response = pending.register_internal(id.clone());
+            if let Err(e) = transport.send(Message::Request(request)).await {
+                warn!(%uri, error = %e, "Failed to restore resource subscription");
+                continue;
             }
-            Message::Request(request) => {
-                // Handle off the router loop: a slow handler (or a spec-blocking
-                // `tasks/result`, which waits for the task to reach a terminal
-                // status) must not stall response routing or the `tasks/cancel`
-                // that would unblock it.
-                let handler = Arc::clone(handler);
-                let transport = Arc::clone(transport);
-                let client_caps = Arc::clone(client_caps);
-                let tasks = tasks.cloned();
-                tokio::spawn(async move {
+            unanswered.insert(id);
+            runtime::spawn(async move {
+                match response.recv().await {
+                    Ok(response) if response.error.is_none() => {
+                        debug!(%uri, "Restored resource subscription");
+                    }
+                    Ok(response) => {
+                        warn!(%uri, error = ?response.error, "Server refused to restore resource subscription");
+                    }
+                    Err(_) => warn!(%uri, "Resource subscription was not restored"),
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Start the worker that answers server-initiated requests.
+    fn spawn_request_worker(
+        handler: Arc<H>,
+        transport: Arc<T>,
+        client_caps: Arc<ClientCapabilities>,
+        tasks: Option<Arc<TaskManager>>,
+        roots: Arc<OnceLock<RootsManager>>,
+    ) -> mpsc::UnboundedSender<Request> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Request>();
+        runtime::spawn(async move {
+            while let Some(request) = rx.recv().await {
+                // Each request gets its own task: a slow handler (or a
+                // spec-blocking `tasks/result`, which waits for the task to
+                // reach a terminal status) must not stall the others or the
+                // `tasks/cancel` that would unblock it.
+                let handler = Arc::clone(&handler);
+                let transport = Arc::clone(&transport);
+                let client_caps = Arc::clone(&client_caps);
+                let tasks = tasks.clone();
+                let roots = Arc::clone(&roots);
+                runtime::spawn(async move {
                     Self::handle_server_request(
                         request,
                         &handler,
                         &transport,
                         &client_caps,
                         tasks.as_ref(),
+                        roots.get(),
                     )
                     .await;
                 });
             }
-            Message::Notification(notification) => {
-                Self::handle_notification(notification, handler).await;
+        });
+        tx
+    }
+
+    /// Start the worker that runs the handler's notification and lifecycle
+    /// callbacks, one at a time and in arrival order.
+    fn spawn_event_worker(handler: Arc<H>) -> mpsc::UnboundedSender<ClientEvent> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<ClientEvent>();
+        runtime::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                match event {
+                    ClientEvent::Notification(notification) => {
+                        Self::handle_notification(notification, &handler).await;
+                    }
+                    ClientEvent::Reconnected => handler.on_reconnected().await,
+                    ClientEvent::Disconnected => handler.on_disconnected().await,
+                }
             }
-        }
+        });
+        tx
     }
 
-    /// Route a response to the appropriate pending request.
-    async fn route_response(
-        response: Response,
-        pending: &Arc<RwLock<HashMap<RequestId, oneshot::Sender<Response>>>>,
+    /// Dispatch an incoming message from the server.
+    fn handle_incoming_message(
+        message: Message,
+        pending: &PendingRequests,
+        requests: &mpsc::UnboundedSender<Request>,
+        events: &mpsc::UnboundedSender<ClientEvent>,
+        shared: &Shared,
     ) {
-        let pending_count = pending.read().await.len();
-        let sender = {
-            let mut pending_guard = pending.write().await;
-            pending_guard.remove(&response.id)
-        };
-
-        if let Some(sender) = sender {
-            debug!(?response.id, pending_count, "Routing response to pending request (found in pending)");
-            if sender.send(response).is_err() {
-                warn!("Pending request receiver dropped");
+        match message {
+            Message::Response(response) => {
+                let id = response.id.clone();
+                if pending.complete(response) {
+                    debug!(?id, "Routed response to pending request");
+                } else {
+                    // This can happen benignly when:
+                    // 1. A response arrives after its caller gave up
+                    // 2. The server sends an unsolicited response
+                    // 3. A previous response is re-delivered due to transport buffering
+                    // Log at debug level to help diagnose correlation issues.
+                    debug!(
+                        ?id,
+                        "Response not found in pending (possible race or duplicate)"
+                    );
+                }
+            }
+            Message::Request(request) => {
+                let _ = requests.send(request);
+            }
+            Message::Notification(notification) => {
+                // Client state is updated here so a response that follows the
+                // notification already sees it; the handler runs on the worker.
+                Self::apply_notification(&notification, shared);
+                let _ = events.send(ClientEvent::Notification(notification));
             }
-        } else {
-            // This can happen benignly when:
-            // 1. A response arrives that was already handled (e.g., after timeout)
-            // 2. The server sends an unsolicited response
-            // 3. A previous response is re-delivered due to transport buffering
-            // Log at debug level to help diagnose correlation issues.
-            debug!(?response.id, pending_count, "Response not found in pending (possible race or duplicate)");
         }
     }
 
@@ -355,6 +805,7 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         transport: &Arc<T>,
         client_caps: &Arc<ClientCapabilities>,
         tasks: Option<&Arc<TaskManager>>,
+        roots: Option<&RootsManager>,
     ) {
         trace!(method = %request.method, "Handling server request");
 
@@ -366,7 +817,7 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                 Self::handle_tasks_request(&request, method, client_caps, tasks).await
             }
             "elicitation/create" => Self::handle_elicitation_request(&request, handler).await,
-            "roots/list" => Self::handle_roots_request(&request, handler).await,
+            "roots/list" => Self::handle_roots_request(&request, handler, roots).await,
             "ping" => {
                 // Respond to ping with empty result
                 Response::success(request.id.clone(), serde_json::json!({}))
@@ -447,7 +898,7 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                 let ctx =
                     RequestContext::with_cancellation(handle.cancel_token().unwrap_or_default());
                 let handler = Arc::clone(handler);
-                tokio::spawn(async move {
+                runtime::spawn(async move {
                     match handler.create_message(params, &ctx).await {
                         Ok(result) => match serde_json::to_value(result) {
                             Ok(value) => {
@@ -578,9 +1029,18 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         }
     }
 
-    /// Handle a roots/list request.
-    async fn handle_roots_request(request: &Request, handler: &Arc<H>) -> Response {
-        match handler.list_roots().await {
+    /// Handle a roots/list request, answering from the roots manager if one is installed.
+    async fn handle_roots_request(
+        request: &Request,
+        handler: &Arc<H>,
+        roots: Option<&RootsManager>,
+    ) -> Response {
+        let listed = if let Some(roots) = roots {
+            Ok(roots.roots())
+        } else {
+            handler.list_roots().await
+        };
+        match listed {
             Ok(roots) => {
                 let result = mcpkit_core::types::ListRootsResult { roots, meta: None };
                 match serde_json::to_value(result) {
@@ -598,7 +1058,49 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         }
     }
 
-    /// Handle a notification from the server.
+    /// Update the client's own state from a server notification.
+    fn apply_notification(notification: &Notification, shared: &Shared) {
+        match notification.method.as_ref() {
+            "notifications/resources/updated" => {
+                if let Some(uri) = notification
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("uri"))
+                    .and_then(|v| v.as_str())
+                {
+                    shared.resource_watchers.notify(uri);
+                }
+            }
+            "notifications/resources/list_changed" => {
+                if let Some(cache) = shared.list_cache.get() {
+                    cache.invalidate(&cache.resources);
+                }
+            }
+            "notifications/tools/list_changed" => {
+                if let Some(cache) = shared.list_cache.get() {
+                    cache.invalidate(&cache.tools);
+                }
+                shared.tool_schemas.invalidate();
+            }
+            "notifications/prompts/list_changed" => {
+                if let Some(cache) = shared.list_cache.get() {
+                    cache.invalidate(&cache.prompts);
+                }
+            }
+            "notifications/tasks/status" => {
+                if let Some(Ok(params)) = notification
+                    .params
+                    .clone()
+                    .map(serde_json::from_value::<TaskStatusNotificationParams>)
+                {
+                    shared.task_watchers.update(&params.task);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Hand a notification from the server to the handler.
     async fn handle_notification(notification: Notification, handler: &Arc<H>) {
         trace!(method = %notification.method, "Received server notification");
 
@@ -646,6 +1148,21 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                 debug!("Prompts list changed");
                 handler.on_prompts_list_changed().await;
             }
+            "notifications/tasks/status" => {
+                let params = notification
+                    .params
+                    .map(serde_json::from_value::<TaskStatusNotificationParams>);
+                match params {
+                    Some(Ok(params)) => {
+                        debug!(task_id = %params.task.task_id, status = %params.task.status, "Task status");
+                        handler.on_task_status(params.task).await;
+                    }
+                    Some(Err(e)) => {
+                        debug!(error = %e, "Ignoring malformed task status notification");
+                    }
+                    None => debug!("Ignoring task status notification without params"),
+                }
+            }
             "notifications/elicitation/complete" => {
                 if let Some(id) = notification
                     .params
@@ -664,11 +1181,13 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
     }
 
     /// Get the server information.
+    #[must_use]
     pub const fn server_info(&self) -> &ServerInfo {
         &self.server_info
     }
 
     /// Get the server capabilities.
+    #[must_use]
     pub const fn server_capabilities(&self) -> &ServerCapabilities {
         &self.server_caps
     }
@@ -681,55 +1200,93 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
     ///     // Use task-related features
     /// }
     /// ```
+    #[must_use]
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.protocol_version
     }
 
     /// Get the client information.
+    #[must_use]
     pub const fn client_info(&self) -> &ClientInfo {
         &self.client_info
     }
 
     /// Get the client capabilities.
+    #[must_use]
     pub const fn client_capabilities(&self) -> &ClientCapabilities {
         &self.client_caps
     }
 
     /// Get the server instructions, if provided.
+    #[must_use]
     pub fn instructions(&self) -> Option<&str> {
         self.instructions.as_deref()
     }
 
     /// Check if the server supports tools.
+    #[must_use]
     pub const fn has_tools(&self) -> bool {
         self.server_caps.has_tools()
     }
 
+    /// The client-side tools listed alongside the server's.
+    pub(crate) const fn local_tools(&self) -> &LocalTools {
+        &self.local_tools
+    }
+
     /// Check if the server supports resources.
+    #[must_use]
     pub const fn has_resources(&self) -> bool {
         self.server_caps.has_resources()
     }
 
     /// Check if the server supports prompts.
+    #[must_use]
     pub const fn has_prompts(&self) -> bool {
         self.server_caps.has_prompts()
     }
 
     /// Check if the server supports tasks.
+    #[must_use]
     pub const fn has_tasks(&self) -> bool {
         self.server_caps.has_tasks()
     }
 
     /// Check if the server supports completions.
+    #[must_use]
     pub const fn has_completions(&self) -> bool {
         self.server_caps.has_completions()
     }
 
     /// Check if the client is still connected.
+    #[must_use]
     pub fn is_connected(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
 
+    /// Check that the client is connected and, with
+    /// [`ClientBuilder::ping_supervision`](crate::ClientBuilder::ping_supervision)
+    /// enabled, that the server still answers pings.
+    #[must_use]
+    pub fn connection_healthy(&self) -> bool {
+        self.is_connected() && self.ping_health.as_ref().is_none_or(PingHealth::is_healthy)
+    }
+
+    /// Ping counters and the latest round-trip time, or `None` unless
+    /// [`ClientBuilder::ping_supervision`](crate::ClientBuilder::ping_supervision)
+    /// is enabled.
+    #[must_use]
+    pub const fn ping_health(&self) -> Option<&PingHealth> {
+        self.ping_health.as_ref()
+    }
+
+    /// Hit, miss, and invalidation counts of the list cache, or `None` unless
+    /// [`ClientBuilder::cache_lists`](crate::ClientBuilder::cache_lists) is enabled.
+    #[must_use]
+    pub fn list_cache_stats(&self) -> Option<ListCacheStats> {
+        self.shared.list_cache.get().map(ListCache::stats)
+    }
+
     // ==========================================================================
     // Tool Operations
     // ==========================================================================
@@ -768,19 +1325,102 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         }
     }
 
+    /// Stream every item of a paginated list, fetching each page lazily.
+    ///
+    /// A failed capability check (`supported`) becomes the stream's only
+    /// item. Like [`list_all`](Self::list_all), a non-advancing cursor ends
+    /// the stream with an error.
+    fn stream_all<'a, Item: 'a, R>(
+        &'a self,
+        method: &'static str,
+        supported: Result<(), McpError>,
+        extract: fn(R) -> (Vec<Item>, Option<String>),
+    ) -> impl Stream<Item = Result<Item, McpError>> + 'a
+    where
+        R: serde::de::DeserializeOwned + 'a,
+    {
+        // State: `Some(cursor)` while pages remain, `None` once exhausted.
+        let pages = stream::try_unfold(Some(None::<String>), move |state| async move {
+            let Some(cursor) = state else {
+                return Ok(None);
+            };
+            let params = cursor
+                .as_deref()
+                .map(|c| serde_json::json!({ "cursor": c }));
+            let (items, next) = extract(self.request(method, params).await?);
+            let state = match next {
+                Some(next) if cursor.as_ref() != Some(&next) => Some(Some(next)),
+                Some(_) => {
+                    return Err(McpError::internal(format!(
+                        "{method} returned a non-advancing pagination cursor"
+                    )));
+                }
+                None => None,
+            };
+            Ok(Some((stream::iter(items.into_iter().map(Ok)), state)))
+        });
+        match supported {
+            Ok(()) => Either::Left(pages.try_flatten()),
+            Err(e) => Either::Right(stream::once(async { Err(e) })),
+        }
+    }
+
     /// List all available tools, following pagination to exhaustion.
     ///
+    /// Local tools registered with
+    /// [`ClientBuilder::local_tools`](crate::ClientBuilder::local_tools) are
+    /// merged in according to their [`ToolResolution`]. With
+    /// [`ClientBuilder::cache_lists`](crate::ClientBuilder::cache_lists) the
+    /// server's list is fetched once and reused until it changes.
+    ///
     /// # Errors
     ///
-    /// Returns an error if tools are not supported or the request fails.
+    /// Returns an error if tools are not supported, the request fails, or a
+    /// local tool clashes with a server tool under
+    /// [`ToolResolution::ErrorOnConflict`].
     pub async fn list_tools(&self) -> Result<Vec<Tool>, McpError> {
+        if !self.local_tools.is_empty() {
+            let remote = if self.has_tools() {
+                self.list_remote_tools().await?
+            } else {
+                Vec::new()
+            };
+            return self.local_tools.merge(remote);
+        }
         self.ensure_capability("tools", self.has_tools())?;
-        self.list_all("tools/list", |r: ListToolsResult| (r.tools, r.next_cursor))
-            .await
+        self.list_remote_tools().await
+    }
+
+    /// List every tool the server offers, ignoring local tools.
+    async fn list_remote_tools(&self) -> Result<Vec<Tool>, McpError> {
+        let fetch = self.list_all("tools/list", |r: ListToolsResult| (r.tools, r.next_cursor));
+        match self.shared.list_cache.get() {
+            Some(cache) => cache.get_or_fetch(&cache.tools, fetch).await,
+            None => fetch.await,
+        }
+    }
+
+    /// Refetch the tool list, bypassing and replacing any cached copy.
+    ///
+    /// Only needed with
+    /// [`ClientBuilder::cache_lists`](crate::ClientBuilder::cache_lists), for
+    /// servers that change their tools without sending
+    /// `notifications/tools/list_changed`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`list_tools`](Self::list_tools).
+    pub async fn refresh_tools(&self) -> Result<Vec<Tool>, McpError> {
+        if let Some(cache) = self.shared.list_cache.get() {
+            cache.invalidate(&cache.tools);
+        }
+        self.list_tools().await
     }
 
     /// List tools with pagination.
     ///
+    /// Returns the server's pages as-is; local tools are not included.
+    ///
     /// # Errors
     ///
     /// Returns an error if tools are not supported or the request fails.
@@ -794,6 +1434,19 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         self.request("tools/list", params).await
     }
 
+    /// Stream all tools, requesting further pages only as the stream is
+    /// consumed.
+    ///
+    /// Errors (unsupported capability, failed page request) are yielded as
+    /// stream items.
+    pub fn list_all_tools(&self) -> impl Stream<Item = Result<Tool, McpError>> + '_ {
+        self.stream_all(
+            "tools/list",
+            self.ensure_capability("tools", self.has_tools()),
+            |r: ListToolsResult| (r.tools, r.next_cursor),
+        )
+    }
+
     /// Call a tool by name.
     ///
     /// # Arguments
@@ -803,17 +1456,125 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
     ///   requires `arguments` to be a JSON **object**; `null` is treated as
     ///   "no arguments".
     ///
+    ///
+    /// A local tool with this name runs in-process instead, subject to its
+    /// [`ToolResolution`]. Under `PreferRemote` and `ErrorOnConflict`, calling
+    /// a local tool first lists the server's tools to detect a clash.
+    ///
     /// # Errors
     ///
     /// Returns an error if tools are not supported, `arguments` is neither an
-    /// object nor `null`, or the call fails.
+    /// object nor `null`, the call fails, or a local tool clashes with a server
+    /// tool under [`ToolResolution::ErrorOnConflict`].
     pub async fn call_tool(
         &self,
         name: impl Into<String>,
         arguments: serde_json::Value,
     ) -> Result<CallToolResult, McpError> {
-        self.ensure_capability("tools", self.has_tools())?;
+        self.call_tool_with(name, arguments, CallOptions::new())
+            .await
+    }
+
+    /// Call a tool and deserialize its structured output into `T`, after
+    /// validating it against the tool's advertised `outputSchema`.
+    ///
+    /// Output schemas come from [`list_tools`](Self::list_tools), fetched on
+    /// first use and kept until the server sends
+    /// `notifications/tools/list_changed` or names a tool the client has not
+    /// seen. Tools without an output schema are deserialized unchecked, as by
+    /// [`typed::structured_output`](crate::typed::structured_output).
+    ///
+    /// ```no_run
+    /// # use mcpkit_client::Client;
+    /// # use mcpkit_transport::Transport;
+    /// # #[derive(serde::Deserialize)]
+    /// # struct Forecast { high: f64 }
+    /// # async fn example<T: Transport + 'static>(client: &Client<T>) -> Result<(), mcpkit_client::typed::TypedCallError> {
+    /// let forecast: Forecast = client
+    ///     .call_tool_typed("forecast", serde_json::json!({ "city": "Oslo" }))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypedCallError`](crate::typed::TypedCallError) telling
+    /// apart a failed call, a tool error result, a schema violation, and a
+    /// payload that does not deserialize into `T`.
+    #[cfg(feature = "schema-validation")]
+    pub async fn call_tool_typed<R: serde::de::DeserializeOwned>(
+        &self,
+        name: impl Into<String>,
+        arguments: serde_json::Value,
+    ) -> Result<R, crate::typed::TypedCallError> {
+        let name = name.into();
+        let schema = self.output_schema(&name).await?;
+        let result = self.call_tool(name.as_str(), arguments).await?;
+        crate::typed::validated_output(&name, result, schema.as_ref())
+    }
+
+    /// The output schema of tool `name`, from the cached tool list.
+    #[cfg(feature = "schema-validation")]
+    async fn output_schema(&self, name: &str) -> Result<Option<serde_json::Value>, McpError> {
+        Ok(self
+            .cached_tool(name)
+            .await?
+            .and_then(|tool| tool.output_schema))
+    }
+
+    /// The definition of tool `name`, from the cached tool list.
+    async fn cached_tool(&self, name: &str) -> Result<Option<Tool>, McpError> {
+        let slot = &self.shared.tool_schemas;
+        let find = |tools: &[Tool]| tools.iter().find(|tool| tool.name == name).cloned();
+        if let Ok(tools) = slot.lookup() {
+            if let Some(tool) = find(&tools) {
+                return Ok(Some(tool));
+            }
+            // The tool may have been added without a `list_changed`.
+            slot.invalidate();
+        }
+        let generation = slot.lookup().err();
+        let tools = self.list_tools().await?;
+        if let Some(generation) = generation {
+            slot.store(generation, &tools);
+        }
+        Ok(find(&tools))
+    }
 
+    /// Call a tool by name with per-call options.
+    ///
+    /// Behaves like [`call_tool`](Self::call_tool), with `options` applied to
+    /// the request sent to the server:
+    ///
+    /// ```no_run
+    /// # use mcpkit_client::{CallOptions, Client};
+    /// # use mcpkit_transport::Transport;
+    /// # use std::time::Duration;
+    /// # async fn example<T: Transport + 'static>(client: &Client<T>) -> Result<(), mcpkit_core::error::McpError> {
+    /// let result = client
+    ///     .call_tool_with(
+    ///         "search",
+    ///         serde_json::json!({ "query": "mcp" }),
+    ///         CallOptions::timeout(Duration::from_secs(5)),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`call_tool`](Self::call_tool), including a
+    /// [`TransportErrorKind::Timeout`] error when the response does not
+    /// arrive within the timeout.
+    pub async fn call_tool_with(
+        &self,
+        name: impl Into<String>,
+        arguments: serde_json::Value,
+        options: CallOptions,
+    ) -> Result<CallToolResult, McpError> {
+        let name = name.into();
         let arguments = match arguments {
             serde_json::Value::Object(map) => Some(map),
             serde_json::Value::Null => None,
@@ -824,30 +1585,91 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                 ));
             }
         };
+
+        if self.local_tools.contains(&name) && self.prefer_local_tool(&name).await? {
+            return self
+                .local_tools
+                .call(&name, arguments.unwrap_or_default())
+                .await
+                .ok_or_else(|| McpError::tool_error(&name, "local tool is not registered"))?;
+        }
+
+        self.ensure_capability("tools", self.has_tools())?;
+        if self.tool_gate.is_enabled() {
+            let tool = self
+                .cached_tool(&name)
+                .await?
+                .unwrap_or_else(|| Tool::new(&name));
+            self.tool_gate.check(tool, arguments.as_ref()).await?;
+        }
         let request = CallToolRequest {
-            name: name.into(),
+            name,
             arguments,
             task: None,
         };
-        self.request("tools/call", Some(serde_json::to_value(request)?))
+        let mut params = serde_json::to_value(request)?;
+        // Every resend carries the same key, so the server can recognize it.
+        let key = options.idempotency_key.clone().or_else(|| {
+            (self.reconnect_policy().max_replays > 0).then(|| uuid::Uuid::new_v4().to_string())
+        });
+        if let Some(key) = key {
+            params = Meta::with_idempotency_key_in_params(Some(params), &key);
+        }
+        self.request_with("tools/call", Some(params), &options)
             .await
     }
 
+    /// Decide whether a call to the local tool `name` runs locally.
+    async fn prefer_local_tool(&self, name: &str) -> Result<bool, McpError> {
+        let resolution = self.local_tools.resolution();
+        if resolution == ToolResolution::PreferLocal || !self.has_tools() {
+            return Ok(true);
+        }
+        let on_server = self
+            .list_remote_tools()
+            .await?
+            .iter()
+            .any(|tool| tool.name == name);
+        if on_server && resolution == ToolResolution::ErrorOnConflict {
+            return Err(local_tools::conflict(name));
+        }
+        Ok(!on_server)
+    }
+
+    // ==========================================================================
+    // Resource Operations
     // ==========================================================================
-    // Resource Operations
-    // ==========================================================================
 
     /// List all available resources, following pagination to exhaustion.
     ///
+    /// Served from memory when
+    /// [`ClientBuilder::cache_lists`](crate::ClientBuilder::cache_lists) is
+    /// enabled and the list has not changed since it was last fetched.
+    ///
     /// # Errors
     ///
     /// Returns an error if resources are not supported or the request fails.
     pub async fn list_resources(&self) -> Result<Vec<Resource>, McpError> {
         self.ensure_capability("resources", self.has_resources())?;
-        self.list_all("resources/list", |r: ListResourcesResult| {
+        let fetch = self.list_all("resources/list", |r: ListResourcesResult| {
             (r.resources, r.next_cursor)
-        })
-        .await
+        });
+        match self.shared.list_cache.get() {
+            Some(cache) => cache.get_or_fetch(&cache.resources, fetch).await,
+            None => fetch.await,
+        }
+    }
+
+    /// Refetch the resource list, bypassing and replacing any cached copy.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`list_resources`](Self::list_resources).
+    pub async fn refresh_resources(&self) -> Result<Vec<Resource>, McpError> {
+        if let Some(cache) = self.shared.list_cache.get() {
+            cache.invalidate(&cache.resources);
+        }
+        self.list_resources().await
     }
 
     /// List resources with pagination.
@@ -865,6 +1687,19 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         self.request("resources/list", params).await
     }
 
+    /// Stream all resources, requesting further pages only as the stream is
+    /// consumed.
+    ///
+    /// Errors (unsupported capability, failed page request) are yielded as
+    /// stream items.
+    pub fn list_all_resources(&self) -> impl Stream<Item = Result<Resource, McpError>> + '_ {
+        self.stream_all(
+            "resources/list",
+            self.ensure_capability("resources", self.has_resources()),
+            |r: ListResourcesResult| (r.resources, r.next_cursor),
+        )
+    }
+
     /// List resource templates, following pagination to exhaustion.
     ///
     /// # Errors
@@ -897,21 +1732,108 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         Ok(result.contents)
     }
 
+    /// Read a resource as a stream of byte chunks, for resources too large
+    /// to move in one message.
+    ///
+    /// Each chunk is a ranged `resources/read` (see [`RANGE_META_KEY`]),
+    /// requested only as the stream is consumed, so neither side holds more
+    /// than a chunk of the resource at a time; see
+    /// [`ClientBuilder::resource_chunk_size`](crate::ClientBuilder::resource_chunk_size).
+    /// A server that does not read ranges returns the whole resource, which
+    /// is yielded as a single chunk. Only the first contents of the resource
+    /// are read.
+    ///
+    /// Errors (unsupported capability, failed read) are yielded as stream
+    /// items.
+    pub fn read_resource_streaming(
+        &self,
+        uri: impl Into<String>,
+    ) -> impl Stream<Item = Result<Bytes, McpError>> + '_ {
+        let uri = uri.into();
+        let supported = self.ensure_capability("resources", self.has_resources());
+        // State: the offset of the next chunk, `None` once the resource is
+        // exhausted.
+        let chunks = stream::try_unfold(Some(0), move |state| {
+            let uri = uri.clone();
+            async move {
+                let Some(offset) = state else {
+                    return Ok(None);
+                };
+                let range = ByteRange::new(offset, self.resource_chunk_size);
+                let params = serde_json::json!({
+                    "uri": uri,
+                    "_meta": Meta::new().with(RANGE_META_KEY, serde_json::to_value(range)?),
+                });
+                let result: ReadResourceResult =
+                    self.request("resources/read", Some(params)).await?;
+                let contents = result
+                    .contents
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| McpError::resource_not_found(&uri))?;
+                let data = contents.to_bytes().map_err(|e| {
+                    McpError::internal(format!("invalid blob in resource {uri}: {e}"))
+                })?;
+                // Without chunk info the server sent the whole resource.
+                let next = match contents.chunk_info() {
+                    Some(info) if info.offset != offset => {
+                        return Err(McpError::internal(format!(
+                            "resources/read returned the chunk at {} of {uri} for offset {offset}",
+                            info.offset
+                        )));
+                    }
+                    Some(info) => {
+                        let end = offset + data.len() as u64;
+                        (!data.is_empty() && end < info.total).then_some(end)
+                    }
+                    None => None,
+                };
+                if data.is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some((Bytes::from(data), next)))
+            }
+        });
+        match supported {
+            Ok(()) => Either::Left(chunks),
+            Err(e) => Either::Right(stream::once(async { Err(e) })),
+        }
+    }
+
     // ==========================================================================
     // Prompt Operations
     // ==========================================================================
 
     /// List all available prompts, following pagination to exhaustion.
     ///
+    /// Served from memory when
+    /// [`ClientBuilder::cache_lists`](crate::ClientBuilder::cache_lists) is
+    /// enabled and the list has not changed since it was last fetched.
+    ///
     /// # Errors
     ///
     /// Returns an error if prompts are not supported or the request fails.
     pub async fn list_prompts(&self) -> Result<Vec<Prompt>, McpError> {
         self.ensure_capability("prompts", self.has_prompts())?;
-        self.list_all("prompts/list", |r: ListPromptsResult| {
+        let fetch = self.list_all("prompts/list", |r: ListPromptsResult| {
             (r.prompts, r.next_cursor)
-        })
-        .await
+        });
+        match self.shared.list_cache.get() {
+            Some(cache) => cache.get_or_fetch(&cache.prompts, fetch).await,
+            None => fetch.await,
+        }
+    }
+
+    /// Refetch the prompt list, bypassing and replacing any cached copy.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`list_prompts`](Self::list_prompts).
+    pub async fn refresh_prompts(&self) -> Result<Vec<Prompt>, McpError> {
+        if let Some(cache) = self.shared.list_cache.get() {
+            cache.invalidate(&cache.prompts);
+        }
+        self.list_prompts().await
     }
 
     /// List prompts with pagination.
@@ -929,6 +1851,19 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         self.request("prompts/list", params).await
     }
 
+    /// Stream all prompts, requesting further pages only as the stream is
+    /// consumed.
+    ///
+    /// Errors (unsupported capability, failed page request) are yielded as
+    /// stream items.
+    pub fn list_all_prompts(&self) -> impl Stream<Item = Result<Prompt, McpError>> + '_ {
+        self.stream_all(
+            "prompts/list",
+            self.ensure_capability("prompts", self.has_prompts()),
+            |r: ListPromptsResult| (r.prompts, r.next_cursor),
+        )
+    }
+
     /// Get a prompt by name, optionally with arguments.
     ///
     /// # Errors
@@ -1028,6 +1963,45 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
             .await
     }
 
+    /// Wait for a task to reach a terminal status (`completed`, `failed`, or
+    /// `cancelled`) and return its final state.
+    ///
+    /// Resolves as soon as the server sends a terminal
+    /// `notifications/tasks/status`. Servers need not send those, so the task
+    /// is also polled with `tasks/get` at its suggested `pollInterval`
+    /// ([`DEFAULT_TASK_POLL_INTERVAL`] when it has none). Fetch the result of a
+    /// completed task with `tasks/result`. Wrap the call in a timeout to bound
+    /// the wait.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if tasks are not supported, the task is not found, or
+    /// a poll fails.
+    pub async fn await_task(&self, id: impl Into<String>) -> Result<Task, McpError> {
+        let id = TaskId::from(id.into());
+        // Watch before the first poll so a notification sent in between is not missed.
+        let mut terminal = self.shared.task_watchers.watch(&id);
+        loop {
+            let task = self.get_task(id.0.clone()).await?.task;
+            if task.status.is_terminal() {
+                return Ok(task);
+            }
+            let interval = task
+                .poll_interval
+                .map_or(DEFAULT_TASK_POLL_INTERVAL, Duration::from_millis);
+            tokio::select! {
+                notified = &mut terminal => {
+                    if let Ok(task) = notified {
+                        return Ok(task);
+                    }
+                    // The waiter was dropped; keep polling.
+                    terminal = self.shared.task_watchers.watch(&id);
+                }
+                () = runtime::sleep(interval) => {}
+            }
+        }
+    }
+
     // ==========================================================================
     // Completion Operations
     // ==========================================================================
@@ -1131,10 +2105,12 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
             });
         }
 
-        let request = SubscribeRequest { uri: uri.into() };
+        let uri = uri.into();
+        let request = SubscribeRequest { uri: uri.clone() };
         let _: serde_json::Value = self
             .request("resources/subscribe", Some(serde_json::to_value(request)?))
             .await?;
+        self.subscriptions.write().await.insert(uri);
         Ok(())
     }
 
@@ -1154,7 +2130,9 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
             });
         }
 
-        let request = UnsubscribeRequest { uri: uri.into() };
+        let uri = uri.into();
+        self.subscriptions.write().await.remove(&uri);
+        let request = UnsubscribeRequest { uri };
         let _: serde_json::Value = self
             .request(
                 "resources/unsubscribe",
@@ -1164,6 +2142,58 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         Ok(())
     }
 
+    /// Subscribe to a resource and stream its updates.
+    ///
+    /// The returned [`ResourceWatcher`] yields an item for every
+    /// `notifications/resources/updated` for `uri`, and one after the session
+    /// is resumed on a reconnected transport. Call
+    /// [`fetch_contents`](ResourceWatcher::fetch_contents) to have each item
+    /// carry the resource's new contents. Dropping the last watcher for `uri`
+    /// unsubscribes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if resource subscriptions are not supported or the request fails.
+    pub async fn subscribe(
+        &self,
+        uri: impl Into<String>,
+    ) -> Result<ResourceWatcher<'_, T, H>, McpError> {
+        let uri = uri.into();
+        // Watch before subscribing so no update is missed in between.
+        let updates = self.shared.resource_watchers.watch(&uri);
+        // Wait out an unsubscribe from a watcher dropped just before this
+        // call, so it cannot reach the server after our subscribe.
+        drop(self.subscriptions.write().await);
+        self.subscribe_resource(uri.clone()).await?;
+        Ok(ResourceWatcher::new(self, uri, updates))
+    }
+
+    /// Unsubscribe from `uri` once its last watcher is gone (called by
+    /// [`ResourceWatcher`] on drop).
+    pub(crate) fn release_watch(&self, uri: &str) {
+        if !self.is_connected() || self.shared.resource_watchers.is_watched(uri) {
+            return;
+        }
+        let watchers = Arc::clone(&self.shared.resource_watchers);
+        let subscriptions = Arc::clone(&self.subscriptions);
+        let outgoing_tx = self.outgoing_tx.clone();
+        let id = self.next_request_id();
+        let uri = uri.to_string();
+        runtime::spawn(async move {
+            // Holding the lock orders this against a concurrent `subscribe`.
+            let mut subscriptions = subscriptions.write().await;
+            if watchers.is_watched(&uri) {
+                return;
+            }
+            subscriptions.remove(&uri);
+            let params = serde_json::json!({ "uri": uri });
+            let request = Request::with_params("resources/unsubscribe", id, params);
+            if outgoing_tx.send(Message::Request(request)).await.is_err() {
+                debug!(%uri, "Client closed before unsubscribing a dropped watcher");
+            }
+        });
+    }
+
     /// Notify the server that this client's root list has changed
     /// (`notifications/roots/list_changed`, sent with no params).
     ///
@@ -1186,6 +2216,17 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
             })
     }
 
+    /// The [`RootsManager`] installed with
+    /// [`ClientBuilder::roots_manager`](crate::ClientBuilder::roots_manager), if any.
+    ///
+    /// Changes made through it notify the server automatically, so
+    /// [`notify_roots_list_changed`](Self::notify_roots_list_changed) is only
+    /// needed for roots served by a [`ClientHandler`].
+    #[must_use]
+    pub fn roots_manager(&self) -> Option<&RootsManager> {
+        self.shared.roots.get()
+    }
+
     // ==========================================================================
     // Connection Operations
     // ==========================================================================
@@ -1255,11 +2296,94 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         self.request(method, Some(params)).await
     }
 
-    /// Send a request and wait for the response.
+    /// Send a request and wait for the response, resending it (up to the
+    /// configured number of times) if it was lost to a reconnect.
     async fn request<R: serde::de::DeserializeOwned>(
         &self,
         method: &str,
         params: Option<serde_json::Value>,
+    ) -> Result<R, McpError> {
+        self.request_with(method, params, &CallOptions::new()).await
+    }
+
+    /// [`request`](Self::request) with per-call options.
+    ///
+    /// Resends share one deadline, and each attempt tells the server how much
+    /// of it remains (`_meta["mcpkit/timeoutMs"]`).
+    async fn request_with<R: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        options: &CallOptions,
+    ) -> Result<R, McpError> {
+        let params = mcpkit_transport::telemetry::inject_current_context(params);
+        let timeout = options
+            .timeout
+            .or_else(|| self.method_timeouts.get(method).copied())
+            .unwrap_or(self.request_timeout);
+        let deadline = Instant::now() + timeout;
+        let mut retries = self.reconnect_policy().max_replays;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let attempt = Meta::with_timeout_in_params(params.clone(), remaining);
+            match self.request_once(method, Some(attempt), remaining).await {
+                Err(McpError::Transport(details))
+                    if retries > 0 && details.kind == TransportErrorKind::Reconnected =>
+                {
+                    if !self.may_replay(method, params.as_ref()).await {
+                        return Err(McpError::Transport(Box::new(TransportDetails {
+                            kind: TransportErrorKind::Interrupted,
+                            message: format!(
+                                "Request '{method}' was interrupted by a reconnect and is not safe to resend"
+                            ),
+                            context: TransportContext::default(),
+                            source: None,
+                        })));
+                    }
+                    retries -= 1;
+                    debug!(method, "Resending request lost to a reconnect");
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Whether a request lost to a reconnect may be resent under the policy:
+    /// every request with `replay_all`, otherwise read-only methods and calls
+    /// to tools annotated as idempotent or read-only.
+    async fn may_replay(&self, method: &str, params: Option<&serde_json::Value>) -> bool {
+        if self.reconnect_policy().replay_all || crate::reconnect::is_idempotent_method(method) {
+            return true;
+        }
+        if method != "tools/call" {
+            return false;
+        }
+        let Some(name) = params
+            .and_then(|p| p.get("name"))
+            .and_then(serde_json::Value::as_str)
+        else {
+            return false;
+        };
+        // Boxed because listing tools goes back through `request_with`.
+        match Box::pin(self.cached_tool(name)).await {
+            Ok(Some(tool)) => {
+                tool.is_read_only()
+                    || tool
+                        .annotations
+                        .as_ref()
+                        .and_then(|a| a.idempotent_hint)
+                        .unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Send a request once and wait for the response.
+    async fn request_once<R: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+        timeout: Duration,
     ) -> Result<R, McpError> {
         if !self.is_connected() {
             return Err(McpError::Transport(Box::new(TransportDetails {
@@ -1278,13 +2402,15 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         };
 
         trace!(?id, method, "Sending request");
+        let session_epoch = self.session_epoch.load(Ordering::SeqCst);
 
-        // Create a channel for the response
-        let (tx, rx) = oneshot::channel();
-        {
-            let mut pending = self.pending.write().await;
-            pending.insert(id.clone(), tx);
-        }
+        // Register for the response. Dropping `response` before it arrives
+        // (timeout, or the caller abandoning this future) removes the entry
+        // and tells the server the request is cancelled.
+        let mut response = self
+            .pending
+            .register(id.clone())?
+            .cancel_via(self.outgoing_tx.clone());
 
         // Send the request through the outgoing channel
         self.outgoing_tx
@@ -1300,13 +2426,21 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
             })?;
 
         // Wait for the response, bounded by the configured request timeout.
-        // On either elapse or a dropped sender we must remove our entry from
-        // `pending`, otherwise stale senders accumulate without bound.
-        let response = match tokio::time::timeout(self.request_timeout, rx).await {
+        let response = match runtime::timeout(timeout, response.recv()).await {
             Ok(Ok(response)) => response,
             Ok(Err(_)) => {
-                // Sender was dropped (router exited / connection closed).
-                self.pending.write().await.remove(&id);
+                // Sender was dropped: either the router resumed the session on
+                // a reconnected transport, or it exited.
+                if self.session_epoch.load(Ordering::SeqCst) != session_epoch {
+                    return Err(McpError::Transport(Box::new(TransportDetails {
+                        kind: TransportErrorKind::Reconnected,
+                        message: format!(
+                            "Request '{method}' was lost when the connection was re-established"
+                        ),
+                        context: TransportContext::default(),
+                        source: None,
+                    })));
+                }
                 return Err(McpError::Transport(Box::new(TransportDetails {
                     kind: TransportErrorKind::ConnectionClosed,
                     message: "Response channel closed (server may have disconnected)".to_string(),
@@ -1315,13 +2449,9 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
                 })));
             }
             Err(_elapsed) => {
-                self.pending.write().await.remove(&id);
                 return Err(McpError::Transport(Box::new(TransportDetails {
                     kind: TransportErrorKind::Timeout,
-                    message: format!(
-                        "Request '{method}' timed out after {:?}",
-                        self.request_timeout
-                    ),
+                    message: format!("Request '{method}' timed out after {timeout:?}"),
                     context: TransportContext::default(),
                     source: None,
                 })));
@@ -1329,11 +2459,9 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
         };
 
         // Process the response
+        // Keep the server's code and data so callers can inspect them.
         if let Some(error) = response.error {
-            return Err(McpError::Internal {
-                message: error.message,
-                source: None,
-            });
+            return Err(McpError::JsonRpc(error));
         }
 
         let result = response.result.ok_or_else(|| McpError::Internal {
@@ -1378,6 +2506,42 @@ impl<T: Transport + 'static, H: ClientHandler + 'static> Client<T, H> {
     }
 }
 
+/// Send one supervised `ping` and wait for its response.
+///
+/// A ping abandoned on timeout drops its `pending` entry with it.
+async fn send_ping(
+    id: RequestId,
+    outgoing_tx: mpsc::Sender<Message>,
+    pending: Arc<PendingRequests>,
+    running: Arc<AtomicBool>,
+) -> Result<(), McpError> {
+    let closed = || {
+        McpError::Transport(Box::new(TransportDetails {
+            kind: TransportErrorKind::ConnectionClosed,
+            message: "Client is not connected".to_string(),
+            context: TransportContext::default(),
+            source: None,
+        }))
+    };
+    if !running.load(Ordering::SeqCst) {
+        return Err(closed());
+    }
+
+    let mut response = pending.register_internal(id.clone());
+    if outgoing_tx
+        .send(Message::Request(Request::new("ping", id)))
+        .await
+        .is_err()
+    {
+        return Err(closed());
+    }
+    let response = response.recv().await.map_err(|_| closed())?;
+    match response.error {
+        Some(error) => Err(McpError::JsonRpc(error)),
+        None => Ok(()),
+    }
+}
+
 /// Initialize a client connection.
 ///
 /// This performs the MCP handshake with protocol version negotiation:
@@ -1565,6 +2729,62 @@ mod tests {
         }
     }
 
+    /// A supervised client closes a connection whose server stops answering
+    /// pings, leaving no abandoned ping in `pending`.
+    #[tokio::test(start_paused = true)]
+    async fn ping_supervision_closes_silent_connection() {
+        let unhealthy = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&unhealthy);
+        let config = PingConfig::new()
+            .with_interval(Duration::from_secs(1))
+            .with_timeout(Duration::from_secs(1))
+            .with_max_missed(3)
+            .on_unhealthy(move |_| flag.store(true, Ordering::SeqCst));
+        let client = Client::new(
+            SilentTransport,
+            test_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+        .with_ping_supervision(Some(config));
+        assert!(client.connection_healthy());
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+
+        assert!(unhealthy.load(Ordering::SeqCst));
+        assert!(!client.connection_healthy());
+        assert!(!client.is_connected());
+        let snapshot = client.ping_health().expect("supervised").snapshot();
+        assert_eq!((snapshot.sent, snapshot.missed), (3, 3));
+        assert!(client.pending.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn ping_supervision_tracks_rtt_of_answered_pings() {
+        let config = PingConfig::new().with_interval(Duration::from_secs(1));
+        let client = Client::new(
+            PaginatingTransport::new(0, 1, false),
+            test_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+        .with_ping_supervision(Some(config));
+
+        tokio::time::sleep(Duration::from_millis(3500)).await;
+
+        assert!(client.connection_healthy());
+        let health = client.ping_health().expect("supervised");
+        let snapshot = health.snapshot();
+        assert_eq!(
+            (snapshot.sent, snapshot.answered, snapshot.missed),
+            (3, 3, 0)
+        );
+        assert!(health.last_rtt().is_some());
+        assert!(client.pending.is_empty());
+    }
+
     /// A transport that reports a clean close (`recv` -> `Ok(None)`) as soon as
     /// the first message is sent, simulating a server that disconnects while a
     /// request is in flight.
@@ -1636,7 +2856,7 @@ mod tests {
         }
 
         assert!(
-            client.pending.read().await.is_empty(),
+            client.pending.is_empty(),
             "timed-out request must be removed from the pending map"
         );
     }
@@ -1671,11 +2891,145 @@ mod tests {
         }
 
         assert!(
-            client.pending.read().await.is_empty(),
+            client.pending.is_empty(),
             "pending requests must be drained when the connection closes"
         );
     }
 
+    /// A transport the test plays the server on: it hands over everything the
+    /// client sends and delivers whatever the test queues.
+    struct ScriptedTransport {
+        sent: tokio::sync::mpsc::UnboundedSender<Message>,
+        inbound: tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<Message>>,
+    }
+
+    fn scripted() -> (
+        ScriptedTransport,
+        tokio::sync::mpsc::UnboundedReceiver<Message>,
+        tokio::sync::mpsc::UnboundedSender<Message>,
+    ) {
+        let (sent, sent_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (inbound_tx, inbound) = tokio::sync::mpsc::unbounded_channel();
+        let transport = ScriptedTransport {
+            sent,
+            inbound: tokio::sync::Mutex::new(inbound),
+        };
+        (transport, sent_rx, inbound_tx)
+    }
+
+    impl Transport for ScriptedTransport {
+        type Error = std::convert::Infallible;
+
+        async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+            let _ = self.sent.send(msg);
+            Ok(())
+        }
+
+        async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+            Ok(self.inbound.lock().await.recv().await)
+        }
+
+        async fn close(&self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        fn metadata(&self) -> TransportMetadata {
+            TransportMetadata::new("scripted-test")
+        }
+    }
+
+    #[tokio::test]
+    async fn responses_are_correlated_out_of_order() {
+        let (transport, mut sent, inbound) = scripted();
+        let client = Client::new(
+            transport,
+            test_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(3600),
+        );
+
+        let calls = async {
+            tokio::join!(
+                client.request::<serde_json::Value>("first", None),
+                client.request::<serde_json::Value>("second", None),
+            )
+        };
+        let server = async {
+            let mut requests = Vec::new();
+            while requests.len() < 2 {
+                if let Some(Message::Request(r)) = sent.recv().await {
+                    requests.push(r);
+                }
+            }
+            // Interleave a server request and a notification, then answer
+            // in the reverse order the requests were sent.
+            let ping_id = RequestId::String("server-ping".into());
+            let _ = inbound.send(Message::Request(Request::new("ping", ping_id.clone())));
+            let _ = inbound.send(Message::Notification(Notification::new(
+                "notifications/tools/list_changed",
+            )));
+            for r in requests.into_iter().rev() {
+                let result = serde_json::json!(r.method.as_ref());
+                let _ = inbound.send(Message::Response(Response::success(r.id, result)));
+            }
+            loop {
+                if let Some(Message::Response(r)) = sent.recv().await {
+                    assert_eq!(r.id, ping_id);
+                    break;
+                }
+            }
+        };
+        let ((first, second), ()) = tokio::join!(calls, server);
+
+        assert_eq!(first.unwrap(), "first");
+        assert_eq!(second.unwrap(), "second");
+        assert!(client.pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dropped_request_is_cancelled_and_frees_its_slot() {
+        let (transport, mut sent, _inbound) = scripted();
+        let client = Client::new(
+            transport,
+            test_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(3600),
+        )
+        .with_max_pending_requests(1);
+
+        let mut call = Box::pin(client.request::<serde_json::Value>("slow", None));
+        let id = tokio::select! {
+            _ = &mut call => panic!("request was never answered"),
+            Some(Message::Request(r)) = sent.recv() => r.id,
+        };
+
+        let err = client
+            .request::<serde_json::Value>("other", None)
+            .await
+            .expect_err("the only slot is taken");
+        assert!(matches!(
+            err,
+            McpError::Transport(ref details) if details.kind == TransportErrorKind::ResourceExhausted
+        ));
+
+        drop(call);
+        assert!(client.pending.is_empty());
+        let Some(Message::Notification(cancelled)) = sent.recv().await else {
+            panic!("expected notifications/cancelled");
+        };
+        assert_eq!(cancelled.method.as_ref(), "notifications/cancelled");
+        assert_eq!(
+            cancelled.params.unwrap()["requestId"],
+            serde_json::to_value(&id).unwrap()
+        );
+    }
+
     #[tokio::test]
     async fn call_tool_rejects_non_object_arguments_before_sending() {
         let init = InitializeResult {
@@ -1704,13 +3058,16 @@ mod tests {
 
     /// A transport that serves `tools/list` in fixed-size pages, echoing an
     /// opaque numeric `nextCursor`. With `stuck_cursor` it always returns the
-    /// same cursor, to exercise the non-advancing-cursor guard.
+    /// same cursor, to exercise the non-advancing-cursor guard. Every
+    /// `tools/call` returns the text `remote`. `pages_served` counts the
+    /// `tools/list` requests answered.
     struct PaginatingTransport {
         resp_tx: tokio::sync::mpsc::UnboundedSender<Message>,
         resp_rx: tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<Message>>,
         total: usize,
         page_size: usize,
         stuck_cursor: bool,
+        pages_served: Arc<AtomicU64>,
     }
 
     impl PaginatingTransport {
@@ -1722,6 +3079,7 @@ mod tests {
                 total,
                 page_size,
                 stuck_cursor,
+                pages_served: Arc::new(AtomicU64::new(0)),
             }
         }
     }
@@ -1733,6 +3091,15 @@ mod tests {
             let Message::Request(req) = msg else {
                 return Ok(());
             };
+            if req.method == "tools/call" {
+                let result =
+                    serde_json::json!({ "content": [{ "type": "text", "text": "remote" }] });
+                let _ = self
+                    .resp_tx
+                    .send(Message::Response(Response::success(req.id, result)));
+                return Ok(());
+            }
+            self.pages_served.fetch_add(1, Ordering::SeqCst);
             let offset: usize = req
                 .params
                 .as_ref()
@@ -1830,34 +3197,270 @@ mod tests {
         );
     }
 
-    /// Regression: a `notifications/progress` with a **numeric** progress token
-    /// must reach `on_progress` with typed params (the old code only accepted
-    /// string tokens and mis-parsed `progress` as a `TaskProgress`).
+    /// `list_all_tools` yields every tool across pages, and surfaces a
+    /// non-advancing cursor as a stream error.
     #[tokio::test]
-    async fn progress_notification_routes_to_on_progress_with_numeric_token() {
-        use mcpkit_core::protocol::ProgressToken;
-        use mcpkit_core::types::ProgressNotificationParams;
-        use std::sync::Mutex;
-
-        struct Rec(Arc<Mutex<Vec<ProgressNotificationParams>>>);
-        impl ClientHandler for Rec {
-            async fn on_progress(&self, params: ProgressNotificationParams) {
-                self.0.lock().unwrap().push(params);
-            }
-        }
-
-        let seen = Arc::new(Mutex::new(Vec::new()));
-        let handler = Arc::new(Rec(Arc::clone(&seen)));
-        let notif = Notification::with_params(
-            "notifications/progress",
-            serde_json::json!({ "progressToken": 5, "progress": 0.25, "total": 1.0 }),
+    async fn list_all_tools_streams_every_page() {
+        let client = Client::new(
+            PaginatingTransport::new(5, 2, false),
+            tools_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
         );
+        let tools: Vec<Tool> = client.list_all_tools().try_collect().await.expect("stream");
+        let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["t0", "t1", "t2", "t3", "t4"]);
 
-        Client::<SilentTransport, Rec>::handle_notification(notif, &handler).await;
-
-        let seen = seen.lock().unwrap();
-        assert_eq!(seen.len(), 1);
-        assert_eq!(seen[0].progress_token, ProgressToken::Number(5));
+        let stuck = Client::new(
+            PaginatingTransport::new(5, 2, true),
+            tools_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        );
+        let err = stuck
+            .list_all_tools()
+            .try_collect::<Vec<_>>()
+            .await
+            .expect_err("stuck cursor");
+        assert!(err.to_string().contains("non-advancing"), "{err}");
+    }
+
+    /// With the list cache on, `list_tools` is answered from memory until
+    /// `notifications/tools/list_changed` arrives or `refresh_tools` is called.
+    #[tokio::test]
+    async fn cached_tool_list_is_invalidated_by_list_changed() {
+        let transport = PaginatingTransport::new(5, 2, false);
+        let pages_served = Arc::clone(&transport.pages_served);
+        let server_tx = transport.resp_tx.clone();
+        let client = Client::new(
+            transport,
+            tools_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+        .with_list_cache(true);
+
+        assert_eq!(client.list_tools().await.expect("list").len(), 5);
+        assert_eq!(client.list_tools().await.expect("list").len(), 5);
+        assert_eq!(pages_served.load(Ordering::SeqCst), 3);
+
+        server_tx
+            .send(Message::Notification(Notification::new(
+                "notifications/tools/list_changed",
+            )))
+            .expect("notify");
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while client.list_cache_stats().expect("cache").invalidations == 0 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("list_changed should invalidate the cache");
+
+        client.list_tools().await.expect("list");
+        assert_eq!(pages_served.load(Ordering::SeqCst), 6);
+        client.refresh_tools().await.expect("refresh");
+        assert_eq!(pages_served.load(Ordering::SeqCst), 9);
+
+        let stats = client.list_cache_stats().expect("cache");
+        assert_eq!((stats.hits, stats.misses, stats.invalidations), (1, 3, 2));
+    }
+
+    #[tokio::test]
+    async fn list_all_prompts_reports_missing_capability() {
+        let client = Client::new(
+            SilentTransport,
+            test_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(3600),
+        );
+        let items: Vec<_> = futures::StreamExt::collect(client.list_all_prompts()).await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+
+    fn local_tools(resolution: ToolResolution) -> LocalTools {
+        use mcpkit_core::types::ToolOutput;
+        LocalTools::new()
+            .with_resolution(resolution)
+            .register(Tool::new("t1"), |_| async { Ok(ToolOutput::text("local")) })
+            .register(Tool::new("extra"), |_| async {
+                Ok(ToolOutput::text("local"))
+            })
+    }
+
+    fn tool_client(resolution: ToolResolution) -> Client<PaginatingTransport> {
+        Client::new(
+            PaginatingTransport::new(2, 10, false),
+            tools_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+        .with_local_tools(local_tools(resolution))
+    }
+
+    async fn call_text<T: Transport + 'static>(client: &Client<T>, name: &str) -> String {
+        let result = client
+            .call_tool(name, serde_json::Value::Null)
+            .await
+            .expect("call_tool");
+        serde_json::to_value(result).unwrap()["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    /// `call_tool_typed` validates against the listed `outputSchema`,
+    /// fetching the tool list once.
+    #[cfg(feature = "schema-validation")]
+    #[tokio::test]
+    async fn call_tool_typed_validates_against_output_schema() {
+        use crate::typed::TypedCallError;
+
+        #[derive(Debug, serde::Deserialize, PartialEq)]
+        struct Sum {
+            total: f64,
+        }
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "total": { "type": "number" } },
+            "required": ["total"]
+        });
+        let structured = |value: serde_json::Value| {
+            let serde_json::Value::Object(map) = value else {
+                unreachable!()
+            };
+            async move { Ok(CallToolResult::text("").with_structured_content(map)) }
+        };
+        let tools = LocalTools::new()
+            .register(Tool::new("sum").output_schema(schema.clone()), move |_| {
+                structured(serde_json::json!({ "total": 3 }))
+            })
+            .register(Tool::new("broken").output_schema(schema), move |_| {
+                structured(serde_json::json!({ "total": "three" }))
+            });
+        let transport = PaginatingTransport::new(2, 10, false);
+        let pages_served = Arc::clone(&transport.pages_served);
+        let client = Client::new(
+            transport,
+            tools_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+        .with_local_tools(tools);
+
+        let sum: Sum = client
+            .call_tool_typed("sum", serde_json::Value::Null)
+            .await
+            .expect("typed call");
+        assert_eq!(sum, Sum { total: 3.0 });
+
+        let err = client
+            .call_tool_typed::<Sum>("broken", serde_json::Value::Null)
+            .await
+            .expect_err("schema violation");
+        assert!(matches!(err, TypedCallError::Schema { .. }), "{err}");
+        assert_eq!(pages_served.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn local_tools_shadow_server_tools_by_default() {
+        let client = tool_client(ToolResolution::PreferLocal);
+
+        let tools = client.list_tools().await.expect("list_tools");
+        let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["t0", "extra", "t1"]);
+
+        assert_eq!(call_text(&client, "t1").await, "local");
+        assert_eq!(call_text(&client, "extra").await, "local");
+        assert_eq!(call_text(&client, "t0").await, "remote");
+    }
+
+    #[tokio::test]
+    async fn prefer_remote_uses_local_tools_only_as_fallback() {
+        let client = tool_client(ToolResolution::PreferRemote);
+
+        let tools = client.list_tools().await.expect("list_tools");
+        let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["t0", "t1", "extra"]);
+
+        assert_eq!(call_text(&client, "t1").await, "remote");
+        assert_eq!(call_text(&client, "extra").await, "local");
+    }
+
+    #[tokio::test]
+    async fn error_on_conflict_rejects_shadowed_names() {
+        let client = tool_client(ToolResolution::ErrorOnConflict);
+
+        let err = client.list_tools().await.expect_err("t1 clashes");
+        assert!(err.to_string().contains("'t1'"), "{err}");
+        let err = client
+            .call_tool("t1", serde_json::Value::Null)
+            .await
+            .expect_err("t1 clashes");
+        assert!(err.to_string().contains("'t1'"), "{err}");
+
+        assert_eq!(call_text(&client, "extra").await, "local");
+    }
+
+    /// Local tools polyfill a server that has no tools capability at all.
+    #[tokio::test]
+    async fn local_tools_work_without_server_tools_capability() {
+        let client = Client::new(
+            SilentTransport,
+            test_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(3600),
+        )
+        .with_local_tools(local_tools(ToolResolution::ErrorOnConflict));
+
+        let tools = client.list_tools().await.expect("list_tools");
+        assert_eq!(tools.len(), 2);
+        assert_eq!(call_text(&client, "t1").await, "local");
+        assert!(
+            client
+                .call_tool("t0", serde_json::Value::Null)
+                .await
+                .is_err()
+        );
+    }
+
+    /// Regression: a `notifications/progress` with a **numeric** progress token
+    /// must reach `on_progress` with typed params (the old code only accepted
+    /// string tokens and mis-parsed `progress` as a `TaskProgress`).
+    #[tokio::test]
+    async fn progress_notification_routes_to_on_progress_with_numeric_token() {
+        use mcpkit_core::protocol::ProgressToken;
+        use mcpkit_core::types::ProgressNotificationParams;
+        use std::sync::Mutex;
+
+        struct Rec(Arc<Mutex<Vec<ProgressNotificationParams>>>);
+        impl ClientHandler for Rec {
+            async fn on_progress(&self, params: ProgressNotificationParams) {
+                self.0.lock().unwrap().push(params);
+            }
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(Rec(Arc::clone(&seen)));
+        let notif = Notification::with_params(
+            "notifications/progress",
+            serde_json::json!({ "progressToken": 5, "progress": 0.25, "total": 1.0 }),
+        );
+
+        Client::<SilentTransport, Rec>::handle_notification(notif, &handler).await;
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].progress_token, ProgressToken::Number(5));
         assert!((seen[0].progress - 0.25).abs() < f64::EPSILON);
         assert_eq!(seen[0].total, Some(1.0));
     }
@@ -2219,4 +3822,545 @@ mod tests {
         .await;
         assert_eq!(resp.error.expect("unknown task").code, -32602);
     }
+
+    /// A server whose connection drops (and is re-established) on the first
+    /// `tools/call`, losing its session. It records every method it receives,
+    /// and the idempotency key of each `tools/call`. Of its tools, `lookup` is
+    /// annotated idempotent and `work` is not.
+    struct ReconnectingTransport {
+        resp_tx: tokio::sync::mpsc::UnboundedSender<Result<Message, std::io::Error>>,
+        resp_rx: tokio::sync::Mutex<
+            tokio::sync::mpsc::UnboundedReceiver<Result<Message, std::io::Error>>,
+        >,
+        epoch: AtomicU64,
+        seen: std::sync::Mutex<Vec<String>>,
+        call_keys: std::sync::Mutex<Vec<Option<String>>>,
+        /// `initialize` requests to reject before accepting one.
+        failing_inits: AtomicU64,
+    }
+
+    impl ReconnectingTransport {
+        fn new() -> Self {
+            let (resp_tx, resp_rx) = tokio::sync::mpsc::unbounded_channel();
+            Self {
+                resp_tx,
+                resp_rx: tokio::sync::Mutex::new(resp_rx),
+                epoch: AtomicU64::new(0),
+                seen: std::sync::Mutex::new(Vec::new()),
+                call_keys: std::sync::Mutex::new(Vec::new()),
+                failing_inits: AtomicU64::new(0),
+            }
+        }
+
+        fn reply(&self, id: RequestId, result: serde_json::Value) {
+            let _ = self
+                .resp_tx
+                .send(Ok(Message::Response(Response::success(id, result))));
+        }
+    }
+
+    impl Transport for ReconnectingTransport {
+        type Error = std::io::Error;
+
+        async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+            let req = match msg {
+                Message::Request(req) => req,
+                Message::Notification(n) => {
+                    self.seen.lock().unwrap().push(n.method.to_string());
+                    return Ok(());
+                }
+                Message::Response(_) => return Ok(()),
+            };
+            let uri = req
+                .params
+                .as_ref()
+                .and_then(|p| p.get("uri"))
+                .and_then(serde_json::Value::as_str);
+            let entry = match uri {
+                Some(uri) => format!("{} {uri}", req.method),
+                None => req.method.to_string(),
+            };
+            self.seen.lock().unwrap().push(entry);
+            if req.method == "tools/call" {
+                let key = req
+                    .params
+                    .as_ref()
+                    .and_then(Meta::idempotency_key_from_params)
+                    .map(String::from);
+                self.call_keys.lock().unwrap().push(key);
+            }
+            match req.method.as_ref() {
+                "initialize" if self.failing_inits.load(Ordering::SeqCst) > 0 => {
+                    self.failing_inits.fetch_sub(1, Ordering::SeqCst);
+                    let _ = self.resp_tx.send(Ok(Message::Response(Response::error(
+                        req.id,
+                        JsonRpcError::internal_error("not ready"),
+                    ))));
+                }
+                "initialize" => {
+                    let init = InitializeResult {
+                        capabilities: ServerCapabilities::new()
+                            .with_tools()
+                            .with_resources_and_subscriptions(),
+                        ..test_init_result()
+                    };
+                    self.reply(req.id, serde_json::to_value(init).unwrap());
+                }
+                "tools/call" if self.epoch.load(Ordering::SeqCst) == 0 => {
+                    self.epoch.fetch_add(1, Ordering::SeqCst);
+                    let _ = self.resp_tx.send(Err(std::io::Error::other("reconnected")));
+                }
+                "tools/call" => self.reply(
+                    req.id,
+                    serde_json::json!({ "content": [{ "type": "text", "text": "remote" }] }),
+                ),
+                "tools/list" => {
+                    let lookup = Tool::new("lookup")
+                        .annotations(mcpkit_core::types::ToolAnnotations::idempotent());
+                    let tools = [Tool::new("work"), lookup];
+                    self.reply(req.id, serde_json::json!({ "tools": tools }));
+                }
+                _ => self.reply(req.id, serde_json::json!({})),
+            }
+            Ok(())
+        }
+
+        async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+            self.resp_rx.lock().await.recv().await.transpose()
+        }
+
+        async fn close(&self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        fn metadata(&self) -> TransportMetadata {
+            TransportMetadata::new("reconnecting-test")
+        }
+
+        fn connection_epoch(&self) -> u64 {
+            self.epoch.load(Ordering::SeqCst)
+        }
+    }
+
+    fn reconnecting_client() -> Client<ReconnectingTransport> {
+        let init = InitializeResult {
+            capabilities: ServerCapabilities::new()
+                .with_tools()
+                .with_resources_and_subscriptions(),
+            ..test_init_result()
+        };
+        Client::new(
+            ReconnectingTransport::new(),
+            init,
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+    }
+
+    #[tokio::test]
+    async fn reconnect_reinitializes_and_replays_subscriptions() {
+        let client = reconnecting_client();
+        client.subscribe_resource("file:///a").await.unwrap();
+        client.subscribe_resource("file:///b").await.unwrap();
+        client.unsubscribe_resource("file:///b").await.unwrap();
+
+        let err = client
+            .call_tool("work", serde_json::json!({}))
+            .await
+            .expect_err("the in-flight call is lost to the reconnect");
+        assert!(
+            matches!(&err, McpError::Transport(d) if d.kind == TransportErrorKind::Reconnected),
+            "expected a Reconnected error, got {err:?}"
+        );
+        assert!(err.is_recoverable());
+        assert!(client.is_connected());
+
+        // The caller can retry on the resumed session.
+        assert_eq!(call_text(&client, "work").await, "remote");
+        let seen = client.transport.seen.lock().unwrap().clone();
+        assert_eq!(
+            seen,
+            [
+                "resources/subscribe file:///a",
+                "resources/subscribe file:///b",
+                "resources/unsubscribe file:///b",
+                "tools/call",
+                "initialize",
+                "notifications/initialized",
+                "resources/subscribe file:///a",
+                "tools/call",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn resource_watcher_streams_updates_and_unsubscribes_on_drop() {
+        use futures::StreamExt;
+
+        let (client_tr, server) = mcpkit_transport::MemoryTransport::pair();
+        let init = InitializeResult {
+            capabilities: ServerCapabilities::new().with_resources_and_subscriptions(),
+            ..test_init_result()
+        };
+        let client = Client::new(
+            client_tr,
+            init,
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        );
+        let (seen_tx, mut seen_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok(Some(Message::Request(request))) = server.recv().await {
+                let _ = seen_tx.send(request.method.to_string());
+                let result = match request.method.as_ref() {
+                    "resources/read" => serde_json::json!({
+                        "contents": [{ "uri": "file:///a", "text": "v2" }]
+                    }),
+                    _ => serde_json::json!({}),
+                };
+                let subscribed = request.method == "resources/subscribe";
+                server
+                    .send(Message::Response(Response::success(request.id, result)))
+                    .await
+                    .unwrap();
+                if subscribed {
+                    for _ in 0..2 {
+                        let params = serde_json::json!({ "uri": "file:///a" });
+                        let updated =
+                            Notification::with_params("notifications/resources/updated", params);
+                        server.send(Message::Notification(updated)).await.unwrap();
+                    }
+                }
+            }
+        });
+
+        let mut plain = client.subscribe("file:///a").await.unwrap();
+        let update = plain.next().await.unwrap().unwrap();
+        assert_eq!(update.uri, "file:///a");
+        assert!(update.contents.is_none());
+
+        let mut fetching = client
+            .subscribe("file:///a")
+            .await
+            .unwrap()
+            .fetch_contents();
+        let update = tokio::time::timeout(Duration::from_secs(2), fetching.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.contents.unwrap()[0].text.as_deref(), Some("v2"));
+
+        // Only dropping the last watcher unsubscribes.
+        drop(plain);
+        drop(fetching);
+        let mut methods = Vec::new();
+        while methods.last().map(String::as_str) != Some("resources/unsubscribe") {
+            let method = tokio::time::timeout(Duration::from_secs(2), seen_rx.recv())
+                .await
+                .expect("the last drop sends resources/unsubscribe")
+                .unwrap();
+            methods.push(method);
+        }
+        assert_eq!(
+            methods,
+            [
+                "resources/subscribe",
+                "resources/subscribe",
+                "resources/read",
+                "resources/unsubscribe",
+            ]
+        );
+        assert!(client.subscriptions.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn retry_on_reconnect_resends_lost_requests() {
+        let client = reconnecting_client()
+            .with_reconnect_policy(ReconnectPolicy::none().with_max_replays(1));
+        assert_eq!(call_text(&client, "work").await, "remote");
+        let seen = client.transport.seen.lock().unwrap().clone();
+        assert_eq!(
+            seen,
+            [
+                "tools/call",
+                "initialize",
+                "notifications/initialized",
+                "tools/call"
+            ]
+        );
+        assert!(client.pending.is_empty());
+
+        // Both attempts carry the same generated idempotency key.
+        let keys = client.transport.call_keys.lock().unwrap().clone();
+        assert_eq!(keys.len(), 2);
+        assert!(keys[0].is_some());
+        assert_eq!(keys[0], keys[1]);
+    }
+
+    #[tokio::test]
+    async fn reconnect_policy_resends_only_idempotent_requests() {
+        let policy = ReconnectPolicy::new();
+        let client = reconnecting_client().with_reconnect_policy(policy);
+        let err = client
+            .call_tool("work", serde_json::json!({}))
+            .await
+            .expect_err("a non-idempotent call is not resent");
+        assert!(
+            matches!(&err, McpError::Transport(d) if d.kind == TransportErrorKind::Interrupted),
+            "expected an Interrupted error, got {err:?}"
+        );
+        assert!(!err.is_recoverable());
+
+        let client = reconnecting_client().with_reconnect_policy(policy);
+        assert_eq!(call_text(&client, "lookup").await, "remote");
+        let seen = client.transport.seen.lock().unwrap().clone();
+        assert_eq!(
+            seen,
+            [
+                "tools/call",
+                "initialize",
+                "notifications/initialized",
+                "tools/list",
+                "tools/call"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reconnect_policy_retries_the_handshake_with_backoff() {
+        let policy = ReconnectPolicy::new()
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+            .with_replay_all();
+        let client = reconnecting_client().with_reconnect_policy(policy);
+        client.transport.failing_inits.store(2, Ordering::SeqCst);
+
+        assert_eq!(call_text(&client, "work").await, "remote");
+        let seen = client.transport.seen.lock().unwrap().clone();
+        assert_eq!(
+            seen.iter().filter(|m| *m == "initialize").count(),
+            3,
+            "{seen:?}"
+        );
+
+        // Without retries the failed handshake ends the connection.
+        let client = reconnecting_client();
+        client.transport.failing_inits.store(1, Ordering::SeqCst);
+        let _ = client.call_tool("work", serde_json::json!({})).await;
+        assert!(!client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn roots_manager_answers_roots_list_and_notifies_changes() {
+        use mcpkit_core::types::{ListRootsResult, Root};
+        use mcpkit_transport::MemoryTransport;
+
+        let (client_tr, server) = MemoryTransport::pair();
+        let roots = RootsManager::with_roots([Root::new("file:///a")]);
+        let client = Client::new(
+            client_tr,
+            test_init_result(),
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default().with_roots_and_changes(),
+            Duration::from_secs(5),
+        )
+        .with_roots_manager(Some(roots.clone()));
+        assert!(client.roots_manager().is_some());
+
+        let recv = async || {
+            tokio::time::timeout(Duration::from_secs(2), server.recv())
+                .await
+                .expect("client never replied")
+                .expect("recv")
+                .expect("message")
+        };
+
+        server
+            .send(Message::Request(Request::new(
+                "roots/list",
+                RequestId::Number(1),
+            )))
+            .await
+            .expect("send");
+        let Message::Response(response) = recv().await else {
+            panic!("expected a roots/list response");
+        };
+        let listed: ListRootsResult =
+            serde_json::from_value(response.result.expect("result")).expect("parse");
+        assert_eq!(listed.roots, [Root::new("file:///a")]);
+
+        roots.add_root(Root::new("file:///b")).await;
+        let Message::Notification(notification) = recv().await else {
+            panic!("expected roots/list_changed");
+        };
+        assert_eq!(
+            notification.method.as_ref(),
+            "notifications/roots/list_changed"
+        );
+    }
+
+    /// Serve `tasks/get` from `server`, answering with `statuses` in turn
+    /// (the last one repeats), each with the given poll interval. Returns the
+    /// number of polls served so far.
+    fn serve_tasks_get(
+        server: mcpkit_transport::MemoryTransport,
+        statuses: Vec<TaskStatus>,
+        poll_interval_ms: u64,
+        notify_completed: bool,
+    ) -> Arc<AtomicU64> {
+        let polls = Arc::new(AtomicU64::new(0));
+        let served = Arc::clone(&polls);
+        tokio::spawn(async move {
+            while let Ok(Some(Message::Request(request))) = server.recv().await {
+                assert_eq!(request.method.as_ref(), "tasks/get");
+                let poll = usize::try_from(served.fetch_add(1, Ordering::SeqCst)).unwrap();
+                let status = statuses[poll.min(statuses.len() - 1)];
+                let task = Task::new(TaskId::from("t-1")).poll_interval(poll_interval_ms);
+                let task = Task { status, ..task };
+                server
+                    .send(Message::Response(Response::success(
+                        request.id,
+                        serde_json::to_value(&task).unwrap(),
+                    )))
+                    .await
+                    .unwrap();
+                if notify_completed {
+                    let done = Task {
+                        status: TaskStatus::Completed,
+                        ..task
+                    };
+                    let params = TaskStatusNotificationParams::from(done);
+                    server
+                        .send(Message::Notification(Notification::with_params(
+                            "notifications/tasks/status",
+                            serde_json::to_value(params).unwrap(),
+                        )))
+                        .await
+                        .unwrap();
+                }
+            }
+        });
+        polls
+    }
+
+    fn tasks_client(
+        transport: mcpkit_transport::MemoryTransport,
+    ) -> Client<mcpkit_transport::MemoryTransport> {
+        let init = InitializeResult {
+            capabilities: ServerCapabilities::new().with_tasks(),
+            ..test_init_result()
+        };
+        Client::new(
+            transport,
+            init,
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+    }
+
+    #[tokio::test]
+    async fn await_task_resolves_on_terminal_status_notification() {
+        let (client_tr, server) = mcpkit_transport::MemoryTransport::pair();
+        // The poll interval is far longer than the test's timeout, so only the
+        // notification can finish the wait.
+        let polls = serve_tasks_get(server, vec![TaskStatus::Working], 3_600_000, true);
+        let client = tasks_client(client_tr);
+
+        let task = tokio::time::timeout(Duration::from_secs(2), client.await_task("t-1"))
+            .await
+            .expect("await_task should be woken by the notification")
+            .expect("await_task");
+        assert_eq!(task.status, TaskStatus::Completed);
+
+        assert_eq!(polls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn await_task_polls_at_the_suggested_interval() {
+        let (client_tr, server) = mcpkit_transport::MemoryTransport::pair();
+        let polls = serve_tasks_get(
+            server,
+            vec![TaskStatus::Working, TaskStatus::Working, TaskStatus::Failed],
+            5,
+            false,
+        );
+        let client = tasks_client(client_tr);
+
+        let task = tokio::time::timeout(Duration::from_secs(2), client.await_task("t-1"))
+            .await
+            .expect("polling should observe the terminal status")
+            .expect("await_task");
+        assert_eq!(task.status, TaskStatus::Failed);
+
+        assert_eq!(polls.load(Ordering::SeqCst), 3);
+    }
+
+    fn tools_client(
+        transport: mcpkit_transport::MemoryTransport,
+    ) -> Client<mcpkit_transport::MemoryTransport> {
+        let init = InitializeResult {
+            capabilities: ServerCapabilities::new().with_tools(),
+            ..test_init_result()
+        };
+        Client::new(
+            transport,
+            init,
+            ClientInfo::new("test-client", "1.0.0"),
+            ClientCapabilities::default(),
+            Duration::from_secs(5),
+        )
+    }
+
+    fn is_timeout(err: &McpError) -> bool {
+        matches!(err, McpError::Transport(d) if d.kind == TransportErrorKind::Timeout)
+    }
+
+    #[tokio::test]
+    async fn call_options_timeout_bounds_the_call_and_reaches_the_server() {
+        let (client_tr, server) = mcpkit_transport::MemoryTransport::pair();
+        let client = tools_client(client_tr);
+
+        let err = client
+            .call_tool_with(
+                "slow",
+                serde_json::json!({}),
+                CallOptions::timeout(Duration::from_millis(20)),
+            )
+            .await
+            .expect_err("the server never answers");
+        assert!(is_timeout(&err), "expected a timeout, got {err:?}");
+
+        let Ok(Some(Message::Request(request))) = server.recv().await else {
+            panic!("expected the tools/call request");
+        };
+        let budget = request
+            .params
+            .as_ref()
+            .and_then(Meta::timeout_from_params)
+            .expect("the timeout travels in _meta");
+        assert!(budget <= Duration::from_millis(20), "{budget:?}");
+    }
+
+    #[tokio::test]
+    async fn method_timeouts_override_the_default() {
+        let (client_tr, _server) = mcpkit_transport::MemoryTransport::pair();
+        let client = tools_client(client_tr).with_method_timeouts(HashMap::from([(
+            "tools/call".to_string(),
+            Duration::from_millis(20),
+        )]));
+
+        let call = client.call_tool("slow", serde_json::json!({}));
+        let err = tokio::time::timeout(Duration::from_secs(2), call)
+            .await
+            .expect("the method timeout applies, not the 5s default")
+            .expect_err("the server never answers");
+        assert!(is_timeout(&err), "expected a timeout, got {err:?}");
+    }
 }
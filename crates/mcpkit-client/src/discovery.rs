@@ -6,7 +6,35 @@
 //! - Finding servers in standard locations
 //! - Parsing server configuration files
 //! - Spawning server processes
-
+//!
+//! # Configuration Formats
+//!
+//! Besides mcpkit's own `{"servers": [...]}` format, configuration files
+//! written for other MCP clients are understood:
+//!
+//! - `{"mcpServers": {"name": {...}}}`, used by `claude_desktop_config.json`,
+//!   `.mcp.json`, and `mcp.json` in many editors
+//! - `{"servers": {"name": {...}}}`, used by VS Code's `.vscode/mcp.json`
+//!   (also accepted nested under an `"mcp"` key, as in `settings.json`)
+//!
+//! A server entry has either a `command` (with `args`, `env`, and `cwd`) or a
+//! `url` (with `headers`), an optional `type` (`stdio`, `http`, `sse`, or
+//! `ws`), and an optional `"disabled": true` or `"enabled": false`.
+//!
+//! # Variables
+//!
+//! `${VAR}` and `${env:VAR}` in commands, arguments, URLs, environment
+//! values, and headers expand to environment variables, and `${VAR:-default}`
+//! supplies a fallback. `${workspaceFolder}` is the directory the
+//! configuration belongs to, and `${userHome}` the home directory. Other
+//! names, such as VS Code's `${input:api-key}`, can be supplied with
+//! [`ServerDiscovery::variable`]. A variable that can't be resolved fails
+//! loading with [`DiscoveryError::UnresolvedVariable`].
+
+use mcpkit_transport::TransportError;
+use mcpkit_transport::http::{HttpTransport, HttpTransportConfig};
+#[cfg(feature = "tokio-runtime")]
+use mcpkit_transport::spawn::{SpawnedTransport, SpawnedTransportBuilder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -27,6 +55,15 @@ pub struct DiscoveredServer {
     /// Environment variables to set when spawning.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub env: HashMap<String, String>,
+    /// Working directory to spawn the server in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// HTTP headers to send to the server.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Whether the server is turned off in its configuration.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
 }
 
 impl DiscoveredServer {
@@ -41,6 +78,9 @@ impl DiscoveredServer {
             description: None,
             icon: None,
             env: HashMap::new(),
+            cwd: None,
+            headers: HashMap::new(),
+            disabled: false,
         }
     }
 
@@ -52,6 +92,9 @@ impl DiscoveredServer {
             description: None,
             icon: None,
             env: HashMap::new(),
+            cwd: None,
+            headers: HashMap::new(),
+            disabled: false,
         }
     }
 
@@ -66,6 +109,115 @@ impl DiscoveredServer {
         self.env.insert(key.into(), value.into());
         self
     }
+
+    /// Set the working directory.
+    #[must_use]
+    pub fn cwd(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(dir.into());
+        self
+    }
+
+    /// Add an HTTP header.
+    #[must_use]
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set whether the server is enabled.
+    #[must_use]
+    pub const fn enabled(mut self, enabled: bool) -> Self {
+        self.disabled = !enabled;
+        self
+    }
+
+    /// Check whether the server is enabled.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        !self.disabled
+    }
+
+    /// A builder for spawning this server, with its arguments, environment,
+    /// and working directory applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiscoveryError::WrongTransport`] if this is not a stdio
+    /// server.
+    #[cfg(feature = "tokio-runtime")]
+    pub fn spawn_builder(&self) -> Result<SpawnedTransportBuilder, DiscoveryError> {
+        let ServerTransport::Stdio { command, args } = &self.transport else {
+            return Err(self.wrong_transport("stdio"));
+        };
+        let mut builder = SpawnedTransport::builder(command)
+            .args(args)
+            .envs(&self.env);
+        if let Some(cwd) = &self.cwd {
+            builder = builder.working_dir(cwd.clone());
+        }
+        Ok(builder)
+    }
+
+    /// Spawn this server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is not a stdio server or the process cannot
+    /// be spawned.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn spawn(&self) -> Result<SpawnedTransport, DiscoveryError> {
+        self.spawn_builder()?
+            .spawn()
+            .await
+            .map_err(|source| self.transport_error(source))
+    }
+
+    /// The HTTP transport configuration for this server, with its headers
+    /// applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiscoveryError::WrongTransport`] if this is not an HTTP
+    /// server.
+    pub fn http_config(&self) -> Result<HttpTransportConfig, DiscoveryError> {
+        let ServerTransport::Http { url } = &self.transport else {
+            return Err(self.wrong_transport("http"));
+        };
+        Ok(self
+            .headers
+            .iter()
+            .fold(HttpTransportConfig::new(url), |config, (name, value)| {
+                config.with_header(name, value)
+            }))
+    }
+
+    /// Connect to this server over HTTP.
+    ///
+    /// Requires the `http` feature of `mcpkit-transport`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is not an HTTP server or the connection
+    /// fails.
+    pub async fn connect_http(&self) -> Result<HttpTransport, DiscoveryError> {
+        HttpTransport::connect(self.http_config()?)
+            .await
+            .map_err(|source| self.transport_error(source))
+    }
+
+    fn wrong_transport(&self, expected: &'static str) -> DiscoveryError {
+        DiscoveryError::WrongTransport {
+            server: self.name.clone(),
+            expected,
+        }
+    }
+
+    fn transport_error(&self, source: TransportError) -> DiscoveryError {
+        DiscoveryError::Transport {
+            server: self.name.clone(),
+            source,
+        }
+    }
 }
 
 /// Transport configuration for a server.
@@ -104,6 +256,8 @@ pub struct ServerDiscovery {
     servers: HashMap<String, DiscoveredServer>,
     /// Configuration file paths to check.
     config_paths: Vec<PathBuf>,
+    /// Values for `${name}` variables in configuration files.
+    variables: HashMap<String, String>,
 }
 
 impl Default for ServerDiscovery {
@@ -118,8 +272,9 @@ impl ServerDiscovery {
     pub fn new() -> Self {
         let mut config_paths = Vec::new();
 
-        // Add standard config locations
+        // Add standard config locations; later files take precedence
         if let Some(config_dir) = dirs_config_dir() {
+            config_paths.push(config_dir.join("Claude").join("claude_desktop_config.json"));
             config_paths.push(config_dir.join("mcp").join("servers.json"));
         }
         if let Some(home) = dirs_home_dir() {
@@ -129,6 +284,7 @@ impl ServerDiscovery {
         Self {
             servers: HashMap::new(),
             config_paths,
+            variables: HashMap::new(),
         }
     }
 
@@ -138,6 +294,27 @@ impl ServerDiscovery {
         self
     }
 
+    /// Add a project's configuration files: `.vscode/mcp.json`, `mcp.json`,
+    /// and `.mcp.json` in `dir`.
+    #[must_use]
+    pub fn project_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        self.config_paths.push(dir.join(".vscode").join("mcp.json"));
+        self.config_paths.push(dir.join("mcp.json"));
+        self.config_paths.push(dir.join(".mcp.json"));
+        self
+    }
+
+    /// Set the value of a `${name}` variable in configuration files.
+    ///
+    /// Variables set here take precedence over environment variables and
+    /// built-ins. Use the full name, such as `input:api-key`.
+    #[must_use]
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
     /// Register a server manually.
     #[must_use]
     pub fn register(mut self, server: DiscoveredServer) -> Self {
@@ -164,11 +341,38 @@ impl ServerDiscovery {
         Ok(())
     }
 
-    /// Get all discovered servers.
+    /// Load servers from a configuration file now.
+    ///
+    /// Servers replace any already known by the same name. Returns the
+    /// number of servers loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or uses a
+    /// variable that cannot be resolved.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<usize, DiscoveryError> {
+        self.load_config_file(path.as_ref())
+    }
+
+    /// Get all discovered servers, including disabled ones.
     pub fn servers(&self) -> impl Iterator<Item = &DiscoveredServer> {
         self.servers.values()
     }
 
+    /// Get the discovered servers that are enabled.
+    pub fn enabled_servers(&self) -> impl Iterator<Item = &DiscoveredServer> {
+        self.servers.values().filter(|s| s.is_enabled())
+    }
+
+    /// Enable or disable a server. Returns `false` if no server has that
+    /// name.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        self.servers
+            .get_mut(name)
+            .map(|server| server.disabled = !enabled)
+            .is_some()
+    }
+
     /// Get a server by name.
     #[must_use]
     pub fn get(&self, name: &str) -> Option<&DiscoveredServer> {
@@ -182,30 +386,227 @@ impl ServerDiscovery {
     }
 
     /// Load servers from a configuration file.
-    fn load_config_file(&mut self, path: &Path) -> Result<(), DiscoveryError> {
+    fn load_config_file(&mut self, path: &Path) -> Result<usize, DiscoveryError> {
         let contents = std::fs::read_to_string(path).map_err(|e| DiscoveryError::Io {
             path: path.to_path_buf(),
             source: e,
         })?;
+        let parse_error = |e| DiscoveryError::Parse {
+            path: path.to_path_buf(),
+            source: e,
+        };
 
-        let config: ServerConfig =
-            serde_json::from_str(&contents).map_err(|e| DiscoveryError::Parse {
-                path: path.to_path_buf(),
-                source: e,
-            })?;
+        let mut config: serde_json::Value = serde_json::from_str(&contents).map_err(parse_error)?;
+        // VS Code's settings.json nests the servers under "mcp".
+        if let Some(mcp) = config.get_mut("mcp").filter(|mcp| mcp.is_object()) {
+            config = mcp.take();
+        }
 
-        for server in config.servers {
+        // `servers` is an array in mcpkit's own format and an object keyed by
+        // name in VS Code's; `mcpServers` is always keyed by name.
+        let key = if config.get("mcpServers").is_some() {
+            "mcpServers"
+        } else {
+            "servers"
+        };
+        let servers = match config.get_mut(key).map(serde_json::Value::take) {
+            Some(entries @ serde_json::Value::Object(_)) => {
+                let entries: HashMap<String, ServerEntry> =
+                    serde_json::from_value(entries).map_err(parse_error)?;
+                entries
+                    .into_iter()
+                    .map(|(name, entry)| entry.into_server(name, path))
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            Some(servers @ serde_json::Value::Array(_)) if key == "servers" => {
+                serde_json::from_value::<Vec<DiscoveredServer>>(servers).map_err(parse_error)?
+            }
+            _ => {
+                return Err(parse_error(serde::de::Error::custom(
+                    "expected a `servers` or `mcpServers` field",
+                )));
+            }
+        };
+
+        let variables = Variables {
+            custom: &self.variables,
+            workspace_folder: workspace_folder(path),
+        };
+        let count = servers.len();
+        for mut server in servers {
+            variables.expand_server(&mut server, path)?;
             self.servers.insert(server.name.clone(), server);
         }
 
-        Ok(())
+        Ok(count)
     }
 }
 
-/// Configuration file format.
+/// A server entry in the `mcpServers` or VS Code configuration formats.
 #[derive(Debug, Deserialize)]
-struct ServerConfig {
-    servers: Vec<DiscoveredServer>,
+struct ServerEntry {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    cwd: Option<PathBuf>,
+    #[serde(alias = "serverUrl")]
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    disabled: bool,
+    enabled: Option<bool>,
+    description: Option<String>,
+}
+
+impl ServerEntry {
+    fn into_server(self, name: String, path: &Path) -> Result<DiscoveredServer, DiscoveryError> {
+        let invalid = |message: String| DiscoveryError::InvalidServer {
+            path: path.to_path_buf(),
+            server: name.clone(),
+            message,
+        };
+        let kind = match self.kind.as_deref() {
+            Some(kind) => kind,
+            None if self.command.is_some() => "stdio",
+            None if self
+                .url
+                .as_deref()
+                .is_some_and(|url| url.starts_with("ws://") || url.starts_with("wss://")) =>
+            {
+                "ws"
+            }
+            None if self.url.is_some() => "http",
+            None => return Err(invalid("expected a `command` or a `url`".to_string())),
+        };
+        let needs = |field: &str| invalid(format!("a `{kind}` server needs a `{field}`"));
+        let transport = match kind {
+            "stdio" => ServerTransport::Stdio {
+                command: self.command.ok_or_else(|| needs("command"))?,
+                args: self.args,
+            },
+            "http" | "sse" | "streamable-http" | "streamableHttp" => ServerTransport::Http {
+                url: self.url.ok_or_else(|| needs("url"))?,
+            },
+            "ws" | "websocket" => ServerTransport::WebSocket {
+                url: self.url.ok_or_else(|| needs("url"))?,
+            },
+            _ => return Err(invalid(format!("unsupported server type `{kind}`"))),
+        };
+        Ok(DiscoveredServer {
+            name,
+            transport,
+            description: self.description,
+            icon: None,
+            env: self.env,
+            cwd: self.cwd,
+            headers: self.headers,
+            disabled: self.disabled || self.enabled == Some(false),
+        })
+    }
+}
+
+/// Resolves `${name}` variables in a configuration file.
+struct Variables<'a> {
+    custom: &'a HashMap<String, String>,
+    workspace_folder: Option<PathBuf>,
+}
+
+impl Variables<'_> {
+    /// Expand the variables in every string field of `server`.
+    fn expand_server(
+        &self,
+        server: &mut DiscoveredServer,
+        path: &Path,
+    ) -> Result<(), DiscoveryError> {
+        let expand = |value: &mut String| {
+            *value = self
+                .expand(value)
+                .map_err(|variable| DiscoveryError::UnresolvedVariable {
+                    path: path.to_path_buf(),
+                    server: server.name.clone(),
+                    variable,
+                })?;
+            Ok::<_, DiscoveryError>(())
+        };
+
+        match &mut server.transport {
+            ServerTransport::Stdio { command, args } => {
+                expand(command)?;
+                args.iter_mut().try_for_each(&expand)?;
+            }
+            ServerTransport::Http { url } | ServerTransport::WebSocket { url } => expand(url)?,
+        }
+        server.env.values_mut().try_for_each(&expand)?;
+        server.headers.values_mut().try_for_each(&expand)?;
+        if let Some(cwd) = &mut server.cwd {
+            let mut dir = cwd.to_string_lossy().into_owned();
+            expand(&mut dir)?;
+            *cwd = PathBuf::from(dir);
+        }
+        Ok(())
+    }
+
+    /// Expand `${name}` and `${name:-default}`. Returns the name of the
+    /// first variable that can't be resolved.
+    fn expand(&self, input: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(input.len());
+        let mut rest = input;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            // An unterminated `${` is left as-is.
+            let Some(end) = after.find('}') else {
+                out.push_str(&rest[start..]);
+                return Ok(out);
+            };
+            let (name, default) = match after[..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&after[..end], None),
+            };
+            let value = self
+                .lookup(name)
+                .or_else(|| default.map(str::to_string))
+                .ok_or_else(|| name.to_string())?;
+            out.push_str(&value);
+            rest = &after[end + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    fn lookup(&self, name: &str) -> Option<String> {
+        if let Some(value) = self.custom.get(name) {
+            return Some(value.clone());
+        }
+        match name {
+            "workspaceFolder" => {
+                return self
+                    .workspace_folder
+                    .as_ref()
+                    .map(|dir| dir.display().to_string());
+            }
+            "userHome" => return dirs_home_dir().map(|dir| dir.display().to_string()),
+            "pathSeparator" => return Some(std::path::MAIN_SEPARATOR.to_string()),
+            _ => {}
+        }
+        std::env::var(name.strip_prefix("env:").unwrap_or(name)).ok()
+    }
+}
+
+/// The directory a configuration file belongs to: the project for
+/// `.vscode/mcp.json`, otherwise the file's own directory.
+fn workspace_folder(path: &Path) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    if dir.file_name().is_some_and(|name| name == ".vscode") {
+        dir.parent().map(Path::to_path_buf)
+    } else {
+        Some(dir.to_path_buf())
+    }
 }
 
 /// Error type for server discovery.
@@ -227,6 +628,42 @@ pub enum DiscoveryError {
         /// The underlying error.
         source: serde_json::Error,
     },
+    /// A server entry in a configuration file is malformed.
+    #[error("Invalid server `{server}` in {path}: {message}")]
+    InvalidServer {
+        /// The file path.
+        path: PathBuf,
+        /// The server name.
+        server: String,
+        /// What is wrong with the entry.
+        message: String,
+    },
+    /// A `${variable}` in a configuration file has no value.
+    #[error("Unresolved variable `{variable}` for server `{server}` in {path}")]
+    UnresolvedVariable {
+        /// The file path.
+        path: PathBuf,
+        /// The server name.
+        server: String,
+        /// The variable name.
+        variable: String,
+    },
+    /// The server uses a different transport than the one requested.
+    #[error("Server `{server}` is not a {expected} server")]
+    WrongTransport {
+        /// The server name.
+        server: String,
+        /// The transport that was requested.
+        expected: &'static str,
+    },
+    /// Spawning or connecting to the server failed.
+    #[error("Failed to connect to server `{server}`: {source}")]
+    Transport {
+        /// The server name.
+        server: String,
+        /// The underlying error.
+        source: TransportError,
+    },
 }
 
 // Platform-agnostic directory helpers
@@ -290,6 +727,149 @@ mod tests {
         assert!(!discovery.contains("unknown"));
     }
 
+    /// Write `contents` to `file` in a fresh temporary directory.
+    fn write_config(test: &str, file: &str, contents: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("mcpkit-discovery-{test}-{}", std::process::id()));
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_mcp_servers_configs() -> Result<(), Box<dyn std::error::Error>> {
+        let path = write_config(
+            "claude",
+            "claude_desktop_config.json",
+            r#"{
+                "mcpServers": {
+                    "files": {
+                        "command": "npx",
+                        "args": ["-y", "server-filesystem", "${workspaceFolder}/docs"],
+                        "env": {"API_KEY": "${input:key}", "MODE": "${MCPKIT_UNSET_VAR:-fast}"}
+                    },
+                    "remote": {"url": "https://example.com/mcp", "headers": {"X-Path": "${env:PATH}"}},
+                    "socket": {"url": "ws://localhost:9000/mcp", "disabled": true}
+                }
+            }"#,
+        );
+        let mut discovery = ServerDiscovery::new().variable("input:key", "secret");
+        assert_eq!(discovery.load_file(&path)?, 3);
+
+        let dir = path.parent().unwrap().display().to_string();
+        let files = discovery.get("files").unwrap();
+        match &files.transport {
+            ServerTransport::Stdio { command, args } => {
+                assert_eq!(command, "npx");
+                assert_eq!(args[2], format!("{dir}/docs"));
+            }
+            _ => panic!("Expected stdio transport"),
+        }
+        assert_eq!(files.env["API_KEY"], "secret");
+        assert_eq!(files.env["MODE"], "fast");
+
+        let remote = discovery.get("remote").unwrap();
+        assert!(matches!(remote.transport, ServerTransport::Http { .. }));
+        assert_eq!(remote.headers["X-Path"], std::env::var("PATH")?);
+        assert!(remote.http_config().is_ok());
+        assert!(matches!(
+            remote.spawn_builder(),
+            Err(DiscoveryError::WrongTransport { .. })
+        ));
+
+        let socket = discovery.get("socket").unwrap();
+        assert!(matches!(
+            socket.transport,
+            ServerTransport::WebSocket { .. }
+        ));
+        assert!(!socket.is_enabled());
+        assert_eq!(discovery.enabled_servers().count(), 2);
+        assert!(discovery.set_enabled("socket", true));
+        assert_eq!(discovery.enabled_servers().count(), 3);
+        assert!(!discovery.set_enabled("unknown", true));
+
+        std::fs::remove_dir_all(path.parent().unwrap())?;
+        Ok(())
+    }
+
+    #[test]
+    fn loads_vscode_configs() -> Result<(), Box<dyn std::error::Error>> {
+        let path = write_config(
+            "vscode",
+            ".vscode/mcp.json",
+            r#"{
+                "inputs": [{"type": "promptString", "id": "token"}],
+                "servers": {
+                    "github": {"type": "http", "url": "https://api.example.com/mcp",
+                               "headers": {"Authorization": "Bearer ${input:token}"}},
+                    "local": {"type": "stdio", "command": "${workspaceFolder}/bin/server",
+                              "cwd": "${workspaceFolder}", "enabled": false}
+                }
+            }"#,
+        );
+        let project = path.parent().unwrap().parent().unwrap().to_path_buf();
+
+        // `${input:token}` has no value yet.
+        let mut discovery = ServerDiscovery::new();
+        assert!(matches!(
+            discovery.load_file(&path),
+            Err(DiscoveryError::UnresolvedVariable { ref variable, .. }) if variable == "input:token"
+        ));
+
+        let mut discovery = ServerDiscovery::new()
+            .project_dir(&project)
+            .variable("input:token", "t0k3n");
+        discovery.discover()?;
+        assert_eq!(
+            discovery.get("github").unwrap().headers["Authorization"],
+            "Bearer t0k3n"
+        );
+        let local = discovery.get("local").unwrap();
+        assert!(!local.is_enabled());
+        assert_eq!(local.cwd.as_deref(), Some(project.as_path()));
+        assert!(local.spawn_builder().is_ok());
+
+        std::fs::remove_dir_all(&project)?;
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        let cases = [
+            ("unknown-shape", r#"{"other": {}}"#),
+            ("no-command", r#"{"mcpServers": {"x": {"args": ["a"]}}}"#),
+            (
+                "bad-type",
+                r#"{"servers": {"x": {"type": "carrier-pigeon", "url": "u"}}}"#,
+            ),
+            ("missing-url", r#"{"servers": {"x": {"type": "http"}}}"#),
+        ];
+        for (test, contents) in cases {
+            let path = write_config(test, "mcp.json", contents);
+            let result = ServerDiscovery::new().load_file(&path);
+            std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+            assert!(
+                matches!(
+                    result,
+                    Err(DiscoveryError::Parse { .. } | DiscoveryError::InvalidServer { .. })
+                ),
+                "{test}: {result:?}"
+            );
+        }
+
+        // mcpkit's own format still loads.
+        let path = write_config(
+            "native",
+            "servers.json",
+            r#"{"servers": [{"name": "n", "transport": {"type": "stdio", "command": "srv"}}]}"#,
+        );
+        let mut discovery = ServerDiscovery::new();
+        assert_eq!(discovery.load_file(&path).unwrap(), 1);
+        assert!(discovery.contains("n"));
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
     #[test]
     fn test_transport_serialization() -> Result<(), Box<dyn std::error::Error>> {
         let server = DiscoveredServer::stdio("test", "test-cmd");
@@ -0,0 +1,279 @@
+//! Correlation of responses with the requests waiting on them.
+//!
+//! A [`Client`](crate::Client) shares one connection between its own
+//! requests and the ones the server sends back. The message router reads
+//! every incoming message and dispatches it by kind:
+//!
+//! - a response completes the [`PendingRequests`] entry with its id, whatever
+//!   order responses arrive in;
+//! - a server-initiated request is queued to a worker that answers each one on
+//!   its own task;
+//! - a notification is queued, in order, to a worker that runs the handler
+//!   callbacks.
+//!
+//! A slow handler therefore never holds up a response. The number of
+//! requests awaiting a response is capped, see
+//! [`ClientBuilder::max_pending_requests`](crate::ClientBuilder::max_pending_requests),
+//! and a caller that stops waiting (its future is dropped, or times out)
+//! removes its entry and tells the server with `notifications/cancelled`.
+
+use futures::channel::oneshot;
+use mcpkit_core::error::{McpError, TransportContext, TransportDetails, TransportErrorKind};
+use mcpkit_core::protocol::{Message, Notification, RequestId, Response};
+use mcpkit_core::types::CancelledNotificationParams;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::sync::mpsc;
+use tracing::{debug, trace};
+
+/// Default cap on requests awaiting a response.
+pub const DEFAULT_MAX_PENDING_REQUESTS: usize = 1024;
+
+/// Requests sent to the server and not yet answered, keyed by id.
+pub(crate) struct PendingRequests {
+    inner: Mutex<Inner>,
+    max: AtomicUsize,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Waiters, and whether each counts against the cap.
+    entries: HashMap<RequestId, (oneshot::Sender<Response>, bool)>,
+    /// Entries that count against the cap.
+    counted: usize,
+}
+
+impl Inner {
+    fn remove(&mut self, id: &RequestId) -> Option<oneshot::Sender<Response>> {
+        let (sender, counted) = self.entries.remove(id)?;
+        if counted {
+            self.counted -= 1;
+        }
+        Some(sender)
+    }
+}
+
+impl PendingRequests {
+    /// An empty table admitting at most `max` caller requests.
+    pub(crate) fn new(max: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner::default()),
+            max: AtomicUsize::new(max),
+        }
+    }
+
+    /// Change the cap; requests already pending are unaffected.
+    pub(crate) fn set_max(&self, max: usize) {
+        self.max.store(max, Ordering::Relaxed);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Register a caller request, failing once the cap is reached.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TransportErrorKind::ResourceExhausted`] error if
+    /// `max_pending_requests` requests are already awaiting a response.
+    pub(crate) fn register(self: &Arc<Self>, id: RequestId) -> Result<PendingResponse, McpError> {
+        let max = self.max.load(Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut inner = self.lock();
+            if inner.counted >= max {
+                return Err(McpError::Transport(Box::new(TransportDetails {
+                    kind: TransportErrorKind::ResourceExhausted,
+                    message: format!("Too many pending requests (limit {max})"),
+                    context: TransportContext::default(),
+                    source: None,
+                })));
+            }
+            inner.entries.insert(id.clone(), (tx, true));
+            inner.counted += 1;
+        }
+        Ok(PendingResponse {
+            pending: Arc::clone(self),
+            id,
+            rx,
+            cancel: None,
+        })
+    }
+
+    /// Register a request the client sends on its own behalf (pings,
+    /// restored subscriptions), which is not subject to the cap.
+    pub(crate) fn register_internal(self: &Arc<Self>, id: RequestId) -> PendingResponse {
+        let (tx, rx) = oneshot::channel();
+        // The client never reuses an id, so this cannot displace an entry.
+        self.lock().entries.insert(id.clone(), (tx, false));
+        PendingResponse {
+            pending: Arc::clone(self),
+            id,
+            rx,
+            cancel: None,
+        }
+    }
+
+    /// Hand a response to the request waiting on it.
+    ///
+    /// Returns `false` if no request with that id is pending: it was already
+    /// answered, its caller gave up, or the server sent an unsolicited
+    /// response.
+    pub(crate) fn complete(&self, response: Response) -> bool {
+        let sender = self.lock().remove(&response.id);
+        let Some(sender) = sender else {
+            return false;
+        };
+        if sender.send(response).is_err() {
+            trace!("Pending request receiver dropped");
+        }
+        true
+    }
+
+    /// Whether a request with this id is still waiting.
+    pub(crate) fn contains(&self, id: &RequestId) -> bool {
+        self.lock().entries.contains_key(id)
+    }
+
+    /// Fail a request: its waiter sees the sender dropped.
+    pub(crate) fn remove(&self, id: &RequestId) -> bool {
+        self.lock().remove(id).is_some()
+    }
+
+    /// Fail every pending request.
+    pub(crate) fn clear(&self) {
+        *self.lock() = Inner::default();
+    }
+
+    /// Whether no request is awaiting a response.
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+}
+
+/// The receiving end of a pending request.
+///
+/// Dropping it before the response arrives removes the entry, so abandoned
+/// requests never accumulate, and — if [`cancel_via`](Self::cancel_via) was
+/// set — sends `notifications/cancelled` for the request.
+pub(crate) struct PendingResponse {
+    pending: Arc<PendingRequests>,
+    id: RequestId,
+    rx: oneshot::Receiver<Response>,
+    cancel: Option<mpsc::Sender<Message>>,
+}
+
+impl PendingResponse {
+    /// Tell the server through `outgoing` if this request is abandoned.
+    pub(crate) fn cancel_via(mut self, outgoing: mpsc::Sender<Message>) -> Self {
+        self.cancel = Some(outgoing);
+        self
+    }
+
+    /// Wait for the response; fails if the request was removed (the
+    /// connection closed or the session was resumed).
+    pub(crate) async fn recv(&mut self) -> Result<Response, oneshot::Canceled> {
+        (&mut self.rx).await
+    }
+}
+
+impl Drop for PendingResponse {
+    fn drop(&mut self) {
+        if !self.pending.remove(&self.id) {
+            return;
+        }
+        let Some(outgoing) = &self.cancel else {
+            return;
+        };
+        debug!(id = %self.id, "Request abandoned, notifying server");
+        let params = CancelledNotificationParams {
+            request_id: Some(self.id.clone()),
+            reason: Some("Request abandoned by the client".to_string()),
+            meta: None,
+        };
+        if let Ok(params) = serde_json::to_value(params) {
+            // Best effort: a full or closed queue means the connection is
+            // backed up or gone, and the server will drop the request anyway.
+            let _ = outgoing.try_send(Message::Notification(Notification::with_params(
+                "notifications/cancelled",
+                params,
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn responses_complete_out_of_order() {
+        let pending = Arc::new(PendingRequests::new(8));
+        let mut first = pending.register(RequestId::Number(1)).unwrap();
+        let mut second = pending.register(RequestId::Number(2)).unwrap();
+
+        assert!(pending.complete(Response::success(
+            RequestId::Number(2),
+            serde_json::json!("two")
+        )));
+        assert!(pending.complete(Response::success(
+            RequestId::Number(1),
+            serde_json::json!("one")
+        )));
+        assert!(!pending.complete(Response::success(
+            RequestId::Number(1),
+            serde_json::json!("again")
+        )));
+
+        assert_eq!(second.recv().await.unwrap().result, Some("two".into()));
+        assert_eq!(first.recv().await.unwrap().result, Some("one".into()));
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn register_fails_at_the_cap() {
+        let pending = Arc::new(PendingRequests::new(1));
+        let held = pending.register(RequestId::Number(1)).unwrap();
+        let err = pending.register(RequestId::Number(2)).err().unwrap();
+        assert!(err.to_string().contains("Too many pending requests"));
+
+        // Internal requests are not counted against the cap.
+        let _ping = pending.register_internal(RequestId::Number(3));
+        drop(held);
+        assert!(pending.register(RequestId::Number(4)).is_ok());
+    }
+
+    #[test]
+    fn dropping_an_unanswered_request_cancels_it() {
+        let pending = Arc::new(PendingRequests::new(8));
+        let (tx, mut rx) = mpsc::channel(4);
+        drop(
+            pending
+                .register(RequestId::Number(7))
+                .unwrap()
+                .cancel_via(tx.clone()),
+        );
+        assert!(pending.is_empty());
+
+        let Ok(Message::Notification(n)) = rx.try_recv() else {
+            panic!("expected notifications/cancelled");
+        };
+        assert_eq!(n.method.as_ref(), "notifications/cancelled");
+        assert_eq!(n.params.unwrap()["requestId"], 7);
+
+        // An answered request is not cancelled.
+        let answered = pending
+            .register(RequestId::Number(8))
+            .unwrap()
+            .cancel_via(tx);
+        pending.complete(Response::success(
+            RequestId::Number(8),
+            serde_json::json!({}),
+        ));
+        drop(answered);
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! Choosing which returned content to show.
+//!
+//! Tool results and prompt messages may annotate each content block with an
+//! intended audience and a priority between `0.0` and `1.0`. A host that
+//! renders results to the user, rather than handing them to a model, can use
+//! these helpers to pick what to show:
+//!
+//! - [`for_display`] keeps the blocks intended for an audience, highest
+//!   priority first;
+//! - [`top_content`] returns the single most important of them.
+//!
+//! Blocks without an audience are intended for everyone, and blocks without a
+//! priority rank as [`DEFAULT_PRIORITY`]. Blocks of equal priority keep the
+//! order the server sent them in.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_client::display::{for_display, top_content};
+//! use mcpkit_core::types::{CallToolResult, Content, Role};
+//!
+//! let result = CallToolResult::content(vec![
+//!     Content::text("{\"rows\": 3}").for_audience(Role::Assistant),
+//!     Content::text("Details").priority(0.2),
+//!     Content::text("Imported 3 rows").for_audience(Role::User).priority(0.9),
+//! ]);
+//!
+//! let shown: Vec<_> = for_display(&result.content, Role::User)
+//!     .into_iter()
+//!     .filter_map(Content::as_text)
+//!     .collect();
+//! assert_eq!(shown, ["Imported 3 rows", "Details"]);
+//! assert_eq!(
+//!     top_content(&result.content, Role::User).and_then(Content::as_text),
+//!     Some("Imported 3 rows")
+//! );
+//! ```
+
+use mcpkit_core::types::{Content, Role};
+
+/// The priority of a block that does not state one.
+pub const DEFAULT_PRIORITY: f64 = 0.5;
+
+/// A block's priority, or [`DEFAULT_PRIORITY`].
+fn priority_of(content: &Content) -> f64 {
+    content
+        .annotations()
+        .and_then(|a| a.priority)
+        .unwrap_or(DEFAULT_PRIORITY)
+}
+
+/// The blocks intended for `audience`, highest priority first.
+#[must_use]
+pub fn for_display<'a>(
+    content: impl IntoIterator<Item = &'a Content>,
+    audience: Role,
+) -> Vec<&'a Content> {
+    let mut shown: Vec<&Content> = content.into_iter().filter(|c| c.is_for(audience)).collect();
+    // Stable, so equal priorities keep the server's order.
+    shown.sort_by(|a, b| priority_of(b).total_cmp(&priority_of(a)));
+    shown
+}
+
+/// The highest-priority block intended for `audience`; the first one sent if
+/// several tie.
+#[must_use]
+pub fn top_content<'a>(
+    content: impl IntoIterator<Item = &'a Content>,
+    audience: Role,
+) -> Option<&'a Content> {
+    content
+        .into_iter()
+        .filter(|c| c.is_for(audience))
+        .fold(None, |best: Option<&Content>, c| match best {
+            Some(b) if priority_of(b) >= priority_of(c) => Some(b),
+            _ => Some(c),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ties_keep_the_server_order() {
+        let content = [
+            Content::text("a"),
+            Content::text("b").priority(DEFAULT_PRIORITY),
+            Content::text("c")
+                .for_audience(Role::Assistant)
+                .priority(1.0),
+        ];
+        let shown: Vec<_> = for_display(&content, Role::User)
+            .into_iter()
+            .filter_map(Content::as_text)
+            .collect();
+        assert_eq!(shown, ["a", "b"]);
+        assert_eq!(
+            top_content(&content, Role::User).and_then(Content::as_text),
+            Some("a")
+        );
+        assert_eq!(
+            top_content(&content, Role::Assistant).and_then(Content::as_text),
+            Some("c")
+        );
+        assert!(top_content(&[], Role::User).is_none());
+    }
+}
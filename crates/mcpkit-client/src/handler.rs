@@ -12,7 +12,7 @@ use mcpkit_core::error::McpError;
 use mcpkit_core::tasks::{CancellationToken, CancelledFuture};
 use mcpkit_core::types::{
     CreateMessageRequest, CreateMessageResult, ElicitRequest, ElicitResult,
-    ProgressNotificationParams, TaskId, TaskProgress, UrlElicitRequest,
+    ProgressNotificationParams, Task, TaskId, TaskProgress, UrlElicitRequest,
 };
 use std::future::Future;
 
@@ -169,6 +169,16 @@ pub trait ClientHandler: Send + Sync {
         async {}
     }
 
+    /// Called after the transport reconnected and the session was
+    /// re-initialized.
+    ///
+    /// Resource subscriptions have already been replayed, but other
+    /// server-side state from the old session (such as task ids) is gone.
+    /// Override this to refresh cached tool, resource, or prompt lists.
+    fn on_reconnected(&self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
     // =========================================================================
     // Notification Handlers
     // =========================================================================
@@ -192,6 +202,15 @@ pub trait ClientHandler: Send + Sync {
         async {}
     }
 
+    /// Called on a `notifications/tasks/status` update for a task the server
+    /// is running.
+    ///
+    /// [`Client::await_task`](crate::Client::await_task) is woken by these
+    /// independently of this hook.
+    fn on_task_status(&self, _task: Task) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+
     /// Called when a resource has been updated.
     ///
     /// Override this to react to resource changes (requires subscription).
@@ -63,27 +63,65 @@
 //!     // Override default methods as needed
 //! }
 //! ```
+//!
+//! # Browser Support
+//!
+//! With default features disabled and the `wasm` feature enabled, the client
+//! compiles to `wasm32-unknown-unknown` and runs its background tasks on the
+//! JavaScript event loop. Connect it with the browser transports in
+//! `mcpkit_transport::wasm`. [`ClientPool`] needs the `tokio-runtime` feature.
 
 #![deny(missing_docs)]
 
 pub mod builder;
 pub mod client;
 pub mod discovery;
+pub mod dispatch;
+pub mod display;
 pub mod handler;
+pub mod list_cache;
+pub mod local_tools;
+pub mod mux;
+#[cfg(feature = "tokio-runtime")]
 pub mod pool;
+pub mod reconnect;
+pub mod roots;
+pub mod tool_policy;
+pub mod typed;
+pub mod watch;
 
 // Re-export commonly used types
-pub use builder::ClientBuilder;
-pub use client::Client;
-pub use discovery::{DiscoveredServer, ServerDiscovery};
+pub use builder::{ClientBuilder, ConnectedClient};
+pub use client::{CallOptions, Client};
+pub use discovery::{DiscoveredServer, DiscoveryError, ServerDiscovery, ServerTransport};
 pub use handler::{ClientHandler, RequestContext};
-pub use pool::{ClientPool, ClientPoolBuilder, PoolConfig, PoolStats};
+pub use list_cache::ListCacheStats;
+pub use local_tools::{LocalTools, ToolResolution};
+pub use mux::{ClientMux, MuxEvent, MuxEventKind, MuxHandler, Prefixing};
+#[cfg(feature = "tokio-runtime")]
+pub use pool::{
+    CheckoutStrategy, ClientPool, ClientPoolBuilder, PoolConfig, PoolMetrics, PoolStats,
+};
+pub use reconnect::ReconnectPolicy;
+pub use roots::RootsManager;
+pub use tool_policy::{ConfirmationRequest, Decision, ToolConfirmation};
+pub use watch::{ResourceUpdate, ResourceWatcher};
 
 /// Prelude module for convenient imports.
 pub mod prelude {
-    pub use crate::builder::ClientBuilder;
-    pub use crate::client::Client;
+    pub use crate::builder::{ClientBuilder, ConnectedClient};
+    pub use crate::client::{CallOptions, Client};
     pub use crate::discovery::{DiscoveredServer, ServerDiscovery};
     pub use crate::handler::{ClientHandler, RequestContext};
-    pub use crate::pool::{ClientPool, ClientPoolBuilder, PoolConfig, PoolStats};
+    pub use crate::list_cache::ListCacheStats;
+    pub use crate::local_tools::{LocalTools, ToolResolution};
+    pub use crate::mux::{ClientMux, MuxEvent, MuxEventKind, MuxHandler, Prefixing};
+    #[cfg(feature = "tokio-runtime")]
+    pub use crate::pool::{
+        CheckoutStrategy, ClientPool, ClientPoolBuilder, PoolConfig, PoolMetrics, PoolStats,
+    };
+    pub use crate::reconnect::ReconnectPolicy;
+    pub use crate::roots::RootsManager;
+    pub use crate::tool_policy::{ConfirmationRequest, Decision, ToolConfirmation};
+    pub use crate::watch::{ResourceUpdate, ResourceWatcher};
 }
@@ -0,0 +1,197 @@
+//! Client-side caching of tool, resource, and prompt lists.
+//!
+//! Agents often call [`Client::list_tools`](crate::Client::list_tools) before
+//! every model turn. With
+//! [`ClientBuilder::cache_lists`](crate::ClientBuilder::cache_lists) enabled,
+//! the client fetches each list once and serves it from memory until:
+//!
+//! - the server sends the matching `notifications/{tools,resources,prompts}/list_changed`,
+//! - the session is resumed after a transport reconnect, or
+//! - the caller forces a refetch with
+//!   [`Client::refresh_tools`](crate::Client::refresh_tools) (or its
+//!   resource/prompt counterparts).
+//!
+//! Hit and miss counts are available from
+//! [`Client::list_cache_stats`](crate::Client::list_cache_stats).
+
+use std::future::Future;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{Prompt, Resource, Tool};
+
+/// Counters describing how well the list cache is doing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListCacheStats {
+    /// Lists served from memory.
+    pub hits: u64,
+    /// Lists fetched from the server because no cached copy was available.
+    pub misses: u64,
+    /// Cached lists dropped by a `list_changed` notification, a reconnect,
+    /// or an explicit refresh.
+    pub invalidations: u64,
+}
+
+impl ListCacheStats {
+    /// Fraction of lookups served from memory, or `0.0` before any lookup.
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// One cached list.
+///
+/// The generation is bumped on every invalidation so that a fetch which
+/// raced a `list_changed` notification does not store the stale result.
+pub(crate) struct Slot<T> {
+    state: Mutex<(u64, Option<Vec<T>>)>,
+}
+
+impl<T> Default for Slot<T> {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new((0, None)),
+        }
+    }
+}
+
+impl<T: Clone> Slot<T> {
+    /// The cached list, or the current generation to pass to [`store`](Self::store).
+    pub(crate) fn lookup(&self) -> Result<Vec<T>, u64> {
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        state.1.clone().ok_or(state.0)
+    }
+
+    /// Cache `items` unless the slot was invalidated since `generation`.
+    pub(crate) fn store(&self, generation: u64, items: &[T]) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if state.0 == generation {
+            state.1 = Some(items.to_vec());
+        }
+    }
+
+    /// Drop the cached list, reporting whether there was one.
+    pub(crate) fn invalidate(&self) -> bool {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        state.0 += 1;
+        state.1.take().is_some()
+    }
+}
+
+/// Memoized `tools/list`, `resources/list`, and `prompts/list` results.
+#[derive(Default)]
+pub(crate) struct ListCache {
+    pub(crate) tools: Slot<Tool>,
+    pub(crate) resources: Slot<Resource>,
+    pub(crate) prompts: Slot<Prompt>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl ListCache {
+    /// Serve `slot` from memory, or run `fetch` and cache its result.
+    pub(crate) async fn get_or_fetch<T, F>(
+        &self,
+        slot: &Slot<T>,
+        fetch: F,
+    ) -> Result<Vec<T>, McpError>
+    where
+        T: Clone,
+        F: Future<Output = Result<Vec<T>, McpError>>,
+    {
+        match slot.lookup() {
+            Ok(items) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Ok(items)
+            }
+            Err(generation) => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                let items = fetch.await?;
+                slot.store(generation, &items);
+                Ok(items)
+            }
+        }
+    }
+
+    /// Drop the cached copy of one list.
+    pub(crate) fn invalidate<T: Clone>(&self, slot: &Slot<T>) {
+        if slot.invalidate() {
+            self.invalidations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Drop every cached list.
+    pub(crate) fn clear(&self) {
+        self.invalidate(&self.tools);
+        self.invalidate(&self.resources);
+        self.invalidate(&self.prompts);
+    }
+
+    /// Snapshot of the hit, miss, and invalidation counters.
+    pub(crate) fn stats(&self) -> ListCacheStats {
+        ListCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fetch_racing_an_invalidation_is_not_cached() {
+        let cache = ListCache::default();
+        let fetch = async {
+            // `list_changed` lands while the fetch is in flight.
+            cache.invalidate(&cache.prompts);
+            Ok(vec![Prompt::new("stale")])
+        };
+        let prompts = cache.get_or_fetch(&cache.prompts, fetch).await.unwrap();
+        assert_eq!(prompts.len(), 1);
+
+        let refetched = cache
+            .get_or_fetch(&cache.prompts, async { Ok(Vec::new()) })
+            .await
+            .unwrap();
+        assert!(refetched.is_empty());
+        assert_eq!(
+            cache.stats(),
+            ListCacheStats {
+                hits: 0,
+                misses: 2,
+                invalidations: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn hit_rate() {
+        assert!(ListCacheStats::default().hit_rate().abs() < f64::EPSILON);
+        let stats = ListCacheStats {
+            hits: 3,
+            misses: 1,
+            invalidations: 0,
+        };
+        assert!((stats.hit_rate() - 0.75).abs() < f64::EPSILON);
+    }
+}
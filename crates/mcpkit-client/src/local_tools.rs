@@ -0,0 +1,246 @@
+//! Client-side local tools.
+//!
+//! A [`LocalTools`] registry lets a host register Rust functions as tools on
+//! the client. They are listed and called through the same
+//! [`Client::list_tools`](crate::Client::list_tools) /
+//! [`Client::call_tool`](crate::Client::call_tool) API as server tools. Use
+//! them to polyfill tools a server lacks or to intercept specific calls
+//! locally. When a local tool has the same name as a server tool, the
+//! [`ToolResolution`] policy decides which one wins.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_client::{LocalTools, ToolResolution};
+//! use mcpkit_core::types::{Tool, ToolOutput};
+//!
+//! let tools = LocalTools::new()
+//!     .with_resolution(ToolResolution::PreferRemote)
+//!     .register(Tool::new("now").description("Current Unix time"), |_args| async {
+//!         Ok(ToolOutput::text("1700000000"))
+//!     });
+//! assert!(tools.contains("now"));
+//! ```
+
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{CallToolResult, Object, Tool};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// A boxed, `Send` future returned by a local tool.
+type BoxFut = Pin<Box<dyn Future<Output = Result<CallToolResult, McpError>> + Send>>;
+
+/// A type-erased local tool function.
+type LocalToolFn = Arc<dyn Fn(Object) -> BoxFut + Send + Sync>;
+
+/// Which tool wins when a local tool and a server tool share a name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToolResolution {
+    /// The local tool shadows the server tool (the default).
+    #[default]
+    PreferLocal,
+    /// The server tool is used when the server has one; the local tool is a
+    /// fallback for servers that lack it.
+    PreferRemote,
+    /// A name clash is an error, reported by `list_tools` and `call_tool`.
+    ErrorOnConflict,
+}
+
+/// A local tool: its definition and the function that runs it.
+#[derive(Clone)]
+struct LocalTool {
+    tool: Tool,
+    call: LocalToolFn,
+}
+
+/// Registry of tools executed in-process by the client.
+#[derive(Clone, Default)]
+pub struct LocalTools {
+    tools: HashMap<String, LocalTool>,
+    resolution: ToolResolution,
+}
+
+impl LocalTools {
+    /// Create an empty registry using [`ToolResolution::PreferLocal`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the conflict resolution policy.
+    #[must_use]
+    pub const fn with_resolution(mut self, resolution: ToolResolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Register a local tool, replacing any earlier one with the same name.
+    ///
+    /// The function receives the call's arguments (an empty object when none
+    /// were given) and may return anything convertible to a
+    /// [`CallToolResult`], such as a `ToolOutput`.
+    #[must_use]
+    pub fn register<F, Fut, R>(mut self, tool: Tool, f: F) -> Self
+    where
+        F: Fn(Object) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, McpError>> + Send + 'static,
+        R: Into<CallToolResult>,
+    {
+        let call: LocalToolFn = Arc::new(move |args| {
+            let fut = f(args);
+            Box::pin(async move { fut.await.map(Into::into) })
+        });
+        self.tools
+            .insert(tool.name.clone(), LocalTool { tool, call });
+        self
+    }
+
+    /// The conflict resolution policy.
+    #[must_use]
+    pub const fn resolution(&self) -> ToolResolution {
+        self.resolution
+    }
+
+    /// Whether a local tool with this name is registered.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
+    }
+
+    /// Number of registered local tools.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether no local tools are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Definitions of the registered local tools, sorted by name.
+    #[must_use]
+    pub fn tools(&self) -> Vec<Tool> {
+        let mut tools: Vec<Tool> = self.tools.values().map(|t| t.tool.clone()).collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        tools
+    }
+
+    /// Run a local tool, or return `None` if no tool has this name.
+    pub async fn call(&self, name: &str, args: Object) -> Option<Result<CallToolResult, McpError>> {
+        let call = Arc::clone(&self.tools.get(name)?.call);
+        Some(call(args).await)
+    }
+
+    /// Merge the server's tools with the local tools under the policy.
+    ///
+    /// # Errors
+    ///
+    /// With [`ToolResolution::ErrorOnConflict`], returns an error naming the
+    /// first tool defined both locally and on the server.
+    pub fn merge(&self, remote: Vec<Tool>) -> Result<Vec<Tool>, McpError> {
+        let mut merged = Vec::with_capacity(remote.len() + self.tools.len());
+        for tool in remote {
+            if !self.contains(&tool.name) {
+                merged.push(tool);
+                continue;
+            }
+            match self.resolution {
+                ToolResolution::PreferLocal => {}
+                ToolResolution::PreferRemote => merged.push(tool),
+                ToolResolution::ErrorOnConflict => return Err(conflict(&tool.name)),
+            }
+        }
+        for tool in self.tools() {
+            let shadowed = self.resolution == ToolResolution::PreferRemote
+                && merged.iter().any(|t| t.name == tool.name);
+            if !shadowed {
+                merged.push(tool);
+            }
+        }
+        Ok(merged)
+    }
+}
+
+/// The error reported for a local/remote name clash.
+pub(crate) fn conflict(name: &str) -> McpError {
+    McpError::invalid_request(format!(
+        "tool '{name}' is defined both locally and on the server"
+    ))
+}
+
+impl fmt::Debug for LocalTools {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&str> = self.tools.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        f.debug_struct("LocalTools")
+            .field("tools", &names)
+            .field("resolution", &self.resolution)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::types::ToolOutput;
+
+    fn registry(resolution: ToolResolution) -> LocalTools {
+        LocalTools::new()
+            .with_resolution(resolution)
+            .register(Tool::new("shared"), |_| async {
+                Ok(ToolOutput::text("local"))
+            })
+            .register(Tool::new("only_local"), |args| async move {
+                Ok(ToolOutput::text(format!("{}", args.len())))
+            })
+    }
+
+    fn names(tools: &[Tool]) -> Vec<&str> {
+        tools.iter().map(|t| t.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn call_runs_registered_function() {
+        let tools = registry(ToolResolution::PreferLocal);
+        let mut args = Object::new();
+        args.insert("a".into(), 1.into());
+        let result = tools.call("only_local", args).await.unwrap().unwrap();
+        assert_eq!(
+            serde_json::to_value(&result).unwrap()["content"][0]["text"],
+            "1"
+        );
+        assert!(tools.call("missing", Object::new()).await.is_none());
+    }
+
+    #[test]
+    fn merge_follows_policy() {
+        let remote = || {
+            vec![
+                Tool::new("remote").description("r"),
+                Tool::new("shared").description("server"),
+            ]
+        };
+
+        let local = registry(ToolResolution::PreferLocal)
+            .merge(remote())
+            .unwrap();
+        assert_eq!(names(&local), ["remote", "only_local", "shared"]);
+        assert!(local[2].description.is_none(), "local definition wins");
+
+        let remote_first = registry(ToolResolution::PreferRemote)
+            .merge(remote())
+            .unwrap();
+        assert_eq!(names(&remote_first), ["remote", "shared", "only_local"]);
+        assert_eq!(remote_first[1].description.as_deref(), Some("server"));
+
+        let err = registry(ToolResolution::ErrorOnConflict)
+            .merge(remote())
+            .unwrap_err();
+        assert!(err.to_string().contains("shared"), "{err}");
+    }
+}
@@ -0,0 +1,1003 @@
+//! Aggregating several MCP servers behind one client.
+//!
+//! A [`ClientMux`] owns one [`Client`] per server, each registered under an
+//! *origin* name. It merges the servers' tools, resources, and prompts into a
+//! single namespace, routes [`call_tool`](ClientMux::call_tool),
+//! [`read_resource`](ClientMux::read_resource), and
+//! [`get_prompt`](ClientMux::get_prompt) to the server that owns the name, and
+//! re-emits each server's notifications tagged with its origin.
+//!
+//! Tool and prompt names that clash across servers are disambiguated by
+//! prefixing them with the origin (`github__search`), as chosen by the
+//! [`Prefixing`] policy. Resource URIs are never rewritten, since they are
+//! meaningful to the server; when two servers list the same URI, the server
+//! added first serves it.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use mcpkit_client::{ClientBuilder, ClientMux, Prefixing};
+//! use mcpkit_transport::SpawnedTransport;
+//!
+//! # async fn example() -> Result<(), mcpkit_core::error::McpError> {
+//! let mut mux = ClientMux::new().with_prefixing(Prefixing::OnCollision);
+//! let mut events = mux.subscribe();
+//!
+//! let github = SpawnedTransport::spawn("github-mcp", &[] as &[&str]).await?;
+//! mux.connect("github", ClientBuilder::new(), github).await?;
+//! let files = SpawnedTransport::spawn("fs-mcp", &[] as &[&str]).await?;
+//! mux.connect("files", ClientBuilder::new(), files).await?;
+//!
+//! // One namespace for the LLM; calls are routed to the owning server.
+//! let tools = mux.list_tools().await?;
+//! let result = mux.call_tool(&tools[0].name, serde_json::json!({})).await?;
+//!
+//! // Notifications from every server, tagged with where they came from.
+//! while let Ok(event) = events.recv().await {
+//!     println!("{}: {:?}", event.origin, event.kind);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::builder::ClientBuilder;
+use crate::client::Client;
+use crate::handler::{ClientHandler, NoOpHandler, RequestContext};
+use futures::future::join_all;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{
+    CallToolResult, CreateMessageRequest, CreateMessageResult, ElicitRequest, ElicitResult,
+    GetPromptResult, ProgressNotificationParams, Prompt, Resource, ResourceContents, Root, Task,
+    TaskId, TaskProgress, Tool, UrlElicitRequest,
+};
+use mcpkit_transport::Transport;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, PoisonError, RwLock};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+/// Separator between origin and name in prefixed names, unless configured.
+pub const DEFAULT_SEPARATOR: &str = "__";
+
+/// Number of events buffered for each [`ClientMux::subscribe`] receiver.
+const EVENT_CAPACITY: usize = 256;
+
+/// When tool and prompt names are prefixed with their server's origin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Prefixing {
+    /// Only names offered by more than one server are prefixed, in every
+    /// server that offers them (the default).
+    #[default]
+    OnCollision,
+    /// Every name is prefixed, so exposed names never change as servers
+    /// come and go.
+    Always,
+    /// Names are never prefixed; on a clash the server added first wins and
+    /// the others' entries are hidden.
+    Never,
+}
+
+/// A notification from one of the servers behind a [`ClientMux`].
+#[derive(Debug, Clone)]
+pub struct MuxEvent {
+    /// Origin of the server that sent it.
+    pub origin: String,
+    /// What happened.
+    pub kind: MuxEventKind,
+}
+
+/// The notification carried by a [`MuxEvent`].
+#[derive(Debug, Clone)]
+pub enum MuxEventKind {
+    /// The connection was established.
+    Connected,
+    /// The connection was closed.
+    Disconnected,
+    /// The transport reconnected and the session was re-initialized.
+    Reconnected,
+    /// The server's tool list changed.
+    ToolsListChanged,
+    /// The server's resource list changed.
+    ResourcesListChanged,
+    /// The server's prompt list changed.
+    PromptsListChanged,
+    /// A subscribed resource changed.
+    ResourceUpdated {
+        /// URI of the resource.
+        uri: String,
+    },
+    /// Progress on a request that carried a progress token.
+    Progress(ProgressNotificationParams),
+    /// A task's status changed.
+    TaskStatus(Task),
+    /// A task made progress.
+    TaskProgress {
+        /// The task.
+        task_id: TaskId,
+        /// Its progress.
+        progress: TaskProgress,
+    },
+    /// A URL-mode elicitation's out-of-band interaction completed.
+    ElicitationComplete {
+        /// The elicitation that completed.
+        elicitation_id: String,
+    },
+}
+
+/// A boxed, `Send` future returned by a backend.
+type BoxFut<'a, T> = Pin<Box<dyn Future<Output = Result<T, McpError>> + Send + 'a>>;
+
+/// The operations a [`ClientMux`] needs from a client, type-erased over the
+/// client's transport and handler.
+trait Backend: Send + Sync {
+    fn list_tools(&self) -> BoxFut<'_, Vec<Tool>>;
+    fn call_tool(&self, name: String, arguments: serde_json::Value) -> BoxFut<'_, CallToolResult>;
+    fn list_resources(&self) -> BoxFut<'_, Vec<Resource>>;
+    fn read_resource(&self, uri: String) -> BoxFut<'_, Vec<ResourceContents>>;
+    fn list_prompts(&self) -> BoxFut<'_, Vec<Prompt>>;
+    fn get_prompt(
+        &self,
+        name: String,
+        arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> BoxFut<'_, GetPromptResult>;
+    fn close(self: Box<Self>) -> BoxFut<'static, ()>;
+}
+
+impl<T: Transport + 'static, H: ClientHandler + 'static> Backend for Client<T, H> {
+    fn list_tools(&self) -> BoxFut<'_, Vec<Tool>> {
+        // A server without the capability simply contributes nothing.
+        if !self.has_tools() && self.local_tools().is_empty() {
+            return Box::pin(async { Ok(Vec::new()) });
+        }
+        Box::pin(Self::list_tools(self))
+    }
+
+    fn call_tool(&self, name: String, arguments: serde_json::Value) -> BoxFut<'_, CallToolResult> {
+        Box::pin(Self::call_tool(self, name, arguments))
+    }
+
+    fn list_resources(&self) -> BoxFut<'_, Vec<Resource>> {
+        if !self.has_resources() {
+            return Box::pin(async { Ok(Vec::new()) });
+        }
+        Box::pin(Self::list_resources(self))
+    }
+
+    fn read_resource(&self, uri: String) -> BoxFut<'_, Vec<ResourceContents>> {
+        Box::pin(Self::read_resource(self, uri))
+    }
+
+    fn list_prompts(&self) -> BoxFut<'_, Vec<Prompt>> {
+        if !self.has_prompts() {
+            return Box::pin(async { Ok(Vec::new()) });
+        }
+        Box::pin(Self::list_prompts(self))
+    }
+
+    fn get_prompt(
+        &self,
+        name: String,
+        arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> BoxFut<'_, GetPromptResult> {
+        Box::pin(Self::get_prompt(self, name, arguments))
+    }
+
+    fn close(self: Box<Self>) -> BoxFut<'static, ()> {
+        Box::pin(Self::close(*self))
+    }
+}
+
+/// Where an exposed name is served: the server's origin and its own name.
+#[derive(Debug, Clone)]
+struct Route {
+    origin: String,
+    name: String,
+}
+
+/// Exposed names and URIs mapped to the servers that own them, as of the
+/// last listing.
+#[derive(Debug, Default)]
+struct Routes {
+    tools: HashMap<String, Route>,
+    prompts: HashMap<String, Route>,
+    resources: HashMap<String, String>,
+}
+
+/// State shared between a mux and the handlers of its clients.
+struct Shared {
+    events: broadcast::Sender<MuxEvent>,
+    routes: RwLock<Routes>,
+}
+
+impl Shared {
+    fn routes(&self) -> std::sync::RwLockWriteGuard<'_, Routes> {
+        self.routes.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Several MCP clients presented as one.
+///
+/// Add servers with [`connect`](Self::connect), or build clients yourself
+/// with [`handler`](Self::handler) and register them with
+/// [`add`](Self::add). Listing methods query every server concurrently and
+/// refresh the routing table used by the call methods; a call for a name not
+/// yet seen triggers one fresh listing before failing.
+pub struct ClientMux {
+    /// Servers in the order they were added, which decides clashes.
+    backends: Vec<(String, Box<dyn Backend>)>,
+    prefixing: Prefixing,
+    separator: String,
+    shared: Arc<Shared>,
+}
+
+impl Default for ClientMux {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientMux {
+    /// Create an empty mux using [`Prefixing::OnCollision`] and
+    /// [`DEFAULT_SEPARATOR`].
+    #[must_use]
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            backends: Vec::new(),
+            prefixing: Prefixing::default(),
+            separator: DEFAULT_SEPARATOR.to_string(),
+            shared: Arc::new(Shared {
+                events,
+                routes: RwLock::new(Routes::default()),
+            }),
+        }
+    }
+
+    /// Set when names are prefixed with their server's origin.
+    #[must_use]
+    pub const fn with_prefixing(mut self, prefixing: Prefixing) -> Self {
+        self.prefixing = prefixing;
+        self
+    }
+
+    /// Set the separator between origin and name (default `__`).
+    #[must_use]
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// A client handler that forwards the server's notifications to this
+    /// mux's subscribers, tagged with `origin`.
+    ///
+    /// Server-initiated requests are rejected, as by [`NoOpHandler`]; use
+    /// [`handler_with`](Self::handler_with) to answer them. Register the
+    /// client under the same origin.
+    #[must_use]
+    pub fn handler(&self, origin: impl Into<String>) -> MuxHandler {
+        self.handler_with(origin, NoOpHandler)
+    }
+
+    /// Like [`handler`](Self::handler), delegating server-initiated requests
+    /// and notifications to `inner` as well.
+    #[must_use]
+    pub fn handler_with<H: ClientHandler>(
+        &self,
+        origin: impl Into<String>,
+        inner: H,
+    ) -> MuxHandler<H> {
+        MuxHandler {
+            origin: origin.into(),
+            shared: Arc::clone(&self.shared),
+            inner,
+        }
+    }
+
+    /// Connect to a server and add it under `origin`, with a
+    /// [`handler`](Self::handler) forwarding its notifications.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `origin` is already taken or the handshake fails.
+    pub async fn connect<T: Transport + 'static>(
+        &mut self,
+        origin: impl Into<String>,
+        builder: ClientBuilder,
+        transport: T,
+    ) -> Result<(), McpError> {
+        let origin = origin.into();
+        self.check_origin(&origin)?;
+        let client = builder
+            .build_with_handler(transport, self.handler(origin.clone()))
+            .await?;
+        self.add(origin, client)
+    }
+
+    /// Add a connected client under `origin`.
+    ///
+    /// Its notifications reach [`subscribe`](Self::subscribe) receivers only
+    /// if it was built with this mux's [`handler`](Self::handler).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `origin` is empty or already taken.
+    pub fn add<T: Transport + 'static, H: ClientHandler + 'static>(
+        &mut self,
+        origin: impl Into<String>,
+        client: Client<T, H>,
+    ) -> Result<(), McpError> {
+        self.insert(origin.into(), Box::new(client))
+    }
+
+    fn insert(&mut self, origin: String, backend: Box<dyn Backend>) -> Result<(), McpError> {
+        self.check_origin(&origin)?;
+        self.backends.push((origin, backend));
+        Ok(())
+    }
+
+    fn check_origin(&self, origin: &str) -> Result<(), McpError> {
+        if origin.is_empty() {
+            return Err(McpError::invalid_request("mux origin must not be empty"));
+        }
+        if self.backends.iter().any(|(existing, _)| existing == origin) {
+            return Err(McpError::invalid_request(format!(
+                "mux origin '{origin}' is already registered"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Remove the server registered under `origin`, returning whether one was.
+    ///
+    /// Its client is dropped; use [`close`](Self::close) for an orderly
+    /// shutdown of every server.
+    pub fn remove(&mut self, origin: &str) -> bool {
+        let before = self.backends.len();
+        self.backends.retain(|(existing, _)| existing != origin);
+        let mut routes = self.shared.routes();
+        routes.tools.retain(|_, route| route.origin != origin);
+        routes.prompts.retain(|_, route| route.origin != origin);
+        routes.resources.retain(|_, owner| owner != origin);
+        self.backends.len() != before
+    }
+
+    /// Origins of the registered servers, in the order they were added.
+    #[must_use]
+    pub fn origins(&self) -> Vec<&str> {
+        self.backends
+            .iter()
+            .map(|(origin, _)| origin.as_str())
+            .collect()
+    }
+
+    /// Number of registered servers.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.backends.len()
+    }
+
+    /// Whether no servers are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.backends.is_empty()
+    }
+
+    /// Receive the notifications of every server built with this mux's
+    /// [`handler`](Self::handler), tagged with their origin.
+    ///
+    /// Each receiver sees events sent after it subscribed. A receiver that
+    /// falls more than 256 events behind skips the oldest.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<MuxEvent> {
+        self.shared.events.subscribe()
+    }
+
+    /// The origin and server-side name behind an exposed tool name, as of
+    /// the last [`list_tools`](Self::list_tools).
+    #[must_use]
+    pub fn resolve_tool(&self, name: &str) -> Option<(String, String)> {
+        let routes = self.shared.routes();
+        routes
+            .tools
+            .get(name)
+            .map(|route| (route.origin.clone(), route.name.clone()))
+    }
+
+    /// List the tools of every server under their exposed names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if every server failed to list; otherwise a
+    /// failing server is logged and its tools are omitted.
+    pub async fn list_tools(&self) -> Result<Vec<Tool>, McpError> {
+        let listed = self.list_each("tools", |b| b.list_tools()).await?;
+        let (tools, routes) = self.expose(listed, |tool| &mut tool.name);
+        self.shared.routes().tools = routes;
+        Ok(tools)
+    }
+
+    /// Call a tool by its exposed name on the server that owns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error for an unknown tool, or the owning
+    /// server's error.
+    pub async fn call_tool(
+        &self,
+        name: impl Into<String>,
+        arguments: serde_json::Value,
+    ) -> Result<CallToolResult, McpError> {
+        let name = name.into();
+        let route = if let Some(route) = self.route(&name, |routes| &routes.tools) {
+            route
+        } else {
+            self.list_tools().await?;
+            self.route(&name, |routes| &routes.tools).ok_or_else(|| {
+                McpError::invalid_params("tools/call", format!("Unknown tool: {name}"))
+            })?
+        };
+        self.backend(&route.origin)?
+            .call_tool(route.name, arguments)
+            .await
+    }
+
+    /// List the prompts of every server under their exposed names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if every server failed to list; otherwise a
+    /// failing server is logged and its prompts are omitted.
+    pub async fn list_prompts(&self) -> Result<Vec<Prompt>, McpError> {
+        let listed = self.list_each("prompts", |b| b.list_prompts()).await?;
+        let (prompts, routes) = self.expose(listed, |prompt| &mut prompt.name);
+        self.shared.routes().prompts = routes;
+        Ok(prompts)
+    }
+
+    /// Get a prompt by its exposed name from the server that owns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error for an unknown prompt, or the owning
+    /// server's error.
+    pub async fn get_prompt(
+        &self,
+        name: impl Into<String>,
+        arguments: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<GetPromptResult, McpError> {
+        let name = name.into();
+        let route = if let Some(route) = self.route(&name, |routes| &routes.prompts) {
+            route
+        } else {
+            self.list_prompts().await?;
+            self.route(&name, |routes| &routes.prompts).ok_or_else(|| {
+                McpError::invalid_params("prompts/get", format!("Unknown prompt: {name}"))
+            })?
+        };
+        self.backend(&route.origin)?
+            .get_prompt(route.name, arguments)
+            .await
+    }
+
+    /// List the resources of every server.
+    ///
+    /// URIs are kept as the servers report them; a URI listed by several
+    /// servers is listed (and served) once, by the server added first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if every server failed to list; otherwise a
+    /// failing server is logged and its resources are omitted.
+    pub async fn list_resources(&self) -> Result<Vec<Resource>, McpError> {
+        let listed = self.list_each("resources", |b| b.list_resources()).await?;
+        let mut routes = HashMap::new();
+        let mut resources = Vec::new();
+        for (origin, listing) in listed {
+            for resource in listing {
+                if let Some(owner) = routes.get(&resource.uri) {
+                    warn!(uri = %resource.uri, %owner, shadowed = %origin, "Duplicate resource URI");
+                    continue;
+                }
+                routes.insert(resource.uri.clone(), origin.clone());
+                resources.push(resource);
+            }
+        }
+        self.shared.routes().resources = routes;
+        Ok(resources)
+    }
+
+    /// Read a resource from the server that listed its URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns a resource-not-found error for a URI no server lists, or the
+    /// owning server's error.
+    pub async fn read_resource(
+        &self,
+        uri: impl Into<String>,
+    ) -> Result<Vec<ResourceContents>, McpError> {
+        let uri = uri.into();
+        let owner = |mux: &Self| mux.shared.routes().resources.get(&uri).cloned();
+        let origin = if let Some(origin) = owner(self) {
+            origin
+        } else {
+            self.list_resources().await?;
+            owner(self).ok_or_else(|| McpError::resource_not_found(uri.clone()))?
+        };
+        self.backend(&origin)?.read_resource(uri).await
+    }
+
+    /// Close every server's connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error encountered; every connection is closed
+    /// regardless.
+    pub async fn close(self) -> Result<(), McpError> {
+        let results = join_all(self.backends.into_iter().map(|(_, b)| b.close())).await;
+        results.into_iter().collect()
+    }
+
+    /// Run a listing on every server concurrently, pairing results with
+    /// origins and dropping (with a warning) the servers that failed.
+    async fn list_each<'a, T>(
+        &'a self,
+        what: &str,
+        list: impl Fn(&'a dyn Backend) -> BoxFut<'a, Vec<T>>,
+    ) -> Result<Vec<(String, Vec<T>)>, McpError> {
+        let results = join_all(self.backends.iter().map(|(_, b)| list(b.as_ref()))).await;
+        let mut listed = Vec::with_capacity(results.len());
+        let mut last_error = None;
+        for ((origin, _), result) in self.backends.iter().zip(results) {
+            match result {
+                Ok(items) => listed.push((origin.clone(), items)),
+                Err(error) => {
+                    warn!(%origin, %error, "Failed to list {what}");
+                    last_error = Some(error);
+                }
+            }
+        }
+        match last_error {
+            Some(error) if listed.is_empty() => Err(error),
+            _ => Ok(listed),
+        }
+    }
+
+    /// Give each listed item its exposed name under the prefixing policy,
+    /// returning the items and the routes back to their servers.
+    fn expose<T>(
+        &self,
+        mut listed: Vec<(String, Vec<T>)>,
+        name_of: impl Fn(&mut T) -> &mut String,
+    ) -> (Vec<T>, HashMap<String, Route>) {
+        // How many entries carry each name, across all servers.
+        let mut offered: HashMap<String, usize> = HashMap::new();
+        for (_, items) in &mut listed {
+            for item in items.iter_mut() {
+                *offered.entry(name_of(item).clone()).or_default() += 1;
+            }
+        }
+
+        let mut routes = HashMap::new();
+        let mut exposed = Vec::new();
+        for (origin, items) in listed {
+            for mut item in items {
+                let name = name_of(&mut item);
+                let prefix = match self.prefixing {
+                    Prefixing::Always => true,
+                    Prefixing::OnCollision => offered[name.as_str()] > 1,
+                    Prefixing::Never => false,
+                };
+                let original = name.clone();
+                if prefix {
+                    *name = format!("{origin}{}{original}", self.separator);
+                }
+                if let Some(owner) = routes.get(name.as_str()).map(|r: &Route| &r.origin) {
+                    warn!(name = %name, %owner, shadowed = %origin, "Duplicate name across servers");
+                    continue;
+                }
+                routes.insert(
+                    name.clone(),
+                    Route {
+                        origin: origin.clone(),
+                        name: original,
+                    },
+                );
+                exposed.push(item);
+            }
+        }
+        (exposed, routes)
+    }
+
+    fn route(
+        &self,
+        name: &str,
+        table: impl Fn(&Routes) -> &HashMap<String, Route>,
+    ) -> Option<Route> {
+        table(&self.shared.routes()).get(name).cloned()
+    }
+
+    fn backend(&self, origin: &str) -> Result<&dyn Backend, McpError> {
+        self.backends
+            .iter()
+            .find(|(existing, _)| existing == origin)
+            .map(|(_, backend)| backend.as_ref())
+            .ok_or_else(|| McpError::invalid_request(format!("mux origin '{origin}' was removed")))
+    }
+}
+
+impl fmt::Debug for ClientMux {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientMux")
+            .field("origins", &self.origins())
+            .field("prefixing", &self.prefixing)
+            .field("separator", &self.separator)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Client handler that forwards a server's notifications to a [`ClientMux`].
+///
+/// Created with [`ClientMux::handler`] or [`ClientMux::handler_with`].
+/// Server-initiated requests and every notification are also passed to the
+/// wrapped handler. A list-changed notification drops the mux's cached routes
+/// for that kind, so the next call re-lists.
+pub struct MuxHandler<H = NoOpHandler> {
+    origin: String,
+    shared: Arc<Shared>,
+    inner: H,
+}
+
+impl<H> MuxHandler<H> {
+    /// The origin this handler tags notifications with.
+    #[must_use]
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    fn emit(&self, kind: MuxEventKind) {
+        // No subscribers is not an error: the events are simply unobserved.
+        let _ = self.shared.events.send(MuxEvent {
+            origin: self.origin.clone(),
+            kind,
+        });
+    }
+}
+
+impl<H: fmt::Debug> fmt::Debug for MuxHandler<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MuxHandler")
+            .field("origin", &self.origin)
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: ClientHandler> ClientHandler for MuxHandler<H> {
+    fn create_message(
+        &self,
+        request: CreateMessageRequest,
+        ctx: &RequestContext,
+    ) -> impl Future<Output = Result<CreateMessageResult, McpError>> + Send {
+        self.inner.create_message(request, ctx)
+    }
+
+    fn elicit(
+        &self,
+        request: ElicitRequest,
+    ) -> impl Future<Output = Result<ElicitResult, McpError>> + Send {
+        self.inner.elicit(request)
+    }
+
+    fn elicit_url(
+        &self,
+        request: UrlElicitRequest,
+    ) -> impl Future<Output = Result<ElicitResult, McpError>> + Send {
+        self.inner.elicit_url(request)
+    }
+
+    fn on_elicitation_complete(&self, elicitation_id: String) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::ElicitationComplete {
+            elicitation_id: elicitation_id.clone(),
+        });
+        self.inner.on_elicitation_complete(elicitation_id)
+    }
+
+    fn list_roots(&self) -> impl Future<Output = Result<Vec<Root>, McpError>> + Send {
+        self.inner.list_roots()
+    }
+
+    fn on_connected(&self) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::Connected);
+        self.inner.on_connected()
+    }
+
+    fn on_disconnected(&self) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::Disconnected);
+        self.inner.on_disconnected()
+    }
+
+    fn on_reconnected(&self) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::Reconnected);
+        self.inner.on_reconnected()
+    }
+
+    fn on_progress(&self, params: ProgressNotificationParams) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::Progress(params.clone()));
+        self.inner.on_progress(params)
+    }
+
+    fn on_task_progress(
+        &self,
+        task_id: TaskId,
+        progress: TaskProgress,
+    ) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::TaskProgress {
+            task_id: task_id.clone(),
+            progress: progress.clone(),
+        });
+        self.inner.on_task_progress(task_id, progress)
+    }
+
+    fn on_task_status(&self, task: Task) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::TaskStatus(task.clone()));
+        self.inner.on_task_status(task)
+    }
+
+    fn on_resource_updated(&self, uri: String) -> impl Future<Output = ()> + Send {
+        self.emit(MuxEventKind::ResourceUpdated { uri: uri.clone() });
+        self.inner.on_resource_updated(uri)
+    }
+
+    fn on_resources_list_changed(&self) -> impl Future<Output = ()> + Send {
+        self.shared.routes().resources.clear();
+        self.emit(MuxEventKind::ResourcesListChanged);
+        self.inner.on_resources_list_changed()
+    }
+
+    fn on_tools_list_changed(&self) -> impl Future<Output = ()> + Send {
+        self.shared.routes().tools.clear();
+        self.emit(MuxEventKind::ToolsListChanged);
+        self.inner.on_tools_list_changed()
+    }
+
+    fn on_prompts_list_changed(&self) -> impl Future<Output = ()> + Send {
+        self.shared.routes().prompts.clear();
+        self.emit(MuxEventKind::PromptsListChanged);
+        self.inner.on_prompts_list_changed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::types::TaskStatus;
+    use std::sync::Mutex;
+
+    /// A backend with fixed listings that records the calls it receives.
+    #[derive(Default)]
+    struct Fake {
+        tools: Vec<&'static str>,
+        resources: Vec<&'static str>,
+        fail: bool,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Backend for Fake {
+        fn list_tools(&self) -> BoxFut<'_, Vec<Tool>> {
+            let listed = if self.fail {
+                Err(McpError::internal("down"))
+            } else {
+                Ok(self.tools.iter().map(|name| Tool::new(*name)).collect())
+            };
+            Box::pin(async move { listed })
+        }
+
+        fn call_tool(&self, name: String, _: serde_json::Value) -> BoxFut<'_, CallToolResult> {
+            self.calls.lock().unwrap().push(name.clone());
+            Box::pin(async move { Ok(CallToolResult::text(name)) })
+        }
+
+        fn list_resources(&self) -> BoxFut<'_, Vec<Resource>> {
+            let listed = self
+                .resources
+                .iter()
+                .map(|uri| Resource::new(*uri, *uri))
+                .collect();
+            Box::pin(async move { Ok(listed) })
+        }
+
+        fn read_resource(&self, uri: String) -> BoxFut<'_, Vec<ResourceContents>> {
+            self.calls.lock().unwrap().push(uri.clone());
+            Box::pin(async move { Ok(vec![ResourceContents::text(uri, "body")]) })
+        }
+
+        fn list_prompts(&self) -> BoxFut<'_, Vec<Prompt>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+
+        fn get_prompt(
+            &self,
+            name: String,
+            _: Option<serde_json::Map<String, serde_json::Value>>,
+        ) -> BoxFut<'_, GetPromptResult> {
+            Box::pin(async move { Err(McpError::invalid_params("prompts/get", name)) })
+        }
+
+        fn close(self: Box<Self>) -> BoxFut<'static, ()> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    fn fake(tools: &[&'static str]) -> (Box<dyn Backend>, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::default();
+        let backend = Fake {
+            tools: tools.to_vec(),
+            calls: Arc::clone(&calls),
+            ..Fake::default()
+        };
+        (Box::new(backend), calls)
+    }
+
+    fn mux(prefixing: Prefixing) -> (ClientMux, Arc<Mutex<Vec<String>>>, Arc<Mutex<Vec<String>>>) {
+        let mut mux = ClientMux::new().with_prefixing(prefixing);
+        let (github, github_calls) = fake(&["search", "create_issue"]);
+        let (files, files_calls) = fake(&["search", "read_file"]);
+        mux.insert("github".into(), github).unwrap();
+        mux.insert("files".into(), files).unwrap();
+        (mux, github_calls, files_calls)
+    }
+
+    async fn names(mux: &ClientMux) -> Vec<String> {
+        let tools = mux.list_tools().await.unwrap();
+        tools.into_iter().map(|tool| tool.name).collect()
+    }
+
+    #[tokio::test]
+    async fn prefixing_policies_name_tools() {
+        let (collide, _, _) = mux(Prefixing::OnCollision);
+        assert_eq!(
+            names(&collide).await,
+            [
+                "github__search",
+                "create_issue",
+                "files__search",
+                "read_file"
+            ]
+        );
+
+        let (always, _, _) = mux(Prefixing::Always);
+        let always = always.with_separator(".");
+        assert_eq!(
+            names(&always).await,
+            [
+                "github.search",
+                "github.create_issue",
+                "files.search",
+                "files.read_file"
+            ]
+        );
+
+        let (never, _, _) = mux(Prefixing::Never);
+        assert_eq!(names(&never).await, ["search", "create_issue", "read_file"]);
+        assert_eq!(
+            never.resolve_tool("search"),
+            Some(("github".to_string(), "search".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_are_routed_by_exposed_name() {
+        let (mux, github, files) = mux(Prefixing::OnCollision);
+        // Routes are learned on first use, without an explicit listing.
+        mux.call_tool("files__search", serde_json::Value::Null)
+            .await
+            .unwrap();
+        mux.call_tool("create_issue", serde_json::Value::Null)
+            .await
+            .unwrap();
+        assert_eq!(*files.lock().unwrap(), ["search"]);
+        assert_eq!(*github.lock().unwrap(), ["create_issue"]);
+
+        let err = mux
+            .call_tool("search", serde_json::Value::Null)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown tool"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn failing_servers_are_skipped_unless_all_fail() {
+        let mut mux = ClientMux::new();
+        let (ok, _) = fake(&["search"]);
+        let down = || {
+            Box::new(Fake {
+                fail: true,
+                ..Fake::default()
+            })
+        };
+        mux.insert("ok".into(), ok).unwrap();
+        mux.insert("down".into(), down()).unwrap();
+        assert_eq!(names(&mux).await, ["search"]);
+
+        let mut all_down = ClientMux::new();
+        all_down.insert("a".into(), down()).unwrap();
+        all_down.insert("b".into(), down()).unwrap();
+        assert!(all_down.list_tools().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn resources_keep_uris_and_first_server_wins() {
+        let mut mux = ClientMux::new();
+        let first_calls = Arc::default();
+        let first = Fake {
+            resources: vec!["file:///shared", "file:///a"],
+            calls: Arc::clone(&first_calls),
+            ..Fake::default()
+        };
+        let second = Fake {
+            resources: vec!["file:///shared", "file:///b"],
+            ..Fake::default()
+        };
+        mux.insert("first".into(), Box::new(first)).unwrap();
+        mux.insert("second".into(), Box::new(second)).unwrap();
+
+        let uris: Vec<String> = mux
+            .list_resources()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.uri)
+            .collect();
+        assert_eq!(uris, ["file:///shared", "file:///a", "file:///b"]);
+
+        mux.read_resource("file:///shared").await.unwrap();
+        assert_eq!(*first_calls.lock().unwrap(), ["file:///shared"]);
+        assert!(mux.read_resource("file:///missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn origins_are_unique_and_removable() {
+        let (mut mux, _, _) = mux(Prefixing::OnCollision);
+        let (again, _) = fake(&[]);
+        assert!(mux.insert("github".into(), again).is_err());
+        assert_eq!(mux.origins(), ["github", "files"]);
+
+        mux.list_tools().await.unwrap();
+        assert!(mux.remove("github"));
+        assert!(!mux.remove("github"));
+        assert!(mux.resolve_tool("create_issue").is_none());
+        // With one server left, `search` no longer collides.
+        assert_eq!(names(&mux).await, ["search", "read_file"]);
+    }
+
+    #[tokio::test]
+    async fn handler_tags_notifications_and_drops_stale_routes() {
+        let (mux, _, _) = mux(Prefixing::OnCollision);
+        let mut events = mux.subscribe();
+        mux.list_tools().await.unwrap();
+        assert!(mux.resolve_tool("read_file").is_some());
+
+        let handler = mux.handler("files");
+        handler.on_tools_list_changed().await;
+        handler
+            .on_task_status(Task {
+                status: TaskStatus::Completed,
+                ..Task::new(TaskId::from("t-1"))
+            })
+            .await;
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.origin, "files");
+        assert!(matches!(event.kind, MuxEventKind::ToolsListChanged));
+        assert!(mux.resolve_tool("read_file").is_none());
+
+        let event = events.try_recv().unwrap();
+        assert!(
+            matches!(event.kind, MuxEventKind::TaskStatus(task) if task.status == TaskStatus::Completed)
+        );
+    }
+}
@@ -2,6 +2,14 @@
 //!
 //! This module provides connection pooling for MCP clients,
 //! allowing efficient reuse of connections to MCP servers.
+//!
+//! A pool can also balance load across replicas of a server. Give the
+//! builder the replicas' endpoints and a [`CheckoutStrategy`], then call
+//! [`ClientPool::checkout`] with a function that connects to an endpoint.
+//! An endpoint whose connection fails is skipped for
+//! [`PoolConfig::endpoint_cooldown`]. With a
+//! [`PoolConfig::health_check_interval`], idle connections are pinged in the
+//! background and evicted when they stop answering.
 
 use crate::builder::ClientBuilder;
 use crate::client::Client;
@@ -10,11 +18,24 @@ use mcpkit_core::error::McpError;
 use mcpkit_transport::Transport;
 use std::collections::HashMap;
 use std::future::Future;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
 use tracing::{debug, trace, warn};
 
 // Pool is tokio-specific due to spawn and timeout requirements
-use tokio::sync::{Mutex, Semaphore};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+
+/// How [`ClientPool::checkout`] picks an endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CheckoutStrategy {
+    /// Take endpoints in turn.
+    #[default]
+    RoundRobin,
+    /// Take the endpoint with the fewest checked-out clients, breaking ties
+    /// in round-robin order.
+    LeastInFlight,
+}
 
 /// Configuration for a client connection pool.
 #[derive(Debug, Clone)]
@@ -27,6 +48,17 @@ pub struct PoolConfig {
     pub validate_on_acquire: bool,
     /// Maximum idle time before a connection is closed.
     pub max_idle_time: std::time::Duration,
+    /// How [`ClientPool::checkout`] picks an endpoint.
+    pub strategy: CheckoutStrategy,
+    /// How often idle connections are pinged, or `None` to not check them
+    /// in the background.
+    pub health_check_interval: Option<std::time::Duration>,
+    /// How long a health-check ping may take before the connection is
+    /// evicted.
+    pub health_check_timeout: std::time::Duration,
+    /// How long [`ClientPool::checkout`] avoids an endpoint after a failed
+    /// connection or health check.
+    pub endpoint_cooldown: std::time::Duration,
 }
 
 impl Default for PoolConfig {
@@ -36,6 +68,10 @@ impl Default for PoolConfig {
             acquire_timeout: std::time::Duration::from_secs(30),
             validate_on_acquire: true,
             max_idle_time: std::time::Duration::from_secs(300),
+            strategy: CheckoutStrategy::RoundRobin,
+            health_check_interval: None,
+            health_check_timeout: std::time::Duration::from_secs(5),
+            endpoint_cooldown: std::time::Duration::from_secs(30),
         }
     }
 }
@@ -74,6 +110,34 @@ impl PoolConfig {
         self.max_idle_time = time;
         self
     }
+
+    /// Set the checkout strategy.
+    #[must_use]
+    pub const fn strategy(mut self, strategy: CheckoutStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Ping idle connections at this interval and evict those that fail.
+    #[must_use]
+    pub const fn health_check_interval(mut self, interval: std::time::Duration) -> Self {
+        self.health_check_interval = Some(interval);
+        self
+    }
+
+    /// Set the health-check ping timeout.
+    #[must_use]
+    pub const fn health_check_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.health_check_timeout = timeout;
+        self
+    }
+
+    /// Set how long a failing endpoint is avoided.
+    #[must_use]
+    pub const fn endpoint_cooldown(mut self, cooldown: std::time::Duration) -> Self {
+        self.endpoint_cooldown = cooldown;
+        self
+    }
 }
 
 /// A pooled client connection.
@@ -83,6 +147,8 @@ pub struct PooledClient<T: Transport + 'static> {
     client: Option<Client<T>>,
     pool: Arc<ClientPoolInner<T>>,
     key: String,
+    server: Arc<ServerState>,
+    permit: Option<OwnedSemaphorePermit>,
 }
 
 impl<T: Transport + 'static> PooledClient<T> {
@@ -92,6 +158,7 @@ impl<T: Transport + 'static> PooledClient<T> {
     ///
     /// Panics if the client was already dropped or taken. This should never
     /// happen in normal use - the panic indicates a bug in the pool implementation.
+    #[must_use]
     pub fn client(&self) -> &Client<T> {
         self.client.as_ref().expect("Client already dropped")
     }
@@ -105,6 +172,22 @@ impl<T: Transport + 'static> PooledClient<T> {
     pub fn client_mut(&mut self) -> &mut Client<T> {
         self.client.as_mut().expect("Client already dropped")
     }
+
+    /// The key, or endpoint, this client is connected to.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Close the connection instead of returning it to the pool.
+    ///
+    /// Use this when the connection is known to be broken.
+    pub fn discard(mut self) {
+        if self.client.take().is_some() {
+            self.pool.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+            debug!(key = %self.key, "Discarded pooled connection");
+        }
+    }
 }
 
 impl<T: Transport + 'static> std::ops::Deref for PooledClient<T> {
@@ -123,12 +206,16 @@ impl<T: Transport + 'static> std::ops::DerefMut for PooledClient<T> {
 
 impl<T: Transport + 'static> Drop for PooledClient<T> {
     fn drop(&mut self) {
+        self.server.in_flight.fetch_sub(1, Ordering::SeqCst);
         if let Some(client) = self.client.take() {
-            // Return the connection to the pool
+            // Return the connection to the pool, holding the permit until
+            // it is back so the next acquire can reuse it
             let pool = Arc::clone(&self.pool);
             let key = self.key.clone();
+            let permit = self.permit.take();
             tokio::spawn(async move {
                 pool.return_connection(key, client).await;
+                drop(permit);
             });
         }
     }
@@ -140,12 +227,80 @@ struct ClientPoolInner<T: Transport> {
     config: PoolConfig,
     /// Available connections by server key.
     connections: Mutex<HashMap<String, Vec<PooledEntry<T>>>>,
-    /// Semaphore for limiting concurrent connections.
-    semaphores: Mutex<HashMap<String, Arc<Semaphore>>>,
+    /// Per-server limits and load, by server key.
+    servers: Mutex<HashMap<String, Arc<ServerState>>>,
     /// Client info to use for new connections.
     client_info: ClientInfo,
     /// Client capabilities.
     client_caps: ClientCapabilities,
+    /// Endpoints that `checkout` balances across.
+    endpoints: Vec<String>,
+    /// Round-robin position for `checkout`.
+    next_endpoint: AtomicUsize,
+    /// Whether the background health-check task has been started.
+    health_task_started: AtomicBool,
+    /// Pool metrics.
+    metrics: MetricsInner,
+}
+
+/// Limits and load for one server key.
+struct ServerState {
+    /// Semaphore for limiting concurrent connections.
+    semaphore: Arc<Semaphore>,
+    /// Number of clients currently checked out.
+    in_flight: AtomicUsize,
+    /// `checkout` skips the server until this time after a failure.
+    down_until: std::sync::Mutex<Option<Instant>>,
+}
+
+impl ServerState {
+    fn is_down(&self) -> bool {
+        self.down_until
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    fn set_down_until(&self, until: Option<Instant>) {
+        *self
+            .down_until
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = until;
+    }
+}
+
+/// Counters behind [`PoolMetrics`].
+#[derive(Default)]
+struct MetricsInner {
+    checkouts: AtomicU64,
+    connections_created: AtomicU64,
+    connections_reused: AtomicU64,
+    evictions: AtomicU64,
+    failed_connects: AtomicU64,
+    health_checks: AtomicU64,
+    total_wait_nanos: AtomicU64,
+    max_wait_nanos: AtomicU64,
+}
+
+impl MetricsInner {
+    fn record_wait(&self, wait: Duration) {
+        let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+        self.total_wait_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_wait_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> PoolMetrics {
+        PoolMetrics {
+            checkouts: self.checkouts.load(Ordering::Relaxed),
+            connections_created: self.connections_created.load(Ordering::Relaxed),
+            connections_reused: self.connections_reused.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            failed_connects: self.failed_connects.load(Ordering::Relaxed),
+            health_checks: self.health_checks.load(Ordering::Relaxed),
+            total_wait: Duration::from_nanos(self.total_wait_nanos.load(Ordering::Relaxed)),
+            max_wait: Duration::from_nanos(self.max_wait_nanos.load(Ordering::Relaxed)),
+        }
+    }
 }
 
 /// An entry in the pool.
@@ -154,9 +309,14 @@ struct PooledEntry<T: Transport> {
     last_used: std::time::Instant,
 }
 
-impl<T: Transport> ClientPoolInner<T> {
+impl<T: Transport + 'static> ClientPoolInner<T> {
     /// Return a connection to the pool.
     async fn return_connection(&self, key: String, client: Client<T>) {
+        if !client.is_connected() {
+            debug!(%key, "Evicting closed connection");
+            self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
         trace!(%key, "Returning connection to pool");
 
         let entry = PooledEntry {
@@ -168,14 +328,222 @@ impl<T: Transport> ClientPoolInner<T> {
         connections.entry(key).or_insert_with(Vec::new).push(entry);
     }
 
-    /// Get a semaphore for rate limiting connections to a server.
-    async fn get_semaphore(&self, key: &str) -> Arc<Semaphore> {
-        let mut semaphores = self.semaphores.lock().await;
-        semaphores
+    /// Get the state for a server, creating it on first use.
+    async fn server(&self, key: &str) -> Arc<ServerState> {
+        let mut servers = self.servers.lock().await;
+        servers
             .entry(key.to_string())
-            .or_insert_with(|| Arc::new(Semaphore::new(self.config.max_connections)))
+            .or_insert_with(|| {
+                Arc::new(ServerState {
+                    semaphore: Arc::new(Semaphore::new(self.config.max_connections)),
+                    in_flight: AtomicUsize::new(0),
+                    down_until: std::sync::Mutex::new(None),
+                })
+            })
             .clone()
     }
+
+    /// Mark a server as failing, so `checkout` avoids it for a while.
+    fn mark_down(&self, key: &str, server: &ServerState) {
+        warn!(%key, cooldown = ?self.config.endpoint_cooldown, "Marking server as down");
+        server.set_down_until(Instant::now().checked_add(self.config.endpoint_cooldown));
+    }
+
+    /// Acquire a connection to `key`, reusing an idle one if possible.
+    async fn acquire<F, Fut>(
+        self: &Arc<Self>,
+        key: String,
+        connect: F,
+    ) -> Result<PooledClient<T>, McpError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, McpError>>,
+    {
+        let server = self.server(&key).await;
+
+        // Acquire a permit (with timeout)
+        let started = Instant::now();
+        let permit = tokio::time::timeout(
+            self.config.acquire_timeout,
+            Arc::clone(&server.semaphore).acquire_owned(),
+        )
+        .await;
+        // Timeouts count towards the wait time too.
+        self.metrics.record_wait(started.elapsed());
+        let permit = permit
+            .map_err(|_| McpError::Internal {
+                message: format!("Timeout acquiring connection for {key}"),
+                source: None,
+            })?
+            .map_err(|_| McpError::Internal {
+                message: "Pool semaphore closed".to_string(),
+                source: None,
+            })?;
+
+        let client = match self.take_idle(&key).await {
+            Some(client) => {
+                self.metrics
+                    .connections_reused
+                    .fetch_add(1, Ordering::Relaxed);
+                client
+            }
+            None => match self.connect(connect).await {
+                Ok(client) => {
+                    server.set_down_until(None);
+                    client
+                }
+                Err(e) => {
+                    self.metrics.failed_connects.fetch_add(1, Ordering::Relaxed);
+                    self.mark_down(&key, &server);
+                    return Err(e);
+                }
+            },
+        };
+
+        self.metrics.checkouts.fetch_add(1, Ordering::Relaxed);
+        server.in_flight.fetch_add(1, Ordering::SeqCst);
+        Ok(PooledClient {
+            client: Some(client),
+            pool: Arc::clone(self),
+            key,
+            server,
+            permit: Some(permit),
+        })
+    }
+
+    /// Take an idle connection to `key`, dropping stale and broken ones.
+    async fn take_idle(&self, key: &str) -> Option<Client<T>> {
+        let mut connections = self.connections.lock().await;
+        let entries = connections.get_mut(key)?;
+
+        // Remove stale connections
+        let max_idle = self.config.max_idle_time;
+        let before = entries.len();
+        entries.retain(|e| e.last_used.elapsed() < max_idle);
+        self.metrics
+            .evictions
+            .fetch_add((before - entries.len()) as u64, Ordering::Relaxed);
+
+        while let Some(entry) = entries.pop() {
+            trace!(%key, "Reusing existing connection");
+
+            // Optionally validate the connection
+            if !self.config.validate_on_acquire || entry.client.ping().await.is_ok() {
+                return Some(entry.client);
+            }
+            warn!(%key, "Cached connection failed validation");
+            self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        None
+    }
+
+    /// Open and initialize a new connection.
+    async fn connect<F, Fut>(&self, connect: F) -> Result<Client<T>, McpError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, McpError>>,
+    {
+        debug!("Creating new connection");
+        let transport = connect().await?;
+
+        let client = ClientBuilder::new()
+            .name(self.client_info.name.clone())
+            .version(self.client_info.version.clone())
+            .capabilities(self.client_caps.clone())
+            .build(transport)
+            .await?;
+        self.metrics
+            .connections_created
+            .fetch_add(1, Ordering::Relaxed);
+        Ok(client)
+    }
+
+    /// The endpoints to try for a checkout, best first.
+    async fn candidates(&self) -> Vec<(String, Arc<ServerState>)> {
+        let n = self.endpoints.len();
+        let start = self.next_endpoint.fetch_add(1, Ordering::Relaxed) % n;
+        let mut candidates = Vec::with_capacity(n);
+        for i in 0..n {
+            let endpoint = &self.endpoints[(start + i) % n];
+            candidates.push((endpoint.clone(), self.server(endpoint).await));
+        }
+        // Both sorts are stable, so ties keep the round-robin order.
+        if self.config.strategy == CheckoutStrategy::LeastInFlight {
+            candidates.sort_by_key(|(_, server)| server.in_flight.load(Ordering::SeqCst));
+        }
+        // Endpoints that recently failed are tried last, not skipped, so a
+        // checkout still succeeds if they have recovered.
+        candidates.sort_by_key(|(_, server)| server.is_down());
+        candidates
+    }
+
+    /// Ping every idle connection and evict those that fail or have been
+    /// idle too long. Returns the number evicted.
+    async fn health_check(&self) -> usize {
+        // Check outside the lock; connections returned meanwhile are kept.
+        let idle: Vec<(String, Vec<PooledEntry<T>>)> =
+            self.connections.lock().await.drain().collect();
+
+        let mut kept = Vec::new();
+        let mut evicted = 0;
+        for (key, entries) in idle {
+            for entry in entries {
+                if entry.last_used.elapsed() >= self.config.max_idle_time {
+                    evicted += 1;
+                    continue;
+                }
+                let ping =
+                    tokio::time::timeout(self.config.health_check_timeout, entry.client.ping())
+                        .await;
+                if matches!(ping, Ok(Ok(()))) {
+                    kept.push((key.clone(), entry));
+                } else {
+                    warn!(%key, "Pooled connection failed health check");
+                    evicted += 1;
+                    let server = self.server(&key).await;
+                    self.mark_down(&key, &server);
+                }
+            }
+        }
+
+        let mut connections = self.connections.lock().await;
+        for (key, entry) in kept {
+            connections.entry(key).or_default().push(entry);
+        }
+        drop(connections);
+
+        self.metrics.health_checks.fetch_add(1, Ordering::Relaxed);
+        self.metrics
+            .evictions
+            .fetch_add(evicted as u64, Ordering::Relaxed);
+        evicted
+    }
+
+    /// Start the background health-check task, if configured and not
+    /// already running. The task stops when the pool is dropped.
+    fn start_health_checks(self: &Arc<Self>) {
+        let Some(interval) = self.config.health_check_interval else {
+            return;
+        };
+        if self.health_task_started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let pool: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval.max(Duration::from_millis(1)));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                let evicted = pool.health_check().await;
+                trace!(evicted, "Pool health check complete");
+            }
+        });
+    }
 }
 
 /// A pool of client connections.
@@ -211,6 +579,33 @@ impl<T: Transport> ClientPoolInner<T> {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// To balance across replicas, configure endpoints and use
+/// [`checkout`](Self::checkout):
+///
+/// ```no_run
+/// use mcpkit_client::{CheckoutStrategy, ClientPool};
+/// use mcpkit_transport::{WebSocketConfig, WebSocketTransport};
+/// use mcpkit_core::error::McpError;
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), McpError> {
+/// let pool = ClientPool::<WebSocketTransport>::builder()
+///     .client_info("my-client", "1.0.0")
+///     .endpoints(["ws://replica-1:8080/mcp", "ws://replica-2:8080/mcp"])
+///     .strategy(CheckoutStrategy::LeastInFlight)
+///     .health_check_interval(Duration::from_secs(15))
+///     .build();
+///
+/// let client = pool
+///     .checkout(|endpoint| async move {
+///         Ok(WebSocketTransport::connect(WebSocketConfig::new(endpoint)).await?)
+///     })
+///     .await?;
+/// let tools = client.list_tools().await?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct ClientPool<T: Transport> {
     inner: Arc<ClientPoolInner<T>>,
 }
@@ -234,18 +629,40 @@ impl<T: Transport + 'static> ClientPool<T> {
         client_info: ClientInfo,
         client_caps: ClientCapabilities,
         config: PoolConfig,
+    ) -> Self {
+        Self::with_endpoints(client_info, client_caps, config, Vec::new())
+    }
+
+    /// Create a new pool that balances [`checkout`](Self::checkout) across
+    /// `endpoints`.
+    #[must_use]
+    pub fn with_endpoints(
+        client_info: ClientInfo,
+        client_caps: ClientCapabilities,
+        config: PoolConfig,
+        endpoints: Vec<String>,
     ) -> Self {
         Self {
             inner: Arc::new(ClientPoolInner {
                 config,
                 connections: Mutex::new(HashMap::new()),
-                semaphores: Mutex::new(HashMap::new()),
+                servers: Mutex::new(HashMap::new()),
                 client_info,
                 client_caps,
+                endpoints,
+                next_endpoint: AtomicUsize::new(0),
+                health_task_started: AtomicBool::new(false),
+                metrics: MetricsInner::default(),
             }),
         }
     }
 
+    /// The endpoints [`checkout`](Self::checkout) balances across.
+    #[must_use]
+    pub fn endpoints(&self) -> &[String] {
+        &self.inner.endpoints
+    }
+
     /// Acquire a connection from the pool.
     ///
     /// If a cached connection is available, it is returned. Otherwise,
@@ -270,73 +687,59 @@ impl<T: Transport + 'static> ClientPool<T> {
     {
         let key = key.into();
         debug!(%key, "Acquiring connection from pool");
+        self.inner.start_health_checks();
+        self.inner.acquire(key, connect).await
+    }
 
-        // Get the semaphore for rate limiting
-        let semaphore = self.inner.get_semaphore(&key).await;
+    /// Check out a client from one of the configured endpoints.
+    ///
+    /// The endpoint is picked by the [`CheckoutStrategy`], preferring
+    /// endpoints that have not failed recently. `connect` is called with
+    /// the endpoint when a new connection is needed; if it fails, the next
+    /// endpoint is tried.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no endpoints are configured, or the last error
+    /// if no endpoint could be reached.
+    pub async fn checkout<F, Fut>(&self, connect: F) -> Result<PooledClient<T>, McpError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T, McpError>>,
+    {
+        if self.inner.endpoints.is_empty() {
+            return Err(McpError::internal(
+                "Client pool has no endpoints configured",
+            ));
+        }
+        self.inner.start_health_checks();
 
-        // Acquire a permit (with timeout)
-        let _permit =
-            tokio::time::timeout(self.inner.config.acquire_timeout, semaphore.acquire_owned())
-                .await
-                .map_err(|_| McpError::Internal {
-                    message: format!("Timeout acquiring connection for {key}"),
-                    source: None,
-                })?
-                .map_err(|_| McpError::Internal {
-                    message: "Pool semaphore closed".to_string(),
-                    source: None,
-                })?;
-
-        // Try to get an existing connection
-        {
-            let mut connections = self.inner.connections.lock().await;
-            if let Some(entries) = connections.get_mut(&key) {
-                // Remove stale connections
-                let max_idle = self.inner.config.max_idle_time;
-                entries.retain(|e| e.last_used.elapsed() < max_idle);
-
-                // Get a connection if available
-                if let Some(entry) = entries.pop() {
-                    trace!(%key, "Reusing existing connection");
-
-                    // Optionally validate the connection
-                    if self.inner.config.validate_on_acquire {
-                        // Try to ping
-                        if entry.client.ping().await.is_ok() {
-                            return Ok(PooledClient {
-                                client: Some(entry.client),
-                                pool: Arc::clone(&self.inner),
-                                key,
-                            });
-                        }
-                        warn!(%key, "Cached connection failed validation");
-                    } else {
-                        return Ok(PooledClient {
-                            client: Some(entry.client),
-                            pool: Arc::clone(&self.inner),
-                            key,
-                        });
-                    }
-                }
+        let mut last_error = None;
+        for (endpoint, _) in self.inner.candidates().await {
+            debug!(%endpoint, "Checking out connection");
+            let connect_to = endpoint.clone();
+            match self.inner.acquire(endpoint, || connect(connect_to)).await {
+                Ok(client) => return Ok(client),
+                Err(e) => last_error = Some(e),
             }
         }
+        Err(last_error
+            .unwrap_or_else(|| McpError::internal("Client pool has no endpoints configured")))
+    }
 
-        // Create a new connection
-        debug!(%key, "Creating new connection");
-        let transport = connect().await?;
-
-        let client = ClientBuilder::new()
-            .name(self.inner.client_info.name.clone())
-            .version(self.inner.client_info.version.clone())
-            .capabilities(self.inner.client_caps.clone())
-            .build(transport)
-            .await?;
+    /// Ping every idle connection now and evict those that fail.
+    ///
+    /// This is what the background task started by
+    /// [`PoolConfig::health_check_interval`] runs. Returns the number of
+    /// connections evicted.
+    pub async fn health_check(&self) -> usize {
+        self.inner.health_check().await
+    }
 
-        Ok(PooledClient {
-            client: Some(client),
-            pool: Arc::clone(&self.inner),
-            key,
-        })
+    /// Get the pool's metrics.
+    #[must_use]
+    pub fn metrics(&self) -> PoolMetrics {
+        self.inner.metrics.snapshot()
     }
 
     /// Clear all cached connections.
@@ -392,11 +795,46 @@ pub struct PoolStats {
     pub max_connections: usize,
 }
 
+/// Counters describing a pool's activity since it was created.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolMetrics {
+    /// Clients handed out by `acquire` or `checkout`.
+    pub checkouts: u64,
+    /// New connections opened.
+    pub connections_created: u64,
+    /// Idle connections handed out again.
+    pub connections_reused: u64,
+    /// Connections dropped for being idle too long, failing validation or a
+    /// health check, closing, or being discarded.
+    pub evictions: u64,
+    /// Attempts to open a connection that failed.
+    pub failed_connects: u64,
+    /// Health-check passes run.
+    pub health_checks: u64,
+    /// Total time spent waiting for a connection slot, including waits that
+    /// timed out.
+    pub total_wait: Duration,
+    /// Longest time spent waiting for a connection slot.
+    pub max_wait: Duration,
+}
+
+impl PoolMetrics {
+    /// Average wait per checkout.
+    #[must_use]
+    pub fn average_wait(&self) -> Duration {
+        u32::try_from(self.checkouts)
+            .ok()
+            .filter(|&n| n > 0)
+            .map_or(Duration::ZERO, |n| self.total_wait / n)
+    }
+}
+
 /// Builder for creating a client pool.
 pub struct ClientPoolBuilder {
     config: PoolConfig,
     client_info: Option<ClientInfo>,
     client_caps: ClientCapabilities,
+    endpoints: Vec<String>,
 }
 
 impl ClientPoolBuilder {
@@ -407,6 +845,7 @@ impl ClientPoolBuilder {
             config: PoolConfig::default(),
             client_info: None,
             client_caps: ClientCapabilities::default(),
+            endpoints: Vec::new(),
         }
     }
 
@@ -456,6 +895,54 @@ impl ClientPoolBuilder {
         self
     }
 
+    /// Add an endpoint for [`ClientPool::checkout`] to balance across.
+    #[must_use]
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoints.push(endpoint.into());
+        self
+    }
+
+    /// Add several endpoints for [`ClientPool::checkout`] to balance across.
+    #[must_use]
+    pub fn endpoints<I, S>(mut self, endpoints: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.endpoints.extend(endpoints.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the checkout strategy.
+    #[must_use]
+    pub const fn strategy(mut self, strategy: CheckoutStrategy) -> Self {
+        self.config.strategy = strategy;
+        self
+    }
+
+    /// Ping idle connections at this interval and evict those that fail.
+    ///
+    /// The background task starts on the first `acquire` or `checkout`.
+    #[must_use]
+    pub const fn health_check_interval(mut self, interval: std::time::Duration) -> Self {
+        self.config.health_check_interval = Some(interval);
+        self
+    }
+
+    /// Set the health-check ping timeout.
+    #[must_use]
+    pub const fn health_check_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.config.health_check_timeout = timeout;
+        self
+    }
+
+    /// Set how long a failing endpoint is avoided.
+    #[must_use]
+    pub const fn endpoint_cooldown(mut self, cooldown: std::time::Duration) -> Self {
+        self.config.endpoint_cooldown = cooldown;
+        self
+    }
+
     /// Build the pool.
     ///
     /// # Panics
@@ -467,7 +954,7 @@ impl ClientPoolBuilder {
             .client_info
             .expect("client_info must be set before building pool");
 
-        ClientPool::with_config(client_info, self.client_caps, self.config)
+        ClientPool::with_endpoints(client_info, self.client_caps, self.config, self.endpoints)
     }
 }
 
@@ -480,6 +967,10 @@ impl Default for ClientPoolBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mcpkit_core::capability::{InitializeResult, ServerCapabilities, ServerInfo};
+    use mcpkit_core::error::JsonRpcError;
+    use mcpkit_core::protocol::{Message, Response};
+    use mcpkit_transport::MemoryTransport;
 
     #[test]
     fn test_pool_config() {
@@ -500,9 +991,188 @@ mod tests {
         let builder = ClientPoolBuilder::new()
             .client_info("test-client", "1.0.0")
             .max_connections(10)
-            .validate_on_acquire(true);
+            .validate_on_acquire(true)
+            .endpoint("a")
+            .endpoints(["b", "c"])
+            .strategy(CheckoutStrategy::LeastInFlight)
+            .health_check_interval(Duration::from_secs(15))
+            .endpoint_cooldown(Duration::from_secs(5));
 
         assert_eq!(builder.config.max_connections, 10);
         assert!(builder.config.validate_on_acquire);
+        assert_eq!(builder.endpoints, ["a", "b", "c"]);
+        assert_eq!(builder.config.strategy, CheckoutStrategy::LeastInFlight);
+        assert_eq!(
+            builder.config.health_check_interval,
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(builder.config.endpoint_cooldown.as_secs(), 5);
+    }
+
+    /// Connect to an in-memory server that answers `initialize`, and answers
+    /// `ping` while `alive` is set.
+    fn fake_server(alive: Arc<AtomicBool>) -> MemoryTransport {
+        let (client, server) = MemoryTransport::pair();
+        tokio::spawn(async move {
+            loop {
+                let request = match server.recv().await {
+                    Ok(Some(Message::Request(request))) => request,
+                    Ok(Some(_)) => continue,
+                    _ => break,
+                };
+                let response = match request.method.as_ref() {
+                    "initialize" => {
+                        let init = InitializeResult::new(
+                            ServerInfo::new("test-server", "1.0.0"),
+                            ServerCapabilities::new(),
+                        );
+                        Response::success(request.id, serde_json::to_value(init).unwrap())
+                    }
+                    "ping" if alive.load(Ordering::SeqCst) => {
+                        Response::success(request.id, serde_json::json!({}))
+                    }
+                    _ => Response::error(request.id, JsonRpcError::internal_error("down")),
+                };
+                if server.send(Message::Response(response)).await.is_err() {
+                    break;
+                }
+            }
+        });
+        client
+    }
+
+    fn test_pool(builder: ClientPoolBuilder) -> ClientPool<MemoryTransport> {
+        builder
+            .client_info("test-client", "1.0.0")
+            .validate_on_acquire(false)
+            .build()
+    }
+
+    async fn connect_ok(_endpoint: String) -> Result<MemoryTransport, McpError> {
+        Ok(fake_server(Arc::new(AtomicBool::new(true))))
+    }
+
+    /// Wait for dropped clients to make their way back to the pool.
+    async fn wait_for_idle(pool: &ClientPool<MemoryTransport>, count: usize) {
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while pool.stats().await.total_connections < count {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("connections were not returned to the pool");
+    }
+
+    #[tokio::test]
+    async fn round_robin_spreads_checkouts() -> Result<(), McpError> {
+        let pool = test_pool(ClientPoolBuilder::new().endpoints(["a", "b", "c"]));
+
+        let a = pool.checkout(connect_ok).await?;
+        let b = pool.checkout(connect_ok).await?;
+        let c = pool.checkout(connect_ok).await?;
+        let d = pool.checkout(connect_ok).await?;
+        assert_eq!([a.key(), b.key(), c.key(), d.key()], ["a", "b", "c", "a"]);
+
+        let metrics = pool.metrics();
+        assert_eq!(metrics.checkouts, 4);
+        assert_eq!(metrics.connections_created, 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn least_in_flight_picks_the_idlest_endpoint() -> Result<(), McpError> {
+        let pool = test_pool(
+            ClientPoolBuilder::new()
+                .endpoints(["a", "b"])
+                .strategy(CheckoutStrategy::LeastInFlight),
+        );
+
+        let a = pool.checkout(connect_ok).await?;
+        let b = pool.checkout(connect_ok).await?;
+        drop(b);
+        wait_for_idle(&pool, 1).await;
+
+        // Round robin would come back to `a`, which still has a client out.
+        let next = pool.checkout(connect_ok).await?;
+        assert_eq!(next.key(), "b");
+        assert_eq!(pool.metrics().connections_reused, 1);
+        drop(a);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failing_endpoints_are_skipped() -> Result<(), McpError> {
+        let pool = test_pool(ClientPoolBuilder::new().endpoints(["down", "up"]));
+        let connect = |endpoint: String| async move {
+            if endpoint == "down" {
+                Err(McpError::internal("connection refused"))
+            } else {
+                connect_ok(endpoint).await
+            }
+        };
+
+        let first = pool.checkout(connect).await?;
+        assert_eq!(first.key(), "up");
+        // `down` is now cooling off, so it is tried last even on its turn.
+        let second = pool.checkout(connect).await?;
+        assert_eq!(second.key(), "up");
+        assert_eq!(pool.metrics().failed_connects, 1);
+
+        let empty = test_pool(ClientPoolBuilder::new());
+        assert!(empty.checkout(connect_ok).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn health_check_evicts_dead_connections() -> Result<(), McpError> {
+        let pool = test_pool(ClientPoolBuilder::new());
+        let healthy = Arc::new(AtomicBool::new(true));
+        let dying = Arc::new(AtomicBool::new(true));
+
+        let first = pool
+            .acquire("healthy", || async {
+                Ok(fake_server(Arc::clone(&healthy)))
+            })
+            .await?;
+        let second = pool
+            .acquire("dying", || async { Ok(fake_server(Arc::clone(&dying))) })
+            .await?;
+        drop((first, second));
+        wait_for_idle(&pool, 2).await;
+
+        dying.store(false, Ordering::SeqCst);
+        assert_eq!(pool.health_check().await, 1);
+
+        let stats = pool.stats().await;
+        assert_eq!(stats.total_connections, 1);
+        assert_eq!(stats.connections_per_server.get("healthy"), Some(&1));
+        let metrics = pool.metrics();
+        assert_eq!(metrics.evictions, 1);
+        assert_eq!(metrics.health_checks, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn max_connections_bounds_checked_out_clients() -> Result<(), McpError> {
+        let pool = test_pool(
+            ClientPoolBuilder::new()
+                .max_connections(1)
+                .acquire_timeout(Duration::from_millis(50)),
+        );
+
+        let held = pool.acquire("server", || connect_ok(String::new())).await?;
+        assert!(
+            pool.acquire("server", || connect_ok(String::new()))
+                .await
+                .is_err()
+        );
+
+        held.discard();
+        let again = pool.acquire("server", || connect_ok(String::new())).await?;
+        assert_eq!(again.key(), "server");
+        let metrics = pool.metrics();
+        assert_eq!(metrics.evictions, 1);
+        assert!(metrics.max_wait >= Duration::from_millis(50));
+        Ok(())
     }
 }
@@ -0,0 +1,193 @@
+//! How a [`Client`](crate::Client) rides out a transport reconnect.
+//!
+//! Transports that reconnect on their own (WebSocket, streamable HTTP) report
+//! it through [`Transport::connection_epoch`](mcpkit_transport::Transport::connection_epoch).
+//! The client then re-runs `initialize` and replays its resource
+//! subscriptions. A [`ReconnectPolicy`], set with
+//! [`ClientBuilder::reconnect_policy`](crate::ClientBuilder::reconnect_policy),
+//! decides how hard it tries and what happens to requests that were in
+//! flight:
+//!
+//! - a failed `initialize` is retried with exponential backoff, up to
+//!   [`max_attempts`](ReconnectPolicy::max_attempts) times;
+//! - a lost request that is safe to repeat is resent, up to
+//!   [`max_replays`](ReconnectPolicy::max_replays) times. Safe means a
+//!   read-only method (`ping`, the `*/list` methods, `resources/read`,
+//!   `prompts/get`, ...) or a `tools/call` of a tool the server annotates as
+//!   idempotent or read-only;
+//! - any other lost request fails with
+//!   [`TransportErrorKind::Interrupted`](mcpkit_core::error::TransportErrorKind::Interrupted):
+//!   the server may or may not have acted on it, so the caller decides.
+//!
+//! ```no_run
+//! use mcpkit_client::{ClientBuilder, ReconnectPolicy};
+//! use std::time::Duration;
+//!
+//! let builder = ClientBuilder::new().reconnect_policy(
+//!     ReconnectPolicy::new()
+//!         .with_max_attempts(10)
+//!         .with_backoff(Duration::from_millis(100), Duration::from_secs(30)),
+//! );
+//! ```
+
+use std::time::Duration;
+
+/// Default number of `initialize` attempts after a reconnect.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Default number of times a lost request is resent.
+pub const DEFAULT_MAX_REPLAYS: u32 = 3;
+
+/// Default delay before the second `initialize` attempt.
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Default cap on the delay between `initialize` attempts.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Methods that read server state without changing it, and so can be resent
+/// after a reconnect.
+const IDEMPOTENT_METHODS: &[&str] = &[
+    "ping",
+    "tools/list",
+    "resources/list",
+    "resources/templates/list",
+    "resources/read",
+    "resources/subscribe",
+    "resources/unsubscribe",
+    "prompts/list",
+    "prompts/get",
+    "completion/complete",
+    "logging/setLevel",
+    "tasks/get",
+    "tasks/list",
+    "tasks/result",
+];
+
+/// Whether `method` can be resent without changing its effect.
+///
+/// `tools/call` is not listed: whether a call is safe depends on the tool's
+/// annotations.
+#[must_use]
+pub fn is_idempotent_method(method: &str) -> bool {
+    IDEMPOTENT_METHODS.contains(&method)
+}
+
+/// How a client resumes its session after the transport reconnects.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// `initialize` attempts on the new connection before the client gives up
+    /// and disconnects; `0` is treated as `1`. Defaults to
+    /// [`DEFAULT_MAX_ATTEMPTS`].
+    pub max_attempts: u32,
+    /// Delay before the second attempt, doubled for each later one. Defaults
+    /// to [`DEFAULT_INITIAL_BACKOFF`].
+    pub initial_backoff: Duration,
+    /// Cap on the delay between attempts. Defaults to [`DEFAULT_MAX_BACKOFF`].
+    pub max_backoff: Duration,
+    /// Times a single request lost to a reconnect is resent. Defaults to
+    /// [`DEFAULT_MAX_REPLAYS`].
+    pub max_replays: u32,
+    /// Resend every lost request, not only idempotent ones. Each `tools/call`
+    /// then carries a generated idempotency key, which mcpkit servers use to
+    /// answer a resend with the original result. Off by default.
+    pub replay_all: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReconnectPolicy {
+    /// A policy with the defaults.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            max_replays: DEFAULT_MAX_REPLAYS,
+            replay_all: false,
+        }
+    }
+
+    /// What a client does without a policy: one `initialize` attempt, and
+    /// lost requests fail with
+    /// [`TransportErrorKind::Reconnected`](mcpkit_core::error::TransportErrorKind::Reconnected).
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            max_replays: 0,
+            replay_all: true,
+        }
+    }
+
+    /// Set how many `initialize` attempts are made.
+    #[must_use]
+    pub const fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the delay before the second attempt and the cap on later delays.
+    #[must_use]
+    pub const fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set how many times a lost request is resent.
+    #[must_use]
+    pub const fn with_max_replays(mut self, max_replays: u32) -> Self {
+        self.max_replays = max_replays;
+        self
+    }
+
+    /// Resend every lost request, not only idempotent ones.
+    #[must_use]
+    pub const fn with_replay_all(mut self) -> Self {
+        self.replay_all = true;
+        self
+    }
+
+    /// The delay after failed attempt number `attempt` (starting at 1).
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = ReconnectPolicy::new()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(350));
+        let delays: Vec<_> = (1..=4).map(|attempt| policy.backoff(attempt)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 350, 350].map(Duration::from_millis).to_vec()
+        );
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn only_read_methods_are_idempotent() {
+        assert!(is_idempotent_method("resources/read"));
+        assert!(is_idempotent_method("tools/list"));
+        assert!(!is_idempotent_method("tools/call"));
+        assert!(!is_idempotent_method("sampling/createMessage"));
+    }
+}
@@ -0,0 +1,284 @@
+//! Client-managed roots with automatic change notifications.
+//!
+//! A [`RootsManager`] holds the roots a client exposes to servers. Install it
+//! with [`ClientBuilder::roots_manager`](crate::ClientBuilder::roots_manager)
+//! and the client answers `roots/list` from it. Every change made through
+//! [`add_root`](RootsManager::add_root),
+//! [`remove_root`](RootsManager::remove_root), or
+//! [`set_roots`](RootsManager::set_roots) sends
+//! `notifications/roots/list_changed` to each connected server, so servers
+//! re-list roots without the host having to remember to notify them.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use mcpkit_client::{ClientBuilder, RootsManager};
+//! use mcpkit_core::types::Root;
+//! use mcpkit_transport::SpawnedTransport;
+//!
+//! # async fn example() -> Result<(), mcpkit_core::error::McpError> {
+//! let roots = RootsManager::with_roots([Root::new("file:///home/user/project")]);
+//! let transport = SpawnedTransport::spawn("my-server", &[] as &[&str]).await?;
+//! let client = ClientBuilder::new()
+//!     .roots_manager(roots.clone())
+//!     .build(transport)
+//!     .await?;
+//!
+//! // The server is told the list changed and re-requests it.
+//! roots.add_root(Root::new("file:///home/user/notes").name("notes")).await;
+//! # Ok(())
+//! # }
+//! ```
+
+use mcpkit_core::protocol::{Message, Notification};
+use mcpkit_core::types::Root;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// Shared state behind a [`RootsManager`].
+#[derive(Default)]
+struct Inner {
+    roots: RwLock<Vec<Root>>,
+    /// Outgoing channels of the clients this manager is installed on.
+    notifiers: Mutex<Vec<mpsc::Sender<Message>>>,
+}
+
+/// The roots a client exposes, kept in sync with connected servers.
+///
+/// Cloning is cheap and clones share state, so keep a clone to change the
+/// roots after handing one to the builder. One manager may be installed on
+/// several clients; each is notified of every change.
+#[derive(Clone, Default)]
+pub struct RootsManager {
+    inner: Arc<Inner>,
+}
+
+impl RootsManager {
+    /// Create a manager with no roots.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a manager with an initial set of roots.
+    #[must_use]
+    pub fn with_roots(roots: impl IntoIterator<Item = Root>) -> Self {
+        let manager = Self::new();
+        *manager.write() = dedup_by_uri(roots);
+        manager
+    }
+
+    /// A snapshot of the current roots.
+    #[must_use]
+    pub fn roots(&self) -> Vec<Root> {
+        self.inner
+            .roots
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Whether a root with this URI is present.
+    #[must_use]
+    pub fn contains(&self, uri: &str) -> bool {
+        self.inner
+            .roots
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .any(|root| root.uri == uri)
+    }
+
+    /// Add a root, replacing any root with the same URI.
+    ///
+    /// Connected servers are notified when the list changed. Returns `false`
+    /// (and sends nothing) if an identical root was already present.
+    pub async fn add_root(&self, root: Root) -> bool {
+        let changed = {
+            let mut roots = self.write();
+            match roots.iter_mut().find(|existing| existing.uri == root.uri) {
+                Some(existing) if *existing == root => false,
+                Some(existing) => {
+                    *existing = root;
+                    true
+                }
+                None => {
+                    roots.push(root);
+                    true
+                }
+            }
+        };
+        if changed {
+            self.notify().await;
+        }
+        changed
+    }
+
+    /// Remove the root with this URI, returning it.
+    ///
+    /// Connected servers are notified when a root was removed.
+    pub async fn remove_root(&self, uri: &str) -> Option<Root> {
+        let removed = {
+            let mut roots = self.write();
+            roots
+                .iter()
+                .position(|root| root.uri == uri)
+                .map(|index| roots.remove(index))
+        };
+        if removed.is_some() {
+            self.notify().await;
+        }
+        removed
+    }
+
+    /// Replace every root at once, notifying servers if the list changed.
+    pub async fn set_roots(&self, roots: impl IntoIterator<Item = Root>) {
+        let roots = dedup_by_uri(roots);
+        let changed = {
+            let mut current = self.write();
+            let changed = *current != roots;
+            *current = roots;
+            changed
+        };
+        if changed {
+            self.notify().await;
+        }
+    }
+
+    /// Send change notifications through this client channel (called by the client).
+    pub(crate) fn attach(&self, outgoing: mpsc::Sender<Message>) {
+        let mut notifiers = self
+            .inner
+            .notifiers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        notifiers.retain(|tx| !tx.is_closed());
+        notifiers.push(outgoing);
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<Root>> {
+        self.inner
+            .roots
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Tell every attached client's server that the root list changed.
+    ///
+    /// A client that has closed is dropped from the list; the change itself
+    /// is never rolled back because a notification could not be sent.
+    async fn notify(&self) {
+        let notifiers: Vec<_> = {
+            let mut notifiers = self
+                .inner
+                .notifiers
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            notifiers.retain(|tx| !tx.is_closed());
+            notifiers.clone()
+        };
+        for tx in notifiers {
+            let notification = Notification::new("notifications/roots/list_changed");
+            if tx.send(Message::Notification(notification)).await.is_err() {
+                warn!("Failed to send roots/list_changed (client closed)");
+            } else {
+                debug!("Sent roots/list_changed");
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RootsManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RootsManager")
+            .field("roots", &self.roots())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Collect roots, keeping the last root for each URI in first-seen order.
+fn dedup_by_uri(roots: impl IntoIterator<Item = Root>) -> Vec<Root> {
+    let mut out: Vec<Root> = Vec::new();
+    for root in roots {
+        if let Some(existing) = out.iter_mut().find(|existing| existing.uri == root.uri) {
+            *existing = root;
+        } else {
+            out.push(root);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attached() -> (RootsManager, mpsc::Receiver<Message>) {
+        let (tx, rx) = mpsc::channel(8);
+        let manager = RootsManager::with_roots([Root::new("file:///a")]);
+        manager.attach(tx);
+        (manager, rx)
+    }
+
+    fn assert_notified(rx: &mut mpsc::Receiver<Message>) {
+        match rx.try_recv() {
+            Ok(Message::Notification(n)) => {
+                assert_eq!(n.method.as_ref(), "notifications/roots/list_changed");
+            }
+            other => panic!("expected roots/list_changed, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn changes_notify_and_no_ops_do_not() {
+        let (manager, mut rx) = attached();
+
+        assert!(manager.add_root(Root::new("file:///b")).await);
+        assert_notified(&mut rx);
+
+        // Re-adding an identical root is a no-op; renaming one is a change.
+        assert!(!manager.add_root(Root::new("file:///b")).await);
+        assert!(rx.try_recv().is_err());
+        assert!(manager.add_root(Root::new("file:///b").name("b")).await);
+        assert_notified(&mut rx);
+        assert_eq!(manager.roots().len(), 2);
+
+        assert_eq!(
+            manager.remove_root("file:///a").await,
+            Some(Root::new("file:///a"))
+        );
+        assert_notified(&mut rx);
+        assert!(manager.remove_root("file:///a").await.is_none());
+        assert!(rx.try_recv().is_err());
+
+        manager.set_roots(manager.roots()).await;
+        assert!(rx.try_recv().is_err());
+        manager.set_roots([]).await;
+        assert_notified(&mut rx);
+        assert!(manager.roots().is_empty());
+    }
+
+    #[tokio::test]
+    async fn closed_clients_are_dropped_without_failing_changes() {
+        let (manager, rx) = attached();
+        drop(rx);
+
+        assert!(manager.add_root(Root::new("file:///b")).await);
+        assert!(manager.contains("file:///b"));
+        assert!(manager.inner.notifiers.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn with_roots_keeps_one_root_per_uri() {
+        let manager = RootsManager::with_roots([
+            Root::new("file:///a"),
+            Root::new("file:///b"),
+            Root::new("file:///a").name("a"),
+        ]);
+        let roots = manager.roots();
+        assert_eq!(roots.len(), 2);
+        assert_eq!(roots[0].name.as_deref(), Some("a"));
+    }
+}
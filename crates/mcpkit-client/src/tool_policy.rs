@@ -0,0 +1,266 @@
+//! Client-side gating of tool calls.
+//!
+//! A host that must not let a model invoke destructive tools unattended sets
+//! a policy with [`ClientBuilder::tool_policy`](crate::ClientBuilder::tool_policy).
+//! Before [`Client::call_tool`](crate::Client::call_tool) sends a call to the
+//! server, the policy is given the tool's definition as the server
+//! advertised it, annotations included, and returns a [`Decision`]:
+//!
+//! - [`Decision::Allow`] sends the call;
+//! - [`Decision::Deny`] fails it with [`McpError::ToolAccessDenied`] without
+//!   contacting the server;
+//! - [`Decision::RequireConfirmation`] asks the [`ToolConfirmation`] set with
+//!   [`ClientBuilder::tool_confirmation`](crate::ClientBuilder::tool_confirmation),
+//!   typically a UI prompt, and fails the call with
+//!   [`McpError::UserRejected`] if it declines or none is set.
+//!
+//! [`confirm_destructive`] is a ready-made policy following the MCP
+//! annotation defaults: read-only tools and tools annotated as not
+//! destructive run, everything else needs confirmation. Annotations are
+//! hints from the server, so a policy is only as good as the server's
+//! honesty; local tools are host code and are not gated.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use mcpkit_client::tool_policy::{ConfirmationRequest, Decision, confirm_destructive};
+//! use mcpkit_client::ClientBuilder;
+//!
+//! let builder = ClientBuilder::new()
+//!     .tool_policy(|tool| match tool.name.as_str() {
+//!         "drop_database" => Decision::Deny,
+//!         _ => confirm_destructive(tool),
+//!     })
+//!     .tool_confirmation(|request: ConfirmationRequest| async move {
+//!         // Ask the user in the host's UI.
+//!         println!("Allow {}({})?", request.tool.name, request.arguments);
+//!         false
+//!     });
+//! ```
+//!
+//! [`McpError::ToolAccessDenied`]: mcpkit_core::error::McpError::ToolAccessDenied
+//! [`McpError::UserRejected`]: mcpkit_core::error::McpError::UserRejected
+
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::Tool;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// What a tool policy decides for a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Send the call.
+    Allow,
+    /// Send the call only if the [`ToolConfirmation`] approves it.
+    RequireConfirmation,
+    /// Fail the call without sending it.
+    Deny,
+}
+
+/// Allow read-only tools and tools annotated `destructiveHint: false`, and
+/// require confirmation for the rest.
+///
+/// Per the MCP specification a tool that is not read-only is assumed
+/// destructive unless it says otherwise, so unannotated tools need
+/// confirmation too.
+#[must_use]
+pub fn confirm_destructive(tool: &Tool) -> Decision {
+    let annotations = tool.annotations.as_ref();
+    let read_only = annotations.and_then(|a| a.read_only_hint) == Some(true);
+    let harmless = annotations.and_then(|a| a.destructive_hint) == Some(false);
+    if read_only || harmless {
+        Decision::Allow
+    } else {
+        Decision::RequireConfirmation
+    }
+}
+
+/// A call awaiting the user's approval.
+#[derive(Debug, Clone)]
+pub struct ConfirmationRequest {
+    /// The tool as the server advertised it, or just its name if the server
+    /// does not list it.
+    pub tool: Tool,
+    /// The call's arguments (`null` when none were given).
+    pub arguments: serde_json::Value,
+}
+
+/// Asks the user whether a tool call may proceed.
+///
+/// Implemented for closures taking a [`ConfirmationRequest`] and returning a
+/// future of `bool`.
+pub trait ToolConfirmation: Send + Sync + 'static {
+    /// Resolve to `true` to send the call, `false` to decline it.
+    fn confirm(&self, request: ConfirmationRequest) -> impl Future<Output = bool> + Send;
+}
+
+impl<F, Fut> ToolConfirmation for F
+where
+    F: Fn(ConfirmationRequest) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = bool> + Send,
+{
+    fn confirm(&self, request: ConfirmationRequest) -> impl Future<Output = bool> + Send {
+        self(request)
+    }
+}
+
+/// A boxed, `Send` confirmation future.
+type BoxFut<'a> = Pin<Box<dyn Future<Output = bool> + Send + 'a>>;
+
+/// Object-safe form of [`ToolConfirmation`].
+trait DynToolConfirmation: Send + Sync {
+    fn confirm(&self, request: ConfirmationRequest) -> BoxFut<'_>;
+}
+
+impl<C: ToolConfirmation> DynToolConfirmation for C {
+    fn confirm(&self, request: ConfirmationRequest) -> BoxFut<'_> {
+        Box::pin(ToolConfirmation::confirm(self, request))
+    }
+}
+
+/// A type-erased policy function.
+type PolicyFn = Arc<dyn Fn(&Tool) -> Decision + Send + Sync>;
+
+/// The policy and confirmation a client checks tool calls against.
+#[derive(Clone, Default)]
+pub(crate) struct ToolGate {
+    policy: Option<PolicyFn>,
+    confirmation: Option<Arc<dyn DynToolConfirmation>>,
+}
+
+impl ToolGate {
+    pub(crate) fn set_policy(
+        &mut self,
+        policy: impl Fn(&Tool) -> Decision + Send + Sync + 'static,
+    ) {
+        self.policy = Some(Arc::new(policy));
+    }
+
+    pub(crate) fn set_confirmation(&mut self, confirmation: impl ToolConfirmation) {
+        self.confirmation = Some(Arc::new(confirmation));
+    }
+
+    /// Whether calls are checked at all.
+    pub(crate) const fn is_enabled(&self) -> bool {
+        self.policy.is_some()
+    }
+
+    /// Check a call to `tool`, asking for confirmation if the policy wants
+    /// it.
+    pub(crate) async fn check(
+        &self,
+        tool: Tool,
+        arguments: Option<&mcpkit_core::types::Object>,
+    ) -> Result<(), McpError> {
+        let Some(policy) = &self.policy else {
+            return Ok(());
+        };
+        match policy(&tool) {
+            Decision::Allow => Ok(()),
+            Decision::Deny => Err(McpError::tool_access_denied(
+                tool.name,
+                Some("blocked by the client's tool policy".to_string()),
+            )),
+            Decision::RequireConfirmation => {
+                let Some(confirmation) = &self.confirmation else {
+                    return Err(rejected(
+                        &tool.name,
+                        "requires confirmation, but no confirmation handler is set",
+                    ));
+                };
+                let name = tool.name.clone();
+                let request = ConfirmationRequest {
+                    tool,
+                    arguments: arguments
+                        .cloned()
+                        .map_or(serde_json::Value::Null, serde_json::Value::Object),
+                };
+                if confirmation.confirm(request).await {
+                    Ok(())
+                } else {
+                    Err(rejected(&name, "was declined"))
+                }
+            }
+        }
+    }
+}
+
+fn rejected(tool: &str, why: &str) -> McpError {
+    McpError::UserRejected {
+        message: format!("call to tool `{tool}` {why}"),
+        operation: "tools/call".to_string(),
+    }
+}
+
+impl fmt::Debug for ToolGate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolGate")
+            .field("policy", &self.policy.is_some())
+            .field("confirmation", &self.confirmation.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::types::ToolAnnotations;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn destructive_and_unannotated_tools_need_confirmation() {
+        let tool = |annotations: Option<ToolAnnotations>| Tool {
+            annotations,
+            ..Tool::new("t")
+        };
+        assert_eq!(
+            confirm_destructive(&tool(Some(ToolAnnotations::read_only()))),
+            Decision::Allow
+        );
+        assert_eq!(
+            confirm_destructive(&tool(Some(
+                ToolAnnotations::default().with_destructive(false)
+            ))),
+            Decision::Allow
+        );
+        assert_eq!(
+            confirm_destructive(&tool(Some(ToolAnnotations::destructive()))),
+            Decision::RequireConfirmation
+        );
+        assert_eq!(
+            confirm_destructive(&tool(None)),
+            Decision::RequireConfirmation
+        );
+    }
+
+    #[tokio::test]
+    async fn the_gate_asks_and_honours_the_answer() {
+        let mut gate = ToolGate::default();
+        gate.set_policy(|tool| match tool.name.as_str() {
+            "ls" => Decision::Allow,
+            "rm" => Decision::RequireConfirmation,
+            _ => Decision::Deny,
+        });
+        let err = gate.check(Tool::new("rm"), None).await.unwrap_err();
+        assert!(matches!(err, McpError::UserRejected { .. }));
+
+        let asked = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&asked);
+        gate.set_confirmation(move |request: ConfirmationRequest| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { request.arguments["force"] == false }
+        });
+        let mut args = mcpkit_core::types::Object::new();
+        args.insert("force".into(), false.into());
+        assert!(gate.check(Tool::new("rm"), Some(&args)).await.is_ok());
+        args.insert("force".into(), true.into());
+        assert!(gate.check(Tool::new("rm"), Some(&args)).await.is_err());
+        assert!(gate.check(Tool::new("ls"), None).await.is_ok());
+        assert_eq!(asked.load(Ordering::SeqCst), 2);
+
+        let err = gate.check(Tool::new("mkfs"), None).await.unwrap_err();
+        assert!(matches!(err, McpError::ToolAccessDenied { .. }));
+    }
+}
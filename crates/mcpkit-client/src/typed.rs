@@ -0,0 +1,289 @@
+//! Runtime support for typed clients.
+//!
+//! `#[mcp_server(client = "CalculatorClient")]` generates a client wrapper
+//! with one method per `#[tool]`, mirroring the tool's signature:
+//!
+//! ```ignore
+//! let calc = CalculatorClient::new(client);
+//! let sum: String = calc.add(1.0, 2.0).await?;
+//! ```
+//!
+//! The generated methods serialize their arguments with [`argument`], call
+//! the tool, and convert the result with [`text_output`] or, for tools that
+//! return `Json<T>`, [`structured_output`]. The functions are public so
+//! hand-written wrappers can convert results the same way.
+//!
+//! Without generated wrappers, `Client::call_tool_typed` (with the
+//! `schema-validation` feature) checks a result against the tool's advertised
+//! `outputSchema` before deserializing it, and reports what went wrong as a
+//! [`TypedCallError`].
+
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::CallToolResult;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Serialize the argument `name` of a call to `tool`.
+///
+/// # Errors
+///
+/// Returns an invalid-params error if `value` cannot be serialized.
+pub fn argument<T: Serialize + ?Sized>(
+    tool: &str,
+    name: &str,
+    value: &T,
+) -> Result<Value, McpError> {
+    serde_json::to_value(value).map_err(|e| {
+        McpError::invalid_params(tool, format!("cannot serialize argument '{name}': {e}"))
+    })
+}
+
+/// The text of a tool result, with multiple text blocks joined by newlines.
+///
+/// # Errors
+///
+/// Returns a tool execution error carrying the result's text if the tool
+/// reported an error (`isError`).
+pub fn text_output(tool: &str, result: CallToolResult) -> Result<String, McpError> {
+    let text = text_of(&result);
+    if result.is_error() {
+        return Err(McpError::tool_error(tool, text));
+    }
+    Ok(text)
+}
+
+/// Deserialize a tool result's structured output.
+///
+/// Uses `structuredContent` when present, and otherwise parses the text
+/// content as JSON (tools returning `Json<T>` put non-object values there).
+///
+/// # Errors
+///
+/// Returns a tool execution error if the tool reported an error, or an
+/// internal error if the output does not deserialize into `T`.
+pub fn structured_output<T: DeserializeOwned>(
+    tool: &str,
+    result: CallToolResult,
+) -> Result<T, McpError> {
+    if result.is_error() {
+        return Err(McpError::tool_error(tool, text_of(&result)));
+    }
+    let parsed = match result.structured_content {
+        Some(object) => serde_json::from_value(Value::Object(object)),
+        None => serde_json::from_str(&text_of(&result)),
+    };
+    parsed.map_err(|e| {
+        McpError::internal(format!(
+            "output of tool '{tool}' does not match its declared type: {e}"
+        ))
+    })
+}
+
+/// Why a typed tool call did not produce a value.
+#[derive(Debug, thiserror::Error)]
+pub enum TypedCallError {
+    /// The call itself failed: the transport, the server, or the arguments.
+    #[error(transparent)]
+    Call(#[from] McpError),
+    /// The tool ran and reported an error (`isError`).
+    #[error("tool '{tool}' failed: {message}")]
+    Tool {
+        /// The tool that was called.
+        tool: String,
+        /// The text of the error result.
+        message: String,
+    },
+    /// The output does not conform to the tool's `outputSchema`.
+    #[error("output of tool '{tool}' violates its output schema: {}", .violations.join("; "))]
+    Schema {
+        /// The tool that was called.
+        tool: String,
+        /// One message per violation.
+        violations: Vec<String>,
+    },
+    /// The output does not deserialize into the requested type.
+    #[error("output of tool '{tool}' does not match its declared type: {source}")]
+    Deserialize {
+        /// The tool that was called.
+        tool: String,
+        /// The deserialization error.
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+impl From<TypedCallError> for McpError {
+    fn from(err: TypedCallError) -> Self {
+        match err {
+            TypedCallError::Call(err) => err,
+            TypedCallError::Tool { tool, message } => Self::tool_error(tool, message),
+            err @ TypedCallError::Schema { .. } => Self::internal(err.to_string()),
+            TypedCallError::Deserialize { tool, source } => Self::internal_with_source(
+                format!("output of tool '{tool}' does not match its declared type"),
+                source,
+            ),
+        }
+    }
+}
+
+/// Check a tool result against the tool's `outputSchema`, then deserialize
+/// its structured output.
+///
+/// A tool that declares an output schema must return `structuredContent`
+/// conforming to it. Without a schema this behaves like
+/// [`structured_output`]. A schema that fails to compile is a server bug and
+/// skips validation with a warning.
+///
+/// # Errors
+///
+/// Returns [`TypedCallError::Tool`] if the tool reported an error,
+/// [`TypedCallError::Schema`] if the output violates the schema, and
+/// [`TypedCallError::Deserialize`] if it does not deserialize into `T`.
+#[cfg(feature = "schema-validation")]
+pub fn validated_output<T: DeserializeOwned>(
+    tool: &str,
+    mut result: CallToolResult,
+    output_schema: Option<&Value>,
+) -> Result<T, TypedCallError> {
+    if result.is_error() {
+        return Err(TypedCallError::Tool {
+            tool: tool.to_string(),
+            message: text_of(&result),
+        });
+    }
+    let payload = match (result.structured_content.take(), output_schema) {
+        (Some(object), _) => Value::Object(object),
+        (None, None) => serde_json::from_str(&text_of(&result)).map_err(|source| {
+            TypedCallError::Deserialize {
+                tool: tool.to_string(),
+                source,
+            }
+        })?,
+        (None, Some(_)) => {
+            return Err(TypedCallError::Schema {
+                tool: tool.to_string(),
+                violations: vec![
+                    "the tool declares an outputSchema but returned no structuredContent"
+                        .to_string(),
+                ],
+            });
+        }
+    };
+    if let Some(schema) = output_schema {
+        match jsonschema::validator_for(schema) {
+            Ok(validator) => {
+                let violations: Vec<String> = validator
+                    .iter_errors(&payload)
+                    .map(|e| e.to_string())
+                    .collect();
+                if !violations.is_empty() {
+                    return Err(TypedCallError::Schema {
+                        tool: tool.to_string(),
+                        violations,
+                    });
+                }
+            }
+            Err(error) => {
+                tracing::warn!(%error, tool, "output schema failed to compile; skipping validation");
+            }
+        }
+    }
+    serde_json::from_value(payload).map_err(|source| TypedCallError::Deserialize {
+        tool: tool.to_string(),
+        source,
+    })
+}
+
+fn text_of(result: &CallToolResult) -> String {
+    result
+        .content
+        .iter()
+        .filter_map(|content| content.as_text())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Sum {
+        total: f64,
+    }
+
+    #[test]
+    fn structured_output_prefers_structured_content() {
+        let mut object = serde_json::Map::new();
+        object.insert("total".to_string(), 3.into());
+        let result = CallToolResult::text("ignored").with_structured_content(object);
+        let sum: Sum = structured_output("add", result).unwrap();
+        assert_eq!(sum, Sum { total: 3.0 });
+
+        let list: Vec<u32> = structured_output("list", CallToolResult::text("[1, 2]")).unwrap();
+        assert_eq!(list, [1, 2]);
+
+        let err = structured_output::<Sum>("add", CallToolResult::text("nope")).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err}");
+    }
+
+    #[test]
+    fn error_results_become_tool_errors() {
+        let err = text_output("divide", CallToolResult::error("division by zero")).unwrap_err();
+        assert!(matches!(err, McpError::ToolExecution(_)));
+        assert!(err.to_string().contains("division by zero"), "{err}");
+        assert!(structured_output::<Sum>("divide", CallToolResult::error("x")).is_err());
+    }
+
+    #[cfg(feature = "schema-validation")]
+    #[test]
+    fn validated_output_distinguishes_failures() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "total": { "type": "number" } },
+            "required": ["total"]
+        });
+        let structured = |value: Value| {
+            let Value::Object(object) = value else {
+                unreachable!()
+            };
+            CallToolResult::text("").with_structured_content(object)
+        };
+
+        let sum: Sum = validated_output(
+            "add",
+            structured(serde_json::json!({ "total": 3 })),
+            Some(&schema),
+        )
+        .unwrap();
+        assert_eq!(sum, Sum { total: 3.0 });
+
+        let err = validated_output::<Sum>("add", CallToolResult::error("boom"), Some(&schema))
+            .unwrap_err();
+        assert!(matches!(err, TypedCallError::Tool { ref message, .. } if message == "boom"));
+
+        let err = validated_output::<Sum>(
+            "add",
+            structured(serde_json::json!({ "total": "three" })),
+            Some(&schema),
+        )
+        .unwrap_err();
+        assert!(matches!(err, TypedCallError::Schema { .. }), "{err}");
+
+        let err =
+            validated_output::<Sum>("add", CallToolResult::text("{}"), Some(&schema)).unwrap_err();
+        assert!(matches!(err, TypedCallError::Schema { .. }), "{err}");
+
+        // Valid per a looser schema, but not a `Sum`.
+        let err = validated_output::<Sum>(
+            "add",
+            structured(serde_json::json!({ "sum": 3 })),
+            Some(&serde_json::json!({ "type": "object" })),
+        )
+        .unwrap_err();
+        assert!(matches!(err, TypedCallError::Deserialize { .. }), "{err}");
+        assert!(matches!(McpError::from(err), McpError::Internal { .. }));
+    }
+}
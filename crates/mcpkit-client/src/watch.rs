@@ -0,0 +1,214 @@
+//! Streams of resource update notifications.
+//!
+//! [`Client::subscribe`](crate::Client::subscribe) subscribes to a resource
+//! and returns a [`ResourceWatcher`], a [`Stream`] that yields a
+//! [`ResourceUpdate`] for every `notifications/resources/updated` the server
+//! sends for that URI. The watcher:
+//!
+//! - optionally reads the new contents before yielding each update
+//!   ([`ResourceWatcher::fetch_contents`]),
+//! - yields an extra update after the session is resumed on a reconnected
+//!   transport, since changes made while disconnected were not announced
+//!   (the subscription itself is replayed by the client), and
+//! - unsubscribes when the last watcher for its URI is dropped.
+//!
+//! The stream ends when the connection closes.
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use mcpkit_client::ClientBuilder;
+//! use mcpkit_transport::SpawnedTransport;
+//!
+//! # async fn example() -> Result<(), mcpkit_core::error::McpError> {
+//! let transport = SpawnedTransport::spawn("my-server", &[] as &[&str]).await?;
+//! let client = ClientBuilder::new().build(transport).await?;
+//!
+//! let mut config = client.subscribe("file:///config.toml").await?.fetch_contents();
+//! while let Some(update) = config.next().await {
+//!     let contents = update?.contents.unwrap_or_default();
+//!     println!("config changed: {} part(s)", contents.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::ResourceContents;
+use mcpkit_transport::Transport;
+use tokio::sync::mpsc;
+
+use crate::client::Client;
+use crate::handler::ClientHandler;
+
+type ReadFut<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<ResourceContents>, McpError>> + Send + 'a>>;
+
+/// A change to a watched resource.
+#[derive(Debug, Clone)]
+pub struct ResourceUpdate {
+    /// URI of the resource that changed.
+    pub uri: String,
+    /// The resource's contents after the change, when the watcher was
+    /// created with [`fetch_contents`](ResourceWatcher::fetch_contents).
+    pub contents: Option<Vec<ResourceContents>>,
+}
+
+/// A stream of updates to one subscribed resource.
+///
+/// Created by [`Client::subscribe`]. Dropping the last watcher for a URI
+/// sends `resources/unsubscribe`, which also ends a subscription made with
+/// [`Client::subscribe_resource`] for that URI.
+#[must_use = "streams do nothing unless polled"]
+pub struct ResourceWatcher<'a, T: Transport + 'static, H: ClientHandler + 'static> {
+    client: &'a Client<T, H>,
+    uri: String,
+    updates: mpsc::UnboundedReceiver<()>,
+    fetch: bool,
+    reading: Option<ReadFut<'a>>,
+}
+
+impl<'a, T: Transport + 'static, H: ClientHandler + 'static> ResourceWatcher<'a, T, H> {
+    pub(crate) const fn new(
+        client: &'a Client<T, H>,
+        uri: String,
+        updates: mpsc::UnboundedReceiver<()>,
+    ) -> Self {
+        Self {
+            client,
+            uri,
+            updates,
+            fetch: false,
+            reading: None,
+        }
+    }
+
+    /// Read the resource before yielding each update, filling in
+    /// [`ResourceUpdate::contents`]. A failed read is yielded as an error and
+    /// the stream continues.
+    pub const fn fetch_contents(mut self) -> Self {
+        self.fetch = true;
+        self
+    }
+
+    /// URI of the watched resource.
+    #[must_use]
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+}
+
+impl<T: Transport + 'static, H: ClientHandler + 'static> Stream for ResourceWatcher<'_, T, H> {
+    type Item = Result<ResourceUpdate, McpError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.reading.is_none() {
+            match this.updates.poll_recv(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(Some(())) if !this.fetch => {
+                    return Poll::Ready(Some(Ok(ResourceUpdate {
+                        uri: this.uri.clone(),
+                        contents: None,
+                    })));
+                }
+                Poll::Ready(Some(())) => {
+                    // Notifications that arrived meanwhile are covered by this read.
+                    while this.updates.try_recv().is_ok() {}
+                    this.reading = Some(Box::pin(this.client.read_resource(this.uri.clone())));
+                }
+            }
+        }
+        let Some(reading) = this.reading.as_mut() else {
+            return Poll::Pending;
+        };
+        let contents = std::task::ready!(reading.as_mut().poll(cx));
+        this.reading = None;
+        Poll::Ready(Some(contents.map(|contents| ResourceUpdate {
+            uri: this.uri.clone(),
+            contents: Some(contents),
+        })))
+    }
+}
+
+impl<T: Transport + 'static, H: ClientHandler + 'static> Drop for ResourceWatcher<'_, T, H> {
+    fn drop(&mut self) {
+        // Close first so this watcher no longer counts as watching the URI.
+        self.updates.close();
+        self.client.release_watch(&self.uri);
+    }
+}
+
+impl<T: Transport + 'static, H: ClientHandler + 'static> std::fmt::Debug
+    for ResourceWatcher<'_, T, H>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceWatcher")
+            .field("uri", &self.uri)
+            .field("fetch", &self.fetch)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Open [`ResourceWatcher`]s by URI, fed by the client's message router.
+#[derive(Default)]
+pub(crate) struct ResourceWatchers {
+    senders: Mutex<HashMap<String, Vec<mpsc::UnboundedSender<()>>>>,
+}
+
+impl ResourceWatchers {
+    fn senders(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<String, Vec<mpsc::UnboundedSender<()>>>> {
+        self.senders
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Register a watcher for `uri`.
+    pub(crate) fn watch(&self, uri: &str) -> mpsc::UnboundedReceiver<()> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.senders().entry(uri.to_string()).or_default().push(tx);
+        rx
+    }
+
+    /// Whether any open watcher remains for `uri`.
+    pub(crate) fn is_watched(&self, uri: &str) -> bool {
+        let mut senders = self.senders();
+        let Some(list) = senders.get_mut(uri) else {
+            return false;
+        };
+        list.retain(|tx| !tx.is_closed());
+        if list.is_empty() {
+            senders.remove(uri);
+            return false;
+        }
+        true
+    }
+
+    /// Wake every watcher of `uri`.
+    pub(crate) fn notify(&self, uri: &str) {
+        if let Some(list) = self.senders().get_mut(uri) {
+            list.retain(|tx| tx.send(()).is_ok());
+        }
+    }
+
+    /// Wake every watcher, e.g. after a reconnect.
+    pub(crate) fn notify_all(&self) {
+        for list in self.senders().values_mut() {
+            list.retain(|tx| tx.send(()).is_ok());
+        }
+    }
+
+    /// End every watcher's stream.
+    pub(crate) fn close(&self) {
+        self.senders().clear();
+    }
+}
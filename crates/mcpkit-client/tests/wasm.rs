@@ -0,0 +1,47 @@
+//! Browser tests for the client, run with `wasm-bindgen-test`:
+//!
+//! ```text
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test -p mcpkit-client --no-default-features --features wasm \
+//!     --target wasm32-unknown-unknown --test wasm
+//! ```
+//!
+//! The server is a `data:` URL, which answers every request with the same
+//! `initialize` result, so the handshake runs without a server.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use mcpkit_client::ClientBuilder;
+use mcpkit_transport::http::HttpTransportConfig;
+use mcpkit_transport::wasm::FetchTransport;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const INITIALIZE_RESULT: &str = r#"{"jsonrpc":"2.0","id":0,"result":{
+    "protocolVersion":"2025-11-25",
+    "capabilities":{"tools":{}},
+    "serverInfo":{"name":"browser-test-server","version":"1.0.0"}
+}}"#;
+
+#[wasm_bindgen_test]
+async fn client_initializes_over_fetch() {
+    let body: String = INITIALIZE_RESULT
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect();
+    let config =
+        HttpTransportConfig::new(format!("data:application/json,{body}")).without_sse_stream();
+
+    let client = ClientBuilder::new()
+        .name("browser-client")
+        .version("1.0.0")
+        .build(FetchTransport::new(config))
+        .await
+        .expect("initialize over fetch");
+
+    assert_eq!(client.server_info().name, "browser-test-server");
+    assert!(client.has_tools());
+    client.close().await.expect("close");
+}
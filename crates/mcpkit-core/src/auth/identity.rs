@@ -15,7 +15,8 @@ use serde::{Deserialize, Serialize};
 /// context but is deliberately *not* part of identity equality — validating the
 /// audience is a token-validation concern (is this token meant for this
 /// resource?), and a returning user's token may legitimately be re-issued with a
-/// different audience.
+/// different audience. The same goes for `roles`, which authorization
+/// policies read but which can change between token refreshes.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct VerifiedUser {
     /// The token subject (`sub`).
@@ -26,6 +27,9 @@ pub struct VerifiedUser {
     /// The token audience(s) (`aud`). Context only; not used for binding.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub audience: Vec<String>,
+    /// Role claims granted to the user. Context only; not used for binding.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
 }
 
 impl VerifiedUser {
@@ -36,6 +40,7 @@ impl VerifiedUser {
             subject: subject.into(),
             issuer: None,
             audience: Vec::new(),
+            roles: Vec::new(),
         }
     }
 
@@ -57,9 +62,26 @@ impl VerifiedUser {
         self
     }
 
+    /// Set the roles granted to the user.
+    #[must_use]
+    pub fn roles<I, S>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.roles = roles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether the user has been granted `role`.
+    #[must_use]
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
     /// Whether two identities are the same user — equal `(issuer, subject)`.
     ///
-    /// Audience is intentionally not compared.
+    /// Audience and roles are intentionally not compared.
     #[must_use]
     pub fn is_same_user(&self, other: &Self) -> bool {
         self.subject == other.subject && self.issuer == other.issuer
@@ -68,6 +90,9 @@ impl VerifiedUser {
     /// Build a verified user from validated JWT claims, or `None` if the token
     /// has no `sub`.
     ///
+    /// Roles come from a `roles` claim holding either an array of strings or a
+    /// single space-separated string.
+    ///
     /// The caller is responsible for having *validated* the token first; this
     /// only projects the claims into an identity.
     #[cfg(feature = "jwt")]
@@ -80,10 +105,22 @@ impl VerifiedUser {
             Some(Audience::Multiple(v)) => v.clone(),
             None => Vec::new(),
         };
+        let roles = match claims.extra.get("roles") {
+            Some(serde_json::Value::Array(roles)) => roles
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .map(String::from)
+                .collect(),
+            Some(serde_json::Value::String(roles)) => {
+                roles.split_whitespace().map(String::from).collect()
+            }
+            _ => Vec::new(),
+        };
         Some(Self {
             subject,
             issuer: claims.iss.clone(),
             audience,
+            roles,
         })
     }
 }
@@ -156,6 +193,26 @@ mod tests {
         assert!(!a.is_same_user(&d), "different subject is a different user");
     }
 
+    #[test]
+    fn roles_are_not_identity() {
+        let alice = VerifiedUser::new("alice").issuer("https://idp");
+        let admin = alice.clone().roles(["admin"]);
+        assert!(alice.is_same_user(&admin), "roles must not affect identity");
+        assert!(admin.has_role("admin") && !alice.has_role("admin"));
+    }
+
+    #[cfg(feature = "jwt")]
+    #[test]
+    fn roles_from_claims() {
+        let claims = |roles: serde_json::Value| -> crate::auth::jwt::TokenClaims {
+            serde_json::from_value(serde_json::json!({ "sub": "alice", "roles": roles })).unwrap()
+        };
+        let user = VerifiedUser::from_claims(&claims(serde_json::json!(["admin", "ops"]))).unwrap();
+        assert_eq!(user.roles, ["admin", "ops"]);
+        let user = VerifiedUser::from_claims(&claims(serde_json::json!("reader writer"))).unwrap();
+        assert!(user.has_role("writer"));
+    }
+
     #[test]
     fn binding_rules() {
         let alice = VerifiedUser::new("alice").issuer("https://idp");
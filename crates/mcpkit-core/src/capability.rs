@@ -448,6 +448,80 @@ impl ClientCapabilities {
             .as_ref()
             .and_then(ExtensionRegistry::from_experimental)
     }
+
+    /// Check if `capability` was declared.
+    #[must_use]
+    pub fn supports(&self, capability: ClientCapability) -> bool {
+        match capability {
+            ClientCapability::Roots => self.has_roots(),
+            ClientCapability::Sampling => self.has_sampling(),
+            ClientCapability::SamplingTools => self.has_sampling_tools(),
+            ClientCapability::Elicitation => self.has_elicitation(),
+            ClientCapability::UrlElicitation => self.has_url_elicitation(),
+            ClientCapability::Tasks => self.tasks.is_some(),
+        }
+    }
+
+    /// The capabilities declared by exactly one of `self` and `other`.
+    #[must_use]
+    pub fn changed_capabilities(&self, other: &Self) -> Vec<ClientCapability> {
+        ClientCapability::ALL
+            .into_iter()
+            .filter(|&c| self.supports(c) != other.supports(c))
+            .collect()
+    }
+}
+
+/// A client capability a server feature can depend on.
+///
+/// Used with [`ClientCapabilities::supports`] and the server's
+/// `Context::require_capability` to check for a capability by name instead
+/// of inspecting the nested capability structs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientCapability {
+    /// File system roots (`roots`).
+    Roots,
+    /// LLM sampling (`sampling`).
+    Sampling,
+    /// Tool use during sampling (`sampling.tools`).
+    SamplingTools,
+    /// Form-mode elicitation (`elicitation`).
+    Elicitation,
+    /// URL-mode elicitation (`elicitation.url`).
+    UrlElicitation,
+    /// Task-augmented requests (`tasks`).
+    Tasks,
+}
+
+impl ClientCapability {
+    /// Every client capability.
+    pub const ALL: [Self; 6] = [
+        Self::Roots,
+        Self::Sampling,
+        Self::SamplingTools,
+        Self::Elicitation,
+        Self::UrlElicitation,
+        Self::Tasks,
+    ];
+
+    /// The capability's path in the `initialize` capabilities object.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Roots => "roots",
+            Self::Sampling => "sampling",
+            Self::SamplingTools => "sampling.tools",
+            Self::Elicitation => "elicitation",
+            Self::UrlElicitation => "elicitation.url",
+            Self::Tasks => "tasks",
+        }
+    }
+}
+
+impl std::fmt::Display for ClientCapability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 /// Tool capability flags.
@@ -1053,4 +1127,29 @@ mod tests {
         assert!(json.contains("\"listChanged\":true"));
         Ok(())
     }
+
+    #[test]
+    fn test_client_capability_checks() {
+        let before = ClientCapabilities::new().with_sampling();
+        let after = ClientCapabilities::new()
+            .with_sampling_tools()
+            .with_url_elicitation();
+
+        assert!(before.supports(ClientCapability::Sampling));
+        assert!(!before.supports(ClientCapability::Elicitation));
+        assert!(after.supports(ClientCapability::UrlElicitation));
+        assert_eq!(
+            before.changed_capabilities(&after),
+            [
+                ClientCapability::SamplingTools,
+                ClientCapability::Elicitation,
+                ClientCapability::UrlElicitation,
+            ]
+        );
+        assert!(after.changed_capabilities(&after).is_empty());
+        assert_eq!(
+            ClientCapability::UrlElicitation.to_string(),
+            "elicitation.url"
+        );
+    }
 }
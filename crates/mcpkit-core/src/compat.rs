@@ -0,0 +1,198 @@
+//! Version-adaptive encoding of results.
+//!
+//! The types in [`types`](crate::types) always serialize the shape of the
+//! latest protocol version. A peer that negotiated an older version may
+//! reject fields it does not know, or content types that did not exist yet.
+//! [`adapt_result`] rewrites a serialized result for the negotiated
+//! [`ProtocolVersion`], using the same feature checks the version exposes
+//! (`supports_title_field`, `supports_audio_content`, ...):
+//!
+//! | Dropped or rewritten | Unless the version supports |
+//! |----------------------|-----------------------------|
+//! | `title` on tools, resources, templates, prompts, prompt arguments, and `serverInfo` | [`supports_title_field`](ProtocolVersion::supports_title_field) |
+//! | `icons` on the same | [`supports_icons`](ProtocolVersion::supports_icons) |
+//! | `_meta` on results and the entities above | [`supports_meta_field`](ProtocolVersion::supports_meta_field) |
+//! | tool `annotations` | [`supports_tool_annotations`](ProtocolVersion::supports_tool_annotations) |
+//! | tool `outputSchema`; `structuredContent`, moved into a text block when there is no other content | [`supports_structured_tool_output`](ProtocolVersion::supports_structured_tool_output) |
+//! | tool `execution` and the `tasks` capability | [`supports_tasks`](ProtocolVersion::supports_tasks) |
+//! | the `completions` capability | [`supports_completions_capability`](ProtocolVersion::supports_completions_capability) |
+//! | `audio` content, replaced by a text placeholder | [`supports_audio_content`](ProtocolVersion::supports_audio_content) |
+//! | `resource_link` content, replaced by a text block naming the link | [`supports_resource_links`](ProtocolVersion::supports_resource_links) |
+//!
+//! For the latest version the result is left untouched. Servers built on
+//! `mcpkit-server` apply this to every response; call it directly when
+//! encoding responses yourself.
+//!
+//! ```rust
+//! use mcpkit_core::compat::adapt_result;
+//! use mcpkit_core::protocol_version::ProtocolVersion;
+//! use mcpkit_core::types::{ListToolsResult, Tool, ToolAnnotations};
+//!
+//! let tool = Tool::new("search").title("Search").annotations(ToolAnnotations::read_only());
+//! let list = ListToolsResult { tools: vec![tool], next_cursor: None, meta: None };
+//! let mut result = serde_json::to_value(&list).unwrap();
+//!
+//! adapt_result("tools/list", &mut result, ProtocolVersion::V2024_11_05);
+//! assert_eq!(result["tools"][0], serde_json::json!({
+//!     "name": "search",
+//!     "inputSchema": { "type": "object", "properties": {} }
+//! }));
+//! ```
+
+use serde_json::{Map, Value};
+
+use crate::protocol_version::ProtocolVersion;
+
+/// Rewrite `result`, the latest-shape result of a `method` request, into the
+/// wire shape of `version`.
+///
+/// Results of methods the module does not know are only stripped of `_meta`
+/// when the version predates it.
+pub fn adapt_result(method: &str, result: &mut Value, version: ProtocolVersion) {
+    if version == ProtocolVersion::LATEST {
+        return;
+    }
+    let Some(result) = result.as_object_mut() else {
+        return;
+    };
+    strip_meta(result, version);
+    match method {
+        "initialize" => {
+            if let Some(caps) = object(result, "capabilities") {
+                adapt_server_capabilities(caps, version);
+            }
+            if let Some(info) = object(result, "serverInfo") {
+                adapt_described(info, version);
+            }
+        }
+        "tools/list" => each(result, "tools", |tool| adapt_tool(tool, version)),
+        "tools/call" => adapt_call_tool_result(result, version),
+        "resources/list" => each(result, "resources", |r| adapt_described(r, version)),
+        "resources/templates/list" => {
+            each(result, "resourceTemplates", |r| adapt_described(r, version));
+        }
+        "prompts/list" => each(result, "prompts", |prompt| {
+            adapt_described(prompt, version);
+            each(prompt, "arguments", |arg| adapt_described(arg, version));
+        }),
+        "prompts/get" => each(result, "messages", |message| {
+            if let Some(content) = message.get_mut("content") {
+                adapt_content(content, version);
+            }
+        }),
+        _ => {}
+    }
+}
+
+/// Drop capabilities the version cannot declare.
+fn adapt_server_capabilities(caps: &mut Map<String, Value>, version: ProtocolVersion) {
+    if !version.supports_completions_capability() {
+        caps.remove("completions");
+    }
+    if !version.supports_tasks() {
+        caps.remove("tasks");
+    }
+}
+
+/// Drop `title`, `icons`, and `_meta` from a named entity.
+fn adapt_described(entity: &mut Map<String, Value>, version: ProtocolVersion) {
+    if !version.supports_title_field() {
+        entity.remove("title");
+    }
+    if !version.supports_icons() {
+        entity.remove("icons");
+    }
+    strip_meta(entity, version);
+}
+
+fn adapt_tool(tool: &mut Map<String, Value>, version: ProtocolVersion) {
+    adapt_described(tool, version);
+    if !version.supports_tool_annotations() {
+        tool.remove("annotations");
+    }
+    if !version.supports_structured_tool_output() {
+        tool.remove("outputSchema");
+    }
+    if !version.supports_tasks() {
+        tool.remove("execution");
+    }
+}
+
+fn adapt_call_tool_result(result: &mut Map<String, Value>, version: ProtocolVersion) {
+    if !version.supports_structured_tool_output() {
+        if let Some(structured) = result.remove("structuredContent") {
+            // The spec asks servers to also serialize structured output as
+            // text; keep it visible when the tool did not.
+            let content = result
+                .entry("content")
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Some(content) = content.as_array_mut().filter(|c| c.is_empty()) {
+                content.push(text_block(structured.to_string()));
+            }
+        }
+    }
+    if let Some(Value::Array(content)) = result.get_mut("content") {
+        for block in content {
+            adapt_content(block, version);
+        }
+    }
+}
+
+/// Replace content types the version does not know with text.
+fn adapt_content(block: &mut Value, version: ProtocolVersion) {
+    let Some(fields) = block.as_object_mut() else {
+        return;
+    };
+    let replacement = match fields.get("type").and_then(Value::as_str) {
+        Some("audio") if !version.supports_audio_content() => {
+            let mime = fields
+                .get("mimeType")
+                .and_then(Value::as_str)
+                .unwrap_or("audio");
+            Some(format!("[{mime} content omitted]"))
+        }
+        Some("resource_link") if !version.supports_resource_links() => {
+            let uri = fields
+                .get("uri")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            Some(match fields.get("name").and_then(Value::as_str) {
+                Some(name) => format!("{name}: {uri}"),
+                None => uri.to_string(),
+            })
+        }
+        _ => None,
+    };
+    if let Some(text) = replacement {
+        *block = text_block(text);
+    } else {
+        strip_meta(fields, version);
+        if let Some(resource) = object(fields, "resource") {
+            strip_meta(resource, version);
+        }
+    }
+}
+
+fn text_block(text: String) -> Value {
+    serde_json::json!({ "type": "text", "text": text })
+}
+
+fn strip_meta(fields: &mut Map<String, Value>, version: ProtocolVersion) {
+    if !version.supports_meta_field() {
+        fields.remove("_meta");
+    }
+}
+
+fn object<'a>(fields: &'a mut Map<String, Value>, key: &str) -> Option<&'a mut Map<String, Value>> {
+    fields.get_mut(key).and_then(Value::as_object_mut)
+}
+
+/// Apply `f` to each object in the array at `key`.
+fn each(fields: &mut Map<String, Value>, key: &str, mut f: impl FnMut(&mut Map<String, Value>)) {
+    if let Some(Value::Array(items)) = fields.get_mut(key) {
+        items
+            .iter_mut()
+            .filter_map(Value::as_object_mut)
+            .for_each(&mut f);
+    }
+}
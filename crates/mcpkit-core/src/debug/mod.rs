@@ -8,6 +8,7 @@
 //!
 //! - **Message Inspector**: Capture and analyze protocol messages
 //! - **Session Recorder**: Record and replay MCP sessions
+//! - **Recording Policy**: Keep sensitive tool calls out of recordings
 //! - **Protocol Validator**: Validate message sequences
 //!
 //! # Example
@@ -23,10 +24,12 @@
 //! ```
 
 mod inspector;
+mod privacy;
 mod recorder;
 mod validator;
 
 pub use inspector::{MessageInspector, MessageRecord, MessageStats};
+pub use privacy::{REDACTED, RecordingPolicy};
 pub use recorder::{RecordedSession, SessionEvent, SessionRecorder};
 pub use validator::{
     ProtocolValidator, ValidationError, ValidationResult, validate_message_sequence,
@@ -0,0 +1,238 @@
+//! Privacy controls for recorded and dumped traffic.
+//!
+//! A [`RecordingPolicy`] names tools whose calls must never be captured:
+//! password managers, PII lookups and the like. Recorders and wire dumps pass
+//! every message through [`RecordingPolicy::redact`], which swaps the
+//! `tools/call` arguments and the matching response for a placeholder while
+//! keeping the JSON-RPC id, method and tool name, so transcripts stay coherent.
+//!
+//! Tools can be excluded by name, or by the server marking their definition
+//! with [`Tool::no_record`] (`#[tool(no_record = true)]` in the macros); the
+//! policy picks those up from any `tools/list` response it sees.
+
+use crate::protocol::{Message, RequestId};
+use crate::types::Tool;
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+/// Placeholder that replaces redacted content.
+pub const REDACTED: &str = "<redacted>";
+
+#[derive(Debug, Default)]
+struct PolicyState {
+    tools: HashSet<String>,
+    pending: HashSet<RequestId>,
+}
+
+/// Set of tools whose calls are excluded from recording.
+///
+/// Cloning is cheap and clones share state, so one policy can be handed to a
+/// [`SessionRecorder`](super::SessionRecorder) and a logging layer at once.
+///
+/// # Example
+///
+/// ```rust
+/// use mcpkit_core::debug::{RecordingPolicy, REDACTED};
+/// use mcpkit_core::protocol::{Message, Request};
+/// use serde_json::json;
+///
+/// let policy = RecordingPolicy::new().exclude_tool("vault_get");
+/// let call = Message::Request(Request::with_params(
+///     "tools/call",
+///     1,
+///     json!({ "name": "vault_get", "arguments": { "entry": "bank" } }),
+/// ));
+///
+/// let Message::Request(req) = policy.redact(&call) else { unreachable!() };
+/// let params = req.params.unwrap();
+/// assert_eq!(params["name"], "vault_get");
+/// assert_eq!(params["arguments"], REDACTED);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RecordingPolicy {
+    state: Arc<RwLock<PolicyState>>,
+}
+
+impl RecordingPolicy {
+    /// Create a policy that excludes nothing until told otherwise.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy from tool definitions, excluding those marked
+    /// [`no_record`](Tool::no_record).
+    #[must_use]
+    pub fn from_tools<'a>(tools: impl IntoIterator<Item = &'a Tool>) -> Self {
+        let policy = Self::new();
+        policy.learn_tools(tools);
+        policy
+    }
+
+    /// Exclude a tool by name, returning `self` for chaining.
+    #[must_use]
+    pub fn exclude_tool(self, name: impl Into<String>) -> Self {
+        self.add_tool(name);
+        self
+    }
+
+    /// Exclude a tool by name.
+    pub fn add_tool(&self, name: impl Into<String>) {
+        if let Ok(mut state) = self.state.write() {
+            state.tools.insert(name.into());
+        }
+    }
+
+    /// Exclude every tool in `tools` marked [`no_record`](Tool::no_record).
+    pub fn learn_tools<'a>(&self, tools: impl IntoIterator<Item = &'a Tool>) {
+        for tool in tools {
+            if tool.is_no_record() {
+                self.add_tool(tool.name.clone());
+            }
+        }
+    }
+
+    /// Whether calls to `name` are excluded.
+    #[must_use]
+    pub fn is_excluded(&self, name: &str) -> bool {
+        self.state.read().is_ok_and(|s| s.tools.contains(name))
+    }
+
+    /// Return the copy of `message` that may be recorded.
+    ///
+    /// Calls to excluded tools keep their tool name but have `arguments`
+    /// replaced by [`REDACTED`]; the response to such a call keeps its id and
+    /// `isError` flag (or error code) but loses its content. Everything else
+    /// is returned unchanged. `tools/list` results are inspected for tools
+    /// marked [`no_record`](Tool::no_record).
+    #[must_use]
+    pub fn redact(&self, message: &Message) -> Message {
+        let mut message = message.clone();
+        match &mut message {
+            Message::Request(request) if request.method == "tools/call" => {
+                let excluded = request
+                    .params
+                    .as_ref()
+                    .and_then(|p| p.get("name"))
+                    .and_then(Value::as_str)
+                    .is_some_and(|name| self.is_excluded(name));
+                if excluded {
+                    if let Some(Value::Object(params)) = &mut request.params {
+                        params.insert("arguments".to_string(), Value::from(REDACTED));
+                    }
+                    if let Ok(mut state) = self.state.write() {
+                        state.pending.insert(request.id.clone());
+                    }
+                }
+            }
+            Message::Response(response) => {
+                let was_excluded = self
+                    .state
+                    .write()
+                    .is_ok_and(|mut s| s.pending.remove(&response.id));
+                if was_excluded {
+                    if let Some(result) = &mut response.result {
+                        let is_error = result.get("isError").cloned();
+                        let mut redacted =
+                            json!({ "content": [{ "type": "text", "text": REDACTED }] });
+                        if let Some(is_error) = is_error {
+                            redacted["isError"] = is_error;
+                        }
+                        *result = redacted;
+                    }
+                    if let Some(error) = &mut response.error {
+                        error.message = REDACTED.to_string();
+                        error.data = None;
+                    }
+                } else if let Some(tools) = response
+                    .result
+                    .as_ref()
+                    .and_then(|r| r.get("tools"))
+                    .and_then(|t| serde_json::from_value::<Vec<Tool>>(t.clone()).ok())
+                {
+                    self.learn_tools(&tools);
+                }
+            }
+            _ => {}
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::JsonRpcError;
+    use crate::protocol::{Request, Response};
+
+    fn call(id: u64, tool: &str) -> Message {
+        Message::Request(Request::with_params(
+            "tools/call",
+            id,
+            json!({ "name": tool, "arguments": { "ssn": "123-45-6789" } }),
+        ))
+    }
+
+    fn json_of(message: &Message) -> Value {
+        serde_json::to_value(message).expect("serialize")
+    }
+
+    fn result_of(message: &Message) -> &Value {
+        let Message::Response(response) = message else {
+            panic!("expected a response");
+        };
+        response.result.as_ref().expect("result")
+    }
+
+    #[test]
+    fn excluded_call_and_its_response_are_redacted() {
+        let policy = RecordingPolicy::new().exclude_tool("pii_lookup");
+
+        let redacted = json_of(&policy.redact(&call(1, "pii_lookup"))).to_string();
+        assert!(!redacted.contains("123-45-6789"), "{redacted}");
+        assert!(redacted.contains("pii_lookup"), "{redacted}");
+
+        let response = Message::Response(Response::success(
+            1,
+            json!({ "content": [{ "type": "text", "text": "Jane Doe" }], "isError": false }),
+        ));
+        let redacted = policy.redact(&response);
+        assert_eq!(result_of(&redacted)["content"][0]["text"], REDACTED);
+        assert_eq!(result_of(&redacted)["isError"], false);
+
+        // The pending id is consumed: a later response with the same id is kept.
+        assert_eq!(json_of(&policy.redact(&response)), json_of(&response));
+    }
+
+    #[test]
+    fn other_tools_and_errors() {
+        let policy = RecordingPolicy::new().exclude_tool("pii_lookup");
+        let other = call(2, "search");
+        assert_eq!(json_of(&policy.redact(&other)), json_of(&other));
+
+        let _ = policy.redact(&call(3, "pii_lookup"));
+        let error = Message::Response(Response::error(
+            3,
+            JsonRpcError::invalid_params("no record for Jane Doe"),
+        ));
+        let Message::Response(redacted) = policy.redact(&error) else {
+            panic!("expected a response");
+        };
+        let error = redacted.error.expect("error");
+        assert_eq!(error.code, -32602);
+        assert_eq!(error.message, REDACTED);
+    }
+
+    #[test]
+    fn marked_tools_are_learned_from_tools_list() {
+        let policy = RecordingPolicy::new();
+        let tools = vec![Tool::new("search"), Tool::new("vault_get").no_record()];
+        let list = Message::Response(Response::success(7, json!({ "tools": tools })));
+        let _ = policy.redact(&list);
+
+        assert!(policy.is_excluded("vault_get"));
+        assert!(!policy.is_excluded("search"));
+        assert!(RecordingPolicy::from_tools(&tools).is_excluded("vault_get"));
+    }
+}
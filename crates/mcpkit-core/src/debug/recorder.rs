@@ -3,6 +3,7 @@
 //! The session recorder captures entire MCP sessions for later
 //! analysis, testing, or replay.
 
+use super::privacy::RecordingPolicy;
 use crate::protocol::Message;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
@@ -189,6 +190,8 @@ pub struct SessionRecorder {
     started: Instant,
     /// Recording state.
     state: Arc<RwLock<RecorderState>>,
+    /// Redaction applied to messages before they are stored.
+    policy: Option<RecordingPolicy>,
 }
 
 struct RecorderState {
@@ -214,9 +217,18 @@ impl SessionRecorder {
                 recording: true,
                 metadata: SessionMetadata::default(),
             })),
+            policy: None,
         }
     }
 
+    /// Redact messages through `policy` before recording them, so calls to
+    /// excluded tools are stored with placeholder arguments and results.
+    #[must_use]
+    pub fn with_policy(mut self, policy: RecordingPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
     /// Set session metadata.
     pub fn set_metadata(&self, metadata: SessionMetadata) {
         if let Ok(mut state) = self.state.write() {
@@ -249,7 +261,7 @@ impl SessionRecorder {
             if state.recording {
                 state.events.push(SessionEvent::MessageSent {
                     offset: self.started.elapsed(),
-                    message,
+                    message: self.screen(message),
                 });
             }
         }
@@ -261,7 +273,7 @@ impl SessionRecorder {
             if state.recording {
                 state.events.push(SessionEvent::MessageReceived {
                     offset: self.started.elapsed(),
-                    message,
+                    message: self.screen(message),
                 });
             }
         }
@@ -292,6 +304,13 @@ impl SessionRecorder {
         }
     }
 
+    fn screen(&self, message: Message) -> Message {
+        match &self.policy {
+            Some(policy) => policy.redact(&message),
+            None => message,
+        }
+    }
+
     /// Get the number of recorded events.
     #[must_use]
     pub fn event_count(&self) -> usize {
@@ -325,6 +344,7 @@ impl Clone for SessionRecorder {
             name: self.name.clone(),
             started: self.started,
             state: Arc::clone(&self.state),
+            policy: self.policy.clone(),
         }
     }
 }
@@ -386,6 +406,27 @@ mod tests {
         assert_eq!(restored.events.len(), session.events.len());
     }
 
+    #[test]
+    fn test_policy_redacts_excluded_tool_calls() {
+        let policy = RecordingPolicy::new().exclude_tool("vault_get");
+        let recorder = SessionRecorder::new("test").with_policy(policy);
+
+        recorder.record_received(Message::Request(Request::with_params(
+            "tools/call",
+            1,
+            serde_json::json!({ "name": "vault_get", "arguments": { "entry": "bank" } }),
+        )));
+        recorder.record_sent(Message::Response(Response::success(
+            RequestId::from(1),
+            serde_json::json!({ "content": [{ "type": "text", "text": "hunter2" }] }),
+        )));
+
+        let json = recorder.finalize().to_json().expect("Failed to serialize");
+        assert!(!json.contains("bank"), "{json}");
+        assert!(!json.contains("hunter2"), "{json}");
+        assert!(json.contains("vault_get"), "{json}");
+    }
+
     #[test]
     fn test_session_metadata() {
         let recorder = SessionRecorder::new("test");
@@ -32,5 +32,8 @@ pub const USER_REJECTED: i32 = -1;
 /// Resource was not found.
 pub const RESOURCE_NOT_FOUND: i32 = -32002;
 
+/// A tool call was refused by an authorization policy.
+pub const TOOL_ACCESS_DENIED: i32 = -32011;
+
 /// The server requires a URL-mode elicitation before the request can proceed.
 pub const URL_ELICITATION_REQUIRED: i32 = -32042;
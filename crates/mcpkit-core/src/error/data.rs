@@ -0,0 +1,48 @@
+//! Typed payloads carried in the `data` member of JSON-RPC errors.
+//!
+//! Any [`McpError`](super::McpError) can carry extra data with
+//! [`with_data`](super::McpError::with_data); the types here are the
+//! payloads with typed builders and accessors on both sides of the wire:
+//!
+//! | Key | Builder | Accessor |
+//! |-----|---------|----------|
+//! | `retry_after_ms` | [`with_retry_after`](super::McpError::with_retry_after) | [`retry_after`](super::McpError::retry_after) |
+//! | `validation_errors` | [`with_validation_errors`](super::McpError::with_validation_errors) | [`validation_errors`](super::McpError::validation_errors) |
+
+use serde::{Deserialize, Serialize};
+
+/// Key of the retry delay, in milliseconds, in an error's data.
+pub const RETRY_AFTER_KEY: &str = "retry_after_ms";
+
+/// Key of the [`FieldError`] list in an error's data.
+pub const VALIDATION_ERRORS_KEY: &str = "validation_errors";
+
+/// One invalid field of a request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Path to the field, such as `arguments.email` or `items[2].id`.
+    pub path: String,
+    /// What is wrong with the field.
+    pub message: String,
+    /// A machine-readable code for the failure, such as `format` or `range`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+impl FieldError {
+    /// Create a field error.
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            code: None,
+        }
+    }
+
+    /// Set the machine-readable code.
+    #[must_use]
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+}
@@ -72,6 +72,18 @@ impl From<&McpError> for JsonRpcError {
         if let McpError::JsonRpc(e) = err {
             return e.clone();
         }
+        // Extra data is merged over the data of the wrapped error.
+        if let McpError::WithData { source, data } = err {
+            let mut inner = Self::from(source.as_ref());
+            inner.data = Some(match (inner.data, data.as_ref()) {
+                (Some(serde_json::Value::Object(mut fields)), serde_json::Value::Object(extra)) => {
+                    fields.extend(extra.clone());
+                    serde_json::Value::Object(fields)
+                }
+                (_, data) => data.clone(),
+            });
+            return inner;
+        }
         let code = err.code();
         let message = err.to_string();
         let data = match err {
@@ -99,6 +111,24 @@ impl From<&McpError> for JsonRpcError {
                 "client_version": details.client_version,
                 "server_version": details.server_version,
             })),
+            McpError::ClientCapabilityRequired { tool, capability } => Some(serde_json::json!({
+                "tool": tool,
+                "capability": capability,
+            })),
+            McpError::ClientCapabilityMissing { capability } => Some(serde_json::json!({
+                "capability": capability,
+            })),
+            McpError::RateLimited {
+                method,
+                retry_after,
+            } => Some(serde_json::json!({
+                "method": method,
+                "retry_after_ms": u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX),
+            })),
+            McpError::ToolAccessDenied { tool, reason } => Some(serde_json::json!({
+                "tool": tool,
+                "reason": reason,
+            })),
             McpError::UrlElicitationRequired { elicitations } => Some(serde_json::json!({
                 "elicitations": elicitations,
             })),
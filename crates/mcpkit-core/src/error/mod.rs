@@ -109,6 +109,7 @@
 
 pub mod codes;
 mod context;
+mod data;
 mod details;
 mod jsonrpc;
 mod transport;
@@ -117,6 +118,7 @@ mod types;
 // Re-export all public types
 pub use codes::*;
 pub use context::McpResultExt;
+pub use data::{FieldError, RETRY_AFTER_KEY, VALIDATION_ERRORS_KEY};
 pub use details::{
     BoxError, HandshakeDetails, InvalidParamsDetails, ToolExecutionDetails, TransportDetails,
 };
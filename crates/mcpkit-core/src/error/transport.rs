@@ -33,6 +33,13 @@ pub enum TransportErrorKind {
     ResourceExhausted,
     /// Rate limit exceeded.
     RateLimited,
+    /// The connection was re-established and the peer's session state was
+    /// reset; the operation was lost and can be retried.
+    Reconnected,
+    /// The connection was re-established while an operation that is not safe
+    /// to repeat was in flight. The peer may or may not have acted on it, so
+    /// it was not resent.
+    Interrupted,
 }
 
 impl fmt::Display for TransportErrorKind {
@@ -49,6 +56,8 @@ impl fmt::Display for TransportErrorKind {
             Self::ProtocolViolation => write!(f, "protocol violation"),
             Self::ResourceExhausted => write!(f, "resource exhausted"),
             Self::RateLimited => write!(f, "rate limited"),
+            Self::Reconnected => write!(f, "reconnected"),
+            Self::Interrupted => write!(f, "interrupted"),
         }
     }
 }
@@ -7,9 +7,11 @@ use miette::Diagnostic;
 use thiserror::Error;
 
 use super::codes;
+use super::data::{FieldError, RETRY_AFTER_KEY, VALIDATION_ERRORS_KEY};
 use super::details::{
     BoxError, HandshakeDetails, InvalidParamsDetails, ToolExecutionDetails, TransportDetails,
 };
+use super::jsonrpc::JsonRpcError;
 use super::transport::{TransportContext, TransportErrorKind};
 
 /// The primary error type for the MCP SDK.
@@ -160,6 +162,32 @@ pub enum McpError {
         available: Box<[String]>,
     },
 
+    /// A tool requires a client capability the connected client did not
+    /// declare (e.g. a tool that samples from a client without `sampling`).
+    #[error("Tool '{tool}' requires the client capability '{capability}'")]
+    #[diagnostic(
+        code(mcp::capability::client_required),
+        help("Declare the capability when initializing the client")
+    )]
+    ClientCapabilityRequired {
+        /// The tool that was called.
+        tool: String,
+        /// The missing client capability (e.g. `sampling`).
+        capability: String,
+    },
+
+    /// An operation needs a client capability the connected client did not
+    /// declare (see `Context::require_capability` in `mcpkit-server`).
+    #[error("The client did not declare the '{capability}' capability")]
+    #[diagnostic(
+        code(mcp::capability::client_missing),
+        help("Declare the capability when initializing the client")
+    )]
+    ClientCapabilityMissing {
+        /// The missing client capability (e.g. `elicitation`).
+        capability: String,
+    },
+
     // ========================================================================
     // User/Client Errors
     // ========================================================================
@@ -189,6 +217,38 @@ pub enum McpError {
         duration: std::time::Duration,
     },
 
+    // ========================================================================
+    // Authorization
+    // ========================================================================
+    /// A tool call was refused by an authorization policy.
+    #[error("Tool access denied: {tool}")]
+    #[diagnostic(
+        code(mcp::tool::access_denied),
+        help("The caller lacks permission to call this tool with these arguments")
+    )]
+    ToolAccessDenied {
+        /// The tool that was called.
+        tool: String,
+        /// The reason for denial, if disclosed.
+        reason: Option<String>,
+    },
+
+    // ========================================================================
+    // Rate Limiting
+    // ========================================================================
+    /// The request exceeded a server-side rate limit.
+    #[error("Rate limit exceeded for '{method}', retry after {retry_after:?}")]
+    #[diagnostic(
+        code(mcp::rate_limited),
+        help("Wait for the retry-after period before sending more requests")
+    )]
+    RateLimited {
+        /// The method (or `tools/call` tool) that was limited.
+        method: String,
+        /// How long to wait before the request would be allowed.
+        retry_after: std::time::Duration,
+    },
+
     // ========================================================================
     // Cancellation
     // ========================================================================
@@ -216,6 +276,18 @@ pub enum McpError {
         source: Box<McpError>,
     },
 
+    /// An error with extra data for the `data` member of its JSON-RPC error
+    /// (see [`McpError::with_data`]).
+    #[error("{source}")]
+    #[diagnostic(code(mcp::with_data))]
+    WithData {
+        /// The underlying error.
+        #[source]
+        source: Box<McpError>,
+        /// The extra data (boxed to reduce enum size).
+        data: Box<serde_json::Value>,
+    },
+
     // ========================================================================
     // Generic Internal Error (simple variant)
     // ========================================================================
@@ -443,6 +515,26 @@ impl McpError {
         }
     }
 
+    /// Create an error for a tool that needs a client capability the client
+    /// did not declare.
+    pub fn client_capability_required(
+        tool: impl Into<String>,
+        capability: impl Into<String>,
+    ) -> Self {
+        Self::ClientCapabilityRequired {
+            tool: tool.into(),
+            capability: capability.into(),
+        }
+    }
+
+    /// Create an error for an operation that needs a client capability the
+    /// client did not declare.
+    pub fn client_capability_missing(capability: impl Into<String>) -> Self {
+        Self::ClientCapabilityMissing {
+            capability: capability.into(),
+        }
+    }
+
     /// Create a capability not supported error with available list.
     pub fn capability_not_supported_with_available(
         capability: impl Into<String>,
@@ -462,6 +554,22 @@ impl McpError {
         }
     }
 
+    /// Create a rate-limited error.
+    pub fn rate_limited(method: impl Into<String>, retry_after: std::time::Duration) -> Self {
+        Self::RateLimited {
+            method: method.into(),
+            retry_after,
+        }
+    }
+
+    /// Create a tool access denied error.
+    pub fn tool_access_denied(tool: impl Into<String>, reason: Option<String>) -> Self {
+        Self::ToolAccessDenied {
+            tool: tool.into(),
+            reason,
+        }
+    }
+
     /// Create a cancelled error.
     pub fn cancelled(operation: impl Into<String>) -> Self {
         Self::Cancelled {
@@ -478,6 +586,113 @@ impl McpError {
         }
     }
 
+    /// Create an invalid params error listing the fields that failed
+    /// validation, which clients read back with
+    /// [`validation_errors`](Self::validation_errors).
+    pub fn validation_failed(method: impl Into<String>, errors: Vec<FieldError>) -> Self {
+        let message = errors
+            .iter()
+            .map(|e| format!("{}: {}", e.path, e.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        Self::invalid_params(method, message).with_validation_errors(errors)
+    }
+
+    /// Attach extra data to the `data` member of this error's JSON-RPC error.
+    ///
+    /// When both this data and the data the error already carries are JSON
+    /// objects they are merged, with keys from `data` taking precedence;
+    /// otherwise `data` replaces it. The code, message, and
+    /// [`is_recoverable`](Self::is_recoverable) are those of the wrapped
+    /// error.
+    ///
+    /// ```rust
+    /// use mcpkit_core::error::{JsonRpcError, McpError};
+    /// use serde_json::json;
+    ///
+    /// let err = McpError::resource_not_found("db://orders/7")
+    ///     .with_data(json!({"table": "orders"}));
+    /// let wire = JsonRpcError::from(&err);
+    /// assert_eq!(wire.code, err.code());
+    /// assert_eq!(wire.data.unwrap()["table"], "orders");
+    /// ```
+    #[must_use]
+    pub fn with_data(self, data: serde_json::Value) -> Self {
+        match (self, data) {
+            // Merge into an existing object rather than nesting wrappers.
+            (
+                Self::WithData {
+                    source,
+                    data: mut existing,
+                },
+                serde_json::Value::Object(extra),
+            ) if existing.is_object() => {
+                if let Some(fields) = existing.as_object_mut() {
+                    fields.extend(extra);
+                }
+                Self::WithData {
+                    source,
+                    data: existing,
+                }
+            }
+            (error, data) => Self::WithData {
+                source: Box::new(error),
+                data: Box::new(data),
+            },
+        }
+    }
+
+    /// Tell the client how long to wait before retrying, read back with
+    /// [`retry_after`](Self::retry_after).
+    #[must_use]
+    pub fn with_retry_after(self, retry_after: std::time::Duration) -> Self {
+        let millis = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+        self.with_data(serde_json::json!({ RETRY_AFTER_KEY: millis }))
+    }
+
+    /// List the fields that failed validation, read back with
+    /// [`validation_errors`](Self::validation_errors).
+    #[must_use]
+    pub fn with_validation_errors(self, errors: impl IntoIterator<Item = FieldError>) -> Self {
+        let errors: Vec<FieldError> = errors.into_iter().collect();
+        self.with_data(serde_json::json!({ VALIDATION_ERRORS_KEY: errors }))
+    }
+
+    /// The `data` member of this error's JSON-RPC error.
+    ///
+    /// For an error received from a server ([`McpError::JsonRpc`]) this is
+    /// the data the server sent.
+    #[must_use]
+    pub fn data(&self) -> Option<serde_json::Value> {
+        JsonRpcError::from(self).data
+    }
+
+    /// How long to wait before retrying, from a [`McpError::RateLimited`]
+    /// error or the `retry_after_ms` data set by
+    /// [`with_retry_after`](Self::with_retry_after).
+    #[must_use]
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        self.data()?
+            .get(RETRY_AFTER_KEY)?
+            .as_u64()
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// The fields that failed validation, from the `validation_errors` data
+    /// set by [`with_validation_errors`](Self::with_validation_errors).
+    ///
+    /// Empty when the error carries none, or they are malformed.
+    #[must_use]
+    pub fn validation_errors(&self) -> Vec<FieldError> {
+        self.data()
+            .and_then(|mut data| {
+                data.get_mut(VALIDATION_ERRORS_KEY)
+                    .map(serde_json::Value::take)
+            })
+            .and_then(|errors| serde_json::from_value(errors).ok())
+            .unwrap_or_default()
+    }
+
     /// Get the JSON-RPC error code for this error.
     #[must_use]
     pub fn code(&self) -> i32 {
@@ -496,11 +711,15 @@ impl McpError {
             Self::ConnectionFailed { .. } => codes::SERVER_ERROR_START - 3,
             Self::SessionExpired { .. } => codes::SERVER_ERROR_START - 4,
             Self::HandshakeFailed(_) => codes::SERVER_ERROR_START - 5,
-            Self::CapabilityNotSupported { .. } => codes::SERVER_ERROR_START - 6,
+            Self::CapabilityNotSupported { .. }
+            | Self::ClientCapabilityRequired { .. }
+            | Self::ClientCapabilityMissing { .. } => codes::SERVER_ERROR_START - 6,
             Self::UserRejected { .. } => codes::USER_REJECTED,
             Self::Timeout { .. } => codes::SERVER_ERROR_START - 7,
             Self::Cancelled { .. } => codes::SERVER_ERROR_START - 8,
-            Self::WithContext { source, .. } => source.code(),
+            Self::RateLimited { .. } => codes::SERVER_ERROR_START - 10,
+            Self::ToolAccessDenied { .. } => codes::TOOL_ACCESS_DENIED,
+            Self::WithContext { source, .. } | Self::WithData { source, .. } => source.code(),
             Self::InternalMessage { .. } => codes::INTERNAL_ERROR,
             Self::UrlElicitationRequired { .. } => codes::URL_ELICITATION_REQUIRED,
             Self::JsonRpc(e) => e.code,
@@ -515,7 +734,11 @@ impl McpError {
             Self::InvalidParams(_) => true,
             Self::ResourceNotFound { .. } => true,
             Self::Timeout { .. } => true,
-            Self::WithContext { source, .. } => source.is_recoverable(),
+            Self::RateLimited { .. } => true,
+            Self::Transport(details) => details.kind == TransportErrorKind::Reconnected,
+            Self::WithContext { source, .. } | Self::WithData { source, .. } => {
+                source.is_recoverable()
+            }
             Self::InternalMessage { .. } => false,
             _ => false,
         }
@@ -578,6 +801,57 @@ mod tests {
         assert_eq!(data["elicitations"][0]["mode"], "url");
     }
 
+    #[test]
+    fn data_survives_the_wire_and_reads_back() {
+        use crate::error::jsonrpc::JsonRpcError;
+        use std::time::Duration;
+
+        let err = McpError::validation_failed(
+            "tools/call",
+            vec![
+                FieldError::new("arguments.email", "not an email").with_code("format"),
+                FieldError::new("arguments.age", "must be positive"),
+            ],
+        )
+        .with_retry_after(Duration::from_secs(2))
+        .with_data(serde_json::json!({"request": "r1"}));
+        assert_eq!(err.code(), codes::INVALID_PARAMS);
+        assert!(err.is_recoverable());
+        assert!(err.to_string().contains("arguments.email: not an email"));
+
+        // The variant's own data is kept and the extra data merged over it.
+        let wire = JsonRpcError::from(&err);
+        assert_eq!(wire.code, codes::INVALID_PARAMS);
+        let data = wire.data.clone().expect("data present");
+        assert_eq!(data["method"], "tools/call");
+        assert_eq!(data["retry_after_ms"], 2000);
+        assert_eq!(data["request"], "r1");
+        assert_eq!(data["validation_errors"][0]["code"], "format");
+
+        // A client sees the same through the raw wire error.
+        let received = McpError::JsonRpc(wire);
+        assert_eq!(received.retry_after(), Some(Duration::from_secs(2)));
+        let fields = received.validation_errors();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(
+            fields[1],
+            FieldError::new("arguments.age", "must be positive")
+        );
+
+        let limited = McpError::rate_limited("tools/call", Duration::from_millis(250));
+        assert_eq!(limited.retry_after(), Some(Duration::from_millis(250)));
+        assert!(McpError::internal("x").retry_after().is_none());
+        assert!(McpError::internal("x").validation_errors().is_empty());
+    }
+
+    #[test]
+    fn non_object_data_replaces_the_variant_data() {
+        let err = McpError::tool_error("t", "failed").with_data(serde_json::json!([1, 2]));
+        assert_eq!(err.data(), Some(serde_json::json!([1, 2])));
+        let err = err.with_data(serde_json::json!({"a": 1}));
+        assert_eq!(err.data(), Some(serde_json::json!({"a": 1})));
+    }
+
     #[test]
     fn resource_errors_have_distinct_codes() {
         // Regression for #17: access-denied must not share -32002 with not-found.
@@ -55,11 +55,17 @@
 //! - **`jwt`**: Enable JWT validation helpers with JWKS fetching. This adds
 //!   the `auth::jwt` module with functions for validating JWT access tokens
 //!   and fetching JSON Web Key Sets from authorization servers.
+//!
+//! - **`schemars`**: Generate tool input and output schemas from types
+//!   deriving `schemars::JsonSchema` (see [`schema::schema_for`]). Nested
+//!   structs, enums, doc comments, and serde attributes are all reflected in
+//!   the schema, and such types can be used directly as `#[tool]` parameters.
 
 #![deny(missing_docs)]
 
 pub mod auth;
 pub mod capability;
+pub mod compat;
 pub mod debug;
 pub mod error;
 pub mod extension;
@@ -71,11 +77,14 @@ pub mod state;
 pub mod tasks;
 pub mod types;
 
+#[cfg(feature = "schemars")]
+pub use schemars;
+
 // Re-export commonly used types at the crate root
 pub use capability::{
-    ClientCapabilities, ClientInfo, InitializeRequest, InitializeResult, PROTOCOL_VERSION,
-    SUPPORTED_PROTOCOL_VERSIONS, ServerCapabilities, ServerInfo, VersionNegotiationResult,
-    is_version_supported, negotiate_version, negotiate_version_detailed,
+    ClientCapabilities, ClientCapability, ClientInfo, InitializeRequest, InitializeResult,
+    PROTOCOL_VERSION, SUPPORTED_PROTOCOL_VERSIONS, ServerCapabilities, ServerInfo,
+    VersionNegotiationResult, is_version_supported, negotiate_version, negotiate_version_detailed,
 };
 pub use error::{JsonRpcError, McpError, McpResultExt};
 pub use protocol::{Message, Notification, ProgressToken, Request, RequestId, Response};
@@ -95,9 +104,10 @@ pub mod prelude {
     pub use serde_json::json;
 
     pub use crate::capability::{
-        ClientCapabilities, ClientInfo, InitializeRequest, InitializeResult, PROTOCOL_VERSION,
-        SUPPORTED_PROTOCOL_VERSIONS, ServerCapabilities, ServerInfo, VersionNegotiationResult,
-        is_version_supported, negotiate_version, negotiate_version_detailed,
+        ClientCapabilities, ClientCapability, ClientInfo, InitializeRequest, InitializeResult,
+        PROTOCOL_VERSION, SUPPORTED_PROTOCOL_VERSIONS, ServerCapabilities, ServerInfo,
+        VersionNegotiationResult, is_version_supported, negotiate_version,
+        negotiate_version_detailed,
     };
     pub use crate::error::{McpError, McpResultExt};
     pub use crate::protocol::{Message, Notification, ProgressToken, Request, RequestId, Response};
@@ -118,8 +128,10 @@ pub mod prelude {
         CreateTaskResult,
         // Elicitation types
         ElicitAction,
+        ElicitOutcome,
         ElicitRequest,
         ElicitResult,
+        Elicitable,
         ElicitationSchema,
         // Prompt types
         GetPromptResult,
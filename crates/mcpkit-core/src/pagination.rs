@@ -89,6 +89,52 @@ pub fn paginate<T>(
     Ok((page, next))
 }
 
+/// One page of a list result: the items plus the cursor for the next page.
+///
+/// Handlers that page natively (e.g. straight from a database) return a
+/// `Page` from their `list_*_page` methods; `next_cursor` is then whatever
+/// opaque token the handler will accept back as `cursor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    /// Items on this page.
+    pub items: Vec<T>,
+    /// Cursor for the next page, or `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// A final page holding `items`.
+    #[must_use]
+    pub const fn new(items: Vec<T>) -> Self {
+        Self {
+            items,
+            next_cursor: None,
+        }
+    }
+
+    /// Set the cursor for the next page.
+    #[must_use]
+    pub fn with_next_cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.next_cursor = Some(cursor.into());
+        self
+    }
+
+    /// Page a complete list with [`paginate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `invalid_params` if `cursor` is malformed.
+    pub fn from_all(
+        items: Vec<T>,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        method: &str,
+    ) -> Result<Self, McpError> {
+        let (items, next_cursor) = paginate(items, cursor, page_size, method)?;
+        Ok(Self { items, next_cursor })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +187,18 @@ mod tests {
         assert!(page.is_empty());
         assert!(next.is_none());
     }
+
+    #[test]
+    fn page_from_all_matches_paginate() {
+        let page = Page::from_all(vec![1, 2, 3], None, Some(2), "m").unwrap();
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_cursor, Some(encode_cursor(2)));
+        assert_eq!(
+            Page::new(vec![1])
+                .with_next_cursor("x")
+                .next_cursor
+                .as_deref(),
+            Some("x")
+        );
+    }
 }
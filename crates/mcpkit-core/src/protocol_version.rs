@@ -302,6 +302,15 @@ impl ProtocolVersion {
         matches!(self, Self::V2025_06_18 | Self::V2025_11_25)
     }
 
+    /// Whether this version supports `icons` on tools, resources, prompts,
+    /// and the server's implementation info.
+    ///
+    /// Added in 2025-11-25.
+    #[must_use]
+    pub const fn supports_icons(&self) -> bool {
+        matches!(self, Self::V2025_11_25)
+    }
+
     /// Whether this version supports the `context` field in completion requests.
     ///
     /// Provides previously-resolved variable values.
@@ -22,6 +22,10 @@
 //!     .required(["query"])
 //!     .build();
 //! ```
+//!
+//! With the `schemars` feature, a schema can instead be generated from any
+//! type deriving `schemars::JsonSchema` with [`schema_for`], or attached to
+//! a tool with [`Tool::input_schema_for`](crate::types::Tool::input_schema_for).
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -594,6 +598,107 @@ impl SchemaBuilder {
     }
 }
 
+/// Types that can describe themselves as a tool's JSON Schema.
+///
+/// The `#[tool]` macro uses this for parameter types that are not JSON
+/// primitives. `#[derive(ToolInput)]` types provide an inherent
+/// `tool_input_schema`, which takes precedence; with the `schemars` feature,
+/// every `schemars::JsonSchema` type implements this trait through
+/// [`schema_for`].
+pub trait ToolInputSchema {
+    /// Generate the JSON Schema for this type.
+    fn tool_input_schema() -> Value;
+}
+
+#[cfg(feature = "schemars")]
+impl<T: schemars::JsonSchema> ToolInputSchema for T {
+    fn tool_input_schema() -> Value {
+        schema_for::<T>()
+    }
+}
+
+/// Generate the JSON Schema (draft 2020-12) for `T`.
+///
+/// Subschemas are inlined rather than referenced through `$defs`, so the
+/// result stays valid when nested inside another schema, e.g. as one
+/// property of a tool's input. Recursive types reference their own
+/// definition under `$defs` (never the document root `#`); the `#[tool]`
+/// macro hoists such `$defs` to the root of the tool's input schema. The
+/// `$schema` keyword is omitted.
+///
+/// # Example
+///
+/// ```rust
+/// use mcpkit_core::schema::schema_for;
+/// use schemars::JsonSchema;
+///
+/// /// Search parameters.
+/// #[derive(JsonSchema)]
+/// struct SearchArgs {
+///     /// Text to search for.
+///     query: String,
+///     #[serde(default)]
+///     limit: Option<u32>,
+/// }
+///
+/// let schema = schema_for::<SearchArgs>();
+/// assert_eq!(schema["properties"]["query"]["description"], "Text to search for.");
+/// assert_eq!(schema["required"], serde_json::json!(["query"]));
+/// ```
+#[cfg(feature = "schemars")]
+#[must_use]
+pub fn schema_for<T: schemars::JsonSchema>() -> Value {
+    let generator = schemars::generate::SchemaSettings::draft2020_12()
+        .with(|settings| {
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator();
+    let mut schema = generator.into_root_schema_for::<T>().to_value();
+
+    // A recursive type refers back to itself as `#`, which would resolve to
+    // the enclosing schema once this one is nested; point it at a definition.
+    let name = T::schema_name();
+    let target = format!("#/$defs/{name}");
+    if replace_root_refs(&mut schema, &target) {
+        if let Value::Object(obj) = &mut schema {
+            let mut definition = obj.clone();
+            definition.remove("$defs");
+            let defs = obj
+                .entry("$defs")
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            if let Value::Object(defs) = defs {
+                defs.insert(name.into_owned(), Value::Object(definition));
+            }
+        }
+    }
+    schema
+}
+
+/// Replace every `"$ref": "#"` in `value` with `target`, returning whether
+/// any was found.
+#[cfg(feature = "schemars")]
+fn replace_root_refs(value: &mut Value, target: &str) -> bool {
+    match value {
+        Value::Object(obj) => {
+            let mut found = false;
+            for (key, child) in obj.iter_mut() {
+                if key == "$ref" && child == "#" {
+                    *child = Value::String(target.to_string());
+                    found = true;
+                } else {
+                    found |= replace_root_refs(child, target);
+                }
+            }
+            found
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .fold(false, |found, item| replace_root_refs(item, target) | found),
+        _ => false,
+    }
+}
+
 /// Common string format hints.
 pub mod formats {
     /// Email address format.
@@ -0,0 +1,130 @@
+//! Per-task event log for following one task's progress.
+//!
+//! Every status change and every progress report of a tracked task is
+//! appended to its log with a sequence number, so a follower (such as an SSE
+//! stream in a web integration) can resume after the last event it saw with
+//! [`TaskManager::next_events`](super::TaskManager::next_events).
+
+use crate::types::task::{Task, TaskId, TaskStatusNotificationParams};
+use event_listener::Event;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// Events kept per task; older ones are dropped first.
+pub const MAX_TASK_EVENTS: usize = 256;
+
+/// One entry in a task's event log.
+#[derive(Debug, Clone)]
+pub struct TaskEvent {
+    /// Sequence number, increasing from 1 within the task.
+    pub id: u64,
+    /// The task the event belongs to.
+    pub task_id: TaskId,
+    /// What happened.
+    pub kind: TaskEventKind,
+}
+
+/// What a [`TaskEvent`] records.
+#[derive(Debug, Clone)]
+pub enum TaskEventKind {
+    /// The task's status or status message changed; carries the new state.
+    Status(Task),
+    /// The task reported progress.
+    Progress {
+        /// Progress so far.
+        progress: f64,
+        /// Total expected, if known.
+        total: Option<f64>,
+        /// Human-readable description of the step.
+        message: Option<String>,
+    },
+}
+
+impl TaskEvent {
+    /// The event's name: `status` or `progress`.
+    #[must_use]
+    pub const fn event_type(&self) -> &'static str {
+        match self.kind {
+            TaskEventKind::Status(_) => "status",
+            TaskEventKind::Progress { .. } => "progress",
+        }
+    }
+
+    /// Whether this event moved the task to a terminal status, after which
+    /// no further events follow.
+    #[must_use]
+    pub fn is_terminal(&self) -> bool {
+        matches!(&self.kind, TaskEventKind::Status(task) if task.status.is_terminal())
+    }
+
+    /// The event's JSON payload: the `notifications/tasks/status` params for
+    /// a status event, or `{taskId, progress, total?, message?}` for progress.
+    #[must_use]
+    pub fn data(&self) -> Value {
+        match &self.kind {
+            TaskEventKind::Status(task) => {
+                serde_json::to_value(TaskStatusNotificationParams::from(task.clone()))
+                    .unwrap_or_default()
+            }
+            TaskEventKind::Progress {
+                progress,
+                total,
+                message,
+            } => {
+                let mut data = serde_json::json!({
+                    "taskId": self.task_id.as_str(),
+                    "progress": progress,
+                });
+                if let Some(total) = total {
+                    data["total"] = (*total).into();
+                }
+                if let Some(message) = message {
+                    data["message"] = message.clone().into();
+                }
+                data
+            }
+        }
+    }
+}
+
+/// The bounded log behind [`TaskEvent`]s, with an [`Event`] to wake followers.
+#[derive(Debug)]
+pub(super) struct TaskEventLog {
+    events: VecDeque<TaskEvent>,
+    next_id: u64,
+    pub(super) changed: Event,
+}
+
+impl TaskEventLog {
+    pub(super) fn new() -> Self {
+        Self {
+            events: VecDeque::new(),
+            next_id: 1,
+            changed: Event::new(),
+        }
+    }
+
+    /// Append an event and wake followers.
+    pub(super) fn push(&mut self, task_id: &TaskId, kind: TaskEventKind) {
+        self.events.push_back(TaskEvent {
+            id: self.next_id,
+            task_id: task_id.clone(),
+            kind,
+        });
+        self.next_id += 1;
+        if self.events.len() > MAX_TASK_EVENTS {
+            self.events.pop_front();
+        }
+        self.changed.notify(usize::MAX);
+    }
+
+    /// The retained events after `after` (all of them for `None`).
+    pub(super) fn after(&self, after: Option<u64>) -> Vec<TaskEvent> {
+        let after = after.unwrap_or(0);
+        self.events
+            .iter()
+            .filter(|event| event.id > after)
+            .cloned()
+            .collect()
+    }
+}
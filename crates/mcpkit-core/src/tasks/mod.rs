@@ -6,12 +6,29 @@
 //! task-augmented `tools/call`, clients receive task-augmented
 //! `sampling/createMessage` / `elicitation/create`. This module is the shared
 //! store and dispatch used by both.
+//!
+//! Tasks live in memory unless the [`TaskManager`] is given a durable
+//! [`TaskStore`] (see [`TaskManager::with_store`]).
+//!
+//! Each task also keeps a short log of its status changes and progress
+//! reports, which [`TaskManager::follow`] streams to a single follower.
+
+mod events;
+mod store;
+
+pub use events::{MAX_TASK_EVENTS, TaskEvent, TaskEventKind};
+
+#[cfg(feature = "file-task-store")]
+pub use store::FileTaskStore;
+pub use store::{MemoryTaskStore, StoredTask, TaskStore};
 
 use crate::error::{JsonRpcError, McpError};
 use crate::types::task::{
     CancelTaskResult, GetTaskResult, ListTasksResult, Task, TaskId, TaskStatus,
 };
 use event_listener::Event;
+use events::TaskEventLog;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::future::Future;
@@ -19,7 +36,8 @@ use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::task::{Context as TaskContext, Poll};
-use std::time::Instant;
+// `std::time::Instant` panics in the browser.
+use web_time::Instant;
 
 /// The `_meta` key associating a message with a task
 /// (`io.modelcontextprotocol/related-task`).
@@ -134,7 +152,8 @@ impl Future for CancelledFuture {
 ///
 /// Per spec, `tasks/result` must return exactly what the underlying request
 /// would have returned: a successful result, or the JSON-RPC error.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum TaskPayload {
     /// The successful result of the underlying request. Note a *failed* task
     /// can still carry a `Success` payload — e.g. a `tools/call` whose result
@@ -163,19 +182,77 @@ pub struct TaskState {
     /// Notified when the task transitions to a terminal status, waking
     /// blocked `tasks/result` waiters.
     terminal: Arc<Event>,
+    /// Status and progress events, shared by all snapshots of the task.
+    log: Arc<std::sync::Mutex<TaskEventLog>>,
 }
 
 impl TaskState {
     fn new(task: Task) -> Self {
         let now = Instant::now();
-        Self {
+        let state = Self {
             task,
             payload: None,
             cancel_token: CancellationToken::new(),
             last_access: now,
             created: now,
             terminal: Arc::new(Event::new()),
+            log: Arc::new(std::sync::Mutex::new(TaskEventLog::new())),
+        };
+        state.push_status();
+        state
+    }
+
+    /// Rebuild the state of a task loaded from a [`TaskStore`], dating its
+    /// creation from the record's `createdAt`.
+    fn restore(record: StoredTask) -> Self {
+        let now = Instant::now();
+        let age = chrono::DateTime::parse_from_rfc3339(&record.task.created_at)
+            .ok()
+            .and_then(|created| (chrono::Utc::now() - created.to_utc()).to_std().ok())
+            .unwrap_or_default();
+        let cancel_token = CancellationToken::new();
+        if record.task.status == TaskStatus::Cancelled {
+            cancel_token.cancel();
         }
+        let state = Self {
+            task: record.task,
+            payload: record.payload,
+            cancel_token,
+            last_access: now,
+            created: now.checked_sub(age).unwrap_or(now),
+            terminal: Arc::new(Event::new()),
+            log: Arc::new(std::sync::Mutex::new(TaskEventLog::new())),
+        };
+        state.push_status();
+        state
+    }
+
+    /// Append an event to the task's log.
+    fn push_event(&self, kind: TaskEventKind) {
+        if let Ok(mut log) = self.log.lock() {
+            log.push(&self.task.task_id, kind);
+        }
+    }
+
+    /// Append the task's current state to its log.
+    fn push_status(&self) {
+        self.push_event(TaskEventKind::Status(self.task.clone()));
+    }
+
+    /// The record written to a [`TaskStore`].
+    fn record(&self) -> StoredTask {
+        StoredTask {
+            task: self.task.clone(),
+            payload: self.payload.clone(),
+        }
+    }
+
+    /// Whether the task is terminal and older than its `ttl`.
+    fn is_expired(&self) -> bool {
+        self.task.status.is_terminal()
+            && self.task.ttl.is_some_and(|ttl_ms| {
+                self.created.elapsed() >= std::time::Duration::from_millis(ttl_ms)
+            })
     }
 
     /// Check if the task is cancelled.
@@ -217,6 +294,31 @@ impl TaskHandle {
             .set_status(&self.task_id, TaskStatus::InputRequired, None)
     }
 
+    /// Move an `input_required` task back to `working` once its input arrived.
+    pub fn mark_working(&self) -> Result<(), McpError> {
+        self.manager
+            .set_status(&self.task_id, TaskStatus::Working, None)
+    }
+
+    /// Replace the task's status message without changing its status.
+    ///
+    /// Rejected once the task is terminal, whose message is final.
+    pub fn set_status_message(&self, message: impl Into<String>) -> Result<(), McpError> {
+        self.manager.set_message(&self.task_id, message.into())
+    }
+
+    /// Record a progress report in the task's event log (see
+    /// [`TaskManager::next_events`]). Reports on a terminal task are ignored.
+    pub fn record_progress(
+        &self,
+        progress: f64,
+        total: Option<f64>,
+        message: Option<String>,
+    ) -> Result<(), McpError> {
+        self.manager
+            .record_progress(&self.task_id, progress, total, message)
+    }
+
     /// Mark the task `completed` and store its payload.
     pub fn complete(&self, payload: Value) -> Result<(), McpError> {
         self.manager.finish(
@@ -294,6 +396,9 @@ impl TaskHandle {
 /// (one hour, in milliseconds). Override via [`TaskManager::with_default_ttl`].
 pub const DEFAULT_TASK_TTL_MS: u64 = 60 * 60 * 1000;
 
+/// Status message of a task a restart interrupted.
+const INTERRUPTED_MESSAGE: &str = "Task interrupted by a restart";
+
 /// Manager coordinating the lifecycle of tracked tasks.
 #[derive(Debug)]
 pub struct TaskManager {
@@ -301,6 +406,8 @@ pub struct TaskManager {
     /// Retention applied to a task when the request omits `ttl`. `None` means
     /// unlimited (such tasks are never TTL-evicted).
     default_ttl_ms: Option<u64>,
+    /// Durable copy of every task, consulted for tasks not in memory.
+    store: Option<Arc<dyn TaskStore>>,
 }
 
 impl Default for TaskManager {
@@ -325,9 +432,62 @@ impl TaskManager {
         Self {
             tasks: RwLock::new(HashMap::new()),
             default_ttl_ms,
+            store: None,
         }
     }
 
+    /// Write every task through to `store`, and serve tasks found there that
+    /// are not in memory, so task state survives a restart.
+    ///
+    /// Tasks the store still records as running are marked `failed`, since the
+    /// work behind them ended with the previous process, and expired tasks are
+    /// removed. A store should therefore back only one manager.
+    #[must_use]
+    pub fn with_store(mut self, store: Arc<dyn TaskStore>) -> Self {
+        for record in store.list().unwrap_or_default() {
+            let mut state = TaskState::restore(record);
+            if state.is_expired() {
+                let _ = store.remove(&state.task.task_id);
+            } else if !state.task.status.is_terminal() {
+                state.task.set_status(TaskStatus::Failed);
+                state.task.status_message = Some(INTERRUPTED_MESSAGE.to_string());
+                state.payload = Some(TaskPayload::Error(JsonRpcError::internal_error(
+                    INTERRUPTED_MESSAGE,
+                )));
+                let _ = store.put(state.record());
+            }
+        }
+        self.store = Some(store);
+        self
+    }
+
+    /// The durable store backing this manager, if any.
+    #[must_use]
+    pub fn store(&self) -> Option<&Arc<dyn TaskStore>> {
+        self.store.as_ref()
+    }
+
+    /// Write a task's current state through to the store.
+    fn persist(&self, state: &TaskState) -> Result<(), McpError> {
+        match &self.store {
+            Some(store) => store.put(state.record()),
+            None => Ok(()),
+        }
+    }
+
+    /// Load a task that is not in memory from the store, keeping it in memory
+    /// from then on. Expired tasks are removed instead.
+    fn load(&self, id: &TaskId) -> Option<TaskState> {
+        let store = self.store.as_ref()?;
+        let state = TaskState::restore(store.get(id).ok()??);
+        if state.is_expired() {
+            let _ = store.remove(id);
+            return None;
+        }
+        let mut tasks = self.tasks.write().ok()?;
+        Some(tasks.entry(id.clone()).or_insert(state).clone())
+    }
+
     /// Create a new `working` task and return a handle to it.
     ///
     /// A `None` `ttl` is materialized to the manager's default so the returned
@@ -339,8 +499,12 @@ impl TaskManager {
         task.ttl = ttl.or(self.default_ttl_ms);
         let task_id = task.task_id.clone();
 
+        let state = TaskState::new(task);
+        // A store failure leaves the task in memory only; later transitions
+        // retry the write.
+        let _ = self.persist(&state);
         if let Ok(mut tasks) = self.tasks.write() {
-            tasks.insert(task_id.clone(), TaskState::new(task));
+            tasks.insert(task_id.clone(), state);
         }
 
         TaskHandle {
@@ -352,22 +516,37 @@ impl TaskManager {
     /// Get a snapshot of a task's state by ID.
     #[must_use]
     pub fn get(&self, id: &TaskId) -> Option<TaskState> {
-        self.tasks.read().ok()?.get(id).cloned()
+        let state = self.tasks.read().ok()?.get(id).cloned();
+        state.or_else(|| self.load(id))
     }
 
-    /// List all tracked tasks.
+    /// List all tracked tasks, including those only in the store.
     #[must_use]
     pub fn list(&self) -> Vec<Task> {
-        self.tasks
+        let mut list: Vec<Task> = self
+            .tasks
             .read()
             .map(|tasks| tasks.values().map(|s| s.task.clone()).collect())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        if let Some(store) = &self.store {
+            let stored: Vec<Task> = store
+                .list()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|r| !list.iter().any(|t| t.task_id == r.task.task_id))
+                .map(TaskState::restore)
+                .filter(|state| !state.is_expired())
+                .map(|state| state.task)
+                .collect();
+            list.extend(stored);
+        }
+        list
     }
 
     /// Get the stored outcome of a terminal task, if available.
     #[must_use]
     pub fn payload(&self, id: &TaskId) -> Option<TaskPayload> {
-        self.tasks.read().ok()?.get(id)?.payload.clone()
+        self.get(id)?.payload
     }
 
     /// Wait until the task reaches a terminal status, returning its final
@@ -377,6 +556,8 @@ impl TaskManager {
     /// The wait parks on a per-task event notified by terminal transitions;
     /// no lock is held across an await.
     pub async fn wait_terminal(&self, id: &TaskId) -> Option<TaskState> {
+        // Bring a stored task into memory so the loop below sees it.
+        self.get(id)?;
         loop {
             let listener = {
                 let tasks = self.tasks.read().ok()?;
@@ -398,6 +579,8 @@ impl TaskManager {
     /// Cancelling a task already in a terminal status is rejected with
     /// *invalid params* (spec).
     pub fn cancel(&self, id: &TaskId) -> Result<(), McpError> {
+        // Bring a stored task into memory so its status is checked below.
+        let _ = self.get(id);
         let mut tasks = self
             .tasks
             .write()
@@ -417,7 +600,8 @@ impl TaskManager {
             state.task.set_status(TaskStatus::Cancelled);
             state.last_access = Instant::now();
             state.terminal.notify(usize::MAX);
-            Ok(())
+            state.push_status();
+            self.persist(state)
         } else {
             Err(McpError::invalid_params(
                 "tasks/cancel",
@@ -426,6 +610,96 @@ impl TaskManager {
         }
     }
 
+    /// Record a progress report in a task's event log. Reports on a terminal
+    /// task are ignored.
+    pub fn record_progress(
+        &self,
+        id: &TaskId,
+        progress: f64,
+        total: Option<f64>,
+        message: Option<String>,
+    ) -> Result<(), McpError> {
+        let tasks = self
+            .tasks
+            .read()
+            .map_err(|_| McpError::internal("Failed to acquire task lock"))?;
+        let Some(state) = tasks.get(id) else {
+            return Err(McpError::invalid_params(
+                "tasks/get",
+                format!("Unknown task: {}", id.as_str()),
+            ));
+        };
+        if !state.task.status.is_terminal() {
+            state.push_event(TaskEventKind::Progress {
+                progress,
+                total,
+                message,
+            });
+        }
+        Ok(())
+    }
+
+    /// The task's retained events after event `after` (all of them for
+    /// `None`), waiting for one if there are none yet.
+    ///
+    /// Returns an empty list once the task is terminal and nothing follows
+    /// `after`, and `None` if the task is unknown or evicted while waiting.
+    pub async fn next_events(&self, id: &TaskId, after: Option<u64>) -> Option<Vec<TaskEvent>> {
+        // Bring a stored task into memory so the loop below sees it.
+        self.get(id)?;
+        loop {
+            let listener = {
+                let tasks = self.tasks.read().ok()?;
+                let state = tasks.get(id)?;
+                let log = state.log.lock().ok()?;
+                let events = log.after(after);
+                if !events.is_empty() || state.task.status.is_terminal() {
+                    return Some(events);
+                }
+                // Registered under the task lock, which every event takes.
+                log.changed.listen()
+            };
+            listener.await;
+        }
+    }
+
+    /// Stream a task's events after event `after` (all retained ones for
+    /// `None`) as they happen.
+    ///
+    /// The stream ends after the event that makes the task terminal, or when
+    /// the task is unknown or evicted. Web integrations serve it as a per-task
+    /// SSE stream, resuming from the `Last-Event-ID` header.
+    pub fn follow(
+        self: Arc<Self>,
+        id: TaskId,
+        after: Option<u64>,
+    ) -> impl futures::Stream<Item = TaskEvent> + Send + 'static {
+        let pending = std::collections::VecDeque::<TaskEvent>::new();
+        futures::stream::unfold(
+            (self, id, after, pending, false),
+            |(manager, id, mut after, mut pending, mut done)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        after = Some(event.id);
+                        if event.is_terminal() {
+                            done = true;
+                            pending.clear();
+                        }
+                        return Some((event, (manager, id, after, pending, done)));
+                    }
+                    if done {
+                        return None;
+                    }
+                    let events = manager.next_events(&id, after).await?;
+                    if events.is_empty() {
+                        return None;
+                    }
+                    pending.extend(events);
+                }
+            },
+        )
+    }
+
     /// Set a task's status (and optional status message).
     fn set_status(
         &self,
@@ -451,6 +725,16 @@ impl TaskManager {
                     ),
                 ));
             }
+            if !state.task.status.can_transition_to(status) {
+                return Err(McpError::invalid_params(
+                    "tasks/get",
+                    format!(
+                        "task {} cannot move from '{}' to '{status}'",
+                        id.as_str(),
+                        state.task.status
+                    ),
+                ));
+            }
             state.task.set_status(status);
             if message.is_some() {
                 state.task.status_message = message;
@@ -459,7 +743,8 @@ impl TaskManager {
             if status.is_terminal() {
                 state.terminal.notify(usize::MAX);
             }
-            Ok(())
+            state.push_status();
+            self.persist(state)
         } else {
             Err(McpError::invalid_params(
                 "tasks/get",
@@ -468,6 +753,36 @@ impl TaskManager {
         }
     }
 
+    /// Replace a non-terminal task's status message.
+    fn set_message(&self, id: &TaskId, message: String) -> Result<(), McpError> {
+        let mut tasks = self
+            .tasks
+            .write()
+            .map_err(|_| McpError::internal("Failed to acquire task lock"))?;
+
+        let Some(state) = tasks.get_mut(id) else {
+            return Err(McpError::invalid_params(
+                "tasks/get",
+                format!("Unknown task: {}", id.as_str()),
+            ));
+        };
+        if state.task.status.is_terminal() {
+            return Err(McpError::invalid_params(
+                "tasks/get",
+                format!(
+                    "task {} is already terminal ('{}')",
+                    id.as_str(),
+                    state.task.status
+                ),
+            ));
+        }
+        state.task.status_message = Some(message);
+        state.task.last_updated_at = chrono::Utc::now().to_rfc3339();
+        state.last_access = Instant::now();
+        state.push_status();
+        self.persist(state)
+    }
+
     /// Move a task to a terminal status, storing its outcome.
     fn finish(
         &self,
@@ -502,7 +817,8 @@ impl TaskManager {
             state.payload = payload;
             state.last_access = Instant::now();
             state.terminal.notify(usize::MAX);
-            Ok(())
+            state.push_status();
+            self.persist(state)
         } else {
             Err(McpError::invalid_params(
                 "tasks/result",
@@ -513,12 +829,9 @@ impl TaskManager {
 
     /// Remove terminal tasks older than `max_age`.
     pub fn cleanup(&self, max_age: std::time::Duration) {
-        if let Ok(mut tasks) = self.tasks.write() {
-            tasks.retain(|_, state| {
-                let is_terminal = state.task.status.is_terminal();
-                !is_terminal || state.last_access.elapsed() < max_age
-            });
-        }
+        self.retain(|state| {
+            !state.task.status.is_terminal() || state.last_access.elapsed() < max_age
+        });
     }
 
     /// Evict terminal tasks whose age since creation exceeds their own `ttl`
@@ -526,19 +839,31 @@ impl TaskManager {
     /// `None` (unlimited) is never evicted. Called on `create` and on task-store
     /// access, so no timer is required.
     pub fn cleanup_expired(&self) {
+        self.retain(|state| !state.is_expired());
+    }
+
+    /// Keep only the in-memory tasks matching `keep`, removing the others from
+    /// the store as well.
+    fn retain(&self, keep: impl Fn(&TaskState) -> bool) {
+        let mut evicted = Vec::new();
         if let Ok(mut tasks) = self.tasks.write() {
-            tasks.retain(|_, state| {
-                if !state.task.status.is_terminal() {
-                    return true;
-                }
-                match state.task.ttl {
-                    Some(ttl_ms) => {
-                        state.created.elapsed() < std::time::Duration::from_millis(ttl_ms)
+            tasks.retain(|id, state| {
+                let kept = keep(state);
+                if !kept {
+                    evicted.push(id.clone());
+                    // Followers see the task gone.
+                    if let Ok(log) = state.log.lock() {
+                        log.changed.notify(usize::MAX);
                     }
-                    None => true,
                 }
+                kept
             });
         }
+        if let Some(store) = &self.store {
+            for id in &evicted {
+                let _ = store.remove(id);
+            }
+        }
     }
 }
 
@@ -672,6 +997,49 @@ pub async fn route_task_store(
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_stored_tasks_survive_restart() -> Result<(), Box<dyn std::error::Error>> {
+        let store: Arc<dyn TaskStore> = Arc::new(MemoryTaskStore::new());
+
+        let manager = Arc::new(TaskManager::new().with_store(Arc::clone(&store)));
+        let done = manager.create(None);
+        done.complete(serde_json::json!({"result": "ok"}))?;
+        let running = manager.create(None);
+        drop(manager);
+
+        // A new manager over the same store, as after a restart.
+        let manager = TaskManager::new().with_store(store);
+        assert_eq!(manager.list().len(), 2);
+
+        let params = serde_json::json!({ "taskId": done.id().as_str() });
+        let result = route_task_store(&manager, "tasks/result", Some(&params))
+            .await
+            .ok_or("task not found")??;
+        assert_eq!(result["result"], "ok");
+
+        let state = manager.get(running.id()).ok_or("task not found")?;
+        assert_eq!(state.task.status, TaskStatus::Failed);
+        assert_eq!(
+            state.task.status_message.as_deref(),
+            Some(INTERRUPTED_MESSAGE)
+        );
+        assert!(manager.cancel(running.id()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_tasks_leave_the_store() -> Result<(), Box<dyn std::error::Error>> {
+        let store: Arc<dyn TaskStore> = Arc::new(MemoryTaskStore::new());
+        let manager = Arc::new(TaskManager::new().with_store(Arc::clone(&store)));
+        let handle = manager.create(Some(0));
+        handle.complete(serde_json::json!({}))?;
+        assert_eq!(store.list()?.len(), 1);
+
+        manager.cleanup_expired();
+        assert!(store.list()?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_task_manager_create_and_list() {
         let manager = Arc::new(TaskManager::new());
@@ -722,6 +1090,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn status_changes_follow_the_state_machine() -> Result<(), Box<dyn std::error::Error>> {
+        let manager = Arc::new(TaskManager::new());
+        let handle = manager.create(None);
+        let task_id = handle.id().clone();
+
+        // working -> working is not a transition.
+        assert!(handle.mark_working().is_err());
+        handle.mark_input_required()?;
+        assert!(handle.mark_input_required().is_err());
+        handle.mark_working()?;
+        handle.set_status_message("halfway")?;
+        let state = manager.get(&task_id).ok_or("not found")?;
+        assert_eq!(state.task.status, TaskStatus::Working);
+        assert_eq!(state.task.status_message.as_deref(), Some("halfway"));
+
+        handle.complete(serde_json::json!({}))?;
+        assert!(handle.mark_working().is_err());
+        assert!(handle.set_status_message("too late").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_task_cancellation() -> Result<(), Box<dyn std::error::Error>> {
         let manager = Arc::new(TaskManager::new());
@@ -816,6 +1206,40 @@ mod tests {
         assert!(manager.get(&id).is_none(), "access did not trigger cleanup");
     }
 
+    #[tokio::test]
+    async fn follow_streams_events_until_terminal_and_resumes() {
+        use futures::StreamExt;
+
+        let manager = Arc::new(TaskManager::new());
+        let handle = manager.create(None);
+        let id = handle.id().clone();
+        let follower = tokio::spawn(
+            Arc::clone(&manager)
+                .follow(id.clone(), None)
+                .collect::<Vec<_>>(),
+        );
+
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        handle
+            .record_progress(1.0, Some(2.0), Some("halfway".into()))
+            .unwrap();
+        handle.complete(serde_json::json!({})).unwrap();
+        // Ignored once the task is terminal.
+        handle.record_progress(2.0, Some(2.0), None).unwrap();
+
+        let events = follower.await.unwrap();
+        let kinds: Vec<_> = events.iter().map(TaskEvent::event_type).collect();
+        assert_eq!(kinds, ["status", "progress", "status"]);
+        assert_eq!(events[1].data()["message"], "halfway");
+        assert!(events[2].is_terminal());
+        assert_eq!(events[2].data()["status"], "completed");
+
+        // Resuming after the first event replays only the rest.
+        let resumed: Vec<_> = manager.follow(id, Some(events[0].id)).collect().await;
+        let ids: Vec<_> = resumed.iter().map(|e| e.id).collect();
+        assert_eq!(ids, [events[1].id, events[2].id]);
+    }
+
     // --- #143 phase 2: blocking tasks/result, error passthrough, _meta -----
 
     fn result_params(id: &TaskId) -> Value {
@@ -860,7 +1284,7 @@ mod tests {
         };
 
         let params = result_params(handle.id());
-        let started = std::time::Instant::now();
+        let started = Instant::now();
         let result = tokio::time::timeout(
             std::time::Duration::from_secs(5),
             route_task_store(&manager, "tasks/result", Some(&params)),
@@ -0,0 +1,288 @@
+//! Durable backing for a [`TaskManager`](super::TaskManager).
+//!
+//! A [`TaskManager`](super::TaskManager) tracks live tasks in memory. Giving
+//! it a [`TaskStore`] with [`TaskManager::with_store`](super::TaskManager::with_store)
+//! writes every task and its outcome through to the store, so `tasks/get`,
+//! `tasks/list`, and `tasks/result` keep answering for tasks created before a
+//! restart.
+//!
+//! Two stores are provided: [`MemoryTaskStore`], which keeps records in a map
+//! and is mostly useful in tests, and `FileTaskStore` (feature
+//! `file-task-store`), which keeps one JSON file per task in a directory.
+//!
+//! A store should back a single task manager: a manager given a store fails
+//! the tasks it finds still running, since the work behind them was lost with
+//! the previous process.
+
+use super::TaskPayload;
+use crate::error::McpError;
+use crate::types::task::{Task, TaskId, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::RwLock;
+
+/// A task as kept by a [`TaskStore`]: its metadata and, once terminal, the
+/// outcome returned by `tasks/result`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredTask {
+    /// Task metadata (status, timestamps, ttl).
+    pub task: Task,
+    /// The stored outcome, once the task is terminal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<TaskPayload>,
+}
+
+/// Durable storage for task records.
+///
+/// Implementations must be safe to call from several threads; the
+/// [`TaskManager`](super::TaskManager) calls them while holding its own lock,
+/// so they should not block for long.
+pub trait TaskStore: Send + Sync + fmt::Debug {
+    /// Get a task by ID.
+    fn get(&self, id: &TaskId) -> Result<Option<StoredTask>, McpError>;
+
+    /// Insert or replace a task.
+    fn put(&self, record: StoredTask) -> Result<(), McpError>;
+
+    /// List every stored task.
+    fn list(&self) -> Result<Vec<StoredTask>, McpError>;
+
+    /// Remove a task. Removing an unknown task is not an error.
+    fn remove(&self, id: &TaskId) -> Result<(), McpError>;
+
+    /// Set a task's status (and status message, if given), returning the
+    /// updated task or `None` if it is unknown.
+    fn update_status(
+        &self,
+        id: &TaskId,
+        status: TaskStatus,
+        message: Option<String>,
+    ) -> Result<Option<Task>, McpError> {
+        let Some(mut record) = self.get(id)? else {
+            return Ok(None);
+        };
+        record.task.set_status(status);
+        if message.is_some() {
+            record.task.status_message = message;
+        }
+        let task = record.task.clone();
+        self.put(record)?;
+        Ok(Some(task))
+    }
+}
+
+fn lock_error() -> McpError {
+    McpError::internal("Failed to acquire task store lock")
+}
+
+/// A [`TaskStore`] keeping records in memory.
+#[derive(Debug, Default)]
+pub struct MemoryTaskStore {
+    records: RwLock<HashMap<TaskId, StoredTask>>,
+}
+
+impl MemoryTaskStore {
+    /// Create an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TaskStore for MemoryTaskStore {
+    fn get(&self, id: &TaskId) -> Result<Option<StoredTask>, McpError> {
+        let records = self.records.read().map_err(|_| lock_error())?;
+        Ok(records.get(id).cloned())
+    }
+
+    fn put(&self, record: StoredTask) -> Result<(), McpError> {
+        let mut records = self.records.write().map_err(|_| lock_error())?;
+        records.insert(record.task.task_id.clone(), record);
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<StoredTask>, McpError> {
+        let records = self.records.read().map_err(|_| lock_error())?;
+        Ok(records.values().cloned().collect())
+    }
+
+    fn remove(&self, id: &TaskId) -> Result<(), McpError> {
+        let mut records = self.records.write().map_err(|_| lock_error())?;
+        records.remove(id);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "file-task-store")]
+pub use file::FileTaskStore;
+
+#[cfg(feature = "file-task-store")]
+mod file {
+    use super::{StoredTask, TaskStore};
+    use crate::error::McpError;
+    use crate::types::task::TaskId;
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    /// A [`TaskStore`] keeping one `<task id>.json` file per task in a
+    /// directory.
+    ///
+    /// Files are replaced atomically (written beside the target, then
+    /// renamed), so a crash never leaves a half-written record.
+    #[derive(Debug, Clone)]
+    pub struct FileTaskStore {
+        dir: PathBuf,
+    }
+
+    impl FileTaskStore {
+        /// Open a store in `dir`, creating the directory if needed.
+        pub fn new(dir: impl Into<PathBuf>) -> Result<Self, McpError> {
+            let dir = dir.into();
+            std::fs::create_dir_all(&dir).map_err(|e| io_error(&dir, &e))?;
+            Ok(Self { dir })
+        }
+
+        /// The directory holding the task files.
+        #[must_use]
+        pub fn dir(&self) -> &Path {
+            &self.dir
+        }
+
+        /// The file for `id`, or `None` if the ID cannot be a file name.
+        fn path(&self, id: &TaskId) -> Option<PathBuf> {
+            let id = id.as_str();
+            let valid = !id.is_empty()
+                && !id.starts_with('.')
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+            valid.then(|| self.dir.join(format!("{id}.json")))
+        }
+    }
+
+    fn io_error(path: &Path, error: &io::Error) -> McpError {
+        McpError::internal(format!("Task store {}: {error}", path.display()))
+    }
+
+    fn read(path: &Path) -> Result<Option<StoredTask>, McpError> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(path, &e)),
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|e| McpError::internal(format!("Task store {}: {e}", path.display())))
+    }
+
+    impl TaskStore for FileTaskStore {
+        fn get(&self, id: &TaskId) -> Result<Option<StoredTask>, McpError> {
+            match self.path(id) {
+                Some(path) => read(&path),
+                None => Ok(None),
+            }
+        }
+
+        fn put(&self, record: StoredTask) -> Result<(), McpError> {
+            let Some(path) = self.path(&record.task.task_id) else {
+                return Err(McpError::internal(format!(
+                    "Task ID {} cannot be stored as a file",
+                    record.task.task_id.as_str()
+                )));
+            };
+            let bytes = serde_json::to_vec(&record)
+                .map_err(|e| McpError::internal(format!("Failed to serialize task: {e}")))?;
+            let tmp = path.with_extension("json.tmp");
+            std::fs::write(&tmp, bytes).map_err(|e| io_error(&tmp, &e))?;
+            std::fs::rename(&tmp, &path).map_err(|e| io_error(&path, &e))
+        }
+
+        fn list(&self) -> Result<Vec<StoredTask>, McpError> {
+            let entries = std::fs::read_dir(&self.dir).map_err(|e| io_error(&self.dir, &e))?;
+            let mut records = Vec::new();
+            for entry in entries {
+                let path = entry.map_err(|e| io_error(&self.dir, &e))?.path();
+                if path.extension().is_some_and(|ext| ext == "json") {
+                    records.extend(read(&path)?);
+                }
+            }
+            Ok(records)
+        }
+
+        fn remove(&self, id: &TaskId) -> Result<(), McpError> {
+            let Some(path) = self.path(id) else {
+                return Ok(());
+            };
+            match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(io_error(&path, &e)),
+                _ => Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(status: TaskStatus) -> StoredTask {
+        let mut task = Task::create();
+        task.set_status(status);
+        StoredTask {
+            task,
+            payload: None,
+        }
+    }
+
+    fn exercise(store: &dyn TaskStore) -> Result<(), Box<dyn std::error::Error>> {
+        let working = record(TaskStatus::Working);
+        let id = working.task.task_id.clone();
+        store.put(working)?;
+        assert_eq!(store.list()?.len(), 1);
+
+        let task = store
+            .update_status(&id, TaskStatus::Failed, Some("boom".to_string()))?
+            .ok_or("task not found")?;
+        assert_eq!(task.status, TaskStatus::Failed);
+        let stored = store.get(&id)?.ok_or("task not found")?;
+        assert_eq!(stored.task.status_message.as_deref(), Some("boom"));
+
+        store.remove(&id)?;
+        assert!(store.get(&id)?.is_none());
+        assert!(
+            store
+                .update_status(&id, TaskStatus::Completed, None)?
+                .is_none()
+        );
+        store.remove(&id)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_store() -> Result<(), Box<dyn std::error::Error>> {
+        exercise(&MemoryTaskStore::new())
+    }
+
+    #[cfg(feature = "file-task-store")]
+    #[test]
+    fn test_file_store() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!("mcpkit-tasks-{}", uuid::Uuid::new_v4()));
+        let store = FileTaskStore::new(&dir)?;
+        exercise(&store)?;
+
+        let mut done = record(TaskStatus::Completed);
+        done.payload = Some(TaskPayload::Success(serde_json::json!({ "ok": true })));
+        let id = done.task.task_id.clone();
+        store.put(done)?;
+
+        // A second store over the same directory sees the record.
+        let reopened = FileTaskStore::new(&dir)?;
+        let stored = reopened.get(&id)?.ok_or("task not found")?;
+        assert!(matches!(stored.payload, Some(TaskPayload::Success(_))));
+        assert!(reopened.get(&TaskId::new("../escape"))?.is_none());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
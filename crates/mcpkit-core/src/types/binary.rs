@@ -0,0 +1,344 @@
+//! Helpers for binary payloads.
+//!
+//! MCP carries images, audio, and resource blobs as base64 text. The helpers
+//! here work from raw bytes instead: they sniff MIME types, refuse payloads
+//! too large to inline, and encode or decode base64 in chunks so a file
+//! never sits in memory as both bytes and text.
+//!
+//! Most code uses them through [`Content::image_from_path`],
+//! [`Content::audio_bytes`], [`ResourceContents::blob_from_reader`] and
+//! friends rather than calling this module directly.
+//!
+//! The async helpers take [`futures::io::AsyncRead`] and
+//! [`futures::io::AsyncWrite`]. Tokio types can be adapted with
+//! `tokio_util::compat`.
+//!
+//! [`Content::image_from_path`]: super::Content::image_from_path
+//! [`Content::audio_bytes`]: super::Content::audio_bytes
+//! [`ResourceContents::blob_from_reader`]: super::ResourceContents::blob_from_reader
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use base64::write::EncoderStringWriter;
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Default limit on the size of a binary payload read from a file or stream.
+pub const DEFAULT_MAX_BINARY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Size of the chunks read from a stream.
+const READ_CHUNK: usize = 64 * 1024;
+
+/// Size of the base64 chunks decoded at a time. A multiple of 4.
+const DECODE_CHUNK: usize = 64 * 1024;
+
+/// Errors from loading, encoding, or decoding binary payloads.
+#[derive(Debug, thiserror::Error)]
+pub enum BinaryError {
+    /// The payload exceeds the size limit.
+    #[error("binary payload of {size} bytes exceeds the limit of {limit} bytes")]
+    TooLarge {
+        /// Size of the payload, or of the part read before giving up.
+        size: u64,
+        /// The limit that was exceeded.
+        limit: usize,
+    },
+
+    /// The MIME type could not be determined from the data or file name.
+    #[error("could not determine the MIME type of the payload")]
+    UnknownMimeType,
+
+    /// The payload is not of the expected kind.
+    #[error("expected {expected} content, got {mime_type}")]
+    UnexpectedMimeType {
+        /// The expected kind (`image` or `audio`).
+        expected: &'static str,
+        /// The MIME type that was detected.
+        mime_type: String,
+    },
+
+    /// Reading or writing the payload failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The payload is not valid base64.
+    #[error("invalid base64 data: {0}")]
+    Decode(#[from] base64::DecodeError),
+}
+
+impl From<BinaryError> for crate::error::McpError {
+    fn from(err: BinaryError) -> Self {
+        Self::internal(err.to_string())
+    }
+}
+
+/// Detect the MIME type of a payload from its leading bytes.
+///
+/// Recognizes common image (PNG, JPEG, GIF, WebP, BMP, ICO, TIFF, AVIF),
+/// audio (WAV, MP3, FLAC, Ogg, M4A), and document (PDF) formats. Returns
+/// `None` for anything else, including text formats like SVG.
+#[must_use]
+pub fn sniff_mime_type(data: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+        (b"II*\x00", "image/tiff"),
+        (b"MM\x00*", "image/tiff"),
+        (b"fLaC", "audio/flac"),
+        (b"OggS", "audio/ogg"),
+        (b"ID3", "audio/mpeg"),
+        (b"%PDF-", "application/pdf"),
+    ];
+
+    if data.len() >= 12 && &data[..4] == b"RIFF" {
+        return match &data[8..12] {
+            b"WEBP" => Some("image/webp"),
+            b"WAVE" => Some("audio/wav"),
+            _ => None,
+        };
+    }
+    if data.len() >= 12 && &data[4..8] == b"ftyp" {
+        return match &data[8..12] {
+            b"avif" | b"avis" => Some("image/avif"),
+            b"M4A " => Some("audio/mp4"),
+            _ => None,
+        };
+    }
+    // MPEG audio frame sync without an ID3 tag.
+    if data.len() >= 2 && data[0] == 0xff && data[1] & 0xe0 == 0xe0 && data[1] & 0x06 != 0 {
+        return Some("audio/mpeg");
+    }
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| data.starts_with(magic))
+        .map(|&(_, mime)| mime)
+}
+
+/// Guess the MIME type of a file from its extension.
+#[must_use]
+pub fn mime_type_from_extension(path: impl AsRef<Path>) -> Option<&'static str> {
+    let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+    Some(match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "tif" | "tiff" => "image/tiff",
+        "avif" => "image/avif",
+        "svg" => "image/svg+xml",
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "ogg" | "oga" => "audio/ogg",
+        "m4a" => "audio/mp4",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "txt" => "text/plain",
+        _ => return None,
+    })
+}
+
+/// Encode bytes as standard base64.
+#[must_use]
+pub fn encode(data: &[u8]) -> String {
+    STANDARD.encode(data)
+}
+
+/// Decode standard base64.
+///
+/// # Errors
+///
+/// Returns an error if `data` is not valid base64.
+pub fn decode(data: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    STANDARD.decode(data)
+}
+
+/// Read a file, refusing it if it is larger than `max_size` bytes.
+///
+/// # Errors
+///
+/// Returns [`BinaryError::TooLarge`] if the file exceeds the limit, or
+/// [`BinaryError::Io`] if it cannot be read.
+pub fn read_file(path: impl AsRef<Path>, max_size: usize) -> Result<Vec<u8>, BinaryError> {
+    let file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    check_size(len, max_size)?;
+    // The file may grow between the metadata call and the read.
+    let mut data = Vec::with_capacity(usize::try_from(len).unwrap_or(max_size));
+    file.take(max_size as u64 + 1).read_to_end(&mut data)?;
+    check_size(data.len() as u64, max_size)?;
+    Ok(data)
+}
+
+/// Read a stream to the end and return it base64 encoded.
+///
+/// The stream is encoded as it is read, so only the encoded text is held in
+/// memory. Reading stops with [`BinaryError::TooLarge`] as soon as more than
+/// `max_size` bytes have been read.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or the stream exceeds the limit.
+pub async fn encode_reader<R>(mut reader: R, max_size: usize) -> Result<String, BinaryError>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut encoder = EncoderStringWriter::new(&STANDARD);
+    let mut buf = vec![0u8; READ_CHUNK];
+    let mut total = 0u64;
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        total += n as u64;
+        check_size(total, max_size)?;
+        encoder.write_all(&buf[..n])?;
+    }
+    Ok(encoder.into_inner())
+}
+
+/// Decode base64 into a writer, a chunk at a time.
+///
+/// Returns the number of bytes written. The writer is flushed but not
+/// closed.
+///
+/// # Errors
+///
+/// Returns an error if `data` is not valid base64 or writing fails.
+pub async fn decode_to_writer<W>(data: &str, mut writer: W) -> Result<u64, BinaryError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut written = 0u64;
+    // Base64 is ASCII, so byte chunks never split a character.
+    for chunk in data.as_bytes().chunks(DECODE_CHUNK) {
+        let bytes = STANDARD.decode(chunk)?;
+        writer.write_all(&bytes).await?;
+        written += bytes.len() as u64;
+    }
+    writer.flush().await?;
+    Ok(written)
+}
+
+/// Fail with [`BinaryError::TooLarge`] if `size` exceeds `max_size`.
+pub(crate) const fn check_size(size: u64, max_size: usize) -> Result<(), BinaryError> {
+    if size > max_size as u64 {
+        Err(BinaryError::TooLarge {
+            size,
+            limit: max_size,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Determine the MIME type of a payload, preferring its contents over the
+/// file name, and check that it is of the `expected` kind.
+pub(crate) fn detect_mime_type(
+    data: &[u8],
+    path: Option<&Path>,
+    expected: &'static str,
+) -> Result<&'static str, BinaryError> {
+    let mime_type = sniff_mime_type(data)
+        .or_else(|| path.and_then(mime_type_from_extension))
+        .ok_or(BinaryError::UnknownMimeType)?;
+    if mime_type.split('/').next() == Some(expected) {
+        Ok(mime_type)
+    } else {
+        Err(BinaryError::UnexpectedMimeType {
+            expected,
+            mime_type: mime_type.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+
+    #[test]
+    fn sniffs_common_formats() {
+        assert_eq!(sniff_mime_type(PNG), Some("image/png"));
+        assert_eq!(sniff_mime_type(b"\xff\xd8\xff\xe0JFIF"), Some("image/jpeg"));
+        assert_eq!(sniff_mime_type(b"GIF89a...."), Some("image/gif"));
+        assert_eq!(sniff_mime_type(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(sniff_mime_type(b"RIFF\0\0\0\0WAVEfmt "), Some("audio/wav"));
+        assert_eq!(sniff_mime_type(b"ID3\x04\0\0"), Some("audio/mpeg"));
+        assert_eq!(sniff_mime_type(b"\xff\xfb\x90\x00"), Some("audio/mpeg"));
+        assert_eq!(sniff_mime_type(b"\0\0\0\x20ftypM4A "), Some("audio/mp4"));
+        assert_eq!(sniff_mime_type(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff_mime_type(b"<svg xmlns="), None);
+        assert_eq!(sniff_mime_type(b""), None);
+    }
+
+    #[test]
+    fn mime_type_checks() {
+        assert_eq!(
+            mime_type_from_extension("photos/Cat.JPG"),
+            Some("image/jpeg")
+        );
+        assert_eq!(mime_type_from_extension("README"), None);
+
+        assert_eq!(detect_mime_type(PNG, None, "image").unwrap(), "image/png");
+        let svg = Path::new("logo.svg");
+        assert_eq!(
+            detect_mime_type(b"<svg/>", Some(svg), "image").unwrap(),
+            "image/svg+xml"
+        );
+        assert!(matches!(
+            detect_mime_type(PNG, None, "audio"),
+            Err(BinaryError::UnexpectedMimeType { .. })
+        ));
+        assert!(matches!(
+            detect_mime_type(b"hello", None, "image"),
+            Err(BinaryError::UnknownMimeType)
+        ));
+    }
+
+    #[test]
+    fn read_file_enforces_the_limit() {
+        let path = std::env::temp_dir().join(format!("mcpkit-binary-{}.png", std::process::id()));
+        std::fs::write(&path, PNG).unwrap();
+
+        assert_eq!(read_file(&path, PNG.len()).unwrap(), PNG);
+        assert!(matches!(
+            read_file(&path, PNG.len() - 1),
+            Err(BinaryError::TooLarge { limit, .. }) if limit == PNG.len() - 1
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn streams_round_trip() {
+        // Spans several read chunks and ends mid-chunk.
+        let data: Vec<u8> = (0..READ_CHUNK * 2 + 1000)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let encoded = encode_reader(futures::io::Cursor::new(&data), data.len())
+            .await
+            .unwrap();
+        assert_eq!(encoded, encode(&data));
+
+        let mut out = futures::io::Cursor::new(Vec::new());
+        let written = decode_to_writer(&encoded, &mut out).await.unwrap();
+        assert_eq!(written, data.len() as u64);
+        assert_eq!(out.into_inner(), data);
+
+        let err = encode_reader(futures::io::Cursor::new(&data), READ_CHUNK)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BinaryError::TooLarge { .. }));
+    }
+}
@@ -130,6 +130,12 @@ impl Completion {
     }
 }
 
+impl From<Vec<String>> for Completion {
+    fn from(values: Vec<String>) -> Self {
+        Self::new(values)
+    }
+}
+
 /// Result of a completion request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompleteResult {
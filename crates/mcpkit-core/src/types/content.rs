@@ -2,15 +2,28 @@
 //!
 //! Content represents the payload in tool results, resource contents,
 //! and prompt messages. MCP supports text, images, audio, and embedded resources.
+//!
+//! Images and audio can be built from raw bytes or files with
+//! [`Content::image_bytes`], [`Content::image_from_path`],
+//! [`Content::audio_bytes`], and [`Content::audio_from_path`], which detect
+//! the MIME type and handle the base64 encoding.
+//!
+//! Vendor-specific kinds travel as [`Content::Custom`]: any block whose `type`
+//! is not one of the standard kinds is kept verbatim, so it survives a
+//! round-trip and peers that don't understand it can skip it. Implement
+//! [`CustomContentType`] to give such a kind a typed representation.
 
+use super::binary::{self, BinaryError, DEFAULT_MAX_BINARY_SIZE};
 use super::resource::ResourceContents;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
 
 /// Content that can be included in messages and results.
 ///
 /// Content is polymorphic - it can be text, images, audio, or
 /// references to other resources.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum Content {
     /// Plain text content.
@@ -24,6 +37,49 @@ pub enum Content {
     /// A link to a resource (not embedded).
     #[serde(rename = "resource_link")]
     ResourceLink(ResourceLinkContent),
+    /// Content of a non-standard kind, preserved as-is.
+    #[serde(untagged)]
+    Custom(CustomContent),
+}
+
+/// The `type` values of the standard content kinds.
+const STANDARD_KINDS: &[&str] = &["text", "image", "audio", "resource", "resource_link"];
+
+/// Deserialization mirror of the standard [`Content`] variants.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum StandardContent {
+    Text(TextContent),
+    Image(ImageContent),
+    Audio(AudioContent),
+    Resource(ResourceContent),
+    #[serde(rename = "resource_link")]
+    ResourceLink(ResourceLinkContent),
+}
+
+impl<'de> Deserialize<'de> for Content {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let kind = value
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or_else(|| de::Error::missing_field("type"))?;
+        // Malformed standard blocks are errors, not custom content.
+        if !STANDARD_KINDS.contains(&kind) {
+            return CustomContent::deserialize(value)
+                .map(Self::Custom)
+                .map_err(de::Error::custom);
+        }
+        Ok(
+            match StandardContent::deserialize(value).map_err(de::Error::custom)? {
+                StandardContent::Text(c) => Self::Text(c),
+                StandardContent::Image(c) => Self::Image(c),
+                StandardContent::Audio(c) => Self::Audio(c),
+                StandardContent::Resource(c) => Self::Resource(c),
+                StandardContent::ResourceLink(c) => Self::ResourceLink(c),
+            },
+        )
+    }
 }
 
 impl Content {
@@ -69,6 +125,75 @@ impl Content {
         })
     }
 
+    /// Create image content from raw bytes, detecting the MIME type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a recognized image format.
+    pub fn image_bytes(data: &[u8]) -> Result<Self, BinaryError> {
+        let mime_type = binary::detect_mime_type(data, None, "image")?;
+        Ok(Self::image(binary::encode(data), mime_type))
+    }
+
+    /// Create image content from a file.
+    ///
+    /// The MIME type is detected from the file's contents, falling back to
+    /// its extension (so SVG files work). Files larger than
+    /// [`DEFAULT_MAX_BINARY_SIZE`] are refused; use [`binary::read_file`]
+    /// and [`Content::image_bytes`] for a different limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is too large, or is not
+    /// an image.
+    pub fn image_from_path(path: impl AsRef<std::path::Path>) -> Result<Self, BinaryError> {
+        let path = path.as_ref();
+        let data = binary::read_file(path, DEFAULT_MAX_BINARY_SIZE)?;
+        let mime_type = binary::detect_mime_type(&data, Some(path), "image")?;
+        Ok(Self::image(binary::encode(&data), mime_type))
+    }
+
+    /// Create audio content from raw bytes, detecting the MIME type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a recognized audio format.
+    pub fn audio_bytes(data: &[u8]) -> Result<Self, BinaryError> {
+        let mime_type = binary::detect_mime_type(data, None, "audio")?;
+        Ok(Self::audio(binary::encode(data), mime_type))
+    }
+
+    /// Create audio content from a file.
+    ///
+    /// Detection and size limits work as in [`Content::image_from_path`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, is too large, or is not
+    /// audio.
+    pub fn audio_from_path(path: impl AsRef<std::path::Path>) -> Result<Self, BinaryError> {
+        let path = path.as_ref();
+        let data = binary::read_file(path, DEFAULT_MAX_BINARY_SIZE)?;
+        let mime_type = binary::detect_mime_type(&data, Some(path), "audio")?;
+        Ok(Self::audio(binary::encode(&data), mime_type))
+    }
+
+    /// Decode the binary payload of image, audio, or embedded blob content.
+    ///
+    /// Returns `None` for content without binary data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not valid base64.
+    pub fn decode_data(&self) -> Result<Option<Vec<u8>>, base64::DecodeError> {
+        match self {
+            Self::Image(c) => c.decode().map(Some),
+            Self::Audio(c) => c.decode().map(Some),
+            Self::Resource(c) => c.resource.decode_blob(),
+            _ => Ok(None),
+        }
+    }
+
     /// Create a resource reference.
     #[must_use]
     pub fn resource(uri: impl Into<String>) -> Self {
@@ -136,6 +261,221 @@ impl Content {
             _ => None,
         }
     }
+
+    /// The block's annotations, if any.
+    ///
+    /// Custom content has no annotations.
+    #[must_use]
+    pub const fn annotations(&self) -> Option<&ContentAnnotations> {
+        match self {
+            Self::Text(c) => c.annotations.as_ref(),
+            Self::Image(c) => c.annotations.as_ref(),
+            Self::Audio(c) => c.annotations.as_ref(),
+            Self::Resource(c) => c.annotations.as_ref(),
+            Self::ResourceLink(c) => c.annotations.as_ref(),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Mutable access to the block's annotations slot; `None` for custom
+    /// content, which has none.
+    pub(crate) const fn annotations_mut(&mut self) -> Option<&mut Option<ContentAnnotations>> {
+        match self {
+            Self::Text(c) => Some(&mut c.annotations),
+            Self::Image(c) => Some(&mut c.annotations),
+            Self::Audio(c) => Some(&mut c.annotations),
+            Self::Resource(c) => Some(&mut c.annotations),
+            Self::ResourceLink(c) => Some(&mut c.annotations),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Replace the block's annotations (ignored for custom content).
+    #[must_use]
+    pub fn with_annotations(mut self, annotations: ContentAnnotations) -> Self {
+        if let Some(slot) = self.annotations_mut() {
+            *slot = Some(annotations);
+        }
+        self
+    }
+
+    /// Add `role` to the block's intended audience (ignored for custom
+    /// content).
+    ///
+    /// ```rust
+    /// use mcpkit_core::types::{Content, Role};
+    ///
+    /// let note = Content::text("Saved 3 files").for_audience(Role::User).priority(0.9);
+    /// assert!(note.is_for(Role::User));
+    /// assert!(!note.is_for(Role::Assistant));
+    /// ```
+    #[must_use]
+    pub fn for_audience(mut self, role: Role) -> Self {
+        if let Some(slot) = self.annotations_mut() {
+            let audience = slot
+                .get_or_insert_with(ContentAnnotations::default)
+                .audience
+                .get_or_insert_with(Vec::new);
+            if !audience.contains(&role) {
+                audience.push(role);
+            }
+        }
+        self
+    }
+
+    /// Set the block's priority, clamped to `0.0..=1.0` (ignored for custom
+    /// content).
+    #[must_use]
+    pub fn priority(mut self, priority: f64) -> Self {
+        if let Some(slot) = self.annotations_mut() {
+            slot.get_or_insert_with(ContentAnnotations::default)
+                .priority = Some(priority.clamp(0.0, 1.0));
+        }
+        self
+    }
+
+    /// Whether the block is intended for `role`.
+    ///
+    /// Blocks without an audience are intended for everyone.
+    #[must_use]
+    pub fn is_for(&self, role: Role) -> bool {
+        self.annotations().is_none_or(|a| a.is_for(role))
+    }
+
+    /// Create content of a non-standard kind from its raw fields.
+    ///
+    /// `kind` should not be one of the standard kinds (`text`, `image`, ...),
+    /// or peers will try to read the block as that kind.
+    #[must_use]
+    pub fn custom(kind: impl Into<String>, data: Map<String, Value>) -> Self {
+        Self::Custom(CustomContent {
+            kind: kind.into(),
+            data,
+        })
+    }
+
+    /// Create custom content from a typed [`CustomContentType`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` does not serialize to a JSON object.
+    pub fn from_custom<T: CustomContentType>(value: &T) -> Result<Self, serde_json::Error> {
+        match serde_json::to_value(value)? {
+            Value::Object(mut data) => {
+                data.remove("type");
+                Ok(Self::custom(T::KIND, data))
+            }
+            other => Err(serde::ser::Error::custom(format!(
+                "custom content `{}` must serialize to an object, got {other}",
+                T::KIND
+            ))),
+        }
+    }
+
+    /// Check if this is content of a non-standard kind.
+    #[must_use]
+    pub const fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
+
+    /// Get the custom content if this is a non-standard kind.
+    #[must_use]
+    pub const fn as_custom(&self) -> Option<&CustomContent> {
+        match self {
+            Self::Custom(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// The block's `type` discriminator (e.g. `"text"`, or a custom kind).
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Text(_) => "text",
+            Self::Image(_) => "image",
+            Self::Audio(_) => "audio",
+            Self::Resource(_) => "resource",
+            Self::ResourceLink(_) => "resource_link",
+            Self::Custom(c) => &c.kind,
+        }
+    }
+
+    /// Decode this block as `T` if it is custom content of kind `T::KIND`.
+    ///
+    /// Returns `Ok(None)` for any other content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the kind matches but the fields don't decode as `T`.
+    pub fn to_custom<T: CustomContentType>(&self) -> Result<Option<T>, serde_json::Error> {
+        match self {
+            Self::Custom(c) if c.kind == T::KIND => c.decode().map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// A typed vendor-specific content kind.
+///
+/// The type serializes to the block's fields; [`KIND`](Self::KIND) becomes
+/// its `type`. Namespace the kind (e.g. `"acme/model3d"`) so it can't collide
+/// with kinds added to the protocol later.
+///
+/// # Example
+///
+/// ```rust
+/// use mcpkit_core::types::{Content, CustomContentType};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct ModelRef {
+///     uri: String,
+///     format: String,
+/// }
+///
+/// impl CustomContentType for ModelRef {
+///     const KIND: &'static str = "acme/model3d";
+/// }
+///
+/// let content = Content::from_custom(&ModelRef {
+///     uri: "https://cdn.example.com/chair.glb".into(),
+///     format: "glb".into(),
+/// })?;
+/// let wire = serde_json::to_string(&content)?;
+/// assert!(wire.contains(r#""type":"acme/model3d""#));
+///
+/// let back: Content = serde_json::from_str(&wire)?;
+/// let model: ModelRef = back.to_custom()?.expect("acme/model3d content");
+/// assert_eq!(model.format, "glb");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub trait CustomContentType: Serialize + DeserializeOwned {
+    /// The `type` discriminator used on the wire.
+    const KIND: &'static str;
+}
+
+/// Content of a non-standard kind.
+///
+/// Serializes as `{ "type": kind, ...data }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomContent {
+    /// The `type` discriminator.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The remaining fields of the block.
+    #[serde(flatten)]
+    pub data: Map<String, Value>,
+}
+
+impl CustomContent {
+    /// Decode the block's fields as `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fields don't decode as `T`.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(Value::Object(self.data.clone()))
+    }
 }
 
 /// Text content.
@@ -167,6 +507,17 @@ pub struct ImageContent {
     pub meta: Option<super::meta::Meta>,
 }
 
+impl ImageContent {
+    /// Decode the image data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not valid base64.
+    pub fn decode(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        binary::decode(&self.data)
+    }
+}
+
 /// Audio content (base64 encoded).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioContent {
@@ -183,6 +534,17 @@ pub struct AudioContent {
     pub meta: Option<super::meta::Meta>,
 }
 
+impl AudioContent {
+    /// Decode the audio data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not valid base64.
+    pub fn decode(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        binary::decode(&self.data)
+    }
+}
+
 /// Embedded resource content.
 ///
 /// The resource payload is nested under `resource` to match the spec's
@@ -295,6 +657,16 @@ impl ContentAnnotations {
         self.priority = Some(priority.clamp(0.0, 1.0));
         self
     }
+
+    /// Whether content with these annotations is intended for `role`.
+    ///
+    /// No audience means everyone.
+    #[must_use]
+    pub fn is_for(&self, role: Role) -> bool {
+        self.audience
+            .as_ref()
+            .is_none_or(|audience| audience.contains(&role))
+    }
 }
 
 /// The role of a message participant.
@@ -320,6 +692,39 @@ impl std::fmt::Display for Role {
 mod tests {
     use super::*;
 
+    #[test]
+    fn binary_content_helpers() -> Result<(), Box<dyn std::error::Error>> {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+        let image = Content::image_bytes(png)?;
+        let Content::Image(ref img) = image else {
+            return Err("expected image content".into());
+        };
+        assert_eq!(img.mime_type, "image/png");
+        assert_eq!(image.decode_data()?.as_deref(), Some(&png[..]));
+
+        let wav = b"RIFF\x24\x00\x00\x00WAVEfmt ";
+        let audio = Content::audio_bytes(wav)?;
+        assert!(audio.is_audio());
+        assert_eq!(audio.decode_data()?.as_deref(), Some(&wav[..]));
+
+        assert!(matches!(
+            Content::audio_bytes(png),
+            Err(BinaryError::UnexpectedMimeType { .. })
+        ));
+        assert_eq!(Content::text("hi").decode_data()?, None);
+
+        // SVG has no magic bytes; the extension decides.
+        let path = std::env::temp_dir().join(format!("mcpkit-content-{}.svg", std::process::id()));
+        std::fs::write(&path, "<svg xmlns=\"http://www.w3.org/2000/svg\"/>")?;
+        let svg = Content::image_from_path(&path);
+        std::fs::remove_file(&path)?;
+        let Content::Image(svg) = svg? else {
+            return Err("expected image content".into());
+        };
+        assert_eq!(svg.mime_type, "image/svg+xml");
+        Ok(())
+    }
+
     #[test]
     fn content_block_meta_round_trips() -> Result<(), Box<dyn std::error::Error>> {
         // `_meta` on a content block must survive deserialize -> serialize.
@@ -406,6 +811,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn audience_and_priority_helpers() -> Result<(), Box<dyn std::error::Error>> {
+        let content = Content::text("hi")
+            .for_audience(Role::User)
+            .for_audience(Role::User)
+            .priority(1.5);
+        let annotations = content.annotations().ok_or("Expected annotations")?;
+        assert_eq!(annotations.audience.as_deref(), Some(&[Role::User][..]));
+        assert_eq!(annotations.priority, Some(1.0));
+        assert!(!content.is_for(Role::Assistant));
+        assert!(Content::text("everyone").is_for(Role::Assistant));
+        assert!(
+            Content::text("both")
+                .for_audience(Role::User)
+                .for_audience(Role::Assistant)
+                .is_for(Role::Assistant)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_embedded_resource_nests_under_resource_key() -> Result<(), Box<dyn std::error::Error>> {
         // Spec EmbeddedResource: { "type": "resource", "resource": { "uri": .. } }
@@ -423,6 +848,60 @@ mod tests {
         Ok(())
     }
 
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct ModelRef {
+        uri: String,
+        format: String,
+    }
+
+    impl CustomContentType for ModelRef {
+        const KIND: &'static str = "acme/model3d";
+    }
+
+    #[test]
+    fn unknown_kinds_round_trip_as_custom() -> Result<(), Box<dyn std::error::Error>> {
+        let wire = serde_json::json!({
+            "type": "acme/model3d",
+            "uri": "https://cdn.example.com/chair.glb",
+            "format": "glb",
+            "_meta": {"k": "v"}
+        });
+        let content: Content = serde_json::from_value(wire.clone())?;
+        assert!(content.is_custom());
+        assert_eq!(content.kind(), "acme/model3d");
+        assert_eq!(content.as_text(), None);
+        assert_eq!(serde_json::to_value(&content)?, wire);
+
+        let model: ModelRef = content.to_custom()?.ok_or("expected model")?;
+        assert_eq!(model.format, "glb");
+        assert_eq!(Content::text("hi").to_custom::<ModelRef>()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn typed_custom_content_serializes_with_kind() -> Result<(), Box<dyn std::error::Error>> {
+        let model = ModelRef {
+            uri: "u".to_string(),
+            format: "obj".to_string(),
+        };
+        let content = Content::from_custom(&model)?;
+        let json = serde_json::to_value(&content)?;
+        assert_eq!(json["type"], "acme/model3d");
+        assert_eq!(json["format"], "obj");
+
+        let back: Content = serde_json::from_value(json)?;
+        assert_eq!(back.to_custom::<ModelRef>()?, Some(model));
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_standard_kinds_are_rejected() {
+        // A broken `text` block must not be swallowed as custom content.
+        let wire = serde_json::json!({ "type": "text", "body": "no text field" });
+        assert!(serde_json::from_value::<Content>(wire).is_err());
+        assert!(serde_json::from_value::<Content>(serde_json::json!({ "text": "x" })).is_err());
+    }
+
     #[test]
     fn test_resource_link_content() -> Result<(), Box<dyn std::error::Error>> {
         let content = Content::resource_link("https://example.com/file.pdf", "My File");
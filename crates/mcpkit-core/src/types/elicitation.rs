@@ -5,6 +5,8 @@
 //! can gather user preferences, confirmations, or data.
 
 use super::meta::Meta;
+use crate::error::McpError;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 /// The mode of an elicitation request.
@@ -41,6 +43,12 @@ impl ElicitRequest {
         }
     }
 
+    /// Create a request for the form described by `T`.
+    #[must_use]
+    pub fn form<T: Elicitable>(message: impl Into<String>) -> Self {
+        Self::new(message, T::elicitation_schema())
+    }
+
     /// Create a simple text input request.
     #[must_use]
     pub fn text(message: impl Into<String>, field_name: impl Into<String>) -> Self {
@@ -190,6 +198,191 @@ impl ElicitationSchema {
         self.required.get_or_insert_with(Vec::new).push(name);
         self
     }
+
+    /// Start building a schema property by property.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mcpkit_core::types::ElicitationSchema;
+    ///
+    /// let schema = ElicitationSchema::builder()
+    ///     .string("name").required().max_length(64)
+    ///     .integer("age").min(0.0).description("Age in years")
+    ///     .boolean("subscribe")
+    ///     .build();
+    ///
+    /// assert_eq!(schema.required, Some(vec!["name".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn builder() -> ElicitationSchemaBuilder {
+        ElicitationSchemaBuilder::default()
+    }
+
+    /// Check accepted content against this schema.
+    ///
+    /// Verifies that required properties are present and that each declared
+    /// property has the declared type and respects its enum, numeric range,
+    /// and length constraints. `pattern` is not checked, and properties the
+    /// schema doesn't declare are ignored. A `null` value counts as absent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error for `elicitation/create` naming the
+    /// first offending property.
+    pub fn validate(
+        &self,
+        content: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), McpError> {
+        for name in self.required.iter().flatten() {
+            if content.get(name).is_none_or(serde_json::Value::is_null) {
+                return Err(invalid_content(
+                    name,
+                    "required property is missing",
+                    None,
+                    None,
+                ));
+            }
+        }
+
+        for (name, property) in &self.properties {
+            let Some(value) = content.get(name).filter(|v| !v.is_null()) else {
+                continue;
+            };
+            let Ok(property) = serde_json::from_value::<PropertySchema>(property.clone()) else {
+                continue;
+            };
+            property.check(name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for [`ElicitationSchema`], created with [`ElicitationSchema::builder`].
+///
+/// Each type method (`string`, `integer`, ...) adds a property; the
+/// modifier methods that follow apply to the most recently added one.
+/// Modifiers called before any property is added are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ElicitationSchemaBuilder {
+    properties: Vec<(String, PropertySchema, bool)>,
+}
+
+impl ElicitationSchemaBuilder {
+    /// Add a property with an explicit schema.
+    #[must_use]
+    pub fn property(mut self, name: impl Into<String>, schema: PropertySchema) -> Self {
+        self.properties.push((name.into(), schema, false));
+        self
+    }
+
+    /// Add a string property.
+    #[must_use]
+    pub fn string(self, name: impl Into<String>) -> Self {
+        self.property(name, PropertySchema::string())
+    }
+
+    /// Add a number property.
+    #[must_use]
+    pub fn number(self, name: impl Into<String>) -> Self {
+        self.property(name, PropertySchema::number())
+    }
+
+    /// Add an integer property.
+    #[must_use]
+    pub fn integer(self, name: impl Into<String>) -> Self {
+        self.property(name, PropertySchema::integer())
+    }
+
+    /// Add a boolean property.
+    #[must_use]
+    pub fn boolean(self, name: impl Into<String>) -> Self {
+        self.property(name, PropertySchema::boolean())
+    }
+
+    /// Add a string property restricted to `values`.
+    #[must_use]
+    pub fn enumeration<I, S>(self, name: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let values = values.into_iter().map(Into::into).collect();
+        self.property(name, PropertySchema::enum_values(values))
+    }
+
+    /// Mark the current property as required.
+    #[must_use]
+    pub fn required(mut self) -> Self {
+        if let Some((_, _, required)) = self.properties.last_mut() {
+            *required = true;
+        }
+        self
+    }
+
+    /// Set the current property's description.
+    #[must_use]
+    pub fn description(self, desc: impl Into<String>) -> Self {
+        self.modify(|p| p.description(desc))
+    }
+
+    /// Set the current property's default value.
+    #[must_use]
+    pub fn default_value(self, value: serde_json::Value) -> Self {
+        self.modify(|p| p.default_value(value))
+    }
+
+    /// Set the current property's minimum value.
+    #[must_use]
+    pub fn min(self, min: f64) -> Self {
+        self.modify(|p| p.min(min))
+    }
+
+    /// Set the current property's maximum value.
+    #[must_use]
+    pub fn max(self, max: f64) -> Self {
+        self.modify(|p| p.max(max))
+    }
+
+    /// Set the current property's minimum string length.
+    #[must_use]
+    pub fn min_length(self, len: u32) -> Self {
+        self.modify(|p| p.min_length(len))
+    }
+
+    /// Set the current property's maximum string length.
+    #[must_use]
+    pub fn max_length(self, len: u32) -> Self {
+        self.modify(|p| p.max_length(len))
+    }
+
+    /// Set the current property's regex pattern.
+    #[must_use]
+    pub fn pattern(self, pattern: impl Into<String>) -> Self {
+        self.modify(|p| p.pattern(pattern))
+    }
+
+    /// Build the schema.
+    #[must_use]
+    pub fn build(self) -> ElicitationSchema {
+        self.properties.into_iter().fold(
+            ElicitationSchema::object(),
+            |schema, (name, property, required)| {
+                if required {
+                    schema.required_property(name, property)
+                } else {
+                    schema.property(name, property)
+                }
+            },
+        )
+    }
+
+    fn modify(mut self, f: impl FnOnce(PropertySchema) -> PropertySchema) -> Self {
+        if let Some((name, property, required)) = self.properties.pop() {
+            self.properties.push((name, f(property), required));
+        }
+        self
+    }
 }
 
 impl Default for ElicitationSchema {
@@ -359,6 +552,80 @@ impl PropertySchema {
         self.pattern = Some(pattern.into());
         self
     }
+
+    /// Check a single non-null value against this property.
+    fn check(&self, name: &str, value: &serde_json::Value) -> Result<(), McpError> {
+        let type_matches = match self.property_type.as_str() {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            _ => true,
+        };
+        if !type_matches {
+            return Err(invalid_content(
+                name,
+                "value has the wrong type",
+                Some(self.property_type.clone()),
+                Some(value.to_string()),
+            ));
+        }
+
+        if let Some(text) = value.as_str() {
+            if let Some(allowed) = &self.enum_values {
+                if !allowed.iter().any(|v| v == text) {
+                    return Err(invalid_content(
+                        name,
+                        "value is not one of the allowed options",
+                        Some(allowed.join(", ")),
+                        Some(text.to_string()),
+                    ));
+                }
+            }
+            let len = text.chars().count();
+            if self.min_length.is_some_and(|min| len < min as usize) {
+                return Err(invalid_content(name, "value is too short", None, None));
+            }
+            if self.max_length.is_some_and(|max| len > max as usize) {
+                return Err(invalid_content(name, "value is too long", None, None));
+            }
+        }
+
+        if let Some(number) = value.as_f64() {
+            if self.minimum.is_some_and(|min| number < min) {
+                return Err(invalid_content(
+                    name,
+                    "value is below the minimum",
+                    self.minimum.map(|min| format!(">= {min}")),
+                    Some(number.to_string()),
+                ));
+            }
+            if self.maximum.is_some_and(|max| number > max) {
+                return Err(invalid_content(
+                    name,
+                    "value is above the maximum",
+                    self.maximum.map(|max| format!("<= {max}")),
+                    Some(number.to_string()),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_content(
+    name: &str,
+    message: &str,
+    expected: Option<String>,
+    actual: Option<String>,
+) -> McpError {
+    McpError::invalid_params_detailed(
+        "elicitation/create",
+        format!("elicitation response property '{name}': {message}"),
+        Some(name.to_string()),
+        expected,
+        actual,
+    )
 }
 
 /// Result of an elicitation request.
@@ -428,6 +695,115 @@ impl ElicitResult {
     pub fn get_number(&self, key: &str) -> Option<f64> {
         self.content.as_ref()?.get(key)?.as_f64()
     }
+
+    /// Validate accepted content against `schema` and deserialize it.
+    ///
+    /// Declined and cancelled results map to the matching
+    /// [`ElicitOutcome`] variant without looking at the content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error if accepted content is missing,
+    /// fails [`ElicitationSchema::validate`], or doesn't deserialize as `T`.
+    pub fn parse<T: DeserializeOwned>(
+        self,
+        schema: &ElicitationSchema,
+    ) -> Result<ElicitOutcome<T>, McpError> {
+        match self.action {
+            ElicitAction::Decline => Ok(ElicitOutcome::Declined),
+            ElicitAction::Cancel => Ok(ElicitOutcome::Cancelled),
+            ElicitAction::Accept => {
+                let content = self.content.unwrap_or_default();
+                schema.validate(&content)?;
+                serde_json::from_value(serde_json::Value::Object(content))
+                    .map(ElicitOutcome::Accepted)
+                    .map_err(|e| {
+                        McpError::invalid_params(
+                            "elicitation/create",
+                            format!("elicitation response does not match the form: {e}"),
+                        )
+                    })
+            }
+        }
+    }
+
+    /// Validate and deserialize accepted content as the form `T`.
+    ///
+    /// This is what the `TryFrom<ElicitResult>` implementation generated by
+    /// `#[derive(ElicitForm)]` calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::UserRejected`] if the user declined,
+    /// [`McpError::Cancelled`] if they cancelled, and the errors of
+    /// [`parse`](Self::parse) if the content doesn't match the form.
+    pub fn into_form<T: Elicitable>(self) -> Result<T, McpError> {
+        match self.parse(&T::elicitation_schema())? {
+            ElicitOutcome::Accepted(form) => Ok(form),
+            ElicitOutcome::Declined => Err(McpError::UserRejected {
+                message: "the user declined the form".to_string(),
+                operation: "elicitation/create".to_string(),
+            }),
+            ElicitOutcome::Cancelled => Err(McpError::cancelled("elicitation/create")),
+        }
+    }
+}
+
+/// A form that can be requested from the user with form-mode elicitation.
+///
+/// Usually derived with `#[derive(Elicitable)]` or `#[derive(ElicitForm)]`,
+/// which build the schema from the struct's fields and doc comments.
+pub trait Elicitable: DeserializeOwned {
+    /// The schema sent as `requestedSchema`.
+    fn elicitation_schema() -> ElicitationSchema;
+}
+
+/// An enum offered as a choice in an elicitation form.
+///
+/// Usually derived with `#[derive(ElicitForm)]` on an enum of unit variants.
+/// A form field of this type becomes a string property restricted to
+/// [`choices`](Self::choices).
+pub trait ElicitChoices {
+    /// The values the user can pick, as the enum deserializes them.
+    fn choices() -> &'static [&'static str];
+}
+
+/// The typed outcome of a form-mode elicitation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElicitOutcome<T> {
+    /// The user submitted the form.
+    Accepted(T),
+    /// The user explicitly declined to answer.
+    Declined,
+    /// The user dismissed the request without choosing.
+    Cancelled,
+}
+
+impl<T> ElicitOutcome<T> {
+    /// The submitted form, if the user accepted.
+    #[must_use]
+    pub fn accepted(self) -> Option<T> {
+        match self {
+            Self::Accepted(value) => Some(value),
+            Self::Declined | Self::Cancelled => None,
+        }
+    }
+
+    /// Check if the user accepted.
+    #[must_use]
+    pub const fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted(_))
+    }
+
+    /// The action the user took.
+    #[must_use]
+    pub const fn action(&self) -> ElicitAction {
+        match self {
+            Self::Accepted(_) => ElicitAction::Accept,
+            Self::Declined => ElicitAction::Decline,
+            Self::Cancelled => ElicitAction::Cancel,
+        }
+    }
 }
 
 /// The action taken in response to an elicitation.
@@ -577,6 +953,120 @@ mod tests {
         assert!(result.content.is_none());
     }
 
+    fn signup_schema() -> ElicitationSchema {
+        ElicitationSchema::builder()
+            .string("name")
+            .required()
+            .min_length(1)
+            .max_length(8)
+            .integer("age")
+            .min(0.0)
+            .max(150.0)
+            .description("Age in years")
+            .enumeration("plan", ["free", "pro"])
+            .required()
+            .boolean("newsletter")
+            .build()
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Signup {
+        name: String,
+        age: Option<u32>,
+        plan: String,
+        newsletter: Option<bool>,
+    }
+
+    #[test]
+    fn test_schema_builder() {
+        let schema = signup_schema();
+        assert_eq!(
+            schema.required,
+            Some(vec!["name".to_string(), "plan".to_string()])
+        );
+        assert_eq!(schema.properties["name"]["maxLength"], 8);
+        assert_eq!(schema.properties["age"]["type"], "integer");
+        assert_eq!(schema.properties["age"]["description"], "Age in years");
+        assert_eq!(
+            schema.properties["plan"]["enum"],
+            serde_json::json!(["free", "pro"])
+        );
+        assert_eq!(schema.properties["newsletter"]["type"], "boolean");
+
+        // Modifiers before any property are ignored.
+        let empty = ElicitationSchema::builder().required().min(1.0).build();
+        assert!(empty.properties.is_empty());
+        assert!(empty.required.is_none());
+    }
+
+    #[test]
+    fn test_validate_content() {
+        let schema = signup_schema();
+        let content = |value: serde_json::Value| value.as_object().unwrap().clone();
+
+        assert!(
+            schema
+                .validate(&content(serde_json::json!({"name": "ada", "plan": "pro"})))
+                .is_ok()
+        );
+
+        for (bad, property) in [
+            (serde_json::json!({"plan": "pro"}), "name"),
+            (serde_json::json!({"name": null, "plan": "pro"}), "name"),
+            (serde_json::json!({"name": "", "plan": "pro"}), "name"),
+            (
+                serde_json::json!({"name": "much too long", "plan": "pro"}),
+                "name",
+            ),
+            (serde_json::json!({"name": "ada", "plan": "gold"}), "plan"),
+            (
+                serde_json::json!({"name": "ada", "plan": "pro", "age": 1.5}),
+                "age",
+            ),
+            (
+                serde_json::json!({"name": "ada", "plan": "pro", "age": 200}),
+                "age",
+            ),
+            (serde_json::json!({"name": 7, "plan": "pro"}), "name"),
+        ] {
+            let err = schema.validate(&content(bad.clone())).unwrap_err();
+            assert!(
+                err.to_string().contains(&format!("'{property}'")),
+                "{bad}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_typed_result() {
+        let schema = signup_schema();
+        let accepted = ElicitResult::accepted(
+            serde_json::json!({"name": "ada", "plan": "free", "age": 36})
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        let outcome: ElicitOutcome<Signup> = accepted.parse(&schema).unwrap();
+        assert_eq!(
+            outcome,
+            ElicitOutcome::Accepted(Signup {
+                name: "ada".to_string(),
+                age: Some(36),
+                plan: "free".to_string(),
+                newsletter: None,
+            })
+        );
+
+        let declined: ElicitOutcome<Signup> = ElicitResult::declined().parse(&schema).unwrap();
+        assert_eq!(declined, ElicitOutcome::Declined);
+        assert_eq!(declined.action(), ElicitAction::Decline);
+        let cancelled: ElicitOutcome<Signup> = ElicitResult::cancelled().parse(&schema).unwrap();
+        assert!(cancelled.accepted().is_none());
+
+        let invalid = ElicitResult::accepted(serde_json::Map::new());
+        assert!(invalid.parse::<Signup>(&schema).is_err());
+    }
+
     #[test]
     fn test_complex_schema() {
         let schema = ElicitationSchema::object()
@@ -3,15 +3,34 @@
 //! The MCP 2025-11-25 schema attaches an optional `_meta` object to request
 //! params, notification params, and results. It is an open, string-keyed map for
 //! protocol- and implementation-defined metadata. This module provides the
-//! [`Meta`] type plus helpers for the one well-known key, `progressToken`.
+//! [`Meta`] type plus helpers for the well-known keys: `progressToken`, the
+//! W3C trace-context keys `traceparent` and `tracestate`, mcpkit's
+//! `mcpkit/timeoutMs` request deadline, and its `mcpkit/idempotencyKey`
+//! retry key.
 
 use crate::protocol::ProgressToken;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use std::time::Duration;
 
 /// The well-known request `_meta` key carrying a progress token.
 const PROGRESS_TOKEN_KEY: &str = "progressToken";
 
+/// The request `_meta` key carrying a W3C `traceparent`.
+const TRACEPARENT_KEY: &str = "traceparent";
+
+/// The request `_meta` key carrying a W3C `tracestate`.
+const TRACESTATE_KEY: &str = "tracestate";
+
+/// The request `_meta` key carrying the caller's remaining time budget in
+/// milliseconds. It is relative, like gRPC's `grpc-timeout`, so the deadline
+/// survives clock skew between client and server.
+const TIMEOUT_KEY: &str = "mcpkit/timeoutMs";
+
+/// The request `_meta` key carrying a client-chosen key that stays the same
+/// when a `tools/call` is resent, so the server can recognize the retry.
+const IDEMPOTENCY_KEY: &str = "mcpkit/idempotencyKey";
+
 /// The `_meta` field carried by MCP requests, notifications, and results.
 ///
 /// `_meta` is an open, string-keyed map. Keys beginning with
@@ -21,7 +40,14 @@ const PROGRESS_TOKEN_KEY: &str = "progressToken";
 ///
 /// On a **request**, `_meta.progressToken` associates progress notifications
 /// with the call — see [`with_progress_token`](Self::with_progress_token) and
-/// [`progress_token`](Self::progress_token).
+/// [`progress_token`](Self::progress_token). `_meta.traceparent` and
+/// `_meta.tracestate` carry [W3C trace context](https://www.w3.org/TR/trace-context/)
+/// so a server's spans join the caller's distributed trace — see
+/// [`with_trace_context`](Self::with_trace_context). `_meta["mcpkit/timeoutMs"]`
+/// tells the server how long the caller will wait for the response — see
+/// [`with_timeout_in_params`](Self::with_timeout_in_params).
+/// `_meta["mcpkit/idempotencyKey"]` marks resends of the same call — see
+/// [`with_idempotency_key_in_params`](Self::with_idempotency_key_in_params).
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Meta(pub Map<String, Value>);
@@ -85,6 +111,46 @@ impl Meta {
         self
     }
 
+    /// The W3C `traceparent` (`_meta.traceparent`), if present.
+    #[must_use]
+    pub fn traceparent(&self) -> Option<&str> {
+        self.0.get(TRACEPARENT_KEY).and_then(Value::as_str)
+    }
+
+    /// The W3C `tracestate` (`_meta.tracestate`), if present.
+    #[must_use]
+    pub fn tracestate(&self) -> Option<&str> {
+        self.0.get(TRACESTATE_KEY).and_then(Value::as_str)
+    }
+
+    /// Set the W3C trace context, returning `self` for chaining.
+    #[must_use]
+    pub fn with_trace_context(mut self, traceparent: &str, tracestate: Option<&str>) -> Self {
+        self.0
+            .insert(TRACEPARENT_KEY.to_string(), traceparent.into());
+        if let Some(state) = tracestate {
+            self.0.insert(TRACESTATE_KEY.to_string(), state.into());
+        }
+        self
+    }
+
+    /// How long the caller will wait for the response
+    /// (`_meta["mcpkit/timeoutMs"]`), if present and valid.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.0
+            .get(TIMEOUT_KEY)
+            .and_then(Value::as_u64)
+            .map(Duration::from_millis)
+    }
+
+    /// The key identifying resends of the same call
+    /// (`_meta["mcpkit/idempotencyKey"]`), if present.
+    #[must_use]
+    pub fn idempotency_key(&self) -> Option<&str> {
+        self.0.get(IDEMPOTENCY_KEY).and_then(Value::as_str)
+    }
+
     /// Extract a request's progress token directly from raw params
     /// (`params._meta.progressToken`) without deserializing the whole `_meta`.
     ///
@@ -107,26 +173,109 @@ impl Meta {
     /// (both invalid for MCP), are replaced so the token is always attached.
     #[must_use]
     pub fn with_progress_token_in_params(params: Option<Value>, token: &ProgressToken) -> Value {
-        let mut obj = match params {
-            Some(Value::Object(map)) => map,
-            _ => Map::new(),
-        };
-        let meta = obj
-            .entry("_meta")
-            .or_insert_with(|| Value::Object(Map::new()));
-        // A pre-existing non-object `_meta` is malformed; replace it so the token
-        // is always inserted (the helper guarantees progress wiring).
-        if !meta.is_object() {
-            *meta = Value::Object(Map::new());
-        }
-        if let Value::Object(meta_obj) = meta {
-            meta_obj.insert(
-                PROGRESS_TOKEN_KEY.to_string(),
-                serde_json::to_value(token).unwrap_or(Value::Null),
-            );
+        let value = serde_json::to_value(token).unwrap_or(Value::Null);
+        insert_in_params(params, [(PROGRESS_TOKEN_KEY, value)])
+    }
+
+    /// Extract a request's W3C trace context directly from raw params
+    /// (`params._meta.traceparent` and `params._meta.tracestate`).
+    ///
+    /// Returns `None` when there is no `traceparent`; the `tracestate` is
+    /// optional.
+    #[must_use]
+    pub fn trace_context_from_params(params: &Value) -> Option<(&str, Option<&str>)> {
+        let meta = params.get("_meta")?;
+        let traceparent = meta.get(TRACEPARENT_KEY)?.as_str()?;
+        Some((
+            traceparent,
+            meta.get(TRACESTATE_KEY).and_then(Value::as_str),
+        ))
+    }
+
+    /// Attach W3C trace context (`_meta.traceparent`, and `_meta.tracestate`
+    /// when given) to (possibly absent) request params, so the server's spans
+    /// for the call become children of the caller's span.
+    ///
+    /// Params are merged the same way as
+    /// [`with_progress_token_in_params`](Self::with_progress_token_in_params).
+    #[must_use]
+    pub fn with_trace_context_in_params(
+        params: Option<Value>,
+        traceparent: &str,
+        tracestate: Option<&str>,
+    ) -> Value {
+        let entries = std::iter::once((TRACEPARENT_KEY, traceparent.into()))
+            .chain(tracestate.map(|state| (TRACESTATE_KEY, state.into())));
+        insert_in_params(params, entries)
+    }
+
+    /// Extract how long the caller will wait for the response directly from
+    /// raw params (`params._meta["mcpkit/timeoutMs"]`).
+    #[must_use]
+    pub fn timeout_from_params(params: &Value) -> Option<Duration> {
+        params
+            .get("_meta")?
+            .get(TIMEOUT_KEY)?
+            .as_u64()
+            .map(Duration::from_millis)
+    }
+
+    /// Attach the caller's remaining time budget (`_meta["mcpkit/timeoutMs"]`)
+    /// to (possibly absent) request params, so the server can stop work the
+    /// caller will no longer wait for.
+    ///
+    /// Params are merged the same way as
+    /// [`with_progress_token_in_params`](Self::with_progress_token_in_params).
+    #[must_use]
+    pub fn with_timeout_in_params(params: Option<Value>, timeout: Duration) -> Value {
+        let millis = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        insert_in_params(params, [(TIMEOUT_KEY, millis.into())])
+    }
+
+    /// Extract a request's idempotency key directly from raw params
+    /// (`params._meta["mcpkit/idempotencyKey"]`).
+    #[must_use]
+    pub fn idempotency_key_from_params(params: &Value) -> Option<&str> {
+        params.get("_meta")?.get(IDEMPOTENCY_KEY)?.as_str()
+    }
+
+    /// Attach an idempotency key (`_meta["mcpkit/idempotencyKey"]`) to
+    /// (possibly absent) request params. Send the same key with every resend
+    /// of a call so the server can answer a duplicate with the original
+    /// result instead of running it again.
+    ///
+    /// Params are merged the same way as
+    /// [`with_progress_token_in_params`](Self::with_progress_token_in_params).
+    #[must_use]
+    pub fn with_idempotency_key_in_params(params: Option<Value>, key: &str) -> Value {
+        insert_in_params(params, [(IDEMPOTENCY_KEY, key.into())])
+    }
+}
+
+/// Insert entries into `params._meta`, creating the params object and `_meta`
+/// as needed.
+fn insert_in_params<'a>(
+    params: Option<Value>,
+    entries: impl IntoIterator<Item = (&'a str, Value)>,
+) -> Value {
+    let mut obj = match params {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    let meta = obj
+        .entry("_meta")
+        .or_insert_with(|| Value::Object(Map::new()));
+    // A pre-existing non-object `_meta` is malformed; replace it so the entries
+    // are always inserted (the helpers guarantee the wiring).
+    if !meta.is_object() {
+        *meta = Value::Object(Map::new());
+    }
+    if let Value::Object(meta_obj) = meta {
+        for (key, value) in entries {
+            meta_obj.insert(key.to_string(), value);
         }
-        Value::Object(obj)
     }
+    Value::Object(obj)
 }
 
 #[cfg(test)]
@@ -201,6 +350,68 @@ mod tests {
         assert_eq!(out, json!({ "name": "t", "_meta": { "progressToken": 1 } }));
     }
 
+    #[test]
+    fn trace_context_round_trips_through_params() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let params = json!({ "name": "t", "_meta": { "progressToken": 1 } });
+        let out = Meta::with_trace_context_in_params(Some(params), traceparent, Some("k=v"));
+        assert_eq!(
+            out,
+            json!({ "name": "t", "_meta": {
+                "progressToken": 1,
+                "traceparent": traceparent,
+                "tracestate": "k=v",
+            } })
+        );
+        assert_eq!(
+            Meta::trace_context_from_params(&out),
+            Some((traceparent, Some("k=v")))
+        );
+
+        let out = Meta::with_trace_context_in_params(None, traceparent, None);
+        assert_eq!(
+            Meta::trace_context_from_params(&out),
+            Some((traceparent, None))
+        );
+        assert_eq!(Meta::trace_context_from_params(&json!({})), None);
+
+        let meta: Meta = serde_json::from_value(out["_meta"].clone()).unwrap();
+        assert_eq!(meta.traceparent(), Some(traceparent));
+        assert_eq!(meta.tracestate(), None);
+    }
+
+    #[test]
+    fn timeout_round_trips_through_params() {
+        let out =
+            Meta::with_timeout_in_params(Some(json!({ "name": "t" })), Duration::from_millis(1500));
+        assert_eq!(
+            out,
+            json!({ "name": "t", "_meta": { "mcpkit/timeoutMs": 1500 } })
+        );
+        assert_eq!(
+            Meta::timeout_from_params(&out),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(Meta::timeout_from_params(&json!({ "name": "t" })), None);
+
+        let meta: Meta = serde_json::from_value(out["_meta"].clone()).unwrap();
+        assert_eq!(meta.timeout(), Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn idempotency_key_round_trips_through_params() {
+        let out = Meta::with_idempotency_key_in_params(Some(json!({ "name": "t" })), "k-1");
+        assert_eq!(
+            out,
+            json!({ "name": "t", "_meta": { "mcpkit/idempotencyKey": "k-1" } })
+        );
+        assert_eq!(Meta::idempotency_key_from_params(&out), Some("k-1"));
+        assert_eq!(Meta::idempotency_key_from_params(&json!({})), None);
+
+        let meta: Meta = serde_json::from_value(out["_meta"].clone()).unwrap();
+        assert_eq!(meta.idempotency_key(), Some("k-1"));
+    }
+
     #[test]
     fn result_meta_serializes_as_underscore_meta_and_omits_when_none() {
         use crate::types::CallToolResult;
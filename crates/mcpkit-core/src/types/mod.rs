@@ -14,7 +14,10 @@
 //! - **Sampling**: Requesting LLM completions from the client
 //! - **Elicitation**: Requesting structured input from the user
 //! - **Content**: Polymorphic content types (text, images, audio, resources)
+//!
+//! Helpers for loading and encoding binary payloads live in [`binary`].
 
+pub mod binary;
 pub mod completion;
 pub mod content;
 pub mod elicitation;
@@ -31,6 +34,7 @@ pub mod task;
 pub mod tool;
 
 // Re-export all public types at the module level
+pub use binary::{BinaryError, DEFAULT_MAX_BINARY_SIZE};
 pub use completion::*;
 pub use content::*;
 pub use elicitation::*;
@@ -4,6 +4,7 @@
 //! They can be files, database entries, API responses, or any other
 //! addressable content.
 
+use super::binary::{self, BinaryError};
 use super::meta::Meta;
 use super::metadata::Icon;
 use serde::{Deserialize, Serialize};
@@ -205,6 +206,114 @@ impl ResourceTemplate {
         self.icons = Some(icons.into_iter().collect());
         self
     }
+
+    /// Match `uri` against this template's `uri_template`.
+    ///
+    /// See [`match_uri_template`].
+    #[must_use]
+    pub fn match_uri<'a>(&'a self, uri: &'a str) -> Option<Vec<(&'a str, &'a str)>> {
+        match_uri_template(&self.uri_template, uri)
+    }
+}
+
+/// Match `uri` against a URI template, returning each variable's name and
+/// captured text in template order.
+///
+/// Supports the simple (`{var}`) and reserved (`{+var}`) expressions of
+/// RFC 6570: `{var}` captures one or more characters other than `/`, while
+/// `{+var}` may also span `/`. Text outside braces must match literally.
+/// Captures are returned as they appear in `uri`, without percent-decoding.
+/// A template without variables matches only itself, and a malformed
+/// template (unbalanced braces, empty names) matches nothing.
+///
+/// # Example
+///
+/// ```rust
+/// use mcpkit_core::types::match_uri_template;
+///
+/// let captures = match_uri_template("db://{schema}/{table}", "db://public/users");
+/// assert_eq!(captures, Some(vec![("schema", "public"), ("table", "users")]));
+/// assert_eq!(match_uri_template("db://{schema}/{table}", "db://public"), None);
+/// ```
+#[must_use]
+pub fn match_uri_template<'t, 'u>(
+    template: &'t str,
+    uri: &'u str,
+) -> Option<Vec<(&'t str, &'u str)>> {
+    let parts = parse_uri_template(template)?;
+    let mut captures = Vec::new();
+    match_template_parts(&parts, uri, &mut captures).then_some(captures)
+}
+
+/// One piece of a parsed URI template.
+enum TemplatePart<'t> {
+    Literal(&'t str),
+    Variable { name: &'t str, reserved: bool },
+}
+
+fn parse_uri_template(template: &str) -> Option<Vec<TemplatePart<'_>>> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return None;
+        }
+        if open > 0 {
+            parts.push(TemplatePart::Literal(&rest[..open]));
+        }
+        let expression = &rest[open + 1..];
+        let close = expression.find('}')?;
+        let (name, reserved) = match expression[..close].strip_prefix('+') {
+            Some(name) => (name, true),
+            None => (&expression[..close], false),
+        };
+        if name.is_empty() || name.contains('{') {
+            return None;
+        }
+        parts.push(TemplatePart::Variable { name, reserved });
+        rest = &expression[close + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Literal(rest));
+    }
+    Some(parts)
+}
+
+fn match_template_parts<'t, 'u>(
+    parts: &[TemplatePart<'t>],
+    uri: &'u str,
+    captures: &mut Vec<(&'t str, &'u str)>,
+) -> bool {
+    let Some((first, rest)) = parts.split_first() else {
+        return uri.is_empty();
+    };
+    match *first {
+        TemplatePart::Literal(literal) => uri
+            .strip_prefix(literal)
+            .is_some_and(|tail| match_template_parts(rest, tail, captures)),
+        TemplatePart::Variable { name, reserved } => {
+            // Shortest capture first, so the following literal anchors at its
+            // earliest occurrence.
+            let ends = uri
+                .char_indices()
+                .map(|(i, _)| i)
+                .skip(1)
+                .chain(std::iter::once(uri.len()))
+                .filter(|&end| end > 0);
+            for end in ends {
+                let value = &uri[..end];
+                if !reserved && value.ends_with('/') {
+                    break;
+                }
+                captures.push((name, value));
+                if match_template_parts(rest, &uri[end..], captures) {
+                    return true;
+                }
+                captures.pop();
+            }
+            false
+        }
+    }
 }
 
 /// The contents of a resource.
@@ -271,6 +380,35 @@ impl ResourceContents {
         }
     }
 
+    /// Create binary resource contents by streaming from a reader.
+    ///
+    /// The data is base64 encoded as it is read, so the raw bytes are never
+    /// buffered in full. Reading stops with [`BinaryError::TooLarge`] once
+    /// more than `max_size` bytes have been read. Tokio readers can be
+    /// adapted with `tokio_util::compat`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails or the data exceeds `max_size`.
+    pub async fn blob_from_reader<R>(
+        uri: impl Into<String>,
+        reader: R,
+        mime_type: impl Into<String>,
+        max_size: usize,
+    ) -> Result<Self, BinaryError>
+    where
+        R: futures::io::AsyncRead + Unpin,
+    {
+        let blob = binary::encode_reader(reader, max_size).await?;
+        Ok(Self {
+            uri: uri.into(),
+            mime_type: Some(mime_type.into()),
+            text: None,
+            blob: Some(blob),
+            meta: None,
+        })
+    }
+
     /// Check if this is text content.
     #[must_use]
     pub const fn is_text(&self) -> bool {
@@ -301,6 +439,148 @@ impl ResourceContents {
             .map(|b| base64::engine::general_purpose::STANDARD.decode(b))
             .transpose()
     }
+
+    /// Decode the binary content into a writer, a chunk at a time.
+    ///
+    /// Returns the number of bytes written, or `None` if this is not binary
+    /// content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob is not valid base64 or writing fails.
+    pub async fn write_blob_to<W>(&self, writer: W) -> Result<Option<u64>, BinaryError>
+    where
+        W: futures::io::AsyncWrite + Unpin,
+    {
+        match &self.blob {
+            Some(blob) => binary::decode_to_writer(blob, writer).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// The raw bytes of the content: the decoded blob, or the UTF-8 text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob is not valid base64.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, base64::DecodeError> {
+        match (&self.blob, &self.text) {
+            (Some(_), _) => Ok(self.decode_blob()?.unwrap_or_default()),
+            (None, Some(text)) => Ok(text.as_bytes().to_vec()),
+            (None, None) => Ok(Vec::new()),
+        }
+    }
+
+    /// One chunk of a ranged read: `data` starts at `offset` in a resource
+    /// of `total` bytes.
+    ///
+    /// The chunk is always binary, since a byte range may split a UTF-8
+    /// character, and carries its position in `_meta["mcpkit/chunk"]`.
+    #[must_use]
+    pub fn chunk(
+        uri: impl Into<String>,
+        data: &[u8],
+        mime_type: Option<String>,
+        offset: u64,
+        total: u64,
+    ) -> Self {
+        use base64::Engine;
+        let info = ChunkInfo { offset, total };
+        Self {
+            uri: uri.into(),
+            mime_type,
+            text: None,
+            blob: Some(base64::engine::general_purpose::STANDARD.encode(data)),
+            meta: Some(Meta::new().with(
+                CHUNK_META_KEY,
+                serde_json::to_value(info).unwrap_or_default(),
+            )),
+        }
+    }
+
+    /// Cut `range` out of this content, as a [`chunk`](Self::chunk).
+    ///
+    /// A range reaching past the end is cut short; one starting past the end
+    /// yields an empty chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob is not valid base64.
+    pub fn slice(&self, range: ByteRange) -> Result<Self, BinaryError> {
+        let bytes = self.to_bytes()?;
+        let total = bytes.len() as u64;
+        let start = range.offset.min(total);
+        let end = start.saturating_add(range.length).min(total);
+        // Both are at most `bytes.len()`, so they fit in a usize.
+        #[allow(clippy::cast_possible_truncation)]
+        let data = &bytes[start as usize..end as usize];
+        Ok(Self::chunk(
+            self.uri.clone(),
+            data,
+            self.mime_type.clone(),
+            start,
+            total,
+        ))
+    }
+
+    /// Where this content sits in the resource, if it is a chunk of a
+    /// ranged read.
+    #[must_use]
+    pub fn chunk_info(&self) -> Option<ChunkInfo> {
+        let value = self.meta.as_ref()?.get(CHUNK_META_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// `resources/read` request `_meta` key asking for a [`ByteRange`] of the
+/// resource rather than all of it.
+///
+/// A server that understands it answers with a single
+/// [`chunk`](ResourceContents::chunk); one that does not ignores it and
+/// returns the whole resource, which a client can tell by the missing
+/// [`CHUNK_META_KEY`].
+pub const RANGE_META_KEY: &str = "mcpkit/range";
+
+/// `_meta` key on the contents of a ranged read, holding its [`ChunkInfo`].
+pub const CHUNK_META_KEY: &str = "mcpkit/chunk";
+
+/// Largest chunk a server returns for one ranged read; longer ranges are
+/// cut short.
+pub const MAX_RESOURCE_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+
+/// A byte range of a resource, requested with [`RANGE_META_KEY`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteRange {
+    /// First byte of the range.
+    pub offset: u64,
+    /// Number of bytes wanted.
+    pub length: u64,
+}
+
+impl ByteRange {
+    /// The range of `length` bytes starting at `offset`.
+    #[must_use]
+    pub const fn new(offset: u64, length: u64) -> Self {
+        Self { offset, length }
+    }
+
+    /// The range requested in `resources/read` params
+    /// (`params._meta["mcpkit/range"]`), if any.
+    #[must_use]
+    pub fn from_params(params: &serde_json::Value) -> Option<Self> {
+        let value = params.get("_meta")?.get(RANGE_META_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// The position of a chunk in its resource, sent as
+/// `_meta["mcpkit/chunk"]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkInfo {
+    /// Offset of the chunk's first byte.
+    pub offset: u64,
+    /// Size of the whole resource in bytes.
+    pub total: u64,
 }
 
 /// Request parameters for listing resources.
@@ -384,6 +664,53 @@ pub struct ResourceListChangedNotification {}
 mod tests {
     use super::*;
 
+    #[test]
+    fn uri_templates_capture_variables() {
+        assert_eq!(
+            match_uri_template("db://tables/{table}", "db://tables/users"),
+            Some(vec![("table", "users")])
+        );
+        // Simple variables stop at `/`; the literal suffix must match too.
+        assert_eq!(
+            match_uri_template("db://tables/{table}", "db://tables/users/sample"),
+            None
+        );
+        assert_eq!(
+            match_uri_template("db://tables/{table}/sample", "db://tables/users/sample"),
+            Some(vec![("table", "users")])
+        );
+        assert_eq!(
+            match_uri_template("db://tables/{table}", "db://tables/"),
+            None
+        );
+        assert_eq!(
+            match_uri_template("file:///{+path}", "file:///src/lib.rs"),
+            Some(vec![("path", "src/lib.rs")])
+        );
+        assert_eq!(
+            match_uri_template("v://{major}.{minor}", "v://1.2.3"),
+            Some(vec![("major", "1"), ("minor", "2.3")])
+        );
+    }
+
+    #[test]
+    fn uri_templates_without_variables_or_malformed_match_exactly_or_never() {
+        assert_eq!(
+            match_uri_template("config://app", "config://app"),
+            Some(vec![])
+        );
+        assert_eq!(match_uri_template("config://app", "config://app/x"), None);
+        assert_eq!(match_uri_template("db://{table", "db://{table"), None);
+        assert_eq!(match_uri_template("db://table}", "db://table}"), None);
+        assert_eq!(match_uri_template("db://{}", "db://x"), None);
+
+        let template = ResourceTemplate::new("user://{id}/profile", "profile");
+        assert_eq!(
+            template.match_uri("user://42/profile"),
+            Some(vec![("id", "42")])
+        );
+    }
+
     #[test]
     fn resource_types_meta_round_trip() -> Result<(), Box<dyn std::error::Error>> {
         let r: Resource =
@@ -462,6 +789,36 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn resource_blobs_stream() -> Result<(), Box<dyn std::error::Error>> {
+        let data: Vec<u8> = (0..=255).cycle().take(200_000).collect();
+        let contents = ResourceContents::blob_from_reader(
+            "file:///data.bin",
+            futures::io::Cursor::new(&data),
+            "application/octet-stream",
+            data.len(),
+        )
+        .await?;
+        assert_eq!(contents.decode_blob()?.as_deref(), Some(data.as_slice()));
+
+        let mut out = futures::io::Cursor::new(Vec::new());
+        assert_eq!(
+            contents.write_blob_to(&mut out).await?,
+            Some(data.len() as u64)
+        );
+        assert_eq!(out.into_inner(), data);
+
+        let too_small = ResourceContents::blob_from_reader(
+            "file:///data.bin",
+            futures::io::Cursor::new(&data),
+            "application/octet-stream",
+            1024,
+        )
+        .await;
+        assert!(matches!(too_small, Err(BinaryError::TooLarge { .. })));
+        Ok(())
+    }
+
     #[test]
     fn test_resource_contents_json() -> Result<(), Box<dyn std::error::Error>> {
         #[derive(Serialize)]
@@ -499,4 +856,34 @@ mod tests {
             serde_json::from_value(serde_json::json!({ "uri": "file:///y" })).unwrap();
         assert_eq!(back.uri, "file:///y");
     }
+
+    #[test]
+    fn contents_are_sliced_into_chunks() -> Result<(), Box<dyn std::error::Error>> {
+        let contents = ResourceContents::text("file:///big.txt", "héllo world");
+        let chunk = contents.slice(ByteRange::new(1, 2))?;
+        assert_eq!(chunk.decode_blob()?, Some("é".as_bytes().to_vec()));
+        assert_eq!(chunk.mime_type.as_deref(), Some("text/plain"));
+        assert_eq!(
+            chunk.chunk_info(),
+            Some(ChunkInfo {
+                offset: 1,
+                total: 12
+            })
+        );
+
+        // Ranges are clamped to the content.
+        let tail = contents.slice(ByteRange::new(10, 100))?;
+        assert_eq!(tail.to_bytes()?, b"ld");
+        let past = contents.slice(ByteRange::new(100, 1))?;
+        assert_eq!(past.to_bytes()?, b"");
+        assert_eq!(past.chunk_info().map(|c| c.offset), Some(12));
+        assert_eq!(contents.chunk_info(), None);
+
+        let params = serde_json::json!({
+            "uri": "file:///big.txt",
+            "_meta": { "mcpkit/range": { "offset": 5, "length": 10 } }
+        });
+        assert_eq!(ByteRange::from_params(&params), Some(ByteRange::new(5, 10)));
+        Ok(())
+    }
 }
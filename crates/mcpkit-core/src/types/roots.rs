@@ -2,6 +2,7 @@
 
 use super::meta::Meta;
 use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
 
 /// A root a client exposes to servers (typically a project directory).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -29,6 +30,119 @@ impl Root {
         self.name = Some(name.into());
         self
     }
+
+    /// The local path of a `file://` root, or `None` for other URI schemes.
+    #[must_use]
+    pub fn file_path(&self) -> Option<PathBuf> {
+        let url = url::Url::parse(&self.uri).ok()?;
+        if url.scheme() != "file" {
+            return None;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let path = url.to_file_path().ok()?;
+        // `Url::to_file_path` needs a native path type; in WASM, decode the
+        // URL path as a Unix path.
+        #[cfg(target_arch = "wasm32")]
+        let path = PathBuf::from(
+            percent_encoding::percent_decode_str(url.path())
+                .decode_utf8()
+                .ok()?
+                .into_owned(),
+        );
+        Some(normalize(&path))
+    }
+
+    /// Whether `path` is this root's directory or lies beneath it.
+    ///
+    /// The comparison is lexical: `.` and `..` are resolved but symlinks are
+    /// not, so canonicalize `path` first if it may contain links. Relative
+    /// paths and non-`file://` roots never match.
+    #[must_use]
+    pub fn contains_path(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        if !path.is_absolute() {
+            return false;
+        }
+        self.file_path()
+            .is_some_and(|root| normalize(path).starts_with(root))
+    }
+}
+
+/// The roots a client reported, with helpers for checking paths against them.
+///
+/// Returned by the server's `Context::client_roots()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientRoots {
+    roots: Vec<Root>,
+}
+
+impl ClientRoots {
+    /// Wrap a list of roots.
+    #[must_use]
+    pub const fn new(roots: Vec<Root>) -> Self {
+        Self { roots }
+    }
+
+    /// The client's roots.
+    #[must_use]
+    pub fn roots(&self) -> &[Root] {
+        &self.roots
+    }
+
+    /// Whether the client reported no roots.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Whether `path` lies within any of the client's roots.
+    ///
+    /// See [`Root::contains_path`] for how paths are compared.
+    #[must_use]
+    pub fn is_within_roots(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        self.roots.iter().any(|root| root.contains_path(path))
+    }
+
+    /// The first root containing `path`, if any.
+    #[must_use]
+    pub fn root_for(&self, path: impl AsRef<Path>) -> Option<&Root> {
+        let path = path.as_ref();
+        self.roots.iter().find(|root| root.contains_path(path))
+    }
+
+    /// Consume the wrapper and return the roots.
+    #[must_use]
+    pub fn into_roots(self) -> Vec<Root> {
+        self.roots
+    }
+}
+
+impl From<Vec<Root>> for ClientRoots {
+    fn from(roots: Vec<Root>) -> Self {
+        Self::new(roots)
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                // `..` at the root stays at the root.
+                if !matches!(
+                    out.components().next_back(),
+                    None | Some(Component::RootDir | Component::Prefix(_))
+                ) {
+                    out.pop();
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
 }
 
 /// Result of a `roots/list` request — the roots the client exposes.
@@ -81,4 +195,38 @@ mod tests {
         assert_eq!(back.roots.len(), 2);
         assert_eq!(back.roots[1].name.as_deref(), Some("b"));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn contains_path_matches_whole_components_lexically() {
+        let root = Root::new("file:///home/user/my%20project");
+        assert_eq!(
+            root.file_path(),
+            Some(PathBuf::from("/home/user/my project"))
+        );
+        assert!(root.contains_path("/home/user/my project"));
+        assert!(root.contains_path("/home/user/my project/src/../Cargo.toml"));
+        assert!(!root.contains_path("/home/user/my project/../other"));
+        assert!(!root.contains_path("/home/user/my project-old/file"));
+        assert!(!root.contains_path("my project/src"));
+        assert!(!Root::new("https://example.com/repo").contains_path("/repo"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn client_roots_checks_every_root() {
+        let roots = ClientRoots::new(vec![
+            Root::new("file:///work").name("work"),
+            Root::new("file:///tmp/scratch"),
+        ]);
+        assert!(roots.is_within_roots("/work/a/b.rs"));
+        assert!(roots.is_within_roots("/tmp/scratch/./x"));
+        assert!(!roots.is_within_roots("/tmp/other"));
+        assert!(!roots.is_within_roots("/work/../../etc/passwd"));
+        assert_eq!(
+            roots.root_for("/work/a").and_then(|r| r.name.as_deref()),
+            Some("work")
+        );
+        assert!(!ClientRoots::default().is_within_roots("/work"));
+    }
 }
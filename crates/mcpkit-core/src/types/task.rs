@@ -74,6 +74,20 @@ impl TaskStatus {
     pub const fn is_terminal(&self) -> bool {
         matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
     }
+
+    /// Check whether the spec's task state machine allows moving from this
+    /// status to `next`.
+    ///
+    /// `working` and `input_required` may move to each other or to any
+    /// terminal status; terminal statuses are final.
+    #[must_use]
+    pub const fn can_transition_to(&self, next: Self) -> bool {
+        match self {
+            Self::Working => !matches!(next, Self::Working),
+            Self::InputRequired => !matches!(next, Self::InputRequired),
+            Self::Completed | Self::Failed | Self::Cancelled => false,
+        }
+    }
 }
 
 impl std::fmt::Display for TaskStatus {
@@ -432,6 +446,20 @@ mod tests {
         assert!(!TaskStatus::Working.is_terminal());
     }
 
+    #[test]
+    fn status_transitions_follow_the_state_machine() {
+        use TaskStatus::{Cancelled, Completed, Failed, InputRequired, Working};
+        assert!(Working.can_transition_to(InputRequired));
+        assert!(InputRequired.can_transition_to(Working));
+        assert!(Working.can_transition_to(Completed));
+        assert!(InputRequired.can_transition_to(Cancelled));
+        assert!(!Working.can_transition_to(Working));
+        for terminal in [Completed, Failed, Cancelled] {
+            assert!(!terminal.can_transition_to(Working));
+            assert!(!terminal.can_transition_to(Failed));
+        }
+    }
+
     #[test]
     fn set_status_updates_timestamp() {
         let mut task = Task::new(TaskId::new("t"));
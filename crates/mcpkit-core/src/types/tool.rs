@@ -3,10 +3,22 @@
 //! Tools are functions that MCP servers expose for AI assistants to invoke.
 //! Each tool has a name, description, and JSON Schema defining its input.
 
-use super::content::Content;
+use super::content::{Content, ContentAnnotations, Role};
 use super::meta::Meta;
 use super::metadata::Icon;
+use crate::error::McpError;
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Tool `_meta` key marking calls that must not be recorded or dumped.
+pub const NO_RECORD_META_KEY: &str = "mcpkit/noRecord";
+
+/// Tool `_meta` key holding the tool's execution timeout in milliseconds.
+pub const TIMEOUT_META_KEY: &str = "mcpkit/timeoutMs";
+
+/// Tool `_meta` key holding the filesystem and network access the tool
+/// declares.
+pub const SANDBOX_META_KEY: &str = "mcpkit/sandbox";
 
 /// A tool definition exposed by an MCP server.
 ///
@@ -144,6 +156,24 @@ impl Tool {
         self
     }
 
+    /// Set the tool's input schema to the schema generated for `T`.
+    ///
+    /// See [`schema_for`](crate::schema::schema_for).
+    #[cfg(feature = "schemars")]
+    #[must_use]
+    pub fn input_schema_for<T: schemars::JsonSchema>(self) -> Self {
+        self.input_schema(crate::schema::schema_for::<T>())
+    }
+
+    /// Set the tool's output schema to the schema generated for `T`.
+    ///
+    /// See [`schema_for`](crate::schema::schema_for).
+    #[cfg(feature = "schemars")]
+    #[must_use]
+    pub fn output_schema_for<T: schemars::JsonSchema>(self) -> Self {
+        self.output_schema(crate::schema::schema_for::<T>())
+    }
+
     /// Set the tool's annotations.
     #[must_use]
     pub fn annotations(mut self, annotations: ToolAnnotations) -> Self {
@@ -169,6 +199,70 @@ impl Tool {
             .unwrap_or(false)
     }
 
+    /// Mark this tool's calls as excluded from session recording and wire
+    /// dumps (sets `_meta["mcpkit/noRecord"]`).
+    ///
+    /// See [`RecordingPolicy`](crate::debug::RecordingPolicy).
+    #[must_use]
+    pub fn no_record(mut self) -> Self {
+        self.meta
+            .get_or_insert_with(Meta::new)
+            .insert(NO_RECORD_META_KEY, serde_json::Value::Bool(true));
+        self
+    }
+
+    /// Check if this tool's calls are excluded from recording.
+    #[must_use]
+    pub fn is_no_record(&self) -> bool {
+        self.meta
+            .as_ref()
+            .and_then(|m| m.get(NO_RECORD_META_KEY))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+    }
+
+    /// Limit how long a call to this tool may run (sets
+    /// `_meta["mcpkit/timeoutMs"]`).
+    ///
+    /// The server runtime stops a call that runs longer and fails it with
+    /// [`McpError::Timeout`]. See `mcpkit_server::timeout`.
+    #[must_use]
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        let millis = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        self.meta
+            .get_or_insert_with(Meta::new)
+            .insert(TIMEOUT_META_KEY, serde_json::Value::from(millis));
+        self
+    }
+
+    /// The execution timeout this tool declares, if any.
+    #[must_use]
+    pub fn declared_timeout(&self) -> Option<std::time::Duration> {
+        self.meta
+            .as_ref()
+            .and_then(|m| m.get(TIMEOUT_META_KEY))
+            .and_then(serde_json::Value::as_u64)
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// Declare the filesystem and network access this tool needs (sets
+    /// `_meta["mcpkit/sandbox"]`).
+    ///
+    /// `policy` is a serialized `mcpkit_server::sandbox::SandboxPolicy`.
+    #[must_use]
+    pub fn sandbox(mut self, policy: serde_json::Value) -> Self {
+        self.meta
+            .get_or_insert_with(Meta::new)
+            .insert(SANDBOX_META_KEY, policy);
+        self
+    }
+
+    /// The filesystem and network access this tool declares, if any.
+    #[must_use]
+    pub fn declared_sandbox(&self) -> Option<&serde_json::Value> {
+        self.meta.as_ref().and_then(|m| m.get(SANDBOX_META_KEY))
+    }
+
     /// Add a string parameter to the tool's input schema.
     ///
     /// If `input_schema` is not an object (or its `properties` is not an
@@ -479,6 +573,26 @@ impl ToolOutput {
         Self::Success(CallToolResult::content(content))
     }
 
+    /// Start building a result from several content blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mcpkit_core::types::{Role, ToolOutput};
+    ///
+    /// let output = ToolOutput::builder()
+    ///     .text("Rendered the chart.")
+    ///     .audience([Role::User])
+    ///     .image("iVBORw0KGgo=", "image/png")
+    ///     .priority(0.8)
+    ///     .resource_link("file:///reports/q3.csv", "q3.csv")
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn builder() -> ToolOutputBuilder {
+        ToolOutputBuilder::default()
+    }
+
     /// Create a JSON result.
     ///
     /// # Errors
@@ -511,6 +625,122 @@ impl ToolOutput {
     }
 }
 
+/// Fluent builder for multi-part [`ToolOutput`]s, from [`ToolOutput::builder`].
+///
+/// Content blocks are kept in the order they are added. [`audience`](Self::audience),
+/// [`priority`](Self::priority), and [`annotations`](Self::annotations)
+/// annotate the block added last, and are ignored before the first block.
+#[derive(Debug, Clone, Default)]
+pub struct ToolOutputBuilder {
+    result: CallToolResult,
+}
+
+impl ToolOutputBuilder {
+    /// Add a content block.
+    #[must_use]
+    pub fn content(mut self, content: Content) -> Self {
+        self.result.content.push(content);
+        self
+    }
+
+    /// Add several content blocks.
+    #[must_use]
+    pub fn contents(mut self, contents: impl IntoIterator<Item = Content>) -> Self {
+        self.result.content.extend(contents);
+        self
+    }
+
+    /// Add a text block.
+    #[must_use]
+    pub fn text(self, text: impl Into<String>) -> Self {
+        self.content(Content::text(text))
+    }
+
+    /// Add an image block from base64 data.
+    #[must_use]
+    pub fn image(self, data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        self.content(Content::image(data, mime_type))
+    }
+
+    /// Add an audio block from base64 data.
+    #[must_use]
+    pub fn audio(self, data: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        self.content(Content::audio(data, mime_type))
+    }
+
+    /// Add a link to a resource.
+    #[must_use]
+    pub fn resource_link(self, uri: impl Into<String>, name: impl Into<String>) -> Self {
+        self.content(Content::resource_link(uri, name))
+    }
+
+    /// Replace the annotations of the last block.
+    #[must_use]
+    pub fn annotations(mut self, annotations: ContentAnnotations) -> Self {
+        if let Some(slot) = self.last_annotations() {
+            *slot = Some(annotations);
+        }
+        self
+    }
+
+    /// Set who the last block is intended for.
+    #[must_use]
+    pub fn audience(mut self, audience: impl IntoIterator<Item = Role>) -> Self {
+        if let Some(slot) = self.last_annotations() {
+            slot.get_or_insert_with(ContentAnnotations::default)
+                .audience = Some(audience.into_iter().collect());
+        }
+        self
+    }
+
+    /// Set the priority of the last block, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn priority(mut self, priority: f64) -> Self {
+        if let Some(slot) = self.last_annotations() {
+            slot.get_or_insert_with(ContentAnnotations::default)
+                .priority = Some(priority.clamp(0.0, 1.0));
+        }
+        self
+    }
+
+    /// Mark the result as an error the model can see and react to.
+    #[must_use]
+    pub const fn is_error(mut self, is_error: bool) -> Self {
+        self.result.is_error = Some(is_error);
+        self
+    }
+
+    /// Attach structured content (matching the tool's `outputSchema`).
+    #[must_use]
+    pub fn structured(mut self, value: super::object::Object) -> Self {
+        self.result.structured_content = Some(value);
+        self
+    }
+
+    /// Attach protocol metadata (`_meta`).
+    #[must_use]
+    pub fn meta(mut self, meta: Meta) -> Self {
+        self.result.meta = Some(meta);
+        self
+    }
+
+    /// Finish the result.
+    #[must_use]
+    pub fn build(self) -> ToolOutput {
+        ToolOutput::Success(self.result)
+    }
+
+    fn last_annotations(&mut self) -> Option<&mut Option<ContentAnnotations>> {
+        self.result.content.last_mut()?.annotations_mut()
+    }
+}
+
+impl From<ToolOutputBuilder> for ToolOutput {
+    fn from(builder: ToolOutputBuilder) -> Self {
+        builder.build()
+    }
+}
+
 impl From<ToolOutput> for CallToolResult {
     fn from(output: ToolOutput) -> Self {
         match output {
@@ -572,6 +802,29 @@ impl From<String> for ToolOutput {
     }
 }
 
+impl From<Vec<Content>> for ToolOutput {
+    /// Convert content blocks into a successful `ToolOutput`.
+    fn from(content: Vec<Content>) -> Self {
+        Self::content(content)
+    }
+}
+
+impl From<Content> for ToolOutput {
+    /// Convert a single content block into a successful `ToolOutput`.
+    fn from(content: Content) -> Self {
+        Self::content(vec![content])
+    }
+}
+
+impl From<serde_json::Value> for ToolOutput {
+    /// Convert a JSON value into a `ToolOutput`, as [`Json`] does: an object
+    /// becomes `structuredContent`, and every value is also rendered as
+    /// pretty-printed text.
+    fn from(value: serde_json::Value) -> Self {
+        Json(value).into()
+    }
+}
+
 impl From<&str> for ToolOutput {
     /// Convert a string slice into a text `ToolOutput`.
     ///
@@ -587,6 +840,174 @@ impl From<&str> for ToolOutput {
     }
 }
 
+/// Validation constraints on a single tool argument.
+///
+/// `#[mcp_server]` builds one of these for each `#[tool]` parameter carrying
+/// `#[mcp(range(..), min_length = .., max_length = .., pattern = .., choices = [..])]`
+/// and checks the raw argument with [`check`](Self::check) before the method
+/// is invoked. The same constraints are emitted into the tool's input schema.
+///
+/// Numeric bounds apply to numbers, the rest to strings; a value of another
+/// type (or `null`) passes, leaving type errors to deserialization.
+///
+/// # Example
+///
+/// ```rust
+/// use mcpkit_core::types::ParamConstraints;
+///
+/// static LIMIT: ParamConstraints = ParamConstraints::new().minimum(1.0).maximum(100.0);
+///
+/// assert!(LIMIT.check("search", "limit", &serde_json::json!(10)).is_ok());
+/// assert!(LIMIT.check("search", "limit", &serde_json::json!(500)).is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct ParamConstraints {
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    min_length: Option<u32>,
+    max_length: Option<u32>,
+    pattern: Option<&'static str>,
+    choices: Option<&'static [&'static str]>,
+    /// `pattern`, compiled on first use.
+    regex: OnceLock<Result<regex::Regex, regex::Error>>,
+}
+
+impl ParamConstraints {
+    /// Create a set of constraints that accepts every value.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            minimum: None,
+            maximum: None,
+            min_length: None,
+            max_length: None,
+            pattern: None,
+            choices: None,
+            regex: OnceLock::new(),
+        }
+    }
+
+    /// Set the inclusive minimum for numbers.
+    #[must_use]
+    pub const fn minimum(mut self, min: f64) -> Self {
+        self.minimum = Some(min);
+        self
+    }
+
+    /// Set the inclusive maximum for numbers.
+    #[must_use]
+    pub const fn maximum(mut self, max: f64) -> Self {
+        self.maximum = Some(max);
+        self
+    }
+
+    /// Set the minimum string length, in characters.
+    #[must_use]
+    pub const fn min_length(mut self, len: u32) -> Self {
+        self.min_length = Some(len);
+        self
+    }
+
+    /// Set the maximum string length, in characters.
+    #[must_use]
+    pub const fn max_length(mut self, len: u32) -> Self {
+        self.max_length = Some(len);
+        self
+    }
+
+    /// Require strings to match a regex (unanchored, as in JSON Schema).
+    #[must_use]
+    pub const fn pattern(mut self, pattern: &'static str) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// Restrict strings to a fixed set of values.
+    #[must_use]
+    pub const fn choices(mut self, choices: &'static [&'static str]) -> Self {
+        self.choices = Some(choices);
+        self
+    }
+
+    /// Check the raw argument `value` of parameter `name` of `tool`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid-params error for `tool` naming the parameter, the
+    /// violated constraint, and the offending value. An invalid `pattern` is
+    /// reported as an internal error.
+    pub fn check(&self, tool: &str, name: &str, value: &serde_json::Value) -> Result<(), McpError> {
+        let invalid = |message: &str, expected: String, actual: String| {
+            McpError::invalid_params_detailed(
+                tool,
+                format!("invalid parameter '{name}': {message}"),
+                Some(name.to_string()),
+                Some(expected),
+                Some(actual),
+            )
+        };
+
+        if let Some(number) = value.as_f64() {
+            if let Some(min) = self.minimum.filter(|min| number < *min) {
+                return Err(invalid(
+                    "value is below the minimum",
+                    format!(">= {min}"),
+                    value.to_string(),
+                ));
+            }
+            if let Some(max) = self.maximum.filter(|max| number > *max) {
+                return Err(invalid(
+                    "value is above the maximum",
+                    format!("<= {max}"),
+                    value.to_string(),
+                ));
+            }
+        }
+
+        if let Some(text) = value.as_str() {
+            if let Some(choices) = self.choices.filter(|choices| !choices.contains(&text)) {
+                return Err(invalid(
+                    "value is not one of the allowed options",
+                    choices.join(", "),
+                    value.to_string(),
+                ));
+            }
+            let len = text.chars().count();
+            if let Some(min) = self.min_length.filter(|min| len < *min as usize) {
+                return Err(invalid(
+                    "value is too short",
+                    format!("at least {min} characters"),
+                    format!("{len} characters"),
+                ));
+            }
+            if let Some(max) = self.max_length.filter(|max| len > *max as usize) {
+                return Err(invalid(
+                    "value is too long",
+                    format!("at most {max} characters"),
+                    format!("{len} characters"),
+                ));
+            }
+            if let Some(pattern) = self.pattern {
+                let regex = self
+                    .regex
+                    .get_or_init(|| regex::Regex::new(pattern))
+                    .as_ref()
+                    .map_err(|e| {
+                        McpError::internal(format!("invalid pattern for parameter '{name}': {e}"))
+                    })?;
+                if !regex.is_match(text) {
+                    return Err(invalid(
+                        "value does not match the pattern",
+                        format!("matches /{pattern}/"),
+                        value.to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Request parameters for listing tools.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ListToolsRequest {
@@ -640,6 +1061,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn timeout_is_stored_in_meta() -> Result<(), Box<dyn std::error::Error>> {
+        let tool = Tool::new("slow").timeout(std::time::Duration::from_millis(1500));
+        assert_eq!(
+            serde_json::to_value(&tool)?["_meta"][TIMEOUT_META_KEY],
+            1500
+        );
+        assert_eq!(
+            tool.declared_timeout(),
+            Some(std::time::Duration::from_millis(1500))
+        );
+        assert_eq!(Tool::new("n").declared_timeout(), None);
+        Ok(())
+    }
+
     #[test]
     fn with_param_coerces_non_object_properties_instead_of_panicking() {
         // #18: `properties` present but not an object used to panic on insert.
@@ -795,6 +1231,54 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn tool_output_builder_mixes_content_and_annotations() {
+        let output = ToolOutput::builder()
+            .priority(0.1)
+            .text("summary")
+            .audience([Role::User])
+            .priority(1.5)
+            .image("aGk=", "image/png")
+            .resource_link("file:///report.csv", "report.csv")
+            .annotations(ContentAnnotations::for_assistant())
+            .structured(serde_json::Map::from_iter([("rows".to_string(), 3.into())]))
+            .is_error(true)
+            .build();
+        let ToolOutput::Success(result) = output else {
+            panic!("builder output is a success result");
+        };
+
+        assert_eq!(result.content.len(), 3);
+        let summary = result.content[0].annotations().unwrap();
+        assert_eq!(summary.audience, Some(vec![Role::User]));
+        assert_eq!(summary.priority, Some(1.0));
+        assert!(result.content[1].annotations().is_none());
+        assert_eq!(
+            result.content[2].annotations().unwrap().audience,
+            Some(vec![Role::Assistant])
+        );
+        assert!(result.is_error());
+        assert_eq!(result.structured_content.unwrap()["rows"], 3);
+    }
+
+    #[test]
+    fn tool_output_from_json_value_and_content() {
+        let CallToolResult {
+            structured_content,
+            content,
+            ..
+        } = ToolOutput::from(serde_json::json!({"ok": true})).into();
+        assert_eq!(structured_content.unwrap()["ok"], true);
+        assert!(content[0].as_text().unwrap().contains("\"ok\""));
+
+        let result: CallToolResult = ToolOutput::from(serde_json::json!([1, 2])).into();
+        assert!(result.structured_content.is_none());
+
+        let result: CallToolResult =
+            ToolOutput::from(vec![Content::text("a"), Content::text("b")]).into();
+        assert_eq!(result.content.len(), 2);
+    }
+
     #[test]
     fn test_tool_output_from_string() -> Result<(), Box<dyn std::error::Error>> {
         // From<String>
@@ -828,4 +1312,57 @@ mod tests {
         assert!(json.contains("\"inputSchema\""));
         Ok(())
     }
+
+    #[test]
+    fn param_constraints_report_the_violated_field() {
+        static LEVEL: ParamConstraints = ParamConstraints::new().choices(&["low", "high"]);
+        static CODE: ParamConstraints = ParamConstraints::new()
+            .min_length(2)
+            .max_length(4)
+            .pattern("^[A-Z]+$");
+        static COUNT: ParamConstraints = ParamConstraints::new().minimum(-1.0).maximum(2.5);
+
+        assert!(LEVEL.check("t", "level", &serde_json::json!("low")).is_ok());
+        assert!(CODE.check("t", "code", &serde_json::json!("ABC")).is_ok());
+        assert!(COUNT.check("t", "count", &serde_json::json!(-1)).is_ok());
+        // Values of another type are left to deserialization.
+        assert!(COUNT.check("t", "count", &serde_json::json!("x")).is_ok());
+        assert!(CODE.check("t", "code", &serde_json::Value::Null).is_ok());
+
+        let cases = [
+            (
+                &LEVEL,
+                serde_json::json!("mid"),
+                "not one of the allowed options",
+            ),
+            (&CODE, serde_json::json!("A"), "too short"),
+            (&CODE, serde_json::json!("ABCDE"), "too long"),
+            (
+                &CODE,
+                serde_json::json!("abc"),
+                "does not match the pattern",
+            ),
+            (&COUNT, serde_json::json!(-2), "below the minimum"),
+            (&COUNT, serde_json::json!(3), "above the maximum"),
+        ];
+        for (constraints, value, message) in cases {
+            let err = constraints.check("t", "field", &value).unwrap_err();
+            match err {
+                McpError::InvalidParams(details) => {
+                    assert!(details.message.contains(message), "{}", details.message);
+                    assert_eq!(details.method, "t");
+                    assert_eq!(details.param_path.as_deref(), Some("field"));
+                    assert!(details.actual.is_some() && details.expected.is_some());
+                }
+                other => panic!("expected invalid params, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn param_constraints_invalid_pattern_is_internal_error() {
+        static BAD: ParamConstraints = ParamConstraints::new().pattern("(");
+        let err = BAD.check("t", "x", &serde_json::json!("a")).unwrap_err();
+        assert!(matches!(err, McpError::Internal { .. }), "{err:?}");
+    }
 }
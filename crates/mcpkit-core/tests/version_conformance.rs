@@ -0,0 +1,262 @@
+//! Per-version conformance of [`adapt_result`].
+//!
+//! Each test serializes results that use every feature of the latest
+//! protocol version, adapts them for each supported version, and checks that
+//! only fields and content types that version defines remain, and that the
+//! adapted payload still parses as the typed result.
+
+use std::collections::BTreeSet;
+
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_core::compat::adapt_result;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::{
+    CallToolResult, Content, GetPromptResult, Icon, ListPromptsResult, ListResourcesResult,
+    ListToolsResult, Meta, Prompt, PromptArgument, PromptMessage, Resource, Role, TaskSupport,
+    Tool, ToolAnnotations,
+};
+use serde_json::Value;
+
+use ProtocolVersion::{V2024_11_05, V2025_03_26, V2025_06_18, V2025_11_25};
+
+fn adapted<T: serde::Serialize>(method: &str, result: &T, version: ProtocolVersion) -> Value {
+    let mut value = serde_json::to_value(result).unwrap();
+    adapt_result(method, &mut value, version);
+    value
+}
+
+fn keys(value: &Value) -> BTreeSet<&str> {
+    value
+        .as_object()
+        .expect("an object")
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
+
+fn set<'a>(items: &[&'a str]) -> BTreeSet<&'a str> {
+    items.iter().copied().collect()
+}
+
+fn meta() -> Meta {
+    serde_json::from_value(serde_json::json!({ "trace": "abc" })).unwrap()
+}
+
+#[test]
+fn initialize_drops_capabilities_and_server_info_fields() {
+    let mut server_info = ServerInfo::new("srv", "1.0.0");
+    server_info.protocol_version = None;
+    server_info.title = Some("Server".to_string());
+    server_info.icons = Some(vec![Icon::new("https://example.com/icon.png")]);
+    let caps = ServerCapabilities::new()
+        .with_tools()
+        .with_completions()
+        .with_tasks();
+
+    for &version in ProtocolVersion::ALL {
+        let result = adapted(
+            "initialize",
+            &serde_json::json!({
+                "protocolVersion": version.as_str(),
+                "serverInfo": server_info,
+                "capabilities": caps,
+            }),
+            version,
+        );
+        let expected_caps = match version {
+            V2024_11_05 => set(&["tools"]),
+            V2025_03_26 | V2025_06_18 => set(&["tools", "completions"]),
+            V2025_11_25 => set(&["tools", "completions", "tasks"]),
+        };
+        assert_eq!(keys(&result["capabilities"]), expected_caps, "{version}");
+        let expected_info = match version {
+            V2024_11_05 | V2025_03_26 => set(&["name", "version"]),
+            V2025_06_18 => set(&["name", "version", "title"]),
+            V2025_11_25 => set(&["name", "version", "title", "icons"]),
+        };
+        assert_eq!(keys(&result["serverInfo"]), expected_info, "{version}");
+        serde_json::from_value::<ServerCapabilities>(result["capabilities"].clone()).unwrap();
+    }
+}
+
+#[test]
+fn tools_list_keeps_only_fields_of_the_version() {
+    let mut tool = Tool::new("search")
+        .title("Search")
+        .description("Find things")
+        .icon(Icon::new("https://example.com/search.png"))
+        .annotations(ToolAnnotations::read_only())
+        .output_schema(serde_json::json!({ "type": "object" }))
+        .task_support(TaskSupport::Optional);
+    tool.meta = Some(meta());
+    let list = ListToolsResult {
+        tools: vec![tool],
+        next_cursor: None,
+        meta: Some(meta()),
+    };
+
+    for &version in ProtocolVersion::ALL {
+        let result = adapted("tools/list", &list, version);
+        let mut expected = set(&["name", "description", "inputSchema"]);
+        if version >= V2025_03_26 {
+            expected.insert("annotations");
+        }
+        if version >= V2025_06_18 {
+            expected.extend(["title", "outputSchema", "_meta"]);
+        }
+        if version >= V2025_11_25 {
+            expected.extend(["icons", "execution"]);
+        }
+        assert_eq!(keys(&result["tools"][0]), expected, "{version}");
+        assert_eq!(
+            result.get("_meta").is_some(),
+            version.supports_meta_field(),
+            "{version}"
+        );
+        serde_json::from_value::<ListToolsResult>(result).unwrap();
+    }
+}
+
+#[test]
+fn tools_call_replaces_content_the_version_lacks() {
+    let result = CallToolResult {
+        content: vec![
+            Content::text("hello"),
+            Content::audio("UklGRg==", "audio/wav"),
+            Content::resource_link("file:///report.pdf", "report"),
+        ],
+        is_error: None,
+        structured_content: None,
+        meta: None,
+    };
+
+    for &version in ProtocolVersion::ALL {
+        let adapted = adapted("tools/call", &result, version);
+        let kinds: Vec<&str> = adapted["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["type"].as_str().unwrap())
+            .collect();
+        let expected = match version {
+            V2024_11_05 => ["text", "text", "text"],
+            V2025_03_26 => ["text", "audio", "text"],
+            V2025_06_18 | V2025_11_25 => ["text", "audio", "resource_link"],
+        };
+        assert_eq!(kinds, expected, "{version}");
+        if !version.supports_resource_links() {
+            assert_eq!(adapted["content"][2]["text"], "report: file:///report.pdf");
+        }
+        serde_json::from_value::<CallToolResult>(adapted).unwrap();
+    }
+}
+
+#[test]
+fn structured_output_falls_back_to_text() {
+    let structured = serde_json::json!({ "temperature": 21 });
+    let result = CallToolResult {
+        content: Vec::new(),
+        is_error: None,
+        structured_content: structured.as_object().cloned(),
+        meta: None,
+    };
+
+    for &version in ProtocolVersion::ALL {
+        let adapted = adapted("tools/call", &result, version);
+        if version.supports_structured_tool_output() {
+            assert_eq!(adapted["structuredContent"], structured, "{version}");
+        } else {
+            assert!(adapted.get("structuredContent").is_none(), "{version}");
+            let text = adapted["content"][0]["text"].as_str().unwrap();
+            assert_eq!(serde_json::from_str::<Value>(text).unwrap(), structured);
+        }
+    }
+}
+
+#[test]
+fn prompts_and_resources_drop_display_fields() {
+    let prompts = ListPromptsResult {
+        prompts: vec![
+            Prompt::new("greet")
+                .title("Greet")
+                .icon(Icon::new("https://example.com/greet.png"))
+                .argument(PromptArgument::required("name", "Who").title("Name")),
+        ],
+        next_cursor: None,
+    };
+    let resources = ListResourcesResult {
+        resources: vec![
+            Resource::new("file:///a", "a")
+                .title("A")
+                .icon(Icon::new("https://example.com/a.png")),
+        ],
+        next_cursor: None,
+        meta: None,
+    };
+    let prompt = GetPromptResult {
+        description: None,
+        messages: vec![PromptMessage::with_content(
+            Role::Assistant,
+            Content::audio("UklGRg==", "audio/wav"),
+        )],
+        meta: None,
+    };
+
+    for &version in ProtocolVersion::ALL {
+        let mut display = set(&[]);
+        if version.supports_title_field() {
+            display.insert("title");
+        }
+        if version.supports_icons() {
+            display.insert("icons");
+        }
+
+        let listed = adapted("prompts/list", &prompts, version);
+        let extra: BTreeSet<_> = keys(&listed["prompts"][0])
+            .difference(&set(&["name", "arguments"]))
+            .copied()
+            .collect();
+        assert_eq!(extra, display, "{version}");
+        assert_eq!(
+            listed["prompts"][0]["arguments"][0].get("title").is_some(),
+            version.supports_title_field(),
+            "{version}"
+        );
+        serde_json::from_value::<ListPromptsResult>(listed).unwrap();
+
+        let listed = adapted("resources/list", &resources, version);
+        let extra: BTreeSet<_> = keys(&listed["resources"][0])
+            .difference(&set(&["uri", "name"]))
+            .copied()
+            .collect();
+        assert_eq!(extra, display, "{version}");
+        serde_json::from_value::<ListResourcesResult>(listed).unwrap();
+
+        let got = adapted("prompts/get", &prompt, version);
+        let kind = got["messages"][0]["content"]["type"].as_str().unwrap();
+        let expected = if version.supports_audio_content() {
+            "audio"
+        } else {
+            "text"
+        };
+        assert_eq!(kind, expected, "{version}");
+        serde_json::from_value::<GetPromptResult>(got).unwrap();
+    }
+}
+
+#[test]
+fn latest_version_is_untouched() {
+    let tool = Tool::new("t")
+        .title("T")
+        .icon(Icon::new("https://example.com/t.png"));
+    let list = ListToolsResult {
+        tools: vec![tool],
+        next_cursor: None,
+        meta: Some(meta()),
+    };
+    let original = serde_json::to_value(&list).unwrap();
+    assert_eq!(
+        adapted("tools/list", &list, ProtocolVersion::LATEST),
+        original
+    );
+}
@@ -0,0 +1,104 @@
+//! `coerce_args` on `#[tool]` or `#[mcp_server]` accepts quoted numbers and
+//! booleans; tools without it keep strict deserialization.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, ToolHandler};
+use mcpkit::types::CallToolResult;
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use serde_json::json;
+
+struct Shop;
+
+#[mcp_server(name = "shop", version = "1.0.0")]
+impl Shop {
+    #[tool(description = "Search products", coerce_args)]
+    async fn search(
+        &self,
+        limit: u32,
+        in_stock: bool,
+        max_price: Option<f64>,
+        #[mcp(choices = ["asc", "desc"])] order: String,
+    ) -> String {
+        format!("{limit} {in_stock} {max_price:?} {order}")
+    }
+
+    #[tool(description = "Reserve a product")]
+    async fn reserve(&self, quantity: u32) -> String {
+        quantity.to_string()
+    }
+}
+
+struct Lenient;
+
+#[mcp_server(name = "lenient", version = "1.0.0", coerce_args)]
+impl Lenient {
+    #[tool(description = "Repeat a word")]
+    async fn repeat(&self, word: String, times: usize) -> String {
+        word.repeat(times)
+    }
+}
+
+async fn call<H: ToolHandler>(
+    handler: &H,
+    tool: &str,
+    args: serde_json::Value,
+) -> Result<String, McpError> {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let serde_json::Value::Object(args) = args else {
+        unreachable!("arguments are an object")
+    };
+    let output = handler.call_tool(tool, args, &ctx).await?;
+    let result = CallToolResult::from(output);
+    Ok(result.content[0].as_text().unwrap_or_default().to_string())
+}
+
+#[tokio::test]
+async fn tool_arguments_are_coerced_when_enabled() {
+    let text = call(
+        &Shop,
+        "search",
+        json!({ "limit": " 5", "in_stock": "True", "max_price": "9.5", "order": "desc " }),
+    )
+    .await
+    .expect("coerced call");
+    assert_eq!(text, "5 true Some(9.5) desc");
+
+    let err = call(
+        &Shop,
+        "search",
+        json!({ "limit": "many", "in_stock": true, "order": "asc" }),
+    )
+    .await
+    .expect_err("not a number");
+    assert!(matches!(err, McpError::InvalidParams { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn tools_without_coercion_stay_strict() {
+    let err = call(&Shop, "reserve", json!({ "quantity": "2" }))
+        .await
+        .expect_err("strict tool");
+    assert!(matches!(err, McpError::InvalidParams { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn server_level_coercion_applies_to_every_tool() {
+    let text = call(&Lenient, "repeat", json!({ "word": "ab", "times": "3" }))
+        .await
+        .expect("coerced call");
+    assert_eq!(text, "ababab");
+}
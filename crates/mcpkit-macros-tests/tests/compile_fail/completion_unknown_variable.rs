@@ -0,0 +1,13 @@
+use mcpkit::mcp_server;
+
+struct Files;
+
+#[mcp_server(name = "files", version = "1.0.0")]
+impl Files {
+    #[completion(resource = "file:///{dir}/{name}", argument = "path")]
+    fn complete_path(&self, prefix: &str) -> Vec<String> {
+        vec![prefix.to_string()]
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use mcpkit::Elicitable;
+use serde::Deserialize;
+
+#[derive(Deserialize, Elicitable)]
+struct Form {
+    tags: Vec<String>,
+}
+
+fn main() {}
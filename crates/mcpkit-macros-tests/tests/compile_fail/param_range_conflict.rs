@@ -0,0 +1,13 @@
+use mcpkit::mcp_server;
+
+struct Search;
+
+#[mcp_server(name = "search", version = "1.0.0")]
+impl Search {
+    #[tool(description = "Search")]
+    async fn search(&self, #[mcp(range(1, 100), max = 50)] limit: i64) -> mcpkit::types::ToolOutput {
+        mcpkit::types::ToolOutput::text(limit.to_string())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mcpkit::mcp_server;
+
+struct Tables;
+
+#[mcp_server(name = "tables", version = "1.0.0")]
+impl Tables {
+    #[resource(uri_pattern = "db://tables/{table_name}", name = "Table")]
+    async fn table(&self, table: u32) -> mcpkit::types::ResourceContents {
+        mcpkit::types::ResourceContents::text("db://tables", table.to_string())
+    }
+}
+
+fn main() {}
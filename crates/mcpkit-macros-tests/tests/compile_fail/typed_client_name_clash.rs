@@ -0,0 +1,13 @@
+use mcpkit::mcp_server;
+
+struct Factory;
+
+#[mcp_server(name = "factory", version = "1.0.0", client = "FactoryClient")]
+impl Factory {
+    #[tool(description = "Create a widget")]
+    async fn new(&self, name: String) -> mcpkit::types::ToolOutput {
+        mcpkit::types::ToolOutput::text(name)
+    }
+}
+
+fn main() {}
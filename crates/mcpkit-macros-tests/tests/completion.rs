@@ -0,0 +1,108 @@
+//! `#[completion]` methods answer `completion/complete` for prompt arguments
+//! and resource template variables, and advertise the `completions` capability.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, RequestRouter, ServerHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::{Completion, GetPromptResult, PromptMessage};
+use serde_json::{Value, json};
+
+struct Srv;
+
+#[mcp_server(name = "srv", version = "1.0.0")]
+impl Srv {
+    #[prompt(description = "Review code")]
+    async fn review(&self, language: String) -> GetPromptResult {
+        GetPromptResult {
+            description: None,
+            messages: vec![PromptMessage::user(format!("Review this {language}"))],
+            meta: None,
+        }
+    }
+
+    #[completion(prompt = "review", argument = "language")]
+    async fn complete_language(&self, prefix: String) -> Vec<String> {
+        ["python", "perl", "rust"]
+            .into_iter()
+            .filter(|l| l.starts_with(&prefix))
+            .map(String::from)
+            .collect()
+    }
+
+    #[completion(resource = "file:///{path}", argument = "path")]
+    fn complete_path(&self, prefix: &str) -> Result<Completion, McpError> {
+        if prefix.contains("..") {
+            return Err(McpError::invalid_params(
+                "completion/complete",
+                "no parents",
+            ));
+        }
+        Ok(Completion {
+            values: vec![format!("{prefix}/README.md")],
+            total: None,
+            has_more: Some(true),
+        })
+    }
+
+    #[completion(prompt = "review", argument = "style")]
+    async fn complete_style(&self) -> Vec<String> {
+        vec!["strict".to_string()]
+    }
+}
+
+async fn complete(reference: Value, argument: &str, value: &str) -> Result<Value, McpError> {
+    let server = Srv.into_server();
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let params = json!({ "ref": reference, "argument": { "name": argument, "value": value } });
+    server
+        .route("completion/complete", Some(&params), &ctx)
+        .await
+}
+
+#[test]
+fn completions_capability_is_advertised() {
+    assert!(Srv.capabilities().has_completions());
+    assert!(Srv.into_server().capabilities().has_completions());
+}
+
+#[tokio::test]
+async fn prompt_argument_is_completed_from_partial_value() {
+    let reference = json!({ "type": "ref/prompt", "name": "review" });
+    let result = complete(reference.clone(), "language", "p").await.unwrap();
+    assert_eq!(result["completion"]["values"], json!(["python", "perl"]));
+    assert_eq!(result["completion"]["total"], 2);
+
+    let result = complete(reference, "style", "").await.unwrap();
+    assert_eq!(result["completion"]["values"], json!(["strict"]));
+}
+
+#[tokio::test]
+async fn resource_variable_is_completed_and_errors_propagate() {
+    let reference = json!({ "type": "ref/resource", "uri": "file:///{path}" });
+    let result = complete(reference.clone(), "path", "docs").await.unwrap();
+    assert_eq!(result["completion"]["values"], json!(["docs/README.md"]));
+    assert_eq!(result["completion"]["hasMore"], true);
+
+    assert!(complete(reference, "path", "../etc").await.is_err());
+}
+
+#[tokio::test]
+async fn unknown_argument_gets_empty_completion() {
+    let reference = json!({ "type": "ref/prompt", "name": "other" });
+    let result = complete(reference, "language", "p").await.unwrap();
+    assert_eq!(result["completion"]["values"], json!([]));
+}
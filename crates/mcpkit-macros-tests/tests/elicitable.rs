@@ -0,0 +1,175 @@
+//! `#[derive(Elicitable)]` builds a form schema from the struct's fields, and
+//! the typed outcome round-trips through validation and deserialization.
+//! `#[derive(ElicitForm)]` adds enum choices and `TryFrom<ElicitResult>`.
+
+use mcpkit::error::McpError;
+use mcpkit::types::{ElicitChoices, ElicitOutcome, ElicitResult, Elicitable as _};
+use mcpkit::{ElicitForm, Elicitable};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Deserialize, Elicitable)]
+#[serde(rename_all = "camelCase")]
+struct Deployment {
+    /// Target environment
+    #[mcp(choices = ["staging", "production"])]
+    environment: String,
+    /// Number of replicas
+    #[mcp(min = 1, max = 10, default = 2)]
+    replica_count: u32,
+    #[mcp(description = "Traffic share", min = 0.0, max = 1.0)]
+    canary_weight: Option<f64>,
+    #[serde(rename = "ticket")]
+    #[mcp(min_length = 3, pattern = "^[A-Z]+-[0-9]+$")]
+    change_ticket: Option<String>,
+    notify: bool,
+}
+
+#[test]
+fn schema_reflects_fields_and_attributes() {
+    let schema = Deployment::elicitation_schema();
+    let value = serde_json::to_value(&schema).unwrap();
+
+    assert_eq!(value["type"], "object");
+    assert_eq!(
+        value["required"],
+        json!(["environment", "replicaCount", "notify"])
+    );
+    assert_eq!(
+        value["properties"]["environment"],
+        json!({
+            "type": "string",
+            "description": "Target environment",
+            "enum": ["staging", "production"],
+        })
+    );
+    assert_eq!(
+        value["properties"]["replicaCount"],
+        json!({
+            "type": "integer",
+            "description": "Number of replicas",
+            "default": 2,
+            "minimum": 1.0,
+            "maximum": 10.0,
+        })
+    );
+    assert_eq!(
+        value["properties"]["canaryWeight"]["description"],
+        "Traffic share"
+    );
+    assert_eq!(value["properties"]["ticket"]["minLength"], 3);
+    assert_eq!(value["properties"]["ticket"]["pattern"], "^[A-Z]+-[0-9]+$");
+    assert_eq!(value["properties"]["notify"]["type"], "boolean");
+}
+
+#[test]
+fn accepted_content_is_validated_and_typed() {
+    let schema = Deployment::elicitation_schema();
+    let accept =
+        |content: serde_json::Value| ElicitResult::accepted(content.as_object().unwrap().clone());
+
+    let outcome = accept(json!({
+        "environment": "staging",
+        "replicaCount": 3,
+        "ticket": "OPS-42",
+        "notify": true,
+    }))
+    .parse::<Deployment>(&schema)
+    .unwrap();
+    assert_eq!(
+        outcome,
+        ElicitOutcome::Accepted(Deployment {
+            environment: "staging".to_string(),
+            replica_count: 3,
+            canary_weight: None,
+            change_ticket: Some("OPS-42".to_string()),
+            notify: true,
+        })
+    );
+
+    let out_of_range = accept(json!({
+        "environment": "staging",
+        "replicaCount": 11,
+        "notify": true,
+    }));
+    assert!(out_of_range.parse::<Deployment>(&schema).is_err());
+
+    let unknown_choice = accept(json!({
+        "environment": "qa",
+        "replicaCount": 1,
+        "notify": false,
+    }));
+    assert!(unknown_choice.parse::<Deployment>(&schema).is_err());
+
+    assert_eq!(
+        ElicitResult::declined()
+            .parse::<Deployment>(&schema)
+            .unwrap(),
+        ElicitOutcome::Declined
+    );
+}
+
+#[derive(Debug, PartialEq, Deserialize, ElicitForm)]
+#[serde(rename_all = "kebab-case")]
+enum Region {
+    UsEast,
+    EuWest,
+    #[serde(rename = "ap")]
+    AsiaPacific,
+}
+
+#[derive(Debug, PartialEq, Deserialize, ElicitForm)]
+struct Placement {
+    /// Where to run
+    region: Region,
+    fallback: Option<Region>,
+    /// Instances per region
+    #[serde(default)]
+    #[mcp(default = 1)]
+    instances: u32,
+}
+
+#[test]
+fn enums_become_choices_and_serde_defaults_are_optional() {
+    assert_eq!(Region::choices(), ["us-east", "eu-west", "ap"]);
+
+    let value = serde_json::to_value(Placement::elicitation_schema()).unwrap();
+    assert_eq!(value["required"], json!(["region"]));
+    assert_eq!(
+        value["properties"]["region"],
+        json!({
+            "type": "string",
+            "description": "Where to run",
+            "enum": ["us-east", "eu-west", "ap"],
+        })
+    );
+    assert_eq!(
+        value["properties"]["fallback"]["enum"],
+        json!(["us-east", "eu-west", "ap"])
+    );
+    assert_eq!(value["properties"]["instances"]["default"], 1);
+}
+
+#[test]
+fn results_convert_into_forms() {
+    let accepted = ElicitResult::accepted(json!({ "region": "ap" }).as_object().unwrap().clone());
+    assert_eq!(
+        Placement::try_from(accepted).unwrap(),
+        Placement {
+            region: Region::AsiaPacific,
+            fallback: None,
+            instances: 0,
+        }
+    );
+
+    let invalid = ElicitResult::accepted(json!({ "region": "mars" }).as_object().unwrap().clone());
+    assert!(Placement::try_from(invalid).is_err());
+    assert!(matches!(
+        Placement::try_from(ElicitResult::declined()),
+        Err(McpError::UserRejected { .. })
+    ));
+    assert!(matches!(
+        Placement::try_from(ElicitResult::cancelled()),
+        Err(McpError::Cancelled { .. })
+    ));
+}
@@ -0,0 +1,35 @@
+//! Test: Generic impl blocks with where-clauses expand correctly
+
+use mcpkit::mcp_server;
+use serde_json as _;  // Re-export for generated code
+
+trait Store {
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+struct KvServer<S> {
+    store: S,
+}
+
+#[mcp_server(name = "kv", version = "1.0.0")]
+impl<S> KvServer<S>
+where
+    S: Store + Send + Sync + 'static,
+{
+    /// Look up a key
+    #[tool(description = "Get a value")]
+    async fn get(&self, key: String) -> mcpkit::types::ToolOutput {
+        mcpkit::types::ToolOutput::text(self.store.get(&key).unwrap_or_default())
+    }
+
+    #[prompt(description = "Describe a key")]
+    async fn describe(&self, key: String) -> mcpkit::types::GetPromptResult {
+        mcpkit::types::GetPromptResult {
+            description: None,
+            messages: vec![mcpkit::types::PromptMessage::user(key)],
+            meta: None,
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,115 @@
+//! `#[mcp_server]` accepts generic impl blocks (including where-clauses), and
+//! handlers may hold trait objects, so servers can be written over a backend.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, RequestRouter, ServerHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::ResourceContents;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+trait Backend: Send + Sync + 'static {
+    fn lookup(&self, key: &str) -> Option<String>;
+}
+
+struct Fixed(&'static str);
+
+impl Backend for Fixed {
+    fn lookup(&self, key: &str) -> Option<String> {
+        (key == "greeting").then(|| self.0.to_string())
+    }
+}
+
+impl Backend for Arc<dyn Backend> {
+    fn lookup(&self, key: &str) -> Option<String> {
+        (**self).lookup(key)
+    }
+}
+
+struct Server<B: Backend> {
+    backend: B,
+    fallback: Arc<dyn Backend>,
+}
+
+#[mcp_server(name = "generic", version = "1.0.0")]
+impl<B> Server<B>
+where
+    B: Backend,
+{
+    #[tool(description = "Look up a key")]
+    async fn lookup(&self, key: String) -> Result<String, McpError> {
+        self.backend
+            .lookup(&key)
+            .or_else(|| self.fallback.lookup(&key))
+            .ok_or_else(|| McpError::invalid_params("lookup", format!("unknown key {key}")))
+    }
+
+    #[resource(uri_pattern = "kv://{key}", name = "Value")]
+    async fn value(&self, uri: &str, key: &str) -> ResourceContents {
+        ResourceContents::text(uri, self.backend.lookup(key).unwrap_or_default())
+    }
+}
+
+async fn route<B: Backend>(
+    server: Server<B>,
+    method: &str,
+    params: Value,
+) -> Result<Value, McpError> {
+    let server = server.into_server();
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    server.route(method, Some(&params), &ctx).await
+}
+
+#[tokio::test]
+async fn generic_server_routes_tools_and_resources() {
+    let server = Server {
+        backend: Fixed("hello"),
+        fallback: Arc::new(Fixed("unused")),
+    };
+    assert!(server.capabilities().has_tools());
+
+    let params = json!({ "name": "lookup", "arguments": { "key": "greeting" } });
+    let result = route(server, "tools/call", params).await.unwrap();
+    assert_eq!(result["content"][0]["text"], "hello");
+
+    let server = Server {
+        backend: Fixed("hello"),
+        fallback: Arc::new(Fixed("unused")),
+    };
+    let params = json!({ "uri": "kv://greeting" });
+    let result = route(server, "resources/read", params).await.unwrap();
+    assert_eq!(result["contents"][0]["text"], "hello");
+}
+
+#[tokio::test]
+async fn trait_object_backend_is_supported() {
+    let backend: Arc<dyn Backend> = Arc::new(Fixed("from dyn"));
+    let server = Server {
+        backend: Arc::clone(&backend),
+        fallback: backend,
+    };
+    let params = json!({ "name": "lookup", "arguments": { "key": "greeting" } });
+    let result = route(server, "tools/call", params).await.unwrap();
+    assert_eq!(result["content"][0]["text"], "from dyn");
+
+    let server = Server {
+        backend: Fixed("hello"),
+        fallback: Arc::new(Fixed("unused")),
+    };
+    let params = json!({ "name": "lookup", "arguments": { "key": "missing" } });
+    assert!(route(server, "tools/call", params).await.is_err());
+}
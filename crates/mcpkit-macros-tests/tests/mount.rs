@@ -0,0 +1,131 @@
+//! `#[mcp_server]` types can be mounted into one server under name prefixes;
+//! tools and prompts are listed with the prefix and routed back by it.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, RequestRouter, ServerBuilder, ServerHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::{GetPromptResult, PromptMessage, ResourceContents};
+use serde_json::{Value, json};
+
+struct FilesystemTools;
+
+#[mcp_server(name = "fs", version = "1.0.0")]
+impl FilesystemTools {
+    #[tool(description = "Read a file")]
+    async fn read(&self, path: String) -> String {
+        format!("contents of {path}")
+    }
+
+    #[resource(uri_pattern = "file:///{path}", name = "File")]
+    async fn file(&self, uri: &str, path: &str) -> ResourceContents {
+        ResourceContents::text(uri, format!("file {path}"))
+    }
+}
+
+struct GitTools;
+
+#[mcp_server(name = "git", version = "1.0.0")]
+impl GitTools {
+    #[tool(description = "Show the status")]
+    async fn status(&self) -> String {
+        "clean".to_string()
+    }
+
+    #[tool(description = "Read a blob")]
+    async fn read(&self, rev: String) -> String {
+        format!("blob at {rev}")
+    }
+
+    #[prompt(description = "Write a commit message")]
+    async fn commit(&self) -> GetPromptResult {
+        GetPromptResult {
+            description: None,
+            messages: vec![PromptMessage::user("Summarize the staged changes")],
+            meta: None,
+        }
+    }
+}
+
+struct Main;
+
+impl ServerHandler for Main {
+    fn server_info(&self) -> ServerInfo {
+        ServerInfo::new("main", "1.0.0")
+    }
+}
+
+async fn route(method: &str, params: Value) -> Result<Value, McpError> {
+    let server = ServerBuilder::new(Main)
+        .mount("fs_", FilesystemTools)
+        .mount("git_", GitTools)
+        .build();
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    server.route(method, Some(&params), &ctx).await
+}
+
+#[test]
+fn mounted_capabilities_are_advertised() {
+    let server = ServerBuilder::new(Main)
+        .mount("fs_", FilesystemTools)
+        .build();
+    let caps = server.capabilities();
+    assert!(caps.has_tools());
+    assert!(caps.has_resources());
+    assert!(!caps.has_prompts());
+}
+
+#[tokio::test]
+async fn tools_and_prompts_are_prefixed() {
+    let tools = route("tools/list", json!({})).await.unwrap();
+    let names: Vec<_> = tools["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["fs_read", "git_status", "git_read"]);
+
+    let prompts = route("prompts/list", json!({})).await.unwrap();
+    assert_eq!(prompts["prompts"][0]["name"], "git_commit");
+}
+
+#[tokio::test]
+async fn calls_are_routed_to_the_mounted_module() {
+    let params = json!({ "name": "fs_read", "arguments": { "path": "a.txt" } });
+    let result = route("tools/call", params).await.unwrap();
+    assert_eq!(result["content"][0]["text"], "contents of a.txt");
+
+    let params = json!({ "name": "git_read", "arguments": { "rev": "HEAD" } });
+    let result = route("tools/call", params).await.unwrap();
+    assert_eq!(result["content"][0]["text"], "blob at HEAD");
+
+    let result = route("prompts/get", json!({ "name": "git_commit" }))
+        .await
+        .unwrap();
+    assert_eq!(
+        result["messages"][0]["content"]["text"],
+        "Summarize the staged changes"
+    );
+
+    let result = route("resources/read", json!({ "uri": "file:///notes.md" }))
+        .await
+        .unwrap();
+    assert_eq!(result["contents"][0]["text"], "file notes.md");
+
+    let params = json!({ "name": "read", "arguments": {} });
+    assert!(route("tools/call", params).await.is_err());
+}
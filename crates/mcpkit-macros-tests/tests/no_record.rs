@@ -0,0 +1,67 @@
+//! `#[tool(no_record = true)]` marks the tool definition so a
+//! `RecordingPolicy` keeps its calls out of session recordings.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, ToolHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::debug::{RecordingPolicy, SessionRecorder};
+use mcpkit_core::protocol::{Message, Request, RequestId, Response};
+use mcpkit_core::protocol_version::ProtocolVersion;
+use serde_json::json;
+
+struct Vault;
+
+#[mcp_server(name = "vault", version = "1.0.0")]
+impl Vault {
+    #[tool(description = "Look up a stored password", no_record = true)]
+    async fn vault_get(&self, entry: String) -> String {
+        format!("password for {entry}")
+    }
+
+    #[tool(description = "List entry names")]
+    async fn vault_list(&self) -> String {
+        "bank".to_string()
+    }
+}
+
+#[tokio::test]
+async fn no_record_tools_are_redacted_from_recordings() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let tools = <Vault as ToolHandler>::list_tools(&Vault, &ctx)
+        .await
+        .expect("list_tools");
+    let find = |name: &str| tools.iter().find(|t| t.name == name).expect("tool");
+    assert!(find("vault_get").is_no_record());
+    assert!(!find("vault_list").is_no_record());
+
+    // The policy learns the marker from the `tools/list` response on the wire.
+    let recorder = SessionRecorder::new("vault").with_policy(RecordingPolicy::new());
+    recorder.record_sent(Message::Response(Response::success(
+        RequestId::Number(1),
+        json!({ "tools": tools }),
+    )));
+    recorder.record_received(Message::Request(Request::with_params(
+        "tools/call",
+        2,
+        json!({ "name": "vault_get", "arguments": { "entry": "bank" } }),
+    )));
+    recorder.record_sent(Message::Response(Response::success(
+        RequestId::Number(2),
+        json!({ "content": [{ "type": "text", "text": "password for bank" }] }),
+    )));
+
+    let transcript = recorder.finalize().to_json().expect("serialize");
+    assert!(!transcript.contains("bank"), "{transcript}");
+    assert!(transcript.contains("<redacted>"), "{transcript}");
+}
@@ -0,0 +1,140 @@
+//! `#[mcp(range(..), min_length = .., pattern = .., choices = [..])]` on tool
+//! parameters: constraints are emitted into the input schema and checked
+//! before the method runs, failing with field-level invalid-params errors.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, ToolHandler};
+use mcpkit::types::ToolOutput;
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Default)]
+struct Catalog {
+    calls: AtomicUsize,
+}
+
+#[mcp_server(name = "catalog", version = "1.0.0")]
+impl Catalog {
+    /// Search the catalog.
+    #[tool(description = "Search")]
+    async fn search(
+        &self,
+        /// The query string
+        #[mcp(min_length = 2, max_length = 20)]
+        query: String,
+        /// Maximum results to return
+        #[mcp(default = 10, range(1, 100))]
+        limit: i64,
+        /// Result ordering
+        #[mcp(choices = ["asc", "desc"])]
+        order: Option<String>,
+        /// Minimum score
+        #[mcp(min = -1.5, max = 1.5)]
+        threshold: Option<f64>,
+        /// Stock-keeping unit filter
+        #[mcp(pattern = "^[A-Z]{3}-[0-9]+$")]
+        sku: Option<String>,
+    ) -> ToolOutput {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let _ = (query, limit, order, threshold, sku);
+        ToolOutput::text("ok")
+    }
+}
+
+fn args(value: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    match value {
+        serde_json::Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+#[tokio::test]
+async fn constraints_are_emitted_into_input_schema() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+
+    let tools = Catalog::default()
+        .list_tools(&ctx)
+        .await
+        .expect("list_tools");
+    let props = &tools[0].input_schema["properties"];
+
+    assert_eq!(props["query"]["minLength"], json!(2));
+    assert_eq!(props["query"]["maxLength"], json!(20));
+    assert_eq!(props["limit"]["minimum"], json!(1));
+    assert_eq!(props["limit"]["maximum"], json!(100));
+    assert_eq!(props["limit"]["default"], json!(10));
+    assert_eq!(props["order"]["enum"], json!(["asc", "desc"]));
+    assert_eq!(props["threshold"]["minimum"], json!(-1.5));
+    assert_eq!(props["threshold"]["maximum"], json!(1.5));
+    assert_eq!(props["sku"]["pattern"], json!("^[A-Z]{3}-[0-9]+$"));
+}
+
+#[tokio::test]
+async fn invalid_arguments_are_rejected_before_the_call() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let catalog = Catalog::default();
+
+    let valid = json!({
+        "query": "shoes",
+        "limit": 100,
+        "order": "desc",
+        "threshold": -1.5,
+        "sku": "ABC-42",
+    });
+    catalog
+        .call_tool("search", args(valid.clone()), &ctx)
+        .await
+        .expect("valid arguments");
+
+    let cases = [
+        ("query", json!("s")),
+        ("query", json!("s".repeat(21))),
+        ("limit", json!(0)),
+        ("limit", json!(101)),
+        ("order", json!("sideways")),
+        ("threshold", json!(2.0)),
+        ("sku", json!("abc-42")),
+    ];
+    for (field, value) in cases {
+        let mut arguments = args(valid.clone());
+        arguments.insert(field.to_string(), value.clone());
+        let err = catalog
+            .call_tool("search", arguments, &ctx)
+            .await
+            .expect_err("constraint violation");
+        let McpError::InvalidParams(details) = err else {
+            panic!("expected invalid params for {field} = {value}, got {err:?}");
+        };
+        assert_eq!(details.method, "search");
+        assert_eq!(details.param_path.as_deref(), Some(field));
+        assert!(details.actual.is_some());
+    }
+
+    assert_eq!(catalog.calls.load(Ordering::SeqCst), 1);
+}
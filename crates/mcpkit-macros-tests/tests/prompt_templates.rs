@@ -0,0 +1,141 @@
+//! `#[prompt(template = ...)]` renders prompts from inline or file templates,
+//! with partials, and lists the template's placeholders as arguments.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, PromptHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::{Content, GetPromptResult};
+use serde_json::{Value, json};
+
+struct Reviewer {
+    checks: Vec<&'static str>,
+}
+
+#[mcp_server(name = "reviewer", version = "1.0.0")]
+impl Reviewer {
+    #[prompt(description = "Greet someone", template = "Hello, {{ name }}!")]
+    fn greet(&self) {}
+
+    #[prompt(
+        description = "Review code",
+        template_file = "tests/prompts/review.md",
+        partials = "tests/prompts/partials",
+        variables = [checks]
+    )]
+    async fn review(
+        &self,
+        /// Language of the code
+        language: String,
+        /// What to focus on
+        focus: Option<String>,
+    ) -> Result<Value, McpError> {
+        if language.is_empty() {
+            return Err(McpError::invalid_params("review", "empty language"));
+        }
+        // Returned variables take precedence over the arguments.
+        Ok(json!({"checks": self.checks, "focus": focus.map(|f| f.to_uppercase())}))
+    }
+}
+
+fn reviewer() -> Reviewer {
+    Reviewer {
+        checks: vec!["naming", "error handling"],
+    }
+}
+
+fn text(result: &GetPromptResult) -> &str {
+    match &result.messages[0].content {
+        Content::Text(t) => &t.text,
+        other => panic!("expected text, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn templates_render_and_list_their_arguments() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let server = reviewer();
+
+    let prompts = server.list_prompts(&ctx).await.expect("list_prompts");
+    let greet = prompts.iter().find(|p| p.name == "greet").expect("greet");
+    let greet_args = greet.arguments.as_ref().expect("arguments");
+    assert_eq!(greet_args.len(), 1);
+    assert_eq!(greet_args[0].name, "name");
+    assert_eq!(greet_args[0].required, Some(true));
+
+    // Parameters come first with their docs; `checks` is filled in by the
+    // method and is not an argument.
+    let review = prompts.iter().find(|p| p.name == "review").expect("review");
+    let review_args: Vec<_> = review
+        .arguments
+        .as_ref()
+        .expect("arguments")
+        .iter()
+        .map(|a| (a.name.as_str(), a.description.as_deref(), a.required))
+        .collect();
+    assert_eq!(
+        review_args,
+        [
+            ("language", Some("Language of the code"), Some(true)),
+            ("focus", Some("What to focus on"), Some(false)),
+            ("code", None, Some(true)),
+        ]
+    );
+
+    let args = json!({"name": "Ada"});
+    let result = server
+        .get_prompt("greet", args.as_object().cloned(), &ctx)
+        .await
+        .expect("greet");
+    assert_eq!(text(&result), "Hello, Ada!");
+
+    let args = json!({"language": "Rust", "code": "fn main() {}"});
+    let result = server
+        .get_prompt("review", args.as_object().cloned(), &ctx)
+        .await
+        .expect("review");
+    assert_eq!(
+        text(&result),
+        "Review this Rust code:\nfn main() {}\nCheck naming, error handling."
+    );
+
+    let args = json!({"language": "Rust", "code": "x", "focus": "safety"});
+    let result = server
+        .get_prompt("review", args.as_object().cloned(), &ctx)
+        .await
+        .expect("review");
+    assert!(text(&result).contains("Focus on SAFETY.\n"));
+
+    let err = server
+        .get_prompt(
+            "review",
+            json!({"language": "Rust"}).as_object().cloned(),
+            &ctx,
+        )
+        .await
+        .expect_err("missing code");
+    assert!(err.to_string().contains("missing required argument: code"));
+
+    let err = server
+        .get_prompt(
+            "review",
+            json!({"language": "", "code": "x"}).as_object().cloned(),
+            &ctx,
+        )
+        .await
+        .expect_err("method error");
+    assert!(err.to_string().contains("empty language"));
+}
@@ -0,0 +1,153 @@
+//! `#[resource]` URI patterns are matched in full, and their `{variables}`
+//! are passed to the method as typed parameters.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, RequestRouter, ResourceHandler, ServerHandler};
+use mcpkit::types::ResourceContents;
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+
+struct Tables;
+
+#[mcp_server(name = "tables", version = "1.0.0")]
+impl Tables {
+    #[resource(
+        uri_pattern = "db://tables/{table_name}",
+        name = "Table Schema",
+        description = "Columns of a table",
+        mime_type = "application/sql"
+    )]
+    async fn schema(&self, uri: &str, table_name: &str) -> ResourceContents {
+        ResourceContents::text(uri, format!("schema of {table_name}"))
+    }
+
+    #[resource(uri_pattern = "db://tables/{table_name}/rows/{row}", name = "Row")]
+    fn row(&self, row: u32, table_name: String) -> Result<ResourceContents, McpError> {
+        if row == 0 {
+            return Err(McpError::invalid_params(
+                "resources/read",
+                "rows start at 1",
+            ));
+        }
+        Ok(ResourceContents::text(
+            format!("db://tables/{table_name}/rows/{row}"),
+            format!("row {row} of {table_name}"),
+        ))
+    }
+
+    #[resource(uri_pattern = "db://tables/summary", name = "Summary")]
+    async fn summary(&self, _uri: &str) -> ResourceContents {
+        ResourceContents::text("db://tables/summary", "summary")
+    }
+
+    #[resource(uri_pattern = "file:///{+path}", name = "File")]
+    async fn file(&self, path: String) -> ResourceContents {
+        ResourceContents::text(format!("file:///{path}"), path)
+    }
+}
+
+async fn read(uri: &str) -> Result<String, McpError> {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &NoOpPeer,
+    );
+    let contents = Tables.read_resource(uri, &ctx).await?;
+    Ok(contents[0].text.clone().unwrap_or_default())
+}
+
+#[tokio::test]
+async fn variables_are_extracted_and_typed() {
+    assert_eq!(read("db://tables/users").await.unwrap(), "schema of users");
+    assert_eq!(
+        read("db://tables/users/rows/7").await.unwrap(),
+        "row 7 of users"
+    );
+    assert_eq!(read("file:///src/lib.rs").await.unwrap(), "src/lib.rs");
+}
+
+#[tokio::test]
+async fn static_uris_win_and_unmatched_uris_are_not_found() {
+    // `summary` also fits `db://tables/{table_name}`, but the exact URI wins.
+    assert_eq!(read("db://tables/summary").await.unwrap(), "summary");
+    assert!(matches!(
+        read("db://tables/users/extra").await,
+        Err(McpError::ResourceNotFound { .. })
+    ));
+}
+
+#[tokio::test]
+async fn unparseable_variables_are_invalid_params() {
+    let err = read("db://tables/users/rows/seven").await.unwrap_err();
+    assert!(err.to_string().contains("invalid `row`"), "{err}");
+    // Errors from the method itself propagate unchanged.
+    let err = read("db://tables/users/rows/0").await.unwrap_err();
+    assert!(err.to_string().contains("rows start at 1"), "{err}");
+}
+
+#[tokio::test]
+async fn templates_are_listed_from_patterns() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &NoOpPeer,
+    );
+    let templates = Tables.list_resource_templates(&ctx).await.unwrap();
+    let patterns: Vec<_> = templates.iter().map(|t| t.uri_template.as_str()).collect();
+    assert_eq!(
+        patterns,
+        [
+            "db://tables/{table_name}",
+            "db://tables/{table_name}/rows/{row}",
+            "file:///{+path}"
+        ]
+    );
+    let resources = Tables.list_resources(&ctx).await.unwrap();
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0].uri, "db://tables/summary");
+}
+
+#[tokio::test]
+async fn templates_list_is_routed_and_advertised() {
+    assert!(Tables.capabilities().has_resources());
+
+    let server = Tables.into_server();
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &NoOpPeer,
+    );
+    let listed = server
+        .route("resources/templates/list", None, &ctx)
+        .await
+        .unwrap();
+    let templates = listed["resourceTemplates"].as_array().unwrap();
+    assert_eq!(templates.len(), 3);
+    assert_eq!(templates[0]["uriTemplate"], "db://tables/{table_name}");
+    assert_eq!(templates[0]["name"], "Table Schema");
+    assert_eq!(templates[0]["description"], "Columns of a table");
+    assert_eq!(templates[0]["mimeType"], "application/sql");
+    // Unset descriptions are omitted, and the MIME type defaults.
+    assert!(templates[1].get("description").is_none());
+    assert_eq!(templates[1]["mimeType"], "text/plain");
+}
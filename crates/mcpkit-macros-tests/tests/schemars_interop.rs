@@ -0,0 +1,137 @@
+//! `#[tool]` parameters and `Json<T>` outputs whose types derive
+//! `schemars::JsonSchema` get full generated schemas.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, ToolHandler};
+use mcpkit::types::{Json, Tool, ToolOutput};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Where to search.
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct Scope {
+    /// Directory to search under.
+    root_dir: String,
+    /// How the match is performed.
+    mode: Mode,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum Mode {
+    Exact,
+    Fuzzy { distance: u8 },
+}
+
+/// A directory tree node; recursive, so it keeps a `$defs` entry.
+#[derive(Deserialize, JsonSchema)]
+struct Node {
+    name: String,
+    children: Vec<Node>,
+}
+
+#[derive(Serialize, JsonSchema)]
+struct Hits {
+    /// Number of matches.
+    count: u32,
+}
+
+struct Search;
+
+#[mcp_server(name = "search", version = "1.0.0")]
+impl Search {
+    /// Search files.
+    #[tool(description = "search")]
+    async fn search(&self, query: String, scope: Scope) -> Json<Hits> {
+        let count = match scope.mode {
+            Mode::Exact => u32::from(query == scope.root_dir),
+            Mode::Fuzzy { distance } => u32::from(distance),
+        };
+        Json(Hits { count })
+    }
+
+    /// Walk a tree.
+    #[tool(description = "walk")]
+    async fn walk(&self, tree: Node) -> ToolOutput {
+        ToolOutput::text(format!("{}: {}", tree.name, tree.children.len()))
+    }
+}
+
+async fn tool(name: &str) -> Tool {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    <Search as ToolHandler>::list_tools(&Search, &ctx)
+        .await
+        .expect("list_tools")
+        .into_iter()
+        .find(|t| t.name == name)
+        .expect("tool")
+}
+
+#[tokio::test]
+async fn nested_parameter_schema_is_generated() {
+    let tool = tool("search").await;
+    let scope = &tool.input_schema["properties"]["scope"];
+
+    // Nested struct with serde renames and doc comments, inlined.
+    assert_eq!(scope["type"], "object");
+    assert_eq!(
+        scope["properties"]["rootDir"]["description"],
+        "Directory to search under."
+    );
+    assert_eq!(scope["required"], serde_json::json!(["rootDir", "mode"]));
+
+    // A data-carrying enum becomes `oneOf`.
+    let mode = &scope["properties"]["mode"];
+    let variants = mode["oneOf"].as_array().expect("oneOf");
+    assert_eq!(variants.len(), 2);
+    assert!(mode.get("$ref").is_none(), "{mode}");
+
+    assert_eq!(
+        tool.input_schema["required"],
+        serde_json::json!(["query", "scope"])
+    );
+    assert_eq!(
+        tool.output_schema.expect("output schema")["properties"]["count"]["description"],
+        "Number of matches."
+    );
+}
+
+#[tokio::test]
+async fn recursive_definitions_are_hoisted_to_the_root() {
+    let tool = tool("walk").await;
+    let tree = &tool.input_schema["properties"]["tree"];
+    assert!(tree.get("$defs").is_none(), "{tree}");
+    assert!(
+        tool.input_schema["$defs"]["Node"].is_object(),
+        "{}",
+        tool.input_schema
+    );
+}
+
+#[test]
+fn tool_input_schema_for_uses_generated_schema() {
+    let tool = Tool::new("scoped").input_schema_for::<Scope>();
+    assert_eq!(tool.input_schema["title"], "Scope");
+    assert!(tool.input_schema.get("$schema").is_none());
+    assert_eq!(
+        tool.input_schema["properties"]["mode"]["oneOf"]
+            .as_array()
+            .map(Vec::len),
+        Some(2)
+    );
+}
@@ -0,0 +1,86 @@
+//! Tool methods may take a `&Context` parameter, which is passed the request
+//! context rather than read from the arguments, and report progress through it.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, Peer, ToolHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::{Notification, ProgressToken, RequestId};
+use mcpkit_core::protocol_version::ProtocolVersion;
+use serde_json::json;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+struct Indexer;
+
+#[mcp_server(name = "indexer", version = "1.0.0")]
+impl Indexer {
+    #[tool(description = "Index some files")]
+    async fn index(&self, count: u32, ctx: &Context<'_>) -> Result<String, McpError> {
+        let progress = ctx.progress_reporter();
+        let worker = progress.clone();
+        tokio::spawn(async move { worker.report(0.0, Some(f64::from(count)), None).await })
+            .await
+            .expect("join")?;
+        progress
+            .report(f64::from(count), Some(f64::from(count)), Some("done"))
+            .await?;
+        Ok(format!("indexed {count}"))
+    }
+}
+
+#[derive(Clone)]
+struct RecPeer(Arc<Mutex<Vec<Notification>>>);
+
+impl Peer for RecPeer {
+    fn notify(
+        &self,
+        notification: Notification,
+    ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+        self.0.lock().unwrap().push(notification);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn to_shared(&self) -> Option<Arc<dyn Peer>> {
+        Some(Arc::new(self.clone()))
+    }
+}
+
+#[tokio::test]
+async fn context_parameter_is_not_a_tool_argument() {
+    let request_id = RequestId::Number(1);
+    let token = ProgressToken::String("p".to_string());
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = RecPeer(Arc::new(Mutex::new(Vec::new())));
+    let ctx = Context::new(
+        &request_id,
+        Some(&token),
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+
+    let tools = Indexer.list_tools(&ctx).await.expect("list_tools");
+    let schema = serde_json::to_value(&tools[0].input_schema).unwrap();
+    assert_eq!(
+        schema["properties"],
+        json!({ "count": { "type": "integer" } })
+    );
+    assert_eq!(schema["required"], json!(["count"]));
+
+    let mut args = serde_json::Map::new();
+    args.insert("count".to_string(), json!(3));
+    let output = Indexer.call_tool("index", args, &ctx).await.expect("call");
+    let result = serde_json::to_value(mcpkit_core::types::CallToolResult::from(output)).unwrap();
+    assert_eq!(result["content"][0]["text"], "indexed 3");
+
+    let sent = peer.0.lock().unwrap();
+    assert_eq!(sent.len(), 2);
+    let last = sent[1].params.as_ref().unwrap();
+    assert_eq!(last["progressToken"], "p");
+    assert_eq!(last["progress"], 3.0);
+    assert_eq!(last["message"], "done");
+}
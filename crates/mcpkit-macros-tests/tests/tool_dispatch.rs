@@ -0,0 +1,65 @@
+//! Servers with many tools dispatch `tools/call` through a lookup table; every
+//! tool must still be reachable by name, and unknown names still rejected.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, ToolHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::CallToolResult;
+
+struct Many;
+
+/// Declare one tool per name, each returning its own name.
+macro_rules! many_tools {
+    ($($name:ident),*) => {
+        #[mcp_server(name = "many", version = "1.0.0")]
+        impl Many {
+            $(
+                #[tool(description = "Return this tool's name")]
+                async fn $name(&self) -> String {
+                    stringify!($name).to_string()
+                }
+            )*
+        }
+    };
+}
+
+many_tools!(
+    t00, t01, t02, t03, t04, t05, t06, t07, t08, t09, t10, t11, t12, t13, t14, t15, t16, t17, t18,
+    t19
+);
+
+#[tokio::test]
+async fn every_tool_is_dispatched_by_name() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+
+    let tools = Many.list_tools(&ctx).await.expect("list_tools");
+    assert_eq!(tools.len(), 20);
+    for tool in &tools {
+        let output = Many
+            .call_tool(&tool.name, serde_json::Map::new(), &ctx)
+            .await
+            .expect("call");
+        let result = CallToolResult::from(output);
+        assert_eq!(result.content[0].as_text(), Some(tool.name.as_str()));
+    }
+
+    let err = Many
+        .call_tool("t20", serde_json::Map::new(), &ctx)
+        .await
+        .expect_err("unknown tool");
+    assert!(matches!(err, McpError::MethodNotFound { .. }));
+}
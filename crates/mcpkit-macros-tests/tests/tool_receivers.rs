@@ -0,0 +1,115 @@
+//! Tool methods need not be `async fn`: plain `fn`s are called directly,
+//! methods returning `impl Future` are awaited, and `&mut self` methods put
+//! the server behind a lock.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, LockStrategy, NoOpPeer, ToolHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::CallToolResult;
+use serde_json::json;
+use std::future::Future;
+
+struct Plain;
+
+#[mcp_server(name = "plain", version = "1.0.0")]
+impl Plain {
+    #[tool(description = "Add two numbers")]
+    fn add(&self, a: i64, b: i64) -> String {
+        (a + b).to_string()
+    }
+
+    #[tool(description = "Shout after a yield")]
+    fn shout(&self, text: String) -> impl Future<Output = Result<String, McpError>> + Send {
+        // Work done before the future is returned runs on the call.
+        let text = text.to_uppercase();
+        async move {
+            tokio::task::yield_now().await;
+            Ok(text)
+        }
+    }
+}
+
+struct Counter {
+    count: i64,
+}
+
+#[mcp_server(name = "counter", version = "1.0.0")]
+impl Counter {
+    #[tool(description = "Add to the counter")]
+    fn bump(&mut self, by: i64) -> String {
+        self.count += by;
+        self.count.to_string()
+    }
+
+    #[tool(description = "Add to the counter after a yield")]
+    async fn bump_later(&mut self, by: i64) -> String {
+        tokio::task::yield_now().await;
+        self.count += by;
+        self.count.to_string()
+    }
+
+    #[tool(description = "Read the counter", read_only)]
+    fn get(&self) -> String {
+        self.count.to_string()
+    }
+}
+
+struct Serialized;
+
+#[mcp_server(name = "serialized", version = "1.0.0", lock = "mutex")]
+impl Serialized {
+    #[tool(description = "Do nothing")]
+    fn noop(&self) -> String {
+        String::new()
+    }
+}
+
+async fn call(handler: &impl ToolHandler, name: &str, args: serde_json::Value) -> String {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let serde_json::Value::Object(args) = args else {
+        panic!("arguments must be an object");
+    };
+    let output = handler.call_tool(name, args, &ctx).await.expect("call");
+    let result = CallToolResult::from(output);
+    result.content[0].as_text().expect("text").to_string()
+}
+
+#[tokio::test]
+async fn sync_and_future_returning_tools_are_dispatched() {
+    assert_eq!(call(&Plain, "add", json!({"a": 2, "b": 3})).await, "5");
+    assert_eq!(call(&Plain, "shout", json!({"text": "hi"})).await, "HI");
+}
+
+#[tokio::test]
+async fn mut_self_tools_run_behind_a_lock() {
+    let counter = LockedCounter::from(Counter { count: 0 });
+    assert_eq!(counter.server().strategy(), LockStrategy::RwLock);
+
+    assert_eq!(call(&counter, "bump", json!({"by": 2})).await, "2");
+    assert_eq!(call(&counter, "bump_later", json!({"by": 3})).await, "5");
+    assert_eq!(call(&counter, "get", json!({})).await, "5");
+    assert_eq!(counter.server().read().await.count, 5);
+
+    let _server = Counter { count: 0 }.into_server();
+}
+
+#[tokio::test]
+async fn lock_strategy_can_be_chosen() {
+    let serialized = LockedSerialized::from(Serialized);
+    assert_eq!(serialized.server().strategy(), LockStrategy::Mutex);
+    assert_eq!(call(&serialized, "noop", json!({})).await, "");
+}
@@ -0,0 +1,88 @@
+//! `#[tool(requires = [..])]` hides a tool from clients that lack a required
+//! capability and rejects calls from them with a typed error.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, ToolHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+
+struct Srv;
+
+#[mcp_server(name = "srv", version = "1.0.0")]
+impl Srv {
+    /// Always available.
+    #[tool(description = "plain tool")]
+    async fn plain(&self) -> String {
+        "plain".to_string()
+    }
+
+    /// Needs the client to sample and elicit.
+    #[tool(description = "asks the client's model", requires = [sampling, elicitation])]
+    async fn summarize(&self) -> String {
+        "summary".to_string()
+    }
+
+    /// Needs URL-mode elicitation, declared with the `requires_client` alias.
+    #[tool(description = "signs the user in", requires_client = "url_elicitation")]
+    async fn sign_in(&self) -> String {
+        "signed in".to_string()
+    }
+}
+
+/// List tool names and call `summarize` as a client with `caps`.
+async fn as_client(caps: ClientCapabilities) -> (Vec<String>, Result<(), McpError>) {
+    let request_id = RequestId::Number(1);
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let names = <Srv as ToolHandler>::list_tools(&Srv, &ctx)
+        .await
+        .expect("list_tools")
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    let call = <Srv as ToolHandler>::call_tool(&Srv, "summarize", serde_json::Map::new(), &ctx)
+        .await
+        .map(|_| ());
+    (names, call)
+}
+
+#[tokio::test]
+async fn tool_is_hidden_and_rejected_without_required_capability() {
+    let (names, call) = as_client(ClientCapabilities::new().with_sampling()).await;
+    assert_eq!(names, ["plain"]);
+    match call {
+        Err(McpError::ClientCapabilityRequired { tool, capability }) => {
+            assert_eq!(tool, "summarize");
+            assert_eq!(capability, "elicitation");
+        }
+        other => panic!("expected ClientCapabilityRequired, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn tool_is_available_with_required_capabilities() {
+    let caps = ClientCapabilities::new().with_sampling().with_elicitation();
+    let (names, call) = as_client(caps).await;
+    assert_eq!(names, ["plain", "summarize"]);
+    assert!(call.is_ok());
+}
+
+#[tokio::test]
+async fn requires_client_alias_gates_the_tool() {
+    let (names, _) = as_client(ClientCapabilities::new().with_elicitation()).await;
+    assert!(!names.iter().any(|n| n == "sign_in"));
+
+    let caps = ClientCapabilities::new().with_url_elicitation();
+    let (names, _) = as_client(caps).await;
+    assert!(names.iter().any(|n| n == "sign_in"));
+}
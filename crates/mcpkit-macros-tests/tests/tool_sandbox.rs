@@ -0,0 +1,119 @@
+//! `#[tool(allow_fs_read = .., allow_net = ..)]` lists the tool's access in its
+//! definition and limits what `ctx.fs()` and `ctx.http()` reach.
+
+use mcpkit::mcp_server;
+use mcpkit::server::sandbox::SandboxPolicy;
+use mcpkit::server::{Context, NoOpPeer, ToolHandler};
+use mcpkit::types::CallToolResult;
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use serde_json::json;
+
+struct Reader;
+
+#[mcp_server(name = "reader", version = "1.0.0")]
+impl Reader {
+    // Tests run from the crate directory, so `tests` is this file's directory.
+    #[tool(
+        description = "Read a file",
+        allow_fs_read = "tests",
+        allow_net = ["api.example.com", "*.example.org"]
+    )]
+    async fn read(&self, path: String, ctx: &Context<'_>) -> Result<String, McpError> {
+        ctx.fs().read_to_string(path).await
+    }
+
+    #[tool(description = "Fetch a URL", allow_net = "api.example.com")]
+    async fn fetch(&self, url: String, ctx: &Context<'_>) -> Result<String, McpError> {
+        Ok(ctx.http().check(&url)?.to_string())
+    }
+
+    #[tool(description = "Read without a sandbox")]
+    async fn unsandboxed(&self, path: String, ctx: &Context<'_>) -> Result<String, McpError> {
+        ctx.fs().read_to_string(path).await
+    }
+}
+
+async fn call(tool: &str, args: serde_json::Value) -> Result<String, McpError> {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let serde_json::Value::Object(args) = args else {
+        unreachable!("arguments are an object")
+    };
+    let output = Reader.call_tool(tool, args, &ctx).await?;
+    let result = CallToolResult::from(output);
+    Ok(result.content[0].as_text().unwrap_or_default().to_string())
+}
+
+#[tokio::test]
+async fn declared_access_is_listed() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let tools = Reader.list_tools(&ctx).await.expect("list_tools");
+    let find = |name: &str| tools.iter().find(|t| t.name == name).expect("tool");
+
+    assert_eq!(
+        find("read").declared_sandbox(),
+        Some(&json!({ "fsRead": ["tests"], "net": ["api.example.com", "*.example.org"] }))
+    );
+    assert_eq!(
+        SandboxPolicy::of_tool(find("fetch")),
+        Some(SandboxPolicy::new().allow_net("api.example.com"))
+    );
+    assert_eq!(find("unsandboxed").declared_sandbox(), None);
+}
+
+#[tokio::test]
+async fn fs_access_is_limited_to_declared_paths() {
+    let text = call("read", json!({ "path": "tests/tool_sandbox.rs" }))
+        .await
+        .expect("allowed read");
+    assert!(text.starts_with("//! `#[tool(allow_fs_read"));
+
+    for path in ["Cargo.toml", "tests/../Cargo.toml"] {
+        let err = call("read", json!({ "path": path }))
+            .await
+            .expect_err("outside the sandbox");
+        assert!(matches!(err, McpError::ToolAccessDenied { .. }), "{err:?}");
+    }
+
+    let err = call("unsandboxed", json!({ "path": "tests/tool_sandbox.rs" }))
+        .await
+        .expect_err("no sandbox");
+    assert!(matches!(err, McpError::ToolAccessDenied { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn net_access_is_limited_to_declared_hosts() {
+    let url = call("fetch", json!({ "url": "https://api.example.com/v1" }))
+        .await
+        .expect("allowed host");
+    assert_eq!(url, "https://api.example.com/v1");
+
+    let err = call("fetch", json!({ "url": "https://evil.example.net/" }))
+        .await
+        .expect_err("other host");
+    assert!(matches!(err, McpError::ToolAccessDenied { .. }), "{err:?}");
+}
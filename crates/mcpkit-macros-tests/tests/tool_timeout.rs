@@ -0,0 +1,78 @@
+//! `#[tool(timeout_ms = ...)]` marks the tool definition, and the server
+//! cancels calls that run past it with a timeout error.
+
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, NoOpPeer, RequestRouter, ServerBuilder, ToolHandler};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+struct Reports;
+
+#[mcp_server(name = "reports", version = "1.0.0")]
+impl Reports {
+    #[tool(description = "Build a report", timeout_ms = 20)]
+    async fn build(&self) -> String {
+        std::future::pending::<()>().await;
+        unreachable!("the call is cancelled first")
+    }
+
+    #[tool(description = "List reports")]
+    async fn list(&self) -> String {
+        "monthly".to_string()
+    }
+}
+
+async fn call(tool: &str) -> Result<Value, McpError> {
+    let server = ServerBuilder::new(Reports).with_tools(Reports).build();
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let params = json!({ "name": tool, "arguments": {} });
+    server.route("tools/call", Some(&params), &ctx).await
+}
+
+#[tokio::test]
+async fn declared_timeouts_are_listed_and_enforced() {
+    let request_id = RequestId::Number(1);
+    let client_caps = ClientCapabilities::default();
+    let server_caps = ServerCapabilities::default();
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &server_caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let tools = <Reports as ToolHandler>::list_tools(&Reports, &ctx)
+        .await
+        .expect("list_tools");
+    let find = |name: &str| tools.iter().find(|t| t.name == name).expect("tool");
+    assert_eq!(
+        find("build").declared_timeout(),
+        Some(Duration::from_millis(20))
+    );
+    assert_eq!(find("list").declared_timeout(), None);
+
+    match call("build").await {
+        Err(McpError::Timeout { duration, .. }) => {
+            assert_eq!(duration, Duration::from_millis(20));
+        }
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+    assert!(call("list").await.is_ok());
+}
@@ -0,0 +1,102 @@
+//! `#[mcp_server(client = "...")]` generates a typed client whose methods
+//! mirror the server's tools.
+
+use mcpkit::ToolInput;
+use mcpkit::client::ClientBuilder;
+use mcpkit::error::McpError;
+use mcpkit::mcp_server;
+use mcpkit::server::{Context, ServerBuilder};
+use mcpkit::transport::MemoryTransport;
+use mcpkit::types::{Json, ToolOutput};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ToolInput)]
+struct Stats {
+    /// Number of values.
+    count: usize,
+    /// Their mean.
+    mean: f64,
+}
+
+struct Calc;
+
+#[mcp_server(name = "calc", version = "1.0.0", client = "CalcClient")]
+impl Calc {
+    #[tool(description = "Add two numbers")]
+    async fn add(&self, a: f64, b: f64) -> ToolOutput {
+        ToolOutput::text((a + b).to_string())
+    }
+
+    #[tool(description = "Divide two numbers")]
+    async fn divide(&self, a: f64, b: f64) -> Result<ToolOutput, McpError> {
+        if b == 0.0 {
+            return Ok(ToolOutput::error("division by zero"));
+        }
+        Ok(ToolOutput::text((a / b).to_string()))
+    }
+
+    #[tool(description = "Summarize values")]
+    async fn stats(&self, values: Vec<f64>) -> Json<Stats> {
+        #[allow(clippy::cast_precision_loss)]
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        Json(Stats {
+            count: values.len(),
+            mean,
+        })
+    }
+
+    #[tool(name = "greet", description = "Greet someone")]
+    async fn greet_someone(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        title: Option<String>,
+    ) -> String {
+        let _ = ctx;
+        match title {
+            Some(title) => format!("Hello, {title} {name}"),
+            None => format!("Hello, {name}"),
+        }
+    }
+}
+
+async fn connect() -> CalcClient<MemoryTransport> {
+    let (client_side, server_side) = MemoryTransport::pair();
+    let server = ServerBuilder::new(Calc).with_tools(Calc).build();
+    tokio::spawn(server.serve(server_side));
+    CalcClient::new(ClientBuilder::new().build(client_side).await.unwrap())
+}
+
+#[tokio::test]
+async fn methods_mirror_tool_signatures() {
+    let calc = connect().await;
+
+    assert_eq!(calc.add(1.5, 2.0).await.unwrap(), "3.5");
+    assert_eq!(
+        calc.stats(vec![1.0, 2.0, 6.0]).await.unwrap(),
+        Stats {
+            count: 3,
+            mean: 3.0
+        }
+    );
+    assert_eq!(
+        calc.greet_someone("Ada".to_string(), None).await.unwrap(),
+        "Hello, Ada"
+    );
+    assert_eq!(
+        calc.greet_someone("Ada".to_string(), Some("Dr.".to_string()))
+            .await
+            .unwrap(),
+        "Hello, Dr. Ada"
+    );
+    assert_eq!(calc.client().server_info().name, "calc");
+}
+
+#[tokio::test]
+async fn tool_errors_are_returned_as_errors() {
+    let calc = connect().await;
+
+    let err = calc.divide(1.0, 0.0).await.unwrap_err();
+    assert!(matches!(err, McpError::ToolExecution(_)), "{err:?}");
+    assert!(err.to_string().contains("division by zero"), "{err}");
+}
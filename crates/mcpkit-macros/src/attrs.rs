@@ -20,6 +20,20 @@ pub struct ServerAttrs {
     #[darling(default)]
     pub instructions: Option<String>,
 
+    /// Name of a typed client wrapper to generate alongside the server.
+    #[darling(default)]
+    pub client: Option<String>,
+
+    /// Coerce quoted numbers and booleans in every tool's arguments.
+    #[darling(default)]
+    pub coerce_args: bool,
+
+    /// How the server is locked for `&mut self` tools: `"rwlock"` or
+    /// `"mutex"`. Setting it puts the server behind a lock even without such
+    /// tools.
+    #[darling(default)]
+    pub lock: Option<String>,
+
     /// Debug mode - print expanded code.
     #[darling(default)]
     pub debug_expand: bool,
@@ -81,6 +95,142 @@ pub struct ToolAttrs {
     /// ```
     #[darling(default)]
     pub max_input_size: Option<usize>,
+
+    /// Client capabilities the tool needs, e.g. `requires = [sampling]`.
+    ///
+    /// The tool is hidden from `tools/list` and rejected by `tools/call`
+    /// when the connected client did not declare all of them.
+    #[darling(default)]
+    pub requires: ClientRequirements,
+
+    /// Alias for `requires`, e.g. `requires_client = "sampling"`.
+    #[darling(default)]
+    pub requires_client: ClientRequirements,
+
+    /// Keep calls to this tool out of session recordings and wire dumps.
+    ///
+    /// Marks the tool definition with `_meta["mcpkit/noRecord"]`, which a
+    /// `RecordingPolicy` uses to replace its arguments and results with a
+    /// placeholder.
+    #[darling(default)]
+    pub no_record: bool,
+
+    /// How long a call may run, in milliseconds.
+    ///
+    /// Marks the tool definition with `_meta["mcpkit/timeoutMs"]`. The server
+    /// runtime cancels a call that runs longer and fails it with
+    /// `McpError::Timeout`.
+    #[darling(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Coerce quoted numbers and booleans in this tool's arguments to the
+    /// types its input schema declares (see `mcpkit_server::coercion`).
+    #[darling(default)]
+    pub coerce_args: bool,
+
+    /// Paths the tool may read through `ctx.fs()`.
+    #[darling(default)]
+    pub allow_fs_read: NameList,
+
+    /// Paths the tool may read and write through `ctx.fs()`.
+    #[darling(default)]
+    pub allow_fs_write: NameList,
+
+    /// Hosts the tool may connect to, checked by `ctx.http()`.
+    #[darling(default)]
+    pub allow_net: NameList,
+}
+
+/// Client capabilities a tool can require, with their `ClientCapability`
+/// variants.
+pub const CLIENT_CAPABILITIES: &[(&str, &str)] = &[
+    ("sampling", "Sampling"),
+    ("sampling_tools", "SamplingTools"),
+    ("elicitation", "Elicitation"),
+    ("url_elicitation", "UrlElicitation"),
+    ("roots", "Roots"),
+    ("tasks", "Tasks"),
+];
+
+/// The `ClientCapability` variant for a capability named in `requires`.
+pub fn client_capability_variant(name: &str) -> Option<&'static str> {
+    CLIENT_CAPABILITIES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, variant)| *variant)
+}
+
+/// The list in `requires = [sampling, elicitation]`.
+///
+/// Accepts bare identifiers or string literals, and a single item without
+/// brackets.
+#[derive(Debug, Default)]
+pub struct ClientRequirements(pub Vec<String>);
+
+impl FromMeta for ClientRequirements {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let items: Vec<&syn::Expr> = match expr {
+            syn::Expr::Array(array) => array.elems.iter().collect(),
+            other => vec![other],
+        };
+        items
+            .into_iter()
+            .map(|item| {
+                let name = match item {
+                    syn::Expr::Path(path) => path.path.get_ident().map(ToString::to_string),
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }) => Some(s.value()),
+                    _ => None,
+                }
+                .ok_or_else(|| darling::Error::unexpected_expr_type(item))?;
+                if client_capability_variant(&name).is_some() {
+                    Ok(name)
+                } else {
+                    let names: Vec<_> = CLIENT_CAPABILITIES.iter().map(|(n, _)| *n).collect();
+                    Err(darling::Error::custom(format!(
+                        "unknown client capability `{name}`; expected one of: {}",
+                        names.join(", ")
+                    ))
+                    .with_span(item))
+                }
+            })
+            .collect::<darling::Result<_>>()
+            .map(Self)
+    }
+}
+
+/// A list of names, e.g. `variables = [today, "user"]`.
+///
+/// Accepts bare identifiers or string literals, and a single item without
+/// brackets.
+#[derive(Debug, Default)]
+pub struct NameList(pub Vec<String>);
+
+impl FromMeta for NameList {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let items: Vec<&syn::Expr> = match expr {
+            syn::Expr::Array(array) => array.elems.iter().collect(),
+            other => vec![other],
+        };
+        items
+            .into_iter()
+            .map(|item| match item {
+                syn::Expr::Path(path) => path
+                    .path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .ok_or_else(|| darling::Error::unexpected_expr_type(item)),
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Ok(s.value()),
+                _ => Err(darling::Error::unexpected_expr_type(item)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(Self)
+    }
 }
 
 impl ToolAttrs {
@@ -127,13 +277,89 @@ pub struct PromptAttrs {
     /// Override the prompt name (defaults to method name).
     #[darling(default)]
     pub name: Option<String>,
+
+    /// Inline template source to render the prompt from.
+    #[darling(default)]
+    pub template: Option<String>,
+
+    /// Template file to render the prompt from, relative to the crate root.
+    #[darling(default)]
+    pub template_file: Option<String>,
+
+    /// Directory of partials the template can include, relative to the
+    /// crate root.
+    #[darling(default)]
+    pub partials: Option<String>,
+
+    /// Template variables the method fills in, which are not arguments.
+    #[darling(default)]
+    pub variables: NameList,
 }
 
 impl PromptAttrs {
     /// Parse prompt attributes from attribute tokens.
+    ///
+    /// At most one of `template` or `template_file` may be given, and
+    /// `partials` and `variables` only with one of them.
     pub fn parse(attr: proc_macro2::TokenStream) -> Result<Self, darling::Error> {
         let attr_args = NestedMeta::parse_meta_list(attr)?;
-        Self::from_list(&attr_args)
+        let attrs = Self::from_list(&attr_args)?;
+        if attrs.template.is_some() && attrs.template_file.is_some() {
+            return Err(darling::Error::custom(
+                "expected at most one of `template = \"...\"` or `template_file = \"...\"`",
+            ));
+        }
+        if (attrs.partials.is_some() || !attrs.variables.0.is_empty()) && !attrs.is_template() {
+            return Err(darling::Error::custom(
+                "`partials` and `variables` require `template` or `template_file`",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    /// Whether the prompt is rendered from a template.
+    pub const fn is_template(&self) -> bool {
+        self.template.is_some() || self.template_file.is_some()
+    }
+}
+
+/// Attributes for the `#[completion]` macro.
+#[derive(Debug, FromMeta)]
+pub struct CompletionAttrs {
+    /// Name of the prompt whose argument is completed.
+    #[darling(default)]
+    pub prompt: Option<String>,
+
+    /// URI template of the resource whose variable is completed.
+    #[darling(default)]
+    pub resource: Option<String>,
+
+    /// Name of the prompt argument or template variable (required).
+    pub argument: String,
+}
+
+impl CompletionAttrs {
+    /// Parse completion attributes from attribute tokens.
+    ///
+    /// Exactly one of `prompt` or `resource` must be given.
+    pub fn parse(attr: proc_macro2::TokenStream) -> Result<Self, darling::Error> {
+        let attr_args = NestedMeta::parse_meta_list(attr)?;
+        let attrs = Self::from_list(&attr_args)?;
+        if attrs.prompt.is_some() == attrs.resource.is_some() {
+            return Err(darling::Error::custom(
+                "expected exactly one of `prompt = \"...\"` or `resource = \"...\"`",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    /// The `CompletionRef` type tag and value this completion answers.
+    pub fn reference(&self) -> (&'static str, &str) {
+        match (&self.prompt, &self.resource) {
+            (Some(prompt), _) => ("ref/prompt", prompt),
+            (None, Some(uri)) => ("ref/resource", uri),
+            (None, None) => unreachable!("validated in `parse`"),
+        }
     }
 }
 
@@ -146,11 +372,170 @@ pub struct ParamAttrs {
 
     /// Minimum value (for numeric types).
     #[darling(default)]
-    pub min: Option<i64>,
+    pub min: Option<Bound>,
 
     /// Maximum value (for numeric types).
     #[darling(default)]
-    pub max: Option<i64>,
+    pub max: Option<Bound>,
+
+    /// Inclusive `range(min, max)`, shorthand for `min` and `max`.
+    #[darling(default)]
+    pub range: Option<Range>,
+
+    /// Minimum length (for string types).
+    #[darling(default)]
+    pub min_length: Option<u32>,
+
+    /// Maximum length (for string types).
+    #[darling(default)]
+    pub max_length: Option<u32>,
+
+    /// Regex pattern (for string types).
+    #[darling(default)]
+    pub pattern: Option<String>,
+
+    /// Allowed values (for string types).
+    #[darling(default)]
+    pub choices: Option<StringList>,
+}
+
+impl ParamAttrs {
+    /// The effective `(min, max)` bounds, from `range(..)` or `min`/`max`.
+    pub fn bounds(&self) -> darling::Result<(Option<Bound>, Option<Bound>)> {
+        let (min, max) = match (&self.range, self.min, self.max) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                return Err(darling::Error::custom(
+                    "`range(..)` cannot be combined with `min` or `max`",
+                ));
+            }
+            (Some(Range(min, max)), None, None) => (Some(*min), Some(*max)),
+            (None, min, max) => (min, max),
+        };
+        if let (Some(Bound(min)), Some(Bound(max))) = (min, max) {
+            if min > max {
+                return Err(darling::Error::custom(format!(
+                    "minimum {min} is greater than maximum {max}"
+                )));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_length, self.max_length) {
+            if min > max {
+                return Err(darling::Error::custom(format!(
+                    "`min_length` {min} is greater than `max_length` {max}"
+                )));
+            }
+        }
+        Ok((min, max))
+    }
+}
+
+/// Attributes for the `#[mcp(...)]` helper attribute on `#[derive(Elicitable)]`
+/// fields.
+#[derive(Debug, Default, FromMeta)]
+pub struct ElicitFieldAttrs {
+    /// Description shown to the user; defaults to the field's doc comment.
+    #[darling(default)]
+    pub description: Option<String>,
+
+    /// Default value pre-filled in the form.
+    #[darling(default)]
+    pub default: Option<syn::Lit>,
+
+    /// Minimum value (for numeric fields).
+    #[darling(default)]
+    pub min: Option<Bound>,
+
+    /// Maximum value (for numeric fields).
+    #[darling(default)]
+    pub max: Option<Bound>,
+
+    /// Minimum length (for string fields).
+    #[darling(default)]
+    pub min_length: Option<u32>,
+
+    /// Maximum length (for string fields).
+    #[darling(default)]
+    pub max_length: Option<u32>,
+
+    /// Regex pattern (for string fields).
+    #[darling(default)]
+    pub pattern: Option<String>,
+
+    /// Allowed values (for string fields).
+    #[darling(default)]
+    pub choices: Option<StringList>,
+}
+
+/// A numeric bound written as an integer or float literal, possibly negative.
+#[derive(Debug, Clone, Copy)]
+pub struct Bound(pub f64);
+
+impl FromMeta for Bound {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => Self::from_expr(expr).map(|Self(v)| Self(-v)),
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => Self::from_value(lit),
+            other => Err(darling::Error::unexpected_expr_type(other)),
+        }
+    }
+
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        match value {
+            syn::Lit::Int(i) => i.base10_parse::<f64>().map(Self).map_err(Into::into),
+            syn::Lit::Float(f) => f.base10_parse::<f64>().map(Self).map_err(Into::into),
+            other => Err(darling::Error::unexpected_lit_type(other)),
+        }
+        .map_err(|e: darling::Error| e.with_span(value))
+    }
+}
+
+/// The bounds in `range(1, 100)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Range(pub Bound, pub Bound);
+
+impl FromMeta for Range {
+    fn from_meta(item: &syn::Meta) -> darling::Result<Self> {
+        let syn::Meta::List(list) = item else {
+            return Err(darling::Error::custom("expected `range(min, max)`").with_span(item));
+        };
+        let bounds = list
+            .parse_args_with(
+                syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+            )
+            .map_err(darling::Error::from)?;
+        match bounds.iter().collect::<Vec<_>>().as_slice() {
+            [min, max] => Ok(Self(Bound::from_expr(min)?, Bound::from_expr(max)?)),
+            _ => Err(darling::Error::custom("expected `range(min, max)`").with_span(item)),
+        }
+    }
+}
+
+/// The list in `choices = ["low", "high"]`.
+#[derive(Debug, Default)]
+pub struct StringList(pub Vec<String>);
+
+impl FromMeta for StringList {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let syn::Expr::Array(array) = expr else {
+            return Err(darling::Error::unexpected_expr_type(expr));
+        };
+        array
+            .elems
+            .iter()
+            .map(|item| match item {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Ok(s.value()),
+                other => Err(darling::Error::unexpected_expr_type(other)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(Self)
+    }
 }
 
 /// Attributes for the `#[mcp_client]` macro.
@@ -201,4 +586,43 @@ mod tests {
         assert!(!attrs.idempotent);
         Ok(())
     }
+
+    #[test]
+    fn test_tool_attrs_requires() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let tokens = quote::quote!(description = "t", requires = [sampling, "roots"]);
+        let attrs = ToolAttrs::parse(tokens)?;
+        assert_eq!(attrs.requires.0, ["sampling", "roots"]);
+
+        let tokens = quote::quote!(description = "t", requires = elicitation);
+        assert_eq!(ToolAttrs::parse(tokens)?.requires.0, ["elicitation"]);
+
+        let tokens = quote::quote!(description = "t", requires_client = "url_elicitation");
+        assert_eq!(
+            ToolAttrs::parse(tokens)?.requires_client.0,
+            ["url_elicitation"]
+        );
+
+        let tokens = quote::quote!(description = "t", requires = [smapling]);
+        let err = ToolAttrs::parse(tokens).expect_err("unknown capability");
+        assert!(err.to_string().contains("smapling"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_completion_attrs_parse() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let tokens = quote::quote!(prompt = "review", argument = "language");
+        let attrs = CompletionAttrs::parse(tokens)?;
+        assert_eq!(attrs.reference(), ("ref/prompt", "review"));
+        assert_eq!(attrs.argument, "language");
+
+        let tokens = quote::quote!(resource = "file:///{path}", argument = "path");
+        let attrs = CompletionAttrs::parse(tokens)?;
+        assert_eq!(attrs.reference(), ("ref/resource", "file:///{path}"));
+
+        let both = quote::quote!(prompt = "p", resource = "r://x", argument = "a");
+        assert!(CompletionAttrs::parse(both).is_err());
+        let neither = quote::quote!(argument = "a");
+        assert!(CompletionAttrs::parse(neither).is_err());
+        Ok(())
+    }
 }
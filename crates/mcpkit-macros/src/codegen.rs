@@ -31,11 +31,28 @@ pub struct ToolMethod {
     pub idempotent: bool,
     /// Whether the tool is read-only
     pub read_only: bool,
+    /// Client capabilities the tool requires (`sampling`, `elicitation`,
+    /// `roots`).
+    pub requires: Vec<String>,
+    /// Whether calls are excluded from session recording
+    pub no_record: bool,
+    /// How long a call may run, in milliseconds
+    pub timeout_ms: Option<u64>,
+    /// Whether quoted numbers and booleans in the arguments are coerced
+    pub coerce_args: bool,
+    /// Paths the tool may read through `ctx.fs()`
+    pub allow_fs_read: Vec<String>,
+    /// Paths the tool may read and write through `ctx.fs()`
+    pub allow_fs_write: Vec<String>,
+    /// Hosts the tool may connect to through `ctx.http()`
+    pub allow_net: Vec<String>,
     /// The parameters (excluding &self)
     pub params: Vec<ToolParam>,
-    /// Whether the method is async
+    /// Whether the method is async or returns `impl Future`
     pub is_async: bool,
-    /// Whether the return type is Result
+    /// Whether the method takes `&mut self`
+    pub mut_receiver: bool,
+    /// Whether the return type (the future's output, for async methods) is Result
     pub returns_result: bool,
     /// The `T` of a `Json<T>` return, whose schema is the tool's `outputSchema`.
     pub output_type: Option<Type>,
@@ -54,19 +71,131 @@ pub struct ToolParam {
     pub is_optional: bool,
     /// Default value, from `#[mcp(default = ...)]`.
     pub default: Option<syn::Lit>,
-    /// Minimum value, from `#[mcp(min = ...)]`.
-    pub min: Option<i64>,
-    /// Maximum value, from `#[mcp(max = ...)]`.
-    pub max: Option<i64>,
+    /// Minimum value, from `#[mcp(min = ...)]` or `#[mcp(range(..))]`.
+    pub min: Option<f64>,
+    /// Maximum value, from `#[mcp(max = ...)]` or `#[mcp(range(..))]`.
+    pub max: Option<f64>,
+    /// Minimum string length, from `#[mcp(min_length = ...)]`.
+    pub min_length: Option<u32>,
+    /// Maximum string length, from `#[mcp(max_length = ...)]`.
+    pub max_length: Option<u32>,
+    /// Regex the string must match, from `#[mcp(pattern = ...)]`.
+    pub pattern: Option<String>,
+    /// Allowed string values, from `#[mcp(choices = [...])]`.
+    pub choices: Option<Vec<String>>,
+    /// Whether this is a `&Context` parameter, passed the request context
+    /// rather than a tool argument.
+    pub is_context: bool,
+}
+
+impl ToolParam {
+    /// Whether the parameter carries any constraint checked before the call.
+    fn has_constraints(&self) -> bool {
+        self.min.is_some()
+            || self.max.is_some()
+            || self.min_length.is_some()
+            || self.max_length.is_some()
+            || self.pattern.is_some()
+            || self.choices.is_some()
+    }
+
+    /// JSON Schema keyword insertions for the parameter's constraints.
+    fn schema_constraints(&self) -> TokenStream {
+        let mut inserts = Vec::new();
+        for (keyword, bound) in [("minimum", self.min), ("maximum", self.max)] {
+            if let Some(bound) = bound {
+                let value = json_number(bound);
+                inserts.push(quote!(obj.insert(#keyword.to_string(), #value);));
+            }
+        }
+        for (keyword, len) in [
+            ("minLength", self.min_length),
+            ("maxLength", self.max_length),
+        ] {
+            if let Some(len) = len {
+                inserts.push(quote!(obj.insert(#keyword.to_string(), ::serde_json::json!(#len));));
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            inserts.push(quote!(obj.insert("pattern".to_string(), ::serde_json::json!(#pattern));));
+        }
+        if let Some(choices) = &self.choices {
+            inserts.push(
+                quote!(obj.insert("enum".to_string(), ::serde_json::json!([#(#choices),*]));),
+            );
+        }
+        quote!(#(#inserts)*)
+    }
+
+    /// A `ParamConstraints` expression for the parameter's constraints.
+    fn constraints(&self) -> TokenStream {
+        let mut calls = Vec::new();
+        if let Some(min) = self.min {
+            calls.push(quote!(.minimum(#min)));
+        }
+        if let Some(max) = self.max {
+            calls.push(quote!(.maximum(#max)));
+        }
+        if let Some(len) = self.min_length {
+            calls.push(quote!(.min_length(#len)));
+        }
+        if let Some(len) = self.max_length {
+            calls.push(quote!(.max_length(#len)));
+        }
+        if let Some(pattern) = &self.pattern {
+            calls.push(quote!(.pattern(#pattern)));
+        }
+        if let Some(choices) = &self.choices {
+            calls.push(quote!(.choices(&[#(#choices),*])));
+        }
+        quote!(::mcpkit::types::ParamConstraints::new()#(#calls)*)
+    }
+}
+
+/// A bound as a JSON number, written as an integer when it is one so that
+/// `min = 1` appears as `1` rather than `1.0` in the schema.
+#[allow(clippy::cast_possible_truncation)]
+fn json_number(value: f64) -> TokenStream {
+    if value.fract() == 0.0 && value.abs() < 9.0e15 {
+        let int = value as i64;
+        quote!(::serde_json::json!(#int))
+    } else {
+        quote!(::serde_json::json!(#value))
+    }
 }
 
 impl ToolMethod {
+    /// Whether the method takes a `&Context` parameter.
+    pub fn takes_context(&self) -> bool {
+        self.params.iter().any(|p| p.is_context)
+    }
+
+    /// Whether the tool declares filesystem or network access.
+    pub fn is_sandboxed(&self) -> bool {
+        !(self.allow_fs_read.is_empty()
+            && self.allow_fs_write.is_empty()
+            && self.allow_net.is_empty())
+    }
+
+    /// An expression building the tool's `SandboxPolicy`.
+    pub fn sandbox_policy(&self) -> TokenStream {
+        let fs_read = &self.allow_fs_read;
+        let fs_write = &self.allow_fs_write;
+        let net = &self.allow_net;
+        quote! {
+            ::mcpkit::server::sandbox::SandboxPolicy::new()
+                #(.allow_fs_read(#fs_read))*
+                #(.allow_fs_write(#fs_write))*
+                #(.allow_net(#net))*
+        }
+    }
+
     /// Generate the JSON Schema for this tool's input.
     pub fn generate_input_schema(&self) -> TokenStream {
         let mut properties = Vec::new();
         let mut required = Vec::new();
 
-        for param in &self.params {
+        for param in self.params.iter().filter(|p| !p.is_context) {
             let name = param.name.to_string();
             let ty = &param.ty;
 
@@ -77,20 +206,13 @@ impl ToolMethod {
                 |d| quote!(::serde_json::Value::String(#d.to_string())),
             );
 
-            // `#[mcp(default = ..., min = ..., max = ...)]` -> JSON Schema
-            // `default` / `minimum` / `maximum`.
+            // `#[mcp(default = ..., range(..), pattern = ..., ...)]` -> JSON
+            // Schema `default` / `minimum` / `maximum` / `pattern` / ...
             let default_insert = param.default.as_ref().map_or_else(
                 || quote!(),
                 |lit| quote!(obj.insert("default".to_string(), ::serde_json::json!(#lit));),
             );
-            let min_insert = param.min.map_or_else(
-                || quote!(),
-                |m| quote!(obj.insert("minimum".to_string(), ::serde_json::json!(#m));),
-            );
-            let max_insert = param.max.map_or_else(
-                || quote!(),
-                |m| quote!(obj.insert("maximum".to_string(), ::serde_json::json!(#m));),
-            );
+            let constraint_inserts = param.schema_constraints();
 
             properties.push(quote! {
                 (#name.to_string(), {
@@ -100,8 +222,7 @@ impl ToolMethod {
                             obj.insert("description".to_string(), #description);
                         }
                         #default_insert
-                        #min_insert
-                        #max_insert
+                        #constraint_inserts
                     }
                     prop
                 })
@@ -126,7 +247,21 @@ impl ToolMethod {
                     for (name, value) in vec![#(#properties),*] {
                         properties.insert(name, value);
                     }
+                    // Generated schemas of recursive types reference their own
+                    // `$defs`; hoist those to the root, where `#/$defs/..` resolves.
+                    let mut defs = ::serde_json::Map::new();
+                    for value in properties.values_mut() {
+                        if let Some(::serde_json::Value::Object(nested)) = value
+                            .as_object_mut()
+                            .and_then(|prop| prop.remove("$defs"))
+                        {
+                            defs.extend(nested);
+                        }
+                    }
                     obj.insert("properties".to_string(), ::serde_json::Value::Object(properties));
+                    if !defs.is_empty() {
+                        obj.insert("$defs".to_string(), ::serde_json::Value::Object(defs));
+                    }
                     let required: Vec<String> = vec![#(#required.to_string()),*];
                     if !required.is_empty() {
                         obj.insert("required".to_string(), ::serde_json::Value::Array(
@@ -139,8 +274,12 @@ impl ToolMethod {
         }
     }
 
-    /// Generate the tool call dispatch code.
-    pub fn generate_call_dispatch(&self) -> TokenStream {
+    /// Generate the tool call dispatch arm, matching `pattern`.
+    pub fn generate_call_dispatch(
+        &self,
+        pattern: &TokenStream,
+        receiver: &TokenStream,
+    ) -> TokenStream {
         let method_name = &self.name;
         let tool_name = &self.tool_name;
 
@@ -148,26 +287,68 @@ impl ToolMethod {
         let param_extractions: Vec<_> = self
             .params
             .iter()
+            .filter(|param| !param.is_context)
             .map(|param| {
                 let name = &param.name;
                 let name_str = name.to_string();
                 let ty = &param.ty;
 
+                // Constrained parameters are checked against the raw argument
+                // before it is deserialized or the method is invoked.
+                let constraints_var = quote::format_ident!(
+                    "__{}_CONSTRAINTS",
+                    name_str.trim_start_matches("r#").to_uppercase()
+                );
+                let (constraints_static, check) = if param.has_constraints() {
+                    let constraints = param.constraints();
+                    (
+                        quote! {
+                            static #constraints_var: ::mcpkit::types::ParamConstraints = #constraints;
+                        },
+                        quote!(#constraints_var.check(#tool_name, #name_str, value)?;),
+                    )
+                } else {
+                    (quote!(), quote!())
+                };
+
                 if param.is_optional {
+                    let check = if param.has_constraints() {
+                        quote! {
+                            if let Some(value) = args.get(#name_str) {
+                                #check
+                            }
+                        }
+                    } else {
+                        quote!()
+                    };
                     quote! {
+                        #constraints_static
+                        #check
                         let #name: #ty = args.get(#name_str)
                             .and_then(|v| ::serde_json::from_value(v.clone()).ok());
                     }
                 } else {
                     // Use a different variable name for the Value to avoid type conflict
                     let value_var = quote::format_ident!("__{}_value", name);
+                    let check = if param.has_constraints() {
+                        quote! {
+                            {
+                                let value = &#value_var;
+                                #check
+                            }
+                        }
+                    } else {
+                        quote!()
+                    };
                     quote! {
+                        #constraints_static
                         let #value_var = args.get(#name_str)
                             .ok_or_else(|| ::mcpkit::error::McpError::invalid_params(
                                 #tool_name,
                                 format!("missing required parameter: {}", #name_str),
                             ))?
                             .clone();
+                        #check
                         let #name: #ty = ::serde_json::from_value(#value_var)
                             .map_err(|e| ::mcpkit::error::McpError::invalid_params(
                                 #tool_name,
@@ -178,12 +359,24 @@ impl ToolMethod {
             })
             .collect();
 
-        let param_names: Vec<_> = self.params.iter().map(|p| &p.name).collect();
+        // `&Context` parameters receive the `call_tool` context.
+        let param_names: Vec<_> = self
+            .params
+            .iter()
+            .map(|p| {
+                if p.is_context {
+                    quote!(ctx)
+                } else {
+                    let name = &p.name;
+                    quote!(#name)
+                }
+            })
+            .collect();
 
         let call = if self.is_async {
-            quote!(self.#method_name(#(#param_names),*).await)
+            quote!(#receiver.#method_name(#(#param_names),*).await)
         } else {
-            quote!(self.#method_name(#(#param_names),*))
+            quote!(#receiver.#method_name(#(#param_names),*))
         };
 
         // Convert the return value into `ToolOutput` via `Into`, so a tool may
@@ -196,8 +389,37 @@ impl ToolMethod {
             quote!(Ok(::core::convert::Into::into(#call)))
         };
 
+        // Coercion rewrites the raw arguments before they are checked against
+        // constraints and deserialized.
+        let coercion = if self.coerce_args {
+            let input_schema = self.generate_input_schema();
+            quote! {
+                let mut args = args;
+                ::mcpkit::server::coercion::coerce_arguments(#tool_name, &#input_schema, &mut args);
+            }
+        } else {
+            quote!()
+        };
+
+        // Sandboxed tools see a context whose `fs()` and `http()` enforce the
+        // declared policy.
+        let sandbox = if self.is_sandboxed() {
+            let policy = self.sandbox_policy();
+            quote! {
+                static __MCPKIT_SANDBOX: ::std::sync::LazyLock<::mcpkit::server::sandbox::Sandbox> =
+                    ::std::sync::LazyLock::new(|| {
+                        ::mcpkit::server::sandbox::Sandbox::new(#tool_name, #policy)
+                    });
+                let ctx = &ctx.sandboxed(&__MCPKIT_SANDBOX);
+            }
+        } else {
+            quote!()
+        };
+
         quote! {
-            #tool_name => {
+            #pattern => {
+                #coercion
+                #sandbox
                 #(#param_extractions)*
                 #call_with_conversion
             }
@@ -209,9 +431,10 @@ impl ToolMethod {
 ///
 /// For primitive types (String, integers, floats, bool), this returns a static schema.
 /// For container types (Option, Vec, `HashMap`), it recurses into the inner type.
-/// For custom struct types, it attempts to call `Type::tool_input_schema()`, which
-/// requires the type to derive `ToolInput`. If the type doesn't have this method,
-/// compilation will fail with a clear error message.
+/// For custom types, it calls `Type::tool_input_schema()`, provided by
+/// `#[derive(ToolInput)]` or, with the `schemars` feature, by any type deriving
+/// `schemars::JsonSchema` (through `ToolInputSchema`). Other types fail to
+/// compile with an error pointing at the type.
 fn type_to_json_schema(ty: &Type) -> TokenStream {
     if let Type::Path(path) = ty {
         // Match on the *last* path segment ident so qualified paths such as
@@ -247,18 +470,28 @@ fn type_to_json_schema(ty: &Type) -> TokenStream {
                 quote!(::serde_json::json!({"type": "object", "additionalProperties": true}))
             }
             "Value" => quote!(::serde_json::json!({})),
-            _ => {
-                // For custom struct types, call their tool_input_schema() method.
-                // This requires the type to derive ToolInput; otherwise the user
-                // gets a compile error pointing at the type.
-                quote!(#path::tool_input_schema())
-            }
+            _ => custom_type_schema(path),
         }
     } else {
         quote!(::serde_json::json!({}))
     }
 }
 
+/// The schema of a custom type, from its `tool_input_schema()`.
+///
+/// An inherent `tool_input_schema` (from `#[derive(ToolInput)]`) takes
+/// precedence over the `ToolInputSchema` trait, which covers
+/// `schemars::JsonSchema` types.
+pub fn custom_type_schema(path: &syn::TypePath) -> TokenStream {
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use ::mcpkit::schema::ToolInputSchema as _;
+            #path::tool_input_schema()
+        }
+    }
+}
+
 /// Return the first generic type argument of a path segment (e.g. `T` in
 /// `Option<T>` or `Vec<T>`), if present.
 fn first_type_arg(segment: &syn::PathSegment) -> Option<&Type> {
@@ -272,7 +505,7 @@ fn first_type_arg(segment: &syn::PathSegment) -> Option<&Type> {
 
 /// Extract parameter information from a function argument.
 ///
-/// Parses (and strips) the `#[mcp(default = ..., min = ..., max = ...)]` helper
+/// Parses (and strips) the `#[mcp(default = ..., range(..), pattern = ..., ...)]` helper
 /// attribute so it does not leak into the re-emitted impl block. Returns an
 /// error for a malformed `#[mcp(...)]` attribute instead of silently ignoring it.
 pub fn extract_param(arg: &mut FnArg) -> syn::Result<Option<ToolParam>> {
@@ -312,6 +545,10 @@ pub fn extract_param(arg: &mut FnArg) -> syn::Result<Option<ToolParam>> {
                         .map_err(|e| syn::Error::new_spanned(attr, e.to_string()))?;
                 }
             }
+            let (min, max) = param_attrs.bounds().map_err(|e| {
+                let attr = attrs.iter().find(|attr| attr.path().is_ident("mcp"));
+                syn::Error::new_spanned(attr, e.to_string())
+            })?;
             // Strip the attributes the macro consumes (`#[mcp(...)]` and the doc
             // comments read above) so they aren't re-emitted onto the parameter,
             // where the compiler rejects all but a few built-in attributes.
@@ -319,6 +556,7 @@ pub fn extract_param(arg: &mut FnArg) -> syn::Result<Option<ToolParam>> {
 
             // Check if optional
             let is_optional = is_option_type(ty);
+            let is_context = is_context_type(ty);
 
             Ok(Some(ToolParam {
                 name,
@@ -326,8 +564,13 @@ pub fn extract_param(arg: &mut FnArg) -> syn::Result<Option<ToolParam>> {
                 doc,
                 is_optional,
                 default: param_attrs.default,
-                min: param_attrs.min,
-                max: param_attrs.max,
+                min: min.map(|bound| bound.0),
+                max: max.map(|bound| bound.0),
+                min_length: param_attrs.min_length,
+                max_length: param_attrs.max_length,
+                pattern: param_attrs.pattern,
+                choices: param_attrs.choices.map(|choices| choices.0),
+                is_context,
             }))
         }
         FnArg::Receiver(_) => Ok(None),
@@ -344,6 +587,18 @@ fn is_option_type(ty: &Type) -> bool {
     false
 }
 
+/// Check if a type is a reference to `Context` (e.g. `&Context<'_>`).
+fn is_context_type(ty: &Type) -> bool {
+    if let Type::Reference(reference) = ty {
+        if let Type::Path(path) = reference.elem.as_ref() {
+            if let Some(segment) = path.path.segments.last() {
+                return segment.ident == "Context";
+            }
+        }
+    }
+    false
+}
+
 /// Check if a return type is Result.
 pub fn is_result_type(ret: &ReturnType) -> bool {
     match ret {
@@ -359,6 +614,36 @@ pub fn is_result_type(ret: &ReturnType) -> bool {
     }
 }
 
+/// If `ret` is `impl Future<Output = T>`, return `T`.
+///
+/// Such a method is awaited like an `async fn` returning `T`.
+pub fn future_output(ret: &ReturnType) -> Option<Type> {
+    let ReturnType::Type(_, ty) = ret else {
+        return None;
+    };
+    let Type::ImplTrait(impl_trait) = ty.as_ref() else {
+        return None;
+    };
+    impl_trait.bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let seg = bound.path.segments.last()?;
+        if seg.ident != "Future" {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+            return Some(syn::parse_quote!(()));
+        };
+        args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::AssocType(assoc) if assoc.ident == "Output" => {
+                Some(assoc.ty.clone())
+            }
+            _ => None,
+        })
+    })
+}
+
 /// If a tool returns `Json<T>` (optionally wrapped in `Result<_, E>`), return
 /// the inner `T`. Its schema becomes the tool's `outputSchema`.
 pub fn output_schema_type(ret: &ReturnType) -> Option<Type> {
@@ -406,6 +691,18 @@ mod tests {
         assert!(!is_option_type(&ty));
     }
 
+    #[test]
+    fn test_is_context_type() {
+        let ty: Type = parse_quote!(&Context<'_>);
+        assert!(is_context_type(&ty));
+
+        let ty: Type = parse_quote!(&::mcpkit::Context);
+        assert!(is_context_type(&ty));
+
+        let ty: Type = parse_quote!(Context);
+        assert!(!is_context_type(&ty));
+    }
+
     #[test]
     fn test_is_result_type() {
         let ret: ReturnType = parse_quote!(-> Result<ToolOutput, McpError>);
@@ -0,0 +1,125 @@
+//! Implementation of the `#[completion]` attribute macro.
+//!
+//! This module handles parsing and code generation for completion methods.
+//!
+//! The main entry point is [`expand_completion`], which transforms methods annotated
+//! with `#[completion]` into methods with metadata markers that `#[mcp_server]` can discover.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, FnArg, ImplItemFn, Result, parse2};
+
+use crate::attrs::CompletionAttrs;
+
+/// Expand the `#[completion]` attribute.
+///
+/// When used standalone (not inside `#[mcp_server]`), this macro
+/// preserves the method but adds metadata that `#[mcp_server]` can discover.
+pub fn expand_completion(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    // Parse the attribute
+    let attrs =
+        CompletionAttrs::parse(attr).map_err(|e| Error::new(proc_macro2::Span::call_site(), e))?;
+
+    // Parse the method
+    let method: ImplItemFn = parse2(item)?;
+
+    // Validate the method signature
+    validate_completion_method(&method)?;
+
+    let (ref_type, ref_value) = attrs.reference();
+    let argument = &attrs.argument;
+
+    // Generate a hidden constant that mcp_server can find
+    let marker_name = syn::Ident::new(
+        &format!("__MCP_COMPLETION_{}", method.sig.ident),
+        method.sig.ident.span(),
+    );
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        const #marker_name: (&str, &str, &str) = (#ref_type, #ref_value, #argument);
+
+        #[allow(dead_code)]
+        #method
+    })
+}
+
+/// Validate that a method has a valid signature for a completion.
+///
+/// Completion methods take `&self` and at most one more parameter, which
+/// receives the partial argument value the user has typed so far.
+pub fn validate_completion_method(method: &ImplItemFn) -> Result<()> {
+    // Must have &self receiver
+    let Some(receiver) = method.sig.receiver() else {
+        return Err(Error::new_spanned(
+            &method.sig,
+            "completion methods must take &self",
+        ));
+    };
+
+    // Check that receiver is &self (not &mut self or self)
+    if receiver.mutability.is_some() {
+        return Err(Error::new_spanned(
+            receiver,
+            "completion methods should take &self, not &mut self\n\
+             help: use interior mutability (e.g., Mutex, RwLock) if you need to modify state",
+        ));
+    }
+
+    if let Some(extra) = method
+        .sig
+        .inputs
+        .iter()
+        .filter(|arg| matches!(arg, FnArg::Typed(_)))
+        .nth(1)
+    {
+        return Err(Error::new_spanned(
+            extra,
+            "completion methods take at most one parameter: the partial value being completed\n\
+             help: implement `CompletionHandler` by hand to inspect the full request",
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_validate_completion_method() {
+        // Valid methods
+        let method: ImplItemFn = parse_quote! {
+            async fn languages(&self, prefix: String) -> Vec<String> {
+                vec![]
+            }
+        };
+        assert!(validate_completion_method(&method).is_ok());
+
+        let method: ImplItemFn = parse_quote! {
+            fn everything(&self) -> Vec<String> {
+                vec![]
+            }
+        };
+        assert!(validate_completion_method(&method).is_ok());
+
+        // Method without self - invalid
+        let method: ImplItemFn = parse_quote! {
+            async fn languages(prefix: String) -> Vec<String> {
+                vec![]
+            }
+        };
+        assert!(validate_completion_method(&method).is_err());
+
+        // Too many parameters - invalid
+        let method: ImplItemFn = parse_quote! {
+            async fn languages(&self, prefix: String, limit: usize) -> Vec<String> {
+                vec![]
+            }
+        };
+        assert!(validate_completion_method(&method).is_err());
+    }
+}
@@ -1,8 +1,11 @@
 //! Implementation of derive macros for MCP types.
 //!
 //! This module provides derive macros for automatically generating
-//! JSON Schema and other metadata for tool input types.
+//! JSON Schema and other metadata for tool input types and elicitation
+//! forms.
 
+use crate::attrs::ElicitFieldAttrs;
+use darling::FromMeta;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{Data, DeriveInput, Error, Fields, GenericArgument, PathArguments, Result, Type, parse2};
@@ -132,6 +135,333 @@ pub fn expand_tool_input(input: TokenStream) -> Result<TokenStream> {
     })
 }
 
+/// Expand the `#[derive(Elicitable)]` macro.
+///
+/// This implements `Elicitable` with a flat form schema built from the
+/// struct's fields: `String`, `bool`, integer and float fields, and
+/// `ElicitChoices` enums (optionally wrapped in `Option`), described by
+/// their doc comments and constrained by `#[mcp(...)]`. Property names
+/// follow the field's `#[serde(rename)]` or the struct's
+/// `#[serde(rename_all)]` so the schema matches deserialization.
+pub fn expand_elicitable(input: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = parse2(input)?;
+    elicitable_impl(&input, "Elicitable")
+}
+
+/// Expand the `#[derive(ElicitForm)]` macro.
+///
+/// On a struct this is `#[derive(Elicitable)]` plus `TryFrom<ElicitResult>`.
+/// On an enum of unit variants it implements `ElicitChoices`, so the enum
+/// can be a field of a form.
+pub fn expand_elicit_form(input: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = parse2(input)?;
+    if let Data::Enum(data) = &input.data {
+        return elicit_choices_impl(&input, data);
+    }
+    let elicitable = elicitable_impl(&input, "ElicitForm")?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #elicitable
+
+        impl #impl_generics ::core::convert::TryFrom<::mcpkit::types::ElicitResult>
+            for #name #ty_generics #where_clause
+        {
+            type Error = ::mcpkit::error::McpError;
+
+            fn try_from(result: ::mcpkit::types::ElicitResult) -> Result<Self, Self::Error> {
+                result.into_form()
+            }
+        }
+    })
+}
+
+/// Implement `ElicitChoices` for an enum of unit variants, naming each
+/// choice as serde would.
+fn elicit_choices_impl(input: &DeriveInput, data: &syn::DataEnum) -> Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let rename_all = serde_rename(&input.attrs, "rename_all")?;
+
+    let mut choices = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "ElicitForm enums must have unit variants only",
+            ));
+        }
+        let choice = if let Some(renamed) = serde_rename(&variant.attrs, "rename")? {
+            renamed
+        } else {
+            let raw = variant.ident.to_string();
+            match &rename_all {
+                Some(rule) => apply_variant_rename_rule(&raw, rule).ok_or_else(|| {
+                    Error::new_spanned(name, format!("unsupported rename_all rule `{rule}`"))
+                })?,
+                None => raw,
+            }
+        };
+        choices.push(choice);
+    }
+    if choices.is_empty() {
+        return Err(Error::new_spanned(
+            name,
+            "ElicitForm enums need at least one variant",
+        ));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::mcpkit::types::ElicitChoices for #name #ty_generics #where_clause {
+            fn choices() -> &'static [&'static str] {
+                &[#(#choices),*]
+            }
+        }
+    })
+}
+
+/// Implement `Elicitable` for a struct; `derive` names the derive in errors.
+fn elicitable_impl(input: &DeriveInput, derive: &str) -> Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    name,
+                    format!("{derive} can only be derived for structs with named fields"),
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                format!("{derive} can only be derived for structs"),
+            ));
+        }
+    };
+
+    let rename_all = serde_rename(&input.attrs, "rename_all")?;
+    let container_default = serde_flag(&input.attrs, "default")?;
+    let mut properties = Vec::new();
+
+    for field in fields {
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("expected named field but found tuple field - this should not happen");
+        let field_name = if let Some(renamed) = serde_rename(&field.attrs, "rename")? {
+            renamed
+        } else {
+            let raw = ident.to_string();
+            let raw = raw.trim_start_matches("r#");
+            match &rename_all {
+                Some(rule) => apply_rename_rule(raw, rule).ok_or_else(|| {
+                    Error::new_spanned(name, format!("unsupported rename_all rule `{rule}`"))
+                })?,
+                None => raw.to_string(),
+            }
+        };
+
+        let mut attrs = ElicitFieldAttrs::default();
+        for attr in &field.attrs {
+            if attr.path().is_ident("mcp") {
+                attrs = ElicitFieldAttrs::from_meta(&attr.meta)
+                    .map_err(|e| Error::new_spanned(attr, e.to_string()))?;
+            }
+        }
+
+        // Optional fields and fields serde fills in are not required.
+        let (ty, mut required) = match get_option_inner_type(&field.ty) {
+            Some(inner) => (inner, false),
+            None => (&field.ty, true),
+        };
+        if container_default || serde_flag(&field.attrs, "default")? {
+            required = false;
+        }
+        let kind = elicit_kind(ty).ok_or_else(|| {
+            Error::new_spanned(
+                &field.ty,
+                format!(
+                    "{derive} fields must be String, bool, an integer, a float, or an \
+                     ElicitForm enum, optionally wrapped in Option"
+                ),
+            )
+        })?;
+
+        let add = match (&attrs.choices, kind) {
+            (Some(choices), "string") => {
+                let choices = &choices.0;
+                quote!(.enumeration(#field_name, [#(#choices),*]))
+            }
+            (None, "choices") => quote! {
+                .enumeration(
+                    #field_name,
+                    <#ty as ::mcpkit::types::ElicitChoices>::choices().iter().copied(),
+                )
+            },
+            (Some(_), _) => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "`choices` is only supported on string fields",
+                ));
+            }
+            (None, kind) => {
+                let method = syn::Ident::new(kind, proc_macro2::Span::call_site());
+                quote!(.#method(#field_name))
+            }
+        };
+
+        let mut modifiers = Vec::new();
+        if required {
+            modifiers.push(quote!(.required()));
+        }
+        if let Some(desc) = attrs
+            .description
+            .or_else(|| extract_doc_comment(&field.attrs))
+        {
+            modifiers.push(quote!(.description(#desc)));
+        }
+        if let Some(lit) = &attrs.default {
+            modifiers.push(quote!(.default_value(::serde_json::json!(#lit))));
+        }
+        if let Some(min) = attrs.min {
+            let min = min.0;
+            modifiers.push(quote!(.min(#min)));
+        }
+        if let Some(max) = attrs.max {
+            let max = max.0;
+            modifiers.push(quote!(.max(#max)));
+        }
+        if let Some(len) = attrs.min_length {
+            modifiers.push(quote!(.min_length(#len)));
+        }
+        if let Some(len) = attrs.max_length {
+            modifiers.push(quote!(.max_length(#len)));
+        }
+        if let Some(pattern) = &attrs.pattern {
+            modifiers.push(quote!(.pattern(#pattern)));
+        }
+
+        properties.push(quote!(#add #(#modifiers)*));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::mcpkit::types::Elicitable for #name #ty_generics #where_clause {
+            fn elicitation_schema() -> ::mcpkit::types::ElicitationSchema {
+                ::mcpkit::types::ElicitationSchema::builder()
+                    #(#properties)*
+                    .build()
+            }
+        }
+    })
+}
+
+/// The elicitation property kind (and builder method) for a field type.
+///
+/// Any other type without generic arguments is taken to be an
+/// `ElicitChoices` enum (`"choices"`).
+fn elicit_kind(ty: &Type) -> Option<&'static str> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    match segment.ident.to_string().as_str() {
+        "String" => Some("string"),
+        "bool" => Some("boolean"),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => Some("integer"),
+        "f32" | "f64" => Some("number"),
+        _ if segment.arguments.is_empty() && path.qself.is_none() => Some("choices"),
+        _ => None,
+    }
+}
+
+/// Whether `#[serde(<key>)]` appears as a bare flag in the attributes.
+fn serde_flag(attrs: &[syn::Attribute], key: &str) -> Result<bool> {
+    let mut found = false;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            } else if meta.path.is_ident(key) {
+                found = true;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(found)
+}
+
+/// Read `#[serde(<key> = "...")]` from attributes, skipping other serde keys.
+fn serde_rename(attrs: &[syn::Attribute], key: &str) -> Result<Option<String>> {
+    let mut found = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(key) && meta.input.peek(syn::Token![=]) {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                found = Some(value.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(found)
+}
+
+/// Apply a serde `rename_all` rule to a `snake_case` field name.
+fn apply_rename_rule(field: &str, rule: &str) -> Option<String> {
+    let words = field.split('_').filter(|w| !w.is_empty());
+    let capitalize = |w: &str| {
+        let mut chars = w.chars();
+        chars.next().map_or_else(String::new, |c| {
+            c.to_uppercase().chain(chars).collect::<String>()
+        })
+    };
+    Some(match rule {
+        "lowercase" | "snake_case" => field.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_uppercase(),
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.replace('_', "-").to_uppercase(),
+        "PascalCase" => words.map(capitalize).collect(),
+        "camelCase" => {
+            let pascal: String = words.map(capitalize).collect();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map_or_else(String::new, |c| c.to_lowercase().chain(chars).collect())
+        }
+        _ => return None,
+    })
+}
+
+/// Apply a serde `rename_all` rule to a `PascalCase` variant name.
+fn apply_variant_rename_rule(variant: &str, rule: &str) -> Option<String> {
+    match rule {
+        "lowercase" => return Some(variant.to_lowercase()),
+        "UPPERCASE" => return Some(variant.to_uppercase()),
+        "PascalCase" => return Some(variant.to_string()),
+        _ => {}
+    }
+    let mut snake = String::new();
+    for (i, c) in variant.char_indices() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    apply_rename_rule(&snake, rule)
+}
+
 /// Extract doc comments from attributes.
 fn extract_doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
     let docs: Vec<String> = attrs
@@ -187,9 +517,8 @@ fn get_option_inner_type(ty: &Type) -> Option<&Type> {
 ///
 /// For primitive types (String, integers, floats, bool), this returns a static schema.
 /// For container types (Option, Vec, `HashMap`), it recurses into the inner type.
-/// For custom struct types, it attempts to call `Type::tool_input_schema()`, which
-/// requires the type to derive `ToolInput`. If the type doesn't have this method,
-/// compilation will fail with a clear error message.
+/// For custom types, it calls `Type::tool_input_schema()` (see
+/// [`crate::codegen::custom_type_schema`]).
 fn type_to_json_schema(ty: &Type) -> TokenStream {
     if let Type::Path(path) = ty {
         let path_str = quote!(#path).to_string().replace(' ', "");
@@ -237,12 +566,7 @@ fn type_to_json_schema(ty: &Type) -> TokenStream {
                 // JSON Value can be any type
                 quote!(serde_json::json!({}))
             }
-            _ => {
-                // For custom struct types, call their tool_input_schema() method.
-                // This requires the type to derive ToolInput.
-                // If it doesn't, the user gets a compile error with a helpful message.
-                quote!(#path::tool_input_schema())
-            }
+            _ => crate::codegen::custom_type_schema(path),
         }
     } else {
         quote!(serde_json::json!({}))
@@ -263,6 +587,36 @@ mod tests {
         assert!(!is_option_type(&ty));
     }
 
+    #[test]
+    fn test_apply_rename_rule() {
+        assert_eq!(
+            apply_rename_rule("max_items", "camelCase").as_deref(),
+            Some("maxItems")
+        );
+        assert_eq!(
+            apply_rename_rule("max_items", "PascalCase").as_deref(),
+            Some("MaxItems")
+        );
+        assert_eq!(
+            apply_rename_rule("max_items", "kebab-case").as_deref(),
+            Some("max-items")
+        );
+        assert_eq!(apply_rename_rule("max_items", "Train-Case"), None);
+    }
+
+    #[test]
+    fn test_serde_rename() {
+        let attrs: Vec<syn::Attribute> = vec![
+            parse_quote!(#[serde(default, skip_serializing_if = "Option::is_none")]),
+            parse_quote!(#[serde(rename = "userName")]),
+        ];
+        assert_eq!(
+            serde_rename(&attrs, "rename").unwrap().as_deref(),
+            Some("userName")
+        );
+        assert_eq!(serde_rename(&attrs, "rename_all").unwrap(), None);
+    }
+
     #[test]
     fn test_extract_doc_comment() {
         let attrs: Vec<syn::Attribute> = vec![parse_quote!(#[doc = " This is a test "])];
@@ -9,7 +9,13 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote_spanned;
 
 /// Known attribute names for `#[mcp_server]`.
-const SERVER_ATTRS: &[&str] = &["name", "version", "instructions", "debug_expand"];
+const SERVER_ATTRS: &[&str] = &[
+    "name",
+    "version",
+    "instructions",
+    "coerce_args",
+    "debug_expand",
+];
 
 /// Known attribute names for `#[tool]`.
 const TOOL_ATTRS: &[&str] = &[
@@ -18,6 +24,14 @@ const TOOL_ATTRS: &[&str] = &[
     "destructive",
     "idempotent",
     "read_only",
+    "requires",
+    "requires_client",
+    "no_record",
+    "timeout_ms",
+    "coerce_args",
+    "allow_fs_read",
+    "allow_fs_write",
+    "allow_net",
 ];
 
 /// Known attribute names for `#[resource]`.
@@ -26,6 +40,9 @@ const RESOURCE_ATTRS: &[&str] = &["uri_pattern", "name", "description", "mime_ty
 /// Known attribute names for `#[prompt]`.
 const PROMPT_ATTRS: &[&str] = &["description", "name"];
 
+/// Known attribute names for `#[completion]`.
+const COMPLETION_ATTRS: &[&str] = &["prompt", "resource", "argument"];
+
 /// Create an error for an unknown attribute with suggestions.
 pub fn unknown_attr_error(attr_name: &str, context: AttrContext, span: Span) -> TokenStream {
     let known = match context {
@@ -33,6 +50,7 @@ pub fn unknown_attr_error(attr_name: &str, context: AttrContext, span: Span) ->
         AttrContext::Tool => TOOL_ATTRS,
         AttrContext::Resource => RESOURCE_ATTRS,
         AttrContext::Prompt => PROMPT_ATTRS,
+        AttrContext::Completion => COMPLETION_ATTRS,
     };
 
     let suggestion = find_similar(attr_name, known);
@@ -61,6 +79,7 @@ pub fn missing_attr_error(attr_name: &str, context: AttrContext, span: Span) ->
         AttrContext::Tool => "tool",
         AttrContext::Resource => "resource",
         AttrContext::Prompt => "prompt",
+        AttrContext::Completion => "completion",
     };
 
     let message = format!(
@@ -104,6 +123,8 @@ pub enum AttrContext {
     Resource,
     /// `#[prompt]` attribute
     Prompt,
+    /// `#[completion]` attribute
+    Completion,
 }
 
 /// Find a similar string in a list (for typo suggestions).
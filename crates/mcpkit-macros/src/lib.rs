@@ -11,6 +11,7 @@
 //! - `#[tool]` - Mark a method as an MCP tool
 //! - `#[resource]` - Mark a method as an MCP resource handler
 //! - `#[prompt]` - Mark a method as an MCP prompt handler
+//! - `#[completion]` - Mark a method as an argument completion provider
 //!
 //! # Example
 //!
@@ -60,6 +61,7 @@
 mod attrs;
 mod client;
 mod codegen;
+mod completion;
 mod derive;
 mod error;
 mod prompt;
@@ -80,6 +82,12 @@ use proc_macro::TokenStream;
 /// - `version` - Server version (required, can use `env!("CARGO_PKG_VERSION")`)
 /// - `instructions` - Optional usage instructions sent to clients
 /// - `capabilities` - Optional list of capabilities to advertise
+/// - `client` - Optional name of a typed client wrapper to generate, with one
+///   method per tool (see below)
+/// - `coerce_args` - Coerce quoted numbers and booleans in every tool's
+///   arguments, as `#[tool(coerce_args)]` does for one tool
+/// - `lock` - `"rwlock"` or `"mutex"`: how the server is locked when a tool
+///   takes `&mut self` (default `"rwlock"`, see below)
 /// - `debug_expand` - Set to `true` to print generated code (default: false)
 ///
 /// # Example
@@ -102,6 +110,31 @@ use proc_macro::TokenStream;
 /// 2. `impl ToolHandler` with `list_tools()` and `call_tool()` (if any `#[tool]` methods)
 /// 3. `impl ResourceHandler` (if any `#[resource]` methods)
 /// 4. `impl PromptHandler` (if any `#[prompt]` methods)
+/// 5. `impl CompletionHandler` (if any `#[completion]` methods), which also
+///    advertises the `completions` capability
+/// 6. `impl Mountable` (if any tools, resources, or prompts), so the type can
+///    be mounted into another server with `ServerBuilder::mount`
+/// 7. With `client = "CalcClient"`, a `CalcClient<T, H>` wrapper around a
+///    connected `Client`. Its methods mirror the `#[tool]` signatures and
+///    return `Result<String, McpError>`, or `Result<T, McpError>` for tools
+///    returning `Json<T>`:
+///
+/// ```ignore
+/// let calc = CalcClient::new(client);
+/// let sum = calc.add(1.0, 2.0).await?;
+/// ```
+///
+/// When a `#[tool]` method takes `&mut self` (or `lock` is set), the server is
+/// put behind a lock and the handler traits are implemented for a generated
+/// `Locked<Name>` wrapper rather than for the type itself. `into_server()`
+/// wraps the value; elsewhere build the wrapper with
+/// `LockedMyServer::from(server)`. With `"rwlock"`, `&self` methods share the
+/// lock and `&mut self` methods take it exclusively; with `"mutex"`, every
+/// call takes it exclusively.
+///
+/// Generic impl blocks are supported: the generic parameters and where-clause
+/// carry over to every generated impl. The handler traits require
+/// `Send + Sync + 'static`, so bound the parameters accordingly.
 ///
 /// To serve the MCP server, use `ServerBuilder` with your preferred transport:
 ///
@@ -154,6 +187,63 @@ pub fn mcp_server(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn set_email(&self, user_id: String, email: String) -> ToolOutput { ... }
 /// ```
 ///
+/// ## Client Capability Requirements
+///
+/// - `requires = [sampling, elicitation]` - The tool needs these client
+///   capabilities (`sampling`, `sampling_tools`, `elicitation`,
+///   `url_elicitation`, `roots`, `tasks`). Clients that did not declare them
+///   don't see the tool in `tools/list`, and calling it returns
+///   `McpError::ClientCapabilityRequired` instead of failing mid-execution.
+///   `requires_client = "sampling"` is an alias.
+///
+/// ```ignore
+/// #[tool(description = "Summarize with the client's model", requires = [sampling])]
+/// async fn summarize(&self, text: String) -> ToolOutput { ... }
+/// ```
+///
+/// ## Execution Timeout
+///
+/// - `timeout_ms = 5000` - Cancel calls that run longer than this and fail
+///   them with `McpError::Timeout`. Overrides the method and default
+///   timeouts set with `ServerBuilder::timeouts`.
+///
+/// ```ignore
+/// #[tool(description = "Build a report", timeout_ms = 60_000)]
+/// async fn build_report(&self, month: String) -> ToolOutput { ... }
+/// ```
+///
+/// ## Argument Coercion
+///
+/// - `coerce_args` - Accept `"5"` for an integer parameter, `"true"` for a
+///   boolean, and similar, converting them to the types in the tool's input
+///   schema before validation. Each coercion is logged at `debug` level. See
+///   `mcpkit_server::coercion` for the rules.
+///
+/// ```ignore
+/// #[tool(description = "Search for items", coerce_args)]
+/// async fn search(&self, query: String, limit: u32) -> ToolOutput { ... }
+/// ```
+///
+/// ## Sandbox
+///
+/// - `allow_fs_read = "/data"` - Paths the tool may read through `ctx.fs()`
+/// - `allow_fs_write = "/tmp/out"` - Paths it may read and write
+/// - `allow_net = "api.example.com"` - Hosts `ctx.http().check(url)` accepts
+///   (`*.example.com` for subdomains)
+///
+/// Each takes a string or a list of strings. The tool must take a `&Context`;
+/// `ctx.fs()` and `ctx.http()` deny anything not declared. The declaration is
+/// listed in the tool's `_meta["mcpkit/sandbox"]`. See
+/// `mcpkit_server::sandbox`.
+///
+/// ```ignore
+/// #[tool(description = "Read a report", allow_fs_read = ["/data/reports"])]
+/// async fn read_report(&self, name: String, ctx: &Context<'_>) -> Result<ToolOutput, McpError> {
+///     let text = ctx.fs().read_to_string(format!("/data/reports/{name}")).await?;
+///     Ok(ToolOutput::text(text))
+/// }
+/// ```
+///
 /// # Parameter Extraction
 ///
 /// Tool parameters are extracted directly from the function signature:
@@ -174,6 +264,21 @@ pub fn mcp_server(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Method Forms
+///
+/// A tool may be an `async fn`, a plain `fn` (called directly, so it should
+/// not block for long), or a `fn` returning `impl Future<Output = ...>`,
+/// which is awaited. Methods taking `&mut self` run behind a lock; see
+/// [`macro@mcp_server`].
+///
+/// ```ignore
+/// #[tool(description = "Add to the counter")]
+/// fn add(&mut self, n: u64) -> String {
+///     self.count += n;
+///     self.count.to_string()
+/// }
+/// ```
+///
 /// # Return Types
 ///
 /// Tools can return either `ToolOutput` or `Result<ToolOutput, McpError>`:
@@ -242,6 +347,12 @@ pub fn tool(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - `description` - Description of the resource
 /// - `mime_type` - MIME type of the resource content
 ///
+/// A pattern without placeholders is listed by `resources/list`. One with
+/// `{variable}` (or `{+variable}`) placeholders is a resource template: it is
+/// listed by `resources/templates/list`, with its name, description and MIME
+/// type, and its variables can be completed with `#[completion(resource =
+/// ...)]`. Each pattern may be declared once per server.
+///
 /// # Example
 ///
 /// ```ignore
@@ -271,6 +382,19 @@ pub fn resource(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// - `description` - Description of what the prompt does
 /// - `name` - Override the prompt name (defaults to the method name)
+/// - `template` - Render the prompt from this template source (needs the
+///   `templates` feature)
+/// - `template_file` - Render the prompt from this template file, relative
+///   to the crate root
+/// - `partials` - Directory of files the template can include, relative to
+///   the crate root
+/// - `variables` - Template variables the method fills in, which are not
+///   listed as arguments
+///
+/// A template prompt's arguments are its parameters followed by the other
+/// placeholders in the template, and it renders as a single user message.
+/// The method returns extra template variables (any value serializing to an
+/// object, or `()` for none); see `mcpkit_server::template`.
 ///
 /// # Example
 ///
@@ -284,6 +408,9 @@ pub fn resource(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///         ],
 ///     }
 /// }
+///
+/// #[prompt(description = "Review code", template = "Review this {{ language }} code:\n{{ code }}")]
+/// fn review(&self) {}
 /// ```
 #[proc_macro_attribute]
 pub fn prompt(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -292,6 +419,46 @@ pub fn prompt(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Mark a method as an argument completion provider (`completion/complete`).
+///
+/// The method supplies suggestions for one prompt argument or one resource
+/// template variable. It takes `&self` plus, optionally, the partial value
+/// typed so far (`String`, `&str`, or `Cow<str>`), and returns suggestions as
+/// `Vec<String>` or `Completion`, optionally wrapped in `Result<_, McpError>`.
+/// Requests for arguments without a provider get an empty completion.
+///
+/// # Attributes
+///
+/// - `prompt` - Name of the prompt whose argument is completed
+/// - `resource` - URI template of the resource whose variable is completed
+/// - `argument` - Name of the argument or template variable (required)
+///
+/// Exactly one of `prompt` or `resource` must be given.
+///
+/// # Example
+///
+/// ```ignore
+/// #[completion(prompt = "code-review", argument = "language")]
+/// async fn complete_language(&self, prefix: String) -> Vec<String> {
+///     ["python", "rust", "typescript"]
+///         .into_iter()
+///         .filter(|l| l.starts_with(&prefix))
+///         .map(String::from)
+///         .collect()
+/// }
+///
+/// #[completion(resource = "file:///{path}", argument = "path")]
+/// async fn complete_path(&self, prefix: &str) -> Result<Vec<String>, McpError> {
+///     self.list_files(prefix).await
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn completion(attr: TokenStream, item: TokenStream) -> TokenStream {
+    completion::expand_completion(attr.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 /// Derive macro for tool input types.
 ///
 /// This derive macro generates JSON Schema information for complex
@@ -318,6 +485,84 @@ pub fn derive_tool_input(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derive macro for elicitation forms.
+///
+/// Implements `Elicitable` so the struct can be requested from the user with
+/// `ctx.elicit_form::<T>(message)`. Fields must be `String`, `bool`, an
+/// integer, or a float; `Option` fields are optional, all others required.
+/// Doc comments become property descriptions. The struct must also derive
+/// `Deserialize`, and `#[serde(rename)]` / `#[serde(rename_all)]` are
+/// reflected in the property names.
+///
+/// Field attributes: `#[mcp(description = "...", default = ..., min = ...,
+/// max = ..., min_length = ..., max_length = ..., pattern = "...",
+/// choices = ["a", "b"])]`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Deserialize, Elicitable)]
+/// struct Deployment {
+///     /// Target environment
+///     #[mcp(choices = ["staging", "production"])]
+///     environment: String,
+///     /// Number of replicas
+///     #[mcp(min = 1, max = 10, default = 2)]
+///     replicas: u32,
+///     /// Optional change ticket
+///     ticket: Option<String>,
+/// }
+/// ```
+#[proc_macro_derive(Elicitable, attributes(mcp))]
+pub fn derive_elicitable(input: TokenStream) -> TokenStream {
+    derive::expand_elicitable(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive macro for elicitation forms and their choices.
+///
+/// On a struct, this does what `#[derive(Elicitable)]` does and also
+/// implements `TryFrom<ElicitResult>`, so a raw result converts straight into
+/// the form: declining or cancelling is an error, and accepted content is
+/// validated against the schema before it is deserialized.
+///
+/// On an enum of unit variants, it implements `ElicitChoices`. A field of
+/// that enum type becomes a property restricted to the variant names, as
+/// serde spells them (`#[serde(rename)]` and `#[serde(rename_all)]` are
+/// honored). Fields marked `#[serde(default)]`, or all fields of a struct
+/// marked with it, are optional in the form.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Deserialize, ElicitForm)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Environment {
+///     Staging,
+///     Production,
+/// }
+///
+/// #[derive(Deserialize, ElicitForm)]
+/// struct Deployment {
+///     /// Target environment
+///     environment: Environment,
+///     /// Number of replicas
+///     #[serde(default)]
+///     #[mcp(min = 1, max = 10, default = 2)]
+///     replicas: u32,
+/// }
+///
+/// let result = ctx.elicit(ElicitRequest::form::<Deployment>("Deploy where?")).await?;
+/// let deployment = Deployment::try_from(result)?;
+/// ```
+#[proc_macro_derive(ElicitForm, attributes(mcp))]
+pub fn derive_elicit_form(input: TokenStream) -> TokenStream {
+    derive::expand_elicit_form(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
 // =============================================================================
 // Client Macros
 // =============================================================================
@@ -3,11 +3,13 @@
 //! This is the main macro that generates the full MCP server implementation.
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{Attribute, Error, FnArg, ImplItem, ImplItemFn, ItemImpl, Result, parse2};
 
-use crate::attrs::{PromptAttrs, ResourceAttrs, ServerAttrs, ToolAttrs};
-use crate::codegen::{ToolMethod, ToolParam, extract_param, is_result_type, output_schema_type};
+use crate::attrs::{CompletionAttrs, PromptAttrs, ResourceAttrs, ServerAttrs, ToolAttrs};
+use crate::codegen::{
+    ToolMethod, ToolParam, extract_param, future_output, is_result_type, output_schema_type,
+};
 
 /// Information about a resource method extracted from the AST.
 #[derive(Debug)]
@@ -22,12 +24,31 @@ struct ResourceMethod {
     description: String,
     /// MIME type
     mime_type: String,
+    /// The parameters (excluding &self), in declaration order
+    params: Vec<ResourceParam>,
     /// Whether the method is async
     is_async: bool,
     /// Whether the return type is Result
     returns_result: bool,
 }
 
+/// What a resource method parameter is passed.
+#[derive(Debug)]
+enum ResourceParam {
+    /// The full requested URI (any `&str` parameter not named after a variable).
+    Uri,
+    /// The text captured by the `index`-th `{variable}` of the URI pattern.
+    Variable {
+        /// The variable's position in the pattern
+        index: usize,
+        /// The variable name
+        name: String,
+        /// The parameter type; `&str` borrows the capture, anything else is
+        /// parsed with `FromStr`
+        ty: Box<syn::Type>,
+    },
+}
+
 /// Information about a prompt method extracted from the AST.
 #[derive(Debug)]
 struct PromptMethod {
@@ -43,6 +64,19 @@ struct PromptMethod {
     is_async: bool,
     /// Whether the return type is Result
     returns_result: bool,
+    /// The template the prompt is rendered from, if any
+    template: Option<PromptTemplateSource>,
+}
+
+/// The template a prompt is rendered from.
+#[derive(Debug)]
+struct PromptTemplateSource {
+    /// Expression for the template source (a literal or `include_str!`)
+    source: TokenStream,
+    /// The partials, as names and source expressions
+    partials: Vec<(String, TokenStream)>,
+    /// Variables the method fills in, which are not arguments
+    variables: Vec<String>,
 }
 
 /// Information about a prompt parameter.
@@ -58,6 +92,47 @@ struct PromptParam {
     is_optional: bool,
 }
 
+/// Information about a completion method extracted from the AST.
+#[derive(Debug)]
+struct CompletionMethod {
+    /// The method name
+    name: syn::Ident,
+    /// The `CompletionRef` type tag (`ref/prompt` or `ref/resource`)
+    ref_type: &'static str,
+    /// The prompt name or resource URI template
+    ref_value: String,
+    /// The prompt argument or template variable being completed
+    argument: String,
+    /// Whether the method takes the partial value as a parameter
+    takes_value: bool,
+    /// Whether the method is async
+    is_async: bool,
+    /// Whether the return type is Result
+    returns_result: bool,
+}
+
+/// How the generated handler impls reach the server value.
+struct Access {
+    /// The type the handler traits are implemented for: the server type, or
+    /// its generated lock wrapper.
+    target: syn::Type,
+    /// The server type, as named in invocation instrumentation.
+    name: String,
+    /// Whether the server sits behind a lock.
+    locked: bool,
+}
+
+impl Access {
+    /// An expression borrowing the server, exclusively if `exclusive`.
+    fn receiver(&self, exclusive: bool) -> TokenStream {
+        match (self.locked, exclusive) {
+            (false, _) => quote!(self),
+            (true, false) => quote!(self.0.read().await),
+            (true, true) => quote!(self.0.write().await),
+        }
+    }
+}
+
 /// Expand the `#[mcp_server]` attribute macro.
 pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     // Parse attributes
@@ -67,19 +142,13 @@ pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenSt
     // Parse the impl block
     let mut impl_block: ItemImpl = parse2(item)?;
 
-    // Generic impl blocks aren't supported: the generated trait impls reference
-    // the concrete self type and would otherwise produce confusing "undefined
-    // type parameter" errors. Fail with a clear message instead.
-    if !impl_block.generics.params.is_empty() {
-        return Err(Error::new_spanned(
-            &impl_block.generics,
-            "#[mcp_server] does not support generic impl blocks; \
-             implement it on a concrete type",
-        ));
-    }
-
     // Find all tool methods
-    let tool_methods = extract_tool_methods(&mut impl_block)?;
+    let mut tool_methods = extract_tool_methods(&mut impl_block)?;
+    if attrs.coerce_args {
+        for tool in &mut tool_methods {
+            tool.coerce_args = true;
+        }
+    }
 
     // Find all resource methods
     let resource_methods = extract_resource_methods(&mut impl_block)?;
@@ -87,8 +156,29 @@ pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenSt
     // Find all prompt methods
     let prompt_methods = extract_prompt_methods(&mut impl_block)?;
 
-    // Extract the type name
+    // Find all completion methods
+    let completion_methods = extract_completion_methods(&mut impl_block)?;
+
+    // Extract the type name and generics; the generated impls carry the impl
+    // block's generic parameters and where-clause over unchanged.
     let self_ty = &impl_block.self_ty;
+    let generics = &impl_block.generics;
+
+    // A `&mut self` tool (or an explicit `lock`) puts the server behind a
+    // lock, and the handler traits are implemented for the lock wrapper.
+    let lock = lock_strategy(&attrs, &tool_methods)?;
+    let (lock_wrapper, target) = if let Some(strategy) = &lock {
+        let (wrapper, target) = generate_lock_wrapper(self_ty, generics, strategy)?;
+        (wrapper, target)
+    } else {
+        (quote!(), (**self_ty).clone())
+    };
+    let access = Access {
+        target,
+        name: handler_name(self_ty),
+        locked: lock.is_some(),
+    };
+    let target = &access.target;
 
     // Generate ServerHandler impl with correct capabilities
     let has_task_tools = tool_methods
@@ -96,10 +186,12 @@ pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenSt
         .any(|t| t.task_support.as_deref().is_some_and(|s| s != "forbidden"));
     let server_handler_impl = generate_server_handler(
         &attrs,
-        self_ty,
+        target,
+        generics,
         !tool_methods.is_empty(),
         !resource_methods.is_empty(),
         !prompt_methods.is_empty(),
+        !completion_methods.is_empty(),
         has_task_tools,
     );
 
@@ -107,31 +199,56 @@ pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenSt
     let tool_handler_impl = if tool_methods.is_empty() {
         quote!()
     } else {
-        generate_tool_handler(&tool_methods, self_ty)
+        generate_tool_handler(&tool_methods, &access, generics)
     };
 
     // Generate ResourceHandler impl if there are any resources
     let resource_handler_impl = if resource_methods.is_empty() {
         quote!()
     } else {
-        generate_resource_handler(&resource_methods, self_ty)
+        generate_resource_handler(&resource_methods, &access, generics)
     };
 
     // Generate PromptHandler impl if there are any prompts
     let prompt_handler_impl = if prompt_methods.is_empty() {
         quote!()
     } else {
-        generate_prompt_handler(&prompt_methods, self_ty)
+        generate_prompt_handler(&prompt_methods, self_ty, &access, generics)
+    };
+
+    // Generate CompletionHandler impl if there are any completions
+    let completion_handler_impl = if completion_methods.is_empty() {
+        quote!()
+    } else {
+        generate_completion_handler(&completion_methods, &access, generics)
     };
 
     // Generate convenience methods
     let convenience_methods = generate_convenience_methods(
         self_ty,
+        &access,
+        generics,
+        !tool_methods.is_empty(),
+        !resource_methods.is_empty(),
+        !prompt_methods.is_empty(),
+        !completion_methods.is_empty(),
+    );
+
+    // Generate the Mountable impl so the type can be mounted into another server
+    let mountable_impl = generate_mountable(
+        target,
+        generics,
         !tool_methods.is_empty(),
         !resource_methods.is_empty(),
         !prompt_methods.is_empty(),
     );
 
+    // Generate the typed client if requested
+    let typed_client = match &attrs.client {
+        Some(client) => generate_typed_client(&attrs.name, client, &tool_methods)?,
+        None => quote!(),
+    };
+
     // Debug output if requested
     if attrs.debug_expand {
         eprintln!("=== Generated code for {} ===", quote!(#self_ty));
@@ -139,6 +256,8 @@ pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenSt
         eprintln!("{tool_handler_impl}");
         eprintln!("{resource_handler_impl}");
         eprintln!("{prompt_handler_impl}");
+        eprintln!("{completion_handler_impl}");
+        eprintln!("{typed_client}");
         eprintln!("=== End generated code ===");
     }
 
@@ -146,6 +265,8 @@ pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenSt
     Ok(quote! {
         #impl_block
 
+        #lock_wrapper
+
         #server_handler_impl
 
         #tool_handler_impl
@@ -154,7 +275,13 @@ pub fn expand_mcp_server(attr: TokenStream, item: TokenStream) -> Result<TokenSt
 
         #prompt_handler_impl
 
+        #completion_handler_impl
+
         #convenience_methods
+
+        #mountable_impl
+
+        #typed_client
     })
 }
 
@@ -237,9 +364,33 @@ fn extract_tool_info(method: &mut ImplItemFn, attrs: ToolAttrs) -> Result<ToolMe
         }
     }
 
-    let is_async = method.sig.asyncness.is_some();
-    let returns_result = is_result_type(&method.sig.output);
-    let output_type = output_schema_type(&method.sig.output);
+    // The declared access is only reachable through `ctx.fs()` and
+    // `ctx.http()`, so a sandboxed tool must take the context.
+    let sandboxed = !(attrs.allow_fs_read.0.is_empty()
+        && attrs.allow_fs_write.0.is_empty()
+        && attrs.allow_net.0.is_empty());
+    if sandboxed && !params.iter().any(|p| p.is_context) {
+        return Err(Error::new_spanned(
+            &method.sig.ident,
+            "`allow_fs_read`, `allow_fs_write`, and `allow_net` need a `&Context` parameter\n\
+             help: add `ctx: &Context<'_>` and use `ctx.fs()` or `ctx.http()`",
+        ));
+    }
+
+    // A method returning `impl Future<Output = T>` is awaited like an
+    // `async fn` returning `T`; a plain `fn` is called directly.
+    let (is_async, output) = match future_output(&method.sig.output) {
+        Some(ty) => (
+            true,
+            syn::ReturnType::Type(syn::token::RArrow::default(), Box::new(ty)),
+        ),
+        None => (method.sig.asyncness.is_some(), method.sig.output.clone()),
+    };
+    let returns_result = is_result_type(&output);
+    let output_type = output_schema_type(&output);
+    let mut_receiver = method.sig.receiver().is_some_and(
+        |r| matches!(r.ty.as_ref(), syn::Type::Reference(r) if r.mutability.is_some()),
+    );
 
     Ok(ToolMethod {
         name,
@@ -250,8 +401,21 @@ fn extract_tool_info(method: &mut ImplItemFn, attrs: ToolAttrs) -> Result<ToolMe
         destructive: attrs.destructive,
         idempotent: attrs.idempotent,
         read_only: attrs.read_only,
+        requires: attrs
+            .requires
+            .0
+            .into_iter()
+            .chain(attrs.requires_client.0)
+            .collect(),
+        no_record: attrs.no_record,
+        timeout_ms: attrs.timeout_ms,
+        coerce_args: attrs.coerce_args,
+        allow_fs_read: attrs.allow_fs_read.0,
+        allow_fs_write: attrs.allow_fs_write.0,
+        allow_net: attrs.allow_net.0,
         params,
         is_async,
+        mut_receiver,
         returns_result,
         output_type,
     })
@@ -266,9 +430,21 @@ fn extract_resource_methods(impl_block: &mut ItemImpl) -> Result<Vec<ResourceMet
             // Check for #[resource] attribute
             if let Some((idx, resource_attrs)) = find_resource_attr(&method.attrs)? {
                 // Remove the #[resource] attribute so it doesn't cause errors
-                method.attrs.remove(idx);
-
-                let resource = extract_resource_info(method, resource_attrs)?;
+                let span = method.attrs.remove(idx).bracket_token.span.join();
+
+                let resource = extract_resource_info(method, resource_attrs, span)?;
+                if resources
+                    .iter()
+                    .any(|r: &ResourceMethod| r.uri_pattern == resource.uri_pattern)
+                {
+                    return Err(Error::new(
+                        span,
+                        format!(
+                            "duplicate #[resource] uri_pattern \"{}\"",
+                            resource.uri_pattern
+                        ),
+                    ));
+                }
                 resources.push(resource);
             }
         }
@@ -309,10 +485,63 @@ fn find_resource_attr(attrs: &[Attribute]) -> Result<Option<(usize, ResourceAttr
 }
 
 /// Extract resource information from a method.
-#[allow(clippy::unnecessary_wraps)] // Returns Result for future validation extensibility
-fn extract_resource_info(method: &ImplItemFn, attrs: ResourceAttrs) -> Result<ResourceMethod> {
+///
+/// Each parameter named after a `{variable}` of the URI pattern (ignoring a
+/// leading `_`) is passed that variable; any other `&str` parameter is passed
+/// the full URI. `span` locates errors in the URI pattern.
+fn extract_resource_info(
+    method: &ImplItemFn,
+    attrs: ResourceAttrs,
+    span: proc_macro2::Span,
+) -> Result<ResourceMethod> {
     let name = method.sig.ident.clone();
     let resource_name = attrs.name.unwrap_or_else(|| name.to_string());
+    let variables = uri_pattern_variables(&attrs.uri_pattern)
+        .map_err(|e| Error::new(span, format!("invalid uri_pattern: {e}")))?;
+
+    let mut params = Vec::new();
+    for arg in &method.sig.inputs {
+        let FnArg::Typed(pat_type) = arg else {
+            continue;
+        };
+        let syn::Pat::Ident(pat) = pat_type.pat.as_ref() else {
+            return Err(Error::new_spanned(
+                &pat_type.pat,
+                "resource parameters must be plain identifiers",
+            ));
+        };
+        let param_name = pat.ident.to_string();
+        let param_name = param_name.strip_prefix('_').unwrap_or(&param_name);
+        if let Some(index) = variables.iter().position(|v| v == param_name) {
+            params.push(ResourceParam::Variable {
+                index,
+                name: variables[index].clone(),
+                ty: pat_type.ty.clone(),
+            });
+        } else if is_str_ref(&pat_type.ty) {
+            params.push(ResourceParam::Uri);
+        } else {
+            let help = if variables.is_empty() {
+                "help: take the URI as `uri: &str`".to_string()
+            } else {
+                format!(
+                    "help: name it after a variable ({}) or take the URI as `uri: &str`",
+                    variables
+                        .iter()
+                        .map(|v| format!("`{v}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            return Err(Error::new_spanned(
+                pat_type,
+                format!(
+                    "parameter `{}` is not a variable of uri_pattern \"{}\"\n{help}",
+                    pat.ident, attrs.uri_pattern
+                ),
+            ));
+        }
+    }
 
     let is_async = method.sig.asyncness.is_some();
     let returns_result = is_result_type(&method.sig.output);
@@ -323,11 +552,54 @@ fn extract_resource_info(method: &ImplItemFn, attrs: ResourceAttrs) -> Result<Re
         resource_name,
         description: attrs.description.unwrap_or_default(),
         mime_type: attrs.mime_type.unwrap_or_else(|| "text/plain".to_string()),
+        params,
         is_async,
         returns_result,
     })
 }
 
+/// The `{variable}` (and `{+variable}`) names of a URI pattern, in order.
+///
+/// Mirrors the runtime matcher, `mcpkit_core::types::match_uri_template`.
+fn uri_pattern_variables(pattern: &str) -> std::result::Result<Vec<String>, String> {
+    let mut variables: Vec<String> = Vec::new();
+    let mut rest = pattern;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err("unmatched `}`".to_string());
+        }
+        let expression = &rest[open + 1..];
+        let close = expression
+            .find('}')
+            .ok_or_else(|| "unclosed `{`".to_string())?;
+        let name = expression[..close]
+            .strip_prefix('+')
+            .unwrap_or(&expression[..close]);
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(format!(
+                "`{{{}}}` is not a variable; use `{{name}}` or `{{+name}}`",
+                &expression[..close]
+            ));
+        }
+        if variables.iter().any(|v| v == name) {
+            return Err(format!("variable `{name}` appears more than once"));
+        }
+        variables.push(name.to_string());
+        rest = &expression[close + 1..];
+    }
+    Ok(variables)
+}
+
+/// Check if a type is `&str`.
+fn is_str_ref(ty: &syn::Type) -> bool {
+    if let syn::Type::Reference(reference) = ty {
+        if let syn::Type::Path(path) = reference.elem.as_ref() {
+            return path.path.is_ident("str");
+        }
+    }
+    false
+}
+
 /// Extract prompt methods from the impl block.
 fn extract_prompt_methods(impl_block: &mut ItemImpl) -> Result<Vec<PromptMethod>> {
     let mut prompts = Vec::new();
@@ -341,6 +613,14 @@ fn extract_prompt_methods(impl_block: &mut ItemImpl) -> Result<Vec<PromptMethod>
 
                 let prompt = extract_prompt_info(method, prompt_attrs)?;
                 prompts.push(prompt);
+
+                // Parameter doc comments became argument descriptions; they
+                // are not allowed on parameters in the emitted method.
+                for input in &mut method.sig.inputs {
+                    if let FnArg::Typed(param) = input {
+                        param.attrs.retain(|attr| !attr.path().is_ident("doc"));
+                    }
+                }
             }
         }
     }
@@ -380,9 +660,9 @@ fn find_prompt_attr(attrs: &[Attribute]) -> Result<Option<(usize, PromptAttrs)>>
 }
 
 /// Extract prompt information from a method.
-#[allow(clippy::unnecessary_wraps)] // Returns Result for future validation extensibility
 fn extract_prompt_info(method: &ImplItemFn, attrs: PromptAttrs) -> Result<PromptMethod> {
     let name = method.sig.ident.clone();
+    let template = prompt_template(&attrs, method)?;
     let prompt_name = attrs.name.unwrap_or_else(|| name.to_string());
 
     // Extract parameters (skip &self)
@@ -403,9 +683,88 @@ fn extract_prompt_info(method: &ImplItemFn, attrs: PromptAttrs) -> Result<Prompt
         params,
         is_async,
         returns_result,
+        template,
     })
 }
 
+/// Resolve a prompt's `template`, `template_file`, and `partials` attributes.
+///
+/// Files are embedded with `include_str!`, so editing one rebuilds the crate;
+/// a file added to the partials directory is only seen after a rebuild.
+fn prompt_template(
+    attrs: &PromptAttrs,
+    method: &ImplItemFn,
+) -> Result<Option<PromptTemplateSource>> {
+    let source = match (&attrs.template, &attrs.template_file) {
+        (Some(source), _) => quote!(#source),
+        (None, Some(file)) => {
+            let path = crate_path(file);
+            if !path.is_file() {
+                return Err(Error::new_spanned(
+                    &method.sig.ident,
+                    format!("template file not found: {}", path.display()),
+                ));
+            }
+            let path = path.to_string_lossy().into_owned();
+            quote!(::core::include_str!(#path))
+        }
+        (None, None) => return Ok(None),
+    };
+
+    let mut partials = Vec::new();
+    if let Some(dir) = &attrs.partials {
+        let dir = crate_path(dir);
+        let files = partial_files(&dir).map_err(|e| {
+            Error::new_spanned(
+                &method.sig.ident,
+                format!("cannot read partials in {}: {e}", dir.display()),
+            )
+        })?;
+        for (name, path) in files {
+            let path = path.to_string_lossy().into_owned();
+            partials.push((name, quote!(::core::include_str!(#path))));
+        }
+    }
+
+    Ok(Some(PromptTemplateSource {
+        source,
+        partials,
+        variables: attrs.variables.0.clone(),
+    }))
+}
+
+/// A path relative to the root of the crate being compiled.
+fn crate_path(relative: &str) -> std::path::PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_default()
+        .join(relative)
+}
+
+/// The files under `dir`, named by their path relative to it with `/`
+/// separators, in name order.
+fn partial_files(dir: &std::path::Path) -> std::io::Result<Vec<(String, std::path::PathBuf)>> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                let name = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((name, path));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
 /// Extract parameter information from a function argument for prompts.
 fn extract_prompt_param(arg: &FnArg) -> Option<PromptParam> {
     match arg {
@@ -450,6 +809,108 @@ fn extract_prompt_param(arg: &FnArg) -> Option<PromptParam> {
     }
 }
 
+/// Extract completion methods from the impl block.
+fn extract_completion_methods(impl_block: &mut ItemImpl) -> Result<Vec<CompletionMethod>> {
+    let mut completions: Vec<CompletionMethod> = Vec::new();
+
+    for item in &mut impl_block.items {
+        if let ImplItem::Fn(method) = item {
+            // Check for #[completion] attribute
+            if let Some((idx, completion_attrs)) = find_completion_attr(&method.attrs)? {
+                // Remove the #[completion] attribute so it doesn't cause errors
+                method.attrs.remove(idx);
+
+                let completion = extract_completion_info(method, &completion_attrs)?;
+                if completions.iter().any(|c| {
+                    (c.ref_type, &c.ref_value, &c.argument)
+                        == (
+                            completion.ref_type,
+                            &completion.ref_value,
+                            &completion.argument,
+                        )
+                }) {
+                    return Err(Error::new_spanned(
+                        &method.sig.ident,
+                        format!(
+                            "duplicate #[completion] for argument `{}` of `{}`",
+                            completion.argument, completion.ref_value
+                        ),
+                    ));
+                }
+                completions.push(completion);
+            }
+        }
+    }
+
+    Ok(completions)
+}
+
+/// Find the #[completion] attribute and parse it.
+fn find_completion_attr(attrs: &[Attribute]) -> Result<Option<(usize, CompletionAttrs)>> {
+    for (idx, attr) in attrs.iter().enumerate() {
+        if attr.path().is_ident("completion") {
+            let syn::Meta::List(list) = &attr.meta else {
+                return Err(Error::new_spanned(
+                    attr,
+                    "invalid #[completion] syntax\n\
+                     help: use #[completion(prompt = \"...\", argument = \"...\")]",
+                ));
+            };
+
+            let completion_attrs = CompletionAttrs::parse(list.tokens.clone())
+                .map_err(|e| Error::new(attr.bracket_token.span.join(), e))?;
+
+            return Ok(Some((idx, completion_attrs)));
+        }
+    }
+    Ok(None)
+}
+
+/// Extract completion information from a method.
+fn extract_completion_info(
+    method: &ImplItemFn,
+    attrs: &CompletionAttrs,
+) -> Result<CompletionMethod> {
+    crate::completion::validate_completion_method(method)?;
+
+    let (ref_type, ref_value) = attrs.reference();
+    if ref_type == "ref/resource" {
+        // The argument of a resource completion is a variable of its template.
+        let variables = uri_pattern_variables(ref_value).map_err(|e| {
+            Error::new_spanned(&method.sig.ident, format!("invalid resource template: {e}"))
+        })?;
+        if !variables.contains(&attrs.argument) {
+            let help = if variables.is_empty() {
+                format!("\"{ref_value}\" has no `{{variables}}` to complete")
+            } else {
+                format!("complete one of `{}`", variables.join("`, `"))
+            };
+            return Err(Error::new_spanned(
+                &method.sig.ident,
+                format!(
+                    "argument `{}` is not a variable of \"{ref_value}\"\nhelp: {help}",
+                    attrs.argument
+                ),
+            ));
+        }
+    }
+    let takes_value = method
+        .sig
+        .inputs
+        .iter()
+        .any(|arg| matches!(arg, FnArg::Typed(_)));
+
+    Ok(CompletionMethod {
+        name: method.sig.ident.clone(),
+        ref_type,
+        ref_value: ref_value.to_string(),
+        argument: attrs.argument.clone(),
+        takes_value,
+        is_async: method.sig.asyncness.is_some(),
+        returns_result: is_result_type(&method.sig.output),
+    })
+}
+
 /// Check if a type is Option<T>.
 fn is_option_type(ty: &syn::Type) -> bool {
     if let syn::Type::Path(path) = ty {
@@ -479,15 +940,18 @@ fn extract_option_inner_type(ty: &syn::Type) -> syn::Type {
 }
 
 /// Generate the `ServerHandler` implementation.
-#[allow(clippy::fn_params_excessive_bools)] // internal codegen flags
+#[allow(clippy::fn_params_excessive_bools, clippy::too_many_arguments)] // internal codegen flags
 fn generate_server_handler(
     attrs: &ServerAttrs,
     self_ty: &syn::Type,
+    generics: &syn::Generics,
     has_tools: bool,
     has_resources: bool,
     has_prompts: bool,
+    has_completions: bool,
     has_task_tools: bool,
 ) -> TokenStream {
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
     let name = &attrs.name;
     let version = &attrs.version;
     let instructions = attrs
@@ -507,6 +971,9 @@ fn generate_server_handler(
     if has_prompts {
         capability_chain.push(quote!(.with_prompts()));
     }
+    if has_completions {
+        capability_chain.push(quote!(.with_completions()));
+    }
     // A tool declaring `task_support` other than "forbidden" makes the server
     // task-augmentable, so advertise the `tasks` capability including
     // task-augmented `tools/call`.
@@ -526,7 +993,7 @@ fn generate_server_handler(
     };
 
     quote! {
-        impl ::mcpkit::ServerHandler for #self_ty {
+        impl #impl_generics ::mcpkit::ServerHandler for #self_ty #where_clause {
             fn server_info(&self) -> ::mcpkit::capability::ServerInfo {
                 ::mcpkit::capability::ServerInfo::new(#name, #version)
             }
@@ -542,8 +1009,97 @@ fn generate_server_handler(
     }
 }
 
+/// The number of tools from which `call_tool` dispatches through a
+/// `DispatchTable` instead of a `match` on the name.
+const DISPATCH_TABLE_MIN_TOOLS: usize = 16;
+
+/// The handler type's name as written, for `mcp.handler` spans.
+/// The lock a server is put behind, if any: `attrs.lock`, or the default
+/// strategy when a tool takes `&mut self`.
+fn lock_strategy(attrs: &ServerAttrs, tools: &[ToolMethod]) -> Result<Option<String>> {
+    if let Some(lock) = &attrs.lock {
+        if !matches!(lock.as_str(), "rwlock" | "mutex") {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                format!("invalid lock value {lock:?}\nhelp: expected \"rwlock\" or \"mutex\""),
+            ));
+        }
+        return Ok(Some(lock.clone()));
+    }
+    Ok(tools
+        .iter()
+        .any(|t| t.mut_receiver)
+        .then(|| "rwlock".to_string()))
+}
+
+/// Generate the `Locked<Name>` wrapper a locked server is served through,
+/// returning it and the wrapper type.
+fn generate_lock_wrapper(
+    self_ty: &syn::Type,
+    generics: &syn::Generics,
+    strategy: &str,
+) -> Result<(TokenStream, syn::Type)> {
+    let syn::Type::Path(path) = self_ty else {
+        return Err(Error::new_spanned(
+            self_ty,
+            "`&mut self` tools need a named server type",
+        ));
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return Err(Error::new_spanned(self_ty, "expected a type name"));
+    };
+    let wrapper = format_ident!("Locked{}", segment.ident);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let params = &generics.params;
+    let target: syn::Type = syn::parse_quote!(#wrapper #ty_generics);
+    let strategy = if strategy == "mutex" {
+        quote!(::mcpkit::server::LockStrategy::Mutex)
+    } else {
+        quote!(::mcpkit::server::LockStrategy::RwLock)
+    };
+    let doc = format!(
+        "[`{}`] behind a lock, generated by `#[mcp_server]` for its `&mut self` tools.\n\n\
+         The MCP handler traits are implemented for this type; build one with `From`, \
+         or let `into_server()` do it, and reach the server through `server()`.",
+        segment.ident
+    );
+
+    let wrapper_def = quote! {
+        #[doc = #doc]
+        pub struct #wrapper<#params>(::mcpkit::server::Locked<#self_ty>) #where_clause;
+
+        impl #impl_generics ::core::convert::From<#self_ty> for #target #where_clause {
+            fn from(server: #self_ty) -> Self {
+                Self(::mcpkit::server::Locked::new(server, #strategy))
+            }
+        }
+
+        // The wrapper is `pub` whatever the server's visibility, so it may
+        // expose a less visible server type.
+        #[allow(dead_code, private_interfaces)]
+        impl #impl_generics #target #where_clause {
+            /// The locked server.
+            pub const fn server(&self) -> &::mcpkit::server::Locked<#self_ty> {
+                &self.0
+            }
+        }
+    };
+    Ok((wrapper_def, target))
+}
+
+fn handler_name(self_ty: &syn::Type) -> String {
+    quote!(#self_ty).to_string().replace(' ', "")
+}
+
 /// Generate the `ToolHandler` implementation.
-fn generate_tool_handler(tools: &[ToolMethod], self_ty: &syn::Type) -> TokenStream {
+fn generate_tool_handler(
+    tools: &[ToolMethod],
+    access: &Access,
+    generics: &syn::Generics,
+) -> TokenStream {
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let self_ty = &access.target;
+    let handler_name = &access.name;
     // Generate tool definitions
     let tool_defs: Vec<_> = tools
         .iter()
@@ -559,7 +1115,11 @@ fn generate_tool_handler(tools: &[ToolMethod], self_ty: &syn::Type) -> TokenStre
 
             // Derive the output schema from a `Json<T>` return type, if present.
             let output_schema = if let Some(ty) = &tool.output_type {
-                quote!(Some(<#ty>::tool_input_schema()))
+                quote!(Some({
+                    #[allow(unused_imports)]
+                    use ::mcpkit::schema::ToolInputSchema as _;
+                    <#ty>::tool_input_schema()
+                }))
             } else {
                 quote!(None)
             };
@@ -570,6 +1130,32 @@ fn generate_tool_handler(tools: &[ToolMethod], self_ty: &syn::Type) -> TokenStre
                 quote!(None)
             };
 
+            let mut meta_entries = Vec::new();
+            if tool.no_record {
+                meta_entries.push(quote!(.with(
+                    ::mcpkit::types::NO_RECORD_META_KEY,
+                    ::serde_json::Value::Bool(true)
+                )));
+            }
+            if let Some(timeout_ms) = tool.timeout_ms {
+                meta_entries.push(quote!(.with(
+                    ::mcpkit::types::TIMEOUT_META_KEY,
+                    ::serde_json::Value::from(#timeout_ms)
+                )));
+            }
+            if tool.is_sandboxed() {
+                let policy = tool.sandbox_policy();
+                meta_entries.push(quote!(.with(
+                    ::mcpkit::types::SANDBOX_META_KEY,
+                    #policy.to_json()
+                )));
+            }
+            let meta = if meta_entries.is_empty() {
+                quote!(None)
+            } else {
+                quote!(Some(::mcpkit::types::Meta::new() #(#meta_entries)*))
+            };
+
             // `task_support` is validated in `extract_tool_info`, so the
             // fallback arm is unreachable.
             let execution = match tool.task_support.as_deref() {
@@ -607,32 +1193,100 @@ fn generate_tool_handler(tools: &[ToolMethod], self_ty: &syn::Type) -> TokenStre
                     }),
                     execution: #execution,
                     output_schema: #output_schema,
-                    meta: None,
+                    meta: #meta,
                 }
             }
         })
         .collect();
 
-    // Generate dispatch arms
+    // Get the list of tool names for error message
+    let tool_names: Vec<_> = tools.iter().map(|t| t.tool_name.as_str()).collect();
+
+    // Small servers match the name directly. Larger ones look it up once in a
+    // table and match on its position, so a call costs one hash rather than a
+    // comparison per tool.
+    let use_table = tools.len() >= DISPATCH_TABLE_MIN_TOOLS;
     let dispatch_arms: Vec<_> = tools
         .iter()
-        .map(super::codegen::ToolMethod::generate_call_dispatch)
+        .enumerate()
+        .map(|(i, tool)| {
+            let pattern = if use_table {
+                quote!(Some(#i))
+            } else {
+                let name = &tool.tool_name;
+                quote!(#name)
+            };
+            tool.generate_call_dispatch(&pattern, &access.receiver(tool.mut_receiver))
+        })
         .collect();
+    let dispatch = if use_table {
+        quote! {
+            static __MCPKIT_TOOLS: ::mcpkit::server::router::DispatchTable =
+                ::mcpkit::server::router::DispatchTable::new(&[#(#tool_names),*]);
+            match __MCPKIT_TOOLS.get(name)
+        }
+    } else {
+        quote!(match name)
+    };
 
-    // Get the list of tool names for error message
-    let tool_names: Vec<_> = tools.iter().map(|t| t.tool_name.as_str()).collect();
-    let _available_tools = tool_names.join(", ");
+    // Tools with `requires = [..]` are listed only for clients that declared
+    // every required capability, and calls from other clients fail up front
+    // with a typed error rather than mid-execution.
+    let any_requires = tools.iter().any(|t| !t.requires.is_empty());
+    let ctx = if any_requires || tools.iter().any(super::codegen::ToolMethod::takes_context) {
+        quote!(ctx)
+    } else {
+        quote!(_ctx)
+    };
+    let list_body = if any_requires {
+        let pushes = tools.iter().zip(&tool_defs).map(|(tool, def)| {
+            if tool.requires.is_empty() {
+                return quote!(tools.push(#def););
+            }
+            let checks = tool.requires.iter().map(|cap| client_capability_check(cap));
+            quote! {
+                if #(#checks)&&* {
+                    tools.push(#def);
+                }
+            }
+        });
+        quote! {
+            let mut tools = Vec::new();
+            #(#pushes)*
+            Ok(tools)
+        }
+    } else {
+        quote!(Ok(vec![#(#tool_defs),*]))
+    };
+    let requirement_checks = tools.iter().filter(|t| !t.requires.is_empty()).map(|tool| {
+        let tool_name = &tool.tool_name;
+        let checks = tool.requires.iter().map(|cap| {
+            let check = client_capability_check(cap);
+            let variant = client_capability(cap);
+            quote! {
+                if !(#check) {
+                    return Err(::mcpkit::error::McpError::client_capability_required(
+                        #tool_name,
+                        #variant.name(),
+                    ));
+                }
+            }
+        });
+        quote! {
+            if name == #tool_name {
+                #(#checks)*
+            }
+        }
+    });
 
     quote! {
-        impl ::mcpkit::ToolHandler for #self_ty {
+        impl #impl_generics ::mcpkit::ToolHandler for #self_ty #where_clause {
             fn list_tools(
                 &self,
-                _ctx: &::mcpkit::Context,
+                #ctx: &::mcpkit::Context,
             ) -> impl std::future::Future<Output = Result<Vec<::mcpkit::types::Tool>, ::mcpkit::error::McpError>> + Send {
                 async move {
-                    Ok(vec![
-                        #(#tool_defs),*
-                    ])
+                    #list_body
                 }
             }
 
@@ -640,24 +1294,54 @@ fn generate_tool_handler(tools: &[ToolMethod], self_ty: &syn::Type) -> TokenStre
                 &self,
                 name: &str,
                 args: ::serde_json::Map<String, ::serde_json::Value>,
-                _ctx: &::mcpkit::Context,
+                #ctx: &::mcpkit::Context,
             ) -> impl std::future::Future<Output = Result<::mcpkit::types::ToolOutput, ::mcpkit::error::McpError>> + Send {
-                async move {
-                    match name {
-                        #(#dispatch_arms)*
-                        _ => Err(::mcpkit::error::McpError::method_not_found_with_suggestions(
-                            name,
-                            vec![#(#tool_names.to_string()),*],
-                        ))
-                    }
-                }
+                ::mcpkit::server::invocation::instrument(
+                    ::mcpkit::server::invocation::InvocationKind::Tool,
+                    #handler_name,
+                    name,
+                    async move {
+                        #(#requirement_checks)*
+                        #dispatch {
+                            #(#dispatch_arms)*
+                            _ => Err(::mcpkit::error::McpError::method_not_found_with_suggestions(
+                                name,
+                                vec![#(#tool_names.to_string()),*],
+                            ))
+                        }
+                    },
+                )
             }
         }
     }
 }
 
+/// An expression testing whether the client (`ctx`) declared `capability`.
+fn client_capability_check(capability: &str) -> TokenStream {
+    let variant = client_capability(capability);
+    quote!(ctx.client_caps.supports(#variant))
+}
+
+/// The `ClientCapability` for a capability named in `requires`.
+///
+/// `capability` is validated against `CLIENT_CAPABILITIES` at parse time.
+fn client_capability(capability: &str) -> TokenStream {
+    let variant = crate::attrs::client_capability_variant(capability)
+        .expect("capability validated while parsing `requires`");
+    let variant = format_ident!("{}", variant);
+    quote!(::mcpkit::capability::ClientCapability::#variant)
+}
+
 /// Generate the `ResourceHandler` implementation.
-fn generate_resource_handler(resources: &[ResourceMethod], self_ty: &syn::Type) -> TokenStream {
+fn generate_resource_handler(
+    resources: &[ResourceMethod],
+    access: &Access,
+    generics: &syn::Generics,
+) -> TokenStream {
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let self_ty = &access.target;
+    let handler_name = &access.name;
+    let receiver = access.receiver(false);
     // Generate static resource definitions (non-template URIs)
     let resource_defs: Vec<_> = resources
         .iter()
@@ -717,68 +1401,73 @@ fn generate_resource_handler(resources: &[ResourceMethod], self_ty: &syn::Type)
         })
         .collect();
 
-    // Generate dispatch code for read_resource
-    let dispatch_arms: Vec<_> = resources
+    // Generate dispatch code for read_resource. Exact URIs are tried before
+    // templates, so a static resource wins over a template that also matches.
+    let (static_resources, template_resources): (Vec<_>, Vec<_>) = resources
         .iter()
+        .partition(|resource| !resource.uri_pattern.contains('{'));
+    let dispatch_arms: Vec<_> = static_resources
+        .into_iter()
+        .chain(template_resources)
         .map(|resource| {
             let method_name = &resource.name;
             let uri_pattern = &resource.uri_pattern;
 
+            let args = resource.params.iter().map(|param| match param {
+                ResourceParam::Uri => quote!(uri),
+                ResourceParam::Variable { index, ty, .. } if is_str_ref(ty) => {
+                    quote!(captures[#index].1)
+                }
+                ResourceParam::Variable { index, name, ty } => quote! {
+                    captures[#index].1.parse::<#ty>().map_err(|e| {
+                        ::mcpkit::error::McpError::invalid_params(
+                            "resources/read",
+                            format!("invalid `{}` in {}: {}", #name, uri, e),
+                        )
+                    })?
+                },
+            });
             let call = if resource.is_async {
-                quote!(self.#method_name(uri).await)
+                quote!(#receiver.#method_name(#(#args),*).await)
             } else {
-                quote!(self.#method_name(uri))
+                quote!(#receiver.#method_name(#(#args),*))
+            };
+            // Method returns Result: use ? to propagate errors
+            let result = if resource.returns_result {
+                quote!(#call?)
+            } else {
+                quote!(#call)
             };
 
-            // Check if this is a template pattern
             if uri_pattern.contains('{') {
-                // Template matching - match by prefix
-                let pattern_prefix = uri_pattern.split('{').next().unwrap_or("");
-                if resource.returns_result {
-                    // Method returns Result, use ? to propagate errors
-                    quote! {
-                        if uri.starts_with(#pattern_prefix) {
-                            let result = #call?;
-                            return Ok(vec![result]);
-                        }
-                    }
+                let uses_captures = resource
+                    .params
+                    .iter()
+                    .any(|param| matches!(param, ResourceParam::Variable { .. }));
+                let binding = if uses_captures {
+                    quote!(captures)
                 } else {
-                    // Method returns value directly, no need for ?
-                    quote! {
-                        if uri.starts_with(#pattern_prefix) {
-                            let result = #call;
-                            return Ok(vec![result]);
-                        }
+                    quote!(_)
+                };
+                quote! {
+                    if let Some(#binding) = ::mcpkit::types::match_uri_template(#uri_pattern, uri) {
+                        let result = #result;
+                        return Ok(vec![result]);
                     }
                 }
             } else {
-                // Exact URI match
-                if resource.returns_result {
-                    // Method returns Result, use ? to propagate errors
-                    quote! {
-                        if uri == #uri_pattern {
-                            let result = #call?;
-                            return Ok(vec![result]);
-                        }
-                    }
-                } else {
-                    // Method returns value directly, no need for ?
-                    quote! {
-                        if uri == #uri_pattern {
-                            let result = #call;
-                            return Ok(vec![result]);
-                        }
+                quote! {
+                    if uri == #uri_pattern {
+                        let result = #result;
+                        return Ok(vec![result]);
                     }
                 }
             }
         })
         .collect();
 
-    // URI patterns are available for future error message enhancement
-    let _uri_patterns: Vec<_> = resources.iter().map(|r| r.uri_pattern.as_str()).collect();
-
     quote! {
-        impl ::mcpkit::ResourceHandler for #self_ty {
+        impl #impl_generics ::mcpkit::ResourceHandler for #self_ty #where_clause {
             fn list_resources(
                 &self,
                 _ctx: &::mcpkit::Context,
@@ -808,19 +1497,33 @@ fn generate_resource_handler(resources: &[ResourceMethod], self_ty: &syn::Type)
             ) -> impl std::future::Future<Output = Result<Vec<::mcpkit::types::ResourceContents>, ::mcpkit::error::McpError>> + Send {
                 let uri_owned = uri.to_string();
 
-                async move {
-                    let uri: &str = &uri_owned;
-                    #(#dispatch_arms)*
+                ::mcpkit::server::invocation::instrument(
+                    ::mcpkit::server::invocation::InvocationKind::Resource,
+                    #handler_name,
+                    uri,
+                    async move {
+                        let uri: &str = &uri_owned;
+                        #(#dispatch_arms)*
 
-                    Err(::mcpkit::error::McpError::resource_not_found(uri))
-                }
+                        Err(::mcpkit::error::McpError::resource_not_found(uri))
+                    },
+                )
             }
         }
     }
 }
 
 /// Generate the `PromptHandler` implementation.
-fn generate_prompt_handler(prompts: &[PromptMethod], self_ty: &syn::Type) -> TokenStream {
+fn generate_prompt_handler(
+    prompts: &[PromptMethod],
+    self_ty: &syn::Type,
+    access: &Access,
+    generics: &syn::Generics,
+) -> TokenStream {
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let target = &access.target;
+    let handler_name = &access.name;
+    let receiver = access.receiver(false);
     // Generate prompt definitions
     let prompt_defs: Vec<_> = prompts
         .iter()
@@ -848,7 +1551,14 @@ fn generate_prompt_handler(prompts: &[PromptMethod], self_ty: &syn::Type) -> Tok
                 })
                 .collect();
 
-            let arguments_expr = if arguments.is_empty() {
+            let arguments_expr = if prompt.template.is_some() {
+                // The template adds the arguments its placeholders use.
+                let template_fn = prompt_template_fn(prompt);
+                quote!({
+                    let arguments = <#self_ty>::#template_fn()?.arguments();
+                    if arguments.is_empty() { None } else { Some(arguments) }
+                })
+            } else if arguments.is_empty() {
                 quote!(None)
             } else {
                 quote!(Some(vec![#(#arguments),*]))
@@ -926,12 +1636,23 @@ fn generate_prompt_handler(prompts: &[PromptMethod], self_ty: &syn::Type) -> Tok
             let param_names: Vec<_> = prompt.params.iter().map(|p| &p.name).collect();
 
             let call = if prompt.is_async {
-                quote!(self.#method_name(#(#param_names),*).await)
+                quote!(#receiver.#method_name(#(#param_names),*).await)
             } else {
-                quote!(self.#method_name(#(#param_names),*))
+                quote!(#receiver.#method_name(#(#param_names),*))
             };
 
-            let call_with_conversion = if prompt.returns_result {
+            let call_with_conversion = if prompt.template.is_some() {
+                // The method returns extra variables for the template.
+                let template_fn = prompt_template_fn(prompt);
+                let extra = if prompt.returns_result {
+                    quote!(#call?)
+                } else {
+                    quote!(#call)
+                };
+                quote! {
+                    <#self_ty>::#template_fn()?.render_prompt_with(#prompt_name, arguments.as_ref(), #extra)
+                }
+            } else if prompt.returns_result {
                 quote!(#call)
             } else {
                 quote!(Ok(#call))
@@ -949,8 +1670,12 @@ fn generate_prompt_handler(prompts: &[PromptMethod], self_ty: &syn::Type) -> Tok
     // Get the list of prompt names for error message
     let prompt_names: Vec<_> = prompts.iter().map(|p| p.prompt_name.as_str()).collect();
 
+    let template_fns = generate_prompt_template_fns(prompts, self_ty, generics);
+
     quote! {
-        impl ::mcpkit::PromptHandler for #self_ty {
+        #template_fns
+
+        impl #impl_generics ::mcpkit::PromptHandler for #target #where_clause {
             fn list_prompts(
                 &self,
                 _ctx: &::mcpkit::Context,
@@ -968,17 +1693,340 @@ fn generate_prompt_handler(prompts: &[PromptMethod], self_ty: &syn::Type) -> Tok
                 arguments: Option<::serde_json::Map<String, ::serde_json::Value>>,
                 _ctx: &::mcpkit::Context,
             ) -> impl std::future::Future<Output = Result<::mcpkit::types::GetPromptResult, ::mcpkit::error::McpError>> + Send {
-                let name = name.to_string();
+                let name_owned = name.to_string();
+
+                ::mcpkit::server::invocation::instrument(
+                    ::mcpkit::server::invocation::InvocationKind::Prompt,
+                    #handler_name,
+                    name,
+                    async move {
+                        let name = name_owned;
+                        match name.as_str() {
+                            #(#dispatch_arms)*
+                            _ => Err(::mcpkit::error::McpError::method_not_found_with_suggestions(
+                                &name,
+                                vec![#(#prompt_names.to_string()),*],
+                            ))
+                        }
+                    },
+                )
+            }
+        }
+    }
+}
+
+/// Name of the hidden function returning a template prompt's template.
+fn prompt_template_fn(prompt: &PromptMethod) -> syn::Ident {
+    format_ident!("__mcp_prompt_template_{}", prompt.name)
+}
+
+/// Generate a hidden function per template prompt that builds its
+/// `PromptTemplate` once.
+///
+/// Method parameters are declared as template arguments, so their doc
+/// comments and optionality override what the placeholders imply.
+fn generate_prompt_template_fns(
+    prompts: &[PromptMethod],
+    self_ty: &syn::Type,
+    generics: &syn::Generics,
+) -> TokenStream {
+    let fns: Vec<_> = prompts
+        .iter()
+        .filter_map(|prompt| {
+            let template = prompt.template.as_ref()?;
+            let fn_name = prompt_template_fn(prompt);
+            let prompt_name = &prompt.prompt_name;
+            let source = &template.source;
+            let partial_names = template.partials.iter().map(|(name, _)| name);
+            let partial_sources = template.partials.iter().map(|(_, source)| source);
+            let variables = &template.variables;
+            let arguments = prompt.params.iter().map(|param| {
+                let param_name = param.name.to_string();
+                let param_desc = param.doc.as_deref().unwrap_or("");
+                let required = !param.is_optional;
+                quote! {
+                    ::mcpkit::types::PromptArgument {
+                        name: #param_name.to_string(),
+                        title: None,
+                        description: if #param_desc.is_empty() { None } else { Some(#param_desc.to_string()) },
+                        required: Some(#required),
+                    }
+                }
+            });
+
+            Some(quote! {
+                #[doc(hidden)]
+                fn #fn_name() -> Result<&'static ::mcpkit::server::template::PromptTemplate, ::mcpkit::error::McpError> {
+                    static TEMPLATE: ::std::sync::LazyLock<Result<::mcpkit::server::template::PromptTemplate, String>> =
+                        ::std::sync::LazyLock::new(|| {
+                            ::mcpkit::server::template::PromptTemplate::named(#prompt_name, #source)
+                                #(.and_then(|t| t.with_partial(#partial_names, #partial_sources)))*
+                                .map(|t| t #(.with_argument(#arguments))* #(.with_variable(#variables))*)
+                                .map_err(|e| e.to_string())
+                        });
+                    TEMPLATE
+                        .as_ref()
+                        .map_err(|e| ::mcpkit::error::McpError::internal(e.clone()))
+                }
+            })
+        })
+        .collect();
+
+    if fns.is_empty() {
+        return quote!();
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #self_ty #where_clause {
+            #(#fns)*
+        }
+    }
+}
 
+/// Generate the `CompletionHandler` implementation.
+///
+/// Requests are matched on the reference and argument name; anything no
+/// `#[completion]` method claims gets an empty completion.
+fn generate_completion_handler(
+    completions: &[CompletionMethod],
+    access: &Access,
+    generics: &syn::Generics,
+) -> TokenStream {
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let self_ty = &access.target;
+    let receiver = access.receiver(false);
+    let dispatch_arms: Vec<_> = completions
+        .iter()
+        .map(|completion| {
+            let method_name = &completion.name;
+            let ref_type = completion.ref_type;
+            let ref_value = &completion.ref_value;
+            let argument = &completion.argument;
+
+            // `.into()` accepts `String`, `&str`, and `Cow<str>` parameters alike.
+            let args = if completion.takes_value {
+                quote!(request.argument.value.as_str().into())
+            } else {
+                quote!()
+            };
+            let call = if completion.is_async {
+                quote!(#receiver.#method_name(#args).await)
+            } else {
+                quote!(#receiver.#method_name(#args))
+            };
+            let call = if completion.returns_result {
+                quote!(#call?)
+            } else {
+                call
+            };
+
+            quote! {
+                (#ref_type, #ref_value, #argument) => ::mcpkit::types::Completion::from(#call),
+            }
+        })
+        .collect();
+
+    quote! {
+        impl #impl_generics ::mcpkit::CompletionHandler for #self_ty #where_clause {
+            fn complete(
+                &self,
+                request: &::mcpkit::types::CompleteRequest,
+                _ctx: &::mcpkit::Context,
+            ) -> impl std::future::Future<Output = Result<::mcpkit::types::CompleteResult, ::mcpkit::error::McpError>> + Send {
                 async move {
-                    match name.as_str() {
+                    let completion = match (
+                        request.ref_.ref_type(),
+                        request.ref_.value(),
+                        request.argument.name.as_str(),
+                    ) {
                         #(#dispatch_arms)*
-                        _ => Err(::mcpkit::error::McpError::method_not_found_with_suggestions(
-                            &name,
-                            vec![#(#prompt_names.to_string()),*],
-                        ))
+                        _ => ::mcpkit::types::Completion::new(Vec::new()),
+                    };
+                    Ok(completion.into())
+                }
+            }
+        }
+    }
+}
+
+/// Generate a typed client wrapper with one method per tool.
+///
+/// Each method takes the tool's parameters (minus any `&Context`), calls the
+/// tool through a wrapped `Client`, and converts the result: `Json<T>` tools
+/// return `T`, every other tool returns its text.
+fn generate_typed_client(
+    server_name: &str,
+    client_name: &str,
+    tools: &[ToolMethod],
+) -> Result<TokenStream> {
+    let client_ty: syn::Ident = syn::parse_str(client_name).map_err(|_| {
+        Error::new(
+            proc_macro2::Span::call_site(),
+            format!("invalid client name {client_name:?}: expected an identifier"),
+        )
+    })?;
+
+    let mut methods = Vec::new();
+    for tool in tools {
+        let method_name = &tool.name;
+        if ["new", "client", "into_inner"].contains(&method_name.to_string().as_str()) {
+            return Err(Error::new_spanned(
+                method_name,
+                format!(
+                    "tool method `{method_name}` clashes with a method of the generated client\n\
+                     help: rename the method and keep the tool name with #[tool(name = \"{method_name}\")]"
+                ),
+            ));
+        }
+        let tool_name = &tool.tool_name;
+        let params: Vec<_> = tool.params.iter().filter(|p| !p.is_context).collect();
+        let signature = params.iter().map(|p| {
+            let name = &p.name;
+            let ty = &p.ty;
+            quote!(#name: #ty)
+        });
+        let inserts = params.iter().map(|p| {
+            let name = &p.name;
+            let key = name.to_string();
+            let value = quote! {
+                ::mcpkit::client::typed::argument(#tool_name, #key, &#name)?
+            };
+            if p.is_optional {
+                quote! {
+                    if let Some(#name) = &#name {
+                        __args.insert(#key.to_string(), #value);
                     }
                 }
+            } else {
+                quote!(__args.insert(#key.to_string(), #value);)
+            }
+        });
+        let (output, convert) = match &tool.output_type {
+            Some(ty) => (
+                quote!(#ty),
+                quote!(::mcpkit::client::typed::structured_output::<#ty>(#tool_name, __result)),
+            ),
+            None => (
+                quote!(::std::string::String),
+                quote!(::mcpkit::client::typed::text_output(#tool_name, __result)),
+            ),
+        };
+        let doc = format!("Call the `{tool_name}` tool: {}", tool.description);
+
+        methods.push(quote! {
+            #[doc = #doc]
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the call fails, the tool reports an error,
+            /// or its output cannot be converted.
+            pub async fn #method_name(
+                &self,
+                #(#signature),*
+            ) -> ::std::result::Result<#output, ::mcpkit::error::McpError> {
+                #[allow(unused_mut)]
+                let mut __args = ::serde_json::Map::new();
+                #(#inserts)*
+                let __result = self
+                    .client
+                    .call_tool(#tool_name, ::serde_json::Value::Object(__args))
+                    .await?;
+                #convert
+            }
+        });
+    }
+
+    let doc = format!("Typed client for the `{server_name}` MCP server.");
+    Ok(quote! {
+        #[doc = #doc]
+        ///
+        /// Each method calls the tool of the same name and converts its
+        /// result. Use [`client`](Self::client) for anything else the
+        /// server offers.
+        pub struct #client_ty<
+            T: ::mcpkit::transport::Transport + 'static,
+            H: ::mcpkit::client::ClientHandler + 'static = ::mcpkit::client::handler::NoOpHandler,
+        > {
+            client: ::mcpkit::client::Client<T, H>,
+        }
+
+        impl<T, H> #client_ty<T, H>
+        where
+            T: ::mcpkit::transport::Transport + 'static,
+            H: ::mcpkit::client::ClientHandler + 'static,
+        {
+            /// Wrap a connected client.
+            #[must_use]
+            pub const fn new(client: ::mcpkit::client::Client<T, H>) -> Self {
+                Self { client }
+            }
+
+            /// The wrapped client.
+            #[must_use]
+            pub const fn client(&self) -> &::mcpkit::client::Client<T, H> {
+                &self.client
+            }
+
+            /// Unwrap the client.
+            #[must_use]
+            pub fn into_inner(self) -> ::mcpkit::client::Client<T, H> {
+                self.client
+            }
+
+            #(#methods)*
+        }
+    })
+}
+
+/// Generate the `Mountable` implementation.
+///
+/// Registers each handler the type implements, sharing one `Arc<Self>`, so the
+/// type can be mounted into another server with `ServerBuilder::mount`.
+/// Nothing is generated for a type without tools, resources, or prompts.
+fn generate_mountable(
+    self_ty: &syn::Type,
+    generics: &syn::Generics,
+    has_tools: bool,
+    has_resources: bool,
+    has_prompts: bool,
+) -> TokenStream {
+    if !(has_tools || has_resources || has_prompts) {
+        return quote!();
+    }
+
+    // `Mountable` requires `'static`; bounding `Self` keeps generic impls valid.
+    let mut generics = generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(Self: 'static));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let mut registrations = Vec::new();
+    if has_tools {
+        registrations.push(quote!(mounts.add_tools(prefix, ::std::sync::Arc::clone(&self));));
+    }
+    if has_resources {
+        registrations.push(quote!(mounts.add_resources(::std::sync::Arc::clone(&self));));
+    }
+    if has_prompts {
+        registrations.push(quote!(mounts.add_prompts(prefix, ::std::sync::Arc::clone(&self));));
+    }
+    // Only tools and prompts are prefixed.
+    let prefix = if has_tools || has_prompts {
+        quote!(prefix)
+    } else {
+        quote!(_prefix)
+    };
+
+    quote! {
+        impl #impl_generics ::mcpkit::server::compose::Mountable for #self_ty #where_clause {
+            fn mount(
+                self: ::std::sync::Arc<Self>,
+                #prefix: &str,
+                mounts: &mut ::mcpkit::server::compose::Mounts,
+            ) {
+                #(#registrations)*
             }
         }
     }
@@ -987,7 +2035,8 @@ fn generate_prompt_handler(prompts: &[PromptMethod], self_ty: &syn::Type) -> Tok
 /// Generate convenience methods.
 ///
 /// Generates an `into_server()` method that automatically wires up all handlers
-/// defined via `#[tool]`, `#[resource]`, and `#[prompt]` attributes.
+/// defined via `#[tool]`, `#[resource]`, `#[prompt]`, and `#[completion]`
+/// attributes.
 ///
 /// Uses `Arc` internally to share the handler across registrations, eliminating
 /// the need for users to implement `Clone` on their handler types.
@@ -995,14 +2044,29 @@ fn generate_prompt_handler(prompts: &[PromptMethod], self_ty: &syn::Type) -> Tok
 /// Note: We intentionally do NOT generate runtime-specific methods like `serve_stdio()`
 /// because the SDK is runtime-agnostic. Users should create their own transport
 /// and call `server.serve(transport)` directly.
+#[allow(clippy::fn_params_excessive_bools)] // internal codegen flags
 fn generate_convenience_methods(
     self_ty: &syn::Type,
+    access: &Access,
+    generics: &syn::Generics,
     has_tools: bool,
     has_resources: bool,
     has_prompts: bool,
+    has_completions: bool,
 ) -> TokenStream {
-    // Type alias for Arc<Self>
-    let arc_self = quote!(::std::sync::Arc<Self>);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    // Type alias for Arc<Self>, or for the lock wrapper a locked server is
+    // shared in.
+    let (arc_self, wrap) = if access.locked {
+        let target = &access.target;
+        (
+            quote!(::std::sync::Arc<#target>),
+            quote!(<#target>::from(self)),
+        )
+    } else {
+        (quote!(::std::sync::Arc<Self>), quote!(self))
+    };
 
     // Determine the Server type parameters based on which handlers exist
     // Note: We use Arc<Self> as the handler type since we wrap in Arc internally
@@ -1029,10 +2093,17 @@ fn generate_convenience_methods(
         .filter(|&&x| x)
         .count();
 
-    let builder_body = if handler_count == 0 {
+    // Completion is not a typestate slot; it is attached after `build()`.
+    let completion = if has_completions {
+        quote!(.with_completion(::std::sync::Arc::clone(&handler)))
+    } else {
+        quote!()
+    };
+
+    let builder_body = if handler_count == 0 && !has_completions {
         // No handlers - wrap in Arc for consistency
         quote! {
-            let handler = ::std::sync::Arc::new(self);
+            let handler = ::std::sync::Arc::new(#wrap);
             ::mcpkit::ServerBuilder::new(handler).build()
         }
     } else {
@@ -1053,17 +2124,17 @@ fn generate_convenience_methods(
         }
 
         quote! {
-            let handler = ::std::sync::Arc::new(self);
-            #method_chain.build()
+            let handler = ::std::sync::Arc::new(#wrap);
+            #method_chain.build()#completion
         }
     };
 
     quote! {
-        impl #self_ty {
+        impl #impl_generics #self_ty #where_clause {
             /// Convert this handler into a fully-configured MCP server.
             ///
             /// This method automatically registers all handlers defined on this type
-            /// via `#[tool]`, `#[resource]`, and `#[prompt]` attributes.
+            /// via `#[tool]`, `#[resource]`, `#[prompt]`, and `#[completion]` attributes.
             ///
             /// The handler is wrapped in `Arc` internally, so there's no need to
             /// implement `Clone` on your handler type.
@@ -1098,4 +2169,17 @@ mod tests {
         assert_eq!(attrs.version, "1.0.0");
         Ok(())
     }
+
+    #[test]
+    fn test_uri_pattern_variables() {
+        assert_eq!(
+            uri_pattern_variables("db://{schema}/{+path}"),
+            Ok(vec!["schema".to_string(), "path".to_string()])
+        );
+        assert_eq!(uri_pattern_variables("config://app"), Ok(vec![]));
+        assert!(uri_pattern_variables("db://{table").is_err());
+        assert!(uri_pattern_variables("db://table}").is_err());
+        assert!(uri_pattern_variables("db://{?query}").is_err());
+        assert!(uri_pattern_variables("db://{id}/{id}").is_err());
+    }
 }
@@ -0,0 +1,49 @@
+//! MCP proxy/gateway for the MCP SDK.
+//!
+//! This crate puts one MCP endpoint in front of one or more upstream servers.
+//! Inbound connections are served with `mcpkit-server`; requests are forwarded
+//! upstream through a [`ClientMux`](mcpkit_client::ClientMux) from
+//! `mcpkit-client`. Hooks decide which tools are exposed and under what
+//! names, can rewrite or reject each forwarded call (e.g. to inject
+//! credentials or enforce access control), and a
+//! [`SessionRecorder`](mcpkit_core::debug::SessionRecorder) can record the
+//! upstream traffic.
+//!
+//! A typical use is a gateway in front of untrusted servers: only an
+//! allow-listed set of their tools is visible to the model, and every call
+//! that reaches them is audited.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use mcpkit_client::{ClientBuilder, ClientMux};
+//! use mcpkit_proxy::McpProxy;
+//! use mcpkit_transport::SpawnedTransport;
+//! use mcpkit_transport::stdio::StdioTransport;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), mcpkit_core::error::McpError> {
+//!     let mut mux = ClientMux::new();
+//!     let github = SpawnedTransport::spawn("github-mcp", &[] as &[&str]).await?;
+//!     mux.connect("github", ClientBuilder::new(), github).await?;
+//!
+//!     let proxy = McpProxy::builder(mux)
+//!         .name("gateway")
+//!         // Expose only read-only tools.
+//!         .filter_tools(|_origin, tool| tool.is_read_only())
+//!         .build();
+//!
+//!     proxy.into_server().serve(StdioTransport::new()).await
+//! }
+//! ```
+
+#![deny(missing_docs)]
+
+pub mod proxy;
+
+pub use proxy::{McpProxy, ORIGIN_META_KEY, ProxyBuilder, ProxyCall, ProxyServer};
+
+/// Prelude module for convenient imports.
+pub mod prelude {
+    pub use crate::proxy::{McpProxy, ProxyBuilder, ProxyCall, ProxyServer};
+}
@@ -0,0 +1,472 @@
+//! The proxy server and its builder.
+//!
+//! [`McpProxy`] implements the server handler traits by forwarding to a
+//! [`ClientMux`]. Build one with [`McpProxy::builder`], then serve it with
+//! [`McpProxy::into_server`] (or register it on a `ServerBuilder` yourself to
+//! add middleware or other capabilities).
+//!
+//! Tools pass through three hooks, in order:
+//!
+//! 1. [`filter_tools`](ProxyBuilder::filter_tools) decides which upstream
+//!    tools are exposed. A hidden tool cannot be called either.
+//! 2. [`rename_tools`](ProxyBuilder::rename_tools) picks the name a tool is
+//!    exposed under; without it, the mux's (possibly prefixed) name is used.
+//! 3. [`intercept_calls`](ProxyBuilder::intercept_calls) sees every forwarded
+//!    call with the inbound request context and may rewrite its arguments or
+//!    reject it.
+//!
+//! Resources and prompts are forwarded as the mux lists them.
+
+use mcpkit_client::ClientMux;
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_core::debug::SessionRecorder;
+use mcpkit_core::error::{JsonRpcError, McpError};
+use mcpkit_core::protocol::{Message, Request, RequestId, Response};
+use mcpkit_core::types::{
+    CallToolResult, GetPromptResult, Object, Prompt, Resource, ResourceContents, Tool, ToolOutput,
+};
+use mcpkit_server::builder::{NotRegistered, Registered};
+use mcpkit_server::{
+    Context, PromptHandler, ResourceHandler, Server, ServerBuilder, ServerHandler, ToolHandler,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use tracing::{debug, warn};
+
+/// `_meta` key naming the upstream origin on requests recorded by the proxy.
+pub const ORIGIN_META_KEY: &str = "mcpkit/origin";
+
+/// Decides whether an upstream tool is exposed: `(origin, tool)`, where the
+/// tool carries its upstream name.
+type ToolFilter = Box<dyn Fn(&str, &Tool) -> bool + Send + Sync>;
+
+/// Picks the exposed name of a tool: `(origin, upstream name)`.
+type ToolRename = Box<dyn Fn(&str, &str) -> String + Send + Sync>;
+
+/// Rewrites or rejects a forwarded tool call.
+type CallInterceptor =
+    Box<dyn Fn(&mut ProxyCall, &Context<'_>) -> Result<(), McpError> + Send + Sync>;
+
+/// The server a proxy runs as: the proxy handles tools, resources, and
+/// prompts.
+pub type ProxyServer = Server<
+    McpProxy,
+    Registered<McpProxy>,
+    Registered<McpProxy>,
+    Registered<McpProxy>,
+    NotRegistered,
+>;
+
+/// A tool call about to be forwarded upstream, as seen by
+/// [`ProxyBuilder::intercept_calls`].
+#[derive(Debug, Clone)]
+pub struct ProxyCall {
+    origin: String,
+    tool: String,
+    exposed: String,
+    arguments: Object,
+}
+
+impl ProxyCall {
+    /// Origin of the upstream server the call goes to.
+    #[must_use]
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    /// The tool's name on the upstream server.
+    #[must_use]
+    pub fn tool(&self) -> &str {
+        &self.tool
+    }
+
+    /// The name the inbound client called the tool by.
+    #[must_use]
+    pub fn exposed_name(&self) -> &str {
+        &self.exposed
+    }
+
+    /// The arguments that will be sent upstream.
+    #[must_use]
+    pub const fn arguments(&self) -> &Object {
+        &self.arguments
+    }
+
+    /// Mutable access to the arguments, e.g. to inject credentials the
+    /// upstream tool expects.
+    pub const fn arguments_mut(&mut self) -> &mut Object {
+        &mut self.arguments
+    }
+}
+
+/// Builder for [`McpProxy`], created with [`McpProxy::builder`].
+pub struct ProxyBuilder {
+    mux: ClientMux,
+    info: ServerInfo,
+    instructions: Option<String>,
+    filter: Option<ToolFilter>,
+    rename: Option<ToolRename>,
+    interceptors: Vec<CallInterceptor>,
+    recorder: Option<Arc<SessionRecorder>>,
+}
+
+impl ProxyBuilder {
+    /// Set the server name reported to inbound clients (default
+    /// `mcpkit-proxy`).
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.info.name = name.into();
+        self
+    }
+
+    /// Set the server version reported to inbound clients.
+    #[must_use]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.info.version = version.into();
+        self
+    }
+
+    /// Set instructions sent to inbound clients during initialization.
+    #[must_use]
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Expose only the upstream tools for which `filter(origin, tool)` is
+    /// true. `tool` carries its upstream name.
+    #[must_use]
+    pub fn filter_tools(
+        mut self,
+        filter: impl Fn(&str, &Tool) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Expose each tool as `rename(origin, upstream_name)` instead of the
+    /// mux's name for it.
+    ///
+    /// When two tools are renamed to the same name, the first listed wins.
+    #[must_use]
+    pub fn rename_tools(
+        mut self,
+        rename: impl Fn(&str, &str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.rename = Some(Box::new(rename));
+        self
+    }
+
+    /// Run `interceptor` on every tool call before it is forwarded.
+    ///
+    /// The interceptor may change the call's arguments, for instance to add
+    /// a credential derived from the inbound context, or return an error to
+    /// reject the call; the error is returned to the inbound client and the
+    /// upstream server is never contacted. Interceptors run in the order they
+    /// were added.
+    #[must_use]
+    pub fn intercept_calls(
+        mut self,
+        interceptor: impl Fn(&mut ProxyCall, &Context<'_>) -> Result<(), McpError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
+    /// Record every forwarded request and upstream response in `recorder`.
+    ///
+    /// Requests carry the upstream origin in `_meta` under
+    /// [`ORIGIN_META_KEY`]. The recorder's
+    /// [`RecordingPolicy`](mcpkit_core::debug::RecordingPolicy), if any,
+    /// applies as usual.
+    #[must_use]
+    pub fn record_to(mut self, recorder: Arc<SessionRecorder>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Build the proxy.
+    #[must_use]
+    pub fn build(self) -> McpProxy {
+        McpProxy {
+            inner: Arc::new(Inner {
+                mux: self.mux,
+                info: self.info,
+                instructions: self.instructions,
+                filter: self.filter,
+                rename: self.rename,
+                interceptors: self.interceptors,
+                recorder: self.recorder,
+                tools: RwLock::new(HashMap::new()),
+                next_id: AtomicU64::new(1),
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for ProxyBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyBuilder")
+            .field("mux", &self.mux)
+            .field("info", &self.info)
+            .field("interceptors", &self.interceptors.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An MCP server that forwards to upstream servers.
+///
+/// Cloning is cheap and clones share state, which is how one proxy fills the
+/// handler, tool, resource, and prompt slots of a server.
+#[derive(Clone)]
+pub struct McpProxy {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    mux: ClientMux,
+    info: ServerInfo,
+    instructions: Option<String>,
+    filter: Option<ToolFilter>,
+    rename: Option<ToolRename>,
+    interceptors: Vec<CallInterceptor>,
+    recorder: Option<Arc<SessionRecorder>>,
+    /// Exposed tool names mapped to the mux's names, as of the last listing.
+    tools: RwLock<HashMap<String, String>>,
+    /// Ids for requests written to the recorder.
+    next_id: AtomicU64,
+}
+
+impl McpProxy {
+    /// Start building a proxy in front of the servers in `mux`.
+    #[must_use]
+    pub fn builder(mux: ClientMux) -> ProxyBuilder {
+        ProxyBuilder {
+            mux,
+            info: ServerInfo::new("mcpkit-proxy", env!("CARGO_PKG_VERSION")),
+            instructions: None,
+            filter: None,
+            rename: None,
+            interceptors: Vec::new(),
+            recorder: None,
+        }
+    }
+
+    /// The upstream servers.
+    #[must_use]
+    pub fn mux(&self) -> &ClientMux {
+        &self.inner.mux
+    }
+
+    /// A server handling tools, resources, and prompts with this proxy.
+    #[must_use]
+    pub fn into_server(self) -> ProxyServer {
+        ServerBuilder::new(self.clone())
+            .with_tools(self.clone())
+            .with_resources(self.clone())
+            .with_prompts(self)
+            .build()
+    }
+
+    /// List the exposed tools and rebuild the exposed-name table.
+    async fn exposed_tools(&self) -> Result<Vec<Tool>, McpError> {
+        let inner = &self.inner;
+        let mut names = HashMap::new();
+        let mut tools = Vec::new();
+        for mut tool in inner.mux.list_tools().await? {
+            let Some((origin, upstream)) = inner.mux.resolve_tool(&tool.name) else {
+                continue;
+            };
+            let mux_name = std::mem::replace(&mut tool.name, upstream);
+            if inner
+                .filter
+                .as_ref()
+                .is_some_and(|filter| !filter(&origin, &tool))
+            {
+                debug!(%origin, tool = %tool.name, "Tool hidden by proxy filter");
+                continue;
+            }
+            tool.name = match &inner.rename {
+                Some(rename) => rename(&origin, &tool.name),
+                None => mux_name.clone(),
+            };
+            if names.contains_key(&tool.name) {
+                warn!(%origin, name = %tool.name, "Duplicate exposed tool name");
+                continue;
+            }
+            names.insert(tool.name.clone(), mux_name);
+            tools.push(tool);
+        }
+        *inner.tools.write().unwrap_or_else(PoisonError::into_inner) = names;
+        Ok(tools)
+    }
+
+    /// The mux's name for an exposed tool, listing once on a miss.
+    async fn resolve(&self, name: &str) -> Result<String, McpError> {
+        let lookup = || {
+            self.inner
+                .tools
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(name)
+                .cloned()
+        };
+        if let Some(mux_name) = lookup() {
+            return Ok(mux_name);
+        }
+        self.exposed_tools().await?;
+        lookup()
+            .ok_or_else(|| McpError::invalid_params("tools/call", format!("Unknown tool: {name}")))
+    }
+
+    /// Forward `method` upstream through `forward`, recording the exchange.
+    async fn forwarded<T: serde::Serialize>(
+        &self,
+        method: &'static str,
+        origin: Option<&str>,
+        params: Value,
+        forward: impl Future<Output = Result<T, McpError>>,
+    ) -> Result<T, McpError> {
+        let Some(recorder) = &self.inner.recorder else {
+            return forward.await;
+        };
+        let id = RequestId::Number(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        let mut params = params;
+        if let (Some(origin), Value::Object(params)) = (origin, &mut params) {
+            params.insert(
+                "_meta".to_string(),
+                serde_json::json!({ ORIGIN_META_KEY: origin }),
+            );
+        }
+        recorder.record_sent(Message::Request(Request::with_params(
+            method,
+            id.clone(),
+            params,
+        )));
+        let result = forward.await;
+        let response = match &result {
+            Ok(value) => match serde_json::to_value(value) {
+                Ok(value) => Response::success(id, value),
+                Err(e) => Response::error(id, JsonRpcError::from(McpError::from(e))),
+            },
+            Err(e) => Response::error(id, JsonRpcError::from(e)),
+        };
+        recorder.record_received(Message::Response(response));
+        result
+    }
+}
+
+impl fmt::Debug for McpProxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McpProxy")
+            .field("info", &self.inner.info)
+            .field("mux", &self.inner.mux)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ServerHandler for McpProxy {
+    fn server_info(&self) -> ServerInfo {
+        self.inner.info.clone()
+    }
+
+    fn capabilities(&self) -> ServerCapabilities {
+        ServerCapabilities::new()
+            .with_tools()
+            .with_resources()
+            .with_prompts()
+    }
+
+    fn instructions(&self) -> Option<String> {
+        self.inner.instructions.clone()
+    }
+}
+
+impl ToolHandler for McpProxy {
+    async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        self.exposed_tools().await
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        args: Object,
+        ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        let mux_name = self.resolve(name).await?;
+        let (origin, tool) = self.inner.mux.resolve_tool(&mux_name).ok_or_else(|| {
+            McpError::invalid_params("tools/call", format!("Unknown tool: {name}"))
+        })?;
+        let mut call = ProxyCall {
+            origin,
+            tool,
+            exposed: name.to_string(),
+            arguments: args,
+        };
+        for interceptor in &self.inner.interceptors {
+            interceptor(&mut call, ctx)?;
+        }
+
+        let params = serde_json::json!({ "name": call.tool, "arguments": call.arguments });
+        let result: CallToolResult = self
+            .forwarded(
+                "tools/call",
+                Some(&call.origin),
+                params,
+                self.inner
+                    .mux
+                    .call_tool(mux_name, Value::Object(call.arguments.clone())),
+            )
+            .await?;
+        Ok(ToolOutput::Success(result))
+    }
+}
+
+impl ResourceHandler for McpProxy {
+    async fn list_resources(&self, _ctx: &Context<'_>) -> Result<Vec<Resource>, McpError> {
+        self.inner.mux.list_resources().await
+    }
+
+    async fn read_resource(
+        &self,
+        uri: &str,
+        _ctx: &Context<'_>,
+    ) -> Result<Vec<ResourceContents>, McpError> {
+        self.forwarded(
+            "resources/read",
+            None,
+            serde_json::json!({ "uri": uri }),
+            self.inner.mux.read_resource(uri),
+        )
+        .await
+    }
+}
+
+impl PromptHandler for McpProxy {
+    async fn list_prompts(&self, _ctx: &Context<'_>) -> Result<Vec<Prompt>, McpError> {
+        self.inner.mux.list_prompts().await
+    }
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        args: Option<serde_json::Map<String, Value>>,
+        _ctx: &Context<'_>,
+    ) -> Result<GetPromptResult, McpError> {
+        let params = serde_json::json!({ "name": name, "arguments": args });
+        self.forwarded(
+            "prompts/get",
+            None,
+            params,
+            self.inner.mux.get_prompt(name, args.clone()),
+        )
+        .await
+    }
+}
@@ -0,0 +1,170 @@
+//! End-to-end tests: an inbound client talks to a proxy that forwards to
+//! mock upstream servers, all over in-memory transports.
+
+use mcpkit_client::{Client, ClientBuilder, ClientMux};
+use mcpkit_core::debug::SessionRecorder;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{ToolAnnotations, ToolOutput};
+use mcpkit_proxy::{McpProxy, ProxyBuilder};
+use mcpkit_server::ServerBuilder;
+use mcpkit_testing::mock::MockResource;
+use mcpkit_testing::{MockServer, MockTool};
+use mcpkit_transport::MemoryTransport;
+use serde_json::json;
+use std::sync::Arc;
+
+/// An upstream tool that echoes its arguments back as JSON text.
+fn echo(name: &str) -> MockTool {
+    MockTool::new(name).handler(|args| Ok(ToolOutput::text(serde_json::to_string(&args)?)))
+}
+
+/// Serve `server` on a fresh in-memory pair and return the client end.
+fn serve(server: MockServer) -> MemoryTransport {
+    let (client_side, server_side) = MemoryTransport::pair();
+    let server = Arc::new(server);
+    let server = ServerBuilder::new(Arc::clone(&server))
+        .with_tools(Arc::clone(&server))
+        .with_resources(server)
+        .build();
+    tokio::spawn(server.serve(server_side));
+    client_side
+}
+
+/// A mux over two upstreams, `fs` and `web`, which both have a `search` tool.
+async fn mux() -> ClientMux {
+    let fs = MockServer::builder()
+        .name("fs")
+        .tool(echo("search").annotations(ToolAnnotations::read_only()))
+        .tool(echo("delete"))
+        .resource(MockResource::new("file:///readme", "readme").content("hello"))
+        .build();
+    let web = MockServer::builder()
+        .name("web")
+        .tool(echo("search").annotations(ToolAnnotations::read_only()))
+        .build();
+
+    let mut mux = ClientMux::new();
+    mux.connect("fs", ClientBuilder::new(), serve(fs))
+        .await
+        .unwrap();
+    mux.connect("web", ClientBuilder::new(), serve(web))
+        .await
+        .unwrap();
+    mux
+}
+
+/// Serve `proxy` and connect an inbound client to it.
+async fn connect(proxy: ProxyBuilder) -> Client<MemoryTransport> {
+    let (client_side, server_side) = MemoryTransport::pair();
+    tokio::spawn(proxy.build().into_server().serve(server_side));
+    ClientBuilder::new().build(client_side).await.unwrap()
+}
+
+fn text(result: &mcpkit_core::types::CallToolResult) -> serde_json::Value {
+    let text = result.content[0].as_text().unwrap();
+    serde_json::from_str(text).unwrap()
+}
+
+#[tokio::test]
+async fn forwards_tools_and_resources() {
+    let client = connect(McpProxy::builder(mux().await).name("gateway")).await;
+    assert_eq!(client.server_info().name, "gateway");
+
+    let mut names: Vec<_> = client
+        .list_tools()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tool| tool.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, ["delete", "fs__search", "web__search"]);
+
+    let result = client
+        .call_tool("web__search", json!({ "q": "rust" }))
+        .await
+        .unwrap();
+    assert_eq!(text(&result), json!({ "q": "rust" }));
+
+    let contents = client.read_resource("file:///readme").await.unwrap();
+    assert_eq!(contents[0].as_text(), Some("hello"));
+}
+
+#[tokio::test]
+async fn filtered_tools_are_hidden_and_uncallable() {
+    let proxy = McpProxy::builder(mux().await).filter_tools(|_, tool| tool.is_read_only());
+    let client = connect(proxy).await;
+
+    let tools = client.list_tools().await.unwrap();
+    assert_eq!(tools.len(), 2);
+    assert!(tools.iter().all(|tool| tool.name != "delete"));
+
+    let err = client.call_tool("delete", json!({})).await.unwrap_err();
+    assert!(err.to_string().contains("Unknown tool"), "{err}");
+}
+
+#[tokio::test]
+async fn renamed_tools_are_called_by_their_new_name() {
+    let proxy = McpProxy::builder(mux().await)
+        .filter_tools(|origin, _| origin == "fs")
+        .rename_tools(|origin, name| format!("{origin}.{name}"));
+    let client = connect(proxy).await;
+
+    let mut names: Vec<_> = client
+        .list_tools()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tool| tool.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, ["fs.delete", "fs.search"]);
+
+    let result = client.call_tool("fs.search", json!({ "q": 1 })).await;
+    assert_eq!(text(&result.unwrap()), json!({ "q": 1 }));
+    assert!(client.call_tool("fs__search", json!({})).await.is_err());
+}
+
+#[tokio::test]
+async fn interceptors_inject_arguments_and_reject_calls() {
+    let proxy = McpProxy::builder(mux().await)
+        .intercept_calls(|call, _ctx| {
+            if call.tool() == "delete" {
+                return Err(McpError::invalid_request("delete is not allowed"));
+            }
+            Ok(())
+        })
+        .intercept_calls(|call, _ctx| {
+            let token = format!("token-for-{}", call.origin());
+            call.arguments_mut()
+                .insert("auth".to_string(), json!(token));
+            Ok(())
+        });
+    let client = connect(proxy).await;
+
+    let result = client
+        .call_tool("fs__search", json!({ "q": "x" }))
+        .await
+        .unwrap();
+    assert_eq!(text(&result), json!({ "q": "x", "auth": "token-for-fs" }));
+
+    let err = client.call_tool("delete", json!({})).await.unwrap_err();
+    assert!(err.to_string().contains("not allowed"), "{err}");
+}
+
+#[tokio::test]
+async fn forwarded_traffic_is_recorded() {
+    let recorder = Arc::new(SessionRecorder::new("proxy"));
+    let proxy = McpProxy::builder(mux().await).record_to(Arc::clone(&recorder));
+    let client = connect(proxy).await;
+
+    let before = recorder.event_count();
+    client
+        .call_tool("web__search", json!({ "q": "x" }))
+        .await
+        .unwrap();
+    client.read_resource("file:///readme").await.unwrap();
+
+    // A request and a response for each forwarded call.
+    assert_eq!(recorder.event_count(), before + 4);
+}
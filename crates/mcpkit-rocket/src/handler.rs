@@ -1,18 +1,14 @@
 //! HTTP handlers for MCP requests using Rocket.
 
+use crate::SUPPORTED_VERSIONS;
 use crate::state::{HasServerInfo, McpState};
-use crate::{SUPPORTED_VERSIONS, is_supported_version};
 use mcpkit_core::auth::VerifiedUser;
-use mcpkit_core::capability::ClientCapabilities;
 use mcpkit_core::protocol::Message;
-use mcpkit_core::protocol_version::ProtocolVersion;
-use mcpkit_server::capability::tasks::{TaskManager, route_task_store};
-use mcpkit_server::context::{Context, NoOpPeer};
-use mcpkit_server::{
-    AugmentedTaskOutcome, PromptHandler, ResourceHandler, ServerHandler, ToolHandler,
-    begin_augmented_task, route_completion, route_logging, route_prompts, route_resources,
-    route_tools,
-};
+use mcpkit_core::types::task::TaskId;
+use mcpkit_server::health::{HealthProbes, ProbeResponse, session_health};
+use mcpkit_server::service::{ServiceOutcome, ServiceRejection, ServiceRequest};
+use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use rocket::futures::StreamExt;
 use rocket::http::{ContentType, Header, Status};
 use rocket::request::{FromRequest, Outcome, Request};
 use rocket::response::stream::{Event, EventStream};
@@ -83,6 +79,19 @@ impl<'r> FromRequest<'r> for OriginHeader {
     }
 }
 
+/// `Accept` header, for choosing between a JSON and an SSE response.
+pub struct AcceptHeader(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AcceptHeader {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let accept = request.headers().get_one("accept").map(String::from);
+        Outcome::Success(AcceptHeader(accept))
+    }
+}
+
 /// Last-Event-ID header for SSE reconnection.
 pub struct LastEventIdHeader(pub Option<String>);
 
@@ -116,6 +125,17 @@ impl McpResponse {
         }
     }
 
+    /// Create a `text/event-stream` response carrying pre-rendered SSE events.
+    #[must_use]
+    pub fn event_stream(body: String, session_id: String) -> Self {
+        Self {
+            status: Status::Ok,
+            content_type: ContentType::EventStream,
+            session_id: Some(session_id),
+            body,
+        }
+    }
+
     /// Create an accepted response (for notifications).
     #[must_use]
     pub fn accepted(session_id: String) -> Self {
@@ -194,11 +214,15 @@ impl<H> HandlerContext<H> {
 /// Handle MCP POST requests.
 ///
 /// This is the core handler function that processes JSON-RPC messages.
+/// `accept` is the request's `Accept` header; it picks a JSON or an SSE
+/// response (see
+/// [`ResponseMode::negotiate`](mcpkit_server::service::ResponseMode::negotiate)).
 pub async fn handle_mcp_post<H>(
     state: &McpState<H>,
     version: Option<&str>,
     session_id: Option<String>,
     origin: Option<&str>,
+    accept: Option<&str>,
     user: Option<VerifiedUser>,
     body: &str,
 ) -> McpResponse
@@ -212,287 +236,48 @@ where
         + Sync
         + 'static,
 {
-    // Reject disallowed Origins (DNS-rebinding protection) before any work.
-    if !state.origin_validator.is_allowed(origin) {
-        warn!(
-            origin = origin.unwrap_or("none"),
-            "Rejected: origin not allowed"
-        );
-        return McpResponse::error(Status::Forbidden, "origin not allowed".to_string());
-    }
-
-    // Validate protocol version
-    if !is_supported_version(version) {
-        let provided = version.unwrap_or("none");
-        warn!(version = provided, "Unsupported protocol version");
-        return McpResponse::error(
-            Status::BadRequest,
-            format!(
-                "Unsupported protocol version: {} (supported: {})",
-                provided,
-                SUPPORTED_VERSIONS.join(", ")
-            ),
-        );
-    }
-
-    // Get or create session (binding it to the verified user, if any).
-    let session_id = match session_id {
-        Some(id) => match state.sessions.touch_verified(&id, user.as_ref()) {
-            Ok(true) => id,
-            // Reject an unknown session id rather than silently proceeding.
-            Ok(false) => {
-                warn!(session_id = %id, "Rejected: unknown session id");
-                return McpResponse::error(Status::NotFound, "unknown session id".to_string());
-            }
-            Err(e) => {
-                warn!(session_id = %id, error = %e, "Rejected: session binding violation");
-                return McpResponse::error(Status::Forbidden, e.to_string());
-            }
-        },
-        None => state.sessions.create_for_user(user),
-    };
-
-    debug!(session_id = %session_id, "Processing MCP request");
-
-    // Parse message
-    let msg: Message = match serde_json::from_str(body) {
-        Ok(m) => m,
-        Err(e) => {
-            warn!(error = %e, "Failed to parse JSON-RPC message");
-            return McpResponse::error(Status::BadRequest, format!("Invalid message: {e}"));
-        }
-    };
-
-    // Process message
-    match msg {
-        Message::Request(request) => {
-            info!(
-                method = %request.method,
-                id = ?request.id,
-                session_id = %session_id,
-                "Handling MCP request"
-            );
-
-            // On initialize, negotiate the protocol version and record it (and
-            // the client's capabilities) on the session, so subsequent requests
-            // observe the negotiated values.
-            if request.method.as_ref() == "initialize" {
-                let (negotiated, caps) = negotiate_initialize(request.params.as_ref());
-                state.sessions.set_negotiated(&session_id, negotiated, caps);
-            }
-
-            // Resolve the session's negotiated values for the request context,
-            // falling back to defaults before initialization completes.
-            let (protocol_version, client_caps) =
-                state.sessions.negotiated(&session_id).map_or_else(
-                    || (ProtocolVersion::LATEST, ClientCapabilities::default()),
-                    |(v, c)| (v, c.unwrap_or_default()),
-                );
-            // This session's task store (per-session isolation for `tasks/*`).
-            let task_store = state.sessions.tasks(&session_id);
-
-            let response = create_response_for_request(
-                state,
-                &request,
-                protocol_version,
-                &client_caps,
-                task_store.as_ref(),
-            )
-            .await;
-
-            match serde_json::to_string(&Message::Response(response)) {
+    let request = ServiceRequest::from_bytes(body)
+        .with_origin(origin.map(String::from))
+        .with_protocol_version(version.map(String::from))
+        .with_session_id(session_id)
+        .with_accept(accept.map(String::from))
+        .with_user(user);
+
+    let reply = state.service().handle(request).await;
+    let sse = reply.sse_body();
+    match reply.outcome {
+        ServiceOutcome::Response {
+            session_id,
+            response,
+        } => match sse {
+            Some(body) => McpResponse::event_stream(body, session_id),
+            None => match serde_json::to_string(&Message::Response(response)) {
                 Ok(body) => McpResponse::success(body, session_id),
                 Err(e) => McpResponse::error(
                     Status::InternalServerError,
                     format!("Serialization error: {e}"),
                 ),
-            }
-        }
-        Message::Notification(notification) => {
-            debug!(
-                method = %notification.method,
-                session_id = %session_id,
-                "Received notification"
-            );
-            McpResponse::accepted(session_id)
-        }
-        _ => {
-            warn!("Unexpected message type received");
-            McpResponse::error(
-                Status::BadRequest,
-                "Expected request or notification".to_string(),
-            )
-        }
-    }
-}
-
-/// Negotiate the protocol version and extract client capabilities from an
-/// `initialize` request's params.
-///
-/// The negotiated version is the highest supported version not exceeding the
-/// client's requested version, falling back to the latest supported version
-/// when the request omits or names an unknown version.
-fn negotiate_initialize(
-    params: Option<&serde_json::Value>,
-) -> (ProtocolVersion, Option<ClientCapabilities>) {
-    let requested = params
-        .and_then(|p| p.get("protocolVersion"))
-        .and_then(serde_json::Value::as_str)
-        .unwrap_or("");
-    let version = ProtocolVersion::negotiate(requested, ProtocolVersion::ALL)
-        .unwrap_or(ProtocolVersion::LATEST);
-    let capabilities = params
-        .and_then(|p| p.get("capabilities"))
-        .and_then(|c| serde_json::from_value::<ClientCapabilities>(c.clone()).ok());
-    (version, capabilities)
-}
-
-/// Create a response for a request.
-async fn create_response_for_request<H>(
-    state: &McpState<H>,
-    request: &mcpkit_core::protocol::Request,
-    protocol_version: ProtocolVersion,
-    client_caps: &ClientCapabilities,
-    task_store: Option<&Arc<TaskManager>>,
-) -> mcpkit_core::protocol::Response
-where
-    H: ServerHandler + ToolHandler + ResourceHandler + PromptHandler + Send + Sync + 'static,
-{
-    use mcpkit_core::error::JsonRpcError;
-    use mcpkit_core::protocol::Response;
-
-    let method = request.method.as_ref();
-    let params = request.params.as_ref();
-
-    // Create a context for the request
-    let req_id = request.id.clone();
-    let server_caps = state.effective_capabilities();
-    let peer = NoOpPeer;
-    let ctx = Context::new(
-        &req_id,
-        None,
-        client_caps,
-        &server_caps,
-        protocol_version,
-        &peer,
-    );
-
-    match method {
-        "ping" => Response::success(request.id.clone(), serde_json::json!({})),
-        "initialize" => {
-            let init_result = serde_json::json!({
-                "protocolVersion": protocol_version.as_str(),
-                "serverInfo": state.server_info,
-                "capabilities": server_caps,
-            });
-            Response::success(request.id.clone(), init_result)
-        }
-        _ => {
-            // Task-augmented tools/call and tasks/* are served from this
-            // session's own task store (per-session isolation).
-            if let Some(store) = task_store {
-                if method == "tools/call" {
-                    match begin_augmented_task(
-                        state.handler.clone(),
-                        store,
-                        params,
-                        client_caps.clone(),
-                        server_caps.clone(),
-                        protocol_version,
-                    )
-                    .await
-                    {
-                        AugmentedTaskOutcome::Started(create_result, fut) => {
-                            tokio::spawn(fut);
-                            return Response::success(request.id.clone(), create_result);
-                        }
-                        AugmentedTaskOutcome::Rejected(e) => {
-                            return Response::error(request.id.clone(), e.into());
-                        }
-                        AugmentedTaskOutcome::NotApplicable => {}
-                    }
-                } else if let Some(result) = route_task_store(store, method, params).await {
-                    return match result {
-                        Ok(value) => Response::success(request.id.clone(), value),
-                        Err(e) => Response::error(request.id.clone(), e.into()),
-                    };
-                }
-            }
-
-            // Try routing to tools
-            if let Some(result) = route_tools(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing to resources
-            if let Some(result) = route_resources(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing to prompts
-            if let Some(result) = route_prompts(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing logging/setLevel (gated on the advertised capability)
-            if let Some(result) =
-                route_logging(state.handler.as_ref(), &server_caps, method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing completion/complete (when a completion handler is set)
-            if let Some(result) =
-                route_completion(state.completion.as_deref(), method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Method not found
-            Response::error(
-                request.id.clone(),
-                JsonRpcError::method_not_found(format!("Method '{method}' not found")),
-            )
+            },
+        },
+        ServiceOutcome::Accepted { session_id } => McpResponse::accepted(session_id),
+        ServiceOutcome::Rejected(rejection) => {
+            let status = Status::from_code(rejection.status_code()).unwrap_or(Status::BadRequest);
+            let message = match rejection {
+                ServiceRejection::UnsupportedVersion(provided) => format!(
+                    "Unsupported protocol version: {} (supported: {})",
+                    provided,
+                    SUPPORTED_VERSIONS.join(", ")
+                ),
+                ServiceRejection::SessionNotFound(_) => "unknown session id".to_string(),
+                ServiceRejection::InvalidMessage(e) => format!("Invalid message: {e}"),
+                other => other.to_string(),
+            };
+            McpResponse::error(status, message)
         }
+        _ => McpResponse::error(
+            Status::InternalServerError,
+            "unexpected service outcome".to_string(),
+        ),
     }
 }
 
@@ -518,13 +303,35 @@ where
         (id, rx)
     };
 
-    EventStream! {
+    #[cfg(feature = "prometheus")]
+    let connection = state
+        .metrics
+        .as_ref()
+        .map(mcpkit_transport::McpMetrics::sse_connection);
+
+    let shutdown = state.shutdown.wait();
+    state.sse_sessions.start_reaper();
+    let heartbeat = state.sse_sessions.session_config().heartbeat_interval;
+
+    let stream = EventStream! {
+        // Counted as an open SSE connection until the stream is dropped.
+        #[cfg(feature = "prometheus")]
+        let _connection = connection;
+        let mut shutdown = shutdown;
+
         // Send connected event with session ID
         yield Event::data(session_id.clone()).event("connected").id("evt-connected");
 
-        // Stream new messages
+        // Stream new messages until the channel closes or the server shuts down
         loop {
-            match rx.recv().await {
+            let received = tokio::select! {
+                received = rx.recv() => received,
+                () = &mut shutdown => {
+                    debug!("SSE stream ended by shutdown");
+                    break;
+                }
+            };
+            match received {
                 Ok(msg) => {
                     let event_id = format!("evt-{}", uuid::Uuid::new_v4());
                     yield Event::data(msg).event("message").id(event_id);
@@ -538,37 +345,185 @@ where
                 }
             }
         }
+    };
+    stream.heartbeat(heartbeat)
+}
+
+/// Handle SSE connections following a single task.
+///
+/// Served at `/mcp/tasks/<task_id>/events` by the `mcp_task_events` route of
+/// [`create_mcp_routes!`](crate::create_mcp_routes). Streams the task's
+/// `status` and `progress` events (see
+/// [`TaskEvent`](mcpkit_server::capability::tasks::TaskEvent)) and ends after
+/// the event that makes the task terminal. Tasks are scoped to the session in
+/// `session_id`; `last_event_id` resumes after the given event.
+///
+/// # Errors
+///
+/// Returns `403 Forbidden` for a disallowed origin or a session binding
+/// violation, `400 Bad Request` without a session, and `404 Not Found` for an
+/// unknown session or task.
+pub fn handle_task_events<H>(
+    state: &McpState<H>,
+    task_id: String,
+    session_id: Option<String>,
+    origin: Option<&str>,
+    last_event_id: Option<String>,
+    user: Option<VerifiedUser>,
+) -> Result<EventStream![], Status> {
+    if !state.origin_validator.is_allowed(origin) {
+        warn!(
+            origin = origin.unwrap_or("none"),
+            "Rejected task SSE: origin not allowed"
+        );
+        return Err(Status::Forbidden);
+    }
+    let session_id = session_id.ok_or(Status::BadRequest)?;
+    match state.sessions.touch_verified(&session_id, user.as_ref()) {
+        Ok(true) => {}
+        Ok(false) => return Err(Status::NotFound),
+        Err(e) => {
+            warn!(session_id = %session_id, error = %e, "Rejected task SSE: session binding violation");
+            return Err(Status::Forbidden);
+        }
     }
+
+    let task_id = TaskId::new(task_id);
+    let tasks = state
+        .sessions
+        .tasks(&session_id)
+        .filter(|tasks| tasks.get(&task_id).is_some())
+        .ok_or(Status::NotFound)?;
+    let after = last_event_id.and_then(|id| id.parse().ok());
+    debug!(session_id = %session_id, task_id = task_id.as_str(), ?after, "Following task events");
+
+    let events = tasks
+        .follow(task_id, after)
+        .take_until(state.shutdown.wait());
+    let heartbeat = state.sse_sessions.session_config().heartbeat_interval;
+    let stream = EventStream! {
+        let mut events = std::pin::pin!(events);
+        while let Some(event) = events.next().await {
+            yield Event::data(event.data().to_string())
+                .event(event.event_type())
+                .id(event.id.to_string());
+        }
+    };
+    Ok(stream.heartbeat(heartbeat))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// State of the `/metrics` route.
+///
+/// [`McpRouter::into_rocket`](crate::McpRouter::into_rocket) manages it and
+/// mounts [`metrics`] when metrics are enabled. With
+/// [`into_state`](crate::McpRouter::into_state), do the same yourself:
+///
+/// ```ignore
+/// let state = McpRouter::new(MyHandler).with_metrics().into_state();
+/// let metrics = MetricsState::from_state(&state).expect("metrics are enabled");
+/// rocket::build()
+///     .manage(state)
+///     .manage(metrics)
+///     .mount("/", routes![mcp_post, mcp_sse, mcpkit_rocket::handler::metrics]);
+/// ```
+#[cfg(feature = "prometheus")]
+pub struct MetricsState {
+    metrics: mcpkit_transport::McpMetrics,
+    sessions: crate::session::SessionStore,
+}
 
-    #[test]
-    fn negotiate_uses_requested_supported_version() {
-        let params = serde_json::json!({
-            "protocolVersion": "2025-06-18",
-            "capabilities": {}
-        });
-        let (version, caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::V2025_06_18);
-        assert!(caps.is_some());
+#[cfg(feature = "prometheus")]
+impl MetricsState {
+    /// The metrics of `state`, or `None` if it has none.
+    #[must_use]
+    pub fn from_state<H>(state: &McpState<H>) -> Option<Self> {
+        Some(Self {
+            metrics: state.metrics.clone()?,
+            sessions: state.sessions.clone(),
+        })
     }
+}
 
-    #[test]
-    fn negotiate_defaults_to_latest_when_absent() {
-        let (version, caps) = negotiate_initialize(None);
-        assert_eq!(version, ProtocolVersion::LATEST);
-        assert!(caps.is_none());
-    }
+/// Serve Prometheus metrics in the text exposition format at `GET /metrics`.
+#[cfg(feature = "prometheus")]
+#[rocket::get("/metrics")]
+pub fn metrics(state: &rocket::State<MetricsState>) -> Result<(ContentType, String), Status> {
+    state
+        .metrics
+        .set_active_sessions(state.sessions.session_count());
+    let exporter = mcpkit_transport::MetricsExporter::from_metrics(&state.metrics);
+    let body = exporter.gather().map_err(|e| {
+        warn!(error = %e, "Failed to encode metrics");
+        Status::InternalServerError
+    })?;
+    let content_type =
+        ContentType::parse_flexible(exporter.content_type()).unwrap_or(ContentType::Plain);
+    Ok((content_type, body))
+}
 
-    #[test]
-    fn negotiate_unknown_version_falls_back_to_latest() {
-        let params = serde_json::json!({ "protocolVersion": "2099-01-01" });
-        let (version, _caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::LATEST);
+/// State of the `/healthz` and `/readyz` routes.
+///
+/// [`McpRouter::into_rocket`](crate::McpRouter::into_rocket) manages it and
+/// mounts [`liveness`] and [`readiness`] when health checks are enabled. With
+/// [`into_state`](crate::McpRouter::into_state), do the same yourself:
+///
+/// ```ignore
+/// let state = McpRouter::new(MyHandler).with_health_checks().into_state();
+/// let health = HealthState::from_state(&state).expect("health checks are enabled");
+/// rocket::build()
+///     .manage(state)
+///     .manage(health)
+///     .mount("/", routes![mcp_post, mcp_sse, handler::liveness, handler::readiness]);
+/// ```
+pub struct HealthState {
+    probes: HealthProbes,
+    sessions: crate::session::SessionStore,
+    shutdown: mcpkit_server::ShutdownHandle,
+}
+
+impl HealthState {
+    /// The health probes of `state`, or `None` if it has none.
+    #[must_use]
+    pub fn from_state<H>(state: &McpState<H>) -> Option<Self> {
+        Some(Self {
+            probes: state.health.clone()?,
+            sessions: state.sessions.clone(),
+            shutdown: state.shutdown.clone(),
+        })
     }
+}
+
+/// Answer the liveness probe at `GET /healthz`.
+#[rocket::get("/healthz")]
+pub fn liveness(
+    state: &rocket::State<HealthState>,
+    uri: &rocket::http::uri::Origin<'_>,
+) -> (Status, (ContentType, String)) {
+    let verbose = HealthProbes::verbose_requested(uri.query().map(|q| q.as_str()));
+    probe_response(state.probes.liveness(verbose))
+}
+
+/// Answer the readiness probe at `GET /readyz`: the configured checks plus
+/// session health, which fails once shutdown begins.
+#[rocket::get("/readyz")]
+pub fn readiness(
+    state: &rocket::State<HealthState>,
+    uri: &rocket::http::uri::Origin<'_>,
+) -> (Status, (ContentType, String)) {
+    let sessions = session_health(state.sessions.session_count(), state.shutdown.is_shutdown());
+    let verbose = HealthProbes::verbose_requested(uri.query().map(|q| q.as_str()));
+    probe_response(state.probes.readiness(sessions, verbose))
+}
+
+fn probe_response(probe: ProbeResponse) -> (Status, (ContentType, String)) {
+    let content_type =
+        ContentType::parse_flexible(probe.content_type).unwrap_or(ContentType::Plain);
+    (Status::new(probe.status), (content_type, probe.body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     // Test HandlerContext
     struct TestHandler {
@@ -10,6 +10,7 @@
 //! - Session management with automatic cleanup
 //! - Protocol version validation
 //! - CORS support via Rocket fairings
+//! - Prometheus `/metrics` endpoint (`prometheus` feature)
 //!
 //! # HTTP Protocol Requirements
 //!
@@ -98,9 +99,11 @@ mod session;
 mod state;
 
 pub use error::RocketError;
+#[cfg(feature = "prometheus")]
+pub use handler::MetricsState;
 pub use handler::{
-    LastEventIdHeader, McpResponse, OriginHeader, ProtocolVersionHeader, SessionIdHeader,
-    handle_mcp_post, handle_sse,
+    AcceptHeader, HealthState, LastEventIdHeader, McpResponse, OriginHeader, ProtocolVersionHeader,
+    SessionIdHeader, handle_mcp_post, handle_sse, handle_task_events,
 };
 pub use router::{Cors, McpRouter};
 pub use session::{DEFAULT_SESSION_TIMEOUT, SessionManager, SessionStore};
@@ -115,7 +118,7 @@ pub use state::McpState;
 /// ```
 pub mod prelude {
     pub use crate::error::RocketError;
-    pub use crate::handler::{handle_mcp_post, handle_sse};
+    pub use crate::handler::{handle_mcp_post, handle_sse, handle_task_events};
     pub use crate::router::McpRouter;
     pub use crate::session::{DEFAULT_SESSION_TIMEOUT, SessionManager, SessionStore};
     pub use crate::state::McpState;
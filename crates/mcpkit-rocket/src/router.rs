@@ -1,7 +1,10 @@
 //! Router builder for MCP endpoints in Rocket.
 
 use crate::state::{HasServerInfo, McpState};
-use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use mcpkit_server::health::{HealthChecker, HealthProbes};
+use mcpkit_server::service::ResponseMode;
+use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ShutdownHandle, ToolHandler};
+use mcpkit_transport::TransportLimits;
 use mcpkit_transport::http::OriginValidator;
 use rocket::fairing::{Fairing, Info, Kind};
 use rocket::http::Header;
@@ -30,6 +33,7 @@ use rocket::{Build, Request, Response, Rocket};
 pub struct McpRouter<H> {
     state: McpState<H>,
     enable_cors: bool,
+    limits: Option<TransportLimits>,
 }
 
 impl<H> McpRouter<H>
@@ -48,6 +52,7 @@ where
         Self {
             state: McpState::new(handler),
             enable_cors: false,
+            limits: None,
         }
     }
 
@@ -69,6 +74,64 @@ where
         self
     }
 
+    /// Rate limit requests per session, per method, and per tool.
+    ///
+    /// Buckets are keyed by the `mcp-session-id` header. A request over a
+    /// quota gets a JSON-RPC error whose `data.retry_after_ms` says when to
+    /// retry. Disabled by default.
+    #[must_use]
+    pub fn with_rate_limit(mut self, config: mcpkit_server::RateLimitConfig) -> Self {
+        self.state = self.state.with_rate_limit(config);
+        self
+    }
+
+    /// Check `tools/call` requests against a tool policy.
+    ///
+    /// The policy sees the `VerifiedUser` your auth middleware attached, and a
+    /// denied call gets a JSON-RPC error with code `-32011`. Disabled by
+    /// default.
+    #[must_use]
+    pub fn with_tool_authorizer(mut self, authorizer: mcpkit_server::ToolAuthorizer) -> Self {
+        self.state = self.state.with_tool_authorizer(authorizer);
+        self
+    }
+
+    /// Write an audit record for each tool call and resource read.
+    ///
+    /// Records carry the `mcp-session-id` and the verified user's subject.
+    /// See [`AuditLogger`](mcpkit_server::AuditLogger) for sinks, sampling,
+    /// and redaction. Disabled by default.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: mcpkit_server::AuditLogger) -> Self {
+        self.state = self.state.with_audit_logger(logger);
+        self
+    }
+
+    /// Trace tool, resource, and prompt invocations.
+    ///
+    /// Each call runs in an `mcp.tool`, `mcp.resource`, or `mcp.prompt` span
+    /// carrying the request and session ids, duration, and outcome. See
+    /// [`InvocationTracing`](mcpkit_server::InvocationTracing) for argument
+    /// capture. Arguments are not captured by default.
+    #[must_use]
+    pub fn with_invocation_tracing(mut self, tracing: mcpkit_server::InvocationTracing) -> Self {
+        self.state = self.state.with_invocation_tracing(tracing);
+        self
+    }
+
+    /// Record request counts, errors, and latency percentiles per method and
+    /// per tool into the layer's
+    /// [`ServerMetrics`](mcpkit_server::ServerMetrics).
+    ///
+    /// With [`MetricsLayer::expose_resource`](mcpkit_server::MetricsLayer::expose_resource),
+    /// clients can also read a JSON snapshot from the `metrics://server`
+    /// resource. Disabled by default.
+    #[must_use]
+    pub fn with_server_metrics(mut self, metrics: mcpkit_server::MetricsLayer) -> Self {
+        self.state = self.state.with_server_metrics(metrics);
+        self
+    }
+
     /// Set the default task retention (milliseconds) for each session's task
     /// store, applied when a task-augmented `tools/call` omits a `ttl`. Pass
     /// `None` for unlimited retention.
@@ -78,6 +141,20 @@ where
         self
     }
 
+    /// Set session lifetimes, the session cap, and the SSE keep-alive
+    /// interval.
+    ///
+    /// A reaper task, started with the first SSE stream, ends streams idle
+    /// past `idle_timeout` or older than `max_age` and reports each eviction
+    /// to the config's `on_evict` callbacks. The idle timeout also applies to
+    /// HTTP sessions. See [`SessionConfig`](mcpkit_server::SessionConfig) for
+    /// the defaults.
+    #[must_use]
+    pub fn with_session_config(mut self, config: mcpkit_server::SessionConfig) -> Self {
+        self.state = self.state.with_session_config(config);
+        self
+    }
+
     /// Enable CORS with permissive defaults.
     #[must_use]
     pub const fn with_cors(mut self) -> Self {
@@ -117,6 +194,83 @@ where
         self
     }
 
+    /// Choose how POSTed requests are answered when the client's `Accept`
+    /// header allows both JSON and SSE.
+    ///
+    /// With [`ResponseMode::Sse`], the response is a `text/event-stream`
+    /// carrying the notifications the handler sent (progress, logs) and then
+    /// the JSON-RPC response. Clients that accept only `application/json`
+    /// still get plain JSON. Defaults to [`ResponseMode::Json`].
+    #[must_use]
+    pub const fn with_response_mode(mut self, mode: ResponseMode) -> Self {
+        self.state.response_mode = mode;
+        self
+    }
+
+    /// Apply [`TransportLimits`] to the MCP endpoints.
+    ///
+    /// Bodies over `max_message_size` are answered with `413 Payload Too
+    /// Large` and requests beyond `max_in_flight` with `503 Service
+    /// Unavailable`. [`into_rocket`](Self::into_rocket) also raises Rocket's
+    /// `string` data limit to match; with [`into_state`](Self::into_state),
+    /// configure `limits.string` yourself. `read_timeout` is not applied.
+    #[must_use]
+    pub fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.state = self.state.with_limits(limits.clone());
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Serve Kubernetes liveness and readiness probes at `/healthz` and
+    /// `/readyz`.
+    ///
+    /// `/healthz` answers `200` while the process responds. `/readyz`
+    /// answers `503` once shutdown begins. Add `?verbose` for a JSON report.
+    /// Use [`with_readiness_checks`](Self::with_readiness_checks) to make
+    /// readiness depend on your own components as well.
+    /// [`into_rocket`](Self::into_rocket) mounts the routes; with
+    /// [`into_state`](Self::into_state), see
+    /// [`HealthState`](crate::handler::HealthState).
+    #[must_use]
+    pub fn with_health_checks(self) -> Self {
+        let checker = HealthChecker::new(&self.state.server_info.name);
+        self.with_readiness_checks(checker)
+    }
+
+    /// Like [`with_health_checks`](Self::with_health_checks), with `/readyz`
+    /// also running `checker`'s checks (e.g. that the database is reachable).
+    #[must_use]
+    pub fn with_readiness_checks(mut self, checker: HealthChecker) -> Self {
+        self.state.health = Some(HealthProbes::new(checker));
+        self
+    }
+
+    /// Record Prometheus metrics and serve them at `GET /metrics`.
+    ///
+    /// Exports request counts and latencies per JSON-RPC method, error counts
+    /// by JSON-RPC error code, open sessions, open SSE streams, and tool calls
+    /// and failures per tool. [`into_rocket`](Self::into_rocket) mounts the
+    /// route; with [`into_state`](Self::into_state), see
+    /// [`MetricsState`](crate::handler::MetricsState). Requires the
+    /// `prometheus` feature.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_metrics(self) -> Self {
+        let metrics =
+            mcpkit_transport::McpMetrics::new().expect("MCP metric names are valid and distinct");
+        self.with_prometheus(metrics)
+    }
+
+    /// Like [`with_metrics`](Self::with_metrics), recording into an existing
+    /// [`McpMetrics`](mcpkit_transport::McpMetrics), e.g. one shared with a
+    /// transport's `TelemetryLayer`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_prometheus(mut self, metrics: mcpkit_transport::McpMetrics) -> Self {
+        self.state.metrics = Some(metrics);
+        self
+    }
+
     /// Build a Rocket instance with MCP routes.
     ///
     /// Note: Due to Rocket's type system constraints, this method creates
@@ -124,8 +278,33 @@ where
     /// macro in your application to generate the routes.
     #[must_use]
     pub fn into_rocket(self) -> Rocket<Build> {
+        #[cfg(feature = "prometheus")]
+        let metrics = crate::handler::MetricsState::from_state(&self.state);
+        let health = crate::handler::HealthState::from_state(&self.state);
         let mut rocket = rocket::build().manage(self.state);
 
+        if let Some(health) = health {
+            rocket = rocket.manage(health).mount(
+                "/",
+                rocket::routes![crate::handler::liveness, crate::handler::readiness],
+            );
+        }
+
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = metrics {
+            rocket = rocket
+                .manage(metrics)
+                .mount("/", rocket::routes![crate::handler::metrics]);
+        }
+
+        if let Some(limits) = self.limits {
+            let figment = rocket
+                .figment()
+                .clone()
+                .merge(("limits.string", limits.max_message_size));
+            rocket = rocket.configure(figment);
+        }
+
         if self.enable_cors {
             rocket = rocket.attach(Cors);
         }
@@ -139,12 +318,30 @@ where
         self.state
     }
 
+    /// Get a [`ShutdownHandle`] that stops [`launch`](Self::launch)
+    /// gracefully.
+    ///
+    /// Once triggered, new requests get `503 Service Unavailable`, open SSE
+    /// streams end, and Rocket's own graceful shutdown (bounded by its
+    /// `shutdown.grace` and `shutdown.mercy` settings) runs.
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.state.shutdown.clone()
+    }
+
     /// Launch the MCP server.
     ///
     /// This is a convenience method that provides a stdio-like experience.
     /// Note: You'll need to mount the routes separately using macros.
     pub async fn launch(self) -> Result<(), rocket::Error> {
-        let _ = self.into_rocket().launch().await?;
+        let shutdown = self.shutdown_handle();
+        let rocket = self.into_rocket().ignite().await?;
+        let notify = rocket.shutdown();
+        tokio::spawn(async move {
+            shutdown.wait().await;
+            notify.notify();
+        });
+        let _ = rocket.launch().await?;
         Ok(())
     }
 }
@@ -180,9 +377,11 @@ impl Fairing for Cors {
 
 /// Create MCP route handlers for a specific handler type.
 ///
-/// This macro generates the Rocket route handlers for your MCP server.
-/// Due to Rocket's type system constraints, the route handlers must be
-/// generated at compile time for your specific handler type.
+/// This macro generates the Rocket route handlers for your MCP server:
+/// `mcp_post` (`POST /mcp`), `mcp_sse` (`GET /mcp/sse`), and
+/// `mcp_task_events` (`GET /mcp/tasks/<task_id>/events`, one task's progress
+/// as SSE). Due to Rocket's type system constraints, the route handlers must
+/// be generated at compile time for your specific handler type.
 ///
 /// # Example
 ///
@@ -201,7 +400,7 @@ impl Fairing for Cors {
 ///
 ///     rocket::build()
 ///         .manage(state)
-///         .mount("/", routes![mcp_post, mcp_sse])
+///         .mount("/", routes![mcp_post, mcp_sse, mcp_task_events])
 ///         .launch()
 ///         .await?;
 ///
@@ -217,14 +416,24 @@ macro_rules! create_mcp_routes {
             version: $crate::handler::ProtocolVersionHeader,
             session: $crate::handler::SessionIdHeader,
             origin: $crate::handler::OriginHeader,
+            accept: $crate::handler::AcceptHeader,
             user: $crate::handler::VerifiedUserGuard,
-            body: String,
+            body: ::rocket::data::Capped<String>,
         ) -> $crate::handler::McpResponse {
+            // Rocket truncates bodies at its `string` limit; refuse those
+            // rather than parse a partial message.
+            if !body.is_complete() {
+                return $crate::handler::McpResponse::error(
+                    ::rocket::http::Status::PayloadTooLarge,
+                    "request body exceeds the configured limit".to_string(),
+                );
+            }
             $crate::handler::handle_mcp_post(
                 state.inner(),
                 version.0.as_deref(),
                 session.0,
                 origin.0.as_deref(),
+                accept.0.as_deref(),
                 user.0,
                 &body,
             )
@@ -263,6 +472,28 @@ macro_rules! create_mcp_routes {
             }
             ::std::result::Result::Ok($crate::handler::handle_sse(state.inner(), session.0))
         }
+
+        #[rocket::get("/mcp/tasks/<task_id>/events")]
+        fn mcp_task_events(
+            state: &::rocket::State<$crate::McpState<$handler_type>>,
+            task_id: ::std::string::String,
+            session: $crate::handler::SessionIdHeader,
+            origin: $crate::handler::OriginHeader,
+            last_event_id: $crate::handler::LastEventIdHeader,
+            user: $crate::handler::VerifiedUserGuard,
+        ) -> ::std::result::Result<
+            ::rocket::response::stream::EventStream![],
+            ::rocket::http::Status,
+        > {
+            $crate::handler::handle_task_events(
+                state.inner(),
+                task_id,
+                session.0,
+                origin.0.as_deref(),
+                last_event_id.0,
+                user.0,
+            )
+        }
     };
 }
 
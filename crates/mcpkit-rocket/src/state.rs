@@ -2,7 +2,8 @@
 
 use crate::session::SessionStore;
 use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
-use mcpkit_server::ServerHandler;
+use mcpkit_server::service::{McpService, ResponseMode, ServiceLimits};
+use mcpkit_server::{ServerHandler, ShutdownHandle};
 use mcpkit_transport::http::OriginValidator;
 use std::sync::Arc;
 
@@ -35,6 +36,29 @@ pub struct McpState<H> {
     pub list_page_size: Option<usize>,
     /// Optional completion handler for `completion/complete`.
     pub completion: Option<Arc<dyn mcpkit_server::dispatch::DynCompletionHandler>>,
+    /// Per-session request rate limits; `None` disables rate limiting.
+    pub rate_limiter: Option<mcpkit_server::RateLimiter>,
+    /// Authorization policy for `tools/call`; `None` allows every call.
+    pub tool_authorizer: Option<mcpkit_server::ToolAuthorizer>,
+    /// Audit log for tool calls and resource reads; `None` disables it.
+    pub audit_logger: Option<mcpkit_server::AuditLogger>,
+    /// How tool, resource, and prompt invocations are traced.
+    pub invocation_tracing: mcpkit_server::InvocationTracing,
+    /// Request metrics and the `metrics://server` resource; `None` disables
+    /// them.
+    pub server_metrics: Option<mcpkit_server::MetricsLayer>,
+    /// Message size and in-flight limits applied to each request.
+    pub limits: ServiceLimits,
+    /// Body format for POSTed requests when the `Accept` header allows both.
+    pub response_mode: ResponseMode,
+    /// Graceful shutdown trigger; once fired, new requests are rejected and
+    /// SSE streams end.
+    pub shutdown: ShutdownHandle,
+    /// Answers `/healthz` and `/readyz`; `None` leaves them unmounted.
+    pub health: Option<mcpkit_server::HealthProbes>,
+    /// Prometheus metrics recorded for each request; `None` disables them.
+    #[cfg(feature = "prometheus")]
+    pub metrics: Option<mcpkit_transport::McpMetrics>,
 }
 
 impl<H> McpState<H>
@@ -52,6 +76,17 @@ where
             origin_validator: Arc::new(OriginValidator::default()),
             list_page_size: None,
             completion: None,
+            rate_limiter: None,
+            tool_authorizer: None,
+            audit_logger: None,
+            invocation_tracing: mcpkit_server::InvocationTracing::default(),
+            server_metrics: None,
+            limits: ServiceLimits::default(),
+            response_mode: ResponseMode::default(),
+            shutdown: ShutdownHandle::new(),
+            health: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
         }
     }
 
@@ -75,6 +110,17 @@ impl<H> Clone for McpState<H> {
             origin_validator: Arc::clone(&self.origin_validator),
             list_page_size: self.list_page_size,
             completion: self.completion.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            tool_authorizer: self.tool_authorizer.clone(),
+            audit_logger: self.audit_logger.clone(),
+            invocation_tracing: self.invocation_tracing.clone(),
+            server_metrics: self.server_metrics.clone(),
+            limits: self.limits.clone(),
+            response_mode: self.response_mode,
+            shutdown: self.shutdown.clone(),
+            health: self.health.clone(),
+            #[cfg(feature = "prometheus")]
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -101,6 +147,57 @@ impl<H> McpState<H> {
         self
     }
 
+    /// Reject requests over `config`'s quotas, keyed by `mcp-session-id`.
+    #[must_use]
+    pub fn with_rate_limit(mut self, config: mcpkit_server::RateLimitConfig) -> Self {
+        self.rate_limiter = Some(mcpkit_server::RateLimiter::new(config));
+        self
+    }
+
+    /// Refuse `tools/call` requests the authorizer's policy denies.
+    #[must_use]
+    pub fn with_tool_authorizer(mut self, authorizer: mcpkit_server::ToolAuthorizer) -> Self {
+        self.tool_authorizer = Some(authorizer);
+        self
+    }
+
+    /// Record tool calls and resource reads to `logger`.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: mcpkit_server::AuditLogger) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Trace tool, resource, and prompt invocations with `tracing`.
+    #[must_use]
+    pub fn with_invocation_tracing(mut self, tracing: mcpkit_server::InvocationTracing) -> Self {
+        self.invocation_tracing = tracing;
+        self
+    }
+
+    /// Record every request into `metrics`.
+    #[must_use]
+    pub fn with_server_metrics(mut self, metrics: mcpkit_server::MetricsLayer) -> Self {
+        self.server_metrics = Some(metrics);
+        self
+    }
+
+    /// Reject bodies over `limits.max_message_size` and requests beyond
+    /// `limits.max_in_flight`.
+    #[must_use]
+    pub fn with_limits(mut self, limits: mcpkit_transport::TransportLimits) -> Self {
+        self.limits = ServiceLimits::new(limits);
+        self
+    }
+
+    /// Answer POSTed requests in `mode` when the client accepts both JSON
+    /// and SSE.
+    #[must_use]
+    pub const fn with_response_mode(mut self, mode: ResponseMode) -> Self {
+        self.response_mode = mode;
+        self
+    }
+
     /// Set the default task retention (milliseconds) for each session's task
     /// store, applied when a task-augmented `tools/call` omits a `ttl`. Pass
     /// `None` for unlimited retention. Defaults to
@@ -110,6 +207,15 @@ impl<H> McpState<H> {
         self.sessions.default_task_ttl = default_task_ttl;
         self
     }
+
+    /// Apply `config` to the SSE sessions, and its idle timeout to the HTTP
+    /// sessions.
+    #[must_use]
+    pub fn with_session_config(mut self, config: mcpkit_server::SessionConfig) -> Self {
+        self.sessions = self.sessions.with_idle_timeout(config.idle_timeout);
+        self.sse_sessions = self.sse_sessions.with_session_config(config);
+        self
+    }
 }
 
 impl<H: mcpkit_server::ServerHandler> McpState<H> {
@@ -127,6 +233,48 @@ impl<H: mcpkit_server::ServerHandler> McpState<H> {
     }
 }
 
+impl<H: ServerHandler> McpState<H> {
+    /// A transport-agnostic [`McpService`] over this state's handler,
+    /// sessions, and settings. The handlers delegate request processing to it.
+    #[must_use]
+    pub fn service(&self) -> McpService<H, SessionStore> {
+        let mut service = McpService::from_parts(
+            Arc::clone(&self.handler),
+            self.sessions.clone(),
+            self.server_info.clone(),
+        )
+        .with_origin_validator(Arc::clone(&self.origin_validator));
+        if let Some(page_size) = self.list_page_size {
+            service = service.with_list_page_size(page_size);
+        }
+        if let Some(completion) = &self.completion {
+            service = service.with_shared_completion(Arc::clone(completion));
+        }
+        if let Some(limiter) = &self.rate_limiter {
+            service = service.with_rate_limiter(limiter.clone());
+        }
+        if let Some(authorizer) = &self.tool_authorizer {
+            service = service.with_tool_authorizer(authorizer.clone());
+        }
+        if let Some(logger) = &self.audit_logger {
+            service = service.with_audit_logger(logger.clone());
+        }
+        service = service.with_invocation_tracing(self.invocation_tracing.clone());
+        if let Some(metrics) = &self.server_metrics {
+            service = service.with_server_metrics(metrics.clone());
+        }
+        service = service
+            .with_limits(self.limits.clone())
+            .with_response_mode(self.response_mode)
+            .with_shutdown(self.shutdown.clone());
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.metrics {
+            service = service.with_metrics(metrics.clone());
+        }
+        service
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
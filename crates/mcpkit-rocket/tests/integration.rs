@@ -117,7 +117,7 @@ fn create_test_client() -> Client {
     let state = McpRouter::new(TestHandler).into_state();
     let rocket = rocket::build()
         .manage(state)
-        .mount("/", rocket::routes![mcp_post, mcp_sse]);
+        .mount("/", rocket::routes![mcp_post, mcp_sse, mcp_task_events]);
     Client::tracked(rocket).expect("valid rocket instance")
 }
 
@@ -291,7 +291,7 @@ fn test_cors_headers() {
     let state = McpRouter::new(TestHandler).into_state();
     let rocket = rocket::build()
         .manage(state)
-        .mount("/", rocket::routes![mcp_post, mcp_sse])
+        .mount("/", rocket::routes![mcp_post, mcp_sse, mcp_task_events])
         .attach(Cors);
     let client = Client::tracked(rocket).expect("valid rocket instance");
 
@@ -312,3 +312,133 @@ fn test_cors_headers() {
             .is_some()
     );
 }
+
+#[test]
+fn test_with_limits() {
+    let client = |max| {
+        let rocket = McpRouter::new(TestHandler)
+            .with_limits(mcpkit_transport::TransportLimits::new().with_max_message_size(max))
+            .into_rocket()
+            .mount("/", rocket::routes![mcp_post, mcp_sse, mcp_task_events]);
+        Client::tracked(rocket).expect("valid rocket instance")
+    };
+    // Larger than Rocket's default 8 KiB string limit.
+    let body = format!(
+        r#"{{"jsonrpc":"2.0","method":"ping","id":1,"params":{{"pad":"{}"}}}}"#,
+        "x".repeat(16 * 1024)
+    );
+    let post = |client: &Client| {
+        client
+            .post("/mcp")
+            .header(ContentType::JSON)
+            .header(Header::new("mcp-protocol-version", "2025-11-25"))
+            .body(&body)
+            .dispatch()
+            .status()
+    };
+
+    assert_eq!(post(&client(1024 * 1024)), Status::Ok);
+    assert_eq!(post(&client(1024)), Status::PayloadTooLarge);
+}
+
+#[test]
+fn test_health_checks() {
+    let unmounted = Client::tracked(McpRouter::new(TestHandler).into_rocket()).unwrap();
+    assert_eq!(
+        unmounted.get("/healthz").dispatch().status(),
+        Status::NotFound
+    );
+
+    let mut checker = mcpkit_server::HealthChecker::new("test-server");
+    checker.add_check("database", mcpkit_server::ComponentHealth::healthy);
+    let router = McpRouter::new(TestHandler).with_readiness_checks(checker);
+    let shutdown = router.shutdown_handle();
+    let client = Client::tracked(router.into_rocket()).expect("valid rocket instance");
+
+    let response = client.get("/healthz").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "ok");
+
+    let response = client.get("/readyz?verbose").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let report: serde_json::Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    assert_eq!(report["components"]["database"]["status"], "healthy");
+
+    shutdown.shutdown();
+    let response = client.get("/readyz").dispatch();
+    assert_eq!(response.status(), Status::ServiceUnavailable);
+    assert_eq!(response.into_string().unwrap(), "not ready");
+}
+
+#[cfg(feature = "prometheus")]
+#[test]
+fn test_with_metrics() {
+    let rocket = McpRouter::new(TestHandler)
+        .with_metrics()
+        .into_rocket()
+        .mount("/", rocket::routes![mcp_post, mcp_sse, mcp_task_events]);
+    let client = Client::tracked(rocket).expect("valid rocket instance");
+
+    let response = client
+        .post("/mcp")
+        .header(ContentType::JSON)
+        .header(Header::new("mcp-protocol-version", "2025-11-25"))
+        .body(r#"{"jsonrpc":"2.0","method":"tools/call","params":{"name":"missing"},"id":1}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client.get("/metrics").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_string().unwrap();
+    assert!(
+        body.contains(r#"mcp_requests_total{method="tools/call"} 1"#),
+        "{body}"
+    );
+    assert!(
+        body.contains(r#"mcp_tool_errors_total{tool="missing"} 1"#),
+        "{body}"
+    );
+    assert!(body.contains("mcp_active_sessions 1"), "{body}");
+}
+
+#[test]
+fn test_task_events() {
+    let state = McpRouter::new(TestHandler).into_state();
+    let session_id = state.sessions.create();
+    let tasks = state.sessions.tasks(&session_id).unwrap();
+    let handle = tasks.create(None);
+    handle.record_progress(1.0, None, None).unwrap();
+    handle.complete(serde_json::json!({})).unwrap();
+    let path = format!("/mcp/tasks/{}/events", handle.id().as_str());
+    let rocket = rocket::build()
+        .manage(state)
+        .mount("/", rocket::routes![mcp_post, mcp_sse, mcp_task_events]);
+    let client = Client::tracked(rocket).expect("valid rocket instance");
+
+    let response = client
+        .get(path.clone())
+        .header(Header::new("mcp-session-id", session_id.clone()))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_string().unwrap();
+    let events: Vec<_> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("event:"))
+        .collect();
+    assert_eq!(events, ["status", "progress", "status"], "{body}");
+
+    let response = client
+        .get(path)
+        .header(Header::new("mcp-session-id", session_id.clone()))
+        .header(Header::new("last-event-id", "2"))
+        .dispatch();
+    let body = response.into_string().unwrap();
+    assert_eq!(body.matches("event:").count(), 1, "{body}");
+    assert!(body.contains("id:3"), "{body}");
+
+    let response = client
+        .get("/mcp/tasks/unknown/events")
+        .header(Header::new("mcp-session-id", session_id))
+        .dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
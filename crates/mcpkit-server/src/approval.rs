@@ -0,0 +1,489 @@
+//! Human approval of destructive tool calls.
+//!
+//! Tools annotated `destructiveHint` (`#[tool(destructive = true)]`) may
+//! delete or overwrite data. An [`ApprovingToolHandler`] asks the user to
+//! confirm each call to one before it runs, by sending the client an
+//! `elicitation/create` confirmation request:
+//!
+//! - if the user accepts (and does not untick `confirmed`), the tool runs;
+//! - if they decline or cancel, the call fails with
+//!   [`McpError::UserRejected`] and the tool never runs.
+//!
+//! The check fails closed: when the client does not support elicitation, the
+//! call fails with the elicitation error instead of running unapproved.
+//! Calls to tools that are not destructive are forwarded untouched.
+//!
+//! The confirmation message is rendered from a template in which `{tool}` is
+//! replaced by the tool name and `{arguments}` by the call's arguments as
+//! JSON ([`DEFAULT_APPROVAL_MESSAGE`] unless set otherwise).
+//!
+//! Enable it with [`ServerBuilder::require_approval_for_destructive`] (or
+//! wrap a handler directly for the HTTP adapters).
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::approval::ApprovingToolHandler;
+//! use serde_json::json;
+//!
+//! # struct Tools;
+//! let tools = ApprovingToolHandler::new(Tools)
+//!     .message_template("Really run {tool}? {arguments}");
+//! let args = json!({ "path": "/tmp/cache" }).as_object().cloned().unwrap();
+//! assert_eq!(
+//!     tools.approval_message("delete", &args),
+//!     r#"Really run delete? {"path":"/tmp/cache"}"#
+//! );
+//! ```
+//!
+//! [`ServerBuilder::require_approval_for_destructive`]: crate::builder::ServerBuilder::require_approval_for_destructive
+
+use crate::context::Context;
+use crate::handler::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_core::error::McpError;
+use mcpkit_core::pagination::Page;
+use mcpkit_core::types::{
+    ByteRange, ElicitRequest, GetPromptResult, Object, Prompt, Resource, ResourceContents,
+    ResourceTemplate, Tool, ToolOutput,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// The confirmation message used unless another template is set.
+pub const DEFAULT_APPROVAL_MESSAGE: &str = "The tool `{tool}` may make destructive changes. Allow it to run with these arguments?\n{arguments}";
+
+/// A [`ToolHandler`] decorator that asks the user to approve each call to a
+/// destructive tool before forwarding it.
+///
+/// The names of the destructive tools are read from the inner handler's
+/// [`list_tools`](ToolHandler::list_tools) on the first call and kept until
+/// [`on_tools_changed`](ToolHandler::on_tools_changed). If the listing fails,
+/// approval is asked for anyway and the listing is retried on the next call.
+pub struct ApprovingToolHandler<H> {
+    inner: H,
+    enabled: bool,
+    template: String,
+    destructive: Mutex<Option<Arc<HashSet<String>>>>,
+}
+
+impl<H> ApprovingToolHandler<H> {
+    /// Wrap `inner`, requiring approval for its destructive tools.
+    #[must_use]
+    pub fn new(inner: H) -> Self {
+        Self {
+            inner,
+            enabled: true,
+            template: DEFAULT_APPROVAL_MESSAGE.to_string(),
+            destructive: Mutex::new(None),
+        }
+    }
+
+    /// Turn the approval step on or off. When off, every call is forwarded.
+    #[must_use]
+    pub const fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the confirmation message template; `{tool}` and `{arguments}` are
+    /// substituted.
+    #[must_use]
+    pub fn message_template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Whether destructive calls need approval.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// The confirmation message for a call to `tool` with `args`.
+    #[must_use]
+    pub fn approval_message(&self, tool: &str, args: &Object) -> String {
+        let arguments = serde_json::to_string(args).unwrap_or_default();
+        self.template
+            .replace("{tool}", tool)
+            .replace("{arguments}", &arguments)
+    }
+
+    /// Unwrap, returning the inner handler.
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: ToolHandler> ApprovingToolHandler<H> {
+    /// Whether `name` is a destructive tool of the inner handler.
+    async fn is_destructive(&self, name: &str, ctx: &Context<'_>) -> bool {
+        let cached = self
+            .destructive
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(names) = cached {
+            return names.contains(name);
+        }
+        match self.inner.list_tools(ctx).await {
+            Ok(tools) => {
+                let names: HashSet<String> = tools
+                    .into_iter()
+                    .filter(Tool::is_destructive)
+                    .map(|tool| tool.name)
+                    .collect();
+                let destructive = names.contains(name);
+                *self
+                    .destructive
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(names));
+                destructive
+            }
+            Err(error) => {
+                tracing::warn!(%error, tool = name, "could not list tools; asking for approval");
+                true
+            }
+        }
+    }
+}
+
+impl<H: ToolHandler> ToolHandler for ApprovingToolHandler<H> {
+    async fn list_tools(&self, ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        self.inner.list_tools(ctx).await
+    }
+
+    fn list_tools_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Tool>, McpError>> + Send {
+        self.inner.list_tools_page(cursor, page_size, ctx)
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        args: Object,
+        ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        if self.enabled && self.is_destructive(name, ctx).await {
+            let message = self.approval_message(name, &args);
+            let answer = ctx.elicit(ElicitRequest::confirm(message)).await?;
+            if !answer.is_accepted() || answer.get_bool("confirmed") == Some(false) {
+                tracing::info!(tool = name, "destructive tool call not approved");
+                return Err(McpError::UserRejected {
+                    message: format!("call to tool `{name}` was not approved"),
+                    operation: "tools/call".to_string(),
+                });
+            }
+            tracing::debug!(tool = name, "destructive tool call approved");
+        }
+        self.inner.call_tool(name, args, ctx).await
+    }
+
+    async fn on_tools_changed(&self) {
+        *self
+            .destructive
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+        self.inner.on_tools_changed().await;
+    }
+}
+
+// Transparent forwarding of the other handler traits, so a combined handler
+// wrapped in `ApprovingToolHandler` stays a drop-in for the HTTP adapters.
+
+impl<H: ServerHandler> ServerHandler for ApprovingToolHandler<H> {
+    fn server_info(&self) -> ServerInfo {
+        self.inner.server_info()
+    }
+
+    fn capabilities(&self) -> ServerCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn instructions(&self) -> Option<String> {
+        self.inner.instructions()
+    }
+
+    fn on_initialized(&self, ctx: &Context<'_>) -> impl Future<Output = ()> + Send {
+        self.inner.on_initialized(ctx)
+    }
+
+    fn on_roots_list_changed(&self, ctx: &Context<'_>) -> impl Future<Output = ()> + Send {
+        self.inner.on_roots_list_changed(ctx)
+    }
+
+    fn on_shutdown(&self) -> impl Future<Output = ()> + Send {
+        self.inner.on_shutdown()
+    }
+
+    fn set_log_level(
+        &self,
+        level: crate::handler::LogLevel,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<(), McpError>> + Send {
+        self.inner.set_log_level(level, ctx)
+    }
+}
+
+impl<H: ResourceHandler> ResourceHandler for ApprovingToolHandler<H> {
+    fn list_resources(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<Resource>, McpError>> + Send {
+        self.inner.list_resources(ctx)
+    }
+
+    fn list_resources_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Resource>, McpError>> + Send {
+        self.inner.list_resources_page(cursor, page_size, ctx)
+    }
+
+    fn list_resource_templates(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<ResourceTemplate>, McpError>> + Send {
+        self.inner.list_resource_templates(ctx)
+    }
+
+    fn list_resource_templates_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<ResourceTemplate>, McpError>> + Send {
+        self.inner
+            .list_resource_templates_page(cursor, page_size, ctx)
+    }
+
+    fn read_resource(
+        &self,
+        uri: &str,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<ResourceContents>, McpError>> + Send {
+        self.inner.read_resource(uri, ctx)
+    }
+
+    fn read_resource_range(
+        &self,
+        uri: &str,
+        range: ByteRange,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<ResourceContents, McpError>> + Send {
+        self.inner.read_resource_range(uri, range, ctx)
+    }
+
+    fn subscribe(
+        &self,
+        uri: &str,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<bool, McpError>> + Send {
+        self.inner.subscribe(uri, ctx)
+    }
+
+    fn unsubscribe(
+        &self,
+        uri: &str,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<bool, McpError>> + Send {
+        self.inner.unsubscribe(uri, ctx)
+    }
+}
+
+impl<H: PromptHandler> PromptHandler for ApprovingToolHandler<H> {
+    fn list_prompts(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<Prompt>, McpError>> + Send {
+        self.inner.list_prompts(ctx)
+    }
+
+    fn list_prompts_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Prompt>, McpError>> + Send {
+        self.inner.list_prompts_page(cursor, page_size, ctx)
+    }
+
+    fn get_prompt(
+        &self,
+        name: &str,
+        args: Option<serde_json::Map<String, Value>>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<GetPromptResult, McpError>> + Send {
+        self.inner.get_prompt(name, args, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{ContextData, Peer};
+    use mcpkit_core::capability::ClientCapabilities;
+    use mcpkit_core::protocol::{Notification, RequestId, Response};
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::{ElicitResult, ToolAnnotations};
+    use serde_json::json;
+    use std::borrow::Cow;
+    use std::pin::Pin;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A client that answers every elicitation with `reply` and records the
+    /// messages it was shown.
+    struct Confirming {
+        reply: ElicitResult,
+        shown: Mutex<Vec<String>>,
+    }
+
+    impl Peer for Confirming {
+        fn notify(
+            &self,
+            _n: Notification,
+        ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn request(
+            &self,
+            method: Cow<'static, str>,
+            params: Option<Value>,
+        ) -> Pin<Box<dyn Future<Output = Result<Response, McpError>> + Send + '_>> {
+            assert_eq!(method, "elicitation/create");
+            let message = params.unwrap()["message"].as_str().unwrap().to_string();
+            self.shown.lock().unwrap().push(message);
+            let result = serde_json::to_value(&self.reply).unwrap();
+            Box::pin(async move { Ok(Response::success(RequestId::Number(1), result)) })
+        }
+    }
+
+    #[derive(Default)]
+    struct Files {
+        calls: AtomicUsize,
+        listings: AtomicUsize,
+        unlistable: bool,
+    }
+
+    impl ToolHandler for Files {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            self.listings.fetch_add(1, Ordering::SeqCst);
+            if self.unlistable {
+                return Err(McpError::internal("catalog unavailable"));
+            }
+            Ok(vec![
+                Tool::new("delete").annotations(ToolAnnotations::destructive()),
+                Tool::new("read"),
+            ])
+        }
+
+        async fn call_tool(
+            &self,
+            name: &str,
+            _args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ToolOutput::text(name))
+        }
+    }
+
+    async fn call(
+        tools: &ApprovingToolHandler<Files>,
+        peer: &Confirming,
+        name: &str,
+    ) -> Result<ToolOutput, McpError> {
+        let data = ContextData::new(
+            RequestId::Number(1),
+            ClientCapabilities::default().with_elicitation(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        );
+        let ctx = data.to_context(peer);
+        let args = json!({ "path": "/data" }).as_object().cloned().unwrap();
+        tools.call_tool(name, args, &ctx).await
+    }
+
+    #[tokio::test]
+    async fn destructive_calls_wait_for_approval() {
+        let tools = ApprovingToolHandler::new(Files::default()).message_template("Run {tool}?");
+        let approving = Confirming {
+            reply: ElicitResult::accepted(
+                json!({ "confirmed": true }).as_object().cloned().unwrap(),
+            ),
+            shown: Mutex::default(),
+        };
+        call(&tools, &approving, "delete").await.unwrap();
+        call(&tools, &approving, "read").await.unwrap();
+        assert_eq!(*approving.shown.lock().unwrap(), ["Run delete?"]);
+        assert_eq!(tools.inner.calls.load(Ordering::SeqCst), 2);
+
+        for reply in [
+            ElicitResult::declined(),
+            ElicitResult::cancelled(),
+            ElicitResult::accepted(json!({ "confirmed": false }).as_object().cloned().unwrap()),
+        ] {
+            let refusing = Confirming {
+                reply,
+                shown: Mutex::default(),
+            };
+            let err = call(&tools, &refusing, "delete").await.unwrap_err();
+            assert!(matches!(err, McpError::UserRejected { .. }));
+        }
+        assert_eq!(tools.inner.calls.load(Ordering::SeqCst), 2);
+
+        let disabled = ApprovingToolHandler::new(Files::default()).enabled(false);
+        let silent = Confirming {
+            reply: ElicitResult::declined(),
+            shown: Mutex::default(),
+        };
+        call(&disabled, &silent, "delete").await.unwrap();
+        assert!(silent.shown.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn destructive_tools_are_listed_once_until_they_change() {
+        let tools = ApprovingToolHandler::new(Files::default());
+        let approving = Confirming {
+            reply: ElicitResult::accepted(
+                json!({ "confirmed": true }).as_object().cloned().unwrap(),
+            ),
+            shown: Mutex::default(),
+        };
+        for name in ["delete", "read", "delete"] {
+            call(&tools, &approving, name).await.unwrap();
+        }
+        assert_eq!(tools.inner.listings.load(Ordering::SeqCst), 1);
+        assert_eq!(approving.shown.lock().unwrap().len(), 2);
+
+        tools.on_tools_changed().await;
+        call(&tools, &approving, "read").await.unwrap();
+        assert_eq!(tools.inner.listings.load(Ordering::SeqCst), 2);
+
+        // A failed listing asks for approval and is not cached.
+        let unlistable = ApprovingToolHandler::new(Files {
+            unlistable: true,
+            ..Files::default()
+        });
+        let refusing = Confirming {
+            reply: ElicitResult::declined(),
+            shown: Mutex::default(),
+        };
+        for _ in 0..2 {
+            let err = call(&unlistable, &refusing, "read").await.unwrap_err();
+            assert!(matches!(err, McpError::UserRejected { .. }));
+        }
+        assert_eq!(unlistable.inner.listings.load(Ordering::SeqCst), 2);
+        assert_eq!(unlistable.inner.calls.load(Ordering::SeqCst), 0);
+    }
+}
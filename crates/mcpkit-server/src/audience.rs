@@ -0,0 +1,202 @@
+//! Stripping content not intended for the recipient.
+//!
+//! Content blocks can name their intended audience in their annotations
+//! ([`Content::for_audience`](mcpkit_core::types::Content::for_audience)):
+//! a tool may return a summary for the user next to raw data for the model.
+//! When a server knows who reads its responses, for instance a client that
+//! shows tool results to the user without passing them to a model, an
+//! [`AudienceFilter`] removes the blocks meant for someone else before they
+//! are sent:
+//!
+//! - `tools/call` results keep only the `content` blocks intended for the
+//!   audience (task-augmented calls included);
+//! - `prompts/get` results keep only the messages whose content is.
+//!
+//! Blocks without an audience are intended for everyone and always kept.
+//! `structuredContent` is left alone.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_core::types::Role;
+//! use mcpkit_server::audience::AudienceFilter;
+//!
+//! let filter = AudienceFilter::new(Role::User);
+//! let mut result = serde_json::json!({
+//!     "content": [
+//!         { "type": "text", "text": "Done", "annotations": { "audience": ["user"] } },
+//!         { "type": "text", "text": "{...}", "annotations": { "audience": ["assistant"] } },
+//!     ]
+//! });
+//! filter.filter("tools/call", &mut result);
+//! assert_eq!(result["content"].as_array().unwrap().len(), 1);
+//! ```
+
+use crate::context::Context;
+use crate::middleware::{McpMiddleware, Next};
+use crate::server::RequestRouter;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::Role;
+use serde_json::Value;
+
+/// [`McpMiddleware`] that drops tool result content and prompt messages not
+/// intended for one audience.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudienceFilter {
+    audience: Role,
+}
+
+impl AudienceFilter {
+    /// Keep only content intended for `audience`.
+    #[must_use]
+    pub const fn new(audience: Role) -> Self {
+        Self { audience }
+    }
+
+    /// The audience content is kept for.
+    #[must_use]
+    pub const fn audience(&self) -> Role {
+        self.audience
+    }
+
+    /// Remove the blocks of a `method` result not intended for the audience.
+    ///
+    /// Results of methods other than `tools/call` and `prompts/get` are left
+    /// unchanged.
+    pub fn filter(&self, method: &str, result: &mut Value) {
+        match method {
+            "tools/call" => {
+                if let Some(Value::Array(content)) = result.get_mut("content") {
+                    content.retain(|block| self.is_for(block));
+                }
+            }
+            "prompts/get" => {
+                if let Some(Value::Array(messages)) = result.get_mut("messages") {
+                    messages
+                        .retain(|message| message.get("content").is_none_or(|c| self.is_for(c)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether a serialized content block is intended for the audience.
+    fn is_for(self, block: &Value) -> bool {
+        let Some(Value::Array(audience)) = block.pointer("/annotations/audience") else {
+            return true;
+        };
+        let role = self.audience.to_string();
+        audience.iter().any(|r| r.as_str() == Some(role.as_str()))
+    }
+}
+
+impl McpMiddleware for AudienceFilter {
+    async fn handle<R: RequestRouter>(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+        next: Next<'_, R>,
+    ) -> Result<Value, McpError> {
+        let mut result = next.run(method, params, ctx).await?;
+        self.filter(method, &mut result);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{ContextData, NoOpPeer};
+    use crate::middleware::Layered;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::{CallToolResult, Content, Object};
+    use serde_json::json;
+
+    /// Returns one block for each audience, and one for everyone.
+    struct Mixed;
+
+    impl Mixed {
+        fn result() -> Value {
+            serde_json::to_value(CallToolResult::content(vec![
+                Content::text("for the user").for_audience(Role::User),
+                Content::text("for the model").for_audience(Role::Assistant),
+                Content::text("for everyone"),
+            ]))
+            .unwrap()
+        }
+    }
+
+    impl RequestRouter for Mixed {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("mixed", "1.0.0")
+        }
+
+        async fn route(
+            &self,
+            method: &str,
+            _params: Option<&Value>,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            Ok(match method {
+                "prompts/get" => json!({ "messages": [
+                    { "role": "user", "content": { "type": "text", "text": "kept" } },
+                    { "role": "user", "content": {
+                        "type": "text", "text": "dropped",
+                        "annotations": { "audience": ["assistant"] }
+                    } },
+                ] }),
+                _ => Self::result(),
+            })
+        }
+
+        async fn call_tool_json(
+            &self,
+            _name: &str,
+            _args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            Ok(Self::result())
+        }
+    }
+
+    fn texts(result: &Value) -> Vec<&str> {
+        result["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|block| block["text"].as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn content_for_other_audiences_is_stripped() {
+        let router = Layered::new(Mixed, AudienceFilter::new(Role::User));
+        let data = ContextData::new(
+            RequestId::Number(1),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        );
+        let peer = NoOpPeer;
+        let ctx = data.to_context(&peer);
+
+        let result = router.route("tools/call", None, &ctx).await.unwrap();
+        assert_eq!(texts(&result), ["for the user", "for everyone"]);
+        let task = router
+            .call_tool_json("t", Object::new(), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(texts(&task), ["for the user", "for everyone"]);
+
+        let prompt = router.route("prompts/get", None, &ctx).await.unwrap();
+        assert_eq!(prompt["messages"].as_array().unwrap().len(), 1);
+        assert_eq!(prompt["messages"][0]["content"]["text"], "kept");
+
+        // Other methods are untouched.
+        let other = router.route("resources/read", None, &ctx).await.unwrap();
+        assert_eq!(texts(&other).len(), 3);
+    }
+}
@@ -0,0 +1,784 @@
+//! Audit logging of tool calls and resource reads.
+//!
+//! An [`AuditLogger`] turns each audited request into an [`AuditRecord`]:
+//! when it happened, the session and user, the method, the tool or resource,
+//! the arguments (hashed, redacted, or omitted), the outcome, and how long it
+//! took. Records go to one or more [`AuditSink`]s: [`JsonlSink`] appends them
+//! to a file as JSON lines, [`TracingSink`] emits them as `tracing` events,
+//! and any other destination is an `AuditSink` implementation (or closure)
+//! away.
+//!
+//! By default `tools/call` and `resources/read` are audited. Successful
+//! requests can be sampled with [`AuditLogger::sample_rate`]; failures and
+//! policy denials are always recorded unless
+//! [`AuditLogger::sample_failures`] says otherwise.
+//!
+//! For the [`ServerRuntime`](crate::ServerRuntime), apply an [`AuditLayer`]
+//! with [`Server::layer`](crate::Server::layer). Apply it after (outside) a
+//! [`ToolPolicyLayer`](crate::policy::ToolPolicyLayer) so denials are
+//! recorded too. The HTTP framework adapters take a logger via
+//! `McpRouter::with_audit_logger` and record the `mcp-session-id` with
+//! each entry.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use mcpkit_server::audit::{ArgumentsMode, AuditLogger, JsonlSink, TracingSink};
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let logger = AuditLogger::new()
+//!     .sink(JsonlSink::create("audit.jsonl")?)
+//!     .sink(TracingSink)
+//!     .arguments(ArgumentsMode::Redacted)
+//!     .redact_key("password")
+//!     .sample_rate(0.25);
+//! # let _ = logger;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::context::Context;
+use crate::middleware::{McpMiddleware, Next};
+use crate::policy::AUDIT_TARGET;
+use crate::server::RequestRouter;
+use chrono::{DateTime, Utc};
+use mcpkit_core::auth::VerifiedUser;
+use mcpkit_core::debug::{REDACTED, RecordingPolicy};
+use mcpkit_core::error::{JsonRpcError, McpError, codes};
+use mcpkit_core::protocol::RequestId;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How an audited request ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum AuditOutcome {
+    /// The request succeeded.
+    Success,
+    /// The tool ran but reported an error result (`isError: true`).
+    ToolError,
+    /// An authorization policy refused the request.
+    Denied {
+        /// The denial reason, if disclosed.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// The request failed with a JSON-RPC error.
+    Error {
+        /// The JSON-RPC error code.
+        code: i32,
+        /// The error message.
+        message: String,
+    },
+}
+
+impl AuditOutcome {
+    /// Whether the request succeeded outright.
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+
+    /// Classify a request's result as sent to the client.
+    #[must_use]
+    pub fn from_result(result: Result<&Value, &JsonRpcError>) -> Self {
+        match result {
+            Ok(value) if value.get("isError").and_then(Value::as_bool) == Some(true) => {
+                Self::ToolError
+            }
+            Ok(_) => Self::Success,
+            Err(e) if e.code == codes::TOOL_ACCESS_DENIED => Self::Denied {
+                reason: e
+                    .data
+                    .as_ref()
+                    .and_then(|d| d.get("reason"))
+                    .and_then(Value::as_str)
+                    .map(String::from),
+            },
+            Err(e) => Self::Error {
+                code: e.code,
+                message: e.message.clone(),
+            },
+        }
+    }
+}
+
+/// One audited request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// When the request arrived.
+    pub timestamp: DateTime<Utc>,
+    /// The session the request belongs to, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// The JSON-RPC request id.
+    pub request_id: RequestId,
+    /// The JSON-RPC method.
+    pub method: String,
+    /// The tool called, for `tools/call`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// The resource read, for `resources/read`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+    /// The verified user's subject (`sub`), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// SHA-256 of the arguments as canonical JSON, hex-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments_hash: Option<String>,
+    /// The arguments, after redaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+    /// How the request ended.
+    pub outcome: AuditOutcome,
+    /// How long the request took, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// A destination for [`AuditRecord`]s.
+///
+/// Implemented for closures taking a `&AuditRecord`.
+pub trait AuditSink: Send + Sync {
+    /// Write one record. Sinks report their own I/O failures; the request
+    /// is never failed because of them.
+    fn record(&self, record: &AuditRecord);
+
+    /// Flush buffered records.
+    fn flush(&self) {}
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(&AuditRecord) + Send + Sync,
+{
+    fn record(&self, record: &AuditRecord) {
+        self(record);
+    }
+}
+
+impl<S: AuditSink + ?Sized> AuditSink for Arc<S> {
+    fn record(&self, record: &AuditRecord) {
+        (**self).record(record);
+    }
+
+    fn flush(&self) {
+        (**self).flush();
+    }
+}
+
+/// Appends records to a writer as JSON lines.
+///
+/// Each record is flushed as it is written, so the trail survives a crash.
+pub struct JsonlSink {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl JsonlSink {
+    /// Write records to `writer`.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+        }
+    }
+
+    /// Append records to the file at `path`, creating it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+
+    fn write(&self, record: &AuditRecord) -> io::Result<()> {
+        let line = serde_json::to_string(record)?;
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| io::Error::other("audit writer poisoned"))?;
+        writeln!(writer, "{line}")?;
+        writer.flush()
+    }
+}
+
+impl AuditSink for JsonlSink {
+    fn record(&self, record: &AuditRecord) {
+        if let Err(e) = self.write(record) {
+            warn!(target: AUDIT_TARGET, error = %e, "Failed to write audit record");
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl fmt::Debug for JsonlSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonlSink").finish_non_exhaustive()
+    }
+}
+
+/// Emits records as `info` events on the `mcpkit::audit` tracing target.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+impl AuditSink for TracingSink {
+    fn record(&self, record: &AuditRecord) {
+        let outcome = serde_json::to_value(&record.outcome).unwrap_or_default();
+        info!(
+            target: AUDIT_TARGET,
+            timestamp = %record.timestamp.to_rfc3339(),
+            session_id = record.session_id.as_deref(),
+            request_id = %record.request_id,
+            method = %record.method,
+            tool = record.tool.as_deref(),
+            uri = record.uri.as_deref(),
+            subject = record.subject.as_deref(),
+            arguments_hash = record.arguments_hash.as_deref(),
+            arguments = record.arguments.as_ref().map(tracing::field::display),
+            outcome = %outcome,
+            duration_ms = record.duration_ms,
+            "Audit"
+        );
+    }
+}
+
+/// What an [`AuditRecord`] keeps of the request arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArgumentsMode {
+    /// Record nothing about the arguments.
+    Omit,
+    /// Record only a SHA-256 hash, so equal arguments can be correlated.
+    #[default]
+    Hash,
+    /// Record the arguments with secrets redacted, plus the hash.
+    Redacted,
+}
+
+/// Records audited requests to [`AuditSink`]s.
+///
+/// Cloning is cheap and clones share their sinks.
+#[derive(Clone)]
+pub struct AuditLogger {
+    sinks: Vec<Arc<dyn AuditSink>>,
+    methods: Vec<String>,
+    sample_rate: f64,
+    sample_failures: bool,
+    arguments: ArgumentsMode,
+    redact_keys: Vec<String>,
+    policy: RecordingPolicy,
+}
+
+impl Default for AuditLogger {
+    fn default() -> Self {
+        Self {
+            sinks: Vec::new(),
+            methods: vec!["tools/call".to_string(), "resources/read".to_string()],
+            sample_rate: 1.0,
+            sample_failures: false,
+            arguments: ArgumentsMode::Hash,
+            redact_keys: Vec::new(),
+            policy: RecordingPolicy::new(),
+        }
+    }
+}
+
+impl AuditLogger {
+    /// Create a logger auditing `tools/call` and `resources/read`, with no
+    /// sinks yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send records to `sink` as well.
+    #[must_use]
+    pub fn sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Audit `method` too (e.g. `prompts/get`).
+    #[must_use]
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.methods.push(method.into());
+        self
+    }
+
+    /// Audit every request, whatever its method.
+    #[must_use]
+    pub fn all_methods(mut self) -> Self {
+        self.methods = vec!["*".to_string()];
+        self
+    }
+
+    /// Record only this fraction (0.0 to 1.0) of successful requests.
+    #[must_use]
+    pub const fn sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Apply the sample rate to failed and denied requests as well. By
+    /// default every failure is recorded.
+    #[must_use]
+    pub const fn sample_failures(mut self, sample: bool) -> Self {
+        self.sample_failures = sample;
+        self
+    }
+
+    /// Choose what records keep of the arguments (a hash by default).
+    #[must_use]
+    pub const fn arguments(mut self, mode: ArgumentsMode) -> Self {
+        self.arguments = mode;
+        self
+    }
+
+    /// Replace the value of every argument named `key`, at any depth, with
+    /// `<redacted>` in recorded arguments. Matching ignores case.
+    #[must_use]
+    pub fn redact_key(mut self, key: impl Into<String>) -> Self {
+        self.redact_keys.push(key.into());
+        self
+    }
+
+    /// Never record the arguments of tools `policy` excludes (including
+    /// tools marked `no_record`); only their hash is kept.
+    #[must_use]
+    pub fn recording_policy(mut self, policy: RecordingPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Whether requests for `method` are audited.
+    #[must_use]
+    pub fn audits(&self, method: &str) -> bool {
+        self.methods.iter().any(|m| m == "*" || m == method)
+    }
+
+    /// Flush every sink.
+    pub fn flush(&self) {
+        for sink in &self.sinks {
+            sink.flush();
+        }
+    }
+
+    /// Build the record for a finished request, or `None` if it isn't
+    /// audited or sampling skipped it.
+    pub(crate) fn build_record(
+        &self,
+        request: &AuditedRequest<'_>,
+        started: DateTime<Utc>,
+        elapsed: Duration,
+        outcome: AuditOutcome,
+    ) -> Option<AuditRecord> {
+        let AuditedRequest {
+            session_id,
+            request_id,
+            method,
+            params,
+            user,
+        } = *request;
+        if !self.audits(method) {
+            return None;
+        }
+        if (outcome.is_success() || self.sample_failures) && !self.sampled() {
+            return None;
+        }
+
+        let field = |name: &str| {
+            params
+                .and_then(|p| p.get(name))
+                .and_then(Value::as_str)
+                .map(String::from)
+        };
+        let tool = (method == "tools/call").then(|| field("name")).flatten();
+        let args = if method == "tools/call" {
+            params.and_then(|p| p.get("arguments"))
+        } else {
+            params
+        };
+        let args = args.filter(|a| !a.is_null());
+        let (arguments_hash, arguments) = match self.arguments {
+            ArgumentsMode::Omit => (None, None),
+            ArgumentsMode::Hash => (args.map(hash), None),
+            ArgumentsMode::Redacted => {
+                let excluded = tool.as_deref().is_some_and(|t| self.policy.is_excluded(t));
+                let redacted = args.filter(|_| !excluded).map(|a| self.redact(a));
+                (args.map(hash), redacted)
+            }
+        };
+
+        Some(AuditRecord {
+            timestamp: started,
+            session_id: session_id.map(String::from),
+            request_id: request_id.clone(),
+            method: method.to_string(),
+            tool,
+            uri: (method == "resources/read").then(|| field("uri")).flatten(),
+            subject: user.map(|u| u.subject.clone()),
+            arguments_hash,
+            arguments,
+            outcome,
+            duration_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+        })
+    }
+
+    /// Send a record to every sink.
+    pub fn emit(&self, record: &AuditRecord) {
+        for sink in &self.sinks {
+            sink.record(record);
+        }
+    }
+
+    fn sampled(&self) -> bool {
+        self.sample_rate >= 1.0 || rand::thread_rng().gen_bool(self.sample_rate)
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| {
+                        let secret = self.redact_keys.iter().any(|r| r.eq_ignore_ascii_case(k));
+                        let v = if secret {
+                            Value::from(REDACTED)
+                        } else {
+                            self.redact(v)
+                        };
+                        (k.clone(), v)
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.redact(v)).collect()),
+            other => other.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for AuditLogger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditLogger")
+            .field("sinks", &self.sinks.len())
+            .field("methods", &self.methods)
+            .field("sample_rate", &self.sample_rate)
+            .field("sample_failures", &self.sample_failures)
+            .field("arguments", &self.arguments)
+            .field("redact_keys", &self.redact_keys)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The parts of a request an [`AuditRecord`] is built from.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AuditedRequest<'a> {
+    pub(crate) session_id: Option<&'a str>,
+    pub(crate) request_id: &'a RequestId,
+    pub(crate) method: &'a str,
+    pub(crate) params: Option<&'a Value>,
+    pub(crate) user: Option<&'a VerifiedUser>,
+}
+
+/// SHA-256 of `value` as canonical JSON (object keys sorted), hex-encoded.
+fn hash(value: &Value) -> String {
+    let digest = Sha256::digest(value.to_string().as_bytes());
+    digest.iter().fold(String::with_capacity(64), |mut hex, b| {
+        use std::fmt::Write as _;
+        let _ = write!(hex, "{b:02x}");
+        hex
+    })
+}
+
+/// [`McpMiddleware`] that audits requests through an [`AuditLogger`].
+#[derive(Debug, Clone)]
+pub struct AuditLayer {
+    logger: AuditLogger,
+    session_id: Option<String>,
+}
+
+impl AuditLayer {
+    /// Create a layer recording to `logger`.
+    #[must_use]
+    pub const fn new(logger: AuditLogger) -> Self {
+        Self {
+            logger,
+            session_id: None,
+        }
+    }
+
+    /// Label this connection's records with `session_id`.
+    #[must_use]
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// The underlying logger.
+    #[must_use]
+    pub const fn logger(&self) -> &AuditLogger {
+        &self.logger
+    }
+}
+
+impl McpMiddleware for AuditLayer {
+    async fn handle<R: RequestRouter>(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+        next: Next<'_, R>,
+    ) -> Result<Value, McpError> {
+        if !self.logger.audits(method) {
+            return next.run(method, params, ctx).await;
+        }
+        let started = Utc::now();
+        let clock = Instant::now();
+        let result = next.run(method, params, ctx).await;
+        let outcome = match &result {
+            Ok(value) => AuditOutcome::from_result(Ok(value)),
+            Err(e) => AuditOutcome::from_result(Err(&JsonRpcError::from(e))),
+        };
+        let request = AuditedRequest {
+            session_id: self.session_id.as_deref(),
+            request_id: ctx.request_id,
+            method,
+            params,
+            user: ctx.extension::<VerifiedUser>(),
+        };
+        if let Some(record) = self
+            .logger
+            .build_record(&request, started, clock.elapsed(), outcome)
+        {
+            self.logger.emit(&record);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{ContextData, NoOpPeer};
+    use crate::extensions::Extensions;
+    use crate::middleware::Layered;
+    use crate::policy::{DeclarativePolicy, ToolPolicyLayer};
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use serde_json::json;
+
+    /// Fails `tools/call` for `broken`, succeeds otherwise.
+    struct Echo;
+
+    impl RequestRouter for Echo {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("echo", "1.0.0")
+        }
+
+        async fn route(
+            &self,
+            method: &str,
+            params: Option<&Value>,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            match params.and_then(|p| p.get("name")).and_then(Value::as_str) {
+                Some("broken") => Err(McpError::internal("boom")),
+                Some("failing") => Ok(json!({ "content": [], "isError": true })),
+                _ => Ok(json!({ "method": method })),
+            }
+        }
+    }
+
+    fn ctx_data() -> ContextData {
+        ContextData::new(
+            RequestId::Number(3),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        )
+    }
+
+    fn collector() -> (Arc<Mutex<Vec<AuditRecord>>>, impl AuditSink) {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let records = records.clone();
+            move |r: &AuditRecord| records.lock().unwrap().push(r.clone())
+        };
+        (records, sink)
+    }
+
+    #[tokio::test]
+    async fn records_tool_calls_and_reads() {
+        let (records, sink) = collector();
+        let layer = AuditLayer::new(AuditLogger::new().sink(sink)).with_session_id("s-1");
+        let router = Layered::new(Echo, layer);
+
+        let data = ctx_data();
+        let peer = NoOpPeer;
+        let mut extensions = Extensions::new();
+        extensions.insert(VerifiedUser::new("alice"));
+        let ctx = data.to_context(&peer).with_extensions(&extensions);
+
+        let call = json!({ "name": "search", "arguments": { "q": "x" } });
+        router.route("tools/call", Some(&call), &ctx).await.unwrap();
+        let read = json!({ "uri": "file:///a" });
+        router
+            .route("resources/read", Some(&read), &ctx)
+            .await
+            .unwrap();
+        router.route("tools/list", None, &ctx).await.unwrap();
+        let broken = json!({ "name": "broken" });
+        router
+            .route("tools/call", Some(&broken), &ctx)
+            .await
+            .unwrap_err();
+        let failing = json!({ "name": "failing" });
+        router
+            .route("tools/call", Some(&failing), &ctx)
+            .await
+            .unwrap();
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 4, "tools/list is not audited by default");
+        let first = &records[0];
+        assert_eq!(first.session_id.as_deref(), Some("s-1"));
+        assert_eq!(first.request_id, RequestId::Number(3));
+        assert_eq!(first.tool.as_deref(), Some("search"));
+        assert_eq!(first.subject.as_deref(), Some("alice"));
+        assert_eq!(
+            first.arguments_hash.as_deref(),
+            Some(hash(&json!({ "q": "x" })).as_str())
+        );
+        assert!(first.arguments.is_none());
+        assert_eq!(first.outcome, AuditOutcome::Success);
+        assert_eq!(records[1].uri.as_deref(), Some("file:///a"));
+        assert!(matches!(
+            records[2].outcome,
+            AuditOutcome::Error { code: -32603, .. }
+        ));
+        assert_eq!(records[3].outcome, AuditOutcome::ToolError);
+    }
+
+    #[tokio::test]
+    async fn redaction_and_policy_denials() {
+        let (records, sink) = collector();
+        let logger = AuditLogger::new()
+            .sink(sink)
+            .arguments(ArgumentsMode::Redacted)
+            .redact_key("password")
+            .recording_policy(RecordingPolicy::new().exclude_tool("vault_get"));
+        let router = Layered::new(
+            Echo,
+            ToolPolicyLayer::new(DeclarativePolicy::new().deny_tool("rm")),
+        )
+        .layer(AuditLayer::new(logger));
+
+        let (data, peer) = (ctx_data(), NoOpPeer);
+        let ctx = data.to_context(&peer);
+        let login = json!({ "name": "login", "arguments": { "user": "a", "auth": { "Password": "hunter2" } } });
+        router
+            .route("tools/call", Some(&login), &ctx)
+            .await
+            .unwrap();
+        let vault = json!({ "name": "vault_get", "arguments": { "entry": "bank" } });
+        router
+            .route("tools/call", Some(&vault), &ctx)
+            .await
+            .unwrap();
+        let rm = json!({ "name": "rm", "arguments": {} });
+        router
+            .route("tools/call", Some(&rm), &ctx)
+            .await
+            .unwrap_err();
+
+        let records = records.lock().unwrap();
+        assert_eq!(
+            records[0].arguments,
+            Some(json!({ "user": "a", "auth": { "Password": REDACTED } }))
+        );
+        assert!(records[1].arguments.is_none());
+        assert!(records[1].arguments_hash.is_some());
+        assert!(matches!(
+            &records[2].outcome,
+            AuditOutcome::Denied { reason: Some(r) } if r.contains("denied")
+        ));
+    }
+
+    #[tokio::test]
+    async fn sampling_keeps_failures() {
+        let (records, sink) = collector();
+        let logger = AuditLogger::new().sink(sink).sample_rate(0.0);
+        let router = Layered::new(Echo, AuditLayer::new(logger));
+        let (data, peer) = (ctx_data(), NoOpPeer);
+        let ctx = data.to_context(&peer);
+
+        for _ in 0..5 {
+            let ok = json!({ "name": "search" });
+            router.route("tools/call", Some(&ok), &ctx).await.unwrap();
+        }
+        let broken = json!({ "name": "broken" });
+        router
+            .route("tools/call", Some(&broken), &ctx)
+            .await
+            .unwrap_err();
+        assert_eq!(records.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn jsonl_sink_writes_one_line_per_record() {
+        #[derive(Clone, Default)]
+        struct Shared(Arc<Mutex<Vec<u8>>>);
+        impl Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Shared::default();
+        let sink = JsonlSink::new(buffer.clone());
+        let record = AuditRecord {
+            timestamp: Utc::now(),
+            session_id: None,
+            request_id: RequestId::Number(1),
+            method: "tools/call".to_string(),
+            tool: Some("search".to_string()),
+            uri: None,
+            subject: None,
+            arguments_hash: None,
+            arguments: None,
+            outcome: AuditOutcome::Error {
+                code: -32603,
+                message: "boom".to_string(),
+            },
+            duration_ms: 12,
+        };
+        sink.record(&record);
+        sink.record(&record);
+
+        let text = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: AuditRecord = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(parsed, record);
+        let raw: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(raw["outcome"]["status"], "error");
+        assert_eq!(raw["durationMs"], 12);
+    }
+}
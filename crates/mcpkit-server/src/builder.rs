@@ -67,6 +67,7 @@
 //! assert!(server.capabilities().has_tools());
 //! ```
 
+use crate::compose::{Mountable, MountedPrompts, MountedResources, MountedTools, Mounts};
 use crate::handler::{PromptHandler, ResourceHandler, ServerHandler, TaskHandler, ToolHandler};
 use mcpkit_core::capability::ServerCapabilities;
 
@@ -98,6 +99,7 @@ pub struct ServerBuilder<H, Tools, Resources, Prompts, Tasks> {
     prompts: Prompts,
     tasks: Tasks,
     capabilities: ServerCapabilities,
+    timeouts: crate::timeout::Timeouts,
 }
 
 // Initial builder with no handlers registered
@@ -118,6 +120,7 @@ impl<H: ServerHandler>
             prompts: NotRegistered,
             tasks: NotRegistered,
             capabilities,
+            timeouts: crate::timeout::Timeouts::default(),
         }
     }
 }
@@ -142,6 +145,17 @@ where
     pub const fn get_capabilities(&self) -> &ServerCapabilities {
         &self.capabilities
     }
+
+    /// Limit how long requests may run.
+    ///
+    /// A request that runs past its timeout is cancelled and fails with
+    /// `McpError::Timeout`. Tools can declare their own timeout with
+    /// `#[tool(timeout_ms = ...)]`. See [`crate::timeout`].
+    #[must_use]
+    pub fn timeouts(mut self, timeouts: crate::timeout::Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
 }
 
 // Tool handler registration (only when tools are not yet registered)
@@ -171,10 +185,91 @@ where
             prompts: self.prompts,
             tasks: self.tasks,
             capabilities,
+            timeouts: self.timeouts,
         }
     }
 }
 
+// Opt-in tool result caching. Like validation below, wrapping the registered
+// tool handler covers every dispatch path.
+impl<H, TH, R, P, K> ServerBuilder<H, Registered<TH>, R, P, K>
+where
+    H: ServerHandler,
+    TH: ToolHandler,
+{
+    /// Serve repeated calls to read-only, idempotent tools from `cache`.
+    ///
+    /// Only tools annotated `readOnlyHint` and `idempotentHint` are cached.
+    /// Keep a clone of the cache to invalidate it outside tool calls; inside
+    /// them use [`Context::invalidate_tool_cache`](crate::Context::invalidate_tool_cache).
+    /// See [`crate::cache`].
+    #[must_use]
+    pub fn cache_tool_results(
+        self,
+        cache: crate::cache::ToolCache,
+    ) -> ServerBuilder<H, Registered<crate::cache::CachingToolHandler<TH>>, R, P, K> {
+        ServerBuilder {
+            handler: self.handler,
+            tools: Registered(crate::cache::CachingToolHandler::new(self.tools.0, cache)),
+            resources: self.resources,
+            prompts: self.prompts,
+            tasks: self.tasks,
+            capabilities: self.capabilities,
+            timeouts: self.timeouts,
+        }
+    }
+}
+
+// Opt-in human approval of destructive tool calls, wrapping the registered
+// tool handler like caching above.
+impl<H, TH, R, P, K> ServerBuilder<H, Registered<TH>, R, P, K>
+where
+    H: ServerHandler,
+    TH: ToolHandler,
+{
+    /// Ask the user to approve each call to a tool annotated
+    /// `destructiveHint` before it runs.
+    ///
+    /// The server sends the client an elicitation confirmation request and
+    /// runs the tool only if the user accepts; otherwise the call fails with
+    /// `McpError::UserRejected`. Calls fail too when the client does not
+    /// support elicitation. Change the message with
+    /// [`approval_message`](ServerBuilder::approval_message). See
+    /// [`crate::approval`].
+    #[must_use]
+    pub fn require_approval_for_destructive(
+        self,
+        required: bool,
+    ) -> ServerBuilder<H, Registered<crate::approval::ApprovingToolHandler<TH>>, R, P, K> {
+        ServerBuilder {
+            handler: self.handler,
+            tools: Registered(
+                crate::approval::ApprovingToolHandler::new(self.tools.0).enabled(required),
+            ),
+            resources: self.resources,
+            prompts: self.prompts,
+            tasks: self.tasks,
+            capabilities: self.capabilities,
+            timeouts: self.timeouts,
+        }
+    }
+}
+
+impl<H, TH, R, P, K>
+    ServerBuilder<H, Registered<crate::approval::ApprovingToolHandler<TH>>, R, P, K>
+where
+    H: ServerHandler,
+    TH: ToolHandler,
+{
+    /// Set the template of the approval message; `{tool}` and `{arguments}`
+    /// are replaced by the tool name and the call's arguments as JSON.
+    #[must_use]
+    pub fn approval_message(mut self, template: impl Into<String>) -> Self {
+        self.tools = Registered(self.tools.0.message_template(template));
+        self
+    }
+}
+
 // Opt-in tool I/O schema validation (feature `schema-validation`). Wrapping the
 // registered tool handler covers every dispatch path (normal `tools/call`,
 // task-augmented execution, and the HTTP adapters) because they all go through
@@ -229,6 +324,7 @@ where
             prompts: self.prompts,
             tasks: self.tasks,
             capabilities: self.capabilities,
+            timeouts: self.timeouts,
         }
     }
 }
@@ -253,6 +349,7 @@ where
             prompts: self.prompts,
             tasks: self.tasks,
             capabilities: self.capabilities.with_resources(),
+            timeouts: self.timeouts,
         }
     }
 }
@@ -277,6 +374,86 @@ where
             prompts: Registered(prompts),
             tasks: self.tasks,
             capabilities: self.capabilities.with_prompts(),
+            timeouts: self.timeouts,
+        }
+    }
+}
+
+/// The builder once modules are mounted: the tool, resource, and prompt
+/// slots hold the merged handlers of every mounted module.
+type Mounted<H, K> = ServerBuilder<
+    H,
+    Registered<MountedTools>,
+    Registered<MountedResources>,
+    Registered<MountedPrompts>,
+    K,
+>;
+
+// Module mounting (only when no tool, resource, or prompt handler is
+// registered directly). See `crate::compose`.
+impl<H, K> ServerBuilder<H, NotRegistered, NotRegistered, NotRegistered, K>
+where
+    H: ServerHandler,
+{
+    /// Mount `module` under `prefix`, merging its tools, resources, and
+    /// prompts into this server.
+    ///
+    /// Tool and prompt names are exposed as `prefix` + name; resources keep
+    /// their URIs. Call `mount` again to add more modules, including the base
+    /// handler's own type under `""`. See [`crate::compose`].
+    #[must_use]
+    pub fn mount<M: Mountable>(self, prefix: impl Into<String>, module: M) -> Mounted<H, K> {
+        ServerBuilder {
+            handler: self.handler,
+            tools: Registered(MountedTools::default()),
+            resources: Registered(MountedResources::default()),
+            prompts: Registered(MountedPrompts::default()),
+            tasks: self.tasks,
+            capabilities: self.capabilities,
+            timeouts: self.timeouts,
+        }
+        .mount(prefix, module)
+    }
+}
+
+impl<H, K> Mounted<H, K>
+where
+    H: ServerHandler,
+{
+    /// Mount another `module` under `prefix`.
+    ///
+    /// When prefixes overlap, the longest matching prefix is tried first.
+    #[must_use]
+    pub fn mount<M: Mountable>(self, prefix: impl Into<String>, module: M) -> Self {
+        let mounts = Mounts::from_parts(self.tools.0, self.resources.0, self.prompts.0)
+            .mount(prefix, module);
+        let (tools, resources, prompts) = mounts.into_parts();
+
+        // Keep flags (such as `listChanged`) the base handler already declared.
+        let mut capabilities = self.capabilities;
+        if !tools.is_empty() && capabilities.tools.is_none() {
+            capabilities = capabilities.with_tools();
+            if capabilities.tasks.is_some() {
+                capabilities = capabilities.with_task_tools();
+            }
+        }
+        if tools.list_changed() {
+            capabilities = capabilities.with_tools_and_changes();
+        }
+        if !resources.is_empty() && capabilities.resources.is_none() {
+            capabilities = capabilities.with_resources();
+        }
+        if !prompts.is_empty() && capabilities.prompts.is_none() {
+            capabilities = capabilities.with_prompts();
+        }
+        ServerBuilder {
+            handler: self.handler,
+            tools: Registered(tools),
+            resources: Registered(resources),
+            prompts: Registered(prompts),
+            tasks: self.tasks,
+            capabilities,
+            timeouts: self.timeouts,
         }
     }
 }
@@ -310,6 +487,7 @@ where
             prompts: self.prompts,
             tasks: Registered(tasks),
             capabilities,
+            timeouts: self.timeouts,
         }
     }
 }
@@ -335,8 +513,11 @@ where
             prompts: self.prompts,
             tasks: self.tasks,
             capabilities: self.capabilities,
+            timeouts: self.timeouts,
             list_page_size: None,
+            identity: crate::config::IdentityConfig::default(),
             completion: None,
+            shutdown: crate::shutdown::ShutdownHandle::new(),
         }
     }
 }
@@ -359,10 +540,16 @@ pub struct Server<H, T, R, P, K> {
     /// Page size for `*/list` results; `None` disables pagination (list
     /// responses return everything, no `nextCursor`).
     pub(crate) list_page_size: Option<usize>,
+    /// Execution timeouts enforced when routing requests.
+    pub(crate) timeouts: crate::timeout::Timeouts,
+    /// Configured overrides of the handler's server info and instructions.
+    pub(crate) identity: crate::config::IdentityConfig,
     /// Optional completion handler (`completion/complete`). Not a typestate slot
     /// — completion is a leaf capability registered post-build so it can also be
     /// carried by the framework adapters, which take a flat combined handler.
     pub(crate) completion: Option<std::sync::Arc<dyn crate::dispatch::DynCompletionHandler>>,
+    /// Stops [`serve`](Self::serve) gracefully when triggered.
+    shutdown: crate::shutdown::ShutdownHandle,
 }
 
 impl<H, T, R, P, K> Server<H, T, R, P, K>
@@ -375,6 +562,15 @@ where
         &self.capabilities
     }
 
+    /// Get a handle that stops [`serve`](Self::serve) gracefully.
+    ///
+    /// Take it before calling `serve`, which consumes the server. See
+    /// [`crate::shutdown`].
+    #[must_use]
+    pub fn shutdown_handle(&self) -> crate::shutdown::ShutdownHandle {
+        self.shutdown.clone()
+    }
+
     /// Enable pagination of `tools/list`, `resources/list`,
     /// `resources/templates/list`, and `prompts/list` at the given page size.
     ///
@@ -388,6 +584,27 @@ where
         self
     }
 
+    /// Apply a deployment configuration's server identity, capabilities,
+    /// timeouts, and list page size (see [`crate::config`]).
+    ///
+    /// Configured timeouts replace those set with
+    /// [`ServerBuilder::timeouts`]. The runtime settings and quotas apply
+    /// where the server is run, through
+    /// [`ServerConfig::runtime_config`](crate::config::ServerConfig::runtime_config)
+    /// and [`ServerConfig::rate_limit`](crate::config::ServerConfig::rate_limit).
+    #[must_use]
+    pub fn configure(mut self, config: &crate::config::ServerConfig) -> Self {
+        self.identity = config.server.clone();
+        self.capabilities = config.capabilities.apply(self.capabilities);
+        if !config.timeouts.is_empty() {
+            self.timeouts = config.timeouts.timeouts();
+        }
+        if let Some(page_size) = config.limits.list_page_size {
+            self.list_page_size = Some(page_size);
+        }
+        self
+    }
+
     /// Register a completion handler and advertise the `completions` capability.
     ///
     /// This wires `completion/complete` on both the runtime and the framework
@@ -409,10 +626,21 @@ where
         &self.handler
     }
 
-    /// Get the server info from the base handler.
+    /// Get the server info from the base handler, with any configured
+    /// overrides applied.
     #[must_use]
     pub fn server_info(&self) -> mcpkit_core::capability::ServerInfo {
-        self.handler.server_info()
+        self.identity.apply(self.handler.server_info())
+    }
+
+    /// Get the instructions sent during initialization: the configured ones,
+    /// or else the base handler's.
+    #[must_use]
+    pub fn instructions(&self) -> Option<String> {
+        self.identity
+            .instructions
+            .clone()
+            .or_else(|| self.handler.instructions())
     }
 }
 
@@ -533,6 +761,31 @@ mod tests {
         let _tool_handler: &TestToolHandler = server.tool_handler();
     }
 
+    #[test]
+    fn configure_overrides_identity_and_capabilities() {
+        let config = crate::config::ServerConfig::parse(
+            r#"{
+                "server": { "name": "deployed", "instructions": "Be brief." },
+                "capabilities": { "tools": false, "logging": true }
+            }"#,
+            crate::config::ConfigFormat::Json,
+        )
+        .unwrap();
+        let server = ServerBuilder::new(TestHandler)
+            .with_tools(TestToolHandler)
+            .build()
+            .configure(&config);
+
+        let info = server.server_info();
+        assert_eq!(
+            (info.name.as_str(), info.version.as_str()),
+            ("deployed", "1.0.0")
+        );
+        assert_eq!(server.instructions().as_deref(), Some("Be brief."));
+        assert!(!server.capabilities().has_tools());
+        assert!(server.capabilities().logging.is_some());
+    }
+
     struct TestTaskHandler;
 
     impl crate::handler::TaskHandler for TestTaskHandler {
@@ -0,0 +1,693 @@
+//! Opt-in caching of tool results.
+//!
+//! A [`ToolCache`] stores `tools/call` results keyed by tool name and
+//! arguments. Only tools that declare both the `readOnlyHint` and
+//! `idempotentHint` annotations (`#[tool(read_only = true, idempotent = true)]`)
+//! are cached, and only successful results (`isError` unset) are stored.
+//! Argument objects are compared canonically, so key order does not matter.
+//!
+//! Entries expire after the configured TTL. When the cache is full, the least
+//! recently used entry is evicted. A tool that changes the state a cached tool
+//! reads should invalidate it with [`Context::invalidate_tool_cache`]; code
+//! outside a tool call can invalidate through any clone of the cache.
+//!
+//! Wrap a tool handler with [`ServerBuilder::cache_tool_results`] (or
+//! construct a [`CachingToolHandler`] directly for the HTTP adapters). Pass a
+//! shared [`ServerMetrics`] in the configuration to count hits and misses.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::cache::{ToolCache, ToolCacheConfig};
+//! use mcpkit_server::metrics::ServerMetrics;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let metrics = Arc::new(ServerMetrics::new());
+//! let cache = ToolCache::new(
+//!     ToolCacheConfig::new()
+//!         .ttl(Duration::from_secs(30))
+//!         .max_entries(512)
+//!         .metrics(Arc::clone(&metrics)),
+//! );
+//! assert!(cache.is_empty());
+//! ```
+//!
+//! [`Context::invalidate_tool_cache`]: crate::context::Context::invalidate_tool_cache
+//! [`ServerBuilder::cache_tool_results`]: crate::builder::ServerBuilder::cache_tool_results
+
+use crate::context::Context;
+use crate::handler::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use crate::metrics::ServerMetrics;
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_core::error::McpError;
+use mcpkit_core::pagination::Page;
+use mcpkit_core::types::{
+    ByteRange, CallToolResult, GetPromptResult, Object, Prompt, Resource, ResourceContents,
+    ResourceTemplate, Tool, ToolOutput,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Default time a cached result stays valid.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// Default maximum number of cached results.
+pub const DEFAULT_MAX_ENTRIES: usize = 1024;
+
+/// Settings for a [`ToolCache`].
+#[derive(Debug, Clone)]
+pub struct ToolCacheConfig {
+    ttl: Duration,
+    max_entries: usize,
+    metrics: Option<Arc<ServerMetrics>>,
+}
+
+impl Default for ToolCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_TTL,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            metrics: None,
+        }
+    }
+}
+
+impl ToolCacheConfig {
+    /// Create a configuration with the default TTL and size.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long a cached result stays valid.
+    #[must_use]
+    pub const fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the maximum number of cached results (at least one).
+    #[must_use]
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Count hits and misses in `metrics`.
+    #[must_use]
+    pub fn metrics(mut self, metrics: Arc<ServerMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+}
+
+/// Cache key: tool name and canonical arguments.
+pub(crate) type Key = (String, String);
+
+/// A cached result.
+struct Entry {
+    result: CallToolResult,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+/// Mutable cache contents.
+#[derive(Default)]
+struct Entries {
+    map: HashMap<Key, Entry>,
+    /// Logical clock for least-recently-used ordering.
+    tick: u64,
+}
+
+/// A cache of tool results, shared by its clones.
+#[derive(Clone)]
+pub struct ToolCache {
+    config: ToolCacheConfig,
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl ToolCache {
+    /// Create an empty cache.
+    #[must_use]
+    pub fn new(config: ToolCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Arc::default(),
+        }
+    }
+
+    /// The cache's configuration.
+    #[must_use]
+    pub const fn config(&self) -> &ToolCacheConfig {
+        &self.config
+    }
+
+    /// Look up an unexpired result for `name` called with `args`.
+    ///
+    /// Does not record a hit or miss; [`CachingToolHandler`] does that.
+    #[must_use]
+    pub fn get(&self, name: &str, args: &Object) -> Option<CallToolResult> {
+        let key = key(name, args);
+        let mut entries = self.lock();
+        let now = Instant::now();
+        entries.tick += 1;
+        let tick = entries.tick;
+        match entries.map.get_mut(&key) {
+            Some(entry) if entry.expires_at > now => {
+                entry.last_used = tick;
+                Some(entry.result.clone())
+            }
+            Some(_) => {
+                entries.map.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store the result of calling `name` with `args`.
+    ///
+    /// When the cache is full, expired entries are dropped first, then the
+    /// least recently used one.
+    pub fn insert(&self, name: &str, args: &Object, result: CallToolResult) {
+        let key = key(name, args);
+        let mut entries = self.lock();
+        let now = Instant::now();
+        if !entries.map.contains_key(&key) && entries.map.len() >= self.config.max_entries {
+            entries.map.retain(|_, entry| entry.expires_at > now);
+            if entries.map.len() >= self.config.max_entries {
+                let oldest = entries
+                    .map
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.map.remove(&oldest);
+                }
+            }
+        }
+        entries.tick += 1;
+        let last_used = entries.tick;
+        entries.map.insert(
+            key,
+            Entry {
+                result,
+                expires_at: now + self.config.ttl,
+                last_used,
+            },
+        );
+    }
+
+    /// Drop the cached result of `name` called with `args`, if any.
+    #[must_use = "returns whether a result was dropped"]
+    pub fn invalidate(&self, name: &str, args: &Object) -> bool {
+        self.lock().map.remove(&key(name, args)).is_some()
+    }
+
+    /// Drop every cached result of the tool `name`, returning how many there were.
+    #[must_use = "returns how many results were dropped"]
+    pub fn invalidate_tool(&self, name: &str) -> usize {
+        let mut entries = self.lock();
+        let before = entries.map.len();
+        entries.map.retain(|(tool, _), _| tool != name);
+        before - entries.map.len()
+    }
+
+    /// Drop every cached result.
+    pub fn clear(&self) {
+        self.lock().map.clear();
+    }
+
+    /// Number of cached results, including expired ones not yet dropped.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().map.len()
+    }
+
+    /// Whether nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record(&self, hit: bool) {
+        if let Some(metrics) = &self.config.metrics {
+            if hit {
+                metrics.record_cache_hit();
+            } else {
+                metrics.record_cache_miss();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ToolCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolCache")
+            .field("config", &self.config)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Whether a tool's annotations allow caching its results.
+fn is_cacheable(tool: &Tool) -> bool {
+    tool.annotations.as_ref().is_some_and(|annotations| {
+        annotations.read_only_hint == Some(true) && annotations.idempotent_hint == Some(true)
+    })
+}
+
+pub(crate) fn key(name: &str, args: &Object) -> Key {
+    let mut canonical = String::new();
+    for (name, value) in sorted(args) {
+        write_canonical_entry(&mut canonical, name, value);
+    }
+    (name.to_string(), canonical)
+}
+
+fn sorted(map: &Object) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+fn write_canonical_entry(out: &mut String, name: &str, value: &Value) {
+    out.push_str(&Value::String(name.to_string()).to_string());
+    out.push(':');
+    write_canonical(out, value);
+    out.push(',');
+}
+
+/// Serialize `value` with object keys sorted, so equal arguments map to
+/// the same key whatever their order.
+fn write_canonical(out: &mut String, value: &Value) {
+    match value {
+        Value::Object(map) => {
+            out.push('{');
+            for (name, value) in sorted(map) {
+                write_canonical_entry(out, name, value);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for item in items {
+                write_canonical(out, item);
+                out.push(',');
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+/// A [`ToolHandler`] decorator that serves repeated read-only calls from a
+/// [`ToolCache`].
+///
+/// Calls it forwards see the cache through [`Context::tool_cache`], so a
+/// mutating tool can invalidate the results it makes stale. Cacheability is
+/// read from the inner handler's [`list_tools`](ToolHandler::list_tools) on
+/// each miss, and the whole cache is cleared when the tool list changes.
+///
+/// [`Context::tool_cache`]: crate::context::Context::tool_cache
+pub struct CachingToolHandler<H> {
+    inner: H,
+    cache: ToolCache,
+}
+
+impl<H> CachingToolHandler<H> {
+    /// Wrap `inner`, caching its results in `cache`.
+    #[must_use]
+    pub const fn new(inner: H, cache: ToolCache) -> Self {
+        Self { inner, cache }
+    }
+
+    /// The cache results are stored in.
+    #[must_use]
+    pub const fn cache(&self) -> &ToolCache {
+        &self.cache
+    }
+
+    /// Unwrap, returning the inner handler.
+    pub fn into_inner(self) -> H {
+        self.inner
+    }
+}
+
+impl<H: ToolHandler> ToolHandler for CachingToolHandler<H> {
+    async fn list_tools(&self, ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        self.inner.list_tools(ctx).await
+    }
+
+    fn list_tools_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Tool>, McpError>> + Send {
+        self.inner.list_tools_page(cursor, page_size, ctx)
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        args: Object,
+        ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        if let Some(result) = self.cache.get(name, &args) {
+            tracing::trace!(tool = name, "tool result served from cache");
+            self.cache.record(true);
+            return Ok(ToolOutput::Success(result));
+        }
+
+        // A failed listing just disables caching for this call.
+        let cacheable = match self.inner.list_tools(ctx).await {
+            Ok(tools) => tools
+                .iter()
+                .any(|tool| tool.name == name && is_cacheable(tool)),
+            Err(error) => {
+                tracing::warn!(%error, tool = name, "could not list tools; not caching");
+                false
+            }
+        };
+        let key_args = cacheable.then(|| args.clone());
+
+        let ctx = ctx.reborrow().with_tool_cache(&self.cache);
+        let output = self.inner.call_tool(name, args, &ctx).await?;
+
+        if let Some(args) = key_args {
+            self.cache.record(false);
+            if let ToolOutput::Success(result) = &output {
+                if result.is_error != Some(true) {
+                    self.cache.insert(name, &args, result.clone());
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    async fn on_tools_changed(&self) {
+        self.cache.clear();
+        self.inner.on_tools_changed().await;
+    }
+}
+
+// Transparent forwarding of the other handler traits, so a combined handler
+// wrapped in `CachingToolHandler` stays a drop-in for the HTTP adapters.
+
+impl<H: ServerHandler> ServerHandler for CachingToolHandler<H> {
+    fn server_info(&self) -> ServerInfo {
+        self.inner.server_info()
+    }
+
+    fn capabilities(&self) -> ServerCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn instructions(&self) -> Option<String> {
+        self.inner.instructions()
+    }
+
+    fn on_initialized(&self, ctx: &Context<'_>) -> impl Future<Output = ()> + Send {
+        self.inner.on_initialized(ctx)
+    }
+
+    fn on_roots_list_changed(&self, ctx: &Context<'_>) -> impl Future<Output = ()> + Send {
+        self.inner.on_roots_list_changed(ctx)
+    }
+
+    fn on_shutdown(&self) -> impl Future<Output = ()> + Send {
+        self.inner.on_shutdown()
+    }
+
+    fn set_log_level(
+        &self,
+        level: crate::handler::LogLevel,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<(), McpError>> + Send {
+        self.inner.set_log_level(level, ctx)
+    }
+}
+
+impl<H: ResourceHandler> ResourceHandler for CachingToolHandler<H> {
+    fn list_resources(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<Resource>, McpError>> + Send {
+        self.inner.list_resources(ctx)
+    }
+
+    fn list_resources_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Resource>, McpError>> + Send {
+        self.inner.list_resources_page(cursor, page_size, ctx)
+    }
+
+    fn list_resource_templates(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<ResourceTemplate>, McpError>> + Send {
+        self.inner.list_resource_templates(ctx)
+    }
+
+    fn list_resource_templates_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<ResourceTemplate>, McpError>> + Send {
+        self.inner
+            .list_resource_templates_page(cursor, page_size, ctx)
+    }
+
+    fn read_resource(
+        &self,
+        uri: &str,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<ResourceContents>, McpError>> + Send {
+        self.inner.read_resource(uri, ctx)
+    }
+
+    fn read_resource_range(
+        &self,
+        uri: &str,
+        range: ByteRange,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<ResourceContents, McpError>> + Send {
+        self.inner.read_resource_range(uri, range, ctx)
+    }
+
+    fn subscribe(
+        &self,
+        uri: &str,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<bool, McpError>> + Send {
+        self.inner.subscribe(uri, ctx)
+    }
+
+    fn unsubscribe(
+        &self,
+        uri: &str,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<bool, McpError>> + Send {
+        self.inner.unsubscribe(uri, ctx)
+    }
+}
+
+impl<H: PromptHandler> PromptHandler for CachingToolHandler<H> {
+    fn list_prompts(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Vec<Prompt>, McpError>> + Send {
+        self.inner.list_prompts(ctx)
+    }
+
+    fn list_prompts_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Prompt>, McpError>> + Send {
+        self.inner.list_prompts_page(cursor, page_size, ctx)
+    }
+
+    fn get_prompt(
+        &self,
+        name: &str,
+        args: Option<serde_json::Map<String, Value>>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<GetPromptResult, McpError>> + Send {
+        self.inner.get_prompt(name, args, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::NoOpPeer;
+    use mcpkit_core::capability::ClientCapabilities;
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::ToolAnnotations;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn obj(v: Value) -> Object {
+        match v {
+            Value::Object(map) => map,
+            other => panic!("expected object, got {other}"),
+        }
+    }
+
+    /// "lookup" is read-only and idempotent, "now" is only read-only, and
+    /// "store" invalidates "lookup" through the context.
+    #[derive(Default)]
+    struct CountingTools {
+        calls: AtomicUsize,
+    }
+
+    impl ToolHandler for CountingTools {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(vec![
+                Tool::new("lookup").annotations(ToolAnnotations {
+                    read_only_hint: Some(true),
+                    idempotent_hint: Some(true),
+                    ..Default::default()
+                }),
+                Tool::new("now").annotations(ToolAnnotations::read_only()),
+                Tool::new("store"),
+            ])
+        }
+
+        async fn call_tool(
+            &self,
+            name: &str,
+            _args: Object,
+            ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            if name == "store" {
+                assert!(ctx.tool_cache().is_some_and(|cache| cache.len() == 1));
+                ctx.invalidate_tool_cache("lookup");
+            }
+            Ok(ToolOutput::text(n.to_string()))
+        }
+    }
+
+    fn text(output: ToolOutput) -> String {
+        match output {
+            ToolOutput::Success(result) => {
+                serde_json::to_value(result).unwrap()["content"][0]["text"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            }
+            ToolOutput::RecoverableError { message, .. } => panic!("tool failed: {message}"),
+        }
+    }
+
+    async fn call(handler: &CachingToolHandler<CountingTools>, name: &str, args: Value) -> String {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &NoOpPeer,
+        );
+        text(handler.call_tool(name, obj(args), &ctx).await.unwrap())
+    }
+
+    fn handler(config: ToolCacheConfig) -> CachingToolHandler<CountingTools> {
+        CachingToolHandler::new(CountingTools::default(), ToolCache::new(config))
+    }
+
+    #[tokio::test]
+    async fn caches_read_only_idempotent_tools_by_canonical_args() {
+        let metrics = Arc::new(ServerMetrics::new());
+        let handler = handler(ToolCacheConfig::new().metrics(Arc::clone(&metrics)));
+
+        assert_eq!(
+            call(
+                &handler,
+                "lookup",
+                json!({"a": 1, "b": [1, {"x": 1, "y": 2}]})
+            )
+            .await,
+            "0"
+        );
+        assert_eq!(
+            call(
+                &handler,
+                "lookup",
+                json!({"b": [1, {"y": 2, "x": 1}], "a": 1})
+            )
+            .await,
+            "0"
+        );
+        assert_eq!(call(&handler, "lookup", json!({"a": 2})).await, "1");
+
+        // Not idempotent, so never cached.
+        assert_eq!(call(&handler, "now", json!({})).await, "2");
+        assert_eq!(call(&handler, "now", json!({})).await, "3");
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.cache_hits, 1);
+        assert_eq!(snapshot.cache_misses, 2);
+        assert_eq!(handler.cache().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn context_invalidation_drops_stale_results() {
+        let handler = handler(ToolCacheConfig::new());
+        assert_eq!(call(&handler, "lookup", json!({"k": 1})).await, "0");
+        assert_eq!(call(&handler, "store", json!({"k": 1})).await, "1");
+        assert_eq!(call(&handler, "lookup", json!({"k": 1})).await, "2");
+
+        handler.on_tools_changed().await;
+        assert!(handler.cache().is_empty());
+    }
+
+    #[tokio::test]
+    async fn entries_expire_after_ttl() {
+        let handler = handler(ToolCacheConfig::new().ttl(Duration::from_millis(20)));
+        assert_eq!(call(&handler, "lookup", json!({})).await, "0");
+        assert_eq!(call(&handler, "lookup", json!({})).await, "0");
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(call(&handler, "lookup", json!({})).await, "1");
+    }
+
+    #[test]
+    fn full_cache_evicts_least_recently_used() {
+        let cache = ToolCache::new(ToolCacheConfig::new().max_entries(2));
+        let (a, b, c) = (
+            obj(json!({"n": 1})),
+            obj(json!({"n": 2})),
+            obj(json!({"n": 3})),
+        );
+        cache.insert("t", &a, CallToolResult::text("a"));
+        cache.insert("t", &b, CallToolResult::text("b"));
+        assert!(cache.get("t", &a).is_some());
+
+        cache.insert("t", &c, CallToolResult::text("c"));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("t", &b).is_none(), "b was least recently used");
+        assert!(cache.get("t", &a).is_some());
+        assert!(cache.invalidate("t", &c));
+        assert_eq!(cache.invalidate_tool("t"), 1);
+    }
+}
@@ -141,17 +141,10 @@ impl ResourceService {
 
     /// Check if a URI matches a template pattern.
     ///
-    /// Simple implementation supporting `{param}` placeholders.
-    /// Uses prefix matching for templates with parameters.
+    /// See [`match_uri_template`](mcpkit_core::types::match_uri_template)
+    /// for the supported `{param}` and `{+param}` placeholders.
     fn matches_template(template: &str, uri: &str) -> bool {
-        // For now, do a simple prefix match
-        // A full implementation would use proper URI template matching (RFC 6570)
-        if template.contains('{') {
-            let prefix = template.split('{').next().unwrap_or("");
-            uri.starts_with(prefix)
-        } else {
-            template == uri
-        }
+        mcpkit_core::types::match_uri_template(template, uri).is_some()
     }
 
     /// Get the number of registered resources.
@@ -316,6 +309,10 @@ mod tests {
             "myserver://data/{id}",
             "myserver://data/123"
         ));
+        assert!(!ResourceService::matches_template(
+            "myserver://data/{id}",
+            "myserver://data/123/history"
+        ));
         assert!(ResourceService::matches_template(
             "file:///config.json",
             "file:///config.json"
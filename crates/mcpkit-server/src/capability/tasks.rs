@@ -5,17 +5,54 @@
 //!
 //! The store itself ([`TaskManager`], [`TaskHandle`], [`route_task_store`]) is
 //! shared with the client side and lives in [`mcpkit_core::tasks`]; this
-//! module re-exports it and adds the server-only [`TaskService`].
+//! module re-exports it and adds the server-only [`TaskService`] and the
+//! [`TaskContext`] handed to work started with
+//! [`Context::spawn_task`](crate::Context::spawn_task).
+//!
+//! Tasks are kept in memory unless the runtime is given a durable
+//! [`TaskStore`] through
+//! [`RuntimeConfig::task_store`](crate::RuntimeConfig::task_store), such as
+//! `FileTaskStore` (feature `file-task-store`), so clients can still poll
+//! `tasks/get` and `tasks/result` after a restart.
+//!
+//! # Spawning tracked tasks
+//!
+//! A tool can hand long-running work to the runtime's task store instead of
+//! implementing [`TaskHandler`] itself. The task is registered as `working`,
+//! its progress is reported against the request's progress token, a
+//! `tasks/cancel` aborts it, and its outcome is stored for `tasks/result`:
+//!
+//! ```rust,ignore
+//! #[tool(description = "Re-index the repository in the background")]
+//! async fn reindex(&self, ctx: &Context<'_>) -> Result<ToolOutput, McpError> {
+//!     let files = self.files();
+//!     let task = ctx.spawn_task("reindex", async move |task| {
+//!         for (i, file) in files.iter().enumerate() {
+//!             index_file(file).await?;
+//!             task.progress(i as f64 + 1.0, Some(files.len() as f64), None).await?;
+//!         }
+//!         Ok(CallToolResult::text(format!("indexed {} files", files.len())))
+//!     })?;
+//!     Ok(ToolOutput::text(format!("started task {}", task.task_id.as_str())))
+//! }
+//! ```
 
+#[cfg(feature = "file-task-store")]
+pub use mcpkit_core::tasks::FileTaskStore;
 pub use mcpkit_core::tasks::{
-    DEFAULT_TASK_TTL_MS, RELATED_TASK_META_KEY, TaskHandle, TaskManager, TaskPayload, TaskState,
-    route_task_store,
+    DEFAULT_TASK_TTL_MS, MemoryTaskStore, RELATED_TASK_META_KEY, StoredTask, TaskEvent,
+    TaskEventKind, TaskHandle, TaskManager, TaskPayload, TaskState, TaskStore, route_task_store,
 };
 
-use crate::context::Context;
+use crate::context::{CancellationToken, CancelledFuture, Context, Peer};
 use crate::handler::TaskHandler;
+use crate::progress::ProgressReporter;
 use mcpkit_core::error::McpError;
-use mcpkit_core::types::task::{CancelTaskResult, GetTaskResult, ListTasksResult, TaskId};
+use mcpkit_core::protocol::Notification;
+use mcpkit_core::types::task::{
+    CancelTaskResult, GetTaskResult, ListTasksResult, Task, TaskId, TaskStatusNotificationParams,
+};
+use std::fmt;
 use std::sync::Arc;
 
 /// Task service implementing the [`TaskHandler`] trait over a [`TaskManager`].
@@ -85,9 +122,366 @@ impl TaskHandler for TaskService {
     }
 }
 
+/// The execution context of a task started with
+/// [`Context::spawn_task`](crate::Context::spawn_task).
+///
+/// Unlike [`Context`] it is owned and `'static`, so it lives as long as the
+/// spawned work. Cloning is cheap; clones drive the same task.
+#[derive(Clone)]
+pub struct TaskContext {
+    handle: Arc<TaskHandle>,
+    cancel: CancellationToken,
+    peer: Option<Arc<dyn Peer>>,
+    progress: ProgressReporter,
+}
+
+impl TaskContext {
+    /// The task's ID.
+    #[must_use]
+    pub fn id(&self) -> &TaskId {
+        self.handle.id()
+    }
+
+    /// A snapshot of the task's current state.
+    #[must_use]
+    pub fn task(&self) -> Option<Task> {
+        self.handle.task()
+    }
+
+    /// Check if the task has been cancelled via `tasks/cancel`.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// A future that completes when the task is cancelled.
+    ///
+    /// The runtime already stops polling the task's future on cancellation;
+    /// this is for work that must clean up or hand cancellation further down.
+    #[must_use]
+    pub fn cancelled(&self) -> CancelledFuture {
+        self.cancel.cancelled()
+    }
+
+    /// The task's cancellation token.
+    #[must_use]
+    pub const fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancel
+    }
+
+    /// Report progress against the originating request's progress token.
+    ///
+    /// Updates are throttled as by [`ProgressReporter::report`]; without a
+    /// progress token every report is a no-op returning `Ok(false)`. Every
+    /// report is also recorded in the task's event log, which web
+    /// integrations stream per task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification could not be sent.
+    pub async fn progress(
+        &self,
+        current: f64,
+        total: Option<f64>,
+        message: Option<&str>,
+    ) -> Result<bool, McpError> {
+        // The log is best effort; a terminal task ignores the report anyway.
+        let _ = self
+            .handle
+            .record_progress(current, total, message.map(String::from));
+        self.progress.report(current, total, message).await
+    }
+
+    /// The progress reporter backing [`progress`](Self::progress).
+    #[must_use]
+    pub const fn progress_reporter(&self) -> &ProgressReporter {
+        &self.progress
+    }
+
+    /// Replace the task's status message, as seen by `tasks/get`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task is already terminal.
+    pub fn set_status_message(&self, message: impl Into<String>) -> Result<(), McpError> {
+        self.handle.set_status_message(message)
+    }
+
+    /// Move the task to `input_required` while it waits on the client (for
+    /// example during an elicitation), notifying the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task is not `working`.
+    pub async fn input_required(&self) -> Result<(), McpError> {
+        self.handle.mark_input_required()?;
+        self.notify_status().await;
+        Ok(())
+    }
+
+    /// Move an `input_required` task back to `working`, notifying the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task is not `input_required`.
+    pub async fn resume(&self) -> Result<(), McpError> {
+        self.handle.mark_working()?;
+        self.notify_status().await;
+        Ok(())
+    }
+
+    /// Send `notifications/tasks/status` with the task's current state.
+    ///
+    /// Best effort: the client can always poll `tasks/get`.
+    async fn notify_status(&self) {
+        let (Some(peer), Some(task)) = (&self.peer, self.handle.task()) else {
+            return;
+        };
+        let notification = serde_json::to_value(TaskStatusNotificationParams::from(task))
+            .map(|params| Notification::with_params("notifications/tasks/status", params));
+        if let Ok(notification) = notification {
+            if let Err(e) = peer.notify(notification).await {
+                tracing::debug!(error = %e, "Failed to send task status notification");
+            }
+        }
+    }
+}
+
+impl fmt::Debug for TaskContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskContext")
+            .field("id", self.id())
+            .field("cancelled", &self.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Register a `working` task in `store` and run `f` on the tokio runtime,
+/// storing its outcome for `tasks/result`.
+///
+/// A `tasks/cancel` stops polling the work; a panic fails the task. Every
+/// status change is announced with `notifications/tasks/status` when `peer`
+/// is set.
+#[cfg(feature = "tokio-runtime")]
+pub(crate) fn spawn_tracked<F, Fut, R>(
+    store: &Arc<TaskManager>,
+    name: &str,
+    peer: Option<Arc<dyn Peer>>,
+    progress: ProgressReporter,
+    f: F,
+) -> Result<Task, McpError>
+where
+    F: FnOnce(TaskContext) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<R, McpError>> + Send + 'static,
+    R: serde::Serialize + Send + 'static,
+{
+    use futures::FutureExt;
+    use futures::future::{Either, select};
+    use tracing::Instrument;
+
+    let handle = store.create(None);
+    let task = handle
+        .task()
+        .ok_or_else(|| McpError::internal("task was evicted on creation"))?;
+    let cancel = handle
+        .cancel_token()
+        .ok_or_else(|| McpError::internal("task was evicted on creation"))?;
+    let task_ctx = TaskContext {
+        handle: Arc::new(handle),
+        cancel: cancel.clone(),
+        peer,
+        progress,
+    };
+    let span = tracing::debug_span!("task", name, id = task.task_id.as_str());
+
+    let work = std::panic::AssertUnwindSafe(f(task_ctx.clone())).catch_unwind();
+    tokio::spawn(
+        async move {
+            let outcome = match select(Box::pin(work), cancel.cancelled()).await {
+                Either::Left((outcome, _)) => outcome,
+                Either::Right(((), _)) => {
+                    tracing::debug!("task cancelled");
+                    task_ctx.notify_status().await;
+                    return;
+                }
+            };
+            let stored = match outcome {
+                Ok(Ok(result)) => match serde_json::to_value(result) {
+                    Ok(payload) => task_ctx.handle.complete(payload),
+                    Err(e) => task_ctx
+                        .handle
+                        .fail(format!("failed to serialize task result: {e}")),
+                },
+                Ok(Err(e)) => task_ctx.handle.fail_with_error(e.into()),
+                Err(_) => task_ctx.handle.fail("task panicked"),
+            };
+            // A task cancelled as it finished keeps its `cancelled` status.
+            if let Err(e) = stored {
+                tracing::debug!(error = %e, "task outcome discarded");
+            }
+            task_ctx.notify_status().await;
+        }
+        .instrument(span),
+    );
+    Ok(task)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::context::ContextData;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::task::TaskStatus;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Mutex;
+
+    /// A shareable peer recording every notification.
+    #[derive(Clone, Default)]
+    struct RecPeer(Arc<Mutex<Vec<Notification>>>);
+
+    impl Peer for RecPeer {
+        fn notify(
+            &self,
+            notification: Notification,
+        ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+            self.0.lock().unwrap().push(notification);
+            Box::pin(async { Ok(()) })
+        }
+
+        fn to_shared(&self) -> Option<Arc<dyn Peer>> {
+            Some(Arc::new(self.clone()))
+        }
+    }
+
+    impl RecPeer {
+        fn statuses(&self) -> Vec<String> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|n| n.method == "notifications/tasks/status")
+                .filter_map(|n| n.params.as_ref()?["status"].as_str().map(String::from))
+                .collect()
+        }
+    }
+
+    fn data() -> ContextData {
+        ContextData::new(
+            RequestId::Number(1),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        )
+    }
+
+    /// Spawn `f` through a context attached to `store`.
+    fn spawn<Fut>(
+        store: &Arc<TaskManager>,
+        peer: &RecPeer,
+        f: impl FnOnce(TaskContext) -> Fut + Send + 'static,
+    ) -> Task
+    where
+        Fut: std::future::Future<Output = Result<serde_json::Value, McpError>> + Send + 'static,
+    {
+        let data = data();
+        let ctx = Context::new(
+            &data.request_id,
+            None,
+            &data.client_caps,
+            &data.server_caps,
+            data.protocol_version,
+            peer,
+        )
+        .with_task_store(store);
+        ctx.spawn_task("test", f).unwrap()
+    }
+
+    #[tokio::test]
+    async fn spawned_task_stores_its_result() {
+        let store = Arc::new(TaskManager::new());
+        let peer = RecPeer::default();
+        let task = spawn(&store, &peer, async move |task: TaskContext| {
+            task.set_status_message("crunching")?;
+            task.input_required().await?;
+            task.resume().await?;
+            Ok(serde_json::json!({"content": [], "answer": 42}))
+        });
+        assert_eq!(task.status, TaskStatus::Working);
+
+        let state = store.wait_terminal(&task.task_id).await.unwrap();
+        assert_eq!(state.task.status, TaskStatus::Completed);
+        assert_eq!(state.task.status_message.as_deref(), Some("crunching"));
+        match store.payload(&task.task_id) {
+            Some(TaskPayload::Success(v)) => assert_eq!(v["answer"], 42),
+            other => panic!("expected success payload, got {other:?}"),
+        }
+        tokio::task::yield_now().await;
+        assert_eq!(peer.statuses(), ["input_required", "working", "completed"]);
+    }
+
+    #[tokio::test]
+    async fn spawned_task_errors_and_panics_fail_the_task() {
+        let store = Arc::new(TaskManager::new());
+        let peer = RecPeer::default();
+
+        let task = spawn(&store, &peer, |_| async {
+            Err(McpError::invalid_params("tools/call", "bad input"))
+        });
+        let state = store.wait_terminal(&task.task_id).await.unwrap();
+        assert_eq!(state.task.status, TaskStatus::Failed);
+        assert!(matches!(
+            store.payload(&task.task_id),
+            Some(TaskPayload::Error(e)) if e.code == -32602
+        ));
+
+        let task = spawn(&store, &peer, |_| async { panic!("boom") });
+        let state = store.wait_terminal(&task.task_id).await.unwrap();
+        assert_eq!(state.task.status, TaskStatus::Failed);
+        assert_eq!(state.task.status_message.as_deref(), Some("task panicked"));
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_spawned_task_stops_the_work() {
+        let store = Arc::new(TaskManager::new());
+        let peer = RecPeer::default();
+        let reached_end = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = Arc::clone(&reached_end);
+        let task = spawn(&store, &peer, move |_| async move {
+            futures::future::pending::<()>().await;
+            flag.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(serde_json::json!({}))
+        });
+
+        store.cancel(&task.task_id).unwrap();
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(
+            store.get(&task.task_id).unwrap().task.status,
+            TaskStatus::Cancelled
+        );
+        assert!(store.payload(&task.task_id).is_none());
+        assert!(!reached_end.load(std::sync::atomic::Ordering::SeqCst));
+        assert_eq!(peer.statuses(), ["cancelled"]);
+    }
+
+    #[test]
+    fn spawn_task_requires_a_task_store() {
+        let data = data();
+        let ctx = Context::new(
+            &data.request_id,
+            None,
+            &data.client_caps,
+            &data.server_caps,
+            data.protocol_version,
+            &crate::context::NoOpPeer,
+        );
+        let result = ctx.spawn_task("orphan", |_| async { Ok(()) });
+        assert!(result.is_err());
+    }
 
     #[tokio::test]
     async fn test_task_service_handler() -> Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,355 @@
+//! Offline export of a server's catalog.
+//!
+//! [`Server::export_catalog`] lists every tool, resource, resource template
+//! and prompt through the server's own routing, the same way a client would
+//! see them, and returns a serializable [`Catalog`] together with the server
+//! info and capabilities. No transport is started, so the catalog can be
+//! generated at build time for documentation or a tool registry.
+//!
+//! [`Catalog::render`] encodes it as JSON, or as YAML with the `catalog-yaml`
+//! feature. [`Server::export_catalog_if_requested`] wires this to an
+//! `--export-catalog[=json|yaml]` command-line flag, so any server binary can
+//! dump its catalog to stdout instead of serving:
+//!
+//! ```rust,ignore
+//! #[tokio::main]
+//! async fn main() -> Result<(), McpError> {
+//!     let server = ServerBuilder::new(MyServer).with_tools(MyServer).build();
+//!     if server.export_catalog_if_requested().await? {
+//!         return Ok(());
+//!     }
+//!     server.serve(StdioTransport::new()).await
+//! }
+//! ```
+//!
+//! ```text
+//! $ my-server --export-catalog=yaml > catalog.yaml
+//! ```
+
+use crate::Server;
+use crate::context::{Context, NoOpPeer};
+use crate::middleware::HasCapabilities;
+use crate::self_check::list_all;
+use crate::server::RequestRouter;
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::{Prompt, Resource, ResourceTemplate, Tool};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The command-line flag recognized by [`CatalogFormat::from_args`].
+pub const EXPORT_FLAG: &str = "--export-catalog";
+
+/// Everything a server advertises to clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Catalog {
+    /// Server name, version and display metadata.
+    pub server_info: ServerInfo,
+    /// Capabilities advertised during initialization.
+    pub capabilities: ServerCapabilities,
+    /// Tools, with their input and output schemas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<Tool>,
+    /// Static resources.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<Resource>,
+    /// Resource templates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_templates: Vec<ResourceTemplate>,
+    /// Prompts, with their arguments.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompts: Vec<Prompt>,
+}
+
+/// Encoding used by [`Catalog::render`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CatalogFormat {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+    /// YAML. Rendering requires the `catalog-yaml` feature.
+    Yaml,
+}
+
+impl CatalogFormat {
+    /// Look for `--export-catalog` or `--export-catalog=<format>` among
+    /// command-line arguments, e.g. `std::env::args()`.
+    ///
+    /// Returns `Ok(None)` when the flag is absent, and an error for an
+    /// unknown format. The bare flag selects JSON.
+    pub fn from_args<I, S>(args: I) -> Result<Option<Self>, McpError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for arg in args {
+            let arg = arg.as_ref();
+            if arg == EXPORT_FLAG {
+                return Ok(Some(Self::Json));
+            }
+            if let Some(format) = arg
+                .strip_prefix(EXPORT_FLAG)
+                .and_then(|rest| rest.strip_prefix('='))
+            {
+                return format.parse().map(Some);
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl FromStr for CatalogFormat {
+    type Err = McpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            other => Err(McpError::invalid_request(format!(
+                "unknown catalog format {other:?}, expected \"json\" or \"yaml\""
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CatalogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
+impl Catalog {
+    /// Encode the catalog in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Fails if serialization fails, or for [`CatalogFormat::Yaml`] when the
+    /// `catalog-yaml` feature is disabled.
+    pub fn render(&self, format: CatalogFormat) -> Result<String, McpError> {
+        match format {
+            CatalogFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| McpError::internal_with_source("failed to encode catalog", e)),
+            #[cfg(feature = "catalog-yaml")]
+            CatalogFormat::Yaml => serde_yaml_ng::to_string(self)
+                .map_err(|e| McpError::internal_with_source("failed to encode catalog", e)),
+            #[cfg(not(feature = "catalog-yaml"))]
+            CatalogFormat::Yaml => Err(McpError::invalid_request(
+                "YAML catalogs require the `catalog-yaml` feature of mcpkit-server",
+            )),
+        }
+    }
+}
+
+impl<H, T, R, P, K> Server<H, T, R, P, K>
+where
+    H: crate::ServerHandler,
+    Self: RequestRouter + HasCapabilities,
+{
+    /// Collect the server's catalog without starting a transport.
+    ///
+    /// See the [module documentation](crate::catalog).
+    ///
+    /// # Errors
+    ///
+    /// Fails if a list request fails or returns a malformed result.
+    pub async fn export_catalog(&self) -> Result<Catalog, McpError> {
+        export_catalog(self, self.server_info()).await
+    }
+
+    /// Print the catalog to stdout when the process was started with
+    /// `--export-catalog[=json|yaml]`.
+    ///
+    /// Returns `true` when the catalog was printed, in which case the caller
+    /// should exit instead of serving.
+    ///
+    /// # Errors
+    ///
+    /// Fails for an unknown format or when the catalog cannot be collected
+    /// or encoded.
+    pub async fn export_catalog_if_requested(&self) -> Result<bool, McpError> {
+        let Some(format) = CatalogFormat::from_args(std::env::args().skip(1))? else {
+            return Ok(false);
+        };
+        println!("{}", self.export_catalog().await?.render(format)?);
+        Ok(true)
+    }
+}
+
+/// Collect the catalog of any router, e.g. a server with middleware applied.
+///
+/// # Errors
+///
+/// Fails if a list request fails or returns a malformed result.
+pub async fn export_catalog<S>(server: &S, server_info: ServerInfo) -> Result<Catalog, McpError>
+where
+    S: RequestRouter + HasCapabilities,
+{
+    let capabilities = server.server_capabilities();
+    // Declare every client capability so tools gated on `requires` are listed.
+    let client_caps = ClientCapabilities::default()
+        .with_roots()
+        .with_sampling()
+        .with_elicitation();
+    let request_id = RequestId::String("__export_catalog__".to_string());
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &capabilities,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+
+    let mut catalog = Catalog {
+        server_info,
+        capabilities: capabilities.clone(),
+        tools: Vec::new(),
+        resources: Vec::new(),
+        resource_templates: Vec::new(),
+        prompts: Vec::new(),
+    };
+    if capabilities.has_tools() {
+        catalog.tools = list(server, &ctx, "tools/list", "tools").await?;
+    }
+    if capabilities.has_resources() {
+        catalog.resources = list(server, &ctx, "resources/list", "resources").await?;
+        catalog.resource_templates = list(
+            server,
+            &ctx,
+            "resources/templates/list",
+            "resourceTemplates",
+        )
+        .await?;
+    }
+    if capabilities.has_prompts() {
+        catalog.prompts = list(server, &ctx, "prompts/list", "prompts").await?;
+    }
+    Ok(catalog)
+}
+
+async fn list<S: RequestRouter, T: DeserializeOwned>(
+    server: &S,
+    ctx: &Context<'_>,
+    method: &str,
+    field: &str,
+) -> Result<Vec<T>, McpError> {
+    match list_all(server, ctx, method, field).await {
+        (items, None) => Ok(items),
+        (_, Some(message)) => Err(McpError::internal(format!("{method}: {message}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServerBuilder;
+    use crate::handler::{PromptHandler, ServerHandler, ToolHandler};
+    use mcpkit_core::types::{GetPromptResult, PromptArgument, ToolOutput};
+
+    struct Catalogued;
+
+    impl ServerHandler for Catalogued {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("catalogued", "2.1.0")
+        }
+        fn capabilities(&self) -> ServerCapabilities {
+            ServerCapabilities::new().with_tools().with_prompts()
+        }
+    }
+
+    impl ToolHandler for Catalogued {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(vec![
+                Tool::new("search").description("Find things"),
+                Tool::new("fetch").description("Fetch a URL"),
+            ])
+        }
+        async fn call_tool(
+            &self,
+            _name: &str,
+            _args: mcpkit_core::types::Object,
+            _ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            Ok(ToolOutput::text("ok"))
+        }
+    }
+
+    impl PromptHandler for Catalogued {
+        async fn list_prompts(&self, _ctx: &Context<'_>) -> Result<Vec<Prompt>, McpError> {
+            Ok(vec![Prompt::new("greet").argument(
+                PromptArgument::required("name", "Who to greet"),
+            )])
+        }
+        async fn get_prompt(
+            &self,
+            _name: &str,
+            _args: Option<mcpkit_core::types::Object>,
+            _ctx: &Context<'_>,
+        ) -> Result<GetPromptResult, McpError> {
+            Err(McpError::internal("not rendered in this test"))
+        }
+    }
+
+    #[tokio::test]
+    async fn catalog_lists_everything_the_server_advertises() {
+        let server = ServerBuilder::new(Catalogued)
+            .with_tools(Catalogued)
+            .with_prompts(Catalogued)
+            .build()
+            .list_page_size(1);
+        let catalog = server.export_catalog().await.unwrap();
+
+        assert_eq!(catalog.server_info.version, "2.1.0");
+        let tools: Vec<_> = catalog.tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tools, ["search", "fetch"]);
+        assert_eq!(catalog.prompts[0].name, "greet");
+        assert!(catalog.resources.is_empty());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&catalog.render(CatalogFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["serverInfo"]["name"], "catalogued");
+        assert_eq!(json["tools"][1]["inputSchema"]["type"], "object");
+        assert!(json.get("resources").is_none());
+    }
+
+    #[test]
+    fn format_is_read_from_the_command_line() {
+        let parse = |args: &[&str]| CatalogFormat::from_args(args);
+        assert_eq!(parse(&["--verbose"]).unwrap(), None);
+        assert_eq!(
+            parse(&["--export-catalog"]).unwrap(),
+            Some(CatalogFormat::Json)
+        );
+        assert_eq!(
+            parse(&["--export-catalog=YAML"]).unwrap(),
+            Some(CatalogFormat::Yaml)
+        );
+        assert!(parse(&["--export-catalog=toml"]).is_err());
+        assert_eq!(parse(&["--export-catalogue"]).unwrap(), None);
+    }
+
+    #[cfg(feature = "catalog-yaml")]
+    #[tokio::test]
+    async fn catalog_renders_as_yaml() {
+        let server = ServerBuilder::new(Catalogued)
+            .with_tools(Catalogued)
+            .with_prompts(Catalogued)
+            .build();
+        let yaml = server
+            .export_catalog()
+            .await
+            .unwrap()
+            .render(CatalogFormat::Yaml)
+            .unwrap();
+        assert!(yaml.contains("name: search"), "{yaml}");
+    }
+}
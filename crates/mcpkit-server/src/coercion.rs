@@ -0,0 +1,264 @@
+//! Lenient coercion of tool arguments.
+//!
+//! Language models often quote scalars, sending `"5"` for an integer or
+//! `"true"` for a boolean, and strict deserialization rejects such calls with
+//! `invalid_params`. [`coerce_arguments`] rewrites those arguments to the types
+//! a tool's `inputSchema` declares before they are checked and deserialized.
+//!
+//! Coercion is opt-in: per server with `#[mcp_server(.., coerce_args)]`, per
+//! tool with `#[tool(.., coerce_args)]`, or by calling [`coerce_arguments`]
+//! from a hand-written [`ToolHandler`](crate::ToolHandler).
+//!
+//! Only unambiguous conversions are made, on top-level arguments and the
+//! elements of array arguments:
+//!
+//! - a string holding an integer, for `integer` (and `number`) properties,
+//! - a string holding a finite number, for `number` properties,
+//! - `"true"` or `"false"` in any case, for `boolean` properties,
+//! - a string with surrounding whitespace, for `enum` properties when the
+//!   trimmed string is one of the choices.
+//!
+//! Surrounding whitespace is ignored when parsing. Properties that also accept
+//! strings are left alone. Each coercion is logged at `debug` level.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::coercion::coerce_arguments;
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "properties": {
+//!         "limit": { "type": "integer" },
+//!         "exact": { "type": "boolean" },
+//!         "query": { "type": "string" }
+//!     }
+//! });
+//! let mut args = json!({ "limit": " 5", "exact": "TRUE", "query": "42" })
+//!     .as_object()
+//!     .cloned()
+//!     .unwrap();
+//!
+//! let coercions = coerce_arguments("search", &schema, &mut args);
+//! assert_eq!(coercions.len(), 2);
+//! assert_eq!(args["limit"], json!(5));
+//! assert_eq!(args["exact"], json!(true));
+//! assert_eq!(args["query"], json!("42"));
+//! ```
+
+use mcpkit_core::types::Object;
+use serde_json::{Number, Value};
+
+/// One argument rewritten by [`coerce_arguments`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coercion {
+    /// The argument's name, with `[index]` appended for array elements.
+    pub param: String,
+    /// The value the client sent.
+    pub from: Value,
+    /// The value passed on to the tool.
+    pub to: Value,
+}
+
+/// Coerce `args` in place to the property types declared by `schema`, a
+/// tool's `inputSchema`, returning what was changed.
+///
+/// Arguments without a property schema, or whose schema has no `type`, are
+/// left as they are. See the [module documentation](self) for the rules.
+pub fn coerce_arguments(tool: &str, schema: &Value, args: &mut Object) -> Vec<Coercion> {
+    let mut coercions = Vec::new();
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return coercions;
+    };
+
+    for (name, value) in args.iter_mut() {
+        let Some(property) = properties.get(name) else {
+            continue;
+        };
+        match value {
+            Value::Array(items) if types(property).contains(&"array") => {
+                let Some(item_schema) = property.get("items") else {
+                    continue;
+                };
+                for (index, item) in items.iter_mut().enumerate() {
+                    if let Some(coerced) = coerce_value(item_schema, item) {
+                        record(
+                            tool,
+                            format!("{name}[{index}]"),
+                            item,
+                            coerced,
+                            &mut coercions,
+                        );
+                    }
+                }
+            }
+            _ => {
+                if let Some(coerced) = coerce_value(property, value) {
+                    record(tool, name.clone(), value, coerced, &mut coercions);
+                }
+            }
+        }
+    }
+    coercions
+}
+
+/// Replace `value` with `coerced`, logging and recording the change.
+fn record(
+    tool: &str,
+    param: String,
+    value: &mut Value,
+    coerced: Value,
+    coercions: &mut Vec<Coercion>,
+) {
+    tracing::debug!(
+        tool,
+        param = %param,
+        from = %value,
+        to = %coerced,
+        "Coerced tool argument"
+    );
+    let from = std::mem::replace(value, coerced.clone());
+    coercions.push(Coercion {
+        param,
+        from,
+        to: coerced,
+    });
+}
+
+/// The JSON types `schema` accepts, other than `null`.
+fn types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|ty| *ty != "null")
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The value a string `value` coerces to under `schema`, if any.
+fn coerce_value(schema: &Value, value: &Value) -> Option<Value> {
+    let Value::String(text) = value else {
+        return None;
+    };
+    let trimmed = text.trim();
+    let types = types(schema);
+
+    if types.contains(&"string") {
+        let choices = schema.get("enum")?.as_array()?;
+        let is_choice = |s: &str| choices.iter().any(|choice| choice.as_str() == Some(s));
+        return (trimmed != text && !is_choice(text) && is_choice(trimmed))
+            .then(|| Value::String(trimmed.to_string()));
+    }
+    if types.contains(&"integer") || types.contains(&"number") {
+        if let Some(number) = parse_integer(trimmed) {
+            return Some(Value::Number(number));
+        }
+    }
+    if types.contains(&"number") {
+        if let Some(number) = trimmed.parse::<f64>().ok().and_then(Number::from_f64) {
+            return Some(Value::Number(number));
+        }
+    }
+    if types.contains(&"boolean") {
+        if trimmed.eq_ignore_ascii_case("true") {
+            return Some(Value::Bool(true));
+        }
+        if trimmed.eq_ignore_ascii_case("false") {
+            return Some(Value::Bool(false));
+        }
+    }
+    None
+}
+
+fn parse_integer(text: &str) -> Option<Number> {
+    text.parse::<i64>()
+        .map(Number::from)
+        .or_else(|_| text.parse::<u64>().map(Number::from))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn coerce(schema: Value, args: Value) -> (Value, Vec<Coercion>) {
+        let mut args = args.as_object().cloned().unwrap();
+        let coercions = coerce_arguments("test", &schema, &mut args);
+        (Value::Object(args), coercions)
+    }
+
+    fn schema(property: Value) -> Value {
+        json!({ "type": "object", "properties": { "x": property } })
+    }
+
+    #[test]
+    fn coerces_numbers_and_booleans() {
+        let (args, coercions) = coerce(
+            json!({ "type": "object", "properties": {
+                "count": { "type": "integer" },
+                "ratio": { "type": "number" },
+                "flag": { "type": ["boolean", "null"] }
+            }}),
+            json!({ "count": "5", "ratio": " 0.25 ", "flag": "False" }),
+        );
+        assert_eq!(args, json!({ "count": 5, "ratio": 0.25, "flag": false }));
+        assert_eq!(coercions.len(), 3);
+
+        let count = coercions.iter().find(|c| c.param == "count").unwrap();
+        assert_eq!((&count.from, &count.to), (&json!("5"), &json!(5)));
+    }
+
+    #[test]
+    fn rejects_ambiguous_conversions() {
+        for (property, value) in [
+            (json!({ "type": "integer" }), json!("5.5")),
+            (json!({ "type": "integer" }), json!("five")),
+            (json!({ "type": "number" }), json!("NaN")),
+            (json!({ "type": "boolean" }), json!("yes")),
+            (json!({ "type": "boolean" }), json!("1")),
+            (json!({ "type": ["string", "integer"] }), json!("5")),
+            (json!({}), json!("5")),
+        ] {
+            let (args, coercions) = coerce(schema(property.clone()), json!({ "x": value }));
+            assert!(coercions.is_empty(), "{property} coerced {value}");
+            assert_eq!(args["x"], value);
+        }
+    }
+
+    #[test]
+    fn leaves_typed_and_unknown_arguments_alone() {
+        let (args, coercions) = coerce(
+            schema(json!({ "type": "integer" })),
+            json!({ "x": 5, "y": "6" }),
+        );
+        assert!(coercions.is_empty());
+        assert_eq!(args, json!({ "x": 5, "y": "6" }));
+    }
+
+    #[test]
+    fn coerces_array_elements() {
+        let (args, coercions) = coerce(
+            schema(json!({ "type": "array", "items": { "type": "integer" } })),
+            json!({ "x": ["1", 2, "three"] }),
+        );
+        assert_eq!(args["x"], json!([1, 2, "three"]));
+        assert_eq!(coercions.len(), 1);
+        assert_eq!(coercions[0].param, "x[0]");
+    }
+
+    #[test]
+    fn trims_enum_choices() {
+        let property = json!({ "type": "string", "enum": ["asc", "desc"] });
+        let (args, _) = coerce(schema(property.clone()), json!({ "x": " desc\n" }));
+        assert_eq!(args["x"], "desc");
+
+        let (args, coercions) = coerce(schema(property), json!({ "x": " up " }));
+        assert!(coercions.is_empty());
+        assert_eq!(args["x"], " up ");
+    }
+}
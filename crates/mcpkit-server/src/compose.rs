@@ -0,0 +1,536 @@
+//! Mounting handlers from several modules into one server.
+//!
+//! Large servers are easier to build from reusable parts: a
+//! `FilesystemTools` type and a `GitTools` type, each written with
+//! `#[mcp_server]`, served together as one server. [`ServerBuilder::mount`]
+//! adds a module under a name prefix. Tool and prompt names are listed with
+//! the prefix prepended (`fs_` + `read` = `fs_read`), and calls are routed to
+//! the module whose prefix matches, with the prefix stripped again. Resources
+//! are identified by URI, so they are merged without a prefix and a read is
+//! offered to each module in mount order until one knows the URI.
+//!
+//! `#[mcp_server]` types implement [`Mountable`] automatically. Implement it by
+//! hand for other handlers, registering whichever parts the type provides on
+//! the [`Mounts`].
+//!
+//! When prefixes overlap (for example `""` and `fs_`), the longest matching
+//! prefix is tried first, and a module that does not know the name passes the
+//! call on to the next match. Completions are not mounted; attach a completion
+//! handler to the built server with
+//! [`Server::with_completion`](crate::builder::Server::with_completion).
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::{Context, ServerBuilder, ServerHandler, ToolHandler};
+//! use mcpkit_server::compose::{Mountable, Mounts};
+//! use mcpkit_core::capability::ServerInfo;
+//! use mcpkit_core::error::McpError;
+//! use mcpkit_core::types::{Object, Tool, ToolOutput};
+//! use std::sync::Arc;
+//!
+//! struct Main;
+//! impl ServerHandler for Main {
+//!     fn server_info(&self) -> ServerInfo {
+//!         ServerInfo::new("main", "1.0.0")
+//!     }
+//! }
+//!
+//! struct Files;
+//! impl ToolHandler for Files {
+//!     async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+//!         Ok(vec![Tool::new("read")])
+//!     }
+//!     async fn call_tool(&self, _name: &str, _args: Object, _ctx: &Context<'_>) -> Result<ToolOutput, McpError> {
+//!         Ok(ToolOutput::text("contents"))
+//!     }
+//! }
+//! impl Mountable for Files {
+//!     fn mount(self: Arc<Self>, prefix: &str, mounts: &mut Mounts) {
+//!         mounts.add_tools(prefix, self);
+//!     }
+//! }
+//!
+//! let server = ServerBuilder::new(Main).mount("fs_", Files).build();
+//! assert!(server.capabilities().has_tools());
+//! ```
+//!
+//! [`ServerBuilder::mount`]: crate::builder::ServerBuilder::mount
+
+use crate::context::Context;
+use crate::handler::{PromptHandler, ResourceHandler, ToolHandler};
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{
+    ByteRange, GetPromptResult, Object, Prompt, Resource, ResourceContents, ResourceTemplate, Tool,
+    ToolOutput,
+};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A module that can be mounted into a composed server.
+///
+/// Implemented by `#[mcp_server]` for every server type. The implementation
+/// registers each handler the type provides on `mounts`, passing `prefix`
+/// through for tools and prompts.
+pub trait Mountable: Send + Sync + 'static {
+    /// Register this module's handlers on `mounts` under `prefix`.
+    fn mount(self: Arc<Self>, prefix: &str, mounts: &mut Mounts);
+}
+
+impl<M: Mountable> Mountable for Arc<M> {
+    fn mount(self: Arc<Self>, prefix: &str, mounts: &mut Mounts) {
+        M::mount(Arc::clone(&*self), prefix, mounts);
+    }
+}
+
+/// The handlers of all mounted modules, split by capability.
+///
+/// [`ServerBuilder::mount`](crate::builder::ServerBuilder::mount) manages one
+/// of these for you. Build one directly to register the parts with an HTTP
+/// adapter, or with a builder that registers its own handlers too.
+#[derive(Clone, Default)]
+pub struct Mounts {
+    tools: MountedTools,
+    resources: MountedResources,
+    prompts: MountedPrompts,
+}
+
+impl Mounts {
+    /// Create an empty set of mounts.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mount `module` under `prefix`.
+    #[must_use]
+    pub fn mount<M: Mountable>(mut self, prefix: impl Into<String>, module: M) -> Self {
+        Arc::new(module).mount(&prefix.into(), &mut self);
+        self
+    }
+
+    /// Register a tool handler whose tools are exposed as `prefix` + name.
+    pub fn add_tools<T: ToolHandler + 'static>(&mut self, prefix: &str, handler: T) {
+        self.tools.mounts.push(Mounted {
+            prefix: prefix.to_string(),
+            handler: Arc::new(handler),
+        });
+    }
+
+    /// Declare that the mounted tool set changes at runtime, so the server
+    /// advertises `tools.listChanged`. See
+    /// [`DynamicToolRegistry`](crate::registry::DynamicToolRegistry).
+    pub const fn announce_tool_changes(&mut self) {
+        self.tools.list_changed = true;
+    }
+
+    /// Register a resource handler. Resource URIs are not prefixed.
+    pub fn add_resources<R: ResourceHandler + 'static>(&mut self, handler: R) {
+        self.resources.mounts.push(Arc::new(handler));
+    }
+
+    /// Register a prompt handler whose prompts are exposed as `prefix` + name.
+    pub fn add_prompts<P: PromptHandler + 'static>(&mut self, prefix: &str, handler: P) {
+        self.prompts.mounts.push(Mounted {
+            prefix: prefix.to_string(),
+            handler: Arc::new(handler),
+        });
+    }
+
+    /// Split into the tool, resource, and prompt handlers.
+    #[must_use]
+    pub fn into_parts(self) -> (MountedTools, MountedResources, MountedPrompts) {
+        (self.tools, self.resources, self.prompts)
+    }
+
+    pub(crate) fn from_parts(
+        tools: MountedTools,
+        resources: MountedResources,
+        prompts: MountedPrompts,
+    ) -> Self {
+        Self {
+            tools,
+            resources,
+            prompts,
+        }
+    }
+}
+
+impl std::fmt::Debug for Mounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mounts")
+            .field("tools", &self.tools)
+            .field("resources", &self.resources)
+            .field("prompts", &self.prompts)
+            .finish()
+    }
+}
+
+/// A handler mounted under a name prefix.
+struct Mounted<H: ?Sized> {
+    prefix: String,
+    handler: Arc<H>,
+}
+
+impl<H: ?Sized> Clone for Mounted<H> {
+    fn clone(&self) -> Self {
+        Self {
+            prefix: self.prefix.clone(),
+            handler: Arc::clone(&self.handler),
+        }
+    }
+}
+
+/// The mounts whose prefix matches `name`, longest prefix first, each paired
+/// with the name stripped of its prefix.
+fn matching<'m, 'n, H: ?Sized>(
+    mounts: &'m [Mounted<H>],
+    name: &'n str,
+) -> Vec<(&'m Mounted<H>, &'n str)> {
+    let mut matches: Vec<_> = mounts
+        .iter()
+        .filter_map(|m| name.strip_prefix(m.prefix.as_str()).map(|rest| (m, rest)))
+        .collect();
+    matches.sort_by_key(|(m, _)| std::cmp::Reverse(m.prefix.len()));
+    matches
+}
+
+/// The tool handlers of all mounted modules, as one [`ToolHandler`].
+#[derive(Clone, Default)]
+pub struct MountedTools {
+    mounts: Vec<Mounted<dyn crate::dispatch::DynToolHandler>>,
+    list_changed: bool,
+}
+
+impl MountedTools {
+    /// Whether no tool handler is mounted.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mounts.is_empty()
+    }
+
+    /// Whether a mounted module announced that its tools change at runtime.
+    #[must_use]
+    pub const fn list_changed(&self) -> bool {
+        self.list_changed
+    }
+}
+
+impl std::fmt::Debug for MountedTools {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.mounts.iter().map(|m| &m.prefix))
+            .finish()
+    }
+}
+
+impl ToolHandler for MountedTools {
+    async fn list_tools(&self, ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        let mut tools = Vec::new();
+        for mount in &self.mounts {
+            for mut tool in mount.handler.list_tools(ctx).await? {
+                tool.name = format!("{}{}", mount.prefix, tool.name);
+                tools.push(tool);
+            }
+        }
+        Ok(tools)
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        args: Object,
+        ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        for (mount, local) in matching(&self.mounts, name) {
+            match mount.handler.call_tool(local, args.clone(), ctx).await {
+                Err(McpError::MethodNotFound { .. }) => {}
+                result => return result,
+            }
+        }
+        let available = self
+            .list_tools(ctx)
+            .await
+            .map(|tools| tools.into_iter().map(|t| t.name).collect())
+            .unwrap_or_default();
+        Err(McpError::method_not_found_with_suggestions(name, available))
+    }
+}
+
+/// The resource handlers of all mounted modules, as one [`ResourceHandler`].
+#[derive(Clone, Default)]
+pub struct MountedResources {
+    mounts: Vec<Arc<dyn crate::dispatch::DynResourceHandler>>,
+}
+
+impl MountedResources {
+    /// Whether no resource handler is mounted.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mounts.is_empty()
+    }
+}
+
+impl std::fmt::Debug for MountedResources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MountedResources")
+            .field("mounts", &self.mounts.len())
+            .finish()
+    }
+}
+
+impl ResourceHandler for MountedResources {
+    async fn list_resources(&self, ctx: &Context<'_>) -> Result<Vec<Resource>, McpError> {
+        let mut resources = Vec::new();
+        for handler in &self.mounts {
+            resources.extend(handler.list_resources(ctx).await?);
+        }
+        Ok(resources)
+    }
+
+    async fn list_resource_templates(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<ResourceTemplate>, McpError> {
+        let mut templates = Vec::new();
+        for handler in &self.mounts {
+            templates.extend(handler.list_resource_templates(ctx).await?);
+        }
+        Ok(templates)
+    }
+
+    async fn read_resource(
+        &self,
+        uri: &str,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<ResourceContents>, McpError> {
+        for handler in &self.mounts {
+            match handler.read_resource(uri, ctx).await {
+                Err(McpError::ResourceNotFound { .. }) => {}
+                result => return result,
+            }
+        }
+        Err(McpError::resource_not_found(uri))
+    }
+
+    async fn read_resource_range(
+        &self,
+        uri: &str,
+        range: ByteRange,
+        ctx: &Context<'_>,
+    ) -> Result<ResourceContents, McpError> {
+        for handler in &self.mounts {
+            match handler.read_resource_range(uri, range, ctx).await {
+                Err(McpError::ResourceNotFound { .. }) => {}
+                result => return result,
+            }
+        }
+        Err(McpError::resource_not_found(uri))
+    }
+
+    async fn subscribe(&self, uri: &str, ctx: &Context<'_>) -> Result<bool, McpError> {
+        for handler in &self.mounts {
+            if handler.subscribe(uri, ctx).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn unsubscribe(&self, uri: &str, ctx: &Context<'_>) -> Result<bool, McpError> {
+        for handler in &self.mounts {
+            if handler.unsubscribe(uri, ctx).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// The prompt handlers of all mounted modules, as one [`PromptHandler`].
+#[derive(Clone, Default)]
+pub struct MountedPrompts {
+    mounts: Vec<Mounted<dyn crate::dispatch::DynPromptHandler>>,
+}
+
+impl MountedPrompts {
+    /// Whether no prompt handler is mounted.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mounts.is_empty()
+    }
+}
+
+impl std::fmt::Debug for MountedPrompts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.mounts.iter().map(|m| &m.prefix))
+            .finish()
+    }
+}
+
+impl PromptHandler for MountedPrompts {
+    async fn list_prompts(&self, ctx: &Context<'_>) -> Result<Vec<Prompt>, McpError> {
+        let mut prompts = Vec::new();
+        for mount in &self.mounts {
+            for mut prompt in mount.handler.list_prompts(ctx).await? {
+                prompt.name = format!("{}{}", mount.prefix, prompt.name);
+                prompts.push(prompt);
+            }
+        }
+        Ok(prompts)
+    }
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        args: Option<serde_json::Map<String, Value>>,
+        ctx: &Context<'_>,
+    ) -> Result<GetPromptResult, McpError> {
+        for (mount, local) in matching(&self.mounts, name) {
+            match mount.handler.get_prompt(local, args.clone(), ctx).await {
+                Err(McpError::MethodNotFound { .. }) => {}
+                result => return result,
+            }
+        }
+        let available = self
+            .list_prompts(ctx)
+            .await
+            .map(|prompts| prompts.into_iter().map(|p| p.name).collect())
+            .unwrap_or_default();
+        Err(McpError::method_not_found_with_suggestions(name, available))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::NoOpPeer;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+
+    /// Tools named `names`, each answering with `"{label}:{name}"`.
+    struct Labeled {
+        label: &'static str,
+        names: &'static [&'static str],
+    }
+
+    impl ToolHandler for Labeled {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(self.names.iter().map(|n| Tool::new(*n)).collect())
+        }
+
+        async fn call_tool(
+            &self,
+            name: &str,
+            _args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            if self.names.contains(&name) {
+                Ok(ToolOutput::text(format!("{}:{name}", self.label)))
+            } else {
+                Err(McpError::method_not_found(name))
+            }
+        }
+    }
+
+    impl Mountable for Labeled {
+        fn mount(self: Arc<Self>, prefix: &str, mounts: &mut Mounts) {
+            mounts.add_tools(prefix, self);
+        }
+    }
+
+    async fn list(tools: &MountedTools) -> Vec<String> {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+        let tools = tools.list_tools(&ctx).await.expect("list_tools");
+        tools.into_iter().map(|t| t.name).collect()
+    }
+
+    async fn call(tools: &MountedTools, name: &str) -> Result<String, McpError> {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+        match tools.call_tool(name, Object::new(), &ctx).await? {
+            ToolOutput::Success(result) => Ok(serde_json::to_value(&result.content[0])
+                .expect("serialize")["text"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string()),
+            other => panic!("unexpected output {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn tools_are_listed_and_routed_by_prefix() {
+        let (tools, _, _) = Mounts::new()
+            .mount(
+                "fs_",
+                Labeled {
+                    label: "fs",
+                    names: &["read", "write"],
+                },
+            )
+            .mount(
+                "git_",
+                Labeled {
+                    label: "git",
+                    names: &["read"],
+                },
+            )
+            .into_parts();
+
+        assert_eq!(list(&tools).await, ["fs_read", "fs_write", "git_read"]);
+
+        assert_eq!(call(&tools, "fs_read").await.unwrap(), "fs:read");
+        assert_eq!(call(&tools, "git_read").await.unwrap(), "git:read");
+        match call(&tools, "git_write").await {
+            Err(McpError::MethodNotFound { method, .. }) => assert_eq!(method, "git_write"),
+            other => panic!("expected method not found, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn overlapping_prefixes_prefer_the_longest_and_fall_through() {
+        let (tools, _, _) = Mounts::new()
+            .mount(
+                "",
+                Labeled {
+                    label: "root",
+                    names: &["fs_status", "ping"],
+                },
+            )
+            .mount(
+                "fs_",
+                Labeled {
+                    label: "fs",
+                    names: &["ping"],
+                },
+            )
+            .into_parts();
+
+        assert_eq!(call(&tools, "fs_ping").await.unwrap(), "fs:ping");
+        assert_eq!(call(&tools, "fs_status").await.unwrap(), "root:fs_status");
+        assert_eq!(call(&tools, "ping").await.unwrap(), "root:ping");
+    }
+}
@@ -0,0 +1,923 @@
+//! Deployment configuration loaded from a file.
+//!
+//! A [`ServerConfig`] collects the settings an operator tunes per deployment
+//! — server identity, advertised capabilities, timeouts, concurrency and rate
+//! limits, telemetry, and which transport to serve — so they can change
+//! without recompiling. [`ServerConfig::from_file`] reads `mcpkit.json`, or
+//! `mcpkit.toml` with the `config-toml` feature, then applies environment
+//! overrides and validates the result.
+//!
+//! ```toml
+//! [server]
+//! name = "inventory"
+//! version = "2.1.0"
+//! instructions = "Look items up by SKU."
+//!
+//! [capabilities]
+//! logging = true
+//!
+//! [timeouts]
+//! default_ms = 30000
+//! tools = { generate_report = 300000 }
+//!
+//! [limits]
+//! max_concurrent_requests = 64
+//!
+//! [rate_limit]
+//! per_session = { requests = 600, per_secs = 60 }
+//!
+//! [telemetry]
+//! arguments = "redacted"
+//!
+//! [transport]
+//! kind = "http"
+//! bind = "0.0.0.0:3000"
+//! ```
+//!
+//! # Environment overrides
+//!
+//! A variable named `MCPKIT_<SECTION>_<KEY>` replaces one setting, e.g.
+//! `MCPKIT_LIMITS_MAX_CONCURRENT_REQUESTS=16` or `MCPKIT_TRANSPORT_BIND=...`.
+//! Numbers and booleans are parsed as such. Only the settings directly in a
+//! section can be overridden, not per-method or per-tool entries.
+//!
+//! # Errors
+//!
+//! A [`ConfigError`] names where the offending value came from — the file
+//! and line, or the environment variable — and, for a bad value, the dotted
+//! path of the setting:
+//!
+//! ```text
+//! mcpkit.toml:14: `limits.max_concurrent_requests`: must be at least 1
+//! ```
+//!
+//! # Applying it
+//!
+//! [`Server::configure`](crate::Server::configure) applies the identity,
+//! capabilities, timeouts, and page size to a built server;
+//! [`ServerConfig::runtime_config`] (or [`RuntimeConfig::from_file`]) gives
+//! the runtime settings; [`ServerConfig::rate_limit`] the quotas for a
+//! [`RateLimitLayer`](crate::rate_limit::RateLimitLayer). The transport
+//! section is read by the binary, which picks a transport accordingly:
+//!
+//! ```rust,ignore
+//! let config = ServerConfig::from_file("mcpkit.toml")?;
+//! let server = ServerBuilder::new(MyServer).with_tools(MyServer).build().configure(&config);
+//! match config.transport.kind {
+//!     TransportKind::Stdio => {
+//!         ServerRuntime::with_config(server, StdioTransport::new(), config.runtime_config())
+//!             .run()
+//!             .await?
+//!     }
+//!     // Validation guarantees `bind` is set for these.
+//!     TransportKind::Http => serve_http(server, config.transport.bind.unwrap()).await?,
+//!     TransportKind::Websocket => serve_ws(server, config.transport.bind.unwrap()).await?,
+//! }
+//! ```
+
+use crate::invocation::{ArgumentCapture, InvocationTracing};
+use crate::rate_limit::{Quota, RateLimitConfig};
+use crate::server::RuntimeConfig;
+use crate::timeout::Timeouts;
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::time::Duration;
+
+/// Prefix of the environment variables that override settings.
+pub const ENV_PREFIX: &str = "MCPKIT_";
+
+/// The top-level sections, as they appear in the file.
+const SECTIONS: [&str; 7] = [
+    "server",
+    "capabilities",
+    "timeouts",
+    "limits",
+    "rate_limit",
+    "telemetry",
+    "transport",
+];
+
+/// The encoding of a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// JSON (`.json`).
+    Json,
+    /// TOML (`.toml`), with the `config-toml` feature.
+    Toml,
+}
+
+impl ConfigFormat {
+    /// The format of a file, from its extension.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Where a configuration value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    /// A line of the configuration, if it could be found.
+    Source {
+        /// The file name, or `<config>` for a parsed string.
+        name: String,
+        /// The 1-based line.
+        line: Option<usize>,
+    },
+    /// An environment variable.
+    Env(String),
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source {
+                name,
+                line: Some(line),
+            } => write!(f, "{name}:{line}"),
+            Self::Source { name, line: None } => f.write_str(name),
+            Self::Env(var) => write!(f, "environment variable {var}"),
+        }
+    }
+}
+
+/// Why a configuration could not be loaded.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The file could not be read.
+    #[error("cannot read {path}: {source}")]
+    Io {
+        /// The file.
+        path: String,
+        /// The underlying error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// The file is not `.json` or `.toml`, or TOML support is not compiled
+    /// in.
+    #[error("{path}: {reason}")]
+    UnsupportedFormat {
+        /// The file.
+        path: String,
+        /// What is missing.
+        reason: &'static str,
+    },
+    /// The file is not valid JSON or TOML.
+    #[error("{origin}: {message}")]
+    Syntax {
+        /// Where parsing failed.
+        origin: ConfigOrigin,
+        /// The parser's message.
+        message: String,
+    },
+    /// A setting is unknown, has the wrong type, or an invalid value.
+    #[error("{origin}: `{field}`: {message}")]
+    Invalid {
+        /// Where the value came from.
+        origin: ConfigOrigin,
+        /// Dotted path of the setting, e.g. `limits.max_concurrent_requests`.
+        field: String,
+        /// What is wrong with it.
+        message: String,
+    },
+}
+
+impl ConfigError {
+    /// The dotted path of the offending setting, if the error concerns one.
+    #[must_use]
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            Self::Invalid { field, .. } => Some(field),
+            _ => None,
+        }
+    }
+
+    /// The line of the configuration the error points at, if known.
+    #[must_use]
+    pub const fn line(&self) -> Option<usize> {
+        match self {
+            Self::Syntax { origin, .. } | Self::Invalid { origin, .. } => match origin {
+                ConfigOrigin::Source { line, .. } => *line,
+                ConfigOrigin::Env(_) => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// A deployment configuration; see the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Server identity (`[server]`).
+    pub server: IdentityConfig,
+    /// Advertised capabilities (`[capabilities]`).
+    pub capabilities: CapabilitiesConfig,
+    /// Request timeouts (`[timeouts]`).
+    pub timeouts: TimeoutsConfig,
+    /// Concurrency and paging limits (`[limits]`).
+    pub limits: LimitsConfig,
+    /// Per-session quotas (`[rate_limit]`).
+    pub rate_limit: RateLimitsConfig,
+    /// Invocation tracing (`[telemetry]`).
+    pub telemetry: TelemetryConfig,
+    /// The transport to serve (`[transport]`).
+    pub transport: TransportConfig,
+}
+
+/// Overrides of the server info and instructions the handler reports.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdentityConfig {
+    /// Server name.
+    #[serde(deserialize_with = "scalar_string")]
+    pub name: Option<String>,
+    /// Human-readable display title.
+    #[serde(deserialize_with = "scalar_string")]
+    pub title: Option<String>,
+    /// Server version.
+    #[serde(deserialize_with = "scalar_string")]
+    pub version: Option<String>,
+    /// Instructions sent to the client during initialization.
+    #[serde(deserialize_with = "scalar_string")]
+    pub instructions: Option<String>,
+}
+
+impl IdentityConfig {
+    /// Replace the fields of `info` this configuration sets.
+    #[must_use]
+    pub fn apply(&self, mut info: ServerInfo) -> ServerInfo {
+        if let Some(name) = &self.name {
+            info.name.clone_from(name);
+        }
+        if let Some(title) = &self.title {
+            info.title = Some(title.clone());
+        }
+        if let Some(version) = &self.version {
+            info.version.clone_from(version);
+        }
+        info
+    }
+}
+
+/// Capabilities to advertise (`true`) or withdraw (`false`); unset ones are
+/// left as the server declares them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CapabilitiesConfig {
+    /// `tools`.
+    pub tools: Option<bool>,
+    /// `resources`.
+    pub resources: Option<bool>,
+    /// `prompts`.
+    pub prompts: Option<bool>,
+    /// `tasks`.
+    pub tasks: Option<bool>,
+    /// `logging`.
+    pub logging: Option<bool>,
+    /// `completions`.
+    pub completions: Option<bool>,
+}
+
+impl CapabilitiesConfig {
+    /// Apply the settings to `caps`.
+    #[must_use]
+    pub fn apply(&self, mut caps: ServerCapabilities) -> ServerCapabilities {
+        macro_rules! toggle {
+            ($($field:ident => $enable:ident),*) => {$(
+                match self.$field {
+                    Some(true) if caps.$field.is_none() => caps = caps.$enable(),
+                    Some(false) => caps.$field = None,
+                    _ => {}
+                }
+            )*};
+        }
+        toggle!(
+            tools => with_tools,
+            resources => with_resources,
+            prompts => with_prompts,
+            tasks => with_tasks,
+            logging => with_logging,
+            completions => with_completions
+        );
+        caps
+    }
+}
+
+/// Request timeouts, in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeoutsConfig {
+    /// Timeout of requests with no more specific one.
+    pub default_ms: Option<u64>,
+    /// Timeouts per JSON-RPC method.
+    pub methods: BTreeMap<String, u64>,
+    /// Timeouts per tool.
+    pub tools: BTreeMap<String, u64>,
+    /// How long a server-initiated request waits for the client.
+    pub outbound_request_ms: Option<u64>,
+    /// How long a cancelled handler may keep running.
+    pub cancellation_grace_ms: Option<u64>,
+    /// How long a shutdown waits for in-flight requests.
+    pub shutdown_ms: Option<u64>,
+}
+
+impl TimeoutsConfig {
+    /// Whether no request timeout is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.default_ms.is_none() && self.methods.is_empty() && self.tools.is_empty()
+    }
+
+    /// The request timeouts.
+    #[must_use]
+    pub fn timeouts(&self) -> Timeouts {
+        let mut timeouts = Timeouts::new();
+        if let Some(ms) = self.default_ms {
+            timeouts = timeouts.default_timeout(Duration::from_millis(ms));
+        }
+        for (method, ms) in &self.methods {
+            timeouts = timeouts.method(method, Duration::from_millis(*ms));
+        }
+        for (tool, ms) in &self.tools {
+            timeouts = timeouts.tool(tool, Duration::from_millis(*ms));
+        }
+        timeouts
+    }
+}
+
+/// Concurrency and paging limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Maximum requests handled at once.
+    pub max_concurrent_requests: Option<usize>,
+    /// Page size of `*/list` results.
+    pub list_page_size: Option<usize>,
+    /// Retention of tasks created without a `ttl`, in milliseconds.
+    pub default_task_ttl_ms: Option<u64>,
+}
+
+/// Per-session request quotas.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitsConfig {
+    /// Quota on every request of a session.
+    pub per_session: Option<QuotaConfig>,
+    /// Quotas per JSON-RPC method.
+    pub methods: BTreeMap<String, QuotaConfig>,
+    /// Quotas per tool.
+    pub tools: BTreeMap<String, QuotaConfig>,
+}
+
+/// `requests` per `per_secs` seconds, at most `burst` back to back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaConfig {
+    /// Requests allowed per window.
+    pub requests: u32,
+    /// Window length in seconds.
+    #[serde(default = "one")]
+    pub per_secs: u64,
+    /// Requests allowed back to back; defaults to `requests`.
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+const fn one() -> u64 {
+    1
+}
+
+impl QuotaConfig {
+    /// The quota.
+    #[must_use]
+    pub fn quota(&self) -> Quota {
+        let quota = Quota::new(self.requests, Duration::from_secs(self.per_secs));
+        match self.burst {
+            Some(burst) => quota.with_burst(burst),
+            None => quota,
+        }
+    }
+}
+
+/// Invocation tracing settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryConfig {
+    /// What invocation spans record of the arguments: `off`, `redacted`, or
+    /// `full`.
+    pub arguments: ArgumentCapture,
+    /// Argument names redacted in addition to the defaults.
+    pub redact_keys: Vec<String>,
+}
+
+/// Which transport a server binary should serve.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    /// Standard input and output.
+    #[default]
+    Stdio,
+    /// Streamable HTTP.
+    Http,
+    /// WebSocket.
+    Websocket,
+}
+
+/// The transport section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransportConfig {
+    /// The transport.
+    pub kind: TransportKind,
+    /// Address to listen on; required for `http` and `websocket`.
+    pub bind: Option<SocketAddr>,
+    /// Path of the MCP endpoint for `http` and `websocket`.
+    #[serde(deserialize_with = "scalar_string")]
+    pub path: Option<String>,
+}
+
+impl ServerConfig {
+    /// Load a `.json` or `.toml` file, apply the `MCPKIT_*` environment
+    /// overrides, and validate it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`] if the file cannot be read or parsed, or a
+    /// setting is unknown or invalid.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let Some(format) = ConfigFormat::from_path(path) else {
+            return Err(ConfigError::UnsupportedFormat {
+                path: name,
+                reason: "expected a .json or .toml file",
+            });
+        };
+        let source = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: name.clone(),
+            source,
+        })?;
+        load(&source, format, &name, std::env::vars())
+    }
+
+    /// Parse and validate a configuration, without environment overrides.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`] if the source cannot be parsed, or a setting
+    /// is unknown or invalid.
+    pub fn parse(source: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        load(source, format, "<config>", std::iter::empty())
+    }
+
+    /// Parse a configuration, apply overrides from `vars` (name and value
+    /// pairs, as from [`std::env::vars`]), and validate it.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::parse`].
+    pub fn parse_with_env(
+        source: &str,
+        format: ConfigFormat,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        load(source, format, "<config>", vars)
+    }
+
+    /// The runtime settings: concurrency, outbound and shutdown timeouts,
+    /// task retention, and invocation tracing.
+    #[must_use]
+    pub fn runtime_config(&self) -> RuntimeConfig {
+        let mut config = RuntimeConfig::default();
+        if let Some(max) = self.limits.max_concurrent_requests {
+            config.max_concurrent_requests = max;
+        }
+        if let Some(ttl) = self.limits.default_task_ttl_ms {
+            config.default_task_ttl_ms = Some(ttl);
+        }
+        let timeouts = &self.timeouts;
+        if let Some(ms) = timeouts.outbound_request_ms {
+            config.outbound_request_timeout = Duration::from_millis(ms);
+        }
+        if let Some(ms) = timeouts.cancellation_grace_ms {
+            config.cancellation_grace = Duration::from_millis(ms);
+        }
+        if let Some(ms) = timeouts.shutdown_ms {
+            config.shutdown_timeout = Duration::from_millis(ms);
+        }
+        config.invocation_tracing = self.invocation_tracing();
+        config
+    }
+
+    /// The invocation tracing settings.
+    #[must_use]
+    pub fn invocation_tracing(&self) -> InvocationTracing {
+        let tracing = InvocationTracing::new().arguments(self.telemetry.arguments);
+        self.telemetry
+            .redact_keys
+            .iter()
+            .fold(tracing, InvocationTracing::redact_key)
+    }
+
+    /// The configured quotas, or `None` if there are none.
+    #[must_use]
+    pub fn rate_limit(&self) -> Option<RateLimitConfig> {
+        let limits = &self.rate_limit;
+        let mut config = RateLimitConfig::new();
+        if let Some(quota) = &limits.per_session {
+            config = config.per_session(quota.quota());
+        }
+        for (method, quota) in &limits.methods {
+            config = config.method(method, quota.quota());
+        }
+        for (tool, quota) in &limits.tools {
+            config = config.tool(tool, quota.quota());
+        }
+        (!config.is_empty()).then_some(config)
+    }
+
+    /// Check the values deserialization cannot, returning the path of the
+    /// offending setting and the problem.
+    fn validate(&self) -> Result<(), (String, String)> {
+        fn positive(field: String, value: Option<u64>) -> Result<(), (String, String)> {
+            match value {
+                Some(0) => Err((field, "must be greater than zero".to_string())),
+                _ => Ok(()),
+            }
+        }
+
+        if self.server.name.as_deref().is_some_and(str::is_empty) {
+            return Err(("server.name".into(), "must not be empty".into()));
+        }
+        let timeouts = &self.timeouts;
+        positive("timeouts.default_ms".into(), timeouts.default_ms)?;
+        for (method, ms) in &timeouts.methods {
+            positive(format!("timeouts.methods.{method}"), Some(*ms))?;
+        }
+        for (tool, ms) in &timeouts.tools {
+            positive(format!("timeouts.tools.{tool}"), Some(*ms))?;
+        }
+        positive(
+            "timeouts.outbound_request_ms".into(),
+            timeouts.outbound_request_ms,
+        )?;
+        if self.limits.max_concurrent_requests == Some(0) {
+            return Err((
+                "limits.max_concurrent_requests".into(),
+                "must be at least 1".into(),
+            ));
+        }
+
+        let rate_limit = &self.rate_limit;
+        let quotas = rate_limit
+            .per_session
+            .iter()
+            .map(|quota| ("rate_limit.per_session".to_string(), quota))
+            .chain(
+                rate_limit
+                    .methods
+                    .iter()
+                    .map(|(method, quota)| (format!("rate_limit.methods.{method}"), quota)),
+            )
+            .chain(
+                rate_limit
+                    .tools
+                    .iter()
+                    .map(|(tool, quota)| (format!("rate_limit.tools.{tool}"), quota)),
+            );
+        for (field, quota) in quotas {
+            positive(format!("{field}.requests"), Some(u64::from(quota.requests)))?;
+            positive(format!("{field}.per_secs"), Some(quota.per_secs))?;
+        }
+
+        let transport = &self.transport;
+        match (transport.kind, transport.bind) {
+            (TransportKind::Http | TransportKind::Websocket, None) => {
+                return Err((
+                    "transport.bind".into(),
+                    "required by the http and websocket transports".into(),
+                ));
+            }
+            (TransportKind::Stdio, Some(_)) => {
+                return Err((
+                    "transport.bind".into(),
+                    "the stdio transport does not listen on an address".into(),
+                ));
+            }
+            _ => {}
+        }
+        if let Some(path) = &transport.path {
+            if !path.starts_with('/') {
+                return Err(("transport.path".into(), "must start with `/`".into()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RuntimeConfig {
+    /// Load the runtime settings from a configuration file.
+    ///
+    /// Shorthand for [`ServerConfig::from_file`] followed by
+    /// [`ServerConfig::runtime_config`]; load the [`ServerConfig`] itself to
+    /// also apply the identity, capabilities, and timeouts to a server.
+    ///
+    /// # Errors
+    ///
+    /// As for [`ServerConfig::from_file`].
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        ServerConfig::from_file(path).map(|config| config.runtime_config())
+    }
+}
+
+fn load(
+    source: &str,
+    format: ConfigFormat,
+    name: &str,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<ServerConfig, ConfigError> {
+    let origin = |line| ConfigOrigin::Source {
+        name: name.to_string(),
+        line,
+    };
+    let mut value = parse_value(source, format, name)?;
+    let overrides = apply_env(&mut value, vars);
+    let located = |field: String, message: String| {
+        let origin = overrides.get(&field).map_or_else(
+            || origin(locate(source, &field)),
+            |var| ConfigOrigin::Env(var.clone()),
+        );
+        ConfigError::Invalid {
+            origin,
+            field,
+            message,
+        }
+    };
+
+    let config: ServerConfig = serde_path_to_error::deserialize(value).map_err(|err| {
+        let field = err.path().to_string();
+        located(field, err.into_inner().to_string())
+    })?;
+    config
+        .validate()
+        .map_err(|(field, message)| located(field, message))?;
+    Ok(config)
+}
+
+fn parse_value(source: &str, format: ConfigFormat, name: &str) -> Result<Value, ConfigError> {
+    let syntax = |line, message| ConfigError::Syntax {
+        origin: ConfigOrigin::Source {
+            name: name.to_string(),
+            line,
+        },
+        message,
+    };
+    let value = match format {
+        ConfigFormat::Json => serde_json::from_str(source).map_err(|err| {
+            let line = (err.line() > 0).then_some(err.line());
+            // serde_json appends " at line L column C"; the origin has it.
+            let message = err.to_string();
+            let message = message
+                .rsplit_once(" at line ")
+                .map_or(message.as_str(), |(message, _)| message)
+                .to_string();
+            syntax(line, message)
+        })?,
+        #[cfg(feature = "config-toml")]
+        ConfigFormat::Toml => toml::from_str(source).map_err(|err| {
+            let line = err
+                .span()
+                .map(|span| source[..span.start].matches('\n').count() + 1);
+            syntax(line, err.message().to_string())
+        })?,
+        #[cfg(not(feature = "config-toml"))]
+        ConfigFormat::Toml => {
+            return Err(ConfigError::UnsupportedFormat {
+                path: name.to_string(),
+                reason: "TOML configuration needs the `config-toml` feature",
+            });
+        }
+    };
+    match value {
+        Value::Object(_) => Ok(value),
+        _ => Err(syntax(None, "expected a table of sections".to_string())),
+    }
+}
+
+/// Apply `MCPKIT_<SECTION>_<KEY>` variables to `value`, returning the path
+/// each one set and its variable name.
+fn apply_env(
+    value: &mut Value,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> HashMap<String, String> {
+    let mut overrides = HashMap::new();
+    let Value::Object(root) = value else {
+        return overrides;
+    };
+    for (var, raw) in vars {
+        let Some(rest) = var.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        let rest = rest.to_ascii_lowercase();
+        let Some((section, key)) = SECTIONS.iter().find_map(|section| {
+            let key = rest.strip_prefix(section)?.strip_prefix('_')?;
+            Some((*section, key))
+        }) else {
+            continue;
+        };
+        let setting = match raw.parse::<Value>() {
+            Ok(parsed @ (Value::Bool(_) | Value::Number(_) | Value::Array(_))) => parsed,
+            _ => Value::String(raw),
+        };
+        let entry = root
+            .entry(section)
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        if let Value::Object(table) = entry {
+            table.insert(key.to_string(), setting);
+            overrides.insert(format!("{section}.{key}"), var);
+        }
+    }
+    overrides
+}
+
+/// The line declaring the setting at the dotted `field`, or the closest
+/// enclosing one found.
+fn locate(source: &str, field: &str) -> Option<usize> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut found = None;
+    let mut from = 0;
+    for key in field.split('.').filter(|key| !key.is_empty()) {
+        let Some(offset) = lines[from..].iter().position(|line| declares(line, key)) else {
+            break;
+        };
+        from += offset;
+        found = Some(from + 1);
+    }
+    found
+}
+
+/// Whether `line` declares `key`, as a TOML key or table header or a JSON
+/// object key.
+fn declares(line: &str, key: &str) -> bool {
+    let line = line.trim_start();
+    if let Some(header) = line.strip_prefix('[') {
+        let header = header.trim_start_matches('[');
+        let header = header.split(']').next().unwrap_or_default();
+        return header
+            .split('.')
+            .any(|part| part.trim().trim_matches('"') == key);
+    }
+    let rest = line
+        .strip_prefix('"')
+        .and_then(|line| line.strip_prefix(key))
+        .and_then(|rest| rest.strip_prefix('"'))
+        .or_else(|| line.strip_prefix(key));
+    rest.is_some_and(|rest| matches!(rest.trim_start().chars().next(), Some('=' | ':')))
+}
+
+/// Accept a number or boolean where a string is expected, so that an
+/// environment override such as `MCPKIT_SERVER_VERSION=2` still reads as
+/// the string `"2"`.
+fn scalar_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(value @ (Value::Number(_) | Value::Bool(_))) => Ok(Some(value.to_string())),
+        Some(other) => Err(D::Error::custom(format!(
+            "invalid type: {other}, expected a string"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+  "server": { "name": "inventory", "version": "2.1.0" },
+  "capabilities": { "logging": true, "prompts": false },
+  "timeouts": {
+    "default_ms": 30000,
+    "tools": { "generate_report": 300000 }
+  },
+  "limits": {
+    "max_concurrent_requests": 64
+  },
+  "rate_limit": { "per_session": { "requests": 600, "per_secs": 60 } },
+  "transport": { "kind": "http", "bind": "127.0.0.1:3000" }
+}"#;
+
+    #[test]
+    fn json_configures_every_section() {
+        let config = ServerConfig::parse(JSON, ConfigFormat::Json).unwrap();
+        assert_eq!(config.server.name.as_deref(), Some("inventory"));
+        assert_eq!(config.transport.kind, TransportKind::Http);
+        assert_eq!(config.transport.bind.map(|addr| addr.port()), Some(3000));
+
+        let runtime = config.runtime_config();
+        assert_eq!(runtime.max_concurrent_requests, 64);
+        let timeouts = config.timeouts.timeouts();
+        assert_eq!(
+            timeouts.for_method("prompts/get"),
+            Some(Duration::from_secs(30))
+        );
+        let quota = config.rate_limit().unwrap();
+        assert!(!quota.is_empty());
+
+        let caps = config
+            .capabilities
+            .apply(ServerCapabilities::new().with_tools().with_prompts());
+        assert!(caps.logging.is_some() && caps.tools.is_some());
+        assert!(caps.prompts.is_none());
+    }
+
+    #[test]
+    fn environment_overrides_and_is_blamed_for_bad_values() {
+        let env = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect::<Vec<_>>()
+        };
+        let config = ServerConfig::parse_with_env(
+            JSON,
+            ConfigFormat::Json,
+            env(&[
+                ("MCPKIT_LIMITS_MAX_CONCURRENT_REQUESTS", "8"),
+                ("MCPKIT_SERVER_VERSION", "3"),
+                ("MCPKIT_RATE_LIMIT_PER_SESSION", r#"{"requests": 1}"#),
+                ("UNRELATED", "1"),
+            ]),
+        );
+        // A JSON object is not a scalar, so it arrives as a string.
+        let err = config.unwrap_err();
+        assert_eq!(err.field(), Some("rate_limit.per_session"));
+        assert!(err.to_string().starts_with(
+            "environment variable MCPKIT_RATE_LIMIT_PER_SESSION: `rate_limit.per_session`"
+        ));
+
+        let config = ServerConfig::parse_with_env(
+            JSON,
+            ConfigFormat::Json,
+            env(&[
+                ("MCPKIT_LIMITS_MAX_CONCURRENT_REQUESTS", "8"),
+                ("MCPKIT_SERVER_VERSION", "3"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.limits.max_concurrent_requests, Some(8));
+        assert_eq!(config.server.version.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn errors_point_at_the_field_and_line() {
+        let invalid = JSON.replace(
+            "\"max_concurrent_requests\": 64",
+            "\"max_concurrent_requests\": 0",
+        );
+        let err = ServerConfig::parse(&invalid, ConfigFormat::Json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "<config>:9: `limits.max_concurrent_requests`: must be at least 1"
+        );
+
+        let unknown = JSON.replace("\"default_ms\"", "\"defualt_ms\"");
+        let err = ServerConfig::parse(&unknown, ConfigFormat::Json).unwrap_err();
+        assert_eq!(err.field(), Some("timeouts.defualt_ms"));
+        assert_eq!(err.line(), Some(5));
+
+        let unbound = JSON.replace(r#", "bind": "127.0.0.1:3000""#, "");
+        let err = ServerConfig::parse(&unbound, ConfigFormat::Json).unwrap_err();
+        assert_eq!(err.field(), Some("transport.bind"));
+        assert_eq!(err.line(), Some(12));
+
+        let err = ServerConfig::parse("{\n  \"server\": {", ConfigFormat::Json).unwrap_err();
+        assert!(matches!(err, ConfigError::Syntax { .. }));
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[cfg(feature = "config-toml")]
+    #[test]
+    fn toml_errors_carry_lines() {
+        let source = "[server]\nname = \"inventory\"\n\n[limits]\nmax_concurrent_requests = 0\n";
+        let err = ServerConfig::parse(source, ConfigFormat::Toml).unwrap_err();
+        assert_eq!(err.line(), Some(5));
+        assert_eq!(err.field(), Some("limits.max_concurrent_requests"));
+
+        let err = ServerConfig::parse("[server\n", ConfigFormat::Toml).unwrap_err();
+        assert!(matches!(err, ConfigError::Syntax { .. }));
+        assert_eq!(err.line(), Some(1));
+    }
+}
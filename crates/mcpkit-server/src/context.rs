@@ -43,18 +43,30 @@
 //! assert!(ctx.protocol_version.supports_tasks());
 //! ```
 
-use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use crate::cache::ToolCache;
+use crate::capability::tasks::TaskManager;
+use crate::extensions::{Extensions, HttpRequestInfo, SessionId};
+use crate::idempotency::IdempotencyCache;
+use crate::invocation::InvocationTracing;
+use crate::progress::ProgressReporter;
+use crate::sandbox::{Sandbox, SandboxFs, SandboxHttp};
+use crate::subscription::{self, SubscriptionManager};
+use mcpkit_core::capability::{ClientCapabilities, ClientCapability, ServerCapabilities};
 use mcpkit_core::error::McpError;
 use mcpkit_core::protocol::{Notification, ProgressToken, RequestId, Response};
 use mcpkit_core::protocol_version::ProtocolVersion;
-use mcpkit_core::types::elicitation::{ElicitRequest, ElicitResult, UrlElicitRequest};
+use mcpkit_core::types::elicitation::{
+    ElicitOutcome, ElicitRequest, ElicitResult, Elicitable, UrlElicitRequest,
+};
 use mcpkit_core::types::logging::{LoggingLevel, LoggingMessageNotificationParams};
 use mcpkit_core::types::notifications::ProgressNotificationParams;
-use mcpkit_core::types::roots::{ListRootsResult, Root};
+use mcpkit_core::types::roots::{ClientRoots, ListRootsResult, Root};
 use mcpkit_core::types::sampling::{CreateMessageRequest, CreateMessageResult};
 use std::borrow::Cow;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, LazyLock};
+use std::time::Instant;
 
 /// Trait for sending messages to the peer (client or server).
 ///
@@ -87,6 +99,15 @@ pub trait Peer: Send + Sync {
             ))
         })
     }
+
+    /// Return an owned handle to this peer, usable beyond the borrow of a
+    /// [`Context`] (for example from a spawned task).
+    ///
+    /// The default returns `None`, in which case
+    /// [`Context::progress_reporter`] hands out an inactive reporter.
+    fn to_shared(&self) -> Option<Arc<dyn Peer>> {
+        None
+    }
 }
 
 // The cancellation token is shared with the client-side task machinery and
@@ -120,6 +141,22 @@ pub struct Context<'a> {
     peer: &'a dyn Peer,
     /// Cancellation token for this request.
     cancel: CancellationToken,
+    /// Per-request values attached by the transport or framework adapter.
+    extensions: Option<&'a Extensions>,
+    /// The connection's resource subscriptions, if tracked.
+    subscriptions: Option<&'a SubscriptionManager>,
+    /// The tool result cache, when the call runs under a [`CachingToolHandler`](crate::cache::CachingToolHandler).
+    tool_cache: Option<&'a ToolCache>,
+    /// Results of keyed `tools/call`s, for answering retries.
+    idempotency: Option<&'a IdempotencyCache>,
+    /// The connection's task store, used by [`spawn_task`](Self::spawn_task).
+    tasks: Option<&'a Arc<TaskManager>>,
+    /// How tool, resource, and prompt invocations are traced.
+    invocation_tracing: Option<&'a InvocationTracing>,
+    /// When the client stops waiting for the response, if it said.
+    deadline: Option<Instant>,
+    /// The calling tool's filesystem and network policy.
+    sandbox: Option<&'a Sandbox>,
 }
 
 /// Sentinel [`RequestId`] for notification-scoped contexts (see
@@ -147,6 +184,14 @@ impl<'a> Context<'a> {
             protocol_version,
             peer,
             cancel: CancellationToken::new(),
+            extensions: None,
+            subscriptions: None,
+            tool_cache: None,
+            idempotency: None,
+            tasks: None,
+            invocation_tracing: None,
+            deadline: None,
+            sandbox: None,
         }
     }
 
@@ -169,6 +214,14 @@ impl<'a> Context<'a> {
             protocol_version,
             peer,
             cancel,
+            extensions: None,
+            subscriptions: None,
+            tool_cache: None,
+            idempotency: None,
+            tasks: None,
+            invocation_tracing: None,
+            deadline: None,
+            sandbox: None,
         }
     }
 
@@ -195,6 +248,285 @@ impl<'a> Context<'a> {
             protocol_version,
             peer,
             cancel: CancellationToken::new(),
+            extensions: None,
+            subscriptions: None,
+            tool_cache: None,
+            idempotency: None,
+            tasks: None,
+            invocation_tracing: None,
+            deadline: None,
+            sandbox: None,
+        }
+    }
+
+    /// Attach per-request extensions to this context.
+    ///
+    /// Framework adapters use this to expose request metadata such as
+    /// [`HttpRequestInfo`] to handlers.
+    #[must_use]
+    pub const fn with_extensions(mut self, extensions: &'a Extensions) -> Self {
+        self.extensions = Some(extensions);
+        self
+    }
+
+    /// Get a per-request extension of type `T`, if one was attached.
+    #[must_use]
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.and_then(Extensions::get::<T>)
+    }
+
+    /// The per-request extensions, or an empty map when none were attached.
+    ///
+    /// Framework adapters fill this with values taken from the HTTP request
+    /// (headers, peer address, authentication claims), so a handler can read
+    /// them with `ctx.extensions().get::<T>()`.
+    #[must_use]
+    pub fn extensions(&self) -> &Extensions {
+        static EMPTY: LazyLock<Extensions> = LazyLock::new(Extensions::new);
+        self.extensions.unwrap_or(&EMPTY)
+    }
+
+    /// Get the HTTP request metadata, when served over HTTP with the
+    /// request-info bridge enabled.
+    #[must_use]
+    pub fn http_request(&self) -> Option<&HttpRequestInfo> {
+        self.extension::<HttpRequestInfo>()
+    }
+
+    /// The id of the session this request belongs to, when served through
+    /// [`McpService`](crate::service::McpService) (the HTTP adapters).
+    #[must_use]
+    pub fn session_id(&self) -> Option<&str> {
+        self.extension::<SessionId>().map(SessionId::as_str)
+    }
+
+    /// Attach the connection's resource subscriptions to this context.
+    ///
+    /// The server runtime does this for every request so that
+    /// [`notify_resource_updated`](Self::notify_resource_updated) can skip
+    /// URIs the client never subscribed to.
+    #[must_use]
+    pub const fn with_subscriptions(mut self, subscriptions: &'a SubscriptionManager) -> Self {
+        self.subscriptions = Some(subscriptions);
+        self
+    }
+
+    /// Get the connection's resource subscriptions, if attached.
+    #[must_use]
+    pub const fn subscriptions(&self) -> Option<&'a SubscriptionManager> {
+        self.subscriptions
+    }
+
+    /// Attach a tool result cache to this context.
+    ///
+    /// [`CachingToolHandler`](crate::cache::CachingToolHandler) does this for
+    /// the calls it forwards, so tools can invalidate cached results.
+    #[must_use]
+    pub const fn with_tool_cache(mut self, cache: &'a ToolCache) -> Self {
+        self.tool_cache = Some(cache);
+        self
+    }
+
+    /// Get the tool result cache, if attached.
+    #[must_use]
+    pub const fn tool_cache(&self) -> Option<&'a ToolCache> {
+        self.tool_cache
+    }
+
+    /// Drop every cached result of the tool `name`.
+    ///
+    /// Call this from a tool that changes the state a cached tool reads. Does
+    /// nothing when no cache is attached.
+    pub fn invalidate_tool_cache(&self, name: &str) {
+        if let Some(cache) = self.tool_cache {
+            let dropped = cache.invalidate_tool(name);
+            tracing::trace!(tool = name, dropped, "invalidated cached tool results");
+        }
+    }
+
+    /// Drop every cached tool result.
+    pub fn clear_tool_cache(&self) {
+        if let Some(cache) = self.tool_cache {
+            cache.clear();
+        }
+    }
+
+    /// Attach the cache that answers retried `tools/call`s.
+    ///
+    /// The server runtime does this with
+    /// [`RuntimeConfig::idempotency`](crate::server::RuntimeConfig::idempotency);
+    /// see [`crate::idempotency`].
+    #[must_use]
+    pub const fn with_idempotency_cache(mut self, cache: &'a IdempotencyCache) -> Self {
+        self.idempotency = Some(cache);
+        self
+    }
+
+    /// Get the cache that answers retried `tools/call`s, if attached.
+    #[must_use]
+    pub const fn idempotency_cache(&self) -> Option<&'a IdempotencyCache> {
+        self.idempotency
+    }
+
+    /// Attach the connection's task store to this context.
+    ///
+    /// The server runtime does this for every request so that
+    /// [`spawn_task`](Self::spawn_task) registers tasks where `tasks/get`,
+    /// `tasks/result`, and `tasks/cancel` find them.
+    #[must_use]
+    pub const fn with_task_store(mut self, tasks: &'a Arc<TaskManager>) -> Self {
+        self.tasks = Some(tasks);
+        self
+    }
+
+    /// Get the connection's task store, if attached.
+    #[must_use]
+    pub const fn task_store(&self) -> Option<&'a Arc<TaskManager>> {
+        self.tasks
+    }
+
+    /// Attach the settings for tracing tool, resource, and prompt
+    /// invocations.
+    ///
+    /// The server runtime does this with
+    /// [`RuntimeConfig::invocation_tracing`](crate::server::RuntimeConfig::invocation_tracing);
+    /// see [`crate::invocation`]. Without it, invocations are still traced
+    /// but their arguments are never captured.
+    #[must_use]
+    pub const fn with_invocation_tracing(mut self, tracing: &'a InvocationTracing) -> Self {
+        self.invocation_tracing = Some(tracing);
+        self
+    }
+
+    /// Get the invocation tracing settings, if attached.
+    #[must_use]
+    pub const fn invocation_tracing(&self) -> Option<&'a InvocationTracing> {
+        self.invocation_tracing
+    }
+
+    /// Set the time after which the client no longer waits for the response.
+    ///
+    /// The server runtime sets this from the timeout a client sends in the
+    /// request's `_meta` (see
+    /// [`Meta::with_timeout_in_params`](mcpkit_core::types::Meta::with_timeout_in_params)).
+    #[must_use]
+    pub const fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// The time after which the client no longer waits for the response, if
+    /// the client sent one.
+    ///
+    /// Long-running handlers can check this to give up, or return partial
+    /// results, before the work is wasted.
+    #[must_use]
+    pub const fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Attach the calling tool's sandbox, enforced by [`fs`](Self::fs) and
+    /// [`http`](Self::http).
+    #[must_use]
+    pub const fn with_sandbox(mut self, sandbox: &'a Sandbox) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    /// A copy of this context running under `sandbox`.
+    ///
+    /// `#[mcp_server]` does this for tools declaring `allow_fs_read`,
+    /// `allow_fs_write`, or `allow_net`; see [`crate::sandbox`].
+    #[must_use]
+    pub fn sandboxed<'b>(&'b self, sandbox: &'b Sandbox) -> Context<'b> {
+        self.reborrow().with_sandbox(sandbox)
+    }
+
+    /// The calling tool's sandbox, if attached.
+    #[must_use]
+    pub const fn sandbox(&self) -> Option<&'a Sandbox> {
+        self.sandbox
+    }
+
+    /// Filesystem access limited to the paths the tool's sandbox allows.
+    ///
+    /// Denies everything when no sandbox is attached.
+    #[must_use]
+    pub const fn fs(&self) -> SandboxFs<'a> {
+        SandboxFs::new(self.sandbox)
+    }
+
+    /// Network access limited to the hosts the tool's sandbox allows.
+    ///
+    /// Denies everything when no sandbox is attached.
+    #[must_use]
+    pub const fn http(&self) -> SandboxHttp<'a> {
+        SandboxHttp::new(self.sandbox)
+    }
+
+    /// Run `f` in the background as a tracked task, returning the new
+    /// `working` task.
+    ///
+    /// The task outlives this request: `f` receives an owned
+    /// [`TaskContext`](crate::capability::tasks::TaskContext) for reporting
+    /// progress and status. Its `Ok` value is stored as the `tasks/result`
+    /// payload (usually a `CallToolResult`) and its `Err` is reproduced as
+    /// the JSON-RPC error; `tasks/cancel` stops the work. Status changes are
+    /// announced with `notifications/tasks/status`. `name` labels the task
+    /// in traces.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no task store is attached (e.g. a hand-built
+    /// context) or the negotiated protocol version predates tasks.
+    #[cfg(feature = "tokio-runtime")]
+    pub fn spawn_task<F, Fut, R>(
+        &self,
+        name: &str,
+        f: F,
+    ) -> Result<mcpkit_core::types::Task, McpError>
+    where
+        F: FnOnce(crate::capability::tasks::TaskContext) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<R, McpError>> + Send + 'static,
+        R: serde::Serialize + Send + 'static,
+    {
+        if !self.protocol_version.supports_tasks() {
+            return Err(McpError::internal(
+                "the negotiated protocol version does not support tasks",
+            ));
+        }
+        let Some(store) = self.tasks else {
+            return Err(McpError::internal(
+                "no task store is attached to this context",
+            ));
+        };
+        crate::capability::tasks::spawn_tracked(
+            store,
+            name,
+            self.peer.to_shared(),
+            self.progress_reporter(),
+            f,
+        )
+    }
+
+    /// A copy of this context sharing its borrows and cancellation token.
+    pub(crate) fn reborrow(&self) -> Context<'_> {
+        Context {
+            request_id: self.request_id,
+            progress_token: self.progress_token,
+            client_caps: self.client_caps,
+            server_caps: self.server_caps,
+            protocol_version: self.protocol_version,
+            peer: self.peer,
+            cancel: self.cancel.clone(),
+            extensions: self.extensions,
+            subscriptions: self.subscriptions,
+            tool_cache: self.tool_cache,
+            idempotency: self.idempotency,
+            tasks: self.tasks,
+            invocation_tracing: self.invocation_tracing,
+            deadline: self.deadline,
+            sandbox: self.sandbox,
         }
     }
 
@@ -238,6 +570,22 @@ impl<'a> Context<'a> {
         self.peer.notify(notification).await
     }
 
+    /// Tell the client that the resource at `uri` changed.
+    ///
+    /// Sends `notifications/resources/updated` when the client is subscribed
+    /// to `uri`. Without attached subscriptions (e.g. a hand-built context)
+    /// the notification is always sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification could not be sent.
+    pub async fn notify_resource_updated(&self, uri: &str) -> Result<(), McpError> {
+        match self.subscriptions {
+            Some(subscriptions) => subscriptions.notify_updated(self.peer, uri).await.map(drop),
+            None => self.peer.notify(subscription::resource_updated(uri)?).await,
+        }
+    }
+
     /// Report progress for this operation.
     ///
     /// This sends a progress notification to the client if a progress token
@@ -276,6 +624,19 @@ impl<'a> Context<'a> {
         .await
     }
 
+    /// Get a throttled progress handle for this request.
+    ///
+    /// Unlike [`progress`](Self::progress), the reporter is cloneable and
+    /// `'static`, so it can be moved into spawned tasks. It is inactive (every
+    /// report is a no-op) when the request carried no progress token.
+    #[must_use]
+    pub fn progress_reporter(&self) -> ProgressReporter {
+        match (self.progress_token, self.peer.to_shared()) {
+            (Some(token), Some(peer)) => ProgressReporter::new(peer, token.clone()),
+            _ => ProgressReporter::inactive(),
+        }
+    }
+
     /// Emit a `notifications/message` log to the client at `level`, optionally
     /// tagged with a `logger` name and carrying arbitrary JSON `data`.
     ///
@@ -296,6 +657,33 @@ impl<'a> Context<'a> {
             .await
     }
 
+    /// Whether the client declared `capability`.
+    ///
+    /// Use this to degrade gracefully, e.g. fall back to a default instead of
+    /// asking the user when elicitation is unavailable.
+    #[must_use]
+    pub fn supports(&self, capability: ClientCapability) -> bool {
+        self.client_caps.supports(capability)
+    }
+
+    /// Fail unless the client declared `capability`.
+    ///
+    /// ```rust,ignore
+    /// ctx.require_capability(ClientCapability::Elicitation)?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::ClientCapabilityMissing`] if the capability was not
+    /// declared.
+    pub fn require_capability(&self, capability: ClientCapability) -> Result<(), McpError> {
+        if self.supports(capability) {
+            Ok(())
+        } else {
+            Err(McpError::client_capability_missing(capability.name()))
+        }
+    }
+
     /// Send a request to the client and await its response.
     ///
     /// This is the basis for server-initiated requests (e.g. elicitation,
@@ -348,17 +736,47 @@ impl<'a> Context<'a> {
                 "the negotiated protocol version does not support elicitation",
             ));
         }
-        if !self.client_caps.has_elicitation() {
-            return Err(McpError::internal(
-                "the client did not declare the elicitation capability",
-            ));
-        }
+        self.require_capability(ClientCapability::Elicitation)?;
 
         let params = serde_json::to_value(&request).map_err(McpError::from)?;
         let result = self.request("elicitation/create", Some(params)).await?;
         serde_json::from_value(result).map_err(McpError::from)
     }
 
+    /// Ask the user to fill in the form described by `T` and return it typed.
+    ///
+    /// The response is validated against `T::elicitation_schema()` before
+    /// it is deserialized, so an accepted outcome always satisfies the
+    /// schema's required fields, types, and ranges.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// #[derive(Deserialize, Elicitable)]
+    /// struct Confirm {
+    ///     /// Type the repository name to confirm
+    ///     repository: String,
+    /// }
+    ///
+    /// match ctx.elicit_form::<Confirm>("Delete this repository?").await? {
+    ///     ElicitOutcome::Accepted(form) => delete(&form.repository).await,
+    ///     ElicitOutcome::Declined | ElicitOutcome::Cancelled => Ok(ToolOutput::text("Kept")),
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`elicit`](Self::elicit), or an invalid-params
+    /// error if accepted content doesn't match the form.
+    pub async fn elicit_form<T: Elicitable>(
+        &self,
+        message: impl Into<String>,
+    ) -> Result<ElicitOutcome<T>, McpError> {
+        let request = ElicitRequest::form::<T>(message);
+        let schema = request.requested_schema.clone();
+        self.elicit(request).await?.parse(&schema)
+    }
+
     /// Request the roots this client exposes (`roots/list`).
     ///
     /// Requires the client to have declared the `roots` capability.
@@ -368,16 +786,33 @@ impl<'a> Context<'a> {
     /// Returns an error if the client did not declare roots support, or the
     /// request fails, times out, or the response could not be parsed.
     pub async fn list_roots(&self) -> Result<Vec<Root>, McpError> {
-        if !self.client_caps.has_roots() {
-            return Err(McpError::internal(
-                "the client did not declare the roots capability",
-            ));
-        }
+        self.require_capability(ClientCapability::Roots)?;
         let result = self.request("roots/list", None).await?;
         let result: ListRootsResult = serde_json::from_value(result).map_err(McpError::from)?;
         Ok(result.roots)
     }
 
+    /// Fetch the client's roots for checking paths against them.
+    ///
+    /// Like [`list_roots`](Self::list_roots), but wraps the result in
+    /// [`ClientRoots`] so a tool can confirm a path is inside the workspace
+    /// before touching it:
+    ///
+    /// ```rust,ignore
+    /// let roots = ctx.client_roots().await?;
+    /// if !roots.is_within_roots(&path) {
+    ///     return Err(McpError::invalid_params("read_file", "path is outside the client's roots"));
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client did not declare roots support, or the
+    /// request fails, times out, or the response could not be parsed.
+    pub async fn client_roots(&self) -> Result<ClientRoots, McpError> {
+        self.list_roots().await.map(ClientRoots::new)
+    }
+
     /// Request a URL-mode elicitation: ask the client to have the user navigate
     /// to a URL for an out-of-band interaction (e.g. authorization or payment).
     ///
@@ -406,11 +841,7 @@ impl<'a> Context<'a> {
                 "the negotiated protocol version does not support elicitation",
             ));
         }
-        if !self.client_caps.has_url_elicitation() {
-            return Err(McpError::internal(
-                "the client did not declare URL-mode elicitation support",
-            ));
-        }
+        self.require_capability(ClientCapability::UrlElicitation)?;
 
         let params = serde_json::to_value(&request).map_err(McpError::from)?;
         let result = self.request("elicitation/create", Some(params)).await?;
@@ -431,11 +862,7 @@ impl<'a> Context<'a> {
         &self,
         request: CreateMessageRequest,
     ) -> Result<CreateMessageResult, McpError> {
-        if !self.client_caps.has_sampling() {
-            return Err(McpError::internal(
-                "the client did not declare the sampling capability",
-            ));
-        }
+        self.require_capability(ClientCapability::Sampling)?;
 
         let params = serde_json::to_value(&request).map_err(McpError::from)?;
         let result = self.request("sampling/createMessage", Some(params)).await?;
@@ -452,6 +879,8 @@ impl std::fmt::Debug for Context<'_> {
             .field("server_caps", &self.server_caps)
             .field("protocol_version", &self.protocol_version)
             .field("is_cancelled", &self.is_cancelled())
+            .field("extensions", &self.extensions)
+            .field("subscriptions", &self.subscriptions)
             .finish()
     }
 }
@@ -469,6 +898,10 @@ impl Peer for NoOpPeer {
     ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
         Box::pin(async { Ok(()) })
     }
+
+    fn to_shared(&self) -> Option<Arc<dyn Peer>> {
+        Some(Arc::new(Self))
+    }
 }
 
 /// Owned data for creating contexts.
@@ -486,12 +919,14 @@ pub struct ContextData {
     pub server_caps: ServerCapabilities,
     /// The negotiated protocol version.
     pub protocol_version: ProtocolVersion,
+    /// Per-request extensions.
+    pub extensions: Extensions,
 }
 
 impl ContextData {
     /// Create a new context data struct.
     #[must_use]
-    pub const fn new(
+    pub fn new(
         request_id: RequestId,
         client_caps: ClientCapabilities,
         server_caps: ServerCapabilities,
@@ -503,6 +938,7 @@ impl ContextData {
             client_caps,
             server_caps,
             protocol_version,
+            extensions: Extensions::new(),
         }
     }
 
@@ -513,6 +949,13 @@ impl ContextData {
         self
     }
 
+    /// Attach a per-request extension, replacing any value of the same type.
+    #[must_use]
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
     /// Create a context from this data with the given peer.
     #[must_use]
     pub fn to_context<'a>(&'a self, peer: &'a dyn Peer) -> Context<'a> {
@@ -524,6 +967,7 @@ impl ContextData {
             self.protocol_version,
             peer,
         )
+        .with_extensions(&self.extensions)
     }
 }
 
@@ -552,6 +996,121 @@ mod tests {
         assert_eq!(ctx.protocol_version, ProtocolVersion::LATEST);
     }
 
+    #[test]
+    fn test_require_capability() {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::new().with_sampling();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+
+        assert!(ctx.supports(ClientCapability::Sampling));
+        assert!(ctx.require_capability(ClientCapability::Sampling).is_ok());
+        match ctx.require_capability(ClientCapability::Elicitation) {
+            Err(McpError::ClientCapabilityMissing { capability }) => {
+                assert_eq!(capability, "elicitation");
+            }
+            other => panic!("expected ClientCapabilityMissing, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_context_extensions() {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+        assert!(ctx.http_request().is_none());
+
+        let mut ext = Extensions::new();
+        ext.insert(HttpRequestInfo::new("POST", "/mcp").with_header("X-Tenant", "acme"));
+        ext.insert(7u32);
+        let ctx = ctx.with_extensions(&ext);
+
+        assert_eq!(ctx.http_request().unwrap().header("x-tenant"), Some("acme"));
+        assert_eq!(ctx.extension::<u32>(), Some(&7));
+        assert!(ctx.extension::<String>().is_none());
+        assert_eq!(ctx.extensions().len(), 2);
+    }
+
+    #[test]
+    fn test_context_data_extensions() {
+        let data = ContextData::new(
+            RequestId::Number(1),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        );
+        let peer = NoOpPeer;
+        assert!(data.to_context(&peer).extensions().is_empty());
+
+        let data = data.with_extension(String::from("alice"));
+        let ctx = data.to_context(&peer);
+        assert_eq!(
+            ctx.extensions().get::<String>().map(String::as_str),
+            Some("alice")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_resource_updated_respects_subscriptions() {
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct RecPeer(Mutex<Vec<String>>);
+
+        impl Peer for RecPeer {
+            fn notify(
+                &self,
+                notification: Notification,
+            ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+                let uri = notification.params.unwrap()["uri"].to_string();
+                self.0.lock().unwrap().push(uri);
+                Box::pin(async { Ok(()) })
+            }
+        }
+
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = RecPeer::default();
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+
+        // No subscription tracking: always sent.
+        ctx.notify_resource_updated("file:///a").await.unwrap();
+
+        let subs = SubscriptionManager::new();
+        subs.subscribe("file:///b");
+        let ctx = ctx.with_subscriptions(&subs);
+        ctx.notify_resource_updated("file:///a").await.unwrap();
+        ctx.notify_resource_updated("file:///b").await.unwrap();
+
+        assert_eq!(*peer.0.lock().unwrap(), ["\"file:///a\"", "\"file:///b\""]);
+    }
+
     #[test]
     fn test_context_with_progress_token() {
         let request_id = RequestId::Number(1);
@@ -637,6 +1196,13 @@ mod tests {
         let roots = ctx.list_roots().await.expect("roots listed");
         assert_eq!(roots.len(), 1);
         assert_eq!(roots[0].name.as_deref(), Some("a"));
+        let roots = ctx.client_roots().await.expect("roots listed");
+        assert_eq!(roots.roots().len(), 1);
+        #[cfg(unix)]
+        {
+            assert!(roots.is_within_roots("/a/src/lib.rs"));
+            assert!(!roots.is_within_roots("/a/../b"));
+        }
 
         // Not advertised -> error before any request.
         let no_roots = ClientCapabilities::default();
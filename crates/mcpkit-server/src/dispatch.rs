@@ -15,8 +15,9 @@ use crate::builder::{NotRegistered, Registered};
 use crate::context::Context;
 use crate::handler::{CompletionHandler, PromptHandler, ResourceHandler, TaskHandler, ToolHandler};
 use mcpkit_core::error::McpError;
+use mcpkit_core::pagination::Page;
 use mcpkit_core::types::{
-    CancelTaskResult, CompleteRequest, CompleteResult, GetPromptResult, GetTaskResult,
+    ByteRange, CancelTaskResult, CompleteRequest, CompleteResult, GetPromptResult, GetTaskResult,
     ListTasksResult, Object, Prompt, Resource, ResourceContents, ResourceTemplate, TaskId, Tool,
     ToolOutput,
 };
@@ -36,6 +37,13 @@ type BoxFut<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 pub trait DynToolHandler: Send + Sync {
     /// See [`ToolHandler::list_tools`].
     fn list_tools<'a>(&'a self, ctx: &'a Context<'_>) -> BoxFut<'a, Result<Vec<Tool>, McpError>>;
+    /// See [`ToolHandler::list_tools_page`].
+    fn list_tools_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<Tool>, McpError>>;
     /// See [`ToolHandler::call_tool`].
     fn call_tool<'a>(
         &'a self,
@@ -49,6 +57,14 @@ impl<T: ToolHandler> DynToolHandler for T {
     fn list_tools<'a>(&'a self, ctx: &'a Context<'_>) -> BoxFut<'a, Result<Vec<Tool>, McpError>> {
         Box::pin(ToolHandler::list_tools(self, ctx))
     }
+    fn list_tools_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<Tool>, McpError>> {
+        Box::pin(ToolHandler::list_tools_page(self, cursor, page_size, ctx))
+    }
     fn call_tool<'a>(
         &'a self,
         name: &'a str,
@@ -66,17 +82,38 @@ pub trait DynResourceHandler: Send + Sync {
         &'a self,
         ctx: &'a Context<'_>,
     ) -> BoxFut<'a, Result<Vec<Resource>, McpError>>;
+    /// See [`ResourceHandler::list_resources_page`].
+    fn list_resources_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<Resource>, McpError>>;
     /// See [`ResourceHandler::list_resource_templates`].
     fn list_resource_templates<'a>(
         &'a self,
         ctx: &'a Context<'_>,
     ) -> BoxFut<'a, Result<Vec<ResourceTemplate>, McpError>>;
+    /// See [`ResourceHandler::list_resource_templates_page`].
+    fn list_resource_templates_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<ResourceTemplate>, McpError>>;
     /// See [`ResourceHandler::read_resource`].
     fn read_resource<'a>(
         &'a self,
         uri: &'a str,
         ctx: &'a Context<'_>,
     ) -> BoxFut<'a, Result<Vec<ResourceContents>, McpError>>;
+    /// See [`ResourceHandler::read_resource_range`].
+    fn read_resource_range<'a>(
+        &'a self,
+        uri: &'a str,
+        range: ByteRange,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<ResourceContents, McpError>>;
     /// See [`ResourceHandler::subscribe`].
     fn subscribe<'a>(
         &'a self,
@@ -98,12 +135,32 @@ impl<R: ResourceHandler> DynResourceHandler for R {
     ) -> BoxFut<'a, Result<Vec<Resource>, McpError>> {
         Box::pin(ResourceHandler::list_resources(self, ctx))
     }
+    fn list_resources_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<Resource>, McpError>> {
+        Box::pin(ResourceHandler::list_resources_page(
+            self, cursor, page_size, ctx,
+        ))
+    }
     fn list_resource_templates<'a>(
         &'a self,
         ctx: &'a Context<'_>,
     ) -> BoxFut<'a, Result<Vec<ResourceTemplate>, McpError>> {
         Box::pin(ResourceHandler::list_resource_templates(self, ctx))
     }
+    fn list_resource_templates_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<ResourceTemplate>, McpError>> {
+        Box::pin(ResourceHandler::list_resource_templates_page(
+            self, cursor, page_size, ctx,
+        ))
+    }
     fn read_resource<'a>(
         &'a self,
         uri: &'a str,
@@ -111,6 +168,14 @@ impl<R: ResourceHandler> DynResourceHandler for R {
     ) -> BoxFut<'a, Result<Vec<ResourceContents>, McpError>> {
         Box::pin(ResourceHandler::read_resource(self, uri, ctx))
     }
+    fn read_resource_range<'a>(
+        &'a self,
+        uri: &'a str,
+        range: ByteRange,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<ResourceContents, McpError>> {
+        Box::pin(ResourceHandler::read_resource_range(self, uri, range, ctx))
+    }
     fn subscribe<'a>(
         &'a self,
         uri: &'a str,
@@ -134,6 +199,13 @@ pub trait DynPromptHandler: Send + Sync {
         &'a self,
         ctx: &'a Context<'_>,
     ) -> BoxFut<'a, Result<Vec<Prompt>, McpError>>;
+    /// See [`PromptHandler::list_prompts_page`].
+    fn list_prompts_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<Prompt>, McpError>>;
     /// See [`PromptHandler::get_prompt`].
     fn get_prompt<'a>(
         &'a self,
@@ -150,6 +222,16 @@ impl<P: PromptHandler> DynPromptHandler for P {
     ) -> BoxFut<'a, Result<Vec<Prompt>, McpError>> {
         Box::pin(PromptHandler::list_prompts(self, ctx))
     }
+    fn list_prompts_page<'a>(
+        &'a self,
+        cursor: Option<&'a str>,
+        page_size: Option<usize>,
+        ctx: &'a Context<'_>,
+    ) -> BoxFut<'a, Result<Page<Prompt>, McpError>> {
+        Box::pin(PromptHandler::list_prompts_page(
+            self, cursor, page_size, ctx,
+        ))
+    }
     fn get_prompt<'a>(
         &'a self,
         name: &'a str,
@@ -0,0 +1,183 @@
+//! Per-request extensions carried by a [`Context`](crate::Context).
+//!
+//! Transports and framework adapters can attach typed values to a request
+//! before it is dispatched; handlers read them back through
+//! [`Context::extension`](crate::Context::extension). The map is keyed by
+//! type, so each type has at most one value.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::{Extensions, HttpRequestInfo};
+//!
+//! let mut ext = Extensions::new();
+//! ext.insert(HttpRequestInfo::new("POST", "/mcp").with_header("x-tenant", "acme"));
+//!
+//! let info = ext.get::<HttpRequestInfo>().unwrap();
+//! assert_eq!(info.header("X-Tenant"), Some("acme"));
+//! ```
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+/// A type-keyed map of values attached to a single request.
+#[derive(Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Create an empty extension map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a value, returning the previous value of the same type.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.map
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|prev| prev.downcast().ok().map(|boxed| *boxed))
+    }
+
+    /// Get a reference to the value of type `T`, if present.
+    #[must_use]
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Remove and return the value of type `T`, if present.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
+        self.map
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok().map(|boxed| *boxed))
+    }
+
+    /// Number of values in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether the map is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl std::fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+/// HTTP metadata for the request that carried a JSON-RPC message.
+///
+/// Framework adapters insert this into the request's [`Extensions`] when the
+/// bridge is enabled (e.g. `McpRouter::with_request_info` in `mcpkit-axum`).
+/// Only the headers the application selected are captured. It uses plain
+/// types so handlers stay independent of the web framework.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HttpRequestInfo {
+    /// HTTP method (e.g. `POST`).
+    pub method: String,
+    /// Request path, without the query string.
+    pub path: String,
+    /// Captured headers as `(lowercase name, value)` pairs.
+    pub headers: Vec<(String, String)>,
+    /// Remote peer address, when the server exposes connection info.
+    pub peer_addr: Option<SocketAddr>,
+}
+
+impl HttpRequestInfo {
+    /// Create request info for the given method and path.
+    #[must_use]
+    pub fn new(method: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            path: path.into(),
+            headers: Vec::new(),
+            peer_addr: None,
+        }
+    }
+
+    /// Add a captured header. The name is stored lowercased.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers
+            .push((name.into().to_ascii_lowercase(), value.into()));
+        self
+    }
+
+    /// Set the remote peer address.
+    #[must_use]
+    pub const fn with_peer_addr(mut self, addr: SocketAddr) -> Self {
+        self.peer_addr = Some(addr);
+        self
+    }
+
+    /// Look up a captured header by name (case-insensitive).
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// The id of the session a request belongs to.
+///
+/// [`McpService`](crate::service::McpService) inserts this into every
+/// request's [`Extensions`]; read it with
+/// [`Context::session_id`](crate::Context::session_id).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionId(pub String);
+
+impl SessionId {
+    /// The session id as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_get_remove_by_type() {
+        let mut ext = Extensions::new();
+        assert!(ext.is_empty());
+        assert!(ext.insert(7u32).is_none());
+        assert_eq!(ext.insert(8u32), Some(7));
+        ext.insert("tenant".to_string());
+
+        assert_eq!(ext.len(), 2);
+        assert_eq!(ext.get::<u32>(), Some(&8));
+        assert_eq!(ext.get::<String>().map(String::as_str), Some("tenant"));
+        assert!(ext.get::<u64>().is_none());
+
+        assert_eq!(ext.remove::<u32>(), Some(8));
+        assert!(ext.get::<u32>().is_none());
+    }
+
+    #[test]
+    fn header_lookup_is_case_insensitive() {
+        let info = HttpRequestInfo::new("POST", "/mcp")
+            .with_header("X-Forwarded-For", "10.0.0.1")
+            .with_peer_addr("127.0.0.1:9000".parse().unwrap());
+
+        assert_eq!(info.header("x-forwarded-for"), Some("10.0.0.1"));
+        assert_eq!(info.headers[0].0, "x-forwarded-for");
+        assert!(info.header("authorization").is_none());
+        assert_eq!(info.peer_addr.map(|a| a.port()), Some(9000));
+    }
+}
@@ -0,0 +1,392 @@
+//! Notification fan-out to many SSE sessions.
+//!
+//! Broadcasting a notification such as `notifications/tools/list_changed` to
+//! thousands of open SSE streams must not copy the message once per session,
+//! nor wake every stream's task once per message. A [`FanOut`] hands each
+//! session an [`Outbox`], a bounded queue read by one [`Inbox`]:
+//!
+//! - [`FanOut::publish`] encodes the payload once as [`Bytes`]; every outbox
+//!   gets a reference-counted handle to the same buffer;
+//! - a parked inbox is woken by the first event queued after it parked, and
+//!   events queued before it runs are drained together as one [`Batch`], so a
+//!   burst costs one wake-up and one write per session rather than one per
+//!   event;
+//! - a full outbox drops its oldest event to make room. The inbox learns how
+//!   many it missed from [`Batch::dropped`], and the totals are kept in
+//!   [`FanOutStats`].
+//!
+//! An outbox delivers to one inbox at a time: [`Outbox::subscribe`] hands the
+//! queue, with whatever is still in it, to a new inbox and ends the previous
+//! one, as when a client reopens its SSE stream. Dropping the outbox ends its
+//! inbox once the queued events are read.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::fanout::{FanOut, FanOutConfig};
+//!
+//! # futures::executor::block_on(async {
+//! let fanout = FanOut::new(FanOutConfig::new().with_capacity(2));
+//! let sessions: Vec<_> = (0..3).map(|_| fanout.outbox()).collect();
+//! let mut inbox = sessions[0].subscribe();
+//!
+//! for n in 0..3 {
+//!     fanout.publish(format!("event {n}"), &sessions);
+//! }
+//!
+//! let batch = inbox.recv_batch().await.unwrap();
+//! assert_eq!(batch.events, ["event 1", "event 2"]);
+//! assert_eq!(batch.dropped, 1);
+//! assert_eq!(fanout.stats().dropped, 3);
+//! # });
+//! ```
+
+use bytes::Bytes;
+use futures::Stream;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+/// Default number of events an outbox holds before dropping the oldest.
+pub const DEFAULT_FANOUT_CAPACITY: usize = 100;
+
+/// Default maximum number of events in one [`Batch`].
+pub const DEFAULT_MAX_BATCH: usize = 64;
+
+/// Queue sizes for a [`FanOut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FanOutConfig {
+    /// Events an outbox holds before dropping the oldest.
+    pub capacity: usize,
+    /// Maximum number of events an inbox takes at once.
+    pub max_batch: usize,
+}
+
+impl Default for FanOutConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FanOutConfig {
+    /// The default sizes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            capacity: DEFAULT_FANOUT_CAPACITY,
+            max_batch: DEFAULT_MAX_BATCH,
+        }
+    }
+
+    /// Set how many events an outbox holds (at least one).
+    #[must_use]
+    pub const fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = if capacity == 0 { 1 } else { capacity };
+        self
+    }
+
+    /// Set the maximum number of events in one batch (at least one).
+    #[must_use]
+    pub const fn with_max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = if max_batch == 0 { 1 } else { max_batch };
+        self
+    }
+}
+
+/// Counts of events passed through a [`FanOut`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FanOutStats {
+    /// Events queued, counted once per outbox.
+    pub published: u64,
+    /// Events handed to an inbox.
+    pub delivered: u64,
+    /// Events dropped from a full outbox.
+    pub dropped: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    published: AtomicU64,
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Creates session outboxes and publishes to many of them at once.
+#[derive(Debug, Default)]
+pub struct FanOut {
+    config: FanOutConfig,
+    counters: Arc<Counters>,
+}
+
+impl FanOut {
+    /// A fan-out with the given queue sizes.
+    #[must_use]
+    pub fn new(config: FanOutConfig) -> Self {
+        Self {
+            config,
+            counters: Arc::default(),
+        }
+    }
+
+    /// The queue sizes.
+    #[must_use]
+    pub const fn config(&self) -> &FanOutConfig {
+        &self.config
+    }
+
+    /// A new, empty outbox for one session.
+    #[must_use]
+    pub fn outbox(&self) -> Outbox {
+        Outbox {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State::default()),
+                config: self.config,
+                counters: Arc::clone(&self.counters),
+            }),
+        }
+    }
+
+    /// Queue one payload on every outbox, sharing a single buffer, and
+    /// return how many outboxes it was queued on.
+    pub fn publish<'a>(
+        &self,
+        payload: impl Into<Bytes>,
+        outboxes: impl IntoIterator<Item = &'a Outbox>,
+    ) -> usize {
+        let payload = payload.into();
+        outboxes
+            .into_iter()
+            .map(|outbox| outbox.push(payload.clone()))
+            .count()
+    }
+
+    /// The event counts of every outbox this fan-out created.
+    #[must_use]
+    pub fn stats(&self) -> FanOutStats {
+        FanOutStats {
+            published: self.counters.published.load(Ordering::Relaxed),
+            delivered: self.counters.delivered.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    config: FanOutConfig,
+    counters: Arc<Counters>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    events: VecDeque<Bytes>,
+    /// Events dropped since the inbox last took a batch.
+    dropped: u64,
+    /// Bumped by each subscription; older inboxes end.
+    generation: u64,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The sending end of one session's event queue.
+///
+/// Dropping it ends the session's inbox after the queued events.
+#[derive(Debug)]
+pub struct Outbox {
+    shared: Arc<Shared>,
+}
+
+impl Outbox {
+    /// Queue an event, dropping the oldest one if the queue is full.
+    pub fn push(&self, payload: impl Into<Bytes>) {
+        let payload = payload.into();
+        let counters = &self.shared.counters;
+        let waker = {
+            let mut state = self.shared.lock();
+            if state.events.len() >= self.shared.config.capacity {
+                state.events.pop_front();
+                state.dropped += 1;
+                counters.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            state.events.push_back(payload);
+            state.waker.take()
+        };
+        counters.published.fetch_add(1, Ordering::Relaxed);
+        // Only the first event since the inbox parked wakes it; the rest are
+        // picked up in the same batch.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Start reading the queue from a new inbox, ending the previous one.
+    ///
+    /// Events still queued are delivered to the new inbox.
+    #[must_use]
+    pub fn subscribe(&self) -> Inbox {
+        let (generation, waker) = {
+            let mut state = self.shared.lock();
+            state.generation += 1;
+            (state.generation, state.waker.take())
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Inbox {
+            shared: Arc::clone(&self.shared),
+            generation,
+        }
+    }
+
+    /// The number of queued events.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shared.lock().events.len()
+    }
+
+    /// Whether no events are queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for Outbox {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.shared.lock();
+            state.closed = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Events taken from an outbox in one go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Batch {
+    /// The events, oldest first.
+    pub events: Vec<Bytes>,
+    /// Events dropped from the full outbox since the previous batch.
+    pub dropped: u64,
+}
+
+/// The receiving end of one session's event queue.
+///
+/// Also a [`Stream`] of [`Batch`]es.
+#[derive(Debug)]
+pub struct Inbox {
+    shared: Arc<Shared>,
+    generation: u64,
+}
+
+impl Inbox {
+    /// Wait for the queued events, or `None` once the outbox is dropped and
+    /// drained, or a newer inbox has subscribed.
+    pub async fn recv_batch(&mut self) -> Option<Batch> {
+        futures::future::poll_fn(|cx| self.poll_recv_batch(cx)).await
+    }
+
+    /// Poll for the queued events; see [`Self::recv_batch`].
+    pub fn poll_recv_batch(&mut self, cx: &mut Context<'_>) -> Poll<Option<Batch>> {
+        let mut state = self.shared.lock();
+        if state.generation != self.generation {
+            return Poll::Ready(None);
+        }
+        if !state.events.is_empty() || state.dropped > 0 {
+            let count = state.events.len().min(self.shared.config.max_batch);
+            let events: Vec<Bytes> = state.events.drain(..count).collect();
+            let dropped = std::mem::take(&mut state.dropped);
+            self.shared
+                .counters
+                .delivered
+                .fetch_add(events.len() as u64, Ordering::Relaxed);
+            return Poll::Ready(Some(Batch { events, dropped }));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl Stream for Inbox {
+    type Item = Batch;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Batch>> {
+        self.get_mut().poll_recv_batch(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[tokio::test]
+    async fn bursts_are_batched_and_a_push_wakes_a_parked_inbox() {
+        let fanout = FanOut::new(FanOutConfig::new().with_max_batch(2));
+        let outbox = fanout.outbox();
+        let mut inbox = outbox.subscribe();
+        assert!(inbox.recv_batch().now_or_never().is_none());
+
+        let waiting = tokio::spawn(async move {
+            let first = inbox.recv_batch().await.unwrap();
+            (inbox, first)
+        });
+        tokio::task::yield_now().await;
+        fanout.publish("a", [&outbox]);
+        let (mut inbox, first) = waiting.await.unwrap();
+        assert_eq!(first.events, ["a"]);
+
+        for event in ["b", "c", "d"] {
+            outbox.push(event);
+        }
+        assert_eq!(inbox.recv_batch().await.unwrap().events, ["b", "c"]);
+        assert_eq!(inbox.recv_batch().await.unwrap().events, ["d"]);
+        assert_eq!(fanout.stats().delivered, 4);
+    }
+
+    #[tokio::test]
+    async fn subscribing_again_takes_over_the_queue() {
+        let fanout = FanOut::default();
+        let outbox = fanout.outbox();
+        let mut old = outbox.subscribe();
+        outbox.push("queued");
+
+        let mut new = outbox.subscribe();
+        assert_eq!(old.recv_batch().await, None);
+        assert_eq!(new.recv_batch().await.unwrap().events, ["queued"]);
+
+        outbox.push("last");
+        drop(outbox);
+        assert_eq!(new.recv_batch().await.unwrap().events, ["last"]);
+        assert_eq!(new.recv_batch().await, None);
+    }
+
+    #[test]
+    fn payloads_are_shared_between_outboxes() {
+        let fanout = FanOut::default();
+        let outboxes = [fanout.outbox(), fanout.outbox()];
+        let inboxes = outboxes.each_ref().map(Outbox::subscribe);
+        assert_eq!(fanout.publish(String::from("shared"), &outboxes), 2);
+
+        let [mut a, mut b] = inboxes;
+        let a = a.recv_batch().now_or_never().flatten().unwrap();
+        let b = b.recv_batch().now_or_never().flatten().unwrap();
+        assert_eq!(a.events[0].as_ptr(), b.events[0].as_ptr());
+        assert_eq!(fanout.stats().published, 2);
+    }
+}
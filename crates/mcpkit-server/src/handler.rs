@@ -36,8 +36,9 @@
 
 use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
 use mcpkit_core::error::McpError;
+use mcpkit_core::pagination::Page;
 use mcpkit_core::types::{
-    CancelTaskResult, CompleteRequest, CompleteResult, GetPromptResult, GetTaskResult,
+    ByteRange, CancelTaskResult, CompleteRequest, CompleteResult, GetPromptResult, GetTaskResult,
     ListTasksResult, Object, Prompt, Resource, ResourceContents, ResourceTemplate, TaskId, Tool,
     ToolOutput,
 };
@@ -45,6 +46,7 @@ use serde_json::Value;
 use std::future::Future;
 
 use crate::context::Context;
+use crate::router::methods;
 
 /// Core server handler trait - required for all MCP servers.
 ///
@@ -121,6 +123,30 @@ pub trait ToolHandler: Send + Sync {
         ctx: &Context<'_>,
     ) -> impl Future<Output = Result<Vec<Tool>, McpError>> + Send;
 
+    /// List one page of tools.
+    ///
+    /// `cursor` is the client's opaque cursor and `page_size` the server's
+    /// configured page size (see
+    /// [`ServerBuilder::list_page_size`](crate::builder::ServerBuilder::list_page_size)).
+    /// The default pages the full [`list_tools`](Self::list_tools) list with
+    /// offset cursors. Override it to page natively, e.g. from a database,
+    /// returning whatever cursor the handler accepts back.
+    fn list_tools_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Tool>, McpError>> + Send {
+        async move {
+            Page::from_all(
+                self.list_tools(ctx).await?,
+                cursor,
+                page_size,
+                methods::TOOLS_LIST,
+            )
+        }
+    }
+
     /// Call a tool with the given arguments.
     ///
     /// `args` is passed through **unvalidated**: this generic path does not check
@@ -174,6 +200,30 @@ pub trait ResourceHandler: Send + Sync {
         ctx: &Context<'_>,
     ) -> impl Future<Output = Result<Vec<Resource>, McpError>> + Send;
 
+    /// List one page of static resources.
+    ///
+    /// `cursor` is the client's opaque cursor and `page_size` the server's
+    /// configured page size (see
+    /// [`ServerBuilder::list_page_size`](crate::builder::ServerBuilder::list_page_size)).
+    /// The default pages the full [`list_resources`](Self::list_resources) list with
+    /// offset cursors. Override it to page natively, e.g. from a database,
+    /// returning whatever cursor the handler accepts back.
+    fn list_resources_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Resource>, McpError>> + Send {
+        async move {
+            Page::from_all(
+                self.list_resources(ctx).await?,
+                cursor,
+                page_size,
+                methods::RESOURCES_LIST,
+            )
+        }
+    }
+
     /// List all available resource templates.
     ///
     /// Resource templates describe dynamic resources with parameterized URIs.
@@ -188,6 +238,30 @@ pub trait ResourceHandler: Send + Sync {
         async { Ok(vec![]) }
     }
 
+    /// List one page of resource templates.
+    ///
+    /// `cursor` is the client's opaque cursor and `page_size` the server's
+    /// configured page size (see
+    /// [`ServerBuilder::list_page_size`](crate::builder::ServerBuilder::list_page_size)).
+    /// The default pages the full [`list_resource_templates`](Self::list_resource_templates) list with
+    /// offset cursors. Override it to page natively, e.g. from a database,
+    /// returning whatever cursor the handler accepts back.
+    fn list_resource_templates_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<ResourceTemplate>, McpError>> + Send {
+        async move {
+            Page::from_all(
+                self.list_resource_templates(ctx).await?,
+                cursor,
+                page_size,
+                methods::RESOURCES_TEMPLATES_LIST,
+            )
+        }
+    }
+
     /// Read a resource by URI.
     fn read_resource(
         &self,
@@ -195,6 +269,28 @@ pub trait ResourceHandler: Send + Sync {
         ctx: &Context<'_>,
     ) -> impl Future<Output = Result<Vec<ResourceContents>, McpError>> + Send;
 
+    /// Read a byte range of a resource, for a client reading it in chunks
+    /// (see [`RANGE_META_KEY`](mcpkit_core::types::RANGE_META_KEY)).
+    ///
+    /// The default reads the whole resource with
+    /// [`read_resource`](Self::read_resource) and slices its first contents.
+    /// Override it for large resources to read only the range, e.g. by
+    /// seeking in a file, so neither side holds the whole resource in memory.
+    fn read_resource_range(
+        &self,
+        uri: &str,
+        range: ByteRange,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<ResourceContents, McpError>> + Send {
+        async move {
+            let contents = self.read_resource(uri, ctx).await?;
+            let first = contents
+                .first()
+                .ok_or_else(|| McpError::resource_not_found(uri))?;
+            Ok(first.slice(range)?)
+        }
+    }
+
     /// Subscribe to resource updates.
     ///
     /// Returns true if the subscription was successful.
@@ -226,6 +322,30 @@ pub trait PromptHandler: Send + Sync {
         ctx: &Context<'_>,
     ) -> impl Future<Output = Result<Vec<Prompt>, McpError>> + Send;
 
+    /// List one page of prompts.
+    ///
+    /// `cursor` is the client's opaque cursor and `page_size` the server's
+    /// configured page size (see
+    /// [`ServerBuilder::list_page_size`](crate::builder::ServerBuilder::list_page_size)).
+    /// The default pages the full [`list_prompts`](Self::list_prompts) list with
+    /// offset cursors. Override it to page natively, e.g. from a database,
+    /// returning whatever cursor the handler accepts back.
+    fn list_prompts_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Prompt>, McpError>> + Send {
+        async move {
+            Page::from_all(
+                self.list_prompts(ctx).await?,
+                cursor,
+                page_size,
+                methods::PROMPTS_LIST,
+            )
+        }
+    }
+
     /// Get a prompt with the given arguments.
     fn get_prompt(
         &self,
@@ -344,6 +464,15 @@ impl<T: ToolHandler> ToolHandler for Arc<T> {
         (**self).list_tools(ctx)
     }
 
+    fn list_tools_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Tool>, McpError>> + Send {
+        (**self).list_tools_page(cursor, page_size, ctx)
+    }
+
     fn call_tool(
         &self,
         name: &str,
@@ -366,6 +495,15 @@ impl<T: ResourceHandler> ResourceHandler for Arc<T> {
         (**self).list_resources(ctx)
     }
 
+    fn list_resources_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Resource>, McpError>> + Send {
+        (**self).list_resources_page(cursor, page_size, ctx)
+    }
+
     fn list_resource_templates(
         &self,
         ctx: &Context<'_>,
@@ -373,6 +511,15 @@ impl<T: ResourceHandler> ResourceHandler for Arc<T> {
         (**self).list_resource_templates(ctx)
     }
 
+    fn list_resource_templates_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<ResourceTemplate>, McpError>> + Send {
+        (**self).list_resource_templates_page(cursor, page_size, ctx)
+    }
+
     fn read_resource(
         &self,
         uri: &str,
@@ -381,6 +528,15 @@ impl<T: ResourceHandler> ResourceHandler for Arc<T> {
         (**self).read_resource(uri, ctx)
     }
 
+    fn read_resource_range(
+        &self,
+        uri: &str,
+        range: ByteRange,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<ResourceContents, McpError>> + Send {
+        (**self).read_resource_range(uri, range, ctx)
+    }
+
     fn subscribe(
         &self,
         uri: &str,
@@ -406,6 +562,15 @@ impl<T: PromptHandler> PromptHandler for Arc<T> {
         (**self).list_prompts(ctx)
     }
 
+    fn list_prompts_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Prompt>, McpError>> + Send {
+        (**self).list_prompts_page(cursor, page_size, ctx)
+    }
+
     fn get_prompt(
         &self,
         name: &str,
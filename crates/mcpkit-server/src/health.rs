@@ -25,6 +25,16 @@
 //! let status = checker.check();
 //! assert!(status.is_healthy());
 //! ```
+//!
+//! # Kubernetes probes
+//!
+//! The HTTP integrations' `McpRouter::with_health_checks` mounts
+//! [`LIVENESS_PATH`] and [`READINESS_PATH`], answered by [`HealthProbes`].
+//! `/healthz` succeeds while the process serves requests. `/readyz` runs the
+//! checker's checks plus a `sessions` component that fails once the server
+//! starts shutting down, so the pod leaves the load balancer while it drains.
+//! Both answer `ok` as plain text, or the full report as JSON when the query
+//! string contains `verbose`.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -307,18 +317,7 @@ impl HealthChecker {
             let check_start = Instant::now();
             let mut result = check_fn();
             result.check_duration = check_start.elapsed();
-
-            // Update overall status based on component status
-            match (&overall_status, &result.status) {
-                (HealthStatus::Healthy, HealthStatus::Degraded) => {
-                    overall_status = HealthStatus::Degraded;
-                }
-                (_, HealthStatus::Unhealthy) => {
-                    overall_status = HealthStatus::Unhealthy;
-                }
-                _ => {}
-            }
-
+            overall_status = worse(overall_status, &result.status);
             components.insert(name.clone(), result);
         }
 
@@ -345,6 +344,15 @@ impl HealthChecker {
     }
 }
 
+/// The worse of an overall status and a component's status.
+fn worse(overall: HealthStatus, component: &HealthStatus) -> HealthStatus {
+    match (&overall, component) {
+        (HealthStatus::Healthy, HealthStatus::Degraded) => HealthStatus::Degraded,
+        (_, HealthStatus::Unhealthy) => HealthStatus::Unhealthy,
+        _ => overall,
+    }
+}
+
 impl std::fmt::Debug for HealthChecker {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HealthChecker")
@@ -444,6 +452,102 @@ impl ReadinessResponse {
     }
 }
 
+/// Path of the liveness probe mounted by `with_health_checks`.
+pub const LIVENESS_PATH: &str = "/healthz";
+
+/// Path of the readiness probe mounted by `with_health_checks`.
+pub const READINESS_PATH: &str = "/readyz";
+
+/// An HTTP response to a health probe, for the integrations to convert into
+/// their framework's response type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeResponse {
+    /// HTTP status code: `200`, or `503` when not ready.
+    pub status: u16,
+    /// Value of the `Content-Type` header.
+    pub content_type: &'static str,
+    /// Response body.
+    pub body: String,
+}
+
+impl ProbeResponse {
+    fn new(status: u16, verbose: bool, json: &serde_json::Value, text: &str) -> Self {
+        if verbose {
+            Self {
+                status,
+                content_type: "application/json",
+                body: json.to_string(),
+            }
+        } else {
+            Self {
+                status,
+                content_type: "text/plain; charset=utf-8",
+                body: text.to_string(),
+            }
+        }
+    }
+}
+
+/// Answers the `/healthz` and `/readyz` probes of an HTTP integration.
+///
+/// Cloning is cheap; clones share the checker.
+#[derive(Debug, Clone)]
+pub struct HealthProbes {
+    checker: Arc<HealthChecker>,
+}
+
+impl HealthProbes {
+    /// Serve probes backed by `checker`, whose checks decide readiness.
+    #[must_use]
+    pub fn new(checker: HealthChecker) -> Self {
+        Self {
+            checker: Arc::new(checker),
+        }
+    }
+
+    /// Whether a probe's query string asks for the JSON report
+    /// (`?verbose` or `?verbose=...`).
+    #[must_use]
+    pub fn verbose_requested(query: Option<&str>) -> bool {
+        query.is_some_and(|query| {
+            query
+                .split('&')
+                .any(|pair| pair == "verbose" || pair.starts_with("verbose="))
+        })
+    }
+
+    /// Answer `/healthz`: always `200` while the process can respond.
+    #[must_use]
+    pub fn liveness(&self, verbose: bool) -> ProbeResponse {
+        let response = LivenessResponse::alive(&self.checker.service_name);
+        ProbeResponse::new(200, verbose, &response.to_json(), "ok")
+    }
+
+    /// Answer `/readyz`: run the checks, add `sessions` (see
+    /// [`session_health`]), and answer `503` if any component is unhealthy.
+    #[must_use]
+    pub fn readiness(&self, sessions: ComponentHealth, verbose: bool) -> ProbeResponse {
+        let mut report = self.checker.check();
+        report.status = worse(report.status, &sessions.status);
+        report.components.insert("sessions".to_string(), sessions);
+        let status = report.status.http_status_code();
+        let text = if status == 200 { "ok" } else { "not ready" };
+        ProbeResponse::new(status, verbose, &report.to_json(), text)
+    }
+}
+
+/// Health of an HTTP integration's session manager: unhealthy once shutdown
+/// has begun, with the number of open sessions as a detail.
+#[must_use]
+pub fn session_health(active_sessions: usize, shutting_down: bool) -> ComponentHealth {
+    let health = if shutting_down {
+        ComponentHealth::unhealthy("shutting down")
+    } else {
+        ComponentHealth::healthy()
+    };
+    health.with_detail("active", active_sessions.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -535,4 +639,34 @@ mod tests {
         assert_eq!(json["service"], "test-service");
         assert_eq!(json["version"], "1.0.0");
     }
+
+    #[test]
+    fn test_probes() {
+        let mut checker = HealthChecker::new("test-service");
+        checker.add_check("database", ComponentHealth::healthy);
+        let probes = HealthProbes::new(checker);
+
+        let live = probes.liveness(false);
+        assert_eq!((live.status, live.body.as_str()), (200, "ok"));
+
+        let ready = probes.readiness(session_health(2, false), false);
+        assert_eq!((ready.status, ready.body.as_str()), (200, "ok"));
+
+        let draining = probes.readiness(session_health(2, true), true);
+        assert_eq!(draining.status, 503);
+        assert_eq!(draining.content_type, "application/json");
+        let json: serde_json::Value = serde_json::from_str(&draining.body).unwrap();
+        assert_eq!(json["status"], "unhealthy");
+        assert_eq!(json["components"]["database"]["status"], "healthy");
+        assert_eq!(json["components"]["sessions"]["message"], "shutting down");
+        assert_eq!(json["components"]["sessions"]["details"]["active"], "2");
+    }
+
+    #[test]
+    fn test_verbose_requested() {
+        assert!(HealthProbes::verbose_requested(Some("verbose")));
+        assert!(HealthProbes::verbose_requested(Some("a=1&verbose=true")));
+        assert!(!HealthProbes::verbose_requested(Some("verbosity=1")));
+        assert!(!HealthProbes::verbose_requested(None));
+    }
 }
@@ -0,0 +1,552 @@
+//! Deduplication of retried tool calls.
+//!
+//! A client that resends a `tools/call` after a transport error attaches the
+//! same key to every attempt in `_meta["mcpkit/idempotencyKey"]` (see
+//! [`Meta::with_idempotency_key_in_params`]). The server runtime records the
+//! result of each keyed call in an [`IdempotencyCache`] and answers a
+//! duplicate with that result instead of running the tool again. A duplicate
+//! that arrives while the first attempt is still running waits for it.
+//!
+//! Tools annotated `idempotentHint` (`#[tool(idempotent = true)]`) are safe to
+//! run twice and are not tracked. Only calls that produce a result are
+//! recorded, including `isError` results; a call that fails with a protocol
+//! error (or is cancelled) can be retried. Reusing a key for a different tool
+//! or different arguments is rejected as invalid params.
+//!
+//! Results are kept for the configured TTL. When the cache is full, expired
+//! results are dropped first, then the oldest. The runtime uses
+//! [`RuntimeConfig::idempotency`]; clone one configuration (or cache) into
+//! every runtime serving the same clients so that a retry arriving on a new
+//! connection is recognized.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::idempotency::{IdempotencyCache, IdempotencyConfig};
+//! use mcpkit_server::RuntimeConfig;
+//! use std::time::Duration;
+//!
+//! let cache = IdempotencyCache::new(
+//!     IdempotencyConfig::new()
+//!         .ttl(Duration::from_secs(600))
+//!         .max_entries(4096),
+//! );
+//! let config = RuntimeConfig {
+//!     idempotency: Some(cache),
+//!     ..RuntimeConfig::default()
+//! };
+//! assert!(config.idempotency.is_some_and(|cache| cache.is_empty()));
+//! ```
+//!
+//! [`Meta::with_idempotency_key_in_params`]: mcpkit_core::types::Meta::with_idempotency_key_in_params
+//! [`RuntimeConfig::idempotency`]: crate::server::RuntimeConfig::idempotency
+
+use crate::cache::Key;
+use crate::context::Context;
+use crate::dispatch::DynToolHandler;
+use event_listener::{Event, EventListener};
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{CallToolResult, Meta, Object, Tool};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Default time a recorded result is kept.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// Default maximum number of recorded results.
+pub const DEFAULT_MAX_ENTRIES: usize = 1024;
+
+/// Settings for an [`IdempotencyCache`].
+#[derive(Debug, Clone)]
+pub struct IdempotencyConfig {
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_TTL,
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+impl IdempotencyConfig {
+    /// Create a configuration with the default TTL and size.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long a result is kept for retries. Keep it longer than the
+    /// clients' retry window.
+    #[must_use]
+    pub const fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the maximum number of recorded results (at least one).
+    #[must_use]
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+}
+
+/// What a key currently maps to.
+enum State {
+    /// The first attempt is running; duplicates wait on the event.
+    Running(Arc<Event>),
+    /// The first attempt finished with this result.
+    Done {
+        result: CallToolResult,
+        expires_at: Instant,
+    },
+}
+
+/// A recorded call.
+struct Entry {
+    /// Tool name and canonical arguments the key was first used with.
+    call: Key,
+    state: State,
+    /// Insertion order, for evicting the oldest result.
+    seq: u64,
+}
+
+/// Mutable cache contents.
+#[derive(Default)]
+struct Entries {
+    map: HashMap<String, Entry>,
+    seq: u64,
+}
+
+/// How to proceed with a keyed call.
+enum Claim {
+    /// Run the call; this attempt owns the key.
+    Run,
+    /// Another attempt is running; wait and look again.
+    Wait(EventListener),
+    /// Answer with the recorded result.
+    Replay(CallToolResult),
+}
+
+/// Results of keyed tool calls, shared by its clones.
+#[derive(Clone, Default)]
+pub struct IdempotencyCache {
+    config: IdempotencyConfig,
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl IdempotencyCache {
+    /// Create an empty cache.
+    #[must_use]
+    pub fn new(config: IdempotencyConfig) -> Self {
+        Self {
+            config,
+            entries: Arc::default(),
+        }
+    }
+
+    /// The cache's configuration.
+    #[must_use]
+    pub const fn config(&self) -> &IdempotencyConfig {
+        &self.config
+    }
+
+    /// Run `call` once per `key`.
+    ///
+    /// The first call with a key runs and its result is recorded; later calls
+    /// with the same key, `name`, and `args` get that result (waiting for it if
+    /// it is still running). If the first attempt fails with an error or is
+    /// dropped, the key is released and the next attempt runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `call`, or invalid params if `key` was already
+    /// used for a different tool or arguments.
+    pub async fn run<F, Fut>(
+        &self,
+        key: &str,
+        name: &str,
+        args: Object,
+        call: F,
+    ) -> Result<CallToolResult, McpError>
+    where
+        F: FnOnce(Object) -> Fut,
+        Fut: Future<Output = Result<CallToolResult, McpError>>,
+    {
+        let fingerprint = crate::cache::key(name, &args);
+        loop {
+            match self.claim(key, &fingerprint)? {
+                Claim::Run => break,
+                Claim::Wait(listener) => listener.await,
+                Claim::Replay(result) => {
+                    tracing::debug!(tool = name, key, "replaying result of a retried call");
+                    return Ok(result);
+                }
+            }
+        }
+
+        let release = Release { cache: self, key };
+        let result = call(args).await;
+        if let Ok(result) = &result {
+            self.complete(key, result.clone());
+        }
+        drop(release);
+        result
+    }
+
+    /// Number of recorded keys, including running calls and expired results
+    /// not yet dropped.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().map.len()
+    }
+
+    /// Whether nothing is recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every recorded result. Running calls are kept.
+    pub fn clear(&self) {
+        self.lock()
+            .map
+            .retain(|_, entry| matches!(entry.state, State::Running(_)));
+    }
+
+    fn claim(&self, key: &str, call: &Key) -> Result<Claim, McpError> {
+        let mut entries = self.lock();
+        let now = Instant::now();
+        match entries.map.get(key) {
+            Some(Entry {
+                state: State::Done { expires_at, .. },
+                ..
+            }) if *expires_at <= now => {}
+            Some(entry) if entry.call != *call => {
+                return Err(McpError::invalid_params(
+                    "tools/call",
+                    "idempotency key was already used for a different call",
+                ));
+            }
+            Some(Entry {
+                state: State::Running(event),
+                ..
+            }) => return Ok(Claim::Wait(event.listen())),
+            Some(Entry {
+                state: State::Done { result, .. },
+                ..
+            }) => return Ok(Claim::Replay(result.clone())),
+            None => {}
+        }
+
+        if !entries.map.contains_key(key) && entries.map.len() >= self.config.max_entries {
+            entries.map.retain(|_, entry| match entry.state {
+                State::Running(_) => true,
+                State::Done { expires_at, .. } => expires_at > now,
+            });
+            if entries.map.len() >= self.config.max_entries {
+                let oldest = entries
+                    .map
+                    .iter()
+                    .filter(|(_, entry)| matches!(entry.state, State::Done { .. }))
+                    .min_by_key(|(_, entry)| entry.seq)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.map.remove(&oldest);
+                }
+            }
+        }
+        entries.seq += 1;
+        let seq = entries.seq;
+        entries.map.insert(
+            key.to_string(),
+            Entry {
+                call: call.clone(),
+                state: State::Running(Arc::new(Event::new())),
+                seq,
+            },
+        );
+        Ok(Claim::Run)
+    }
+
+    /// Record the result of the running call for `key` and wake its waiters.
+    fn complete(&self, key: &str, result: CallToolResult) {
+        let mut entries = self.lock();
+        if let Some(entry) = entries.map.get_mut(key) {
+            let done = State::Done {
+                result,
+                expires_at: Instant::now() + self.config.ttl,
+            };
+            if let State::Running(event) = std::mem::replace(&mut entry.state, done) {
+                event.notify(usize::MAX);
+            }
+        }
+    }
+
+    /// Forget `key` if its call is still marked running, waking its waiters
+    /// so one of them can run instead.
+    fn release(&self, key: &str) {
+        let mut entries = self.lock();
+        if let Some(Entry {
+            state: State::Running(event),
+            ..
+        }) = entries.map.get(key)
+        {
+            event.notify(usize::MAX);
+            entries.map.remove(key);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for IdempotencyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdempotencyCache")
+            .field("config", &self.config)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Releases a key whose call failed or was dropped before completing.
+struct Release<'a> {
+    cache: &'a IdempotencyCache,
+    key: &'a str,
+}
+
+impl Drop for Release<'_> {
+    fn drop(&mut self) {
+        self.cache.release(self.key);
+    }
+}
+
+/// Whether a tool's annotations declare it safe to run twice.
+fn is_idempotent(tool: &Tool) -> bool {
+    tool.annotations
+        .as_ref()
+        .is_some_and(|annotations| annotations.idempotent_hint == Some(true))
+}
+
+/// Call the tool `name`, deduplicating by the request's idempotency key when
+/// the context carries an [`IdempotencyCache`] and the tool is not marked
+/// idempotent.
+pub(crate) async fn call_tool(
+    handler: &dyn DynToolHandler,
+    name: &str,
+    args: Object,
+    params: &Value,
+    ctx: &Context<'_>,
+) -> Result<CallToolResult, McpError> {
+    let keyed = ctx
+        .idempotency_cache()
+        .zip(Meta::idempotency_key_from_params(params));
+    let Some((cache, key)) = keyed else {
+        return handler.call_tool(name, args, ctx).await.map(Into::into);
+    };
+
+    // A failed listing still deduplicates; that is the safe choice.
+    let idempotent = match handler.list_tools(ctx).await {
+        Ok(tools) => tools
+            .iter()
+            .any(|tool| tool.name == name && is_idempotent(tool)),
+        Err(error) => {
+            tracing::warn!(%error, tool = name, "could not list tools; deduplicating");
+            false
+        }
+    };
+    if idempotent {
+        return handler.call_tool(name, args, ctx).await.map(Into::into);
+    }
+    cache
+        .run(key, name, args, |args| async move {
+            handler.call_tool(name, args, ctx).await.map(Into::into)
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::NoOpPeer;
+    use crate::handler::ToolHandler;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::{ToolAnnotations, ToolOutput};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// "charge" has side effects; "set" is idempotent.
+    #[derive(Default)]
+    struct Payments {
+        calls: AtomicUsize,
+    }
+
+    impl ToolHandler for Payments {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(vec![
+                Tool::new("charge"),
+                Tool::new("set").annotations(ToolAnnotations::idempotent()),
+            ])
+        }
+
+        async fn call_tool(
+            &self,
+            _name: &str,
+            _args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ToolOutput::text(n.to_string()))
+        }
+    }
+
+    fn text(result: &CallToolResult) -> String {
+        serde_json::to_value(result).unwrap()["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    async fn call(
+        handler: &Payments,
+        cache: &IdempotencyCache,
+        params: &Value,
+    ) -> Result<CallToolResult, McpError> {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        )
+        .with_idempotency_cache(cache);
+        let name = params["name"].as_str().unwrap();
+        let args = params["arguments"].as_object().cloned().unwrap_or_default();
+        call_tool(handler, name, args, params, &ctx).await
+    }
+
+    #[tokio::test]
+    async fn retried_calls_replay_the_first_result() {
+        let handler = Payments::default();
+        let cache = IdempotencyCache::default();
+        let keyed = Meta::with_idempotency_key_in_params(
+            Some(json!({ "name": "charge", "arguments": { "cents": 500 } })),
+            "k-1",
+        );
+
+        let first = call(&handler, &cache, &keyed).await.unwrap();
+        let retry = call(&handler, &cache, &keyed).await.unwrap();
+        assert_eq!(text(&first), "0");
+        assert_eq!(text(&retry), "0");
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 1);
+
+        // Unkeyed calls and idempotent tools always run.
+        let unkeyed = json!({ "name": "charge", "arguments": { "cents": 500 } });
+        assert_eq!(text(&call(&handler, &cache, &unkeyed).await.unwrap()), "1");
+        let set = Meta::with_idempotency_key_in_params(Some(json!({ "name": "set" })), "k-2");
+        call(&handler, &cache, &set).await.unwrap();
+        call(&handler, &cache, &set).await.unwrap();
+        assert_eq!(handler.calls.load(Ordering::SeqCst), 4);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reused_key_with_other_arguments_is_rejected() {
+        let handler = Payments::default();
+        let cache = IdempotencyCache::default();
+        let first = Meta::with_idempotency_key_in_params(
+            Some(json!({ "name": "charge", "arguments": { "cents": 500 } })),
+            "k-1",
+        );
+        let other = Meta::with_idempotency_key_in_params(
+            Some(json!({ "name": "charge", "arguments": { "cents": 900 } })),
+            "k-1",
+        );
+        call(&handler, &cache, &first).await.unwrap();
+        let err = call(&handler, &cache, &other).await.unwrap_err();
+        assert!(err.to_string().contains("idempotency key"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn failed_and_expired_calls_run_again() {
+        let cache = IdempotencyCache::new(IdempotencyConfig::new().ttl(Duration::ZERO));
+        let runs = &AtomicUsize::new(0);
+        let run = |fail: bool| {
+            cache.run("k", "charge", Object::new(), move |_| async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                if fail {
+                    Err(McpError::internal("connection reset"))
+                } else {
+                    Ok(CallToolResult::text("ok"))
+                }
+            })
+        };
+        assert!(run(true).await.is_err());
+        assert!(cache.is_empty());
+        run(false).await.unwrap();
+        // The TTL is zero, so the result has already expired.
+        run(false).await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn concurrent_duplicate_waits_for_the_first_attempt() {
+        let cache = IdempotencyCache::default();
+        let runs = AtomicUsize::new(0);
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        let first = cache.run("k", "charge", Object::new(), |_| async {
+            runs.fetch_add(1, Ordering::SeqCst);
+            released.await.unwrap();
+            Ok(CallToolResult::text("first"))
+        });
+        let second = async {
+            tokio::task::yield_now().await;
+            cache
+                .run("k", "charge", Object::new(), |_| async {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    Ok(CallToolResult::text("second"))
+                })
+                .await
+        };
+        let unblock = async {
+            tokio::task::yield_now().await;
+            tokio::task::yield_now().await;
+            release.send(()).unwrap();
+        };
+        let (first, second, ()) = tokio::join!(first, second, unblock);
+        assert_eq!(text(&first.unwrap()), "first");
+        assert_eq!(text(&second.unwrap()), "first");
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn full_cache_evicts_the_oldest_result() {
+        let cache = IdempotencyCache::new(IdempotencyConfig::new().max_entries(2));
+        for key in ["a", "b", "c"] {
+            futures::executor::block_on(cache.run(key, "charge", Object::new(), |_| async {
+                Ok(CallToolResult::text(key))
+            }))
+            .unwrap();
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.lock().map.contains_key("a"));
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
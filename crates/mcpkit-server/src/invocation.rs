@@ -0,0 +1,545 @@
+//! Tracing spans around tool, resource, and prompt invocations.
+//!
+//! Every `tools/call`, `resources/read`, and `prompts/get` the router
+//! dispatches runs inside a span named `mcp.tool`, `mcp.resource`, or
+//! `mcp.prompt` (target [`INVOCATION_TARGET`]), nested in the
+//! `mcp.request` span of the request. The span carries:
+//!
+//! | Field | Value |
+//! |-------|-------|
+//! | `mcp.name` | The tool name, resource URI, or prompt name |
+//! | `mcp.request_id` | The JSON-RPC request id |
+//! | `mcp.session_id` | The session id, when served over HTTP |
+//! | `mcp.arguments` | The arguments, when [`ArgumentCapture`] allows it |
+//! | `duration_ms` | How long the handler ran |
+//! | `outcome` | `ok`, `tool_error`, or `error` |
+//! | `error.code` | The JSON-RPC error code, for protocol errors |
+//!
+//! When the invocation finishes, an event tells the outcomes apart: a tool
+//! that returned an error result (`isError: true`, which the model sees and
+//! can recover from) is logged at `info`, while a JSON-RPC error (unknown
+//! tool, invalid params, a handler failure) is logged at `warn`.
+//!
+//! Arguments are not captured by default, since they often hold secrets.
+//! [`ArgumentCapture::Redacted`] records them with the values of
+//! secret-looking keys replaced by `<redacted>`; [`ArgumentCapture::Full`]
+//! records them verbatim. Tools a [`RecordingPolicy`] excludes (including
+//! tools marked `no_record`) never have their arguments captured.
+//!
+//! Handlers generated by `#[mcp_server]` also enter a `debug`-level
+//! `mcp.handler` span naming the Rust type that serves the call.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::invocation::{ArgumentCapture, InvocationTracing};
+//! use mcpkit_server::server::RuntimeConfig;
+//!
+//! let config = RuntimeConfig {
+//!     invocation_tracing: InvocationTracing::new()
+//!         .arguments(ArgumentCapture::Redacted)
+//!         .redact_key("ssn"),
+//!     ..RuntimeConfig::default()
+//! };
+//! # let _ = config;
+//! ```
+
+use crate::context::Context;
+use mcpkit_core::debug::{REDACTED, RecordingPolicy};
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{CallToolResult, Object};
+use serde_json::Value;
+use std::future::Future;
+use std::time::Instant;
+use tracing::instrument::Instrumented;
+use tracing::{Instrument, Span, field};
+
+/// The `tracing` target of invocation spans and events.
+pub const INVOCATION_TARGET: &str = "mcpkit::invocation";
+
+/// Argument keys redacted by default under [`ArgumentCapture::Redacted`].
+pub const DEFAULT_REDACT_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "client_secret",
+    "token",
+    "access_token",
+    "refresh_token",
+    "api_key",
+    "apikey",
+    "authorization",
+    "private_key",
+];
+
+/// What invocation spans record of the arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgumentCapture {
+    /// Record nothing about the arguments.
+    #[default]
+    Off,
+    /// Record the arguments with secrets redacted.
+    Redacted,
+    /// Record the arguments as sent.
+    Full,
+}
+
+/// What kind of handler an invocation runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvocationKind {
+    /// A `tools/call`.
+    Tool,
+    /// A `resources/read`.
+    Resource,
+    /// A `prompts/get`.
+    Prompt,
+}
+
+impl InvocationKind {
+    /// The kind as a lowercase label (`tool`, `resource`, `prompt`).
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tool => "tool",
+            Self::Resource => "resource",
+            Self::Prompt => "prompt",
+        }
+    }
+
+    const fn method(self) -> &'static str {
+        match self {
+            Self::Tool => "tools/call",
+            Self::Resource => "resources/read",
+            Self::Prompt => "prompts/get",
+        }
+    }
+}
+
+/// How tool, resource, and prompt invocations are traced.
+///
+/// Set it with
+/// [`RuntimeConfig::invocation_tracing`](crate::server::RuntimeConfig::invocation_tracing)
+/// for the server runtime, or `McpRouter::with_invocation_tracing` in the
+/// HTTP adapters.
+#[derive(Debug, Clone)]
+pub struct InvocationTracing {
+    arguments: ArgumentCapture,
+    redact_keys: Vec<String>,
+    policy: RecordingPolicy,
+}
+
+impl Default for InvocationTracing {
+    fn default() -> Self {
+        Self {
+            arguments: ArgumentCapture::Off,
+            redact_keys: DEFAULT_REDACT_KEYS
+                .iter()
+                .map(|k| (*k).to_string())
+                .collect(),
+            policy: RecordingPolicy::new(),
+        }
+    }
+}
+
+impl InvocationTracing {
+    /// Create settings that trace every invocation without capturing
+    /// arguments.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Choose what spans record of the arguments (nothing by default).
+    #[must_use]
+    pub const fn arguments(mut self, capture: ArgumentCapture) -> Self {
+        self.arguments = capture;
+        self
+    }
+
+    /// Replace the value of every argument named `key`, at any depth, with
+    /// `<redacted>` in captured arguments. Matching ignores case. Adds to
+    /// [`DEFAULT_REDACT_KEYS`].
+    #[must_use]
+    pub fn redact_key(mut self, key: impl Into<String>) -> Self {
+        self.redact_keys.push(key.into());
+        self
+    }
+
+    /// Never capture the arguments of tools `policy` excludes (including
+    /// tools marked `no_record`).
+    #[must_use]
+    pub fn recording_policy(mut self, policy: RecordingPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The arguments to record for an invocation of `name`, if any.
+    fn capture(&self, kind: InvocationKind, name: &str, arguments: &Object) -> Option<String> {
+        if kind == InvocationKind::Tool && self.policy.is_excluded(name) {
+            return None;
+        }
+        match self.arguments {
+            ArgumentCapture::Off => None,
+            ArgumentCapture::Redacted => {
+                Some(self.redact(&Value::Object(arguments.clone())).to_string())
+            }
+            ArgumentCapture::Full => serde_json::to_string(arguments).ok(),
+        }
+    }
+
+    fn redact(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| {
+                        let secret = self.redact_keys.iter().any(|r| r.eq_ignore_ascii_case(k));
+                        let v = if secret {
+                            Value::from(REDACTED)
+                        } else {
+                            self.redact(v)
+                        };
+                        (k.clone(), v)
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => Value::Array(items.iter().map(|v| self.redact(v)).collect()),
+            other => other.clone(),
+        }
+    }
+}
+
+/// How an invocation ended.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Outcome<'e> {
+    /// The handler succeeded.
+    Ok,
+    /// The tool returned an error result the model can recover from.
+    ToolError,
+    /// The request failed with a JSON-RPC error.
+    Error(&'e McpError),
+}
+
+impl<'e> Outcome<'e> {
+    /// The outcome of a `tools/call`.
+    pub(crate) fn of_tool(output: &'e Result<CallToolResult, McpError>) -> Self {
+        match output {
+            Ok(result) if result.is_error() => Self::ToolError,
+            Ok(_) => Self::Ok,
+            Err(e) => Self::Error(e),
+        }
+    }
+
+    /// The outcome of a resource read or prompt retrieval.
+    pub(crate) fn of<T>(result: &'e Result<T, McpError>) -> Self {
+        result.as_ref().map_or_else(Self::Error, |_| Self::Ok)
+    }
+}
+
+/// A traced invocation in progress.
+pub(crate) struct Invocation {
+    span: Span,
+    kind: InvocationKind,
+    started: Instant,
+}
+
+impl Invocation {
+    /// Open the span for invoking `name`, capturing `arguments` as the
+    /// context's [`InvocationTracing`] allows.
+    pub(crate) fn start(
+        ctx: &Context<'_>,
+        kind: InvocationKind,
+        name: &str,
+        arguments: Option<&Object>,
+    ) -> Self {
+        let span = invocation_span(ctx, kind, name);
+        let captured = ctx
+            .invocation_tracing()
+            .zip(arguments)
+            .and_then(|(tracing, args)| tracing.capture(kind, name, args));
+        if let Some(captured) = captured {
+            span.record("mcp.arguments", captured.as_str());
+        }
+        tracing::debug!(
+            target: INVOCATION_TARGET,
+            parent: &span,
+            "Invoking {}",
+            kind.as_str()
+        );
+        Self {
+            span,
+            kind,
+            started: Instant::now(),
+        }
+    }
+
+    /// The span the handler should run in.
+    pub(crate) fn span(&self) -> Span {
+        self.span.clone()
+    }
+
+    /// Record how the invocation ended and emit its completion event.
+    pub(crate) fn finish(self, outcome: Outcome<'_>) {
+        let duration_ms = u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let kind = self.kind.as_str();
+        let span = &self.span;
+        span.record("duration_ms", duration_ms);
+        match outcome {
+            Outcome::Ok => {
+                span.record("outcome", "ok");
+                tracing::info!(
+                    target: INVOCATION_TARGET,
+                    parent: span,
+                    duration_ms,
+                    "{kind} invocation completed"
+                );
+            }
+            Outcome::ToolError => {
+                span.record("outcome", "tool_error");
+                tracing::info!(
+                    target: INVOCATION_TARGET,
+                    parent: span,
+                    duration_ms,
+                    "{kind} returned an error result"
+                );
+            }
+            Outcome::Error(error) => {
+                let code = error.code();
+                span.record("outcome", "error");
+                span.record("error.code", code);
+                tracing::warn!(
+                    target: INVOCATION_TARGET,
+                    parent: span,
+                    duration_ms,
+                    error.code = code,
+                    error = %error,
+                    "{kind} invocation failed"
+                );
+            }
+        }
+    }
+}
+
+fn invocation_span(ctx: &Context<'_>, kind: InvocationKind, name: &str) -> Span {
+    macro_rules! span {
+        ($name:literal) => {
+            tracing::info_span!(
+                target: INVOCATION_TARGET,
+                $name,
+                otel.name = %format_args!("{} {}", kind.method(), name),
+                mcp.name = %name,
+                mcp.request_id = %ctx.request_id,
+                mcp.session_id = ctx.session_id(),
+                mcp.arguments = field::Empty,
+                duration_ms = field::Empty,
+                outcome = field::Empty,
+                error.code = field::Empty,
+            )
+        };
+    }
+    match kind {
+        InvocationKind::Tool => span!("mcp.tool"),
+        InvocationKind::Resource => span!("mcp.resource"),
+        InvocationKind::Prompt => span!("mcp.prompt"),
+    }
+}
+
+/// Run a generated handler's dispatch in a `debug`-level `mcp.handler` span
+/// naming the type that serves it. Used by `#[mcp_server]`.
+#[doc(hidden)]
+pub fn instrument<F: Future>(
+    kind: InvocationKind,
+    handler: &'static str,
+    name: &str,
+    future: F,
+) -> Instrumented<F> {
+    future.instrument(tracing::debug_span!(
+        target: INVOCATION_TARGET,
+        "mcp.handler",
+        mcp.kind = kind.as_str(),
+        mcp.name = %name,
+        code.namespace = handler,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::NoOpPeer;
+    use crate::extensions::{Extensions, SessionId};
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::subscriber::Subscriber;
+    use tracing_subscriber::layer::{Context as LayerContext, Layer, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// Collects the fields of every span, keyed by span name.
+    #[derive(Clone, Default)]
+    struct SpanFields(Arc<Mutex<HashMap<String, HashMap<String, String>>>>);
+
+    impl SpanFields {
+        fn get(&self, span: &str, field: &str) -> Option<String> {
+            self.0.lock().ok()?.get(span)?.get(field).cloned()
+        }
+    }
+
+    struct Visitor<'a>(&'a mut HashMap<String, String>);
+
+    impl field::Visit for Visitor<'_> {
+        fn record_debug(&mut self, field: &field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(
+                field.name().to_string(),
+                format!("{value:?}").replace('"', ""),
+            );
+        }
+
+        fn record_str(&mut self, field: &field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    impl<S: Subscriber + for<'l> LookupSpan<'l>> Layer<S> for SpanFields {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _: &Id, _: LayerContext<'_, S>) {
+            if let Ok(mut spans) = self.0.lock() {
+                let fields = spans
+                    .entry(attrs.metadata().name().to_string())
+                    .or_default();
+                attrs.record(&mut Visitor(fields));
+            }
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: LayerContext<'_, S>) {
+            let Some(span) = ctx.span(id) else { return };
+            if let Ok(mut spans) = self.0.lock() {
+                let fields = spans.entry(span.name().to_string()).or_default();
+                values.record(&mut Visitor(fields));
+            }
+        }
+    }
+
+    fn context_parts() -> (
+        RequestId,
+        ClientCapabilities,
+        ServerCapabilities,
+        Extensions,
+    ) {
+        let mut extensions = Extensions::new();
+        extensions.insert(SessionId("session-1".to_string()));
+        (
+            RequestId::Number(7),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            extensions,
+        )
+    }
+
+    fn object(value: Value) -> Object {
+        match value {
+            Value::Object(map) => map,
+            _ => Object::new(),
+        }
+    }
+
+    #[test]
+    fn capture_modes_and_redaction() {
+        let args =
+            object(json!({ "query": "rust", "auth": { "Password": "hunter2" }, "api_key": "k" }));
+        let off = InvocationTracing::new();
+        assert_eq!(off.capture(InvocationKind::Tool, "search", &args), None);
+
+        let redacted = InvocationTracing::new().arguments(ArgumentCapture::Redacted);
+        let captured = redacted.capture(InvocationKind::Tool, "search", &args);
+        let captured: Value =
+            serde_json::from_str(&captured.unwrap_or_default()).unwrap_or_default();
+        assert_eq!(
+            captured,
+            json!({ "query": "rust", "auth": { "Password": REDACTED }, "api_key": REDACTED })
+        );
+
+        let full = InvocationTracing::new().arguments(ArgumentCapture::Full);
+        assert_eq!(
+            full.capture(InvocationKind::Tool, "search", &args),
+            Some(Value::Object(args.clone()).to_string())
+        );
+
+        let excluded = full.recording_policy(RecordingPolicy::new().exclude_tool("search"));
+        assert_eq!(
+            excluded.capture(InvocationKind::Tool, "search", &args),
+            None
+        );
+        assert!(
+            excluded
+                .capture(InvocationKind::Prompt, "search", &args)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn span_records_invocation_fields_and_outcome() {
+        let fields = SpanFields::default();
+        let subscriber = tracing_subscriber::registry().with(fields.clone());
+        let (request_id, client_caps, server_caps, extensions) = context_parts();
+        let settings = InvocationTracing::new()
+            .arguments(ArgumentCapture::Redacted)
+            .redact_key("ssn");
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        )
+        .with_extensions(&extensions)
+        .with_invocation_tracing(&settings);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let args = object(json!({ "ssn": "123-45-6789" }));
+            let invocation = Invocation::start(&ctx, InvocationKind::Tool, "lookup", Some(&args));
+            let output: Result<CallToolResult, McpError> = Ok(CallToolResult::error("not found"));
+            invocation.finish(Outcome::of_tool(&output));
+
+            let invocation = Invocation::start(&ctx, InvocationKind::Prompt, "greet", None);
+            let result: Result<(), McpError> =
+                Err(McpError::invalid_params("prompts/get", "missing name"));
+            invocation.finish(Outcome::of(&result));
+        });
+
+        assert_eq!(
+            fields.get("mcp.tool", "mcp.name").as_deref(),
+            Some("lookup")
+        );
+        assert_eq!(
+            fields.get("mcp.tool", "mcp.request_id").as_deref(),
+            Some("7")
+        );
+        assert_eq!(
+            fields.get("mcp.tool", "mcp.session_id").as_deref(),
+            Some("session-1")
+        );
+        assert_eq!(
+            fields.get("mcp.tool", "mcp.arguments"),
+            Some(json!({ "ssn": REDACTED }).to_string())
+        );
+        assert_eq!(
+            fields.get("mcp.tool", "outcome").as_deref(),
+            Some("tool_error")
+        );
+        assert!(fields.get("mcp.tool", "duration_ms").is_some());
+        assert_eq!(
+            fields.get("mcp.prompt", "outcome").as_deref(),
+            Some("error")
+        );
+        assert_eq!(
+            fields.get("mcp.prompt", "error.code").as_deref(),
+            Some("-32602")
+        );
+        assert_eq!(fields.get("mcp.prompt", "mcp.arguments"), None);
+    }
+}
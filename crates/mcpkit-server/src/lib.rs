@@ -57,51 +57,133 @@
 
 #![deny(missing_docs)]
 
+pub mod approval;
+pub mod audience;
+pub mod audit;
 pub mod builder;
+pub mod cache;
 pub mod capability;
+pub mod catalog;
+pub mod coercion;
+pub mod compose;
+pub mod config;
 pub mod context;
 pub mod dispatch;
+pub mod extensions;
+pub mod fanout;
 pub mod handler;
 pub mod health;
+pub mod idempotency;
+pub mod invocation;
+pub mod locked;
 pub mod metrics;
+pub mod middleware;
+pub mod policy;
+pub mod progress;
+pub mod rate_limit;
+pub mod registry;
 pub mod router;
+pub mod sampling;
+pub mod sandbox;
+pub mod self_check;
 pub mod server;
+#[cfg(feature = "tokio-runtime")]
+pub mod service;
+#[cfg(feature = "tokio-runtime")]
+pub mod session;
+pub mod shutdown;
 pub mod state;
+pub mod subscription;
+#[cfg(feature = "templates")]
+pub mod template;
+pub mod timeout;
 #[cfg(feature = "schema-validation")]
 pub mod validation;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
 
 // Re-export commonly used types
+pub use approval::ApprovingToolHandler;
+pub use audience::AudienceFilter;
+pub use audit::{
+    ArgumentsMode, AuditLayer, AuditLogger, AuditOutcome, AuditRecord, AuditSink, JsonlSink,
+    TracingSink,
+};
 pub use builder::{FullServer, MinimalServer, NotRegistered, Registered, Server, ServerBuilder};
+pub use cache::{CachingToolHandler, ToolCache, ToolCacheConfig};
+pub use capability::tasks::TaskContext;
+pub use catalog::{Catalog, CatalogFormat};
+pub use coercion::{Coercion, coerce_arguments};
+pub use compose::{Mountable, Mounts};
+pub use config::{ConfigError, ConfigFormat, ServerConfig, TransportKind};
 pub use context::{CancellationToken, CancelledFuture, Context, ContextData, NoOpPeer, Peer};
+pub use extensions::{Extensions, HttpRequestInfo, SessionId};
+pub use fanout::{Batch, FanOut, FanOutConfig, FanOutStats, Inbox, Outbox};
 pub use handler::{
     CompletionHandler, LogLevel, PromptHandler, ResourceHandler, ServerHandler, TaskHandler,
     ToolHandler,
 };
 pub use health::{
-    ComponentHealth, HealthChecker, HealthReport, HealthStatus, LivenessResponse, ReadinessResponse,
+    ComponentHealth, HealthChecker, HealthProbes, HealthReport, HealthStatus, LivenessResponse,
+    ProbeResponse, ReadinessResponse,
+};
+pub use idempotency::{IdempotencyCache, IdempotencyConfig};
+pub use invocation::{ArgumentCapture, InvocationTracing};
+pub use locked::{LockStrategy, Locked};
+pub use metrics::{MethodStats, MetricsLayer, MetricsSnapshot, ServerMetrics};
+pub use middleware::{HasCapabilities, Layered, McpLayer, McpMiddleware, Next};
+pub use policy::{
+    ArgConstraint, Decision, DeclarativePolicy, PolicyDenial, SessionInfo, ToolAuthorizer,
+    ToolPolicy, ToolPolicyLayer,
 };
-pub use metrics::{MethodStats, MetricsSnapshot, ServerMetrics};
+pub use progress::ProgressReporter;
+pub use rate_limit::{Quota, RateLimitConfig, RateLimitLayer, RateLimiter};
+pub use registry::DynamicToolRegistry;
 pub use router::{
     AugmentedTaskOutcome, begin_augmented_task, call_tool_json, route_completion, route_logging,
     route_prompts, route_resources, route_tools, run_augmented_tool, tool_task_support,
 };
+pub use sampling::SamplingSession;
+pub use sandbox::{Sandbox, SandboxFs, SandboxHttp, SandboxPolicy};
+pub use self_check::{Finding, SelfCheckConfig, SelfCheckReport, Severity};
 pub use server::{
     RequestRouter, RuntimeConfig, ServerNotifier, ServerRuntime, ServerState, TransportPeer,
 };
+#[cfg(feature = "tokio-runtime")]
+pub use session::{EvictionReason, SessionConfig};
+pub use shutdown::ShutdownHandle;
+pub use subscription::SubscriptionManager;
+#[cfg(feature = "templates")]
+pub use template::PromptTemplate;
+pub use timeout::Timeouts;
 #[cfg(feature = "schema-validation")]
 pub use validation::{ValidatingToolHandler, ValidationMode, validate_json};
 
 /// Prelude module for convenient imports.
 pub mod prelude {
+    pub use crate::audience::AudienceFilter;
+    pub use crate::audit::{AuditLayer, AuditLogger, AuditSink};
     pub use crate::builder::{
         FullServer, MinimalServer, NotRegistered, Registered, Server, ServerBuilder,
     };
+    pub use crate::cache::{ToolCache, ToolCacheConfig};
+    pub use crate::capability::tasks::TaskContext;
     pub use crate::context::{
         CancellationToken, CancelledFuture, Context, ContextData, NoOpPeer, Peer,
     };
+    pub use crate::extensions::{Extensions, HttpRequestInfo};
     pub use crate::handler::{
         CompletionHandler, LogLevel, PromptHandler, ResourceHandler, ServerHandler, TaskHandler,
         ToolHandler,
     };
-    pub use crate::metrics::{MethodStats, MetricsSnapshot, ServerMetrics};
+    pub use crate::metrics::{MethodStats, MetricsLayer, MetricsSnapshot, ServerMetrics};
+    pub use crate::middleware::{McpLayer, McpMiddleware, Next};
+    pub use crate::policy::{
+        ArgConstraint, Decision, DeclarativePolicy, ToolPolicy, ToolPolicyLayer,
+    };
+    pub use crate::progress::ProgressReporter;
+    pub use crate::rate_limit::{Quota, RateLimitConfig, RateLimitLayer};
+    pub use crate::sampling::SamplingSession;
+    pub use crate::shutdown::ShutdownHandle;
+    pub use crate::subscription::SubscriptionManager;
 }
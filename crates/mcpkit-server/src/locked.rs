@@ -0,0 +1,141 @@
+//! Servers whose tools take `&mut self`.
+//!
+//! Handlers are called through `&self`, many at a time. A `#[tool]` method
+//! that takes `&mut self` therefore needs the server behind a lock: for such
+//! a type, `#[mcp_server]` generates a `Locked<Name>` wrapper around a
+//! [`Locked`] and implements the handler traits for the wrapper instead.
+//! `into_server()` wraps the server itself, so only code that names the
+//! handler type (mounting, say) sees the difference. The wrapper converts
+//! `From` the server and hands out the lock with `server()`:
+//!
+//! ```ignore
+//! struct Counter {
+//!     count: u64,
+//! }
+//!
+//! #[mcp_server(name = "counter", version = "1.0.0")]
+//! impl Counter {
+//!     #[tool(description = "Add to the counter")]
+//!     fn add(&mut self, n: u64) -> String {
+//!         self.count += n;
+//!         self.count.to_string()
+//!     }
+//!
+//!     #[tool(description = "Read the counter", read_only)]
+//!     fn get(&self) -> String {
+//!         self.count.to_string()
+//!     }
+//! }
+//!
+//! let server = Counter { count: 0 }.into_server();
+//! ```
+//!
+//! The [`LockStrategy`] is chosen with `#[mcp_server(lock = "...")]`:
+//! `"rwlock"` (the default) lets `&self` tools run side by side and gives
+//! `&mut self` tools exclusive access; `"mutex"` runs every call alone.
+//! The lock is held until the tool returns, including across its `.await`s.
+
+use async_lock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::ops::Deref;
+
+/// How a [`Locked`] server admits concurrent calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LockStrategy {
+    /// Shared access for `&self` calls, exclusive for `&mut self` calls.
+    #[default]
+    RwLock,
+    /// Exclusive access for every call.
+    Mutex,
+}
+
+/// A server value behind an async lock.
+#[derive(Debug)]
+pub struct Locked<S> {
+    inner: RwLock<S>,
+    strategy: LockStrategy,
+}
+
+impl<S> Locked<S> {
+    /// Put `server` behind a lock taken according to `strategy`.
+    pub const fn new(server: S, strategy: LockStrategy) -> Self {
+        Self {
+            inner: RwLock::new(server),
+            strategy,
+        }
+    }
+
+    /// The strategy the lock is taken with.
+    #[must_use]
+    pub const fn strategy(&self) -> LockStrategy {
+        self.strategy
+    }
+
+    /// Borrow the server for a `&self` call.
+    ///
+    /// Shared under [`LockStrategy::RwLock`], exclusive under
+    /// [`LockStrategy::Mutex`].
+    pub async fn read(&self) -> LockedRef<'_, S> {
+        match self.strategy {
+            LockStrategy::RwLock => LockedRef(Guard::Shared(self.inner.read().await)),
+            LockStrategy::Mutex => LockedRef(Guard::Exclusive(self.inner.write().await)),
+        }
+    }
+
+    /// Borrow the server exclusively, for a `&mut self` call.
+    pub async fn write(&self) -> RwLockWriteGuard<'_, S> {
+        self.inner.write().await
+    }
+
+    /// Mutable access without locking, given exclusive access to the lock.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.inner.get_mut()
+    }
+
+    /// Take the server back out.
+    pub fn into_inner(self) -> S {
+        self.inner.into_inner()
+    }
+}
+
+/// A borrow of a [`Locked`] server, from [`Locked::read`].
+#[derive(Debug)]
+pub struct LockedRef<'a, S>(Guard<'a, S>);
+
+#[derive(Debug)]
+enum Guard<'a, S> {
+    Shared(RwLockReadGuard<'a, S>),
+    Exclusive(RwLockWriteGuard<'a, S>),
+}
+
+impl<S> Deref for LockedRef<'_, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        match &self.0 {
+            Guard::Shared(guard) => guard,
+            Guard::Exclusive(guard) => guard,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn strategy_decides_whether_reads_share() {
+        let shared = Locked::new(1, LockStrategy::RwLock);
+        let first = shared.read().await;
+        assert!(shared.inner.try_read().is_some());
+        assert!(shared.inner.try_write().is_none());
+        drop(first);
+        *shared.write().await += 1;
+        assert_eq!(*shared.read().await, 2);
+
+        let exclusive = Locked::new(1, LockStrategy::Mutex);
+        let first = exclusive.read().await;
+        assert!(exclusive.inner.try_read().is_none());
+        drop(first);
+        assert_eq!(exclusive.into_inner(), 1);
+    }
+}
@@ -2,6 +2,14 @@
 //!
 //! This module provides request-level metrics tracking for MCP servers,
 //! complementing the transport-level telemetry in `mcpkit_transport::telemetry`.
+//! Latencies go into fixed-bucket histograms, so every method and every tool
+//! reports p50/p95/p99 estimates alongside its request and error counts.
+//!
+//! Apply a [`MetricsLayer`] to record every request a server handles (or pass
+//! one to `McpRouter::with_server_metrics` in the HTTP adapters). With
+//! [`MetricsLayer::expose_resource`], the server also lists a
+//! [`METRICS_RESOURCE_URI`] resource whose contents are a JSON snapshot, so
+//! any MCP client can check the server's health without Prometheus.
 //!
 //! # Example
 //!
@@ -12,22 +20,151 @@
 //!
 //! // Record a request
 //! metrics.record_request("tools/call", std::time::Duration::from_millis(50), true);
+//! metrics.record_tool_call("search", std::time::Duration::from_millis(50), true);
 //!
 //! // Get statistics
 //! let stats = metrics.snapshot();
 //! println!("Total requests: {}", stats.total_requests);
 //! println!("Error rate: {:.2}%", stats.error_rate() * 100.0);
+//! println!("search p95: {:.1}ms", stats.tool("search").unwrap().p95_ms);
 //! ```
 
+use crate::context::Context;
+use crate::middleware::{McpMiddleware, Next};
+use crate::server::RequestRouter;
+use mcpkit_core::capability::ServerCapabilities;
+use mcpkit_core::error::{McpError, codes};
+use mcpkit_core::types::{Resource, ResourceContents};
+use serde::Serialize;
+use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::RwLock;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Duration;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// URI of the resource a [`MetricsLayer`] exposes its snapshot under.
+pub const METRICS_RESOURCE_URI: &str = "metrics://server";
+
+/// Upper bounds of the latency histogram buckets, in microseconds. A final
+/// overflow bucket holds everything slower.
+const BUCKET_BOUNDS_US: [u64; 20] = [
+    100,
+    250,
+    500,
+    1_000,
+    2_500,
+    5_000,
+    10_000,
+    25_000,
+    50_000,
+    100_000,
+    250_000,
+    500_000,
+    1_000_000,
+    2_500_000,
+    5_000_000,
+    10_000_000,
+    30_000_000,
+    60_000_000,
+    120_000_000,
+    300_000_000,
+];
+
+/// A fixed-bucket latency histogram.
+#[derive(Debug, Default)]
+struct LatencyHistogram {
+    buckets: [AtomicU64; BUCKET_BOUNDS_US.len() + 1],
+    count: AtomicU64,
+    sum_us: AtomicU64,
+    max_us: AtomicU64,
+}
+
+impl LatencyHistogram {
+    fn record(&self, latency_us: u64) {
+        let bucket = BUCKET_BOUNDS_US.partition_point(|&bound| bound < latency_us);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add(latency_us, Ordering::Relaxed);
+        self.max_us.fetch_max(latency_us, Ordering::Relaxed);
+    }
+
+    /// Estimate the `q` quantile (0.0 to 1.0) in milliseconds, interpolating
+    /// linearly within the bucket that holds it.
+    fn quantile_ms(&self, q: f64) -> f64 {
+        let count = self.count.load(Ordering::Relaxed);
+        if count == 0 {
+            return 0.0;
+        }
+        let max_us = self.max_us.load(Ordering::Relaxed);
+        let rank = (q.clamp(0.0, 1.0) * count as f64).ceil().max(1.0);
+        let mut seen = 0u64;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            let in_bucket = bucket.load(Ordering::Relaxed);
+            if in_bucket == 0 {
+                continue;
+            }
+            if (seen + in_bucket) as f64 >= rank {
+                let lower = if i == 0 { 0 } else { BUCKET_BOUNDS_US[i - 1] };
+                let upper = BUCKET_BOUNDS_US
+                    .get(i)
+                    .copied()
+                    .unwrap_or(max_us)
+                    .min(max_us);
+                let fraction = (rank - seen as f64) / in_bucket as f64;
+                let width = upper.saturating_sub(lower) as f64;
+                return width.mul_add(fraction, lower as f64) / 1000.0;
+            }
+            seen += in_bucket;
+        }
+        max_us as f64 / 1000.0
+    }
+
+    fn stats(&self, errors: u64) -> MethodStats {
+        let requests = self.count.load(Ordering::Relaxed);
+        let sum_us = self.sum_us.load(Ordering::Relaxed);
+        MethodStats {
+            requests,
+            errors,
+            avg_latency_ms: if requests > 0 {
+                (sum_us as f64 / requests as f64) / 1000.0
+            } else {
+                0.0
+            },
+            p50_ms: self.quantile_ms(0.50),
+            p95_ms: self.quantile_ms(0.95),
+            p99_ms: self.quantile_ms(0.99),
+            max_ms: self.max_us.load(Ordering::Relaxed) as f64 / 1000.0,
+        }
+    }
+}
+
+/// Request count, error count, and latencies of one method or tool.
+#[derive(Debug, Default)]
+struct Series {
+    errors: AtomicU64,
+    latency: LatencyHistogram,
+}
+
+impl Series {
+    fn record(&self, latency_us: u64, success: bool) {
+        self.latency.record(latency_us);
+        if !success {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn stats(&self) -> MethodStats {
+        self.latency.stats(self.errors.load(Ordering::Relaxed))
+    }
+}
+
+type SeriesMap = RwLock<HashMap<String, Arc<Series>>>;
 
 /// Server metrics collector.
 ///
-/// Tracks request counts, latencies, and errors at the MCP method level.
-/// All operations are thread-safe and lock-free where possible.
+/// Tracks request counts, latencies, and errors at the MCP method level, and
+/// per tool for `tools/call`. All operations are thread-safe and lock-free
+/// where possible.
 #[derive(Debug, Default)]
 pub struct ServerMetrics {
     /// Total requests received.
@@ -36,14 +173,20 @@ pub struct ServerMetrics {
     successful_requests: AtomicU64,
     /// Total failed requests.
     failed_requests: AtomicU64,
-    /// Total latency in microseconds (for average calculation).
-    total_latency_us: AtomicU64,
-    /// Per-method request counts.
-    method_counts: RwLock<HashMap<String, AtomicU64>>,
-    /// Per-method error counts.
-    method_errors: RwLock<HashMap<String, AtomicU64>>,
-    /// Per-method total latency in microseconds.
-    method_latency_us: RwLock<HashMap<String, AtomicU64>>,
+    /// Latency of every request.
+    latency: LatencyHistogram,
+    /// Per-method counts and latencies.
+    methods: SeriesMap,
+    /// Per-tool counts and latencies.
+    tools: SeriesMap,
+    /// Tool calls answered from the tool result cache.
+    cache_hits: AtomicU64,
+    /// Cacheable tool calls that ran the tool.
+    cache_misses: AtomicU64,
+    /// Requests stopped for running past their timeout.
+    timeouts: AtomicU64,
+    /// Per-operation timeout counts.
+    operation_timeouts: RwLock<HashMap<String, AtomicU64>>,
 }
 
 impl ServerMetrics {
@@ -65,8 +208,7 @@ impl ServerMetrics {
 
         // Update global counters
         self.total_requests.fetch_add(1, Ordering::Relaxed);
-        self.total_latency_us
-            .fetch_add(latency_us, Ordering::Relaxed);
+        self.latency.record(latency_us);
 
         if success {
             self.successful_requests.fetch_add(1, Ordering::Relaxed);
@@ -74,13 +216,16 @@ impl ServerMetrics {
             self.failed_requests.fetch_add(1, Ordering::Relaxed);
         }
 
-        // Update per-method counters
-        self.increment_method_counter(&self.method_counts, method);
-        self.add_method_latency(method, latency_us);
+        Self::series(&self.methods, method).record(latency_us, success);
+    }
 
-        if !success {
-            self.increment_method_counter(&self.method_errors, method);
-        }
+    /// Record a call to the tool `tool`.
+    ///
+    /// A call fails if it returned a JSON-RPC error or an error result
+    /// (`isError: true`). Record the `tools/call` request itself with
+    /// [`record_request`](Self::record_request) as well.
+    pub fn record_tool_call(&self, tool: &str, duration: Duration, success: bool) {
+        Self::series(&self.tools, tool).record(duration.as_micros() as u64, success);
     }
 
     /// Record a successful request (convenience method).
@@ -93,61 +238,50 @@ impl ServerMetrics {
         self.record_request(method, duration, false);
     }
 
+    /// Record a tool call answered from the tool result cache.
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a cacheable tool call that had to run the tool.
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a request stopped for running past its timeout.
+    ///
+    /// `operation` is the method, or `tools/call:<tool>` for a tool call.
+    pub fn record_timeout(&self, operation: &str) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+        self.increment_method_counter(&self.operation_timeouts, operation);
+    }
+
     /// Get a snapshot of current metrics.
     #[must_use]
     pub fn snapshot(&self) -> MetricsSnapshot {
-        let method_counts = self
-            .method_counts
-            .read()
-            .unwrap_or_else(std::sync::PoisonError::into_inner);
-        let method_errors = self
-            .method_errors
-            .read()
-            .unwrap_or_else(std::sync::PoisonError::into_inner);
-        let method_latency = self
-            .method_latency_us
+        let timed_out = self
+            .operation_timeouts
             .read()
-            .unwrap_or_else(std::sync::PoisonError::into_inner);
-
-        let per_method: HashMap<String, MethodStats> = method_counts
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
             .iter()
-            .map(|(method, count)| {
-                let requests = count.load(Ordering::Relaxed);
-                let errors = method_errors
-                    .get(method)
-                    .map_or(0, |c| c.load(Ordering::Relaxed));
-                let latency_us = method_latency
-                    .get(method)
-                    .map_or(0, |c| c.load(Ordering::Relaxed));
-
-                (
-                    method.clone(),
-                    MethodStats {
-                        requests,
-                        errors,
-                        avg_latency_ms: if requests > 0 {
-                            (latency_us as f64 / requests as f64) / 1000.0
-                        } else {
-                            0.0
-                        },
-                    },
-                )
-            })
+            .map(|(operation, count)| (operation.clone(), count.load(Ordering::Relaxed)))
             .collect();
-
-        let total = self.total_requests.load(Ordering::Relaxed);
-        let total_latency = self.total_latency_us.load(Ordering::Relaxed);
+        let latency = self.latency.stats(0);
 
         MetricsSnapshot {
-            total_requests: total,
+            total_requests: self.total_requests.load(Ordering::Relaxed),
             successful_requests: self.successful_requests.load(Ordering::Relaxed),
             failed_requests: self.failed_requests.load(Ordering::Relaxed),
-            avg_latency_ms: if total > 0 {
-                (total_latency as f64 / total as f64) / 1000.0
-            } else {
-                0.0
-            },
-            per_method,
+            avg_latency_ms: latency.avg_latency_ms,
+            p50_latency_ms: latency.p50_ms,
+            p95_latency_ms: latency.p95_ms,
+            p99_latency_ms: latency.p99_ms,
+            per_method: Self::all_stats(&self.methods),
+            per_tool: Self::all_stats(&self.tools),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            timed_out,
         }
     }
 
@@ -156,17 +290,46 @@ impl ServerMetrics {
         self.total_requests.store(0, Ordering::Relaxed);
         self.successful_requests.store(0, Ordering::Relaxed);
         self.failed_requests.store(0, Ordering::Relaxed);
-        self.total_latency_us.store(0, Ordering::Relaxed);
+        self.cache_hits.store(0, Ordering::Relaxed);
+        self.cache_misses.store(0, Ordering::Relaxed);
+        self.timeouts.store(0, Ordering::Relaxed);
+        for bucket in &self.latency.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.latency.count.store(0, Ordering::Relaxed);
+        self.latency.sum_us.store(0, Ordering::Relaxed);
+        self.latency.max_us.store(0, Ordering::Relaxed);
 
-        if let Ok(mut counts) = self.method_counts.write() {
-            counts.clear();
+        if let Ok(mut methods) = self.methods.write() {
+            methods.clear();
+        }
+        if let Ok(mut tools) = self.tools.write() {
+            tools.clear();
         }
-        if let Ok(mut errors) = self.method_errors.write() {
-            errors.clear();
+        if let Ok(mut timeouts) = self.operation_timeouts.write() {
+            timeouts.clear();
         }
-        if let Ok(mut latency) = self.method_latency_us.write() {
-            latency.clear();
+    }
+
+    /// The series for `key`, created on first use.
+    fn series(map: &SeriesMap, key: &str) -> Arc<Series> {
+        if let Ok(series) = map.read() {
+            if let Some(series) = series.get(key) {
+                return Arc::clone(series);
+            }
         }
+        let mut series = map
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        Arc::clone(series.entry(key.to_string()).or_default())
+    }
+
+    fn all_stats(map: &SeriesMap) -> HashMap<String, MethodStats> {
+        map.read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|(key, series)| (key.clone(), series.stats()))
+            .collect()
     }
 
     fn increment_method_counter(&self, map: &RwLock<HashMap<String, AtomicU64>>, method: &str) {
@@ -186,28 +349,14 @@ impl ServerMetrics {
                 .fetch_add(1, Ordering::Relaxed);
         }
     }
-
-    fn add_method_latency(&self, method: &str, latency_us: u64) {
-        // Try to add to existing counter
-        if let Ok(latencies) = self.method_latency_us.read() {
-            if let Some(counter) = latencies.get(method) {
-                counter.fetch_add(latency_us, Ordering::Relaxed);
-                return;
-            }
-        }
-
-        // Counter doesn't exist, need to create it
-        if let Ok(mut latencies) = self.method_latency_us.write() {
-            latencies
-                .entry(method.to_string())
-                .or_insert_with(|| AtomicU64::new(0))
-                .fetch_add(latency_us, Ordering::Relaxed);
-        }
-    }
 }
 
 /// A point-in-time snapshot of server metrics.
-#[derive(Debug, Clone)]
+///
+/// Serializes (camelCase) to the JSON served as the [`METRICS_RESOURCE_URI`]
+/// resource.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MetricsSnapshot {
     /// Total requests received.
     pub total_requests: u64,
@@ -217,8 +366,25 @@ pub struct MetricsSnapshot {
     pub failed_requests: u64,
     /// Average request latency in milliseconds.
     pub avg_latency_ms: f64,
+    /// Estimated median request latency in milliseconds.
+    pub p50_latency_ms: f64,
+    /// Estimated 95th percentile request latency in milliseconds.
+    pub p95_latency_ms: f64,
+    /// Estimated 99th percentile request latency in milliseconds.
+    pub p99_latency_ms: f64,
     /// Per-method statistics.
     pub per_method: HashMap<String, MethodStats>,
+    /// Per-tool statistics for `tools/call`, keyed by tool name.
+    pub per_tool: HashMap<String, MethodStats>,
+    /// Tool calls answered from the tool result cache.
+    pub cache_hits: u64,
+    /// Cacheable tool calls that ran the tool.
+    pub cache_misses: u64,
+    /// Requests stopped for running past their timeout.
+    pub timeouts: u64,
+    /// Timeouts per operation: the method, or `tools/call:<tool>` for a tool
+    /// call.
+    pub timed_out: HashMap<String, u64>,
 }
 
 impl MetricsSnapshot {
@@ -238,12 +404,29 @@ impl MetricsSnapshot {
         1.0 - self.error_rate()
     }
 
+    /// Fraction of cacheable tool calls answered from the cache (0.0 to 1.0).
+    #[must_use]
+    pub fn cache_hit_rate(&self) -> f64 {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / lookups as f64
+        }
+    }
+
     /// Get statistics for a specific method.
     #[must_use]
     pub fn method(&self, name: &str) -> Option<&MethodStats> {
         self.per_method.get(name)
     }
 
+    /// Get statistics for a specific tool.
+    #[must_use]
+    pub fn tool(&self, name: &str) -> Option<&MethodStats> {
+        self.per_tool.get(name)
+    }
+
     /// Get the most called methods, sorted by request count.
     #[must_use]
     pub fn top_methods(&self, limit: usize) -> Vec<(&String, &MethodStats)> {
@@ -273,8 +456,12 @@ impl MetricsSnapshot {
     }
 }
 
-/// Statistics for a single MCP method.
-#[derive(Debug, Clone)]
+/// Statistics for a single MCP method or tool.
+///
+/// Percentiles are estimated from a fixed-bucket histogram, so they are
+/// accurate to within the bucket that holds them.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MethodStats {
     /// Total requests for this method.
     pub requests: u64,
@@ -282,6 +469,14 @@ pub struct MethodStats {
     pub errors: u64,
     /// Average latency in milliseconds.
     pub avg_latency_ms: f64,
+    /// Estimated median latency in milliseconds.
+    pub p50_ms: f64,
+    /// Estimated 95th percentile latency in milliseconds.
+    pub p95_ms: f64,
+    /// Estimated 99th percentile latency in milliseconds.
+    pub p99_ms: f64,
+    /// Slowest request in milliseconds.
+    pub max_ms: f64,
 }
 
 impl MethodStats {
@@ -302,9 +497,200 @@ impl MethodStats {
     }
 }
 
+/// Middleware recording every request into a [`ServerMetrics`].
+///
+/// Each request is recorded under its method; `tools/call` is also recorded
+/// under the tool's name, counting error results (`isError: true`) as
+/// failures. With [`expose_resource`](Self::expose_resource) the layer also
+/// serves a JSON snapshot as the [`METRICS_RESOURCE_URI`] resource, listed
+/// after the server's own resources, and advertises the `resources`
+/// capability if the server does not.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use mcpkit_server::metrics::{MetricsLayer, ServerMetrics};
+///
+/// let metrics = Arc::new(ServerMetrics::new());
+/// let layer = MetricsLayer::new(Arc::clone(&metrics)).expose_resource();
+/// // server.layer(layer).serve(transport).await?;
+/// # let _ = layer;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetricsLayer {
+    metrics: Arc<ServerMetrics>,
+    resource: bool,
+}
+
+impl MetricsLayer {
+    /// Create a layer recording into `metrics`.
+    #[must_use]
+    pub const fn new(metrics: Arc<ServerMetrics>) -> Self {
+        Self {
+            metrics,
+            resource: false,
+        }
+    }
+
+    /// Serve a snapshot of the metrics as the [`METRICS_RESOURCE_URI`]
+    /// resource.
+    #[must_use]
+    pub const fn expose_resource(mut self) -> Self {
+        self.resource = true;
+        self
+    }
+
+    /// The metrics this layer records into.
+    #[must_use]
+    pub const fn metrics(&self) -> &Arc<ServerMetrics> {
+        &self.metrics
+    }
+
+    /// The resource the snapshot is listed as.
+    #[must_use]
+    pub fn resource() -> Resource {
+        Resource::new(METRICS_RESOURCE_URI, "server-metrics")
+            .title("Server metrics")
+            .description("Request counts, error rates, and latency percentiles per method and tool")
+            .mime_type("application/json")
+    }
+
+    /// Answer a `resources/read` of the metrics resource.
+    pub(crate) fn read(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+    ) -> Option<Result<Value, McpError>> {
+        let uri = params.and_then(|p| p.get("uri")).and_then(Value::as_str);
+        if !self.resource || method != "resources/read" || uri != Some(METRICS_RESOURCE_URI) {
+            return None;
+        }
+        let contents = ResourceContents::json(METRICS_RESOURCE_URI, &self.metrics.snapshot())
+            .map_err(|e| McpError::internal(e.to_string()));
+        Some(contents.map(|contents| serde_json::json!({ "contents": [contents] })))
+    }
+
+    /// Add the metrics resource to the last page of a `resources/list`
+    /// result, answering the list itself when the server has no resources.
+    pub(crate) fn list(
+        &self,
+        method: &str,
+        result: Result<Value, McpError>,
+    ) -> Result<Value, McpError> {
+        if !self.resource || method != "resources/list" {
+            return result;
+        }
+        let resource = serde_json::to_value(Self::resource()).unwrap_or_default();
+        match result {
+            Ok(mut value) => {
+                let last_page = value.get("nextCursor").is_none_or(Value::is_null);
+                if let Some(resources) = value.get_mut("resources").and_then(Value::as_array_mut) {
+                    if last_page {
+                        resources.push(resource);
+                    }
+                }
+                Ok(value)
+            }
+            Err(e) if e.code() == codes::METHOD_NOT_FOUND => {
+                Ok(serde_json::json!({ "resources": [resource] }))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Record a finished request; `result` is `None` for a JSON-RPC error.
+    pub(crate) fn record(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        result: Option<&Value>,
+        elapsed: Duration,
+    ) {
+        self.metrics
+            .record_request(method, elapsed, result.is_some());
+        if method != "tools/call" {
+            return;
+        }
+        if let Some(tool) = params.and_then(|p| p.get("name")).and_then(Value::as_str) {
+            let tool_error = result
+                .and_then(|r| r.get("isError"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            let success = result.is_some() && !tool_error;
+            self.metrics.record_tool_call(tool, elapsed, success);
+        }
+    }
+}
+
+impl McpMiddleware for MetricsLayer {
+    async fn handle<R: RequestRouter>(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+        next: Next<'_, R>,
+    ) -> Result<Value, McpError> {
+        let started = Instant::now();
+        let result = match self.read(method, params) {
+            Some(result) => result,
+            None => next.run(method, params, ctx).await,
+        };
+        let result = self.list(method, result);
+        self.record(method, params, result.as_ref().ok(), started.elapsed());
+        result
+    }
+
+    fn capabilities(&self, caps: ServerCapabilities) -> ServerCapabilities {
+        if self.resource && !caps.has_resources() {
+            caps.with_resources()
+        } else {
+            caps
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::context::{ContextData, NoOpPeer};
+    use crate::middleware::{HasCapabilities, Layered};
+    use mcpkit_core::capability::{ClientCapabilities, ServerInfo};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use serde_json::json;
+
+    /// Fails `tools/call` for `broken`, returns an error result for
+    /// `failing`, and has no resources.
+    struct Echo;
+
+    impl RequestRouter for Echo {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("echo", "1.0.0")
+        }
+
+        async fn route(
+            &self,
+            method: &str,
+            params: Option<&Value>,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            if method.starts_with("resources/") {
+                return Err(McpError::method_not_found(method));
+            }
+            match params.and_then(|p| p.get("name")).and_then(Value::as_str) {
+                Some("broken") => Err(McpError::internal("boom")),
+                Some("failing") => Ok(json!({ "content": [], "isError": true })),
+                _ => Ok(json!({ "content": [] })),
+            }
+        }
+    }
+
+    impl HasCapabilities for Echo {
+        fn server_capabilities(&self) -> ServerCapabilities {
+            ServerCapabilities::new().with_tools()
+        }
+    }
 
     #[test]
     fn test_basic_metrics() {
@@ -352,6 +738,42 @@ mod tests {
         assert_eq!(metrics.snapshot().total_requests, 0);
     }
 
+    #[test]
+    fn test_cache_counters() {
+        let metrics = ServerMetrics::new();
+        assert!(metrics.snapshot().cache_hit_rate().abs() < f64::EPSILON);
+
+        metrics.record_cache_miss();
+        metrics.record_cache_hit();
+        metrics.record_cache_hit();
+        metrics.record_cache_hit();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.cache_hits, 3);
+        assert_eq!(snapshot.cache_misses, 1);
+        assert!((snapshot.cache_hit_rate() - 0.75).abs() < f64::EPSILON);
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot().cache_hits, 0);
+    }
+
+    #[test]
+    fn test_timeout_counters() {
+        let metrics = ServerMetrics::new();
+        metrics.record_timeout("tools/call:slow");
+        metrics.record_timeout("tools/call:slow");
+        metrics.record_timeout("resources/read");
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.timeouts, 3);
+        assert_eq!(snapshot.timed_out.get("tools/call:slow"), Some(&2));
+        assert_eq!(snapshot.timed_out.get("resources/read"), Some(&1));
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot().timeouts, 0);
+        assert!(metrics.snapshot().timed_out.is_empty());
+    }
+
     #[test]
     fn test_top_methods() {
         let metrics = ServerMetrics::new();
@@ -373,4 +795,100 @@ mod tests {
         assert_eq!(top[0].0, "tools/call");
         assert_eq!(top[1].0, "resources/read");
     }
+
+    #[test]
+    fn test_latency_percentiles() -> Result<(), Box<dyn std::error::Error>> {
+        let metrics = ServerMetrics::new();
+        for ms in 1..=100 {
+            metrics.record_success("tools/call", Duration::from_millis(ms));
+        }
+
+        let snapshot = metrics.snapshot();
+        let stats = snapshot.method("tools/call").ok_or("Method not found")?;
+        assert!((stats.max_ms - 100.0).abs() < f64::EPSILON);
+        // Bucket interpolation keeps estimates within the bucket's range.
+        assert!(
+            (25.0..=50.0).contains(&stats.p50_ms),
+            "p50 {}",
+            stats.p50_ms
+        );
+        assert!(
+            (50.0..=100.0).contains(&stats.p95_ms),
+            "p95 {}",
+            stats.p95_ms
+        );
+        assert!(stats.p99_ms >= stats.p95_ms && stats.p99_ms <= 100.0);
+        assert!((snapshot.p99_latency_ms - stats.p99_ms).abs() < f64::EPSILON);
+        Ok(())
+    }
+
+    #[test]
+    fn test_per_tool_stats() -> Result<(), Box<dyn std::error::Error>> {
+        let metrics = ServerMetrics::new();
+        metrics.record_tool_call("search", Duration::from_millis(10), true);
+        metrics.record_tool_call("search", Duration::from_millis(30), false);
+        metrics.record_tool_call("fetch", Duration::from_millis(5), true);
+
+        let snapshot = metrics.snapshot();
+        let search = snapshot.tool("search").ok_or("Tool not found")?;
+        assert_eq!(search.requests, 2);
+        assert_eq!(search.errors, 1);
+        assert!((search.error_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(snapshot.per_tool.len(), 2);
+        assert_eq!(
+            snapshot.total_requests, 0,
+            "tool calls are recorded separately"
+        );
+
+        metrics.reset();
+        assert!(metrics.snapshot().per_tool.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn layer_records_requests_and_serves_snapshot() {
+        let metrics = Arc::new(ServerMetrics::new());
+        let router = Layered::new(
+            Echo,
+            MetricsLayer::new(Arc::clone(&metrics)).expose_resource(),
+        );
+        assert!(router.server_capabilities().has_resources());
+
+        let data = ContextData::new(
+            RequestId::Number(1),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        );
+        let peer = NoOpPeer;
+        let ctx = data.to_context(&peer);
+        for name in ["search", "search", "failing", "broken"] {
+            let _ = router
+                .route("tools/call", Some(&json!({ "name": name })), &ctx)
+                .await;
+        }
+
+        let list = router.route("resources/list", None, &ctx).await.unwrap();
+        assert_eq!(list["resources"][0]["uri"], METRICS_RESOURCE_URI);
+
+        let read = json!({ "uri": METRICS_RESOURCE_URI });
+        let result = router
+            .route("resources/read", Some(&read), &ctx)
+            .await
+            .unwrap();
+        let text = result["contents"][0]["text"].as_str().unwrap();
+        let snapshot: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(snapshot["perMethod"]["tools/call"]["requests"], 4);
+        assert_eq!(snapshot["perMethod"]["tools/call"]["errors"], 1);
+        assert_eq!(snapshot["perTool"]["search"]["errors"], 0);
+        assert_eq!(snapshot["perTool"]["failing"]["errors"], 1);
+        assert_eq!(snapshot["perTool"]["broken"]["errors"], 1);
+        assert!(snapshot["perTool"]["search"]["p95Ms"].is_number());
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            snapshot.method("resources/list").map(|s| s.requests),
+            Some(1)
+        );
+    }
 }
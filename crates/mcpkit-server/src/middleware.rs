@@ -0,0 +1,471 @@
+//! Request-level middleware for MCP servers.
+//!
+//! Transport middleware (`mcpkit_transport::middleware`) sees raw frames.
+//! The middleware here sees routed JSON-RPC requests instead: the method
+//! name, its params, and the request [`Context`]. That is the right level for
+//! auth checks, per-method rate limits, request rewriting, and audit logging.
+//!
+//! An [`McpMiddleware`] wraps a [`RequestRouter`]. It can inspect a request,
+//! reject it, rewrite it, or pass it on through [`Next::run`] and post-process
+//! the result. Middleware is applied with [`Server::layer`](crate::Server::layer)
+//! (or [`Layered::layer`] to stack more); the first layer applied is the
+//! innermost.
+//!
+//! Task-augmented `tools/call` requests also pass through middleware (as a
+//! `tools/call` with the tool `name` and `arguments`), so a check on
+//! `tools/call` cannot be bypassed by asking for task execution.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_core::error::McpError;
+//! use mcpkit_server::middleware::{McpMiddleware, Next};
+//! use mcpkit_server::{Context, RequestRouter};
+//! use serde_json::Value;
+//!
+//! /// Reject calls to tools whose names start with `admin_`.
+//! #[derive(Clone)]
+//! struct DenyAdmin;
+//!
+//! impl McpMiddleware for DenyAdmin {
+//!     async fn handle<R: RequestRouter>(
+//!         &self,
+//!         method: &str,
+//!         params: Option<&Value>,
+//!         ctx: &Context<'_>,
+//!         next: Next<'_, R>,
+//!     ) -> Result<Value, McpError> {
+//!         let tool = params.and_then(|p| p.get("name")).and_then(Value::as_str);
+//!         if method == "tools/call" && tool.is_some_and(|t| t.starts_with("admin_")) {
+//!             return Err(McpError::invalid_request("tool not permitted"));
+//!         }
+//!         next.run(method, params, ctx).await
+//!     }
+//! }
+//! ```
+
+use crate::builder::Server;
+use crate::context::Context;
+use crate::handler::ServerHandler;
+use crate::server::{RequestRouter, RuntimeConfig, ServerRuntime};
+use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{Object, TaskSupport};
+use mcpkit_transport::Transport;
+use serde_json::Value;
+
+/// Middleware around request routing.
+///
+/// Implementations must call [`Next::run`] to continue to the inner router,
+/// or return early to short-circuit the request.
+#[allow(async_fn_in_trait)]
+pub trait McpMiddleware: Send + Sync {
+    /// Handle a request, delegating to `next` to reach the inner router.
+    async fn handle<R: RequestRouter>(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+        next: Next<'_, R>,
+    ) -> Result<Value, McpError>;
+
+    /// The capabilities to advertise, given those of the wrapped router.
+    ///
+    /// Middleware that serves requests itself (e.g. an extra resource)
+    /// overrides this to declare them. The default returns `caps` unchanged.
+    fn capabilities(&self, caps: ServerCapabilities) -> ServerCapabilities {
+        caps
+    }
+}
+
+/// A transformation from one request router to another.
+///
+/// This mirrors `tower::Layer` for [`RequestRouter`]s. Every `Clone`
+/// [`McpMiddleware`] is a layer producing a [`Layered`] router.
+pub trait McpLayer<R> {
+    /// The router produced by this layer.
+    type Router: RequestRouter;
+
+    /// Wrap `inner` with this layer.
+    fn layer(&self, inner: R) -> Self::Router;
+}
+
+impl<R, M> McpLayer<R> for M
+where
+    R: RequestRouter,
+    M: McpMiddleware + Clone,
+{
+    type Router = Layered<R, M>;
+
+    fn layer(&self, inner: R) -> Self::Router {
+        Layered::new(inner, self.clone())
+    }
+}
+
+/// How [`Next::run`] reaches the inner router.
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    /// A regular request: [`RequestRouter::route`].
+    Route,
+    /// A task-augmented tool run: [`RequestRouter::call_tool_json`].
+    CallTool,
+}
+
+/// The rest of the middleware chain, ending at the wrapped router.
+#[derive(Debug)]
+pub struct Next<'a, R> {
+    inner: &'a R,
+    target: Target,
+}
+
+impl<R: RequestRouter> Next<'_, R> {
+    /// Pass the (possibly rewritten) request on to the inner router.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever error the inner router produces.
+    pub async fn run(
+        self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+    ) -> Result<Value, McpError> {
+        match self.target {
+            Target::CallTool if method == "tools/call" => {
+                let (name, args) = tool_call_parts(params)?;
+                self.inner.call_tool_json(&name, args, ctx).await
+            }
+            _ => self.inner.route(method, params, ctx).await,
+        }
+    }
+}
+
+/// Split `tools/call` params into the tool name and its arguments.
+fn tool_call_parts(params: Option<&Value>) -> Result<(String, Object), McpError> {
+    let name = params
+        .and_then(|p| p.get("name"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| McpError::invalid_params("tools/call", "missing tool name"))?;
+    let args = params
+        .and_then(|p| p.get("arguments"))
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    Ok((name.to_string(), args))
+}
+
+/// A router wrapped in an [`McpMiddleware`].
+#[derive(Debug, Clone)]
+pub struct Layered<R, M> {
+    inner: R,
+    middleware: M,
+}
+
+impl<R, M> Layered<R, M> {
+    /// Wrap `inner` with `middleware`.
+    pub const fn new(inner: R, middleware: M) -> Self {
+        Self { inner, middleware }
+    }
+
+    /// The wrapped router.
+    pub const fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// The middleware.
+    pub const fn middleware(&self) -> &M {
+        &self.middleware
+    }
+
+    /// Apply another layer on top of this one.
+    pub fn layer<L: McpLayer<Self>>(self, layer: L) -> L::Router {
+        layer.layer(self)
+    }
+}
+
+impl<R, M> RequestRouter for Layered<R, M>
+where
+    R: RequestRouter,
+    M: McpMiddleware,
+{
+    fn server_info(&self) -> ServerInfo {
+        self.inner.server_info()
+    }
+
+    fn instructions(&self) -> Option<String> {
+        self.inner.instructions()
+    }
+
+    async fn route(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+    ) -> Result<Value, McpError> {
+        let next = Next {
+            inner: &self.inner,
+            target: Target::Route,
+        };
+        self.middleware.handle(method, params, ctx, next).await
+    }
+
+    async fn route_notification(&self, method: &str, params: Option<&Value>, ctx: &Context<'_>) {
+        self.inner.route_notification(method, params, ctx).await;
+    }
+
+    async fn tool_task_support(&self, name: &str, ctx: &Context<'_>) -> TaskSupport {
+        self.inner.tool_task_support(name, ctx).await
+    }
+
+    async fn call_tool_json(
+        &self,
+        name: &str,
+        args: Object,
+        ctx: &Context<'_>,
+    ) -> Result<Value, McpError> {
+        let params = serde_json::json!({ "name": name, "arguments": args });
+        let next = Next {
+            inner: &self.inner,
+            target: Target::CallTool,
+        };
+        self.middleware
+            .handle("tools/call", Some(&params), ctx, next)
+            .await
+    }
+}
+
+/// A router that can report the capabilities it advertises at `initialize`.
+///
+/// Implemented by [`Server`] and by every [`Layered`] router, so a server
+/// stays runnable after middleware is applied.
+pub trait HasCapabilities {
+    /// The capabilities advertised to clients.
+    fn server_capabilities(&self) -> ServerCapabilities;
+}
+
+impl<H: ServerHandler, T, R, P, K> HasCapabilities for Server<H, T, R, P, K> {
+    fn server_capabilities(&self) -> ServerCapabilities {
+        self.capabilities().clone()
+    }
+}
+
+impl<R: HasCapabilities, M: McpMiddleware> HasCapabilities for Layered<R, M> {
+    fn server_capabilities(&self) -> ServerCapabilities {
+        self.middleware
+            .capabilities(self.inner.server_capabilities())
+    }
+}
+
+impl<R, M> Layered<R, M>
+where
+    Self: RequestRouter + HasCapabilities + Send + Sync + 'static,
+{
+    /// Run this server over the given transport.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message loop fails.
+    pub async fn serve<Tr>(self, transport: Tr) -> Result<(), McpError>
+    where
+        Tr: Transport + 'static,
+        Tr::Error: Into<McpError>,
+    {
+        ServerRuntime::with_config(self, transport, RuntimeConfig::default())
+            .run()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{ContextData, NoOpPeer};
+    use mcpkit_core::capability::ClientCapabilities;
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use std::sync::{Arc, Mutex};
+
+    /// Echoes the method and params it was routed.
+    struct Echo;
+
+    impl RequestRouter for Echo {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("echo", "1.0.0")
+        }
+
+        async fn route(
+            &self,
+            method: &str,
+            params: Option<&Value>,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            Ok(serde_json::json!({ "method": method, "params": params }))
+        }
+
+        async fn call_tool_json(
+            &self,
+            name: &str,
+            args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            Ok(serde_json::json!({ "task_tool": name, "args": args }))
+        }
+    }
+
+    /// Records each method seen, tagged with its name.
+    #[derive(Clone)]
+    struct Audit(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl McpMiddleware for Audit {
+        async fn handle<R: RequestRouter>(
+            &self,
+            method: &str,
+            params: Option<&Value>,
+            ctx: &Context<'_>,
+            next: Next<'_, R>,
+        ) -> Result<Value, McpError> {
+            self.1.lock().unwrap().push(format!("{}:{method}", self.0));
+            next.run(method, params, ctx).await
+        }
+    }
+
+    /// Rejects `tools/call` for `secret`, and rewrites `ping` params.
+    #[derive(Clone)]
+    struct Guard;
+
+    impl McpMiddleware for Guard {
+        async fn handle<R: RequestRouter>(
+            &self,
+            method: &str,
+            params: Option<&Value>,
+            ctx: &Context<'_>,
+            next: Next<'_, R>,
+        ) -> Result<Value, McpError> {
+            let name = params.and_then(|p| p.get("name")).and_then(Value::as_str);
+            if method == "tools/call" && name == Some("secret") {
+                return Err(McpError::invalid_request("denied"));
+            }
+            if method == "ping" {
+                let rewritten = serde_json::json!({ "rewritten": true });
+                return next.run(method, Some(&rewritten), ctx).await;
+            }
+            next.run(method, params, ctx).await
+        }
+    }
+
+    fn ctx_data() -> ContextData {
+        ContextData::new(
+            RequestId::Number(1),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        )
+    }
+
+    #[tokio::test]
+    async fn layers_run_outermost_first() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let router =
+            Layered::new(Echo, Audit("inner", log.clone())).layer(Audit("outer", log.clone()));
+
+        let (data, peer) = (ctx_data(), NoOpPeer);
+        let ctx = data.to_context(&peer);
+        router.route("tools/list", None, &ctx).await.unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["outer:tools/list", "inner:tools/list"]
+        );
+    }
+
+    #[tokio::test]
+    async fn middleware_can_reject_and_rewrite() {
+        let router = Layered::new(Echo, Guard);
+        let (data, peer) = (ctx_data(), NoOpPeer);
+        let ctx = data.to_context(&peer);
+        let denied = router
+            .route(
+                "tools/call",
+                Some(&serde_json::json!({ "name": "secret" })),
+                &ctx,
+            )
+            .await;
+        assert!(denied.is_err());
+
+        let pong = router.route("ping", None, &ctx).await.unwrap();
+        assert_eq!(pong["params"]["rewritten"], true);
+    }
+
+    #[tokio::test]
+    async fn task_augmented_tool_calls_pass_through_middleware() {
+        let router = Layered::new(Echo, Guard);
+        let (data, peer) = (ctx_data(), NoOpPeer);
+        let ctx = data.to_context(&peer);
+        let denied = router.call_tool_json("secret", Object::new(), &ctx).await;
+        assert!(denied.is_err());
+
+        let mut args = Object::new();
+        args.insert("x".into(), 1.into());
+        let ran = router.call_tool_json("open", args, &ctx).await.unwrap();
+        assert_eq!(ran["task_tool"], "open");
+        assert_eq!(ran["args"]["x"], 1);
+    }
+
+    #[tokio::test]
+    async fn layered_server_runs_in_runtime() {
+        use crate::builder::ServerBuilder;
+        use crate::handler::ToolHandler;
+        use mcpkit_core::protocol::{Message, Request};
+        use mcpkit_core::types::{Tool, ToolOutput};
+        use mcpkit_transport::MemoryTransport;
+
+        struct H;
+        impl ServerHandler for H {
+            fn server_info(&self) -> ServerInfo {
+                ServerInfo::new("t", "1.0.0")
+            }
+        }
+        impl ToolHandler for H {
+            async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+                Ok(vec![Tool::new("open"), Tool::new("secret")])
+            }
+            async fn call_tool(
+                &self,
+                name: &str,
+                _args: Object,
+                _ctx: &Context<'_>,
+            ) -> Result<ToolOutput, McpError> {
+                Ok(ToolOutput::text(format!("ran {name}")))
+            }
+        }
+
+        let server = ServerBuilder::new(H).with_tools(H).build().layer(Guard);
+        let (client, server_tr) = MemoryTransport::pair();
+        let runtime = ServerRuntime::new(server, server_tr);
+        runtime.state().set_initialized();
+        let handle = tokio::spawn(async move { runtime.run().await });
+
+        let mut replies = Vec::new();
+        for (id, tool) in [(1, "open"), (2, "secret")] {
+            client
+                .send(Message::Request(Request::with_params(
+                    "tools/call",
+                    id,
+                    serde_json::json!({ "name": tool, "arguments": {} }),
+                )))
+                .await
+                .expect("send");
+            match client.recv().await.expect("recv") {
+                Some(Message::Response(resp)) => replies.push(resp),
+                other => panic!("expected a response, got {other:?}"),
+            }
+        }
+        assert_eq!(
+            replies[0].result.as_ref().unwrap()["content"][0]["text"],
+            "ran open"
+        );
+        assert!(replies[1].error.is_some(), "guard must reject `secret`");
+
+        drop(client);
+        let _ = handle.await;
+    }
+}
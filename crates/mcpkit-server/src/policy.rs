@@ -0,0 +1,926 @@
+//! Tool-level authorization for MCP servers.
+//!
+//! A [`ToolPolicy`] decides, before a tool runs, whether the caller may call
+//! it with the given arguments. It sees a [`SessionInfo`] (the verified user
+//! and their roles, plus the request context), the tool name, and the
+//! arguments, and returns a [`Decision`]. A denied call never reaches
+//! [`ToolHandler::call_tool`](crate::ToolHandler::call_tool); the client gets
+//! [`McpError::ToolAccessDenied`] (JSON-RPC code `-32011`).
+//!
+//! [`DeclarativePolicy`] covers the common cases without code: allow and deny
+//! lists of tool names (with `*` wildcards), required roles, and constraints
+//! on argument values. Implement [`ToolPolicy`] (or pass a closure) for
+//! anything else.
+//!
+//! A [`ToolAuthorizer`] evaluates a policy and audits denials: each one is
+//! logged at `warn` level on the `mcpkit::audit` tracing target and passed to
+//! any [`ToolAuthorizer::on_denial`] callbacks. For the
+//! [`ServerRuntime`](crate::ServerRuntime), apply a [`ToolPolicyLayer`] with
+//! [`Server::layer`](crate::Server::layer); the HTTP framework adapters take a
+//! `ToolAuthorizer` via `McpRouter::with_tool_authorizer`. Task-augmented
+//! calls are checked too. `tools/list` is not filtered.
+//!
+//! The user comes from the [`VerifiedUser`] request extension, which the HTTP
+//! adapters attach from the application's auth middleware. Roles are read from
+//! [`VerifiedUser::roles`]. Servers without per-request authentication (such
+//! as stdio servers) can give the authorizer a fixed user with
+//! [`ToolAuthorizer::with_user`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_core::auth::VerifiedUser;
+//! use mcpkit_server::policy::{ArgConstraint, DeclarativePolicy, SessionInfo, ToolPolicy};
+//! use serde_json::json;
+//!
+//! let policy = DeclarativePolicy::new()
+//!     .deny_tool("debug_*")
+//!     .require_role("admin_*", "admin")
+//!     .constrain(
+//!         "deploy",
+//!         ArgConstraint::one_of("env", ["staging", "dev"]).unless_role("release-manager"),
+//!     );
+//!
+//! let alice = VerifiedUser::new("alice").roles(["developer"]);
+//! let session = SessionInfo::for_user(&alice);
+//! assert!(policy.allow(&session, "deploy", &json!({ "env": "staging" })).is_allowed());
+//! assert!(!policy.allow(&session, "deploy", &json!({ "env": "prod" })).is_allowed());
+//! assert!(!policy.allow(&session, "admin_reset", &json!({})).is_allowed());
+//! assert!(!policy.allow(&session, "debug_dump", &json!({})).is_allowed());
+//! ```
+
+use crate::context::Context;
+use crate::extensions::HttpRequestInfo;
+use crate::middleware::{McpMiddleware, Next};
+use crate::server::RequestRouter;
+use mcpkit_core::auth::VerifiedUser;
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Tracing target for authorization audit events.
+pub const AUDIT_TARGET: &str = "mcpkit::audit";
+
+/// Arguments passed to policies for calls that carry none.
+static NO_ARGUMENTS: std::sync::LazyLock<Value> =
+    std::sync::LazyLock::new(|| Value::Object(serde_json::Map::new()));
+
+/// The outcome of evaluating a [`ToolPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// The call may proceed.
+    Allow,
+    /// The call is refused, for the given reason.
+    Deny(String),
+}
+
+impl Decision {
+    /// Refuse a call.
+    #[must_use]
+    pub fn deny(reason: impl Into<String>) -> Self {
+        Self::Deny(reason.into())
+    }
+
+    /// Whether the call may proceed.
+    #[must_use]
+    pub const fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allow)
+    }
+}
+
+/// Who is calling, as seen by a [`ToolPolicy`].
+#[derive(Clone, Copy, Default)]
+pub struct SessionInfo<'a> {
+    user: Option<&'a VerifiedUser>,
+    context: Option<&'a Context<'a>>,
+}
+
+impl<'a> SessionInfo<'a> {
+    /// A caller with no verified identity and no request context.
+    #[must_use]
+    pub const fn anonymous() -> Self {
+        Self {
+            user: None,
+            context: None,
+        }
+    }
+
+    /// A verified caller with no request context.
+    #[must_use]
+    pub const fn for_user(user: &'a VerifiedUser) -> Self {
+        Self {
+            user: Some(user),
+            context: None,
+        }
+    }
+
+    /// The caller of the request behind `ctx`, taken from its
+    /// [`VerifiedUser`] extension.
+    #[must_use]
+    pub fn from_context(ctx: &'a Context<'a>) -> Self {
+        Self {
+            user: ctx.extension::<VerifiedUser>(),
+            context: Some(ctx),
+        }
+    }
+
+    /// The verified user, if any.
+    #[must_use]
+    pub const fn user(&self) -> Option<&'a VerifiedUser> {
+        self.user
+    }
+
+    /// Whether the caller has a verified identity.
+    #[must_use]
+    pub const fn is_authenticated(&self) -> bool {
+        self.user.is_some()
+    }
+
+    /// The verified user's subject (`sub`), if any.
+    #[must_use]
+    pub fn subject(&self) -> Option<&'a str> {
+        self.user.map(|u| u.subject.as_str())
+    }
+
+    /// The roles granted to the caller (none when anonymous).
+    #[must_use]
+    pub fn roles(&self) -> &'a [String] {
+        self.user.map_or(&[], |u| u.roles.as_slice())
+    }
+
+    /// Whether the caller has been granted `role`.
+    #[must_use]
+    pub fn has_role(&self, role: &str) -> bool {
+        self.user.is_some_and(|u| u.has_role(role))
+    }
+
+    /// The request context, when evaluated for a live request.
+    #[must_use]
+    pub const fn context(&self) -> Option<&'a Context<'a>> {
+        self.context
+    }
+
+    /// The JSON-RPC id of the request being authorized.
+    #[must_use]
+    pub fn request_id(&self) -> Option<&'a RequestId> {
+        self.context.map(|c| c.request_id)
+    }
+
+    /// The HTTP request the call arrived on, if the adapter captured it.
+    #[must_use]
+    pub fn http_request(&self) -> Option<&'a HttpRequestInfo> {
+        self.context.and_then(Context::http_request)
+    }
+
+    /// A per-request value attached by the transport or framework adapter.
+    #[must_use]
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&'a T> {
+        self.context.and_then(Context::extension)
+    }
+}
+
+impl fmt::Debug for SessionInfo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionInfo")
+            .field("user", &self.user)
+            .field("request_id", &self.request_id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Decides whether a caller may call a tool with the given arguments.
+///
+/// Implemented for closures taking the same arguments as [`allow`](Self::allow).
+pub trait ToolPolicy: Send + Sync {
+    /// Decide on a call to `tool` with `args` (an object; empty when the call
+    /// carried no arguments).
+    fn allow(&self, session: &SessionInfo<'_>, tool: &str, args: &Value) -> Decision;
+}
+
+impl<F> ToolPolicy for F
+where
+    F: Fn(&SessionInfo<'_>, &str, &Value) -> Decision + Send + Sync,
+{
+    fn allow(&self, session: &SessionInfo<'_>, tool: &str, args: &Value) -> Decision {
+        self(session, tool, args)
+    }
+}
+
+impl<P: ToolPolicy + ?Sized> ToolPolicy for Arc<P> {
+    fn allow(&self, session: &SessionInfo<'_>, tool: &str, args: &Value) -> Decision {
+        (**self).allow(session, tool, args)
+    }
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    // No `*` at all: the whole name must have been consumed.
+    rest.is_empty()
+}
+
+/// What an [`ArgConstraint`] requires of an argument.
+#[derive(Debug, Clone, PartialEq)]
+enum Rule {
+    OneOf(Vec<Value>),
+    NoneOf(Vec<Value>),
+    StartsWith(Vec<String>),
+    AtMost(f64),
+    AtLeast(f64),
+    MaxLength(usize),
+    Absent,
+}
+
+/// A requirement on one argument of a tool call.
+///
+/// The argument is addressed by a JSON Pointer into the arguments object
+/// (`/options/mode`) or, for top-level arguments, by plain name (`mode`).
+/// Constraints other than [`absent`](Self::absent) pass when the argument is
+/// missing; use schema validation to make arguments required.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgConstraint {
+    pointer: String,
+    rule: Rule,
+    exempt_roles: Vec<String>,
+}
+
+impl ArgConstraint {
+    fn new(argument: &str, rule: Rule) -> Self {
+        let pointer = if argument.starts_with('/') {
+            argument.to_string()
+        } else {
+            format!("/{}", argument.replace('~', "~0").replace('/', "~1"))
+        };
+        Self {
+            pointer,
+            rule,
+            exempt_roles: Vec::new(),
+        }
+    }
+
+    /// The argument must equal one of `values`.
+    #[must_use]
+    pub fn one_of<I, V>(argument: &str, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Value>,
+    {
+        Self::new(
+            argument,
+            Rule::OneOf(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The argument must not equal any of `values`.
+    #[must_use]
+    pub fn none_of<I, V>(argument: &str, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Value>,
+    {
+        Self::new(
+            argument,
+            Rule::NoneOf(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The argument must be a string starting with one of `prefixes`.
+    ///
+    /// This is a plain string comparison: it does not resolve `..` or
+    /// symlinks, so canonicalize paths in the tool as well.
+    #[must_use]
+    pub fn starts_with<I, S>(argument: &str, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(
+            argument,
+            Rule::StartsWith(prefixes.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    /// The argument must be a number no greater than `max`.
+    #[must_use]
+    pub fn at_most(argument: &str, max: f64) -> Self {
+        Self::new(argument, Rule::AtMost(max))
+    }
+
+    /// The argument must be a number no less than `min`.
+    #[must_use]
+    pub fn at_least(argument: &str, min: f64) -> Self {
+        Self::new(argument, Rule::AtLeast(min))
+    }
+
+    /// The argument must be a string of at most `max` characters, or an
+    /// array of at most `max` items.
+    #[must_use]
+    pub fn max_length(argument: &str, max: usize) -> Self {
+        Self::new(argument, Rule::MaxLength(max))
+    }
+
+    /// The argument must not be given at all.
+    #[must_use]
+    pub fn absent(argument: &str) -> Self {
+        Self::new(argument, Rule::Absent)
+    }
+
+    /// Skip this constraint for callers with `role`.
+    #[must_use]
+    pub fn unless_role(mut self, role: impl Into<String>) -> Self {
+        self.exempt_roles.push(role.into());
+        self
+    }
+
+    /// Check the constraint, returning why it failed.
+    fn check(&self, session: &SessionInfo<'_>, args: &Value) -> Result<(), String> {
+        if self.exempt_roles.iter().any(|r| session.has_role(r)) {
+            return Ok(());
+        }
+        let pointer = &self.pointer;
+        let Some(value) = args.pointer(pointer) else {
+            return Ok(());
+        };
+        let ok = match &self.rule {
+            Rule::OneOf(allowed) => allowed.contains(value),
+            Rule::NoneOf(denied) => !denied.contains(value),
+            Rule::StartsWith(prefixes) => value
+                .as_str()
+                .is_some_and(|s| prefixes.iter().any(|p| s.starts_with(p.as_str()))),
+            Rule::AtMost(max) => value.as_f64().is_some_and(|n| n <= *max),
+            Rule::AtLeast(min) => value.as_f64().is_some_and(|n| n >= *min),
+            Rule::MaxLength(max) => match value {
+                Value::String(s) => s.chars().count() <= *max,
+                Value::Array(items) => items.len() <= *max,
+                _ => false,
+            },
+            Rule::Absent => false,
+        };
+        if ok {
+            return Ok(());
+        }
+        Err(match &self.rule {
+            Rule::OneOf(allowed) => format!("argument '{pointer}' must be one of {allowed:?}"),
+            Rule::NoneOf(_) => format!("argument '{pointer}' has a disallowed value"),
+            Rule::StartsWith(prefixes) => {
+                format!("argument '{pointer}' must start with one of {prefixes:?}")
+            }
+            Rule::AtMost(max) => format!("argument '{pointer}' must be at most {max}"),
+            Rule::AtLeast(min) => format!("argument '{pointer}' must be at least {min}"),
+            Rule::MaxLength(max) => format!("argument '{pointer}' must be at most {max} long"),
+            Rule::Absent => format!("argument '{pointer}' is not permitted"),
+        })
+    }
+}
+
+/// A [`ToolPolicy`] built from rules.
+///
+/// Tool names in rules may use `*` wildcards. A call is checked in this order,
+/// and the first rule it fails denies it:
+///
+/// 1. the deny list,
+/// 2. the allow list (when non-empty, only listed tools may be called),
+/// 3. [`require_authenticated`](Self::require_authenticated),
+/// 4. role requirements matching the tool (all must be met),
+/// 5. argument constraints matching the tool (all must pass).
+#[derive(Debug, Clone, Default)]
+pub struct DeclarativePolicy {
+    allow: Vec<String>,
+    deny: Vec<String>,
+    authenticated: bool,
+    roles: Vec<(String, Vec<String>)>,
+    constraints: Vec<(String, ArgConstraint)>,
+}
+
+impl DeclarativePolicy {
+    /// Create a policy that allows every call.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add tools to the allow list. Once any are listed, calls to unlisted
+    /// tools are denied.
+    #[must_use]
+    pub fn allow_tool(mut self, tool: impl Into<String>) -> Self {
+        self.allow.push(tool.into());
+        self
+    }
+
+    /// Deny calls to matching tools, whatever else applies.
+    #[must_use]
+    pub fn deny_tool(mut self, tool: impl Into<String>) -> Self {
+        self.deny.push(tool.into());
+        self
+    }
+
+    /// Deny every call from callers without a verified identity.
+    #[must_use]
+    pub const fn require_authenticated(mut self) -> Self {
+        self.authenticated = true;
+        self
+    }
+
+    /// Only callers with `role` may call matching tools.
+    #[must_use]
+    pub fn require_role(self, tool: impl Into<String>, role: impl Into<String>) -> Self {
+        self.require_any_role(tool, [role])
+    }
+
+    /// Only callers with at least one of `roles` may call matching tools.
+    #[must_use]
+    pub fn require_any_role<I, S>(mut self, tool: impl Into<String>, roles: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.roles
+            .push((tool.into(), roles.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Check `constraint` on calls to matching tools.
+    #[must_use]
+    pub fn constrain(mut self, tool: impl Into<String>, constraint: ArgConstraint) -> Self {
+        self.constraints.push((tool.into(), constraint));
+        self
+    }
+}
+
+impl ToolPolicy for DeclarativePolicy {
+    fn allow(&self, session: &SessionInfo<'_>, tool: &str, args: &Value) -> Decision {
+        if self.deny.iter().any(|p| matches_pattern(p, tool)) {
+            return Decision::deny(format!("tool '{tool}' is denied"));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|p| matches_pattern(p, tool)) {
+            return Decision::deny(format!("tool '{tool}' is not allowed"));
+        }
+        if self.authenticated && !session.is_authenticated() {
+            return Decision::deny(format!("tool '{tool}' requires an authenticated user"));
+        }
+        for (pattern, roles) in &self.roles {
+            if matches_pattern(pattern, tool) && !roles.iter().any(|r| session.has_role(r)) {
+                return Decision::deny(match roles.as_slice() {
+                    [role] => format!("tool '{tool}' requires role '{role}'"),
+                    _ => format!("tool '{tool}' requires one of roles {roles:?}"),
+                });
+            }
+        }
+        for (pattern, constraint) in &self.constraints {
+            if matches_pattern(pattern, tool) {
+                if let Err(reason) = constraint.check(session, args) {
+                    return Decision::Deny(reason);
+                }
+            }
+        }
+        Decision::Allow
+    }
+}
+
+/// A denied tool call, as passed to [`ToolAuthorizer::on_denial`] callbacks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDenial {
+    /// The tool that was called.
+    pub tool: String,
+    /// Why the policy denied the call.
+    pub reason: String,
+    /// The caller's verified identity, if any.
+    pub user: Option<VerifiedUser>,
+    /// The JSON-RPC id of the denied request, if known.
+    pub request_id: Option<RequestId>,
+}
+
+type DenialHook = dyn Fn(&PolicyDenial) + Send + Sync;
+
+/// Evaluates a [`ToolPolicy`] for `tools/call` requests and audits denials.
+///
+/// Cloning is cheap and clones share the policy and callbacks.
+#[derive(Clone)]
+pub struct ToolAuthorizer {
+    policy: Arc<dyn ToolPolicy>,
+    user: Option<VerifiedUser>,
+    hooks: Vec<Arc<DenialHook>>,
+    disclose_reasons: bool,
+}
+
+impl ToolAuthorizer {
+    /// Create an authorizer for `policy`.
+    #[must_use]
+    pub fn new(policy: impl ToolPolicy + 'static) -> Self {
+        Self {
+            policy: Arc::new(policy),
+            user: None,
+            hooks: Vec::new(),
+            disclose_reasons: true,
+        }
+    }
+
+    /// Treat requests without a [`VerifiedUser`] extension as coming from
+    /// `user`, e.g. the local user of a stdio server.
+    #[must_use]
+    pub fn with_user(mut self, user: VerifiedUser) -> Self {
+        self.user = Some(user);
+        self
+    }
+
+    /// Call `hook` for every denied call, after it is logged.
+    #[must_use]
+    pub fn on_denial<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&PolicyDenial) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Leave the denial reason out of the error sent to the client. It is
+    /// still logged and passed to callbacks.
+    #[must_use]
+    pub const fn hide_reasons(mut self) -> Self {
+        self.disclose_reasons = false;
+        self
+    }
+
+    /// Authorize a request, if it is a `tools/call`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::ToolAccessDenied`] if the policy denies the call.
+    pub fn check(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+    ) -> Result<(), McpError> {
+        if method != "tools/call" {
+            return Ok(());
+        }
+        // A call without a name fails later, in routing.
+        let Some(tool) = params.and_then(|p| p.get("name")).and_then(Value::as_str) else {
+            return Ok(());
+        };
+        let args = params
+            .and_then(|p| p.get("arguments"))
+            .filter(|a| !a.is_null())
+            .unwrap_or(&NO_ARGUMENTS);
+
+        let mut session = SessionInfo::from_context(ctx);
+        if session.user.is_none() {
+            session.user = self.user.as_ref();
+        }
+        let Decision::Deny(reason) = self.policy.allow(&session, tool, args) else {
+            return Ok(());
+        };
+
+        warn!(
+            target: AUDIT_TARGET,
+            tool,
+            subject = session.subject().unwrap_or("anonymous"),
+            request_id = %ctx.request_id,
+            reason = %reason,
+            "Tool call denied by policy"
+        );
+        let denial = PolicyDenial {
+            tool: tool.to_string(),
+            reason,
+            user: session.user.cloned(),
+            request_id: Some(ctx.request_id.clone()),
+        };
+        for hook in &self.hooks {
+            hook(&denial);
+        }
+        let reason = self.disclose_reasons.then_some(denial.reason);
+        Err(McpError::tool_access_denied(denial.tool, reason))
+    }
+}
+
+impl fmt::Debug for ToolAuthorizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolAuthorizer")
+            .field("user", &self.user)
+            .field("hooks", &self.hooks.len())
+            .field("disclose_reasons", &self.disclose_reasons)
+            .finish_non_exhaustive()
+    }
+}
+
+/// [`McpMiddleware`] that refuses `tools/call` requests a [`ToolPolicy`] denies.
+#[derive(Debug, Clone)]
+pub struct ToolPolicyLayer {
+    authorizer: ToolAuthorizer,
+}
+
+impl ToolPolicyLayer {
+    /// Create a layer enforcing `policy`.
+    #[must_use]
+    pub fn new(policy: impl ToolPolicy + 'static) -> Self {
+        Self::from_authorizer(ToolAuthorizer::new(policy))
+    }
+
+    /// Create a layer from a configured authorizer.
+    #[must_use]
+    pub const fn from_authorizer(authorizer: ToolAuthorizer) -> Self {
+        Self { authorizer }
+    }
+
+    /// The underlying authorizer.
+    #[must_use]
+    pub const fn authorizer(&self) -> &ToolAuthorizer {
+        &self.authorizer
+    }
+}
+
+impl McpMiddleware for ToolPolicyLayer {
+    async fn handle<R: RequestRouter>(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+        next: Next<'_, R>,
+    ) -> Result<Value, McpError> {
+        self.authorizer.check(method, params, ctx)?;
+        next.run(method, params, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{ContextData, NoOpPeer};
+    use crate::extensions::Extensions;
+    use crate::middleware::Layered;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+    use mcpkit_core::error::JsonRpcError;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::Object;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Echoes the tool it was asked to call.
+    struct Echo;
+
+    impl RequestRouter for Echo {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("echo", "1.0.0")
+        }
+
+        async fn route(
+            &self,
+            method: &str,
+            params: Option<&Value>,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            Ok(json!({ "method": method, "params": params }))
+        }
+
+        async fn call_tool_json(
+            &self,
+            name: &str,
+            _args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<Value, McpError> {
+            Ok(json!({ "task_tool": name }))
+        }
+    }
+
+    fn ctx_data() -> ContextData {
+        ContextData::new(
+            RequestId::Number(7),
+            ClientCapabilities::default(),
+            ServerCapabilities::default(),
+            ProtocolVersion::LATEST,
+        )
+    }
+
+    fn call(tool: &str, args: &Value) -> Value {
+        json!({ "name": tool, "arguments": args })
+    }
+
+    #[test]
+    fn patterns() {
+        assert!(matches_pattern("search", "search"));
+        assert!(!matches_pattern("search", "search_all"));
+        assert!(matches_pattern("admin_*", "admin_reset"));
+        assert!(matches_pattern("*_delete", "file_delete"));
+        assert!(matches_pattern("fs_*_write", "fs_local_write"));
+        assert!(!matches_pattern("fs_*_write", "fs_local_read"));
+        assert!(matches_pattern("*", "anything"));
+    }
+
+    #[test]
+    fn allow_and_deny_lists() {
+        let policy = DeclarativePolicy::new()
+            .allow_tool("fs_*")
+            .allow_tool("search")
+            .deny_tool("fs_delete");
+        let session = SessionInfo::anonymous();
+        let args = json!({});
+        assert!(policy.allow(&session, "fs_read", &args).is_allowed());
+        assert!(policy.allow(&session, "search", &args).is_allowed());
+        assert_eq!(
+            policy.allow(&session, "fs_delete", &args),
+            Decision::deny("tool 'fs_delete' is denied")
+        );
+        assert!(!policy.allow(&session, "shell", &args).is_allowed());
+    }
+
+    #[test]
+    fn roles_and_authentication() {
+        let policy = DeclarativePolicy::new()
+            .require_role("admin_*", "admin")
+            .require_any_role("deploy", ["ops", "admin"]);
+        let alice = VerifiedUser::new("alice").roles(["ops"]);
+        let root = VerifiedUser::new("root").roles(["admin"]);
+        let args = json!({});
+
+        let as_alice = SessionInfo::for_user(&alice);
+        assert!(policy.allow(&as_alice, "deploy", &args).is_allowed());
+        assert!(!policy.allow(&as_alice, "admin_reset", &args).is_allowed());
+        let as_root = SessionInfo::for_user(&root);
+        assert!(policy.allow(&as_root, "admin_reset", &args).is_allowed());
+        assert!(
+            !policy
+                .allow(&SessionInfo::anonymous(), "deploy", &args)
+                .is_allowed()
+        );
+        // Tools without role requirements stay open to anonymous callers...
+        assert!(
+            policy
+                .allow(&SessionInfo::anonymous(), "search", &args)
+                .is_allowed()
+        );
+        // ...unless authentication is required.
+        let policy = policy.require_authenticated();
+        assert!(
+            !policy
+                .allow(&SessionInfo::anonymous(), "search", &args)
+                .is_allowed()
+        );
+        assert!(policy.allow(&as_alice, "search", &args).is_allowed());
+    }
+
+    #[test]
+    fn argument_constraints() {
+        let policy = DeclarativePolicy::new()
+            .constrain("query", ArgConstraint::at_most("/limit", 100.0))
+            .constrain("query", ArgConstraint::max_length("sql", 20))
+            .constrain(
+                "query",
+                ArgConstraint::absent("/options/raw").unless_role("dba"),
+            )
+            .constrain("read_*", ArgConstraint::starts_with("path", ["/srv/data/"]))
+            .constrain(
+                "read_*",
+                ArgConstraint::none_of("path", ["/srv/data/secrets"]),
+            );
+        let anon = SessionInfo::anonymous();
+
+        assert!(
+            policy
+                .allow(&anon, "query", &json!({ "sql": "select 1", "limit": 10 }))
+                .is_allowed()
+        );
+        // Missing arguments pass.
+        assert!(policy.allow(&anon, "query", &json!({})).is_allowed());
+        assert_eq!(
+            policy.allow(&anon, "query", &json!({ "limit": 1000 })),
+            Decision::deny("argument '/limit' must be at most 100")
+        );
+        assert!(
+            !policy
+                .allow(&anon, "query", &json!({ "sql": "x".repeat(21) }))
+                .is_allowed()
+        );
+        let raw = json!({ "options": { "raw": true } });
+        assert!(!policy.allow(&anon, "query", &raw).is_allowed());
+        let dba = VerifiedUser::new("dba").roles(["dba"]);
+        assert!(
+            policy
+                .allow(&SessionInfo::for_user(&dba), "query", &raw)
+                .is_allowed()
+        );
+
+        assert!(
+            policy
+                .allow(&anon, "read_file", &json!({ "path": "/srv/data/a.txt" }))
+                .is_allowed()
+        );
+        assert!(
+            !policy
+                .allow(&anon, "read_file", &json!({ "path": "/etc/passwd" }))
+                .is_allowed()
+        );
+        assert!(
+            !policy
+                .allow(&anon, "read_file", &json!({ "path": "/srv/data/secrets" }))
+                .is_allowed()
+        );
+        // A non-string value can't satisfy a prefix.
+        assert!(
+            !policy
+                .allow(&anon, "read_file", &json!({ "path": 42 }))
+                .is_allowed()
+        );
+    }
+
+    #[tokio::test]
+    async fn layer_denies_and_audits() {
+        let denials = Arc::new(Mutex::new(Vec::new()));
+        let seen = denials.clone();
+        let authorizer =
+            ToolAuthorizer::new(DeclarativePolicy::new().require_role("admin_*", "admin"))
+                .on_denial(move |d| seen.lock().unwrap().push(d.clone()));
+        let router = Layered::new(Echo, ToolPolicyLayer::from_authorizer(authorizer));
+
+        let data = ctx_data();
+        let peer = NoOpPeer;
+        let mut extensions = Extensions::new();
+        extensions.insert(VerifiedUser::new("alice").roles(["dev"]));
+        let ctx = data.to_context(&peer).with_extensions(&extensions);
+
+        let err = router
+            .route("tools/call", Some(&call("admin_reset", &json!({}))), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, McpError::ToolAccessDenied { tool, .. } if tool == "admin_reset"));
+        let rpc = JsonRpcError::from(&err);
+        assert_eq!(rpc.code, -32011);
+        assert_eq!(
+            rpc.data.unwrap()["reason"],
+            "tool 'admin_reset' requires role 'admin'"
+        );
+
+        // Task-augmented calls are checked too.
+        let err = router
+            .call_tool_json("admin_reset", Object::new(), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, McpError::ToolAccessDenied { .. }));
+
+        // Other tools and other methods pass through.
+        let ok = router
+            .route("tools/call", Some(&call("search", &json!({}))), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(ok["params"]["name"], "search");
+        assert!(router.route("tools/list", None, &ctx).await.is_ok());
+
+        let denials = denials.lock().unwrap();
+        assert_eq!(denials.len(), 2);
+        assert_eq!(denials[0].user.as_ref().unwrap().subject, "alice");
+        assert_eq!(denials[0].request_id, Some(RequestId::Number(7)));
+    }
+
+    #[tokio::test]
+    async fn fallback_user_and_hidden_reasons() {
+        let policy = |session: &SessionInfo<'_>, _tool: &str, args: &Value| {
+            if session.subject() == Some("local") && args.get("force").is_none() {
+                Decision::Allow
+            } else {
+                Decision::deny("no")
+            }
+        };
+        let authorizer = ToolAuthorizer::new(policy)
+            .with_user(VerifiedUser::new("local"))
+            .hide_reasons();
+        let layer = ToolPolicyLayer::from_authorizer(authorizer);
+        let router = Layered::new(Echo, layer);
+
+        let (data, peer) = (ctx_data(), NoOpPeer);
+        let ctx = data.to_context(&peer);
+        let ok = router
+            .route("tools/call", Some(&call("anything", &Value::Null)), &ctx)
+            .await;
+        assert!(ok.is_ok());
+
+        let err = router
+            .route(
+                "tools/call",
+                Some(&call("anything", &json!({ "force": true }))),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            McpError::ToolAccessDenied { reason: None, .. }
+        ));
+    }
+}
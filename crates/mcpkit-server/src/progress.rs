@@ -0,0 +1,254 @@
+//! Throttled progress reporting for long-running handlers.
+//!
+//! [`Context::progress`](crate::Context::progress) sends one notification per
+//! call and borrows the context, so it can't be used from a spawned task. A
+//! [`ProgressReporter`], obtained from
+//! [`Context::progress_reporter`](crate::Context::progress_reporter), owns
+//! what it needs: it can be cloned and moved into `tokio::spawn`, and it
+//! drops updates that arrive faster than its minimum interval or that don't
+//! advance the progress value (the spec requires progress to increase).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #[tool(description = "Index every file")]
+//! async fn index(&self, ctx: &Context<'_>) -> Result<String, McpError> {
+//!     let progress = ctx.progress_reporter();
+//!     let files = self.files();
+//!     for (i, file) in files.iter().enumerate() {
+//!         self.index_file(file).await?;
+//!         progress.report(i as f64 + 1.0, Some(files.len() as f64), None).await?;
+//!     }
+//!     Ok(format!("indexed {} files", files.len()))
+//! }
+//! ```
+
+use crate::context::Peer;
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::{Notification, ProgressToken};
+use mcpkit_core::types::notifications::ProgressNotificationParams;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default minimum time between two progress notifications.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Throttle {
+    last_sent: Option<Instant>,
+    last_progress: Option<f64>,
+}
+
+struct Inner {
+    peer: Arc<dyn Peer>,
+    token: ProgressToken,
+    min_interval: Duration,
+    throttle: Mutex<Throttle>,
+}
+
+/// A cloneable, `'static` handle for sending `notifications/progress`.
+///
+/// Clones share their throttle, so several tasks reporting on the same
+/// request together respect the minimum interval. When the request carried
+/// no progress token (or the peer can't be shared) the reporter is inactive
+/// and every report is a no-op.
+#[derive(Clone)]
+pub struct ProgressReporter {
+    inner: Option<Arc<Inner>>,
+}
+
+impl ProgressReporter {
+    /// Create a reporter that sends progress for `token` through `peer`.
+    #[must_use]
+    pub fn new(peer: Arc<dyn Peer>, token: ProgressToken) -> Self {
+        Self {
+            inner: Some(Arc::new(Inner {
+                peer,
+                token,
+                min_interval: DEFAULT_MIN_INTERVAL,
+                throttle: Mutex::new(Throttle::default()),
+            })),
+        }
+    }
+
+    /// Create a reporter that never sends anything.
+    #[must_use]
+    pub const fn inactive() -> Self {
+        Self { inner: None }
+    }
+
+    /// Set the minimum time between two notifications.
+    ///
+    /// Has no effect on clones made before the call.
+    #[must_use]
+    pub fn with_min_interval(self, min_interval: Duration) -> Self {
+        let Some(inner) = self.inner else {
+            return self;
+        };
+        let throttle = inner.throttle.lock().map(|t| *t).unwrap_or_default();
+        Self {
+            inner: Some(Arc::new(Inner {
+                peer: Arc::clone(&inner.peer),
+                token: inner.token.clone(),
+                min_interval,
+                throttle: Mutex::new(throttle),
+            })),
+        }
+    }
+
+    /// Whether reports reach the client.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// The progress token reports are tied to, if active.
+    #[must_use]
+    pub fn token(&self) -> Option<&ProgressToken> {
+        self.inner.as_ref().map(|inner| &inner.token)
+    }
+
+    /// Report `progress` out of `total` (if known), with an optional message.
+    ///
+    /// The update is dropped, returning `Ok(false)`, when it doesn't exceed
+    /// the last reported value or arrives within the minimum interval of the
+    /// previous notification. Reaching `total` is always sent. Returns
+    /// `Ok(true)` when a notification was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification could not be sent.
+    pub async fn report(
+        &self,
+        progress: f64,
+        total: Option<f64>,
+        message: Option<&str>,
+    ) -> Result<bool, McpError> {
+        let Some(inner) = &self.inner else {
+            return Ok(false);
+        };
+        let complete = total.is_some_and(|total| progress >= total);
+        if !inner.admit(progress, complete, Instant::now()) {
+            return Ok(false);
+        }
+
+        let params = ProgressNotificationParams {
+            total,
+            message: message.map(String::from),
+            ..ProgressNotificationParams::new(inner.token.clone(), progress)
+        };
+        let notification = Notification::with_params(
+            crate::router::notifications::PROGRESS,
+            serde_json::to_value(params)?,
+        );
+        inner.peer.notify(notification).await?;
+        Ok(true)
+    }
+}
+
+impl Inner {
+    /// Decide whether an update is sent, recording it if so.
+    fn admit(&self, progress: f64, complete: bool, now: Instant) -> bool {
+        let Ok(mut throttle) = self.throttle.lock() else {
+            return false;
+        };
+        if throttle.last_progress.is_some_and(|last| progress <= last) {
+            return false;
+        }
+        let too_soon = throttle
+            .last_sent
+            .is_some_and(|last| now.saturating_duration_since(last) < self.min_interval);
+        if too_soon && !complete {
+            return false;
+        }
+        throttle.last_sent = Some(now);
+        throttle.last_progress = Some(progress);
+        true
+    }
+}
+
+impl std::fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("token", &self.token())
+            .field(
+                "min_interval",
+                &self.inner.as_ref().map(|inner| inner.min_interval),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+
+    struct RecPeer(Arc<Mutex<Vec<Notification>>>);
+
+    impl Peer for RecPeer {
+        fn notify(
+            &self,
+            notification: Notification,
+        ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+            self.0.lock().unwrap().push(notification);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    fn reporter(min_interval: Duration) -> (ProgressReporter, Arc<Mutex<Vec<Notification>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let peer: Arc<dyn Peer> = Arc::new(RecPeer(Arc::clone(&seen)));
+        let reporter = ProgressReporter::new(peer, ProgressToken::String("op-1".to_string()))
+            .with_min_interval(min_interval);
+        (reporter, seen)
+    }
+
+    #[tokio::test]
+    async fn updates_are_throttled_but_completion_is_sent() {
+        let (reporter, seen) = reporter(Duration::from_secs(60));
+        assert!(
+            reporter
+                .report(1.0, Some(10.0), Some("start"))
+                .await
+                .unwrap()
+        );
+        assert!(!reporter.report(2.0, Some(10.0), None).await.unwrap());
+        assert!(
+            reporter
+                .report(10.0, Some(10.0), Some("done"))
+                .await
+                .unwrap()
+        );
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].method, "notifications/progress");
+        let params = seen[1].params.as_ref().unwrap();
+        assert_eq!(params["progressToken"], "op-1");
+        assert_eq!(params["progress"], 10.0);
+        assert_eq!(params["message"], "done");
+    }
+
+    #[tokio::test]
+    async fn progress_must_increase_and_clones_share_state() {
+        let (reporter, seen) = reporter(Duration::ZERO);
+        let spawned = reporter.clone();
+        tokio::spawn(async move { spawned.report(5.0, None, None).await })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!reporter.report(5.0, None, None).await.unwrap());
+        assert!(!reporter.report(3.0, None, None).await.unwrap());
+        assert!(reporter.report(6.0, None, None).await.unwrap());
+        assert_eq!(seen.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn inactive_reporter_is_a_no_op() {
+        let reporter = ProgressReporter::inactive();
+        assert!(!reporter.is_active());
+        assert!(!reporter.report(1.0, None, None).await.unwrap());
+    }
+}
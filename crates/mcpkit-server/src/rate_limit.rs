@@ -0,0 +1,417 @@
+//! Request rate limiting for MCP servers.
+//!
+//! A [`RateLimiter`] enforces [`Quota`]s per session: one for all requests,
+//! one per JSON-RPC method, and one per tool name for `tools/call`. A request
+//! must fit every quota that applies to it; when it doesn't, it is rejected
+//! with [`McpError::RateLimited`], which clients receive as a JSON-RPC error
+//! whose `data` carries `retry_after_ms`. `initialize` is never limited.
+//!
+//! Quotas use GCRA (the generic cell rate algorithm), which behaves like a
+//! token bucket but keeps a single timestamp per bucket.
+//!
+//! For the [`ServerRuntime`](crate::ServerRuntime), apply a [`RateLimitLayer`]
+//! with [`Server::layer`](crate::Server::layer). Each runtime serves one
+//! connection, so a fresh layer per connection limits per session, while one
+//! layer shared across connections limits them together. The HTTP framework
+//! adapters key buckets by `mcp-session-id` instead; see their
+//! `McpRouter::with_rate_limit`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::rate_limit::{Quota, RateLimitConfig, RateLimiter};
+//!
+//! let config = RateLimitConfig::new()
+//!     .per_session(Quota::per_minute(600))
+//!     .method("resources/read", Quota::per_second(20))
+//!     .tool("send_email", Quota::per_minute(5).with_burst(1));
+//!
+//! let limiter = RateLimiter::new(config);
+//! let params = serde_json::json!({ "name": "send_email", "arguments": {} });
+//! assert!(limiter.check("session-1", "tools/call", Some(&params)).is_ok());
+//! // Burst of 1: the second email within the interval is rejected...
+//! assert!(limiter.check("session-1", "tools/call", Some(&params)).is_err());
+//! // ...but other sessions have their own buckets.
+//! assert!(limiter.check("session-2", "tools/call", Some(&params)).is_ok());
+//! ```
+
+use crate::context::Context;
+use crate::middleware::{McpMiddleware, Next};
+use crate::server::RequestRouter;
+use mcpkit_core::error::McpError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Bucket count above which fully refilled buckets are dropped.
+const PRUNE_THRESHOLD: usize = 4096;
+
+/// An allowed request rate: `burst` requests at once, then one per interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    interval: Duration,
+    burst: u32,
+}
+
+impl Quota {
+    /// Allow `max_requests` per `window`, all of which may arrive at once.
+    ///
+    /// A `max_requests` of zero is treated as one.
+    #[must_use]
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        let max_requests = max_requests.max(1);
+        Self {
+            interval: window / max_requests,
+            burst: max_requests,
+        }
+    }
+
+    /// Allow `max_requests` per second.
+    #[must_use]
+    pub fn per_second(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(1))
+    }
+
+    /// Allow `max_requests` per minute.
+    #[must_use]
+    pub fn per_minute(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(60))
+    }
+
+    /// Set how many requests may arrive back to back (at least one).
+    #[must_use]
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// Time for one request's worth of capacity to refill.
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Maximum requests accepted back to back.
+    #[must_use]
+    pub const fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+/// Which quotas apply to which requests.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimitConfig {
+    session: Option<Quota>,
+    methods: HashMap<String, Quota>,
+    tools: HashMap<String, Quota>,
+}
+
+impl RateLimitConfig {
+    /// Create a configuration with no quotas.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit every request of a session, whatever its method.
+    #[must_use]
+    pub const fn per_session(mut self, quota: Quota) -> Self {
+        self.session = Some(quota);
+        self
+    }
+
+    /// Limit one JSON-RPC method per session (e.g. `resources/read`).
+    #[must_use]
+    pub fn method(mut self, method: impl Into<String>, quota: Quota) -> Self {
+        self.methods.insert(method.into(), quota);
+        self
+    }
+
+    /// Limit `tools/call` for one tool per session.
+    #[must_use]
+    pub fn tool(mut self, name: impl Into<String>, quota: Quota) -> Self {
+        self.tools.insert(name.into(), quota);
+        self
+    }
+
+    /// Whether no quota is configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.session.is_none() && self.methods.is_empty() && self.tools.is_empty()
+    }
+}
+
+/// A bucket: the session it belongs to and what it limits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Scope {
+    Session,
+    Method(String),
+    Tool(String),
+}
+
+/// Shared rate limiting state for a [`RateLimitConfig`].
+///
+/// Cloning is cheap and clones share buckets.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    config: Arc<RateLimitConfig>,
+    /// Theoretical arrival time of the next request, per bucket.
+    buckets: Arc<Mutex<HashMap<(String, Scope), Instant>>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter with empty buckets.
+    #[must_use]
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The configured quotas.
+    #[must_use]
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Admit a request from `session`, consuming capacity from every bucket
+    /// it falls under.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::RateLimited`] if any applicable quota is exhausted.
+    /// No capacity is consumed in that case.
+    pub fn check(
+        &self,
+        session: &str,
+        method: &str,
+        params: Option<&Value>,
+    ) -> Result<(), McpError> {
+        self.check_at(Instant::now(), session, method, params)
+    }
+
+    /// Drop the buckets of a session that has ended.
+    pub fn remove_session(&self, session: &str) {
+        if let Ok(mut buckets) = self.buckets.lock() {
+            buckets.retain(|(s, _), _| s != session);
+        }
+    }
+
+    fn check_at(
+        &self,
+        now: Instant,
+        session: &str,
+        method: &str,
+        params: Option<&Value>,
+    ) -> Result<(), McpError> {
+        if method == "initialize" {
+            return Ok(());
+        }
+        let tool = (method == "tools/call")
+            .then(|| params.and_then(|p| p.get("name")).and_then(Value::as_str))
+            .flatten();
+
+        let mut applicable = Vec::with_capacity(3);
+        if let Some(quota) = self.config.session {
+            applicable.push((Scope::Session, quota));
+        }
+        if let Some(quota) = self.config.methods.get(method) {
+            applicable.push((Scope::Method(method.to_string()), *quota));
+        }
+        if let Some((name, quota)) = tool.and_then(|t| self.config.tools.get_key_value(t)) {
+            applicable.push((Scope::Tool(name.clone()), *quota));
+        }
+        if applicable.is_empty() {
+            return Ok(());
+        }
+
+        let Ok(mut buckets) = self.buckets.lock() else {
+            return Ok(());
+        };
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, tat| *tat > now);
+        }
+
+        let mut updates = Vec::with_capacity(applicable.len());
+        let mut denied: Option<(Scope, Duration)> = None;
+        for (scope, quota) in applicable {
+            let key = (session.to_string(), scope);
+            let tat = buckets.get(&key).map_or(now, |tat| (*tat).max(now));
+            let next = tat + quota.interval;
+            let limit = quota.interval * quota.burst;
+            let ahead = next.saturating_duration_since(now);
+            if let Some(wait) = ahead.checked_sub(limit).filter(|w| !w.is_zero()) {
+                if denied.as_ref().is_none_or(|(_, w)| wait > *w) {
+                    denied = Some((key.1, wait));
+                }
+            } else {
+                updates.push((key, next));
+            }
+        }
+
+        if let Some((scope, retry_after)) = denied {
+            let limited = match scope {
+                Scope::Tool(name) => format!("tools/call:{name}"),
+                Scope::Session | Scope::Method(_) => method.to_string(),
+            };
+            return Err(McpError::rate_limited(limited, retry_after));
+        }
+        buckets.extend(updates);
+        Ok(())
+    }
+}
+
+/// [`McpMiddleware`] that rejects requests over a [`RateLimitConfig`].
+///
+/// All requests through one layer count against the same session, so create
+/// one layer per connection for per-session limits.
+#[derive(Debug, Clone)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+}
+
+impl RateLimitLayer {
+    /// Session key the layer uses for its buckets.
+    const SESSION: &'static str = "";
+
+    /// Create a layer with its own buckets.
+    #[must_use]
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self::from_limiter(RateLimiter::new(config))
+    }
+
+    /// Create a layer sharing an existing limiter's buckets.
+    #[must_use]
+    pub const fn from_limiter(limiter: RateLimiter) -> Self {
+        Self { limiter }
+    }
+
+    /// The underlying limiter.
+    #[must_use]
+    pub const fn limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+}
+
+impl McpMiddleware for RateLimitLayer {
+    async fn handle<R: RequestRouter>(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        ctx: &Context<'_>,
+        next: Next<'_, R>,
+    ) -> Result<Value, McpError> {
+        self.limiter.check(Self::SESSION, method, params)?;
+        next.run(method, params, ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::error::JsonRpcError;
+    use serde_json::json;
+
+    fn call(tool: &str) -> Value {
+        json!({ "name": tool, "arguments": {} })
+    }
+
+    #[test]
+    fn burst_then_refill() {
+        let limiter = RateLimiter::new(
+            RateLimitConfig::new().method("ping", Quota::per_second(10).with_burst(2)),
+        );
+        let start = Instant::now();
+        assert!(limiter.check_at(start, "s", "ping", None).is_ok());
+        assert!(limiter.check_at(start, "s", "ping", None).is_ok());
+        let err = limiter.check_at(start, "s", "ping", None).unwrap_err();
+        let McpError::RateLimited { retry_after, .. } = err else {
+            panic!("expected RateLimited, got {err:?}");
+        };
+        assert_eq!(retry_after, Duration::from_millis(100));
+
+        // One interval later there is room for one more.
+        let later = start + Duration::from_millis(100);
+        assert!(limiter.check_at(later, "s", "ping", None).is_ok());
+        assert!(limiter.check_at(later, "s", "ping", None).is_err());
+        // Unlimited methods are unaffected.
+        assert!(limiter.check_at(later, "s", "tools/list", None).is_ok());
+    }
+
+    #[test]
+    fn tool_quota_is_per_tool_and_per_session() {
+        let limiter =
+            RateLimiter::new(RateLimitConfig::new().tool("send_email", Quota::per_minute(1)));
+        let now = Instant::now();
+        let email = call("send_email");
+        assert!(
+            limiter
+                .check_at(now, "a", "tools/call", Some(&email))
+                .is_ok()
+        );
+        let err = limiter
+            .check_at(now, "a", "tools/call", Some(&email))
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            McpError::RateLimited { method, .. } if method == "tools/call:send_email"
+        ));
+
+        assert!(
+            limiter
+                .check_at(now, "a", "tools/call", Some(&call("search")))
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .check_at(now, "b", "tools/call", Some(&email))
+                .is_ok()
+        );
+
+        limiter.remove_session("a");
+        assert!(
+            limiter
+                .check_at(now, "a", "tools/call", Some(&email))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejected_requests_consume_nothing() {
+        let limiter = RateLimiter::new(
+            RateLimitConfig::new()
+                .per_session(Quota::per_second(2))
+                .tool("slow", Quota::per_minute(1)),
+        );
+        let now = Instant::now();
+        let slow = call("slow");
+        assert!(
+            limiter
+                .check_at(now, "s", "tools/call", Some(&slow))
+                .is_ok()
+        );
+        // Denied by the tool quota; the session quota keeps its last slot.
+        assert!(
+            limiter
+                .check_at(now, "s", "tools/call", Some(&slow))
+                .is_err()
+        );
+        assert!(limiter.check_at(now, "s", "ping", None).is_ok());
+        assert!(limiter.check_at(now, "s", "ping", None).is_err());
+        // `initialize` is never limited.
+        assert!(limiter.check_at(now, "s", "initialize", None).is_ok());
+    }
+
+    #[test]
+    fn rate_limited_error_carries_retry_after() {
+        let err = McpError::rate_limited("ping", Duration::from_millis(1500));
+        assert!(err.is_recoverable());
+        let rpc = JsonRpcError::from(&err);
+        assert_eq!(rpc.code, -32010);
+        let data = rpc.data.expect("data");
+        assert_eq!(data["method"], "ping");
+        assert_eq!(data["retry_after_ms"], 1500);
+    }
+}
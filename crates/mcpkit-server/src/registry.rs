@@ -0,0 +1,336 @@
+//! Tools registered and removed at runtime.
+//!
+//! `#[mcp_server]` tools are fixed at compile time. A [`DynamicToolRegistry`]
+//! holds tools that come and go while the server runs, such as plugins loaded
+//! from configuration. It is a [`ToolHandler`] and a [`Mountable`], so it can
+//! be served on its own or mounted next to macro-generated tools:
+//!
+//! ```rust
+//! use mcpkit_server::registry::DynamicToolRegistry;
+//! use mcpkit_server::{ServerBuilder, ServerHandler};
+//! use mcpkit_core::capability::ServerInfo;
+//! use mcpkit_core::types::{Tool, ToolOutput};
+//!
+//! struct Main;
+//! impl ServerHandler for Main {
+//!     fn server_info(&self) -> ServerInfo {
+//!         ServerInfo::new("main", "1.0.0")
+//!     }
+//! }
+//!
+//! let plugins = DynamicToolRegistry::new();
+//! let server = ServerBuilder::new(Main).mount("", plugins.clone()).build();
+//! assert_eq!(server.capabilities().tools.as_ref().unwrap().list_changed, Some(true));
+//!
+//! # async fn register(plugins: DynamicToolRegistry) {
+//! plugins
+//!     .register(Tool::new("echo"), |args| async move {
+//!         Ok(ToolOutput::text(serde_json::Value::Object(args).to_string()))
+//!     })
+//!     .await;
+//! assert!(plugins.contains("echo"));
+//! # }
+//! ```
+//!
+//! Mounting announces `tools.listChanged`. Every change is then sent as
+//! `notifications/tools/list_changed` through the notifiers attached with
+//! [`DynamicToolRegistry::notify_with`], one per connection (see
+//! [`ServerRuntime::notifier`](crate::server::ServerRuntime::notifier)).
+//!
+//! Unregistering a tool only affects calls that have not started: a call
+//! already running keeps its handler and completes normally, and later calls
+//! fail with "method not found".
+
+use crate::compose::{Mountable, Mounts};
+use crate::context::Context;
+use crate::handler::ToolHandler;
+use crate::server::ServerNotifier;
+use futures::future::BoxFuture;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{Object, Tool, ToolOutput};
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+/// A registered tool's handler.
+type ToolFn = Arc<dyn Fn(Object) -> BoxFuture<'static, Result<ToolOutput, McpError>> + Send + Sync>;
+
+/// A tool definition and its handler.
+struct Entry {
+    tool: Tool,
+    handler: ToolFn,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Registered tools, in registration order.
+    tools: RwLock<Vec<Entry>>,
+    notifiers: Mutex<Vec<ServerNotifier>>,
+}
+
+/// A set of tools that can change while the server runs.
+///
+/// Cloning is cheap and clones share the same tools, so keep a clone to
+/// register and unregister tools after handing one to the server.
+#[derive(Clone, Default)]
+pub struct DynamicToolRegistry {
+    inner: Arc<Inner>,
+}
+
+impl DynamicToolRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `tool`, answered by `handler`, replacing any tool of the same
+    /// name, and announce the change.
+    pub async fn register<F, Fut>(&self, tool: Tool, handler: F)
+    where
+        F: Fn(Object) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ToolOutput, McpError>> + Send + 'static,
+    {
+        let handler: ToolFn = Arc::new(move |args| Box::pin(handler(args)));
+        {
+            let mut tools = self.write();
+            match tools.iter_mut().find(|e| e.tool.name == tool.name) {
+                Some(entry) => *entry = Entry { tool, handler },
+                None => tools.push(Entry { tool, handler }),
+            }
+        }
+        self.announce().await;
+    }
+
+    /// Remove the tool named `name` and announce the change.
+    ///
+    /// Calls already running complete normally. Returns whether the tool was
+    /// registered.
+    pub async fn unregister(&self, name: &str) -> bool {
+        let removed = {
+            let mut tools = self.write();
+            let before = tools.len();
+            tools.retain(|e| e.tool.name != name);
+            tools.len() != before
+        };
+        if removed {
+            self.announce().await;
+        }
+        removed
+    }
+
+    /// Whether a tool named `name` is registered.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.read().iter().any(|e| e.tool.name == name)
+    }
+
+    /// The registered tool definitions, in registration order.
+    #[must_use]
+    pub fn tools(&self) -> Vec<Tool> {
+        self.read().iter().map(|e| e.tool.clone()).collect()
+    }
+
+    /// The number of registered tools.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Whether no tools are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Send `notifications/tools/list_changed` through `notifier` whenever the
+    /// registered tools change.
+    pub fn notify_with(&self, notifier: ServerNotifier) {
+        self.inner
+            .notifiers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(notifier);
+    }
+
+    async fn announce(&self) {
+        let notifiers = self
+            .inner
+            .notifiers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        for notifier in notifiers {
+            // A closed connection must not stop the change or the other notices.
+            if let Err(error) = notifier.tools_list_changed().await {
+                tracing::debug!(%error, "could not announce tool list change");
+            }
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<Entry>> {
+        self.inner
+            .tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<Entry>> {
+        self.inner
+            .tools
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for DynamicToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self.read().iter().map(|e| e.tool.name.clone()).collect();
+        f.debug_struct("DynamicToolRegistry")
+            .field("tools", &names)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ToolHandler for DynamicToolRegistry {
+    async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        Ok(self.tools())
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        args: Object,
+        _ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        // Take the handler out of the lock so unregistering never waits on, or
+        // cancels, a running call.
+        let handler = self
+            .read()
+            .iter()
+            .find(|e| e.tool.name == name)
+            .map(|e| Arc::clone(&e.handler));
+        match handler {
+            Some(handler) => handler(args).await,
+            None => Err(McpError::method_not_found_with_suggestions(
+                name,
+                self.read().iter().map(|e| e.tool.name.clone()).collect(),
+            )),
+        }
+    }
+}
+
+impl Mountable for DynamicToolRegistry {
+    fn mount(self: Arc<Self>, prefix: &str, mounts: &mut Mounts) {
+        mounts.add_tools(prefix, self);
+        mounts.announce_tool_changes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::ServerBuilder;
+    use crate::context::NoOpPeer;
+    use crate::handler::ServerHandler;
+    use crate::server::ServerRuntime;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+    use mcpkit_core::protocol::{Message, RequestId};
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_transport::{MemoryTransport, Transport};
+    use std::time::Duration;
+    use tokio::sync::Notify;
+
+    struct Main;
+
+    impl ServerHandler for Main {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("main", "1.0.0")
+        }
+    }
+
+    async fn call(registry: &DynamicToolRegistry, name: &str) -> Result<ToolOutput, McpError> {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+        registry.call_tool(name, Object::new(), &ctx).await
+    }
+
+    #[tokio::test]
+    async fn unregistering_lets_running_calls_finish() {
+        let registry = DynamicToolRegistry::new();
+        let release = Arc::new(Notify::new());
+        let gate = Arc::clone(&release);
+        registry
+            .register(Tool::new("slow"), move |_| {
+                let gate = Arc::clone(&gate);
+                async move {
+                    gate.notified().await;
+                    Ok(ToolOutput::text("done"))
+                }
+            })
+            .await;
+        assert_eq!(registry.len(), 1);
+
+        let (running, removed) = tokio::join!(call(&registry, "slow"), async {
+            tokio::task::yield_now().await;
+            let removed = registry.unregister("slow").await;
+            release.notify_one();
+            removed
+        });
+        assert!(removed);
+        assert!(running.is_ok());
+
+        assert!(registry.is_empty());
+        assert!(matches!(
+            call(&registry, "slow").await,
+            Err(McpError::MethodNotFound { .. })
+        ));
+        assert!(!registry.unregister("slow").await);
+    }
+
+    #[tokio::test]
+    async fn changes_are_announced_to_attached_notifiers() {
+        let registry = DynamicToolRegistry::new();
+        let server = ServerBuilder::new(Main)
+            .mount("plugin_", registry.clone())
+            .build();
+        let tools = server
+            .capabilities()
+            .tools
+            .clone()
+            .expect("tools capability");
+        assert_eq!(tools.list_changed, Some(true));
+
+        let (client, transport) = MemoryTransport::pair();
+        let runtime = ServerRuntime::new(server, transport);
+        registry.notify_with(runtime.notifier());
+
+        registry
+            .register(Tool::new("echo"), |_| async { Ok(ToolOutput::text("hi")) })
+            .await;
+        assert!(registry.unregister("echo").await);
+
+        for _ in 0..2 {
+            let msg = tokio::time::timeout(Duration::from_secs(2), client.recv())
+                .await
+                .expect("no notification (timed out)")
+                .expect("recv ok")
+                .expect("some message");
+            match msg {
+                Message::Notification(n) => {
+                    assert_eq!(n.method.as_ref(), "notifications/tools/list_changed");
+                }
+                other => panic!("expected a notification, got {other:?}"),
+            }
+        }
+    }
+}
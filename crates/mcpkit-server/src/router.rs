@@ -12,11 +12,22 @@
 //! - **Tasks**: `tasks/list`, `tasks/get`, `tasks/cancel`
 //! - **Sampling**: `sampling/createMessage`
 //! - **Completions**: `completion/complete`
+//!
+//! # Dispatch
+//!
+//! [`Method::parse`] interns a method name once per request, so the server
+//! routes on an enum instead of offering the name to each handler's routing
+//! in turn. [`DispatchTable`] maps a large, fixed set of names to positions
+//! with one hash; `#[mcp_server]` uses one to dispatch `tools/call` when a
+//! server has many tools, where matching on the name would compare it with
+//! every tool.
 
 use mcpkit_core::error::McpError;
 use mcpkit_core::protocol::Request;
 use mcpkit_core::types::Object;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Standard MCP method names as defined in the MCP specification.
 pub mod methods {
@@ -88,6 +99,185 @@ pub mod notifications {
     pub const ELICITATION_COMPLETE: &str = "notifications/elicitation/complete";
 }
 
+/// A standard MCP request method, interned for dispatch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// `initialize`
+    Initialize,
+    /// `ping`
+    Ping,
+    /// `tools/list`
+    ToolsList,
+    /// `tools/call`
+    ToolsCall,
+    /// `resources/list`
+    ResourcesList,
+    /// `resources/read`
+    ResourcesRead,
+    /// `resources/templates/list`
+    ResourcesTemplatesList,
+    /// `resources/subscribe`
+    ResourcesSubscribe,
+    /// `resources/unsubscribe`
+    ResourcesUnsubscribe,
+    /// `prompts/list`
+    PromptsList,
+    /// `prompts/get`
+    PromptsGet,
+    /// `tasks/list`
+    TasksList,
+    /// `tasks/get`
+    TasksGet,
+    /// `tasks/cancel`
+    TasksCancel,
+    /// `sampling/createMessage`
+    SamplingCreateMessage,
+    /// `completion/complete`
+    CompletionComplete,
+    /// `logging/setLevel`
+    LoggingSetLevel,
+    /// `elicitation/create`
+    ElicitationCreate,
+}
+
+impl Method {
+    /// Every method, in declaration order.
+    pub const ALL: [Self; 18] = [
+        Self::Initialize,
+        Self::Ping,
+        Self::ToolsList,
+        Self::ToolsCall,
+        Self::ResourcesList,
+        Self::ResourcesRead,
+        Self::ResourcesTemplatesList,
+        Self::ResourcesSubscribe,
+        Self::ResourcesUnsubscribe,
+        Self::PromptsList,
+        Self::PromptsGet,
+        Self::TasksList,
+        Self::TasksGet,
+        Self::TasksCancel,
+        Self::SamplingCreateMessage,
+        Self::CompletionComplete,
+        Self::LoggingSetLevel,
+        Self::ElicitationCreate,
+    ];
+
+    /// The method named `method`, or `None` for a non-standard method.
+    #[must_use]
+    pub fn parse(method: &str) -> Option<Self> {
+        // The compiler turns a `match` over this few fixed strings into a
+        // switch on length and bytes, which beats hashing.
+        Some(match method {
+            methods::INITIALIZE => Self::Initialize,
+            methods::PING => Self::Ping,
+            methods::TOOLS_LIST => Self::ToolsList,
+            methods::TOOLS_CALL => Self::ToolsCall,
+            methods::RESOURCES_LIST => Self::ResourcesList,
+            methods::RESOURCES_READ => Self::ResourcesRead,
+            methods::RESOURCES_TEMPLATES_LIST => Self::ResourcesTemplatesList,
+            methods::RESOURCES_SUBSCRIBE => Self::ResourcesSubscribe,
+            methods::RESOURCES_UNSUBSCRIBE => Self::ResourcesUnsubscribe,
+            methods::PROMPTS_LIST => Self::PromptsList,
+            methods::PROMPTS_GET => Self::PromptsGet,
+            methods::TASKS_LIST => Self::TasksList,
+            methods::TASKS_GET => Self::TasksGet,
+            methods::TASKS_CANCEL => Self::TasksCancel,
+            methods::SAMPLING_CREATE_MESSAGE => Self::SamplingCreateMessage,
+            methods::COMPLETION_COMPLETE => Self::CompletionComplete,
+            methods::LOGGING_SET_LEVEL => Self::LoggingSetLevel,
+            methods::ELICITATION_CREATE => Self::ElicitationCreate,
+            _ => return None,
+        })
+    }
+
+    /// The method name on the wire.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Initialize => methods::INITIALIZE,
+            Self::Ping => methods::PING,
+            Self::ToolsList => methods::TOOLS_LIST,
+            Self::ToolsCall => methods::TOOLS_CALL,
+            Self::ResourcesList => methods::RESOURCES_LIST,
+            Self::ResourcesRead => methods::RESOURCES_READ,
+            Self::ResourcesTemplatesList => methods::RESOURCES_TEMPLATES_LIST,
+            Self::ResourcesSubscribe => methods::RESOURCES_SUBSCRIBE,
+            Self::ResourcesUnsubscribe => methods::RESOURCES_UNSUBSCRIBE,
+            Self::PromptsList => methods::PROMPTS_LIST,
+            Self::PromptsGet => methods::PROMPTS_GET,
+            Self::TasksList => methods::TASKS_LIST,
+            Self::TasksGet => methods::TASKS_GET,
+            Self::TasksCancel => methods::TASKS_CANCEL,
+            Self::SamplingCreateMessage => methods::SAMPLING_CREATE_MESSAGE,
+            Self::CompletionComplete => methods::COMPLETION_COMPLETE,
+            Self::LoggingSetLevel => methods::LOGGING_SET_LEVEL,
+            Self::ElicitationCreate => methods::ELICITATION_CREATE,
+        }
+    }
+}
+
+impl std::fmt::Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A lookup table from a fixed set of names to their positions.
+///
+/// The hash table is built on the first lookup and shared from then on, so a
+/// table can be declared in a `static`. Lookups cost one hash regardless of
+/// how many names there are, where a `match` on many similar strings compares
+/// the name against each arm in turn. For a dozen or so names a `match` is
+/// faster.
+///
+/// ```rust
+/// use mcpkit_server::router::DispatchTable;
+///
+/// static TOOLS: DispatchTable = DispatchTable::new(&["search", "fetch", "summarize"]);
+///
+/// assert_eq!(TOOLS.get("fetch"), Some(1));
+/// assert_eq!(TOOLS.get("delete"), None);
+/// ```
+#[derive(Debug)]
+pub struct DispatchTable {
+    names: &'static [&'static str],
+    index: OnceLock<HashMap<&'static str, usize>>,
+}
+
+impl DispatchTable {
+    /// Create a table over `names`. A repeated name resolves to its first
+    /// position.
+    #[must_use]
+    pub const fn new(names: &'static [&'static str]) -> Self {
+        Self {
+            names,
+            index: OnceLock::new(),
+        }
+    }
+
+    /// The position of `name`, if it is in the table.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.index
+            .get_or_init(|| {
+                let mut index = HashMap::with_capacity(self.names.len());
+                for (i, name) in self.names.iter().enumerate() {
+                    index.entry(*name).or_insert(i);
+                }
+                index
+            })
+            .get(name)
+            .copied()
+    }
+
+    /// The names in the table, in order.
+    #[must_use]
+    pub const fn names(&self) -> &'static [&'static str] {
+        self.names
+    }
+}
+
 /// Represents a parsed MCP request with typed parameters.
 ///
 /// This enum provides a type-safe representation of all MCP request types,
@@ -528,20 +718,23 @@ use crate::context::Context;
 use crate::dispatch::{
     DynCompletionHandler, DynPromptHandler, DynResourceHandler, DynTaskHandler, DynToolHandler,
 };
-use mcpkit_core::pagination::paginate;
+use crate::invocation::{Invocation, InvocationKind, Outcome};
+use mcpkit_core::pagination::Page;
 use mcpkit_core::types::{
-    CallToolResult, CompleteRequest, CompleteResult, SubscribeRequest, TaskId, UnsubscribeRequest,
+    ByteRange, CallToolResult, CompleteRequest, CompleteResult, MAX_RESOURCE_CHUNK_SIZE,
+    SubscribeRequest, TaskId, UnsubscribeRequest,
 };
+use tracing::Instrument;
 
-/// Build a paginated list result: the items under `key` plus an optional
-/// `nextCursor`.
-fn list_result<T: serde::Serialize>(key: &str, items: Vec<T>, next: Option<String>) -> Value {
+/// Build a paginated list result: the page's items under `key` plus an
+/// optional `nextCursor`.
+fn list_result<T: serde::Serialize>(key: &str, page: Page<T>) -> Value {
     let mut obj = serde_json::Map::new();
     obj.insert(
         key.to_string(),
-        serde_json::to_value(items).unwrap_or_default(),
+        serde_json::to_value(page.items).unwrap_or_default(),
     );
-    if let Some(cursor) = next {
+    if let Some(cursor) = page.next_cursor {
         obj.insert("nextCursor".to_string(), Value::String(cursor));
     }
     Value::Object(obj)
@@ -576,11 +769,11 @@ pub async fn route_tools(
         methods::TOOLS_LIST => {
             tracing::debug!("Listing available tools");
             let result = async {
-                let tools = handler.list_tools(ctx).await?;
-                let (page, next) =
-                    paginate(tools, list_cursor(params), page_size, methods::TOOLS_LIST)?;
-                tracing::debug!(count = page.len(), "Listed tools");
-                Ok(list_result("tools", page, next))
+                let page = handler
+                    .list_tools_page(list_cursor(params), page_size, ctx)
+                    .await?;
+                tracing::debug!(count = page.items.len(), "Listed tools");
+                Ok(list_result("tools", page))
             }
             .await;
             Some(result)
@@ -604,27 +797,13 @@ pub async fn route_tools(
                     }
                 };
 
-                tracing::info!(tool = %name, "Calling tool");
-                let start = std::time::Instant::now();
-                let output = handler.call_tool(name, args, ctx).await;
-                let duration = start.elapsed();
-
-                match &output {
-                    Ok(_) => tracing::info!(
-                        tool = %name,
-                        duration_ms = duration.as_millis(),
-                        "Tool call completed"
-                    ),
-                    Err(e) => tracing::warn!(
-                        tool = %name,
-                        duration_ms = duration.as_millis(),
-                        error = %e,
-                        "Tool call failed"
-                    ),
-                }
+                let invocation = Invocation::start(ctx, InvocationKind::Tool, name, Some(&args));
+                let output = crate::idempotency::call_tool(handler, name, args, params, ctx)
+                    .instrument(invocation.span())
+                    .await;
+                invocation.finish(Outcome::of_tool(&output));
 
-                let output = output?;
-                let result: CallToolResult = output.into();
+                let result = output?;
                 Ok(serde_json::to_value(result).unwrap_or_else(|_| serde_json::json!({})))
             }
             .await;
@@ -853,15 +1032,11 @@ pub async fn route_resources(
         methods::RESOURCES_LIST => {
             tracing::debug!("Listing available resources");
             let result = async {
-                let resources = handler.list_resources(ctx).await?;
-                let (page, next) = paginate(
-                    resources,
-                    list_cursor(params),
-                    page_size,
-                    methods::RESOURCES_LIST,
-                )?;
-                tracing::debug!(count = page.len(), "Listed resources");
-                Ok(list_result("resources", page, next))
+                let page = handler
+                    .list_resources_page(list_cursor(params), page_size, ctx)
+                    .await?;
+                tracing::debug!(count = page.items.len(), "Listed resources");
+                Ok(list_result("resources", page))
             }
             .await;
             Some(result)
@@ -869,15 +1044,11 @@ pub async fn route_resources(
         methods::RESOURCES_TEMPLATES_LIST => {
             tracing::debug!("Listing available resource templates");
             let result = async {
-                let templates = handler.list_resource_templates(ctx).await?;
-                let (page, next) = paginate(
-                    templates,
-                    list_cursor(params),
-                    page_size,
-                    methods::RESOURCES_TEMPLATES_LIST,
-                )?;
-                tracing::debug!(count = page.len(), "Listed resource templates");
-                Ok(list_result("resourceTemplates", page, next))
+                let page = handler
+                    .list_resource_templates_page(list_cursor(params), page_size, ctx)
+                    .await?;
+                tracing::debug!(count = page.items.len(), "Listed resource templates");
+                Ok(list_result("resourceTemplates", page))
             }
             .await;
             Some(result)
@@ -891,24 +1062,26 @@ pub async fn route_resources(
                     McpError::invalid_params(methods::RESOURCES_READ, "missing uri")
                 })?;
 
-                tracing::info!(uri = %uri, "Reading resource");
-                let start = std::time::Instant::now();
-                let contents = handler.read_resource(uri, ctx).await;
-                let duration = start.elapsed();
-
-                match &contents {
-                    Ok(_) => tracing::info!(
-                        uri = %uri,
-                        duration_ms = duration.as_millis(),
-                        "Resource read completed"
-                    ),
-                    Err(e) => tracing::warn!(
-                        uri = %uri,
-                        duration_ms = duration.as_millis(),
-                        error = %e,
-                        "Resource read failed"
-                    ),
-                }
+                let invocation = Invocation::start(ctx, InvocationKind::Resource, uri, None);
+                // A ranged read answers with one chunk, capped in size.
+                let contents = match ByteRange::from_params(params) {
+                    Some(range) => {
+                        let range =
+                            ByteRange::new(range.offset, range.length.min(MAX_RESOURCE_CHUNK_SIZE));
+                        handler
+                            .read_resource_range(uri, range, ctx)
+                            .instrument(invocation.span())
+                            .await
+                            .map(|chunk| vec![chunk])
+                    }
+                    None => {
+                        handler
+                            .read_resource(uri, ctx)
+                            .instrument(invocation.span())
+                            .await
+                    }
+                };
+                invocation.finish(Outcome::of(&contents));
 
                 let contents = contents?;
                 Ok(serde_json::json!({ "contents": contents }))
@@ -927,6 +1100,9 @@ pub async fn route_resources(
                     })?;
                 tracing::info!(uri = %req.uri, "Subscribing to resource");
                 if handler.subscribe(&req.uri, ctx).await? {
+                    if let Some(subscriptions) = ctx.subscriptions() {
+                        subscriptions.subscribe(req.uri);
+                    }
                     Ok(serde_json::json!({}))
                 } else {
                     Err(McpError::internal(format!(
@@ -949,6 +1125,9 @@ pub async fn route_resources(
                     })?;
                 tracing::info!(uri = %req.uri, "Unsubscribing from resource");
                 if handler.unsubscribe(&req.uri, ctx).await? {
+                    if let Some(subscriptions) = ctx.subscriptions() {
+                        let _ = subscriptions.unsubscribe(&req.uri);
+                    }
                     Ok(serde_json::json!({}))
                 } else {
                     Err(McpError::internal(format!(
@@ -988,15 +1167,11 @@ pub async fn route_prompts(
         methods::PROMPTS_LIST => {
             tracing::debug!("Listing available prompts");
             let result = async {
-                let prompts = handler.list_prompts(ctx).await?;
-                let (page, next) = paginate(
-                    prompts,
-                    list_cursor(params),
-                    page_size,
-                    methods::PROMPTS_LIST,
-                )?;
-                tracing::debug!(count = page.len(), "Listed prompts");
-                Ok(list_result("prompts", page, next))
+                let page = handler
+                    .list_prompts_page(list_cursor(params), page_size, ctx)
+                    .await?;
+                tracing::debug!(count = page.items.len(), "Listed prompts");
+                Ok(list_result("prompts", page))
             }
             .await;
             Some(result)
@@ -1020,24 +1195,13 @@ pub async fn route_prompts(
                     }
                 };
 
-                tracing::info!(prompt = %name, "Getting prompt");
-                let start = std::time::Instant::now();
-                let prompt_result = handler.get_prompt(name, args, ctx).await;
-                let duration = start.elapsed();
-
-                match &prompt_result {
-                    Ok(_) => tracing::info!(
-                        prompt = %name,
-                        duration_ms = duration.as_millis(),
-                        "Prompt retrieval completed"
-                    ),
-                    Err(e) => tracing::warn!(
-                        prompt = %name,
-                        duration_ms = duration.as_millis(),
-                        error = %e,
-                        "Prompt retrieval failed"
-                    ),
-                }
+                let invocation =
+                    Invocation::start(ctx, InvocationKind::Prompt, name, args.as_ref());
+                let prompt_result = handler
+                    .get_prompt(name, args, ctx)
+                    .instrument(invocation.span())
+                    .await;
+                invocation.finish(Outcome::of(&prompt_result));
 
                 let result = prompt_result?;
                 Ok(serde_json::to_value(result).unwrap_or_else(|_| serde_json::json!({})))
@@ -1181,6 +1345,27 @@ mod tests {
     use super::*;
     use mcpkit_core::protocol::Request;
 
+    #[test]
+    fn test_method_parse_round_trips() {
+        for method in Method::ALL {
+            assert_eq!(Method::parse(method.as_str()), Some(method));
+            assert_eq!(method.to_string(), method.as_str());
+        }
+        assert_eq!(Method::parse(methods::TOOLS_CALL), Some(Method::ToolsCall));
+        assert_eq!(Method::parse("tools/cal"), None);
+        assert_eq!(Method::parse("custom/method"), None);
+    }
+
+    #[test]
+    fn test_dispatch_table() {
+        static TABLE: DispatchTable = DispatchTable::new(&["a", "b", "a", "c"]);
+        assert_eq!(TABLE.get("a"), Some(0));
+        assert_eq!(TABLE.get("b"), Some(1));
+        assert_eq!(TABLE.get("c"), Some(3));
+        assert_eq!(TABLE.get("d"), None);
+        assert_eq!(TABLE.names().len(), 4);
+    }
+
     fn make_request(method: &'static str, params: Option<Value>) -> Request {
         if let Some(p) = params {
             Request::with_params(method, 1u64, p)
@@ -2007,6 +2192,75 @@ mod tests {
         assert!(err.is_err());
     }
 
+    /// A handler that pages natively sees the raw client cursor, and its own
+    /// `nextCursor` is passed through untouched.
+    #[tokio::test]
+    async fn route_tools_uses_native_handler_pages() {
+        use crate::context::NoOpPeer;
+        use crate::handler::ToolHandler;
+        use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+        use mcpkit_core::pagination::Page;
+        use mcpkit_core::protocol::RequestId;
+        use mcpkit_core::protocol_version::ProtocolVersion;
+        use mcpkit_core::types::{Tool, ToolOutput};
+
+        struct Db;
+        impl ToolHandler for Db {
+            async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+                unreachable!("list_tools_page is overridden")
+            }
+            async fn list_tools_page(
+                &self,
+                cursor: Option<&str>,
+                page_size: Option<usize>,
+                _ctx: &Context<'_>,
+            ) -> Result<Page<Tool>, McpError> {
+                assert_eq!(page_size, Some(50));
+                Ok(match cursor {
+                    None => Page::new(vec![Tool::new("first")]).with_next_cursor("row:1"),
+                    Some("row:1") => Page::new(vec![Tool::new("second")]),
+                    Some(other) => panic!("unexpected cursor {other}"),
+                })
+            }
+            async fn call_tool(
+                &self,
+                _name: &str,
+                _args: serde_json::Map<String, Value>,
+                _ctx: &Context<'_>,
+            ) -> Result<ToolOutput, McpError> {
+                Ok(ToolOutput::text("x"))
+            }
+        }
+
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+
+        let page1 = route_tools(&Db, methods::TOOLS_LIST, None, &ctx, Some(50))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(page1["tools"][0]["name"], "first");
+        assert_eq!(page1["nextCursor"], "row:1");
+
+        let params = serde_json::json!({ "cursor": "row:1" });
+        let page2 = route_tools(&Db, methods::TOOLS_LIST, Some(&params), &ctx, Some(50))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(page2["tools"][0]["name"], "second");
+        assert!(page2.get("nextCursor").is_none());
+    }
+
     #[tokio::test]
     async fn route_resources_dispatches_subscribe_and_unsubscribe() {
         use crate::context::NoOpPeer;
@@ -2106,4 +2360,77 @@ mod tests {
         .expect("ok");
         assert_eq!(ok, serde_json::json!({}));
     }
+
+    #[tokio::test]
+    async fn route_resources_records_accepted_subscriptions() {
+        use crate::context::NoOpPeer;
+        use crate::handler::ResourceHandler;
+        use crate::subscription::SubscriptionManager;
+        use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+        use mcpkit_core::protocol::RequestId;
+        use mcpkit_core::protocol_version::ProtocolVersion;
+        use mcpkit_core::types::{Resource, ResourceContents};
+
+        struct Res;
+        impl ResourceHandler for Res {
+            async fn list_resources(&self, _ctx: &Context<'_>) -> Result<Vec<Resource>, McpError> {
+                Ok(vec![])
+            }
+            async fn read_resource(
+                &self,
+                _uri: &str,
+                _ctx: &Context<'_>,
+            ) -> Result<Vec<ResourceContents>, McpError> {
+                Ok(vec![])
+            }
+            async fn subscribe(&self, uri: &str, _ctx: &Context<'_>) -> Result<bool, McpError> {
+                Ok(uri != "file:///denied")
+            }
+            async fn unsubscribe(&self, _uri: &str, _ctx: &Context<'_>) -> Result<bool, McpError> {
+                Ok(true)
+            }
+        }
+
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let subs = SubscriptionManager::new();
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        )
+        .with_subscriptions(&subs);
+
+        for uri in ["file:///x", "file:///denied"] {
+            let params = serde_json::json!({ "uri": uri });
+            let _ = route_resources(
+                &Res,
+                methods::RESOURCES_SUBSCRIBE,
+                Some(&params),
+                &ctx,
+                None,
+            )
+            .await
+            .expect("routed");
+        }
+        assert_eq!(subs.uris(), ["file:///x"]);
+
+        let params = serde_json::json!({ "uri": "file:///x" });
+        route_resources(
+            &Res,
+            methods::RESOURCES_UNSUBSCRIBE,
+            Some(&params),
+            &ctx,
+            None,
+        )
+        .await
+        .expect("routed")
+        .expect("ok");
+        assert!(subs.is_empty());
+    }
 }
@@ -0,0 +1,647 @@
+//! Multi-turn sampling conversations.
+//!
+//! [`Context::create_message`] sends one `sampling/createMessage` request. A
+//! [`SamplingSession`] keeps the conversation around it: it accumulates the
+//! messages of every turn, reuses the system prompt, model preferences, and
+//! other settings, records each turn's stop reason and model, and spreads an
+//! optional token budget across turns.
+//!
+//! [`run_with_tools`](SamplingSession::run_with_tools) lets the client's
+//! model call the server's own tools: each `tool_use` block in a response is
+//! run through a [`ToolHandler`] and its result sent back, until the model
+//! answers without calling a tool. This requires the client to declare
+//! `sampling.tools`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! #[tool(description = "Answer a question about the repository")]
+//! async fn ask(&self, question: String, ctx: &Context<'_>) -> Result<String, McpError> {
+//!     let mut session = SamplingSession::new(1024)
+//!         .system_prompt("You answer questions about this repository.")
+//!         .model_preferences(ModelPreferences::smart())
+//!         .token_budget(8192)
+//!         .max_tool_rounds(4);
+//!     session.user(question);
+//!     let answer = session.run_with_tools(ctx, self).await?;
+//!     Ok(answer.as_text().unwrap_or_default().to_string())
+//! }
+//! ```
+//!
+//! # Token budget
+//!
+//! The protocol does not report how many tokens a response used, so every
+//! turn is charged the `maxTokens` it was granted. A turn is granted the
+//! smaller of the per-turn limit and what is left of the budget.
+
+use crate::context::Context;
+use crate::handler::ToolHandler;
+use mcpkit_core::capability::ClientCapability;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{
+    CallToolResult, CreateMessageRequest, CreateMessageResult, IncludeContext, ModelPreferences,
+    OneOrMany, Role, SamplingContent, SamplingMessage, StopReason, Tool, ToolChoice,
+    ToolChoiceMode, ToolResultContent, ToolUseContent,
+};
+
+/// Default number of tool-calling rounds in
+/// [`run_with_tools`](SamplingSession::run_with_tools).
+pub const DEFAULT_MAX_TOOL_ROUNDS: usize = 8;
+
+/// A sampling conversation with the client's model.
+///
+/// Settings apply to every turn. Messages are added with
+/// [`user`](Self::user) or [`push`](Self::push), and each response is
+/// appended to the conversation as an assistant message.
+#[derive(Debug, Clone)]
+pub struct SamplingSession {
+    messages: Vec<SamplingMessage>,
+    max_tokens: u32,
+    token_budget: Option<u32>,
+    tokens_used: u32,
+    system_prompt: Option<String>,
+    model_preferences: Option<ModelPreferences>,
+    include_context: Option<IncludeContext>,
+    temperature: Option<f64>,
+    stop_sequences: Vec<String>,
+    allowed_tools: Option<Vec<String>>,
+    max_tool_rounds: usize,
+    turns: usize,
+    stop_reason: Option<StopReason>,
+    model: Option<String>,
+}
+
+impl SamplingSession {
+    /// Create an empty session granting each turn up to `max_tokens`.
+    #[must_use]
+    pub const fn new(max_tokens: u32) -> Self {
+        Self {
+            messages: Vec::new(),
+            max_tokens,
+            token_budget: None,
+            tokens_used: 0,
+            system_prompt: None,
+            model_preferences: None,
+            include_context: None,
+            temperature: None,
+            stop_sequences: Vec::new(),
+            allowed_tools: None,
+            max_tool_rounds: DEFAULT_MAX_TOOL_ROUNDS,
+            turns: 0,
+            stop_reason: None,
+            model: None,
+        }
+    }
+
+    /// Set the system prompt.
+    #[must_use]
+    pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Set model preferences.
+    #[must_use]
+    pub fn model_preferences(mut self, prefs: ModelPreferences) -> Self {
+        self.model_preferences = Some(prefs);
+        self
+    }
+
+    /// Set context inclusion.
+    #[must_use]
+    pub const fn include_context(mut self, context: IncludeContext) -> Self {
+        self.include_context = Some(context);
+        self
+    }
+
+    /// Set the temperature.
+    #[must_use]
+    pub fn temperature(mut self, temp: f64) -> Self {
+        self.temperature = Some(temp.clamp(0.0, 2.0));
+        self
+    }
+
+    /// Add a stop sequence.
+    #[must_use]
+    pub fn stop_sequence(mut self, seq: impl Into<String>) -> Self {
+        self.stop_sequences.push(seq.into());
+        self
+    }
+
+    /// Limit the tokens granted across all turns.
+    #[must_use]
+    pub const fn token_budget(mut self, budget: u32) -> Self {
+        self.token_budget = Some(budget);
+        self
+    }
+
+    /// Offer only the named tools in
+    /// [`run_with_tools`](Self::run_with_tools), rather than every tool the
+    /// handler lists.
+    #[must_use]
+    pub fn allow_tools<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_tools = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set how many rounds of tool calls
+    /// [`run_with_tools`](Self::run_with_tools) runs before asking the model
+    /// for an answer without tools.
+    #[must_use]
+    pub const fn max_tool_rounds(mut self, rounds: usize) -> Self {
+        self.max_tool_rounds = rounds;
+        self
+    }
+
+    /// Add a user message with text content.
+    pub fn user(&mut self, text: impl Into<String>) -> &mut Self {
+        self.push(SamplingMessage::user(text))
+    }
+
+    /// Add a message to the conversation.
+    pub fn push(&mut self, message: SamplingMessage) -> &mut Self {
+        self.messages.push(message);
+        self
+    }
+
+    /// The conversation so far.
+    #[must_use]
+    pub fn messages(&self) -> &[SamplingMessage] {
+        &self.messages
+    }
+
+    /// The number of turns sent.
+    #[must_use]
+    pub const fn turns(&self) -> usize {
+        self.turns
+    }
+
+    /// The tokens granted so far.
+    #[must_use]
+    pub const fn tokens_used(&self) -> u32 {
+        self.tokens_used
+    }
+
+    /// The tokens left in the budget, or `None` without a budget.
+    #[must_use]
+    pub fn remaining_budget(&self) -> Option<u32> {
+        self.token_budget
+            .map(|budget| budget.saturating_sub(self.tokens_used))
+    }
+
+    /// Why the last turn stopped, if the client said.
+    #[must_use]
+    pub const fn stop_reason(&self) -> Option<&StopReason> {
+        self.stop_reason.as_ref()
+    }
+
+    /// The model that produced the last turn.
+    #[must_use]
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// Send the conversation and append the response to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token budget is spent, or the errors of
+    /// [`Context::create_message`].
+    pub async fn send(&mut self, ctx: &Context<'_>) -> Result<CreateMessageResult, McpError> {
+        self.turn(ctx, None, None).await
+    }
+
+    /// Add a user message, send the conversation, and return the text of
+    /// the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`send`](Self::send), or an error if the
+    /// response has no text blocks.
+    pub async fn ask(
+        &mut self,
+        ctx: &Context<'_>,
+        text: impl Into<String>,
+    ) -> Result<String, McpError> {
+        self.user(text);
+        let result = self.send(ctx).await?;
+        let text: Vec<&str> = blocks(&result.content)
+            .iter()
+            .filter_map(|block| match block {
+                SamplingContent::Text(t) => Some(t.text.as_str()),
+                _ => None,
+            })
+            .collect();
+        if text.is_empty() {
+            return Err(McpError::internal("sampling response has no text"));
+        }
+        Ok(text.concat())
+    }
+
+    /// Send the conversation with `tools` on offer, running the tools the
+    /// model calls until it answers without one, and return that answer.
+    ///
+    /// Each round's `tool_use` blocks are called in order and their results
+    /// sent back as one user message. A tool that fails, or that the model
+    /// is not offered, is reported to the model as an error result. After
+    /// [`max_tool_rounds`](Self::max_tool_rounds) rounds, the model is asked
+    /// to answer with tool use turned off.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the client did not declare `sampling.tools`, the
+    /// model still calls a tool with tool use turned off, or the errors of
+    /// [`send`](Self::send).
+    pub async fn run_with_tools<H: ToolHandler>(
+        &mut self,
+        ctx: &Context<'_>,
+        tools: &H,
+    ) -> Result<CreateMessageResult, McpError> {
+        ctx.require_capability(ClientCapability::SamplingTools)?;
+
+        let mut offered = tools.list_tools(ctx).await?;
+        if let Some(allowed) = &self.allowed_tools {
+            offered.retain(|tool| allowed.contains(&tool.name));
+        }
+
+        let mut rounds = 0;
+        loop {
+            let choice = (rounds >= self.max_tool_rounds).then_some(ToolChoiceMode::None);
+            let result = self.turn(ctx, Some(&offered), choice).await?;
+            let calls = tool_uses(&result.content);
+            if calls.is_empty() {
+                return Ok(result);
+            }
+            if choice.is_some() {
+                return Err(McpError::internal(format!(
+                    "sampling model kept calling tools after {rounds} rounds"
+                )));
+            }
+
+            let mut results = Vec::with_capacity(calls.len());
+            for call in calls {
+                let outcome = if offered.iter().any(|tool| tool.name == call.name) {
+                    tools
+                        .call_tool(&call.name, call.input.clone(), ctx)
+                        .await
+                        .map_or_else(|e| CallToolResult::error(e.to_string()), Into::into)
+                } else {
+                    CallToolResult::error(format!("tool '{}' is not available", call.name))
+                };
+                results.push(SamplingContent::ToolResult(ToolResultContent {
+                    tool_use_id: call.id.clone(),
+                    content: outcome.content,
+                    structured_content: outcome.structured_content,
+                    is_error: outcome.is_error,
+                    meta: None,
+                }));
+            }
+            self.push(SamplingMessage::with_content(
+                Role::User,
+                OneOrMany::Many(results),
+            ));
+            rounds += 1;
+        }
+    }
+
+    /// Send one turn and record its response.
+    async fn turn(
+        &mut self,
+        ctx: &Context<'_>,
+        tools: Option<&[Tool]>,
+        choice: Option<ToolChoiceMode>,
+    ) -> Result<CreateMessageResult, McpError> {
+        let max_tokens = match self.remaining_budget() {
+            Some(0) => {
+                return Err(McpError::internal(format!(
+                    "sampling token budget of {} exhausted after {} turns",
+                    self.tokens_used, self.turns
+                )));
+            }
+            Some(remaining) => remaining.min(self.max_tokens),
+            None => self.max_tokens,
+        };
+
+        let mut request = CreateMessageRequest::new(self.messages.clone(), max_tokens);
+        request.system_prompt.clone_from(&self.system_prompt);
+        request
+            .model_preferences
+            .clone_from(&self.model_preferences);
+        request.include_context = self.include_context;
+        request.temperature = self.temperature;
+        if !self.stop_sequences.is_empty() {
+            request.stop_sequences = Some(self.stop_sequences.clone());
+        }
+        request.tools = tools.map(<[Tool]>::to_vec);
+        request.tool_choice = choice.map(|mode| ToolChoice { mode: Some(mode) });
+
+        let result = ctx.create_message(request).await?;
+        self.turns += 1;
+        self.tokens_used = self.tokens_used.saturating_add(max_tokens);
+        self.stop_reason.clone_from(&result.stop_reason);
+        self.model = Some(result.model.clone());
+        self.messages.push(SamplingMessage::with_content(
+            Role::Assistant,
+            result.content.clone(),
+        ));
+        Ok(result)
+    }
+}
+
+/// The content blocks of a response.
+fn blocks(content: &OneOrMany<SamplingContent>) -> &[SamplingContent] {
+    match content {
+        OneOrMany::One(block) => std::slice::from_ref(block),
+        OneOrMany::Many(blocks) => blocks,
+    }
+}
+
+/// The `tool_use` blocks in a response.
+fn tool_uses(content: &OneOrMany<SamplingContent>) -> Vec<&ToolUseContent> {
+    blocks(content)
+        .iter()
+        .filter_map(|block| match block {
+            SamplingContent::ToolUse(call) => Some(call),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Peer;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+    use mcpkit_core::protocol::{Notification, RequestId, Response};
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::Content;
+    use mcpkit_core::types::{ToolOutput, object::Object};
+    use serde_json::{Value, json};
+    use std::borrow::Cow;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Mutex;
+
+    /// A client that answers sampling requests from a script and records
+    /// them.
+    struct ScriptedPeer {
+        replies: Mutex<Vec<CreateMessageResult>>,
+        requests: Mutex<Vec<CreateMessageRequest>>,
+    }
+
+    impl ScriptedPeer {
+        fn new(mut replies: Vec<CreateMessageResult>) -> Self {
+            replies.reverse();
+            Self {
+                replies: Mutex::new(replies),
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Peer for ScriptedPeer {
+        fn notify(
+            &self,
+            _n: Notification,
+        ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn request(
+            &self,
+            method: Cow<'static, str>,
+            params: Option<Value>,
+        ) -> Pin<Box<dyn Future<Output = Result<Response, McpError>> + Send + '_>> {
+            assert_eq!(method, "sampling/createMessage");
+            let request = serde_json::from_value(params.unwrap()).unwrap();
+            self.requests.lock().unwrap().push(request);
+            let reply = self.replies.lock().unwrap().pop().expect("unscripted turn");
+            let result = serde_json::to_value(reply).unwrap();
+            Box::pin(async move { Ok(Response::success(RequestId::Number(1), result)) })
+        }
+    }
+
+    fn reply(content: Vec<SamplingContent>, stop_reason: StopReason) -> CreateMessageResult {
+        CreateMessageResult {
+            role: Role::Assistant,
+            content: OneOrMany::Many(content),
+            model: "test-model".to_string(),
+            stop_reason: Some(stop_reason),
+            meta: None,
+        }
+    }
+
+    fn call(id: &str, name: &str, input: Value) -> SamplingContent {
+        let Value::Object(input) = input else {
+            panic!("input must be an object")
+        };
+        SamplingContent::ToolUse(ToolUseContent {
+            id: id.to_string(),
+            name: name.to_string(),
+            input,
+            meta: None,
+        })
+    }
+
+    struct Calculator;
+
+    impl ToolHandler for Calculator {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(vec![Tool::new("add"), Tool::new("shell")])
+        }
+
+        async fn call_tool(
+            &self,
+            name: &str,
+            args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            match name {
+                "add" => {
+                    let arg = |key| args.get(key).and_then(Value::as_i64).unwrap_or(0);
+                    let sum = arg("a") + arg("b");
+                    Ok(ToolOutput::text(sum.to_string()))
+                }
+                _ => Err(McpError::tool_error(name, "boom")),
+            }
+        }
+    }
+
+    fn tool_results(message: &SamplingMessage) -> Vec<(String, bool)> {
+        let OneOrMany::Many(blocks) = &message.content else {
+            panic!("expected tool results")
+        };
+        blocks
+            .iter()
+            .map(|block| match block {
+                SamplingContent::ToolResult(r) => {
+                    let text = match &r.content[0] {
+                        Content::Text(t) => t.text.clone(),
+                        other => panic!("expected text, got {other:?}"),
+                    };
+                    (text, r.is_error == Some(true))
+                }
+                other => panic!("expected tool result, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn turns_accumulate_messages_and_spend_the_budget() -> Result<(), McpError> {
+        let peer = ScriptedPeer::new(vec![
+            reply(vec![SamplingContent::text("Paris")], StopReason::EndTurn),
+            reply(vec![SamplingContent::text("Lyon")], StopReason::MaxTokens),
+        ]);
+        let caps = ClientCapabilities::default().with_sampling();
+        let server_caps = ServerCapabilities::default();
+        let id = RequestId::Number(1);
+        let ctx = Context::new(
+            &id,
+            None,
+            &caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+
+        let mut session = SamplingSession::new(100)
+            .system_prompt("Be brief.")
+            .model_preferences(ModelPreferences::fast())
+            .token_budget(150);
+        let first = session.ask(&ctx, "Capital of France?").await?;
+        assert_eq!(first, "Paris");
+        session.user("Another city?");
+        session.send(&ctx).await?;
+
+        assert_eq!(session.messages().len(), 4);
+        assert_eq!(session.turns(), 2);
+        assert_eq!(session.stop_reason(), Some(&StopReason::MaxTokens));
+        assert_eq!(session.model(), Some("test-model"));
+        assert_eq!(session.remaining_budget(), Some(0));
+
+        // A spent budget fails before anything is sent.
+        let err = session.ask(&ctx, "And another?").await.unwrap_err();
+        assert!(err.to_string().contains("budget"));
+
+        let requests = peer.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].max_tokens, 100);
+        assert_eq!(requests[1].max_tokens, 50);
+        assert_eq!(requests[1].messages.len(), 3);
+        assert_eq!(requests[1].system_prompt.as_deref(), Some("Be brief."));
+        assert!(requests[1].model_preferences.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tool_calls_loop_until_a_final_answer() -> Result<(), McpError> {
+        let peer = ScriptedPeer::new(vec![
+            reply(
+                vec![
+                    call("1", "add", json!({"a": 2, "b": 3})),
+                    call("2", "shell", json!({})),
+                ],
+                StopReason::ToolUse,
+            ),
+            reply(
+                vec![call("3", "add", json!({"a": 5, "b": 1}))],
+                StopReason::ToolUse,
+            ),
+            reply(vec![SamplingContent::text("6")], StopReason::EndTurn),
+        ]);
+        let caps = ClientCapabilities::default().with_sampling_tools();
+        let server_caps = ServerCapabilities::default();
+        let id = RequestId::Number(1);
+        let ctx = Context::new(
+            &id,
+            None,
+            &caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+
+        let mut session = SamplingSession::new(100).allow_tools(["add"]);
+        session.user("What is 2 + 3 + 1?");
+        let answer = session.run_with_tools(&ctx, &Calculator).await?;
+        assert_eq!(answer.stop_reason, Some(StopReason::EndTurn));
+        assert_eq!(session.turns(), 3);
+
+        let messages = session.messages();
+        assert_eq!(
+            tool_results(&messages[2]),
+            [
+                ("5".to_string(), false),
+                ("tool 'shell' is not available".to_string(), true),
+            ]
+        );
+        assert_eq!(tool_results(&messages[4]), [("6".to_string(), false)]);
+
+        let requests = peer.requests.lock().unwrap();
+        let offered: Vec<_> = requests[0]
+            .tools
+            .iter()
+            .flatten()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(offered, ["add"]);
+        assert!(requests.iter().all(|r| r.tool_choice.is_none()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tool_use_is_turned_off_after_the_last_round() {
+        let peer = ScriptedPeer::new(vec![
+            reply(vec![call("1", "add", json!({}))], StopReason::ToolUse),
+            reply(vec![call("2", "add", json!({}))], StopReason::ToolUse),
+        ]);
+        let caps = ClientCapabilities::default().with_sampling_tools();
+        let server_caps = ServerCapabilities::default();
+        let id = RequestId::Number(1);
+        let ctx = Context::new(
+            &id,
+            None,
+            &caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+
+        let mut session = SamplingSession::new(100).max_tool_rounds(1);
+        session.user("Add forever");
+        let err = session.run_with_tools(&ctx, &Calculator).await.unwrap_err();
+        assert!(err.to_string().contains("kept calling tools"));
+
+        let requests = peer.requests.lock().unwrap();
+        assert!(requests[0].tool_choice.is_none());
+        assert_eq!(
+            requests[1].tool_choice.as_ref().and_then(|c| c.mode),
+            Some(ToolChoiceMode::None)
+        );
+    }
+
+    #[tokio::test]
+    async fn tools_require_the_client_capability() {
+        let peer = ScriptedPeer::new(Vec::new());
+        let caps = ClientCapabilities::default().with_sampling();
+        let server_caps = ServerCapabilities::default();
+        let id = RequestId::Number(1);
+        let ctx = Context::new(
+            &id,
+            None,
+            &caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+
+        let mut session = SamplingSession::new(100);
+        session.user("hi");
+        assert!(session.run_with_tools(&ctx, &Calculator).await.is_err());
+        assert!(peer.requests.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,437 @@
+//! Per-tool filesystem and network policies.
+//!
+//! A tool declares what it may touch, and reaches the filesystem and the
+//! network through [`Context::fs`] and [`Context::http`], which refuse
+//! anything outside the declaration:
+//!
+//! ```ignore
+//! #[tool(
+//!     description = "Summarize a report",
+//!     allow_fs_read = "/data/reports",
+//!     allow_net = "api.example.com",
+//! )]
+//! async fn summarize(&self, name: String, ctx: &Context<'_>) -> Result<ToolOutput, McpError> {
+//!     let text = ctx.fs().read_to_string(format!("/data/reports/{name}")).await?;
+//!     let url = ctx.http().check("https://api.example.com/summarize")?;
+//!     // ... send `text` to `url` with your HTTP client
+//! }
+//! ```
+//!
+//! `#[mcp_server]` records the declaration in the tool's
+//! `_meta["mcpkit/sandbox"]`, so operators can review every tool's
+//! permissions in `tools/list` or an exported [`Catalog`](crate::Catalog),
+//! and attaches a [`Sandbox`] to the context of each call. Handlers written
+//! by hand do the same with [`Tool::sandbox`](mcpkit_core::types::Tool::sandbox)
+//! and [`Context::sandboxed`]. Without a sandbox, `ctx.fs()` and `ctx.http()`
+//! deny everything.
+//!
+//! Violations fail with [`McpError::ToolAccessDenied`].
+//!
+//! The policy is enforced by the facade, not the operating system: code that
+//! calls `std::fs` or an HTTP client directly is not constrained. Run
+//! untrusted tools as WebAssembly plugins (the `wasm-plugins` feature) for
+//! isolation.
+//!
+//! # Paths and hosts
+//!
+//! Paths are resolved with [`std::fs::canonicalize`] before they are checked,
+//! so `..` components and symbolic links cannot leave an allowed directory. A
+//! directory allowed for writing may also be read. Hosts match exactly, or
+//! `*.example.com` matches any subdomain of `example.com`. Only `http` and
+//! `https` URLs are allowed.
+
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::Tool;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What a tool may read, write, and connect to.
+///
+/// Serialized into a tool's `_meta["mcpkit/sandbox"]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxPolicy {
+    /// Directories (or files) the tool may read.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fs_read: Vec<PathBuf>,
+    /// Directories (or files) the tool may read and write.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fs_write: Vec<PathBuf>,
+    /// Hosts the tool may connect to, such as `api.example.com` or
+    /// `*.example.com`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub net: Vec<String>,
+}
+
+impl SandboxPolicy {
+    /// Create a policy that allows nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow reading `path` and everything below it.
+    #[must_use]
+    pub fn allow_fs_read(mut self, path: impl Into<PathBuf>) -> Self {
+        self.fs_read.push(path.into());
+        self
+    }
+
+    /// Allow reading and writing `path` and everything below it.
+    #[must_use]
+    pub fn allow_fs_write(mut self, path: impl Into<PathBuf>) -> Self {
+        self.fs_write.push(path.into());
+        self
+    }
+
+    /// Allow connecting to `host`, or to any subdomain with `*.example.com`.
+    #[must_use]
+    pub fn allow_net(mut self, host: impl Into<String>) -> Self {
+        self.net.push(host.into());
+        self
+    }
+
+    /// The policy a tool declares in its `_meta`, if any.
+    #[must_use]
+    pub fn of_tool(tool: &Tool) -> Option<Self> {
+        let value = tool.declared_sandbox()?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// This policy as a JSON value, for [`Tool::sandbox`].
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn allows_host(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.net.iter().any(|allowed| {
+            let allowed = allowed.to_ascii_lowercase();
+            match allowed.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+                None => host == allowed,
+            }
+        })
+    }
+}
+
+/// A tool's [`SandboxPolicy`], attached to the context of its calls with
+/// [`Context::sandboxed`].
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    tool: String,
+    policy: SandboxPolicy,
+}
+
+impl Sandbox {
+    /// The sandbox of the tool named `tool`.
+    #[must_use]
+    pub fn new(tool: impl Into<String>, policy: SandboxPolicy) -> Self {
+        Self {
+            tool: tool.into(),
+            policy,
+        }
+    }
+
+    /// The tool this sandbox constrains.
+    #[must_use]
+    pub fn tool(&self) -> &str {
+        &self.tool
+    }
+
+    /// The enforced policy.
+    #[must_use]
+    pub const fn policy(&self) -> &SandboxPolicy {
+        &self.policy
+    }
+}
+
+/// Filesystem access for a tool, limited to the paths its policy allows.
+///
+/// Returned by [`Context::fs`].
+#[derive(Debug, Clone, Copy)]
+pub struct SandboxFs<'a> {
+    sandbox: Option<&'a Sandbox>,
+}
+
+impl<'a> SandboxFs<'a> {
+    pub(crate) const fn new(sandbox: Option<&'a Sandbox>) -> Self {
+        Self { sandbox }
+    }
+
+    /// Resolve `path`, failing unless the policy allows reading it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::ToolAccessDenied`] if `path` does not exist or is
+    /// outside the allowed directories.
+    pub fn check_read(&self, path: impl AsRef<Path>) -> Result<PathBuf, McpError> {
+        let path = path.as_ref();
+        let resolved = std::fs::canonicalize(path).map_err(|_| self.denied("read", path))?;
+        let policy = self.sandbox.map(Sandbox::policy);
+        let allowed = policy.is_some_and(|policy| {
+            contains(&policy.fs_read, &resolved) || contains(&policy.fs_write, &resolved)
+        });
+        if allowed {
+            Ok(resolved)
+        } else {
+            Err(self.denied("read", path))
+        }
+    }
+
+    /// Resolve `path`, failing unless the policy allows writing it.
+    ///
+    /// The file need not exist, but its parent directory must.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::ToolAccessDenied`] if the parent directory does
+    /// not exist or `path` is outside the allowed directories.
+    pub fn check_write(&self, path: impl AsRef<Path>) -> Result<PathBuf, McpError> {
+        let path = path.as_ref();
+        let resolved = match std::fs::canonicalize(path) {
+            Ok(resolved) => Some(resolved),
+            Err(_) => path.file_name().and_then(|name| {
+                let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+                let parent = std::fs::canonicalize(parent.unwrap_or_else(|| Path::new(".")));
+                parent.ok().map(|parent| parent.join(name))
+            }),
+        };
+        let policy = self.sandbox.map(Sandbox::policy);
+        match resolved {
+            Some(resolved) if policy.is_some_and(|p| contains(&p.fs_write, &resolved)) => {
+                Ok(resolved)
+            }
+            _ => Err(self.denied("write", path)),
+        }
+    }
+
+    /// Read a whole file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the policy does not allow reading `path` or the read fails.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn read(&self, path: impl AsRef<Path>) -> Result<Vec<u8>, McpError> {
+        let path = self.check_read(path)?;
+        tokio::fs::read(&path)
+            .await
+            .map_err(|e| self.io_error(&path, &e))
+    }
+
+    /// Read a whole file as UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the policy does not allow reading `path` or the read fails.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn read_to_string(&self, path: impl AsRef<Path>) -> Result<String, McpError> {
+        let path = self.check_read(path)?;
+        tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| self.io_error(&path, &e))
+    }
+
+    /// List the entries of a directory.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the policy does not allow reading `path` or the listing
+    /// fails.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn read_dir(&self, path: impl AsRef<Path>) -> Result<Vec<PathBuf>, McpError> {
+        let path = self.check_read(path)?;
+        let mut entries = tokio::fs::read_dir(&path)
+            .await
+            .map_err(|e| self.io_error(&path, &e))?;
+        let mut paths = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| self.io_error(&path, &e))?
+        {
+            paths.push(entry.path());
+        }
+        Ok(paths)
+    }
+
+    /// Create or replace a file.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the policy does not allow writing `path` or the write fails.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn write(
+        &self,
+        path: impl AsRef<Path>,
+        contents: impl AsRef<[u8]>,
+    ) -> Result<(), McpError> {
+        let path = self.check_write(path)?;
+        tokio::fs::write(&path, contents)
+            .await
+            .map_err(|e| self.io_error(&path, &e))
+    }
+
+    fn tool(self) -> &'a str {
+        self.sandbox.map_or("unknown", Sandbox::tool)
+    }
+
+    fn denied(self, access: &str, path: &Path) -> McpError {
+        tracing::warn!(tool = self.tool(), access, path = %path.display(), "Sandbox denied filesystem access");
+        McpError::tool_access_denied(
+            self.tool(),
+            Some(format!(
+                "{access} access to {} is not allowed",
+                path.display()
+            )),
+        )
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    fn io_error(self, path: &Path, error: &std::io::Error) -> McpError {
+        McpError::tool_error(self.tool(), format!("{}: {error}", path.display()))
+    }
+}
+
+/// Network access for a tool, limited to the hosts its policy allows.
+///
+/// Returned by [`Context::http`]. Check each URL before sending a request to
+/// it with your HTTP client.
+#[derive(Debug, Clone, Copy)]
+pub struct SandboxHttp<'a> {
+    sandbox: Option<&'a Sandbox>,
+}
+
+impl<'a> SandboxHttp<'a> {
+    pub(crate) const fn new(sandbox: Option<&'a Sandbox>) -> Self {
+        Self { sandbox }
+    }
+
+    /// Parse `url`, failing unless it is an `http` or `https` URL whose host
+    /// the policy allows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::ToolAccessDenied`] if `url` is malformed, uses
+    /// another scheme, or names a host outside the policy.
+    pub fn check(&self, url: &str) -> Result<url::Url, McpError> {
+        let tool = self.sandbox.map_or("unknown", Sandbox::tool);
+        let parsed = url::Url::parse(url).ok().filter(|parsed| {
+            matches!(parsed.scheme(), "http" | "https")
+                && parsed.host_str().is_some_and(|host| {
+                    self.sandbox
+                        .is_some_and(|sandbox| sandbox.policy.allows_host(host))
+                })
+        });
+        parsed.ok_or_else(|| {
+            tracing::warn!(tool, url, "Sandbox denied network access");
+            McpError::tool_access_denied(tool, Some(format!("access to {url} is not allowed")))
+        })
+    }
+}
+
+/// Whether `path`, already canonical, is one of `roots` or below one.
+fn contains(roots: &[PathBuf], path: &Path) -> bool {
+    roots
+        .iter()
+        .any(|root| std::fs::canonicalize(root).is_ok_and(|root| path.starts_with(root)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(policy: SandboxPolicy) -> Sandbox {
+        Sandbox::new("test", policy)
+    }
+
+    #[test]
+    fn fs_checks_stay_inside_allowed_directories() {
+        let root = std::env::temp_dir().join(format!("mcpkit-sandbox-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("data/nested")).unwrap();
+        std::fs::create_dir_all(root.join("out")).unwrap();
+        std::fs::write(root.join("data/nested/a.txt"), "a").unwrap();
+        std::fs::write(root.join("secret.txt"), "s").unwrap();
+
+        let sandbox = sandbox(
+            SandboxPolicy::new()
+                .allow_fs_read(root.join("data"))
+                .allow_fs_write(root.join("out")),
+        );
+        let fs = SandboxFs::new(Some(&sandbox));
+
+        assert!(fs.check_read(root.join("data/nested/a.txt")).is_ok());
+        assert!(fs.check_read(root.join("data/../secret.txt")).is_err());
+        assert!(fs.check_read(root.join("data/missing.txt")).is_err());
+        assert!(fs.check_write(root.join("data/nested/a.txt")).is_err());
+        assert!(fs.check_write(root.join("out/new.txt")).is_ok());
+        assert!(fs.check_read(root.join("out")).is_ok());
+
+        let err = SandboxFs::new(None)
+            .check_read(root.join("data/nested/a.txt"))
+            .unwrap_err();
+        assert!(matches!(err, McpError::ToolAccessDenied { .. }));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn http_checks_scheme_and_host() {
+        let sandbox = sandbox(
+            SandboxPolicy::new()
+                .allow_net("api.example.com")
+                .allow_net("*.internal.test"),
+        );
+        let http = SandboxHttp::new(Some(&sandbox));
+
+        assert!(http.check("https://api.example.com/v1").is_ok());
+        assert!(http.check("http://API.example.com:8080/").is_ok());
+        assert!(http.check("https://db.internal.test/").is_ok());
+        assert!(http.check("https://internal.test/").is_err());
+        assert!(http.check("https://evilinternal.test/").is_err());
+        assert!(http.check("https://example.com/").is_err());
+        assert!(http.check("ftp://api.example.com/").is_err());
+        assert!(http.check("not a url").is_err());
+        assert!(
+            SandboxHttp::new(None)
+                .check("https://api.example.com/")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn policy_round_trips_through_tool_meta() {
+        let policy = SandboxPolicy::new()
+            .allow_fs_read("/data")
+            .allow_net("api.example.com");
+        let tool = Tool::new("fetch").sandbox(policy.to_json());
+
+        assert_eq!(
+            tool.meta.as_ref().unwrap().get("mcpkit/sandbox"),
+            Some(&serde_json::json!({ "fsRead": ["/data"], "net": ["api.example.com"] }))
+        );
+        assert_eq!(SandboxPolicy::of_tool(&tool), Some(policy));
+        assert_eq!(SandboxPolicy::of_tool(&Tool::new("plain")), None);
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn fs_reads_and_writes_through_the_policy() {
+        let root = std::env::temp_dir().join(format!("mcpkit-sandbox-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let sandbox = sandbox(SandboxPolicy::new().allow_fs_write(&root));
+        let fs = SandboxFs::new(Some(&sandbox));
+
+        fs.write(root.join("note.txt"), "hello").await.unwrap();
+        assert_eq!(
+            fs.read_to_string(root.join("note.txt")).await.unwrap(),
+            "hello"
+        );
+        assert_eq!(fs.read_dir(&root).await.unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,521 @@
+//! Startup self-test for handler wiring and schema sanity.
+//!
+//! Macro misconfigurations (a schema that isn't an object, two tools with the
+//! same name, a missing description) otherwise only surface when a client
+//! connects and trips over them. [`Server::self_check`] lists every tool,
+//! resource, resource template and prompt through the server's own routing,
+//! inspects the results and returns a [`SelfCheckReport`]. Call
+//! [`SelfCheckReport::into_result`] to refuse to serve when it found errors.
+//!
+//! With the `schema-validation` feature, every input and output schema is
+//! also compiled with the JSON Schema validator.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let server = ServerBuilder::new(MyServer).with_tools(MyServer).build();
+//! let report = server.self_check().await.into_result()?;
+//! tracing::info!("{report}");
+//! server.serve(transport).await?;
+//! ```
+
+use crate::Server;
+use crate::context::{Context, NoOpPeer};
+use crate::middleware::HasCapabilities;
+use crate::server::RequestRouter;
+use mcpkit_core::capability::ClientCapabilities;
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::RequestId;
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::{Prompt, Resource, ResourceTemplate, Tool};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Default size above which a schema is reported as oversized.
+pub const DEFAULT_MAX_SCHEMA_BYTES: usize = 32 * 1024;
+
+/// Upper bound on pages fetched per list, guarding against cursors that
+/// never run out.
+const MAX_PAGES: usize = 1000;
+
+/// How seriously a [`Finding`] should be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Worth fixing, but clients can still use the server.
+    Warning,
+    /// Clients will fail or misbehave.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem found by the self-check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// What the problem is about, e.g. `tool "search"` or `tools/list`.
+    pub subject: String,
+    /// Description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.subject, self.message)
+    }
+}
+
+/// Options for [`Server::self_check_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfCheckConfig {
+    max_schema_bytes: usize,
+    require_descriptions: bool,
+    warnings_as_errors: bool,
+}
+
+impl Default for SelfCheckConfig {
+    fn default() -> Self {
+        Self {
+            max_schema_bytes: DEFAULT_MAX_SCHEMA_BYTES,
+            require_descriptions: true,
+            warnings_as_errors: false,
+        }
+    }
+}
+
+impl SelfCheckConfig {
+    /// Create the default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report schemas whose serialized size exceeds `bytes`.
+    #[must_use]
+    pub const fn max_schema_bytes(mut self, bytes: usize) -> Self {
+        self.max_schema_bytes = bytes;
+        self
+    }
+
+    /// Whether tools and prompts without a description are reported
+    /// (default: `true`).
+    #[must_use]
+    pub const fn require_descriptions(mut self, require: bool) -> Self {
+        self.require_descriptions = require;
+        self
+    }
+
+    /// Report every warning as an error, so that
+    /// [`SelfCheckReport::into_result`] fails on them too.
+    #[must_use]
+    pub const fn warnings_as_errors(mut self, strict: bool) -> Self {
+        self.warnings_as_errors = strict;
+        self
+    }
+}
+
+/// The outcome of a self-check.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelfCheckReport {
+    /// Number of tools listed.
+    pub tools: usize,
+    /// Number of resources listed.
+    pub resources: usize,
+    /// Number of resource templates listed.
+    pub resource_templates: usize,
+    /// Number of prompts listed.
+    pub prompts: usize,
+    /// Problems found, in discovery order.
+    pub findings: Vec<Finding>,
+}
+
+impl SelfCheckReport {
+    /// Findings with [`Severity::Error`].
+    pub fn errors(&self) -> impl Iterator<Item = &Finding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+    }
+
+    /// Findings with [`Severity::Warning`].
+    pub fn warnings(&self) -> impl Iterator<Item = &Finding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == Severity::Warning)
+    }
+
+    /// Whether any error was found.
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Whether nothing at all was found.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Return the report if it has no errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an internal error listing every finding if any is an error.
+    pub fn into_result(self) -> Result<Self, McpError> {
+        if self.has_errors() {
+            Err(McpError::internal(format!("self-check failed: {self}")))
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+impl fmt::Display for SelfCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} tools, {} resources, {} resource templates, {} prompts; {} errors, {} warnings",
+            self.tools,
+            self.resources,
+            self.resource_templates,
+            self.prompts,
+            self.errors().count(),
+            self.warnings().count(),
+        )?;
+        for finding in &self.findings {
+            write!(f, "\n  {finding}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<H, T, R, P, K> Server<H, T, R, P, K>
+where
+    Self: RequestRouter + HasCapabilities,
+{
+    /// Run the startup self-check with the default configuration.
+    ///
+    /// See the [module documentation](crate::self_check).
+    pub async fn self_check(&self) -> SelfCheckReport {
+        self.self_check_with(&SelfCheckConfig::default()).await
+    }
+
+    /// Run the startup self-check with the given configuration.
+    pub async fn self_check_with(&self, config: &SelfCheckConfig) -> SelfCheckReport {
+        self_check(self, config).await
+    }
+}
+
+/// Run the self-check against any router, e.g. a server with middleware
+/// applied.
+pub async fn self_check<S>(server: &S, config: &SelfCheckConfig) -> SelfCheckReport
+where
+    S: RequestRouter + HasCapabilities,
+{
+    let caps = server.server_capabilities();
+    // Declare every client capability so tools gated on `requires` are listed.
+    let client_caps = ClientCapabilities::default()
+        .with_roots()
+        .with_sampling()
+        .with_elicitation();
+    let request_id = RequestId::String("__self_check__".to_string());
+    let peer = NoOpPeer;
+    let ctx = Context::new(
+        &request_id,
+        None,
+        &client_caps,
+        &caps,
+        ProtocolVersion::LATEST,
+        &peer,
+    );
+    let mut check = Checker {
+        config,
+        report: SelfCheckReport::default(),
+    };
+
+    if caps.has_tools() {
+        let tools: Vec<Tool> = check.list(server, &ctx, "tools/list", "tools").await;
+        check.report.tools = tools.len();
+        check.tools(&tools);
+    }
+    if caps.has_resources() {
+        let resources: Vec<Resource> = check
+            .list(server, &ctx, "resources/list", "resources")
+            .await;
+        check.report.resources = resources.len();
+        check.duplicates("resource", resources.iter().map(|r| r.uri.as_str()));
+
+        let templates: Vec<ResourceTemplate> = check
+            .list(
+                server,
+                &ctx,
+                "resources/templates/list",
+                "resourceTemplates",
+            )
+            .await;
+        check.report.resource_templates = templates.len();
+        check.duplicates(
+            "resource template",
+            templates.iter().map(|t| t.uri_template.as_str()),
+        );
+    }
+    if caps.has_prompts() {
+        let prompts: Vec<Prompt> = check.list(server, &ctx, "prompts/list", "prompts").await;
+        check.report.prompts = prompts.len();
+        check.duplicates("prompt", prompts.iter().map(|p| p.name.as_str()));
+        for prompt in &prompts {
+            check.description(
+                &format!("prompt {:?}", prompt.name),
+                prompt.description.as_deref(),
+            );
+        }
+    }
+    check.report
+}
+
+/// Fetch every page of a list method through the server's routing.
+///
+/// Returns the items gathered so far together with a description of the
+/// failure, if any page could not be fetched or parsed.
+pub(crate) async fn list_all<S: RequestRouter, T: DeserializeOwned>(
+    server: &S,
+    ctx: &Context<'_>,
+    method: &str,
+    field: &str,
+) -> (Vec<T>, Option<String>) {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut seen = HashSet::new();
+    for _ in 0..MAX_PAGES {
+        let params = cursor.as_ref().map(|c| serde_json::json!({ "cursor": c }));
+        let result = match server.route(method, params.as_ref(), ctx).await {
+            Ok(result) => result,
+            Err(e) => return (items, Some(format!("request failed: {e}"))),
+        };
+        let page = result.get(field).cloned().unwrap_or(Value::Null);
+        match serde_json::from_value::<Vec<T>>(page) {
+            Ok(page) => items.extend(page),
+            Err(e) => return (items, Some(format!("malformed result: {e}"))),
+        }
+        cursor = result
+            .get("nextCursor")
+            .and_then(Value::as_str)
+            .map(String::from);
+        match &cursor {
+            None => return (items, None),
+            Some(next) if !seen.insert(next.clone()) => {
+                return (items, Some(format!("cursor {next:?} repeats")));
+            }
+            Some(_) => {}
+        }
+    }
+    (
+        items,
+        Some(format!(
+            "pagination did not finish within {MAX_PAGES} pages"
+        )),
+    )
+}
+
+struct Checker<'c> {
+    config: &'c SelfCheckConfig,
+    report: SelfCheckReport,
+}
+
+impl Checker<'_> {
+    fn push(&mut self, severity: Severity, subject: impl Into<String>, message: impl Into<String>) {
+        let severity = if self.config.warnings_as_errors {
+            Severity::Error
+        } else {
+            severity
+        };
+        self.report.findings.push(Finding {
+            severity,
+            subject: subject.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Fetch every page of a list method, recording failures as errors.
+    async fn list<S: RequestRouter, T: DeserializeOwned>(
+        &mut self,
+        server: &S,
+        ctx: &Context<'_>,
+        method: &str,
+        field: &str,
+    ) -> Vec<T> {
+        let (items, error) = list_all(server, ctx, method, field).await;
+        if let Some(message) = error {
+            self.push(Severity::Error, method, message);
+        }
+        items
+    }
+
+    fn duplicates<'a>(&mut self, kind: &str, keys: impl Iterator<Item = &'a str>) {
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for key in keys {
+            if !seen.insert(key) && reported.insert(key) {
+                self.push(
+                    Severity::Error,
+                    format!("{kind} {key:?}"),
+                    "registered more than once",
+                );
+            }
+        }
+    }
+
+    fn description(&mut self, subject: &str, description: Option<&str>) {
+        let missing = description.is_none_or(|d| d.trim().is_empty());
+        if self.config.require_descriptions && missing {
+            self.push(Severity::Warning, subject, "has no description");
+        }
+    }
+
+    fn tools(&mut self, tools: &[Tool]) {
+        self.duplicates("tool", tools.iter().map(|t| t.name.as_str()));
+        for tool in tools {
+            let subject = format!("tool {:?}", tool.name);
+            if !is_valid_tool_name(&tool.name) {
+                self.push(
+                    Severity::Warning,
+                    &subject,
+                    "name should be 1-128 characters of A-Z, a-z, 0-9, '_', '-' or '.'",
+                );
+            }
+            self.description(&subject, tool.description.as_deref());
+            self.schema(&subject, "inputSchema", &tool.input_schema);
+            if let Some(output) = &tool.output_schema {
+                self.schema(&subject, "outputSchema", output);
+            }
+        }
+    }
+
+    fn schema(&mut self, subject: &str, which: &str, schema: &Value) {
+        if schema.get("type").and_then(Value::as_str) != Some("object") {
+            self.push(
+                Severity::Error,
+                subject,
+                format!("{which} must be a JSON Schema with \"type\": \"object\""),
+            );
+        }
+        #[cfg(feature = "schema-validation")]
+        if let Err(e) = jsonschema::validator_for(schema) {
+            self.push(
+                Severity::Error,
+                subject,
+                format!("{which} does not compile: {e}"),
+            );
+        }
+        let size = serde_json::to_vec(schema).map_or(0, |bytes| bytes.len());
+        if size > self.config.max_schema_bytes {
+            self.push(
+                Severity::Warning,
+                subject,
+                format!(
+                    "{which} is {size} bytes, over the {} byte limit",
+                    self.config.max_schema_bytes
+                ),
+            );
+        }
+    }
+}
+
+fn is_valid_tool_name(name: &str) -> bool {
+    (1..=128).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServerBuilder;
+    use crate::handler::{ServerHandler, ToolHandler};
+    use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+    use mcpkit_core::types::ToolOutput;
+
+    struct Tools(Vec<Tool>);
+
+    impl ServerHandler for Tools {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("self-check", "1.0.0")
+        }
+        fn capabilities(&self) -> ServerCapabilities {
+            ServerCapabilities::new().with_tools()
+        }
+    }
+
+    impl ToolHandler for Tools {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(self.0.clone())
+        }
+        async fn call_tool(
+            &self,
+            _name: &str,
+            _args: mcpkit_core::types::Object,
+            _ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            Ok(ToolOutput::text("ok"))
+        }
+    }
+
+    fn tool(name: &str) -> Tool {
+        Tool::new(name).description("does things")
+    }
+
+    async fn check(tools: Vec<Tool>, config: &SelfCheckConfig) -> SelfCheckReport {
+        let handler = Tools(tools.clone());
+        let server = ServerBuilder::new(Tools(tools)).with_tools(handler).build();
+        server.self_check_with(config).await
+    }
+
+    #[tokio::test]
+    async fn well_formed_server_is_clean() {
+        let report = check(vec![tool("search"), tool("fetch")], &SelfCheckConfig::new()).await;
+        assert!(report.is_clean(), "{report}");
+        assert_eq!(report.tools, 2);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[tokio::test]
+    async fn duplicates_and_bad_schemas_are_errors() {
+        let mut broken = tool("broken");
+        broken.input_schema = serde_json::json!({ "type": "string" });
+        let report = check(
+            vec![tool("search"), tool("search"), broken],
+            &SelfCheckConfig::new(),
+        )
+        .await;
+
+        let errors: Vec<_> = report.errors().map(|f| f.subject.as_str()).collect();
+        assert_eq!(errors, [r#"tool "search""#, r#"tool "broken""#]);
+        let message = report.into_result().unwrap_err().to_string();
+        assert!(message.contains("registered more than once"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn warnings_can_be_escalated() {
+        let tools = vec![Tool::new("has space"), tool("big")];
+        let config = SelfCheckConfig::new().max_schema_bytes(10);
+        let report = check(tools.clone(), &config).await;
+        assert!(!report.has_errors(), "{report}");
+        // Bad name, missing description, and two oversized schemas.
+        assert_eq!(report.warnings().count(), 4, "{report}");
+
+        let strict = check(tools, &config.warnings_as_errors(true)).await;
+        assert_eq!(strict.errors().count(), 4);
+        assert!(strict.into_result().is_err());
+    }
+}
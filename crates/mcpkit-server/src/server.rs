@@ -37,17 +37,23 @@ use crate::context::{CancellationToken, Context, ContextData, Peer};
 use crate::dispatch::{PromptSlot, ResourceSlot, TaskSlot, ToolSlot};
 use crate::handler::ServerHandler;
 use crate::router::{route_prompts, route_resources, route_tasks, route_tools};
+use crate::shutdown::ShutdownHandle;
+use crate::subscription::SubscriptionManager;
 use futures::channel::oneshot;
-use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+use mcpkit_core::capability::{ClientCapabilities, ClientCapability, ServerCapabilities};
+use mcpkit_core::compat::adapt_result;
 use mcpkit_core::error::McpError;
 use mcpkit_core::protocol::{Message, Notification, ProgressToken, Request, RequestId, Response};
 use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_core::types::Meta;
 use mcpkit_transport::Transport;
+use mcpkit_transport::ping::{PingConfig, PingExit, PingHealth, PingSupervisor};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
 
 /// State for a running server.
 pub struct ServerState {
@@ -67,8 +73,13 @@ pub struct ServerState {
     /// Response channels for in-flight server-initiated (outbound) requests,
     /// keyed by the outbound request id.
     pending_requests: RwLock<HashMap<RequestId, oneshot::Sender<Response>>>,
+    /// Resource URIs the client has subscribed to.
+    pub subscriptions: SubscriptionManager,
     /// Monotonic counter for allocating outbound request ids.
     outbound_id: AtomicU64,
+    /// Whether the client still answers pings (see
+    /// [`RuntimeConfig::ping_supervision`]).
+    ping_health: PingHealth,
 }
 
 impl ServerState {
@@ -82,7 +93,9 @@ impl ServerState {
             cancellations: RwLock::new(HashMap::new()),
             negotiated_version: RwLock::new(None),
             pending_requests: RwLock::new(HashMap::new()),
+            subscriptions: SubscriptionManager::new(),
             outbound_id: AtomicU64::new(1),
+            ping_health: PingHealth::default(),
         }
     }
 
@@ -108,6 +121,33 @@ impl ServerState {
         }
     }
 
+    /// Send `ping` to the client and wait for its response.
+    async fn ping<T>(&self, transport: &T) -> Result<(), McpError>
+    where
+        T: Transport,
+        T::Error: Into<McpError>,
+    {
+        let id = self.next_outbound_id();
+        let rx = self.register_outbound(id.clone());
+        // Dropped with this future, so a ping abandoned on timeout does not
+        // stay registered.
+        let _registered = OutboundGuard {
+            state: self,
+            id: id.clone(),
+        };
+        transport
+            .send(Message::Request(Request::new("ping", id)))
+            .await
+            .map_err(Into::into)?;
+        let response = rx
+            .await
+            .map_err(|_| McpError::internal("response channel closed before a reply arrived"))?;
+        match response.error {
+            Some(error) => Err(McpError::JsonRpc(error)),
+            None => Ok(()),
+        }
+    }
+
     /// Route an inbound response to the outbound request that is waiting for it.
     pub(crate) fn route_response(&self, response: Response) {
         let sender = self
@@ -212,6 +252,18 @@ impl ServerState {
     }
 }
 
+/// Removes an outbound request from [`ServerState`] when dropped.
+struct OutboundGuard<'a> {
+    state: &'a ServerState,
+    id: RequestId,
+}
+
+impl Drop for OutboundGuard<'_> {
+    fn drop(&mut self) {
+        self.state.remove_outbound(&self.id);
+    }
+}
+
 /// Shared state a [`TransportPeer`] needs to make server-initiated requests:
 /// the pending-request registry (on [`ServerState`]) and the outbound timeout.
 #[derive(Clone)]
@@ -319,6 +371,13 @@ where
             }
         })
     }
+
+    fn to_shared(&self) -> Option<Arc<dyn Peer>> {
+        Some(Arc::new(Self {
+            transport: Arc::clone(&self.transport),
+            outbound: self.outbound.clone(),
+        }))
+    }
 }
 
 /// A cloneable handle for sending server-initiated notifications from outside a
@@ -343,6 +402,8 @@ where
 #[derive(Clone)]
 pub struct ServerNotifier {
     peer: Arc<dyn Peer>,
+    subscriptions: SubscriptionManager,
+    state: Arc<ServerState>,
 }
 
 impl ServerNotifier {
@@ -418,15 +479,55 @@ impl ServerNotifier {
 
     /// Notify the client that a subscribed resource was updated.
     ///
+    /// Nothing is sent unless the client subscribed to `uri` via
+    /// `resources/subscribe`. Returns whether a notification was sent.
+    ///
     /// # Errors
     ///
     /// Returns an error if the notification could not be sent.
-    pub async fn resource_updated(&self, uri: impl Into<String>) -> Result<(), McpError> {
-        self.notify(
-            crate::router::notifications::RESOURCES_UPDATED,
-            Some(serde_json::json!({ "uri": uri.into() })),
-        )
-        .await
+    pub async fn resource_updated(&self, uri: impl Into<String>) -> Result<bool, McpError> {
+        self.subscriptions
+            .notify_updated(self.peer.as_ref(), &uri.into())
+            .await
+    }
+
+    /// Replace the client capabilities recorded for this connection.
+    ///
+    /// Capabilities are normally fixed at initialization. Use this when the
+    /// host learns of a change out of band, e.g. the user enabled sampling
+    /// mid-session. Tools gated on client capabilities (`requires = [..]`)
+    /// may appear or disappear as a result, so when any capability changed
+    /// after initialization and the server declared `tools.listChanged`,
+    /// `notifications/tools/list_changed` is sent.
+    ///
+    /// Returns the capabilities that were gained or lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification could not be sent.
+    pub async fn update_client_capabilities(
+        &self,
+        caps: ClientCapabilities,
+    ) -> Result<Vec<ClientCapability>, McpError> {
+        let changed = self.state.client_caps().changed_capabilities(&caps);
+        self.state.set_client_caps(caps);
+        let list_changed = self
+            .state
+            .server_caps
+            .tools
+            .as_ref()
+            .is_some_and(|t| t.list_changed == Some(true));
+        if !changed.is_empty() && list_changed && self.state.is_initialized() {
+            tracing::debug!(?changed, "client capabilities changed");
+            self.tools_list_changed().await?;
+        }
+        Ok(changed)
+    }
+
+    /// The connection's resource subscriptions.
+    #[must_use]
+    pub const fn subscriptions(&self) -> &SubscriptionManager {
+        &self.subscriptions
     }
 
     /// Notify the client that a URL-mode elicitation's out-of-band interaction
@@ -460,6 +561,31 @@ pub struct RuntimeConfig {
     /// Retention (milliseconds) applied to a task whose `tools/call` omits a
     /// `ttl`. `None` means unlimited (such tasks are never TTL-evicted).
     pub default_task_ttl_ms: Option<u64>,
+    /// How long a handler may keep running after its request is cancelled
+    /// (e.g. to clean up after `ctx.cancelled()` resolves) before its future
+    /// is dropped.
+    pub cancellation_grace: Duration,
+    /// How long a shutdown waits for in-flight requests and running tasks
+    /// before cancelling them (see [`crate::shutdown`]).
+    pub shutdown_timeout: Duration,
+    /// Results of `tools/call`s that carry an idempotency key, so a client's
+    /// retry gets the original result instead of running the tool twice.
+    /// Tools marked idempotent are not tracked. `None` disables it; share one
+    /// cache across the runtimes that serve reconnecting clients (see
+    /// [`crate::idempotency`]).
+    pub idempotency: Option<crate::idempotency::IdempotencyCache>,
+    /// How tool, resource, and prompt invocations are traced, including
+    /// whether their arguments are captured (see [`crate::invocation`]).
+    pub invocation_tracing: crate::invocation::InvocationTracing,
+    /// Ping the client periodically and close the connection once it stops
+    /// answering. `None` (the default) disables it; see
+    /// [`ServerRuntime::ping_health`].
+    pub ping_supervision: Option<PingConfig>,
+    /// Durable store the runtime's tasks are written through to, so
+    /// `tasks/get` and `tasks/result` answer for tasks created before a
+    /// restart. `None` (the default) keeps tasks in memory only; see
+    /// [`TaskManager::with_store`](crate::capability::tasks::TaskManager::with_store).
+    pub task_store: Option<Arc<dyn crate::capability::tasks::TaskStore>>,
 }
 
 impl Default for RuntimeConfig {
@@ -469,6 +595,12 @@ impl Default for RuntimeConfig {
             max_concurrent_requests: 100,
             outbound_request_timeout: Duration::from_secs(60),
             default_task_ttl_ms: Some(crate::capability::tasks::DEFAULT_TASK_TTL_MS),
+            cancellation_grace: Duration::from_secs(5),
+            shutdown_timeout: crate::shutdown::DEFAULT_SHUTDOWN_TIMEOUT,
+            idempotency: Some(crate::idempotency::IdempotencyCache::default()),
+            invocation_tracing: crate::invocation::InvocationTracing::default(),
+            ping_supervision: None,
+            task_store: None,
         }
     }
 }
@@ -489,6 +621,8 @@ where
     task_store: Arc<crate::capability::tasks::TaskManager>,
     /// Runtime configuration (concurrency limit, etc.).
     config: RuntimeConfig,
+    /// Stops the message loop gracefully when triggered.
+    shutdown: ShutdownHandle,
 }
 
 /// A task-augmented `tools/call` whose tool runs in the background after the
@@ -511,6 +645,14 @@ enum TaskBegin {
     Deferred(Box<BackgroundExec>),
 }
 
+/// The run loop's in-flight requests, background task executions, and
+/// notification hooks.
+type WorkSets<F1, F2, F3> = (
+    futures::stream::FuturesUnordered<F1>,
+    futures::stream::FuturesUnordered<F2>,
+    futures::stream::FuturesUnordered<F3>,
+);
+
 /// Make progress on in-flight requests and background task executions, returning
 /// any new background work an in-flight request just produced. Background tasks
 /// are polled here but do not count against the request concurrency limit.
@@ -582,12 +724,52 @@ where
     pub fn notifier(&self) -> ServerNotifier {
         ServerNotifier {
             peer: Arc::new(TransportPeer::new(self.transport.clone())),
+            subscriptions: self.state.subscriptions.clone(),
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Whether the client still answers pings, or `None` unless
+    /// [`RuntimeConfig::ping_supervision`] is set.
+    #[must_use]
+    pub fn ping_health(&self) -> Option<&PingHealth> {
+        self.config
+            .ping_supervision
+            .as_ref()
+            .map(|_| &self.state.ping_health)
+    }
+
+    /// Ping the client per [`RuntimeConfig::ping_supervision`], resolving only
+    /// once it stops answering.
+    async fn supervise_pings(&self) {
+        if let Some(config) = self.config.ping_supervision.clone() {
+            let supervisor = PingSupervisor::with_health(config, self.state.ping_health.clone());
+            if supervisor.run(|| self.state.ping(&*self.transport)).await == PingExit::Unhealthy {
+                return;
+            }
         }
+        // Unsupervised, or the transport closed: the run loop notices on its own.
+        std::future::pending::<()>().await;
+    }
+
+    /// Get a [`ShutdownHandle`] that stops [`run`](Self::run) gracefully.
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Stop when `shutdown` is triggered, instead of this runtime's own handle.
+    #[must_use]
+    pub fn with_shutdown(mut self, shutdown: ShutdownHandle) -> Self {
+        self.shutdown = shutdown;
+        self
     }
 
     /// Run the server message loop.
     ///
-    /// This method runs until the connection is closed or an error occurs.
+    /// This method runs until the connection is closed, an error occurs, or
+    /// the [`shutdown_handle`](Self::shutdown_handle) is triggered. A
+    /// shutdown drains in-flight work first; see [`crate::shutdown`].
     ///
     /// Requests are processed concurrently (interleaved on this task) up to
     /// `config.max_concurrent_requests` in flight at once; once that limit is
@@ -596,6 +778,10 @@ where
     /// panicking handler returns a JSON-RPC internal error instead of tearing
     /// down the connection. Notification hooks run concurrently too, so a hook
     /// that issues its own server-to-client request does not deadlock the loop.
+    ///
+    /// With [`RuntimeConfig::ping_supervision`] set, the client is pinged
+    /// alongside the loop, and a client that stops answering has its
+    /// transport closed as if it had disconnected.
     pub async fn run(&self) -> Result<(), McpError> {
         use futures::future::{Either, select};
         use futures::stream::{FuturesUnordered, StreamExt};
@@ -607,6 +793,8 @@ where
             // Boxed: `BackgroundExec` is large (it owns a `ContextData`), so an
             // unboxed variant makes `Step` lopsided (`clippy::large_enum_variant`).
             Progress(Option<Box<BackgroundExec>>),
+            Shutdown,
+            Unhealthy,
         }
 
         let max = self.config.max_concurrent_requests.max(1);
@@ -623,6 +811,8 @@ where
         // handler parked on its own server-initiated request (which needs an
         // inbound response to complete) cannot deadlock the loop.
         let mut queued: std::collections::VecDeque<Request> = std::collections::VecDeque::new();
+        let mut shutdown = self.shutdown.wait();
+        let mut supervision = std::pin::pin!(self.supervise_pings());
 
         let outcome = loop {
             // Dispatch queued requests while concurrency slots are free.
@@ -636,13 +826,10 @@ where
             // Always receive (so responses to our own outbound requests are
             // routed even when every slot is parked) while making progress on
             // in-flight requests and background tasks.
-            let step = if in_flight.is_empty() && background.is_empty() && notifications.is_empty()
-            {
-                match self.transport.recv().await {
-                    Ok(opt) => Step::Message(opt),
-                    Err(e) => break Err(e.into()),
+            let next = async {
+                if in_flight.is_empty() && background.is_empty() && notifications.is_empty() {
+                    return self.transport.recv().await.map(Step::Message);
                 }
-            } else {
                 let recv = std::pin::pin!(self.transport.recv());
                 let progress = std::pin::pin!(drive_sets(
                     &mut in_flight,
@@ -650,14 +837,31 @@ where
                     &mut notifications
                 ));
                 match select(recv, progress).await {
-                    Either::Left((Ok(opt), _)) => Step::Message(opt),
-                    Either::Left((Err(e), _)) => break Err(e.into()),
-                    Either::Right((maybe_exec, _)) => Step::Progress(maybe_exec.map(Box::new)),
+                    Either::Left((opt, _)) => opt.map(Step::Message),
+                    Either::Right((maybe_exec, _)) => Ok(Step::Progress(maybe_exec.map(Box::new))),
+                }
+            };
+            let stop = async {
+                match select(&mut shutdown, supervision.as_mut()).await {
+                    Either::Left(((), _)) => Step::Shutdown,
+                    Either::Right(((), _)) => Step::Unhealthy,
                 }
             };
+            let step = match select(std::pin::pin!(stop), std::pin::pin!(next)).await {
+                Either::Left((step, _)) => step,
+                Either::Right((Ok(step), _)) => step,
+                Either::Right((Err(e), _)) => break Err(e.into()),
+            };
 
             // Borrows on the future sets are released here, so we may push work.
             match step {
+                Step::Shutdown => break Ok(true),
+                Step::Unhealthy => {
+                    if let Err(e) = self.transport.close().await {
+                        tracing::debug!(error = %e.into(), "Failed to close unhealthy connection");
+                    }
+                    break Ok(false);
+                }
                 Step::Progress(Some(exec)) => {
                     background.push(self.run_task(*exec));
                 }
@@ -681,37 +885,203 @@ where
                 }
                 Step::Message(None) => {
                     tracing::info!("Connection closed");
-                    break Ok(());
+                    break Ok(false);
                 }
             }
         };
 
-        // The connection is going away: fail any in-flight outbound requests so
-        // handlers parked on them unblock, then drain the handlers so their
-        // responses are delivered before we return. Background tasks are drained
-        // too so their results are stored before we exit.
-        self.state.fail_pending_requests();
-        while in_flight.next().await.is_some() {}
-        while background.next().await.is_some() {}
-        while notifications.next().await.is_some() {}
+        if matches!(outcome, Ok(true)) {
+            let start = (
+                |exec| self.run_task(exec),
+                |notification| self.handle_notification(notification),
+            );
+            self.shut_down((in_flight, background, notifications), queued, start)
+                .await;
+        } else {
+            // The connection is going away: fail any in-flight outbound requests
+            // so handlers parked on them unblock, then drain the handlers so
+            // their responses are delivered before we return. Background tasks
+            // are drained too so their results are stored before we exit.
+            self.state.fail_pending_requests();
+            while in_flight.next().await.is_some() {}
+            while background.next().await.is_some() {}
+            while notifications.next().await.is_some() {}
+        }
+        self.state.subscriptions.clear();
 
         if let Err(ref err) = outcome {
             tracing::error!(error = %err, "Transport error");
         }
-        outcome
+        outcome.map(|_| ())
+    }
+
+    /// Shut down gracefully: reject queued and new requests, give in-flight
+    /// work `shutdown_timeout` to finish, cancel what remains, and close the
+    /// transport.
+    ///
+    /// `start` holds the functions the run loop uses to start background
+    /// task executions and notification hooks.
+    async fn shut_down<F1, F2, F3>(
+        &self,
+        mut sets: WorkSets<F1, F2, F3>,
+        queued: std::collections::VecDeque<Request>,
+        start: (impl Fn(BackgroundExec) -> F2, impl Fn(Notification) -> F3),
+    ) where
+        F1: std::future::Future<Output = Option<BackgroundExec>>,
+        F2: std::future::Future<Output = ()>,
+        F3: std::future::Future<Output = Result<(), McpError>>,
+    {
+        use mcpkit_transport::runtime::timeout;
+
+        tracing::info!(
+            in_flight = sets.0.len() + queued.len(),
+            "Shutting down; draining in-flight requests"
+        );
+        for request in queued {
+            self.reject_request(request).await;
+        }
+
+        let mut connected = true;
+        let drain = self.drain(&mut sets, &mut connected, &start);
+        if timeout(self.config.shutdown_timeout, drain).await.is_err() {
+            tracing::warn!(
+                timeout = ?self.config.shutdown_timeout,
+                "Shutdown timeout elapsed; cancelling remaining work"
+            );
+            self.cancel_all();
+            let drain = self.drain(&mut sets, &mut connected, &start);
+            let _ = timeout(self.config.cancellation_grace, drain).await;
+        }
+
+        self.state.fail_pending_requests();
+        if let Err(e) = self.transport.close().await {
+            let err: McpError = e.into();
+            tracing::debug!(error = %err, "Failed to close transport");
+        }
+    }
+
+    /// Run in-flight work and running tasks to completion while still
+    /// routing responses and notifications, answering new requests with an
+    /// error.
+    async fn drain<F1, F2, F3>(
+        &self,
+        (in_flight, background, notifications): &mut WorkSets<F1, F2, F3>,
+        connected: &mut bool,
+        (run_task, handle_notification): &(
+            impl Fn(BackgroundExec) -> F2,
+            impl Fn(Notification) -> F3,
+        ),
+    ) where
+        F1: std::future::Future<Output = Option<BackgroundExec>>,
+        F2: std::future::Future<Output = ()>,
+        F3: std::future::Future<Output = Result<(), McpError>>,
+    {
+        use futures::future::{Either, join_all, select};
+
+        enum Step {
+            Message(Option<Message>),
+            Progress(Option<Box<BackgroundExec>>),
+            Closed,
+        }
+
+        loop {
+            let idle = in_flight.is_empty() && background.is_empty() && notifications.is_empty();
+            let running = self.running_tasks();
+            if idle && running.is_empty() {
+                return;
+            }
+            let step = {
+                let work = std::pin::pin!(async {
+                    if idle {
+                        join_all(running.iter().map(|id| self.task_store.wait_terminal(id))).await;
+                        None
+                    } else {
+                        drive_sets(in_flight, background, notifications).await
+                    }
+                });
+                if *connected {
+                    let recv = std::pin::pin!(self.transport.recv());
+                    match select(recv, work).await {
+                        Either::Left((Ok(message), _)) => Step::Message(message),
+                        Either::Left((Err(e), _)) => {
+                            let err: McpError = e.into();
+                            tracing::debug!(error = %err, "Transport error while draining");
+                            Step::Closed
+                        }
+                        Either::Right((exec, _)) => Step::Progress(exec.map(Box::new)),
+                    }
+                } else {
+                    Step::Progress(work.await.map(Box::new))
+                }
+            };
+
+            match step {
+                Step::Message(Some(Message::Request(request))) => {
+                    self.reject_request(request).await;
+                }
+                Step::Message(Some(Message::Notification(notification))) => {
+                    notifications.push(handle_notification(notification));
+                }
+                Step::Message(Some(Message::Response(response))) => {
+                    self.state.route_response(response);
+                }
+                Step::Message(None) | Step::Closed => *connected = false,
+                Step::Progress(Some(exec)) => background.push(run_task(*exec)),
+                Step::Progress(None) => {}
+            }
+        }
+    }
+
+    /// Ids of the tasks in the store that have not reached a terminal status.
+    fn running_tasks(&self) -> Vec<mcpkit_core::types::TaskId> {
+        self.task_store
+            .list()
+            .into_iter()
+            .filter(|task| !task.status.is_terminal())
+            .map(|task| task.task_id)
+            .collect()
+    }
+
+    /// Cancel every in-flight request and running task.
+    fn cancel_all(&self) {
+        if let Ok(cancellations) = self.state.cancellations.read() {
+            for token in cancellations.values() {
+                token.cancel();
+            }
+        }
+        for id in self.running_tasks() {
+            let _ = self.task_store.cancel(&id);
+        }
+    }
+
+    /// Answer a request received during shutdown with an error.
+    async fn reject_request(&self, request: Request) {
+        tracing::debug!(method = %request.method, id = %request.id, "Rejecting request during shutdown");
+        let response = Response::error(
+            request.id,
+            McpError::internal("Server is shutting down").into(),
+        );
+        if let Err(e) = self.transport.send(Message::Response(response)).await {
+            let err: McpError = e.into();
+            tracing::debug!(error = %err, "Failed to send shutdown rejection");
+        }
     }
 
     /// Compute the result for a request without sending it.
-    async fn compute_response(&self, request: &Request) -> Result<serde_json::Value, McpError> {
+    async fn compute_response(
+        &self,
+        request: &Request,
+        cancel: &CancellationToken,
+    ) -> Result<serde_json::Value, McpError> {
         match request.method.as_ref() {
             "initialize" => self.handle_initialize(request).await,
             // `ping` is a liveness check and is valid at any time, including
             // before the initialize handshake completes.
-            "ping" => self.route_request(request).await,
+            "ping" => self.route_request(request, cancel).await,
             _ if !self.state.is_initialized() => {
                 Err(McpError::invalid_request("Server not initialized"))
             }
-            _ => self.route_request(request).await,
+            _ => self.route_request(request, cancel).await,
         }
     }
 
@@ -735,12 +1105,39 @@ where
             TaskBegin::NotApplicable => {}
         }
 
-        let computed = AssertUnwindSafe(self.compute_response(&request))
-            .catch_unwind()
-            .await;
+        // Register the request so a matching `notifications/cancelled` trips
+        // its token. `initialize` must not be cancelled, so it isn't registered.
+        let cancel = CancellationToken::new();
+        let cancel_key = id.to_string();
+        let cancellable = request.method.as_ref() != "initialize";
+        if cancellable {
+            self.state
+                .register_cancellation(&cancel_key, cancel.clone());
+        }
+
+        let computed = AssertUnwindSafe(self.compute_response(&request, &cancel)).catch_unwind();
+        let computed = run_cancellable(computed, &cancel, self.config.cancellation_grace).await;
+        if cancellable {
+            self.state.remove_cancellation(&cancel_key);
+        }
+
+        // Per the spec, a cancelled request gets no response.
+        let Some(computed) = computed else {
+            tracing::debug!(method = %request.method, id = %id, "Request cancelled; response dropped");
+            return None;
+        };
 
         let response_msg = match computed {
-            Ok(Ok(result)) => Response::success(id, result),
+            Ok(Ok(mut result)) => {
+                // Before initialize there is no negotiated version yet; the
+                // initialize result itself is encoded for the one it chose.
+                let version = self
+                    .state
+                    .protocol_version()
+                    .unwrap_or(ProtocolVersion::LATEST);
+                adapt_result(&request.method, &mut result, version);
+                Response::success(id, result)
+            }
             Ok(Err(e)) => Response::error(id, e.into()),
             Err(panic) => {
                 let detail = panic_message(&*panic);
@@ -893,7 +1290,9 @@ where
             ctx_data.protocol_version,
             &peer,
             cancel,
-        );
+        )
+        .with_subscriptions(&self.state.subscriptions)
+        .with_task_store(&self.task_store);
         match self.server.call_tool_json(&name, args, &ctx).await {
             // Per spec, a tool result with `isError: true` moves the task to
             // `failed`, while `tasks/result` still returns that result.
@@ -915,6 +1314,23 @@ where
                 let _ = handle.fail_with_error(e.into());
             }
         }
+
+        // Tell the client the task is done so it need not wait for its next poll.
+        if let Some(task) = handle.task() {
+            let params = mcpkit_core::types::TaskStatusNotificationParams::from(task);
+            let notification = serde_json::to_value(params)
+                .map(|params| Notification::with_params("notifications/tasks/status", params));
+            if let Ok(notification) = notification {
+                if let Err(e) = self
+                    .transport
+                    .send(Message::Notification(notification))
+                    .await
+                {
+                    let err: McpError = e.into();
+                    tracing::debug!(error = %err, "Failed to send task status notification");
+                }
+            }
+        }
     }
 
     /// Serve task queries from the built-in task store. Returns `None` for
@@ -982,11 +1398,14 @@ where
         }
 
         // Build response with negotiated version (serialized to string by serde)
-        let result = serde_json::json!({
+        let mut result = serde_json::json!({
             "protocolVersion": negotiated_version.as_str(),
             "serverInfo": self.server.server_info(),
             "capabilities": self.state.server_caps
         });
+        if let Some(instructions) = self.server.instructions() {
+            result["instructions"] = instructions.into();
+        }
 
         self.state.set_initialized();
 
@@ -994,7 +1413,11 @@ where
     }
 
     /// Route a request to the appropriate handler.
-    async fn route_request(&self, request: &Request) -> Result<serde_json::Value, McpError> {
+    async fn route_request(
+        &self,
+        request: &Request,
+        cancel: &CancellationToken,
+    ) -> Result<serde_json::Value, McpError> {
         let method = request.method.as_ref();
         let params = request.params.as_ref();
 
@@ -1020,14 +1443,6 @@ where
             .protocol_version()
             .unwrap_or(ProtocolVersion::LATEST);
 
-        // Register a cancellation token for this request so a matching
-        // `notifications/cancelled` trips the handler's `ctx.cancel`. The token
-        // is removed once the handler returns.
-        let cancel = CancellationToken::new();
-        let cancel_key = request.id.to_string();
-        self.state
-            .register_cancellation(&cancel_key, cancel.clone());
-
         let ctx = Context::with_cancellation(
             &request.id,
             progress_token.as_ref(),
@@ -1035,13 +1450,24 @@ where
             &self.state.server_caps,
             protocol_version,
             &peer,
-            cancel,
-        );
+            cancel.clone(),
+        )
+        .with_subscriptions(&self.state.subscriptions)
+        .with_task_store(&self.task_store)
+        .with_invocation_tracing(&self.config.invocation_tracing);
+        let ctx = match &self.config.idempotency {
+            Some(cache) => ctx.with_idempotency_cache(cache),
+            None => ctx,
+        };
+        let ctx = match params.and_then(Meta::timeout_from_params) {
+            Some(timeout) => ctx.with_deadline(Instant::now() + timeout),
+            None => ctx,
+        };
 
-        // Delegate to the router, then drop the cancellation registration.
-        let result = self.server.route(method, params, &ctx).await;
-        self.state.remove_cancellation(&cancel_key);
-        result
+        self.server
+            .route(method, params, &ctx)
+            .instrument(mcpkit_transport::telemetry::request_span(request))
+            .await
     }
 
     /// Handle a notification.
@@ -1089,7 +1515,9 @@ where
             &self.state.server_caps,
             protocol_version,
             &peer,
-        );
+        )
+        .with_subscriptions(&self.state.subscriptions)
+        .with_task_store(&self.task_store);
         self.server
             .route_notification(method, notification.params.as_ref(), &ctx)
             .await;
@@ -1097,38 +1525,35 @@ where
     }
 }
 
-// Constructor implementations for ServerRuntime with different server types
-impl<H, T, R, P, K, Tr> ServerRuntime<Server<H, T, R, P, K>, Tr>
+// Constructor implementations for ServerRuntime over any router that reports
+// its advertised capabilities (a `Server`, possibly wrapped in middleware).
+impl<S, Tr> ServerRuntime<S, Tr>
 where
-    H: ServerHandler + Send + Sync,
-    T: Send + Sync,
-    R: Send + Sync,
-    P: Send + Sync,
-    K: Send + Sync,
+    S: crate::middleware::HasCapabilities,
     Tr: Transport + 'static,
     Tr::Error: Into<McpError>,
 {
     /// Create a new server runtime.
-    pub fn new(server: Server<H, T, R, P, K>, transport: Tr) -> Self {
+    pub fn new(server: S, transport: Tr) -> Self {
         Self::with_config(server, transport, RuntimeConfig::default())
     }
 
     /// Create a new server runtime with custom configuration.
-    pub fn with_config(
-        server: Server<H, T, R, P, K>,
-        transport: Tr,
-        config: RuntimeConfig,
-    ) -> Self {
-        let caps = server.capabilities().clone();
-        let task_store = Arc::new(crate::capability::tasks::TaskManager::with_default_ttl(
-            config.default_task_ttl_ms,
-        ));
+    pub fn with_config(server: S, transport: Tr, config: RuntimeConfig) -> Self {
+        let caps = server.server_capabilities();
+        let mut task_store =
+            crate::capability::tasks::TaskManager::with_default_ttl(config.default_task_ttl_ms);
+        if let Some(store) = &config.task_store {
+            task_store = task_store.with_store(Arc::clone(store));
+        }
+        let task_store = Arc::new(task_store);
         Self {
             server,
             transport: Arc::new(transport),
             state: Arc::new(ServerState::new(caps)),
             task_store,
             config,
+            shutdown: ShutdownHandle::new(),
         }
     }
 }
@@ -1142,6 +1567,12 @@ pub trait RequestRouter: Send + Sync {
     /// Get the server info.
     fn server_info(&self) -> mcpkit_core::capability::ServerInfo;
 
+    /// Instructions for using the server, sent in the `initialize` result.
+    /// Defaults to none.
+    fn instructions(&self) -> Option<String> {
+        None
+    }
+
     /// Route a request and return the result.
     async fn route(
         &self,
@@ -1196,24 +1627,112 @@ where
     Self: RequestRouter,
 {
     /// Run this server over the given transport.
+    ///
+    /// Returns when the connection closes, or after a graceful shutdown
+    /// triggered through [`shutdown_handle`](Self::shutdown_handle).
     pub async fn serve<Tr>(self, transport: Tr) -> Result<(), McpError>
     where
         Tr: Transport + 'static,
         Tr::Error: Into<McpError>,
     {
-        let runtime = ServerRuntime::new(self, transport);
+        let shutdown = self.shutdown_handle();
+        let runtime = ServerRuntime::new(self, transport).with_shutdown(shutdown);
         runtime.run().await
     }
+
+    /// Wrap this server's request routing in a middleware layer.
+    ///
+    /// See [`crate::middleware`]. The result can be layered further and run
+    /// with [`Layered::serve`](crate::middleware::Layered::serve) or
+    /// [`ServerRuntime::new`].
+    pub fn layer<L: crate::middleware::McpLayer<Self>>(self, layer: L) -> L::Router {
+        layer.layer(self)
+    }
 }
 
 // ============================================================================
 // Request routing
 // ============================================================================
 
+impl<H, T, R, P, K> Server<H, T, R, P, K>
+where
+    H: ServerHandler + Send + Sync,
+    T: ToolSlot,
+    R: ResourceSlot,
+    P: PromptSlot,
+    K: TaskSlot,
+{
+    /// Route a request to the registered handlers.
+    async fn dispatch(
+        &self,
+        method: &str,
+        params: Option<&serde_json::Value>,
+        ctx: &Context<'_>,
+    ) -> Result<serde_json::Value, McpError> {
+        use crate::router::Method;
+
+        // Intern the method once and go straight to the handler serving it.
+        let Some(parsed) = Method::parse(method) else {
+            return Err(McpError::method_not_found(method));
+        };
+        let page_size = self.list_page_size;
+        let routed = match parsed {
+            Method::Ping => return Ok(serde_json::json!({})),
+            Method::ToolsList | Method::ToolsCall => match self.tools.as_tool_handler() {
+                Some(handler) => route_tools(handler, method, params, ctx, page_size).await,
+                None => None,
+            },
+            Method::ResourcesList
+            | Method::ResourcesRead
+            | Method::ResourcesTemplatesList
+            | Method::ResourcesSubscribe
+            | Method::ResourcesUnsubscribe => match self.resources.as_resource_handler() {
+                Some(handler) => route_resources(handler, method, params, ctx, page_size).await,
+                None => None,
+            },
+            Method::PromptsList | Method::PromptsGet => match self.prompts.as_prompt_handler() {
+                Some(handler) => route_prompts(handler, method, params, ctx, page_size).await,
+                None => None,
+            },
+            Method::TasksList | Method::TasksGet | Method::TasksCancel => {
+                match self.tasks.as_task_handler() {
+                    Some(handler) => route_tasks(handler, method, params, ctx).await,
+                    None => None,
+                }
+            }
+            // `logging/setLevel` is handled by the base handler when the
+            // `logging` capability is advertised (shared with the HTTP
+            // adapters).
+            Method::LoggingSetLevel => {
+                crate::router::route_logging(
+                    self.handler(),
+                    self.capabilities(),
+                    method,
+                    params,
+                    ctx,
+                )
+                .await
+            }
+            // `completion/complete` is handled when a completion handler is
+            // registered (shared with the HTTP adapters).
+            Method::CompletionComplete => {
+                crate::router::route_completion(self.completion.as_deref(), method, params, ctx)
+                    .await
+            }
+            // Answered by the runtime, or sent only by servers.
+            Method::Initialize | Method::SamplingCreateMessage | Method::ElicitationCreate => None,
+        };
+        if let Some(result) = routed {
+            return result;
+        }
+        Err(McpError::method_not_found(method))
+    }
+}
+
 /// Single [`RequestRouter`] implementation over the typestate handler slots.
 ///
 /// Each capability is a slot (`Registered<H>` / `NotRegistered`) exposing an
-/// optional object-safe handler; routing checks each in turn. Adding a
+/// optional object-safe handler; routing picks the slot by method. Adding a
 /// dispatched capability is one slot plus one arm here -- there is no
 /// per-combination explosion. The shared per-method routing logic lives in
 /// [`crate::router`].
@@ -1226,7 +1745,11 @@ where
     K: TaskSlot,
 {
     fn server_info(&self) -> mcpkit_core::capability::ServerInfo {
-        self.handler().server_info()
+        Self::server_info(self)
+    }
+
+    fn instructions(&self) -> Option<String> {
+        Self::instructions(self)
     }
 
     async fn route_notification(
@@ -1252,46 +1775,19 @@ where
         params: Option<&serde_json::Value>,
         ctx: &Context<'_>,
     ) -> Result<serde_json::Value, McpError> {
-        if method == "ping" {
-            return Ok(serde_json::json!({}));
-        }
-        let page_size = self.list_page_size;
-        if let Some(handler) = self.tools.as_tool_handler() {
-            if let Some(result) = route_tools(handler, method, params, ctx, page_size).await {
-                return result;
-            }
-        }
-        if let Some(handler) = self.resources.as_resource_handler() {
-            if let Some(result) = route_resources(handler, method, params, ctx, page_size).await {
-                return result;
-            }
-        }
-        if let Some(handler) = self.prompts.as_prompt_handler() {
-            if let Some(result) = route_prompts(handler, method, params, ctx, page_size).await {
-                return result;
-            }
-        }
-        if let Some(handler) = self.tasks.as_task_handler() {
-            if let Some(result) = route_tasks(handler, method, params, ctx).await {
-                return result;
-            }
-        }
-        // `logging/setLevel` is handled by the base handler when the `logging`
-        // capability is advertised (shared with the HTTP adapters).
-        if let Some(result) =
-            crate::router::route_logging(self.handler(), self.capabilities(), method, params, ctx)
-                .await
-        {
-            return result;
-        }
-        // `completion/complete` is handled when a completion handler is
-        // registered (shared with the HTTP adapters).
-        if let Some(result) =
-            crate::router::route_completion(self.completion.as_deref(), method, params, ctx).await
-        {
-            return result;
-        }
-        Err(McpError::method_not_found(method))
+        let budget = self
+            .timeouts
+            .budget(method, params, self.tools.as_tool_handler(), ctx)
+            .await;
+        let Some(budget) = budget else {
+            return self.dispatch(method, params, ctx).await;
+        };
+        let ctx = ctx
+            .reborrow()
+            .with_deadline(budget.deadline(ctx.deadline()));
+        self.timeouts
+            .run(Some(&budget), self.dispatch(method, params, &ctx))
+            .await?
     }
 
     async fn tool_task_support(
@@ -1322,6 +1818,31 @@ where
 // Helper functions
 // ============================================================================
 
+/// Drive a handler future until it completes or `cancel` trips.
+///
+/// Once cancelled, the handler gets `grace` to observe `ctx.cancelled()` and
+/// clean up before its future is dropped. Returns `None` when the request was
+/// cancelled, whether or not the handler finished within the grace period.
+async fn run_cancellable<F: std::future::Future>(
+    handler: F,
+    cancel: &CancellationToken,
+    grace: Duration,
+) -> Option<F::Output> {
+    use futures::future::{Either, select};
+
+    let cancelled = cancel.cancelled();
+    futures::pin_mut!(handler, cancelled);
+    match select(handler, cancelled).await {
+        Either::Left((output, _)) => (!cancel.is_cancelled()).then_some(output),
+        Either::Right(((), handler)) => {
+            let sleep = mcpkit_transport::runtime::sleep(grace);
+            futures::pin_mut!(sleep);
+            let _ = select(handler, sleep).await;
+            None
+        }
+    }
+}
+
 /// Extract a progress token from request parameters.
 ///
 /// Per the MCP specification, progress tokens are sent in the `_meta.progressToken`
@@ -1350,7 +1871,7 @@ fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
 }
 
 fn extract_progress_token(params: Option<&serde_json::Value>) -> Option<ProgressToken> {
-    params.and_then(mcpkit_core::types::Meta::progress_token_from_params)
+    params.and_then(Meta::progress_token_from_params)
 }
 
 #[cfg(test)]
@@ -1438,11 +1959,13 @@ mod tests {
         }
     }
 
-    /// A router whose handler parks on `ctx.cancelled()` and reports whether the
-    /// request was cancelled, for testing that `notifications/cancelled` trips
-    /// the in-flight handler's context.
+    /// A router whose `wait_cancel` handler parks on `ctx.cancelled()` and
+    /// records that it observed the cancellation, and whose `ignore_cancel`
+    /// handler never finishes, for testing that `notifications/cancelled`
+    /// trips the in-flight handler's context and drops its response.
     struct CancelRouter {
         started: Arc<Notify>,
+        cleaned_up: Arc<AtomicBool>,
     }
 
     impl RequestRouter for CancelRouter {
@@ -1459,8 +1982,14 @@ mod tests {
                 "wait_cancel" => {
                     self.started.notify_one();
                     ctx.cancelled().await;
-                    Ok(serde_json::json!(ctx.is_cancelled()))
+                    self.cleaned_up.store(ctx.is_cancelled(), Ordering::SeqCst);
+                    Ok(serde_json::json!("too late"))
+                }
+                "ignore_cancel" => {
+                    self.started.notify_one();
+                    futures::future::pending().await
                 }
+                "ping" => Ok(serde_json::json!({})),
                 other => Err(McpError::method_not_found(other)),
             }
         }
@@ -1492,6 +2021,21 @@ mod tests {
     /// client and reports the outcome.
     struct ElicitRouter;
 
+    #[derive(serde::Deserialize)]
+    struct AgeForm {
+        age: u8,
+    }
+
+    impl mcpkit_core::types::Elicitable for AgeForm {
+        fn elicitation_schema() -> mcpkit_core::types::ElicitationSchema {
+            mcpkit_core::types::ElicitationSchema::builder()
+                .integer("age")
+                .required()
+                .max(130.0)
+                .build()
+        }
+    }
+
     impl RequestRouter for ElicitRouter {
         fn server_info(&self) -> ServerInfo {
             ServerInfo::new("elicit-test", "0.0.0")
@@ -1512,6 +2056,12 @@ mod tests {
                         "name": result.get_string("name"),
                     }))
                 }
+                "ask_age" => match ctx.elicit_form::<AgeForm>("Your age?").await? {
+                    mcpkit_core::types::ElicitOutcome::Accepted(form) => {
+                        Ok(serde_json::json!({ "age": form.age }))
+                    }
+                    outcome => Ok(serde_json::json!({ "action": outcome.action().to_string() })),
+                },
                 other => Err(McpError::method_not_found(other)),
             }
         }
@@ -1547,15 +2097,20 @@ mod tests {
         Message::Request(Request::new(method, id))
     }
 
+    /// Receive the next response, skipping the task status notifications
+    /// background tasks send when they finish.
     async fn next_response(transport: &MemoryTransport) -> Response {
-        let msg = timeout(Duration::from_secs(2), transport.recv())
-            .await
-            .expect("no response (connection died?)")
-            .expect("recv ok")
-            .expect("some message");
-        match msg {
-            Message::Response(r) => r,
-            other => panic!("expected response, got {other:?}"),
+        loop {
+            let msg = timeout(Duration::from_secs(2), transport.recv())
+                .await
+                .expect("no response (connection died?)")
+                .expect("recv ok")
+                .expect("some message");
+            match msg {
+                Message::Response(r) => return r,
+                Message::Notification(n) if n.method == "notifications/tasks/status" => {}
+                other => panic!("expected response, got {other:?}"),
+            }
         }
     }
 
@@ -1717,6 +2272,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -1755,6 +2311,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -1798,6 +2355,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -1850,6 +2408,7 @@ mod tests {
                 max_concurrent_requests: 1,
                 ..RuntimeConfig::default()
             },
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -1877,22 +2436,188 @@ mod tests {
         let _ = timeout(Duration::from_secs(2), handle).await;
     }
 
+    fn shutdown_runtime(
+        config: RuntimeConfig,
+    ) -> (
+        MemoryTransport,
+        Arc<MemoryTransport>,
+        Arc<Notify>,
+        Arc<Notify>,
+        ShutdownHandle,
+        tokio::task::JoinHandle<Result<(), McpError>>,
+    ) {
+        let (client, server) = MemoryTransport::pair();
+        let server = Arc::new(server);
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let state = Arc::new(ServerState::new(ServerCapabilities::default()));
+        state.set_initialized();
+        let shutdown = ShutdownHandle::new();
+        let runtime = ServerRuntime {
+            server: CoordRouter {
+                started: started.clone(),
+                release: release.clone(),
+            },
+            transport: server.clone(),
+            state,
+            task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
+            config,
+            shutdown: shutdown.clone(),
+        };
+        let handle = tokio::spawn(async move { runtime.run().await });
+        (client, server, started, release, shutdown, handle)
+    }
+
     #[tokio::test]
-    async fn cancelled_notification_trips_in_flight_handler() {
+    async fn shutdown_drains_in_flight_requests_and_rejects_new_ones() {
+        let (client, server, started, release, shutdown, handle) =
+            shutdown_runtime(RuntimeConfig {
+                auto_initialized: true,
+                ..RuntimeConfig::default()
+            });
+
+        client.send(req("blocker", 1)).await.expect("send");
+        timeout(Duration::from_secs(2), started.notified())
+            .await
+            .expect("blocker never started");
+
+        shutdown.shutdown();
+        tokio::task::yield_now().await;
+
+        // New work is refused while the blocker drains.
+        client.send(req("fast", 2)).await.expect("send");
+        let rejected = next_response(&client).await;
+        assert_eq!(rejected.id, RequestId::Number(2));
+        let error = rejected.error.expect("request during shutdown must fail");
+        assert!(error.message.contains("shutting down"), "{error:?}");
+
+        // The in-flight request still completes, then the runtime exits.
+        release.notify_one();
+        let done = next_response(&client).await;
+        assert_eq!(done.id, RequestId::Number(1));
+        assert_eq!(done.result, Some(serde_json::json!("blocked-done")));
+
+        timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("runtime did not stop after draining")
+            .unwrap()
+            .expect("clean shutdown");
+        assert!(!server.is_connected(), "transport must be closed");
+    }
+
+    /// With ping supervision, the runtime pings the client, and closes the
+    /// connection once the client stops answering.
+    #[tokio::test]
+    async fn ping_supervision_closes_unresponsive_client() {
+        let reported = Arc::new(std::sync::Mutex::new(None));
+        let sink = Arc::clone(&reported);
+        let (client, server, _started, _release, _shutdown, handle) =
+            shutdown_runtime(RuntimeConfig {
+                ping_supervision: Some(
+                    PingConfig::new()
+                        .with_interval(Duration::from_millis(10))
+                        .with_timeout(Duration::from_millis(50))
+                        .with_max_missed(2)
+                        .on_unhealthy(move |snapshot| *sink.lock().unwrap() = Some(*snapshot)),
+                ),
+                ..RuntimeConfig::default()
+            });
+
+        // Answer the first ping, then go quiet.
+        let ping = timeout(Duration::from_secs(2), client.recv())
+            .await
+            .expect("no ping sent")
+            .expect("recv")
+            .expect("message");
+        let Message::Request(ping) = ping else {
+            panic!("expected a ping request, got {ping:?}");
+        };
+        assert_eq!(ping.method, "ping");
+        client
+            .send(Message::Response(Response::success(
+                ping.id,
+                serde_json::json!({}),
+            )))
+            .await
+            .expect("pong");
+
+        timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("runtime did not stop")
+            .unwrap()
+            .expect("unhealthy connection closes cleanly");
+        assert!(!server.is_connected(), "transport must be closed");
+        let snapshot = reported.lock().unwrap().expect("hook ran");
+        assert!(!snapshot.healthy);
+        assert_eq!((snapshot.answered, snapshot.missed), (1, 2));
+        assert!(snapshot.last_rtt.is_some());
+    }
+
+    #[tokio::test]
+    async fn shutdown_timeout_cancels_stuck_requests() {
+        let (client, server, started, _release, shutdown, handle) =
+            shutdown_runtime(RuntimeConfig {
+                auto_initialized: true,
+                shutdown_timeout: Duration::from_millis(50),
+                cancellation_grace: Duration::from_millis(50),
+                ..RuntimeConfig::default()
+            });
+
+        client.send(req("blocker", 1)).await.expect("send");
+        timeout(Duration::from_secs(2), started.notified())
+            .await
+            .expect("blocker never started");
+
+        shutdown.shutdown();
+        timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("runtime ignored the shutdown timeout")
+            .unwrap()
+            .expect("shutdown after timeout is not an error");
+        assert!(!server.is_connected(), "transport must be closed");
+    }
+
+    fn cancel_runtime(
+        grace: Duration,
+    ) -> (
+        MemoryTransport,
+        Arc<Notify>,
+        Arc<AtomicBool>,
+        tokio::task::JoinHandle<Result<(), McpError>>,
+    ) {
         let (client, server) = MemoryTransport::pair();
         let started = Arc::new(Notify::new());
+        let cleaned_up = Arc::new(AtomicBool::new(false));
         let state = Arc::new(ServerState::new(ServerCapabilities::default()));
         state.set_initialized();
         let runtime = ServerRuntime {
             server: CancelRouter {
                 started: started.clone(),
+                cleaned_up: cleaned_up.clone(),
             },
             transport: Arc::new(server),
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
-            config: RuntimeConfig::default(),
+            config: RuntimeConfig {
+                cancellation_grace: grace,
+                ..RuntimeConfig::default()
+            },
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
+        (client, started, cleaned_up, handle)
+    }
+
+    fn cancel(id: u64) -> Message {
+        Message::Notification(Notification::with_params(
+            "notifications/cancelled".to_string(),
+            serde_json::json!({ "requestId": id }),
+        ))
+    }
+
+    #[tokio::test]
+    async fn cancelled_notification_trips_in_flight_handler() {
+        let (client, started, cleaned_up, handle) = cancel_runtime(Duration::from_secs(5));
 
         // Start a request whose handler parks on `ctx.cancelled()`.
         client.send(req("wait_cancel", 1)).await.expect("send");
@@ -1900,19 +2625,15 @@ mod tests {
             .await
             .expect("handler never started");
 
-        // Cancel it by id. Before the fix this never reached the handler's token,
-        // so the handler would park forever and `next_response` would time out.
-        let cancel = Message::Notification(Notification::with_params(
-            "notifications/cancelled".to_string(),
-            serde_json::json!({ "requestId": 1 }),
-        ));
-        client.send(cancel).await.expect("send cancel");
+        // Cancel it by id: the handler sees the cancellation, but its response
+        // is dropped, so the next response is the ping's.
+        client.send(cancel(1)).await.expect("send cancel");
+        client.send(req("ping", 2)).await.expect("send ping");
 
         let resp = next_response(&client).await;
-        assert_eq!(resp.id, RequestId::Number(1));
-        assert_eq!(
-            resp.result,
-            Some(serde_json::json!(true)),
+        assert_eq!(resp.id, RequestId::Number(2));
+        assert!(
+            cleaned_up.load(Ordering::SeqCst),
             "ctx.is_cancelled() should be true after notifications/cancelled"
         );
 
@@ -1920,6 +2641,29 @@ mod tests {
         let _ = timeout(Duration::from_secs(2), handle).await;
     }
 
+    #[tokio::test]
+    async fn cancelled_handler_is_dropped_after_grace_period() {
+        let (client, started, _, handle) = cancel_runtime(Duration::from_millis(10));
+
+        // A handler that ignores cancellation would hold its concurrency slot
+        // forever; after the grace period its future is dropped instead.
+        client.send(req("ignore_cancel", 1)).await.expect("send");
+        timeout(Duration::from_secs(2), started.notified())
+            .await
+            .expect("handler never started");
+        client.send(cancel(1)).await.expect("send cancel");
+        client.send(req("ping", 2)).await.expect("send ping");
+        assert_eq!(next_response(&client).await.id, RequestId::Number(2));
+
+        // The connection shuts down promptly because nothing is left in flight.
+        drop(client);
+        timeout(Duration::from_secs(2), handle)
+            .await
+            .expect("runtime should exit once the client disconnects")
+            .expect("join")
+            .expect("run");
+    }
+
     #[tokio::test]
     async fn notifier_sends_list_changed_outside_request() {
         let (client, server) = MemoryTransport::pair();
@@ -1930,6 +2674,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
 
         // The notifier works without an active request and without running the
@@ -1950,6 +2695,54 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn capability_change_announces_tool_list_change() {
+        let (client, server) = MemoryTransport::pair();
+        let state = Arc::new(ServerState::new(
+            ServerCapabilities::new().with_tools_and_changes(),
+        ));
+        state.set_initialized();
+        state.set_client_caps(ClientCapabilities::new().with_sampling());
+        let runtime = ServerRuntime {
+            server: PingRouter,
+            transport: Arc::new(server),
+            state,
+            task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
+            config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
+        };
+        let notifier = runtime.notifier();
+
+        // Unchanged capabilities send nothing.
+        let unchanged = notifier
+            .update_client_capabilities(ClientCapabilities::new().with_sampling())
+            .await
+            .expect("update");
+        assert!(unchanged.is_empty());
+
+        let changed = notifier
+            .update_client_capabilities(ClientCapabilities::new().with_elicitation())
+            .await
+            .expect("update");
+        assert_eq!(
+            changed,
+            [ClientCapability::Sampling, ClientCapability::Elicitation]
+        );
+        assert!(runtime.state().client_caps().has_elicitation());
+
+        let msg = timeout(Duration::from_secs(2), client.recv())
+            .await
+            .expect("no notification (timed out)")
+            .expect("recv ok")
+            .expect("some message");
+        match msg {
+            Message::Notification(n) => {
+                assert_eq!(n.method.as_ref(), "notifications/tools/list_changed");
+            }
+            other => panic!("expected a notification, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn server_initiated_request_roundtrips_at_concurrency_limit() {
         let (client, server) = MemoryTransport::pair();
@@ -1968,6 +2761,7 @@ mod tests {
                 max_concurrent_requests: 1,
                 ..RuntimeConfig::default()
             },
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -2017,6 +2811,7 @@ mod tests {
                 outbound_request_timeout: Duration::from_millis(100),
                 ..RuntimeConfig::default()
             },
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -2050,6 +2845,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -2095,6 +2891,72 @@ mod tests {
         let _ = timeout(Duration::from_secs(2), handle).await;
     }
 
+    #[tokio::test]
+    async fn ctx_elicit_form_validates_response() {
+        let (client, server) = MemoryTransport::pair();
+        let state = Arc::new(ServerState::new(ServerCapabilities::default()));
+        state.set_initialized();
+        state.set_client_caps(ClientCapabilities::default().with_elicitation());
+        let runtime = ServerRuntime {
+            server: ElicitRouter,
+            transport: Arc::new(server),
+            state,
+            task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
+            config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
+        };
+        let handle = tokio::spawn(async move { runtime.run().await });
+
+        let answers = [
+            (
+                serde_json::json!({ "action": "accept", "content": { "age": 36 } }),
+                Some(serde_json::json!({ "age": 36 })),
+            ),
+            (
+                serde_json::json!({ "action": "decline" }),
+                Some(serde_json::json!({ "action": "decline" })),
+            ),
+            // Out of range for the schema: the handler sees an error.
+            (
+                serde_json::json!({ "action": "accept", "content": { "age": 200 } }),
+                None,
+            ),
+        ];
+        for (id, (answer, expected)) in (1..).zip(answers) {
+            client.send(req("ask_age", id)).await.expect("send");
+            let elicit = match timeout(Duration::from_secs(2), client.recv())
+                .await
+                .expect("no elicitation request")
+                .expect("recv ok")
+                .expect("some message")
+            {
+                Message::Request(r) => r,
+                other => panic!("expected elicitation/create, got {other:?}"),
+            };
+            assert_eq!(
+                elicit.params.as_ref().unwrap()["requestedSchema"]["required"],
+                serde_json::json!(["age"])
+            );
+            client
+                .send(Message::Response(Response::success(
+                    elicit.id.clone(),
+                    answer,
+                )))
+                .await
+                .expect("send response");
+
+            let resp = next_response(&client).await;
+            assert_eq!(resp.id, RequestId::Number(id));
+            match expected {
+                Some(result) => assert_eq!(resp.result, Some(result)),
+                None => assert!(resp.error.is_some(), "invalid content should error"),
+            }
+        }
+
+        drop(client);
+        let _ = timeout(Duration::from_secs(2), handle).await;
+    }
+
     #[tokio::test]
     async fn ctx_elicit_requires_client_capability() {
         let (client, server) = MemoryTransport::pair();
@@ -2107,6 +2969,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -2138,6 +3001,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -2195,6 +3059,7 @@ mod tests {
             state,
             task_store: Arc::new(crate::capability::tasks::TaskManager::new()),
             config: RuntimeConfig::default(),
+            shutdown: ShutdownHandle::new(),
         };
         let handle = tokio::spawn(async move { runtime.run().await });
 
@@ -2366,6 +3231,22 @@ mod tests {
             .expect("taskId")
             .to_string();
 
+        // Finishing the task sends its terminal status.
+        let status = match timeout(Duration::from_secs(2), client.recv())
+            .await
+            .expect("no task status notification")
+            .expect("recv ok")
+            .expect("some message")
+        {
+            Message::Notification(n) => {
+                assert_eq!(n.method.as_ref(), "notifications/tasks/status");
+                n.params.expect("params")
+            }
+            other => panic!("expected a task status notification, got {other:?}"),
+        };
+        assert_eq!(status["taskId"], task_id.as_str());
+        assert_eq!(status["status"], "completed");
+
         // The tool runs in the background; tasks/result yields its payload once done.
         let mut payload = None;
         for attempt in 0..100u64 {
@@ -2517,6 +3398,66 @@ mod tests {
         let _ = timeout(Duration::from_secs(2), handle).await;
     }
 
+    #[tokio::test]
+    async fn retried_tools_call_is_answered_from_the_idempotency_cache() {
+        use crate::builder::ServerBuilder;
+        use crate::handler::{ServerHandler, ToolHandler};
+        use mcpkit_core::protocol::Request;
+        use mcpkit_core::types::{Tool, ToolOutput};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        struct H;
+        impl ServerHandler for H {
+            fn server_info(&self) -> ServerInfo {
+                ServerInfo::new("t", "1.0.0")
+            }
+        }
+        impl ToolHandler for H {
+            async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+                Ok(vec![Tool::new("send_email")])
+            }
+            async fn call_tool(
+                &self,
+                _name: &str,
+                _args: serde_json::Map<String, serde_json::Value>,
+                _ctx: &Context<'_>,
+            ) -> Result<ToolOutput, McpError> {
+                let n = CALLS.fetch_add(1, Ordering::SeqCst);
+                Ok(ToolOutput::text(format!("sent #{n}")))
+            }
+        }
+
+        let (client, server_tr) = MemoryTransport::pair();
+        let built = ServerBuilder::new(H).with_tools(H).build();
+        let runtime = ServerRuntime::new(built, server_tr);
+        runtime.state().set_initialized();
+        let handle = tokio::spawn(async move { runtime.run().await });
+
+        let params = Meta::with_idempotency_key_in_params(
+            Some(serde_json::json!({ "name": "send_email", "arguments": {} })),
+            "retry-1",
+        );
+        let mut texts = Vec::new();
+        for id in 1..=2 {
+            client
+                .send(Message::Request(Request::with_params(
+                    "tools/call",
+                    RequestId::Number(id),
+                    params.clone(),
+                )))
+                .await
+                .expect("send");
+            let resp = next_response(&client).await;
+            texts.push(resp.result.expect("result")["content"][0]["text"].clone());
+        }
+        assert_eq!(texts, ["sent #0", "sent #0"]);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        drop(client);
+        let _ = timeout(Duration::from_secs(2), handle).await;
+    }
+
     #[tokio::test]
     async fn task_augmented_call_on_forbidden_tool_is_rejected() {
         use crate::builder::ServerBuilder;
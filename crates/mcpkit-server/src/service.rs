@@ -0,0 +1,1844 @@
+//! Transport-agnostic request handling for HTTP-style integrations.
+//!
+//! Every web framework integration needs the same steps for each incoming
+//! message: validate the `Origin` and `MCP-Protocol-Version` headers, resolve
+//! (or create) the session and enforce its user binding, parse the JSON-RPC
+//! message, record `initialize` negotiation, apply rate, size, and
+//! concurrency limits, and route the request to the handler traits. [`McpService`] does all of that given the
+//! raw body and a few header values, and reports the result as a
+//! [`ServiceOutcome`] the adapter turns into a framework response.
+//!
+//! Session state lives behind the [`SessionStorage`] trait, so adapters can
+//! keep their own stores; [`MemorySessions`] is a ready-made in-memory store
+//! for new integrations.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mcpkit_server::service::{McpService, MemorySessions, ServiceOutcome, ServiceRequest};
+//!
+//! let service = McpService::new(MyServer, MemorySessions::new());
+//!
+//! // In the framework's POST handler:
+//! let request = ServiceRequest::from_bytes(body)
+//!     .with_origin(header("origin"))
+//!     .with_protocol_version(header("mcp-protocol-version"))
+//!     .with_session_id(header("mcp-session-id"))
+//!     .with_accept(header("accept"));
+//! let reply = service.handle(request).await;
+//! let sse = reply.sse_body();
+//! match reply.outcome {
+//!     ServiceOutcome::Response { session_id, response } => {
+//!         // 200 OK, `mcp-session-id: {session_id}`, and either the `sse`
+//!         // body as `text/event-stream` or `response` as JSON
+//!     }
+//!     ServiceOutcome::Accepted { session_id } => { /* 202 Accepted */ }
+//!     ServiceOutcome::Rejected(rejection) => {
+//!         // rejection.status_code(), rejection.to_string()
+//!     }
+//!     _ => {}
+//! }
+//! ```
+
+use crate::audit::{AuditLogger, AuditOutcome, AuditedRequest};
+use crate::capability::tasks::{TaskManager, route_task_store};
+use crate::context::{Context, Peer};
+use crate::dispatch::DynCompletionHandler;
+use crate::extensions::{Extensions, SessionId};
+use crate::handler::{
+    CompletionHandler, PromptHandler, ResourceHandler, ServerHandler, ToolHandler,
+};
+use crate::invocation::InvocationTracing;
+use crate::metrics::MetricsLayer;
+use crate::middleware::McpMiddleware as _;
+use crate::policy::ToolAuthorizer;
+use crate::rate_limit::RateLimiter;
+use crate::router::{
+    AugmentedTaskOutcome, begin_augmented_task, route_completion, route_logging, route_prompts,
+    route_resources, route_tools,
+};
+use crate::shutdown::ShutdownHandle;
+use crate::timeout::Timeouts;
+use chrono::Utc;
+use mcpkit_core::auth::{SessionBindingError, VerifiedUser, check_session_binding};
+use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+use mcpkit_core::compat::adapt_result;
+use mcpkit_core::error::{JsonRpcError, McpError};
+use mcpkit_core::protocol::{Message, Notification, Request, Response};
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_transport::TransportLimits;
+use mcpkit_transport::http::OriginValidator;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{Instrument, debug, info, warn};
+
+/// Storage for the per-session state [`McpService`] needs.
+///
+/// Framework adapters implement this for their own session stores. Methods
+/// are synchronous; implementations are expected to be in-memory maps.
+pub trait SessionStorage: Send + Sync {
+    /// Create a session, bound to `user` if one is given, and return its id.
+    fn open_session(&self, user: Option<VerifiedUser>) -> String;
+
+    /// Mark a session active, enforcing its user binding first.
+    ///
+    /// Returns `Ok(true)` if the session exists, `Ok(false)` if it doesn't,
+    /// or `Err` when `user` doesn't match the session's bound identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns the binding violation when the identities don't match.
+    fn resume_session(
+        &self,
+        id: &str,
+        user: Option<&VerifiedUser>,
+    ) -> Result<bool, SessionBindingError>;
+
+    /// Record the values negotiated by `initialize`.
+    fn record_negotiated(
+        &self,
+        id: &str,
+        protocol_version: ProtocolVersion,
+        capabilities: Option<ClientCapabilities>,
+    );
+
+    /// The session's negotiated values and task store, if it exists.
+    fn session_snapshot(&self, id: &str) -> Option<SessionSnapshot>;
+}
+
+impl<T: SessionStorage + ?Sized> SessionStorage for Arc<T> {
+    fn open_session(&self, user: Option<VerifiedUser>) -> String {
+        (**self).open_session(user)
+    }
+
+    fn resume_session(
+        &self,
+        id: &str,
+        user: Option<&VerifiedUser>,
+    ) -> Result<bool, SessionBindingError> {
+        (**self).resume_session(id, user)
+    }
+
+    fn record_negotiated(
+        &self,
+        id: &str,
+        protocol_version: ProtocolVersion,
+        capabilities: Option<ClientCapabilities>,
+    ) {
+        (**self).record_negotiated(id, protocol_version, capabilities);
+    }
+
+    fn session_snapshot(&self, id: &str) -> Option<SessionSnapshot> {
+        (**self).session_snapshot(id)
+    }
+}
+
+/// A session's negotiated values, as seen by one request.
+#[derive(Debug, Clone, Default)]
+pub struct SessionSnapshot {
+    /// Protocol version negotiated by `initialize`, if it has run.
+    pub protocol_version: Option<ProtocolVersion>,
+    /// Capabilities the client sent with `initialize`.
+    pub client_capabilities: Option<ClientCapabilities>,
+    /// The session's own store for task-augmented `tools/call`.
+    pub tasks: Option<Arc<TaskManager>>,
+}
+
+/// One incoming message plus the transport metadata [`McpService`] checks.
+#[derive(Debug)]
+pub struct ServiceRequest {
+    payload: Payload,
+    origin: Option<String>,
+    protocol_version: Option<String>,
+    session_id: Option<String>,
+    accept: Option<String>,
+    user: Option<VerifiedUser>,
+    extensions: Extensions,
+}
+
+#[derive(Debug)]
+enum Payload {
+    Bytes(Vec<u8>),
+    Message(Message),
+}
+
+impl ServiceRequest {
+    /// A request carrying a raw JSON-RPC body.
+    #[must_use]
+    pub fn from_bytes(body: impl Into<Vec<u8>>) -> Self {
+        Self::with_payload(Payload::Bytes(body.into()))
+    }
+
+    /// A request carrying an already parsed message.
+    #[must_use]
+    pub fn from_message(message: Message) -> Self {
+        Self::with_payload(Payload::Message(message))
+    }
+
+    fn with_payload(payload: Payload) -> Self {
+        Self {
+            payload,
+            origin: None,
+            protocol_version: None,
+            session_id: None,
+            accept: None,
+            user: None,
+            extensions: Extensions::new(),
+        }
+    }
+
+    /// Set the `Origin` header value.
+    #[must_use]
+    pub fn with_origin(mut self, origin: Option<String>) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Set the `MCP-Protocol-Version` header value.
+    #[must_use]
+    pub fn with_protocol_version(mut self, version: Option<String>) -> Self {
+        self.protocol_version = version;
+        self
+    }
+
+    /// Set the `Mcp-Session-Id` header value.
+    #[must_use]
+    pub fn with_session_id(mut self, session_id: Option<String>) -> Self {
+        self.session_id = session_id;
+        self
+    }
+
+    /// Set the `Accept` header value, used to pick the [`ResponseMode`].
+    #[must_use]
+    pub fn with_accept(mut self, accept: Option<String>) -> Self {
+        self.accept = accept;
+        self
+    }
+
+    /// Set the identity verified by the application's auth middleware.
+    ///
+    /// Handlers and middleware can read it with
+    /// `ctx.extension::<VerifiedUser>()`.
+    #[must_use]
+    pub fn with_user(mut self, user: Option<VerifiedUser>) -> Self {
+        self.user = user;
+        self
+    }
+
+    /// Attach per-request values exposed to handlers via [`Context::extension`].
+    #[must_use]
+    pub fn with_extensions(mut self, extensions: Extensions) -> Self {
+        self.extensions = extensions;
+        self
+    }
+}
+
+/// What [`McpService::handle`] produced for one request.
+#[derive(Debug)]
+pub struct ServiceReply {
+    /// What to send back to the client.
+    pub outcome: ServiceOutcome,
+    /// Things that happened while handling the request, in order.
+    pub events: Vec<ServiceEvent>,
+    /// How to send a [`ServiceOutcome::Response`], negotiated from the
+    /// request's `Accept` header.
+    pub response_mode: ResponseMode,
+}
+
+impl ServiceReply {
+    /// The `text/event-stream` body for a response in [`ResponseMode::Sse`]:
+    /// a `message` event for each notification the handler sent, then one
+    /// for the response.
+    ///
+    /// Returns `None` in JSON mode and for outcomes other than
+    /// [`ServiceOutcome::Response`].
+    #[must_use]
+    pub fn sse_body(&self) -> Option<String> {
+        let ServiceOutcome::Response { response, .. } = &self.outcome else {
+            return None;
+        };
+        if self.response_mode != ResponseMode::Sse {
+            return None;
+        }
+        let notifications = self.events.iter().filter_map(|event| match event {
+            ServiceEvent::Notification(n) => Some(Message::Notification(n.clone())),
+            _ => None,
+        });
+        let mut body = String::new();
+        for message in notifications.chain([Message::Response(response.clone())]) {
+            let data = serde_json::to_string(&message).ok()?;
+            body.push_str("event: message\ndata: ");
+            body.push_str(&data);
+            body.push_str("\n\n");
+        }
+        Some(body)
+    }
+}
+
+/// How a POST carrying a request is answered.
+///
+/// The Streamable HTTP transport lets the server reply with either a single
+/// JSON body or an SSE stream. [`negotiate`](Self::negotiate) honors the
+/// client's `Accept` header and falls back to the configured default when
+/// the client takes both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseMode {
+    /// `application/json` with the JSON-RPC response as the body.
+    #[default]
+    Json,
+    /// `text/event-stream` carrying the notifications the handler sent
+    /// while the request ran, followed by the response.
+    Sse,
+}
+
+impl ResponseMode {
+    /// The mode for a request with the given `Accept` header, with `self`
+    /// as the default.
+    ///
+    /// A client accepting only `application/json` gets JSON and one
+    /// accepting only `text/event-stream` gets SSE. A missing header,
+    /// wildcards, or both types leave the choice to the default.
+    #[must_use]
+    pub fn negotiate(self, accept: Option<&str>) -> Self {
+        let Some(accept) = accept else {
+            return self;
+        };
+        let (mut json, mut sse) = (false, false);
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media_type = parts.next().unwrap_or_default().trim();
+            let refused = parts.any(|p| {
+                p.trim()
+                    .strip_prefix("q=")
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .is_some_and(|q| q <= 0.0)
+            });
+            if refused {
+                continue;
+            }
+            match media_type.to_ascii_lowercase().as_str() {
+                "application/json" | "application/*" => json = true,
+                "text/event-stream" | "text/*" => sse = true,
+                "*/*" => (json, sse) = (true, true),
+                _ => {}
+            }
+        }
+        match (json, sse) {
+            (true, false) => Self::Json,
+            (false, true) => Self::Sse,
+            _ => self,
+        }
+    }
+}
+
+/// The response an adapter should send.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ServiceOutcome {
+    /// A JSON-RPC response, sent with `200 OK` and the session id header.
+    Response {
+        /// The session the request ran in.
+        session_id: String,
+        /// The JSON-RPC response.
+        response: Response,
+    },
+    /// A notification was accepted (`202 Accepted`).
+    Accepted {
+        /// The session the notification was received in.
+        session_id: String,
+    },
+    /// The request was refused before reaching the handler.
+    Rejected(ServiceRejection),
+}
+
+/// Side-channel events produced while handling a request.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ServiceEvent {
+    /// A session was created for a request without a session id.
+    SessionCreated {
+        /// The new session's id.
+        session_id: String,
+    },
+    /// `initialize` negotiated a protocol version for the session.
+    Initialized {
+        /// The session's id.
+        session_id: String,
+        /// The negotiated version.
+        protocol_version: ProtocolVersion,
+    },
+    /// A notification the handler sent to the client while the request ran,
+    /// such as progress or a log message. Adapters with a server-to-client
+    /// channel (e.g. SSE) can forward it; otherwise it is dropped.
+    Notification(Notification),
+}
+
+/// Why [`McpService`] refused a request.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ServiceRejection {
+    /// The `Origin` header is not allowed (DNS-rebinding protection).
+    #[error("origin not allowed")]
+    OriginNotAllowed(Option<String>),
+    /// The `MCP-Protocol-Version` header names an unsupported version.
+    #[error("Protocol version '{0}' is not supported")]
+    UnsupportedVersion(String),
+    /// The `Mcp-Session-Id` header names an unknown session.
+    #[error("Session '{0}' not found")]
+    SessionNotFound(String),
+    /// The presenting identity doesn't match the session's bound user.
+    #[error("{0}")]
+    SessionBinding(SessionBindingError),
+    /// The body is not a valid JSON-RPC message.
+    #[error("Invalid JSON-RPC message: {0}")]
+    InvalidMessage(String),
+    /// The body is a valid message, but not a request or notification.
+    #[error("Expected request or notification")]
+    UnexpectedMessage,
+    /// The body exceeds the configured maximum message size.
+    #[error("Message of {size} bytes exceeds the maximum of {max} bytes")]
+    PayloadTooLarge {
+        /// Size of the body in bytes.
+        size: usize,
+        /// Maximum allowed size in bytes.
+        max: usize,
+    },
+    /// The configured number of requests is already being handled.
+    #[error("Too many requests in flight")]
+    Overloaded,
+    /// The server is shutting down and no longer runs new requests.
+    #[error("Server is shutting down")]
+    ShuttingDown,
+}
+
+impl ServiceRejection {
+    /// The HTTP status code conventionally used for this rejection.
+    #[must_use]
+    pub const fn status_code(&self) -> u16 {
+        match self {
+            Self::OriginNotAllowed(_) | Self::SessionBinding(_) => 403,
+            Self::SessionNotFound(_) => 404,
+            Self::UnsupportedVersion(_) | Self::InvalidMessage(_) | Self::UnexpectedMessage => 400,
+            Self::PayloadTooLarge { .. } => 413,
+            Self::Overloaded | Self::ShuttingDown => 503,
+        }
+    }
+}
+
+/// [`TransportLimits`] applied by [`McpService`], with the count of requests
+/// in flight.
+///
+/// Clones share the count, so adapters that build a service per request keep
+/// one `ServiceLimits` and pass clones of it.
+#[derive(Debug, Clone, Default)]
+pub struct ServiceLimits {
+    limits: TransportLimits,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl ServiceLimits {
+    /// Apply `limits`, with no requests in flight yet.
+    #[must_use]
+    pub fn new(limits: TransportLimits) -> Self {
+        Self {
+            limits,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The configured limits.
+    #[must_use]
+    pub const fn limits(&self) -> &TransportLimits {
+        &self.limits
+    }
+
+    /// Take one of the `max_in_flight` slots, released when dropped.
+    fn acquire(&self) -> Option<InFlightSlot> {
+        let max = self.limits.max_in_flight.unwrap_or(usize::MAX);
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()?;
+        Some(InFlightSlot(Arc::clone(&self.in_flight)))
+    }
+}
+
+impl From<TransportLimits> for ServiceLimits {
+    fn from(limits: TransportLimits) -> Self {
+        Self::new(limits)
+    }
+}
+
+/// A request counted against `max_in_flight`.
+struct InFlightSlot(Arc<AtomicUsize>);
+
+impl Drop for InFlightSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Whether a `MCP-Protocol-Version` header value is acceptable.
+///
+/// An absent header is accepted: it is assumed to mean `2025-03-26`, the
+/// version predating the header, per the Streamable HTTP specification.
+#[must_use]
+pub fn is_supported_version(version: Option<&str>) -> bool {
+    version.is_none_or(|v| ProtocolVersion::ALL.iter().any(|p| p.as_str() == v))
+}
+
+/// Transport-agnostic MCP request handling.
+///
+/// Cloning is cheap: the handler and configuration are shared.
+pub struct McpService<H, S = MemorySessions> {
+    handler: Arc<H>,
+    sessions: S,
+    server_info: ServerInfo,
+    origin_validator: Arc<OriginValidator>,
+    list_page_size: Option<usize>,
+    completion: Option<Arc<dyn DynCompletionHandler>>,
+    rate_limiter: Option<RateLimiter>,
+    tool_authorizer: Option<ToolAuthorizer>,
+    audit_logger: Option<AuditLogger>,
+    invocation_tracing: InvocationTracing,
+    server_metrics: Option<MetricsLayer>,
+    limits: ServiceLimits,
+    shutdown: ShutdownHandle,
+    response_mode: ResponseMode,
+    timeouts: Timeouts,
+    #[cfg(feature = "prometheus")]
+    metrics: Option<mcpkit_transport::McpMetrics>,
+}
+
+impl<H, S: Clone> Clone for McpService<H, S> {
+    fn clone(&self) -> Self {
+        Self {
+            handler: Arc::clone(&self.handler),
+            sessions: self.sessions.clone(),
+            server_info: self.server_info.clone(),
+            origin_validator: Arc::clone(&self.origin_validator),
+            list_page_size: self.list_page_size,
+            completion: self.completion.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+            tool_authorizer: self.tool_authorizer.clone(),
+            audit_logger: self.audit_logger.clone(),
+            invocation_tracing: self.invocation_tracing.clone(),
+            server_metrics: self.server_metrics.clone(),
+            limits: self.limits.clone(),
+            shutdown: self.shutdown.clone(),
+            response_mode: self.response_mode,
+            timeouts: self.timeouts.clone(),
+            #[cfg(feature = "prometheus")]
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+impl<H, S: std::fmt::Debug> std::fmt::Debug for McpService<H, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpService")
+            .field("handler", &format_args!("Arc<H>"))
+            .field("sessions", &self.sessions)
+            .field("server_info", &self.server_info)
+            .field("origin_validator", &self.origin_validator)
+            .field("list_page_size", &self.list_page_size)
+            .field("completion", &self.completion.is_some())
+            .field("rate_limiter", &self.rate_limiter)
+            .field("tool_authorizer", &self.tool_authorizer)
+            .field("audit_logger", &self.audit_logger)
+            .field("invocation_tracing", &self.invocation_tracing)
+            .field("server_metrics", &self.server_metrics)
+            .field("limits", &self.limits)
+            .field("shutdown", &self.shutdown)
+            .field("response_mode", &self.response_mode)
+            .field("timeouts", &self.timeouts)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<H: ServerHandler, S> McpService<H, S> {
+    /// Create a service for `handler`, keeping session state in `sessions`.
+    #[must_use]
+    pub fn new(handler: H, sessions: S) -> Self {
+        Self::from_shared(Arc::new(handler), sessions)
+    }
+
+    /// Create a service for a handler that is already shared.
+    #[must_use]
+    pub fn from_shared(handler: Arc<H>, sessions: S) -> Self {
+        let server_info = handler.server_info();
+        Self::from_parts(handler, sessions, server_info)
+    }
+
+    /// The handler's advertised capabilities, plus `completions` when a
+    /// completion handler is registered on the service and `resources` when
+    /// the metrics resource is exposed.
+    #[must_use]
+    pub fn effective_capabilities(&self) -> ServerCapabilities {
+        let caps = self.handler.capabilities();
+        let caps = if self.completion.is_some() && !caps.has_completions() {
+            caps.with_completions()
+        } else {
+            caps
+        };
+        match &self.server_metrics {
+            Some(metrics) => metrics.capabilities(caps),
+            None => caps,
+        }
+    }
+}
+
+impl<H, S> McpService<H, S> {
+    /// Create a service answering `initialize` with `server_info`.
+    #[must_use]
+    pub fn from_parts(handler: Arc<H>, sessions: S, server_info: ServerInfo) -> Self {
+        Self {
+            handler,
+            sessions,
+            server_info,
+            origin_validator: Arc::new(OriginValidator::default()),
+            list_page_size: None,
+            completion: None,
+            rate_limiter: None,
+            tool_authorizer: None,
+            audit_logger: None,
+            invocation_tracing: InvocationTracing::default(),
+            server_metrics: None,
+            limits: ServiceLimits::default(),
+            shutdown: ShutdownHandle::new(),
+            response_mode: ResponseMode::default(),
+            timeouts: Timeouts::default(),
+            #[cfg(feature = "prometheus")]
+            metrics: None,
+        }
+    }
+
+    /// Validate `Origin` headers with `validator` (loopback-only by default).
+    #[must_use]
+    pub fn with_origin_validator(mut self, validator: Arc<OriginValidator>) -> Self {
+        self.origin_validator = validator;
+        self
+    }
+
+    /// Paginate `*/list` results at `page_size`; `0` disables pagination.
+    #[must_use]
+    pub const fn with_list_page_size(mut self, page_size: usize) -> Self {
+        self.list_page_size = Some(page_size);
+        self
+    }
+
+    /// Answer `completion/complete` with `completion`.
+    #[must_use]
+    pub fn with_completion<C: CompletionHandler + 'static>(self, completion: C) -> Self {
+        self.with_shared_completion(Arc::new(completion))
+    }
+
+    /// Answer `completion/complete` with an already shared handler.
+    #[must_use]
+    pub fn with_shared_completion(mut self, completion: Arc<dyn DynCompletionHandler>) -> Self {
+        self.completion = Some(completion);
+        self
+    }
+
+    /// Reject requests over the limiter's quotas, keyed by session id.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Refuse `tools/call` requests the authorizer's policy denies.
+    #[must_use]
+    pub fn with_tool_authorizer(mut self, authorizer: ToolAuthorizer) -> Self {
+        self.tool_authorizer = Some(authorizer);
+        self
+    }
+
+    /// Record audited requests (tool calls and resource reads by default),
+    /// labelled with their session id.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: AuditLogger) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Choose how tool, resource, and prompt invocations are traced,
+    /// including whether their arguments are captured (see
+    /// [`crate::invocation`]).
+    #[must_use]
+    pub fn with_invocation_tracing(mut self, tracing: InvocationTracing) -> Self {
+        self.invocation_tracing = tracing;
+        self
+    }
+
+    /// Record every request into the layer's
+    /// [`ServerMetrics`](crate::metrics::ServerMetrics), and serve the
+    /// `metrics://server` resource if the layer exposes it.
+    #[must_use]
+    pub fn with_server_metrics(mut self, metrics: MetricsLayer) -> Self {
+        self.server_metrics = Some(metrics);
+        self
+    }
+
+    /// Reject bodies over `max_message_size` with `413`, and requests beyond
+    /// `max_in_flight` with `503`.
+    ///
+    /// The read timeout applies while the framework reads the body, so
+    /// adapters configure it themselves.
+    #[must_use]
+    pub fn with_limits(mut self, limits: impl Into<ServiceLimits>) -> Self {
+        self.limits = limits.into();
+        self
+    }
+
+    /// Reject requests with `503` once `shutdown` is triggered.
+    ///
+    /// Notifications are still accepted, and requests already running are
+    /// unaffected.
+    #[must_use]
+    pub fn with_shutdown(mut self, shutdown: ShutdownHandle) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Answer requests in `mode` when the client's `Accept` header allows
+    /// both JSON and SSE (JSON by default).
+    #[must_use]
+    pub const fn with_response_mode(mut self, mode: ResponseMode) -> Self {
+        self.response_mode = mode;
+        self
+    }
+
+    /// Limit how long requests may run; see [`crate::timeout`].
+    #[must_use]
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Record each request's method and latency, and each tool call's
+    /// outcome, into `metrics`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: mcpkit_transport::McpMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// The configured limits.
+    #[must_use]
+    pub const fn limits(&self) -> &TransportLimits {
+        &self.limits.limits
+    }
+
+    /// The handler.
+    #[must_use]
+    pub const fn handler(&self) -> &Arc<H> {
+        &self.handler
+    }
+
+    /// The session storage.
+    #[must_use]
+    pub const fn sessions(&self) -> &S {
+        &self.sessions
+    }
+}
+
+impl<H, S> McpService<H, S>
+where
+    H: ServerHandler + ToolHandler + ResourceHandler + PromptHandler + Send + Sync + 'static,
+    S: SessionStorage,
+{
+    /// Handle one incoming message.
+    pub async fn handle(&self, request: ServiceRequest) -> ServiceReply {
+        let response_mode = self.response_mode.negotiate(request.accept.as_deref());
+        let mut events = Vec::new();
+        let outcome = self.process(request, &mut events).await;
+        ServiceReply {
+            outcome,
+            events,
+            response_mode,
+        }
+    }
+
+    async fn process(
+        &self,
+        request: ServiceRequest,
+        events: &mut Vec<ServiceEvent>,
+    ) -> ServiceOutcome {
+        let ServiceRequest {
+            payload,
+            origin,
+            protocol_version,
+            session_id,
+            accept: _,
+            user,
+            mut extensions,
+        } = request;
+
+        // Reject disallowed Origins (DNS-rebinding protection) before any work.
+        if !self.origin_validator.is_allowed(origin.as_deref()) {
+            warn!(
+                origin = origin.as_deref().unwrap_or("none"),
+                "Rejected: origin not allowed"
+            );
+            return ServiceOutcome::Rejected(ServiceRejection::OriginNotAllowed(origin));
+        }
+
+        if !is_supported_version(protocol_version.as_deref()) {
+            let provided = protocol_version.unwrap_or_else(|| "none".to_string());
+            warn!(version = %provided, "Unsupported protocol version");
+            return ServiceOutcome::Rejected(ServiceRejection::UnsupportedVersion(provided));
+        }
+
+        if let Payload::Bytes(body) = &payload {
+            if let Err(e) = self.limits().check_message_size(body.len()) {
+                warn!(error = %e, "Rejected: message too large");
+                return ServiceOutcome::Rejected(ServiceRejection::PayloadTooLarge {
+                    size: body.len(),
+                    max: self.limits().max_message_size,
+                });
+            }
+        }
+
+        // Expose the verified user to middleware and handlers through the
+        // request context, unless the adapter already attached one.
+        if let Some(user) = &user {
+            if extensions.get::<VerifiedUser>().is_none() {
+                extensions.insert(user.clone());
+            }
+        }
+
+        // Get or create the session, binding it to the verified user (if any).
+        let session_id = if let Some(id) = session_id {
+            match self.sessions.resume_session(&id, user.as_ref()) {
+                Ok(true) => id,
+                // Reject an unknown session id rather than silently proceeding.
+                Ok(false) => {
+                    warn!(session_id = %id, "Rejected: unknown session id");
+                    return ServiceOutcome::Rejected(ServiceRejection::SessionNotFound(id));
+                }
+                Err(e) => {
+                    warn!(session_id = %id, error = %e, "Rejected: session binding violation");
+                    return ServiceOutcome::Rejected(ServiceRejection::SessionBinding(e));
+                }
+            }
+        } else {
+            let id = self.sessions.open_session(user);
+            events.push(ServiceEvent::SessionCreated {
+                session_id: id.clone(),
+            });
+            id
+        };
+
+        debug!(session_id = %session_id, "Processing MCP request");
+        extensions.insert(SessionId(session_id.clone()));
+
+        let message = match payload {
+            Payload::Message(message) => message,
+            Payload::Bytes(body) => match serde_json::from_slice(&body) {
+                Ok(message) => message,
+                Err(e) => {
+                    warn!(error = %e, "Failed to parse JSON-RPC message");
+                    return ServiceOutcome::Rejected(ServiceRejection::InvalidMessage(
+                        e.to_string(),
+                    ));
+                }
+            },
+        };
+
+        match message {
+            Message::Request(request) => {
+                if self.shutdown.is_shutdown() {
+                    warn!(session_id = %session_id, "Rejected: server is shutting down");
+                    return ServiceOutcome::Rejected(ServiceRejection::ShuttingDown);
+                }
+                let Some(_slot) = self.limits.acquire() else {
+                    warn!(session_id = %session_id, "Rejected: too many requests in flight");
+                    return ServiceOutcome::Rejected(ServiceRejection::Overloaded);
+                };
+                info!(
+                    method = %request.method,
+                    id = ?request.id,
+                    session_id = %session_id,
+                    "Handling MCP request"
+                );
+                #[cfg(feature = "prometheus")]
+                let started = Instant::now();
+                let response = self
+                    .respond(&session_id, &request, &extensions, events)
+                    .await;
+                #[cfg(feature = "prometheus")]
+                self.record_metrics(&request, &response, started.elapsed());
+                ServiceOutcome::Response {
+                    session_id,
+                    response,
+                }
+            }
+            Message::Notification(notification) => {
+                debug!(
+                    method = %notification.method,
+                    session_id = %session_id,
+                    "Received notification"
+                );
+                ServiceOutcome::Accepted { session_id }
+            }
+            Message::Response(_) => {
+                warn!("Unexpected message type received");
+                ServiceOutcome::Rejected(ServiceRejection::UnexpectedMessage)
+            }
+        }
+    }
+
+    /// Record a handled request into the Prometheus metrics, if configured.
+    #[cfg(feature = "prometheus")]
+    fn record_metrics(&self, request: &Request, response: &Response, elapsed: Duration) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        let method = request.method.as_ref();
+        metrics.record_request(method);
+        metrics.record_response_time(method, elapsed.as_secs_f64());
+        if let Some(error) = &response.error {
+            metrics.record_error(&error.code.to_string());
+        }
+        if method == "tools/call" {
+            let tool = request
+                .params
+                .as_ref()
+                .and_then(|p| p.get("name"))
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown");
+            let is_error = response
+                .result
+                .as_ref()
+                .and_then(|r| r.get("isError"))
+                .and_then(serde_json::Value::as_bool);
+            metrics.record_tool_call(tool, response.error.is_some() || is_error == Some(true));
+        }
+    }
+
+    /// Record `initialize` negotiation, apply rate limits, and route.
+    async fn respond(
+        &self,
+        session_id: &str,
+        request: &Request,
+        extensions: &Extensions,
+        events: &mut Vec<ServiceEvent>,
+    ) -> Response {
+        // On initialize, negotiate the protocol version and record it (and
+        // the client's capabilities) on the session, so subsequent requests
+        // observe the negotiated values.
+        if request.method.as_ref() == "initialize" {
+            let (version, caps) = negotiate_initialize(request.params.as_ref());
+            self.sessions.record_negotiated(session_id, version, caps);
+            events.push(ServiceEvent::Initialized {
+                session_id: session_id.to_string(),
+                protocol_version: version,
+            });
+        }
+
+        if let Some(limiter) = &self.rate_limiter {
+            if let Err(e) = limiter.check(session_id, &request.method, request.params.as_ref()) {
+                warn!(session_id = %session_id, error = %e, "Rejected: rate limited");
+                return Response::error(request.id.clone(), e.into());
+            }
+        }
+
+        // Resolve the session's negotiated values for the request context,
+        // falling back to defaults before initialization completes.
+        let session = self
+            .sessions
+            .session_snapshot(session_id)
+            .unwrap_or_default();
+        let protocol_version = session.protocol_version.unwrap_or(ProtocolVersion::LATEST);
+        let client_caps = session.client_capabilities.unwrap_or_default();
+
+        let audit = self
+            .audit_logger
+            .as_ref()
+            .filter(|logger| logger.audits(&request.method))
+            .map(|logger| (logger, Utc::now(), Instant::now()));
+        let started = Instant::now();
+
+        let peer = EventPeer::default();
+        let response = self
+            .dispatch(
+                request,
+                protocol_version,
+                &client_caps,
+                session.tasks.as_ref(),
+                extensions,
+                &peer,
+            )
+            .await;
+
+        if let Some(metrics) = &self.server_metrics {
+            metrics.record(
+                &request.method,
+                request.params.as_ref(),
+                response
+                    .result
+                    .as_ref()
+                    .filter(|_| response.error.is_none()),
+                started.elapsed(),
+            );
+        }
+        if let Some((logger, started, clock)) = audit {
+            let outcome = match (&response.result, &response.error) {
+                (_, Some(error)) => AuditOutcome::from_result(Err(error)),
+                (Some(result), None) => AuditOutcome::from_result(Ok(result)),
+                (None, None) => AuditOutcome::Success,
+            };
+            let audited = AuditedRequest {
+                session_id: Some(session_id),
+                request_id: &request.id,
+                method: &request.method,
+                params: request.params.as_ref(),
+                user: extensions.get::<VerifiedUser>(),
+            };
+            if let Some(record) = logger.build_record(&audited, started, clock.elapsed(), outcome) {
+                logger.emit(&record);
+            }
+        }
+        events.extend(peer.take().into_iter().map(ServiceEvent::Notification));
+        response
+    }
+
+    /// Route a request through the handler traits.
+    async fn dispatch(
+        &self,
+        request: &Request,
+        protocol_version: ProtocolVersion,
+        client_caps: &ClientCapabilities,
+        task_store: Option<&Arc<TaskManager>>,
+        extensions: &Extensions,
+        peer: &EventPeer,
+    ) -> Response {
+        let method = request.method.as_ref();
+        let params = request.params.as_ref();
+
+        let server_caps = self.effective_capabilities();
+        let progress_token = params.and_then(mcpkit_core::types::Meta::progress_token_from_params);
+        let ctx = Context::new(
+            &request.id,
+            progress_token.as_ref(),
+            client_caps,
+            &server_caps,
+            protocol_version,
+            peer,
+        )
+        .with_extensions(extensions)
+        .with_invocation_tracing(&self.invocation_tracing);
+        let ctx = match task_store {
+            Some(store) => ctx.with_task_store(store),
+            None => ctx,
+        };
+        let ctx = match params.and_then(mcpkit_core::types::Meta::timeout_from_params) {
+            Some(timeout) => ctx.with_deadline(Instant::now() + timeout),
+            None => ctx,
+        };
+        let budget = self
+            .timeouts
+            .budget(method, params, Some(self.handler.as_ref()), &ctx)
+            .await;
+        let ctx = match &budget {
+            Some(budget) => {
+                let deadline = budget.deadline(ctx.deadline());
+                ctx.with_deadline(deadline)
+            }
+            None => ctx,
+        };
+
+        let result = match method {
+            "ping" => Ok(serde_json::json!({})),
+            "initialize" => Ok(serde_json::json!({
+                "protocolVersion": protocol_version.as_str(),
+                "serverInfo": self.server_info,
+                "capabilities": server_caps,
+            })),
+            _ => match self
+                .timeouts
+                .run(
+                    budget.as_ref(),
+                    self.route(method, params, &ctx, task_store, client_caps, &server_caps),
+                )
+                .instrument(mcpkit_transport::telemetry::request_span(request))
+                .await
+            {
+                Ok(Some(result)) => result,
+                Err(e) => Err(e),
+                Ok(None) => {
+                    return Response::error(
+                        request.id.clone(),
+                        JsonRpcError::method_not_found(format!("Method '{method}' not found")),
+                    );
+                }
+            },
+        };
+        match result {
+            Ok(mut value) => {
+                adapt_result(method, &mut value, protocol_version);
+                Response::success(request.id.clone(), value)
+            }
+            Err(e) => Response::error(request.id.clone(), e.into()),
+        }
+    }
+
+    async fn route(
+        &self,
+        method: &str,
+        params: Option<&serde_json::Value>,
+        ctx: &Context<'_>,
+        task_store: Option<&Arc<TaskManager>>,
+        client_caps: &ClientCapabilities,
+        server_caps: &ServerCapabilities,
+    ) -> Option<Result<serde_json::Value, McpError>> {
+        if let Some(authorizer) = &self.tool_authorizer {
+            if let Err(e) = authorizer.check(method, params, ctx) {
+                return Some(Err(e));
+            }
+        }
+
+        // Task-augmented tools/call and tasks/* are served from this
+        // session's own task store (per-session isolation).
+        if let Some(store) = task_store {
+            if method == "tools/call" {
+                match begin_augmented_task(
+                    self.handler.clone(),
+                    store,
+                    params,
+                    client_caps.clone(),
+                    server_caps.clone(),
+                    ctx.protocol_version,
+                )
+                .await
+                {
+                    AugmentedTaskOutcome::Started(create_result, fut) => {
+                        tokio::spawn(fut);
+                        return Some(Ok(create_result));
+                    }
+                    AugmentedTaskOutcome::Rejected(e) => return Some(Err(e)),
+                    // Not task-augmented — fall through to the normal path.
+                    AugmentedTaskOutcome::NotApplicable => {}
+                }
+            } else if let Some(result) = route_task_store(store, method, params).await {
+                return Some(result);
+            }
+        }
+
+        let handler = self.handler.as_ref();
+        if let Some(result) = route_tools(handler, method, params, ctx, self.list_page_size).await {
+            return Some(result);
+        }
+        if let Some(metrics) = &self.server_metrics {
+            if let Some(result) = metrics.read(method, params) {
+                return Some(result);
+            }
+        }
+        if let Some(result) =
+            route_resources(handler, method, params, ctx, self.list_page_size).await
+        {
+            return Some(match &self.server_metrics {
+                Some(metrics) => metrics.list(method, result),
+                None => result,
+            });
+        }
+        if let Some(result) = route_prompts(handler, method, params, ctx, self.list_page_size).await
+        {
+            return Some(result);
+        }
+        // logging/setLevel is gated on the advertised capability.
+        if let Some(result) = route_logging(handler, server_caps, method, params, ctx).await {
+            return Some(result);
+        }
+        route_completion(self.completion.as_deref(), method, params, ctx).await
+    }
+}
+
+/// Negotiate the protocol version and extract client capabilities from an
+/// `initialize` request's params.
+///
+/// The negotiated version is the highest supported version not exceeding the
+/// client's requested version, falling back to the latest supported version
+/// when the request omits or names an unknown version.
+#[must_use]
+pub fn negotiate_initialize(
+    params: Option<&serde_json::Value>,
+) -> (ProtocolVersion, Option<ClientCapabilities>) {
+    let requested = params
+        .and_then(|p| p.get("protocolVersion"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("");
+    let version = ProtocolVersion::negotiate(requested, ProtocolVersion::ALL)
+        .unwrap_or(ProtocolVersion::LATEST);
+    let capabilities = params
+        .and_then(|p| p.get("capabilities"))
+        .and_then(|c| serde_json::from_value::<ClientCapabilities>(c.clone()).ok());
+    (version, capabilities)
+}
+
+/// Collects notifications sent through the request context.
+///
+/// Notifications sent after the response (e.g. from a spawned task holding a
+/// progress reporter) have no response to ride along with and are dropped.
+#[derive(Clone, Default)]
+struct EventPeer(Arc<Mutex<Vec<Notification>>>);
+
+impl EventPeer {
+    fn take(&self) -> Vec<Notification> {
+        self.0
+            .lock()
+            .map(|mut seen| std::mem::take(&mut *seen))
+            .unwrap_or_default()
+    }
+}
+
+impl Peer for EventPeer {
+    fn notify(
+        &self,
+        notification: Notification,
+    ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+        if let Ok(mut seen) = self.0.lock() {
+            seen.push(notification);
+        }
+        Box::pin(async { Ok(()) })
+    }
+
+    fn to_shared(&self) -> Option<Arc<dyn Peer>> {
+        Some(Arc::new(self.clone()))
+    }
+}
+
+/// Default idle timeout for [`MemorySessions`].
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(3600);
+
+#[derive(Debug)]
+struct MemorySession {
+    last_active: Instant,
+    user: Option<VerifiedUser>,
+    snapshot: SessionSnapshot,
+}
+
+/// A simple in-memory [`SessionStorage`].
+///
+/// Sessions idle longer than the timeout are reaped whenever a new session is
+/// opened. Clones share the same sessions.
+#[derive(Debug, Clone)]
+pub struct MemorySessions {
+    sessions: Arc<RwLock<HashMap<String, MemorySession>>>,
+    idle_timeout: Duration,
+    default_task_ttl: Option<u64>,
+}
+
+impl Default for MemorySessions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemorySessions {
+    /// Create an empty store with the default idle timeout.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::default(),
+            idle_timeout: DEFAULT_SESSION_TIMEOUT,
+            default_task_ttl: Some(crate::capability::tasks::DEFAULT_TASK_TTL_MS),
+        }
+    }
+
+    /// Reap sessions idle longer than `idle_timeout`.
+    #[must_use]
+    pub const fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Default task retention (ms) for each session's task store; `None`
+    /// means unlimited.
+    #[must_use]
+    pub const fn with_task_ttl(mut self, default_task_ttl: Option<u64>) -> Self {
+        self.default_task_ttl = default_task_ttl;
+        self
+    }
+
+    /// Number of live sessions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.sessions.read().map_or(0, |sessions| sessions.len())
+    }
+
+    /// Whether there are no live sessions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove a session, returning whether it existed.
+    #[must_use]
+    pub fn remove(&self, id: &str) -> bool {
+        self.sessions
+            .write()
+            .is_ok_and(|mut sessions| sessions.remove(id).is_some())
+    }
+}
+
+impl SessionStorage for MemorySessions {
+    fn open_session(&self, user: Option<VerifiedUser>) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let session = MemorySession {
+            last_active: Instant::now(),
+            user,
+            snapshot: SessionSnapshot {
+                tasks: Some(Arc::new(TaskManager::with_default_ttl(
+                    self.default_task_ttl,
+                ))),
+                ..SessionSnapshot::default()
+            },
+        };
+        if let Ok(mut sessions) = self.sessions.write() {
+            let timeout = self.idle_timeout;
+            sessions.retain(|_, s| s.last_active.elapsed() < timeout);
+            sessions.insert(id.clone(), session);
+        }
+        id
+    }
+
+    fn resume_session(
+        &self,
+        id: &str,
+        user: Option<&VerifiedUser>,
+    ) -> Result<bool, SessionBindingError> {
+        let Ok(mut sessions) = self.sessions.write() else {
+            return Ok(false);
+        };
+        let Some(session) = sessions.get_mut(id) else {
+            return Ok(false);
+        };
+        check_session_binding(session.user.as_ref(), user)?;
+        session.last_active = Instant::now();
+        Ok(true)
+    }
+
+    fn record_negotiated(
+        &self,
+        id: &str,
+        protocol_version: ProtocolVersion,
+        capabilities: Option<ClientCapabilities>,
+    ) {
+        if let Ok(mut sessions) = self.sessions.write() {
+            if let Some(session) = sessions.get_mut(id) {
+                session.snapshot.protocol_version = Some(protocol_version);
+                session.snapshot.client_capabilities = capabilities;
+            }
+        }
+    }
+
+    fn session_snapshot(&self, id: &str) -> Option<SessionSnapshot> {
+        self.sessions
+            .read()
+            .ok()
+            .and_then(|sessions| sessions.get(id).map(|s| s.snapshot.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::types::logging::LoggingLevel;
+    use mcpkit_core::types::{
+        GetPromptResult, Prompt, Resource, ResourceContents, Tool, ToolOutput,
+    };
+
+    struct Echo;
+
+    impl ServerHandler for Echo {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("echo", "1.0.0")
+        }
+
+        fn capabilities(&self) -> ServerCapabilities {
+            ServerCapabilities::new().with_tools().with_logging()
+        }
+    }
+
+    impl ToolHandler for Echo {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(vec![Tool::new("echo").title("Echo")])
+        }
+
+        async fn call_tool(
+            &self,
+            name: &str,
+            args: serde_json::Map<String, serde_json::Value>,
+            ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            if name == "deadline" {
+                let remaining = ctx
+                    .deadline()
+                    .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+                return Ok(ToolOutput::text(format!("{remaining:?}")));
+            }
+            if name == "stall" {
+                return std::future::pending().await;
+            }
+            ctx.log(LoggingLevel::Info, None, serde_json::json!("echoing"))
+                .await?;
+            Ok(ToolOutput::text(
+                serde_json::Value::Object(args).to_string(),
+            ))
+        }
+    }
+
+    impl ResourceHandler for Echo {
+        async fn list_resources(&self, _ctx: &Context<'_>) -> Result<Vec<Resource>, McpError> {
+            Ok(vec![])
+        }
+
+        async fn read_resource(
+            &self,
+            uri: &str,
+            _ctx: &Context<'_>,
+        ) -> Result<Vec<ResourceContents>, McpError> {
+            Ok(vec![ResourceContents::text(uri, "")])
+        }
+    }
+
+    impl PromptHandler for Echo {
+        async fn list_prompts(&self, _ctx: &Context<'_>) -> Result<Vec<Prompt>, McpError> {
+            Ok(vec![])
+        }
+
+        async fn get_prompt(
+            &self,
+            name: &str,
+            _args: Option<serde_json::Map<String, serde_json::Value>>,
+            _ctx: &Context<'_>,
+        ) -> Result<GetPromptResult, McpError> {
+            Err(McpError::invalid_params(
+                "prompts/get",
+                format!("no prompt {name}"),
+            ))
+        }
+    }
+
+    fn body(value: &serde_json::Value) -> ServiceRequest {
+        ServiceRequest::from_bytes(value.to_string())
+    }
+
+    fn expect_response(reply: ServiceReply) -> (String, Response) {
+        match reply.outcome {
+            ServiceOutcome::Response {
+                session_id,
+                response,
+            } => (session_id, response),
+            other => panic!("expected a response, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn initialize_negotiates_and_opens_a_session() {
+        let service = McpService::new(Echo, MemorySessions::new());
+        let reply = service
+            .handle(body(&serde_json::json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "2025-06-18", "capabilities": {} }
+            })))
+            .await;
+        assert!(matches!(
+            reply.events[0],
+            ServiceEvent::SessionCreated { .. }
+        ));
+        assert!(matches!(
+            reply.events[1],
+            ServiceEvent::Initialized {
+                protocol_version: ProtocolVersion::V2025_06_18,
+                ..
+            }
+        ));
+        let (session_id, response) = expect_response(reply);
+        assert_eq!(response.result.unwrap()["protocolVersion"], "2025-06-18");
+
+        let snapshot = service.sessions().session_snapshot(&session_id).unwrap();
+        assert_eq!(
+            snapshot.protocol_version,
+            Some(ProtocolVersion::V2025_06_18)
+        );
+    }
+
+    #[tokio::test]
+    async fn results_are_encoded_for_the_negotiated_version() {
+        let service = McpService::new(Echo, MemorySessions::new());
+        let list_tools = |version: &'static str| {
+            let service = &service;
+            async move {
+                let reply = service
+                    .handle(body(&serde_json::json!({
+                        "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                        "params": { "protocolVersion": version, "capabilities": {} }
+                    })))
+                    .await;
+                let (session_id, _) = expect_response(reply);
+                let reply = service
+                    .handle(
+                        body(&serde_json::json!({
+                            "jsonrpc": "2.0", "id": 2, "method": "tools/list"
+                        }))
+                        .with_session_id(Some(session_id)),
+                    )
+                    .await;
+                expect_response(reply).1.result.unwrap()["tools"][0].clone()
+            }
+        };
+
+        assert_eq!(list_tools("2025-11-25").await["title"], "Echo");
+        assert!(list_tools("2025-06-18").await.get("title").is_some());
+        assert!(list_tools("2025-03-26").await.get("title").is_none());
+    }
+
+    #[tokio::test]
+    async fn tool_notifications_are_reported_as_events() {
+        let service = McpService::new(Echo, MemorySessions::new());
+        let reply = service
+            .handle(body(&serde_json::json!({
+                "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+                "params": { "name": "echo", "arguments": { "x": 1 } }
+            })))
+            .await;
+        let notifications: Vec<_> = reply
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                ServiceEvent::Notification(n) => Some(n.method.as_ref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(notifications, ["notifications/message"]);
+        let (_, response) = expect_response(reply);
+        assert!(response.is_success());
+    }
+
+    #[test]
+    fn response_mode_follows_the_accept_header() {
+        use ResponseMode::{Json, Sse};
+
+        assert_eq!(Sse.negotiate(Some("application/json")), Json);
+        assert_eq!(Json.negotiate(Some("text/event-stream")), Sse);
+        let both = Some("application/json, text/event-stream");
+        assert_eq!(Json.negotiate(both), Json);
+        assert_eq!(Sse.negotiate(both), Sse);
+        assert_eq!(Sse.negotiate(Some("*/*")), Sse);
+        assert_eq!(Sse.negotiate(None), Sse);
+        assert_eq!(
+            Sse.negotiate(Some("text/event-stream;q=0, application/json")),
+            Json
+        );
+    }
+
+    #[tokio::test]
+    async fn sse_mode_streams_notifications_before_the_response() {
+        let service =
+            McpService::new(Echo, MemorySessions::new()).with_response_mode(ResponseMode::Sse);
+        let call = serde_json::json!({
+            "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+            "params": { "name": "echo", "arguments": {} }
+        });
+
+        let reply = service.handle(body(&call)).await;
+        assert_eq!(reply.response_mode, ResponseMode::Sse);
+        let sse = reply.sse_body().expect("an SSE body");
+        let events: Vec<Message> = sse
+            .split("\n\n")
+            .filter_map(|event| event.strip_prefix("event: message\ndata: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+        assert!(matches!(&events[..], [
+            Message::Notification(n),
+            Message::Response(r),
+        ] if n.method == "notifications/message" && r.is_success()));
+
+        // A client that only takes JSON still gets JSON.
+        let reply = service
+            .handle(body(&call).with_accept(Some("application/json".into())))
+            .await;
+        assert_eq!(reply.response_mode, ResponseMode::Json);
+        assert!(reply.sse_body().is_none());
+    }
+
+    #[tokio::test]
+    async fn request_timeout_in_meta_sets_the_deadline() {
+        let service = McpService::new(Echo, MemorySessions::new());
+        let call = |params: serde_json::Value| {
+            service.handle(body(&serde_json::json!({
+                "jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": params
+            })))
+        };
+
+        let reply = call(serde_json::json!({
+            "name": "deadline",
+            "_meta": { "mcpkit/timeoutMs": 60_000 }
+        }))
+        .await;
+        let (_, response) = expect_response(reply);
+        let text = response.result.unwrap()["content"][0]["text"].clone();
+        assert!(text.as_str().unwrap().starts_with("Some("), "{text}");
+
+        let reply = call(serde_json::json!({ "name": "deadline" })).await;
+        let (_, response) = expect_response(reply);
+        assert_eq!(response.result.unwrap()["content"][0]["text"], "None");
+    }
+
+    #[tokio::test]
+    async fn timeouts_cancel_calls_and_tighten_the_deadline() {
+        let service = McpService::new(Echo, MemorySessions::new()).with_timeouts(
+            Timeouts::new()
+                .method("tools/call", Duration::from_millis(20))
+                .tool("deadline", Duration::from_secs(5)),
+        );
+        let call = |name: &str| {
+            service.handle(body(&serde_json::json!({
+                "jsonrpc": "2.0", "id": 4, "method": "tools/call",
+                "params": { "name": name, "_meta": { "mcpkit/timeoutMs": 60_000 } }
+            })))
+        };
+
+        let (_, response) = expect_response(call("stall").await);
+        assert_eq!(response.error.expect("a timeout error").code, -32007);
+
+        let (_, response) = expect_response(call("deadline").await);
+        let text = response.result.unwrap()["content"][0]["text"].clone();
+        let secs: f64 = text.as_str().unwrap()["Some(".len()..]
+            .trim_end_matches("s)")
+            .parse()
+            .unwrap_or(f64::MAX);
+        assert!(secs <= 5.0, "{text}");
+    }
+
+    #[tokio::test]
+    async fn rejections_carry_status_codes() {
+        let service = McpService::new(Echo, MemorySessions::new());
+
+        let reply = service
+            .handle(
+                ServiceRequest::from_bytes("{}").with_origin(Some("https://evil.example".into())),
+            )
+            .await;
+        assert!(matches!(
+            reply.outcome,
+            ServiceOutcome::Rejected(ref r) if r.status_code() == 403
+        ));
+
+        let reply = service
+            .handle(
+                ServiceRequest::from_bytes("{}").with_protocol_version(Some("1999-01-01".into())),
+            )
+            .await;
+        assert!(matches!(
+            reply.outcome,
+            ServiceOutcome::Rejected(ServiceRejection::UnsupportedVersion(_))
+        ));
+
+        let reply = service
+            .handle(ServiceRequest::from_bytes("{}").with_session_id(Some("missing".into())))
+            .await;
+        assert!(matches!(
+            reply.outcome,
+            ServiceOutcome::Rejected(ref r) if r.status_code() == 404
+        ));
+
+        let reply = service.handle(ServiceRequest::from_bytes("not json")).await;
+        assert!(matches!(
+            reply.outcome,
+            ServiceOutcome::Rejected(ServiceRejection::InvalidMessage(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn limits_reject_large_bodies_and_excess_requests() {
+        let ping = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" });
+        let service = McpService::new(Echo, MemorySessions::new())
+            .with_limits(TransportLimits::new().with_max_message_size(16));
+        let reply = service.handle(body(&ping)).await;
+        assert!(matches!(
+            reply.outcome,
+            ServiceOutcome::Rejected(ref r @ ServiceRejection::PayloadTooLarge { max: 16, .. })
+                if r.status_code() == 413
+        ));
+
+        // Slots are released once a request is answered.
+        let service = McpService::new(Echo, MemorySessions::new())
+            .with_limits(TransportLimits::new().with_max_in_flight(1));
+        expect_response(service.handle(body(&ping)).await);
+        expect_response(service.handle(body(&ping)).await);
+
+        let service = McpService::new(Echo, MemorySessions::new())
+            .with_limits(TransportLimits::new().with_max_in_flight(0));
+        let reply = service.handle(body(&ping)).await;
+        assert!(matches!(
+            reply.outcome,
+            ServiceOutcome::Rejected(ref r @ ServiceRejection::Overloaded) if r.status_code() == 503
+        ));
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn metrics_record_requests_and_tool_calls() -> Result<(), Box<dyn std::error::Error>> {
+        let metrics = mcpkit_transport::McpMetrics::new()?;
+        let service = McpService::new(Echo, MemorySessions::new()).with_metrics(metrics.clone());
+        let call = serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": { "name": "echo", "arguments": {} }
+        });
+        let unknown = serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "nope" });
+        expect_response(service.handle(body(&call)).await);
+        expect_response(service.handle(body(&unknown)).await);
+
+        let count = |method: &str| metrics.requests_total.with_label_values(&[method]).get();
+        assert!((count("tools/call") - 1.0).abs() < f64::EPSILON);
+        assert!((count("nope") - 1.0).abs() < f64::EPSILON);
+        let tool_calls = metrics.tool_calls_total.with_label_values(&["echo"]).get();
+        assert!((tool_calls - 1.0).abs() < f64::EPSILON);
+        assert!(metrics.tool_errors_total.with_label_values(&["echo"]).get() < 0.5);
+        let not_found = metrics.errors_total.with_label_values(&["-32601"]).get();
+        assert!((not_found - 1.0).abs() < f64::EPSILON);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sessions_are_bound_to_their_user() {
+        let service = McpService::new(Echo, MemorySessions::new());
+        let alice = VerifiedUser::new("alice").issuer("https://idp");
+        let bob = VerifiedUser::new("bob").issuer("https://idp");
+        let ping = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" });
+
+        let reply = service
+            .handle(body(&ping).with_user(Some(alice.clone())))
+            .await;
+        let (session_id, _) = expect_response(reply);
+
+        let reply = service
+            .handle(
+                body(&ping)
+                    .with_session_id(Some(session_id.clone()))
+                    .with_user(Some(alice)),
+            )
+            .await;
+        expect_response(reply);
+
+        let reply = service
+            .handle(
+                body(&ping)
+                    .with_session_id(Some(session_id))
+                    .with_user(Some(bob)),
+            )
+            .await;
+        assert!(matches!(
+            reply.outcome,
+            ServiceOutcome::Rejected(ServiceRejection::SessionBinding(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn tool_policy_sees_the_verified_user() {
+        use crate::policy::DeclarativePolicy;
+
+        let policy = DeclarativePolicy::new().require_role("echo", "admin");
+        let service = McpService::new(Echo, MemorySessions::new())
+            .with_tool_authorizer(ToolAuthorizer::new(policy));
+        let call = serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": { "name": "echo", "arguments": {} }
+        });
+
+        let reply = service
+            .handle(body(&call).with_user(Some(VerifiedUser::new("alice"))))
+            .await;
+        let (_, response) = expect_response(reply);
+        assert_eq!(response.error.expect("denied").code, -32011);
+
+        let admin = VerifiedUser::new("root").roles(["admin"]);
+        let reply = service.handle(body(&call).with_user(Some(admin))).await;
+        let (_, response) = expect_response(reply);
+        assert!(response.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn audit_records_carry_the_session_and_user() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let records = records.clone();
+            move |r: &crate::audit::AuditRecord| records.lock().unwrap().push(r.clone())
+        };
+        let service = McpService::new(Echo, MemorySessions::new())
+            .with_audit_logger(AuditLogger::new().sink(sink));
+        let call = serde_json::json!({
+            "jsonrpc": "2.0", "id": 4, "method": "tools/call",
+            "params": { "name": "echo", "arguments": { "x": 1 } }
+        });
+        let reply = service
+            .handle(body(&call).with_user(Some(VerifiedUser::new("alice"))))
+            .await;
+        let (session_id, _) = expect_response(reply);
+        let ping = serde_json::json!({ "jsonrpc": "2.0", "id": 5, "method": "ping" });
+        service
+            .handle(body(&ping).with_session_id(Some(session_id.clone())))
+            .await;
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 1, "ping is not audited");
+        assert_eq!(records[0].session_id.as_deref(), Some(session_id.as_str()));
+        assert_eq!(records[0].subject.as_deref(), Some("alice"));
+        assert_eq!(records[0].tool.as_deref(), Some("echo"));
+        assert_eq!(records[0].outcome, AuditOutcome::Success);
+    }
+
+    #[tokio::test]
+    async fn notifications_are_accepted_and_unknown_methods_fail() {
+        let service = McpService::new(Echo, MemorySessions::new());
+        let reply = service
+            .handle(ServiceRequest::from_message(Message::Notification(
+                Notification::new("notifications/initialized"),
+            )))
+            .await;
+        assert!(matches!(reply.outcome, ServiceOutcome::Accepted { .. }));
+
+        let reply = service
+            .handle(body(&serde_json::json!({
+                "jsonrpc": "2.0", "id": 3, "method": "nope"
+            })))
+            .await;
+        let (_, response) = expect_response(reply);
+        assert!(response.is_error());
+    }
+
+    #[test]
+    fn negotiate_uses_requested_supported_version() {
+        let params = serde_json::json!({
+            "protocolVersion": "2025-06-18",
+            "capabilities": {}
+        });
+        let (version, caps) = negotiate_initialize(Some(&params));
+        assert_eq!(version, ProtocolVersion::V2025_06_18);
+        assert!(caps.is_some());
+    }
+
+    #[test]
+    fn negotiate_defaults_to_latest_when_absent() {
+        let (version, caps) = negotiate_initialize(None);
+        assert_eq!(version, ProtocolVersion::LATEST);
+        assert!(caps.is_none());
+    }
+
+    #[test]
+    fn negotiate_unknown_version_falls_back_to_latest() {
+        let params = serde_json::json!({ "protocolVersion": "2099-01-01" });
+        let (version, _caps) = negotiate_initialize(Some(&params));
+        assert_eq!(version, ProtocolVersion::LATEST);
+    }
+}
@@ -0,0 +1,265 @@
+//! Session lifetime settings for the HTTP framework adapters.
+//!
+//! A [`SessionConfig`] is given to an adapter's `McpRouter` with
+//! `with_session_config`. It bounds how long SSE sessions live and how many
+//! may be open at once, and sets the interval of the keep-alive comments
+//! written to idle SSE streams:
+//!
+//! - a session with no traffic for [`idle_timeout`](SessionConfig::idle_timeout)
+//!   is evicted, as is one older than [`max_age`](SessionConfig::max_age);
+//! - opening a session beyond [`max_sessions`](SessionConfig::max_sessions)
+//!   evicts the least recently active one;
+//! - an SSE stream gets a keep-alive comment every
+//!   [`heartbeat_interval`](SessionConfig::heartbeat_interval) it has nothing
+//!   else to send. Heartbeats do not count as activity.
+//!
+//! Each adapter's session manager runs a background reaper task that checks
+//! its sessions every [`reap_interval`](SessionConfig::reap_interval). Evicting
+//! a session ends its SSE stream, and the callbacks registered with
+//! [`SessionConfig::on_evict`] are told which session went and why.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::session::{EvictionReason, SessionConfig};
+//! use std::time::Duration;
+//!
+//! let config = SessionConfig::new()
+//!     .with_idle_timeout(Duration::from_secs(600))
+//!     .with_max_age(Duration::from_secs(24 * 3600))
+//!     .with_max_sessions(10_000)
+//!     .on_evict(|id, reason| tracing::info!(session_id = id, %reason, "session evicted"));
+//!
+//! let now = std::time::Instant::now();
+//! assert_eq!(config.eviction_reason(now, now), None);
+//! assert_eq!(EvictionReason::Capacity.as_str(), "capacity");
+//! ```
+
+use crate::service::DEFAULT_SESSION_TIMEOUT;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default interval between keep-alive comments on an idle SSE stream.
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Default interval between the reaper's sweeps of a session manager.
+pub const DEFAULT_REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Why a session was evicted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvictionReason {
+    /// The session had no traffic for the idle timeout.
+    Idle,
+    /// The session outlived the maximum session age.
+    MaxAge,
+    /// The session was the least recently active when a new session needed
+    /// room under the maximum session count.
+    Capacity,
+}
+
+impl EvictionReason {
+    /// The reason as a lowercase string, for logs and metrics labels.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::MaxAge => "max_age",
+            Self::Capacity => "capacity",
+        }
+    }
+}
+
+impl fmt::Display for EvictionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+type EvictionHook = dyn Fn(&str, EvictionReason) + Send + Sync;
+
+/// Session lifetime and keep-alive settings.
+///
+/// Cloning is cheap and clones share the eviction callbacks.
+#[derive(Clone)]
+pub struct SessionConfig {
+    /// Evict sessions with no traffic for this long. Defaults to
+    /// [`DEFAULT_SESSION_TIMEOUT`].
+    pub idle_timeout: Duration,
+    /// Evict sessions older than this, however active. `None` (the default)
+    /// lets sessions live as long as they stay active.
+    pub max_age: Option<Duration>,
+    /// Interval between keep-alive comments on an idle SSE stream; `None`
+    /// disables them. Defaults to [`DEFAULT_HEARTBEAT_INTERVAL`].
+    pub heartbeat_interval: Option<Duration>,
+    /// Maximum number of open sessions; `None` (the default) is unbounded.
+    pub max_sessions: Option<usize>,
+    /// Interval between the reaper's sweeps. Defaults to
+    /// [`DEFAULT_REAP_INTERVAL`].
+    pub reap_interval: Duration,
+    hooks: Vec<Arc<EvictionHook>>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout: DEFAULT_SESSION_TIMEOUT,
+            max_age: None,
+            heartbeat_interval: Some(DEFAULT_HEARTBEAT_INTERVAL),
+            max_sessions: None,
+            reap_interval: DEFAULT_REAP_INTERVAL,
+            hooks: Vec::new(),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Create a configuration with the defaults.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the idle timeout.
+    #[must_use]
+    pub const fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Set the maximum session age.
+    #[must_use]
+    pub const fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Set the keep-alive interval; `None` disables keep-alive comments.
+    #[must_use]
+    pub const fn with_heartbeat_interval(mut self, interval: Option<Duration>) -> Self {
+        self.heartbeat_interval = interval;
+        self
+    }
+
+    /// Set the maximum number of open sessions.
+    #[must_use]
+    pub const fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = Some(max_sessions);
+        self
+    }
+
+    /// Set the interval between the reaper's sweeps.
+    #[must_use]
+    pub const fn with_reap_interval(mut self, interval: Duration) -> Self {
+        self.reap_interval = interval;
+        self
+    }
+
+    /// Call `hook` with the id of every evicted session and the reason.
+    ///
+    /// Hooks run on the reaper task, or on the request opening a session
+    /// for [`EvictionReason::Capacity`], so they should not block.
+    #[must_use]
+    pub fn on_evict<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, EvictionReason) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Why a session created at `created_at` and last active at
+    /// `last_active` should be evicted now, or `None` if it should be kept.
+    #[must_use]
+    pub fn eviction_reason(
+        &self,
+        created_at: Instant,
+        last_active: Instant,
+    ) -> Option<EvictionReason> {
+        if self.max_age.is_some_and(|max| created_at.elapsed() >= max) {
+            Some(EvictionReason::MaxAge)
+        } else if last_active.elapsed() >= self.idle_timeout {
+            Some(EvictionReason::Idle)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `count` open sessions leave no room for another.
+    #[must_use]
+    pub fn at_capacity(&self, count: usize) -> bool {
+        self.max_sessions.is_some_and(|max| count >= max)
+    }
+
+    /// Log an eviction and pass it to the [`on_evict`](Self::on_evict)
+    /// callbacks.
+    pub fn notify_evicted(&self, session_id: &str, reason: EvictionReason) {
+        tracing::debug!(session_id, %reason, "Session evicted");
+        for hook in &self.hooks {
+            hook(session_id, reason);
+        }
+    }
+}
+
+impl fmt::Debug for SessionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionConfig")
+            .field("idle_timeout", &self.idle_timeout)
+            .field("max_age", &self.max_age)
+            .field("heartbeat_interval", &self.heartbeat_interval)
+            .field("max_sessions", &self.max_sessions)
+            .field("reap_interval", &self.reap_interval)
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn eviction_reason_checks_age_before_idleness() {
+        let config = SessionConfig::new()
+            .with_idle_timeout(Duration::from_secs(60))
+            .with_max_age(Duration::from_secs(600));
+        let now = Instant::now();
+        let long_ago = now.checked_sub(Duration::from_secs(3600)).unwrap();
+        let a_while_ago = now.checked_sub(Duration::from_secs(120)).unwrap();
+
+        assert_eq!(config.eviction_reason(now, now), None);
+        assert_eq!(
+            config.eviction_reason(a_while_ago, a_while_ago),
+            Some(EvictionReason::Idle)
+        );
+        assert_eq!(
+            config.eviction_reason(long_ago, now),
+            Some(EvictionReason::MaxAge)
+        );
+        assert_eq!(
+            SessionConfig::new().eviction_reason(long_ago, now),
+            None,
+            "no maximum age by default"
+        );
+    }
+
+    #[test]
+    fn capacity_and_hooks() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&seen);
+        let config = SessionConfig::new()
+            .with_max_sessions(2)
+            .on_evict(move |id, reason| recorded.lock().unwrap().push((id.to_string(), reason)));
+
+        assert!(!config.at_capacity(1));
+        assert!(config.at_capacity(2));
+        assert!(!SessionConfig::new().at_capacity(usize::MAX));
+
+        config.notify_evicted("s1", EvictionReason::Capacity);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![("s1".to_string(), EvictionReason::Capacity)]
+        );
+    }
+}
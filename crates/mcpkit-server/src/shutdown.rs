@@ -0,0 +1,124 @@
+//! Graceful shutdown.
+//!
+//! A [`ShutdownHandle`] stops a running server cleanly. Take one before
+//! serving, then trigger it from anywhere — a signal handler, an admin
+//! endpoint, a test:
+//!
+//! ```no_run
+//! use mcpkit_server::{ServerBuilder, ServerHandler};
+//! use mcpkit_core::capability::ServerInfo;
+//! use mcpkit_transport::stdio::StdioTransport;
+//!
+//! struct MyHandler;
+//! impl ServerHandler for MyHandler {
+//!     fn server_info(&self) -> ServerInfo {
+//!         ServerInfo::new("my-server", "1.0.0")
+//!     }
+//! }
+//!
+//! # async fn example() -> Result<(), mcpkit_core::error::McpError> {
+//! let server = ServerBuilder::new(MyHandler).build();
+//! let shutdown = server.shutdown_handle();
+//! shutdown.shutdown_on_ctrl_c();
+//!
+//! server.serve(StdioTransport::new()).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Once triggered, the server runtime:
+//!
+//! 1. answers new requests with an error instead of running them;
+//! 2. waits up to [`RuntimeConfig::shutdown_timeout`] for in-flight handlers
+//!    and running tasks to finish, delivering the notifications they send;
+//! 3. cancels whatever is still running, giving it
+//!    [`RuntimeConfig::cancellation_grace`] to announce its final status;
+//! 4. closes the transport and returns from `serve`.
+//!
+//! The web framework routers take the same handle through their
+//! `shutdown_handle` method and drain open HTTP connections the same way.
+//!
+//! [`RuntimeConfig::shutdown_timeout`]: crate::RuntimeConfig::shutdown_timeout
+//! [`RuntimeConfig::cancellation_grace`]: crate::RuntimeConfig::cancellation_grace
+
+use crate::context::{CancellationToken, CancelledFuture};
+use std::time::Duration;
+
+/// Default time a shutdown waits for in-flight work before cancelling it.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A cloneable trigger that stops a running server gracefully.
+///
+/// All clones share one state: triggering any of them shuts the server down,
+/// and triggering it again has no further effect.
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownHandle {
+    token: CancellationToken,
+}
+
+impl ShutdownHandle {
+    /// Create a handle that has not been triggered.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Begin shutting down.
+    pub fn shutdown(&self) {
+        if !self.token.is_cancelled() {
+            tracing::info!("Shutdown requested");
+        }
+        self.token.cancel();
+    }
+
+    /// Whether shutdown has been requested.
+    #[must_use]
+    pub fn is_shutdown(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// A future that completes once shutdown has been requested.
+    ///
+    /// The future is `'static`, so it can be handed to a framework's own
+    /// graceful-shutdown hook.
+    #[must_use]
+    pub fn wait(&self) -> CancelledFuture {
+        self.token.cancelled()
+    }
+
+    /// Trigger this handle when the process receives ctrl-c (`SIGINT`).
+    ///
+    /// Spawns a background task on the current tokio runtime, so call it
+    /// from within one.
+    #[cfg(feature = "tokio-runtime")]
+    pub fn shutdown_on_ctrl_c(&self) {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            match tokio::signal::ctrl_c().await {
+                Ok(()) => handle.shutdown(),
+                Err(e) => tracing::warn!(error = %e, "Cannot listen for ctrl-c"),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn clones_share_the_trigger() {
+        let handle = ShutdownHandle::new();
+        let waiter = handle.clone();
+        assert!(!waiter.is_shutdown());
+
+        let wait = tokio::spawn(waiter.wait());
+        handle.shutdown();
+        handle.shutdown();
+        tokio::time::timeout(Duration::from_secs(1), wait)
+            .await
+            .expect("wait completes after shutdown")
+            .unwrap();
+        assert!(waiter.is_shutdown());
+    }
+}
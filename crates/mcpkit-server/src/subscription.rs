@@ -0,0 +1,167 @@
+//! Resource subscription tracking.
+//!
+//! A [`SubscriptionManager`] records which resource URIs the connected client
+//! has subscribed to via `resources/subscribe`. The server runtime keeps one
+//! per connection and attaches it to every [`Context`](crate::Context). The
+//! router records a subscription once the
+//! [`ResourceHandler`](crate::ResourceHandler) accepts it and drops it on
+//! `resources/unsubscribe`. Handlers then call
+//! [`Context::notify_resource_updated`](crate::Context::notify_resource_updated),
+//! which sends `notifications/resources/updated` only for subscribed URIs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::SubscriptionManager;
+//!
+//! let subs = SubscriptionManager::new();
+//! assert!(subs.subscribe("file:///notes.md"));
+//! assert!(!subs.subscribe("file:///notes.md")); // already subscribed
+//! assert!(subs.is_subscribed("file:///notes.md"));
+//!
+//! assert!(subs.unsubscribe("file:///notes.md"));
+//! assert!(subs.is_empty());
+//! ```
+
+use crate::context::Peer;
+use crate::router::notifications::RESOURCES_UPDATED;
+use mcpkit_core::error::McpError;
+use mcpkit_core::protocol::Notification;
+use mcpkit_core::types::ResourceUpdatedNotification;
+use std::collections::BTreeSet;
+use std::sync::{Arc, RwLock};
+
+/// Thread-safe set of subscribed resource URIs for one connection.
+///
+/// Cloning is cheap and clones share the same set.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionManager {
+    uris: Arc<RwLock<BTreeSet<String>>>,
+}
+
+impl SubscriptionManager {
+    /// Create an empty subscription set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a subscription. Returns `false` if it already existed.
+    pub fn subscribe(&self, uri: impl Into<String>) -> bool {
+        self.uris
+            .write()
+            .is_ok_and(|mut uris| uris.insert(uri.into()))
+    }
+
+    /// Remove a subscription. Returns `false` if there was none.
+    #[must_use = "returns whether a subscription was removed"]
+    pub fn unsubscribe(&self, uri: &str) -> bool {
+        self.uris.write().is_ok_and(|mut uris| uris.remove(uri))
+    }
+
+    /// Whether the client is subscribed to `uri`.
+    #[must_use]
+    pub fn is_subscribed(&self, uri: &str) -> bool {
+        self.uris.read().is_ok_and(|uris| uris.contains(uri))
+    }
+
+    /// All subscribed URIs, in sorted order.
+    #[must_use]
+    pub fn uris(&self) -> Vec<String> {
+        self.uris
+            .read()
+            .map(|uris| uris.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of subscribed URIs.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.uris.read().map_or(0, |uris| uris.len())
+    }
+
+    /// Whether there are no subscriptions.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every subscription (e.g. when the connection closes).
+    pub fn clear(&self) {
+        if let Ok(mut uris) = self.uris.write() {
+            uris.clear();
+        }
+    }
+
+    /// Send `notifications/resources/updated` for `uri` through `peer` if the
+    /// client is subscribed to it. Returns whether a notification was sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the notification could not be sent.
+    pub async fn notify_updated(&self, peer: &dyn Peer, uri: &str) -> Result<bool, McpError> {
+        if !self.is_subscribed(uri) {
+            return Ok(false);
+        }
+        peer.notify(resource_updated(uri)?).await?;
+        Ok(true)
+    }
+}
+
+/// Build a `notifications/resources/updated` notification for `uri`.
+pub(crate) fn resource_updated(uri: &str) -> Result<Notification, McpError> {
+    let params = serde_json::to_value(ResourceUpdatedNotification {
+        uri: uri.to_string(),
+    })?;
+    Ok(Notification::with_params(RESOURCES_UPDATED, params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecPeer(Mutex<Vec<Notification>>);
+
+    impl Peer for RecPeer {
+        fn notify(
+            &self,
+            notification: Notification,
+        ) -> Pin<Box<dyn Future<Output = Result<(), McpError>> + Send + '_>> {
+            self.0.lock().unwrap().push(notification);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[test]
+    fn clones_share_the_subscription_set() {
+        let subs = SubscriptionManager::new();
+        let other = subs.clone();
+        subs.subscribe("b://2");
+        other.subscribe("a://1");
+        assert_eq!(subs.uris(), ["a://1", "b://2"]);
+        other.clear();
+        assert!(subs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn notify_updated_only_for_subscribed_uris() {
+        let subs = SubscriptionManager::new();
+        let peer = RecPeer::default();
+        subs.subscribe("file:///watched");
+
+        assert!(!subs.notify_updated(&peer, "file:///other").await.unwrap());
+        assert!(subs.notify_updated(&peer, "file:///watched").await.unwrap());
+
+        let sent = peer.0.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].method.as_ref(), RESOURCES_UPDATED);
+        assert_eq!(
+            sent[0].params.as_ref().unwrap()["uri"],
+            serde_json::json!("file:///watched")
+        );
+    }
+}
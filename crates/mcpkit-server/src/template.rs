@@ -0,0 +1,400 @@
+//! Prompt templates rendered with [MiniJinja](https://docs.rs/minijinja).
+//!
+//! A [`PromptTemplate`] renders a prompt from Jinja-style source, with the
+//! prompt's arguments as variables:
+//!
+//! - `{{ code }}` interpolates an argument, and filters, conditionals, and
+//!   loops work as in Jinja (`{% if focus %}...{% endif %}`);
+//! - `{% include "header.md" %}` renders a partial added with
+//!   [`with_partial`](PromptTemplate::with_partial) or loaded from a directory
+//!   with [`with_partials_dir`](PromptTemplate::with_partials_dir);
+//! - [`arguments`](PromptTemplate::arguments) lists the variables the
+//!   template and its partials use as [`PromptArgument`]s, for
+//!   `prompts/list`. They are required unless declared otherwise with
+//!   [`with_argument`](PromptTemplate::with_argument).
+//!
+//! Output is never HTML-escaped, and a newline directly after a block tag is
+//! removed (Jinja's `trim_blocks` and `lstrip_blocks`), so tags on lines of
+//! their own leave no blank lines behind.
+//!
+//! `#[prompt(template = "...")]` and `#[prompt(template_file = "...")]` build
+//! a template at compile time from an inline string or a file in the crate;
+//! see the `#[prompt]` documentation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_core::types::PromptArgument;
+//! use mcpkit_server::template::PromptTemplate;
+//!
+//! let template = PromptTemplate::new(
+//!     "Review this {{ language }} code:\n{{ code }}\n\
+//!      {% if focus %}\nFocus on {{ focus }}.\n{% endif %}\n\
+//!      {% include \"closing\" %}",
+//! )?
+//! .with_partial("closing", "Reply with a list of issues.")?
+//! .with_argument(PromptArgument::optional("focus", "What to focus on"));
+//!
+//! let names: Vec<_> = template.arguments().into_iter().map(|a| a.name).collect();
+//! assert_eq!(names, ["focus", "code", "language"]);
+//!
+//! let arguments = serde_json::json!({"code": "fn main() {}", "language": "Rust"});
+//! let result = template.render_prompt("review", arguments.as_object())?;
+//! assert_eq!(result.messages.len(), 1);
+//! # Ok::<(), mcpkit_core::error::McpError>(())
+//! ```
+//!
+//! This module is gated behind the `templates` feature.
+
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{GetPromptResult, PromptArgument};
+use minijinja::{AutoEscape, Environment};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Name of a template created without one.
+const DEFAULT_NAME: &str = "prompt";
+
+/// A prompt template and its partials.
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    env: Environment<'static>,
+    name: String,
+    declared: Vec<PromptArgument>,
+    variables: Vec<String>,
+}
+
+impl PromptTemplate {
+    /// Create a template from its source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source is not a valid template.
+    pub fn new(source: impl Into<String>) -> Result<Self, McpError> {
+        Self::named(DEFAULT_NAME, source)
+    }
+
+    /// Create a template with a name, used in error messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source is not a valid template.
+    pub fn named(name: impl Into<String>, source: impl Into<String>) -> Result<Self, McpError> {
+        let name = name.into();
+        let mut env = Environment::new();
+        env.set_auto_escape_callback(|_| AutoEscape::None);
+        env.set_trim_blocks(true);
+        env.set_lstrip_blocks(true);
+        env.add_template_owned(name.clone(), source.into())
+            .map_err(|e| template_error(&name, e))?;
+        Ok(Self {
+            env,
+            name,
+            declared: Vec::new(),
+            variables: Vec::new(),
+        })
+    }
+
+    /// Load a template from a file, named after the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid
+    /// template.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, McpError> {
+        let path = path.as_ref();
+        let source = read(path)?;
+        let name = path
+            .file_name()
+            .map_or_else(|| DEFAULT_NAME.into(), |n| n.to_string_lossy());
+        Self::named(name, source)
+    }
+
+    /// Add a partial that the template can `{% include %}` by `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source is not a valid template.
+    pub fn with_partial(
+        mut self,
+        name: impl Into<String>,
+        source: impl Into<String>,
+    ) -> Result<Self, McpError> {
+        let name = name.into();
+        self.env
+            .add_template_owned(name.clone(), source.into())
+            .map_err(|e| template_error(&name, e))?;
+        Ok(self)
+    }
+
+    /// Add every file under `dir` as a partial, named by its path relative
+    /// to `dir` with `/` separators (such as `sections/intro.md`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read or a file is not a
+    /// valid template.
+    pub fn with_partials_dir(mut self, dir: impl AsRef<Path>) -> Result<Self, McpError> {
+        let dir = dir.as_ref();
+        for (name, path) in partial_files(dir)? {
+            self = self.with_partial(name, read(&path)?)?;
+        }
+        Ok(self)
+    }
+
+    /// Declare an argument's description and whether it is required,
+    /// replacing any earlier declaration of the same name.
+    ///
+    /// Declared arguments are listed first, in the order declared, and need
+    /// not appear in the template.
+    #[must_use]
+    pub fn with_argument(mut self, argument: PromptArgument) -> Self {
+        self.declared.retain(|a| a.name != argument.name);
+        self.declared.push(argument);
+        self
+    }
+
+    /// Mark `name` as a variable the server fills in, through the `extra`
+    /// variables of [`render_prompt_with`](Self::render_prompt_with), rather
+    /// than an argument. It is left out of [`arguments`](Self::arguments).
+    #[must_use]
+    pub fn with_variable(mut self, name: impl Into<String>) -> Self {
+        self.variables.push(name.into());
+        self
+    }
+
+    /// The template's name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The template's arguments: the declared arguments, then the other
+    /// variables the template and its partials use, in name order and
+    /// required. Globals such as `range` and variables marked with
+    /// [`with_variable`](Self::with_variable) are not arguments.
+    #[must_use]
+    pub fn arguments(&self) -> Vec<PromptArgument> {
+        let globals: BTreeSet<&str> = self.env.globals().map(|(name, _)| name).collect();
+        let used: BTreeSet<String> = self
+            .env
+            .templates()
+            .flat_map(|(_, template)| template.undeclared_variables(false))
+            .filter(|name| !globals.contains(name.as_str()) && !self.variables.contains(name))
+            .collect();
+
+        let mut arguments = self.declared.clone();
+        arguments.extend(
+            used.into_iter()
+                .filter(|name| !self.declared.iter().any(|a| &a.name == name))
+                .map(|name| PromptArgument {
+                    name,
+                    title: None,
+                    description: None,
+                    required: Some(true),
+                }),
+        );
+        arguments
+    }
+
+    /// Render the template with `context` as its variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if rendering fails, for example when a filter is
+    /// given a value of the wrong type.
+    pub fn render(&self, context: impl Serialize) -> Result<String, McpError> {
+        self.env
+            .get_template(&self.name)
+            .and_then(|template| template.render(context))
+            .map_err(|e| template_error(&self.name, e))
+    }
+
+    /// Render the `prompts/get` result for `prompt` from its arguments, as a
+    /// single user message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::InvalidParams`] if a required argument is missing,
+    /// or an error if rendering fails.
+    pub fn render_prompt(
+        &self,
+        prompt: &str,
+        arguments: Option<&Map<String, Value>>,
+    ) -> Result<GetPromptResult, McpError> {
+        self.render_prompt_with(prompt, arguments, ())
+    }
+
+    /// Like [`render_prompt`](Self::render_prompt), with `extra` variables
+    /// added to the arguments.
+    ///
+    /// `extra` must serialize to an object, whose keys take precedence over
+    /// the arguments, or to `null`, which adds nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::InvalidParams`] if a required argument is in
+    /// neither the arguments nor `extra`, or an error if `extra` is not an
+    /// object or rendering fails.
+    pub fn render_prompt_with(
+        &self,
+        prompt: &str,
+        arguments: Option<&Map<String, Value>>,
+        extra: impl Serialize,
+    ) -> Result<GetPromptResult, McpError> {
+        let mut context = arguments.cloned().unwrap_or_default();
+        match serde_json::to_value(extra)? {
+            Value::Null => {}
+            Value::Object(extra) => context.extend(extra),
+            _ => {
+                return Err(McpError::internal(format!(
+                    "extra variables for prompt '{prompt}' must be an object"
+                )));
+            }
+        }
+
+        if let Some(missing) = self
+            .arguments()
+            .into_iter()
+            .find(|a| a.required == Some(true) && !context.contains_key(&a.name))
+        {
+            return Err(McpError::invalid_params(
+                prompt,
+                format!("missing required argument: {}", missing.name),
+            ));
+        }
+        Ok(GetPromptResult::user(self.render(context)?))
+    }
+}
+
+fn template_error(name: &str, e: minijinja::Error) -> McpError {
+    McpError::internal(format!("prompt template '{name}' failed: {e}"))
+}
+
+fn read(path: &Path) -> Result<String, McpError> {
+    std::fs::read_to_string(path).map_err(|e| {
+        McpError::internal(format!(
+            "cannot read prompt template {}: {e}",
+            path.display()
+        ))
+    })
+}
+
+/// The files under `dir`, with their names relative to it, in name order.
+fn partial_files(dir: &Path) -> Result<Vec<(String, std::path::PathBuf)>, McpError> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries = std::fs::read_dir(&current).map_err(|e| {
+            McpError::internal(format!(
+                "cannot read partials in {}: {e}",
+                current.display()
+            ))
+        })?;
+        for entry in entries {
+            let path = entry
+                .map_err(|e| {
+                    McpError::internal(format!(
+                        "cannot read partials in {}: {e}",
+                        current.display()
+                    ))
+                })?
+                .path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                let name = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((name, path));
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::types::Content;
+    use serde_json::json;
+
+    fn text(result: &GetPromptResult) -> &str {
+        match &result.messages[0].content {
+            Content::Text(t) => &t.text,
+            other => panic!("expected text, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn renders_arguments_partials_and_extra_variables() -> Result<(), McpError> {
+        let template = PromptTemplate::new(
+            "Summarize for {{ audience }}:\n{% for file in files %}\n- {{ file }}\n{% endfor %}\n{% include \"footer\" %}",
+        )?
+        .with_partial("footer", "By {{ author }}")?;
+
+        let arguments = json!({"files": ["a.rs", "b.rs"], "audience": "reviewers"});
+        let result = template.render_prompt_with(
+            "summary",
+            arguments.as_object(),
+            json!({"author": "mcpkit", "audience": "maintainers"}),
+        )?;
+        assert_eq!(
+            text(&result),
+            "Summarize for maintainers:\n- a.rs\n- b.rs\nBy mcpkit"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn arguments_come_from_declarations_and_placeholders() -> Result<(), McpError> {
+        let template = PromptTemplate::new(
+            "{% set n = range(3) %}{{ topic }} {{ user.name }} {{ today }}{% for x in n %}{{ x }}{% endfor %}",
+        )?
+        .with_argument(PromptArgument::optional("tone", "The tone"))
+        .with_argument(PromptArgument::optional("topic", "What to write about"))
+        .with_variable("today");
+
+        let arguments = template.arguments();
+        let names: Vec<_> = arguments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["tone", "topic", "user"]);
+        assert_eq!(arguments[1].required, Some(false));
+        assert_eq!(arguments[2].required, Some(true));
+
+        let err = template.render_prompt("p", None).unwrap_err();
+        assert!(matches!(err, McpError::InvalidParams(_)));
+        assert!(err.to_string().contains("missing required argument: user"));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_templates_are_rejected() {
+        assert!(PromptTemplate::new("{% if %}").is_err());
+        let template = PromptTemplate::new("{% include \"missing\" %}").unwrap();
+        assert!(template.render(json!({})).is_err());
+    }
+
+    #[test]
+    fn partials_load_from_a_directory() -> Result<(), McpError> {
+        let dir = std::env::temp_dir().join(format!("mcpkit-partials-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("partials/sections")).unwrap();
+        std::fs::write(dir.join("partials/sections/intro.md"), "Hello {{ name }}").unwrap();
+        let main = dir.join("main.md");
+        std::fs::write(&main, "{% include \"sections/intro.md\" %}!").unwrap();
+
+        let rendered = PromptTemplate::from_file(&main)
+            .and_then(|t| t.with_partials_dir(dir.join("partials")))
+            .and_then(|t| Ok((t.name().to_string(), t.render(json!({"name": "there"}))?)));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            rendered?,
+            ("main.md".to_string(), "Hello there!".to_string())
+        );
+        Ok(())
+    }
+}
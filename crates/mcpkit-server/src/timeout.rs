@@ -0,0 +1,320 @@
+//! Execution timeouts for requests.
+//!
+//! By default a request runs until its handler returns. [`Timeouts`] bounds
+//! how long requests may run, per method and per tool, and is attached with
+//! [`ServerBuilder::timeouts`] (or [`McpService::with_timeouts`] for the HTTP
+//! adapters). A request that runs past its timeout is dropped, which cancels
+//! the handler at its next `.await`, and fails with [`McpError::Timeout`]
+//! (JSON-RPC code `-32007`). Work a handler moved onto another task is not
+//! stopped; such handlers can watch [`Context::deadline`], which is moved up
+//! to the timeout.
+//!
+//! A `tools/call` uses the first timeout found among:
+//!
+//! 1. the tool's entry in [`Timeouts::tool`],
+//! 2. the timeout the tool declares, with `#[tool(timeout_ms = 5000)]` or
+//!    [`Tool::timeout`](mcpkit_core::types::Tool::timeout),
+//! 3. the method's entry in [`Timeouts::method`],
+//! 4. [`Timeouts::default_timeout`].
+//!
+//! Other requests use the last two. Pass a shared [`ServerMetrics`] in the
+//! configuration to count timed-out requests.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_server::metrics::ServerMetrics;
+//! use mcpkit_server::timeout::Timeouts;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let metrics = Arc::new(ServerMetrics::new());
+//! let timeouts = Timeouts::new()
+//!     .default_timeout(Duration::from_secs(30))
+//!     .method("resources/read", Duration::from_secs(5))
+//!     .tool("generate_report", Duration::from_secs(300))
+//!     .metrics(Arc::clone(&metrics));
+//! assert_eq!(timeouts.for_method("prompts/get"), Some(Duration::from_secs(30)));
+//! ```
+//!
+//! [`ServerBuilder::timeouts`]: crate::builder::ServerBuilder::timeouts
+//! [`McpService::with_timeouts`]: crate::service::McpService::with_timeouts
+//! [`Context::deadline`]: crate::context::Context::deadline
+
+use crate::context::Context;
+use crate::dispatch::DynToolHandler;
+use crate::metrics::ServerMetrics;
+use crate::router::methods;
+use mcpkit_core::error::McpError;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long requests may run.
+#[derive(Debug, Clone, Default)]
+pub struct Timeouts {
+    default: Option<Duration>,
+    methods: HashMap<String, Duration>,
+    tools: HashMap<String, Duration>,
+    metrics: Option<Arc<ServerMetrics>>,
+}
+
+impl Timeouts {
+    /// Create a configuration with no timeouts.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit requests that have no more specific timeout.
+    #[must_use]
+    pub const fn default_timeout(mut self, timeout: Duration) -> Self {
+        self.default = Some(timeout);
+        self
+    }
+
+    /// Limit requests for `method`, such as `"tools/call"` or
+    /// `"resources/read"`.
+    #[must_use]
+    pub fn method(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.methods.insert(method.into(), timeout);
+        self
+    }
+
+    /// Limit calls to the tool named `name`, overriding the timeout the tool
+    /// declares.
+    #[must_use]
+    pub fn tool(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.tools.insert(name.into(), timeout);
+        self
+    }
+
+    /// Count timed-out requests in `metrics`.
+    #[must_use]
+    pub fn metrics(mut self, metrics: Arc<ServerMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// The timeout for a `method` request, ignoring per-tool timeouts.
+    #[must_use]
+    pub fn for_method(&self, method: &str) -> Option<Duration> {
+        self.methods.get(method).copied().or(self.default)
+    }
+
+    /// Choose the timeout for a request.
+    ///
+    /// For a `tools/call`, looks up the timeout the tool declares through
+    /// `tools`.
+    pub(crate) async fn budget(
+        &self,
+        method: &str,
+        params: Option<&serde_json::Value>,
+        tools: Option<&dyn DynToolHandler>,
+        ctx: &Context<'_>,
+    ) -> Option<Budget> {
+        let tool = (method == methods::TOOLS_CALL)
+            .then(|| params?.get("name")?.as_str())
+            .flatten();
+        let Some(tool) = tool else {
+            return self.for_method(method).map(|timeout| Budget {
+                operation: method.to_string(),
+                timeout,
+            });
+        };
+
+        let timeout = match self.tools.get(tool) {
+            Some(timeout) => Some(*timeout),
+            None => match tools {
+                Some(tools) => tools
+                    .list_tools(ctx)
+                    .await
+                    .unwrap_or_default()
+                    .iter()
+                    .find(|t| t.name == tool)
+                    .and_then(mcpkit_core::types::Tool::declared_timeout),
+                None => None,
+            },
+        };
+        timeout
+            .or_else(|| self.for_method(method))
+            .map(|timeout| Budget {
+                operation: format!("{method}:{tool}"),
+                timeout,
+            })
+    }
+
+    /// Run `request` within `budget`, dropping it and failing with
+    /// [`McpError::Timeout`] when the budget runs out.
+    pub(crate) async fn run<F: Future>(
+        &self,
+        budget: Option<&Budget>,
+        request: F,
+    ) -> Result<F::Output, McpError> {
+        let Some(budget) = budget else {
+            return Ok(request.await);
+        };
+        let output = mcpkit_transport::runtime::timeout(budget.timeout, request).await;
+        if output.is_err() {
+            tracing::warn!(
+                operation = %budget.operation,
+                timeout_ms = budget.timeout.as_millis(),
+                "Request timed out"
+            );
+            if let Some(metrics) = &self.metrics {
+                metrics.record_timeout(&budget.operation);
+            }
+        }
+        output.map_err(|_| McpError::timeout(&budget.operation, budget.timeout))
+    }
+}
+
+/// The timeout chosen for one request.
+#[derive(Debug, Clone)]
+pub(crate) struct Budget {
+    /// The method, or `tools/call:<tool>` for a tool call.
+    operation: String,
+    timeout: Duration,
+}
+
+impl Budget {
+    /// The deadline for a request starting now, no later than the one the
+    /// client sent.
+    pub(crate) fn deadline(&self, client: Option<Instant>) -> Instant {
+        let deadline = Instant::now() + self.timeout;
+        client.map_or(deadline, |client| client.min(deadline))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::ServerBuilder;
+    use crate::context::NoOpPeer;
+    use crate::handler::{ServerHandler, ToolHandler};
+    use crate::server::RequestRouter;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities, ServerInfo};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use mcpkit_core::types::{Object, Tool, ToolOutput};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    struct Main;
+
+    impl ServerHandler for Main {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("main", "1.0.0")
+        }
+    }
+
+    /// Sets its flag when dropped.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// `slow` declares a 20 ms timeout, `stuck` declares none; both never
+    /// finish.
+    struct Tools {
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl ToolHandler for Tools {
+        async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+            Ok(vec![
+                Tool::new("slow").timeout(Duration::from_millis(20)),
+                Tool::new("stuck"),
+            ])
+        }
+
+        async fn call_tool(
+            &self,
+            _name: &str,
+            _args: Object,
+            _ctx: &Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            let _flag = DropFlag(Arc::clone(&self.dropped));
+            std::future::pending().await
+        }
+    }
+
+    async fn call<S: RequestRouter>(server: &S, tool: &str) -> Result<serde_json::Value, McpError> {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+        let params = serde_json::json!({ "name": tool, "arguments": {} });
+        server.route(methods::TOOLS_CALL, Some(&params), &ctx).await
+    }
+
+    #[tokio::test]
+    async fn declared_timeout_cancels_the_call() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let metrics = Arc::new(ServerMetrics::new());
+        let server = ServerBuilder::new(Main)
+            .with_tools(Tools {
+                dropped: Arc::clone(&dropped),
+            })
+            .timeouts(Timeouts::new().metrics(Arc::clone(&metrics)))
+            .build();
+
+        let err = tokio::time::timeout(Duration::from_secs(2), call(&server, "slow"))
+            .await
+            .expect("the tool timeout was not enforced")
+            .expect_err("a stuck tool cannot succeed");
+        assert!(matches!(err, McpError::Timeout { .. }));
+        assert_eq!(err.code(), -32007);
+        assert!(dropped.load(Ordering::SeqCst), "the call was not dropped");
+        assert_eq!(
+            metrics.snapshot().timed_out.get("tools/call:slow"),
+            Some(&1)
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_timeouts_take_precedence() {
+        let timeouts = Timeouts::new()
+            .default_timeout(Duration::from_secs(60))
+            .method(methods::TOOLS_CALL, Duration::from_millis(20))
+            .tool("slow", Duration::from_millis(10));
+        let server = ServerBuilder::new(Main)
+            .with_tools(Tools {
+                dropped: Arc::new(AtomicBool::new(false)),
+            })
+            .timeouts(timeouts.clone())
+            .build();
+
+        // `stuck` declares nothing, so the method timeout applies.
+        let err = tokio::time::timeout(Duration::from_secs(2), call(&server, "stuck"))
+            .await
+            .expect("the method timeout was not enforced")
+            .expect_err("a stuck tool cannot succeed");
+        assert!(matches!(
+            err,
+            McpError::Timeout { duration, .. } if duration == Duration::from_millis(20)
+        ));
+        let err = call(&server, "slow").await.expect_err("timed out");
+        assert!(matches!(
+            err,
+            McpError::Timeout { duration, .. } if duration == Duration::from_millis(10)
+        ));
+
+        assert_eq!(
+            timeouts.for_method("resources/read"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(Timeouts::new().for_method("resources/read"), None);
+    }
+}
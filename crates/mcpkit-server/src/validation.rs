@@ -24,9 +24,10 @@ use crate::context::Context;
 use crate::handler::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
 use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
 use mcpkit_core::error::McpError;
+use mcpkit_core::pagination::Page;
 use mcpkit_core::types::{
-    CallToolResult, GetPromptResult, Object, Prompt, Resource, ResourceContents, ResourceTemplate,
-    Tool, ToolOutput,
+    ByteRange, CallToolResult, GetPromptResult, Object, Prompt, Resource, ResourceContents,
+    ResourceTemplate, Tool, ToolOutput,
 };
 use serde_json::Value;
 use std::future::Future;
@@ -142,6 +143,15 @@ impl<H: ToolHandler> ToolHandler for ValidatingToolHandler<H> {
         self.inner.list_tools(ctx).await
     }
 
+    fn list_tools_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Tool>, McpError>> + Send {
+        self.inner.list_tools_page(cursor, page_size, ctx)
+    }
+
     async fn call_tool(
         &self,
         name: &str,
@@ -254,6 +264,15 @@ impl<H: ResourceHandler> ResourceHandler for ValidatingToolHandler<H> {
         self.inner.list_resources(ctx)
     }
 
+    fn list_resources_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Resource>, McpError>> + Send {
+        self.inner.list_resources_page(cursor, page_size, ctx)
+    }
+
     fn list_resource_templates(
         &self,
         ctx: &Context<'_>,
@@ -261,6 +280,16 @@ impl<H: ResourceHandler> ResourceHandler for ValidatingToolHandler<H> {
         self.inner.list_resource_templates(ctx)
     }
 
+    fn list_resource_templates_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<ResourceTemplate>, McpError>> + Send {
+        self.inner
+            .list_resource_templates_page(cursor, page_size, ctx)
+    }
+
     fn read_resource(
         &self,
         uri: &str,
@@ -269,6 +298,15 @@ impl<H: ResourceHandler> ResourceHandler for ValidatingToolHandler<H> {
         self.inner.read_resource(uri, ctx)
     }
 
+    fn read_resource_range(
+        &self,
+        uri: &str,
+        range: ByteRange,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<ResourceContents, McpError>> + Send {
+        self.inner.read_resource_range(uri, range, ctx)
+    }
+
     fn subscribe(
         &self,
         uri: &str,
@@ -294,6 +332,15 @@ impl<H: PromptHandler> PromptHandler for ValidatingToolHandler<H> {
         self.inner.list_prompts(ctx)
     }
 
+    fn list_prompts_page(
+        &self,
+        cursor: Option<&str>,
+        page_size: Option<usize>,
+        ctx: &Context<'_>,
+    ) -> impl Future<Output = Result<Page<Prompt>, McpError>> + Send {
+        self.inner.list_prompts_page(cursor, page_size, ctx)
+    }
+
     fn get_prompt(
         &self,
         name: &str,
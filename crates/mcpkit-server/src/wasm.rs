@@ -0,0 +1,528 @@
+//! Tools loaded from WebAssembly modules.
+//!
+//! A [`WasmToolHost`] loads tool plugins compiled to WebAssembly from any
+//! language and serves them as a [`ToolHandler`]. Plugins run sandboxed: they
+//! are instantiated with no imports, so they have no filesystem, network,
+//! clock, or environment access, and every call runs in a fresh instance
+//! bounded by [`WasmLimits`] (fuel, i.e. executed instructions, and linear
+//! memory).
+//!
+//! # Plugin ABI
+//!
+//! A plugin is a core WebAssembly module with these exports. Strings are UTF-8
+//! JSON in the module's memory; a returned `i64` packs a pointer in the high
+//! 32 bits and a length in the low 32 bits.
+//!
+//! | Export | Signature | Purpose |
+//! |--------|-----------|---------|
+//! | `memory` | memory | Linear memory shared with the host |
+//! | `mcp_alloc` | `(len: i32) -> i32` | Allocate `len` bytes for the host to write into |
+//! | `mcp_tools` | `() -> i64` | The tool definitions, a JSON array of `Tool` |
+//! | `mcp_call` | `(name: i32, name_len: i32, args: i32, args_len: i32) -> i64` | Run a tool; returns a JSON `CallToolResult` |
+//!
+//! A plugin that traps, runs out of fuel, or exceeds its memory limit fails
+//! the call with a tool error (`isError: true`) rather than a protocol error,
+//! and cannot affect the server or other calls.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use mcpkit_server::wasm::{WasmLimits, WasmToolHost};
+//! use mcpkit_server::ServerBuilder;
+//! # use mcpkit_server::ServerHandler;
+//! # use mcpkit_core::capability::ServerInfo;
+//! # struct Main;
+//! # impl ServerHandler for Main {
+//! #     fn server_info(&self) -> ServerInfo { ServerInfo::new("main", "1.0.0") }
+//! # }
+//!
+//! # fn main() -> Result<(), mcpkit_core::error::McpError> {
+//! let mut host = WasmToolHost::new(WasmLimits::new().fuel(50_000_000))?;
+//! host.load_file("plugins/word_count.wasm")?;
+//! let server = ServerBuilder::new(Main).mount("plugin_", host).build();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! This module is gated behind the `wasm-plugins` feature.
+
+use crate::compose::{Mountable, Mounts};
+use crate::context::Context;
+use crate::handler::ToolHandler;
+use mcpkit_core::error::McpError;
+use mcpkit_core::types::{CallToolResult, Object, Tool, ToolOutput};
+use std::path::Path;
+use std::sync::Arc;
+use wasmtime::{Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Default fuel per call.
+pub const DEFAULT_FUEL: u64 = 100_000_000;
+
+/// Default linear memory limit per instance (64 MiB).
+pub const DEFAULT_MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Resource limits applied to each plugin instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    /// Fuel available to one call; roughly one unit per executed instruction.
+    pub fuel: u64,
+    /// Maximum linear memory of one instance, in bytes.
+    pub max_memory_bytes: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_FUEL,
+            max_memory_bytes: DEFAULT_MAX_MEMORY_BYTES,
+        }
+    }
+}
+
+impl WasmLimits {
+    /// Create limits with the defaults.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the fuel available to one call.
+    #[must_use]
+    pub const fn fuel(mut self, fuel: u64) -> Self {
+        self.fuel = fuel;
+        self
+    }
+
+    /// Set the maximum linear memory of one instance.
+    #[must_use]
+    pub const fn max_memory_bytes(mut self, bytes: usize) -> Self {
+        self.max_memory_bytes = bytes;
+        self
+    }
+}
+
+/// A loaded plugin module and the tools it declared.
+struct Plugin {
+    module: Module,
+    tools: Vec<Tool>,
+}
+
+/// Serves tools from sandboxed WebAssembly plugins.
+///
+/// Cloning is cheap; clones share the loaded plugins as of the clone.
+#[derive(Clone)]
+pub struct WasmToolHost {
+    engine: Engine,
+    limits: WasmLimits,
+    plugins: Vec<Arc<Plugin>>,
+}
+
+impl WasmToolHost {
+    /// Create a host that applies `limits` to every plugin call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WebAssembly engine cannot be created.
+    pub fn new(limits: WasmLimits) -> Result<Self, McpError> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| wasm_error("create engine", &e))?;
+        Ok(Self {
+            engine,
+            limits,
+            plugins: Vec::new(),
+        })
+    }
+
+    /// The limits applied to plugin calls.
+    #[must_use]
+    pub const fn limits(&self) -> WasmLimits {
+        self.limits
+    }
+
+    /// Load a plugin from a WebAssembly binary (or, for tests, text) module.
+    ///
+    /// Returns the names of the tools it declared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module does not compile, imports anything, does
+    /// not follow the plugin ABI, or declares a tool name that is already
+    /// loaded.
+    pub fn load(&mut self, bytes: impl AsRef<[u8]>) -> Result<Vec<String>, McpError> {
+        let module =
+            Module::new(&self.engine, bytes).map_err(|e| wasm_error("compile plugin", &e))?;
+        if let Some(import) = module.imports().next() {
+            return Err(McpError::internal(format!(
+                "wasm plugin imports '{}::{}'; plugins may not import anything",
+                import.module(),
+                import.name()
+            )));
+        }
+
+        let mut instance = PluginInstance::new(&self.engine, &module, self.limits)?;
+        let json = instance.call_packed("mcp_tools", |store, instance| {
+            instance
+                .get_typed_func::<(), i64>(&mut *store, "mcp_tools")?
+                .call(&mut *store, ())
+        })?;
+        let tools: Vec<Tool> = serde_json::from_slice(&json).map_err(|e| {
+            McpError::internal(format!(
+                "wasm plugin returned invalid tool definitions: {e}"
+            ))
+        })?;
+
+        for tool in &tools {
+            if self.find(&tool.name).is_some() {
+                return Err(McpError::internal(format!(
+                    "wasm plugin declares tool '{}', which is already loaded",
+                    tool.name
+                )));
+            }
+        }
+        let names = tools.iter().map(|t| t.name.clone()).collect();
+        self.plugins.push(Arc::new(Plugin { module, tools }));
+        Ok(names)
+    }
+
+    /// Load a plugin from a `.wasm` (or `.wat`) file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or [`load`](Self::load)
+    /// fails.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<Vec<String>, McpError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| {
+            McpError::internal(format!("cannot read wasm plugin {}: {e}", path.display()))
+        })?;
+        self.load(bytes)
+    }
+
+    fn find(&self, name: &str) -> Option<&Arc<Plugin>> {
+        self.plugins
+            .iter()
+            .find(|p| p.tools.iter().any(|t| t.name == name))
+    }
+}
+
+impl std::fmt::Debug for WasmToolHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tools: Vec<_> = self
+            .plugins
+            .iter()
+            .flat_map(|p| p.tools.iter().map(|t| t.name.as_str()))
+            .collect();
+        f.debug_struct("WasmToolHost")
+            .field("limits", &self.limits)
+            .field("tools", &tools)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ToolHandler for WasmToolHost {
+    async fn list_tools(&self, _ctx: &Context<'_>) -> Result<Vec<Tool>, McpError> {
+        Ok(self
+            .plugins
+            .iter()
+            .flat_map(|p| p.tools.iter().cloned())
+            .collect())
+    }
+
+    async fn call_tool(
+        &self,
+        name: &str,
+        args: Object,
+        _ctx: &Context<'_>,
+    ) -> Result<ToolOutput, McpError> {
+        let Some(plugin) = self.find(name).cloned() else {
+            let available = self
+                .plugins
+                .iter()
+                .flat_map(|p| p.tools.iter().map(|t| t.name.clone()))
+                .collect();
+            return Err(McpError::method_not_found_with_suggestions(name, available));
+        };
+
+        // Plugins run synchronously; keep them off the async workers.
+        let engine = self.engine.clone();
+        let limits = self.limits;
+        let name = name.to_string();
+        let args = serde_json::to_vec(&args)?;
+        tokio::task::spawn_blocking(move || invoke(&engine, &plugin.module, limits, &name, &args))
+            .await
+            .map_err(|e| McpError::internal(format!("wasm plugin task failed: {e}")))?
+    }
+}
+
+impl Mountable for WasmToolHost {
+    fn mount(self: Arc<Self>, prefix: &str, mounts: &mut Mounts) {
+        mounts.add_tools(prefix, self);
+    }
+}
+
+/// Run tool `name` in a fresh instance of `module`.
+fn invoke(
+    engine: &Engine,
+    module: &Module,
+    limits: WasmLimits,
+    name: &str,
+    args: &[u8],
+) -> Result<ToolOutput, McpError> {
+    let mut instance = PluginInstance::new(engine, module, limits)?;
+    let result = instance.call_packed("mcp_call", |store, instance| {
+        let name_ptr = write_guest(store, instance, name.as_bytes())?;
+        let args_ptr = write_guest(store, instance, args)?;
+        instance
+            .get_typed_func::<(i32, i32, i32, i32), i64>(&mut *store, "mcp_call")?
+            .call(
+                &mut *store,
+                (
+                    name_ptr,
+                    guest_len(name.len())?,
+                    args_ptr,
+                    guest_len(args.len())?,
+                ),
+            )
+    });
+    let json = match result {
+        Ok(json) => json,
+        Err(PluginFailure::Trapped(message)) => return Ok(ToolOutput::error(message)),
+        Err(PluginFailure::Host(error)) => return Err(error),
+    };
+    let result: CallToolResult = serde_json::from_slice(&json).map_err(|e| {
+        McpError::internal(format!("wasm plugin returned an invalid tool result: {e}"))
+    })?;
+    Ok(ToolOutput::Success(result))
+}
+
+/// Why a plugin call produced no result.
+enum PluginFailure {
+    /// The guest trapped, ran out of fuel, or broke the ABI at run time; the
+    /// call fails as a tool error.
+    Trapped(String),
+    /// The host could not run the plugin at all.
+    Host(McpError),
+}
+
+impl From<McpError> for PluginFailure {
+    fn from(error: McpError) -> Self {
+        Self::Host(error)
+    }
+}
+
+impl From<PluginFailure> for McpError {
+    fn from(failure: PluginFailure) -> Self {
+        match failure {
+            PluginFailure::Trapped(message) => Self::internal(message),
+            PluginFailure::Host(error) => error,
+        }
+    }
+}
+
+/// One sandboxed instantiation of a plugin.
+struct PluginInstance {
+    store: Store<StoreLimits>,
+    instance: Instance,
+}
+
+impl PluginInstance {
+    fn new(engine: &Engine, module: &Module, limits: WasmLimits) -> Result<Self, McpError> {
+        let store_limits = StoreLimitsBuilder::new()
+            .memory_size(limits.max_memory_bytes)
+            .instances(1)
+            .build();
+        let mut store = Store::new(engine, store_limits);
+        store.limiter(|limits| limits);
+        store
+            .set_fuel(limits.fuel)
+            .map_err(|e| wasm_error("set fuel", &e))?;
+        // No imports are provided: the plugin gets no ambient capabilities.
+        let instance = Instance::new(&mut store, module, &[])
+            .map_err(|e| wasm_error("instantiate plugin", &e))?;
+        Ok(Self { store, instance })
+    }
+
+    /// Call an export returning a packed pointer and length, and read the
+    /// bytes it points at.
+    fn call_packed(
+        &mut self,
+        export: &str,
+        call: impl FnOnce(&mut Store<StoreLimits>, &Instance) -> wasmtime::Result<i64>,
+    ) -> Result<Vec<u8>, PluginFailure> {
+        let packed = call(&mut self.store, &self.instance).map_err(|e| trapped(export, &e))?;
+        let memory = memory(&mut self.store, &self.instance).map_err(|e| trapped(export, &e))?;
+        #[allow(clippy::cast_sign_loss)] // the ABI packs two u32s into an i64
+        let packed = packed as u64;
+        let (ptr, len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        memory
+            .data(&self.store)
+            .get(ptr..ptr.saturating_add(len))
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| {
+                PluginFailure::Trapped(format!(
+                    "wasm plugin '{export}' returned an out-of-bounds result"
+                ))
+            })
+    }
+}
+
+fn memory(store: &mut Store<StoreLimits>, instance: &Instance) -> wasmtime::Result<Memory> {
+    instance
+        .get_memory(&mut *store, "memory")
+        .ok_or_else(|| wasmtime::Error::msg("plugin does not export 'memory'"))
+}
+
+/// Copy `bytes` into guest memory allocated with `mcp_alloc`.
+fn write_guest(
+    store: &mut Store<StoreLimits>,
+    instance: &Instance,
+    bytes: &[u8],
+) -> wasmtime::Result<i32> {
+    let ptr = instance
+        .get_typed_func::<i32, i32>(&mut *store, "mcp_alloc")?
+        .call(&mut *store, guest_len(bytes.len())?)?;
+    let offset = usize::try_from(ptr)
+        .map_err(|_| wasmtime::Error::msg("mcp_alloc returned a negative pointer"))?;
+    memory(store, instance)?.write(&mut *store, offset, bytes)?;
+    Ok(ptr)
+}
+
+fn guest_len(len: usize) -> wasmtime::Result<i32> {
+    i32::try_from(len).map_err(|_| wasmtime::Error::msg("argument too large for a wasm plugin"))
+}
+
+fn trapped(export: &str, error: &wasmtime::Error) -> PluginFailure {
+    if matches!(
+        error.downcast_ref::<wasmtime::Trap>(),
+        Some(wasmtime::Trap::OutOfFuel)
+    ) {
+        return PluginFailure::Trapped(format!("wasm plugin '{export}' ran out of fuel"));
+    }
+    PluginFailure::Trapped(format!("wasm plugin '{export}' failed: {error:#}"))
+}
+
+fn wasm_error(action: &str, error: &wasmtime::Error) -> McpError {
+    McpError::internal(format!("wasm plugin: cannot {action}: {error:#}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::NoOpPeer;
+    use mcpkit_core::capability::{ClientCapabilities, ServerCapabilities};
+    use mcpkit_core::protocol::RequestId;
+    use mcpkit_core::protocol_version::ProtocolVersion;
+    use serde_json::json;
+
+    const TOOLS: &str = r#"[{"name":"pong","inputSchema":{"type":"object"}},{"name":"spin","inputSchema":{"type":"object"}},{"name":"grow","inputSchema":{"type":"object"}}]"#;
+    const PONG: &str = r#"{"content":[{"type":"text","text":"pong"}]}"#;
+
+    /// A plugin whose tools answer "pong", loop forever, and grow memory by
+    /// 100 pages (trapping if that fails), chosen by the name's first byte.
+    fn plugin() -> String {
+        let escape = |s: &str| s.replace('"', "\\\"");
+        format!(
+            r#"(module
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 4096))
+  (data (i32.const 0) "{tools}")
+  (data (i32.const 2048) "{pong}")
+  (func (export "mcp_alloc") (param $len i32) (result i32)
+    (local $p i32)
+    (local.set $p (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    (local.get $p))
+  (func (export "mcp_tools") (result i64) (i64.const {tools_len}))
+  (func (export "mcp_call") (param $name i32) (param $name_len i32) (param $args i32) (param $args_len i32) (result i64)
+    (if (i32.eq (i32.load8_u (local.get $name)) (i32.const 115))
+      (then (loop $forever (br $forever))))
+    (if (i32.eq (i32.load8_u (local.get $name)) (i32.const 103))
+      (then (if (i32.lt_s (memory.grow (i32.const 100)) (i32.const 0)) (then unreachable))))
+    (i64.or (i64.shl (i64.const 2048) (i64.const 32)) (i64.const {pong_len}))))"#,
+            tools = escape(TOOLS),
+            pong = escape(PONG),
+            tools_len = TOOLS.len(),
+            pong_len = PONG.len(),
+        )
+    }
+
+    async fn call(host: &WasmToolHost, name: &str) -> Result<ToolOutput, McpError> {
+        let request_id = RequestId::Number(1);
+        let client_caps = ClientCapabilities::default();
+        let server_caps = ServerCapabilities::default();
+        let peer = NoOpPeer;
+        let ctx = Context::new(
+            &request_id,
+            None,
+            &client_caps,
+            &server_caps,
+            ProtocolVersion::LATEST,
+            &peer,
+        );
+        host.call_tool(name, Object::new(), &ctx).await
+    }
+
+    fn error_message(output: ToolOutput) -> String {
+        let result = CallToolResult::from(output);
+        assert_eq!(result.is_error, Some(true));
+        serde_json::to_value(&result.content[0]).unwrap()["text"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn plugin_tools_are_listed_and_called() {
+        let mut host = WasmToolHost::new(WasmLimits::new()).unwrap();
+        let names = host.load(plugin()).unwrap();
+        assert_eq!(names, ["pong", "spin", "grow"]);
+
+        match call(&host, "pong").await.unwrap() {
+            ToolOutput::Success(result) => {
+                assert_eq!(
+                    serde_json::to_value(&result.content[0]).unwrap(),
+                    json!({ "type": "text", "text": "pong" })
+                );
+            }
+            other => panic!("unexpected output {other:?}"),
+        }
+        assert!(matches!(
+            call(&host, "missing").await,
+            Err(McpError::MethodNotFound { .. })
+        ));
+
+        // The same tool names can't be loaded twice.
+        assert!(host.load(plugin()).is_err());
+    }
+
+    #[tokio::test]
+    async fn fuel_and_memory_limits_fail_the_call() {
+        let limits = WasmLimits::new()
+            .fuel(1_000_000)
+            .max_memory_bytes(1024 * 1024);
+        let mut host = WasmToolHost::new(limits).unwrap();
+        host.load(plugin()).unwrap();
+
+        let message = error_message(call(&host, "spin").await.unwrap());
+        assert!(message.contains("ran out of fuel"), "{message}");
+
+        let message = error_message(call(&host, "grow").await.unwrap());
+        assert!(message.contains("failed"), "{message}");
+
+        // A failed call leaves the host usable.
+        assert!(matches!(
+            call(&host, "pong").await.unwrap(),
+            ToolOutput::Success(_)
+        ));
+    }
+
+    #[test]
+    fn plugins_with_imports_are_rejected() {
+        let mut host = WasmToolHost::new(WasmLimits::new()).unwrap();
+        let error = host
+            .load(r#"(module (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32))))"#)
+            .unwrap_err();
+        assert!(error.to_string().contains("may not import"), "{error}");
+    }
+}
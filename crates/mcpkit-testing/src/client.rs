@@ -2,16 +2,83 @@
 //!
 //! This module provides a mock client that can be used to test
 //! MCP server implementations.
+//!
+//! # Server-initiated requests
+//!
+//! Scripted responders answer the requests a server sends to its client:
+//! [`on_sampling`](MockClient::on_sampling),
+//! [`on_elicitation`](MockClient::on_elicitation), and
+//! [`on_roots`](MockClient::on_roots). Each also declares the matching
+//! client capability. [`request`](MockClient::request) drives a request over
+//! a transport, answering server requests until its response arrives:
+//!
+//! ```rust,ignore
+//! let client = MockClient::new().on_sampling(|request| {
+//!     Ok(CreateMessageResult {
+//!         role: Role::Assistant,
+//!         content: OneOrMany::One(SamplingContent::text("a summary")),
+//!         model: "mock".to_string(),
+//!         stop_reason: Some(StopReason::EndTurn),
+//!         meta: None,
+//!     })
+//! });
+//! let (transport, server_side) = MemoryTransport::pair();
+//! tokio::spawn(server.serve(server_side));
+//!
+//! client.initialize(&transport).await?;
+//! let request = client.create_call_tool_request("summarize", json!({}));
+//! let response = client.request(&transport, request).await?;
+//! client.assert_sampling_calls(1);
+//! ```
 
 use mcpkit_core::capability::{ClientCapabilities, ClientInfo, ServerCapabilities, ServerInfo};
-use mcpkit_core::error::McpError;
-use mcpkit_core::protocol::{Notification, Request, RequestId, Response};
+use mcpkit_core::error::{JsonRpcError, McpError, TransportErrorKind};
+use mcpkit_core::protocol::{Message, Notification, Request, RequestId, Response};
 use mcpkit_core::types::{
-    CallToolResult, GetPromptResult, Prompt, Resource, ResourceContents, Tool,
+    CallToolResult, CreateMessageRequest, CreateMessageResult, ElicitRequestParams, ElicitResult,
+    GetPromptResult, ListRootsResult, Prompt, Resource, ResourceContents, Root, Tool,
 };
+use mcpkit_transport::Transport;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
-use std::sync::RwLock;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Answers `sampling/createMessage` requests.
+type SamplingResponder =
+    Arc<dyn Fn(CreateMessageRequest) -> Result<CreateMessageResult, McpError> + Send + Sync>;
+/// Answers `elicitation/create` requests.
+type ElicitationResponder =
+    Arc<dyn Fn(ElicitRequestParams) -> Result<ElicitResult, McpError> + Send + Sync>;
+/// Answers `roots/list` requests.
+type RootsResponder = Arc<dyn Fn() -> Result<Vec<Root>, McpError> + Send + Sync>;
+
+/// The scripted responders for server-initiated requests.
+#[derive(Clone, Default)]
+struct Responders {
+    sampling: Option<SamplingResponder>,
+    elicitation: Option<ElicitationResponder>,
+    roots: Option<RootsResponder>,
+}
+
+impl std::fmt::Debug for Responders {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Responders")
+            .field("sampling", &self.sampling.is_some())
+            .field("elicitation", &self.elicitation.is_some())
+            .field("roots", &self.roots.is_some())
+            .finish()
+    }
+}
+
+/// How many times each responder was invoked.
+#[derive(Debug, Default)]
+struct Invocations {
+    sampling: AtomicUsize,
+    elicitation: AtomicUsize,
+    roots: AtomicUsize,
+}
 
 /// A mock MCP client for testing servers.
 ///
@@ -39,6 +106,12 @@ pub struct MockClient {
     server_info: RwLock<Option<ServerInfo>>,
     /// Server capabilities (after initialize).
     server_capabilities: RwLock<Option<ServerCapabilities>>,
+    /// Responders for server-initiated requests.
+    responders: Responders,
+    /// Responder invocation counts.
+    invocations: Invocations,
+    /// Recorded server-initiated requests.
+    server_requests: RwLock<Vec<Request>>,
 }
 
 impl Default for MockClient {
@@ -62,6 +135,9 @@ impl MockClient {
             notifications_received: RwLock::new(Vec::new()),
             server_info: RwLock::new(None),
             server_capabilities: RwLock::new(None),
+            responders: Responders::default(),
+            invocations: Invocations::default(),
+            server_requests: RwLock::new(Vec::new()),
         }
     }
 
@@ -78,6 +154,58 @@ impl MockClient {
         self
     }
 
+    /// Answer `sampling/createMessage` requests with `responder`, and
+    /// declare the `sampling` capability if it isn't already.
+    ///
+    /// An error from the responder is sent back as a JSON-RPC error.
+    #[must_use]
+    pub fn on_sampling<F>(mut self, responder: F) -> Self
+    where
+        F: Fn(CreateMessageRequest) -> Result<CreateMessageResult, McpError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        if !self.capabilities.has_sampling() {
+            self.capabilities = self.capabilities.with_sampling();
+        }
+        self.responders.sampling = Some(Arc::new(responder));
+        self
+    }
+
+    /// Answer `elicitation/create` requests, form or URL mode, with
+    /// `responder`, and declare the `elicitation` capability if it isn't
+    /// already.
+    ///
+    /// An error from the responder is sent back as a JSON-RPC error.
+    #[must_use]
+    pub fn on_elicitation<F>(mut self, responder: F) -> Self
+    where
+        F: Fn(ElicitRequestParams) -> Result<ElicitResult, McpError> + Send + Sync + 'static,
+    {
+        if !self.capabilities.has_elicitation() {
+            self.capabilities = self.capabilities.with_elicitation();
+        }
+        self.responders.elicitation = Some(Arc::new(responder));
+        self
+    }
+
+    /// Answer `roots/list` requests with `responder`, and declare the
+    /// `roots` capability if it isn't already.
+    ///
+    /// An error from the responder is sent back as a JSON-RPC error.
+    #[must_use]
+    pub fn on_roots<F>(mut self, responder: F) -> Self
+    where
+        F: Fn() -> Result<Vec<Root>, McpError> + Send + Sync + 'static,
+    {
+        if !self.capabilities.has_roots() {
+            self.capabilities = self.capabilities.with_roots();
+        }
+        self.responders.roots = Some(Arc::new(responder));
+        self
+    }
+
     /// Get the client info.
     #[must_use]
     pub fn info(&self) -> &ClientInfo {
@@ -209,6 +337,192 @@ impl MockClient {
         Request::new("ping", id)
     }
 
+    /// Answer a server-initiated request with the scripted responders.
+    ///
+    /// `ping` is always answered. A method without a responder gets a
+    /// method-not-found error, and params that don't parse get an
+    /// invalid-params error without invoking the responder.
+    pub fn handle_server_request(&self, request: &Request) -> Response {
+        if let Ok(mut requests) = self.server_requests.write() {
+            requests.push(request.clone());
+        }
+        let result = match request.method.as_ref() {
+            "ping" => Ok(serde_json::json!({})),
+            "sampling/createMessage" => match &self.responders.sampling {
+                Some(responder) => respond(request, &self.invocations.sampling, |params| {
+                    responder(params)
+                }),
+                None => Err(not_scripted(request)),
+            },
+            "elicitation/create" => match &self.responders.elicitation {
+                Some(responder) => respond(request, &self.invocations.elicitation, |params| {
+                    responder(params)
+                }),
+                None => Err(not_scripted(request)),
+            },
+            "roots/list" => match &self.responders.roots {
+                Some(responder) => {
+                    self.invocations.roots.fetch_add(1, Ordering::SeqCst);
+                    responder()
+                        .map(|roots| ListRootsResult { roots, meta: None })
+                        .map_err(JsonRpcError::from)
+                        .and_then(|result| to_result(&result))
+                }
+                None => Err(not_scripted(request)),
+            },
+            _ => Err(not_scripted(request)),
+        };
+        match result {
+            Ok(result) => Response::success(request.id.clone(), result),
+            Err(error) => Response::error(request.id.clone(), error),
+        }
+    }
+
+    /// Initialize a session over `transport`: send `initialize`, process
+    /// the response, and send `notifications/initialized`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transport fails or the server rejects the
+    /// initialize request.
+    pub async fn initialize<T: Transport>(&self, transport: &T) -> Result<Response, McpError> {
+        let response = self
+            .request(transport, self.create_initialize_request())
+            .await?;
+        self.process_initialize_response(&response)?;
+        let initialized = Notification::new("notifications/initialized");
+        self.record_notification_sent(initialized.clone());
+        transport
+            .send(Message::Notification(initialized))
+            .await
+            .map_err(|e| transport_error(TransportErrorKind::WriteFailed, &e))?;
+        Ok(response)
+    }
+
+    /// Send `request` over `transport` and wait for its response.
+    ///
+    /// Server-initiated requests that arrive in the meantime are answered
+    /// with [`handle_server_request`](Self::handle_server_request), and
+    /// notifications are recorded as received. The request and response are
+    /// recorded too.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transport fails or closes before the
+    /// response arrives. A JSON-RPC error response is returned as a
+    /// response, not an error.
+    pub async fn request<T: Transport>(
+        &self,
+        transport: &T,
+        request: Request,
+    ) -> Result<Response, McpError> {
+        let id = request.id.clone();
+        self.record_request(request.clone());
+        transport
+            .send(Message::Request(request))
+            .await
+            .map_err(|e| transport_error(TransportErrorKind::WriteFailed, &e))?;
+
+        loop {
+            let message = transport
+                .recv()
+                .await
+                .map_err(|e| transport_error(TransportErrorKind::ReadFailed, &e))?;
+            match message {
+                Some(Message::Response(response)) if response.id == id => {
+                    self.record_response(response.clone());
+                    return Ok(response);
+                }
+                Some(Message::Request(server_request)) => {
+                    let response = self.handle_server_request(&server_request);
+                    transport
+                        .send(Message::Response(response))
+                        .await
+                        .map_err(|e| transport_error(TransportErrorKind::WriteFailed, &e))?;
+                }
+                Some(Message::Notification(notification)) => {
+                    self.record_notification_received(notification);
+                }
+                Some(Message::Response(_)) => {}
+                None => {
+                    return Err(McpError::transport(
+                        TransportErrorKind::ConnectionClosed,
+                        format!("connection closed before the response to {id}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Get all recorded server-initiated requests.
+    #[must_use]
+    pub fn server_requests(&self) -> Vec<Request> {
+        self.server_requests
+            .read()
+            .map(|r| r.clone())
+            .unwrap_or_default()
+    }
+
+    /// How many times the sampling responder was invoked.
+    #[must_use]
+    pub fn sampling_count(&self) -> usize {
+        self.invocations.sampling.load(Ordering::SeqCst)
+    }
+
+    /// How many times the elicitation responder was invoked.
+    #[must_use]
+    pub fn elicitation_count(&self) -> usize {
+        self.invocations.elicitation.load(Ordering::SeqCst)
+    }
+
+    /// How many times the roots responder was invoked.
+    #[must_use]
+    pub fn roots_count(&self) -> usize {
+        self.invocations.roots.load(Ordering::SeqCst)
+    }
+
+    /// Assert the sampling responder was invoked `expected` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count differs.
+    #[track_caller]
+    pub fn assert_sampling_calls(&self, expected: usize) {
+        let actual = self.sampling_count();
+        assert_eq!(
+            actual, expected,
+            "expected {expected} sampling requests, got {actual}"
+        );
+    }
+
+    /// Assert the elicitation responder was invoked `expected` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count differs.
+    #[track_caller]
+    pub fn assert_elicitation_calls(&self, expected: usize) {
+        let actual = self.elicitation_count();
+        assert_eq!(
+            actual, expected,
+            "expected {expected} elicitation requests, got {actual}"
+        );
+    }
+
+    /// Assert the roots responder was invoked `expected` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count differs.
+    #[track_caller]
+    pub fn assert_roots_calls(&self, expected: usize) {
+        let actual = self.roots_count();
+        assert_eq!(
+            actual, expected,
+            "expected {expected} roots requests, got {actual}"
+        );
+    }
+
     /// Record a request.
     pub fn record_request(&self, request: Request) {
         if let Ok(mut pending) = self.pending.write() {
@@ -308,6 +622,12 @@ impl MockClient {
         if let Ok(mut notifications) = self.notifications_received.write() {
             notifications.clear();
         }
+        if let Ok(mut requests) = self.server_requests.write() {
+            requests.clear();
+        }
+        self.invocations.sampling.store(0, Ordering::SeqCst);
+        self.invocations.elicitation.store(0, Ordering::SeqCst);
+        self.invocations.roots.store(0, Ordering::SeqCst);
     }
 
     /// Parse a tool list response.
@@ -475,13 +795,60 @@ impl Clone for MockClient {
             server_capabilities: RwLock::new(
                 self.server_capabilities.read().ok().and_then(|s| s.clone()),
             ),
+            responders: self.responders.clone(),
+            invocations: Invocations {
+                sampling: AtomicUsize::new(self.sampling_count()),
+                elicitation: AtomicUsize::new(self.elicitation_count()),
+                roots: AtomicUsize::new(self.roots_count()),
+            },
+            server_requests: RwLock::new(self.server_requests()),
         }
     }
 }
 
+/// Parse a server request's params, count the invocation, and run the
+/// responder.
+fn respond<P, R>(
+    request: &Request,
+    count: &AtomicUsize,
+    responder: impl FnOnce(P) -> Result<R, McpError>,
+) -> Result<serde_json::Value, JsonRpcError>
+where
+    P: DeserializeOwned,
+    R: Serialize,
+{
+    let params = request.params.clone().ok_or_else(|| {
+        JsonRpcError::invalid_params(format!("missing params for {}", request.method))
+    })?;
+    let params = serde_json::from_value(params).map_err(|e| {
+        JsonRpcError::invalid_params(format!("invalid params for {}: {e}", request.method))
+    })?;
+    count.fetch_add(1, Ordering::SeqCst);
+    let result = responder(params).map_err(JsonRpcError::from)?;
+    to_result(&result)
+}
+
+fn to_result(result: &impl Serialize) -> Result<serde_json::Value, JsonRpcError> {
+    serde_json::to_value(result).map_err(|e| JsonRpcError::internal_error(e.to_string()))
+}
+
+fn not_scripted(request: &Request) -> JsonRpcError {
+    JsonRpcError::method_not_found(format!("no mock client responder for '{}'", request.method))
+}
+
+fn transport_error(kind: TransportErrorKind, error: &dyn std::error::Error) -> McpError {
+    McpError::transport(kind, error.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mcpkit_core::types::{
+        Content, ElicitRequest, ElicitationSchema, OneOrMany, Role, SamplingContent, StopReason,
+        ToolOutput,
+    };
+    use mcpkit_server::ServerBuilder;
+    use mcpkit_transport::MemoryTransport;
 
     #[test]
     fn test_mock_client_creation() {
@@ -543,6 +910,154 @@ mod tests {
         assert_eq!(tools[0].name, "test");
     }
 
+    /// A server whose tools call back into the client.
+    struct Curious;
+
+    impl mcpkit_server::ServerHandler for Curious {
+        fn server_info(&self) -> ServerInfo {
+            ServerInfo::new("curious", "1.0.0")
+        }
+
+        fn capabilities(&self) -> ServerCapabilities {
+            ServerCapabilities::new().with_tools()
+        }
+    }
+
+    impl mcpkit_server::ToolHandler for Curious {
+        async fn list_tools(
+            &self,
+            _ctx: &mcpkit_server::Context<'_>,
+        ) -> Result<Vec<Tool>, McpError> {
+            Ok(vec![
+                Tool::new("sample"),
+                Tool::new("elicit"),
+                Tool::new("roots"),
+            ])
+        }
+
+        async fn call_tool(
+            &self,
+            name: &str,
+            _args: mcpkit_core::types::object::Object,
+            ctx: &mcpkit_server::Context<'_>,
+        ) -> Result<ToolOutput, McpError> {
+            let text = match name {
+                "sample" => {
+                    let result = ctx
+                        .create_message(CreateMessageRequest::simple("hello", 10))
+                        .await?;
+                    result.as_text().unwrap_or_default().to_string()
+                }
+                "elicit" => {
+                    let schema = ElicitationSchema::builder()
+                        .string("name")
+                        .required()
+                        .build();
+                    let result = ctx
+                        .elicit(ElicitRequest::new("Who are you?", schema))
+                        .await?;
+                    result.action.to_string()
+                }
+                _ => ctx.list_roots().await?.len().to_string(),
+            };
+            Ok(ToolOutput::text(text))
+        }
+    }
+
+    fn tool_text(client: &MockClient, response: &Response) -> String {
+        let result = client.parse_tool_call(response).unwrap();
+        match &result.content[0] {
+            Content::Text(t) => t.text.clone(),
+            other => panic!("expected text, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn scripted_responders_answer_server_requests() -> Result<(), McpError> {
+        let client = MockClient::new()
+            .on_sampling(|request| {
+                assert_eq!(request.max_tokens, 10);
+                Ok(CreateMessageResult {
+                    role: Role::Assistant,
+                    content: OneOrMany::One(SamplingContent::text("hi there")),
+                    model: "mock".to_string(),
+                    stop_reason: Some(StopReason::EndTurn),
+                    meta: None,
+                })
+            })
+            .on_elicitation(|_| Ok(ElicitResult::declined()))
+            .on_roots(|| Ok(vec![Root::new("file:///a"), Root::new("file:///b")]));
+        assert!(client.capabilities().has_sampling());
+        assert!(client.capabilities().has_roots());
+
+        let (transport, server_side) = MemoryTransport::pair();
+        let server = ServerBuilder::new(Curious).with_tools(Curious).build();
+        tokio::spawn(server.serve(server_side));
+        client.initialize(&transport).await?;
+
+        for (tool, expected) in [
+            ("sample", "hi there"),
+            ("sample", "hi there"),
+            ("elicit", "decline"),
+            ("roots", "2"),
+        ] {
+            let request = client.create_call_tool_request(tool, serde_json::json!({}));
+            let response = client.request(&transport, request).await?;
+            assert_eq!(tool_text(&client, &response), expected);
+        }
+
+        client.assert_sampling_calls(2);
+        client.assert_elicitation_calls(1);
+        client.assert_roots_calls(1);
+        let methods: Vec<_> = client
+            .server_requests()
+            .iter()
+            .map(|r| r.method.to_string())
+            .collect();
+        assert_eq!(
+            methods,
+            [
+                "sampling/createMessage",
+                "sampling/createMessage",
+                "elicitation/create",
+                "roots/list"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unscripted_and_failing_requests_get_errors() {
+        let client =
+            MockClient::new().on_sampling(|_| Err(McpError::internal("model unavailable")));
+
+        let request = Request::new("sampling/createMessage", 1).params(serde_json::json!({
+            "messages": [],
+            "maxTokens": 10
+        }));
+        let response = client.handle_server_request(&request);
+        assert!(
+            response
+                .error
+                .unwrap()
+                .message
+                .contains("model unavailable")
+        );
+
+        // Unparseable params are rejected before the responder runs.
+        let request = Request::new("sampling/createMessage", 2);
+        assert!(client.handle_server_request(&request).error.is_some());
+        client.assert_sampling_calls(1);
+
+        let request = Request::new("roots/list", 3);
+        let error = client.handle_server_request(&request).error.unwrap();
+        assert_eq!(error.code, mcpkit_core::error::codes::METHOD_NOT_FOUND);
+        assert_eq!(client.roots_count(), 0);
+
+        let request = Request::new("ping", 4);
+        assert!(client.handle_server_request(&request).result.is_some());
+    }
+
     #[test]
     fn test_parse_resource_list() {
         let client = MockClient::new();
@@ -0,0 +1,796 @@
+//! Protocol conformance checks for MCP servers.
+//!
+//! [`ConformanceSuite`] plays the client side of a session over any
+//! [`Transport`] and runs a battery of spec checks against whatever is on the
+//! other end: version negotiation, error codes for unknown methods and bad
+//! params, cursor pagination, cancellation, and capability gating. The result
+//! is a [`ConformanceReport`] listing every check with its outcome.
+//!
+//! Each check is tagged with the strength of the spec requirement behind it.
+//! A failed [`Requirement::Must`] check makes the server non-conformant; a
+//! failed [`Requirement::Should`] check is reported as a warning.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mcpkit_testing::conformance::ConformanceSuite;
+//!
+//! #[tokio::test]
+//! async fn server_conforms() {
+//!     let (client, server) = MemoryTransport::pair();
+//!     tokio::spawn(my_server().serve(server));
+//!
+//!     let report = ConformanceSuite::new().run(&client).await;
+//!     report.assert_conformant();
+//! }
+//! ```
+//!
+//! The same suite runs against a live server with `mcpkit conformance` from
+//! the `mcpkit-cli` crate.
+
+use mcpkit_core::error::codes;
+use mcpkit_core::protocol::{Message, Notification, Request, RequestId, Response};
+use mcpkit_core::protocol_version::ProtocolVersion;
+use mcpkit_transport::Transport;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Identifiers of every check the suite runs, in order.
+pub const CHECKS: &[&str] = &[
+    "initialize.negotiation",
+    "initialize.server-info",
+    "ping",
+    "errors.unknown-method",
+    "errors.invalid-params",
+    "pagination.tools",
+    "pagination.resources",
+    "pagination.prompts",
+    "pagination.invalid-cursor",
+    "cancellation.unknown-request",
+    "cancellation.in-flight",
+    "capabilities.gating",
+];
+
+/// Default time to wait for each response.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Upper bound on pages fetched per list, guarding against cursors that
+/// never run out.
+const MAX_PAGES: usize = 1000;
+
+/// How strongly the spec requires the behavior a check tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Requirement {
+    /// The server MUST behave this way.
+    Must,
+    /// The server SHOULD behave this way; failures are warnings.
+    Should,
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Must => write!(f, "MUST"),
+            Self::Should => write!(f, "SHOULD"),
+        }
+    }
+}
+
+/// What happened when a check ran.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "detail", rename_all = "lowercase")]
+pub enum Outcome {
+    /// The server behaved as the spec requires.
+    Passed,
+    /// The server did not; holds what went wrong.
+    Failed(String),
+    /// The check did not apply or could not run; holds why.
+    Skipped(String),
+}
+
+/// The outcome of a single check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckResult {
+    /// Identifier of the check, one of [`CHECKS`].
+    pub id: &'static str,
+    /// Strength of the requirement the check tests.
+    pub requirement: Requirement,
+    /// What happened.
+    pub outcome: Outcome,
+}
+
+impl CheckResult {
+    /// Whether the check failed.
+    #[must_use]
+    pub const fn failed(&self) -> bool {
+        matches!(self.outcome, Outcome::Failed(_))
+    }
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (status, detail) = match &self.outcome {
+            Outcome::Passed => ("pass", None),
+            Outcome::Failed(detail) if self.requirement == Requirement::Should => {
+                ("warn", Some(detail))
+            }
+            Outcome::Failed(detail) => ("FAIL", Some(detail)),
+            Outcome::Skipped(detail) => ("skip", Some(detail)),
+        };
+        write!(f, "{status}  {:<28}  {}", self.id, self.requirement)?;
+        if let Some(detail) = detail {
+            write!(f, "  {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The results of a [`ConformanceSuite`] run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConformanceReport {
+    /// The protocol version the suite asked for.
+    pub requested_version: String,
+    /// The protocol version the server answered with, if it initialized.
+    pub negotiated_version: Option<String>,
+    /// `serverInfo` from the initialize result, if it initialized.
+    pub server_info: Option<Value>,
+    /// One result per check, in [`CHECKS`] order.
+    pub results: Vec<CheckResult>,
+}
+
+impl ConformanceReport {
+    /// Whether no MUST-level check failed.
+    #[must_use]
+    pub fn is_conformant(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Failed MUST-level checks.
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results
+            .iter()
+            .filter(|r| r.failed() && r.requirement == Requirement::Must)
+    }
+
+    /// Failed SHOULD-level checks.
+    pub fn warnings(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results
+            .iter()
+            .filter(|r| r.failed() && r.requirement == Requirement::Should)
+    }
+
+    /// The result of the check with the given identifier.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&CheckResult> {
+        self.results.iter().find(|r| r.id == id)
+    }
+
+    /// Panic with the full report unless the server is conformant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any MUST-level check failed.
+    pub fn assert_conformant(&self) {
+        assert!(self.is_conformant(), "server is not conformant:\n{self}");
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            writeln!(f, "{result}")?;
+        }
+        let count =
+            |pred: fn(&CheckResult) -> bool| self.results.iter().filter(|r| pred(r)).count();
+        write!(
+            f,
+            "{} passed, {} failed, {} warnings, {} skipped",
+            count(|r| r.outcome == Outcome::Passed),
+            self.failures().count(),
+            self.warnings().count(),
+            count(|r| matches!(r.outcome, Outcome::Skipped(_))),
+        )
+    }
+}
+
+/// A battery of spec checks run against a server over a [`Transport`].
+///
+/// The suite performs the `initialize` handshake itself, so hand it a fresh
+/// connection. Run it once per protocol version you support to cover each.
+#[derive(Debug, Clone)]
+pub struct ConformanceSuite {
+    protocol_version: String,
+    timeout: Duration,
+    skip: HashSet<String>,
+}
+
+impl Default for ConformanceSuite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConformanceSuite {
+    /// Create a suite requesting the latest protocol version.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            protocol_version: ProtocolVersion::LATEST.as_str().to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            skip: HashSet::new(),
+        }
+    }
+
+    /// Request this protocol version in `initialize`.
+    #[must_use]
+    pub fn protocol_version(mut self, version: impl Into<String>) -> Self {
+        self.protocol_version = version.into();
+        self
+    }
+
+    /// How long to wait for each response before failing the check.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Skip the check with the given identifier.
+    #[must_use]
+    pub fn skip(mut self, id: impl Into<String>) -> Self {
+        self.skip.insert(id.into());
+        self
+    }
+
+    /// Run every check against the server on the other end of `transport`.
+    ///
+    /// Transport failures are recorded as failed checks rather than returned;
+    /// once the connection is lost the remaining checks are skipped.
+    pub async fn run<T: Transport>(&self, transport: &T) -> ConformanceReport {
+        let mut run = Run {
+            suite: self,
+            session: Session {
+                transport,
+                timeout: self.timeout,
+                next_id: AtomicU64::new(1),
+            },
+            report: ConformanceReport {
+                requested_version: self.protocol_version.clone(),
+                negotiated_version: None,
+                server_info: None,
+                results: Vec::new(),
+            },
+            capabilities: None,
+            halted: None,
+        };
+        run.all().await;
+        run.report
+    }
+}
+
+/// Why a request produced no response.
+enum Failure {
+    /// No response arrived in time.
+    Timeout,
+    /// The connection is gone; later checks cannot run.
+    Closed(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "no response before timeout"),
+            Self::Closed(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+/// The client end of the session under test.
+struct Session<'a, T> {
+    transport: &'a T,
+    timeout: Duration,
+    next_id: AtomicU64,
+}
+
+impl<T: Transport> Session<'_, T> {
+    async fn notify(&self, method: &'static str, params: Value) -> Result<(), Failure> {
+        self.transport
+            .send(Message::Notification(
+                Notification::new(method).params(params),
+            ))
+            .await
+            .map_err(|e| Failure::Closed(format!("send failed: {e}")))
+    }
+
+    async fn send(
+        &self,
+        method: &'static str,
+        params: Option<Value>,
+    ) -> Result<RequestId, Failure> {
+        let id = RequestId::from(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let mut request = Request::new(method, id.clone());
+        if let Some(params) = params {
+            request = request.params(params);
+        }
+        self.transport
+            .send(Message::Request(request))
+            .await
+            .map_err(|e| Failure::Closed(format!("send failed: {e}")))?;
+        Ok(id)
+    }
+
+    async fn request(
+        &self,
+        method: &'static str,
+        params: Option<Value>,
+    ) -> Result<Response, Failure> {
+        let id = self.send(method, params).await?;
+        self.response(&id).await
+    }
+
+    /// Wait for the response to `id`, answering server requests and dropping
+    /// notifications and unrelated responses along the way.
+    async fn response(&self, id: &RequestId) -> Result<Response, Failure> {
+        let wait = async {
+            loop {
+                match self.transport.recv().await {
+                    Ok(Some(Message::Response(response))) if &response.id == id => {
+                        return Ok(response);
+                    }
+                    Ok(Some(Message::Request(request))) => self.answer(request).await?,
+                    Ok(Some(_)) => {}
+                    Ok(None) => return Err(Failure::Closed("connection closed".to_string())),
+                    Err(e) => return Err(Failure::Closed(format!("receive failed: {e}"))),
+                }
+            }
+        };
+        tokio::time::timeout(self.timeout, wait)
+            .await
+            .unwrap_or(Err(Failure::Timeout))
+    }
+
+    /// Reply to a server-initiated request; the suite advertises no client
+    /// capabilities, so only `ping` is supported.
+    async fn answer(&self, request: Request) -> Result<(), Failure> {
+        let response = if request.method == "ping" {
+            Response::success(request.id, json!({}))
+        } else {
+            Response::error(
+                request.id,
+                mcpkit_core::JsonRpcError::method_not_found(format!(
+                    "method '{}' not supported by the conformance client",
+                    request.method
+                )),
+            )
+        };
+        self.transport
+            .send(Message::Response(response))
+            .await
+            .map_err(|e| Failure::Closed(format!("send failed: {e}")))
+    }
+}
+
+/// Which list capabilities the server advertised.
+#[derive(Clone, Copy)]
+struct Capabilities {
+    tools: bool,
+    resources: bool,
+    prompts: bool,
+}
+
+impl Capabilities {
+    fn from_result(result: &Value) -> Self {
+        let has = |name: &str| result["capabilities"][name].is_object();
+        Self {
+            tools: has("tools"),
+            resources: has("resources"),
+            prompts: has("prompts"),
+        }
+    }
+
+    /// `(capability, list method, items key, advertised)` for each list.
+    const fn lists(self) -> [(&'static str, &'static str, &'static str, bool); 3] {
+        [
+            ("tools", "tools/list", "tools", self.tools),
+            ("resources", "resources/list", "resources", self.resources),
+            ("prompts", "prompts/list", "prompts", self.prompts),
+        ]
+    }
+}
+
+/// State of one suite run.
+struct Run<'a, T> {
+    suite: &'a ConformanceSuite,
+    session: Session<'a, T>,
+    report: ConformanceReport,
+    capabilities: Option<Capabilities>,
+    /// Set once later checks can no longer run, with the reason.
+    halted: Option<String>,
+}
+
+type CheckOutcome = Result<Outcome, Failure>;
+
+impl<T: Transport> Run<'_, T> {
+    async fn all(&mut self) {
+        let init = self.initialize().await;
+        self.record("initialize.negotiation", Requirement::Must, init);
+        if self.capabilities.is_none() && self.halted.is_none() {
+            self.halted = Some("initialize failed".to_string());
+        }
+        let info = self.server_info();
+        self.record("initialize.server-info", Requirement::Must, Ok(info));
+
+        macro_rules! check {
+            ($id:literal, $requirement:ident, $body:expr) => {
+                // `record` turns checks that may not run into skips.
+                let outcome = if self.runnable($id) {
+                    $body.await
+                } else {
+                    Ok(Outcome::Passed)
+                };
+                self.record($id, Requirement::$requirement, outcome);
+            };
+        }
+
+        check!("ping", Must, self.ping());
+        check!("errors.unknown-method", Must, self.unknown_method());
+        check!("errors.invalid-params", Must, self.invalid_params());
+        check!("pagination.tools", Must, self.pagination(0));
+        check!("pagination.resources", Must, self.pagination(1));
+        check!("pagination.prompts", Must, self.pagination(2));
+        check!("pagination.invalid-cursor", Should, self.invalid_cursor());
+        check!("cancellation.unknown-request", Must, self.cancel_unknown());
+        check!("cancellation.in-flight", Must, self.cancel_in_flight());
+        check!("capabilities.gating", Should, self.gating());
+    }
+
+    /// Whether `id` should run.
+    fn runnable(&self, id: &str) -> bool {
+        self.halted.is_none() && !self.suite.skip.contains(id)
+    }
+
+    fn record(&mut self, id: &'static str, requirement: Requirement, outcome: CheckOutcome) {
+        let outcome = if self.suite.skip.contains(id) {
+            Outcome::Skipped("skipped by configuration".to_string())
+        } else if let Some(reason) = &self.halted {
+            Outcome::Skipped(reason.clone())
+        } else {
+            match outcome {
+                Ok(outcome) => outcome,
+                Err(Failure::Timeout) => Outcome::Failed(Failure::Timeout.to_string()),
+                Err(Failure::Closed(reason)) => {
+                    self.halted = Some(format!("not run: {reason}"));
+                    Outcome::Failed(reason)
+                }
+            }
+        };
+        self.report.results.push(CheckResult {
+            id,
+            requirement,
+            outcome,
+        });
+    }
+
+    fn caps(&self) -> Capabilities {
+        self.capabilities.unwrap_or(Capabilities {
+            tools: false,
+            resources: false,
+            prompts: false,
+        })
+    }
+
+    async fn initialize(&mut self) -> CheckOutcome {
+        let response = self
+            .session
+            .request(
+                "initialize",
+                Some(json!({
+                    "protocolVersion": self.suite.protocol_version,
+                    "capabilities": {},
+                    "clientInfo": { "name": "mcpkit-conformance", "version": env!("CARGO_PKG_VERSION") },
+                })),
+            )
+            .await?;
+        let result = match (response.result, response.error) {
+            (_, Some(error)) => {
+                return Ok(Outcome::Failed(format!(
+                    "initialize failed: {}",
+                    error.message
+                )));
+            }
+            (Some(result), None) => result,
+            (None, None) => {
+                return Ok(Outcome::Failed("initialize returned no result".to_string()));
+            }
+        };
+        self.session
+            .notify("notifications/initialized", json!({}))
+            .await?;
+
+        self.capabilities = Some(Capabilities::from_result(&result));
+        self.report.server_info = result.get("serverInfo").cloned();
+        let Some(negotiated) = result["protocolVersion"].as_str() else {
+            return Ok(Outcome::Failed("result has no protocolVersion".to_string()));
+        };
+        self.report.negotiated_version = Some(negotiated.to_string());
+
+        // A server may answer with a different version it supports; the
+        // report records which one so callers can assert on it.
+        Ok(if negotiated.is_empty() {
+            Outcome::Failed("protocolVersion is empty".to_string())
+        } else {
+            Outcome::Passed
+        })
+    }
+
+    fn server_info(&self) -> Outcome {
+        if self.capabilities.is_none() {
+            return Outcome::Skipped("initialize failed".to_string());
+        }
+        let Some(info) = &self.report.server_info else {
+            return Outcome::Failed("result has no serverInfo".to_string());
+        };
+        if !info["name"].is_string() || !info["version"].is_string() {
+            return Outcome::Failed("serverInfo needs string name and version".to_string());
+        }
+        Outcome::Passed
+    }
+
+    async fn ping(&self) -> CheckOutcome {
+        let response = self.session.request("ping", None).await?;
+        Ok(match (response.result, response.error) {
+            (_, Some(error)) => Outcome::Failed(format!("ping failed: {}", error.message)),
+            (Some(Value::Object(_)), None) => Outcome::Passed,
+            (result, None) => Outcome::Failed(format!("expected an empty object, got {result:?}")),
+        })
+    }
+
+    async fn unknown_method(&self) -> CheckOutcome {
+        let response = self
+            .session
+            .request("mcpkit/conformance/no-such-method", None)
+            .await?;
+        Ok(expect_error(&response, codes::METHOD_NOT_FOUND))
+    }
+
+    async fn invalid_params(&self) -> CheckOutcome {
+        let caps = self.caps();
+        let (method, params) = if caps.tools {
+            ("tools/call", json!({ "name": 42 }))
+        } else if caps.resources {
+            ("resources/read", json!({}))
+        } else if caps.prompts {
+            ("prompts/get", json!({}))
+        } else {
+            return Ok(Outcome::Skipped(
+                "no tools, resources or prompts to call".to_string(),
+            ));
+        };
+        let response = self.session.request(method, Some(params)).await?;
+        Ok(expect_error(&response, codes::INVALID_PARAMS))
+    }
+
+    async fn pagination(&self, list: usize) -> CheckOutcome {
+        let (capability, method, key, advertised) = self.caps().lists()[list];
+        if !advertised {
+            return Ok(Outcome::Skipped(format!("{capability} not advertised")));
+        }
+        let mut cursor: Option<String> = None;
+        let mut seen_cursors = HashSet::new();
+        let mut items = 0;
+        for _ in 0..MAX_PAGES {
+            let params = cursor.as_ref().map(|c| json!({ "cursor": c }));
+            let response = self.session.request(method, params).await?;
+            if let Some(error) = response.error {
+                return Ok(Outcome::Failed(format!(
+                    "{method} failed: {}",
+                    error.message
+                )));
+            }
+            let result = response.result.unwrap_or(Value::Null);
+            let Some(page) = result[key].as_array() else {
+                return Ok(Outcome::Failed(format!("result has no `{key}` array")));
+            };
+            items += page.len();
+            cursor = match result.get("nextCursor") {
+                None | Some(Value::Null) => return Ok(Outcome::Passed),
+                Some(Value::String(next)) => {
+                    if !seen_cursors.insert(next.clone()) {
+                        return Ok(Outcome::Failed(format!("nextCursor {next:?} repeated")));
+                    }
+                    Some(next.clone())
+                }
+                Some(other) => {
+                    return Ok(Outcome::Failed(format!(
+                        "nextCursor is not a string: {other}"
+                    )));
+                }
+            };
+        }
+        Ok(Outcome::Failed(format!(
+            "still paging after {MAX_PAGES} pages ({items} items)"
+        )))
+    }
+
+    async fn invalid_cursor(&self) -> CheckOutcome {
+        let Some((_, method, _, _)) = self.caps().lists().into_iter().find(|l| l.3) else {
+            return Ok(Outcome::Skipped("no lists advertised".to_string()));
+        };
+        let response = self
+            .session
+            .request(method, Some(json!({ "cursor": "not a cursor" })))
+            .await?;
+        Ok(expect_error(&response, codes::INVALID_PARAMS))
+    }
+
+    async fn cancel_unknown(&self) -> CheckOutcome {
+        self.session
+            .notify(
+                "notifications/cancelled",
+                json!({ "requestId": "mcpkit-conformance-unknown", "reason": "conformance check" }),
+            )
+            .await?;
+        self.still_responsive().await
+    }
+
+    async fn cancel_in_flight(&self) -> CheckOutcome {
+        let id = self.session.send("ping", None).await?;
+        self.session
+            .notify(
+                "notifications/cancelled",
+                json!({ "requestId": id, "reason": "conformance check" }),
+            )
+            .await?;
+        // The cancelled request may or may not be answered; either is fine
+        // as long as the session keeps working.
+        self.still_responsive().await
+    }
+
+    async fn still_responsive(&self) -> CheckOutcome {
+        let response = self.session.request("ping", None).await?;
+        Ok(match response.error {
+            Some(error) => Outcome::Failed(format!("ping afterwards failed: {}", error.message)),
+            None => Outcome::Passed,
+        })
+    }
+
+    async fn gating(&self) -> CheckOutcome {
+        let mut checked = Vec::new();
+        for (capability, method, _, advertised) in self.caps().lists() {
+            if advertised {
+                continue;
+            }
+            let response = self.session.request(method, None).await?;
+            if response.error.is_none() {
+                return Ok(Outcome::Failed(format!(
+                    "{method} succeeded without the {capability} capability"
+                )));
+            }
+            checked.push(capability);
+        }
+        Ok(if checked.is_empty() {
+            Outcome::Skipped("every list capability is advertised".to_string())
+        } else {
+            Outcome::Passed
+        })
+    }
+}
+
+/// `Passed` if `response` is an error with `code`.
+fn expect_error(response: &Response, code: i32) -> Outcome {
+    match &response.error {
+        Some(error) if error.code == code => Outcome::Passed,
+        Some(error) => Outcome::Failed(format!("expected error {code}, got {}", error.code)),
+        None => Outcome::Failed(format!("expected error {code}, got a result")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockServer, MockTool};
+    use mcpkit_core::types::ToolOutput;
+    use mcpkit_server::ServerBuilder;
+    use mcpkit_transport::MemoryTransport;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn mock_server_is_conformant() {
+        let mut builder = MockServer::builder();
+        for name in ["a", "b", "c"] {
+            builder = builder.tool(MockTool::new(name).handler(|_| Ok(ToolOutput::text("ok"))));
+        }
+        let mock = Arc::new(builder.build());
+        let (client, server_side) = MemoryTransport::pair();
+        let server = ServerBuilder::new(Arc::clone(&mock))
+            .with_tools(mock)
+            .build()
+            .list_page_size(2);
+        tokio::spawn(server.serve(server_side));
+
+        let report = ConformanceSuite::new().run(&client).await;
+
+        report.assert_conformant();
+        assert_eq!(report.warnings().count(), 0, "{report}");
+        assert_eq!(report.results.len(), CHECKS.len());
+        assert_eq!(
+            report.negotiated_version.as_deref(),
+            Some(ProtocolVersion::LATEST.as_str())
+        );
+        assert_eq!(
+            report.get("pagination.tools").unwrap().outcome,
+            Outcome::Passed
+        );
+        assert!(matches!(
+            report.get("pagination.prompts").unwrap().outcome,
+            Outcome::Skipped(_)
+        ));
+    }
+
+    /// A server that initializes, then answers every request with `{}`.
+    async fn yes_server(transport: MemoryTransport) {
+        while let Ok(Some(message)) = transport.recv().await {
+            if let Message::Request(request) = message {
+                let result = if request.method == "initialize" {
+                    json!({
+                        "protocolVersion": ProtocolVersion::LATEST.as_str(),
+                        "capabilities": { "tools": {} },
+                        "serverInfo": { "name": "yes", "version": "1.0.0" },
+                    })
+                } else {
+                    json!({})
+                };
+                let _ = transport
+                    .send(Message::Response(Response::success(request.id, result)))
+                    .await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn report_lists_spec_violations() {
+        let (client, server) = MemoryTransport::pair();
+        tokio::spawn(yes_server(server));
+
+        let report = ConformanceSuite::new().skip("ping").run(&client).await;
+
+        assert!(!report.is_conformant());
+        assert_eq!(
+            report.get("ping").unwrap().outcome,
+            Outcome::Skipped("skipped by configuration".to_string())
+        );
+        let failed: Vec<_> = report.failures().map(|r| r.id).collect();
+        assert_eq!(
+            failed,
+            [
+                "errors.unknown-method",
+                "errors.invalid-params",
+                "pagination.tools"
+            ]
+        );
+        let warned: Vec<_> = report.warnings().map(|r| r.id).collect();
+        assert_eq!(warned, ["pagination.invalid-cursor", "capabilities.gating"]);
+        assert!(report.to_string().contains("FAIL  errors.unknown-method"));
+    }
+
+    #[tokio::test]
+    async fn closed_connection_skips_remaining_checks() {
+        let (client, server) = MemoryTransport::pair();
+        drop(server);
+
+        let report = ConformanceSuite::new().run(&client).await;
+
+        assert!(report.get("initialize.negotiation").unwrap().failed());
+        assert!(
+            report.results[1..]
+                .iter()
+                .all(|r| matches!(r.outcome, Outcome::Skipped(_)))
+        );
+    }
+}
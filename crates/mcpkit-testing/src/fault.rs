@@ -0,0 +1,612 @@
+//! Fault injection for chaos testing.
+//!
+//! [`FaultInjectionTransport`] wraps any transport and misbehaves on purpose:
+//! it drops, delays, duplicates or corrupts messages and forces disconnects,
+//! each at a configurable probability or at a fixed point in the stream. Use
+//! it to exercise a client's retry paths or a server's handling of a flaky
+//! peer. Faults are drawn from a seeded generator, so a failing run can be
+//! reproduced with the same seed.
+//!
+//! Transports here carry parsed [`Message`]s, not bytes, so corruption is
+//! modeled as a deserialization error on receive and a mangled payload on
+//! send.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_testing::fault::{Delay, Fault, FaultInjectionLayer, FaultRule};
+//! use mcpkit_testing::replay::Direction;
+//! use mcpkit_transport::MemoryTransport;
+//! use mcpkit_transport::middleware::LayerStack;
+//! use std::time::Duration;
+//!
+//! let (client, _server) = MemoryTransport::pair();
+//! let transport = LayerStack::new(client)
+//!     .with(
+//!         FaultInjectionLayer::new()
+//!             .seed(7)
+//!             .drop(0.05)
+//!             .delay(Delay::uniform(Duration::from_millis(1), Duration::from_millis(50)), 0.5)
+//!             .rule(FaultRule::new(Fault::Disconnect).on(Direction::Sent).method("tools/call").nth(3)),
+//!     )
+//!     .into_inner();
+//! ```
+
+use crate::replay::Direction;
+use mcpkit_core::protocol::Message;
+use mcpkit_transport::middleware::TransportLayer;
+use mcpkit_transport::{Transport, TransportError, TransportMetadata};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Payload substituted for a corrupted message's params or result.
+pub const CORRUPTED: &str = "\u{fffd}corrupted";
+
+/// How long a delayed message is held back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delay {
+    /// Always the same delay.
+    Fixed(Duration),
+    /// Uniformly distributed between `min` and `max`.
+    Uniform {
+        /// Shortest delay.
+        min: Duration,
+        /// Longest delay.
+        max: Duration,
+    },
+    /// Exponentially distributed around `mean`, capped at `max`, for the
+    /// long tail of a congested network.
+    Exponential {
+        /// Mean delay.
+        mean: Duration,
+        /// Upper bound.
+        max: Duration,
+    },
+}
+
+impl Delay {
+    /// A delay uniformly distributed between `min` and `max`.
+    #[must_use]
+    pub const fn uniform(min: Duration, max: Duration) -> Self {
+        Self::Uniform { min, max }
+    }
+
+    fn sample(self, rng: &mut StdRng) -> Duration {
+        match self {
+            Self::Fixed(delay) => delay,
+            Self::Uniform { min, max } if max > min => rng.gen_range(min..=max),
+            Self::Uniform { min, .. } => min,
+            Self::Exponential { mean, max } => {
+                let u: f64 = rng.gen_range(f64::EPSILON..1.0);
+                mean.mul_f64(-u.ln()).min(max)
+            }
+        }
+    }
+}
+
+/// What goes wrong with a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The message is lost.
+    Drop,
+    /// The message is held back.
+    Delay(Delay),
+    /// The message is delivered twice.
+    Duplicate,
+    /// The message arrives garbled.
+    Corrupt,
+    /// The connection drops instead of carrying the message, and stays down.
+    Disconnect,
+}
+
+/// When a rule fires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// On each matching message with this probability (0.0 to 1.0).
+    Probability(f64),
+    /// On the nth matching message only, counting from 1.
+    Nth(u64),
+    /// On every matching message.
+    Always,
+}
+
+/// A fault, which messages it applies to, and when it fires.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaultRule {
+    fault: Fault,
+    direction: Option<Direction>,
+    method: Option<String>,
+    trigger: Trigger,
+}
+
+impl FaultRule {
+    /// A rule injecting `fault` into every message in both directions.
+    #[must_use]
+    pub const fn new(fault: Fault) -> Self {
+        Self {
+            fault,
+            direction: None,
+            method: None,
+            trigger: Trigger::Always,
+        }
+    }
+
+    /// Only apply to messages travelling in `direction`.
+    #[must_use]
+    pub const fn on(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Only apply to requests and notifications for `method`.
+    #[must_use]
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    /// Fire on each matching message with probability `p`.
+    #[must_use]
+    pub const fn probability(mut self, p: f64) -> Self {
+        self.trigger = Trigger::Probability(p.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Fire on the nth matching message only, counting from 1.
+    #[must_use]
+    pub const fn nth(mut self, n: u64) -> Self {
+        self.trigger = Trigger::Nth(n);
+        self
+    }
+
+    fn matches(&self, direction: Direction, message: &Message) -> bool {
+        self.direction.is_none_or(|d| d == direction)
+            && self
+                .method
+                .as_deref()
+                .is_none_or(|m| message.method() == Some(m))
+    }
+}
+
+/// Counts of the faults a transport has injected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FaultStats {
+    /// Messages dropped.
+    pub dropped: u64,
+    /// Messages delayed.
+    pub delayed: u64,
+    /// Messages duplicated.
+    pub duplicated: u64,
+    /// Messages corrupted.
+    pub corrupted: u64,
+    /// Forced disconnects.
+    pub disconnects: u64,
+}
+
+/// Layer that wraps a transport in a [`FaultInjectionTransport`].
+///
+/// Rules are checked in the order they were added; the first one that fires
+/// decides what happens to a message.
+#[derive(Debug, Clone, Default)]
+pub struct FaultInjectionLayer {
+    rules: Vec<FaultRule>,
+    seed: Option<u64>,
+}
+
+impl FaultInjectionLayer {
+    /// A layer that injects nothing until rules are added.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed the fault generator so runs are reproducible. Without a seed one
+    /// is picked at random.
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Add a rule.
+    #[must_use]
+    pub fn rule(mut self, rule: FaultRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Drop messages in both directions with probability `p`.
+    #[must_use]
+    pub fn drop(self, p: f64) -> Self {
+        self.rule(FaultRule::new(Fault::Drop).probability(p))
+    }
+
+    /// Delay messages in both directions by `delay` with probability `p`.
+    #[must_use]
+    pub fn delay(self, delay: Delay, p: f64) -> Self {
+        self.rule(FaultRule::new(Fault::Delay(delay)).probability(p))
+    }
+
+    /// Duplicate messages in both directions with probability `p`.
+    #[must_use]
+    pub fn duplicate(self, p: f64) -> Self {
+        self.rule(FaultRule::new(Fault::Duplicate).probability(p))
+    }
+
+    /// Corrupt messages in both directions with probability `p`.
+    #[must_use]
+    pub fn corrupt(self, p: f64) -> Self {
+        self.rule(FaultRule::new(Fault::Corrupt).probability(p))
+    }
+
+    /// Disconnect when the nth message (counting both directions) would pass.
+    #[must_use]
+    pub fn disconnect_at(self, n: u64) -> Self {
+        self.rule(FaultRule::new(Fault::Disconnect).nth(n))
+    }
+}
+
+impl<T> TransportLayer<T> for FaultInjectionLayer
+where
+    T: Transport<Error = TransportError>,
+{
+    type Transport = FaultInjectionTransport<T>;
+
+    fn layer(&self, inner: T) -> Self::Transport {
+        FaultInjectionTransport::new(inner, self.clone())
+    }
+}
+
+/// A transport that injects faults into the traffic of the one it wraps.
+///
+/// Build it with [`FaultInjectionLayer`]. Once a [`Fault::Disconnect`] fires
+/// the inner transport is closed: sends fail with
+/// [`TransportError::ConnectionClosed`] and receives return `Ok(None)`.
+pub struct FaultInjectionTransport<T> {
+    inner: T,
+    rules: Vec<FaultRule>,
+    state: Mutex<State>,
+    enabled: AtomicBool,
+    disconnected: AtomicBool,
+    dropped: AtomicU64,
+    delayed: AtomicU64,
+    duplicated: AtomicU64,
+    corrupted: AtomicU64,
+    disconnects: AtomicU64,
+}
+
+struct State {
+    rng: StdRng,
+    /// Matching messages seen per rule, for [`Trigger::Nth`].
+    seen: Vec<u64>,
+    /// A received message to hand out again after a duplicate.
+    replay: Option<Message>,
+}
+
+impl<T: Transport<Error = TransportError>> FaultInjectionTransport<T> {
+    /// Wrap `inner` with the rules from `layer`.
+    pub fn new(inner: T, layer: FaultInjectionLayer) -> Self {
+        let rng = layer
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+        Self {
+            inner,
+            state: Mutex::new(State {
+                rng,
+                seen: vec![0; layer.rules.len()],
+                replay: None,
+            }),
+            rules: layer.rules,
+            enabled: AtomicBool::new(true),
+            disconnected: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            delayed: AtomicU64::new(0),
+            duplicated: AtomicU64::new(0),
+            corrupted: AtomicU64::new(0),
+            disconnects: AtomicU64::new(0),
+        }
+    }
+
+    /// The wrapped transport.
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Turn fault injection on or off, e.g. to check that a system recovers
+    /// once the network heals. A forced disconnect stays in effect.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Counts of the faults injected so far.
+    pub fn stats(&self) -> FaultStats {
+        FaultStats {
+            dropped: self.dropped.load(Ordering::Relaxed),
+            delayed: self.delayed.load(Ordering::Relaxed),
+            duplicated: self.duplicated.load(Ordering::Relaxed),
+            corrupted: self.corrupted.load(Ordering::Relaxed),
+            disconnects: self.disconnects.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The fault to inject into `message`, if any, and how long to delay it.
+    fn pick(&self, direction: Direction, message: &Message) -> Option<(Fault, Duration)> {
+        if !self.enabled.load(Ordering::SeqCst) {
+            return None;
+        }
+        let mut state = self.state.lock().ok()?;
+        let state = &mut *state;
+        let mut chosen = None;
+        for (rule, seen) in self.rules.iter().zip(state.seen.iter_mut()) {
+            if !rule.matches(direction, message) {
+                continue;
+            }
+            *seen += 1;
+            let fires = match rule.trigger {
+                Trigger::Always => true,
+                Trigger::Nth(n) => *seen == n,
+                Trigger::Probability(p) => state.rng.gen_bool(p),
+            };
+            if fires && chosen.is_none() {
+                chosen = Some(rule.fault);
+            }
+        }
+        let fault = chosen?;
+        let delay = match fault {
+            Fault::Delay(delay) => delay.sample(&mut state.rng),
+            _ => Duration::ZERO,
+        };
+        Some((fault, delay))
+    }
+
+    async fn disconnect(&self) {
+        if !self.disconnected.swap(true, Ordering::SeqCst) {
+            self.disconnects.fetch_add(1, Ordering::Relaxed);
+            let _ = self.inner.close().await;
+        }
+    }
+}
+
+/// `message` with its payload replaced by [`CORRUPTED`].
+fn corrupt(mut message: Message) -> Message {
+    let garbage = Value::String(CORRUPTED.to_string());
+    match &mut message {
+        Message::Request(request) => request.params = Some(garbage),
+        Message::Notification(notification) => notification.params = Some(garbage),
+        Message::Response(response) => {
+            if response.result.is_some() {
+                response.result = Some(garbage);
+            }
+        }
+    }
+    message
+}
+
+impl<T: Transport<Error = TransportError>> Transport for FaultInjectionTransport<T> {
+    type Error = TransportError;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        if self.disconnected.load(Ordering::SeqCst) {
+            return Err(TransportError::ConnectionClosed);
+        }
+        match self.pick(Direction::Sent, &msg) {
+            None => self.inner.send(msg).await,
+            Some((Fault::Drop, _)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Some((Fault::Delay(_), delay)) => {
+                self.delayed.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(delay).await;
+                self.inner.send(msg).await
+            }
+            Some((Fault::Duplicate, _)) => {
+                self.duplicated.fetch_add(1, Ordering::Relaxed);
+                self.inner.send(msg.clone()).await?;
+                self.inner.send(msg).await
+            }
+            Some((Fault::Corrupt, _)) => {
+                self.corrupted.fetch_add(1, Ordering::Relaxed);
+                self.inner.send(corrupt(msg)).await
+            }
+            Some((Fault::Disconnect, _)) => {
+                self.disconnect().await;
+                Err(TransportError::ConnectionClosed)
+            }
+        }
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        loop {
+            if self.disconnected.load(Ordering::SeqCst) {
+                return Ok(None);
+            }
+            let replay = self.state.lock().ok().and_then(|mut s| s.replay.take());
+            if let Some(msg) = replay {
+                return Ok(Some(msg));
+            }
+            let Some(msg) = self.inner.recv().await? else {
+                return Ok(None);
+            };
+            match self.pick(Direction::Received, &msg) {
+                None => return Ok(Some(msg)),
+                Some((Fault::Drop, _)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                Some((Fault::Delay(_), delay)) => {
+                    self.delayed.fetch_add(1, Ordering::Relaxed);
+                    tokio::time::sleep(delay).await;
+                    return Ok(Some(msg));
+                }
+                Some((Fault::Duplicate, _)) => {
+                    self.duplicated.fetch_add(1, Ordering::Relaxed);
+                    if let Ok(mut state) = self.state.lock() {
+                        state.replay = Some(msg.clone());
+                    }
+                    return Ok(Some(msg));
+                }
+                Some((Fault::Corrupt, _)) => {
+                    self.corrupted.fetch_add(1, Ordering::Relaxed);
+                    return Err(TransportError::Deserialization {
+                        message: "injected fault: corrupt message".to_string(),
+                    });
+                }
+                Some((Fault::Disconnect, _)) => {
+                    self.disconnect().await;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.inner.close().await
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.disconnected.load(Ordering::SeqCst) && self.inner.is_connected()
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        self.inner.metadata()
+    }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::protocol::{Notification, Request};
+    use mcpkit_transport::MemoryTransport;
+    use mcpkit_transport::middleware::LayerStack;
+    use serde_json::json;
+
+    fn wrap(
+        layer: FaultInjectionLayer,
+    ) -> (FaultInjectionTransport<MemoryTransport>, MemoryTransport) {
+        let (client, server) = MemoryTransport::pair();
+        (LayerStack::new(client).with(layer).into_inner(), server)
+    }
+
+    fn call(id: u64) -> Message {
+        Message::Request(Request::new("tools/call", id).params(json!({ "name": "add" })))
+    }
+
+    #[tokio::test]
+    async fn disconnects_at_the_configured_point() {
+        let (transport, server) = wrap(
+            FaultInjectionLayer::new().rule(
+                FaultRule::new(Fault::Disconnect)
+                    .on(Direction::Sent)
+                    .method("tools/call")
+                    .nth(2),
+            ),
+        );
+
+        transport
+            .send(Message::Request(Request::new("ping", 1)))
+            .await
+            .unwrap();
+        transport.send(call(2)).await.unwrap();
+        assert!(matches!(
+            transport.send(call(3)).await,
+            Err(TransportError::ConnectionClosed)
+        ));
+        assert!(!transport.is_connected());
+        assert!(transport.recv().await.unwrap().is_none());
+        assert_eq!(transport.stats().disconnects, 1);
+
+        // The peer sees the connection go away too.
+        assert!(!server.is_connected());
+    }
+
+    #[tokio::test]
+    async fn duplicates_and_corrupts_received_messages() {
+        let (transport, server) = wrap(
+            FaultInjectionLayer::new()
+                .rule(
+                    FaultRule::new(Fault::Duplicate)
+                        .method("a")
+                        .on(Direction::Received),
+                )
+                .rule(
+                    FaultRule::new(Fault::Corrupt)
+                        .method("b")
+                        .on(Direction::Received),
+                ),
+        );
+
+        for method in ["a", "b", "c"] {
+            server
+                .send(Message::Notification(Notification::new(method)))
+                .await
+                .unwrap();
+        }
+        assert_eq!(transport.recv().await.unwrap().unwrap().method(), Some("a"));
+        assert_eq!(transport.recv().await.unwrap().unwrap().method(), Some("a"));
+        assert!(matches!(
+            transport.recv().await,
+            Err(TransportError::Deserialization { .. })
+        ));
+        assert_eq!(transport.recv().await.unwrap().unwrap().method(), Some("c"));
+        let stats = transport.stats();
+        assert_eq!((stats.duplicated, stats.corrupted), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn corrupted_sends_carry_garbage() {
+        let (transport, server) = wrap(
+            FaultInjectionLayer::new().rule(FaultRule::new(Fault::Corrupt).on(Direction::Sent)),
+        );
+
+        transport.send(call(1)).await.unwrap();
+        let Some(Message::Request(request)) = server.recv().await.unwrap() else {
+            panic!("expected a request");
+        };
+        assert_eq!(request.params, Some(json!(CORRUPTED)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delays_follow_the_configured_distribution() {
+        let (transport, server) =
+            wrap(FaultInjectionLayer::new().delay(Delay::Fixed(Duration::from_secs(5)), 1.0));
+
+        let start = tokio::time::Instant::now();
+        transport.send(call(1)).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(5));
+        assert!(server.recv().await.unwrap().is_some());
+        assert_eq!(transport.stats().delayed, 1);
+    }
+
+    #[tokio::test]
+    async fn seeded_runs_are_reproducible() {
+        async fn run(seed: u64) -> FaultStats {
+            let (transport, _server) = wrap(
+                FaultInjectionLayer::new()
+                    .seed(seed)
+                    .drop(0.3)
+                    .duplicate(0.3),
+            );
+            for id in 0..12 {
+                transport.send(call(id)).await.unwrap();
+            }
+            transport.stats()
+        }
+
+        let first = run(42).await;
+        assert_eq!(first, run(42).await);
+        assert!(first.dropped > 0 && first.duplicated > 0, "{first:?}");
+
+        let (transport, _server) = wrap(FaultInjectionLayer::new().drop(1.0));
+        transport.set_enabled(false);
+        transport.send(call(1)).await.unwrap();
+        assert_eq!(transport.stats(), FaultStats::default());
+    }
+}
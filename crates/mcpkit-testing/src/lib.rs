@@ -9,6 +9,10 @@
 //! - **Scenario runner** for defining and executing test scenarios
 //! - **Async helpers** for testing async MCP code
 //! - **Session testing** with recording and validation
+//! - **Conformance suite** that checks a server against the spec
+//! - **Record and replay** transports for deterministic tests without a server
+//! - **Fault injection** transport for chaos testing
+//! - **Catalog snapshots** that catch accidental changes to tool contracts
 //!
 //! # Overview
 //!
@@ -38,6 +42,11 @@
 //! let request = client.create_ping_request();
 //! ```
 //!
+//! Scripted responders (`on_sampling`, `on_elicitation`, `on_roots`) let it
+//! answer the server's own requests while
+//! [`MockClient::request`](client::MockClient::request) drives a server over
+//! `MemoryTransport`.
+//!
 //! ## Test Fixtures
 //!
 //! ```rust
@@ -85,24 +94,62 @@
 //! let result = session.finalize();
 //! result.assert_valid();
 //! ```
+//!
+//! ## Conformance Suite
+//!
+//! ```rust,ignore
+//! use mcpkit_testing::ConformanceSuite;
+//!
+//! let report = ConformanceSuite::new().run(&client_transport).await;
+//! report.assert_conformant();
+//! ```
+//!
+//! ## Record and Replay
+//!
+//! ```rust,ignore
+//! use mcpkit_testing::{Redaction, RecordingTransport, ReplayTransport};
+//!
+//! let redaction = Redaction::new().keys(["api_key"]);
+//! let recording = RecordingTransport::create(live_transport, "session.jsonl")?
+//!     .redaction(redaction.clone());
+//! // ... later, in CI, without the server:
+//! let replay = ReplayTransport::open("session.jsonl")?.redaction(redaction);
+//! ```
+//!
+//! ## Catalog Snapshots
+//!
+//! ```rust,ignore
+//! use mcpkit_testing::assert_catalog_snapshot;
+//!
+//! let catalog = server.export_catalog().await?;
+//! assert_catalog_snapshot!(catalog, "tests/snapshots/catalog.json");
+//! ```
 
 #![deny(missing_docs)]
 
 pub mod assertions;
 pub mod async_helpers;
 pub mod client;
+pub mod conformance;
+pub mod fault;
 pub mod fixtures;
 pub mod mock;
+pub mod replay;
 pub mod scenario;
 pub mod session;
+pub mod snapshot;
 
 // Re-export commonly used types
 pub use assertions::{assert_tool_error, assert_tool_success};
 pub use client::MockClient;
+pub use conformance::{ConformanceReport, ConformanceSuite};
+pub use fault::{FaultInjectionLayer, FaultInjectionTransport};
 pub use fixtures::{sample_resources, sample_tools};
 pub use mock::{MockServer, MockServerBuilder, MockTool};
+pub use replay::{MatchMode, RecordingTransport, Redaction, ReplayTransport};
 pub use scenario::{ResponseMatcher, TestScenario};
 pub use session::{TestSession, TestSessionResult};
+pub use snapshot::{CatalogSnapshot, SnapshotResult};
 
 /// Prelude module for convenient imports.
 pub mod prelude {
@@ -111,10 +158,14 @@ pub mod prelude {
         TestBarrier, TestLatch, retry, wait_for, with_default_timeout, with_timeout,
     };
     pub use crate::client::MockClient;
+    pub use crate::conformance::{ConformanceReport, ConformanceSuite};
+    pub use crate::fault::{Delay, Fault, FaultInjectionLayer, FaultInjectionTransport, FaultRule};
     pub use crate::fixtures::{sample_resources, sample_tools};
     pub use crate::mock::{MockPrompt, MockResource, MockServer, MockServerBuilder, MockTool};
+    pub use crate::replay::{MatchMode, RecordingTransport, Redaction, ReplayTransport};
     pub use crate::scenario::{
         MessageQueue, NotificationMatcher, ResponseMatcher, TestScenario, TestStep,
     };
     pub use crate::session::{TestSession, TestSessionBuilder, TestSessionResult};
+    pub use crate::snapshot::{CatalogSnapshot, SnapshotResult, assert_catalog_snapshot};
 }
@@ -0,0 +1,729 @@
+//! Record live sessions and replay them without the server.
+//!
+//! [`RecordingTransport`] wraps the client side of a real connection and
+//! writes every message to a JSONL file, one [`RecordedMessage`] per line with
+//! wall-clock and relative timestamps. [`ReplayTransport`] reads that file back
+//! and stands in for the server: each request the client sends is matched to
+//! a recorded one and answered with the responses (and any notifications) the
+//! server sent for it, so CI runs are deterministic and need no server.
+//!
+//! Secrets never have to reach the file: a [`Redaction`] masks object keys,
+//! applies a [`RecordingPolicy`], and runs custom hooks on each message before
+//! it is written. Give the replay the same redaction so live requests are
+//! masked the same way before they are matched.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mcpkit_testing::replay::{Redaction, RecordingTransport, ReplayTransport};
+//!
+//! let redaction = Redaction::new().keys(["api_key"]);
+//!
+//! // Once, against the real server:
+//! let transport = RecordingTransport::create(live, "tests/data/search.jsonl")?
+//!     .redaction(redaction.clone());
+//! run_scenario(transport).await;
+//!
+//! // In CI:
+//! let transport = ReplayTransport::open("tests/data/search.jsonl")?.redaction(redaction);
+//! run_scenario(transport).await;
+//! ```
+
+use mcpkit_core::debug::RecordingPolicy;
+use mcpkit_core::error::JsonRpcError;
+use mcpkit_core::protocol::{Message, Request, RequestId, Response};
+use mcpkit_transport::{Transport, TransportError, TransportMetadata};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+/// Placeholder written in place of redacted values.
+pub use mcpkit_core::debug::REDACTED;
+
+/// Which way a recorded message travelled, from the client's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Sent by the client to the server.
+    Sent,
+    /// Received by the client from the server.
+    Received,
+}
+
+/// One line of a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedMessage {
+    /// Wall-clock time the message passed through, in milliseconds since the
+    /// Unix epoch.
+    pub timestamp_ms: u64,
+    /// Milliseconds since the recording started.
+    pub offset_ms: u64,
+    /// Which way the message travelled.
+    pub direction: Direction,
+    /// The message, after redaction.
+    pub message: Message,
+}
+
+/// Read a JSONL recording.
+///
+/// Blank lines are skipped.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or a line is not a [`RecordedMessage`].
+pub fn read_recording(reader: impl BufRead) -> io::Result<Vec<RecordedMessage>> {
+    let mut messages = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {e}", index + 1),
+            )
+        })?;
+        messages.push(message);
+    }
+    Ok(messages)
+}
+
+/// A redaction hook: returns the message as it may be recorded.
+type Hook = Arc<dyn Fn(Message) -> Message + Send + Sync>;
+
+/// How messages are sanitized before they are recorded or matched.
+///
+/// Steps run in order: the [`RecordingPolicy`], then key masking, then custom
+/// hooks. Only `params` of requests and notifications, and `result` of
+/// responses, are masked; ids and methods are kept so the recording stays
+/// replayable.
+#[derive(Clone, Default)]
+pub struct Redaction {
+    policy: Option<RecordingPolicy>,
+    keys: HashSet<String>,
+    hooks: Vec<Hook>,
+}
+
+impl fmt::Debug for Redaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Redaction")
+            .field("policy", &self.policy)
+            .field("keys", &self.keys)
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+impl Redaction {
+    /// A redaction that changes nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep calls to tools excluded by `policy` out of the recording.
+    #[must_use]
+    pub fn policy(mut self, policy: RecordingPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Replace the value of any object key matching (case-insensitively) one
+    /// of `keys` with [`REDACTED`], at any depth.
+    #[must_use]
+    pub fn keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keys
+            .extend(keys.into_iter().map(|k| k.into().to_ascii_lowercase()));
+        self
+    }
+
+    /// Run a custom hook on each message after the built-in steps.
+    #[must_use]
+    pub fn with<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(Message) -> Message + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Return the copy of `message` that may be recorded.
+    #[must_use]
+    pub fn apply(&self, message: &Message) -> Message {
+        let mut message = match &self.policy {
+            Some(policy) => policy.redact(message),
+            None => message.clone(),
+        };
+        if !self.keys.is_empty() {
+            let value = match &mut message {
+                Message::Request(request) => request.params.as_mut(),
+                Message::Notification(notification) => notification.params.as_mut(),
+                Message::Response(response) => response.result.as_mut(),
+            };
+            if let Some(value) = value {
+                mask_keys(value, &self.keys);
+            }
+        }
+        self.hooks
+            .iter()
+            .fold(message, |message, hook| hook(message))
+    }
+}
+
+fn mask_keys(value: &mut Value, keys: &HashSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if keys.contains(&key.to_ascii_lowercase()) {
+                    *value = Value::from(REDACTED);
+                } else {
+                    mask_keys(value, keys);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                mask_keys(item, keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A transport that writes all traffic through it to a JSONL recording.
+///
+/// Wrap the client side of a live connection. Messages are forwarded
+/// unchanged; only the recorded copy is redacted. Failing to write the
+/// recording does not fail the session; [`flush`](Self::flush) reports it.
+pub struct RecordingTransport<T> {
+    inner: T,
+    sink: Mutex<Sink>,
+    started: Instant,
+    redaction: Redaction,
+}
+
+struct Sink {
+    writer: Box<dyn Write + Send>,
+    error: Option<io::Error>,
+}
+
+impl<T: Transport> RecordingTransport<T> {
+    /// Record `inner`'s traffic to `writer`.
+    pub fn new(inner: T, writer: impl Write + Send + 'static) -> Self {
+        Self {
+            inner,
+            sink: Mutex::new(Sink {
+                writer: Box::new(writer),
+                error: None,
+            }),
+            started: Instant::now(),
+            redaction: Redaction::new(),
+        }
+    }
+
+    /// Record `inner`'s traffic to a new file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn create(inner: T, path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(inner, BufWriter::new(File::create(path)?)))
+    }
+
+    /// Sanitize messages with `redaction` before they are written.
+    #[must_use]
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// The wrapped transport.
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Flush the recording.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error hit while writing the recording, if any, or
+    /// the error from flushing.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut sink = self
+            .sink
+            .lock()
+            .map_err(|_| io::Error::other("recording sink poisoned"))?;
+        if let Some(error) = sink.error.take() {
+            return Err(error);
+        }
+        sink.writer.flush()
+    }
+
+    fn record(&self, direction: Direction, message: &Message) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let line = RecordedMessage {
+            timestamp_ms: u64::try_from(now.as_millis()).unwrap_or(u64::MAX),
+            offset_ms: u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            direction,
+            message: self.redaction.apply(message),
+        };
+        let Ok(mut sink) = self.sink.lock() else {
+            return;
+        };
+        if sink.error.is_some() {
+            return;
+        }
+        let written = serde_json::to_writer(&mut sink.writer, &line)
+            .map_err(io::Error::from)
+            .and_then(|()| sink.writer.write_all(b"\n"));
+        if let Err(error) = written {
+            sink.error = Some(error);
+        }
+    }
+}
+
+impl<T: Transport> Transport for RecordingTransport<T> {
+    type Error = T::Error;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        self.record(Direction::Sent, &msg);
+        self.inner.send(msg).await
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        let msg = self.inner.recv().await?;
+        if let Some(msg) = &msg {
+            self.record(Direction::Received, msg);
+        }
+        Ok(msg)
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        if let Ok(mut sink) = self.sink.lock() {
+            let _ = sink.writer.flush();
+        }
+        self.inner.close().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        self.inner.metadata()
+    }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
+}
+
+/// How [`ReplayTransport`] picks the recorded exchange for a live request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// The next unused exchange, which must have the same method.
+    Sequential,
+    /// The first unused exchange with the same method.
+    Method,
+    /// The first unused exchange with the same method and params. `_meta`
+    /// is ignored, since it carries per-run values such as progress tokens.
+    #[default]
+    MethodAndParams,
+}
+
+/// A recorded request and what the server sent back for it.
+struct Exchange {
+    method: String,
+    params_hash: u64,
+    id: RequestId,
+    replies: Vec<Message>,
+    used: bool,
+}
+
+/// A transport that answers requests from a recording instead of a server.
+///
+/// Responses are re-addressed to the live request's id. A request with no
+/// matching exchange gets an internal-error response and is listed by
+/// [`unmatched`](Self::unmatched). Notifications and responses the client
+/// sends are accepted and dropped.
+pub struct ReplayTransport {
+    exchanges: Mutex<Vec<Exchange>>,
+    mode: MatchMode,
+    redaction: Redaction,
+    unmatched: Mutex<Vec<Request>>,
+    sender: Mutex<Option<mpsc::UnboundedSender<Message>>>,
+    receiver: tokio::sync::Mutex<mpsc::UnboundedReceiver<Message>>,
+    connected: AtomicBool,
+}
+
+impl ReplayTransport {
+    /// Replay the JSONL recording at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(read_recording(BufReader::new(File::open(
+            path,
+        )?))?))
+    }
+
+    /// Replay `recording`.
+    ///
+    /// Server messages received before the first request are delivered as
+    /// soon as the transport is created.
+    #[must_use]
+    pub fn new(recording: Vec<RecordedMessage>) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut exchanges: Vec<Exchange> = Vec::new();
+        for RecordedMessage {
+            direction, message, ..
+        } in recording
+        {
+            match (direction, message) {
+                (Direction::Sent, Message::Request(request)) => exchanges.push(Exchange {
+                    method: request.method.to_string(),
+                    params_hash: params_hash(request.params.as_ref()),
+                    id: request.id,
+                    replies: Vec::new(),
+                    used: false,
+                }),
+                (Direction::Sent, _) => {}
+                (Direction::Received, message) => {
+                    // A response belongs to its request; anything else to the
+                    // request in flight most recently.
+                    let owner = match &message {
+                        Message::Response(response) => {
+                            exchanges.iter_mut().rev().find(|e| e.id == response.id)
+                        }
+                        _ => exchanges.last_mut(),
+                    };
+                    match owner {
+                        Some(exchange) => exchange.replies.push(message),
+                        None => {
+                            let _ = sender.send(message);
+                        }
+                    }
+                }
+            }
+        }
+        Self {
+            exchanges: Mutex::new(exchanges),
+            mode: MatchMode::default(),
+            redaction: Redaction::new(),
+            unmatched: Mutex::new(Vec::new()),
+            sender: Mutex::new(Some(sender)),
+            receiver: tokio::sync::Mutex::new(receiver),
+            connected: AtomicBool::new(true),
+        }
+    }
+
+    /// Choose how live requests are matched to recorded ones.
+    #[must_use]
+    pub const fn match_mode(mut self, mode: MatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Redact live requests with `redaction` before matching them, so they
+    /// compare equal to requests recorded with it.
+    #[must_use]
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Live requests that had no recorded exchange.
+    #[must_use]
+    pub fn unmatched(&self) -> Vec<Request> {
+        self.unmatched.lock().map(|u| u.clone()).unwrap_or_default()
+    }
+
+    /// Methods of recorded requests that have not been replayed, in order.
+    #[must_use]
+    pub fn unused(&self) -> Vec<String> {
+        self.exchanges
+            .lock()
+            .map(|exchanges| {
+                exchanges
+                    .iter()
+                    .filter(|e| !e.used)
+                    .map(|e| e.method.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The replies recorded for `request`, re-addressed to its id.
+    fn replies_for(&self, request: &Request) -> Option<Vec<Message>> {
+        let Message::Request(redacted) = self.redaction.apply(&Message::Request(request.clone()))
+        else {
+            return None;
+        };
+        let hash = params_hash(redacted.params.as_ref());
+        let mut exchanges = self.exchanges.lock().ok()?;
+        let mut unused = exchanges.iter_mut().filter(|e| !e.used);
+        let exchange = match self.mode {
+            MatchMode::Sequential => unused.next().filter(|e| e.method == redacted.method),
+            MatchMode::Method => unused.find(|e| e.method == redacted.method),
+            MatchMode::MethodAndParams => {
+                unused.find(|e| e.method == redacted.method && e.params_hash == hash)
+            }
+        }?;
+        exchange.used = true;
+        Some(
+            exchange
+                .replies
+                .iter()
+                .cloned()
+                .map(|message| match message {
+                    Message::Response(response) if response.id == exchange.id => {
+                        Message::Response(Response {
+                            id: request.id.clone(),
+                            ..response
+                        })
+                    }
+                    other => other,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Debug for ReplayTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplayTransport")
+            .field("mode", &self.mode)
+            .field("unused", &self.unused())
+            .field("connected", &self.is_connected())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transport for ReplayTransport {
+    type Error = TransportError;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        if !self.is_connected() {
+            return Err(TransportError::NotConnected);
+        }
+        let Message::Request(request) = msg else {
+            return Ok(());
+        };
+        let replies = self.replies_for(&request).unwrap_or_else(|| {
+            let reply = Response::error(
+                request.id.clone(),
+                JsonRpcError::internal_error(format!(
+                    "no recorded response for `{}`",
+                    request.method
+                )),
+            );
+            if let Ok(mut unmatched) = self.unmatched.lock() {
+                unmatched.push(request);
+            }
+            vec![Message::Response(reply)]
+        });
+        let sender = self
+            .sender
+            .lock()
+            .map_err(|_| TransportError::ConnectionClosed)?;
+        let sender = sender.as_ref().ok_or(TransportError::ConnectionClosed)?;
+        for reply in replies {
+            sender
+                .send(reply)
+                .map_err(|_| TransportError::ConnectionClosed)?;
+        }
+        Ok(())
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        Ok(self.receiver.lock().await.recv().await)
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.connected.store(false, Ordering::SeqCst);
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        TransportMetadata::new("replay").connected_now()
+    }
+}
+
+/// Hash of `params` with `_meta` removed, stable within a process. Absent
+/// and empty params hash the same.
+fn params_hash(params: Option<&Value>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    match params {
+        Some(Value::Object(map)) => {
+            let mut map = map.clone();
+            map.remove("_meta");
+            if !map.is_empty() {
+                Value::Object(map).to_string().hash(&mut hasher);
+            }
+        }
+        Some(other) => other.to_string().hash(&mut hasher),
+        None => {}
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockServer, MockTool};
+    use mcpkit_core::types::ToolOutput;
+    use mcpkit_server::ServerBuilder;
+    use mcpkit_transport::MemoryTransport;
+    use serde_json::json;
+
+    /// A writer the test can read back after the transport has it.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn requests() -> Vec<Request> {
+        vec![
+            Request::new("initialize", 1).params(json!({
+                "protocolVersion": "2025-11-25",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1.0.0" },
+            })),
+            Request::new("tools/call", 2).params(json!({
+                "name": "echo",
+                "arguments": { "text": "hi", "api_key": "sk-live" },
+            })),
+            Request::new("tools/call", 3).params(json!({
+                "name": "echo",
+                "arguments": { "text": "bye", "api_key": "sk-live" },
+            })),
+        ]
+    }
+
+    async fn exchange(transport: &impl Transport, request: Request) -> Response {
+        transport.send(Message::Request(request)).await.unwrap();
+        match transport.recv().await.unwrap() {
+            Some(Message::Response(response)) => response,
+            other => panic!("expected a response, got {other:?}"),
+        }
+    }
+
+    async fn record(redaction: Redaction) -> Vec<RecordedMessage> {
+        let mock = Arc::new(
+            MockServer::builder()
+                .tool(MockTool::new("echo").handler(|args| {
+                    Ok(ToolOutput::text(args["text"].as_str().unwrap_or_default()))
+                }))
+                .build(),
+        );
+        let (client, server) = MemoryTransport::pair();
+        tokio::spawn(
+            ServerBuilder::new(Arc::clone(&mock))
+                .with_tools(mock)
+                .build()
+                .serve(server),
+        );
+
+        let buf = Shared::default();
+        let transport = RecordingTransport::new(client, buf.clone()).redaction(redaction);
+        for request in requests() {
+            exchange(&transport, request).await;
+        }
+        transport.flush().unwrap();
+        let bytes = buf.0.lock().unwrap().clone();
+        read_recording(&bytes[..]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn recording_is_redacted_jsonl() {
+        let recording = record(Redaction::new().keys(["API_KEY"])).await;
+
+        assert_eq!(recording.len(), 6);
+        assert_eq!(recording[0].direction, Direction::Sent);
+        assert_eq!(recording[1].direction, Direction::Received);
+        assert!(recording.iter().all(|m| m.timestamp_ms > 0));
+        let Message::Request(call) = &recording[2].message else {
+            panic!("expected the tools/call request");
+        };
+        let arguments = &call.params.as_ref().unwrap()["arguments"];
+        assert_eq!(arguments["api_key"], REDACTED);
+        assert_eq!(arguments["text"], "hi");
+    }
+
+    #[tokio::test]
+    async fn replay_answers_matching_requests() {
+        let redaction = Redaction::new().keys(["api_key"]);
+        let replay = ReplayTransport::new(record(redaction.clone()).await).redaction(redaction);
+
+        // Out of order, with fresh ids and a different secret.
+        let mut requests = requests();
+        requests.swap(1, 2);
+        for (id, mut request) in (10u64..).zip(requests) {
+            request.id = RequestId::from(id);
+            if let Some(params) = &mut request.params {
+                if params.get("arguments").is_some() {
+                    params["arguments"]["api_key"] = json!("sk-other");
+                }
+            }
+            let response = exchange(&replay, request).await;
+            assert_eq!(response.id, RequestId::from(id));
+            assert!(response.error.is_none(), "{response:?}");
+            if id == 11 {
+                assert_eq!(response.result.unwrap()["content"][0]["text"], "bye");
+            }
+        }
+        assert!(replay.unused().is_empty());
+
+        let missing = exchange(&replay, Request::new("prompts/list", 20)).await;
+        assert!(missing.error.is_some());
+        assert_eq!(replay.unmatched().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sequential_mode_rejects_out_of_order_requests() {
+        let replay =
+            ReplayTransport::new(record(Redaction::new()).await).match_mode(MatchMode::Sequential);
+
+        let response = exchange(&replay, requests().remove(1)).await;
+        assert!(response.error.is_some());
+        assert_eq!(replay.unused(), ["initialize", "tools/call", "tools/call"]);
+
+        replay.close().await.unwrap();
+        assert!(replay.recv().await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,368 @@
+//! Snapshot assertions for a server's tool, resource and prompt contracts.
+//!
+//! [`assert_catalog_snapshot`] compares a server's [`Catalog`] with a JSON
+//! file committed next to the tests, so a renamed tool, a dropped argument or
+//! a changed schema fails CI instead of silently breaking clients. Only the
+//! tools, resources, resource templates and prompts are compared (see
+//! [`CatalogSnapshot`]); the server version and capabilities are left out so
+//! a release bump doesn't invalidate every snapshot.
+//!
+//! A mismatch panics with a line diff of the two documents. To accept the
+//! change, rerun the tests with `MCPKIT_UPDATE_SNAPSHOTS=1`, which rewrites
+//! the file (and creates missing ones) instead of failing.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mcpkit_testing::assert_catalog_snapshot;
+//!
+//! #[tokio::test]
+//! async fn tool_contracts_are_stable() {
+//!     let server = ServerBuilder::new(MyServer).with_tools(MyServer).build();
+//!     let catalog = server.export_catalog().await.unwrap();
+//!     // Relative to the crate's manifest directory.
+//!     assert_catalog_snapshot!(catalog, "tests/snapshots/catalog.json");
+//! }
+//! ```
+
+use mcpkit_core::types::{Prompt, Resource, ResourceTemplate, Tool};
+use mcpkit_server::Catalog;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+/// Environment variable that makes snapshot assertions write the actual
+/// value instead of failing.
+pub const UPDATE_ENV: &str = "MCPKIT_UPDATE_SNAPSHOTS";
+
+/// Unchanged lines shown around each change in a diff.
+const CONTEXT_LINES: usize = 3;
+
+/// The client-facing contract of a [`Catalog`].
+///
+/// Entries are sorted by name (or URI) so the snapshot does not depend on
+/// registration order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogSnapshot {
+    /// Tools, with their input and output schemas.
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+    /// Static resources.
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+    /// Resource templates.
+    #[serde(default)]
+    pub resource_templates: Vec<ResourceTemplate>,
+    /// Prompts, with their arguments.
+    #[serde(default)]
+    pub prompts: Vec<Prompt>,
+}
+
+impl CatalogSnapshot {
+    /// Encode the snapshot as pretty-printed JSON with a trailing newline.
+    ///
+    /// Object keys are sorted, so equal snapshots always render the same.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a tool schema cannot be serialized, which does not happen
+    /// for catalogs produced by a server.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let value = serde_json::to_value(self).expect("catalog snapshot serializes");
+        let mut rendered =
+            serde_json::to_string_pretty(&value).expect("catalog snapshot serializes");
+        rendered.push('\n');
+        rendered
+    }
+}
+
+impl From<&Catalog> for CatalogSnapshot {
+    fn from(catalog: &Catalog) -> Self {
+        let mut snapshot = Self {
+            tools: catalog.tools.clone(),
+            resources: catalog.resources.clone(),
+            resource_templates: catalog.resource_templates.clone(),
+            prompts: catalog.prompts.clone(),
+        };
+        snapshot.tools.sort_by(|a, b| a.name.cmp(&b.name));
+        snapshot.resources.sort_by(|a, b| a.uri.cmp(&b.uri));
+        snapshot
+            .resource_templates
+            .sort_by(|a, b| a.uri_template.cmp(&b.uri_template));
+        snapshot.prompts.sort_by(|a, b| a.name.cmp(&b.name));
+        snapshot
+    }
+}
+
+/// How a value compared with its stored snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotResult {
+    /// The stored snapshot equals the value.
+    Matched,
+    /// The snapshot file was written, because updating was requested.
+    Updated,
+    /// There is no snapshot file yet.
+    Missing,
+    /// The stored snapshot differs from the value.
+    Mismatch {
+        /// Line diff from the stored snapshot (`-`) to the value (`+`).
+        diff: String,
+    },
+}
+
+/// Compare `actual` with the snapshot stored at `path`.
+///
+/// With `update`, a missing or different snapshot is (re)written and
+/// [`SnapshotResult::Updated`] returned.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot cannot be read or written.
+pub fn check_snapshot(
+    path: impl AsRef<Path>,
+    actual: &str,
+    update: bool,
+) -> io::Result<SnapshotResult> {
+    let path = path.as_ref();
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => Some(expected),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+    // Compare line by line so a checkout with CRLF line endings still matches.
+    if expected
+        .as_deref()
+        .is_some_and(|e| e.lines().eq(actual.lines()))
+    {
+        return Ok(SnapshotResult::Matched);
+    }
+    if update {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, actual)?;
+        return Ok(SnapshotResult::Updated);
+    }
+    Ok(match expected {
+        None => SnapshotResult::Missing,
+        Some(expected) => SnapshotResult::Mismatch {
+            diff: line_diff(&expected, actual),
+        },
+    })
+}
+
+/// Assert that `actual` equals the snapshot stored at `path`.
+///
+/// When [`UPDATE_ENV`] is set to anything but `0` or an empty string, the
+/// snapshot is written instead.
+///
+/// # Panics
+///
+/// Panics if the snapshot is missing or differs, with a diff of the change,
+/// or if it cannot be read or written.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    let update = std::env::var(UPDATE_ENV).is_ok_and(|v| !v.is_empty() && v != "0");
+    match check_snapshot(path, actual, update) {
+        Ok(SnapshotResult::Matched | SnapshotResult::Updated) => {}
+        Ok(SnapshotResult::Missing) => panic!(
+            "snapshot {} does not exist; rerun with {UPDATE_ENV}=1 to create it",
+            path.display()
+        ),
+        Ok(SnapshotResult::Mismatch { diff }) => panic!(
+            "snapshot {} does not match (- stored, + actual):\n{diff}\n\
+             rerun with {UPDATE_ENV}=1 if the change is intended",
+            path.display()
+        ),
+        Err(e) => panic!("failed to access snapshot {}: {e}", path.display()),
+    }
+}
+
+/// Assert that the tools, resources and prompts of `catalog` equal the
+/// snapshot stored at `path`.
+///
+/// See the [module documentation](self) for what is compared and how to
+/// update snapshots.
+///
+/// # Panics
+///
+/// Panics if the snapshot is missing or differs, or cannot be accessed.
+pub fn assert_catalog_snapshot(catalog: &Catalog, path: impl AsRef<Path>) {
+    assert_snapshot(path, &CatalogSnapshot::from(catalog).render());
+}
+
+/// Assert that a [`Catalog`] matches a snapshot file, resolved relative to
+/// the calling crate's manifest directory.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let catalog = server.export_catalog().await?;
+/// assert_catalog_snapshot!(catalog, "tests/snapshots/catalog.json");
+/// ```
+#[macro_export]
+macro_rules! assert_catalog_snapshot {
+    ($catalog:expr, $path:expr) => {
+        $crate::snapshot::assert_catalog_snapshot(
+            &$catalog,
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path),
+        )
+    };
+}
+
+/// Render the lines that differ between `old` and `new`, with some context.
+fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence lengths of every pair of suffixes.
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', old[i]));
+            i += 1;
+        } else {
+            ops.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(k, _)| k)
+        .collect();
+    let near_change = |k: usize| changed.iter().any(|&c| c.abs_diff(k) <= CONTEXT_LINES);
+
+    let mut out = String::new();
+    let mut skipped = false;
+    for (k, (tag, line)) in ops.iter().enumerate() {
+        if near_change(k) {
+            if skipped {
+                out.push_str("  ...\n");
+                skipped = false;
+            }
+            let _ = writeln!(out, "{tag} {line}");
+        } else {
+            skipped = true;
+        }
+    }
+    if skipped {
+        out.push_str("  ...\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
+    use std::path::PathBuf;
+
+    fn catalog(tools: Vec<Tool>) -> Catalog {
+        Catalog {
+            server_info: ServerInfo::new("snap", "1.0.0"),
+            capabilities: ServerCapabilities::new().with_tools(),
+            tools,
+            resources: Vec::new(),
+            resource_templates: Vec::new(),
+            prompts: Vec::new(),
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("mcpkit-snapshot-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_snapshot_ignores_order_and_version() {
+        let a = catalog(vec![Tool::new("b"), Tool::new("a")]);
+        let mut b = catalog(vec![Tool::new("a"), Tool::new("b")]);
+        b.server_info = ServerInfo::new("snap", "2.0.0");
+
+        assert_eq!(
+            CatalogSnapshot::from(&a).render(),
+            CatalogSnapshot::from(&b).render()
+        );
+    }
+
+    #[test]
+    fn test_check_snapshot_lifecycle() {
+        let path = temp_path("lifecycle/catalog.json");
+        let _ = std::fs::remove_file(&path);
+        let before = CatalogSnapshot::from(&catalog(vec![Tool::new("search")])).render();
+
+        assert_eq!(
+            check_snapshot(&path, &before, false).unwrap(),
+            SnapshotResult::Missing
+        );
+        assert_eq!(
+            check_snapshot(&path, &before, true).unwrap(),
+            SnapshotResult::Updated
+        );
+        assert_eq!(
+            check_snapshot(&path, &before, false).unwrap(),
+            SnapshotResult::Matched
+        );
+
+        let after = CatalogSnapshot::from(&catalog(vec![
+            Tool::new("search").description("Find things"),
+        ]))
+        .render();
+        let SnapshotResult::Mismatch { diff } = check_snapshot(&path, &after, false).unwrap()
+        else {
+            panic!("expected a mismatch");
+        };
+        assert!(
+            diff.contains("+       \"description\": \"Find things\","),
+            "{diff}"
+        );
+        assert!(!diff.contains('-'), "{diff}");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_line_diff_elides_unchanged_lines() {
+        let old = (1..=20).map(|n| format!("{n}\n")).collect::<String>();
+        let new = old.replace("10\n", "ten\n");
+
+        let diff = line_diff(&old, &new);
+        assert_eq!(
+            diff,
+            "  ...\n  7\n  8\n  9\n- 10\n+ ten\n  11\n  12\n  13\n  ...\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match")]
+    fn test_assert_catalog_snapshot_panics_on_change() {
+        let path = temp_path("changed/catalog.json");
+        let stored = CatalogSnapshot::from(&catalog(vec![Tool::new("search")])).render();
+        check_snapshot(&path, &stored, true).unwrap();
+
+        assert_catalog_snapshot(&catalog(vec![Tool::new("fetch")]), &path);
+    }
+}
@@ -0,0 +1,272 @@
+//! Decoding JSON-RPC messages from received frames.
+//!
+//! Every transport hands the frames it reads to this module instead of
+//! parsing them itself, so there is one place that turns bytes into
+//! [`Message`]s:
+//!
+//! - [`decode`] takes ownership of a [`Bytes`] frame, such as a
+//!   [`Frame::Line`](crate::framing::Frame::Line) from the framing decoders.
+//!   Transports that read into a `String` or `Vec<u8>` convert it with
+//!   `Bytes::from`, which keeps the allocation.
+//! - [`decode_slice`] parses a borrowed frame.
+//! - [`decode_ref`] parses only the envelope of a message into a
+//!   [`MessageRef`] that borrows from the frame. Its `params`, `result`, and
+//!   `error` stay unparsed until asked for, which lets a router or middleware
+//!   look at the method and id without building a `serde_json::Value` tree.
+//!
+//! # SIMD parsing
+//!
+//! With the `simd-json` feature, [`decode`] and [`decode_slice`] parse with
+//! [simd-json](https://docs.rs/simd-json). It does not make decoding faster
+//! in the `codec` benchmark (`cargo bench -p mcpkit-benches --bench codec`):
+//! serde buffers each [`Message`] to pick its variant whichever parser runs,
+//! and simd-json is slower on small frames. Measure your own traffic before
+//! enabling it. simd-json parses in place: [`decode`] reuses the frame's
+//! buffer when the frame is its only owner, and copies it otherwise.
+//! [`decode_ref`] always uses `serde_json`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_transport::Bytes;
+//! use mcpkit_transport::codec;
+//!
+//! let frame = Bytes::from_static(br#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#);
+//!
+//! let envelope = codec::decode_ref(&frame)?;
+//! assert_eq!(envelope.method.as_deref(), Some("tools/list"));
+//!
+//! let message = codec::decode(frame)?;
+//! assert!(message.is_request());
+//! # Ok::<(), mcpkit_transport::TransportError>(())
+//! ```
+
+use crate::error::TransportError;
+use bytes::Bytes;
+use mcpkit_core::protocol::{Message, RequestId};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+
+/// Decode a message from a frame, taking ownership of its bytes.
+///
+/// # Errors
+///
+/// Returns [`TransportError::Deserialization`] if the frame is not a valid
+/// JSON-RPC message.
+pub fn decode(frame: Bytes) -> Result<Message, TransportError> {
+    #[cfg(feature = "simd-json")]
+    {
+        // A uniquely owned `Bytes` converts back into its `Vec` without
+        // copying; a shared one is copied.
+        let mut buf = Vec::from(frame);
+        simd_json::serde::from_slice(&mut buf).map_err(deserialization_error)
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        decode_slice(&frame)
+    }
+}
+
+/// Decode a message from a borrowed frame.
+///
+/// With the `simd-json` feature the frame is copied into a scratch buffer
+/// first; prefer [`decode`] when the frame can be given away.
+///
+/// # Errors
+///
+/// Returns [`TransportError::Deserialization`] if the frame is not a valid
+/// JSON-RPC message.
+pub fn decode_slice(frame: &[u8]) -> Result<Message, TransportError> {
+    #[cfg(feature = "simd-json")]
+    {
+        decode(Bytes::copy_from_slice(frame))
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        // Validate the frame once and parse it as a `str`, like the
+        // `serde_json::from_str` transports used before: `from_slice` checks
+        // each string it reads, which is slower on small frames.
+        let text = std::str::from_utf8(frame).map_err(deserialization_error)?;
+        serde_json::from_str(text).map_err(deserialization_error)
+    }
+}
+
+/// Decode the envelope of a message, borrowing from `frame`.
+///
+/// # Errors
+///
+/// Returns [`TransportError::Deserialization`] if the frame is not a JSON
+/// object with the shape of a JSON-RPC message.
+pub fn decode_ref(frame: &[u8]) -> Result<MessageRef<'_>, TransportError> {
+    serde_json::from_slice(frame).map_err(deserialization_error)
+}
+
+fn deserialization_error(e: impl std::fmt::Display) -> TransportError {
+    TransportError::Deserialization {
+        message: format!("Failed to deserialize message: {e}"),
+    }
+}
+
+/// The envelope of a JSON-RPC message, borrowed from its frame.
+///
+/// The members of the message are kept as raw JSON; only the method and id
+/// are parsed. Produced by [`decode_ref`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageRef<'a> {
+    /// The method, for requests and notifications. Borrowed unless it
+    /// contains escape sequences.
+    #[serde(borrow, default, deserialize_with = "borrowed_str")]
+    pub method: Option<Cow<'a, str>>,
+    /// The id, for requests and responses. `None` for notifications and for
+    /// error responses with a `null` id.
+    #[serde(default)]
+    pub id: Option<RequestId>,
+    /// The unparsed `params`.
+    #[serde(borrow, default)]
+    pub params: Option<&'a RawValue>,
+    /// The unparsed `result`, for successful responses.
+    #[serde(borrow, default)]
+    pub result: Option<&'a RawValue>,
+    /// The unparsed `error`, for error responses.
+    #[serde(borrow, default)]
+    pub error: Option<&'a RawValue>,
+}
+
+/// Deserialize an optional string, borrowing it when it has no escapes.
+///
+/// `Option<Cow<str>>` alone always allocates.
+fn borrowed_str<'de, D>(deserializer: D) -> Result<Option<Cow<'de, str>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    Ok(Option::<Borrowed<'de>>::deserialize(deserializer)?.map(|s| s.0))
+}
+
+impl<'a> MessageRef<'a> {
+    /// Check if this is a request.
+    #[must_use]
+    pub const fn is_request(&self) -> bool {
+        self.method.is_some() && self.id.is_some()
+    }
+
+    /// Check if this is a notification.
+    #[must_use]
+    pub const fn is_notification(&self) -> bool {
+        self.method.is_some() && self.id.is_none()
+    }
+
+    /// Check if this is a response: a `result` with an id, or an `error`
+    /// (whose id may be `null`), and no method.
+    #[must_use]
+    pub const fn is_response(&self) -> bool {
+        self.method.is_none()
+            && ((self.result.is_some() && self.id.is_some()) || self.error.is_some())
+    }
+
+    /// Parse the `params` as `T`, which may borrow from the frame.
+    ///
+    /// Returns `Ok(None)` when the message has no `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransportError::Deserialization`] if the `params` do not
+    /// match `T`.
+    pub fn params<T: Deserialize<'a>>(&self) -> Result<Option<T>, TransportError> {
+        self.params
+            .map(|raw| serde_json::from_str(raw.get()))
+            .transpose()
+            .map_err(deserialization_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUEST: &[u8] =
+        br#"{"jsonrpc":"2.0","id":7,"method":"tools/call","params":{"name":"echo","arguments":{"text":"hi"}}}"#;
+
+    #[test]
+    fn decodes_every_message_kind() -> Result<(), TransportError> {
+        let request = decode(Bytes::from_static(REQUEST))?;
+        assert_eq!(request.method(), Some("tools/call"));
+        assert_eq!(request.id(), Some(&RequestId::Number(7)));
+
+        let notification =
+            decode_slice(br#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)?;
+        assert!(notification.is_notification());
+
+        let response = decode(Bytes::from(
+            br#"{"jsonrpc":"2.0","id":"a","result":{"tools":[]}}"#.to_vec(),
+        ))?;
+        assert!(response.is_response());
+        assert_eq!(response.id(), Some(&RequestId::String("a".to_string())));
+
+        let error = decode_slice(
+            br#"{"jsonrpc":"2.0","id":null,"error":{"code":-32700,"message":"Parse error"}}"#,
+        )?;
+        assert!(error.is_response());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_frames() {
+        for frame in [
+            &b"not json"[..],
+            b"{\"jsonrpc\":\"2.0\"",
+            b"[1,2]",
+            b"{\"jsonrpc\":\"2.0\",\"method\":\"\xff\"}",
+        ] {
+            assert!(matches!(
+                decode(Bytes::copy_from_slice(frame)),
+                Err(TransportError::Deserialization { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn envelope_borrows_from_the_frame() -> Result<(), TransportError> {
+        #[derive(Deserialize)]
+        struct CallParams<'a> {
+            name: &'a str,
+        }
+
+        let envelope = decode_ref(REQUEST)?;
+        assert!(envelope.is_request());
+        assert!(matches!(envelope.method, Some(Cow::Borrowed("tools/call"))));
+        assert_eq!(envelope.id, Some(RequestId::Number(7)));
+        assert!(envelope.result.is_none());
+
+        let params: CallParams<'_> = envelope.params()?.expect("params");
+        assert_eq!(params.name, "echo");
+        let range = REQUEST.as_ptr_range();
+        assert!(range.contains(&params.name.as_ptr()), "name was copied");
+
+        let response = decode_ref(br#"{"jsonrpc":"2.0","id":1,"result":{}}"#)?;
+        assert!(response.is_response());
+        assert_eq!(response.result.map(RawValue::get), Some("{}"));
+        Ok(())
+    }
+
+    #[test]
+    fn envelope_without_result_or_error_is_not_a_response() -> Result<(), TransportError> {
+        let error = decode_ref(br#"{"jsonrpc":"2.0","id":null,"error":{"code":-32700}}"#)?;
+        assert!(error.is_response());
+
+        for frame in [
+            &br#"{"jsonrpc":"2.0","id":1}"#[..],
+            br#"{"jsonrpc":"2.0"}"#,
+            br#"{"jsonrpc":"2.0","result":{}}"#,
+        ] {
+            let envelope = decode_ref(frame)?;
+            assert!(!envelope.is_response());
+            assert!(!envelope.is_request());
+            assert!(!envelope.is_notification());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,505 @@
+//! Gzip and deflate compression for HTTP bodies and WebSocket messages.
+//!
+//! Large tool results are mostly repetitive JSON, so they shrink well.
+//! Compression is opt-in per transport through a [`CompressionConfig`]:
+//!
+//! - [`HttpTransport`](crate::http::HttpTransport) advertises
+//!   `Accept-Encoding: gzip, deflate` and decodes compressed JSON and SSE
+//!   responses. With
+//!   [`with_request_compression`](crate::http::HttpTransportConfig::with_request_compression)
+//!   it also compresses request bodies.
+//! - The framework adapters (e.g. `McpRouter::with_compression` in
+//!   `mcpkit-axum`) compress responses for clients that accept it and decode
+//!   compressed request bodies.
+//! - [`WebSocketTransport`](crate::websocket::WebSocketTransport) and
+//!   [`WebSocketListener`](crate::websocket::WebSocketListener) negotiate
+//!   compression during the upgrade. See [`MCP_COMPRESSION_HEADER`].
+//!
+//! Messages smaller than [`CompressionConfig::min_size`] are sent as-is. A
+//! shared [`CompressionStats`] counts the bytes before and after encoding so
+//! the savings can be monitored.
+//!
+//! The `compression` feature, enabled by `http` and `websocket`, provides the
+//! codecs.
+//!
+//! ```rust
+//! # #[cfg(feature = "compression")]
+//! # {
+//! use mcpkit_transport::compression::{CompressionConfig, Encoding, compress, decompress};
+//!
+//! let config = CompressionConfig::new().with_min_size(64);
+//! let body = serde_json::to_vec(&vec!["the same text"; 100]).unwrap();
+//! assert!(config.should_compress(body.len()));
+//!
+//! let encoded = compress(Encoding::Gzip, &body, config.level).unwrap();
+//! assert!(encoded.len() < body.len());
+//! assert_eq!(decompress(Encoding::Gzip, &encoded, body.len()).unwrap(), body);
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "compression")]
+use {crate::error::TransportError, std::io::Write};
+
+/// Handshake header a WebSocket client uses to offer compression, and the
+/// listener echoes to accept it.
+///
+/// tungstenite does not implement the RFC 7692 `permessage-deflate`
+/// extension, so mcpkit negotiates its own: the client offers its
+/// [`Encoding`], and once the listener echoes it, either side may send a
+/// message as a binary frame holding the compressed JSON. Text frames stay
+/// plain JSON, so peers that do not offer the header are unaffected.
+pub const MCP_COMPRESSION_HEADER: &str = "mcp-compression";
+
+/// The `Accept-Encoding` value sent by clients that accept compressed bodies.
+pub const ACCEPT_ENCODING: &str = "gzip, deflate";
+
+/// Default size below which messages are not compressed (1 KiB).
+pub const DEFAULT_MIN_SIZE: usize = 1024;
+
+/// Default compression level.
+pub const DEFAULT_LEVEL: u32 = 6;
+
+/// A content coding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// `gzip` (RFC 1952).
+    #[default]
+    Gzip,
+    /// `deflate`: zlib-wrapped deflate (RFC 1950), as HTTP defines it.
+    Deflate,
+}
+
+impl Encoding {
+    /// The encoding's token in `Content-Encoding` and `Accept-Encoding`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+
+    /// Parse a `Content-Encoding` value.
+    ///
+    /// Returns `Ok(None)` for `identity` or an empty value, and an error
+    /// naming the coding when it is not supported.
+    pub fn from_header(value: &str) -> Result<Option<Self>, String> {
+        let value = value.trim();
+        if value.is_empty() || value.eq_ignore_ascii_case("identity") {
+            return Ok(None);
+        }
+        if value.eq_ignore_ascii_case("gzip") || value.eq_ignore_ascii_case("x-gzip") {
+            Ok(Some(Self::Gzip))
+        } else if value.eq_ignore_ascii_case("deflate") {
+            Ok(Some(Self::Deflate))
+        } else {
+            Err(value.to_string())
+        }
+    }
+
+    /// Choose an encoding the peer accepts, given its `Accept-Encoding`
+    /// header, preferring `preferred`.
+    ///
+    /// Quality values are honoured: a coding with `q=0` is never chosen, and
+    /// `*` covers codings not listed explicitly.
+    #[must_use]
+    pub fn negotiate(accept_encoding: &str, preferred: Self) -> Option<Self> {
+        let mut wildcard = None;
+        let mut listed = [None, None];
+        for item in accept_encoding.split(',') {
+            let mut parts = item.split(';');
+            let name = parts.next().unwrap_or_default().trim();
+            let quality = parts
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if name == "*" {
+                wildcard = Some(quality);
+            } else if let Ok(Some(encoding)) = Self::from_header(name) {
+                listed[encoding as usize] = Some(quality);
+            }
+        }
+        let quality = |encoding: Self| listed[encoding as usize].or(wildcard).unwrap_or(0.0);
+        let other = match preferred {
+            Self::Gzip => Self::Deflate,
+            Self::Deflate => Self::Gzip,
+        };
+        if quality(preferred) > 0.0 && quality(preferred) >= quality(other) {
+            Some(preferred)
+        } else if quality(other) > 0.0 {
+            Some(other)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// When and how to compress outgoing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionConfig {
+    /// Encoding used for messages this side compresses.
+    pub encoding: Encoding,
+    /// Messages smaller than this many bytes are sent uncompressed.
+    pub min_size: usize,
+    /// Compression level, from 0 (store) to 9 (smallest).
+    pub level: u32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompressionConfig {
+    /// Gzip at the default level for messages of at least
+    /// [`DEFAULT_MIN_SIZE`] bytes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            encoding: Encoding::Gzip,
+            min_size: DEFAULT_MIN_SIZE,
+            level: DEFAULT_LEVEL,
+        }
+    }
+
+    /// Set the encoding used for outgoing messages.
+    #[must_use]
+    pub const fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set the size below which messages are sent uncompressed.
+    #[must_use]
+    pub const fn with_min_size(mut self, min_size: usize) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Set the compression level, clamped to 9.
+    #[must_use]
+    pub const fn with_level(mut self, level: u32) -> Self {
+        self.level = if level > 9 { 9 } else { level };
+        self
+    }
+
+    /// Whether a message of `len` bytes is large enough to compress.
+    #[must_use]
+    pub const fn should_compress(&self, len: usize) -> bool {
+        len >= self.min_size
+    }
+}
+
+/// Counts of bytes before and after compression, shared by a transport or
+/// listener and everything it creates.
+#[derive(Debug, Default)]
+pub struct CompressionStats {
+    messages_compressed: AtomicU64,
+    sent_original_bytes: AtomicU64,
+    sent_encoded_bytes: AtomicU64,
+    messages_decompressed: AtomicU64,
+    received_encoded_bytes: AtomicU64,
+    received_original_bytes: AtomicU64,
+}
+
+impl CompressionStats {
+    /// Create zeroed statistics.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an outgoing message compressed from `original` to `encoded`
+    /// bytes.
+    pub fn record_compressed(&self, original: usize, encoded: usize) {
+        self.messages_compressed.fetch_add(1, Ordering::Relaxed);
+        self.sent_original_bytes
+            .fetch_add(original as u64, Ordering::Relaxed);
+        self.sent_encoded_bytes
+            .fetch_add(encoded as u64, Ordering::Relaxed);
+    }
+
+    /// Record an incoming message decompressed from `encoded` to `original`
+    /// bytes.
+    pub fn record_decompressed(&self, encoded: usize, original: usize) {
+        self.messages_decompressed.fetch_add(1, Ordering::Relaxed);
+        self.received_encoded_bytes
+            .fetch_add(encoded as u64, Ordering::Relaxed);
+        self.received_original_bytes
+            .fetch_add(original as u64, Ordering::Relaxed);
+    }
+
+    /// Get a snapshot of the counters.
+    #[must_use]
+    pub fn snapshot(&self) -> CompressionSnapshot {
+        CompressionSnapshot {
+            messages_compressed: self.messages_compressed.load(Ordering::Relaxed),
+            sent_original_bytes: self.sent_original_bytes.load(Ordering::Relaxed),
+            sent_encoded_bytes: self.sent_encoded_bytes.load(Ordering::Relaxed),
+            messages_decompressed: self.messages_decompressed.load(Ordering::Relaxed),
+            received_encoded_bytes: self.received_encoded_bytes.load(Ordering::Relaxed),
+            received_original_bytes: self.received_original_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A point-in-time copy of [`CompressionStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionSnapshot {
+    /// Outgoing messages that were compressed.
+    pub messages_compressed: u64,
+    /// Size of those messages before compression.
+    pub sent_original_bytes: u64,
+    /// Size of those messages on the wire.
+    pub sent_encoded_bytes: u64,
+    /// Incoming messages, or chunks of a compressed stream, that were
+    /// decompressed.
+    pub messages_decompressed: u64,
+    /// Size of those messages on the wire.
+    pub received_encoded_bytes: u64,
+    /// Size of those messages after decompression.
+    pub received_original_bytes: u64,
+}
+
+impl CompressionSnapshot {
+    /// Bytes kept off the wire, in both directions.
+    #[must_use]
+    pub const fn bytes_saved(&self) -> u64 {
+        self.sent_original_bytes
+            .saturating_sub(self.sent_encoded_bytes)
+            .saturating_add(
+                self.received_original_bytes
+                    .saturating_sub(self.received_encoded_bytes),
+            )
+    }
+}
+
+/// Compress `data` with `encoding` at `level` (0-9).
+///
+/// # Errors
+///
+/// Returns [`TransportError::Serialization`] if the encoder fails.
+#[cfg(feature = "compression")]
+pub fn compress(encoding: Encoding, data: &[u8], level: u32) -> Result<Vec<u8>, TransportError> {
+    let level = flate2::Compression::new(level.min(9));
+    let out = Vec::with_capacity(data.len() / 4);
+    let result = match encoding {
+        Encoding::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(out, level);
+            encoder.write_all(data).and_then(|()| encoder.finish())
+        }
+        Encoding::Deflate => {
+            let mut encoder = flate2::write::ZlibEncoder::new(out, level);
+            encoder.write_all(data).and_then(|()| encoder.finish())
+        }
+    };
+    result.map_err(|e| TransportError::Serialization {
+        message: format!("{encoding} compression failed: {e}"),
+    })
+}
+
+/// Decompress `data` encoded with `encoding`, refusing output larger than
+/// `max_size` bytes.
+///
+/// The limit is enforced while inflating, so a small body that expands to
+/// gigabytes is rejected without being held in memory.
+///
+/// # Errors
+///
+/// Returns [`TransportError::MessageTooLarge`] when the output would exceed
+/// `max_size`, and [`TransportError::Protocol`] for corrupt input.
+#[cfg(feature = "compression")]
+pub fn decompress(
+    encoding: Encoding,
+    data: &[u8],
+    max_size: usize,
+) -> Result<Vec<u8>, TransportError> {
+    let mut decoder = Decoder::new(encoding, max_size);
+    decoder.push(data)?;
+    decoder.finish()
+}
+
+/// Incremental decompression of a body that arrives in chunks, such as an
+/// SSE stream.
+#[cfg(feature = "compression")]
+pub(crate) struct Decoder {
+    inner: DecoderKind,
+    encoding: Encoding,
+    max_size: usize,
+    /// Total output produced, for the size limit.
+    produced: usize,
+}
+
+#[cfg(feature = "compression")]
+enum DecoderKind {
+    Gzip(flate2::write::GzDecoder<Vec<u8>>),
+    Deflate(flate2::write::ZlibDecoder<Vec<u8>>),
+}
+
+#[cfg(feature = "compression")]
+impl Decoder {
+    /// A decoder whose total output may not exceed `max_size` bytes.
+    pub(crate) fn new(encoding: Encoding, max_size: usize) -> Self {
+        let inner = match encoding {
+            Encoding::Gzip => DecoderKind::Gzip(flate2::write::GzDecoder::new(Vec::new())),
+            Encoding::Deflate => DecoderKind::Deflate(flate2::write::ZlibDecoder::new(Vec::new())),
+        };
+        Self {
+            inner,
+            encoding,
+            max_size,
+            produced: 0,
+        }
+    }
+
+    /// Feed the next chunk of input.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Result<(), TransportError> {
+        // Write in slices so output is checked against the limit as it grows.
+        for piece in chunk.chunks(8 * 1024) {
+            // Flush so the decoded bytes reach the output buffer now rather
+            // than on the next write.
+            let written = match &mut self.inner {
+                DecoderKind::Gzip(d) => d.write_all(piece).and_then(|()| d.flush()),
+                DecoderKind::Deflate(d) => d.write_all(piece).and_then(|()| d.flush()),
+            };
+            written.map_err(|e| self.corrupt(&e))?;
+            let buffered = self.output().len();
+            if self.produced + buffered > self.max_size {
+                return Err(TransportError::MessageTooLarge {
+                    size: self.produced + buffered,
+                    max: self.max_size,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Take the output decoded so far.
+    pub(crate) fn take(&mut self) -> Vec<u8> {
+        let out = std::mem::take(self.output());
+        self.produced += out.len();
+        out
+    }
+
+    /// Finish the stream and return the remaining output.
+    pub(crate) fn finish(mut self) -> Result<Vec<u8>, TransportError> {
+        let flushed = match &mut self.inner {
+            DecoderKind::Gzip(d) => d.try_finish(),
+            DecoderKind::Deflate(d) => d.try_finish(),
+        };
+        flushed.map_err(|e| self.corrupt(&e))?;
+        let size = self.produced + self.output().len();
+        if size > self.max_size {
+            return Err(TransportError::MessageTooLarge {
+                size,
+                max: self.max_size,
+            });
+        }
+        Ok(self.take())
+    }
+
+    fn output(&mut self) -> &mut Vec<u8> {
+        match &mut self.inner {
+            DecoderKind::Gzip(d) => d.get_mut(),
+            DecoderKind::Deflate(d) => d.get_mut(),
+        }
+    }
+
+    fn corrupt(&self, e: &std::io::Error) -> TransportError {
+        TransportError::Protocol {
+            message: format!("invalid {} data: {e}", self.encoding),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "compression"))]
+mod tests {
+    use super::*;
+
+    fn payload() -> Vec<u8> {
+        serde_json::to_vec(&vec![
+            serde_json::json!({ "name": "item", "value": 42 });
+            200
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn round_trips_both_encodings() {
+        let data = payload();
+        for encoding in [Encoding::Gzip, Encoding::Deflate] {
+            let encoded = compress(encoding, &data, DEFAULT_LEVEL).unwrap();
+            assert!(encoded.len() * 10 < data.len(), "{encoding}");
+            assert_eq!(decompress(encoding, &encoded, data.len()).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn decompression_is_bounded_and_rejects_garbage() {
+        let data = payload();
+        let encoded = compress(Encoding::Gzip, &data, DEFAULT_LEVEL).unwrap();
+        let err = decompress(Encoding::Gzip, &encoded, data.len() - 1).unwrap_err();
+        assert!(
+            matches!(err, TransportError::MessageTooLarge { .. }),
+            "{err}"
+        );
+
+        let err = decompress(Encoding::Deflate, b"not deflate", 1024).unwrap_err();
+        assert!(matches!(err, TransportError::Protocol { .. }), "{err}");
+    }
+
+    #[test]
+    fn chunked_decoding_matches_one_shot() {
+        let data = payload();
+        let encoded = compress(Encoding::Deflate, &data, DEFAULT_LEVEL).unwrap();
+        let mut decoder = Decoder::new(Encoding::Deflate, data.len());
+        let mut out = Vec::new();
+        for chunk in encoded.chunks(7) {
+            decoder.push(chunk).unwrap();
+            out.extend(decoder.take());
+        }
+        out.extend(decoder.finish().unwrap());
+        assert_eq!(out, data);
+    }
+
+    #[test]
+    fn negotiation_honours_preference_and_quality() {
+        use Encoding::{Deflate, Gzip};
+        assert_eq!(Encoding::negotiate("gzip, deflate", Deflate), Some(Deflate));
+        assert_eq!(Encoding::negotiate("deflate", Gzip), Some(Deflate));
+        assert_eq!(
+            Encoding::negotiate("gzip;q=0, deflate", Gzip),
+            Some(Deflate)
+        );
+        assert_eq!(
+            Encoding::negotiate("gzip;q=0.2, deflate;q=0.8", Gzip),
+            Some(Deflate)
+        );
+        assert_eq!(Encoding::negotiate("*", Deflate), Some(Deflate));
+        assert_eq!(Encoding::negotiate("br, identity", Gzip), None);
+        assert_eq!(Encoding::negotiate("", Gzip), None);
+        assert_eq!(Encoding::from_header("X-GZIP"), Ok(Some(Gzip)));
+        assert_eq!(Encoding::from_header("br"), Err("br".to_string()));
+    }
+
+    #[test]
+    fn stats_report_bytes_saved() {
+        let stats = CompressionStats::new();
+        stats.record_compressed(1000, 200);
+        stats.record_decompressed(50, 450);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.messages_compressed, 1);
+        assert_eq!(snapshot.messages_decompressed, 1);
+        assert_eq!(snapshot.bytes_saved(), 1200);
+    }
+}
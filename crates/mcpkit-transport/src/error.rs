@@ -92,6 +92,14 @@ pub enum TransportError {
         /// Suggested retry delay.
         retry_after: Option<std::time::Duration>,
     },
+
+    /// The connection dropped and was re-established; the peer's session
+    /// state was reset and must be rebuilt (e.g. by re-initializing).
+    #[error("Connection re-established after {attempts} attempt(s); session state was reset")]
+    Reconnected {
+        /// Reconnection attempts it took.
+        attempts: u32,
+    },
 }
 
 impl TransportError {
@@ -139,6 +147,7 @@ impl TransportError {
             Self::Protocol { .. } => TransportErrorKind::ProtocolViolation,
             Self::Timeout { .. } => TransportErrorKind::Timeout,
             Self::RateLimited { .. } => TransportErrorKind::RateLimited,
+            Self::Reconnected { .. } => TransportErrorKind::Reconnected,
         }
     }
 }
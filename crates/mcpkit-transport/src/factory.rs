@@ -0,0 +1,667 @@
+//! Transports chosen by URL.
+//!
+//! CLI tools and config-driven hosts usually get a server's address as a
+//! string and should not need to know which transport type it maps to.
+//! [`connect`] and [`listen`] take such a string and return a type-erased
+//! [`BoxedTransport`] or [`BoxedListener`]:
+//!
+//! | URL | [`connect`] | [`listen`] | Feature |
+//! |-----|-------------|------------|---------|
+//! | `stdio:` | this process's stdin/stdout | one connection over stdin/stdout | |
+//! | `stdio:./server --flag` | spawns the command | not supported | |
+//! | `http://…`, `https://…` | [`HttpTransport`](crate::HttpTransport) | not supported | `http` |
+//! | `ws://…`, `wss://…` | [`WebSocketTransport`](crate::WebSocketTransport) | not supported | `websocket` |
+//! | `unix:/path.sock` | [`UnixTransport`](crate::unix::UnixTransport) | [`UnixListener`](crate::unix::UnixListener) | Unix only |
+//! | `npipe:name` | `NamedPipeTransport` | `NamedPipeServer` | Windows only |
+//! | `grpc://host:port`, `grpcs://…` | `GrpcTransport` | `GrpcServer` (`grpc://` only) | `grpc` |
+//!
+//! The command after `stdio:` is split on whitespace; single or double
+//! quotes keep an argument with spaces together. HTTP is served through a
+//! framework adapter (`mcpkit-axum` and friends), and WebSocket through
+//! [`WebSocketListener`](crate::websocket::WebSocketListener), so neither can
+//! be listened on here. Transports needing more than an address (headers,
+//! TLS identities, reconnection policies) are built with their own builders
+//! and can still be boxed with [`BoxedTransport::new`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use mcpkit_transport::{Transport, factory};
+//!
+//! # async fn run(url: &str) -> Result<(), mcpkit_transport::TransportError> {
+//! // e.g. "stdio:npx -y @modelcontextprotocol/server-everything"
+//! let transport = factory::connect(url).await?;
+//! println!("connected over {}", transport.metadata().transport_type);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::TransportError;
+use crate::traits::{Transport, TransportListener, TransportMetadata};
+use mcpkit_core::protocol::Message;
+use std::fmt;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A boxed, `Send` transport operation.
+type BoxFut<'a, T> = Pin<Box<dyn Future<Output = Result<T, TransportError>> + Send + 'a>>;
+
+/// A transport address, parsed from a URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransportUrl {
+    /// `stdio:` — this process's stdin and stdout.
+    Stdio,
+    /// `stdio:<command>` — a server spawned as a subprocess.
+    Spawn {
+        /// The program to run.
+        program: String,
+        /// Its arguments.
+        args: Vec<String>,
+    },
+    /// `http://` or `https://` — a Streamable HTTP endpoint.
+    Http(String),
+    /// `ws://` or `wss://` — a WebSocket endpoint.
+    WebSocket(String),
+    /// `unix:` — a Unix domain socket.
+    Unix(PathBuf),
+    /// `npipe:` — a Windows named pipe.
+    NamedPipe(String),
+    /// `grpc://` or `grpcs://` — a gRPC endpoint.
+    Grpc {
+        /// `host:port`.
+        addr: String,
+        /// Whether to use TLS (`grpcs://`).
+        tls: bool,
+    },
+}
+
+impl TransportUrl {
+    /// Parse a transport URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransportError::Connection`] if the scheme is unknown, the
+    /// address after it is empty, or a quote in a `stdio:` command is not
+    /// closed.
+    pub fn parse(url: &str) -> Result<Self, TransportError> {
+        let url = url.trim();
+        let (scheme, rest) = url
+            .split_once(':')
+            .ok_or_else(|| invalid(url, "missing a scheme"))?;
+        let address = |rest: &str| {
+            let rest = rest.strip_prefix("//").unwrap_or(rest);
+            if rest.is_empty() {
+                Err(invalid(url, "missing an address"))
+            } else {
+                Ok(rest.to_string())
+            }
+        };
+        match scheme {
+            "stdio" => {
+                let mut words =
+                    split_command(rest).ok_or_else(|| invalid(url, "unclosed quote"))?;
+                if words.is_empty() {
+                    return Ok(Self::Stdio);
+                }
+                let program = words.remove(0);
+                Ok(Self::Spawn {
+                    program,
+                    args: words,
+                })
+            }
+            "http" | "https" => address(rest).map(|_| Self::Http(url.to_string())),
+            "ws" | "wss" => address(rest).map(|_| Self::WebSocket(url.to_string())),
+            "unix" => address(rest).map(|path| Self::Unix(PathBuf::from(path))),
+            "npipe" => address(rest).map(Self::NamedPipe),
+            "grpc" | "grpcs" => address(rest).map(|addr| Self::Grpc {
+                addr: addr.trim_end_matches('/').to_string(),
+                tls: scheme == "grpcs",
+            }),
+            _ => Err(invalid(url, "unknown scheme")),
+        }
+    }
+}
+
+impl FromStr for TransportUrl {
+    type Err = TransportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for TransportUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdio => write!(f, "stdio:"),
+            Self::Spawn { program, args } => {
+                write!(f, "stdio:{}", quote(program))?;
+                for arg in args {
+                    write!(f, " {}", quote(arg))?;
+                }
+                Ok(())
+            }
+            Self::Http(url) | Self::WebSocket(url) => f.write_str(url),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+            Self::NamedPipe(name) => write!(f, "npipe:{name}"),
+            Self::Grpc { addr, tls } => {
+                write!(f, "{}://{addr}", if *tls { "grpcs" } else { "grpc" })
+            }
+        }
+    }
+}
+
+fn invalid(url: &str, why: &str) -> TransportError {
+    TransportError::Connection {
+        message: format!("Invalid transport URL `{url}`: {why}"),
+    }
+}
+
+fn unsupported(url: &TransportUrl, why: &str) -> TransportError {
+    TransportError::Connection {
+        message: format!("Cannot use `{url}`: {why}"),
+    }
+}
+
+/// Split a command line on whitespace, keeping quoted text together.
+///
+/// Returns `None` if a quote is not closed.
+fn split_command(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return None;
+    }
+    words.extend(word);
+    Some(words)
+}
+
+/// Quote a command word for display if it would not split back to itself.
+fn quote(word: &str) -> String {
+    if !word.is_empty() && !word.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'') {
+        word.to_string()
+    } else if word.contains('"') {
+        format!("'{word}'")
+    } else {
+        format!("\"{word}\"")
+    }
+}
+
+/// Connect to the transport a URL names.
+///
+/// # Errors
+///
+/// Returns an error if the URL is invalid, names a transport this build or
+/// platform lacks, or the connection fails.
+pub async fn connect(url: &str) -> Result<BoxedTransport, TransportError> {
+    connect_url(&TransportUrl::parse(url)?).await
+}
+
+/// Connect to a parsed [`TransportUrl`].
+///
+/// # Errors
+///
+/// See [`connect`].
+pub async fn connect_url(url: &TransportUrl) -> Result<BoxedTransport, TransportError> {
+    match url {
+        TransportUrl::Stdio => Ok(BoxedTransport::new(ProcessStdio::new())),
+        TransportUrl::Spawn { program, args } => {
+            let transport = crate::spawn::SpawnedTransport::spawn(program, args).await?;
+            Ok(BoxedTransport::new(transport))
+        }
+        #[cfg(feature = "http")]
+        TransportUrl::Http(endpoint) => {
+            let config = crate::http::HttpTransportConfig::new(endpoint.clone());
+            Ok(BoxedTransport::new(
+                crate::http::HttpTransport::connect(config).await?,
+            ))
+        }
+        #[cfg(feature = "websocket")]
+        TransportUrl::WebSocket(endpoint) => {
+            let config = crate::websocket::WebSocketConfig::new(endpoint.clone());
+            Ok(BoxedTransport::new(
+                crate::websocket::WebSocketTransport::connect(config).await?,
+            ))
+        }
+        #[cfg(unix)]
+        TransportUrl::Unix(path) => Ok(BoxedTransport::new(
+            crate::unix::UnixTransport::connect(path).await?,
+        )),
+        #[cfg(windows)]
+        TransportUrl::NamedPipe(name) => Ok(BoxedTransport::new(
+            crate::windows::NamedPipeTransport::connect(name.clone()).await?,
+        )),
+        #[cfg(feature = "grpc")]
+        TransportUrl::Grpc { addr, tls } => {
+            let scheme = if *tls { "https" } else { "http" };
+            let mut config = crate::grpc::GrpcConfig::new(format!("{scheme}://{addr}"));
+            if *tls {
+                config = config.with_tls();
+            }
+            Ok(BoxedTransport::new(
+                crate::grpc::GrpcTransport::connect(config).await?,
+            ))
+        }
+        #[allow(unreachable_patterns)]
+        other => Err(missing_support(other)),
+    }
+}
+
+/// Listen on the transport a URL names.
+///
+/// # Errors
+///
+/// Returns an error if the URL is invalid, names a transport that cannot be
+/// listened on here (see the [module docs](self)), or binding fails.
+pub async fn listen(url: &str) -> Result<BoxedListener, TransportError> {
+    listen_url(&TransportUrl::parse(url)?).await
+}
+
+/// Listen on a parsed [`TransportUrl`].
+///
+/// # Errors
+///
+/// See [`listen`].
+pub async fn listen_url(url: &TransportUrl) -> Result<BoxedListener, TransportError> {
+    match url {
+        TransportUrl::Stdio => Ok(BoxedListener::new(StdioListener::default())),
+        TransportUrl::Spawn { .. } => Err(unsupported(
+            url,
+            "a spawned command is a client transport; listen on `stdio:` in the server",
+        )),
+        TransportUrl::Http(_) => Err(unsupported(
+            url,
+            "serve HTTP with a framework adapter such as mcpkit-axum",
+        )),
+        TransportUrl::WebSocket(_) => Err(unsupported(
+            url,
+            "serve WebSocket connections with `WebSocketListener`",
+        )),
+        #[cfg(unix)]
+        TransportUrl::Unix(path) => Ok(BoxedListener::new(
+            crate::unix::UnixListener::bind(path).await?,
+        )),
+        #[cfg(windows)]
+        TransportUrl::NamedPipe(name) => Ok(BoxedListener::new(
+            crate::windows::NamedPipeServer::bind(name.clone())?,
+        )),
+        #[cfg(feature = "grpc")]
+        TransportUrl::Grpc { tls: true, .. } => Err(unsupported(
+            url,
+            "a TLS listener needs an identity; build a `GrpcServer` with one",
+        )),
+        #[cfg(feature = "grpc")]
+        TransportUrl::Grpc { addr, tls: false } => {
+            let config = crate::grpc::GrpcServerConfig::new(addr.clone());
+            let server = std::sync::Arc::new(crate::grpc::GrpcServer::bind(config).await?);
+            std::sync::Arc::clone(&server).start().await?;
+            Ok(BoxedListener::new(StartedGrpcServer(server)))
+        }
+        #[allow(unreachable_patterns)]
+        other => Err(missing_support(other)),
+    }
+}
+
+/// The error for a transport this build or platform does not include.
+fn missing_support(url: &TransportUrl) -> TransportError {
+    let why = match url {
+        TransportUrl::Http(_) => "requires the `http` feature",
+        TransportUrl::WebSocket(_) => "requires the `websocket` feature",
+        TransportUrl::Unix(_) => "Unix domain sockets are not available on this platform",
+        TransportUrl::NamedPipe(_) => "named pipes are only available on Windows",
+        TransportUrl::Grpc { .. } => "requires the `grpc` feature",
+        _ => "not supported",
+    };
+    unsupported(url, why)
+}
+
+/// Object-safe form of [`Transport`] with errors converted to
+/// [`TransportError`].
+trait DynTransport: Send + Sync {
+    fn send(&self, msg: Message) -> BoxFut<'_, ()>;
+    fn recv(&self) -> BoxFut<'_, Option<Message>>;
+    fn close(&self) -> BoxFut<'_, ()>;
+    fn is_connected(&self) -> bool;
+    fn metadata(&self) -> TransportMetadata;
+    fn connection_epoch(&self) -> u64;
+}
+
+impl<T> DynTransport for T
+where
+    T: Transport,
+    T::Error: Into<TransportError>,
+{
+    fn send(&self, msg: Message) -> BoxFut<'_, ()> {
+        Box::pin(async move { Transport::send(self, msg).await.map_err(Into::into) })
+    }
+
+    fn recv(&self) -> BoxFut<'_, Option<Message>> {
+        Box::pin(async move { Transport::recv(self).await.map_err(Into::into) })
+    }
+
+    fn close(&self) -> BoxFut<'_, ()> {
+        Box::pin(async move { Transport::close(self).await.map_err(Into::into) })
+    }
+
+    fn is_connected(&self) -> bool {
+        Transport::is_connected(self)
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        Transport::metadata(self)
+    }
+
+    fn connection_epoch(&self) -> u64 {
+        Transport::connection_epoch(self)
+    }
+}
+
+/// A transport of any type, with errors converted to [`TransportError`].
+pub struct BoxedTransport {
+    inner: Box<dyn DynTransport>,
+}
+
+impl BoxedTransport {
+    /// Box a transport.
+    pub fn new<T>(transport: T) -> Self
+    where
+        T: Transport + 'static,
+        T::Error: Into<TransportError>,
+    {
+        Self {
+            inner: Box::new(transport),
+        }
+    }
+}
+
+impl fmt::Debug for BoxedTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxedTransport")
+            .field("transport_type", &self.inner.metadata().transport_type)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Transport for BoxedTransport {
+    type Error = TransportError;
+
+    fn send(&self, msg: Message) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        self.inner.send(msg)
+    }
+
+    fn recv(&self) -> impl Future<Output = Result<Option<Message>, Self::Error>> + Send {
+        self.inner.recv()
+    }
+
+    fn close(&self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        self.inner.close()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        self.inner.metadata()
+    }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
+}
+
+/// Object-safe form of [`TransportListener`] producing [`BoxedTransport`]s.
+trait DynListener: Send + Sync {
+    fn accept(&self) -> BoxFut<'_, BoxedTransport>;
+    fn local_addr(&self) -> Option<String>;
+}
+
+impl<L> DynListener for L
+where
+    L: TransportListener,
+    L::Transport: 'static,
+    <L::Transport as Transport>::Error: Into<TransportError>,
+    L::Error: Into<TransportError>,
+{
+    fn accept(&self) -> BoxFut<'_, BoxedTransport> {
+        Box::pin(async move {
+            TransportListener::accept(self)
+                .await
+                .map(BoxedTransport::new)
+                .map_err(Into::into)
+        })
+    }
+
+    fn local_addr(&self) -> Option<String> {
+        TransportListener::local_addr(self)
+    }
+}
+
+/// A listener of any type, producing [`BoxedTransport`]s.
+pub struct BoxedListener {
+    inner: Box<dyn DynListener>,
+}
+
+impl BoxedListener {
+    /// Box a listener.
+    pub fn new<L>(listener: L) -> Self
+    where
+        L: TransportListener + 'static,
+        L::Transport: 'static,
+        <L::Transport as Transport>::Error: Into<TransportError>,
+        L::Error: Into<TransportError>,
+    {
+        Self {
+            inner: Box::new(listener),
+        }
+    }
+}
+
+impl fmt::Debug for BoxedListener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxedListener")
+            .field("local_addr", &self.inner.local_addr())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TransportListener for BoxedListener {
+    type Transport = BoxedTransport;
+    type Error = TransportError;
+
+    fn accept(&self) -> impl Future<Output = Result<Self::Transport, Self::Error>> + Send {
+        self.inner.accept()
+    }
+
+    fn local_addr(&self) -> Option<String> {
+        self.inner.local_addr()
+    }
+}
+
+/// Stdio over this process's stdin and stdout.
+type ProcessStdio = crate::stdio::StdioTransport<
+    crate::runtime::TokioAsyncReadWrapper<tokio::io::Stdin>,
+    crate::runtime::TokioAsyncWriteWrapper<tokio::io::Stdout>,
+>;
+
+/// Accepts a single connection over this process's stdin and stdout.
+#[derive(Debug, Default)]
+struct StdioListener {
+    accepted: AtomicBool,
+}
+
+impl TransportListener for StdioListener {
+    type Transport = ProcessStdio;
+    type Error = TransportError;
+
+    async fn accept(&self) -> Result<ProcessStdio, TransportError> {
+        if self.accepted.swap(true, Ordering::AcqRel) {
+            return Err(TransportError::Connection {
+                message: "stdio accepts a single connection".to_string(),
+            });
+        }
+        Ok(ProcessStdio::new())
+    }
+
+    fn local_addr(&self) -> Option<String> {
+        Some("stdio:".to_string())
+    }
+}
+
+/// A started gRPC server, stopped when dropped.
+#[cfg(feature = "grpc")]
+struct StartedGrpcServer(std::sync::Arc<crate::grpc::GrpcServer>);
+
+#[cfg(feature = "grpc")]
+impl TransportListener for StartedGrpcServer {
+    type Transport = crate::grpc::GrpcTransport;
+    type Error = crate::grpc::GrpcError;
+
+    fn accept(&self) -> impl Future<Output = Result<Self::Transport, Self::Error>> + Send {
+        self.0.accept()
+    }
+
+    fn local_addr(&self) -> Option<String> {
+        self.0.local_addr().map(|addr| addr.to_string())
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl Drop for StartedGrpcServer {
+    fn drop(&mut self) {
+        self.0.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransportUrl, connect, listen};
+    use crate::traits::{Transport, TransportListener};
+    use mcpkit_core::protocol::Message;
+
+    #[test]
+    fn urls_parse_by_scheme() {
+        let parse = |url| TransportUrl::parse(url).unwrap();
+        assert_eq!(parse("stdio:"), TransportUrl::Stdio);
+        assert_eq!(
+            parse("stdio:./server --flag 'two words'"),
+            TransportUrl::Spawn {
+                program: "./server".into(),
+                args: vec!["--flag".into(), "two words".into()],
+            }
+        );
+        assert_eq!(
+            parse("https://example.com/mcp"),
+            TransportUrl::Http("https://example.com/mcp".into())
+        );
+        assert_eq!(
+            parse("ws://localhost:8080"),
+            TransportUrl::WebSocket("ws://localhost:8080".into())
+        );
+        assert_eq!(
+            parse("unix:/tmp/mcp.sock"),
+            TransportUrl::Unix("/tmp/mcp.sock".into())
+        );
+        assert_eq!(parse("unix:///tmp/mcp.sock"), parse("unix:/tmp/mcp.sock"));
+        assert_eq!(parse("npipe:mcp"), TransportUrl::NamedPipe("mcp".into()));
+        assert_eq!(
+            parse("grpcs://localhost:50051/"),
+            TransportUrl::Grpc {
+                addr: "localhost:50051".into(),
+                tls: true,
+            }
+        );
+
+        for bad in [
+            "localhost:80",
+            "ftp://x",
+            "unix:",
+            "stdio:run 'oops",
+            "plain",
+        ] {
+            assert!(TransportUrl::parse(bad).is_err(), "{bad} parsed");
+        }
+    }
+
+    #[test]
+    fn urls_display_as_they_parse() {
+        for url in [
+            "stdio:",
+            "stdio:node \"my server.js\" --port 1",
+            "unix:/tmp/mcp.sock",
+            "npipe:mcp",
+            "grpc://localhost:50051",
+        ] {
+            let parsed = TransportUrl::parse(url).unwrap();
+            assert_eq!(parsed.to_string(), url);
+            assert_eq!(TransportUrl::parse(&parsed.to_string()).unwrap(), parsed);
+        }
+    }
+
+    #[tokio::test]
+    async fn listening_is_refused_for_client_only_transports() {
+        for url in ["stdio:./server", "http://localhost/mcp", "ws://localhost"] {
+            let err = listen(url).await.unwrap_err();
+            assert!(err.to_string().contains("Cannot use"), "{url}: {err}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn stdio_urls_spawn_the_command() {
+        use mcpkit_core::protocol::Notification;
+
+        // `cat` echoes every message back.
+        let transport = connect("stdio:cat").await.unwrap();
+        assert_eq!(transport.metadata().transport_type, "spawned-stdio");
+        transport
+            .send(Message::Notification(Notification::new("echo")))
+            .await
+            .unwrap();
+        let Some(Message::Notification(n)) = transport.recv().await.unwrap() else {
+            panic!("expected the notification back");
+        };
+        assert_eq!(n.method.as_ref(), "echo");
+        transport.close().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_urls_connect_to_listeners() {
+        use mcpkit_core::protocol::Notification;
+
+        let path = std::env::temp_dir().join(format!("mcpkit-factory-{}.sock", std::process::id()));
+        let url = format!("unix:{}", path.display());
+        let listener = listen(&url).await.unwrap();
+        let (client, server) = tokio::join!(connect(&url), listener.accept());
+        let (client, server) = (client.unwrap(), server.unwrap());
+
+        client
+            .send(Message::Notification(Notification::new("hello")))
+            .await
+            .unwrap();
+        let Some(Message::Notification(n)) = server.recv().await.unwrap() else {
+            panic!("expected a notification");
+        };
+        assert_eq!(n.method.as_ref(), "hello");
+        assert_eq!(client.metadata().transport_type, "unix");
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,635 @@
+//! Message framing with a hard size cap.
+//!
+//! Two wire formats are supported (see [`Framing`]): newline-delimited JSON,
+//! and LSP-style `Content-Length` header framing.
+//!
+//! [`LineDecoder`] splits an incoming byte stream into newline-delimited
+//! frames incrementally: bytes are scanned once as they arrive, and a line
+//! that grows past the configured maximum is discarded on the fly rather than
+//! buffered. The decoder then yields [`Frame::TooLarge`] for it, so the
+//! transport can answer with a parse error and carry on with the next line.
+//! Memory use is bounded by the cap plus one read chunk, however large the
+//! peer's line.
+//!
+//! [`FrameDecoder`] accepts both formats, detecting each message's framing
+//! from its first bytes, so one reader serves newline-delimited and
+//! header-framed peers alike. Header-framed bodies are capped the same way.
+//!
+//! The decoders are runtime-independent; the caller owns the read buffer and
+//! feeds it whatever the underlying reader produced.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_transport::framing::{Frame, LineDecoder};
+//! use bytes::BytesMut;
+//!
+//! let mut decoder = LineDecoder::new(16);
+//! let mut buf = BytesMut::from(&b"{\"a\":1}\n{\"too\":\"long...........\"}\n"[..]);
+//!
+//! assert_eq!(decoder.decode(&mut buf), Some(Frame::Line(b"{\"a\":1}"[..].into())));
+//! assert!(matches!(decoder.decode(&mut buf), Some(Frame::TooLarge { .. })));
+//! assert_eq!(decoder.decode(&mut buf), None);
+//! ```
+
+use bytes::{Buf, Bytes, BytesMut};
+
+/// Maximum size of a `Content-Length` header block, in bytes.
+const MAX_HEADER_SIZE: usize = 8 * 1024;
+
+/// The header that starts a header-framed message (matched case-insensitively).
+const CONTENT_LENGTH: &[u8] = b"content-length";
+
+/// How messages are delimited on a byte stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Framing {
+    /// One JSON message per line, terminated by `\n`.
+    #[default]
+    Newline,
+    /// LSP-style framing: a `Content-Length: <bytes>` header, a blank line,
+    /// then exactly that many bytes of JSON.
+    ContentLength,
+}
+
+impl Framing {
+    /// Append `payload` to `dst` framed in this format.
+    ///
+    /// ```rust
+    /// use mcpkit_transport::framing::Framing;
+    ///
+    /// let mut out = Vec::new();
+    /// Framing::ContentLength.encode(b"{}", &mut out);
+    /// assert_eq!(out, b"Content-Length: 2\r\n\r\n{}");
+    /// ```
+    pub fn encode(self, payload: &[u8], dst: &mut Vec<u8>) {
+        match self {
+            Self::Newline => {
+                dst.reserve(payload.len() + 1);
+                dst.extend_from_slice(payload);
+                dst.push(b'\n');
+            }
+            Self::ContentLength => {
+                let header = format!("Content-Length: {}\r\n\r\n", payload.len());
+                dst.reserve(header.len() + payload.len());
+                dst.extend_from_slice(header.as_bytes());
+                dst.extend_from_slice(payload);
+            }
+        }
+    }
+}
+
+/// A frame produced by [`LineDecoder`] or [`FrameDecoder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    /// A complete message: a line with surrounding whitespace (including the
+    /// newline) removed, or a header-framed body. Never empty.
+    Line(Bytes),
+    /// A message exceeded the maximum frame size and was discarded.
+    TooLarge {
+        /// Number of bytes discarded, excluding the terminating newline or
+        /// the headers.
+        size: usize,
+    },
+    /// A `Content-Length` header block could not be used; it was discarded.
+    Malformed(String),
+}
+
+/// Incremental decoder for newline-delimited frames with a hard size cap.
+#[derive(Debug, Clone)]
+pub struct LineDecoder {
+    max_frame_size: usize,
+    /// Bytes of the caller's buffer already scanned for a newline.
+    scanned: usize,
+    /// Bytes dropped so far from an oversized line, while discarding.
+    discarding: Option<usize>,
+}
+
+impl LineDecoder {
+    /// Create a decoder that rejects lines longer than `max_frame_size` bytes.
+    #[must_use]
+    pub const fn new(max_frame_size: usize) -> Self {
+        Self {
+            max_frame_size,
+            scanned: 0,
+            discarding: None,
+        }
+    }
+
+    /// The maximum accepted line length, in bytes.
+    #[must_use]
+    pub const fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Decode the next frame from `src`, consuming the bytes it covers.
+    ///
+    /// Returns `None` when `src` holds no complete line yet; read more data
+    /// into `src` and call again. Blank lines are skipped. While an oversized
+    /// line is being discarded, `src` is drained as it fills, so it never
+    /// holds more than `max_frame_size` bytes between calls.
+    pub fn decode(&mut self, src: &mut BytesMut) -> Option<Frame> {
+        loop {
+            let Some(offset) = src[self.scanned..].iter().position(|&b| b == b'\n') else {
+                self.absorb_partial(src);
+                return None;
+            };
+            let line = src.split_to(self.scanned + offset + 1);
+            self.scanned = 0;
+
+            if let Some(dropped) = self.discarding.take() {
+                return Some(Frame::TooLarge {
+                    size: dropped + line.len() - 1,
+                });
+            }
+            if line.len() - 1 > self.max_frame_size {
+                return Some(Frame::TooLarge {
+                    size: line.len() - 1,
+                });
+            }
+            if let Some(frame) = trim(line) {
+                return Some(Frame::Line(frame));
+            }
+        }
+    }
+
+    /// Decode the final frame once the stream has ended.
+    ///
+    /// A trailing line without a newline is still delivered. Returns `None`
+    /// when nothing remains.
+    pub fn decode_eof(&mut self, src: &mut BytesMut) -> Option<Frame> {
+        if let Some(frame) = self.decode(src) {
+            return Some(frame);
+        }
+        self.scanned = 0;
+        let rest = src.split();
+        if let Some(dropped) = self.discarding.take() {
+            return Some(Frame::TooLarge {
+                size: dropped + rest.len(),
+            });
+        }
+        trim(rest).map(Frame::Line)
+    }
+
+    /// Account for a partial line: remember how far it was scanned, or drop
+    /// it once it can no longer fit.
+    fn absorb_partial(&mut self, src: &mut BytesMut) {
+        if let Some(dropped) = &mut self.discarding {
+            *dropped += src.len();
+            src.clear();
+            self.scanned = 0;
+        } else if src.len() > self.max_frame_size {
+            self.discarding = Some(src.len());
+            src.clear();
+            self.scanned = 0;
+        } else {
+            self.scanned = src.len();
+        }
+    }
+}
+
+/// Incremental decoder accepting both newline-delimited and `Content-Length`
+/// framed messages, with a hard size cap.
+///
+/// Each message's framing is detected from its first bytes: a message that
+/// starts with a `Content-Length` header (case-insensitive) is header-framed,
+/// anything else is a line. Other headers, such as `Content-Type`, are
+/// ignored. [`last_framing`](Self::last_framing) reports the framing of the
+/// latest message, so a writer can answer in kind.
+///
+/// ```rust
+/// use mcpkit_transport::framing::{Frame, FrameDecoder, Framing};
+/// use bytes::BytesMut;
+///
+/// let mut decoder = FrameDecoder::new(1024);
+/// let mut buf = BytesMut::from(&b"Content-Length: 7\r\n\r\n{\"a\":1}{\"b\":2}\n"[..]);
+///
+/// assert_eq!(decoder.decode(&mut buf), Some(Frame::Line(b"{\"a\":1}"[..].into())));
+/// assert_eq!(decoder.last_framing(), Some(Framing::ContentLength));
+/// assert_eq!(decoder.decode(&mut buf), Some(Frame::Line(b"{\"b\":2}"[..].into())));
+/// assert_eq!(decoder.last_framing(), Some(Framing::Newline));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameDecoder {
+    lines: LineDecoder,
+    state: DecodeState,
+    last: Option<Framing>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DecodeState {
+    /// Between messages.
+    Idle,
+    /// Inside a line handed to the line decoder.
+    Line,
+    /// Reading a header block, of which `scanned` bytes hold no terminator.
+    Headers { scanned: usize },
+    /// Waiting for a body of `length` bytes.
+    Body { length: usize },
+    /// Dropping an oversized body of `length` bytes as it arrives.
+    Discard { length: usize, remaining: usize },
+}
+
+impl FrameDecoder {
+    /// Create a decoder that rejects messages longer than `max_frame_size`
+    /// bytes.
+    #[must_use]
+    pub const fn new(max_frame_size: usize) -> Self {
+        Self {
+            lines: LineDecoder::new(max_frame_size),
+            state: DecodeState::Idle,
+            last: None,
+        }
+    }
+
+    /// The maximum accepted message length, in bytes.
+    #[must_use]
+    pub const fn max_frame_size(&self) -> usize {
+        self.lines.max_frame_size()
+    }
+
+    /// Framing of the most recently decoded message, if any.
+    #[must_use]
+    pub const fn last_framing(&self) -> Option<Framing> {
+        self.last
+    }
+
+    /// Decode the next frame from `src`, consuming the bytes it covers.
+    ///
+    /// Returns `None` when `src` holds no complete message yet; read more
+    /// data into `src` and call again. As with [`LineDecoder::decode`],
+    /// oversized messages are drained as they arrive.
+    pub fn decode(&mut self, src: &mut BytesMut) -> Option<Frame> {
+        loop {
+            match self.state {
+                DecodeState::Idle => {
+                    let Some(start) = src.iter().position(|b| !b.is_ascii_whitespace()) else {
+                        src.clear();
+                        return None;
+                    };
+                    src.advance(start);
+                    let n = src.len().min(CONTENT_LENGTH.len());
+                    if !src[..n].eq_ignore_ascii_case(&CONTENT_LENGTH[..n]) {
+                        self.state = DecodeState::Line;
+                    } else if n < CONTENT_LENGTH.len() {
+                        // Too short to tell yet.
+                        return None;
+                    } else {
+                        self.state = DecodeState::Headers { scanned: 0 };
+                    }
+                }
+                DecodeState::Line => {
+                    let frame = self.lines.decode(src)?;
+                    self.finish(Framing::Newline);
+                    return Some(frame);
+                }
+                DecodeState::Headers { scanned } => match header_end(src, scanned) {
+                    Ok((headers, total)) => {
+                        let block = src.split_to(total);
+                        match content_length(&block[..headers]) {
+                            Ok(length) if length > self.max_frame_size() => {
+                                self.state = DecodeState::Discard {
+                                    length,
+                                    remaining: length,
+                                };
+                            }
+                            Ok(length) => self.state = DecodeState::Body { length },
+                            Err(reason) => {
+                                self.finish(Framing::ContentLength);
+                                return Some(Frame::Malformed(reason));
+                            }
+                        }
+                    }
+                    Err(_) if src.len() > MAX_HEADER_SIZE => {
+                        src.clear();
+                        self.finish(Framing::ContentLength);
+                        return Some(Frame::Malformed(format!(
+                            "header block exceeds {MAX_HEADER_SIZE} bytes"
+                        )));
+                    }
+                    Err(scanned) => {
+                        self.state = DecodeState::Headers { scanned };
+                        return None;
+                    }
+                },
+                DecodeState::Body { length } => {
+                    if src.len() < length {
+                        src.reserve(length - src.len());
+                        return None;
+                    }
+                    let body = src.split_to(length).freeze();
+                    self.finish(Framing::ContentLength);
+                    return Some(Frame::Line(body));
+                }
+                DecodeState::Discard { length, remaining } => {
+                    let n = remaining.min(src.len());
+                    src.advance(n);
+                    if n < remaining {
+                        self.state = DecodeState::Discard {
+                            length,
+                            remaining: remaining - n,
+                        };
+                        return None;
+                    }
+                    self.finish(Framing::ContentLength);
+                    return Some(Frame::TooLarge { size: length });
+                }
+            }
+        }
+    }
+
+    /// Decode the final frame once the stream has ended.
+    ///
+    /// A trailing line without a newline is still delivered; a header-framed
+    /// message cut short is reported as [`Frame::Malformed`]. Returns `None`
+    /// when nothing remains.
+    pub fn decode_eof(&mut self, src: &mut BytesMut) -> Option<Frame> {
+        if let Some(frame) = self.decode(src) {
+            return Some(frame);
+        }
+        match std::mem::replace(&mut self.state, DecodeState::Idle) {
+            DecodeState::Idle | DecodeState::Line => {
+                let frame = self.lines.decode_eof(src)?;
+                self.last = Some(Framing::Newline);
+                Some(frame)
+            }
+            DecodeState::Headers { .. } | DecodeState::Body { .. } => {
+                src.clear();
+                self.last = Some(Framing::ContentLength);
+                Some(Frame::Malformed(
+                    "stream ended inside a Content-Length message".to_string(),
+                ))
+            }
+            DecodeState::Discard { length, .. } => {
+                src.clear();
+                self.last = Some(Framing::ContentLength);
+                Some(Frame::TooLarge { size: length })
+            }
+        }
+    }
+
+    fn finish(&mut self, framing: Framing) {
+        self.state = DecodeState::Idle;
+        self.last = Some(framing);
+    }
+}
+
+/// Find the blank line ending a header block, scanning from `from`.
+///
+/// Returns the length of the headers (up to the last header's newline) and
+/// of the whole block, or the offset to resume scanning from.
+fn header_end(src: &[u8], from: usize) -> Result<(usize, usize), usize> {
+    let mut i = from;
+    while let Some(offset) = src[i..].iter().position(|&b| b == b'\n') {
+        let newline = i + offset;
+        match src.get(newline + 1..) {
+            Some([b'\n', ..]) => return Ok((newline, newline + 2)),
+            Some([b'\r', b'\n', ..]) => return Ok((newline, newline + 3)),
+            Some([] | [b'\r']) => return Err(newline),
+            _ => i = newline + 1,
+        }
+    }
+    Err(src.len())
+}
+
+/// Parse the `Content-Length` value out of a header block.
+fn content_length(headers: &[u8]) -> Result<usize, String> {
+    let headers =
+        std::str::from_utf8(headers).map_err(|_| "header block is not valid UTF-8".to_string())?;
+    let mut length = None;
+    for line in headers.split('\n').map(|line| line.trim_end_matches('\r')) {
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(format!("malformed header line: {line:?}"));
+        };
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            let value = value.trim();
+            match value.parse::<usize>() {
+                Ok(0) | Err(_) => return Err(format!("invalid Content-Length: {value:?}")),
+                Ok(n) => length = Some(n),
+            }
+        }
+    }
+    length.ok_or_else(|| "missing Content-Length header".to_string())
+}
+
+/// Strip leading/trailing ASCII whitespace, returning `None` for a blank line.
+fn trim(mut line: BytesMut) -> Option<Bytes> {
+    let end = line.iter().rposition(|b| !b.is_ascii_whitespace())? + 1;
+    let start = line.iter().position(|b| !b.is_ascii_whitespace())?;
+    line.truncate(end);
+    Some(line.freeze().slice(start..))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    /// Feed `input` to a decoder in chunks of the given sizes, collecting frames
+    /// and checking the buffer stays bounded.
+    fn decode_chunked(input: &[u8], max: usize, chunks: &[usize]) -> Vec<Frame> {
+        let mut decoder = LineDecoder::new(max);
+        let mut buf = BytesMut::new();
+        let mut frames = Vec::new();
+        let mut rest = input;
+        let mut sizes = chunks.iter().copied().cycle();
+        while !rest.is_empty() {
+            let n = sizes.next().unwrap_or(1).clamp(1, rest.len());
+            buf.extend_from_slice(&rest[..n]);
+            rest = &rest[n..];
+            while let Some(frame) = decoder.decode(&mut buf) {
+                frames.push(frame);
+            }
+            assert!(buf.len() <= max, "buffer grew to {} (max {max})", buf.len());
+        }
+        frames.extend(decoder.decode_eof(&mut buf));
+        frames
+    }
+
+    /// The frames expected from splitting `input` on newlines in one pass.
+    fn reference(input: &[u8], max: usize) -> Vec<Frame> {
+        input
+            .split(|&b| b == b'\n')
+            .filter_map(|line| {
+                if line.len() > max {
+                    Some(Frame::TooLarge { size: line.len() })
+                } else {
+                    trim(BytesMut::from(line)).map(Frame::Line)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_lines_and_skips_blanks() {
+        let frames = decode_chunked(b"{\"a\":1}\r\n\n  \n{\"b\":2}\n", 64, &[3]);
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Line(Bytes::from_static(b"{\"a\":1}")),
+                Frame::Line(Bytes::from_static(b"{\"b\":2}")),
+            ]
+        );
+    }
+
+    #[test]
+    fn oversized_line_is_discarded_and_stream_continues() {
+        let mut input = vec![b'x'; 1000];
+        input.extend_from_slice(b"\n{\"ok\":true}\n");
+        let frames = decode_chunked(&input, 32, &[7, 64, 13]);
+        assert_eq!(
+            frames,
+            vec![
+                Frame::TooLarge { size: 1000 },
+                Frame::Line(Bytes::from_static(b"{\"ok\":true}")),
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_line_without_newline_is_delivered() {
+        let frames = decode_chunked(b"{\"a\":1}\n{\"b\":2}", 64, &[5]);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1], Frame::Line(Bytes::from_static(b"{\"b\":2}")));
+    }
+
+    #[test]
+    fn line_exactly_at_limit_is_accepted() {
+        let frames = decode_chunked(b"0123456789\n01234567890\n", 10, &[4]);
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Line(Bytes::from_static(b"0123456789")),
+                Frame::TooLarge { size: 11 },
+            ]
+        );
+    }
+
+    /// Feed `input` to a [`FrameDecoder`] in chunks of the given sizes.
+    fn decode_mixed(input: &[u8], max: usize, chunks: &[usize]) -> Vec<(Frame, Framing)> {
+        let mut decoder = FrameDecoder::new(max);
+        let mut buf = BytesMut::new();
+        let mut frames = Vec::new();
+        let mut rest = input;
+        let mut sizes = chunks.iter().copied().cycle();
+        while !rest.is_empty() {
+            let n = sizes.next().unwrap_or(1).clamp(1, rest.len());
+            buf.extend_from_slice(&rest[..n]);
+            rest = &rest[n..];
+            while let Some(frame) = decoder.decode(&mut buf) {
+                frames.push((frame, decoder.last_framing().unwrap()));
+            }
+            assert!(
+                buf.len() <= max.max(MAX_HEADER_SIZE),
+                "buffer grew to {}",
+                buf.len()
+            );
+        }
+        while let Some(frame) = decoder.decode_eof(&mut buf) {
+            frames.push((frame, decoder.last_framing().unwrap()));
+        }
+        frames
+    }
+
+    #[test]
+    fn frame_decoder_detects_framing_per_message() {
+        let input = b"content-length: 7\r\nContent-Type: application/json\r\n\r\n{\"a\":1}\
+            \n{\"b\":2}\nContent-Length: 7\n\n{\"c\":3}";
+        for chunk in [1, 3, 16, 200] {
+            assert_eq!(
+                decode_mixed(input, 64, &[chunk]),
+                vec![
+                    (
+                        Frame::Line(Bytes::from_static(b"{\"a\":1}")),
+                        Framing::ContentLength
+                    ),
+                    (
+                        Frame::Line(Bytes::from_static(b"{\"b\":2}")),
+                        Framing::Newline
+                    ),
+                    (
+                        Frame::Line(Bytes::from_static(b"{\"c\":3}")),
+                        Framing::ContentLength
+                    ),
+                ],
+                "chunk size {chunk}"
+            );
+        }
+    }
+
+    #[test]
+    fn oversized_body_is_discarded_and_stream_continues() {
+        let mut input = b"Content-Length: 1000\r\n\r\n".to_vec();
+        input.extend_from_slice(&[b'x'; 1000]);
+        input.extend_from_slice(b"Content-Length: 2\r\n\r\n{}");
+        let frames: Vec<_> = decode_mixed(&input, 32, &[7, 64, 13])
+            .into_iter()
+            .map(|(frame, _)| frame)
+            .collect();
+        assert_eq!(
+            frames,
+            vec![
+                Frame::TooLarge { size: 1000 },
+                Frame::Line(Bytes::from_static(b"{}")),
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_headers_are_malformed() {
+        for input in [
+            &b"Content-Length: abc\r\n\r\n{}\n"[..],
+            b"Content-Length: 0\r\n\r\n",
+            b"Content-Lengthy: 2\r\n\r\n{}",
+            b"Content-Length: 20\r\n\r\n{}",
+        ] {
+            let frames = decode_mixed(input, 64, &[5]);
+            assert!(
+                matches!(frames.first(), Some((Frame::Malformed(_), _))),
+                "{:?} gave {frames:?}",
+                String::from_utf8_lossy(input)
+            );
+        }
+    }
+
+    #[test]
+    fn framing_round_trips_through_decoder() {
+        let mut out = Vec::new();
+        Framing::ContentLength.encode(b"{\"x\":\"\\n\"}", &mut out);
+        Framing::Newline.encode(b"{}", &mut out);
+        let frames: Vec<_> = decode_mixed(&out, 64, &[4])
+            .into_iter()
+            .map(|(frame, _)| frame)
+            .collect();
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Line(Bytes::from_static(b"{\"x\":\"\\n\"}")),
+                Frame::Line(Bytes::from_static(b"{}")),
+            ]
+        );
+    }
+
+    /// Randomized differential test: any chunking of any input must yield the
+    /// same frames as splitting the whole input at once.
+    #[test]
+    fn fuzz_chunking_matches_reference() {
+        let mut rng = rand::thread_rng();
+        let alphabet = b"{}\"ab \r\n\n\t";
+        for _ in 0..500 {
+            let len = rng.gen_range(0..300);
+            let input: Vec<u8> = (0..len)
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect();
+            let max = rng.gen_range(1..40);
+            let chunks: Vec<usize> = (0..rng.gen_range(1..5))
+                .map(|_| rng.gen_range(1..50))
+                .collect();
+            assert_eq!(
+                decode_chunked(&input, max, &chunks),
+                reference(&input, max),
+                "input={:?} max={max} chunks={chunks:?}",
+                String::from_utf8_lossy(&input)
+            );
+        }
+    }
+}
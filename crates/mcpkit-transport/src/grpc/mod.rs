@@ -17,18 +17,12 @@
 //! - **TLS support**: Secure communication out of the box
 //! - **Load balancing**: Compatible with gRPC load balancers (Envoy, etc.)
 //!
-//! # Current Status
+//! # Server
 //!
-//! The gRPC transport is partially implemented:
-//!
-//! - **Client transport**: Fully functional - can connect to gRPC servers
-//! - **Server transport**: TCP listener and service infrastructure are in place,
-//!   but full bidirectional streaming integration with tonic requires additional
-//!   work (protobuf codegen or manual service implementation)
-//!
-//! For production use, consider:
-//! - Using the HTTP or WebSocket transports which are fully implemented
-//! - Contributing the remaining gRPC server streaming implementation
+//! [`GrpcServer`] implements [`TransportListener`](crate::TransportListener):
+//! each call to the `Stream` RPC is accepted as its own [`GrpcTransport`].
+//! It supports TLS from PEM files, a cap on concurrent sessions, and
+//! graceful shutdown that ends open streams.
 //!
 //! # Example
 //!
@@ -44,6 +38,24 @@
 //! server.serve(transport).await?;
 //! ```
 //!
+//! Serving over gRPC:
+//!
+//! ```ignore
+//! use mcpkit_transport::grpc::{GrpcServer, GrpcServerConfig};
+//! use std::sync::Arc;
+//!
+//! let config = GrpcServerConfig::new("0.0.0.0:50051")
+//!     .with_tls_identity(cert_pem, key_pem)
+//!     .max_connections(256);
+//! let listener = Arc::new(GrpcServer::bind(config).await?);
+//! Arc::clone(&listener).start().await?;
+//!
+//! while let Ok(transport) = listener.accept().await {
+//!     let server = handler.clone().into_server();
+//!     tokio::spawn(async move { server.serve(transport).await });
+//! }
+//! ```
+//!
 //! # Protocol
 //!
 //! Messages are serialized as JSON and wrapped in a simple protobuf message:
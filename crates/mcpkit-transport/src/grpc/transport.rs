@@ -4,7 +4,7 @@
 //! bidirectional streaming. It leverages tonic for the gRPC implementation
 //! and uses generated protobuf code for message serialization.
 
-use crate::{Transport, TransportMetadata};
+use crate::{Transport, TransportListener, TransportMetadata};
 use async_lock::Mutex;
 use mcpkit_core::protocol::Message;
 use std::collections::HashMap;
@@ -13,10 +13,13 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use thiserror::Error;
-use tokio::sync::mpsc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, mpsc, watch};
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::ReceiverStream;
-use tonic::transport::{Channel, Endpoint, Server, Uri};
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::{
+    Certificate, Channel, ClientTlsConfig, Endpoint, Identity, Server, ServerTlsConfig, Uri,
+};
 use tonic::{Request, Response, Status, Streaming};
 use tracing::{debug, error, info, warn};
 
@@ -53,6 +56,18 @@ pub enum GrpcError {
     InvalidUri(String),
 }
 
+impl From<GrpcError> for crate::TransportError {
+    fn from(err: GrpcError) -> Self {
+        match err {
+            GrpcError::Closed => Self::ConnectionClosed,
+            GrpcError::Serialization(e) => Self::Json(e),
+            other => Self::Connection {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
 /// Configuration for gRPC transport.
 #[derive(Debug, Clone)]
 pub struct GrpcConfig {
@@ -64,6 +79,10 @@ pub struct GrpcConfig {
     pub timeout: Duration,
     /// Enable TLS.
     pub tls: bool,
+    /// PEM-encoded CA certificate to verify the server with.
+    pub tls_ca_certificate: Option<Vec<u8>>,
+    /// Server name to verify instead of the endpoint's host.
+    pub tls_domain: Option<String>,
     /// Custom metadata to include in requests.
     pub metadata: HashMap<String, String>,
 }
@@ -77,6 +96,8 @@ impl GrpcConfig {
             connect_timeout: Duration::from_secs(10),
             timeout: Duration::from_secs(30),
             tls: false,
+            tls_ca_certificate: None,
+            tls_domain: None,
             metadata: HashMap::new(),
         }
     }
@@ -102,6 +123,24 @@ impl GrpcConfig {
         self
     }
 
+    /// Enable TLS and verify the server against a PEM-encoded CA certificate.
+    #[must_use]
+    pub fn with_tls_ca_certificate(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.tls = true;
+        self.tls_ca_certificate = Some(pem.into());
+        self
+    }
+
+    /// Verify `domain` instead of the endpoint's host.
+    ///
+    /// Useful when connecting by IP address to a server whose certificate
+    /// names a host.
+    #[must_use]
+    pub fn with_tls_domain(mut self, domain: impl Into<String>) -> Self {
+        self.tls_domain = Some(domain.into());
+        self
+    }
+
     /// Add custom metadata.
     #[must_use]
     pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
@@ -133,6 +172,8 @@ pub struct GrpcTransport {
     metadata: TransportMetadata,
     /// Send channel for outgoing gRPC messages (for server-side transports).
     outgoing_grpc_tx: Option<mpsc::Sender<Result<proto::McpMessage, Status>>>,
+    /// Set on `close()` to end the underlying gRPC stream.
+    closing: watch::Sender<bool>,
 }
 
 impl GrpcTransport {
@@ -155,7 +196,14 @@ impl GrpcTransport {
             .timeout(config.timeout);
 
         let endpoint = if config.tls {
-            endpoint.tls_config(tonic::transport::ClientTlsConfig::new())?
+            let mut tls = ClientTlsConfig::new();
+            if let Some(ca) = &config.tls_ca_certificate {
+                tls = tls.ca_certificate(Certificate::from_pem(ca));
+            }
+            if let Some(domain) = &config.tls_domain {
+                tls = tls.domain_name(domain.clone());
+            }
+            endpoint.tls_config(tls)?
         } else {
             endpoint
         };
@@ -183,7 +231,7 @@ impl GrpcTransport {
         tokio::spawn(async move {
             while let Some(result) = inbound.next().await {
                 match result {
-                    Ok(msg) => match proto_to_message(&msg) {
+                    Ok(msg) => match proto_to_message(msg) {
                         Ok(message) => {
                             if incoming_tx.send(message).await.is_err() {
                                 debug!("Client incoming channel closed");
@@ -204,11 +252,20 @@ impl GrpcTransport {
             debug!("Client incoming stream closed");
         });
 
-        // Create a wrapper sender that converts Message to proto::McpMessage
+        // Create a wrapper sender that converts Message to proto::McpMessage.
+        // Dropping `outgoing_tx` on close ends the request stream.
         let (msg_tx, mut msg_rx) = mpsc::channel::<Message>(100);
+        let (closing, mut closing_rx) = watch::channel(false);
         let outgoing_tx_clone = outgoing_tx;
         tokio::spawn(async move {
-            while let Some(msg) = msg_rx.recv().await {
+            loop {
+                let msg = tokio::select! {
+                    msg = msg_rx.recv() => match msg {
+                        Some(msg) => msg,
+                        None => break,
+                    },
+                    () = signalled(&mut closing_rx) => break,
+                };
                 match message_to_proto(&msg) {
                     Ok(proto_msg) => {
                         if outgoing_tx_clone.send(proto_msg).await.is_err() {
@@ -236,6 +293,7 @@ impl GrpcTransport {
             connected: AtomicBool::new(true),
             metadata,
             outgoing_grpc_tx: None,
+            closing,
         })
     }
 
@@ -260,6 +318,7 @@ impl GrpcTransport {
             connected: AtomicBool::new(true),
             metadata,
             outgoing_grpc_tx: None,
+            closing: watch::Sender::new(false),
         }
     }
 
@@ -317,6 +376,7 @@ impl Transport for GrpcTransport {
     async fn close(&self) -> Result<(), Self::Error> {
         info!("Closing gRPC transport");
         self.connected.store(false, Ordering::SeqCst);
+        self.closing.send_replace(true);
         Ok(())
     }
 
@@ -330,20 +390,42 @@ impl Transport for GrpcTransport {
 }
 
 /// Configuration for gRPC server.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct GrpcServerConfig {
     /// Bind address (e.g., "0.0.0.0:50051").
     pub addr: String,
-    /// Enable TLS.
+    /// Enable TLS. Requires [`tls_identity`](Self::tls_identity).
     pub tls: bool,
-    /// Maximum concurrent streams per connection.
+    /// Certificate chain and private key presented to clients.
+    pub tls_identity: Option<Identity>,
+    /// Maximum concurrent HTTP/2 streams per connection.
     pub max_concurrent_streams: Option<u32>,
+    /// Maximum concurrent MCP sessions across all connections.
+    ///
+    /// Each session is one bidirectional stream. Streams opened past the
+    /// limit are rejected with `RESOURCE_EXHAUSTED`.
+    pub max_connections: Option<usize>,
     /// TCP keepalive interval.
     pub tcp_keepalive: Option<Duration>,
     /// HTTP/2 keepalive interval.
     pub http2_keepalive_interval: Option<Duration>,
 }
 
+impl std::fmt::Debug for GrpcServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The identity holds the private key, so only report its presence.
+        f.debug_struct("GrpcServerConfig")
+            .field("addr", &self.addr)
+            .field("tls", &self.tls)
+            .field("tls_identity", &self.tls_identity.as_ref().map(|_| ".."))
+            .field("max_concurrent_streams", &self.max_concurrent_streams)
+            .field("max_connections", &self.max_connections)
+            .field("tcp_keepalive", &self.tcp_keepalive)
+            .field("http2_keepalive_interval", &self.http2_keepalive_interval)
+            .finish()
+    }
+}
+
 impl GrpcServerConfig {
     /// Create a new server configuration with the given bind address.
     #[must_use]
@@ -351,19 +433,37 @@ impl GrpcServerConfig {
         Self {
             addr: addr.into(),
             tls: false,
+            tls_identity: None,
             max_concurrent_streams: Some(200),
+            max_connections: None,
             tcp_keepalive: Some(Duration::from_secs(60)),
             http2_keepalive_interval: Some(Duration::from_secs(30)),
         }
     }
 
     /// Enable TLS.
+    ///
+    /// An identity must also be set with
+    /// [`with_tls_identity`](Self::with_tls_identity), or the server fails
+    /// to start.
     #[must_use]
     pub const fn with_tls(mut self) -> Self {
         self.tls = true;
         self
     }
 
+    /// Enable TLS with a PEM-encoded certificate chain and private key.
+    #[must_use]
+    pub fn with_tls_identity(
+        mut self,
+        cert_pem: impl AsRef<[u8]>,
+        key_pem: impl AsRef<[u8]>,
+    ) -> Self {
+        self.tls = true;
+        self.tls_identity = Some(Identity::from_pem(cert_pem, key_pem));
+        self
+    }
+
     /// Set maximum concurrent streams per connection.
     #[must_use]
     pub const fn max_concurrent_streams(mut self, max: u32) -> Self {
@@ -371,6 +471,13 @@ impl GrpcServerConfig {
         self
     }
 
+    /// Set maximum concurrent MCP sessions across all connections.
+    #[must_use]
+    pub const fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
     /// Set TCP keepalive interval.
     #[must_use]
     pub const fn tcp_keepalive(mut self, interval: Duration) -> Self {
@@ -398,6 +505,11 @@ impl Default for GrpcServerConfig {
 /// for each connection. Messages are exchanged as JSON-serialized MCP
 /// protocol messages over bidirectional streaming.
 ///
+/// Every call to the `Stream` RPC becomes one [`GrpcTransport`], so a single
+/// HTTP/2 connection can carry several MCP sessions. The server implements
+/// [`TransportListener`] and can be driven the same way as the WebSocket and
+/// Unix socket listeners.
+///
 /// # Protocol
 ///
 /// The server uses a simple message envelope:
@@ -406,16 +518,23 @@ impl Default for GrpcServerConfig {
 /// - The stream is bidirectional, allowing both request/response and
 ///   server-initiated notifications
 ///
+/// # Shutdown
+///
+/// [`stop`](Self::stop) stops accepting connections, ends every open
+/// stream, and makes pending and future [`accept`](Self::accept) calls
+/// fail with [`GrpcError::Closed`]. A stopped server can't be restarted.
+///
 /// # Example
 ///
 /// ```ignore
 /// use mcpkit_transport::grpc::{GrpcServer, GrpcServerConfig};
+/// use std::sync::Arc;
 ///
 /// let config = GrpcServerConfig::new("0.0.0.0:50051");
-/// let server = GrpcServer::new(config);
+/// let server = Arc::new(GrpcServer::bind(config).await?);
+/// Arc::clone(&server).start().await?;
 ///
-/// // The server provides a way to get incoming transports
-/// while let Some(transport) = server.accept().await? {
+/// while let Ok(transport) = server.accept().await {
 ///     tokio::spawn(async move {
 ///         // Handle the MCP connection
 ///         handle_connection(transport).await;
@@ -427,10 +546,15 @@ pub struct GrpcServer {
     /// Channel for receiving new connections (as transports).
     connection_rx: Mutex<mpsc::Receiver<GrpcTransport>>,
     /// Channel for sending new connections (used by the server task).
-    #[allow(dead_code)]
     connection_tx: mpsc::Sender<GrpcTransport>,
     /// Whether the server is running.
     running: AtomicBool,
+    /// Set once the server is stopped.
+    shutdown: watch::Sender<bool>,
+    /// Socket bound ahead of `start()`.
+    bound: std::sync::Mutex<Option<tokio::net::TcpListener>>,
+    /// The bound address, once bound.
+    local_addr: std::sync::Mutex<Option<SocketAddr>>,
 }
 
 impl GrpcServer {
@@ -447,9 +571,31 @@ impl GrpcServer {
             connection_rx: Mutex::new(connection_rx),
             connection_tx,
             running: AtomicBool::new(false),
+            shutdown: watch::Sender::new(false),
+            bound: std::sync::Mutex::new(None),
+            local_addr: std::sync::Mutex::new(None),
         }
     }
 
+    /// Create a server and bind its socket immediately.
+    ///
+    /// [`local_addr`](Self::local_addr) is available before
+    /// [`start`](Self::start) is called, which is useful when binding to
+    /// port 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address is invalid or can't be bound.
+    pub async fn bind(config: GrpcServerConfig) -> Result<Self, GrpcError> {
+        let server = Self::new(config);
+        let socket = server.bind_socket().await?;
+        *server
+            .bound
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(socket);
+        Ok(server)
+    }
+
     /// Get the server configuration.
     #[must_use]
     pub fn config(&self) -> &GrpcServerConfig {
@@ -468,24 +614,69 @@ impl GrpcServer {
         &self.config.addr
     }
 
-    /// Accept an incoming connection.
-    ///
-    /// Returns `None` if the server has been stopped or an error occurred.
+    /// The address the server is bound to, once bound.
+    #[must_use]
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        *self
+            .local_addr
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Accept the next MCP session.
     ///
-    /// # Note
+    /// # Errors
     ///
-    /// This is a placeholder implementation. A full implementation would
-    /// use `tonic::Server` to accept connections and convert them to
-    /// `GrpcTransport` instances.
-    pub async fn accept(&self) -> Option<GrpcTransport> {
+    /// Returns [`GrpcError::Closed`] once the server has been stopped.
+    pub async fn accept(&self) -> Result<GrpcTransport, GrpcError> {
+        let mut shutdown = self.shutdown.subscribe();
         let mut rx = self.connection_rx.lock().await;
-        rx.recv().await
+        tokio::select! {
+            biased;
+            () = signalled(&mut shutdown) => Err(GrpcError::Closed),
+            transport = rx.recv() => transport.ok_or(GrpcError::Closed),
+        }
     }
 
     /// Stop the server.
+    ///
+    /// Open streams are ended, so the transports handed out by
+    /// [`accept`](Self::accept) observe end-of-stream.
     pub fn stop(&self) {
         info!("Stopping gRPC server");
         self.running.store(false, Ordering::SeqCst);
+        self.shutdown.send_replace(true);
+    }
+
+    async fn bind_socket(&self) -> Result<tokio::net::TcpListener, GrpcError> {
+        let addr: SocketAddr = self
+            .config
+            .addr
+            .parse()
+            .map_err(|e| GrpcError::InvalidUri(format!("Invalid bind address: {e}")))?;
+        let socket = tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(|e| GrpcError::Channel(format!("Failed to bind gRPC server: {e}")))?;
+        if let Ok(local) = socket.local_addr() {
+            *self
+                .local_addr
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(local);
+        }
+        Ok(socket)
+    }
+}
+
+impl TransportListener for GrpcServer {
+    type Transport = GrpcTransport;
+    type Error = GrpcError;
+
+    async fn accept(&self) -> Result<Self::Transport, Self::Error> {
+        Self::accept(self).await
+    }
+
+    fn local_addr(&self) -> Option<String> {
+        Self::local_addr(self).map(|addr| addr.to_string())
     }
 }
 
@@ -510,6 +701,17 @@ impl GrpcServerBuilder {
         self
     }
 
+    /// Enable TLS with a PEM-encoded certificate chain and private key.
+    #[must_use]
+    pub fn with_tls_identity(
+        mut self,
+        cert_pem: impl AsRef<[u8]>,
+        key_pem: impl AsRef<[u8]>,
+    ) -> Self {
+        self.config = self.config.with_tls_identity(cert_pem, key_pem);
+        self
+    }
+
     /// Set maximum concurrent streams per connection.
     #[must_use]
     pub fn max_concurrent_streams(mut self, max: u32) -> Self {
@@ -517,6 +719,13 @@ impl GrpcServerBuilder {
         self
     }
 
+    /// Set maximum concurrent MCP sessions across all connections.
+    #[must_use]
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.config = self.config.max_connections(max);
+        self
+    }
+
     /// Set TCP keepalive interval.
     #[must_use]
     pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
@@ -560,8 +769,13 @@ fn message_to_proto(msg: &Message) -> Result<proto::McpMessage, serde_json::Erro
 }
 
 /// Convert a gRPC proto message to an MCP protocol message.
-fn proto_to_message(msg: &proto::McpMessage) -> Result<Message, serde_json::Error> {
-    serde_json::from_str(&msg.payload)
+fn proto_to_message(msg: proto::McpMessage) -> Result<Message, crate::TransportError> {
+    crate::codec::decode(bytes::Bytes::from(msg.payload))
+}
+
+/// Resolve once the flag is set or its sender is gone.
+async fn signalled(rx: &mut watch::Receiver<bool>) {
+    let _ = rx.wait_for(|set| *set).await;
 }
 
 /// Internal service implementation that bridges gRPC streams to MCP transports.
@@ -573,6 +787,10 @@ fn proto_to_message(msg: &proto::McpMessage) -> Result<Message, serde_json::Erro
 struct McpServiceImpl {
     /// Channel for sending new transports when connections are established.
     connection_tx: mpsc::Sender<GrpcTransport>,
+    /// Server-wide shutdown flag.
+    shutdown: watch::Receiver<bool>,
+    /// Session slots when `max_connections` is set.
+    sessions: Option<Arc<Semaphore>>,
 }
 
 #[tonic::async_trait]
@@ -587,15 +805,28 @@ impl proto::mcp_service_server::McpService for McpServiceImpl {
             .remote_addr()
             .map_or_else(|| "unknown".to_string(), |a| a.to_string());
 
+        if *self.shutdown.borrow() {
+            return Err(Status::unavailable("Server is shutting down"));
+        }
+        let permit = match &self.sessions {
+            Some(sessions) => Some(Arc::clone(sessions).try_acquire_owned().map_err(|_| {
+                warn!(remote = %remote_addr, "Rejecting gRPC MCP stream: session limit reached");
+                Status::resource_exhausted("Too many concurrent MCP sessions")
+            })?),
+            None => None,
+        };
+
         info!(remote = %remote_addr, "New gRPC MCP connection");
 
         // Create channels for bidirectional communication
         let (outgoing_tx, outgoing_rx) = mpsc::channel::<Result<proto::McpMessage, Status>>(100);
         let (incoming_tx, incoming_rx) = mpsc::channel::<Message>(100);
+        let (response_tx, response_rx) = mpsc::channel::<Result<proto::McpMessage, Status>>(100);
+        let (closing, closing_rx) = watch::channel(false);
 
         // Create a transport for this connection
         let transport =
-            GrpcTransportInner::new(incoming_rx, outgoing_tx.clone(), remote_addr.clone());
+            GrpcTransportInner::new(incoming_rx, outgoing_tx, closing, remote_addr.clone());
 
         // Send the transport to the server's accept queue
         if let Err(e) = self.connection_tx.send(transport.into_transport()).await {
@@ -603,34 +834,96 @@ impl proto::mcp_service_server::McpService for McpServiceImpl {
             return Err(Status::internal("Failed to accept connection"));
         }
 
-        // Spawn a task to handle incoming messages from the client
-        let mut inbound = request.into_inner();
-        let incoming_tx_clone = incoming_tx;
-        tokio::spawn(async move {
-            while let Some(result) = inbound.next().await {
-                match result {
-                    Ok(msg) => match proto_to_message(&msg) {
-                        Ok(message) => {
-                            if incoming_tx_clone.send(message).await.is_err() {
-                                debug!("Incoming channel closed");
-                                break;
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to parse incoming message: {e}");
+        // Both pump tasks share the session slot; it's released once both end.
+        let permit = permit.map(Arc::new);
+        spawn_inbound_pump(
+            request.into_inner(),
+            incoming_tx,
+            self.shutdown.clone(),
+            closing_rx.clone(),
+            permit.clone(),
+        );
+        spawn_outbound_pump(
+            outgoing_rx,
+            response_tx,
+            self.shutdown.clone(),
+            closing_rx,
+            permit,
+        );
+
+        Ok(Response::new(ReceiverStream::new(response_rx)))
+    }
+}
+
+/// Forward client messages to the transport until the stream ends, the
+/// transport is closed, or the server stops.
+fn spawn_inbound_pump(
+    mut inbound: Streaming<proto::McpMessage>,
+    incoming_tx: mpsc::Sender<Message>,
+    mut shutdown: watch::Receiver<bool>,
+    mut closing: watch::Receiver<bool>,
+    permit: Option<Arc<OwnedSemaphorePermit>>,
+) {
+    tokio::spawn(async move {
+        let _permit = permit;
+        loop {
+            let result = tokio::select! {
+                result = inbound.next() => match result {
+                    Some(result) => result,
+                    None => break,
+                },
+                () = signalled(&mut shutdown) => break,
+                () = signalled(&mut closing) => break,
+            };
+            match result {
+                Ok(msg) => match proto_to_message(msg) {
+                    Ok(message) => {
+                        if incoming_tx.send(message).await.is_err() {
+                            debug!("Incoming channel closed");
+                            break;
                         }
-                    },
+                    }
                     Err(e) => {
-                        warn!("gRPC stream error: {e}");
-                        break;
+                        warn!("Failed to parse incoming message: {e}");
                     }
+                },
+                Err(e) => {
+                    warn!("gRPC stream error: {e}");
+                    break;
                 }
             }
-            debug!("Incoming stream closed");
-        });
+        }
+        debug!("Incoming stream closed");
+    });
+}
 
-        Ok(Response::new(ReceiverStream::new(outgoing_rx)))
-    }
+/// Forward transport messages to the response stream. Returning drops
+/// `response_tx`, which ends the response stream for the client.
+fn spawn_outbound_pump(
+    mut outgoing_rx: mpsc::Receiver<Result<proto::McpMessage, Status>>,
+    response_tx: mpsc::Sender<Result<proto::McpMessage, Status>>,
+    mut shutdown: watch::Receiver<bool>,
+    mut closing: watch::Receiver<bool>,
+    permit: Option<Arc<OwnedSemaphorePermit>>,
+) {
+    tokio::spawn(async move {
+        let _permit = permit;
+        loop {
+            let msg = tokio::select! {
+                msg = outgoing_rx.recv() => match msg {
+                    Some(msg) => msg,
+                    None => break,
+                },
+                () = signalled(&mut shutdown) => break,
+                () = signalled(&mut closing) => break,
+            };
+            if response_tx.send(msg).await.is_err() {
+                debug!("Client response stream dropped");
+                break;
+            }
+        }
+        debug!("Outgoing stream closed");
+    });
 }
 
 /// Internal transport state for gRPC connections.
@@ -642,6 +935,8 @@ struct GrpcTransportInner {
     incoming_rx: Mutex<mpsc::Receiver<Message>>,
     /// Send channel for outgoing gRPC messages.
     outgoing_tx: mpsc::Sender<Result<proto::McpMessage, Status>>,
+    /// Ends the stream when the transport is closed.
+    closing: watch::Sender<bool>,
     /// Connection state.
     connected: AtomicBool,
     /// Transport metadata.
@@ -652,11 +947,13 @@ impl GrpcTransportInner {
     fn new(
         incoming_rx: mpsc::Receiver<Message>,
         outgoing_tx: mpsc::Sender<Result<proto::McpMessage, Status>>,
+        closing: watch::Sender<bool>,
         remote_addr: String,
     ) -> Self {
         Self {
             incoming_rx: Mutex::new(incoming_rx),
             outgoing_tx,
+            closing,
             connected: AtomicBool::new(true),
             metadata: TransportMetadata::new("grpc")
                 .remote_addr(remote_addr)
@@ -678,6 +975,7 @@ impl GrpcTransportInner {
             connected: self.connected,
             metadata: self.metadata,
             outgoing_grpc_tx: Some(self.outgoing_tx),
+            closing: self.closing,
         }
     }
 }
@@ -686,56 +984,77 @@ impl GrpcServer {
     /// Start the gRPC server and begin accepting connections.
     ///
     /// This spawns a background task that runs the tonic server. Incoming
-    /// connections can be retrieved using the `accept()` method.
+    /// connections can be retrieved using the `accept()` method. The socket
+    /// is bound here unless the server was created with [`bind`](Self::bind).
     ///
     /// # Errors
     ///
-    /// Returns an error if the server fails to bind to the configured address.
+    /// Returns an error if the server fails to bind to the configured
+    /// address, has already been stopped, or TLS is enabled without an
+    /// identity.
     pub async fn start(self: Arc<Self>) -> Result<(), GrpcError> {
-        let addr: SocketAddr = self
-            .config
-            .addr
-            .parse()
-            .map_err(|e| GrpcError::InvalidUri(format!("Invalid bind address: {e}")))?;
+        if *self.shutdown.borrow() {
+            return Err(GrpcError::Closed);
+        }
+
+        let mut builder = Server::builder()
+            .max_concurrent_streams(self.config.max_concurrent_streams)
+            .http2_keepalive_interval(self.config.http2_keepalive_interval);
+
+        if self.config.tls {
+            let identity = self.config.tls_identity.clone().ok_or_else(|| {
+                GrpcError::Channel("TLS is enabled but no identity is configured".to_string())
+            })?;
+            builder = builder.tls_config(ServerTlsConfig::new().identity(identity))?;
+        }
+
+        let prebound = self
+            .bound
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        let socket = match prebound {
+            Some(socket) => socket,
+            None => self.bind_socket().await?,
+        };
+        let incoming = TcpIncoming::from_listener(socket, true, self.config.tcp_keepalive)
+            .map_err(|e| GrpcError::Channel(format!("Failed to listen: {e}")))?;
 
-        info!(addr = %addr, "Starting gRPC MCP server");
+        info!(
+            addr = ?self.local_addr(),
+            tls = self.config.tls,
+            "Starting gRPC MCP server"
+        );
 
         self.running.store(true, Ordering::SeqCst);
 
         let service = McpServiceImpl {
             connection_tx: self.connection_tx.clone(),
+            shutdown: self.shutdown.subscribe(),
+            sessions: self
+                .config
+                .max_connections
+                .map(|max| Arc::new(Semaphore::new(max))),
         };
 
-        // Build the tonic server
-        let mut builder = Server::builder();
-
-        if let Some(max_streams) = self.config.max_concurrent_streams {
-            builder = builder.http2_max_pending_accept_reset_streams(Some(max_streams as usize));
-        }
-
         // Create the gRPC service using the generated server wrapper
         let svc = proto::mcp_service_server::McpServiceServer::new(service);
-
+        let mut shutdown = self.shutdown.subscribe();
         let server = Arc::clone(&self);
 
         // Spawn the server task
         tokio::spawn(async move {
             let result = builder
                 .add_service(svc)
-                .serve_with_shutdown(addr, async move {
-                    // Wait until running is set to false
-                    loop {
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                        if !server.running.load(Ordering::SeqCst) {
-                            break;
-                        }
-                    }
+                .serve_with_incoming_shutdown(incoming, async move {
+                    signalled(&mut shutdown).await;
                 })
                 .await;
 
             if let Err(e) = result {
                 error!("gRPC server error: {e}");
             }
+            server.running.store(false, Ordering::SeqCst);
             info!("gRPC server stopped");
         });
 
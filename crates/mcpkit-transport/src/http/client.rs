@@ -1,11 +1,14 @@
 //! HTTP transport client implementation.
 
+use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 use mcpkit_core::protocol::Message;
 
+use crate::compression::{CompressionSnapshot, CompressionStats};
 use crate::error::TransportError;
-use crate::runtime::AsyncMutex;
+use crate::limits::LimitEnforcer;
+use crate::runtime::{AsyncMutex, Notify};
 use crate::traits::{Transport, TransportMetadata};
 
 use super::config::{HttpTransportBuilder, HttpTransportConfig};
@@ -13,26 +16,55 @@ use super::sse::{HttpTransportState, process_sse_buffer};
 
 #[cfg(feature = "http")]
 use {
-    super::config::{MCP_PROTOCOL_VERSION_HEADER, MCP_SESSION_ID_HEADER},
+    super::config::{LAST_EVENT_ID_HEADER, MCP_PROTOCOL_VERSION_HEADER, MCP_SESSION_ID_HEADER},
+    crate::codec,
+    crate::compression::{self, ACCEPT_ENCODING, Decoder, Encoding},
     bytes::Bytes,
     futures::StreamExt,
     reqwest::{
         Client, Response, StatusCode,
-        header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue},
+        header::{self, ACCEPT, CONTENT_TYPE, HeaderMap, HeaderValue},
     },
 };
 
+/// State shared between the transport and its background SSE readers.
+struct Shared {
+    state: AsyncMutex<HttpTransportState>,
+    /// Signalled whenever a message is queued or the transport closes.
+    incoming: Notify,
+    connected: AtomicBool,
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    /// Bytes before and after compression, in both directions.
+    compression: CompressionStats,
+}
+
+impl Shared {
+    /// Wake every `recv` waiting for a message.
+    fn wake(&self) {
+        self.incoming.notify(usize::MAX);
+    }
+}
+
 /// HTTP transport with SSE streaming support.
 ///
 /// This transport implements the MCP Streamable HTTP transport specification.
 /// It sends messages via HTTP POST and receives responses either as direct
 /// JSON or via Server-Sent Events (SSE) streaming.
+///
+/// Once the client sends `notifications/initialized`, the transport also opens
+/// the standalone SSE stream (HTTP GET) so that server-initiated requests such
+/// as `sampling/createMessage` and `elicitation/create` are delivered through
+/// [`recv`](Transport::recv). Servers that do not offer the stream answer the
+/// GET with `405 Method Not Allowed`, which is tolerated. Disable this with
+/// [`HttpTransportConfig::without_sse_stream`].
 pub struct HttpTransport {
     config: HttpTransportConfig,
-    state: AsyncMutex<HttpTransportState>,
-    connected: AtomicBool,
-    messages_sent: AtomicU64,
-    messages_received: AtomicU64,
+    limits: LimitEnforcer,
+    shared: Arc<Shared>,
+    /// Background SSE readers, aborted on close.
+    #[cfg(feature = "http")]
+    streams: std::sync::Mutex<Vec<tokio::task::JoinHandle<()>>>,
     #[cfg(feature = "http")]
     client: Client,
 }
@@ -44,9 +76,10 @@ impl HttpTransport {
     /// Connection is established on first send.
     #[cfg(feature = "http")]
     pub fn new(config: HttpTransportConfig) -> Result<Self, TransportError> {
+        // No overall request timeout: it would cut long-lived SSE streams.
+        // `request_timeout` is applied per POST instead.
         let client = Client::builder()
             .connect_timeout(config.connect_timeout)
-            .timeout(config.request_timeout)
             .build()
             .map_err(|e| TransportError::Connection {
                 message: format!("Failed to create HTTP client: {e}"),
@@ -54,11 +87,17 @@ impl HttpTransport {
 
         let session_id = config.session_id.clone();
         Ok(Self {
+            limits: LimitEnforcer::new(config.limits.clone()),
             config,
-            state: AsyncMutex::new(HttpTransportState::new(session_id)),
-            connected: AtomicBool::new(false),
-            messages_sent: AtomicU64::new(0),
-            messages_received: AtomicU64::new(0),
+            shared: Arc::new(Shared {
+                state: AsyncMutex::new(HttpTransportState::new(session_id)),
+                incoming: Notify::new(),
+                connected: AtomicBool::new(false),
+                messages_sent: AtomicU64::new(0),
+                messages_received: AtomicU64::new(0),
+                compression: CompressionStats::new(),
+            }),
+            streams: std::sync::Mutex::new(Vec::new()),
             client,
         })
     }
@@ -68,11 +107,16 @@ impl HttpTransport {
     pub fn new(config: HttpTransportConfig) -> Result<Self, TransportError> {
         let session_id = config.session_id.clone();
         Ok(Self {
+            limits: LimitEnforcer::new(config.limits.clone()),
             config,
-            state: AsyncMutex::new(HttpTransportState::new(session_id)),
-            connected: AtomicBool::new(false),
-            messages_sent: AtomicU64::new(0),
-            messages_received: AtomicU64::new(0),
+            shared: Arc::new(Shared {
+                state: AsyncMutex::new(HttpTransportState::new(session_id)),
+                incoming: Notify::new(),
+                connected: AtomicBool::new(false),
+                messages_sent: AtomicU64::new(0),
+                messages_received: AtomicU64::new(0),
+                compression: CompressionStats::new(),
+            }),
         })
     }
 
@@ -83,83 +127,72 @@ impl HttpTransport {
     /// on the first send operation.
     pub async fn connect(config: HttpTransportConfig) -> Result<Self, TransportError> {
         let transport = Self::new(config)?;
-        transport.connected.store(true, Ordering::Release);
+        transport.shared.connected.store(true, Ordering::Release);
         Ok(transport)
     }
 
     /// Get the current session ID, if any.
     pub async fn session_id(&self) -> Option<String> {
-        self.state.lock().await.session_id.clone()
+        self.shared.state.lock().await.session_id.clone()
     }
 
     /// Set the session ID.
     pub async fn set_session_id(&self, session_id: impl Into<String>) {
-        self.state.lock().await.session_id = Some(session_id.into());
+        self.shared.state.lock().await.session_id = Some(session_id.into());
+    }
+
+    /// Get the protocol version negotiated by `initialize`, if any.
+    ///
+    /// Once known, it replaces the configured version in the
+    /// `mcp-protocol-version` header of every request.
+    pub async fn protocol_version(&self) -> Option<String> {
+        self.shared.state.lock().await.protocol_version.clone()
     }
 
     /// Get the number of messages sent.
     #[must_use]
     pub fn messages_sent(&self) -> u64 {
-        self.messages_sent.load(Ordering::Relaxed)
+        self.shared.messages_sent.load(Ordering::Relaxed)
     }
 
     /// Get the number of messages received.
     #[must_use]
     pub fn messages_received(&self) -> u64 {
-        self.messages_received.load(Ordering::Relaxed)
+        self.shared.messages_received.load(Ordering::Relaxed)
+    }
+
+    /// Get the bytes saved by compression so far.
+    ///
+    /// Counts stay at zero unless compression was enabled with
+    /// [`HttpTransportConfig::with_compression`].
+    #[must_use]
+    pub fn compression_stats(&self) -> CompressionSnapshot {
+        self.shared.compression.snapshot()
     }
 
     /// Get the last event ID for SSE resumption.
     #[must_use]
     pub async fn last_event_id(&self) -> Option<String> {
-        self.state.lock().await.last_event_id.clone()
+        self.shared.state.lock().await.last_event_id.clone()
     }
 
-    /// Build headers for requests.
+    /// Queue a message for `recv` and wake any waiter.
     #[cfg(feature = "http")]
-    fn build_headers(&self, session_id: Option<&str>) -> Result<HeaderMap, TransportError> {
-        let mut headers = HeaderMap::new();
-
-        // Required headers per MCP spec
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(
-            ACCEPT,
-            HeaderValue::from_static("application/json, text/event-stream"),
-        );
-        headers.insert(
-            MCP_PROTOCOL_VERSION_HEADER,
-            HeaderValue::from_str(&self.config.protocol_version).map_err(|e| {
-                TransportError::Connection {
-                    message: format!("Invalid protocol version header: {e}"),
-                }
-            })?,
-        );
-
-        // Session ID if available
-        if let Some(sid) = session_id {
-            headers.insert(
-                MCP_SESSION_ID_HEADER,
-                HeaderValue::from_str(sid).map_err(|e| TransportError::Connection {
-                    message: format!("Invalid session ID header: {e}"),
-                })?,
-            );
-        }
+    async fn enqueue(&self, msg: Message) {
+        self.shared.state.lock().await.push_incoming(msg);
+        self.shared
+            .messages_received
+            .fetch_add(1, Ordering::Relaxed);
+        self.shared.wake();
+    }
 
-        // Custom headers
-        for (name, value) in &self.config.headers {
-            headers.insert(
-                reqwest::header::HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
-                    TransportError::Connection {
-                        message: format!("Invalid header name '{name}': {e}"),
-                    }
-                })?,
-                HeaderValue::from_str(value).map_err(|e| TransportError::Connection {
-                    message: format!("Invalid header value for '{name}': {e}"),
-                })?,
-            );
+    /// Track a background SSE reader so `close` can abort it.
+    #[cfg(feature = "http")]
+    fn track_stream(&self, handle: tokio::task::JoinHandle<()>) {
+        if let Ok(mut streams) = self.streams.lock() {
+            streams.retain(|h| !h.is_finished());
+            streams.push(handle);
         }
-
-        Ok(headers)
     }
 
     /// Send a message and handle the response.
@@ -169,21 +202,35 @@ impl HttpTransport {
             message: format!("Failed to serialize message: {e}"),
         })?;
 
-        // Check message size limit
-        if body.len() > self.config.max_message_size {
-            return Err(TransportError::MessageTooLarge {
-                size: body.len(),
-                max: self.config.max_message_size,
-            });
-        }
-
-        let session_id = self.state.lock().await.session_id.clone();
-        let headers = self.build_headers(session_id.as_deref())?;
+        self.limits.send(msg, body.len())?;
+
+        let mut headers = {
+            let mut state = self.shared.state.lock().await;
+            state.record_outgoing(msg);
+            build_headers(&self.config, &state)?
+        };
+
+        let body = match self.config.compression {
+            Some(config) if self.config.compress_requests && config.should_compress(body.len()) => {
+                let encoded =
+                    compression::compress(config.encoding, body.as_bytes(), config.level)?;
+                self.shared
+                    .compression
+                    .record_compressed(body.len(), encoded.len());
+                headers.insert(
+                    header::CONTENT_ENCODING,
+                    HeaderValue::from_static(config.encoding.as_str()),
+                );
+                encoded
+            }
+            _ => body.into_bytes(),
+        };
 
         let response = self
             .client
             .post(&self.config.base_url)
             .headers(headers)
+            .timeout(self.config.request_timeout)
             .body(body)
             .send()
             .await
@@ -192,8 +239,8 @@ impl HttpTransport {
             })?;
 
         self.handle_response(response).await?;
-        self.messages_sent.fetch_add(1, Ordering::Relaxed);
-        self.connected.store(true, Ordering::Release);
+        self.shared.messages_sent.fetch_add(1, Ordering::Relaxed);
+        self.shared.connected.store(true, Ordering::Release);
 
         Ok(())
     }
@@ -206,7 +253,7 @@ impl HttpTransport {
         // Check for session ID in response headers
         if let Some(session_id) = response.headers().get(MCP_SESSION_ID_HEADER) {
             if let Ok(sid) = session_id.to_str() {
-                self.state.lock().await.session_id = Some(sid.to_string());
+                self.shared.state.lock().await.session_id = Some(sid.to_string());
             }
         }
 
@@ -219,8 +266,17 @@ impl HttpTransport {
                     .unwrap_or("application/json");
 
                 if content_type.starts_with("text/event-stream") {
-                    // Handle SSE stream
-                    self.process_sse_stream(response).await
+                    // Read the stream in the background: the server may send
+                    // its own requests on it before the response, and the
+                    // caller must be free to answer them.
+                    let shared = Arc::clone(&self.shared);
+                    let max = self.config.limits.max_message_size;
+                    self.track_stream(tokio::spawn(async move {
+                        if let Err(e) = consume_sse(&shared, response, max).await {
+                            tracing::warn!(error = %e, "SSE response stream failed");
+                        }
+                    }));
+                    Ok(())
                 } else {
                     // Handle direct JSON response
                     self.process_json_response(response).await
@@ -233,7 +289,7 @@ impl HttpTransport {
             StatusCode::UNAUTHORIZED => {
                 // 401: credentials/session are stale. Clear the session so a
                 // retry re-establishes one, and surface an authorization error.
-                self.state.lock().await.session_id = None;
+                self.shared.state.lock().await.session_id = None;
                 let body = response.text().await.unwrap_or_default();
                 Err(TransportError::Connection {
                     message: format!("Unauthorized (401): {body}"),
@@ -241,7 +297,7 @@ impl HttpTransport {
             }
             StatusCode::NOT_FOUND => {
                 // Session expired
-                self.state.lock().await.session_id = None;
+                self.shared.state.lock().await.session_id = None;
                 Err(TransportError::Connection {
                     message: "Session expired or not found".to_string(),
                 })
@@ -251,14 +307,13 @@ impl HttpTransport {
                 // (e.g. an error object) in the body. Deliver it to the awaiting
                 // caller instead of tearing down the transport; only a body we
                 // cannot parse as a JSON-RPC message becomes a transport error.
-                let body = response.text().await.unwrap_or_default();
-                if let Ok(msg) = serde_json::from_str::<Message>(&body) {
-                    self.state.lock().await.message_queue.push_back(msg);
-                    self.messages_received.fetch_add(1, Ordering::Relaxed);
+                let body = self.read_body(response).await.unwrap_or_default();
+                if let Ok(msg) = codec::decode_slice(&body) {
+                    self.enqueue(msg).await;
                     Ok(())
                 } else {
                     Err(TransportError::Protocol {
-                        message: format!("HTTP {status}: {body}"),
+                        message: format!("HTTP {status}: {}", String::from_utf8_lossy(&body)),
                     })
                 }
             }
@@ -268,62 +323,79 @@ impl HttpTransport {
     /// Process a direct JSON response.
     #[cfg(feature = "http")]
     async fn process_json_response(&self, response: Response) -> Result<(), TransportError> {
-        let body = response
-            .text()
-            .await
-            .map_err(|e| TransportError::Connection {
-                message: format!("Failed to read response body: {e}"),
-            })?;
+        let body = self.read_body(response).await?;
 
         if body.is_empty() {
             return Ok(());
         }
 
         // Check message size limit
-        if body.len() > self.config.max_message_size {
+        if body.len() > self.config.limits.max_message_size {
             return Err(TransportError::MessageTooLarge {
                 size: body.len(),
-                max: self.config.max_message_size,
+                max: self.config.limits.max_message_size,
             });
         }
 
-        let msg: Message =
-            serde_json::from_str(&body).map_err(|e| TransportError::Serialization {
-                message: format!("Failed to parse response: {e}"),
-            })?;
+        let msg = codec::decode(body)?;
 
-        self.state.lock().await.message_queue.push_back(msg);
-        self.messages_received.fetch_add(1, Ordering::Relaxed);
+        self.enqueue(msg).await;
 
         Ok(())
     }
 
-    /// Process an SSE stream.
+    /// Read a response body, decoding its `Content-Encoding`.
     #[cfg(feature = "http")]
-    async fn process_sse_stream(&self, response: Response) -> Result<(), TransportError> {
-        let mut stream = response.bytes_stream();
-        let mut state = self.state.lock().await;
-
-        while let Some(chunk_result) = stream.next().await {
-            let chunk: Bytes = chunk_result.map_err(|e| TransportError::Connection {
-                message: format!("SSE stream error: {e}"),
-            })?;
-
-            let chunk_str = std::str::from_utf8(&chunk).map_err(|e| TransportError::Protocol {
-                message: format!("Invalid UTF-8 in SSE stream: {e}"),
+    async fn read_body(&self, response: Response) -> Result<Bytes, TransportError> {
+        let encoding = content_encoding(&response)?;
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| TransportError::Connection {
+                message: format!("Failed to read response body: {e}"),
             })?;
+        let Some(encoding) = encoding else {
+            return Ok(body);
+        };
+        let decoded =
+            compression::decompress(encoding, &body, self.config.limits.max_message_size)?;
+        self.shared
+            .compression
+            .record_decompressed(body.len(), decoded.len());
+        Ok(decoded.into())
+    }
 
-            state.sse_buffer.push_str(chunk_str);
-
-            // Process complete events
-            process_sse_buffer(
-                &mut state,
-                &self.messages_received,
-                self.config.max_message_size,
-            )?;
-        }
-
-        Ok(())
+    /// Open the standalone SSE stream (HTTP GET on the MCP endpoint).
+    ///
+    /// Messages the server pushes on the stream — server-initiated requests
+    /// and notifications — are delivered through [`recv`](Transport::recv).
+    /// If the stream drops and auto-reconnect is enabled, it is reopened with
+    /// `Last-Event-ID` so the server can replay missed events.
+    ///
+    /// This is called automatically after `notifications/initialized` is sent
+    /// unless [`HttpTransportConfig::without_sse_stream`] was used.
+    ///
+    /// Returns `Ok(false)` if the server does not offer a stream at this
+    /// endpoint (`405 Method Not Allowed`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the GET fails or the server answers with an
+    /// unexpected status or content type.
+    #[cfg(feature = "http")]
+    pub async fn open_sse_stream(&self) -> Result<bool, TransportError> {
+        let Some(response) = get_sse_stream(&self.client, &self.config, &self.shared).await? else {
+            tracing::debug!(url = %self.config.base_url, "Server does not offer an SSE stream");
+            return Ok(false);
+        };
+
+        let client = self.client.clone();
+        let config = self.config.clone();
+        let shared = Arc::clone(&self.shared);
+        self.track_stream(tokio::spawn(async move {
+            run_sse_stream(client, config, shared, response).await;
+        }));
+        Ok(true)
     }
 
     /// Stub for `send_post` when http feature is disabled.
@@ -340,48 +412,340 @@ impl HttpTransport {
         &self,
         state: &mut HttpTransportState,
     ) -> Result<(), TransportError> {
-        process_sse_buffer(state, &self.messages_received, self.config.max_message_size)
+        process_sse_buffer(
+            state,
+            &self.shared.messages_received,
+            self.config.limits.max_message_size,
+        )
     }
 }
 
-impl Transport for HttpTransport {
-    type Error = TransportError;
+/// Build headers for requests.
+///
+/// Carries the session ID once the server assigned one, and the negotiated
+/// protocol version once `initialize` completed (the configured version
+/// before that).
+#[cfg(feature = "http")]
+fn build_headers(
+    config: &HttpTransportConfig,
+    state: &HttpTransportState,
+) -> Result<HeaderMap, TransportError> {
+    let session_id = state.session_id.as_deref();
+    let protocol_version = state
+        .protocol_version
+        .as_deref()
+        .unwrap_or(&config.protocol_version);
+    let mut headers = HeaderMap::new();
+
+    // Required headers per MCP spec
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(
+        ACCEPT,
+        HeaderValue::from_static("application/json, text/event-stream"),
+    );
+    headers.insert(
+        MCP_PROTOCOL_VERSION_HEADER,
+        HeaderValue::from_str(protocol_version).map_err(|e| TransportError::Connection {
+            message: format!("Invalid protocol version header: {e}"),
+        })?,
+    );
+
+    // Session ID if available
+    if let Some(sid) = session_id {
+        headers.insert(
+            MCP_SESSION_ID_HEADER,
+            HeaderValue::from_str(sid).map_err(|e| TransportError::Connection {
+                message: format!("Invalid session ID header: {e}"),
+            })?,
+        );
+    }
 
-    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
-        self.send_post(&msg).await
+    if config.compression.is_some() {
+        headers.insert(
+            header::ACCEPT_ENCODING,
+            HeaderValue::from_static(ACCEPT_ENCODING),
+        );
     }
 
-    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
-        let mut state = self.state.lock().await;
+    // Custom headers
+    for (name, value) in &config.headers {
+        headers.insert(
+            reqwest::header::HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+                TransportError::Connection {
+                    message: format!("Invalid header name '{name}': {e}"),
+                }
+            })?,
+            HeaderValue::from_str(value).map_err(|e| TransportError::Connection {
+                message: format!("Invalid header value for '{name}': {e}"),
+            })?,
+        );
+    }
+
+    Ok(headers)
+}
+
+/// Issue the GET that opens the standalone SSE stream, resuming from the last
+/// seen event ID. Returns `None` when the server answers `405`.
+#[cfg(feature = "http")]
+async fn get_sse_stream(
+    client: &Client,
+    config: &HttpTransportConfig,
+    shared: &Shared,
+) -> Result<Option<Response>, TransportError> {
+    let (mut headers, last_event_id) = {
+        let state = shared.state.lock().await;
+        (build_headers(config, &state)?, state.last_event_id.clone())
+    };
+    headers.remove(CONTENT_TYPE);
+    headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+    if let Some(id) = last_event_id {
+        headers.insert(
+            LAST_EVENT_ID_HEADER,
+            HeaderValue::from_str(&id).map_err(|e| TransportError::Connection {
+                message: format!("Invalid Last-Event-ID header: {e}"),
+            })?,
+        );
+    }
+
+    let response = client
+        .get(&config.base_url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| TransportError::Connection {
+            message: format!("HTTP GET failed: {e}"),
+        })?;
+
+    match response.status() {
+        StatusCode::METHOD_NOT_ALLOWED => Ok(None),
+        StatusCode::OK => {
+            let is_sse = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|ct| ct.starts_with("text/event-stream"));
+            if is_sse {
+                Ok(Some(response))
+            } else {
+                Err(TransportError::Protocol {
+                    message: "SSE stream GET did not return text/event-stream".to_string(),
+                })
+            }
+        }
+        StatusCode::NOT_FOUND => {
+            shared.state.lock().await.session_id = None;
+            Err(TransportError::Connection {
+                message: "Session expired or not found".to_string(),
+            })
+        }
+        status => Err(TransportError::Connection {
+            message: format!("SSE stream GET failed with HTTP {status}"),
+        }),
+    }
+}
+
+/// Read the standalone SSE stream until it ends, reconnecting with
+/// `Last-Event-ID` while the transport is connected and auto-reconnect allows.
+#[cfg(feature = "http")]
+async fn run_sse_stream(
+    client: Client,
+    config: HttpTransportConfig,
+    shared: Arc<Shared>,
+    mut response: Response,
+) {
+    let mut attempts = 0;
+    loop {
+        match consume_sse(&shared, response, config.limits.max_message_size).await {
+            Ok(()) => tracing::debug!("SSE stream ended"),
+            Err(e) => tracing::warn!(error = %e, "SSE stream failed"),
+        }
 
-        // Return queued messages first
-        if let Some(msg) = state.message_queue.pop_front() {
-            return Ok(Some(msg));
+        loop {
+            if !config.auto_reconnect
+                || attempts >= config.max_reconnect_attempts
+                || !shared.connected.load(Ordering::Acquire)
+            {
+                return;
+            }
+            attempts += 1;
+            let backoff = std::time::Duration::from_millis(100 * 2u64.pow(attempts.min(6)));
+            crate::runtime::sleep(backoff).await;
+
+            match get_sse_stream(&client, &config, &shared).await {
+                Ok(Some(next)) => {
+                    attempts = 0;
+                    response = next;
+                    break;
+                }
+                Ok(None) => return,
+                Err(e) => tracing::warn!(error = %e, attempt = attempts, "SSE reconnect failed"),
+            }
         }
+    }
+}
+
+/// The response's `Content-Encoding`, if it is compressed.
+#[cfg(feature = "http")]
+fn content_encoding(response: &Response) -> Result<Option<Encoding>, TransportError> {
+    let Some(value) = response.headers().get(header::CONTENT_ENCODING) else {
+        return Ok(None);
+    };
+    let value = value.to_str().unwrap_or_default();
+    Encoding::from_header(value).map_err(|coding| TransportError::Protocol {
+        message: format!("Unsupported Content-Encoding: {coding}"),
+    })
+}
+
+/// Consume an SSE response body, queueing each complete event's message.
+///
+/// A compressed stream is inflated as it arrives. The decoded size is only
+/// bounded per event, by `max_message_size`, since the stream may stay open
+/// indefinitely.
+#[cfg(feature = "http")]
+async fn consume_sse(
+    shared: &Shared,
+    response: Response,
+    max_message_size: usize,
+) -> Result<(), TransportError> {
+    let mut decoder = content_encoding(&response)?.map(|e| Decoder::new(e, usize::MAX));
+    let mut stream = response.bytes_stream();
+    // Bytes of a UTF-8 character split across chunks.
+    let mut partial = Vec::new();
+
+    while let Some(chunk_result) = stream.next().await {
+        let chunk: Bytes = chunk_result.map_err(|e| TransportError::Connection {
+            message: format!("SSE stream error: {e}"),
+        })?;
+
+        let chunk = match &mut decoder {
+            Some(decoder) => {
+                decoder.push(&chunk)?;
+                let decoded = decoder.take();
+                shared
+                    .compression
+                    .record_decompressed(chunk.len(), decoded.len());
+                decoded
+            }
+            None => chunk.into(),
+        };
+        partial.extend_from_slice(&chunk);
+
+        let complete = match std::str::from_utf8(&partial) {
+            Ok(_) => partial.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(TransportError::Protocol {
+                    message: format!("Invalid UTF-8 in SSE stream: {e}"),
+                });
+            }
+        };
+        let rest = partial.split_off(complete);
+        let chunk_str = String::from_utf8(std::mem::replace(&mut partial, rest)).map_err(|e| {
+            TransportError::Protocol {
+                message: format!("Invalid UTF-8 in SSE stream: {e}"),
+            }
+        })?;
+
+        // Lock per chunk only, so `recv` can drain messages while the
+        // stream stays open.
+        let result = {
+            let mut state = shared.state.lock().await;
+            state.sse_buffer.push_str(&chunk_str);
+            process_sse_buffer(&mut state, &shared.messages_received, max_message_size)
+        };
+        shared.wake();
+        result?;
+    }
+
+    Ok(())
+}
+
+impl HttpTransport {
+    /// Wait for the next queued message.
+    async fn next_message(&self) -> Result<Option<Message>, TransportError> {
+        loop {
+            // Return queued messages first
+            if let Some(msg) = self.shared.state.lock().await.message_queue.pop_front() {
+                return Ok(Some(msg));
+            }
+            if !self.shared.connected.load(Ordering::Acquire) {
+                return Ok(None);
+            }
+
+            // Register before re-checking so a message queued in between is
+            // not missed.
+            let listener = self.shared.incoming.listen();
+            if let Some(msg) = self.shared.state.lock().await.message_queue.pop_front() {
+                return Ok(Some(msg));
+            }
+            if !self.shared.connected.load(Ordering::Acquire) {
+                return Ok(None);
+            }
+            listener.await;
+        }
+    }
+}
+
+impl Transport for HttpTransport {
+    type Error = TransportError;
 
-        // If no queued messages and not connected, return None
-        if !self.connected.load(Ordering::Acquire) {
-            return Ok(None);
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        self.send_post(&msg).await?;
+
+        // The session is live once the client confirms initialization; open
+        // the standalone stream so server-initiated messages can arrive.
+        #[cfg(feature = "http")]
+        if self.config.open_sse_stream
+            && matches!(&msg, Message::Notification(n) if n.method == "notifications/initialized")
+        {
+            if let Err(e) = self.open_sse_stream().await {
+                tracing::warn!(error = %e, "Failed to open SSE stream");
+            }
         }
 
-        Ok(None)
+        Ok(())
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        self.limits.recv(self.next_message()).await
     }
 
     async fn close(&self) -> Result<(), Self::Error> {
-        self.connected.store(false, Ordering::Release);
+        self.shared.connected.store(false, Ordering::Release);
+        self.shared.wake();
 
         #[cfg(feature = "http")]
         {
-            // Send DELETE to terminate session if we have a session ID
-            let session_id = self.state.lock().await.session_id.clone();
-            if let Some(_sid) = session_id {
-                let headers = self.build_headers(None)?;
-                let _ = self
+            if let Ok(mut streams) = self.streams.lock() {
+                for handle in streams.drain(..) {
+                    handle.abort();
+                }
+            }
+
+            // Terminate the session with a DELETE carrying its ID. Best
+            // effort: a server may answer 405 when it does not let clients
+            // end sessions, and the transport is closed either way.
+            let headers = {
+                let mut state = self.shared.state.lock().await;
+                let headers = build_headers(&self.config, &state);
+                state.session_id.take().map(|_| headers)
+            };
+            if let Some(headers) = headers {
+                let mut headers = headers?;
+                headers.remove(CONTENT_TYPE);
+                match self
                     .client
                     .delete(&self.config.base_url)
                     .headers(headers)
+                    .timeout(self.config.request_timeout)
                     .send()
-                    .await;
+                    .await
+                {
+                    Ok(response) => {
+                        tracing::debug!(status = %response.status(), "Session terminated");
+                    }
+                    Err(e) => tracing::debug!(error = %e, "Session DELETE failed"),
+                }
             }
         }
 
@@ -389,7 +753,7 @@ impl Transport for HttpTransport {
     }
 
     fn is_connected(&self) -> bool {
-        self.connected.load(Ordering::Acquire)
+        self.shared.connected.load(Ordering::Acquire)
     }
 
     fn metadata(&self) -> TransportMetadata {
@@ -544,4 +908,362 @@ mod tests {
             );
         }
     }
+
+    #[cfg(feature = "http")]
+    mod session {
+        use super::super::HttpTransport;
+        use crate::http::config::HttpTransportConfig;
+        use crate::traits::Transport;
+        use mcpkit_core::protocol::{Message, Notification, Request};
+        use serde_json::json;
+        use wiremock::matchers::{body_partial_json, header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        /// A server that assigns session `s-1` and negotiates `2025-06-18`.
+        async fn server() -> MockServer {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({"method": "initialize"})))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("mcp-session-id", "s-1")
+                        .set_body_json(json!({
+                            "jsonrpc": "2.0",
+                            "id": 1,
+                            "result": {"protocolVersion": "2025-06-18"}
+                        })),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+            server
+        }
+
+        async fn initialize(t: &HttpTransport) {
+            t.send(Message::Request(Request::new("initialize", 1u64)))
+                .await
+                .expect("send initialize");
+            t.recv()
+                .await
+                .expect("recv ok")
+                .expect("initialize response");
+        }
+
+        #[tokio::test]
+        async fn session_and_negotiated_version_are_sent_after_initialize() {
+            let server = server().await;
+            Mock::given(method("POST"))
+                .and(header("mcp-session-id", "s-1"))
+                .and(header("mcp-protocol-version", "2025-06-18"))
+                .respond_with(ResponseTemplate::new(202))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let t =
+                HttpTransport::connect(HttpTransportConfig::new(server.uri()).without_sse_stream())
+                    .await
+                    .expect("connect");
+            initialize(&t).await;
+            assert_eq!(t.session_id().await.as_deref(), Some("s-1"));
+            assert_eq!(t.protocol_version().await.as_deref(), Some("2025-06-18"));
+
+            t.send(Message::Notification(Notification::new(
+                "notifications/initialized",
+            )))
+            .await
+            .expect("headers carry the session and negotiated version");
+            server.verify().await;
+        }
+
+        #[tokio::test]
+        async fn close_deletes_the_session() {
+            let server = server().await;
+            Mock::given(method("DELETE"))
+                .and(header("mcp-session-id", "s-1"))
+                .and(header("mcp-protocol-version", "2025-06-18"))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let t = HttpTransport::connect(HttpTransportConfig::new(server.uri()))
+                .await
+                .expect("connect");
+            initialize(&t).await;
+            t.close().await.expect("close");
+            assert!(t.session_id().await.is_none());
+            // A second close has no session left to delete.
+            t.close().await.expect("close again");
+            server.verify().await;
+        }
+
+        #[tokio::test]
+        async fn close_tolerates_servers_that_refuse_termination() {
+            let server = server().await;
+            Mock::given(method("DELETE"))
+                .respond_with(ResponseTemplate::new(405))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let t = HttpTransport::connect(HttpTransportConfig::new(server.uri()))
+                .await
+                .expect("connect");
+            initialize(&t).await;
+            t.close().await.expect("405 on DELETE is not an error");
+        }
+    }
+
+    #[cfg(feature = "http")]
+    mod sse_stream {
+        use super::super::HttpTransport;
+        use crate::http::config::HttpTransportConfig;
+        use crate::traits::Transport;
+        use mcpkit_core::protocol::{Message, Notification, RequestId};
+        use std::time::Duration;
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const SAMPLING_EVENT: &str = "id: 1\ndata: {\"jsonrpc\":\"2.0\",\"id\":7,\"method\":\"sampling/createMessage\",\"params\":{}}\n\n";
+
+        async fn mount_post_accepted(server: &MockServer) {
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(202))
+                .mount(server)
+                .await;
+        }
+
+        async fn initialized(t: &HttpTransport) {
+            t.send(Message::Notification(Notification::new(
+                "notifications/initialized",
+            )))
+            .await
+            .expect("send initialized");
+        }
+
+        #[tokio::test]
+        async fn server_initiated_request_arrives_on_get_stream() {
+            let server = MockServer::start().await;
+            mount_post_accepted(&server).await;
+            Mock::given(method("GET"))
+                .and(header("accept", "text/event-stream"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_raw(SAMPLING_EVENT, "text/event-stream"),
+                )
+                .mount(&server)
+                .await;
+
+            let t = HttpTransport::connect(
+                HttpTransportConfig::new(server.uri()).without_auto_reconnect(),
+            )
+            .await
+            .expect("connect");
+            initialized(&t).await;
+
+            let msg = tokio::time::timeout(Duration::from_secs(5), t.recv())
+                .await
+                .expect("message before timeout")
+                .expect("recv ok")
+                .expect("a message");
+            match msg {
+                Message::Request(r) => {
+                    assert_eq!(r.method, "sampling/createMessage");
+                    assert_eq!(r.id, RequestId::Number(7));
+                }
+                other => panic!("expected request, got {other:?}"),
+            }
+            assert_eq!(t.last_event_id().await.as_deref(), Some("1"));
+        }
+
+        #[tokio::test]
+        async fn stream_reconnects_with_last_event_id() {
+            let server = MockServer::start().await;
+            mount_post_accepted(&server).await;
+            Mock::given(method("GET"))
+                .and(header("last-event-id", "1"))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(
+                    "id: 2\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/tools/list_changed\"}\n\n",
+                    "text/event-stream",
+                ))
+                .with_priority(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_raw(SAMPLING_EVENT, "text/event-stream"),
+                )
+                .up_to_n_times(1)
+                .mount(&server)
+                .await;
+
+            let t = HttpTransport::connect(
+                HttpTransportConfig::new(server.uri()).with_max_reconnect_attempts(1),
+            )
+            .await
+            .expect("connect");
+            initialized(&t).await;
+
+            let first = tokio::time::timeout(Duration::from_secs(5), t.recv())
+                .await
+                .expect("first before timeout")
+                .expect("recv ok");
+            assert!(matches!(first, Some(Message::Request(_))));
+            let second = tokio::time::timeout(Duration::from_secs(5), t.recv())
+                .await
+                .expect("second before timeout")
+                .expect("recv ok");
+            match second {
+                Some(Message::Notification(n)) => {
+                    assert_eq!(n.method, "notifications/tools/list_changed");
+                }
+                other => panic!("expected notification, got {other:?}"),
+            }
+            t.close().await.expect("close");
+        }
+
+        #[tokio::test]
+        async fn method_not_allowed_means_no_stream() {
+            let server = MockServer::start().await;
+            mount_post_accepted(&server).await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(405))
+                .mount(&server)
+                .await;
+
+            let t = HttpTransport::connect(HttpTransportConfig::new(server.uri()))
+                .await
+                .expect("connect");
+            initialized(&t).await;
+            assert!(!t.open_sse_stream().await.expect("405 is not an error"));
+        }
+
+        #[tokio::test]
+        async fn recv_waits_until_close() {
+            let server = MockServer::start().await;
+            let t = std::sync::Arc::new(
+                HttpTransport::connect(HttpTransportConfig::new(server.uri()))
+                    .await
+                    .expect("connect"),
+            );
+
+            let waiter = tokio::spawn({
+                let t = std::sync::Arc::clone(&t);
+                async move { t.recv().await }
+            });
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(!waiter.is_finished(), "recv must wait for a message");
+
+            t.close().await.expect("close");
+            let res = tokio::time::timeout(Duration::from_secs(5), waiter)
+                .await
+                .expect("recv wakes on close")
+                .expect("join");
+            assert!(matches!(res, Ok(None)));
+        }
+    }
+
+    #[cfg(feature = "http")]
+    mod compression {
+        use super::super::HttpTransport;
+        use crate::compression::{CompressionConfig, Encoding, compress};
+        use crate::http::config::HttpTransportConfig;
+        use crate::traits::Transport;
+        use mcpkit_core::protocol::{Message, Request};
+        use std::time::Duration;
+        use wiremock::matchers::{header, headers, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        fn large_result(id: u64) -> String {
+            let text = "lorem ipsum ".repeat(500);
+            format!(r#"{{"jsonrpc":"2.0","id":{id},"result":{{"text":"{text}"}}}}"#)
+        }
+
+        async fn recv(t: &HttpTransport) -> Message {
+            tokio::time::timeout(Duration::from_secs(5), t.recv())
+                .await
+                .expect("message before timeout")
+                .expect("recv ok")
+                .expect("a message")
+        }
+
+        #[tokio::test]
+        async fn compressed_json_response_is_decoded() {
+            let server = MockServer::start().await;
+            let body = large_result(1);
+            Mock::given(method("POST"))
+                .and(headers("accept-encoding", vec!["gzip", "deflate"]))
+                .and(header("content-encoding", "deflate"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-encoding", "gzip")
+                        .set_body_raw(
+                            compress(Encoding::Gzip, body.as_bytes(), 6).unwrap(),
+                            "application/json",
+                        ),
+                )
+                .mount(&server)
+                .await;
+
+            let config = HttpTransportConfig::new(server.uri())
+                .with_compression(CompressionConfig::new().with_encoding(Encoding::Deflate))
+                .with_request_compression()
+                .without_sse_stream();
+            let t = HttpTransport::connect(config).await.expect("connect");
+            let params = serde_json::json!({ "query": "x".repeat(2000) });
+            t.send(Message::Request(Request::with_params(
+                "tools/call",
+                1,
+                params,
+            )))
+            .await
+            .expect("send");
+
+            assert_eq!(
+                recv(&t).await.as_response().map(|r| r.id.clone()),
+                Some(1.into())
+            );
+            let stats = t.compression_stats();
+            assert_eq!(stats.messages_compressed, 1);
+            assert_eq!(stats.messages_decompressed, 1);
+            assert!(stats.bytes_saved() > body.len() as u64, "{stats:?}");
+        }
+
+        #[tokio::test]
+        async fn compressed_sse_response_is_decoded() {
+            let server = MockServer::start().await;
+            let events = format!(
+                "id: 1\ndata: {}\n\nid: 2\ndata: {}\n\n",
+                large_result(1),
+                large_result(2)
+            );
+            Mock::given(method("POST"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-encoding", "deflate")
+                        .set_body_raw(
+                            compress(Encoding::Deflate, events.as_bytes(), 6).unwrap(),
+                            "text/event-stream",
+                        ),
+                )
+                .mount(&server)
+                .await;
+
+            let config = HttpTransportConfig::new(server.uri())
+                .with_compression(CompressionConfig::new())
+                .without_sse_stream();
+            let t = HttpTransport::connect(config).await.expect("connect");
+            t.send(Message::Request(Request::new("tools/list", 1)))
+                .await
+                .expect("send");
+
+            for id in [1u64, 2] {
+                assert_eq!(
+                    recv(&t).await.as_response().map(|r| r.id.clone()),
+                    Some(id.into())
+                );
+            }
+            assert_eq!(t.last_event_id().await.as_deref(), Some("2"));
+        }
+    }
 }
@@ -1,5 +1,7 @@
 //! HTTP transport configuration types and constants.
 
+use crate::compression::CompressionConfig;
+use crate::limits::TransportLimits;
 use std::time::Duration;
 
 /// MCP Protocol version for the HTTP transport.
@@ -19,6 +21,10 @@ pub const MCP_PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
 /// case-insensitive, so lowercase works universally.
 pub const MCP_SESSION_ID_HEADER: &str = "mcp-session-id";
 
+/// Header name a client uses to resume an SSE stream after the last event it
+/// received.
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
 /// Default maximum message size (16 MB).
 pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
 
@@ -41,8 +47,20 @@ pub struct HttpTransportConfig {
     pub headers: Vec<(String, String)>,
     /// Protocol version to use.
     pub protocol_version: String,
-    /// Maximum message size in bytes.
-    pub max_message_size: usize,
+    /// Message size, in-flight, and read timeout limits.
+    pub limits: TransportLimits,
+    /// Whether to open the standalone SSE stream (HTTP GET) once the session
+    /// is initialized, so server-initiated requests and notifications reach
+    /// the client.
+    pub open_sse_stream: bool,
+    /// Compression settings. When set, the client sends `Accept-Encoding`
+    /// and decodes compressed responses.
+    pub compression: Option<CompressionConfig>,
+    /// Whether to also compress request bodies above the threshold.
+    ///
+    /// Only enable this for servers known to accept `Content-Encoding` on
+    /// requests.
+    pub compress_requests: bool,
 }
 
 impl HttpTransportConfig {
@@ -58,14 +76,24 @@ impl HttpTransportConfig {
             max_reconnect_attempts: 5,
             headers: Vec::new(),
             protocol_version: MCP_PROTOCOL_VERSION.to_string(),
-            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            limits: TransportLimits::new().with_max_message_size(DEFAULT_MAX_MESSAGE_SIZE),
+            open_sse_stream: true,
+            compression: None,
+            compress_requests: false,
         }
     }
 
     /// Set the maximum message size.
     #[must_use]
     pub const fn with_max_message_size(mut self, size: usize) -> Self {
-        self.max_message_size = size;
+        self.limits.max_message_size = size;
+        self
+    }
+
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.limits = limits;
         self
     }
 
@@ -97,6 +125,38 @@ impl HttpTransportConfig {
         self
     }
 
+    /// Do not open the standalone SSE stream after initialization.
+    ///
+    /// Without it, the client only receives messages sent in reply to its own
+    /// POSTs; server-initiated requests (sampling, elicitation) and
+    /// notifications sent outside a request are not delivered.
+    #[must_use]
+    pub const fn without_sse_stream(mut self) -> Self {
+        self.open_sse_stream = false;
+        self
+    }
+
+    /// Accept compressed responses, using `config` for anything this client
+    /// compresses.
+    #[must_use]
+    pub const fn with_compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
+    /// Compress request bodies of at least the configured minimum size.
+    ///
+    /// Enables compression with the default settings if it is not already
+    /// configured.
+    #[must_use]
+    pub const fn with_request_compression(mut self) -> Self {
+        if self.compression.is_none() {
+            self.compression = Some(CompressionConfig::new());
+        }
+        self.compress_requests = true;
+        self
+    }
+
     /// Set maximum reconnection attempts.
     #[must_use]
     pub const fn with_max_reconnect_attempts(mut self, attempts: u32) -> Self {
@@ -169,10 +229,31 @@ impl HttpTransportBuilder {
         self
     }
 
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn limits(mut self, limits: TransportLimits) -> Self {
+        self.config.limits = limits;
+        self
+    }
+
     /// Disable automatic reconnection.
     #[must_use]
     pub const fn no_auto_reconnect(mut self) -> Self {
         self.config.auto_reconnect = false;
         self
     }
+
+    /// Do not open the standalone SSE stream after initialization.
+    #[must_use]
+    pub const fn no_sse_stream(mut self) -> Self {
+        self.config.open_sse_stream = false;
+        self
+    }
+
+    /// Accept compressed responses.
+    #[must_use]
+    pub const fn compression(mut self, config: CompressionConfig) -> Self {
+        self.config.compression = Some(config);
+        self
+    }
 }
@@ -7,6 +7,8 @@
 //!
 //! - Standard HTTP POST requests for sending messages
 //! - Server-Sent Events (SSE) for receiving streaming responses
+//! - A standalone SSE stream (HTTP GET) for server-initiated requests and
+//!   notifications, such as `sampling/createMessage`
 //! - Session management with MCP session IDs
 //! - Automatic reconnection with Last-Event-ID support
 //! - Protocol version header handling
@@ -41,19 +43,16 @@
 
 mod client;
 mod config;
-mod sse;
-
-#[cfg(feature = "http")]
 mod origin;
+pub(crate) mod sse;
 
 // Re-export public types
 pub use client::HttpTransport;
 pub use config::{
-    DEFAULT_MAX_MESSAGE_SIZE, HttpTransportBuilder, HttpTransportConfig, MCP_PROTOCOL_VERSION,
-    MCP_PROTOCOL_VERSION_HEADER, MCP_SESSION_ID_HEADER,
+    DEFAULT_MAX_MESSAGE_SIZE, HttpTransportBuilder, HttpTransportConfig, LAST_EVENT_ID_HEADER,
+    MCP_PROTOCOL_VERSION, MCP_PROTOCOL_VERSION_HEADER, MCP_SESSION_ID_HEADER,
 };
 
-#[cfg(feature = "http")]
 pub use origin::OriginValidator;
 
 // Re-export SSE types for testing
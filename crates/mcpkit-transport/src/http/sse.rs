@@ -3,8 +3,9 @@
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicU64, Ordering};
 
-use mcpkit_core::protocol::Message;
+use mcpkit_core::protocol::{Message, RequestId};
 
+use crate::codec;
 use crate::error::TransportError;
 
 /// HTTP transport state for tracking session and messages.
@@ -18,6 +19,11 @@ pub struct HttpTransportState {
     pub last_event_id: Option<String>,
     /// Current SSE buffer for parsing.
     pub sse_buffer: String,
+    /// The protocol version negotiated by `initialize`, sent as
+    /// `mcp-protocol-version` on every later request.
+    pub protocol_version: Option<String>,
+    /// The id of the `initialize` request awaiting its response.
+    pub initialize_id: Option<RequestId>,
 }
 
 impl HttpTransportState {
@@ -29,8 +35,38 @@ impl HttpTransportState {
             message_queue: VecDeque::new(),
             last_event_id: None,
             sse_buffer: String::new(),
+            protocol_version: None,
+            initialize_id: None,
         }
     }
+
+    /// Note an outgoing message: an `initialize` request starts a new
+    /// version negotiation.
+    pub fn record_outgoing(&mut self, msg: &Message) {
+        if let Message::Request(request) = msg {
+            if request.method == "initialize" {
+                self.initialize_id = Some(request.id.clone());
+                self.protocol_version = None;
+            }
+        }
+    }
+
+    /// Queue an incoming message, capturing the negotiated protocol version
+    /// from the `initialize` response.
+    pub fn push_incoming(&mut self, msg: Message) {
+        if let Message::Response(response) = &msg {
+            if self.initialize_id.as_ref() == Some(&response.id) {
+                self.initialize_id = None;
+                self.protocol_version = response
+                    .result
+                    .as_ref()
+                    .and_then(|result| result.get("protocolVersion"))
+                    .and_then(serde_json::Value::as_str)
+                    .map(String::from);
+            }
+        }
+        self.message_queue.push_back(msg);
+    }
 }
 
 /// Process the SSE buffer and extract complete events.
@@ -77,9 +113,9 @@ pub fn process_sse_buffer(
                     });
                 }
 
-                match serde_json::from_str::<Message>(&data) {
+                match codec::decode(bytes::Bytes::from(data)) {
                     Ok(msg) => {
-                        state.message_queue.push_back(msg);
+                        state.push_incoming(msg);
                         messages_received.fetch_add(1, Ordering::Relaxed);
                     }
                     Err(e) => {
@@ -113,6 +149,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn initialize_response_sets_negotiated_version() -> Result<(), Box<dyn std::error::Error>> {
+        let messages_received = AtomicU64::new(0);
+        let mut state = HttpTransportState::new(None);
+        state.record_outgoing(&Message::Request(mcpkit_core::protocol::Request::new(
+            "initialize",
+            1u64,
+        )));
+        state.sse_buffer = String::from(
+            "data: {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{\"protocolVersion\":\"x\"}}\n\n\
+             data: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"protocolVersion\":\"2025-06-18\"}}\n\n",
+        );
+
+        process_sse_buffer(&mut state, &messages_received, 16 * 1024 * 1024)?;
+
+        // Only the response to `initialize` counts.
+        assert_eq!(state.protocol_version.as_deref(), Some("2025-06-18"));
+        assert!(state.initialize_id.is_none());
+        assert_eq!(state.message_queue.len(), 2);
+        Ok(())
+    }
+
     #[test]
     fn test_sse_buffer_multiple_events() -> Result<(), Box<dyn std::error::Error>> {
         let messages_received = AtomicU64::new(0);
@@ -25,6 +25,8 @@
 //! | `grpc::GrpcTransport` | gRPC client with bidirectional streaming | `grpc` feature |
 //! | `unix::UnixTransport` | Unix domain sockets (local IPC) | Unix platforms only |
 //! | `windows::NamedPipeTransport` | Windows named pipes (local IPC) | Windows only |
+//! | `wasm::BrowserWebSocketTransport` | Browser `WebSocket` client | `wasm` feature, wasm32 only |
+//! | `wasm::FetchTransport` | Browser Streamable HTTP client over `fetch` | `wasm` feature, wasm32 only |
 //!
 //! ## Quick Reference
 //!
@@ -61,12 +63,20 @@
 //! let (client, server) = MemoryTransport::pair();
 //! ```
 //!
+//! **From a URL** (see [`factory`]):
+//! ```ignore
+//! let transport = mcpkit_transport::connect("stdio:./my-mcp-server --verbose").await?;
+//! let listener = mcpkit_transport::listen("unix:/tmp/mcp.sock").await?;
+//! ```
+//!
 //! # Runtime Support
 //!
 //! This crate supports multiple async runtimes through feature flags:
 //!
 //! - `tokio-runtime` (default): Use Tokio for async I/O
 //! - `smol-runtime`: Use smol for async I/O
+//! - `wasm`: Browser event loop and transports on `wasm32-unknown-unknown`
+//!   (with default features disabled)
 //!
 //! # Example
 //!
@@ -90,10 +100,17 @@
 
 #![deny(missing_docs)]
 
+pub mod codec;
+pub mod compression;
 pub mod error;
+#[cfg(feature = "tokio-runtime")]
+pub mod factory;
+pub mod framing;
 pub mod http;
+pub mod limits;
 pub mod memory;
 pub mod middleware;
+pub mod ping;
 pub mod pool;
 pub mod runtime;
 pub mod spawn;
@@ -105,6 +122,9 @@ pub mod websocket;
 #[cfg(feature = "grpc")]
 pub mod grpc;
 
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub mod wasm;
+
 #[cfg(unix)]
 pub mod unix;
 
@@ -112,8 +132,12 @@ pub mod unix;
 pub mod windows;
 
 // Re-export commonly used types
+pub use compression::{CompressionConfig, CompressionSnapshot, CompressionStats, Encoding};
 pub use error::TransportError;
-pub use traits::{Transport, TransportExt, TransportListener, TransportMetadata};
+pub use framing::Framing;
+pub use limits::TransportLimits;
+pub use ping::{PingConfig, PingExit, PingHealth, PingSnapshot, PingSupervisor};
+pub use traits::{PeerCredentials, Transport, TransportExt, TransportListener, TransportMetadata};
 
 // Re-export bytes types for zero-copy message handling
 pub use bytes::{Bytes, BytesMut};
@@ -140,7 +164,9 @@ pub use websocket::{
 
 // Unix socket transport
 #[cfg(unix)]
-pub use unix::{UnixListener, UnixSocketConfig, UnixTransport, UnixTransportBuilder};
+pub use unix::{
+    PeerAllowlist, UnixListener, UnixSocketConfig, UnixTransport, UnixTransportBuilder,
+};
 
 // Windows named pipe transport
 #[cfg(windows)]
@@ -153,11 +179,18 @@ pub use grpc::{GrpcConfig, GrpcTransport};
 // Connection pooling
 pub use pool::{Pool, PoolConfig, PoolStats, PooledConnection};
 
+// Transports chosen by URL
+#[cfg(feature = "tokio-runtime")]
+pub use factory::{BoxedListener, BoxedTransport, TransportUrl, connect, listen};
+
 // Subprocess spawning
 #[cfg(feature = "tokio-runtime")]
-pub use spawn::{SpawnedTransport, SpawnedTransportBuilder};
+pub use spawn::{
+    ResourceLimits, ShutdownSignal, SpawnedTransport, SpawnedTransportBuilder, StderrTarget,
+};
 
 // Telemetry
+pub use telemetry::dimensions::{Dimensions, Labels};
 pub use telemetry::{
     LatencyHistogram, MetricsSnapshot, TelemetryConfig, TelemetryLayer, TelemetryMetrics,
     TelemetryTransport,
@@ -169,11 +202,15 @@ pub use telemetry::otel::{OtelConfig, TracingGuard, init_tracing, init_tracing_d
 
 // Prometheus metrics (requires `prometheus` feature)
 #[cfg(feature = "prometheus")]
-pub use telemetry::prom::{McpMetrics, MetricsExporter, create_default_metrics};
+pub use telemetry::prom::{
+    Exemplar, GaugeGuard, McpMetrics, MetricsExporter, OPENMETRICS_CONTENT_TYPE,
+    create_default_metrics,
+};
 
 /// Prelude module for convenient imports.
 pub mod prelude {
     pub use crate::error::TransportError;
+    pub use crate::limits::TransportLimits;
     pub use crate::traits::{Transport, TransportExt, TransportListener, TransportMetadata};
 
     #[cfg(any(feature = "tokio-runtime", feature = "smol-runtime"))]
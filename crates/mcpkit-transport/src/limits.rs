@@ -0,0 +1,263 @@
+//! Resource limits shared by all transports.
+//!
+//! [`TransportLimits`] bounds what one peer can make a transport hold: the
+//! size of a single message, the number of requests it may have outstanding,
+//! and how long a read may wait. Every transport builder accepts it through
+//! `with_limits`, as do the web framework integrations, so one value can be
+//! applied across an application:
+//!
+//! ```rust
+//! use mcpkit_transport::TransportLimits;
+//! use std::time::Duration;
+//!
+//! let limits = TransportLimits::new()
+//!     .with_max_message_size(1024 * 1024)
+//!     .with_max_in_flight(32)
+//!     .with_read_timeout(Duration::from_secs(300));
+//! assert_eq!(limits.max_message_size, 1024 * 1024);
+//! ```
+//!
+//! The message size is enforced while reading, so an oversized message is
+//! rejected with [`TransportError::MessageTooLarge`] before it is buffered in
+//! full. Transports without a builder can be wrapped in
+//! [`LimitsLayer`](crate::middleware::LimitsLayer).
+
+use crate::error::TransportError;
+use crate::runtime::AsyncSemaphore;
+use async_lock::SemaphoreGuardArc;
+use mcpkit_core::protocol::{Message, RequestId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// Default maximum message size (16 MB), matching the transports' defaults.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Limits on what a peer can make a transport hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransportLimits {
+    /// Maximum size of a single message in bytes, in either direction.
+    pub max_message_size: usize,
+    /// Maximum number of requests received but not yet answered. Once
+    /// reached, the transport stops reading until a response is sent
+    /// (backpressure); HTTP integrations answer `503` instead. `None` means
+    /// unlimited.
+    pub max_in_flight: Option<usize>,
+    /// Maximum time to wait for the next message. A read that takes longer
+    /// fails with [`TransportError::Timeout`]. `None` waits indefinitely.
+    pub read_timeout: Option<Duration>,
+}
+
+impl TransportLimits {
+    /// Limits with the default message size and no other bounds.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            max_in_flight: None,
+            read_timeout: None,
+        }
+    }
+
+    /// Set the maximum message size in bytes.
+    #[must_use]
+    pub const fn with_max_message_size(mut self, size: usize) -> Self {
+        self.max_message_size = size;
+        self
+    }
+
+    /// Set the maximum number of unanswered requests.
+    #[must_use]
+    pub const fn with_max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = Some(max);
+        self
+    }
+
+    /// Set the maximum time to wait for the next message.
+    #[must_use]
+    pub const fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Check a message of `size` bytes against the size limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransportError::MessageTooLarge`] if `size` exceeds
+    /// [`max_message_size`](Self::max_message_size).
+    pub const fn check_message_size(&self, size: usize) -> Result<(), TransportError> {
+        if size > self.max_message_size {
+            return Err(TransportError::MessageTooLarge {
+                size,
+                max: self.max_message_size,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for TransportLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Enforces the in-flight and read-timeout parts of [`TransportLimits`]
+/// around a transport's own read and write paths.
+pub(crate) struct LimitEnforcer {
+    limits: TransportLimits,
+    permits: Option<Arc<AsyncSemaphore>>,
+    /// Permits of requests received and not yet answered.
+    held: Mutex<HashMap<RequestId, SemaphoreGuardArc>>,
+    /// A request read while its `recv` was waiting for a permit.
+    pending: Mutex<Option<Message>>,
+}
+
+impl LimitEnforcer {
+    pub(crate) fn new(limits: TransportLimits) -> Self {
+        Self {
+            permits: limits
+                .max_in_flight
+                .map(|max| Arc::new(AsyncSemaphore::new(max))),
+            limits,
+            held: Mutex::new(HashMap::new()),
+            pending: Mutex::new(None),
+        }
+    }
+
+    pub(crate) const fn limits(&self) -> &TransportLimits {
+        &self.limits
+    }
+
+    /// Run `read` under the read timeout. A request it yields takes an
+    /// in-flight slot, waiting for one to free up first.
+    ///
+    /// Cancel-safe: a request read while waiting for a slot is kept and
+    /// returned by the next call instead of being read again.
+    pub(crate) async fn recv<F, E>(&self, read: F) -> Result<Option<Message>, E>
+    where
+        F: Future<Output = Result<Option<Message>, E>>,
+        E: From<TransportError>,
+    {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        let mut message = match (pending, self.limits.read_timeout) {
+            (Some(pending), _) => Some(pending),
+            (None, Some(timeout)) => {
+                crate::runtime::timeout(timeout, read).await.map_err(|_| {
+                    TransportError::Timeout {
+                        operation: "recv".to_string(),
+                        duration: timeout,
+                    }
+                })??
+            }
+            (None, None) => read.await?,
+        };
+
+        let (Some(permits), Some(Message::Request(request))) = (&self.permits, &message) else {
+            return Ok(message);
+        };
+        let id = request.id.clone();
+        let permit = if let Some(permit) = permits.try_acquire_arc() {
+            permit
+        } else {
+            tracing::debug!(%id, "In-flight limit reached; waiting for a response");
+            *self.pending.lock().unwrap_or_else(PoisonError::into_inner) = message;
+            let permit = permits.acquire_arc().await;
+            message = self
+                .pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take();
+            permit
+        };
+        self.held
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, permit);
+        Ok(message)
+    }
+
+    /// Check an outgoing message of `size` bytes, releasing the in-flight
+    /// slot of the request it answers.
+    pub(crate) fn send(&self, message: &Message, size: usize) -> Result<(), TransportError> {
+        self.limits.check_message_size(size)?;
+        if let Message::Response(response) = message {
+            self.held
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&response.id);
+        }
+        Ok(())
+    }
+}
+
+impl Default for LimitEnforcer {
+    fn default() -> Self {
+        Self::new(TransportLimits::default())
+    }
+}
+
+impl std::fmt::Debug for LimitEnforcer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LimitEnforcer")
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "tokio-runtime"))]
+mod tests {
+    use super::*;
+    use mcpkit_core::protocol::{Request, Response};
+
+    fn request(id: u64) -> Message {
+        Message::Request(Request::new("tools/list", id))
+    }
+
+    fn response(id: u64) -> Message {
+        Message::Response(Response::success(id, serde_json::json!({})))
+    }
+
+    #[test]
+    fn size_check() {
+        let limits = TransportLimits::new().with_max_message_size(10);
+        assert!(limits.check_message_size(10).is_ok());
+        assert!(matches!(
+            limits.check_message_size(11),
+            Err(TransportError::MessageTooLarge { size: 11, max: 10 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_timeout_fails_slow_reads() {
+        let enforcer =
+            LimitEnforcer::new(TransportLimits::new().with_read_timeout(Duration::from_millis(10)));
+        let result: Result<_, TransportError> = enforcer.recv(std::future::pending()).await;
+        assert!(matches!(result, Err(TransportError::Timeout { .. })));
+    }
+
+    #[tokio::test]
+    async fn in_flight_requests_wait_for_responses() {
+        let enforcer = LimitEnforcer::new(TransportLimits::new().with_max_in_flight(1));
+        let read = |message| async move { Ok::<_, TransportError>(Some(message)) };
+        enforcer.recv(read(request(1))).await.unwrap();
+
+        // Responses are never held back.
+        enforcer.recv(read(response(9))).await.unwrap();
+
+        let second = enforcer.recv(read(request(2)));
+        let blocked = tokio::time::timeout(Duration::from_millis(20), second).await;
+        assert!(blocked.is_err(), "second request should wait for a slot");
+
+        // The request read by the cancelled call is returned next.
+        enforcer.send(&response(1), 0).unwrap();
+        let next = enforcer.recv(read(request(3))).await.unwrap();
+        assert!(matches!(next, Some(Message::Request(r)) if r.id == RequestId::from(2u64)));
+    }
+}
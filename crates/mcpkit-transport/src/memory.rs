@@ -23,6 +23,11 @@
 //! assert!(server_transport.is_connected());
 //! ```
 
+// Without a runtime (e.g. the `wasm` feature) MemoryTransport is compiled out.
+#![cfg_attr(
+    not(any(feature = "tokio-runtime", feature = "smol-runtime")),
+    allow(dead_code, unused_imports)
+)]
 use crate::error::TransportError;
 use crate::traits::{Transport, TransportMetadata};
 use mcpkit_core::protocol::Message;
@@ -322,6 +322,10 @@ where
         }));
         meta
     }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
 }
 
 /// Statistics about batching operations.
@@ -0,0 +1,160 @@
+//! Limits middleware for MCP transports.
+//!
+//! Applies [`TransportLimits`] to a transport that has no `with_limits` of
+//! its own, such as [`MemoryTransport`](crate::MemoryTransport) or a custom
+//! transport. Incoming message size can only be checked once the inner
+//! transport has read the message, so transports that buffer input should
+//! also cap it themselves.
+
+use crate::error::TransportError;
+use crate::limits::{LimitEnforcer, TransportLimits};
+use crate::middleware::TransportLayer;
+use crate::traits::{Transport, TransportMetadata};
+use mcpkit_core::protocol::Message;
+
+/// A layer that enforces [`TransportLimits`].
+#[derive(Debug, Clone, Default)]
+pub struct LimitsLayer {
+    limits: TransportLimits,
+}
+
+impl LimitsLayer {
+    /// Create a layer enforcing `limits`.
+    #[must_use]
+    pub const fn new(limits: TransportLimits) -> Self {
+        Self { limits }
+    }
+}
+
+impl<T: Transport> TransportLayer<T> for LimitsLayer
+where
+    T::Error: From<TransportError>,
+{
+    type Transport = LimitedTransport<T>;
+
+    fn layer(&self, inner: T) -> Self::Transport {
+        LimitedTransport {
+            inner,
+            enforcer: LimitEnforcer::new(self.limits.clone()),
+        }
+    }
+}
+
+/// A transport wrapped with [`TransportLimits`] enforcement.
+#[derive(Debug)]
+pub struct LimitedTransport<T> {
+    inner: T,
+    enforcer: LimitEnforcer,
+}
+
+impl<T> LimitedTransport<T> {
+    /// Get the enforced limits.
+    pub const fn limits(&self) -> &TransportLimits {
+        self.enforcer.limits()
+    }
+
+    /// Get the inner transport.
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Transport> Transport for LimitedTransport<T>
+where
+    T::Error: From<TransportError>,
+{
+    type Error = T::Error;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        let size = serde_json::to_vec(&msg)
+            .map_err(TransportError::from)?
+            .len();
+        self.enforcer.send(&msg, size)?;
+        self.inner.send(msg).await
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        let message = self.enforcer.recv(self.inner.recv()).await?;
+        if let Some(message) = &message {
+            let size = serde_json::to_vec(message)
+                .map_err(TransportError::from)?
+                .len();
+            self.enforcer.limits().check_message_size(size)?;
+        }
+        Ok(message)
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.inner.close().await
+    }
+
+    fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        self.inner.metadata()
+    }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
+}
+
+#[cfg(all(test, feature = "tokio-runtime"))]
+mod tests {
+    use super::*;
+    use crate::MemoryTransport;
+    use crate::middleware::LayerStack;
+    use mcpkit_core::protocol::{Request, Response};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn enforces_size_and_timeout() {
+        let (client, server) = MemoryTransport::pair();
+        let limits = TransportLimits::new()
+            .with_max_message_size(128)
+            .with_read_timeout(Duration::from_millis(20));
+        let server = LayerStack::new(server)
+            .with(LimitsLayer::new(limits))
+            .into_inner();
+
+        let big =
+            Request::new("tools/call", 1u64).params(serde_json::json!({ "x": "y".repeat(256) }));
+        client.send(Message::Request(big.clone())).await.unwrap();
+        assert!(matches!(
+            server.recv().await,
+            Err(TransportError::MessageTooLarge { max: 128, .. })
+        ));
+        assert!(matches!(
+            server.send(Message::Request(big)).await,
+            Err(TransportError::MessageTooLarge { .. })
+        ));
+
+        assert!(matches!(
+            server.recv().await,
+            Err(TransportError::Timeout { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn answered_requests_free_their_slot() {
+        let (client, server) = MemoryTransport::pair();
+        let server = LimitsLayer::new(TransportLimits::new().with_max_in_flight(1)).layer(server);
+
+        for id in 1..=2u64 {
+            client
+                .send(Message::Request(Request::new("ping", id)))
+                .await
+                .unwrap();
+        }
+        assert!(server.recv().await.unwrap().is_some());
+        let blocked = tokio::time::timeout(Duration::from_millis(20), server.recv()).await;
+        assert!(blocked.is_err(), "second request should wait");
+
+        let answer = Response::success(1u64, serde_json::json!({}));
+        server.send(Message::Response(answer)).await.unwrap();
+        let next = server.recv().await.unwrap().unwrap();
+        assert_eq!(next.method(), Some("ping"));
+    }
+}
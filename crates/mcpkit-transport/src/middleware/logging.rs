@@ -5,8 +5,10 @@
 
 use crate::middleware::TransportLayer;
 use crate::traits::{Transport, TransportMetadata};
+use mcpkit_core::debug::RecordingPolicy;
 use mcpkit_core::protocol::Message;
 use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt;
 use std::sync::Arc;
@@ -33,6 +35,8 @@ pub struct LoggingLayer {
     /// Optional redaction applied to logged content (never to the forwarded
     /// message).
     redactor: Option<Redactor>,
+    /// Tools whose calls are replaced by a placeholder before logging.
+    policy: Option<RecordingPolicy>,
 }
 
 impl fmt::Debug for LoggingLayer {
@@ -41,6 +45,7 @@ impl fmt::Debug for LoggingLayer {
             .field("level", &self.level)
             .field("log_contents", &self.log_contents)
             .field("redactor", &self.redactor.as_ref().map(|_| "<fn>"))
+            .field("policy", &self.policy)
             .finish()
     }
 }
@@ -76,6 +81,7 @@ impl LoggingLayer {
             level,
             log_contents: false,
             redactor: None,
+            policy: None,
         }
     }
 
@@ -129,6 +135,16 @@ impl LoggingLayer {
         self.redactor = Some(Arc::new(redactor));
         self
     }
+
+    /// Keep calls to tools excluded by `policy` out of logged content.
+    ///
+    /// Their arguments and results are replaced by a placeholder before any
+    /// redactor runs. Has no effect unless content logging is enabled.
+    #[must_use]
+    pub fn with_recording_policy(mut self, policy: RecordingPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
 }
 
 /// Serialize `msg` and mask values under sensitive keys. On the (near-
@@ -179,6 +195,7 @@ impl<T: Transport> TransportLayer<T> for LoggingLayer {
             level: self.level,
             log_contents: self.log_contents,
             redactor: self.redactor.clone(),
+            policy: self.policy.clone(),
             messages_sent: AtomicU64::new(0),
             messages_received: AtomicU64::new(0),
         }
@@ -191,11 +208,20 @@ pub struct LoggingTransport<T> {
     level: Level,
     log_contents: bool,
     redactor: Option<Redactor>,
+    policy: Option<RecordingPolicy>,
     messages_sent: AtomicU64,
     messages_received: AtomicU64,
 }
 
 impl<T> LoggingTransport<T> {
+    /// The message as it may appear in logs, after the recording policy.
+    fn screened<'a>(&self, msg: &'a Message) -> Cow<'a, Message> {
+        match &self.policy {
+            Some(policy) => Cow::Owned(policy.redact(msg)),
+            None => Cow::Borrowed(msg),
+        }
+    }
+
     /// Get the number of messages sent.
     pub fn messages_sent(&self) -> u64 {
         self.messages_sent.load(Ordering::Relaxed)
@@ -214,9 +240,10 @@ impl<T: Transport> Transport for LoggingTransport<T> {
         let count = self.messages_sent.fetch_add(1, Ordering::Relaxed) + 1;
 
         if self.log_contents {
+            let shown = self.screened(&msg);
             match &self.redactor {
                 Some(redact) => {
-                    let redacted = redact(&msg);
+                    let redacted = redact(&shown);
                     match self.level {
                         Level::TRACE => trace!(count, ?redacted, "sending message"),
                         Level::DEBUG => debug!(count, ?redacted, "sending message"),
@@ -224,8 +251,8 @@ impl<T: Transport> Transport for LoggingTransport<T> {
                     }
                 }
                 None => match self.level {
-                    Level::TRACE => trace!(count, ?msg, "sending message"),
-                    Level::DEBUG => debug!(count, ?msg, "sending message"),
+                    Level::TRACE => trace!(count, msg = ?shown, "sending message"),
+                    Level::DEBUG => debug!(count, msg = ?shown, "sending message"),
                     _ => debug!(count, "sending message"),
                 },
             }
@@ -248,9 +275,10 @@ impl<T: Transport> Transport for LoggingTransport<T> {
             let count = self.messages_received.fetch_add(1, Ordering::Relaxed) + 1;
 
             if self.log_contents {
+                let shown = self.screened(msg);
                 match &self.redactor {
                     Some(redact) => {
-                        let redacted = redact(msg);
+                        let redacted = redact(&shown);
                         match self.level {
                             Level::TRACE => trace!(count, ?redacted, "received message"),
                             Level::DEBUG => debug!(count, ?redacted, "received message"),
@@ -258,8 +286,8 @@ impl<T: Transport> Transport for LoggingTransport<T> {
                         }
                     }
                     None => match self.level {
-                        Level::TRACE => trace!(count, ?msg, "received message"),
-                        Level::DEBUG => debug!(count, ?msg, "received message"),
+                        Level::TRACE => trace!(count, msg = ?shown, "received message"),
+                        Level::DEBUG => debug!(count, msg = ?shown, "received message"),
                         _ => debug!(count, "received message"),
                     },
                 }
@@ -292,6 +320,10 @@ impl<T: Transport> Transport for LoggingTransport<T> {
     fn metadata(&self) -> TransportMetadata {
         self.inner.metadata()
     }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
 }
 
 #[cfg(test)]
@@ -408,4 +440,26 @@ mod tests {
             "redactor must run when log_contents is true"
         );
     }
+
+    #[tokio::test]
+    async fn recording_policy_screens_excluded_tools_before_redactor() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let (a, _b) = MemoryTransport::pair();
+        let transport = LoggingLayer::new(Level::DEBUG)
+            .with_contents(true)
+            .with_recording_policy(RecordingPolicy::new().exclude_tool("vault_get"))
+            .redact_with(move |msg| {
+                let value = serde_json::to_value(msg).expect("serialize");
+                sink.lock().expect("lock").push(value.clone());
+                value
+            })
+            .layer(a);
+        let call = request_with(json!({ "name": "vault_get", "arguments": { "entry": "bank" } }));
+        transport.send(call).await.expect("send");
+
+        let seen = seen.lock().expect("lock");
+        assert_eq!(seen[0]["params"]["name"], "vault_get");
+        assert_eq!(seen[0]["params"]["arguments"], mcpkit_core::debug::REDACTED);
+    }
 }
@@ -228,6 +228,10 @@ impl<T: Transport> Transport for MetricsTransport<T> {
     fn metadata(&self) -> TransportMetadata {
         self.inner.metadata()
     }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
 }
 
 #[cfg(test)]
@@ -27,6 +27,7 @@
 //! ```
 
 mod batching;
+mod limits;
 mod logging;
 mod metrics;
 pub mod rate_limit;
@@ -34,6 +35,7 @@ mod retry;
 mod timeout;
 
 pub use batching::{BatchingConfig, BatchingLayer, BatchingStats, BatchingTransport};
+pub use limits::{LimitedTransport, LimitsLayer};
 pub use logging::LoggingLayer;
 pub use metrics::MetricsLayer;
 pub use rate_limit::{
@@ -154,6 +154,10 @@ where
     fn metadata(&self) -> TransportMetadata {
         self.inner.metadata()
     }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
 }
 
 #[cfg(test)]
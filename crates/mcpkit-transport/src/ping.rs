@@ -0,0 +1,429 @@
+//! Heartbeat supervision for long-lived connections.
+//!
+//! A [`PingSupervisor`] sends an MCP `ping` every [`PingConfig::interval`],
+//! records the round-trip time of each reply, and gives up on the connection
+//! once [`PingConfig::max_missed`] pings in a row go unanswered within
+//! [`PingConfig::timeout`]. It then runs the config's
+//! [`on_unhealthy`](PingConfig::on_unhealthy) callbacks and returns
+//! [`PingExit::Unhealthy`], so the owner of the connection can close it.
+//!
+//! The supervisor never touches a transport itself: whoever owns the
+//! connection passes in a function that sends one `ping` and awaits the
+//! reply. That makes it work over any transport and on either end of the
+//! connection. The client wires it up with `ClientBuilder::ping_supervision`,
+//! the server with `RuntimeConfig::ping_supervision`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mcpkit_transport::ping::{PingConfig, PingExit, PingSupervisor};
+//! use std::time::Duration;
+//!
+//! let config = PingConfig::new()
+//!     .with_interval(Duration::from_secs(15))
+//!     .on_unhealthy(|snapshot| tracing::warn!(?snapshot, "peer stopped answering"));
+//! let supervisor = PingSupervisor::new(config);
+//! let health = supervisor.health();
+//!
+//! if supervisor.run(|| peer.ping()).await == PingExit::Unhealthy {
+//!     transport.close().await?;
+//! }
+//! ```
+
+use crate::runtime;
+use mcpkit_core::error::{McpError, TransportErrorKind};
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Default interval between pings.
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default time to wait for each ping's reply.
+pub const DEFAULT_PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default number of consecutive unanswered pings before the connection is
+/// declared unhealthy.
+pub const DEFAULT_MAX_MISSED: u32 = 3;
+
+type UnhealthyHook = dyn Fn(&PingSnapshot) + Send + Sync;
+
+/// How often to ping and when to give up on the connection.
+///
+/// Cloning is cheap and clones share the callbacks.
+#[derive(Clone)]
+pub struct PingConfig {
+    /// Time between the end of one ping and the start of the next. Defaults
+    /// to [`DEFAULT_PING_INTERVAL`].
+    pub interval: Duration,
+    /// How long to wait for a reply before counting the ping as missed.
+    /// Defaults to [`DEFAULT_PING_TIMEOUT`].
+    pub timeout: Duration,
+    /// Consecutive missed pings that make the connection unhealthy. Defaults
+    /// to [`DEFAULT_MAX_MISSED`]; `0` is treated as `1`.
+    pub max_missed: u32,
+    hooks: Vec<Arc<UnhealthyHook>>,
+    #[cfg(feature = "prometheus")]
+    metrics: Option<crate::telemetry::prom::McpMetrics>,
+}
+
+impl Default for PingConfig {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_PING_INTERVAL,
+            timeout: DEFAULT_PING_TIMEOUT,
+            max_missed: DEFAULT_MAX_MISSED,
+            hooks: Vec::new(),
+            #[cfg(feature = "prometheus")]
+            metrics: None,
+        }
+    }
+}
+
+impl PingConfig {
+    /// Create a configuration with the defaults.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the interval between pings.
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set how long to wait for each reply.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set how many consecutive missed pings make the connection unhealthy.
+    #[must_use]
+    pub const fn with_max_missed(mut self, max_missed: u32) -> Self {
+        self.max_missed = max_missed;
+        self
+    }
+
+    /// Call `hook` once when the connection is declared unhealthy, before
+    /// the supervisor returns.
+    #[must_use]
+    pub fn on_unhealthy<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&PingSnapshot) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Record round-trip times and missed pings into `metrics`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: crate::telemetry::prom::McpMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+}
+
+impl fmt::Debug for PingConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PingConfig")
+            .field("interval", &self.interval)
+            .field("timeout", &self.timeout)
+            .field("max_missed", &self.max_missed)
+            .field("hooks", &self.hooks.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Point-in-time view of a supervised connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingSnapshot {
+    /// Whether the connection is still considered healthy.
+    pub healthy: bool,
+    /// Pings sent.
+    pub sent: u64,
+    /// Pings answered in time.
+    pub answered: u64,
+    /// Pings that timed out or failed.
+    pub missed: u64,
+    /// Missed pings since the last answered one.
+    pub consecutive_missed: u32,
+    /// Round-trip time of the latest answered ping.
+    pub last_rtt: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct HealthState {
+    unhealthy: AtomicBool,
+    sent: AtomicU64,
+    answered: AtomicU64,
+    missed: AtomicU64,
+    consecutive_missed: AtomicU32,
+    /// Microseconds; `u64::MAX` until the first reply.
+    last_rtt_micros: AtomicU64,
+}
+
+impl Default for HealthState {
+    fn default() -> Self {
+        Self {
+            unhealthy: AtomicBool::new(false),
+            sent: AtomicU64::new(0),
+            answered: AtomicU64::new(0),
+            missed: AtomicU64::new(0),
+            consecutive_missed: AtomicU32::new(0),
+            last_rtt_micros: AtomicU64::new(u64::MAX),
+        }
+    }
+}
+
+/// A cheap, cloneable handle on a supervisor's view of the connection.
+#[derive(Debug, Clone, Default)]
+pub struct PingHealth {
+    state: Arc<HealthState>,
+}
+
+impl PingHealth {
+    /// Whether the connection is still considered healthy. A connection is
+    /// healthy until too many consecutive pings go unanswered.
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        !self.state.unhealthy.load(Ordering::Acquire)
+    }
+
+    /// Round-trip time of the latest answered ping.
+    #[must_use]
+    pub fn last_rtt(&self) -> Option<Duration> {
+        match self.state.last_rtt_micros.load(Ordering::Relaxed) {
+            u64::MAX => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// Current counters.
+    #[must_use]
+    pub fn snapshot(&self) -> PingSnapshot {
+        PingSnapshot {
+            healthy: self.is_healthy(),
+            sent: self.state.sent.load(Ordering::Relaxed),
+            answered: self.state.answered.load(Ordering::Relaxed),
+            missed: self.state.missed.load(Ordering::Relaxed),
+            consecutive_missed: self.state.consecutive_missed.load(Ordering::Relaxed),
+            last_rtt: self.last_rtt(),
+        }
+    }
+
+    fn record_answer(&self, rtt: Duration) {
+        let micros = u64::try_from(rtt.as_micros()).unwrap_or(u64::MAX - 1);
+        self.state.answered.fetch_add(1, Ordering::Relaxed);
+        self.state.consecutive_missed.store(0, Ordering::Relaxed);
+        self.state.last_rtt_micros.store(micros, Ordering::Relaxed);
+    }
+
+    /// Count a missed ping, returning the consecutive misses so far.
+    fn record_miss(&self) -> u32 {
+        self.state.missed.fetch_add(1, Ordering::Relaxed);
+        self.state
+            .consecutive_missed
+            .fetch_add(1, Ordering::Relaxed)
+            + 1
+    }
+}
+
+/// Why [`PingSupervisor::run`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PingExit {
+    /// The connection closed, so there was nothing left to supervise.
+    Closed,
+    /// Too many consecutive pings went unanswered.
+    Unhealthy,
+}
+
+/// Pings a peer periodically and tracks whether it still answers.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct PingSupervisor {
+    config: PingConfig,
+    health: PingHealth,
+}
+
+impl PingSupervisor {
+    /// Create a supervisor for one connection.
+    #[must_use]
+    pub fn new(config: PingConfig) -> Self {
+        Self {
+            config,
+            health: PingHealth::default(),
+        }
+    }
+
+    /// Create a supervisor that reports into an existing `health` handle, for
+    /// owners that hand the handle out before supervision starts.
+    #[must_use]
+    pub const fn with_health(config: PingConfig, health: PingHealth) -> Self {
+        Self { config, health }
+    }
+
+    /// The supervisor's configuration.
+    #[must_use]
+    pub const fn config(&self) -> &PingConfig {
+        &self.config
+    }
+
+    /// A handle on the connection's health, updated as pings complete.
+    #[must_use]
+    pub fn health(&self) -> PingHealth {
+        self.health.clone()
+    }
+
+    /// Ping until the connection closes or stops answering.
+    ///
+    /// `ping` sends one `ping` request and resolves when the reply arrives.
+    /// A JSON-RPC error reply still counts as an answer, since the peer is
+    /// evidently alive; a `ConnectionClosed` transport error ends supervision
+    /// with [`PingExit::Closed`]. Any other error, or no reply within the
+    /// timeout, is a miss.
+    pub async fn run<F, Fut>(self, mut ping: F) -> PingExit
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<(), McpError>>,
+    {
+        let max_missed = self.config.max_missed.max(1);
+        loop {
+            runtime::sleep(self.config.interval).await;
+
+            self.health.state.sent.fetch_add(1, Ordering::Relaxed);
+            let started = Instant::now();
+            let outcome = runtime::timeout(self.config.timeout, ping()).await;
+            let answered = match outcome {
+                Ok(Ok(()) | Err(McpError::JsonRpc(_))) => true,
+                Ok(Err(McpError::Transport(details)))
+                    if details.kind == TransportErrorKind::ConnectionClosed =>
+                {
+                    return PingExit::Closed;
+                }
+                Ok(Err(e)) => {
+                    tracing::debug!(error = %e, "ping failed");
+                    false
+                }
+                Err(_elapsed) => false,
+            };
+
+            if answered {
+                let rtt = started.elapsed();
+                self.health.record_answer(rtt);
+                #[cfg(feature = "prometheus")]
+                if let Some(metrics) = &self.config.metrics {
+                    metrics.record_ping_rtt(rtt.as_secs_f64());
+                }
+                continue;
+            }
+
+            let consecutive = self.health.record_miss();
+            #[cfg(feature = "prometheus")]
+            if let Some(metrics) = &self.config.metrics {
+                metrics.record_missed_ping();
+            }
+            if consecutive >= max_missed {
+                self.health.state.unhealthy.store(true, Ordering::Release);
+                let snapshot = self.health.snapshot();
+                tracing::warn!(
+                    missed = consecutive,
+                    "peer stopped answering pings; connection is unhealthy"
+                );
+                for hook in &self.config.hooks {
+                    hook(&snapshot);
+                }
+                return PingExit::Unhealthy;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tokio-runtime"))]
+mod tests {
+    use super::*;
+    use mcpkit_core::error::JsonRpcError;
+    use std::sync::Mutex;
+
+    fn fast() -> PingConfig {
+        PingConfig::new()
+            .with_interval(Duration::from_millis(5))
+            .with_timeout(Duration::from_millis(50))
+            .with_max_missed(2)
+    }
+
+    fn transport_error(kind: TransportErrorKind) -> McpError {
+        McpError::transport(kind, "test")
+    }
+
+    #[tokio::test]
+    async fn answered_pings_record_rtt_until_closed() {
+        let supervisor = PingSupervisor::new(fast());
+        let health = supervisor.health();
+        let mut calls = 0;
+        let exit = supervisor
+            .run(|| {
+                calls += 1;
+                let reply = match calls {
+                    1 => Ok(()),
+                    // An error reply is still a reply.
+                    2 => Err(McpError::JsonRpc(JsonRpcError::method_not_found("ping"))),
+                    _ => Err(transport_error(TransportErrorKind::ConnectionClosed)),
+                };
+                async move { reply }
+            })
+            .await;
+
+        assert_eq!(exit, PingExit::Closed);
+        let snapshot = health.snapshot();
+        assert!(snapshot.healthy);
+        assert_eq!(
+            (snapshot.sent, snapshot.answered, snapshot.missed),
+            (3, 2, 0)
+        );
+        assert!(snapshot.last_rtt.is_some());
+    }
+
+    #[tokio::test]
+    async fn consecutive_misses_make_the_connection_unhealthy() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        let config = fast().on_unhealthy(move |snapshot| sink.lock().unwrap().push(*snapshot));
+        let supervisor = PingSupervisor::new(config);
+        let health = supervisor.health();
+        let mut calls = 0;
+        let exit = supervisor
+            .run(|| {
+                calls += 1;
+                let answer = calls == 2;
+                async move {
+                    if answer {
+                        Ok(())
+                    } else if calls % 2 == 1 {
+                        Err(transport_error(TransportErrorKind::WriteFailed))
+                    } else {
+                        // Never answers; the timeout counts it as missed.
+                        std::future::pending().await
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(exit, PingExit::Unhealthy);
+        assert!(!health.is_healthy());
+        let reported = reported.lock().unwrap();
+        assert_eq!(reported.len(), 1);
+        // miss, answer, miss, miss: the answer reset the count.
+        assert_eq!(reported[0].sent, 4);
+        assert_eq!(reported[0].missed, 3);
+        assert_eq!(reported[0].consecutive_missed, 2);
+    }
+}
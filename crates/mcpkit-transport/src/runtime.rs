@@ -1,7 +1,8 @@
 //! Runtime abstraction layer for async I/O.
 //!
 //! This module provides runtime-agnostic abstractions over async primitives,
-//! allowing the transport layer to work with Tokio or smol.
+//! allowing the transport layer to work with Tokio, smol, or the browser
+//! event loop.
 //!
 //! # Design Philosophy
 //!
@@ -16,11 +17,28 @@
 //! Enable one of the runtime features:
 //! - `tokio-runtime` (default)
 //! - `smol-runtime`
-
-use futures::io::{AsyncRead, AsyncWrite};
+//! - `wasm` (on `wasm32-unknown-unknown`, with default features disabled)
+//!
+//! The `wasm` backend provides [`spawn`], [`sleep`], and [`timeout`] on top of
+//! `wasm-bindgen-futures` and the JavaScript `setTimeout`. Channels, stdio,
+//! and the `Instant`-based timers are not available there, since
+//! `std::time::Instant` panics in the browser.
+//!
+//! # Timers
+//!
+//! [`sleep`], [`sleep_until`], [`timeout`], and [`interval`] run on whichever
+//! runtime is enabled. Middleware and application code should use these
+//! rather than `tokio::time` so it behaves the same under Tokio and smol;
+//! the built-in `TimeoutLayer`, `RetryLayer`, and `RateLimitLayer` do.
+
+use futures::io::AsyncRead;
+#[cfg(any(feature = "tokio-runtime", feature = "smol-runtime"))]
+use futures::io::AsyncWrite;
 use std::future::Future;
 use std::io;
+#[cfg(feature = "tokio-runtime")]
 use std::pin::Pin;
+#[cfg(feature = "tokio-runtime")]
 use std::task::{Context, Poll};
 
 // =============================================================================
@@ -190,6 +208,21 @@ where
     smol::spawn(future).detach();
 }
 
+/// Spawn a future on the runtime.
+///
+/// In the browser this queues the future on the JavaScript event loop.
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "wasm",
+    not(any(feature = "tokio-runtime", feature = "smol-runtime"))
+))]
+pub fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    wasm_bindgen_futures::spawn_local(future);
+}
+
 // =============================================================================
 // Sleep Abstraction
 // =============================================================================
@@ -206,6 +239,82 @@ pub async fn sleep(duration: std::time::Duration) {
     smol::Timer::after(duration).await;
 }
 
+/// Sleep for the given duration.
+///
+/// Backed by `setTimeout`, so the returned future is `Send` and can be used
+/// inside transports and middleware.
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "wasm",
+    not(any(feature = "tokio-runtime", feature = "smol-runtime"))
+))]
+pub async fn sleep(duration: std::time::Duration) {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    crate::wasm::js::set_timeout(duration, move || {
+        let _ = tx.send(());
+    });
+    let _ = rx.await;
+}
+
+/// Sleep until the given deadline. Returns immediately if it has passed.
+#[cfg(feature = "tokio-runtime")]
+pub async fn sleep_until(deadline: std::time::Instant) {
+    tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)).await;
+}
+
+/// Sleep until the given deadline. Returns immediately if it has passed.
+#[cfg(all(feature = "smol-runtime", not(feature = "tokio-runtime")))]
+pub async fn sleep_until(deadline: std::time::Instant) {
+    smol::Timer::at(deadline).await;
+}
+
+/// Create an [`Interval`] that ticks every `period`, starting immediately.
+///
+/// # Panics
+///
+/// Panics if `period` is zero.
+#[cfg(any(feature = "tokio-runtime", feature = "smol-runtime"))]
+#[must_use]
+pub fn interval(period: std::time::Duration) -> Interval {
+    assert!(!period.is_zero(), "interval period must be non-zero");
+    Interval {
+        next: std::time::Instant::now(),
+        period,
+    }
+}
+
+/// A runtime-agnostic periodic timer, created with [`interval`].
+///
+/// Ticks that are missed (because the caller was busy for longer than a
+/// period) are skipped rather than fired in a burst.
+#[cfg(any(feature = "tokio-runtime", feature = "smol-runtime"))]
+#[derive(Debug)]
+pub struct Interval {
+    next: std::time::Instant,
+    period: std::time::Duration,
+}
+
+#[cfg(any(feature = "tokio-runtime", feature = "smol-runtime"))]
+impl Interval {
+    /// Wait for the next tick, returning the instant it was scheduled for.
+    pub async fn tick(&mut self) -> std::time::Instant {
+        sleep_until(self.next).await;
+        let scheduled = self.next;
+        let now = std::time::Instant::now();
+        self.next += self.period;
+        if self.next <= now {
+            self.next = now + self.period;
+        }
+        scheduled
+    }
+
+    /// The interval's period.
+    #[must_use]
+    pub const fn period(&self) -> std::time::Duration {
+        self.period
+    }
+}
+
 // =============================================================================
 // Timeout Abstraction
 // =============================================================================
@@ -236,6 +345,24 @@ where
     }
 }
 
+/// Apply a timeout to a future.
+#[cfg(all(
+    target_arch = "wasm32",
+    feature = "wasm",
+    not(any(feature = "tokio-runtime", feature = "smol-runtime"))
+))]
+pub async fn timeout<F, T>(duration: std::time::Duration, future: F) -> Result<T, TimeoutError>
+where
+    F: Future<Output = T>,
+{
+    use futures::future::FutureExt;
+
+    futures::select! {
+        result = future.fuse() => Ok(result),
+        () = sleep(duration).fuse() => Err(TimeoutError),
+    }
+}
+
 /// Error returned when a timeout expires.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TimeoutError;
@@ -436,6 +563,46 @@ mod tests {
         assert_eq!(err.to_string(), "operation timed out");
     }
 
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn test_sleep_until_and_timeout() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let deadline = start + Duration::from_millis(20);
+        sleep_until(deadline).await;
+        assert!(Instant::now() >= deadline);
+
+        // A deadline in the past returns immediately.
+        sleep_until(start).await;
+
+        let slow = timeout(Duration::from_millis(10), sleep(Duration::from_secs(5))).await;
+        assert_eq!(slow, Err(TimeoutError));
+        let fast = timeout(Duration::from_secs(5), async { 7 }).await;
+        assert_eq!(fast, Ok(7));
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn test_interval_ticks_and_skips_missed() {
+        use std::time::{Duration, Instant};
+
+        let period = Duration::from_millis(10);
+        let mut ticker = interval(period);
+        let first = ticker.tick().await;
+        let second = ticker.tick().await;
+        assert_eq!(second - first, period);
+
+        // Fall several periods behind: the next tick fires once, then the
+        // schedule resumes a full period later instead of bursting.
+        sleep(period * 5).await;
+        let late = ticker.tick().await;
+        let resumed = Instant::now();
+        let next = ticker.tick().await;
+        assert_eq!(late, second + period);
+        assert!(next + Duration::from_millis(1) >= resumed + period);
+    }
+
     /// Test that `BufReader` doesn't duplicate data when futures are cancelled.
     ///
     /// This is a regression test for a bug where cancelling a `read_line` future
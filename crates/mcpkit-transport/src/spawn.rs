@@ -37,16 +37,86 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Supervision
+//!
+//! By default a crashed server simply closes the transport. The builder can
+//! instead restart it with backoff, check that each new process answers a
+//! `ping` before it is used, forward its stderr into `tracing`, and report
+//! every exit:
+//!
+//! ```no_run
+//! use mcpkit_transport::spawn::SpawnedTransport;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), mcpkit_transport::TransportError> {
+//! let transport = SpawnedTransport::builder("my-mcp-server")
+//!     .auto_restart(true)
+//!     .max_restarts(3)
+//!     .health_check(Duration::from_secs(5))
+//!     .capture_stderr()
+//!     .on_exit(|status| eprintln!("server exited: {status}"))
+//!     .spawn()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Process Controls
+//!
+//! The child's stderr can be inherited, discarded, logged, or appended to a
+//! file, and the child can run in its own process group. Resource limits are
+//! enforced with `setrlimit` on Unix and a job object on Windows; on Unix the
+//! child can also drop to another user and group:
+//!
+//! ```no_run
+//! use mcpkit_transport::spawn::{ResourceLimits, ShutdownSignal, SpawnedTransport, StderrTarget};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), mcpkit_transport::TransportError> {
+//! let transport = SpawnedTransport::builder("my-mcp-server")
+//!     .env_clear()
+//!     .env("PATH", "/usr/bin")
+//!     .current_dir("/srv/mcp")
+//!     .stderr(StderrTarget::File("/var/log/mcp-server.log".into()))
+//!     .process_group(true)
+//!     .resource_limits(
+//!         ResourceLimits::new()
+//!             .max_memory(512 * 1024 * 1024)
+//!             .max_open_files(256),
+//!     )
+//!     .shutdown_signal(ShutdownSignal::Terminate)
+//!     .shutdown_timeout(Duration::from_secs(10))
+//!     .spawn()
+//!     .await?;
+//!
+//! // Signal the server, give it time to exit, then kill it.
+//! let status = transport.shutdown().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A restart is reported to the reader as
+//! [`TransportError::Reconnected`], and [`connection_epoch`](Transport::connection_epoch)
+//! is bumped, so `mcpkit-client` re-initializes the session with the new
+//! process.
 
+// Without a runtime (e.g. the `wasm` feature) SpawnedTransport is compiled out.
+#![cfg_attr(not(feature = "tokio-runtime"), allow(dead_code, unused_imports))]
+use crate::codec;
 use crate::error::TransportError;
+use crate::limits::{LimitEnforcer, TransportLimits};
 use crate::runtime::AsyncMutex;
 use crate::traits::{Transport, TransportMetadata};
+use crate::websocket::ExponentialBackoff;
 use futures::io::AsyncWriteExt;
-use mcpkit_core::protocol::Message;
+use mcpkit_core::protocol::{Message, Request, RequestId};
 use std::ffi::OsStr;
 use std::path::PathBuf;
-use std::process::Stdio;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError};
+use std::time::Duration;
 
 #[cfg(feature = "tokio-runtime")]
 use crate::runtime::{TokioAsyncReadWrapper, TokioAsyncWriteWrapper};
@@ -54,6 +124,139 @@ use crate::runtime::{TokioAsyncReadWrapper, TokioAsyncWriteWrapper};
 /// Maximum allowed message size (16 MB).
 pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
 
+/// Default for [`SpawnedTransportBuilder::max_restarts`].
+pub const DEFAULT_MAX_RESTARTS: u32 = 5;
+
+/// How long a child that closed stdout gets to exit before it is killed.
+const EXIT_GRACE: Duration = Duration::from_secs(2);
+
+/// Default for [`SpawnedTransportBuilder::shutdown_timeout`].
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Request id of the health-check `ping`.
+const HEALTH_CHECK_ID: &str = "mcpkit-health-check";
+
+/// Callback receiving the exit status of each child.
+type ExitCallback = Arc<dyn Fn(ExitStatus) + Send + Sync>;
+
+#[cfg(feature = "tokio-runtime")]
+type ChildWriter = TokioAsyncWriteWrapper<tokio::process::ChildStdin>;
+
+#[cfg(feature = "tokio-runtime")]
+type ChildReader = crate::runtime::BufReader<TokioAsyncReadWrapper<tokio::process::ChildStdout>>;
+
+/// A running child and its pipes.
+#[cfg(feature = "tokio-runtime")]
+struct Process {
+    stdin: ChildWriter,
+    stdout: ChildReader,
+    child: tokio::process::Child,
+    metadata: TransportMetadata,
+    /// Job object enforcing resource limits; closing it may kill the child.
+    #[cfg(windows)]
+    job: Option<sandbox::Job>,
+}
+
+/// Where the child's stderr goes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StderrTarget {
+    /// Share the parent's stderr (the default).
+    #[default]
+    Inherit,
+    /// Discard it.
+    Null,
+    /// Log each line to `tracing` at `INFO` under the
+    /// `mcpkit_transport::spawn::stderr` target.
+    Tracing,
+    /// Append it to a file, created if missing.
+    File(PathBuf),
+}
+
+/// How [`SpawnedTransport::shutdown`] asks the child to exit.
+///
+/// Windows has no equivalent of these signals, so there the child is
+/// terminated immediately whatever the setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShutdownSignal {
+    /// `SIGTERM` (the default).
+    #[default]
+    Terminate,
+    /// `SIGINT`, as if the user pressed Ctrl-C.
+    Interrupt,
+    /// `SIGHUP`.
+    Hangup,
+    /// `SIGKILL`: no grace period.
+    Kill,
+}
+
+/// Limits applied to each spawned child.
+///
+/// On Unix these become `setrlimit` calls made in the child before `exec`;
+/// on Windows the child is placed in a job object. A limit a platform cannot
+/// enforce is ignored there, as noted on each setter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum address space (Unix) or committed memory (Windows), in bytes.
+    pub max_memory: Option<u64>,
+    /// Maximum CPU time.
+    pub max_cpu_time: Option<Duration>,
+    /// Maximum open file descriptors. Unix only.
+    pub max_open_files: Option<u64>,
+    /// Maximum processes: for the child's user on Unix, for the child and
+    /// its descendants on Windows.
+    pub max_processes: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// No limits.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_memory: None,
+            max_cpu_time: None,
+            max_open_files: None,
+            max_processes: None,
+        }
+    }
+
+    /// Limit memory to `bytes`.
+    #[must_use]
+    pub const fn max_memory(mut self, bytes: u64) -> Self {
+        self.max_memory = Some(bytes);
+        self
+    }
+
+    /// Limit CPU time. Unix rounds up to whole seconds.
+    #[must_use]
+    pub const fn max_cpu_time(mut self, time: Duration) -> Self {
+        self.max_cpu_time = Some(time);
+        self
+    }
+
+    /// Limit open file descriptors. Ignored on Windows.
+    #[must_use]
+    pub const fn max_open_files(mut self, count: u64) -> Self {
+        self.max_open_files = Some(count);
+        self
+    }
+
+    /// Limit the number of processes.
+    #[must_use]
+    pub const fn max_processes(mut self, count: u64) -> Self {
+        self.max_processes = Some(count);
+        self
+    }
+
+    /// Whether no limit is set.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.max_memory.is_none()
+            && self.max_cpu_time.is_none()
+            && self.max_open_files.is_none()
+            && self.max_processes.is_none()
+    }
+}
+
 /// A transport connected to a spawned subprocess via stdio.
 ///
 /// This transport spawns a child process and communicates with it via
@@ -62,12 +265,19 @@ pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
 ///
 /// # Lifecycle
 ///
-/// The child process is spawned with `kill_on_drop`, so if the transport is
-/// dropped without an explicit shutdown the child is killed (SIGKILL) rather
-/// than orphaned. For an orderly shutdown, call `kill()` to terminate the child
-/// explicitly; a well-behaved server also exits when its stdin is closed as the
-/// transport is dropped. `close()` marks the transport disconnected but does
-/// not by itself terminate the child.
+/// By default the child process is spawned with `kill_on_drop`, so if the
+/// transport is dropped without an explicit shutdown the child is killed
+/// (SIGKILL) rather than orphaned; see
+/// [`kill_on_drop`](SpawnedTransportBuilder::kill_on_drop). For an orderly
+/// shutdown, call [`shutdown`](Self::shutdown), which sends the configured
+/// [`ShutdownSignal`] and kills the child if it outlives the shutdown
+/// timeout, or `kill()` to terminate it at once. `close()` marks the
+/// transport disconnected but does not by itself terminate the child.
+///
+/// When the child exits, [`recv`](Transport::recv) sees end of stream. With
+/// [`auto_restart`](SpawnedTransportBuilder::auto_restart) enabled, it then
+/// restarts the child and returns [`TransportError::Reconnected`] instead of
+/// `None`; `kill()` and `close()` stop any further restarts.
 ///
 /// # Example
 ///
@@ -84,13 +294,21 @@ pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
 /// ```
 #[cfg(feature = "tokio-runtime")]
 pub struct SpawnedTransport {
-    stdin: AsyncMutex<TokioAsyncWriteWrapper<tokio::process::ChildStdin>>,
-    stdout:
-        AsyncMutex<crate::runtime::BufReader<TokioAsyncReadWrapper<tokio::process::ChildStdout>>>,
+    stdin: AsyncMutex<ChildWriter>,
+    stdout: AsyncMutex<ChildReader>,
     child: AsyncMutex<tokio::process::Child>,
     connected: AtomicBool,
-    metadata: TransportMetadata,
+    metadata: std::sync::Mutex<TransportMetadata>,
     command: String,
+    /// Spawn settings, kept to restart the child.
+    config: SpawnedTransportBuilder,
+    /// Restart attempts made so far, successful or not.
+    restarts: AtomicU32,
+    /// Successful restarts, reported as the connection epoch.
+    epoch: AtomicU64,
+    /// Set by `kill()` and `close()`: the child is no longer restarted.
+    stopped: AtomicBool,
+    limits: LimitEnforcer,
 }
 
 #[cfg(feature = "tokio-runtime")]
@@ -166,6 +384,12 @@ impl SpawnedTransport {
         &self.command
     }
 
+    /// How many times the child has been restarted.
+    #[must_use]
+    pub fn restart_count(&self) -> u64 {
+        self.epoch.load(Ordering::SeqCst)
+    }
+
     /// Check if the child process is still running.
     pub async fn is_running(&self) -> bool {
         let mut child = self.child.lock().await;
@@ -183,44 +407,94 @@ impl SpawnedTransport {
 
     /// Kill the child process forcefully.
     ///
-    /// This sends SIGKILL on Unix and `TerminateProcess` on Windows.
+    /// This sends SIGKILL on Unix and `TerminateProcess` on Windows. The
+    /// child is not restarted afterwards.
     pub async fn kill(&self) -> Result<(), TransportError> {
+        self.stopped.store(true, Ordering::SeqCst);
         let mut child = self.child.lock().await;
         child.kill().await.map_err(TransportError::from)
     }
-}
-
-#[cfg(feature = "tokio-runtime")]
-impl Transport for SpawnedTransport {
-    type Error = TransportError;
 
-    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
-        if !self.is_connected() {
-            return Err(TransportError::NotConnected);
+    /// Stop the child gracefully.
+    ///
+    /// Sends the configured [`ShutdownSignal`] (to the whole process group
+    /// with [`process_group`](SpawnedTransportBuilder::process_group)), waits
+    /// up to the [`shutdown_timeout`](SpawnedTransportBuilder::shutdown_timeout)
+    /// for the child to exit, then kills it. The child is not restarted
+    /// afterwards and the transport is disconnected.
+    pub async fn shutdown(&self) -> Result<ExitStatus, TransportError> {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.connected.store(false, Ordering::SeqCst);
+        let mut child = self.child.lock().await;
+        if let Some(pid) = child.id() {
+            let signal = self.config.shutdown_signal;
+            if let Err(e) = sandbox::signal(pid, signal, self.config.process_group) {
+                tracing::debug!(command = %self.command, error = %e, "Failed to signal spawned MCP server");
+            }
         }
-
-        let json = serde_json::to_string(&msg)?;
-
-        if json.len() > MAX_MESSAGE_SIZE {
-            return Err(TransportError::MessageTooLarge {
-                size: json.len(),
-                max: MAX_MESSAGE_SIZE,
-            });
+        if let Ok(status) = tokio::time::timeout(self.config.shutdown_timeout, child.wait()).await {
+            return status.map_err(TransportError::from);
         }
-
-        let mut stdin = self.stdin.lock().await;
-        stdin.write_all(json.as_bytes()).await?;
-        stdin.write_all(b"\n").await?;
-        stdin.flush().await?;
-
-        Ok(())
+        tracing::warn!(
+            command = %self.command,
+            timeout = ?self.config.shutdown_timeout,
+            "Spawned MCP server ignored the shutdown signal, killing it"
+        );
+        child.kill().await?;
+        child.wait().await.map_err(TransportError::from)
     }
 
-    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
-        if !self.is_connected() {
-            return Err(TransportError::NotConnected);
+    /// Handle the child closing stdout: report its exit, then restart it if
+    /// supervision allows. Returns the attempts a successful restart took.
+    async fn on_child_exit(&self, stdout: &mut ChildReader) -> Option<u32> {
+        let restart = self.config.auto_restart && !self.stopped.load(Ordering::SeqCst);
+        if restart || self.config.on_exit.is_some() {
+            if let Some(status) = self.reap().await {
+                tracing::warn!(command = %self.command, %status, "Spawned MCP server exited");
+                if let Some(on_exit) = &self.config.on_exit {
+                    on_exit(status);
+                }
+            }
+        }
+        if !restart {
+            return None;
         }
 
+        let mut attempt = 0;
+        loop {
+            if self.restarts.fetch_add(1, Ordering::SeqCst) >= self.config.max_restarts {
+                tracing::error!(
+                    command = %self.command,
+                    max_restarts = self.config.max_restarts,
+                    "Spawned MCP server exhausted its restarts"
+                );
+                return None;
+            }
+            tokio::time::sleep(self.config.backoff.delay_for_attempt(attempt)).await;
+            attempt += 1;
+            if self.stopped.load(Ordering::SeqCst) {
+                return None;
+            }
+            match self.config.launch().await {
+                Ok(process) => {
+                    *stdout = process.stdout;
+                    *self.stdin.lock().await = process.stdin;
+                    *self.child.lock().await = process.child;
+                    *self.metadata.lock().unwrap_or_else(PoisonError::into_inner) =
+                        process.metadata;
+                    self.epoch.fetch_add(1, Ordering::SeqCst);
+                    tracing::info!(command = %self.command, attempt, "Restarted spawned MCP server");
+                    return Some(attempt);
+                }
+                Err(e) => {
+                    tracing::warn!(command = %self.command, attempt, error = %e, "Failed to restart spawned MCP server");
+                }
+            }
+        }
+    }
+
+    /// Read the next message from the child's stdout.
+    async fn read_message(&self) -> Result<Option<Message>, TransportError> {
         let mut stdout = self.stdout.lock().await;
 
         loop {
@@ -229,16 +503,14 @@ impl Transport for SpawnedTransport {
 
             if bytes_read == 0 {
                 // EOF - child process closed stdout
+                if let Some(attempts) = self.on_child_exit(&mut stdout).await {
+                    return Err(TransportError::Reconnected { attempts });
+                }
                 self.connected.store(false, Ordering::SeqCst);
                 return Ok(None);
             }
 
-            if line.len() > MAX_MESSAGE_SIZE {
-                return Err(TransportError::MessageTooLarge {
-                    size: line.len(),
-                    max: MAX_MESSAGE_SIZE,
-                });
-            }
+            self.limits.limits().check_message_size(line.len())?;
 
             let trimmed = line.trim();
             if trimmed.is_empty() {
@@ -249,17 +521,58 @@ impl Transport for SpawnedTransport {
             let preview: String = trimmed.chars().take(100).collect();
             tracing::debug!(raw_line_len = line.len(), preview = %preview, "SpawnedTransport read line from stdout");
 
-            let msg: Message = serde_json::from_str(trimmed)?;
-            return Ok(Some(msg));
+            // The surrounding whitespace is valid JSON; hand over the whole line
+            // to reuse its allocation.
+            return codec::decode(bytes::Bytes::from(line)).map(Some);
         }
     }
 
+    /// The exit status of a child that closed stdout, killing it if it does
+    /// not exit on its own.
+    async fn reap(&self) -> Option<ExitStatus> {
+        let mut child = self.child.lock().await;
+        if let Ok(status) = tokio::time::timeout(EXIT_GRACE, child.wait()).await {
+            return status.ok();
+        }
+        let _ = child.kill().await;
+        child.wait().await.ok()
+    }
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl Transport for SpawnedTransport {
+    type Error = TransportError;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        if !self.is_connected() {
+            return Err(TransportError::NotConnected);
+        }
+
+        let json = serde_json::to_string(&msg)?;
+        self.limits.send(&msg, json.len())?;
+
+        let mut stdin = self.stdin.lock().await;
+        stdin.write_all(json.as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+        stdin.flush().await?;
+
+        Ok(())
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        if !self.is_connected() {
+            return Err(TransportError::NotConnected);
+        }
+        self.limits.recv(self.read_message()).await
+    }
+
     async fn close(&self) -> Result<(), Self::Error> {
         self.connected.store(false, Ordering::SeqCst);
+        self.stopped.store(true, Ordering::SeqCst);
 
         // Mark disconnected only. We don't force-kill here: the child receives
         // EOF on stdin and SIGKILL via `kill_on_drop` once the transport is
-        // dropped. Call `kill()` for immediate, deterministic termination.
+        // dropped. Call `shutdown()` or `kill()` for deterministic termination.
 
         Ok(())
     }
@@ -269,16 +582,24 @@ impl Transport for SpawnedTransport {
     }
 
     fn metadata(&self) -> TransportMetadata {
-        self.metadata.clone()
+        self.metadata
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn connection_epoch(&self) -> u64 {
+        self.epoch.load(Ordering::SeqCst)
     }
 }
 
 // Note: We don't implement Drop manually because async operations aren't
 // possible in Drop. Cleanup is instead handled by `kill_on_drop(true)` on the
-// spawned Command: when SpawnedTransport (and its Child handle) is dropped,
-// tokio kills the child with SIGKILL so it is not orphaned. The stdin handle is
-// also dropped, sending EOF, which lets a well-behaved server exit on its own
-// before the SIGKILL is needed. Call kill() for deterministic termination.
+// spawned Command (unless disabled on the builder): when SpawnedTransport (and
+// its Child handle) is dropped, tokio kills the child with SIGKILL so it is not
+// orphaned. The stdin handle is also dropped, sending EOF, which lets a
+// well-behaved server exit on its own before the SIGKILL is needed. Call
+// shutdown() or kill() for deterministic termination.
 
 /// Builder for creating spawned transports with custom configuration.
 ///
@@ -303,8 +624,25 @@ pub struct SpawnedTransportBuilder {
     program: PathBuf,
     args: Vec<String>,
     envs: Vec<(String, String)>,
+    removed_envs: Vec<String>,
     current_dir: Option<PathBuf>,
     clear_env: bool,
+    auto_restart: bool,
+    max_restarts: u32,
+    backoff: ExponentialBackoff,
+    health_check: Option<Duration>,
+    stderr: StderrTarget,
+    on_exit: Option<ExitCallback>,
+    limits: TransportLimits,
+    kill_on_drop: bool,
+    process_group: bool,
+    shutdown_signal: ShutdownSignal,
+    shutdown_timeout: Duration,
+    resource_limits: ResourceLimits,
+    #[cfg(unix)]
+    uid: Option<u32>,
+    #[cfg(unix)]
+    gid: Option<u32>,
 }
 
 #[cfg(feature = "tokio-runtime")]
@@ -316,8 +654,25 @@ impl SpawnedTransportBuilder {
             program: PathBuf::from(program.as_ref()),
             args: Vec::new(),
             envs: Vec::new(),
+            removed_envs: Vec::new(),
             current_dir: None,
             clear_env: false,
+            auto_restart: false,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            backoff: ExponentialBackoff::default(),
+            health_check: None,
+            stderr: StderrTarget::Inherit,
+            on_exit: None,
+            limits: TransportLimits::new().with_max_message_size(MAX_MESSAGE_SIZE),
+            kill_on_drop: true,
+            process_group: false,
+            shutdown_signal: ShutdownSignal::Terminate,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            resource_limits: ResourceLimits::new(),
+            #[cfg(unix)]
+            uid: None,
+            #[cfg(unix)]
+            gid: None,
         }
     }
 
@@ -365,6 +720,15 @@ impl SpawnedTransportBuilder {
         self
     }
 
+    /// Remove an inherited environment variable.
+    #[must_use]
+    pub fn env_remove<K: AsRef<str>>(mut self, key: K) -> Self {
+        let key = key.as_ref();
+        self.envs.retain(|(k, _)| k != key);
+        self.removed_envs.push(key.to_string());
+        self
+    }
+
     /// Set the working directory for the child process.
     #[must_use]
     pub fn working_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
@@ -372,6 +736,15 @@ impl SpawnedTransportBuilder {
         self
     }
 
+    /// Set the working directory for the child process.
+    ///
+    /// Same as [`working_dir`](Self::working_dir), named after
+    /// [`std::process::Command::current_dir`].
+    #[must_use]
+    pub fn current_dir<P: Into<PathBuf>>(self, dir: P) -> Self {
+        self.working_dir(dir)
+    }
+
     /// Clear the environment variables before adding new ones.
     ///
     /// By default, the child inherits the parent's environment.
@@ -381,28 +754,207 @@ impl SpawnedTransportBuilder {
         self
     }
 
+    /// Clear the environment variables before adding new ones.
+    ///
+    /// Same as [`clear_env`](Self::clear_env), named after
+    /// [`std::process::Command::env_clear`].
+    #[must_use]
+    pub const fn env_clear(self) -> Self {
+        self.clear_env()
+    }
+
+    /// Restart the child when it exits (default: off).
+    ///
+    /// Restarts wait according to the [`restart_backoff`](Self::restart_backoff),
+    /// up to [`max_restarts`](Self::max_restarts) attempts over the
+    /// transport's lifetime.
+    #[must_use]
+    pub const fn auto_restart(mut self, enabled: bool) -> Self {
+        self.auto_restart = enabled;
+        self
+    }
+
+    /// Maximum restart attempts, successful or not, over the transport's
+    /// lifetime (default: [`DEFAULT_MAX_RESTARTS`]).
+    #[must_use]
+    pub const fn max_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Delay before each restart attempt; the attempt count resets once a
+    /// restart succeeds.
+    #[must_use]
+    pub const fn restart_backoff(mut self, backoff: ExponentialBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Require each new process to answer a `ping` within `timeout` before
+    /// it is used (default: off).
+    ///
+    /// Any response to the ping counts, including an error. A process that
+    /// fails the check is killed: the spawn fails, or the restart attempt
+    /// counts as failed.
+    #[must_use]
+    pub const fn health_check(mut self, timeout: Duration) -> Self {
+        self.health_check = Some(timeout);
+        self
+    }
+
+    /// Forward the child's stderr, line by line, to `tracing` at `INFO`
+    /// under the `mcpkit_transport::spawn::stderr` target.
+    ///
+    /// Shorthand for `.stderr(StderrTarget::Tracing)`. By default stderr is
+    /// inherited from the parent process.
+    #[must_use]
+    pub fn capture_stderr(self) -> Self {
+        self.stderr(StderrTarget::Tracing)
+    }
+
+    /// Choose where the child's stderr goes (default:
+    /// [`StderrTarget::Inherit`]).
+    #[must_use]
+    pub fn stderr(mut self, target: StderrTarget) -> Self {
+        self.stderr = target;
+        self
+    }
+
+    /// Kill the child when the transport is dropped (default: on).
+    ///
+    /// Turn this off to leave a server running after the client exits.
+    #[must_use]
+    pub const fn kill_on_drop(mut self, enabled: bool) -> Self {
+        self.kill_on_drop = enabled;
+        self
+    }
+
+    /// Start the child in a new process group (default: off).
+    ///
+    /// The child then does not receive signals aimed at the parent's group,
+    /// such as Ctrl-C in a terminal, and [`SpawnedTransport::shutdown`]
+    /// signals the whole group, reaching processes the server started. On
+    /// Windows this uses `CREATE_NEW_PROCESS_GROUP`.
+    #[must_use]
+    pub const fn process_group(mut self, enabled: bool) -> Self {
+        self.process_group = enabled;
+        self
+    }
+
+    /// Signal sent by [`SpawnedTransport::shutdown`] (default:
+    /// [`ShutdownSignal::Terminate`]).
+    #[must_use]
+    pub const fn shutdown_signal(mut self, signal: ShutdownSignal) -> Self {
+        self.shutdown_signal = signal;
+        self
+    }
+
+    /// How long [`SpawnedTransport::shutdown`] waits after signalling before
+    /// killing the child (default: [`DEFAULT_SHUTDOWN_TIMEOUT`]).
+    #[must_use]
+    pub const fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Apply resource limits to the child (default: none).
+    #[must_use]
+    pub const fn resource_limits(mut self, limits: ResourceLimits) -> Self {
+        self.resource_limits = limits;
+        self
+    }
+
+    /// Run the child as user `uid`. Requires the privilege to switch users.
+    #[cfg(unix)]
+    #[must_use]
+    pub const fn uid(mut self, uid: u32) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+
+    /// Run the child with group `gid`. Requires the privilege to switch groups.
+    #[cfg(unix)]
+    #[must_use]
+    pub const fn gid(mut self, gid: u32) -> Self {
+        self.gid = Some(gid);
+        self
+    }
+
+    /// Call `on_exit` with the exit status each time the child exits while
+    /// the transport is reading from it.
+    #[must_use]
+    pub fn on_exit(mut self, on_exit: impl Fn(ExitStatus) + Send + Sync + 'static) -> Self {
+        self.on_exit = Some(Arc::new(on_exit));
+        self
+    }
+
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn limits(mut self, limits: TransportLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Spawn the process and create the transport.
     ///
     /// # Errors
     ///
-    /// Returns an error if the process could not be spawned.
+    /// Returns an error if the process could not be spawned or failed its
+    /// health check.
     pub async fn spawn(self) -> Result<SpawnedTransport, TransportError> {
+        let process = self.launch().await?;
+        Ok(SpawnedTransport {
+            stdin: AsyncMutex::new(process.stdin),
+            stdout: AsyncMutex::new(process.stdout),
+            child: AsyncMutex::new(process.child),
+            connected: AtomicBool::new(true),
+            metadata: std::sync::Mutex::new(process.metadata),
+            command: format!("{} {}", self.program.display(), self.args.join(" ")),
+            limits: LimitEnforcer::new(self.limits.clone()),
+            config: self,
+            restarts: AtomicU32::new(0),
+            epoch: AtomicU64::new(0),
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    /// Start a child process and run its health check.
+    async fn launch(&self) -> Result<Process, TransportError> {
         let mut command = tokio::process::Command::new(&self.program);
 
+        let stderr = match &self.stderr {
+            // Let stderr pass through for debugging unless redirected
+            StderrTarget::Inherit => Stdio::inherit(),
+            StderrTarget::Null => Stdio::null(),
+            StderrTarget::Tracing => Stdio::piped(),
+            StderrTarget::File(path) => std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map(Stdio::from)
+                .map_err(|e| TransportError::Connection {
+                    message: format!("Failed to open stderr file '{}': {e}", path.display()),
+                })?,
+        };
+
         command
             .args(&self.args)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::inherit()) // Let stderr pass through for debugging
+            .stderr(stderr)
             // Safety net: if the transport (and its Child handle) is dropped
             // without an explicit kill(), tokio sends SIGKILL so the child is
             // not orphaned.
-            .kill_on_drop(true);
+            .kill_on_drop(self.kill_on_drop);
 
         if self.clear_env {
             command.env_clear();
         }
 
+        for key in &self.removed_envs {
+            command.env_remove(key);
+        }
+
         for (key, value) in &self.envs {
             command.env(key, value);
         }
@@ -411,6 +963,24 @@ impl SpawnedTransportBuilder {
             command.current_dir(dir);
         }
 
+        #[cfg(unix)]
+        {
+            if self.process_group {
+                command.process_group(0);
+            }
+            if let Some(uid) = self.uid {
+                command.uid(uid);
+            }
+            if let Some(gid) = self.gid {
+                command.gid(gid);
+            }
+            sandbox::set_rlimits(&mut command, &self.resource_limits);
+        }
+        #[cfg(windows)]
+        if self.process_group {
+            command.creation_flags(sandbox::CREATE_NEW_PROCESS_GROUP);
+        }
+
         let mut child = command.spawn().map_err(|e| TransportError::Connection {
             message: format!(
                 "Failed to spawn process '{}': {}",
@@ -430,25 +1000,270 @@ impl SpawnedTransportBuilder {
                 message: "Failed to capture child stdout".to_string(),
             })?;
 
-        let command_str = format!("{} {}", self.program.display(), self.args.join(" "));
-
         let pid = child
             .id()
             .map_or_else(|| "unknown".to_string(), |id| id.to_string());
 
-        Ok(SpawnedTransport {
-            stdin: AsyncMutex::new(TokioAsyncWriteWrapper(stdin)),
-            stdout: AsyncMutex::new(crate::runtime::BufReader::new(TokioAsyncReadWrapper(
-                stdout,
-            ))),
-            child: AsyncMutex::new(child),
-            connected: AtomicBool::new(true),
+        // Assign the job before anything else can go wrong, so a failed
+        // spawn does not leave an unconfined child behind.
+        #[cfg(windows)]
+        let job = if self.resource_limits.is_empty() {
+            None
+        } else {
+            match sandbox::Job::confine(&child, &self.resource_limits, self.kill_on_drop) {
+                Ok(job) => Some(job),
+                Err(e) => {
+                    let _ = child.kill().await;
+                    return Err(TransportError::Connection {
+                        message: format!("Failed to apply resource limits: {e}"),
+                    });
+                }
+            }
+        };
+
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_stderr(stderr, pid.clone()));
+        }
+
+        let mut process = Process {
+            stdin: TokioAsyncWriteWrapper(stdin),
+            stdout: crate::runtime::BufReader::new(TokioAsyncReadWrapper(stdout))
+                .with_max_message_size(self.limits.max_message_size),
+            child,
             metadata: TransportMetadata::new("spawned-stdio")
                 .remote_addr(format!("pid:{pid}"))
                 .local_addr("parent")
                 .connected_now(),
-            command: command_str,
-        })
+            #[cfg(windows)]
+            job,
+        };
+
+        if let Some(timeout) = self.health_check {
+            if let Err(e) = health_check(&mut process, timeout).await {
+                let _ = process.child.kill().await;
+                return Err(e);
+            }
+        }
+
+        Ok(process)
+    }
+}
+
+/// Send a `ping` and wait for the matching response.
+#[cfg(feature = "tokio-runtime")]
+async fn health_check(process: &mut Process, timeout: Duration) -> Result<(), TransportError> {
+    let ping = Message::Request(Request::new("ping", HEALTH_CHECK_ID));
+    let json = serde_json::to_string(&ping)?;
+    let check = async {
+        process.stdin.write_all(json.as_bytes()).await?;
+        process.stdin.write_all(b"\n").await?;
+        process.stdin.flush().await?;
+        loop {
+            let mut line = String::new();
+            if process.stdout.read_line(&mut line).await? == 0 {
+                return Err(TransportError::Connection {
+                    message: "process exited before answering the health check".to_string(),
+                });
+            }
+            if let Ok(Message::Response(response)) = serde_json::from_str(line.trim()) {
+                if response.id == RequestId::from(HEALTH_CHECK_ID) {
+                    return Ok(());
+                }
+            }
+        }
+    };
+    tokio::time::timeout(timeout, check)
+        .await
+        .map_err(|_| TransportError::Timeout {
+            operation: "spawned server health check".to_string(),
+            duration: timeout,
+        })?
+}
+
+/// Log each line the child writes to stderr.
+#[cfg(feature = "tokio-runtime")]
+async fn forward_stderr(stderr: tokio::process::ChildStderr, pid: String) {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut lines = tokio::io::BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        tracing::info!(target: "mcpkit_transport::spawn::stderr", %pid, "{line}");
+    }
+}
+
+/// Platform hooks: resource limits, process groups, and shutdown signals.
+#[cfg(feature = "tokio-runtime")]
+#[allow(unsafe_code)]
+mod sandbox {
+    use super::{ResourceLimits, ShutdownSignal};
+    use std::io;
+
+    /// Apply `limits` with `setrlimit` in the child before `exec`.
+    #[cfg(unix)]
+    pub(super) fn set_rlimits(command: &mut tokio::process::Command, limits: &ResourceLimits) {
+        #[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+        let rlimits: Vec<_> = [
+            (libc::RLIMIT_AS, limits.max_memory),
+            (
+                libc::RLIMIT_CPU,
+                limits
+                    .max_cpu_time
+                    .map(|t| t.as_secs() + u64::from(t.subsec_nanos() > 0)),
+            ),
+            (libc::RLIMIT_NOFILE, limits.max_open_files),
+            (libc::RLIMIT_NPROC, limits.max_processes),
+        ]
+        .into_iter()
+        .filter_map(|(resource, value)| value.map(|v| (resource, v as libc::rlim_t)))
+        .collect();
+        if rlimits.is_empty() {
+            return;
+        }
+        // SAFETY: the closure runs between fork and exec, where only
+        // async-signal-safe functions may be called. It only reads the
+        // already-built vector and calls `setrlimit`, which is
+        // async-signal-safe; it does not allocate.
+        unsafe {
+            command.pre_exec(move || {
+                for &(resource, value) in &rlimits {
+                    let limit = libc::rlimit {
+                        rlim_cur: value,
+                        rlim_max: value,
+                    };
+                    if libc::setrlimit(resource, &raw const limit) != 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+    }
+
+    /// Send `signal` to the child, or to its process group.
+    #[cfg(unix)]
+    pub(super) fn signal(pid: u32, signal: ShutdownSignal, group: bool) -> io::Result<()> {
+        let signal = match signal {
+            ShutdownSignal::Terminate => libc::SIGTERM,
+            ShutdownSignal::Interrupt => libc::SIGINT,
+            ShutdownSignal::Hangup => libc::SIGHUP,
+            ShutdownSignal::Kill => libc::SIGKILL,
+        };
+        let pid = libc::pid_t::try_from(pid)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "pid out of range"))?;
+        // A process group started with `process_group(0)` has the child's pid
+        // as its id; a negative pid addresses the group.
+        let target = if group { -pid } else { pid };
+        // SAFETY: `kill` has no memory-safety preconditions.
+        if unsafe { libc::kill(target, signal) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Windows has no shutdown signals; the caller falls back to killing.
+    #[cfg(not(unix))]
+    pub(super) fn signal(_pid: u32, _signal: ShutdownSignal, _group: bool) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "shutdown signals are not supported on this platform",
+        ))
+    }
+
+    #[cfg(windows)]
+    pub(super) use windows_sys::Win32::System::Threading::CREATE_NEW_PROCESS_GROUP;
+
+    /// A job object holding a child, closed on drop.
+    #[cfg(windows)]
+    pub(super) struct Job(windows_sys::Win32::Foundation::HANDLE);
+
+    // SAFETY: job object handles may be used and closed from any thread.
+    #[cfg(windows)]
+    unsafe impl Send for Job {}
+    // SAFETY: as above; the handle is only read after construction.
+    #[cfg(windows)]
+    unsafe impl Sync for Job {}
+
+    #[cfg(windows)]
+    impl Job {
+        /// Create a job enforcing `limits` and assign `child` to it. With
+        /// `kill_on_close`, dropping the job kills the child and its
+        /// descendants.
+        pub(super) fn confine(
+            child: &tokio::process::Child,
+            limits: &ResourceLimits,
+            kill_on_close: bool,
+        ) -> io::Result<Self> {
+            use windows_sys::Win32::System::JobObjects::{
+                AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_LIMIT_ACTIVE_PROCESS,
+                JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+                JOB_OBJECT_LIMIT_PROCESS_TIME, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+                JobObjectExtendedLimitInformation, SetInformationJobObject,
+            };
+
+            let process = child
+                .raw_handle()
+                .ok_or_else(|| io::Error::other("child has already exited"))?;
+
+            // SAFETY: both arguments may be null: default security, no name.
+            let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+            if handle.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            let job = Self(handle);
+
+            // SAFETY: the struct is plain data for which all-zero is valid.
+            let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+            let basic = &mut info.BasicLimitInformation;
+            if kill_on_close {
+                basic.LimitFlags |= JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+            }
+            if let Some(bytes) = limits.max_memory {
+                basic.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+                info.ProcessMemoryLimit = usize::try_from(bytes).unwrap_or(usize::MAX);
+            }
+            if let Some(time) = limits.max_cpu_time {
+                basic.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+                // In 100-nanosecond units.
+                basic.PerProcessUserTimeLimit =
+                    i64::try_from(time.as_nanos() / 100).unwrap_or(i64::MAX);
+            }
+            if let Some(count) = limits.max_processes {
+                basic.LimitFlags |= JOB_OBJECT_LIMIT_ACTIVE_PROCESS;
+                basic.ActiveProcessLimit = u32::try_from(count).unwrap_or(u32::MAX);
+            }
+
+            // SAFETY: `info` is a valid JOBOBJECT_EXTENDED_LIMIT_INFORMATION
+            // of the stated size, and `job.0` is an open job handle.
+            let set = unsafe {
+                SetInformationJobObject(
+                    job.0,
+                    JobObjectExtendedLimitInformation,
+                    (&raw const info).cast(),
+                    u32::try_from(size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>())
+                        .unwrap_or(u32::MAX),
+                )
+            };
+            if set == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // SAFETY: `process` is the child's open process handle.
+            if unsafe { AssignProcessToJobObject(job.0, process.cast()) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(job)
+        }
+    }
+
+    #[cfg(windows)]
+    impl Drop for Job {
+        fn drop(&mut self) {
+            // SAFETY: the handle was opened by `CreateJobObjectW` and is
+            // closed only here.
+            unsafe {
+                windows_sys::Win32::Foundation::CloseHandle(self.0);
+            }
+        }
     }
 }
 
@@ -514,6 +1329,145 @@ mod tests {
         );
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn crashed_child_is_restarted_up_to_max_restarts() {
+        let exits = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&exits);
+        let transport = SpawnedTransport::builder("sh")
+            .args(["-c", "exit 3"])
+            .auto_restart(true)
+            .max_restarts(2)
+            .restart_backoff(ExponentialBackoff::new(
+                Duration::from_millis(1),
+                Duration::from_millis(1),
+                1.0,
+            ))
+            .on_exit(move |status| seen.lock().unwrap().push(status.code()))
+            .spawn()
+            .await
+            .expect("spawn sh");
+
+        for expected in 1..=2 {
+            let err = transport.recv().await.unwrap_err();
+            assert!(
+                matches!(err, TransportError::Reconnected { attempts: 1 }),
+                "{err}"
+            );
+            assert_eq!(transport.connection_epoch(), expected);
+        }
+        assert!(transport.recv().await.unwrap().is_none());
+        assert!(!transport.is_connected());
+        assert_eq!(transport.restart_count(), 2);
+        assert_eq!(*exits.lock().unwrap(), [Some(3); 3]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn health_check_waits_for_ping_response() {
+        let reply = r#"{"jsonrpc":"2.0","id":"mcpkit-health-check","result":{}}"#;
+        let transport = SpawnedTransport::builder("sh")
+            .args(["-c", &format!("read line; echo '{reply}'; cat")])
+            .health_check(Duration::from_secs(5))
+            .capture_stderr()
+            .spawn()
+            .await
+            .expect("health check should pass");
+
+        // The ping and its response are not visible to the transport's user.
+        let msg = Message::Request(Request::new("tools/list", 1u64));
+        transport.send(msg).await.unwrap();
+        let echoed = transport.recv().await.unwrap().unwrap();
+        assert_eq!(echoed.method(), Some("tools/list"));
+        let _ = transport.kill().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unresponsive_child_fails_health_check() {
+        let result = SpawnedTransport::builder("sleep")
+            .arg("30")
+            .health_check(Duration::from_millis(50))
+            .spawn()
+            .await;
+        assert!(matches!(result, Err(TransportError::Timeout { .. })));
+
+        let result = SpawnedTransport::builder("sh")
+            .args(["-c", "read line"])
+            .health_check(Duration::from_secs(5))
+            .spawn()
+            .await;
+        assert!(matches!(result, Err(TransportError::Connection { .. })));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn environment_and_directory_reach_the_child() {
+        let dir = std::env::temp_dir();
+        let log = dir.join(format!("mcpkit-spawn-stderr-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&log);
+        let transport = SpawnedTransport::builder("/bin/sh")
+            .args([
+                "-c",
+                r#"echo "$KEEP ${DROP:-unset} $(pwd) $(ulimit -n)" >&2"#,
+            ])
+            .env_clear()
+            .env("KEEP", "kept")
+            .env("DROP", "set")
+            .env_remove("DROP")
+            .current_dir(&dir)
+            .stderr(StderrTarget::File(log.clone()))
+            .resource_limits(ResourceLimits::new().max_open_files(64))
+            .spawn()
+            .await
+            .expect("spawn sh");
+        assert!(transport.wait().await.unwrap().success());
+
+        let written = std::fs::read_to_string(&log).unwrap();
+        let _ = std::fs::remove_file(&log);
+        let dir = dir.canonicalize().unwrap();
+        assert_eq!(written.trim(), format!("kept unset {} 64", dir.display()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shutdown_signals_then_kills_after_the_timeout() {
+        let transport = SpawnedTransport::builder("sleep")
+            .arg("30")
+            .process_group(true)
+            .spawn()
+            .await
+            .expect("spawn sleep");
+        let status = transport.shutdown().await.unwrap();
+        assert_eq!(
+            std::os::unix::process::ExitStatusExt::signal(&status),
+            Some(libc::SIGTERM)
+        );
+        assert!(!transport.is_connected());
+
+        // A child that ignores SIGTERM is killed once the timeout passes.
+        let transport = SpawnedTransport::builder("sh")
+            .args(["-c", "trap '' TERM; echo ready; sleep 30"])
+            .shutdown_timeout(Duration::from_millis(100))
+            .spawn()
+            .await
+            .expect("spawn sh");
+        // Wait for the trap to be installed.
+        let mut line = String::new();
+        transport
+            .stdout
+            .lock()
+            .await
+            .read_line(&mut line)
+            .await
+            .unwrap();
+        let status = transport.shutdown().await.unwrap();
+        assert_eq!(
+            std::os::unix::process::ExitStatusExt::signal(&status),
+            Some(libc::SIGKILL)
+        );
+    }
+
     #[tokio::test]
     async fn test_spawn_and_communicate() {
         // Use cat as a simple echo server (it echoes stdin to stdout)
@@ -6,8 +6,25 @@
 //!
 //! # Wire Format
 //!
-//! Messages are newline-delimited JSON. Each JSON-RPC message is serialized
-//! as a single line of JSON, followed by a newline character.
+//! By default messages are newline-delimited JSON: each JSON-RPC message is
+//! serialized as a single line of JSON, followed by a newline character. Some
+//! hosts and LSP-style tooling instead frame each message with a
+//! `Content-Length` header ([`Framing::ContentLength`]).
+//!
+//! Both styles are accepted on the read side, detected per message by
+//! [`FrameDecoder`]. Outgoing messages use the framing of the most recent
+//! incoming message (newline-delimited until one arrives), so a server
+//! answers each host in its own style; [`StdioTransport::with_framing`] and
+//! [`SyncStdioTransport::with_framing`] pin the outgoing framing instead.
+//!
+//! Incoming messages are framed incrementally with a hard size cap
+//! ([`MAX_MESSAGE_SIZE`] by default, configurable with
+//! [`StdioTransport::with_max_message_size`] and
+//! [`SyncStdioTransport::with_max_message_size`]). An oversized message is
+//! discarded as it streams in, so a peer cannot make the transport buffer an
+//! unbounded amount of memory. [`StdioTransport`] answers it with a JSON-RPC
+//! parse error; [`SyncStdioTransport::recv_sync`] returns
+//! [`TransportError::MessageTooLarge`].
 //!
 //! # Runtime Support
 //!
@@ -31,18 +48,129 @@
 //!
 //! For synchronous stdio, see [`SyncStdioTransport`].
 
+// Without a runtime (e.g. the `wasm` feature) StdioTransport is compiled out.
+#![cfg_attr(
+    not(any(feature = "tokio-runtime", feature = "smol-runtime")),
+    allow(dead_code, unused_imports)
+)]
+use crate::codec;
 use crate::error::TransportError;
-use crate::runtime::{AsyncMutex, BufReader};
+use crate::framing::{Frame, FrameDecoder, Framing};
+use crate::limits::{LimitEnforcer, TransportLimits};
+use crate::runtime::AsyncMutex;
 use crate::traits::{Transport, TransportMetadata};
-use futures::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use bytes::BytesMut;
+use futures::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use mcpkit_core::error::JsonRpcError;
 use mcpkit_core::protocol::{Message, RequestId, Response};
-use std::io::{BufRead, Write};
+use std::io::{Read, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Maximum allowed message size (16 MB).
 pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
 
+/// Size of each read from the input stream.
+const READ_CHUNK_SIZE: usize = 8192;
+
+/// Read half of a stdio transport: the raw reader plus framing state.
+struct FrameReader<R> {
+    inner: R,
+    buffer: BytesMut,
+    decoder: FrameDecoder,
+    eof: bool,
+}
+
+impl<R> FrameReader<R> {
+    fn new(inner: R, max_message_size: usize) -> Self {
+        Self {
+            inner,
+            buffer: BytesMut::with_capacity(READ_CHUNK_SIZE),
+            decoder: FrameDecoder::new(max_message_size),
+            eof: false,
+        }
+    }
+
+    /// Take the next frame already in the buffer, if any.
+    fn buffered_frame(&mut self) -> Option<Frame> {
+        if self.eof {
+            self.decoder.decode_eof(&mut self.buffer)
+        } else {
+            self.decoder.decode(&mut self.buffer)
+        }
+    }
+
+    /// Append one read's worth of bytes; an empty read marks end of stream.
+    fn fill(&mut self, chunk: &[u8]) {
+        if chunk.is_empty() {
+            self.eof = true;
+        } else {
+            self.buffer.extend_from_slice(chunk);
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> FrameReader<R> {
+    /// Read until the next frame is available; `None` at end of stream.
+    async fn next_frame(&mut self) -> std::io::Result<Option<Frame>> {
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        loop {
+            if let Some(frame) = self.buffered_frame() {
+                return Ok(Some(frame));
+            }
+            if self.eof {
+                return Ok(None);
+            }
+            let n = self.inner.read(&mut chunk).await?;
+            self.fill(&chunk[..n]);
+        }
+    }
+}
+
+impl<R: Read> FrameReader<R> {
+    /// Blocking counterpart of the async `next_frame`.
+    fn next_frame_sync(&mut self) -> std::io::Result<Option<Frame>> {
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        loop {
+            if let Some(frame) = self.buffered_frame() {
+                return Ok(Some(frame));
+            }
+            if self.eof {
+                return Ok(None);
+            }
+            let n = self.inner.read(&mut chunk)?;
+            self.fill(&chunk[..n]);
+        }
+    }
+}
+
+/// Outgoing framing: pinned with `with_framing`, or mirroring the peer.
+#[derive(Debug, Default)]
+struct OutputFraming {
+    pinned: Option<Framing>,
+    /// Whether the peer's latest message was `Content-Length` framed.
+    peer_content_length: AtomicBool,
+}
+
+impl OutputFraming {
+    fn current(&self) -> Framing {
+        self.pinned.unwrap_or_else(|| {
+            if self.peer_content_length.load(Ordering::Relaxed) {
+                Framing::ContentLength
+            } else {
+                Framing::Newline
+            }
+        })
+    }
+
+    /// Remember the framing of the message just decoded by `decoder`.
+    fn observe(&self, decoder: &FrameDecoder) {
+        if let Some(framing) = decoder.last_framing() {
+            self.peer_content_length
+                .store(framing == Framing::ContentLength, Ordering::Relaxed);
+        }
+    }
+}
+
 /// A runtime-agnostic transport that uses stdin/stdout for communication.
 ///
 /// This is typically used when the MCP server is launched as a subprocess
@@ -62,10 +190,12 @@ where
     R: AsyncRead + Unpin + Send,
     W: AsyncWrite + Unpin + Send,
 {
-    stdin: AsyncMutex<BufReader<R>>,
+    stdin: AsyncMutex<FrameReader<R>>,
     stdout: AsyncMutex<W>,
     connected: AtomicBool,
     metadata: TransportMetadata,
+    limits: LimitEnforcer,
+    framing: OutputFraming,
 }
 
 #[cfg(feature = "tokio-runtime")]
@@ -81,13 +211,18 @@ impl
         use crate::runtime::{TokioAsyncReadWrapper, TokioAsyncWriteWrapper};
 
         Self {
-            stdin: AsyncMutex::new(BufReader::new(TokioAsyncReadWrapper(tokio::io::stdin()))),
+            stdin: AsyncMutex::new(FrameReader::new(
+                TokioAsyncReadWrapper(tokio::io::stdin()),
+                MAX_MESSAGE_SIZE,
+            )),
             stdout: AsyncMutex::new(TokioAsyncWriteWrapper(tokio::io::stdout())),
             connected: AtomicBool::new(true),
             metadata: TransportMetadata::new("stdio")
                 .remote_addr("stdin")
                 .local_addr("stdout")
                 .connected_now(),
+            limits: LimitEnforcer::default(),
+            framing: OutputFraming::default(),
         }
     }
 }
@@ -98,13 +233,18 @@ impl StdioTransport<smol::Unblock<std::io::Stdin>, smol::Unblock<std::io::Stdout
     #[must_use]
     pub fn new() -> Self {
         Self {
-            stdin: AsyncMutex::new(BufReader::new(smol::Unblock::new(std::io::stdin()))),
+            stdin: AsyncMutex::new(FrameReader::new(
+                smol::Unblock::new(std::io::stdin()),
+                MAX_MESSAGE_SIZE,
+            )),
             stdout: AsyncMutex::new(smol::Unblock::new(std::io::stdout())),
             connected: AtomicBool::new(true),
             metadata: TransportMetadata::new("stdio")
                 .remote_addr("stdin")
                 .local_addr("stdout")
                 .connected_now(),
+            limits: LimitEnforcer::default(),
+            framing: OutputFraming::default(),
         }
     }
 }
@@ -140,32 +280,130 @@ where
     #[must_use]
     pub fn with_streams(stdin: R, stdout: W) -> Self {
         Self {
-            stdin: AsyncMutex::new(BufReader::new(stdin)),
+            stdin: AsyncMutex::new(FrameReader::new(stdin, MAX_MESSAGE_SIZE)),
             stdout: AsyncMutex::new(stdout),
             connected: AtomicBool::new(true),
             metadata: TransportMetadata::new("stdio")
                 .remote_addr("custom")
                 .local_addr("custom")
                 .connected_now(),
+            limits: LimitEnforcer::default(),
+            framing: OutputFraming::default(),
         }
     }
 
+    /// Set the maximum size of a single message, in bytes, for both directions.
+    ///
+    /// Defaults to [`MAX_MESSAGE_SIZE`]. Incoming messages longer than this
+    /// are discarded without being buffered and answered with a parse error;
+    /// outgoing messages longer than this fail with
+    /// [`TransportError::MessageTooLarge`].
+    #[must_use]
+    pub fn with_max_message_size(self, max_message_size: usize) -> Self {
+        let limits = self
+            .limits
+            .limits()
+            .clone()
+            .with_max_message_size(max_message_size);
+        self.with_limits(limits)
+    }
+
+    /// Apply [`TransportLimits`]: the message size cap as in
+    /// [`with_max_message_size`](Self::with_max_message_size), plus the
+    /// in-flight request cap and read timeout.
+    #[must_use]
+    pub fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.stdin.get_mut().decoder = FrameDecoder::new(limits.max_message_size);
+        self.limits = LimitEnforcer::new(limits);
+        self
+    }
+
+    /// The maximum size of a single message, in bytes.
+    const fn max_message_size(&self) -> usize {
+        self.limits.limits().max_message_size
+    }
+
+    /// Always frame outgoing messages with `framing`, instead of matching
+    /// the framing of the peer's latest message.
+    ///
+    /// Incoming messages are accepted in either framing regardless.
+    #[must_use]
+    pub const fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing.pinned = Some(framing);
+        self
+    }
+
+    /// Frame `json` and write it to stdout.
+    async fn write_message(&self, json: &[u8]) -> Result<(), TransportError> {
+        let mut framed = Vec::new();
+        self.framing.current().encode(json, &mut framed);
+        let mut stdout = self.stdout.lock().await;
+        stdout.write_all(&framed).await?;
+        stdout.flush().await?;
+        Ok(())
+    }
+
     /// Write a JSON-RPC parse error (`-32700`) with a null id to stdout.
     ///
-    /// Used when an incoming line cannot be parsed, so the connection stays
-    /// open and keeps serving instead of being torn down by one malformed
-    /// message.
-    async fn send_parse_error(&self) -> Result<(), TransportError> {
+    /// Used when an incoming line cannot be parsed or exceeds the size cap, so
+    /// the connection stays open and keeps serving instead of being torn down
+    /// by one bad message.
+    async fn send_parse_error(&self, message: &str) -> Result<(), TransportError> {
         let response = Message::Response(Response::error(
             RequestId::Null,
-            JsonRpcError::parse_error("failed to parse message as JSON-RPC"),
+            JsonRpcError::parse_error(message),
         ));
         let json = serde_json::to_string(&response)?;
-        let mut stdout = self.stdout.lock().await;
-        stdout.write_all(json.as_bytes()).await?;
-        stdout.write_all(b"\n").await?;
-        stdout.flush().await?;
-        Ok(())
+        self.write_message(json.as_bytes()).await
+    }
+
+    /// Read the next message, answering unparseable input with a parse error.
+    async fn read_message(&self) -> Result<Option<Message>, TransportError> {
+        let mut stdin = self.stdin.lock().await;
+
+        loop {
+            let Some(frame) = stdin.next_frame().await? else {
+                // EOF - connection closed
+                self.connected.store(false, Ordering::SeqCst);
+                return Ok(None);
+            };
+            self.framing.observe(&stdin.decoder);
+
+            let line = match frame {
+                Frame::Line(line) => line,
+                Frame::TooLarge { size } => {
+                    // The oversized line was dropped while streaming in; report
+                    // it like any other unparseable line and keep reading.
+                    tracing::warn!(
+                        size,
+                        max = self.max_message_size(),
+                        "StdioTransport discarded oversized message"
+                    );
+                    self.send_parse_error(&format!(
+                        "message exceeds maximum size of {} bytes",
+                        self.max_message_size()
+                    ))
+                    .await?;
+                    continue;
+                }
+                Frame::Malformed(reason) => {
+                    tracing::warn!(%reason, "StdioTransport discarded malformed message");
+                    self.send_parse_error(&reason).await?;
+                    continue;
+                }
+            };
+
+            // Parse the frame in place - no String allocation or copy. A
+            // malformed line is a JSON-RPC parse error: reply -32700 with a null
+            // id and keep reading, rather than tearing down the connection on a
+            // single bad message.
+            let Ok(msg) = codec::decode(line) else {
+                self.send_parse_error("failed to parse message as JSON-RPC")
+                    .await?;
+                continue;
+            };
+            return Ok(Some(msg));
+        }
     }
 }
 
@@ -192,19 +430,9 @@ where
         };
         tracing::debug!(msg = %msg_id, len = json.len(), "StdioTransport sending message");
 
-        if json.len() > MAX_MESSAGE_SIZE {
-            return Err(TransportError::MessageTooLarge {
-                size: json.len(),
-                max: MAX_MESSAGE_SIZE,
-            });
-        }
-
-        let mut stdout = self.stdout.lock().await;
-        stdout.write_all(json.as_bytes()).await?;
-        stdout.write_all(b"\n").await?;
-        stdout.flush().await?;
+        self.limits.send(&msg, json.len())?;
 
-        Ok(())
+        self.write_message(json.as_bytes()).await
     }
 
     async fn recv(&self) -> Result<Option<Message>, Self::Error> {
@@ -212,48 +440,8 @@ where
             return Err(TransportError::NotConnected);
         }
 
-        let mut stdin = self.stdin.lock().await;
-
-        loop {
-            // Use zero-copy read_line_bytes for efficiency
-            let line_bytes = stdin.read_line_bytes().await?;
-
-            if line_bytes.is_empty() {
-                // EOF - connection closed
-                self.connected.store(false, Ordering::SeqCst);
-                return Ok(None);
-            }
-
-            if line_bytes.len() > MAX_MESSAGE_SIZE {
-                return Err(TransportError::MessageTooLarge {
-                    size: line_bytes.len(),
-                    max: MAX_MESSAGE_SIZE,
-                });
-            }
-
-            // Trim trailing whitespace (newline, carriage return, etc.)
-            let trimmed = line_bytes
-                .iter()
-                .rposition(|&b| !b.is_ascii_whitespace())
-                .map_or(&[][..], |end| &line_bytes[..=end]);
-
-            if trimmed.is_empty() {
-                // Empty line, continue reading
-                continue;
-            }
-
-            // Parse JSON directly from bytes - avoids String allocation. A
-            // malformed line is a JSON-RPC parse error: reply -32700 with a null
-            // id and keep reading, rather than tearing down the connection on a
-            // single bad message.
-            let Ok(msg) = serde_json::from_slice::<Message>(trimmed) else {
-                self.send_parse_error().await?;
-                continue;
-            };
-            return Ok(Some(msg));
-        }
+        self.limits.recv(Box::pin(self.read_message())).await
     }
-
     async fn close(&self) -> Result<(), Self::Error> {
         self.connected.store(false, Ordering::SeqCst);
         // Flush any pending output
@@ -280,10 +468,12 @@ where
 /// This is useful when you don't want to use an async runtime.
 /// It does not implement the `Transport` trait since that requires async.
 pub struct SyncStdioTransport {
-    stdin: std::sync::Mutex<std::io::BufReader<std::io::Stdin>>,
+    stdin: std::sync::Mutex<FrameReader<std::io::Stdin>>,
     stdout: std::sync::Mutex<std::io::Stdout>,
     connected: AtomicBool,
     metadata: TransportMetadata,
+    framing: OutputFraming,
+    max_message_size: usize,
 }
 
 impl SyncStdioTransport {
@@ -291,16 +481,41 @@ impl SyncStdioTransport {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            stdin: std::sync::Mutex::new(std::io::BufReader::new(std::io::stdin())),
+            stdin: std::sync::Mutex::new(FrameReader::new(std::io::stdin(), MAX_MESSAGE_SIZE)),
             stdout: std::sync::Mutex::new(std::io::stdout()),
             connected: AtomicBool::new(true),
             metadata: TransportMetadata::new("stdio")
                 .remote_addr("stdin")
                 .local_addr("stdout")
                 .connected_now(),
+            framing: OutputFraming::default(),
+            max_message_size: MAX_MESSAGE_SIZE,
         }
     }
 
+    /// Always frame outgoing messages with `framing`, instead of matching
+    /// the framing of the peer's latest message.
+    ///
+    /// Incoming messages are accepted in either framing regardless.
+    #[must_use]
+    pub const fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing.pinned = Some(framing);
+        self
+    }
+
+    /// Set the maximum size of a single message, in bytes, for both directions.
+    ///
+    /// Defaults to [`MAX_MESSAGE_SIZE`]. Incoming messages longer than this
+    /// are discarded without being buffered and fail
+    /// [`recv_sync`](Self::recv_sync) with [`TransportError::MessageTooLarge`];
+    /// outgoing messages longer than this fail the same way.
+    #[must_use]
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.stdin = std::sync::Mutex::new(FrameReader::new(std::io::stdin(), max_message_size));
+        self.max_message_size = max_message_size;
+        self
+    }
+
     /// Send a message synchronously.
     ///
     /// # Errors
@@ -313,10 +528,10 @@ impl SyncStdioTransport {
 
         let json = serde_json::to_string(msg)?;
 
-        if json.len() > MAX_MESSAGE_SIZE {
+        if json.len() > self.max_message_size {
             return Err(TransportError::MessageTooLarge {
                 size: json.len(),
-                max: MAX_MESSAGE_SIZE,
+                max: self.max_message_size,
             });
         }
 
@@ -324,7 +539,9 @@ impl SyncStdioTransport {
             message: "stdout lock poisoned".to_string(),
         })?;
 
-        writeln!(stdout, "{json}")?;
+        let mut framed = Vec::new();
+        self.framing.current().encode(json.as_bytes(), &mut framed);
+        stdout.write_all(&framed)?;
         stdout.flush()?;
 
         Ok(())
@@ -340,40 +557,24 @@ impl SyncStdioTransport {
             return Err(TransportError::NotConnected);
         }
 
-        let mut line = String::new();
         let mut stdin = self.stdin.lock().map_err(|_| TransportError::Protocol {
             message: "stdin lock poisoned".to_string(),
         })?;
 
-        // Bound the read to one byte past the limit so a peer that never sends a
-        // newline cannot grow `line` without bound before the size check below.
-        let bytes_read = {
-            use std::io::Read as _;
-            (&mut *stdin)
-                .take(MAX_MESSAGE_SIZE as u64 + 1)
-                .read_line(&mut line)?
-        };
-
-        if bytes_read == 0 {
+        let Some(frame) = stdin.next_frame_sync()? else {
             self.connected.store(false, Ordering::SeqCst);
             return Ok(None);
+        };
+        self.framing.observe(&stdin.decoder);
+
+        match frame {
+            Frame::Line(line) => codec::decode(line).map(Some),
+            Frame::TooLarge { size } => Err(TransportError::MessageTooLarge {
+                size,
+                max: self.max_message_size,
+            }),
+            Frame::Malformed(message) => Err(TransportError::Protocol { message }),
         }
-
-        if line.len() > MAX_MESSAGE_SIZE {
-            return Err(TransportError::MessageTooLarge {
-                size: line.len(),
-                max: MAX_MESSAGE_SIZE,
-            });
-        }
-
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            drop(stdin);
-            return self.recv_sync();
-        }
-
-        let msg: Message = serde_json::from_str(trimmed)?;
-        Ok(Some(msg))
     }
 
     /// Close the transport.
@@ -421,6 +622,16 @@ mod tests {
         assert_eq!(MAX_MESSAGE_SIZE, 16 * 1024 * 1024);
     }
 
+    #[test]
+    fn sync_transport_caps_outgoing_messages() {
+        let transport = SyncStdioTransport::new().with_max_message_size(64);
+        let request = Message::Request(mcpkit_core::protocol::Request::new("x".repeat(100), 1u64));
+        assert!(matches!(
+            transport.send_sync(&request),
+            Err(TransportError::MessageTooLarge { max: 64, .. })
+        ));
+    }
+
     #[cfg(feature = "tokio-runtime")]
     #[tokio::test]
     async fn malformed_line_does_not_close_connection() {
@@ -441,4 +652,118 @@ mod tests {
             other => panic!("expected the ping request after the bad line, got {other:?}"),
         }
     }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn oversized_line_is_rejected_without_closing_connection() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let (read_half, mut write_half) = tokio::io::split(client);
+        let (server_read, server_write) = tokio::io::split(server);
+        let transport = StdioTransport::with_streams(
+            crate::runtime::TokioAsyncReadWrapper(server_read),
+            crate::runtime::TokioAsyncWriteWrapper(server_write),
+        )
+        .with_max_message_size(1024);
+
+        // A 1 MiB line, far past the cap, then a valid request.
+        let writer = tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt as _;
+            let big = format!(
+                "{{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"{}\"}}\n",
+                "x".repeat(1 << 20)
+            );
+            write_half.write_all(big.as_bytes()).await.unwrap();
+            write_half
+                .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"ping\"}\n")
+                .await
+                .unwrap();
+        });
+
+        let msg = transport.recv().await.expect("recv");
+        match msg {
+            Some(Message::Request(req)) => assert_eq!(req.id, RequestId::Number(2)),
+            other => panic!("expected the ping after the oversized line, got {other:?}"),
+        }
+        assert!(transport.is_connected());
+        writer.await.unwrap();
+
+        // The peer was told about the dropped message with a -32700 frame.
+        let mut reader = tokio::io::BufReader::new(read_half);
+        let mut line = String::new();
+        tokio::io::AsyncBufReadExt::read_line(&mut reader, &mut line)
+            .await
+            .unwrap();
+        let reply: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(reply["error"]["code"], -32700);
+        assert!(reply["id"].is_null());
+
+        let oversized = Message::Request(mcpkit_core::protocol::Request::new("x".repeat(2048), 1));
+        assert!(matches!(
+            transport.send(oversized).await,
+            Err(TransportError::MessageTooLarge { max: 1024, .. })
+        ));
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn in_flight_limit_holds_back_requests() {
+        use futures::io::Cursor;
+        use mcpkit_core::protocol::Response;
+        let input = b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}\n\
+                      {\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"ping\"}\n"
+            .to_vec();
+        let transport = StdioTransport::with_streams(Cursor::new(input), Cursor::new(Vec::new()))
+            .with_limits(TransportLimits::new().with_max_in_flight(1));
+
+        assert!(transport.recv().await.unwrap().is_some());
+        let second = tokio::time::timeout(std::time::Duration::from_millis(20), transport.recv());
+        assert!(second.await.is_err(), "second request must wait");
+
+        let reply = Response::success(RequestId::Number(1), serde_json::json!({}));
+        transport.send(Message::Response(reply)).await.unwrap();
+        match transport.recv().await.unwrap() {
+            Some(Message::Request(req)) => assert_eq!(req.id, RequestId::Number(2)),
+            other => panic!("expected the second request, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn replies_in_the_framing_of_the_peer() {
+        use futures::io::Cursor;
+        let ping = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+        let input = format!("Content-Length: {}\r\n\r\n{ping}", ping.len());
+        let transport =
+            StdioTransport::with_streams(Cursor::new(input.into_bytes()), Cursor::new(Vec::new()));
+
+        let Some(Message::Request(req)) = transport.recv().await.unwrap() else {
+            panic!("expected the ping request");
+        };
+        transport
+            .send(Message::Response(Response::success(
+                req.id,
+                serde_json::json!({}),
+            )))
+            .await
+            .unwrap();
+        let written = transport.stdout.lock().await.get_ref().clone();
+        let written = String::from_utf8(written).unwrap();
+        assert!(written.starts_with("Content-Length: "), "{written}");
+        assert!(written.ends_with("\"result\":{}}"), "{written}");
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn pinned_framing_overrides_the_peer() {
+        use futures::io::Cursor;
+        let input = b"{\"jsonrpc\":\"2.0\",\"method\":\"notifications/initialized\"}\n".to_vec();
+        let transport = StdioTransport::with_streams(Cursor::new(input), Cursor::new(Vec::new()))
+            .with_framing(Framing::ContentLength);
+
+        transport.recv().await.unwrap().unwrap();
+        let ping = Message::Request(mcpkit_core::protocol::Request::new("ping", 1));
+        transport.send(ping).await.unwrap();
+        let written = transport.stdout.lock().await.get_ref().clone();
+        assert!(written.starts_with(b"Content-Length: "));
+    }
 }
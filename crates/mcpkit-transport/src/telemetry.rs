@@ -34,6 +34,16 @@
 //! | `mcp.transport` | Transport type (stdio, http, websocket, unix) |
 //! | `mcp.message_size` | Size of the message in bytes |
 //! | `mcp.error` | Error message if the operation failed |
+//! | `mcp.tool` | The tool name, on `tools/call` request spans |
+//!
+//! # Trace Propagation
+//!
+//! Trace context crosses the MCP hop in the request's `_meta`, as W3C
+//! `traceparent`/`tracestate` keys. Clients attach it with
+//! [`inject_current_context`], and servers handle each request inside a
+//! [`request_span`] parented on it, so a trace runs from the LLM host through
+//! the MCP server into whatever the tool calls. Both ends need the
+//! `opentelemetry` feature for the spans to be exported as one trace.
 //!
 //! # Metrics
 //!
@@ -46,10 +56,27 @@
 //! | `mcp_message_latency_seconds` | Histogram | Message processing latency |
 //! | `mcp_errors_total` | Counter | Total errors by type |
 //! | `mcp_active_connections` | Gauge | Currently active connections |
+//! | `mcp_active_sessions` | Gauge | Open sessions (HTTP integrations) |
+//! | `mcp_sse_connections` | Gauge | Open SSE streams (HTTP integrations) |
+//! | `mcp_tool_calls_total` | Counter | Tool calls by tool |
+//! | `mcp_tool_errors_total` | Counter | Failed tool calls by tool |
+//! | `mcp_compression_saved_bytes_total` | Counter | Bytes kept off the wire by compression |
+//! | `mcp_ping_rtt_seconds` | Histogram | Round-trip time of supervised pings |
+//! | `mcp_pings_missed_total` | Counter | Supervised pings that went unanswered |
+//! | `mcp_client_requests_total` | Counter | Requests by method and [dimension](dimensions) |
+//! | `mcp_client_errors_total` | Counter | Error responses by method and dimension |
+//! | `mcp_client_response_time_seconds` | Histogram | Response time by method and dimension |
+//!
+//! The per-dimension families are only recorded when
+//! [`TelemetryConfig::with_dimensions`] enables at least one dimension; their
+//! disabled labels are empty.
+
+pub mod dimensions;
 
 use crate::error::TransportError;
 use crate::traits::{Transport, TransportMetadata};
-use mcpkit_core::protocol::Message;
+use dimensions::{CardinalityGuard, ConnectionLabels, DimensionalMetrics, Dimensions};
+use mcpkit_core::protocol::{Message, Request};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
@@ -67,6 +94,8 @@ pub struct TelemetryConfig {
     pub max_recorded_message_size: usize,
     /// Span name prefix.
     pub span_prefix: String,
+    /// Dimensions metrics are also broken down by.
+    pub dimensions: Dimensions,
 }
 
 impl TelemetryConfig {
@@ -79,6 +108,7 @@ impl TelemetryConfig {
             record_timing: true,
             max_recorded_message_size: 0,
             span_prefix: "mcp".to_string(),
+            dimensions: Dimensions::none(),
         }
     }
 
@@ -109,6 +139,13 @@ impl TelemetryConfig {
         self.span_prefix = prefix.into();
         self
     }
+
+    /// Also break metrics down by `dimensions`.
+    #[must_use]
+    pub const fn with_dimensions(mut self, dimensions: Dimensions) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
 }
 
 impl Default for TelemetryConfig {
@@ -345,6 +382,10 @@ pub struct TelemetryLayer {
     metrics: Arc<TelemetryMetrics>,
     send_latency: Arc<LatencyHistogram>,
     recv_latency: Arc<LatencyHistogram>,
+    guard: Arc<CardinalityGuard>,
+    by_labels: Arc<DimensionalMetrics>,
+    #[cfg(feature = "prometheus")]
+    prometheus: Option<prom::McpMetrics>,
 }
 
 impl TelemetryLayer {
@@ -352,13 +393,26 @@ impl TelemetryLayer {
     #[must_use]
     pub fn new(config: TelemetryConfig) -> Self {
         Self {
+            guard: Arc::new(CardinalityGuard::new(config.dimensions.max_values)),
             config,
             metrics: Arc::new(TelemetryMetrics::new()),
             send_latency: Arc::new(LatencyHistogram::new()),
             recv_latency: Arc::new(LatencyHistogram::new()),
+            by_labels: Arc::new(DimensionalMetrics::new()),
+            #[cfg(feature = "prometheus")]
+            prometheus: None,
         }
     }
 
+    /// Also record traffic, request counts, and response times (with trace
+    /// exemplars) in Prometheus `metrics`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_prometheus(mut self, metrics: prom::McpMetrics) -> Self {
+        self.prometheus = Some(metrics);
+        self
+    }
+
     /// Get the metrics.
     #[must_use]
     pub fn metrics(&self) -> &TelemetryMetrics {
@@ -376,18 +430,36 @@ impl TelemetryLayer {
     pub fn recv_latency(&self) -> &LatencyHistogram {
         &self.recv_latency
     }
+
+    /// Get the metrics broken down by the configured dimensions, across
+    /// every transport this layer wrapped.
+    #[must_use]
+    pub fn metrics_by_labels(&self) -> &DimensionalMetrics {
+        &self.by_labels
+    }
+
+    /// Get the guard capping the number of label values.
+    #[must_use]
+    pub fn cardinality_guard(&self) -> &CardinalityGuard {
+        &self.guard
+    }
 }
 
 impl<T: Transport> super::middleware::TransportLayer<T> for TelemetryLayer {
     type Transport = TelemetryTransport<T>;
 
     fn layer(&self, inner: T) -> Self::Transport {
+        let labels = connection_labels(&inner, &self.config, &self.guard, &self.by_labels);
         TelemetryTransport {
             inner,
             config: self.config.clone(),
             metrics: Arc::clone(&self.metrics),
             send_latency: Arc::clone(&self.send_latency),
             recv_latency: Arc::clone(&self.recv_latency),
+            by_labels: Arc::clone(&self.by_labels),
+            labels,
+            #[cfg(feature = "prometheus")]
+            timer: self.prometheus.clone().map(prom::RequestTimer::new),
         }
     }
 }
@@ -399,19 +471,73 @@ pub struct TelemetryTransport<T> {
     metrics: Arc<TelemetryMetrics>,
     send_latency: Arc<LatencyHistogram>,
     recv_latency: Arc<LatencyHistogram>,
+    by_labels: Arc<DimensionalMetrics>,
+    labels: Option<ConnectionLabels>,
+    #[cfg(feature = "prometheus")]
+    timer: Option<prom::RequestTimer>,
+}
+
+/// Per-connection labels for `inner`, when any dimension is enabled.
+///
+/// The session id defaults to a fresh UUID, so every connection is its own
+/// session until [`TelemetryTransport::with_session_id`] says otherwise.
+fn connection_labels<T: Transport>(
+    inner: &T,
+    config: &TelemetryConfig,
+    guard: &Arc<CardinalityGuard>,
+    store: &Arc<DimensionalMetrics>,
+) -> Option<ConnectionLabels> {
+    config.dimensions.is_enabled().then(|| {
+        ConnectionLabels::new(
+            config.dimensions,
+            Arc::clone(guard),
+            Arc::clone(store),
+            &uuid::Uuid::new_v4().to_string(),
+            &inner.metadata().transport_type,
+        )
+    })
 }
 
 impl<T: Transport> TelemetryTransport<T> {
     /// Create a new telemetry transport.
     #[must_use]
     pub fn new(inner: T, config: TelemetryConfig) -> Self {
+        let guard = Arc::new(CardinalityGuard::new(config.dimensions.max_values));
+        let by_labels = Arc::new(DimensionalMetrics::new());
+        let labels = connection_labels(&inner, &config, &guard, &by_labels);
         Self {
             inner,
             config,
             metrics: Arc::new(TelemetryMetrics::new()),
             send_latency: Arc::new(LatencyHistogram::new()),
             recv_latency: Arc::new(LatencyHistogram::new()),
+            by_labels,
+            labels,
+            #[cfg(feature = "prometheus")]
+            timer: None,
+        }
+    }
+
+    /// Report this connection under session `id` rather than a generated
+    /// one, for transports whose sessions outlive a connection.
+    ///
+    /// Only the hash of `id` is recorded
+    /// ([`session_label`](dimensions::session_label)).
+    #[must_use]
+    pub fn with_session_id(self, id: &str) -> Self {
+        if let Some(labels) = &self.labels {
+            labels.set_session(id);
         }
+        self
+    }
+
+    /// Also record traffic, request counts, and response times (with trace
+    /// exemplars) in Prometheus `metrics`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_prometheus(mut self, metrics: prom::McpMetrics) -> Self {
+        self.timer = Some(prom::RequestTimer::new(metrics));
+        self
     }
 
     /// Get the metrics.
@@ -432,6 +558,13 @@ impl<T: Transport> TelemetryTransport<T> {
         &self.recv_latency
     }
 
+    /// Get the metrics broken down by the configured dimensions. Shared with
+    /// the [`TelemetryLayer`] that created this transport, if any.
+    #[must_use]
+    pub fn metrics_by_labels(&self) -> &DimensionalMetrics {
+        &self.by_labels
+    }
+
     /// Get the inner transport.
     #[must_use]
     pub const fn inner(&self) -> &T {
@@ -467,11 +600,21 @@ impl<T: Transport> Transport for TelemetryTransport<T> {
 
         let _guard = span.enter();
 
+        let labeled = self.labels.as_ref().map(|labels| labels.observe(&msg));
+
+        #[cfg(feature = "prometheus")]
+        if let Some(timer) = &self.timer {
+            timer.on_send(&msg, size, labeled.as_ref().map(|(labels, _)| labels));
+        }
+
         let result = self.inner.send(msg).await.map_err(|e| {
             let err = TransportError::Connection {
                 message: e.to_string(),
             };
             self.metrics.record_error(&err);
+            if let Some((_, entry)) = &labeled {
+                entry.metrics.record_error(&err);
+            }
             tracing::error!(
                 mcp.error = %e,
                 "MCP send failed"
@@ -482,6 +625,10 @@ impl<T: Transport> Transport for TelemetryTransport<T> {
         let duration = start.elapsed();
         self.send_latency.record(duration);
         self.metrics.record_send(size);
+        if let Some((_, entry)) = &labeled {
+            entry.send_latency.record(duration);
+            entry.metrics.record_send(size);
+        }
 
         if self.config.record_timing {
             tracing::debug!(
@@ -511,6 +658,9 @@ impl<T: Transport> Transport for TelemetryTransport<T> {
                 message: e.to_string(),
             };
             self.metrics.record_error(&err);
+            if let Some(labels) = &self.labels {
+                labels.current().1.metrics.record_error(&err);
+            }
             tracing::error!(
                 mcp.error = %e,
                 "MCP recv failed"
@@ -522,6 +672,11 @@ impl<T: Transport> Transport for TelemetryTransport<T> {
             let msg_json = serde_json::to_string(msg).unwrap_or_default();
             let size = msg_json.len();
             self.metrics.record_receive(size);
+            let labeled = self.labels.as_ref().map(|labels| labels.observe(msg));
+            #[cfg(feature = "prometheus")]
+            if let Some(timer) = &self.timer {
+                timer.on_recv(msg, size, labeled.as_ref().map(|(labels, _)| labels));
+            }
 
             let method = match msg {
                 Message::Request(req) => &req.method,
@@ -531,6 +686,10 @@ impl<T: Transport> Transport for TelemetryTransport<T> {
 
             let duration = start.elapsed();
             self.recv_latency.record(duration);
+            if let Some((_, entry)) = &labeled {
+                entry.recv_latency.record(duration);
+                entry.metrics.record_receive(size);
+            }
 
             tracing::debug!(
                 mcp.method = %method,
@@ -572,45 +731,101 @@ impl<T: Transport> Transport for TelemetryTransport<T> {
     fn metadata(&self) -> TransportMetadata {
         self.inner.metadata()
     }
+
+    fn connection_epoch(&self) -> u64 {
+        self.inner.connection_epoch()
+    }
+}
+
+/// Create the span a server handles `request` in.
+///
+/// The span is named after the method (`tools/call <tool>` for tool calls,
+/// through the `otel.name` field). With the `opentelemetry` feature, a W3C
+/// trace context in the request's `params._meta` becomes the span's remote
+/// parent, so the server's work joins the caller's distributed trace.
+#[must_use]
+pub fn request_span(request: &Request) -> tracing::Span {
+    let method = request.method.as_ref();
+    let tool = (method == "tools/call")
+        .then(|| request.params.as_ref()?.get("name")?.as_str())
+        .flatten();
+    let name = tool.map_or_else(|| method.to_string(), |tool| format!("{method} {tool}"));
+    let span = tracing::info_span!(
+        "mcp.request",
+        otel.name = %name,
+        otel.kind = "server",
+        mcp.method = %method,
+        mcp.request_id = %request.id,
+        mcp.tool = tool,
+    );
+
+    #[cfg(feature = "opentelemetry")]
+    if let Some(context) = request
+        .params
+        .as_ref()
+        .and_then(propagation::extract_from_params)
+    {
+        otel::set_parent(&span, &context);
+    }
+
+    span
+}
+
+/// Attach the current span's W3C trace context to outgoing request params.
+///
+/// Clients call this before sending a request so the server can continue the
+/// trace (see [`request_span`]). Without the `opentelemetry` feature, or when
+/// the current span is not part of a trace, `params` is returned unchanged.
+#[must_use]
+pub fn inject_current_context(params: Option<serde_json::Value>) -> Option<serde_json::Value> {
+    #[cfg(feature = "opentelemetry")]
+    if let Some(context) = otel::current_trace_context() {
+        return Some(propagation::inject_into_params(&context, params));
+    }
+    params
 }
 
 /// Context propagation utilities for distributed tracing.
 pub mod propagation {
+    use mcpkit_core::types::Meta;
+    use serde_json::Value;
     use std::collections::HashMap;
 
     /// Extract trace context from headers.
     #[must_use]
     pub fn extract_context(headers: &HashMap<String, String>) -> Option<TraceContext> {
-        // W3C Trace Context format
-        let traceparent = headers.get("traceparent")?;
-        let parts: Vec<&str> = traceparent.split('-').collect();
-        if parts.len() != 4 {
-            return None;
-        }
-
-        Some(TraceContext {
-            version: parts[0].to_string(),
-            trace_id: parts[1].to_string(),
-            parent_id: parts[2].to_string(),
-            flags: parts[3].to_string(),
-            tracestate: headers.get("tracestate").cloned(),
-        })
+        TraceContext::parse(
+            headers.get("traceparent")?,
+            headers.get("tracestate").map(String::as_str),
+        )
     }
 
     /// Inject trace context into headers.
     pub fn inject_context(context: &TraceContext, headers: &mut HashMap<String, String>) {
-        headers.insert(
-            "traceparent".to_string(),
-            format!(
-                "{}-{}-{}-{}",
-                context.version, context.trace_id, context.parent_id, context.flags
-            ),
-        );
+        headers.insert("traceparent".to_string(), context.traceparent());
         if let Some(ref state) = context.tracestate {
             headers.insert("tracestate".to_string(), state.clone());
         }
     }
 
+    /// Extract trace context from a request's `params._meta`.
+    #[must_use]
+    pub fn extract_from_params(params: &Value) -> Option<TraceContext> {
+        let (traceparent, tracestate) = Meta::trace_context_from_params(params)?;
+        TraceContext::parse(traceparent, tracestate)
+    }
+
+    /// Inject trace context into a request's `params._meta`, creating the
+    /// params object if needed.
+    #[must_use]
+    pub fn inject_into_params(context: &TraceContext, params: Option<Value>) -> Value {
+        Meta::with_trace_context_in_params(
+            params,
+            &context.traceparent(),
+            context.tracestate.as_deref(),
+        )
+    }
+
     /// W3C Trace Context.
     #[derive(Debug, Clone)]
     pub struct TraceContext {
@@ -627,6 +842,32 @@ pub mod propagation {
     }
 
     impl TraceContext {
+        /// Parse a W3C `traceparent` value and optional `tracestate`.
+        #[must_use]
+        pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+            let parts: Vec<&str> = traceparent.split('-').collect();
+            if parts.len() != 4 {
+                return None;
+            }
+
+            Some(Self {
+                version: parts[0].to_string(),
+                trace_id: parts[1].to_string(),
+                parent_id: parts[2].to_string(),
+                flags: parts[3].to_string(),
+                tracestate: tracestate.map(str::to_string),
+            })
+        }
+
+        /// The `traceparent` value for this context.
+        #[must_use]
+        pub fn traceparent(&self) -> String {
+            format!(
+                "{}-{}-{}-{}",
+                self.version, self.trace_id, self.parent_id, self.flags
+            )
+        }
+
         /// Check if sampling is enabled.
         #[must_use]
         pub fn is_sampled(&self) -> bool {
@@ -864,6 +1105,53 @@ pub mod otel {
 
         init_tracing(config)
     }
+
+    /// The W3C trace context of the current `tracing` span, or `None` when
+    /// the span is not part of a valid trace.
+    #[must_use]
+    pub fn current_trace_context() -> Option<super::propagation::TraceContext> {
+        use opentelemetry::propagation::TextMapPropagator;
+        use opentelemetry_sdk::propagation::TraceContextPropagator;
+        use std::collections::HashMap;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let mut carrier = HashMap::new();
+        TraceContextPropagator::new()
+            .inject_context(&tracing::Span::current().context(), &mut carrier);
+        super::propagation::extract_context(&carrier)
+    }
+
+    /// Make `context` the remote parent of `span`, so the span joins the
+    /// caller's trace. Must be called before `span` is first entered.
+    pub fn set_parent(span: &tracing::Span, context: &super::propagation::TraceContext) {
+        use opentelemetry::propagation::TextMapPropagator;
+        use opentelemetry_sdk::propagation::TraceContextPropagator;
+        use std::collections::HashMap;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let mut carrier = HashMap::new();
+        super::propagation::inject_context(context, &mut carrier);
+        let parent = TraceContextPropagator::new().extract(&carrier);
+        if let Err(e) = span.set_parent(parent) {
+            tracing::debug!(error = ?e, "Cannot set remote trace parent");
+        }
+    }
+
+    /// The OpenTelemetry trace ID of the current `tracing` span, as 32 hex
+    /// characters, or `None` when the span is not part of a valid trace
+    /// (e.g. no OpenTelemetry layer is installed).
+    #[must_use]
+    pub fn current_trace_id() -> Option<String> {
+        use opentelemetry::trace::TraceContextExt;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let context = tracing::Span::current().context();
+        let span = context.span();
+        let span_context = span.span_context();
+        span_context
+            .is_valid()
+            .then(|| span_context.trace_id().to_string())
+    }
 }
 
 /// Prometheus metrics integration.
@@ -895,13 +1183,78 @@ pub mod otel {
 /// let output = exporter.gather()?;
 /// println!("{}", output);
 /// ```
+///
+/// # Exemplars
+///
+/// Response-time observations can carry the trace ID of the request as an
+/// [exemplar](https://grafana.com/docs/grafana/latest/fundamentals/exemplars/),
+/// so a Grafana latency panel links straight to the trace. With the
+/// `opentelemetry` feature also enabled, [`McpMetrics::record_response_time`]
+/// picks the trace ID up from the current span automatically; otherwise pass
+/// it to [`McpMetrics::record_response_time_with_exemplar`]. Exemplars are
+/// only part of the OpenMetrics format, so scrape
+/// [`MetricsExporter::gather_openmetrics`] to see them:
+///
+/// ```rust,ignore
+/// let metrics = McpMetrics::new()?;
+/// metrics.record_response_time_with_exemplar(
+///     "tools/call",
+///     0.042,
+///     Some("4bf92f3577b34da6a3ce929d0e0e4736"),
+/// );
+///
+/// let exporter = MetricsExporter::from_metrics(&metrics);
+/// // mcp_response_time_seconds_bucket{method="tools/call",le="0.05"} 1 # {trace_id="4bf9..."} 0.042 ...
+/// let body = exporter.gather_openmetrics()?;
+/// ```
+///
+/// Attach [`McpMetrics`] to a [`TelemetryLayer`](super::TelemetryLayer) with
+/// `with_prometheus` to have every request/response pair timed this way.
 #[cfg(feature = "prometheus")]
 pub mod prom {
+    use super::dimensions::Labels;
+    use mcpkit_core::protocol::{Message, RequestId};
     use prometheus::{
-        Counter, CounterVec, Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry,
-        TextEncoder,
+        Counter, CounterVec, Encoder, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec,
+        Opts, Registry, TextEncoder,
     };
-    use std::sync::Arc;
+    use std::collections::HashMap;
+    use std::fmt::Write as _;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+    /// Bucket upper bounds of `mcp_response_time_seconds`.
+    pub const RESPONSE_TIME_BUCKETS: &[f64] = &[
+        0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ];
+
+    /// Label names of the per-dimension families, in order.
+    pub const DIMENSION_LABELS: &[&str] = &[
+        "method",
+        "client_name",
+        "client_version",
+        "transport",
+        "session",
+    ];
+
+    /// Content type of [`MetricsExporter::gather_openmetrics`] output.
+    pub const OPENMETRICS_CONTENT_TYPE: &str =
+        "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+    /// A trace exemplar attached to a histogram bucket.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Exemplar {
+        /// Trace ID of the observed request.
+        pub trace_id: String,
+        /// The observed value, in seconds.
+        pub value: f64,
+        /// When the observation was made.
+        pub timestamp: SystemTime,
+    }
+
+    /// Latest exemplar per method and response-time bucket; the last slot is
+    /// the `+Inf` bucket.
+    type ExemplarStore = Arc<Mutex<HashMap<String, Vec<Option<Exemplar>>>>>;
 
     /// MCP-specific Prometheus metrics.
     #[derive(Clone)]
@@ -925,6 +1278,30 @@ pub mod prom {
         pub active_connections: Gauge,
         /// Connection state gauge (by state).
         pub connection_state: GaugeVec,
+        /// Open sessions gauge, maintained by the HTTP integrations.
+        pub active_sessions: Gauge,
+        /// Open SSE streams gauge, maintained by the HTTP integrations.
+        pub sse_connections: Gauge,
+        /// Tool call count by tool.
+        pub tool_calls_total: CounterVec,
+        /// Failed tool call count by tool, counting both JSON-RPC errors and
+        /// results with `isError` set.
+        pub tool_errors_total: CounterVec,
+        /// Bytes kept off the wire by compression.
+        pub compression_saved_bytes: Counter,
+        /// Round-trip time of pings sent by a
+        /// [`PingSupervisor`](crate::ping::PingSupervisor).
+        pub ping_rtt_seconds: Histogram,
+        /// Supervised pings that timed out or failed.
+        pub pings_missed: Counter,
+        /// Request count by method and [dimension](super::dimensions),
+        /// labeled by [`DIMENSION_LABELS`].
+        pub client_requests_total: CounterVec,
+        /// Error response count by method and dimension.
+        pub client_errors_total: CounterVec,
+        /// Response time histogram by method and dimension.
+        pub client_response_time_seconds: HistogramVec,
+        exemplars: ExemplarStore,
     }
 
     impl McpMetrics {
@@ -981,9 +1358,7 @@ pub mod prom {
 
             let response_time_seconds = HistogramVec::new(
                 HistogramOpts::new("mcp_response_time_seconds", "MCP response time in seconds")
-                    .buckets(vec![
-                        0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
-                    ]),
+                    .buckets(RESPONSE_TIME_BUCKETS.to_vec()),
                 &["method"],
             )?;
             registry.register(Box::new(response_time_seconds.clone()))?;
@@ -998,6 +1373,77 @@ pub mod prom {
             )?;
             registry.register(Box::new(connection_state.clone()))?;
 
+            let active_sessions = Gauge::new("mcp_active_sessions", "Number of open MCP sessions")?;
+            registry.register(Box::new(active_sessions.clone()))?;
+
+            let sse_connections =
+                Gauge::new("mcp_sse_connections", "Number of open MCP SSE streams")?;
+            registry.register(Box::new(sse_connections.clone()))?;
+
+            let tool_calls_total = CounterVec::new(
+                Opts::new("mcp_tool_calls_total", "Total MCP tool calls by tool"),
+                &["tool"],
+            )?;
+            registry.register(Box::new(tool_calls_total.clone()))?;
+
+            let tool_errors_total = CounterVec::new(
+                Opts::new(
+                    "mcp_tool_errors_total",
+                    "Total failed MCP tool calls by tool",
+                ),
+                &["tool"],
+            )?;
+            registry.register(Box::new(tool_errors_total.clone()))?;
+
+            let compression_saved_bytes = Counter::new(
+                "mcp_compression_saved_bytes_total",
+                "Total bytes kept off the wire by MCP message compression",
+            )?;
+            registry.register(Box::new(compression_saved_bytes.clone()))?;
+
+            let ping_rtt_seconds = Histogram::with_opts(
+                HistogramOpts::new(
+                    "mcp_ping_rtt_seconds",
+                    "MCP ping round-trip time in seconds",
+                )
+                .buckets(RESPONSE_TIME_BUCKETS.to_vec()),
+            )?;
+            registry.register(Box::new(ping_rtt_seconds.clone()))?;
+
+            let pings_missed = Counter::new(
+                "mcp_pings_missed_total",
+                "Total MCP pings that went unanswered",
+            )?;
+            registry.register(Box::new(pings_missed.clone()))?;
+
+            let client_requests_total = CounterVec::new(
+                Opts::new(
+                    "mcp_client_requests_total",
+                    "Total MCP requests by method, client, transport, and session",
+                ),
+                DIMENSION_LABELS,
+            )?;
+            registry.register(Box::new(client_requests_total.clone()))?;
+
+            let client_errors_total = CounterVec::new(
+                Opts::new(
+                    "mcp_client_errors_total",
+                    "Total MCP error responses by method, client, transport, and session",
+                ),
+                DIMENSION_LABELS,
+            )?;
+            registry.register(Box::new(client_errors_total.clone()))?;
+
+            let client_response_time_seconds = HistogramVec::new(
+                HistogramOpts::new(
+                    "mcp_client_response_time_seconds",
+                    "MCP response time in seconds by method, client, transport, and session",
+                )
+                .buckets(RESPONSE_TIME_BUCKETS.to_vec()),
+                DIMENSION_LABELS,
+            )?;
+            registry.register(Box::new(client_response_time_seconds.clone()))?;
+
             Ok(Self {
                 registry: Arc::new(registry),
                 messages_sent,
@@ -1009,6 +1455,17 @@ pub mod prom {
                 response_time_seconds,
                 active_connections,
                 connection_state,
+                active_sessions,
+                sse_connections,
+                tool_calls_total,
+                tool_errors_total,
+                compression_saved_bytes,
+                ping_rtt_seconds,
+                pings_missed,
+                client_requests_total,
+                client_errors_total,
+                client_response_time_seconds,
+                exemplars: ExemplarStore::default(),
             })
         }
 
@@ -1030,16 +1487,71 @@ pub mod prom {
             self.bytes_received.inc_by(size as f64);
         }
 
+        /// Record a message compressed (or decompressed) between `original`
+        /// and `encoded` bytes.
+        pub fn record_compression(&self, original: usize, encoded: usize) {
+            self.compression_saved_bytes
+                .inc_by(original.saturating_sub(encoded) as f64);
+        }
+
+        /// Record the round-trip time of an answered ping.
+        pub fn record_ping_rtt(&self, seconds: f64) {
+            self.ping_rtt_seconds.observe(seconds);
+        }
+
+        /// Record a ping that went unanswered.
+        pub fn record_missed_ping(&self) {
+            self.pings_missed.inc();
+        }
+
         /// Record a request by method.
         pub fn record_request(&self, method: &str) {
             self.requests_total.with_label_values(&[method]).inc();
         }
 
         /// Record response time for a method.
+        ///
+        /// With the `opentelemetry` feature enabled, the trace ID of the
+        /// current span is attached as an exemplar.
         pub fn record_response_time(&self, method: &str, seconds: f64) {
+            self.record_response_time_with_exemplar(method, seconds, current_trace_id().as_deref());
+        }
+
+        /// Record response time for a method, attaching `trace_id` (if any) as
+        /// the exemplar of the bucket the observation falls into.
+        pub fn record_response_time_with_exemplar(
+            &self,
+            method: &str,
+            seconds: f64,
+            trace_id: Option<&str>,
+        ) {
             self.response_time_seconds
                 .with_label_values(&[method])
                 .observe(seconds);
+            let Some(trace_id) = trace_id else {
+                return;
+            };
+            let bucket = RESPONSE_TIME_BUCKETS
+                .iter()
+                .position(|&le| seconds <= le)
+                .unwrap_or(RESPONSE_TIME_BUCKETS.len());
+            if let Ok(mut exemplars) = self.exemplars.lock() {
+                let slots = exemplars
+                    .entry(method.to_string())
+                    .or_insert_with(|| vec![None; RESPONSE_TIME_BUCKETS.len() + 1]);
+                slots[bucket] = Some(Exemplar {
+                    trace_id: trace_id.to_string(),
+                    value: seconds,
+                    timestamp: SystemTime::now(),
+                });
+            }
+        }
+
+        /// The latest exemplar recorded for `method` in the bucket with upper
+        /// bound `le` (use `f64::INFINITY` for the `+Inf` bucket).
+        #[must_use]
+        pub fn exemplar(&self, method: &str, le: f64) -> Option<Exemplar> {
+            exemplar_for(&self.exemplars, method, le)
         }
 
         /// Record an error by type.
@@ -1066,6 +1578,67 @@ pub mod prom {
         pub fn set_connection_state(&self, state: &str, value: f64) {
             self.connection_state.with_label_values(&[state]).set(value);
         }
+
+        /// Set the open session count.
+        pub fn set_active_sessions(&self, count: usize) {
+            self.active_sessions.set(count as f64);
+        }
+
+        /// Count an open SSE stream until the returned guard is dropped.
+        #[must_use = "the stream is only counted while the guard is alive"]
+        pub fn sse_connection(&self) -> GaugeGuard {
+            GaugeGuard::new(self.sse_connections.clone())
+        }
+
+        /// Record a request by method under `labels`.
+        pub fn record_labeled_request(&self, labels: &Labels, method: &str) {
+            self.client_requests_total
+                .with_label_values(&dimension_values(labels, method))
+                .inc();
+        }
+
+        /// Record the response time of a `method` request under `labels`,
+        /// and whether the response was an error.
+        pub fn record_labeled_response(
+            &self,
+            labels: &Labels,
+            method: &str,
+            seconds: f64,
+            failed: bool,
+        ) {
+            let values = dimension_values(labels, method);
+            self.client_response_time_seconds
+                .with_label_values(&values)
+                .observe(seconds);
+            if failed {
+                self.client_errors_total.with_label_values(&values).inc();
+            }
+        }
+
+        /// Record a call to `tool`, and whether it failed.
+        pub fn record_tool_call(&self, tool: &str, failed: bool) {
+            self.tool_calls_total.with_label_values(&[tool]).inc();
+            if failed {
+                self.tool_errors_total.with_label_values(&[tool]).inc();
+            }
+        }
+    }
+
+    /// Holds a [`Gauge`] incremented until it is dropped.
+    #[derive(Debug)]
+    pub struct GaugeGuard(Gauge);
+
+    impl GaugeGuard {
+        fn new(gauge: Gauge) -> Self {
+            gauge.inc();
+            Self(gauge)
+        }
+    }
+
+    impl Drop for GaugeGuard {
+        fn drop(&mut self) {
+            self.0.dec();
+        }
     }
 
     impl std::fmt::Debug for McpMetrics {
@@ -1076,12 +1649,131 @@ pub mod prom {
         }
     }
 
+    /// Values for [`DIMENSION_LABELS`].
+    fn dimension_values<'a>(labels: &'a Labels, method: &'a str) -> [&'a str; 5] {
+        [
+            method,
+            &labels.client_name,
+            &labels.client_version,
+            &labels.transport,
+            &labels.session,
+        ]
+    }
+
+    /// The latest exemplar in `store` for `method` at bucket bound `le`.
+    fn exemplar_for(store: &ExemplarStore, method: &str, le: f64) -> Option<Exemplar> {
+        let bucket = RESPONSE_TIME_BUCKETS
+            .iter()
+            .position(|&bound| (bound - le).abs() < f64::EPSILON)
+            .or_else(|| le.is_infinite().then_some(RESPONSE_TIME_BUCKETS.len()))?;
+        store.lock().ok()?.get(method)?.get(bucket)?.clone()
+    }
+
+    /// The trace ID of the current span, when OpenTelemetry is enabled.
+    #[cfg(feature = "opentelemetry")]
+    fn current_trace_id() -> Option<String> {
+        super::otel::current_trace_id()
+    }
+
+    /// The trace ID of the current span, when OpenTelemetry is enabled.
+    #[cfg(not(feature = "opentelemetry"))]
+    #[allow(clippy::missing_const_for_fn, clippy::unnecessary_wraps)]
+    fn current_trace_id() -> Option<String> {
+        None
+    }
+
+    /// An in-flight request being timed.
+    struct PendingRequest {
+        method: String,
+        started: Instant,
+        trace_id: Option<String>,
+        labels: Option<Labels>,
+    }
+
+    /// Times request/response pairs on one connection and records them in
+    /// [`McpMetrics`].
+    ///
+    /// Requests are keyed by direction and id: an inbound request is answered
+    /// by an outbound response and vice versa.
+    pub(crate) struct RequestTimer {
+        metrics: McpMetrics,
+        pending: Mutex<HashMap<(bool, RequestId), PendingRequest>>,
+    }
+
+    impl RequestTimer {
+        /// Create a timer recording into `metrics`.
+        pub(crate) fn new(metrics: McpMetrics) -> Self {
+            Self {
+                metrics,
+                pending: Mutex::new(HashMap::new()),
+            }
+        }
+
+        /// Account for an outbound message, also under `labels` if the
+        /// connection has any.
+        pub(crate) fn on_send(&self, msg: &Message, size: usize, labels: Option<&Labels>) {
+            self.metrics.record_send(size);
+            self.observe(msg, false, labels);
+        }
+
+        /// Account for an inbound message, also under `labels` if the
+        /// connection has any.
+        pub(crate) fn on_recv(&self, msg: &Message, size: usize, labels: Option<&Labels>) {
+            self.metrics.record_receive(size);
+            self.observe(msg, true, labels);
+        }
+
+        fn observe(&self, msg: &Message, inbound: bool, labels: Option<&Labels>) {
+            let Ok(mut pending) = self.pending.lock() else {
+                return;
+            };
+            match msg {
+                Message::Request(request) => {
+                    self.metrics.record_request(&request.method);
+                    if let Some(labels) = labels {
+                        self.metrics.record_labeled_request(labels, &request.method);
+                    }
+                    pending.insert(
+                        (inbound, request.id.clone()),
+                        PendingRequest {
+                            method: request.method.to_string(),
+                            started: Instant::now(),
+                            trace_id: current_trace_id(),
+                            labels: labels.cloned(),
+                        },
+                    );
+                }
+                Message::Response(response) => {
+                    if let Some(request) = pending.remove(&(!inbound, response.id.clone())) {
+                        let seconds = request.started.elapsed().as_secs_f64();
+                        self.metrics.record_response_time_with_exemplar(
+                            &request.method,
+                            seconds,
+                            request.trace_id.as_deref(),
+                        );
+                        if let Some(labels) = &request.labels {
+                            self.metrics.record_labeled_response(
+                                labels,
+                                &request.method,
+                                seconds,
+                                response.error.is_some(),
+                            );
+                        }
+                    }
+                }
+                Message::Notification(_) => {}
+            }
+        }
+    }
+
     /// Prometheus metrics exporter.
     ///
-    /// Provides a way to export metrics in Prometheus text format.
+    /// Provides a way to export metrics in Prometheus text format, or in
+    /// OpenMetrics format with response-time exemplars.
     pub struct MetricsExporter {
         registry: Arc<Registry>,
         encoder: TextEncoder,
+        exemplars: Option<ExemplarStore>,
     }
 
     impl MetricsExporter {
@@ -1091,6 +1783,7 @@ pub mod prom {
             Self {
                 registry: Arc::new(registry.clone()),
                 encoder: TextEncoder::new(),
+                exemplars: None,
             }
         }
 
@@ -1100,6 +1793,7 @@ pub mod prom {
             Self {
                 registry: Arc::clone(&metrics.registry),
                 encoder: TextEncoder::new(),
+                exemplars: Some(Arc::clone(&metrics.exemplars)),
             }
         }
 
@@ -1115,6 +1809,68 @@ pub mod prom {
             Ok(String::from_utf8_lossy(&buffer).to_string())
         }
 
+        /// Gather and encode all metrics in OpenMetrics text format, with
+        /// exemplars on `mcp_response_time_seconds` buckets when the exporter
+        /// was created [from `McpMetrics`](Self::from_metrics).
+        ///
+        /// Serve it with [`OPENMETRICS_CONTENT_TYPE`].
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if encoding fails.
+        pub fn gather_openmetrics(&self) -> Result<String, prometheus::Error> {
+            let text = self.gather()?;
+            // OpenMetrics names a counter family without its `_total` suffix.
+            let counters: Vec<&str> = text
+                .lines()
+                .filter_map(|line| line.strip_prefix("# TYPE ")?.strip_suffix(" counter"))
+                .filter(|name| name.ends_with("_total"))
+                .collect();
+            let mut out = String::with_capacity(text.len() + 16);
+            for line in text.lines() {
+                if line.starts_with("# HELP ") || line.starts_with("# TYPE ") {
+                    let (prefix, rest) = line.split_at(7);
+                    let name = rest.split(' ').next().unwrap_or(rest);
+                    if counters.contains(&name) {
+                        let family = name.trim_end_matches("_total");
+                        let _ = writeln!(out, "{prefix}{family}{}", &rest[name.len()..]);
+                        continue;
+                    }
+                }
+                out.push_str(line);
+                if let Some(exemplar) = self.bucket_exemplar(line) {
+                    let ts = exemplar
+                        .timestamp
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs_f64();
+                    let _ = write!(
+                        out,
+                        " # {{trace_id=\"{}\"}} {} {ts:.3}",
+                        exemplar.trace_id, exemplar.value
+                    );
+                }
+                out.push('\n');
+            }
+            out.push_str("# EOF\n");
+            Ok(out)
+        }
+
+        /// The exemplar for a `mcp_response_time_seconds_bucket` sample line.
+        fn bucket_exemplar(&self, line: &str) -> Option<Exemplar> {
+            let labels = line.strip_prefix("mcp_response_time_seconds_bucket{")?;
+            let label = |name: &str| {
+                let start = labels.find(&format!("{name}=\""))? + name.len() + 2;
+                let len = labels[start..].find('"')?;
+                Some(&labels[start..start + len])
+            };
+            let le = match label("le")? {
+                "+Inf" => f64::INFINITY,
+                le => le.parse().ok()?,
+            };
+            exemplar_for(self.exemplars.as_ref()?, label("method")?, le)
+        }
+
         /// Get the content type for the encoded metrics.
         #[must_use]
         pub fn content_type(&self) -> &str {
@@ -1245,4 +2001,294 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_trace_context_params_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = propagation::TraceContext::parse(traceparent, Some("vendor=value"))
+            .ok_or("traceparent not parsed")?;
+
+        let params = propagation::inject_into_params(
+            &context,
+            Some(serde_json::json!({ "name": "search" })),
+        );
+        assert_eq!(params["name"], "search");
+        assert_eq!(params["_meta"]["traceparent"], traceparent);
+
+        let back = propagation::extract_from_params(&params).ok_or("context not found")?;
+        assert_eq!(back.traceparent(), traceparent);
+        assert_eq!(back.tracestate.as_deref(), Some("vendor=value"));
+        assert!(propagation::TraceContext::parse("garbage", None).is_none());
+        Ok(())
+    }
+
+    #[cfg(feature = "opentelemetry")]
+    #[test]
+    fn test_request_span_continues_remote_trace() -> Result<(), Box<dyn std::error::Error>> {
+        use opentelemetry::trace::TracerProvider as _;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let request = Request::with_params(
+            "tools/call",
+            1u64,
+            serde_json::json!({
+                "name": "search",
+                "_meta": {
+                    "traceparent": "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+                },
+            }),
+        );
+
+        let (trace_id, outgoing) = tracing::subscriber::with_default(subscriber, || {
+            let span = request_span(&request);
+            let _guard = span.enter();
+            (otel::current_trace_id(), inject_current_context(None))
+        });
+
+        assert_eq!(
+            trace_id.as_deref(),
+            Some("4bf92f3577b34da6a3ce929d0e0e4736")
+        );
+        let outgoing = outgoing.ok_or("no context injected")?;
+        let child = propagation::extract_from_params(&outgoing).ok_or("no traceparent")?;
+        assert_eq!(child.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_ne!(child.parent_id, "00f067aa0ba902b7");
+        Ok(())
+    }
+
+    #[test]
+    fn test_inject_without_trace_leaves_params() {
+        let params = Some(serde_json::json!({ "name": "search" }));
+        assert_eq!(inject_current_context(params.clone()), params);
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[test]
+    fn test_tool_and_sse_metrics() -> Result<(), Box<dyn std::error::Error>> {
+        use prom::McpMetrics;
+
+        let metrics = McpMetrics::new()?;
+        metrics.record_tool_call("add", false);
+        metrics.record_tool_call("add", true);
+        let guard = metrics.sse_connection();
+        metrics.set_active_sessions(3);
+
+        let calls = metrics.tool_calls_total.with_label_values(&["add"]).get();
+        let errors = metrics.tool_errors_total.with_label_values(&["add"]).get();
+        assert!((calls - 2.0).abs() < f64::EPSILON);
+        assert!((errors - 1.0).abs() < f64::EPSILON);
+        assert!((metrics.sse_connections.get() - 1.0).abs() < f64::EPSILON);
+        assert!((metrics.active_sessions.get() - 3.0).abs() < f64::EPSILON);
+
+        drop(guard);
+        assert!(metrics.sse_connections.get().abs() < f64::EPSILON);
+        Ok(())
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[test]
+    fn test_openmetrics_exemplars() -> Result<(), Box<dyn std::error::Error>> {
+        use prom::{McpMetrics, MetricsExporter};
+
+        let metrics = McpMetrics::new()?;
+        metrics.record_request("tools/call");
+        metrics.record_response_time_with_exemplar(
+            "tools/call",
+            0.042,
+            Some("4bf92f3577b34da6a3ce929d0e0e4736"),
+        );
+        metrics.record_response_time_with_exemplar("tools/call", 0.2, None);
+
+        let exemplar = metrics.exemplar("tools/call", 0.05).ok_or("no exemplar")?;
+        assert_eq!(exemplar.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(metrics.exemplar("tools/call", 0.25).is_none());
+
+        let body = MetricsExporter::from_metrics(&metrics).gather_openmetrics()?;
+        let bucket = body
+            .lines()
+            .find(|l| {
+                l.starts_with("mcp_response_time_seconds_bucket") && l.contains("le=\"0.05\"")
+            })
+            .ok_or("bucket missing")?;
+        assert!(
+            bucket.contains(" # {trace_id=\"4bf92f3577b34da6a3ce929d0e0e4736\"} 0.042 "),
+            "{bucket}"
+        );
+        assert!(body.contains("# TYPE mcp_requests counter"), "{body}");
+        assert!(body.contains("mcp_requests_total{method=\"tools/call\"} 1"));
+        assert!(body.ends_with("# EOF\n"));
+
+        // Plain Prometheus text cannot carry exemplars.
+        assert!(
+            !MetricsExporter::from_metrics(&metrics)
+                .gather()?
+                .contains("trace_id")
+        );
+        Ok(())
+    }
+
+    #[cfg(all(feature = "prometheus", feature = "tokio-runtime"))]
+    #[tokio::test]
+    async fn test_transport_times_requests_into_prometheus()
+    -> Result<(), Box<dyn std::error::Error>> {
+        use crate::memory::MemoryTransport;
+        use mcpkit_core::protocol::{Request, Response};
+
+        let metrics = prom::McpMetrics::new()?;
+        let (client, server) = MemoryTransport::pair();
+        let server = TelemetryTransport::new(server, TelemetryConfig::default())
+            .with_prometheus(metrics.clone());
+
+        client
+            .send(Message::Request(Request::new("tools/call", 7u64)))
+            .await?;
+        let Some(Message::Request(request)) = server.recv().await? else {
+            return Err("expected a request".into());
+        };
+        server
+            .send(Message::Response(Response::success(
+                request.id,
+                serde_json::json!({}),
+            )))
+            .await?;
+
+        let histogram = metrics
+            .response_time_seconds
+            .with_label_values(&["tools/call"]);
+        assert_eq!(histogram.get_sample_count(), 1);
+        assert_eq!(
+            metrics
+                .requests_total
+                .with_label_values(&["tools/call"])
+                .get(),
+            1.0
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn test_metrics_by_labels_cap_sessions() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::memory::MemoryTransport;
+        use crate::middleware::TransportLayer;
+        use dimensions::{Dimensions, OVERFLOW_LABEL};
+        use mcpkit_core::protocol::Notification;
+
+        let layer = TelemetryLayer::new(
+            TelemetryConfig::default().with_dimensions(Dimensions::all().with_max_values(1)),
+        );
+        for _ in 0..2 {
+            let (client, server) = MemoryTransport::pair();
+            let server = layer.layer(server);
+            client
+                .send(Message::Notification(Notification::new("ping")))
+                .await?;
+            server.recv().await?;
+        }
+
+        let snapshot = layer.metrics_by_labels().snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.iter().all(|e| e.labels.transport == "memory"));
+        assert!(snapshot.iter().all(|e| e.metrics.messages_received == 1));
+        assert_eq!(
+            snapshot
+                .iter()
+                .filter(|e| e.labels.session == OVERFLOW_LABEL)
+                .count(),
+            1
+        );
+        assert_eq!(layer.cardinality_guard().overflows(), 1);
+        assert_eq!(layer.metrics().snapshot().messages_received, 2);
+        Ok(())
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn test_labeled_response_times_by_client() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::memory::MemoryTransport;
+        use mcpkit_core::error::JsonRpcError;
+        use mcpkit_core::protocol::{Request, Response};
+
+        let metrics = prom::McpMetrics::new()?;
+        let (client, server) = MemoryTransport::pair();
+        let server = TelemetryTransport::new(
+            server,
+            TelemetryConfig::default().with_dimensions(
+                dimensions::Dimensions::none()
+                    .with_client()
+                    .with_transport(),
+            ),
+        )
+        .with_prometheus(metrics.clone());
+
+        let init = Request::with_params(
+            "initialize",
+            1u64,
+            serde_json::json!({ "clientInfo": { "name": "cursor", "version": "1.2" } }),
+        );
+        client.send(Message::Request(init)).await?;
+        client
+            .send(Message::Request(Request::new("tools/call", 2u64)))
+            .await?;
+        for _ in 0..2 {
+            let Some(Message::Request(request)) = server.recv().await? else {
+                return Err("expected a request".into());
+            };
+            let response = if request.method == "tools/call" {
+                Response::error(request.id, JsonRpcError::internal_error("boom"))
+            } else {
+                Response::success(request.id, serde_json::json!({}))
+            };
+            server.send(Message::Response(response)).await?;
+        }
+
+        let values = ["tools/call", "cursor", "1.2", "memory", ""];
+        assert_eq!(
+            metrics
+                .client_requests_total
+                .with_label_values(&values)
+                .get(),
+            1.0
+        );
+        assert_eq!(
+            metrics.client_errors_total.with_label_values(&values).get(),
+            1.0
+        );
+        assert_eq!(
+            metrics
+                .client_response_time_seconds
+                .with_label_values(&values)
+                .get_sample_count(),
+            1
+        );
+        Ok(())
+    }
+
+    #[cfg(all(feature = "prometheus", feature = "opentelemetry"))]
+    #[test]
+    fn test_response_time_exemplar_from_current_span() -> Result<(), Box<dyn std::error::Error>> {
+        use opentelemetry::trace::TracerProvider as _;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let metrics = prom::McpMetrics::new()?;
+
+        let trace_id = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("tools/call");
+            let _guard = span.enter();
+            metrics.record_response_time("tools/call", 0.003);
+            otel::current_trace_id()
+        })
+        .ok_or("no active trace")?;
+
+        let exemplar = metrics.exemplar("tools/call", 0.005).ok_or("no exemplar")?;
+        assert_eq!(exemplar.trace_id, trace_id);
+        assert_eq!(trace_id.len(), 32);
+        Ok(())
+    }
 }
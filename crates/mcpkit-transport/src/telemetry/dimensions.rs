@@ -0,0 +1,433 @@
+//! Breaking telemetry down by session, client, and transport.
+//!
+//! [`TelemetryMetrics`] aggregates every connection into one set of counters.
+//! With [`Dimensions`] enabled in the
+//! [`TelemetryConfig`](super::TelemetryConfig), a
+//! [`TelemetryLayer`](super::TelemetryLayer) also keeps counters and latency
+//! histograms per combination of [`Labels`]:
+//!
+//! | Dimension | Label | Source |
+//! |-----------|-------|--------|
+//! | Session | `session` | Hash of the connection's session id |
+//! | Client | `client_name`, `client_version` | `clientInfo` of the `initialize` request |
+//! | Transport | `transport` | The inner transport's `transport_type` |
+//!
+//! Session ids are hashed ([`session_label`]) so they never appear in metric
+//! output. Labels come from peers, so every dimension is capped by a
+//! [`CardinalityGuard`]: once `max_values` distinct values have been seen,
+//! new ones are reported as [`OVERFLOW_LABEL`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use mcpkit_transport::telemetry::dimensions::Dimensions;
+//! use mcpkit_transport::telemetry::{TelemetryConfig, TelemetryLayer};
+//!
+//! let layer = TelemetryLayer::new(
+//!     TelemetryConfig::new("my-mcp-service")
+//!         .with_dimensions(Dimensions::none().with_client().with_transport()),
+//! );
+//!
+//! for entry in layer.metrics_by_labels().snapshot() {
+//!     println!(
+//!         "{} {}: {:.1}% errors",
+//!         entry.labels.client_name,
+//!         entry.labels.client_version,
+//!         entry.metrics.error_rate() * 100.0
+//!     );
+//! }
+//! ```
+
+use super::{LatencyHistogram, MetricsSnapshot, TelemetryMetrics};
+use mcpkit_core::protocol::Message;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Default number of distinct values kept per dimension.
+pub const DEFAULT_MAX_LABEL_VALUES: usize = 100;
+
+/// Label value reported once a dimension has reached its limit.
+pub const OVERFLOW_LABEL: &str = "other";
+
+/// Which dimensions telemetry is broken down by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dimensions {
+    /// Label by a hash of the session id.
+    pub session: bool,
+    /// Label by the client name and version sent in `initialize`.
+    pub client: bool,
+    /// Label by transport kind.
+    pub transport: bool,
+    /// Distinct values kept per dimension before reporting
+    /// [`OVERFLOW_LABEL`].
+    pub max_values: usize,
+}
+
+impl Dimensions {
+    /// No dimensions; only the global metrics are kept.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            session: false,
+            client: false,
+            transport: false,
+            max_values: DEFAULT_MAX_LABEL_VALUES,
+        }
+    }
+
+    /// Every dimension.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            session: true,
+            client: true,
+            transport: true,
+            max_values: DEFAULT_MAX_LABEL_VALUES,
+        }
+    }
+
+    /// Label by a hash of the session id.
+    #[must_use]
+    pub const fn with_session(mut self) -> Self {
+        self.session = true;
+        self
+    }
+
+    /// Label by client name and version.
+    #[must_use]
+    pub const fn with_client(mut self) -> Self {
+        self.client = true;
+        self
+    }
+
+    /// Label by transport kind.
+    #[must_use]
+    pub const fn with_transport(mut self) -> Self {
+        self.transport = true;
+        self
+    }
+
+    /// Keep at most `max` distinct values per dimension (at least 1).
+    #[must_use]
+    pub const fn with_max_values(mut self, max: usize) -> Self {
+        self.max_values = if max == 0 { 1 } else { max };
+        self
+    }
+
+    /// Whether any dimension is enabled.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.session || self.client || self.transport
+    }
+}
+
+impl Default for Dimensions {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// The label values telemetry is recorded under.
+///
+/// Disabled dimensions are empty strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Labels {
+    /// Hash of the session id ([`session_label`]).
+    pub session: String,
+    /// Client name from `initialize`.
+    pub client_name: String,
+    /// Client version from `initialize`.
+    pub client_version: String,
+    /// Transport kind (`stdio`, `websocket`, ...).
+    pub transport: String,
+}
+
+/// The label a session id is reported as: its 64-bit FNV-1a hash in hex.
+///
+/// Hashing keeps session ids, which may grant access to the session, out of
+/// metric output while still telling sessions apart.
+#[must_use]
+pub fn session_label(session_id: &str) -> String {
+    let hash = session_id
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// Caps the number of distinct values of each dimension.
+///
+/// Values are admitted first come, first served; once a dimension holds
+/// `max_values` of them, every new value maps to [`OVERFLOW_LABEL`]. A client
+/// name and version are admitted together as one value.
+#[derive(Debug)]
+pub struct CardinalityGuard {
+    max_values: usize,
+    sessions: Mutex<HashSet<String>>,
+    clients: Mutex<HashSet<(String, String)>>,
+    transports: Mutex<HashSet<String>>,
+    overflows: AtomicU64,
+}
+
+impl CardinalityGuard {
+    /// Create a guard keeping `max_values` values per dimension.
+    #[must_use]
+    pub fn new(max_values: usize) -> Self {
+        Self {
+            max_values: max_values.max(1),
+            sessions: Mutex::new(HashSet::new()),
+            clients: Mutex::new(HashSet::new()),
+            transports: Mutex::new(HashSet::new()),
+            overflows: AtomicU64::new(0),
+        }
+    }
+
+    /// The label for a session label, or [`OVERFLOW_LABEL`].
+    pub fn session(&self, label: String) -> String {
+        self.admit(&self.sessions, label)
+            .unwrap_or_else(|| OVERFLOW_LABEL.to_string())
+    }
+
+    /// The labels for a client name and version, or [`OVERFLOW_LABEL`] for
+    /// both.
+    pub fn client(&self, name: String, version: String) -> (String, String) {
+        self.admit(&self.clients, (name, version))
+            .unwrap_or_else(|| (OVERFLOW_LABEL.to_string(), OVERFLOW_LABEL.to_string()))
+    }
+
+    /// The label for a transport kind, or [`OVERFLOW_LABEL`].
+    pub fn transport(&self, kind: String) -> String {
+        self.admit(&self.transports, kind)
+            .unwrap_or_else(|| OVERFLOW_LABEL.to_string())
+    }
+
+    /// How many values were reported as [`OVERFLOW_LABEL`].
+    #[must_use]
+    pub fn overflows(&self) -> u64 {
+        self.overflows.load(Ordering::Relaxed)
+    }
+
+    fn admit<V: Clone + Eq + std::hash::Hash>(
+        &self,
+        seen: &Mutex<HashSet<V>>,
+        value: V,
+    ) -> Option<V> {
+        let mut seen = seen.lock().ok()?;
+        if seen.contains(&value) {
+            return Some(value);
+        }
+        if seen.len() < self.max_values {
+            seen.insert(value.clone());
+            return Some(value);
+        }
+        self.overflows.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+}
+
+/// Counters and latencies recorded under one set of [`Labels`].
+#[derive(Debug, Default)]
+pub(crate) struct LabeledMetrics {
+    pub(crate) metrics: TelemetryMetrics,
+    pub(crate) send_latency: LatencyHistogram,
+    pub(crate) recv_latency: LatencyHistogram,
+}
+
+/// A point-in-time snapshot of the metrics recorded under one set of
+/// [`Labels`].
+#[derive(Debug, Clone)]
+pub struct LabeledSnapshot {
+    /// The labels the metrics were recorded under.
+    pub labels: Labels,
+    /// Message, byte, and error counts.
+    pub metrics: MetricsSnapshot,
+    /// Average send latency in milliseconds.
+    pub send_latency_avg_ms: f64,
+    /// 99th percentile send latency in milliseconds.
+    pub send_latency_p99_ms: u64,
+    /// Average receive latency in milliseconds.
+    pub recv_latency_avg_ms: f64,
+    /// 99th percentile receive latency in milliseconds.
+    pub recv_latency_p99_ms: u64,
+}
+
+/// Metrics kept per combination of [`Labels`].
+#[derive(Debug, Default)]
+pub struct DimensionalMetrics {
+    entries: Mutex<HashMap<Labels, Arc<LabeledMetrics>>>,
+}
+
+impl DimensionalMetrics {
+    /// Create an empty set of metrics.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The metrics recorded under `labels`, created on first use.
+    pub(crate) fn entry(&self, labels: &Labels) -> Arc<LabeledMetrics> {
+        let Ok(mut entries) = self.entries.lock() else {
+            return Arc::default();
+        };
+        Arc::clone(entries.entry(labels.clone()).or_default())
+    }
+
+    /// Snapshot the metrics of every label combination seen, ordered by
+    /// labels.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<LabeledSnapshot> {
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let mut snapshots: Vec<_> = entries
+            .iter()
+            .map(|(labels, entry)| LabeledSnapshot {
+                labels: labels.clone(),
+                metrics: entry.metrics.snapshot(),
+                send_latency_avg_ms: entry.send_latency.average_ms(),
+                send_latency_p99_ms: entry.send_latency.percentile(99.0),
+                recv_latency_avg_ms: entry.recv_latency.average_ms(),
+                recv_latency_p99_ms: entry.recv_latency.percentile(99.0),
+            })
+            .collect();
+        snapshots.sort_by(|a, b| a.labels.cmp(&b.labels));
+        snapshots
+    }
+
+    /// Number of label combinations seen.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.lock().map_or(0, |entries| entries.len())
+    }
+
+    /// Whether no label combination has been seen.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The labels of one connection, updated as its client identifies itself.
+pub(crate) struct ConnectionLabels {
+    dimensions: Dimensions,
+    guard: Arc<CardinalityGuard>,
+    store: Arc<DimensionalMetrics>,
+    current: Mutex<(Labels, Arc<LabeledMetrics>)>,
+}
+
+impl ConnectionLabels {
+    /// Labels for a connection over `transport` with session `session_id`.
+    pub(crate) fn new(
+        dimensions: Dimensions,
+        guard: Arc<CardinalityGuard>,
+        store: Arc<DimensionalMetrics>,
+        session_id: &str,
+        transport: &str,
+    ) -> Self {
+        let mut labels = Labels::default();
+        if dimensions.session {
+            labels.session = guard.session(session_label(session_id));
+        }
+        if dimensions.transport {
+            labels.transport = guard.transport(transport.to_string());
+        }
+        let entry = store.entry(&labels);
+        Self {
+            dimensions,
+            guard,
+            store,
+            current: Mutex::new((labels, entry)),
+        }
+    }
+
+    /// Replace the session id.
+    pub(crate) fn set_session(&self, session_id: &str) {
+        if self.dimensions.session {
+            let label = self.guard.session(session_label(session_id));
+            self.update(|labels| labels.session = label);
+        }
+    }
+
+    /// Pick up the client identity from an `initialize` request, then return
+    /// the labels and metrics `msg` is recorded under.
+    pub(crate) fn observe(&self, msg: &Message) -> (Labels, Arc<LabeledMetrics>) {
+        if self.dimensions.client {
+            if let Message::Request(request) = msg {
+                if request.method == "initialize" {
+                    let info = request
+                        .params
+                        .as_ref()
+                        .and_then(|params| params.get("clientInfo"));
+                    let field = |name: &str| {
+                        info.and_then(|info| info.get(name))
+                            .and_then(serde_json::Value::as_str)
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    let (name, version) = self.guard.client(field("name"), field("version"));
+                    self.update(|labels| {
+                        labels.client_name = name;
+                        labels.client_version = version;
+                    });
+                }
+            }
+        }
+        self.current()
+    }
+
+    /// The labels and metrics the connection currently records under.
+    pub(crate) fn current(&self) -> (Labels, Arc<LabeledMetrics>) {
+        self.current
+            .lock()
+            .map(|current| current.clone())
+            .unwrap_or_default()
+    }
+
+    fn update(&self, change: impl FnOnce(&mut Labels)) {
+        if let Ok(mut current) = self.current.lock() {
+            let mut labels = current.0.clone();
+            change(&mut labels);
+            if labels != current.0 {
+                let entry = self.store.entry(&labels);
+                *current = (labels, entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_caps_each_dimension() {
+        let guard = CardinalityGuard::new(2);
+        assert_eq!(guard.transport("stdio".into()), "stdio");
+        assert_eq!(guard.transport("unix".into()), "unix");
+        assert_eq!(guard.transport("stdio".into()), "stdio");
+        assert_eq!(guard.transport("websocket".into()), OVERFLOW_LABEL);
+
+        assert_eq!(
+            guard.client("a".into(), "1".into()),
+            ("a".to_string(), "1".to_string())
+        );
+        guard.client("a".into(), "2".into());
+        assert_eq!(
+            guard.client("b".into(), "1".into()),
+            (OVERFLOW_LABEL.to_string(), OVERFLOW_LABEL.to_string())
+        );
+        assert_eq!(guard.overflows(), 2);
+    }
+
+    #[test]
+    fn session_label_hides_the_id() {
+        let label = session_label("secret-session-id");
+        assert_eq!(label.len(), 16);
+        assert!(!label.contains("secret"));
+        assert_eq!(label, session_label("secret-session-id"));
+        assert_ne!(label, session_label("other-session-id"));
+        assert_eq!(session_label(""), "cbf29ce484222325");
+    }
+}
@@ -43,6 +43,21 @@ pub struct TransportMetadata {
     pub bidirectional: bool,
     /// Custom metadata specific to the transport type.
     pub custom: Option<serde_json::Value>,
+    /// Credentials of the process on the other end, for local transports
+    /// that can report them (Unix domain sockets).
+    pub peer_credentials: Option<PeerCredentials>,
+}
+
+/// Identity of the process on the other end of a local connection, as
+/// reported by the operating system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PeerCredentials {
+    /// User id.
+    pub uid: u32,
+    /// Group id.
+    pub gid: u32,
+    /// Process id, where the platform reports it.
+    pub pid: Option<i32>,
 }
 
 impl TransportMetadata {
@@ -56,6 +71,7 @@ impl TransportMetadata {
             connected_at: None,
             bidirectional: true,
             custom: None,
+            peer_credentials: None,
         }
     }
 
@@ -73,6 +89,13 @@ impl TransportMetadata {
         self
     }
 
+    /// Set the peer's credentials.
+    #[must_use]
+    pub const fn peer_credentials(mut self, credentials: PeerCredentials) -> Self {
+        self.peer_credentials = Some(credentials);
+        self
+    }
+
     /// Mark the connection time.
     #[must_use]
     pub fn connected_now(mut self) -> Self {
@@ -148,6 +171,17 @@ pub trait Transport: Send + Sync {
 
     /// Get metadata about the transport.
     fn metadata(&self) -> TransportMetadata;
+
+    /// How many times the transport has re-established its connection.
+    ///
+    /// Transports that reconnect on their own bump this before reporting the
+    /// reconnection as an error from [`recv`](Self::recv), so that callers can
+    /// tell a recoverable reconnection from a fatal error and rebuild session
+    /// state. Wrappers forward their inner transport's value. Defaults to `0`
+    /// for transports that never reconnect.
+    fn connection_epoch(&self) -> u64 {
+        0
+    }
 }
 
 /// Listener trait for server-side transports.
@@ -155,8 +189,8 @@ pub trait Transport: Send + Sync {
 /// Transport listeners accept incoming connections and produce
 /// transport instances for each connection.
 ///
-/// Implementations include [`WebSocketListener`](crate::WebSocketListener)
-/// and [`UnixListener`](crate::unix::UnixListener) (Unix only).
+/// Implementations include [`WebSocketListener`](crate::WebSocketListener),
+/// `UnixListener` (Unix only), and `NamedPipeServer` (Windows only).
 pub trait TransportListener: Send + Sync {
     /// The type of transport produced by this listener.
     type Transport: Transport;
@@ -10,6 +10,8 @@
 //! - Abstract socket namespace support (Linux)
 //! - Automatic cleanup of socket files
 //! - Newline-delimited JSON message framing
+//! - Peer credentials (uid, gid, pid) of each connection, with an optional
+//!   allowlist on the listener
 //!
 //! # Example
 //!
@@ -26,14 +28,36 @@
 //!     assert!(config.cleanup_on_close);
 //! }
 //! ```
+//!
+//! # Peer Credentials
+//!
+//! The operating system reports the user, group, and process on the other
+//! end of each connection. They are available from
+//! [`UnixTransport::peer_credentials`] and in the transport's
+//! [`TransportMetadata`]. A listener with a [`PeerAllowlist`] drops
+//! connections from other users before reading anything from them:
+//!
+//! ```rust
+//! #[cfg(unix)]
+//! fn example() {
+//!     use mcpkit_transport::unix::{PeerAllowlist, UnixSocketConfig};
+//!
+//!     let config = UnixSocketConfig::new("/tmp/mcp.sock")
+//!         .with_allowed_peers(PeerAllowlist::new().uid(1000).gid(100));
+//!     assert!(config.allowed_peers.is_some());
+//! }
+//! ```
 
 use crate::error::TransportError;
+use crate::limits::{LimitEnforcer, TransportLimits};
 use crate::runtime::AsyncMutex;
-use crate::traits::{Transport, TransportListener, TransportMetadata};
+use crate::traits::{PeerCredentials, Transport, TransportListener, TransportMetadata};
 use mcpkit_core::protocol::Message;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
+#[cfg(feature = "tokio-runtime")]
+use crate::codec;
 #[cfg(feature = "tokio-runtime")]
 use tokio::{
     io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter},
@@ -54,8 +78,11 @@ pub struct UnixSocketConfig {
     pub read_buffer_size: usize,
     /// Buffer size for writing.
     pub write_buffer_size: usize,
-    /// Maximum message size in bytes.
-    pub max_message_size: usize,
+    /// Message size, in-flight, and read timeout limits.
+    pub limits: TransportLimits,
+    /// Peers a listener accepts connections from; `None` accepts everyone
+    /// who can open the socket.
+    pub allowed_peers: Option<PeerAllowlist>,
 }
 
 impl UnixSocketConfig {
@@ -66,7 +93,8 @@ impl UnixSocketConfig {
             cleanup_on_close: true,
             read_buffer_size: 64 * 1024,  // 64 KB
             write_buffer_size: 64 * 1024, // 64 KB
-            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            limits: TransportLimits::new().with_max_message_size(DEFAULT_MAX_MESSAGE_SIZE),
+            allowed_peers: None,
         }
     }
 
@@ -94,11 +122,65 @@ impl UnixSocketConfig {
     /// Set the maximum message size.
     #[must_use]
     pub const fn with_max_message_size(mut self, size: usize) -> Self {
-        self.max_message_size = size;
+        self.limits.max_message_size = size;
+        self
+    }
+
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Accept connections only from peers on `allowlist`.
+    #[must_use]
+    pub fn with_allowed_peers(mut self, allowlist: PeerAllowlist) -> Self {
+        self.allowed_peers = Some(allowlist);
         self
     }
 }
 
+/// Users and groups a [`UnixListener`] accepts connections from.
+///
+/// A peer is accepted when its user id or its group id is listed. A peer
+/// whose credentials cannot be read is rejected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PeerAllowlist {
+    /// Allowed user ids.
+    pub uids: Vec<u32>,
+    /// Allowed group ids.
+    pub gids: Vec<u32>,
+}
+
+impl PeerAllowlist {
+    /// Create an allowlist that accepts no one.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept peers running as user `uid`.
+    #[must_use]
+    pub fn uid(mut self, uid: u32) -> Self {
+        self.uids.push(uid);
+        self
+    }
+
+    /// Accept peers running with primary group `gid`.
+    #[must_use]
+    pub fn gid(mut self, gid: u32) -> Self {
+        self.gids.push(gid);
+        self
+    }
+
+    /// Whether a peer with `credentials` is accepted.
+    #[must_use]
+    pub fn allows(&self, credentials: &PeerCredentials) -> bool {
+        self.uids.contains(&credentials.uid) || self.gids.contains(&credentials.gid)
+    }
+}
+
 /// Split Unix stream for reading.
 #[cfg(feature = "tokio-runtime")]
 type UnixReader = BufReader<tokio::net::unix::OwnedReadHalf>;
@@ -124,17 +206,36 @@ struct UnixTransportState {
 /// Provides low-latency local IPC using Unix domain sockets.
 pub struct UnixTransport {
     config: UnixSocketConfig,
+    limits: LimitEnforcer,
     state: AsyncMutex<UnixTransportState>,
     connected: AtomicBool,
     messages_sent: AtomicU64,
     messages_received: AtomicU64,
     is_server_side: bool,
+    peer: Option<PeerCredentials>,
+}
+
+/// The credentials of the process on the other end of `stream`.
+#[cfg(feature = "tokio-runtime")]
+fn peer_credentials(stream: &UnixStream) -> Option<PeerCredentials> {
+    match stream.peer_cred() {
+        Ok(cred) => Some(PeerCredentials {
+            uid: cred.uid(),
+            gid: cred.gid(),
+            pid: cred.pid(),
+        }),
+        Err(e) => {
+            tracing::debug!(error = %e, "Could not read Unix socket peer credentials");
+            None
+        }
+    }
 }
 
 impl UnixTransport {
     /// Create a new Unix socket transport from an existing stream.
     #[cfg(feature = "tokio-runtime")]
     fn from_stream(config: UnixSocketConfig, stream: UnixStream, is_server_side: bool) -> Self {
+        let peer = peer_credentials(&stream);
         let (read_half, write_half) = stream.into_split();
         let reader = BufReader::new(read_half);
         let writer = BufWriter::new(write_half);
@@ -145,11 +246,13 @@ impl UnixTransport {
                 writer: Some(writer),
                 line_buffer: String::with_capacity(4096),
             }),
+            limits: LimitEnforcer::new(config.limits.clone()),
             config,
             connected: AtomicBool::new(true),
             messages_sent: AtomicU64::new(0),
             messages_received: AtomicU64::new(0),
             is_server_side,
+            peer,
         }
     }
 
@@ -160,11 +263,13 @@ impl UnixTransport {
             state: AsyncMutex::new(UnixTransportState {
                 line_buffer: String::with_capacity(4096),
             }),
+            limits: LimitEnforcer::new(config.limits.clone()),
             config,
             connected: AtomicBool::new(false),
             messages_sent: AtomicU64::new(0),
             messages_received: AtomicU64::new(0),
             is_server_side,
+            peer: None,
         }
     }
 
@@ -223,6 +328,77 @@ impl UnixTransport {
     pub fn messages_received(&self) -> u64 {
         self.messages_received.load(Ordering::Relaxed)
     }
+
+    /// The credentials of the process on the other end, if the operating
+    /// system reported them.
+    pub const fn peer_credentials(&self) -> Option<PeerCredentials> {
+        self.peer
+    }
+}
+
+impl UnixTransport {
+    #[cfg(feature = "tokio-runtime")]
+    /// Read the next message from the socket.
+    async fn read_message(&self) -> Result<Option<Message>, TransportError> {
+        if !self.connected.load(Ordering::Acquire) {
+            return Ok(None);
+        }
+
+        let mut state = self.state.lock().await;
+
+        let state = &mut *state;
+        let Some(reader) = state.reader.as_mut() else {
+            return Ok(None);
+        };
+
+        // Clear the buffer and read a line
+        state.line_buffer.clear();
+
+        // Bound the read to one byte past the limit so a peer that never sends a
+        // newline cannot grow `line_buffer` without bound; the size check below
+        // then rejects it. Without this, `read_line` would buffer unboundedly
+        // before the post-read check could fire. The reader stays in place so a
+        // read abandoned by the read timeout does not lose the stream.
+        let max = self.config.limits.max_message_size;
+        let result = reader
+            .take(max as u64 + 1)
+            .read_line(&mut state.line_buffer)
+            .await;
+
+        match result {
+            Ok(0) => {
+                // EOF - connection closed
+                self.connected.store(false, Ordering::Release);
+                Ok(None)
+            }
+            Ok(_) => {
+                // Parse the message (trim the newline)
+                let line = state.line_buffer.trim_end();
+                if line.is_empty() {
+                    return Ok(None);
+                }
+
+                // Check message size limit
+                if line.len() > self.config.limits.max_message_size {
+                    return Err(TransportError::MessageTooLarge {
+                        size: line.len(),
+                        max: self.config.limits.max_message_size,
+                    });
+                }
+
+                let msg = codec::decode_slice(line.as_bytes())?;
+
+                self.messages_received.fetch_add(1, Ordering::Relaxed);
+                Ok(Some(msg))
+            }
+            Err(e) => {
+                self.connected.store(false, Ordering::Release);
+                Err(TransportError::Io {
+                    message: format!("Failed to read from Unix socket: {e}"),
+                })
+            }
+        }
+    }
 }
 
 impl Transport for UnixTransport {
@@ -241,13 +417,7 @@ impl Transport for UnixTransport {
             message: format!("Failed to serialize message: {e}"),
         })?;
 
-        // Check message size limit
-        if data.len() > self.config.max_message_size {
-            return Err(TransportError::MessageTooLarge {
-                size: data.len(),
-                max: self.config.max_message_size,
-            });
-        }
+        self.limits.send(&msg, data.len())?;
 
         data.push(b'\n');
 
@@ -282,75 +452,7 @@ impl Transport for UnixTransport {
 
     #[cfg(feature = "tokio-runtime")]
     async fn recv(&self) -> Result<Option<Message>, Self::Error> {
-        if !self.connected.load(Ordering::Acquire) {
-            return Ok(None);
-        }
-
-        let mut state = self.state.lock().await;
-
-        // Take the reader temporarily to avoid borrowing issues
-        let reader = match state.reader.take() {
-            Some(r) => r,
-            None => return Ok(None),
-        };
-
-        // Clear the buffer and read a line
-        state.line_buffer.clear();
-
-        // We need to read into a separate buffer to avoid borrow issues.
-        // Bound the read to one byte past the limit so a peer that never sends a
-        // newline cannot grow `line_buffer` without bound; the size check below
-        // then rejects it. Without this, `read_line` would buffer unboundedly
-        // before the post-read check could fire.
-        let max = self.config.max_message_size;
-        let (result, reader) = {
-            let mut reader = reader;
-            let result = {
-                let mut limited = (&mut reader).take(max as u64 + 1);
-                limited.read_line(&mut state.line_buffer).await
-            };
-            (result, reader)
-        };
-
-        // Put the reader back
-        state.reader = Some(reader);
-
-        match result {
-            Ok(0) => {
-                // EOF - connection closed
-                self.connected.store(false, Ordering::Release);
-                Ok(None)
-            }
-            Ok(_) => {
-                // Parse the message (trim the newline)
-                let line = state.line_buffer.trim_end();
-                if line.is_empty() {
-                    return Ok(None);
-                }
-
-                // Check message size limit
-                if line.len() > self.config.max_message_size {
-                    return Err(TransportError::MessageTooLarge {
-                        size: line.len(),
-                        max: self.config.max_message_size,
-                    });
-                }
-
-                let msg: Message =
-                    serde_json::from_str(line).map_err(|e| TransportError::Deserialization {
-                        message: format!("Failed to deserialize message: {e}"),
-                    })?;
-
-                self.messages_received.fetch_add(1, Ordering::Relaxed);
-                Ok(Some(msg))
-            }
-            Err(e) => {
-                self.connected.store(false, Ordering::Release);
-                Err(TransportError::Io {
-                    message: format!("Failed to read from Unix socket: {e}"),
-                })
-            }
-        }
+        self.limits.recv(self.read_message()).await
     }
 
     #[cfg(not(feature = "tokio-runtime"))]
@@ -386,7 +488,12 @@ impl Transport for UnixTransport {
     }
 
     fn metadata(&self) -> TransportMetadata {
-        TransportMetadata::new("unix").remote_addr(self.config.path.display().to_string())
+        let metadata =
+            TransportMetadata::new("unix").remote_addr(self.config.path.display().to_string());
+        match self.peer {
+            Some(peer) => metadata.peer_credentials(peer),
+            None => metadata,
+        }
     }
 }
 
@@ -449,6 +556,14 @@ impl UnixListener {
         &self.config.path
     }
 
+    /// Accept connections only from peers on `allowlist`; others are closed
+    /// before any message is read.
+    #[must_use]
+    pub fn with_allowed_peers(mut self, allowlist: PeerAllowlist) -> Self {
+        self.config.allowed_peers = Some(allowlist);
+        self
+    }
+
     /// Check if the listener is running.
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::Acquire)
@@ -483,7 +598,12 @@ impl TransportListener for UnixListener {
         }
 
         let mut guard = self.listener.lock().await;
-        if let Some(listener) = guard.as_mut() {
+        let Some(listener) = guard.as_mut() else {
+            return Err(TransportError::Connection {
+                message: "Listener has been stopped".to_string(),
+            });
+        };
+        loop {
             let (stream, addr) =
                 listener
                     .accept()
@@ -492,17 +612,22 @@ impl TransportListener for UnixListener {
                         message: format!("Failed to accept connection: {e}"),
                     })?;
 
+            // Reject unlisted peers here, before anything they send is read.
+            if let Some(allowlist) = &self.config.allowed_peers {
+                let peer = peer_credentials(&stream);
+                if !peer.is_some_and(|peer| allowlist.allows(&peer)) {
+                    tracing::warn!(peer = ?peer, "Rejected Unix socket connection from unlisted peer");
+                    continue;
+                }
+            }
+
             tracing::debug!(addr = ?addr, "Accepted Unix socket connection");
 
-            Ok(UnixTransport::from_stream(
+            return Ok(UnixTransport::from_stream(
                 self.config.clone(),
                 stream,
                 true,
-            ))
-        } else {
-            Err(TransportError::Connection {
-                message: "Listener has been stopped".to_string(),
-            })
+            ));
         }
     }
 
@@ -595,6 +720,20 @@ impl UnixTransportBuilder {
         self
     }
 
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn limits(mut self, limits: TransportLimits) -> Self {
+        self.config.limits = limits;
+        self
+    }
+
+    /// Accept connections only from peers on `allowlist` (listeners only).
+    #[must_use]
+    pub fn allowed_peers(mut self, allowlist: PeerAllowlist) -> Self {
+        self.config.allowed_peers = Some(allowlist);
+        self
+    }
+
     /// Connect to the socket.
     pub async fn connect(self) -> Result<UnixTransport, TransportError> {
         UnixTransport::connect_with_config(self.config).await
@@ -668,6 +807,73 @@ mod tests {
         writer.abort();
     }
 
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn read_timeout_keeps_the_stream() {
+        use std::time::Duration;
+        let (server_stream, mut client_stream) = UnixStream::pair().expect("socketpair");
+        let config = UnixSocketConfig::new("/unused")
+            .with_limits(TransportLimits::new().with_read_timeout(Duration::from_millis(20)));
+        let transport = UnixTransport::from_stream(config, server_stream, true);
+
+        let result = transport.recv().await;
+        assert!(matches!(result, Err(TransportError::Timeout { .. })));
+
+        client_stream
+            .write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"ping\"}\n")
+            .await
+            .unwrap();
+        let msg = transport.recv().await.unwrap();
+        assert!(matches!(msg, Some(Message::Notification(_))), "{msg:?}");
+    }
+
+    #[test]
+    fn allowlist_matches_uid_or_gid() {
+        let peer = PeerCredentials {
+            uid: 1000,
+            gid: 100,
+            pid: Some(42),
+        };
+        assert!(!PeerAllowlist::new().allows(&peer));
+        assert!(PeerAllowlist::new().uid(1000).allows(&peer));
+        assert!(PeerAllowlist::new().uid(0).gid(100).allows(&peer));
+        assert!(!PeerAllowlist::new().uid(0).gid(0).allows(&peer));
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn listener_reports_and_filters_peer_credentials()
+    -> Result<(), Box<dyn std::error::Error>> {
+        use std::time::Duration;
+
+        // Learn our own credentials from a socket pair.
+        let (a, _b) = UnixStream::pair()?;
+        let me = peer_credentials(&a).expect("peer credentials");
+        assert_eq!(me.pid, Some(i32::try_from(std::process::id())?));
+
+        let path = format!("/tmp/mcp-peer-{}.sock", std::process::id());
+        let listener = UnixListener::bind(&path)
+            .await?
+            .with_allowed_peers(PeerAllowlist::new().uid(me.uid));
+        let _client = UnixStream::connect(&path).await?;
+        let transport = listener.accept().await?;
+        assert_eq!(transport.peer_credentials(), Some(me));
+        assert_eq!(transport.metadata().peer_credentials, Some(me));
+        drop(listener);
+
+        // Nobody else is listed, so our own connection is dropped unread.
+        let listener = UnixListener::bind(&path)
+            .await?
+            .with_allowed_peers(PeerAllowlist::new().uid(me.uid.wrapping_add(1)));
+        let mut client = UnixStream::connect(&path).await?;
+        let accepted = tokio::time::timeout(Duration::from_millis(100), listener.accept()).await;
+        assert!(accepted.is_err(), "an unlisted peer was accepted");
+        let mut buf = [0u8; 1];
+        let read = tokio::time::timeout(Duration::from_secs(2), client.read(&mut buf)).await??;
+        assert_eq!(read, 0, "the rejected connection was not closed");
+        Ok(())
+    }
+
     /// Integration test: Test Unix socket client-server communication.
     #[cfg(feature = "tokio-runtime")]
     #[tokio::test]
@@ -0,0 +1,548 @@
+//! Streamable HTTP transport backed by the browser `fetch` API.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use futures::channel::oneshot;
+use futures::future::{AbortHandle, Abortable};
+use mcpkit_core::protocol::Message;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    AbortController, Headers, ReadableStreamDefaultReader, Request, RequestInit, Response,
+};
+
+use super::js;
+use crate::codec;
+use crate::error::TransportError;
+use crate::http::sse::{HttpTransportState, process_sse_buffer};
+use crate::http::{
+    HttpTransportConfig, LAST_EVENT_ID_HEADER, MCP_PROTOCOL_VERSION_HEADER, MCP_SESSION_ID_HEADER,
+};
+use crate::limits::LimitEnforcer;
+use crate::runtime::{AsyncMutex, Notify};
+use crate::traits::{Transport, TransportMetadata};
+
+/// State shared between the transport and the tasks driving its requests.
+struct Shared {
+    config: HttpTransportConfig,
+    state: AsyncMutex<HttpTransportState>,
+    /// Signalled whenever a message is queued or the transport closes.
+    incoming: Notify,
+    connected: AtomicBool,
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    /// Response and standalone SSE streams, aborted on close.
+    streams: Mutex<Vec<AbortHandle>>,
+}
+
+impl Shared {
+    /// Wake every `recv` waiting for a message.
+    fn wake(&self) {
+        self.incoming.notify(usize::MAX);
+    }
+
+    /// Queue a message for `recv` and wake any waiter.
+    async fn enqueue(&self, msg: Message) {
+        self.state.lock().await.push_incoming(msg);
+        self.messages_received.fetch_add(1, Ordering::Relaxed);
+        self.wake();
+    }
+
+    /// Run a body-reading task on the event loop until it ends or the
+    /// transport closes.
+    fn spawn_stream(self: &Arc<Self>, task: impl std::future::Future<Output = ()> + 'static) {
+        let (handle, registration) = AbortHandle::new_pair();
+        {
+            let mut streams = self.streams.lock().unwrap_or_else(PoisonError::into_inner);
+            streams.retain(|h| !h.is_aborted());
+            streams.push(handle);
+        }
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = Abortable::new(task, registration).await;
+        });
+    }
+
+    /// Build the request headers: session, negotiated protocol version, and
+    /// the configured custom headers.
+    async fn headers(&self, accept: &str) -> Result<Headers, TransportError> {
+        let state = self.state.lock().await;
+        let headers = Headers::new().map_err(header_error)?;
+        headers.set("accept", accept).map_err(header_error)?;
+        let version = state
+            .protocol_version
+            .as_deref()
+            .unwrap_or(&self.config.protocol_version);
+        headers
+            .set(MCP_PROTOCOL_VERSION_HEADER, version)
+            .map_err(header_error)?;
+        if let Some(session_id) = &state.session_id {
+            headers
+                .set(MCP_SESSION_ID_HEADER, session_id)
+                .map_err(header_error)?;
+        }
+        for (name, value) in &self.config.headers {
+            headers.set(name, value).map_err(header_error)?;
+        }
+        Ok(headers)
+    }
+
+    /// Record the session the server assigned, if any.
+    async fn capture_session(&self, response: &Response) {
+        if let Ok(Some(session_id)) = response.headers().get(MCP_SESSION_ID_HEADER) {
+            self.state.lock().await.session_id = Some(session_id);
+        }
+    }
+
+    /// POST a message, returning the response once its headers arrive.
+    async fn post(
+        &self,
+        body: String,
+        controller: &AbortController,
+    ) -> Result<Response, TransportError> {
+        let headers = self.headers("application/json, text/event-stream").await?;
+        headers
+            .set("content-type", "application/json")
+            .map_err(header_error)?;
+        let init = RequestInit::new();
+        init.set_method("POST");
+        init.set_headers(&headers);
+        init.set_body(&JsValue::from_str(&body));
+        init.set_signal(Some(&controller.signal()));
+        let request =
+            Request::new_with_str_and_init(&self.config.base_url, &init).map_err(request_error)?;
+
+        let timeout = self.config.request_timeout;
+        let response = crate::runtime::timeout(timeout, js::fetch(&request))
+            .await
+            .map_err(|_| {
+                controller.abort();
+                TransportError::Timeout {
+                    operation: "HTTP POST".to_string(),
+                    duration: timeout,
+                }
+            })??;
+        self.capture_session(&response).await;
+        Ok(response)
+    }
+
+    /// Deliver a POST response: queue a JSON body, or read an SSE body in
+    /// the background so the caller can answer requests sent on it.
+    async fn handle_response(
+        self: &Arc<Self>,
+        response: Response,
+        controller: AbortController,
+    ) -> Result<(), TransportError> {
+        match response.status() {
+            200 => {
+                if is_event_stream(&response) {
+                    let shared = Arc::clone(self);
+                    self.spawn_stream(async move {
+                        let _abort = AbortOnDrop(controller);
+                        if let Err(e) = shared.consume_sse(&response).await {
+                            tracing::warn!(error = %e, "SSE response stream failed");
+                        }
+                    });
+                    Ok(())
+                } else {
+                    let body = js::text(&response).await?;
+                    self.enqueue_body(&body).await
+                }
+            }
+            202 => Ok(()),
+            401 => {
+                self.state.lock().await.session_id = None;
+                let body = js::text(&response).await.unwrap_or_default();
+                Err(TransportError::Connection {
+                    message: format!("Unauthorized (401): {body}"),
+                })
+            }
+            404 => {
+                self.state.lock().await.session_id = None;
+                Err(TransportError::Connection {
+                    message: "Session expired or not found".to_string(),
+                })
+            }
+            status => {
+                // A JSON-RPC error body is delivered to the awaiting caller;
+                // anything else becomes a transport error.
+                let body = js::text(&response).await.unwrap_or_default();
+                if let Ok(msg) = codec::decode_slice(body.as_bytes()) {
+                    self.enqueue(msg).await;
+                    Ok(())
+                } else {
+                    Err(TransportError::Protocol {
+                        message: format!("HTTP {status}: {body}"),
+                    })
+                }
+            }
+        }
+    }
+
+    /// Queue the message in a direct JSON response body.
+    async fn enqueue_body(&self, body: &str) -> Result<(), TransportError> {
+        if body.is_empty() {
+            return Ok(());
+        }
+        let max = self.config.limits.max_message_size;
+        if body.len() > max {
+            return Err(TransportError::MessageTooLarge {
+                size: body.len(),
+                max,
+            });
+        }
+        let msg = codec::decode_slice(body.as_bytes())?;
+        self.enqueue(msg).await;
+        Ok(())
+    }
+
+    /// Consume an SSE body, queueing each complete event's message.
+    async fn consume_sse(&self, response: &Response) -> Result<(), TransportError> {
+        let Some(body) = response.body() else {
+            return Ok(());
+        };
+        let reader: ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+        let mut decoder = Utf8Decoder::default();
+
+        while let Some(chunk) = js::read_chunk(&reader).await? {
+            let text = decoder.decode(&chunk)?;
+            // Lock per chunk only, so `recv` can drain messages while the
+            // stream stays open.
+            let result = {
+                let mut state = self.state.lock().await;
+                state.sse_buffer.push_str(&text);
+                process_sse_buffer(
+                    &mut state,
+                    &self.messages_received,
+                    self.config.limits.max_message_size,
+                )
+            };
+            self.wake();
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Issue the GET that opens the standalone SSE stream, resuming from the
+    /// last seen event ID. Returns `None` when the server answers `405`.
+    async fn get_sse_stream(
+        &self,
+        controller: &AbortController,
+    ) -> Result<Option<Response>, TransportError> {
+        let headers = self.headers("text/event-stream").await?;
+        if let Some(id) = self.state.lock().await.last_event_id.clone() {
+            headers
+                .set(LAST_EVENT_ID_HEADER, &id)
+                .map_err(header_error)?;
+        }
+        let init = RequestInit::new();
+        init.set_method("GET");
+        init.set_headers(&headers);
+        init.set_signal(Some(&controller.signal()));
+        let request =
+            Request::new_with_str_and_init(&self.config.base_url, &init).map_err(request_error)?;
+        let response = js::fetch(&request).await?;
+
+        match response.status() {
+            405 => Ok(None),
+            200 if is_event_stream(&response) => Ok(Some(response)),
+            200 => Err(TransportError::Protocol {
+                message: "SSE stream GET did not return text/event-stream".to_string(),
+            }),
+            404 => {
+                self.state.lock().await.session_id = None;
+                Err(TransportError::Connection {
+                    message: "Session expired or not found".to_string(),
+                })
+            }
+            status => Err(TransportError::Connection {
+                message: format!("SSE stream GET failed with HTTP {status}"),
+            }),
+        }
+    }
+
+    /// Read the standalone SSE stream, reconnecting with `Last-Event-ID`
+    /// while the transport is connected and auto-reconnect allows.
+    async fn run_sse_stream(self: Arc<Self>) {
+        let mut attempts = 0;
+        loop {
+            let controller = new_controller();
+            let _abort = AbortOnDrop(controller.clone());
+            match self.get_sse_stream(&controller).await {
+                Ok(Some(response)) => {
+                    attempts = 0;
+                    match self.consume_sse(&response).await {
+                        Ok(()) => tracing::debug!("SSE stream ended"),
+                        Err(e) => tracing::warn!(error = %e, "SSE stream failed"),
+                    }
+                }
+                Ok(None) => {
+                    tracing::debug!(url = %self.config.base_url, "Server does not offer an SSE stream");
+                    return;
+                }
+                Err(e) => tracing::warn!(error = %e, attempt = attempts, "SSE stream GET failed"),
+            }
+
+            if !self.config.auto_reconnect
+                || attempts >= self.config.max_reconnect_attempts
+                || !self.connected.load(Ordering::Acquire)
+            {
+                return;
+            }
+            attempts += 1;
+            let backoff = std::time::Duration::from_millis(100 * 2u64.pow(attempts.min(6)));
+            crate::runtime::sleep(backoff).await;
+        }
+    }
+
+    /// Send `DELETE` to terminate the session. Best effort.
+    async fn terminate_session(&self) {
+        let Ok(headers) = self.headers("application/json, text/event-stream").await else {
+            return;
+        };
+        let init = RequestInit::new();
+        init.set_method("DELETE");
+        init.set_headers(&headers);
+        let Ok(request) = Request::new_with_str_and_init(&self.config.base_url, &init) else {
+            return;
+        };
+        match js::fetch(&request).await {
+            Ok(response) => tracing::debug!(status = response.status(), "Session terminated"),
+            Err(e) => tracing::debug!(error = %e, "Session DELETE failed"),
+        }
+    }
+}
+
+/// MCP Streamable HTTP transport over the browser's `fetch` API.
+///
+/// Behaves like [`HttpTransport`](crate::http::HttpTransport) and takes the
+/// same [`HttpTransportConfig`]: messages are POSTed to the endpoint, JSON
+/// and SSE responses are both understood, the session ID and negotiated
+/// protocol version are carried on later requests, and once the client sends
+/// `notifications/initialized` the standalone SSE stream (HTTP GET) is
+/// opened for server-initiated messages. `close` aborts open streams and
+/// terminates the session with `DELETE`.
+///
+/// Requests run on the JavaScript event loop; the transport itself holds no
+/// JavaScript values, so it is `Send + Sync` like every other [`Transport`].
+/// `connect_timeout` is not applied, since `fetch` does not separate
+/// connecting from waiting for the response.
+///
+/// Cross-origin servers must allow the `mcp-session-id` and
+/// `mcp-protocol-version` headers through CORS, and expose
+/// `mcp-session-id` on responses.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use mcpkit_transport::http::HttpTransportConfig;
+/// use mcpkit_transport::wasm::FetchTransport;
+///
+/// let transport = FetchTransport::new(HttpTransportConfig::new("https://example.com/mcp"));
+/// ```
+pub struct FetchTransport {
+    limits: LimitEnforcer,
+    shared: Arc<Shared>,
+}
+
+impl FetchTransport {
+    /// Create a transport. No request is made until the first send.
+    #[must_use]
+    pub fn new(config: HttpTransportConfig) -> Self {
+        let session_id = config.session_id.clone();
+        Self {
+            limits: LimitEnforcer::new(config.limits.clone()),
+            shared: Arc::new(Shared {
+                config,
+                state: AsyncMutex::new(HttpTransportState::new(session_id)),
+                incoming: Notify::new(),
+                connected: AtomicBool::new(true),
+                messages_sent: AtomicU64::new(0),
+                messages_received: AtomicU64::new(0),
+                streams: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Get the current session ID, if any.
+    pub async fn session_id(&self) -> Option<String> {
+        self.shared.state.lock().await.session_id.clone()
+    }
+
+    /// The protocol version negotiated by `initialize`, if it has completed.
+    pub async fn protocol_version(&self) -> Option<String> {
+        self.shared.state.lock().await.protocol_version.clone()
+    }
+
+    /// Number of messages sent.
+    #[must_use]
+    pub fn messages_sent(&self) -> u64 {
+        self.shared.messages_sent.load(Ordering::Relaxed)
+    }
+
+    /// Number of messages received.
+    #[must_use]
+    pub fn messages_received(&self) -> u64 {
+        self.shared.messages_received.load(Ordering::Relaxed)
+    }
+
+    /// Wait for the next queued message.
+    async fn next_message(&self) -> Result<Option<Message>, TransportError> {
+        loop {
+            if let Some(msg) = self.shared.state.lock().await.message_queue.pop_front() {
+                return Ok(Some(msg));
+            }
+            if !self.shared.connected.load(Ordering::Acquire) {
+                return Ok(None);
+            }
+
+            // Register before re-checking so a message queued in between is
+            // not missed.
+            let listener = self.shared.incoming.listen();
+            if let Some(msg) = self.shared.state.lock().await.message_queue.pop_front() {
+                return Ok(Some(msg));
+            }
+            if !self.shared.connected.load(Ordering::Acquire) {
+                return Ok(None);
+            }
+            listener.await;
+        }
+    }
+}
+
+impl Transport for FetchTransport {
+    type Error = TransportError;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        if !self.is_connected() {
+            return Err(TransportError::NotConnected);
+        }
+        let body = serde_json::to_string(&msg).map_err(|e| TransportError::Serialization {
+            message: format!("Failed to serialize message: {e}"),
+        })?;
+        self.limits.send(&msg, body.len())?;
+        self.shared.state.lock().await.record_outgoing(&msg);
+
+        // The request runs on the event loop; only its outcome crosses back.
+        let (tx, rx) = oneshot::channel();
+        let shared = Arc::clone(&self.shared);
+        wasm_bindgen_futures::spawn_local(async move {
+            let controller = new_controller();
+            let result = match shared.post(body, &controller).await {
+                Ok(response) => shared.handle_response(response, controller).await,
+                Err(e) => Err(e),
+            };
+            let _ = tx.send(result);
+        });
+        rx.await.map_err(|_| TransportError::ConnectionClosed)??;
+        self.shared.messages_sent.fetch_add(1, Ordering::Relaxed);
+
+        // The session is live once the client confirms initialization; open
+        // the standalone stream so server-initiated messages can arrive.
+        if self.shared.config.open_sse_stream
+            && matches!(&msg, Message::Notification(n) if n.method == "notifications/initialized")
+        {
+            let shared = Arc::clone(&self.shared);
+            self.shared.spawn_stream(shared.run_sse_stream());
+        }
+        Ok(())
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        self.limits.recv(self.next_message()).await
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.shared.connected.store(false, Ordering::Release);
+        self.shared.wake();
+        for handle in self
+            .shared
+            .streams
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain(..)
+        {
+            handle.abort();
+        }
+
+        if self.shared.state.lock().await.session_id.is_some() {
+            let (tx, rx) = oneshot::channel();
+            let shared = Arc::clone(&self.shared);
+            wasm_bindgen_futures::spawn_local(async move {
+                shared.terminate_session().await;
+                shared.state.lock().await.session_id = None;
+                let _ = tx.send(());
+            });
+            let _ = rx.await;
+        }
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.shared.connected.load(Ordering::Acquire)
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        TransportMetadata::new("http").remote_addr(&self.shared.config.base_url)
+    }
+}
+
+/// Aborts an in-flight `fetch` when the task reading it is dropped.
+struct AbortOnDrop(AbortController);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn new_controller() -> AbortController {
+    AbortController::new().expect("AbortController is available in browsers and workers")
+}
+
+fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get("content-type")
+        .ok()
+        .flatten()
+        .is_some_and(|ct| ct.starts_with("text/event-stream"))
+}
+
+fn header_error(e: JsValue) -> TransportError {
+    TransportError::Connection {
+        message: format!("Invalid request header: {}", js::describe(&e)),
+    }
+}
+
+fn request_error(e: JsValue) -> TransportError {
+    TransportError::Connection {
+        message: format!("Invalid request: {}", js::describe(&e)),
+    }
+}
+
+/// Decodes a byte stream as UTF-8, carrying a character split across chunks
+/// over to the next one.
+#[derive(Default)]
+struct Utf8Decoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    fn decode(&mut self, chunk: &[u8]) -> Result<String, TransportError> {
+        self.pending.extend_from_slice(chunk);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // An incomplete sequence at the end waits for the next chunk.
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(TransportError::Protocol {
+                    message: format!("Invalid UTF-8 in SSE stream: {e}"),
+                });
+            }
+        };
+        let rest = self.pending.split_off(valid);
+        let text = String::from_utf8(std::mem::replace(&mut self.pending, rest))
+            .expect("prefix was validated as UTF-8");
+        Ok(text)
+    }
+}
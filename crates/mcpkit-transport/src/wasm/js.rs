@@ -0,0 +1,79 @@
+//! Bindings to the JavaScript globals used by the browser transports.
+//!
+//! `setTimeout` and `fetch` are imported from the global scope rather than
+//! through `window`, so they also work in Web Workers and service workers.
+
+use std::time::Duration;
+
+use js_sys::{Promise, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{ReadableStreamDefaultReader, Request, Response};
+
+use crate::error::TransportError;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout_js(handler: &JsValue, timeout: i32) -> JsValue;
+
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_js(request: &Request) -> Promise;
+}
+
+/// Run `callback` once after `delay`, rounded up to whole milliseconds.
+pub fn set_timeout(delay: Duration, callback: impl FnOnce() + 'static) {
+    let millis = i32::try_from(delay.as_micros().div_ceil(1000)).unwrap_or(i32::MAX);
+    set_timeout_js(&Closure::once_into_js(callback), millis);
+}
+
+/// Describe a thrown JavaScript value for an error message.
+pub fn describe(value: &JsValue) -> String {
+    if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+        return String::from(error.message());
+    }
+    value.as_string().unwrap_or_else(|| format!("{value:?}"))
+}
+
+/// Issue a `fetch`, resolving once the response headers arrive.
+pub async fn fetch(request: &Request) -> Result<Response, TransportError> {
+    let response =
+        JsFuture::from(fetch_js(request))
+            .await
+            .map_err(|e| TransportError::Connection {
+                message: format!("fetch failed: {}", describe(&e)),
+            })?;
+    Ok(response.unchecked_into())
+}
+
+/// Read a response body as text.
+pub async fn text(response: &Response) -> Result<String, TransportError> {
+    let read_error = |e: JsValue| TransportError::Connection {
+        message: format!("Failed to read response body: {}", describe(&e)),
+    };
+    let text = JsFuture::from(response.text().map_err(read_error)?)
+        .await
+        .map_err(read_error)?;
+    Ok(text.as_string().unwrap_or_default())
+}
+
+/// Read the next chunk of a body stream, or `None` once it is done.
+pub async fn read_chunk(
+    reader: &ReadableStreamDefaultReader,
+) -> Result<Option<Vec<u8>>, TransportError> {
+    let result = JsFuture::from(reader.read())
+        .await
+        .map_err(|e| TransportError::Connection {
+            message: format!("Stream read failed: {}", describe(&e)),
+        })?;
+    let done = Reflect::get(&result, &JsValue::from_str("done"))
+        .ok()
+        .and_then(|done| done.as_bool())
+        .unwrap_or(true);
+    if done {
+        return Ok(None);
+    }
+    let value = Reflect::get(&result, &JsValue::from_str("value")).unwrap_or(JsValue::UNDEFINED);
+    Ok(Some(Uint8Array::new(&value).to_vec()))
+}
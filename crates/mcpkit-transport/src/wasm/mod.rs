@@ -0,0 +1,26 @@
+//! Browser transports for `wasm32-unknown-unknown`.
+//!
+//! Available with the `wasm` feature when compiling to WebAssembly. Disable
+//! default features, since the Tokio runtime does not build for the browser:
+//!
+//! ```toml
+//! [dependencies]
+//! mcpkit-transport = { version = "0.7", default-features = false, features = ["wasm"] }
+//! ```
+//!
+//! - [`BrowserWebSocketTransport`] connects over the browser `WebSocket` API,
+//!   configured with a [`WebSocketConfig`](crate::websocket::WebSocketConfig).
+//! - [`FetchTransport`] speaks Streamable HTTP over `fetch`, configured with
+//!   an [`HttpTransportConfig`](crate::http::HttpTransportConfig).
+//!
+//! Both hand their JavaScript objects to a task on the event loop and are
+//! `Send + Sync`, so they work with the generic client and middleware. The
+//! feature also provides the browser backend for [`runtime`](crate::runtime)
+//! timers, so `TimeoutLayer` and `RetryLayer` work in the browser.
+
+mod fetch;
+pub(crate) mod js;
+mod websocket;
+
+pub use fetch::FetchTransport;
+pub use websocket::BrowserWebSocketTransport;
@@ -0,0 +1,348 @@
+//! WebSocket transport backed by the browser `WebSocket` API.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use futures::StreamExt;
+use futures::channel::{mpsc, oneshot};
+use mcpkit_core::protocol::Message;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{BinaryType, CloseEvent, Event, MessageEvent, WebSocket};
+
+use super::js::describe;
+use crate::codec;
+use crate::error::TransportError;
+use crate::limits::LimitEnforcer;
+use crate::runtime::AsyncMutex;
+use crate::traits::{Transport, TransportMetadata};
+use crate::websocket::WebSocketConfig;
+
+type Incoming = Result<Message, TransportError>;
+
+/// A request from the transport to the task that owns the socket.
+enum Command {
+    Send(String, oneshot::Sender<Result<(), TransportError>>),
+    Close(u16, oneshot::Sender<()>),
+}
+
+/// State shared between the transport and the socket's event handlers.
+struct Shared {
+    connected: AtomicBool,
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+}
+
+/// MCP transport over the browser's `WebSocket` API.
+///
+/// The socket and its event handlers live on a task spawned on the
+/// JavaScript event loop; the transport talks to it over channels, which
+/// keeps it `Send + Sync` like every other [`Transport`].
+///
+/// Browsers do not let scripts set handshake headers or send pings, so
+/// [`WebSocketConfig::headers`] and the ping settings are ignored, and the
+/// connection is not re-established automatically. Text and binary frames
+/// are both accepted.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use mcpkit_transport::wasm::BrowserWebSocketTransport;
+/// use mcpkit_transport::websocket::WebSocketConfig;
+///
+/// let transport =
+///     BrowserWebSocketTransport::connect(WebSocketConfig::new("wss://example.com/mcp")).await?;
+/// ```
+pub struct BrowserWebSocketTransport {
+    config: WebSocketConfig,
+    limits: LimitEnforcer,
+    shared: Arc<Shared>,
+    commands: mpsc::UnboundedSender<Command>,
+    incoming: AsyncMutex<mpsc::UnboundedReceiver<Incoming>>,
+}
+
+impl BrowserWebSocketTransport {
+    /// Open a WebSocket connection and wait until it is established.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is rejected, the connection fails, or it
+    /// is not open within [`WebSocketConfig::connect_timeout`].
+    pub async fn connect(config: WebSocketConfig) -> Result<Self, TransportError> {
+        let timeout = config.connect_timeout;
+        let (transport, opened) = Self::open(config)?;
+        match crate::runtime::timeout(timeout, opened).await {
+            Ok(Ok(Ok(()))) => Ok(transport),
+            Ok(Ok(Err(e))) => Err(e),
+            Ok(Err(_)) => Err(TransportError::ConnectionClosed),
+            Err(_) => Err(TransportError::Timeout {
+                operation: "WebSocket connect".to_string(),
+                duration: timeout,
+            }),
+        }
+    }
+
+    /// Create the socket and spawn the task that owns it. Synchronous so no
+    /// JavaScript value is held across an await in [`connect`](Self::connect).
+    #[allow(clippy::type_complexity)]
+    fn open(
+        config: WebSocketConfig,
+    ) -> Result<(Self, oneshot::Receiver<Result<(), TransportError>>), TransportError> {
+        let protocols = js_sys::Array::new();
+        for protocol in &config.subprotocols {
+            protocols.push(&JsValue::from_str(protocol));
+        }
+        let ws = WebSocket::new_with_str_sequence(&config.url, &protocols).map_err(|e| {
+            TransportError::Connection {
+                message: format!("Invalid WebSocket URL '{}': {}", config.url, describe(&e)),
+            }
+        })?;
+        ws.set_binary_type(BinaryType::Arraybuffer);
+
+        let shared = Arc::new(Shared {
+            connected: AtomicBool::new(false),
+            messages_sent: AtomicU64::new(0),
+            messages_received: AtomicU64::new(0),
+        });
+        let (incoming_tx, incoming_rx) = mpsc::unbounded();
+        let (commands_tx, commands_rx) = mpsc::unbounded();
+        let (opened_tx, opened_rx) = oneshot::channel();
+        let socket = Socket::new(
+            ws,
+            Arc::clone(&shared),
+            incoming_tx,
+            opened_tx,
+            config.limits.max_message_size,
+        );
+        wasm_bindgen_futures::spawn_local(socket.run(commands_rx));
+
+        Ok((
+            Self {
+                limits: LimitEnforcer::new(config.limits.clone()),
+                config,
+                shared,
+                commands: commands_tx,
+                incoming: AsyncMutex::new(incoming_rx),
+            },
+            opened_rx,
+        ))
+    }
+
+    /// Number of messages sent.
+    #[must_use]
+    pub fn messages_sent(&self) -> u64 {
+        self.shared.messages_sent.load(Ordering::Relaxed)
+    }
+
+    /// Number of messages received.
+    #[must_use]
+    pub fn messages_received(&self) -> u64 {
+        self.shared.messages_received.load(Ordering::Relaxed)
+    }
+}
+
+impl Transport for BrowserWebSocketTransport {
+    type Error = TransportError;
+
+    async fn send(&self, msg: Message) -> Result<(), Self::Error> {
+        if !self.is_connected() {
+            return Err(TransportError::NotConnected);
+        }
+        let text = serde_json::to_string(&msg).map_err(|e| TransportError::Serialization {
+            message: format!("Failed to serialize message: {e}"),
+        })?;
+        self.limits.send(&msg, text.len())?;
+
+        let (tx, rx) = oneshot::channel();
+        self.commands
+            .unbounded_send(Command::Send(text, tx))
+            .map_err(|_| TransportError::ConnectionClosed)?;
+        rx.await.map_err(|_| TransportError::ConnectionClosed)??;
+        self.shared.messages_sent.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn recv(&self) -> Result<Option<Message>, Self::Error> {
+        self.limits
+            .recv(async {
+                match self.incoming.lock().await.next().await {
+                    Some(Ok(msg)) => Ok(Some(msg)),
+                    Some(Err(e)) => Err(e),
+                    None => Ok(None),
+                }
+            })
+            .await
+    }
+
+    async fn close(&self) -> Result<(), Self::Error> {
+        self.shared.connected.store(false, Ordering::Release);
+        let (tx, rx) = oneshot::channel();
+        if self
+            .commands
+            .unbounded_send(Command::Close(1000, tx))
+            .is_ok()
+        {
+            let _ = rx.await;
+        }
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.shared.connected.load(Ordering::Acquire)
+    }
+
+    fn metadata(&self) -> TransportMetadata {
+        TransportMetadata::new("websocket").remote_addr(&self.config.url)
+    }
+}
+
+/// The browser socket and the handlers that must stay alive with it.
+struct Socket {
+    ws: WebSocket,
+    shared: Arc<Shared>,
+    incoming: mpsc::UnboundedSender<Incoming>,
+    _on_open: Closure<dyn FnMut(Event)>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(Event)>,
+    _on_close: Closure<dyn FnMut(CloseEvent)>,
+}
+
+impl Socket {
+    fn new(
+        ws: WebSocket,
+        shared: Arc<Shared>,
+        incoming: mpsc::UnboundedSender<Incoming>,
+        opened: oneshot::Sender<Result<(), TransportError>>,
+        max_message_size: usize,
+    ) -> Self {
+        // Resolved by whichever of open, error, or close fires first.
+        let opened = Rc::new(RefCell::new(Some(opened)));
+
+        let on_open = {
+            let opened = Rc::clone(&opened);
+            let shared = Arc::clone(&shared);
+            Closure::<dyn FnMut(Event)>::new(move |_| {
+                shared.connected.store(true, Ordering::Release);
+                if let Some(opened) = opened.borrow_mut().take() {
+                    let _ = opened.send(Ok(()));
+                }
+            })
+        };
+
+        let on_message = {
+            let incoming = incoming.clone();
+            let shared = Arc::clone(&shared);
+            Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                let data = event.data();
+                let text = if let Some(text) = data.as_string() {
+                    text
+                } else if data.is_instance_of::<js_sys::ArrayBuffer>() {
+                    let bytes = js_sys::Uint8Array::new(&data).to_vec();
+                    String::from_utf8_lossy(&bytes).into_owned()
+                } else {
+                    tracing::warn!("Ignoring WebSocket frame of unsupported type");
+                    return;
+                };
+                let item = parse_frame(&text, max_message_size);
+                if item.is_ok() {
+                    shared.messages_received.fetch_add(1, Ordering::Relaxed);
+                }
+                let _ = incoming.unbounded_send(item);
+            })
+        };
+
+        let on_error = {
+            let opened = Rc::clone(&opened);
+            Closure::<dyn FnMut(Event)>::new(move |_| {
+                // The event carries no detail; a close event follows.
+                tracing::debug!("WebSocket error event");
+                if let Some(opened) = opened.borrow_mut().take() {
+                    let _ = opened.send(Err(TransportError::Connection {
+                        message: "WebSocket connection failed".to_string(),
+                    }));
+                }
+            })
+        };
+
+        let on_close = {
+            let incoming = incoming.clone();
+            let shared = Arc::clone(&shared);
+            Closure::<dyn FnMut(CloseEvent)>::new(move |event: CloseEvent| {
+                tracing::debug!(code = event.code(), reason = %event.reason(), "WebSocket closed");
+                shared.connected.store(false, Ordering::Release);
+                if let Some(opened) = opened.borrow_mut().take() {
+                    let _ = opened.send(Err(TransportError::Connection {
+                        message: format!("WebSocket closed during handshake ({})", event.code()),
+                    }));
+                }
+                incoming.close_channel();
+            })
+        };
+
+        ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        ws.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        ws.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        ws.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        Self {
+            ws,
+            shared,
+            incoming,
+            _on_open: on_open,
+            _on_message: on_message,
+            _on_error: on_error,
+            _on_close: on_close,
+        }
+    }
+
+    /// Serve commands until the transport closes or is dropped.
+    async fn run(self, mut commands: mpsc::UnboundedReceiver<Command>) {
+        while let Some(command) = commands.next().await {
+            match command {
+                Command::Send(text, reply) => {
+                    let result = if self.ws.ready_state() == WebSocket::OPEN {
+                        self.ws
+                            .send_with_str(&text)
+                            .map_err(|e| TransportError::Connection {
+                                message: format!("WebSocket send failed: {}", describe(&e)),
+                            })
+                    } else {
+                        Err(TransportError::ConnectionClosed)
+                    };
+                    let _ = reply.send(result);
+                }
+                Command::Close(code, reply) => {
+                    let _ = self.ws.close_with_code(code);
+                    let _ = reply.send(());
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        // Detach the handlers before they are freed, then end the stream.
+        self.ws.set_onopen(None);
+        self.ws.set_onmessage(None);
+        self.ws.set_onerror(None);
+        self.ws.set_onclose(None);
+        let _ = self.ws.close();
+        self.shared.connected.store(false, Ordering::Release);
+        self.incoming.close_channel();
+    }
+}
+
+/// Parse one frame into a message, enforcing the size limit.
+fn parse_frame(text: &str, max_message_size: usize) -> Incoming {
+    if text.len() > max_message_size {
+        return Err(TransportError::MessageTooLarge {
+            size: text.len(),
+            max: max_message_size,
+        });
+    }
+    codec::decode_slice(text.as_bytes())
+}
@@ -6,7 +6,9 @@ use std::time::Duration;
 
 use mcpkit_core::protocol::Message;
 
+use crate::compression::{CompressionConfig, CompressionSnapshot, CompressionStats};
 use crate::error::TransportError;
+use crate::limits::{LimitEnforcer, TransportLimits};
 use crate::runtime::AsyncMutex;
 use crate::traits::{Transport, TransportMetadata};
 
@@ -14,11 +16,19 @@ use super::config::{ConnectionState, WebSocketConfig};
 
 #[cfg(feature = "websocket")]
 use {
+    super::WebSocketCodec,
+    crate::compression::{self, Encoding, MCP_COMPRESSION_HEADER},
     futures::{SinkExt, StreamExt},
+    std::sync::Arc,
     tokio::net::TcpStream,
     tokio_tungstenite::{
         MaybeTlsStream, WebSocketStream, connect_async_with_config,
-        tungstenite::protocol::{Message as WsMessage, WebSocketConfig as TungsteniteConfig},
+        tungstenite::{
+            client::IntoClientRequest,
+            handshake::client::{Request, Response},
+            http::HeaderValue,
+            protocol::{Message as WsMessage, WebSocketConfig as TungsteniteConfig},
+        },
     },
 };
 
@@ -27,6 +37,8 @@ use {
 struct WebSocketState {
     /// The WebSocket stream (split for concurrent read/write).
     stream: Option<WebSocketStream<MaybeTlsStream<TcpStream>>>,
+    /// Compression negotiated for the current connection.
+    codec: WebSocketCodec,
     /// Queue of received messages.
     message_queue: VecDeque<Message>,
     /// Reconnection attempt counter.
@@ -50,23 +62,31 @@ struct WebSocketState {
 pub struct WebSocketTransport {
     #[allow(dead_code)] // Used when websocket feature is enabled
     config: WebSocketConfig,
+    limits: LimitEnforcer,
     #[allow(dead_code)] // Used when websocket feature is enabled
     state: AsyncMutex<WebSocketState>,
     connected: AtomicBool,
     connection_state: AtomicU32, // ConnectionState as u32
     messages_sent: AtomicU64,
     messages_received: AtomicU64,
+    /// Successful reconnections, reported as the connection epoch.
+    reconnections: AtomicU64,
+    /// Bytes before and after compression, across reconnections.
+    #[allow(dead_code)] // Shared with the codec when websocket feature is enabled
+    compression: std::sync::Arc<CompressionStats>,
 }
 
 impl WebSocketTransport {
     /// Create a new WebSocket transport (not yet connected).
     #[must_use]
-    pub const fn new(config: WebSocketConfig) -> Self {
+    pub fn new(config: WebSocketConfig) -> Self {
         Self {
-            config,
+            limits: LimitEnforcer::new(config.limits.clone()),
             state: AsyncMutex::new(WebSocketState {
                 #[cfg(feature = "websocket")]
                 stream: None,
+                #[cfg(feature = "websocket")]
+                codec: WebSocketCodec::plain(config.limits.max_message_size),
                 message_queue: VecDeque::new(),
                 reconnect_attempt: 0,
             }),
@@ -74,6 +94,9 @@ impl WebSocketTransport {
             connection_state: AtomicU32::new(ConnectionState::Disconnected as u32),
             messages_sent: AtomicU64::new(0),
             messages_received: AtomicU64::new(0),
+            reconnections: AtomicU64::new(0),
+            compression: std::sync::Arc::new(CompressionStats::new()),
+            config,
         }
     }
 
@@ -106,70 +129,134 @@ impl WebSocketTransport {
         // Apply the configured message-size limit to tungstenite (it is
         // otherwise ignored, leaving tungstenite's own default in effect).
         let ws_config = TungsteniteConfig {
-            max_message_size: Some(self.config.max_message_size),
-            max_frame_size: Some(self.config.max_message_size),
+            max_message_size: Some(self.config.limits.max_message_size),
+            max_frame_size: Some(self.config.limits.max_message_size),
             ..Default::default()
         };
 
         // Connect with timeout
-        let connect_future = connect_async_with_config(url.as_str(), Some(ws_config), false);
-        let result = tokio::time::timeout(self.config.connect_timeout, connect_future)
-            .await
-            .map_err(|_| TransportError::Timeout {
-                operation: "WebSocket connect".to_string(),
-                duration: self.config.connect_timeout,
-            })?;
-
-        let (ws_stream, _response) = result.map_err(|e| TransportError::Connection {
-            message: format!("WebSocket connection failed: {e}"),
-        })?;
+        let connect_future = self.open_stream(&url, ws_config);
+        let (ws_stream, response) =
+            tokio::time::timeout(self.config.connect_timeout, connect_future)
+                .await
+                .map_err(|_| TransportError::Timeout {
+                    operation: "WebSocket connect".to_string(),
+                    duration: self.config.connect_timeout,
+                })??;
+        let encoding = accepted_encoding(&response);
 
         // Store the stream
         {
             let mut state = self.state.lock().await;
             state.stream = Some(ws_stream);
+            state.codec = WebSocketCodec::negotiated(
+                self.config.compression,
+                encoding,
+                self.config.limits.max_message_size,
+                Arc::clone(&self.compression),
+            );
             state.reconnect_attempt = 0;
         }
 
         self.connected.store(true, Ordering::Release);
         self.set_connection_state(ConnectionState::Connected);
 
-        tracing::info!(url = %self.config.url, "WebSocket connected");
+        tracing::info!(
+            url = %self.config.url,
+            compression = encoding.map(Encoding::as_str),
+            "WebSocket connected"
+        );
 
         Ok(())
     }
 
-    /// Attempt to reconnect with exponential backoff.
+    /// Open the TCP (and, for `wss://`, TLS) connection and run the handshake.
     #[cfg(feature = "websocket")]
-    async fn reconnect(&self) -> Result<(), TransportError> {
-        let attempt = {
-            let mut state = self.state.lock().await;
-            state.reconnect_attempt += 1;
-            state.reconnect_attempt
-        };
+    async fn open_stream(
+        &self,
+        url: &url::Url,
+        ws_config: TungsteniteConfig,
+    ) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response), TransportError> {
+        let request = self.handshake_request(url)?;
+
+        #[cfg(feature = "tls")]
+        if let (Some(tls), "wss") = (&self.config.tls, url.scheme()) {
+            return connect_tls(url, request, tls, ws_config).await;
+        }
+
+        connect_async_with_config(request, Some(ws_config), false)
+            .await
+            .map_err(|e| TransportError::Connection {
+                message: format!("WebSocket connection failed: {e}"),
+            })
+    }
 
-        if attempt > self.config.max_reconnect_attempts {
-            return Err(TransportError::Connection {
-                message: format!(
-                    "Maximum reconnection attempts ({}) exceeded",
-                    self.config.max_reconnect_attempts
-                ),
-            });
+    /// Build the handshake request, offering compression if configured.
+    #[cfg(feature = "websocket")]
+    fn handshake_request(&self, url: &url::Url) -> Result<Request, TransportError> {
+        let mut request =
+            url.as_str()
+                .into_client_request()
+                .map_err(|e| TransportError::Connection {
+                    message: format!("Invalid WebSocket URL: {e}"),
+                })?;
+        if self.config.compression.is_some() {
+            request.headers_mut().insert(
+                MCP_COMPRESSION_HEADER,
+                HeaderValue::from_static(compression::ACCEPT_ENCODING),
+            );
         }
+        Ok(request)
+    }
 
-        self.set_connection_state(ConnectionState::Reconnecting);
+    /// Reconnect with exponential backoff until it succeeds or the attempts
+    /// run out. Returns the number of attempts it took.
+    #[cfg(feature = "websocket")]
+    async fn reconnect(&self) -> Result<u32, TransportError> {
+        loop {
+            let attempt = {
+                let mut state = self.state.lock().await;
+                state.stream = None;
+                state.reconnect_attempt += 1;
+                let attempt = state.reconnect_attempt;
+                if attempt > self.config.max_reconnect_attempts {
+                    state.reconnect_attempt = 0;
+                }
+                attempt
+            };
 
-        let delay = self.config.reconnect_backoff.delay_for_attempt(attempt - 1);
-        tracing::info!(
-            attempt = attempt,
-            max_attempts = self.config.max_reconnect_attempts,
-            delay_ms = delay.as_millis(),
-            "Attempting WebSocket reconnection"
-        );
+            if attempt > self.config.max_reconnect_attempts {
+                self.set_connection_state(ConnectionState::Disconnected);
+                return Err(TransportError::Connection {
+                    message: format!(
+                        "Maximum reconnection attempts ({}) exceeded",
+                        self.config.max_reconnect_attempts
+                    ),
+                });
+            }
+
+            self.set_connection_state(ConnectionState::Reconnecting);
 
-        tokio::time::sleep(delay).await;
+            let delay = self.config.reconnect_backoff.delay_for_attempt(attempt - 1);
+            tracing::info!(
+                attempt = attempt,
+                max_attempts = self.config.max_reconnect_attempts,
+                delay_ms = delay.as_millis(),
+                "Attempting WebSocket reconnection"
+            );
 
-        self.do_connect().await
+            tokio::time::sleep(delay).await;
+
+            match self.do_connect().await {
+                Ok(()) => {
+                    self.reconnections.fetch_add(1, Ordering::AcqRel);
+                    return Ok(attempt);
+                }
+                Err(e) => {
+                    tracing::warn!(attempt = attempt, error = %e, "WebSocket reconnection failed");
+                }
+            }
+        }
     }
 
     /// Get the current connection state.
@@ -208,23 +295,33 @@ impl WebSocketTransport {
         self.messages_received.load(Ordering::Relaxed)
     }
 
+    /// Get the bytes saved by compression so far.
+    ///
+    /// Counts stay at zero unless compression was offered with
+    /// [`WebSocketConfig::with_compression`] and the server accepted it.
+    #[must_use]
+    pub fn compression_stats(&self) -> CompressionSnapshot {
+        self.compression.snapshot()
+    }
+
     /// Send a message over the WebSocket.
     #[cfg(feature = "websocket")]
     async fn send_message(&self, msg: &Message) -> Result<(), TransportError> {
         let json = serde_json::to_string(msg).map_err(|e| TransportError::Serialization {
             message: format!("Failed to serialize message: {e}"),
         })?;
+        self.limits.send(msg, json.len())?;
 
         let mut state = self.state.lock().await;
+        let frame = state.codec.encode(json)?;
         let stream = state
             .stream
             .as_mut()
             .ok_or_else(|| TransportError::Connection {
                 message: "WebSocket not connected".to_string(),
             })?;
-
         stream
-            .send(WsMessage::Text(json))
+            .send(frame)
             .await
             .map_err(|e| TransportError::Connection {
                 message: format!("Failed to send WebSocket message: {e}"),
@@ -251,26 +348,29 @@ impl WebSocketTransport {
             }
 
             // Try to receive from the stream
-            let ws_msg = {
+            let (ws_msg, codec) = {
                 let mut state = self.state.lock().await;
+                let codec = state.codec.clone();
                 let stream = match state.stream.as_mut() {
                     Some(s) => s,
                     None => return Ok(None),
                 };
 
                 match stream.next().await {
-                    Some(Ok(msg)) => msg,
+                    Some(Ok(msg)) => (msg, codec),
                     Some(Err(e)) => {
                         // Connection error - mark as disconnected
                         self.connected.store(false, Ordering::Release);
                         self.set_connection_state(ConnectionState::Disconnected);
 
-                        // Try to reconnect if auto-reconnect is enabled
+                        // Try to reconnect if auto-reconnect is enabled. The
+                        // server sees a brand-new connection, so report the
+                        // reconnection rather than carrying on as if the
+                        // session had survived.
                         if self.config.auto_reconnect {
                             drop(state);
-                            if self.reconnect().await.is_ok() {
-                                // Retry receive after reconnection (loop continues)
-                                continue;
+                            if let Ok(attempts) = self.reconnect().await {
+                                return Err(TransportError::Reconnected { attempts });
                             }
                         }
 
@@ -289,23 +389,12 @@ impl WebSocketTransport {
 
             // Process the WebSocket message
             match ws_msg {
-                WsMessage::Text(text) => {
-                    let msg: Message =
-                        serde_json::from_str(&text).map_err(|e| TransportError::Serialization {
-                            message: format!("Failed to parse message: {e}"),
-                        })?;
+                frame @ (WsMessage::Text(_) | WsMessage::Binary(_)) => {
+                    // Binary frames hold compressed JSON when compression
+                    // was negotiated, plain JSON otherwise.
+                    let msg = codec.decode(frame)?;
                     self.messages_received.fetch_add(1, Ordering::Relaxed);
-                    return Ok(Some(msg));
-                }
-                WsMessage::Binary(data) => {
-                    // Try to parse binary as JSON
-                    let msg: Message = serde_json::from_slice(&data).map_err(|e| {
-                        TransportError::Serialization {
-                            message: format!("Failed to parse binary message: {e}"),
-                        }
-                    })?;
-                    self.messages_received.fetch_add(1, Ordering::Relaxed);
-                    return Ok(Some(msg));
+                    return Ok(msg);
                 }
                 WsMessage::Ping(data) => {
                     // Respond to ping with pong
@@ -385,7 +474,7 @@ impl Transport for WebSocketTransport {
     }
 
     async fn recv(&self) -> Result<Option<Message>, Self::Error> {
-        self.recv_message().await
+        self.limits.recv(Box::pin(self.recv_message())).await
     }
 
     async fn close(&self) -> Result<(), Self::Error> {
@@ -399,6 +488,60 @@ impl Transport for WebSocketTransport {
     fn metadata(&self) -> TransportMetadata {
         TransportMetadata::new("websocket").remote_addr(&self.config.url)
     }
+
+    fn connection_epoch(&self) -> u64 {
+        self.reconnections.load(Ordering::Acquire)
+    }
+}
+
+/// The encoding the server accepted in the handshake response, if any.
+#[cfg(feature = "websocket")]
+fn accepted_encoding(response: &Response) -> Option<Encoding> {
+    let value = response.headers().get(MCP_COMPRESSION_HEADER)?;
+    Encoding::from_header(value.to_str().ok()?).ok().flatten()
+}
+
+/// Connect to a `wss://` URL using custom TLS settings.
+#[cfg(feature = "tls")]
+async fn connect_tls(
+    url: &url::Url,
+    request: Request,
+    tls: &super::WebSocketTlsConfig,
+    ws_config: TungsteniteConfig,
+) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response), TransportError> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| TransportError::Connection {
+            message: "WebSocket URL has no host".to_string(),
+        })?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = url.port_or_known_default().unwrap_or(443);
+    let server_name = tls.resolve_server_name(host)?;
+    let connector = tokio_rustls::TlsConnector::from(tls.client_config()?);
+
+    let tcp = TcpStream::connect((host, port))
+        .await
+        .map_err(|e| TransportError::Connection {
+            message: format!("WebSocket connection failed: {e}"),
+        })?;
+    let stream =
+        connector
+            .connect(server_name, tcp)
+            .await
+            .map_err(|e| TransportError::Connection {
+                message: format!("TLS handshake failed: {e}"),
+            })?;
+
+    tokio_tungstenite::client_async_with_config(
+        request,
+        MaybeTlsStream::Rustls(stream),
+        Some(ws_config),
+    )
+    .await
+    .map_err(|e| TransportError::Connection {
+        message: format!("WebSocket connection failed: {e}"),
+    })
 }
 
 /// Builder for WebSocket transport.
@@ -440,7 +583,14 @@ impl WebSocketTransportBuilder {
     /// Set maximum message size.
     #[must_use]
     pub const fn max_message_size(mut self, size: usize) -> Self {
-        self.config.max_message_size = size;
+        self.config.limits.max_message_size = size;
+        self
+    }
+
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn limits(mut self, limits: TransportLimits) -> Self {
+        self.config.limits = limits;
         self
     }
 
@@ -458,6 +608,21 @@ impl WebSocketTransportBuilder {
         self
     }
 
+    /// Offer message compression during the handshake.
+    #[must_use]
+    pub const fn compression(mut self, config: CompressionConfig) -> Self {
+        self.config.compression = Some(config);
+        self
+    }
+
+    /// Set the TLS settings used for `wss://` URLs.
+    #[cfg(feature = "tls")]
+    #[must_use]
+    pub fn tls(mut self, tls: super::WebSocketTlsConfig) -> Self {
+        self.config.tls = Some(tls);
+        self
+    }
+
     /// Build the transport (connects immediately).
     pub async fn connect(self) -> Result<WebSocketTransport, TransportError> {
         WebSocketTransport::connect(self.config).await
@@ -0,0 +1,110 @@
+//! Per-connection message compression for WebSocket transports.
+//!
+//! See [`MCP_COMPRESSION_HEADER`](crate::compression::MCP_COMPRESSION_HEADER)
+//! for how compression is negotiated.
+
+use std::sync::Arc;
+
+use mcpkit_core::protocol::Message;
+use tokio_tungstenite::tungstenite::protocol::Message as WsMessage;
+
+use crate::codec;
+use crate::compression::{self, CompressionConfig, CompressionStats, Encoding};
+use crate::error::TransportError;
+
+/// Encodes outgoing and decodes incoming messages on one WebSocket
+/// connection, compressing them when the handshake negotiated it.
+///
+/// Messages at or above the configured minimum size travel as binary frames
+/// holding compressed JSON; everything else is a plain JSON text frame.
+#[derive(Debug, Clone)]
+pub struct WebSocketCodec {
+    /// Settings with the negotiated encoding, when compression is on.
+    compression: Option<CompressionConfig>,
+    max_message_size: usize,
+    stats: Arc<CompressionStats>,
+}
+
+impl WebSocketCodec {
+    /// A codec for a connection without compression.
+    #[must_use]
+    pub fn plain(max_message_size: usize) -> Self {
+        Self {
+            compression: None,
+            max_message_size,
+            stats: Arc::new(CompressionStats::new()),
+        }
+    }
+
+    /// A codec for a connection that negotiated `encoding`, or none.
+    pub(crate) fn negotiated(
+        config: Option<CompressionConfig>,
+        encoding: Option<Encoding>,
+        max_message_size: usize,
+        stats: Arc<CompressionStats>,
+    ) -> Self {
+        Self {
+            compression: config
+                .zip(encoding)
+                .map(|(config, encoding)| config.with_encoding(encoding)),
+            max_message_size,
+            stats,
+        }
+    }
+
+    /// The negotiated encoding, if compression is on.
+    #[must_use]
+    pub fn encoding(&self) -> Option<Encoding> {
+        self.compression.map(|c| c.encoding)
+    }
+
+    /// Statistics shared by every connection of the same transport or
+    /// listener.
+    #[must_use]
+    pub fn stats(&self) -> &Arc<CompressionStats> {
+        &self.stats
+    }
+
+    /// Turn a serialized message into a frame, compressing it if it is large
+    /// enough.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if compression fails.
+    pub fn encode(&self, json: String) -> Result<WsMessage, TransportError> {
+        match self.compression {
+            Some(config) if config.should_compress(json.len()) => {
+                let encoded =
+                    compression::compress(config.encoding, json.as_bytes(), config.level)?;
+                self.stats.record_compressed(json.len(), encoded.len());
+                Ok(WsMessage::Binary(encoded))
+            }
+            _ => Ok(WsMessage::Text(json)),
+        }
+    }
+
+    /// Decode a data frame into a message.
+    ///
+    /// Returns `Ok(None)` for control frames, which carry no message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for malformed JSON or compressed data, or when a
+    /// message inflates beyond the maximum message size.
+    pub fn decode(&self, frame: WsMessage) -> Result<Option<Message>, TransportError> {
+        let data = match frame {
+            WsMessage::Text(text) => bytes::Bytes::from(text),
+            WsMessage::Binary(data) => match self.encoding() {
+                Some(encoding) => {
+                    let decoded = compression::decompress(encoding, &data, self.max_message_size)?;
+                    self.stats.record_decompressed(data.len(), decoded.len());
+                    bytes::Bytes::from(decoded)
+                }
+                // Without compression, binary frames carry plain JSON.
+                None => bytes::Bytes::from(data),
+            },
+            _ => return Ok(None),
+        };
+        codec::decode(data).map(Some)
+    }
+}
@@ -1,5 +1,7 @@
 //! WebSocket transport configuration types.
 
+use crate::compression::CompressionConfig;
+use crate::limits::TransportLimits;
 use std::time::Duration;
 
 /// Configuration for WebSocket transport.
@@ -13,8 +15,8 @@ pub struct WebSocketConfig {
     pub ping_interval: Duration,
     /// Pong timeout (how long to wait for pong after sending ping).
     pub pong_timeout: Duration,
-    /// Maximum message size in bytes.
-    pub max_message_size: usize,
+    /// Message size, in-flight, and read timeout limits.
+    pub limits: TransportLimits,
     /// Whether to enable automatic reconnection.
     pub auto_reconnect: bool,
     /// Maximum reconnection attempts.
@@ -28,6 +30,13 @@ pub struct WebSocketConfig {
     /// Allowed origins for DNS rebinding protection (server-side).
     /// If empty, origin validation is disabled.
     pub allowed_origins: Vec<String>,
+    /// Compression to offer during the handshake. Used only if the server
+    /// accepts it.
+    pub compression: Option<CompressionConfig>,
+    /// TLS settings for `wss://` URLs. When unset, the bundled webpki roots
+    /// are trusted.
+    #[cfg(feature = "tls")]
+    pub tls: Option<super::WebSocketTlsConfig>,
 }
 
 impl WebSocketConfig {
@@ -39,13 +48,16 @@ impl WebSocketConfig {
             connect_timeout: Duration::from_secs(30),
             ping_interval: Duration::from_secs(30),
             pong_timeout: Duration::from_secs(10),
-            max_message_size: 16 * 1024 * 1024, // 16 MB
+            limits: TransportLimits::new(),
             auto_reconnect: true,
             max_reconnect_attempts: 10,
             reconnect_backoff: ExponentialBackoff::default(),
             subprotocols: vec!["mcp".to_string()],
             headers: Vec::new(),
             allowed_origins: Vec::new(),
+            compression: None,
+            #[cfg(feature = "tls")]
+            tls: None,
         }
     }
 
@@ -115,7 +127,14 @@ impl WebSocketConfig {
     /// Set the maximum message size.
     #[must_use]
     pub const fn with_max_message_size(mut self, size: usize) -> Self {
-        self.max_message_size = size;
+        self.limits.max_message_size = size;
+        self
+    }
+
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.limits = limits;
         self
     }
 
@@ -146,6 +165,25 @@ impl WebSocketConfig {
         self.headers.push((name.into(), value.into()));
         self
     }
+
+    /// Offer message compression during the handshake.
+    ///
+    /// If the server accepts, messages of at least
+    /// [`CompressionConfig::min_size`] bytes are sent compressed in both
+    /// directions, using the encoding the server picks.
+    #[must_use]
+    pub const fn with_compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
+    /// Set the TLS settings used for `wss://` URLs.
+    #[cfg(feature = "tls")]
+    #[must_use]
+    pub fn with_tls(mut self, tls: super::WebSocketTlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
 }
 
 impl Default for WebSocketConfig {
@@ -228,7 +266,7 @@ mod tests {
         assert_eq!(config.connect_timeout, Duration::from_secs(10));
         assert_eq!(config.ping_interval, Duration::from_secs(15));
         assert_eq!(config.pong_timeout, Duration::from_secs(5));
-        assert_eq!(config.max_message_size, 1024 * 1024);
+        assert_eq!(config.limits.max_message_size, 1024 * 1024);
         assert!(config.subprotocols.contains(&"custom".to_string()));
         assert_eq!(config.headers.len(), 1);
     }
@@ -9,7 +9,10 @@
 //! - Automatic ping/pong handling for connection health
 //! - Reconnection with exponential backoff
 //! - Message framing and fragmentation handling
-//! - TLS/SSL support via rustls
+//! - TLS/SSL support via rustls (custom roots, SNI override and server-side
+//!   termination with the `tls` feature)
+//! - Optional gzip/deflate message compression, negotiated during the
+//!   handshake (see [`crate::compression`])
 //!
 //! # Example
 //!
@@ -28,8 +31,12 @@
 //! ```
 
 mod client;
+#[cfg(feature = "websocket")]
+mod compression;
 mod config;
 mod server;
+#[cfg(feature = "tls")]
+mod tls;
 
 // Re-export public types
 pub use client::{WebSocketTransport, WebSocketTransportBuilder};
@@ -37,4 +44,12 @@ pub use config::{ConnectionState, ExponentialBackoff, WebSocketConfig};
 pub use server::{OriginValidationMode, WebSocketServerConfig};
 
 #[cfg(feature = "websocket")]
-pub use server::{AcceptedConnection, WebSocketListener};
+pub use compression::WebSocketCodec;
+#[cfg(feature = "websocket")]
+pub use server::{AcceptedConnection, ListenerStream, WebSocketListener};
+#[cfg(feature = "tls")]
+pub use tls::{WebSocketTlsConfig, server_config_from_pem};
+
+/// The rustls version used for TLS, re-exported for building configurations.
+#[cfg(feature = "tls")]
+pub use rustls;
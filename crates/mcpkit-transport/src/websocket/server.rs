@@ -28,6 +28,9 @@ use std::sync::Arc;
 #[cfg(feature = "websocket")]
 use std::sync::atomic::AtomicU64;
 
+use crate::compression::CompressionConfig;
+#[cfg(feature = "websocket")]
+use crate::compression::{CompressionSnapshot, CompressionStats, Encoding, MCP_COMPRESSION_HEADER};
 use crate::error::TransportError;
 
 /// Origin validation mode for DNS rebinding protection.
@@ -73,6 +76,8 @@ pub struct WebSocketServerConfig {
     pub origin_validation_mode: OriginValidationMode,
     /// Whether the security warning has been acknowledged.
     pub security_warning_acknowledged: bool,
+    /// Compression to accept when a client offers it.
+    pub compression: Option<CompressionConfig>,
 }
 
 impl Default for WebSocketServerConfig {
@@ -93,6 +98,7 @@ impl WebSocketServerConfig {
             max_message_size: 16 * 1024 * 1024, // 16 MB
             origin_validation_mode: OriginValidationMode::WarnAndAllow,
             security_warning_acknowledged: false,
+            compression: None,
         }
     }
 
@@ -104,6 +110,7 @@ impl WebSocketServerConfig {
             max_message_size: 16 * 1024 * 1024,
             origin_validation_mode: OriginValidationMode::AllowList,
             security_warning_acknowledged: true,
+            compression: None,
         }
     }
 
@@ -146,6 +153,17 @@ impl WebSocketServerConfig {
         self
     }
 
+    /// Accept message compression from clients that offer it.
+    ///
+    /// The listener picks [`CompressionConfig::encoding`] if the client
+    /// supports it, and both sides then compress messages of at least their
+    /// configured minimum size.
+    #[must_use]
+    pub const fn with_compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
     /// Check if an origin is allowed based on the current validation mode.
     #[must_use]
     pub fn is_origin_allowed(&self, origin: Option<&str>) -> bool {
@@ -227,6 +245,15 @@ pub struct WebSocketListener {
     active_connections: Arc<AtomicU64>,
     /// Shutdown signal sender.
     shutdown_tx: crate::runtime::AsyncMutex<Option<tokio::sync::broadcast::Sender<()>>>,
+    /// Socket bound ahead of [`start`](Self::start), if any.
+    bound: std::sync::Mutex<Option<tokio::net::TcpListener>>,
+    /// Address the listener is bound to, once bound.
+    local_addr: std::sync::Mutex<Option<std::net::SocketAddr>>,
+    /// Compression statistics shared by all accepted connections.
+    compression: Arc<CompressionStats>,
+    /// TLS configuration for terminating `wss://` connections.
+    #[cfg(feature = "tls")]
+    tls: Option<Arc<rustls::ServerConfig>>,
 }
 
 // SAFETY: WebSocketListener is RefUnwindSafe because:
@@ -236,15 +263,98 @@ pub struct WebSocketListener {
 #[cfg(feature = "websocket")]
 impl std::panic::RefUnwindSafe for WebSocketListener {}
 
+/// The byte stream under an accepted WebSocket connection.
+#[cfg(feature = "websocket")]
+#[non_exhaustive]
+pub enum ListenerStream {
+    /// Plaintext `ws://` connection.
+    Plain(tokio::net::TcpStream),
+    /// TLS-terminated `wss://` connection.
+    #[cfg(feature = "tls")]
+    Tls(Box<tokio_rustls::server::TlsStream<tokio::net::TcpStream>>),
+}
+
+#[cfg(feature = "websocket")]
+impl ListenerStream {
+    /// Whether the connection is TLS-terminated.
+    #[must_use]
+    pub const fn is_tls(&self) -> bool {
+        match self {
+            Self::Plain(_) => false,
+            #[cfg(feature = "tls")]
+            Self::Tls(_) => true,
+        }
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl tokio::io::AsyncRead for ListenerStream {
+    fn poll_read(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => std::pin::Pin::new(stream).poll_read(cx, buf),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => std::pin::Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl tokio::io::AsyncWrite for ListenerStream {
+    fn poll_write(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+        buf: &[u8],
+    ) -> std::task::Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Plain(stream) => std::pin::Pin::new(stream).poll_write(cx, buf),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => std::pin::Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => std::pin::Pin::new(stream).poll_flush(cx),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => std::pin::Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(stream) => std::pin::Pin::new(stream).poll_shutdown(cx),
+            #[cfg(feature = "tls")]
+            Self::Tls(stream) => std::pin::Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+/// How long a client gets to complete the TLS handshake.
+#[cfg(feature = "tls")]
+const TLS_HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 /// An accepted WebSocket connection with metadata.
 #[cfg(feature = "websocket")]
 pub struct AcceptedConnection {
     /// The WebSocket stream.
-    pub stream: tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+    pub stream: tokio_tungstenite::WebSocketStream<ListenerStream>,
     /// Remote peer address.
     pub peer_addr: std::net::SocketAddr,
     /// Connection ID for tracking.
     pub connection_id: u64,
+    /// Encodes and decodes messages with the compression negotiated for
+    /// this connection.
+    pub codec: super::WebSocketCodec,
 }
 
 #[cfg(feature = "websocket")]
@@ -262,6 +372,11 @@ impl WebSocketListener {
             connection_rx: crate::runtime::AsyncMutex::new(rx),
             active_connections: Arc::new(AtomicU64::new(0)),
             shutdown_tx: crate::runtime::AsyncMutex::new(None),
+            bound: std::sync::Mutex::new(None),
+            local_addr: std::sync::Mutex::new(None),
+            compression: Arc::new(CompressionStats::new()),
+            #[cfg(feature = "tls")]
+            tls: None,
         }
     }
 
@@ -277,6 +392,79 @@ impl WebSocketListener {
             connection_rx: crate::runtime::AsyncMutex::new(rx),
             active_connections: Arc::new(AtomicU64::new(0)),
             shutdown_tx: crate::runtime::AsyncMutex::new(None),
+            bound: std::sync::Mutex::new(None),
+            local_addr: std::sync::Mutex::new(None),
+            compression: Arc::new(CompressionStats::new()),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// Bind a listener that terminates TLS and serves `wss://`.
+    ///
+    /// The socket is bound immediately, so [`local_addr`](Self::local_addr)
+    /// is available before [`start`](Self::start) is called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address can't be bound.
+    #[cfg(feature = "tls")]
+    pub async fn bind_tls(
+        bind_addr: impl Into<String>,
+        tls_config: Arc<rustls::ServerConfig>,
+    ) -> Result<Self, TransportError> {
+        let listener = Self::new(bind_addr).with_tls(tls_config);
+        let socket = tokio::net::TcpListener::bind(&listener.bind_addr)
+            .await
+            .map_err(|e| TransportError::Connection {
+                message: format!("Failed to bind WebSocket listener: {e}"),
+            })?;
+        listener.record_local_addr(&socket);
+        *listener
+            .bound
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(socket);
+        Ok(listener)
+    }
+
+    /// Terminate TLS on accepted connections with the given configuration.
+    #[cfg(feature = "tls")]
+    #[must_use]
+    pub fn with_tls(mut self, tls_config: Arc<rustls::ServerConfig>) -> Self {
+        self.tls = Some(tls_config);
+        self
+    }
+
+    /// Whether the listener terminates TLS.
+    #[must_use]
+    pub const fn is_tls(&self) -> bool {
+        #[cfg(feature = "tls")]
+        {
+            self.tls.is_some()
+        }
+        #[cfg(not(feature = "tls"))]
+        {
+            false
+        }
+    }
+
+    /// The address the listener is bound to, once bound.
+    ///
+    /// Useful when binding to port 0.
+    #[must_use]
+    pub fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        *self
+            .local_addr
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn record_local_addr(&self, socket: &tokio::net::TcpListener) {
+        if let Ok(addr) = socket.local_addr() {
+            *self
+                .local_addr
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(addr);
         }
     }
 
@@ -292,6 +480,12 @@ impl WebSocketListener {
         self.active_connections.load(Ordering::Relaxed)
     }
 
+    /// Get the bytes saved by compression across all connections.
+    #[must_use]
+    pub fn compression_stats(&self) -> CompressionSnapshot {
+        self.compression.snapshot()
+    }
+
     /// Accept the next incoming connection.
     ///
     /// This method returns the next accepted WebSocket connection, or an error
@@ -320,15 +514,27 @@ impl WebSocketListener {
     pub async fn start(&self) -> Result<(), TransportError> {
         use tokio::net::TcpListener;
 
-        let listener =
-            TcpListener::bind(&self.bind_addr)
-                .await
-                .map_err(|e| TransportError::Connection {
+        let prebound = self
+            .bound
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        let listener = if let Some(listener) = prebound {
+            listener
+        } else {
+            let listener = TcpListener::bind(&self.bind_addr).await.map_err(|e| {
+                TransportError::Connection {
                     message: format!("Failed to bind WebSocket listener: {e}"),
-                })?;
+                }
+            })?;
+            self.record_local_addr(&listener);
+            listener
+        };
+        #[cfg(feature = "tls")]
+        let tls_acceptor = self.tls.clone().map(tokio_rustls::TlsAcceptor::from);
 
         self.running.store(true, Ordering::Release);
-        tracing::info!(addr = %self.bind_addr, "WebSocket listener started");
+        tracing::info!(addr = %self.bind_addr, tls = self.is_tls(), "WebSocket listener started");
 
         let (shutdown_tx, _) = tokio::sync::broadcast::channel::<()>(1);
         *self.shutdown_tx.lock().await = Some(shutdown_tx.clone());
@@ -346,9 +552,13 @@ impl WebSocketListener {
 
                             let allowed_origins = self.config.allowed_origins.clone();
                             let max_message_size = self.config.max_message_size;
+                            let compression = self.config.compression;
+                            let compression_stats = Arc::clone(&self.compression);
                             let tx = self.connection_tx.clone();
                             let conn_id = connection_id.fetch_add(1, Ordering::Relaxed);
                             let active_conns_counter = Arc::clone(&self.active_connections);
+                            #[cfg(feature = "tls")]
+                            let tls_acceptor = tls_acceptor.clone();
 
                             // Increment active connection count
                             self.active_connections.fetch_add(1, Ordering::Relaxed);
@@ -362,9 +572,31 @@ impl WebSocketListener {
                             tokio::spawn(async move {
                                 let _guard = guard;
 
+                                #[cfg(feature = "tls")]
+                                let stream = match tls_acceptor {
+                                    Some(acceptor) => {
+                                        match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                                            Ok(Ok(tls)) => ListenerStream::Tls(Box::new(tls)),
+                                            Ok(Err(e)) => {
+                                                tracing::warn!(peer = %addr, error = %e, "TLS handshake failed");
+                                                return;
+                                            }
+                                            Err(_) => {
+                                                tracing::warn!(peer = %addr, "TLS handshake timed out");
+                                                return;
+                                            }
+                                        }
+                                    }
+                                    None => ListenerStream::Plain(stream),
+                                };
+                                #[cfg(not(feature = "tls"))]
+                                let stream = ListenerStream::Plain(stream);
+
                                 // Use the callback-based accept for origin validation
+                                // and compression negotiation
+                                let mut negotiated = None;
                                 let callback = |request: &tokio_tungstenite::tungstenite::handshake::server::Request,
-                                               response: tokio_tungstenite::tungstenite::handshake::server::Response| {
+                                               mut response: tokio_tungstenite::tungstenite::handshake::server::Response| {
                                     // Extract origin header
                                     if !allowed_origins.is_empty() {
                                         if let Some(origin) = request.headers().get("origin") {
@@ -392,6 +624,7 @@ impl WebSocketListener {
                                                 .expect("failed to build HTTP 403 response"));
                                         }
                                     }
+                                    negotiated = accept_compression(request, &mut response, compression);
                                     Ok(response)
                                 };
 
@@ -422,6 +655,12 @@ impl WebSocketListener {
                                             stream: ws_stream,
                                             peer_addr: addr,
                                             connection_id: conn_id,
+                                            codec: super::WebSocketCodec::negotiated(
+                                                compression,
+                                                negotiated,
+                                                max_message_size,
+                                                compression_stats,
+                                            ),
                                         };
 
                                         if tx.send(connection).await.is_err() {
@@ -487,6 +726,27 @@ impl WebSocketListener {
     }
 }
 
+/// Pick an encoding from the client's compression offer and echo it in the
+/// handshake response.
+#[cfg(feature = "websocket")]
+fn accept_compression(
+    request: &tokio_tungstenite::tungstenite::handshake::server::Request,
+    response: &mut tokio_tungstenite::tungstenite::handshake::server::Response,
+    config: Option<CompressionConfig>,
+) -> Option<Encoding> {
+    let offer = request
+        .headers()
+        .get(MCP_COMPRESSION_HEADER)?
+        .to_str()
+        .ok()?;
+    let encoding = Encoding::negotiate(offer, config?.encoding)?;
+    response.headers_mut().insert(
+        MCP_COMPRESSION_HEADER,
+        tokio_tungstenite::tungstenite::http::HeaderValue::from_static(encoding.as_str()),
+    );
+    Some(encoding)
+}
+
 /// Guard that decrements active connection count on drop.
 ///
 /// Uses `Arc<AtomicU64>` for safe shared ownership across tasks.
@@ -547,6 +807,18 @@ impl WebSocketListener {
         &self.config
     }
 
+    /// Whether the listener terminates TLS (always false when feature disabled).
+    #[must_use]
+    pub const fn is_tls(&self) -> bool {
+        false
+    }
+
+    /// The address the listener is bound to (always `None` when feature disabled).
+    #[must_use]
+    pub const fn local_addr(&self) -> Option<std::net::SocketAddr> {
+        None
+    }
+
     /// Get the number of active connections (always 0 when feature disabled).
     #[must_use]
     pub fn active_connections(&self) -> u64 {
@@ -0,0 +1,238 @@
+//! TLS configuration for `wss://` connections.
+//!
+//! Without the `tls` feature, `wss://` URLs are verified against the bundled
+//! webpki roots only. [`WebSocketTlsConfig`] adds private CAs, an SNI
+//! override, or a fully custom [`rustls::ClientConfig`] on the client side.
+//! On the server side, [`WebSocketListener::bind_tls`] terminates TLS with a
+//! [`rustls::ServerConfig`], which [`server_config_from_pem`] can build from
+//! PEM files.
+//!
+//! [`WebSocketListener::bind_tls`]: super::WebSocketListener::bind_tls
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use mcpkit_transport::websocket::{WebSocketConfig, WebSocketTlsConfig};
+//!
+//! # fn example() -> Result<(), mcpkit_transport::TransportError> {
+//! let ca = std::fs::read("internal-ca.pem").expect("CA bundle");
+//! let tls = WebSocketTlsConfig::new()
+//!     .with_root_certificates_pem(&ca)?
+//!     .with_server_name("mcp.internal");
+//!
+//! let config = WebSocketConfig::new("wss://10.0.0.5:9443/mcp").with_tls(tls);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+
+use crate::error::TransportError;
+
+/// Client-side TLS settings for `wss://` connections.
+#[derive(Debug, Clone)]
+pub struct WebSocketTlsConfig {
+    root_certificates: Vec<CertificateDer<'static>>,
+    webpki_roots: bool,
+    server_name: Option<String>,
+    client_config: Option<Arc<ClientConfig>>,
+}
+
+impl Default for WebSocketTlsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebSocketTlsConfig {
+    /// Create a configuration that trusts the bundled webpki roots.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            root_certificates: Vec::new(),
+            webpki_roots: true,
+            server_name: None,
+            client_config: None,
+        }
+    }
+
+    /// Trust an additional root certificate (DER encoded).
+    #[must_use]
+    pub fn with_root_certificate(mut self, certificate: CertificateDer<'static>) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Trust every certificate in a PEM bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle can't be parsed or holds no certificate.
+    pub fn with_root_certificates_pem(mut self, pem: &[u8]) -> Result<Self, TransportError> {
+        let certificates = parse_certificates(pem)?;
+        self.root_certificates.extend(certificates);
+        Ok(self)
+    }
+
+    /// Trust only the roots added explicitly, not the bundled webpki roots.
+    #[must_use]
+    pub const fn without_webpki_roots(mut self) -> Self {
+        self.webpki_roots = false;
+        self
+    }
+
+    /// Send and verify `name` instead of the URL's host.
+    ///
+    /// Useful when connecting by IP address to a server whose certificate
+    /// names a host.
+    #[must_use]
+    pub fn with_server_name(mut self, name: impl Into<String>) -> Self {
+        self.server_name = Some(name.into());
+        self
+    }
+
+    /// Use a prebuilt rustls configuration, e.g. for client certificates.
+    ///
+    /// Root certificate settings are ignored when this is set.
+    #[must_use]
+    pub fn with_client_config(mut self, config: Arc<ClientConfig>) -> Self {
+        self.client_config = Some(config);
+        self
+    }
+
+    /// The SNI override, if any.
+    #[must_use]
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// Resolve the server name to verify for a connection to `host`.
+    pub(crate) fn resolve_server_name(
+        &self,
+        host: &str,
+    ) -> Result<ServerName<'static>, TransportError> {
+        let name = self.server_name.as_deref().unwrap_or(host);
+        ServerName::try_from(name.to_string()).map_err(|e| TransportError::Connection {
+            message: format!("Invalid TLS server name '{name}': {e}"),
+        })
+    }
+
+    /// Build the rustls client configuration.
+    pub(crate) fn client_config(&self) -> Result<Arc<ClientConfig>, TransportError> {
+        if let Some(config) = &self.client_config {
+            return Ok(Arc::clone(config));
+        }
+
+        let mut roots = RootCertStore::empty();
+        if self.webpki_roots {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+        for certificate in &self.root_certificates {
+            roots
+                .add(certificate.clone())
+                .map_err(|e| TransportError::Connection {
+                    message: format!("Invalid TLS root certificate: {e}"),
+                })?;
+        }
+        if roots.is_empty() {
+            return Err(TransportError::Connection {
+                message: "TLS configuration trusts no root certificates".to_string(),
+            });
+        }
+
+        let config = ClientConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()
+            .map_err(tls_error)?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(Arc::new(config))
+    }
+}
+
+/// Build a server configuration from a PEM certificate chain and private key.
+///
+/// # Errors
+///
+/// Returns an error if either PEM input can't be parsed or the key doesn't
+/// match the certificate.
+pub fn server_config_from_pem(
+    cert_chain_pem: &[u8],
+    key_pem: &[u8],
+) -> Result<Arc<ServerConfig>, TransportError> {
+    let chain = parse_certificates(cert_chain_pem)?;
+    let key = PrivateKeyDer::from_pem_slice(key_pem).map_err(|e| TransportError::Connection {
+        message: format!("Invalid TLS private key: {e}"),
+    })?;
+
+    let config = ServerConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .with_no_client_auth()
+        .with_single_cert(chain, key)
+        .map_err(tls_error)?;
+    Ok(Arc::new(config))
+}
+
+fn parse_certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>, TransportError> {
+    let certificates = CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TransportError::Connection {
+            message: format!("Invalid PEM certificate: {e}"),
+        })?;
+    if certificates.is_empty() {
+        return Err(TransportError::Connection {
+            message: "PEM input contains no certificate".to_string(),
+        });
+    }
+    Ok(certificates)
+}
+
+fn provider() -> Arc<rustls::crypto::CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+#[allow(clippy::needless_pass_by_value)] // Used with map_err
+fn tls_error(e: rustls::Error) -> TransportError {
+    TransportError::Connection {
+        message: format!("TLS configuration error: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_name_override() {
+        let config = WebSocketTlsConfig::new().with_server_name("mcp.internal");
+        assert_eq!(config.server_name(), Some("mcp.internal"));
+        let name = config.resolve_server_name("10.0.0.5").unwrap();
+        assert_eq!(name.to_str(), "mcp.internal");
+
+        let name = WebSocketTlsConfig::new()
+            .resolve_server_name("10.0.0.5")
+            .unwrap();
+        assert_eq!(name.to_str(), "10.0.0.5");
+    }
+
+    #[test]
+    fn test_invalid_pem_is_rejected() {
+        assert!(
+            WebSocketTlsConfig::new()
+                .with_root_certificates_pem(b"not a certificate")
+                .is_err()
+        );
+        assert!(server_config_from_pem(b"", b"").is_err());
+    }
+
+    #[test]
+    fn test_no_roots_is_an_error() {
+        let config = WebSocketTlsConfig::new().without_webpki_roots();
+        assert!(config.client_config().is_err());
+        assert!(WebSocketTlsConfig::new().client_config().is_ok());
+    }
+}
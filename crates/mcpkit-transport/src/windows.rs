@@ -7,7 +7,8 @@
 //!
 //! - Low-latency local IPC on Windows
 //! - Named pipe addressing (e.g., `\\.\pipe\mcp-server`)
-//! - Multiple client support
+//! - Concurrent clients: a listening pipe instance is always available
+//! - Security descriptors (SDDL) and remote client rejection
 //! - Newline-delimited JSON message framing
 //!
 //! # Example
@@ -44,12 +45,44 @@
 //!     assert!(config2.name.contains("mcp-server"));
 //! }
 //! ```
+//!
+//! # Serving Connections
+//!
+//! [`NamedPipeServer`] implements [`TransportListener`] the same way
+//! `UnixListener` does on Unix, so a server accepts and serves each client
+//! on its own task:
+//!
+//! ```rust,ignore
+//! use mcpkit_transport::windows::NamedPipeServer;
+//! use mcpkit_transport::TransportListener;
+//!
+//! let listener = NamedPipeServer::bind(r"\\.\pipe\mcp")?;
+//! loop {
+//!     let transport = listener.accept().await?;
+//!     tokio::spawn(server.clone().serve(transport));
+//! }
+//! ```
+//!
+//! A new pipe instance is created as soon as a client connects, so further
+//! clients can connect while earlier ones are being served, up to
+//! [`NamedPipeConfig::max_instances`].
 
+use crate::codec;
 use crate::error::TransportError;
+#[cfg(all(windows, feature = "tokio-runtime"))]
+use crate::limits::LimitEnforcer;
+use crate::limits::TransportLimits;
 use crate::runtime::AsyncMutex;
 use crate::traits::{Transport, TransportListener, TransportMetadata};
 use mcpkit_core::protocol::Message;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+#[cfg(all(windows, feature = "tokio-runtime"))]
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::windows::named_pipe as pipe,
+};
 
 /// Default maximum message size (16 MB).
 pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
@@ -57,22 +90,37 @@ pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
 /// Default maximum number of pipe instances.
 pub const DEFAULT_MAX_INSTANCES: u32 = 16;
 
+/// Largest instance limit Windows accepts; `PIPE_UNLIMITED_INSTANCES` is 255.
+pub const MAX_PIPE_INSTANCES: u32 = 254;
+
 /// Default buffer size.
 pub const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
 
+/// Default time a client waits for a free pipe instance.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Configuration for Windows named pipe transport.
 #[derive(Debug, Clone)]
 pub struct NamedPipeConfig {
     /// The pipe name (e.g., `\\.\pipe\mcp-server`).
     pub name: String,
-    /// Maximum number of pipe instances for the server.
+    /// Maximum number of pipe instances for the server (1 to
+    /// [`MAX_PIPE_INSTANCES`]).
     pub max_instances: u32,
     /// Input buffer size.
     pub in_buffer_size: usize,
     /// Output buffer size.
     pub out_buffer_size: usize,
-    /// Maximum message size in bytes.
-    pub max_message_size: usize,
+    /// Message size, in-flight, and read timeout limits.
+    pub limits: TransportLimits,
+    /// Whether the server refuses clients connecting from other machines.
+    pub reject_remote_clients: bool,
+    /// Security descriptor for server pipe instances, in SDDL form
+    /// (e.g. `D:(A;;GA;;;BA)(A;;GA;;;OW)`). `None` uses the default
+    /// descriptor.
+    pub security_descriptor: Option<String>,
+    /// How long a client waits for a free pipe instance when all are busy.
+    pub connect_timeout: Duration,
 }
 
 impl NamedPipeConfig {
@@ -92,7 +140,10 @@ impl NamedPipeConfig {
             max_instances: DEFAULT_MAX_INSTANCES,
             in_buffer_size: DEFAULT_BUFFER_SIZE,
             out_buffer_size: DEFAULT_BUFFER_SIZE,
-            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            limits: TransportLimits::new().with_max_message_size(DEFAULT_MAX_MESSAGE_SIZE),
+            reject_remote_clients: true,
+            security_descriptor: None,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
         }
     }
 
@@ -120,7 +171,39 @@ impl NamedPipeConfig {
     /// Set the maximum message size.
     #[must_use]
     pub const fn with_max_message_size(mut self, size: usize) -> Self {
-        self.max_message_size = size;
+        self.limits.max_message_size = size;
+        self
+    }
+
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set whether the server refuses remote clients (default: `true`).
+    #[must_use]
+    pub const fn with_reject_remote_clients(mut self, reject: bool) -> Self {
+        self.reject_remote_clients = reject;
+        self
+    }
+
+    /// Set the security descriptor of server pipe instances, in SDDL form.
+    ///
+    /// This is the named pipe counterpart of socket file permissions: for
+    /// example `D:(A;;GA;;;SY)(A;;GA;;;OW)` admits only `SYSTEM` and the
+    /// pipe's owner.
+    #[must_use]
+    pub fn with_security_descriptor(mut self, sddl: impl Into<String>) -> Self {
+        self.security_descriptor = Some(sddl.into());
+        self
+    }
+
+    /// Set how long a client waits for a free pipe instance.
+    #[must_use]
+    pub const fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
         self
     }
 
@@ -129,71 +212,85 @@ impl NamedPipeConfig {
     pub fn short_name(&self) -> &str {
         self.name.strip_prefix(r"\\.\pipe\").unwrap_or(&self.name)
     }
-}
 
-/// Internal state for reading/writing.
-#[allow(dead_code)]
-struct NamedPipeState {
-    /// Line buffer for reading complete messages.
-    line_buffer: String,
-    /// Read buffer for accumulating bytes.
-    read_buffer: Vec<u8>,
+    /// Check the settings Windows would otherwise reject at bind time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `max_instances` is out of range or a buffer size
+    /// does not fit in 32 bits.
+    pub fn validate(&self) -> Result<(), TransportError> {
+        if self.max_instances == 0 || self.max_instances > MAX_PIPE_INSTANCES {
+            return Err(TransportError::Connection {
+                message: format!(
+                    "max_instances for named pipe '{}' must be between 1 and {MAX_PIPE_INSTANCES}, got {}",
+                    self.name, self.max_instances
+                ),
+            });
+        }
+        if u32::try_from(self.in_buffer_size).is_err()
+            || u32::try_from(self.out_buffer_size).is_err()
+        {
+            return Err(TransportError::Connection {
+                message: format!(
+                    "buffer sizes for named pipe '{}' must fit in 32 bits",
+                    self.name
+                ),
+            });
+        }
+        Ok(())
+    }
 }
 
+/// Read half of a connected pipe.
+#[cfg(all(windows, feature = "tokio-runtime"))]
+type PipeReader = BufReader<Box<dyn AsyncRead + Send + Unpin>>;
+
+/// Write half of a connected pipe.
+#[cfg(all(windows, feature = "tokio-runtime"))]
+type PipeWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
 /// Windows named pipe transport.
 ///
-/// Provides low-latency local IPC using Windows named pipes.
+/// Provides low-latency local IPC using Windows named pipes. Reads and
+/// writes use separate halves of the pipe, so a pending `recv` does not
+/// block `send`.
 #[cfg(all(windows, feature = "tokio-runtime"))]
 pub struct NamedPipeTransport {
     config: NamedPipeConfig,
-    pipe: AsyncMutex<Option<tokio::net::windows::named_pipe::NamedPipeClient>>,
-    server_pipe: AsyncMutex<Option<tokio::net::windows::named_pipe::NamedPipeServer>>,
-    state: AsyncMutex<NamedPipeState>,
+    limits: LimitEnforcer,
+    reader: AsyncMutex<Option<PipeReader>>,
+    writer: AsyncMutex<Option<PipeWriter>>,
     connected: AtomicBool,
     messages_sent: AtomicU64,
     messages_received: AtomicU64,
-    is_server_side: bool,
+    metadata: TransportMetadata,
 }
 
 #[cfg(all(windows, feature = "tokio-runtime"))]
 impl NamedPipeTransport {
-    /// Create a new client transport from a connected pipe.
-    fn from_client(
-        config: NamedPipeConfig,
-        pipe: tokio::net::windows::named_pipe::NamedPipeClient,
-    ) -> Self {
-        Self {
-            config,
-            pipe: AsyncMutex::new(Some(pipe)),
-            server_pipe: AsyncMutex::new(None),
-            state: AsyncMutex::new(NamedPipeState {
-                line_buffer: String::with_capacity(4096),
-                read_buffer: Vec::with_capacity(8192),
-            }),
-            connected: AtomicBool::new(true),
-            messages_sent: AtomicU64::new(0),
-            messages_received: AtomicU64::new(0),
-            is_server_side: false,
-        }
-    }
+    /// Create a transport over a connected pipe of either end.
+    fn from_pipe<P>(config: NamedPipeConfig, pipe: P, local_addr: &str) -> Self
+    where
+        P: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (read_half, write_half) = tokio::io::split(pipe);
+        let reader: Box<dyn AsyncRead + Send + Unpin> = Box::new(read_half);
+        let writer: PipeWriter = Box::new(write_half);
+        let metadata = TransportMetadata::new("named-pipe")
+            .remote_addr(config.name.clone())
+            .local_addr(local_addr)
+            .connected_now();
 
-    /// Create a new server transport from an accepted pipe.
-    fn from_server(
-        config: NamedPipeConfig,
-        pipe: tokio::net::windows::named_pipe::NamedPipeServer,
-    ) -> Self {
         Self {
+            limits: LimitEnforcer::new(config.limits.clone()),
             config,
-            pipe: AsyncMutex::new(None),
-            server_pipe: AsyncMutex::new(Some(pipe)),
-            state: AsyncMutex::new(NamedPipeState {
-                line_buffer: String::with_capacity(4096),
-                read_buffer: Vec::with_capacity(8192),
-            }),
+            reader: AsyncMutex::new(Some(BufReader::new(reader))),
+            writer: AsyncMutex::new(Some(writer)),
             connected: AtomicBool::new(true),
             messages_sent: AtomicU64::new(0),
             messages_received: AtomicU64::new(0),
-            is_server_side: true,
+            metadata,
         }
     }
 
@@ -204,19 +301,37 @@ impl NamedPipeTransport {
     }
 
     /// Connect with custom configuration.
+    ///
+    /// If every server instance is busy, retries until
+    /// [`NamedPipeConfig::connect_timeout`] elapses.
     pub async fn connect_with_config(config: NamedPipeConfig) -> Result<Self, TransportError> {
-        use tokio::net::windows::named_pipe::ClientOptions;
-
-        // Try to connect to the pipe
-        let pipe =
-            ClientOptions::new()
-                .open(&config.name)
-                .map_err(|e| TransportError::Connection {
-                    message: format!("Failed to connect to named pipe '{}': {}", config.name, e),
-                })?;
+        /// `ERROR_PIPE_BUSY`: all instances are connected to other clients.
+        const ERROR_PIPE_BUSY: i32 = 231;
+        const RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+        let deadline = tokio::time::Instant::now() + config.connect_timeout;
+        let pipe = loop {
+            match pipe::ClientOptions::new().open(&config.name) {
+                Ok(pipe) => break pipe,
+                Err(e)
+                    if e.raw_os_error() == Some(ERROR_PIPE_BUSY)
+                        && tokio::time::Instant::now() < deadline =>
+                {
+                    tokio::time::sleep(RETRY_INTERVAL).await;
+                }
+                Err(e) => {
+                    return Err(TransportError::Connection {
+                        message: format!(
+                            "Failed to connect to named pipe '{}': {}",
+                            config.name, e
+                        ),
+                    });
+                }
+            }
+        };
 
         tracing::debug!(name = %config.name, "Connected to named pipe");
-        Ok(Self::from_client(config, pipe))
+        Ok(Self::from_pipe(config, pipe, "client"))
     }
 
     /// Get the pipe name.
@@ -233,193 +348,62 @@ impl NamedPipeTransport {
     pub fn messages_received(&self) -> u64 {
         self.messages_received.load(Ordering::Relaxed)
     }
+}
 
-    /// Internal send implementation.
-    async fn send_impl(&self, msg: Message) -> Result<(), TransportError> {
-        use tokio::io::AsyncWriteExt;
-
-        if !self.connected.load(Ordering::Acquire) {
-            return Err(TransportError::Connection {
-                message: "Named pipe not connected".to_string(),
-            });
-        }
-
-        // Serialize the message with newline delimiter
-        let mut data = serde_json::to_vec(&msg).map_err(|e| TransportError::Serialization {
-            message: format!("Failed to serialize message: {e}"),
-        })?;
-
-        // Check message size limit
-        if data.len() > self.config.max_message_size {
-            return Err(TransportError::MessageTooLarge {
-                size: data.len(),
-                max: self.config.max_message_size,
-            });
-        }
-
-        data.push(b'\n');
-
-        // Write to the appropriate pipe
-        if self.is_server_side {
-            let mut guard = self.server_pipe.lock().await;
-            if let Some(pipe) = guard.as_mut() {
-                pipe.write_all(&data)
-                    .await
-                    .map_err(|e| TransportError::Io {
-                        message: format!("Failed to write to named pipe: {e}"),
-                    })?;
-                pipe.flush().await.map_err(|e| TransportError::Io {
-                    message: format!("Failed to flush named pipe: {e}"),
-                })?;
-            } else {
-                return Err(TransportError::Connection {
-                    message: "Named pipe not available".to_string(),
-                });
-            }
-        } else {
-            let mut guard = self.pipe.lock().await;
-            if let Some(pipe) = guard.as_mut() {
-                pipe.write_all(&data)
-                    .await
-                    .map_err(|e| TransportError::Io {
-                        message: format!("Failed to write to named pipe: {e}"),
-                    })?;
-                pipe.flush().await.map_err(|e| TransportError::Io {
-                    message: format!("Failed to flush named pipe: {e}"),
-                })?;
-            } else {
-                return Err(TransportError::Connection {
-                    message: "Named pipe not available".to_string(),
-                });
-            }
-        }
-
-        self.messages_sent.fetch_add(1, Ordering::Relaxed);
-        Ok(())
-    }
-
-    /// Internal receive implementation.
-    async fn recv_impl(&self) -> Result<Option<Message>, TransportError> {
-        use tokio::io::AsyncReadExt;
-
+#[cfg(all(windows, feature = "tokio-runtime"))]
+impl NamedPipeTransport {
+    /// Read the next message from the pipe.
+    async fn read_message(&self) -> Result<Option<Message>, TransportError> {
         if !self.connected.load(Ordering::Acquire) {
             return Ok(None);
         }
 
-        let mut state = self.state.lock().await;
-
-        // Check if we have a complete line in the buffer
-        if let Some(newline_pos) = state.line_buffer.find('\n') {
-            let line = state.line_buffer[..newline_pos].to_string();
-            state.line_buffer = state.line_buffer[newline_pos + 1..].to_string();
-
-            if line.is_empty() {
-                return Ok(None);
-            }
-
-            let msg: Message =
-                serde_json::from_str(&line).map_err(|e| TransportError::Deserialization {
-                    message: format!("Failed to deserialize message: {e}"),
-                })?;
-
-            self.messages_received.fetch_add(1, Ordering::Relaxed);
-            return Ok(Some(msg));
-        }
-
-        // Need to read more data
-        let mut temp_buf = [0u8; 4096];
+        let mut guard = self.reader.lock().await;
+        let Some(reader) = guard.as_mut() else {
+            return Ok(None);
+        };
 
-        let bytes_read = if self.is_server_side {
-            let mut guard = self.server_pipe.lock().await;
-            if let Some(pipe) = guard.as_mut() {
-                match pipe.read(&mut temp_buf).await {
-                    Ok(0) => {
-                        self.connected.store(false, Ordering::Release);
-                        return Ok(None);
-                    }
-                    Ok(n) => n,
-                    Err(e) => {
-                        self.connected.store(false, Ordering::Release);
-                        return Err(TransportError::Io {
-                            message: format!("Failed to read from named pipe: {e}"),
-                        });
-                    }
+        let max = self.config.limits.max_message_size;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            // Bound the read to one byte past the limit so a peer that never
+            // sends a newline cannot grow `line` without bound.
+            let result = (&mut *reader)
+                .take(max as u64 + 1)
+                .read_line(&mut line)
+                .await;
+            match result {
+                Ok(0) => {
+                    // EOF - the other end closed the pipe
+                    self.connected.store(false, Ordering::Release);
+                    return Ok(None);
                 }
-            } else {
-                return Ok(None);
-            }
-        } else {
-            let mut guard = self.pipe.lock().await;
-            if let Some(pipe) = guard.as_mut() {
-                match pipe.read(&mut temp_buf).await {
-                    Ok(0) => {
-                        self.connected.store(false, Ordering::Release);
-                        return Ok(None);
-                    }
-                    Ok(n) => n,
-                    Err(e) => {
-                        self.connected.store(false, Ordering::Release);
-                        return Err(TransportError::Io {
-                            message: format!("Failed to read from named pipe: {e}"),
-                        });
-                    }
+                Ok(_) => {}
+                Err(e) => {
+                    self.connected.store(false, Ordering::Release);
+                    return Err(TransportError::Io {
+                        message: format!("Failed to read from named pipe: {e}"),
+                    });
                 }
-            } else {
-                return Ok(None);
-            }
-        };
-
-        // Append to line buffer
-        let chunk = std::str::from_utf8(&temp_buf[..bytes_read]).map_err(|e| {
-            TransportError::Deserialization {
-                message: format!("Invalid UTF-8 in message: {e}"),
             }
-        })?;
-        state.line_buffer.push_str(chunk);
-
-        // Reject a partial line that already exceeds the limit without a
-        // newline, so `line_buffer` cannot grow without bound across recv calls.
-        // Drop the accumulated data so a stuck oversized stream doesn't keep
-        // growing the buffer on every subsequent read.
-        if state.line_buffer.len() > self.config.max_message_size
-            && !state.line_buffer.contains('\n')
-        {
-            let size = state.line_buffer.len();
-            state.line_buffer.clear();
-            return Err(TransportError::MessageTooLarge {
-                size,
-                max: self.config.max_message_size,
-            });
-        }
 
-        // Check if we now have a complete line
-        if let Some(newline_pos) = state.line_buffer.find('\n') {
-            let line = state.line_buffer[..newline_pos].to_string();
-            state.line_buffer = state.line_buffer[newline_pos + 1..].to_string();
-
-            if line.is_empty() {
-                return Ok(None);
-            }
-
-            // Check message size
-            if line.len() > self.config.max_message_size {
+            let trimmed = line.trim_end();
+            if trimmed.len() > max {
                 return Err(TransportError::MessageTooLarge {
                     size: line.len(),
-                    max: self.config.max_message_size,
+                    max,
                 });
             }
+            if trimmed.is_empty() {
+                continue;
+            }
 
-            let msg: Message =
-                serde_json::from_str(&line).map_err(|e| TransportError::Deserialization {
-                    message: format!("Failed to deserialize message: {e}"),
-                })?;
+            let msg = codec::decode_slice(trimmed.as_bytes())?;
 
             self.messages_received.fetch_add(1, Ordering::Relaxed);
             return Ok(Some(msg));
         }
-
-        // No complete message yet, return None
-        Ok(None)
     }
 }
 
@@ -428,24 +412,51 @@ impl Transport for NamedPipeTransport {
     type Error = TransportError;
 
     async fn send(&self, msg: Message) -> Result<(), Self::Error> {
-        self.send_impl(msg).await
+        if !self.connected.load(Ordering::Acquire) {
+            return Err(TransportError::Connection {
+                message: "Named pipe not connected".to_string(),
+            });
+        }
+
+        // Serialize the message with newline delimiter
+        let mut data = serde_json::to_vec(&msg).map_err(|e| TransportError::Serialization {
+            message: format!("Failed to serialize message: {e}"),
+        })?;
+
+        self.limits.send(&msg, data.len())?;
+
+        data.push(b'\n');
+
+        let mut guard = self.writer.lock().await;
+        let Some(writer) = guard.as_mut() else {
+            return Err(TransportError::Connection {
+                message: "Named pipe not available".to_string(),
+            });
+        };
+        writer
+            .write_all(&data)
+            .await
+            .map_err(|e| TransportError::Io {
+                message: format!("Failed to write to named pipe: {e}"),
+            })?;
+        writer.flush().await.map_err(|e| TransportError::Io {
+            message: format!("Failed to flush named pipe: {e}"),
+        })?;
+
+        self.messages_sent.fetch_add(1, Ordering::Relaxed);
+        Ok(())
     }
 
     async fn recv(&self) -> Result<Option<Message>, Self::Error> {
-        self.recv_impl().await
+        self.limits.recv(self.read_message()).await
     }
 
     async fn close(&self) -> Result<(), Self::Error> {
         self.connected.store(false, Ordering::Release);
 
-        // Drop the pipe handles
-        if self.is_server_side {
-            let mut guard = self.server_pipe.lock().await;
-            *guard = None;
-        } else {
-            let mut guard = self.pipe.lock().await;
-            *guard = None;
-        }
+        // Dropping both halves closes the pipe handle
+        *self.writer.lock().await = None;
+        *self.reader.lock().await = None;
 
         Ok(())
     }
@@ -455,37 +466,59 @@ impl Transport for NamedPipeTransport {
     }
 
     fn metadata(&self) -> TransportMetadata {
-        TransportMetadata::new("named-pipe").remote_addr(self.config.name.clone())
+        self.metadata.clone()
     }
 }
 
 /// Windows named pipe server (listener).
 ///
-/// Accepts incoming connections on a named pipe.
+/// The Windows counterpart of `UnixListener`: bind once, then [`accept`](TransportListener::accept) each client. One
+/// pipe instance is always listening, so clients can connect concurrently.
 #[cfg(all(windows, feature = "tokio-runtime"))]
 pub struct NamedPipeServer {
     config: NamedPipeConfig,
+    /// The instance waiting for the next client.
+    listening: AsyncMutex<Option<pipe::NamedPipeServer>>,
     running: AtomicBool,
 }
 
 #[cfg(all(windows, feature = "tokio-runtime"))]
 impl NamedPipeServer {
-    /// Create a new named pipe server.
-    pub fn new(name: impl Into<String>) -> Result<Self, TransportError> {
-        let config = NamedPipeConfig::new(name);
-        Self::with_config(config)
+    /// Bind to a named pipe.
+    ///
+    /// Fails if another process already serves a pipe of that name.
+    pub fn bind(name: impl Into<String>) -> Result<Self, TransportError> {
+        Self::bind_with_config(NamedPipeConfig::new(name))
     }
 
-    /// Create a new named pipe server with custom configuration.
-    pub fn with_config(config: NamedPipeConfig) -> Result<Self, TransportError> {
-        tracing::info!(name = %config.name, "Named pipe server created");
+    /// Bind with custom configuration.
+    pub fn bind_with_config(config: NamedPipeConfig) -> Result<Self, TransportError> {
+        config.validate()?;
+        let first = create_instance(&config, true)?;
+
+        tracing::info!(name = %config.name, "Named pipe server bound");
 
         Ok(Self {
             config,
+            listening: AsyncMutex::new(Some(first)),
             running: AtomicBool::new(true),
         })
     }
 
+    /// Create a new named pipe server.
+    ///
+    /// Equivalent to [`bind`](Self::bind).
+    pub fn new(name: impl Into<String>) -> Result<Self, TransportError> {
+        Self::bind(name)
+    }
+
+    /// Create a new named pipe server with custom configuration.
+    ///
+    /// Equivalent to [`bind_with_config`](Self::bind_with_config).
+    pub fn with_config(config: NamedPipeConfig) -> Result<Self, TransportError> {
+        Self::bind_with_config(config)
+    }
+
     /// Get the pipe name.
     pub fn name(&self) -> &str {
         &self.config.name
@@ -496,50 +529,91 @@ impl NamedPipeServer {
         self.running.load(Ordering::Acquire)
     }
 
-    /// Stop the server.
-    pub fn stop(&self) {
+    /// Stop the server and close its listening instance.
+    ///
+    /// Connections already accepted are unaffected.
+    pub async fn stop(&self) {
         self.running.store(false, Ordering::Release);
+        *self.listening.lock().await = None;
     }
 }
 
+/// Create a server instance of the configured pipe.
+#[cfg(all(windows, feature = "tokio-runtime"))]
+fn create_instance(
+    config: &NamedPipeConfig,
+    first: bool,
+) -> Result<pipe::NamedPipeServer, TransportError> {
+    let mut options = pipe::ServerOptions::new();
+    options
+        .first_pipe_instance(first)
+        .max_instances(config.max_instances as usize)
+        .in_buffer_size(u32::try_from(config.in_buffer_size).unwrap_or(u32::MAX))
+        .out_buffer_size(u32::try_from(config.out_buffer_size).unwrap_or(u32::MAX))
+        .reject_remote_clients(config.reject_remote_clients);
+
+    let created = match &config.security_descriptor {
+        Some(sddl) => security::create(&options, &config.name, sddl),
+        None => options.create(&config.name),
+    };
+    created.map_err(|e| TransportError::Connection {
+        message: format!("Failed to create named pipe '{}': {}", config.name, e),
+    })
+}
+
 #[cfg(all(windows, feature = "tokio-runtime"))]
 impl TransportListener for NamedPipeServer {
     type Transport = NamedPipeTransport;
     type Error = TransportError;
 
     async fn accept(&self) -> Result<Self::Transport, Self::Error> {
-        use tokio::net::windows::named_pipe::ServerOptions;
-
         if !self.running.load(Ordering::Acquire) {
             return Err(TransportError::Connection {
                 message: "Server not running".to_string(),
             });
         }
 
-        // Create a new pipe instance for this connection
-        let pipe = ServerOptions::new()
-            .first_pipe_instance(false)
-            .max_instances(self.config.max_instances as usize)
-            .in_buffer_size(self.config.in_buffer_size as u32)
-            .out_buffer_size(self.config.out_buffer_size as u32)
-            .create(&self.config.name)
-            .map_err(|e| TransportError::Connection {
-                message: format!("Failed to create named pipe '{}': {}", self.config.name, e),
-            })?;
+        let mut guard = self.listening.lock().await;
+        let listening = match &mut *guard {
+            Some(listening) => listening,
+            slot @ None => slot.insert(create_instance(&self.config, false)?),
+        };
 
-        // Wait for a client to connect
-        pipe.connect()
-            .await
-            .map_err(|e| TransportError::Connection {
+        // Wait for a client. The instance stays in place if this future is
+        // dropped, so `accept` is cancel-safe.
+        if let Err(e) = listening.connect().await {
+            // The instance may be unusable (e.g. the client already left);
+            // replace it so the next accept starts fresh.
+            *guard = None;
+            return Err(TransportError::Connection {
                 message: format!(
                     "Failed to accept connection on '{}': {}",
                     self.config.name, e
                 ),
-            })?;
+            });
+        }
+
+        // Put a fresh instance in place before handing this one out, so the
+        // pipe name never lacks a listening instance.
+        // If that fails, the next accept retries the creation.
+        let next = create_instance(&self.config, false)
+            .inspect_err(|e| {
+                tracing::warn!(name = %self.config.name, error = %e, "Failed to create next named pipe instance");
+            })
+            .ok();
+        let Some(connected) = std::mem::replace(&mut *guard, next) else {
+            return Err(TransportError::Connection {
+                message: "Listener has been stopped".to_string(),
+            });
+        };
 
         tracing::debug!(name = %self.config.name, "Accepted named pipe connection");
 
-        Ok(NamedPipeTransport::from_server(self.config.clone(), pipe))
+        Ok(NamedPipeTransport::from_pipe(
+            self.config.clone(),
+            connected,
+            "server",
+        ))
     }
 
     fn local_addr(&self) -> Option<String> {
@@ -547,6 +621,74 @@ impl TransportListener for NamedPipeServer {
     }
 }
 
+/// Security descriptors for server pipe instances.
+#[cfg(all(windows, feature = "tokio-runtime"))]
+#[allow(unsafe_code)]
+mod security {
+    use std::ffi::{OsStr, c_void};
+    use std::io;
+    use std::os::windows::ffi::OsStrExt;
+    use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+    use windows_sys::Win32::Foundation::LocalFree;
+    use windows_sys::Win32::Security::Authorization::{
+        ConvertStringSecurityDescriptorToSecurityDescriptorW, SDDL_REVISION_1,
+    };
+    use windows_sys::Win32::Security::{PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES};
+
+    /// Create a pipe instance whose security descriptor is given in SDDL.
+    pub(super) fn create(
+        options: &ServerOptions,
+        name: &str,
+        sddl: &str,
+    ) -> io::Result<NamedPipeServer> {
+        let wide: Vec<u16> = OsStr::new(sddl)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        let mut descriptor: PSECURITY_DESCRIPTOR = std::ptr::null_mut();
+
+        // SAFETY: `wide` is a NUL-terminated UTF-16 string that outlives the
+        // call, and `descriptor` is a valid out pointer. The size out pointer
+        // is optional.
+        let converted = unsafe {
+            ConvertStringSecurityDescriptorToSecurityDescriptorW(
+                wide.as_ptr(),
+                SDDL_REVISION_1,
+                &raw mut descriptor,
+                std::ptr::null_mut(),
+            )
+        };
+        if converted == 0 {
+            let e = io::Error::last_os_error();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid security descriptor '{sddl}': {e}"),
+            ));
+        }
+
+        let mut attributes = SECURITY_ATTRIBUTES {
+            nLength: u32::try_from(size_of::<SECURITY_ATTRIBUTES>()).unwrap_or(u32::MAX),
+            lpSecurityDescriptor: descriptor,
+            bInheritHandle: 0,
+        };
+
+        // SAFETY: `attributes` is a valid `SECURITY_ATTRIBUTES` whose
+        // descriptor stays allocated until after the call returns.
+        let created = unsafe {
+            options
+                .create_with_security_attributes_raw(name, (&raw mut attributes).cast::<c_void>())
+        };
+
+        // SAFETY: `descriptor` was allocated with `LocalAlloc` by the
+        // conversion above and is not used after this point.
+        unsafe {
+            LocalFree(descriptor);
+        }
+
+        created
+    }
+}
+
 /// Builder for Windows named pipe transport.
 pub struct NamedPipeBuilder {
     config: NamedPipeConfig,
@@ -581,6 +723,41 @@ impl NamedPipeBuilder {
         self
     }
 
+    /// Set the maximum message size.
+    #[must_use]
+    pub const fn max_message_size(mut self, size: usize) -> Self {
+        self.config.limits.max_message_size = size;
+        self
+    }
+
+    /// Set the message size, in-flight, and read timeout limits.
+    #[must_use]
+    pub const fn limits(mut self, limits: TransportLimits) -> Self {
+        self.config.limits = limits;
+        self
+    }
+
+    /// Set whether the server refuses remote clients (default: `true`).
+    #[must_use]
+    pub const fn reject_remote_clients(mut self, reject: bool) -> Self {
+        self.config.reject_remote_clients = reject;
+        self
+    }
+
+    /// Set the security descriptor of server pipe instances, in SDDL form.
+    #[must_use]
+    pub fn security_descriptor(mut self, sddl: impl Into<String>) -> Self {
+        self.config.security_descriptor = Some(sddl.into());
+        self
+    }
+
+    /// Set how long a client waits for a free pipe instance.
+    #[must_use]
+    pub const fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = timeout;
+        self
+    }
+
     /// Connect to the named pipe server.
     #[cfg(all(windows, feature = "tokio-runtime"))]
     pub async fn connect(self) -> Result<NamedPipeTransport, TransportError> {
@@ -598,7 +775,15 @@ impl NamedPipeBuilder {
     /// Create a server on the named pipe.
     #[cfg(all(windows, feature = "tokio-runtime"))]
     pub fn server(self) -> Result<NamedPipeServer, TransportError> {
-        NamedPipeServer::with_config(self.config)
+        NamedPipeServer::bind_with_config(self.config)
+    }
+
+    /// Bind a server on the named pipe.
+    ///
+    /// Equivalent to [`server`](Self::server); named like
+    /// `UnixTransportBuilder::listen`.
+    pub fn listen(self) -> Result<NamedPipeServer, TransportError> {
+        self.server()
     }
 
     /// Create a server on the named pipe (stub for non-Windows).
@@ -669,6 +854,20 @@ pub struct NamedPipeServer {
 
 #[cfg(not(all(windows, feature = "tokio-runtime")))]
 impl NamedPipeServer {
+    /// Bind to a named pipe.
+    pub fn bind(_name: impl Into<String>) -> Result<Self, TransportError> {
+        Err(TransportError::Connection {
+            message: "Named pipe transport is only available on Windows".to_string(),
+        })
+    }
+
+    /// Bind with custom configuration.
+    pub fn bind_with_config(_config: NamedPipeConfig) -> Result<Self, TransportError> {
+        Err(TransportError::Connection {
+            message: "Named pipe transport is only available on Windows".to_string(),
+        })
+    }
+
     /// Create a new named pipe server.
     pub fn new(_name: impl Into<String>) -> Result<Self, TransportError> {
         Err(TransportError::Connection {
@@ -740,4 +939,89 @@ mod tests {
         assert_eq!(builder.config.max_instances, 5);
         assert_eq!(builder.config.in_buffer_size, 128 * 1024);
     }
+
+    #[test]
+    fn test_config_security_and_validation() {
+        let config = NamedPipeConfig::new("secure");
+        assert!(config.reject_remote_clients);
+        assert!(config.security_descriptor.is_none());
+        assert!(config.validate().is_ok());
+
+        let config = config
+            .with_reject_remote_clients(false)
+            .with_security_descriptor("D:(A;;GA;;;OW)")
+            .with_connect_timeout(Duration::from_millis(250));
+        assert!(!config.reject_remote_clients);
+        assert_eq!(
+            config.security_descriptor.as_deref(),
+            Some("D:(A;;GA;;;OW)")
+        );
+        assert_eq!(config.connect_timeout, Duration::from_millis(250));
+
+        assert!(config.clone().with_max_instances(0).validate().is_err());
+        assert!(
+            config
+                .clone()
+                .with_max_instances(MAX_PIPE_INSTANCES + 1)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            config
+                .with_max_instances(MAX_PIPE_INSTANCES)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[cfg(not(all(windows, feature = "tokio-runtime")))]
+    #[test]
+    fn test_bind_unsupported_off_windows() {
+        assert!(NamedPipeServer::bind("mcp-test").is_err());
+    }
+
+    /// Several clients connected at once are each served by their own
+    /// accepted transport.
+    #[cfg(all(windows, feature = "tokio-runtime"))]
+    #[tokio::test]
+    async fn test_concurrent_clients() -> Result<(), Box<dyn std::error::Error>> {
+        use mcpkit_core::protocol::{Request, RequestId};
+
+        let name = format!(r"\\.\pipe\mcp-test-{}", std::process::id());
+        let listener = NamedPipeServer::bind(&name)?;
+        assert_eq!(listener.local_addr().as_deref(), Some(name.as_str()));
+
+        // A second server on the same name is refused.
+        assert!(NamedPipeServer::bind(&name).is_err());
+
+        let server = tokio::spawn(async move {
+            for _ in 0..3 {
+                let transport = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    while let Ok(Some(msg)) = transport.recv().await {
+                        transport.send(msg).await.unwrap();
+                    }
+                });
+            }
+        });
+
+        let mut clients = Vec::new();
+        for _ in 0..3 {
+            clients.push(NamedPipeTransport::connect(name.clone()).await?);
+        }
+        for (id, client) in (1u64..).zip(&clients) {
+            client
+                .send(Message::Request(Request::new("test/echo", id)))
+                .await?;
+        }
+        for (id, client) in (1u64..).zip(&clients) {
+            let Some(Message::Request(echoed)) = client.recv().await? else {
+                panic!("expected an echoed request");
+            };
+            assert_eq!(echoed.id, RequestId::from(id));
+        }
+
+        server.await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,163 @@
+//! Round trips between `GrpcTransport` clients and a `GrpcServer` driven
+//! through `TransportListener`.
+//!
+//! The TLS test reuses the `mcp.test` fixtures from the WebSocket tests.
+
+#![cfg(feature = "grpc")]
+
+use mcpkit_core::protocol::{Message, Request};
+use mcpkit_transport::grpc::{GrpcConfig, GrpcError, GrpcServer, GrpcServerConfig, GrpcTransport};
+use mcpkit_transport::traits::{Transport, TransportListener};
+use std::sync::Arc;
+use std::time::Duration;
+
+const CA: &[u8] = include_bytes!("fixtures/tls/ca.pem");
+const CERT: &[u8] = include_bytes!("fixtures/tls/server.pem");
+const KEY: &[u8] = include_bytes!("fixtures/tls/server.key");
+
+/// Bind and start a server on an ephemeral port; returns it and its address.
+async fn start(config: GrpcServerConfig) -> (Arc<GrpcServer>, String) {
+    let server = Arc::new(GrpcServer::bind(config).await.expect("bind"));
+    let addr = TransportListener::local_addr(server.as_ref()).expect("bound");
+    Arc::clone(&server).start().await.expect("start");
+    (server, addr)
+}
+
+/// Accept through the generic listener interface, as `serve` loops do.
+async fn accept<L: TransportListener>(listener: &L) -> Result<L::Transport, L::Error> {
+    tokio::time::timeout(Duration::from_secs(5), listener.accept())
+        .await
+        .expect("timely accept")
+}
+
+async fn connect(config: GrpcConfig) -> GrpcTransport {
+    GrpcTransport::connect(config.connect_timeout(Duration::from_secs(5)))
+        .await
+        .expect("connect")
+}
+
+async fn recv(transport: &GrpcTransport) -> Option<Message> {
+    tokio::time::timeout(Duration::from_secs(5), transport.recv())
+        .await
+        .expect("timely recv")
+        .expect("recv")
+}
+
+async fn assert_echo(client: &GrpcTransport, server_side: &GrpcTransport, id: u64) {
+    client
+        .send(Message::Request(Request::new("ping", id)))
+        .await
+        .expect("send");
+    let received = recv(server_side).await.expect("message");
+    assert_eq!(received.method(), Some("ping"));
+    server_side.send(received).await.expect("echo");
+    let echoed = recv(client).await.expect("echoed");
+    assert_eq!(echoed.method(), Some("ping"));
+}
+
+#[tokio::test]
+async fn each_stream_becomes_a_transport() {
+    let (server, addr) = start(GrpcServerConfig::new("127.0.0.1:0")).await;
+    let url = format!("http://{addr}");
+
+    let first = connect(GrpcConfig::new(&url)).await;
+    let first_server = accept(server.as_ref()).await.expect("accept first");
+    let second = connect(GrpcConfig::new(&url)).await;
+    let second_server = accept(server.as_ref()).await.expect("accept second");
+
+    assert_echo(&second, &second_server, 2).await;
+    assert_echo(&first, &first_server, 1).await;
+
+    // Closing the client ends the server-side transport.
+    first.close().await.expect("close");
+    assert!(recv(&first_server).await.is_none());
+    assert_echo(&second, &second_server, 3).await;
+
+    server.stop();
+}
+
+#[tokio::test]
+async fn stop_ends_streams_and_accept() {
+    let (server, addr) = start(GrpcServerConfig::new("127.0.0.1:0")).await;
+    let client = connect(GrpcConfig::new(format!("http://{addr}"))).await;
+    let server_side = accept(server.as_ref()).await.expect("accept");
+
+    let pending = {
+        let server = Arc::clone(&server);
+        tokio::spawn(async move { server.accept().await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    server.stop();
+
+    assert!(matches!(
+        pending.await.expect("task"),
+        Err(GrpcError::Closed)
+    ));
+    assert!(matches!(
+        accept(server.as_ref()).await,
+        Err(GrpcError::Closed)
+    ));
+    assert!(recv(&server_side).await.is_none());
+    assert!(recv(&client).await.is_none());
+}
+
+#[tokio::test]
+async fn session_limit_rejects_extra_streams() {
+    let (server, addr) = start(GrpcServerConfig::new("127.0.0.1:0").max_connections(1)).await;
+    let url = format!("http://{addr}");
+
+    let first = connect(GrpcConfig::new(&url)).await;
+    let first_server = accept(server.as_ref()).await.expect("accept");
+
+    let err = GrpcTransport::connect(GrpcConfig::new(&url))
+        .await
+        .err()
+        .expect("second session must be rejected");
+    assert!(
+        matches!(&err, GrpcError::Status(status) if status.code() == mcpkit_transport::grpc::tonic::Code::ResourceExhausted),
+        "{err}"
+    );
+
+    // Ending the first session frees its slot.
+    first_server.close().await.expect("close");
+    drop(first_server);
+    assert!(recv(&first).await.is_none());
+    let mut retry = None;
+    for _ in 0..50 {
+        if let Ok(transport) = GrpcTransport::connect(GrpcConfig::new(&url)).await {
+            retry = Some(transport);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let retry = retry.expect("slot released");
+    let retry_server = accept(server.as_ref()).await.expect("accept");
+    assert_echo(&retry, &retry_server, 4).await;
+
+    server.stop();
+}
+
+#[tokio::test]
+async fn tls_round_trip() {
+    let config = GrpcServerConfig::new("127.0.0.1:0").with_tls_identity(CERT, KEY);
+    let (server, addr) = start(config).await;
+
+    let client = connect(
+        GrpcConfig::new(format!("https://{addr}"))
+            .with_tls_ca_certificate(CA)
+            .with_tls_domain("mcp.test"),
+    )
+    .await;
+    let server_side = accept(server.as_ref()).await.expect("accept");
+    assert_echo(&client, &server_side, 5).await;
+
+    server.stop();
+}
+
+#[tokio::test]
+async fn tls_without_identity_fails_to_start() {
+    let server = Arc::new(GrpcServer::new(
+        GrpcServerConfig::new("127.0.0.1:0").with_tls(),
+    ));
+    assert!(server.start().await.is_err());
+}
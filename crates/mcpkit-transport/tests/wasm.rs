@@ -0,0 +1,138 @@
+//! Browser transport tests, run with `wasm-bindgen-test`:
+//!
+//! ```text
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test -p mcpkit-transport --no-default-features --features wasm \
+//!     --target wasm32-unknown-unknown --test wasm
+//! ```
+//!
+//! They run under Node.js by default. The fetch round trips use `data:` URLs,
+//! which answer any request with their own contents, so no server is needed.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use std::time::Duration;
+
+use mcpkit_core::protocol::{Message, Notification, Request};
+use mcpkit_transport::TransportError;
+use mcpkit_transport::http::HttpTransportConfig;
+use mcpkit_transport::runtime;
+use mcpkit_transport::traits::Transport;
+use mcpkit_transport::wasm::{BrowserWebSocketTransport, FetchTransport};
+use mcpkit_transport::websocket::WebSocketConfig;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// A `data:` URL serving `body` with the given content type.
+fn data_url(content_type: &str, body: &str) -> String {
+    let encoded: String = body
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect();
+    format!("data:{content_type},{encoded}")
+}
+
+#[wasm_bindgen_test]
+async fn timers_run_on_the_event_loop() {
+    runtime::sleep(Duration::from_millis(5)).await;
+    let pending = futures::future::pending::<()>();
+    assert!(
+        runtime::timeout(Duration::from_millis(5), pending)
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        runtime::timeout(Duration::from_secs(1), async { 7 }).await,
+        Ok(7)
+    );
+}
+
+#[wasm_bindgen_test]
+async fn json_response_is_received() {
+    let url = data_url(
+        "application/json",
+        r#"{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18"}}"#,
+    );
+    let transport = FetchTransport::new(HttpTransportConfig::new(url));
+    transport
+        .send(Message::Request(Request::new("initialize", 1u64)))
+        .await
+        .expect("POST succeeds");
+
+    let Ok(Some(Message::Response(response))) = transport.recv().await else {
+        panic!("expected the initialize response");
+    };
+    assert_eq!(response.id, 1u64.into());
+    assert_eq!(
+        transport.protocol_version().await.as_deref(),
+        Some("2025-06-18")
+    );
+    assert_eq!(transport.messages_sent(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn sse_response_is_received() {
+    let url = data_url(
+        "text/event-stream",
+        "event: message\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n\
+         data: {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{}}\n\n",
+    );
+    let transport = FetchTransport::new(HttpTransportConfig::new(url).without_sse_stream());
+    transport
+        .send(Message::Request(Request::new("tools/list", 2u64)))
+        .await
+        .expect("POST succeeds");
+
+    assert!(matches!(
+        transport.recv().await,
+        Ok(Some(Message::Notification(n))) if n.method == "notifications/progress"
+    ));
+    assert!(matches!(
+        transport.recv().await,
+        Ok(Some(Message::Response(r))) if r.id == 2u64.into()
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn closed_fetch_transport_stops() {
+    let transport = FetchTransport::new(HttpTransportConfig::new("http://127.0.0.1:9/mcp"));
+    assert!(transport.is_connected());
+    assert_eq!(transport.metadata().transport_type, "http");
+
+    transport.close().await.expect("close");
+    assert!(!transport.is_connected());
+    assert!(matches!(transport.recv().await, Ok(None)));
+    assert!(matches!(
+        transport
+            .send(Message::Notification(Notification::new(
+                "notifications/initialized"
+            )))
+            .await,
+        Err(TransportError::NotConnected)
+    ));
+}
+
+#[wasm_bindgen_test]
+async fn unreachable_server_fails_the_send() {
+    let transport = FetchTransport::new(HttpTransportConfig::new("http://127.0.0.1:9/mcp"));
+    let result = transport
+        .send(Message::Request(Request::new("ping", 1u64)))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(transport.messages_sent(), 0);
+}
+
+#[wasm_bindgen_test]
+async fn websocket_connect_failure_is_reported() {
+    let refused =
+        WebSocketConfig::new("ws://127.0.0.1:9/mcp").with_connect_timeout(Duration::from_secs(5));
+    assert!(BrowserWebSocketTransport::connect(refused).await.is_err());
+
+    let invalid = WebSocketConfig::new("not a url");
+    assert!(matches!(
+        BrowserWebSocketTransport::connect(invalid).await,
+        Err(TransportError::Connection { .. })
+    ));
+}
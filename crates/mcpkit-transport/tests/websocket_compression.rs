@@ -0,0 +1,108 @@
+//! Compression negotiated between `WebSocketTransport` and
+//! `WebSocketListener`.
+
+#![cfg(feature = "websocket")]
+
+use futures::{SinkExt, StreamExt};
+use mcpkit_core::protocol::{Message, Request};
+use mcpkit_transport::compression::{CompressionConfig, Encoding};
+use mcpkit_transport::traits::Transport;
+use mcpkit_transport::websocket::{
+    WebSocketConfig, WebSocketListener, WebSocketServerConfig, WebSocketTransport,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Start a listener on an ephemeral port; returns it and its URL.
+async fn listener(config: WebSocketServerConfig) -> (Arc<WebSocketListener>, String) {
+    let listener = Arc::new(WebSocketListener::with_config("127.0.0.1:0", config));
+    let running = Arc::clone(&listener);
+    tokio::spawn(async move { running.start().await });
+
+    let addr = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(addr) = listener.local_addr() {
+                return addr;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("listener bound");
+    (listener, format!("ws://{addr}/mcp"))
+}
+
+fn client_config(url: &str) -> WebSocketConfig {
+    WebSocketConfig::new(url)
+        .with_connect_timeout(Duration::from_secs(5))
+        .without_auto_reconnect()
+}
+
+fn large_request() -> Message {
+    let params = serde_json::json!({ "query": "compressible ".repeat(1000) });
+    Message::Request(Request::with_params("tools/call", 1, params))
+}
+
+/// Accept one connection and echo the first message through its codec.
+fn echo_once(listener: Arc<WebSocketListener>) -> tokio::task::JoinHandle<Option<Encoding>> {
+    tokio::spawn(async move {
+        let mut conn = listener.accept().await.expect("accept");
+        let frame = conn.stream.next().await.expect("frame").expect("read");
+        let msg = conn.codec.decode(frame).expect("decode").expect("message");
+        let json = serde_json::to_string(&msg).expect("serialize");
+        let reply = conn.codec.encode(json).expect("encode");
+        conn.stream.send(reply).await.expect("echo");
+        conn.codec.encoding()
+    })
+}
+
+async fn round_trip(client: &WebSocketTransport) {
+    client.send(large_request()).await.expect("send");
+    let echoed = tokio::time::timeout(Duration::from_secs(5), client.recv())
+        .await
+        .expect("timely")
+        .expect("recv")
+        .expect("message");
+    assert_eq!(echoed.method(), Some("tools/call"));
+}
+
+#[tokio::test]
+async fn negotiated_compression_shrinks_large_messages() {
+    let server_compression = CompressionConfig::new().with_encoding(Encoding::Deflate);
+    let (listener, url) =
+        listener(WebSocketServerConfig::new().with_compression(server_compression)).await;
+    let server = echo_once(Arc::clone(&listener));
+
+    let client =
+        WebSocketTransport::connect(client_config(&url).with_compression(CompressionConfig::new()))
+            .await
+            .expect("connect");
+    round_trip(&client).await;
+
+    assert_eq!(server.await.expect("server task"), Some(Encoding::Deflate));
+    let client_stats = client.compression_stats();
+    assert_eq!(client_stats.messages_compressed, 1);
+    assert_eq!(client_stats.messages_decompressed, 1);
+    assert!(client_stats.bytes_saved() > 10_000, "{client_stats:?}");
+    let server_stats = listener.compression_stats();
+    assert_eq!(server_stats.messages_compressed, 1);
+    assert_eq!(server_stats.messages_decompressed, 1);
+    client.close().await.expect("close");
+}
+
+#[tokio::test]
+async fn compression_is_off_unless_both_sides_enable_it() {
+    // The client offers compression, but the listener doesn't accept it.
+    let (listener, url) = listener(WebSocketServerConfig::new()).await;
+    let server = echo_once(Arc::clone(&listener));
+
+    let client =
+        WebSocketTransport::connect(client_config(&url).with_compression(CompressionConfig::new()))
+            .await
+            .expect("connect");
+    round_trip(&client).await;
+
+    assert_eq!(server.await.expect("server task"), None);
+    assert_eq!(client.compression_stats().bytes_saved(), 0);
+    client.close().await.expect("close");
+}
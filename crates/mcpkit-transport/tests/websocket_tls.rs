@@ -0,0 +1,105 @@
+//! `wss://` round trips between `WebSocketTransport` and a TLS-terminating
+//! `WebSocketListener`.
+//!
+//! The fixtures are a self-signed CA and a leaf certificate for `mcp.test`
+//! and `localhost`, generated with `openssl`. The client connects by IP
+//! address, so it must override SNI to verify the server.
+
+#![cfg(feature = "tls")]
+
+use futures::{SinkExt, StreamExt};
+use mcpkit_core::protocol::{Message, Request};
+use mcpkit_transport::traits::Transport;
+use mcpkit_transport::websocket::{
+    WebSocketConfig, WebSocketListener, WebSocketTlsConfig, WebSocketTransport,
+    server_config_from_pem,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+const CA: &[u8] = include_bytes!("fixtures/tls/ca.pem");
+const CERT: &[u8] = include_bytes!("fixtures/tls/server.pem");
+const KEY: &[u8] = include_bytes!("fixtures/tls/server.key");
+
+/// Start a TLS listener on an ephemeral port; returns it and its URL.
+async fn tls_listener() -> (Arc<WebSocketListener>, String) {
+    let server_config = server_config_from_pem(CERT, KEY).expect("server config");
+    let listener = Arc::new(
+        WebSocketListener::bind_tls("127.0.0.1:0", server_config)
+            .await
+            .expect("bind"),
+    );
+    assert!(listener.is_tls());
+    let url = format!("wss://{}/mcp", listener.local_addr().expect("bound"));
+
+    let running = Arc::clone(&listener);
+    tokio::spawn(async move { running.start().await });
+    (listener, url)
+}
+
+fn client_config(url: &str, tls: WebSocketTlsConfig) -> WebSocketConfig {
+    WebSocketConfig::new(url)
+        .with_tls(tls)
+        .with_connect_timeout(Duration::from_secs(5))
+        .without_auto_reconnect()
+}
+
+#[tokio::test]
+async fn wss_round_trip_with_custom_root_and_sni() {
+    let (listener, url) = tls_listener().await;
+
+    let server = tokio::spawn(async move {
+        let mut conn = listener.accept().await.expect("accept");
+        assert!(conn.stream.get_ref().is_tls());
+        let frame = conn.stream.next().await.expect("frame").expect("read");
+        conn.stream.send(frame).await.expect("echo");
+    });
+
+    let tls = WebSocketTlsConfig::new()
+        .with_root_certificates_pem(CA)
+        .expect("CA")
+        .without_webpki_roots()
+        .with_server_name("mcp.test");
+    let client = WebSocketTransport::connect(client_config(&url, tls))
+        .await
+        .expect("connect");
+
+    client
+        .send(Message::Request(Request::new("ping", 1)))
+        .await
+        .expect("send");
+    let echoed = tokio::time::timeout(Duration::from_secs(5), client.recv())
+        .await
+        .expect("timely")
+        .expect("recv")
+        .expect("message");
+    assert_eq!(echoed.method(), Some("ping"));
+
+    server.await.expect("server task");
+    client.close().await.expect("close");
+}
+
+#[tokio::test]
+async fn wss_rejects_untrusted_certificate() {
+    let (_listener, url) = tls_listener().await;
+
+    // The bundled webpki roots don't include the test CA.
+    let tls = WebSocketTlsConfig::new().with_server_name("mcp.test");
+    let err = WebSocketTransport::connect(client_config(&url, tls))
+        .await
+        .err()
+        .expect("untrusted certificate must be rejected");
+    assert!(err.to_string().contains("TLS handshake failed"), "{err}");
+}
+
+#[tokio::test]
+async fn wss_rejects_mismatched_server_name() {
+    let (_listener, url) = tls_listener().await;
+
+    let tls = WebSocketTlsConfig::new()
+        .with_root_certificates_pem(CA)
+        .expect("CA")
+        .with_server_name("other.test");
+    let result = WebSocketTransport::connect(client_config(&url, tls)).await;
+    assert!(result.is_err());
+}
@@ -1,23 +1,18 @@
 //! HTTP handlers for MCP requests using Warp.
 
+use crate::SUPPORTED_VERSIONS;
 use crate::state::{HasServerInfo, McpState};
-use crate::{SUPPORTED_VERSIONS, is_supported_version};
 use futures::StreamExt;
 use mcpkit_core::auth::VerifiedUser;
-use mcpkit_core::capability::ClientCapabilities;
 use mcpkit_core::protocol::Message;
-use mcpkit_core::protocol_version::ProtocolVersion;
-use mcpkit_server::capability::tasks::{TaskManager, route_task_store};
-use mcpkit_server::context::{Context, NoOpPeer};
-use mcpkit_server::{
-    AugmentedTaskOutcome, PromptHandler, ResourceHandler, ServerHandler, ToolHandler,
-    begin_augmented_task, route_completion, route_logging, route_prompts, route_resources,
-    route_tools,
-};
+use mcpkit_core::types::task::TaskId;
+use mcpkit_server::health::{HealthProbes, ProbeResponse, session_health};
+use mcpkit_server::service::{ServiceOutcome, ServiceRejection, ServiceRequest};
+use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
 use std::convert::Infallible;
 use std::sync::Arc;
 use tokio_stream::wrappers::BroadcastStream;
-use tracing::{debug, info, warn};
+use tracing::{info, warn};
 use warp::Filter;
 use warp::http::StatusCode;
 use warp::sse::Event;
@@ -25,14 +20,18 @@ use warp::sse::Event;
 /// Handle MCP POST requests.
 ///
 /// This is the core handler function that processes JSON-RPC messages.
+/// `accept` is the request's `Accept` header; it picks a JSON or an SSE
+/// response (see
+/// [`ResponseMode::negotiate`](mcpkit_server::service::ResponseMode::negotiate)).
 pub async fn handle_mcp_post<H>(
     state: Arc<McpState<H>>,
     version: Option<String>,
     session_id: Option<String>,
     origin: Option<String>,
+    accept: Option<String>,
     user: Option<VerifiedUser>,
     body: String,
-) -> Result<impl warp::Reply, Infallible>
+) -> Result<warp::reply::Response, Infallible>
 where
     H: ServerHandler
         + ToolHandler
@@ -43,340 +42,63 @@ where
         + Sync
         + 'static,
 {
-    // Reject disallowed Origins (DNS-rebinding protection) before any work.
-    if !state.origin_validator.is_allowed(origin.as_deref()) {
-        warn!(
-            origin = origin.as_deref().unwrap_or("none"),
-            "Rejected: origin not allowed"
-        );
-        let error_body = serde_json::json!({
-            "error": { "code": -32600, "message": "origin not allowed" }
-        });
-        return Ok(warp::reply::with_status(
-            warp::reply::json(&error_body),
-            StatusCode::FORBIDDEN,
-        ));
-    }
+    use warp::Reply;
 
-    // Validate protocol version
-    if !is_supported_version(version.as_deref()) {
-        let provided = version.as_deref().unwrap_or("none");
-        warn!(version = provided, "Unsupported protocol version");
-        let error_body = serde_json::json!({
-            "error": {
-                "code": -32600,
-                "message": format!(
-                    "Unsupported protocol version: {} (supported: {})",
-                    provided,
-                    SUPPORTED_VERSIONS.join(", ")
-                )
-            }
-        });
-        return Ok(warp::reply::with_status(
-            warp::reply::json(&error_body),
-            StatusCode::BAD_REQUEST,
-        ));
+    let request = ServiceRequest::from_bytes(body)
+        .with_origin(origin)
+        .with_protocol_version(version)
+        .with_session_id(session_id)
+        .with_accept(accept)
+        .with_user(user);
+
+    let reply = state.service().handle(request).await;
+    if let Some(body) = reply.sse_body() {
+        return Ok(
+            warp::reply::with_header(body, "content-type", "text/event-stream").into_response(),
+        );
     }
-
-    // Get or create session (binding it to the verified user, if any).
-    let session_id = match session_id {
-        Some(id) => match state.sessions.touch_verified(&id, user.as_ref()) {
-            Ok(true) => id,
-            Ok(false) => {
-                warn!(session_id = %id, "Rejected: unknown session id");
-                let error_body = serde_json::json!({
-                    "error": { "code": -32600, "message": "unknown session id" }
-                });
-                return Ok(warp::reply::with_status(
-                    warp::reply::json(&error_body),
-                    StatusCode::NOT_FOUND,
-                ));
-            }
-            Err(e) => {
-                warn!(session_id = %id, error = %e, "Rejected: session binding violation");
-                let error_body = serde_json::json!({
-                    "error": { "code": -32600, "message": e.to_string() }
-                });
-                return Ok(warp::reply::with_status(
-                    warp::reply::json(&error_body),
-                    StatusCode::FORBIDDEN,
-                ));
-            }
-        },
-        None => state.sessions.create_for_user(user),
-    };
-
-    debug!(session_id = %session_id, "Processing MCP request");
-
-    // Parse message
-    let msg: Message = match serde_json::from_str(&body) {
-        Ok(m) => m,
-        Err(e) => {
-            warn!(error = %e, "Failed to parse JSON-RPC message");
-            let error_body = serde_json::json!({
-                "error": {
-                    "code": -32700,
-                    "message": format!("Parse error: {e}")
-                }
-            });
-            return Ok(warp::reply::with_status(
-                warp::reply::json(&error_body),
-                StatusCode::BAD_REQUEST,
-            ));
-        }
-    };
-
-    // Process message
-    match msg {
-        Message::Request(request) => {
-            info!(
-                method = %request.method,
-                id = ?request.id,
-                session_id = %session_id,
-                "Handling MCP request"
-            );
-
-            // On initialize, negotiate the protocol version and record it (and
-            // the client's capabilities) on the session, so subsequent requests
-            // observe the negotiated values.
-            if request.method.as_ref() == "initialize" {
-                let (negotiated, caps) = negotiate_initialize(request.params.as_ref());
-                state.sessions.set_negotiated(&session_id, negotiated, caps);
-            }
-
-            // Resolve the session's negotiated values for the request context,
-            // falling back to defaults before initialization completes.
-            let (protocol_version, client_caps) =
-                state.sessions.negotiated(&session_id).map_or_else(
-                    || (ProtocolVersion::LATEST, ClientCapabilities::default()),
-                    |(v, c)| (v, c.unwrap_or_default()),
-                );
-            // This session's task store (per-session isolation for `tasks/*`).
-            let task_store = state.sessions.tasks(&session_id);
-
-            let response = create_response_for_request(
-                &state,
-                &request,
-                protocol_version,
-                &client_caps,
-                task_store.as_ref(),
-            )
-            .await;
-
+    let (status, body) = match reply.outcome {
+        ServiceOutcome::Response { response, .. } => {
             match serde_json::to_value(Message::Response(response)) {
-                Ok(body) => Ok(warp::reply::with_status(
-                    warp::reply::json(&body),
-                    StatusCode::OK,
-                )),
-                Err(e) => {
-                    let error_body = serde_json::json!({
-                        "error": {
-                            "code": -32603,
-                            "message": format!("Internal error: {e}")
-                        }
-                    });
-                    Ok(warp::reply::with_status(
-                        warp::reply::json(&error_body),
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                    ))
-                }
+                Ok(body) => (StatusCode::OK, body),
+                Err(e) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    error_body(-32603, format!("Internal error: {e}")),
+                ),
             }
         }
-        Message::Notification(notification) => {
-            debug!(
-                method = %notification.method,
-                session_id = %session_id,
-                "Received notification"
-            );
-            Ok(warp::reply::with_status(
-                warp::reply::json(&serde_json::json!({})),
-                StatusCode::ACCEPTED,
-            ))
-        }
-        _ => {
-            warn!("Unexpected message type received");
-            let error_body = serde_json::json!({
-                "error": {
-                    "code": -32600,
-                    "message": "Expected request or notification"
-                }
-            });
-            Ok(warp::reply::with_status(
-                warp::reply::json(&error_body),
-                StatusCode::BAD_REQUEST,
-            ))
-        }
-    }
+        ServiceOutcome::Accepted { .. } => (StatusCode::ACCEPTED, serde_json::json!({})),
+        ServiceOutcome::Rejected(rejection) => rejection_reply(&rejection),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error_body(-32603, "unexpected service outcome".to_string()),
+        ),
+    };
+    Ok(warp::reply::with_status(warp::reply::json(&body), status).into_response())
 }
 
-/// Negotiate the protocol version and extract client capabilities from an
-/// `initialize` request's params.
-///
-/// The negotiated version is the highest supported version not exceeding the
-/// client's requested version, falling back to the latest supported version
-/// when the request omits or names an unknown version.
-fn negotiate_initialize(
-    params: Option<&serde_json::Value>,
-) -> (ProtocolVersion, Option<ClientCapabilities>) {
-    let requested = params
-        .and_then(|p| p.get("protocolVersion"))
-        .and_then(serde_json::Value::as_str)
-        .unwrap_or("");
-    let version = ProtocolVersion::negotiate(requested, ProtocolVersion::ALL)
-        .unwrap_or(ProtocolVersion::LATEST);
-    let capabilities = params
-        .and_then(|p| p.get("capabilities"))
-        .and_then(|c| serde_json::from_value::<ClientCapabilities>(c.clone()).ok());
-    (version, capabilities)
+/// Status and JSON error body for a request the service refused.
+fn rejection_reply(rejection: &ServiceRejection) -> (StatusCode, serde_json::Value) {
+    let status =
+        StatusCode::from_u16(rejection.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let body = match rejection {
+        ServiceRejection::UnsupportedVersion(provided) => error_body(
+            -32600,
+            format!(
+                "Unsupported protocol version: {} (supported: {})",
+                provided,
+                SUPPORTED_VERSIONS.join(", ")
+            ),
+        ),
+        ServiceRejection::SessionNotFound(_) => error_body(-32600, "unknown session id".into()),
+        ServiceRejection::InvalidMessage(e) => error_body(-32700, format!("Parse error: {e}")),
+        other => error_body(-32600, other.to_string()),
+    };
+    (status, body)
 }
 
-/// Create a response for a request.
-async fn create_response_for_request<H>(
-    state: &McpState<H>,
-    request: &mcpkit_core::protocol::Request,
-    protocol_version: ProtocolVersion,
-    client_caps: &ClientCapabilities,
-    task_store: Option<&Arc<TaskManager>>,
-) -> mcpkit_core::protocol::Response
-where
-    H: ServerHandler + ToolHandler + ResourceHandler + PromptHandler + Send + Sync + 'static,
-{
-    use mcpkit_core::error::JsonRpcError;
-    use mcpkit_core::protocol::Response;
-
-    let method = request.method.as_ref();
-    let params = request.params.as_ref();
-
-    // Create a context for the request
-    let req_id = request.id.clone();
-    let server_caps = state.effective_capabilities();
-    let peer = NoOpPeer;
-    let ctx = Context::new(
-        &req_id,
-        None,
-        client_caps,
-        &server_caps,
-        protocol_version,
-        &peer,
-    );
-
-    match method {
-        "ping" => Response::success(request.id.clone(), serde_json::json!({})),
-        "initialize" => {
-            let init_result = serde_json::json!({
-                "protocolVersion": protocol_version.as_str(),
-                "serverInfo": state.server_info,
-                "capabilities": server_caps,
-            });
-            Response::success(request.id.clone(), init_result)
-        }
-        _ => {
-            // Task-augmented tools/call and tasks/* are served from this
-            // session's own task store (per-session isolation).
-            if let Some(store) = task_store {
-                if method == "tools/call" {
-                    match begin_augmented_task(
-                        state.handler.clone(),
-                        store,
-                        params,
-                        client_caps.clone(),
-                        server_caps.clone(),
-                        protocol_version,
-                    )
-                    .await
-                    {
-                        AugmentedTaskOutcome::Started(create_result, fut) => {
-                            tokio::spawn(fut);
-                            return Response::success(request.id.clone(), create_result);
-                        }
-                        AugmentedTaskOutcome::Rejected(e) => {
-                            return Response::error(request.id.clone(), e.into());
-                        }
-                        AugmentedTaskOutcome::NotApplicable => {}
-                    }
-                } else if let Some(result) = route_task_store(store, method, params).await {
-                    return match result {
-                        Ok(value) => Response::success(request.id.clone(), value),
-                        Err(e) => Response::error(request.id.clone(), e.into()),
-                    };
-                }
-            }
-
-            // Try routing to tools
-            if let Some(result) = route_tools(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing to resources
-            if let Some(result) = route_resources(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing to prompts
-            if let Some(result) = route_prompts(
-                state.handler.as_ref(),
-                method,
-                params,
-                &ctx,
-                state.list_page_size,
-            )
-            .await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing logging/setLevel (gated on the advertised capability)
-            if let Some(result) =
-                route_logging(state.handler.as_ref(), &server_caps, method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Try routing completion/complete (when a completion handler is set)
-            if let Some(result) =
-                route_completion(state.completion.as_deref(), method, params, &ctx).await
-            {
-                return match result {
-                    Ok(value) => Response::success(request.id.clone(), value),
-                    Err(e) => Response::error(request.id.clone(), e.into()),
-                };
-            }
-
-            // Method not found
-            Response::error(
-                request.id.clone(),
-                JsonRpcError::method_not_found(format!("Method '{method}' not found")),
-            )
-        }
-    }
+fn error_body(code: i32, message: String) -> serde_json::Value {
+    serde_json::json!({ "error": { "code": code, "message": message } })
 }
 
 /// Handle SSE connections for server-to-client streaming.
@@ -427,7 +149,11 @@ where
         (id, rx)
     };
 
-    // Create a stream of SSE events
+    state.sse_sessions.start_reaper();
+    let heartbeat = state.sse_sessions.session_config().heartbeat_interval;
+
+    // Create a stream of SSE events, ending it on shutdown so graceful
+    // shutdown is not held open by idle SSE connections.
     let stream = BroadcastStream::new(rx).filter_map(move |result| {
         let session = session_id.clone();
         async move {
@@ -445,8 +171,184 @@ where
             }
         }
     });
+    let stream = stream.take_until(state.shutdown.wait());
+
+    #[cfg(feature = "prometheus")]
+    let stream = counted_sse_stream(
+        stream,
+        state
+            .metrics
+            .as_ref()
+            .map(mcpkit_transport::McpMetrics::sse_connection),
+    );
+
+    match heartbeat {
+        Some(interval) => {
+            warp::sse::reply(warp::sse::keep_alive().interval(interval).stream(stream))
+                .into_response()
+        }
+        None => warp::sse::reply(stream).into_response(),
+    }
+}
+
+/// Handle SSE connections following a single task.
+///
+/// Mounted at `/mcp/tasks/{task_id}/events`. Streams the task's `status` and
+/// `progress` events (see
+/// [`TaskEvent`](mcpkit_server::capability::tasks::TaskEvent)) and ends after
+/// the event that makes the task terminal. Tasks are scoped to the session in
+/// `session_id`; `last_event_id` resumes after the given event.
+///
+/// Answers `404 Not Found` for an unknown session or task.
+pub fn handle_task_events<H>(
+    state: Arc<McpState<H>>,
+    task_id: String,
+    session_id: Option<String>,
+    origin: Option<String>,
+    last_event_id: Option<String>,
+    user: Option<VerifiedUser>,
+) -> warp::reply::Response
+where
+    H: Send + Sync + 'static,
+{
+    use warp::Reply;
+
+    if !state.origin_validator.is_allowed(origin.as_deref()) {
+        warn!(
+            origin = origin.as_deref().unwrap_or("none"),
+            "Rejected task SSE: origin not allowed"
+        );
+        return warp::reply::with_status("origin not allowed", StatusCode::FORBIDDEN)
+            .into_response();
+    }
+
+    let Some(session_id) = session_id else {
+        return warp::reply::with_status("missing mcp-session-id", StatusCode::BAD_REQUEST)
+            .into_response();
+    };
+    match state.sessions.touch_verified(&session_id, user.as_ref()) {
+        Ok(true) => {}
+        Ok(false) => {
+            return warp::reply::with_status("unknown session", StatusCode::NOT_FOUND)
+                .into_response();
+        }
+        Err(e) => {
+            warn!(session_id = %session_id, error = %e, "Rejected task SSE: session binding violation");
+            return warp::reply::with_status(e.to_string(), StatusCode::FORBIDDEN).into_response();
+        }
+    }
+
+    let task_id = TaskId::new(task_id);
+    let Some(tasks) = state
+        .sessions
+        .tasks(&session_id)
+        .filter(|tasks| tasks.get(&task_id).is_some())
+    else {
+        return warp::reply::with_status("unknown task", StatusCode::NOT_FOUND).into_response();
+    };
+    let after = last_event_id.and_then(|id| id.parse().ok());
+
+    let stream = tasks
+        .follow(task_id, after)
+        .map(|event| {
+            Ok::<_, Infallible>(
+                Event::default()
+                    .id(event.id.to_string())
+                    .event(event.event_type())
+                    .data(event.data().to_string()),
+            )
+        })
+        .take_until(state.shutdown.wait());
+    match state.sse_sessions.session_config().heartbeat_interval {
+        Some(interval) => {
+            warp::sse::reply(warp::sse::keep_alive().interval(interval).stream(stream))
+                .into_response()
+        }
+        None => warp::sse::reply(stream).into_response(),
+    }
+}
+
+/// Keep `connection` (counting the stream as an open SSE connection) alive
+/// until `stream` is dropped.
+#[cfg(feature = "prometheus")]
+fn counted_sse_stream<S: futures::Stream>(
+    stream: S,
+    connection: Option<mcpkit_transport::GaugeGuard>,
+) -> impl futures::Stream<Item = S::Item> {
+    stream.map(move |event| {
+        let _connection = &connection;
+        event
+    })
+}
+
+/// Serve the router's Prometheus metrics in the text exposition format.
+///
+/// Mounted at `/metrics` by [`McpRouter::with_metrics`](crate::McpRouter::with_metrics).
+/// Rejects with `404 Not Found` if the state has no metrics.
+#[cfg(feature = "prometheus")]
+pub async fn handle_metrics<H>(
+    state: Arc<McpState<H>>,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    use warp::Reply;
+
+    let Some(metrics) = &state.metrics else {
+        return Err(warp::reject::not_found());
+    };
+    metrics.set_active_sessions(state.sessions.session_count());
+    let exporter = mcpkit_transport::MetricsExporter::from_metrics(metrics);
+    match exporter.gather() {
+        Ok(body) => Ok(
+            warp::reply::with_header(body, "content-type", exporter.content_type()).into_response(),
+        ),
+        Err(e) => {
+            warn!(error = %e, "Failed to encode metrics");
+            Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+        }
+    }
+}
+
+/// Answer the liveness probe.
+///
+/// Mounted at `/healthz` by [`McpRouter::with_health_checks`](crate::McpRouter::with_health_checks).
+/// Rejects with `404 Not Found` if the state has no health probes.
+pub async fn handle_liveness<H>(
+    state: Arc<McpState<H>>,
+    query: String,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    let Some(probes) = &state.health else {
+        return Err(warp::reject::not_found());
+    };
+    Ok(probe_response(
+        probes.liveness(HealthProbes::verbose_requested(Some(&query))),
+    ))
+}
+
+/// Answer the readiness probe: the configured checks plus session health,
+/// which fails once shutdown begins.
+///
+/// Mounted at `/readyz` by [`McpRouter::with_health_checks`](crate::McpRouter::with_health_checks).
+/// Rejects with `404 Not Found` if the state has no health probes.
+pub async fn handle_readiness<H>(
+    state: Arc<McpState<H>>,
+    query: String,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    let Some(probes) = &state.health else {
+        return Err(warp::reject::not_found());
+    };
+    let sessions = session_health(state.sessions.session_count(), state.shutdown.is_shutdown());
+    let verbose = HealthProbes::verbose_requested(Some(&query));
+    Ok(probe_response(probes.readiness(sessions, verbose)))
+}
+
+fn probe_response(probe: ProbeResponse) -> warp::reply::Response {
+    use warp::Reply;
 
-    warp::sse::reply(warp::sse::keep_alive().stream(stream)).into_response()
+    let status = StatusCode::from_u16(probe.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    warp::reply::with_status(
+        warp::reply::with_header(probe.body, "content-type", probe.content_type),
+        status,
+    )
+    .into_response()
 }
 
 /// Create a filter to extract the MCP protocol version header.
@@ -469,34 +371,23 @@ pub fn with_origin() -> impl Filter<Extract = (Option<String>,), Error = warp::R
     warp::header::optional("origin")
 }
 
+/// Create a filter to extract the `Last-Event-ID` header (SSE resumption).
+#[must_use]
+pub fn with_last_event_id()
+-> impl Filter<Extract = (Option<String>,), Error = warp::Rejection> + Clone {
+    warp::header::optional("last-event-id")
+}
+
+/// Create a filter to extract the `Accept` header (JSON or SSE responses).
+#[must_use]
+pub fn with_accept() -> impl Filter<Extract = (Option<String>,), Error = warp::Rejection> + Clone {
+    warp::header::optional("accept")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn negotiate_uses_requested_supported_version() {
-        let params = serde_json::json!({
-            "protocolVersion": "2025-06-18",
-            "capabilities": {}
-        });
-        let (version, caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::V2025_06_18);
-        assert!(caps.is_some());
-    }
-
-    #[test]
-    fn negotiate_defaults_to_latest_when_absent() {
-        let (version, caps) = negotiate_initialize(None);
-        assert_eq!(version, ProtocolVersion::LATEST);
-        assert!(caps.is_none());
-    }
-
-    #[test]
-    fn negotiate_unknown_version_falls_back_to_latest() {
-        let params = serde_json::json!({ "protocolVersion": "2099-01-01" });
-        let (version, _caps) = negotiate_initialize(Some(&params));
-        assert_eq!(version, ProtocolVersion::LATEST);
-    }
     use mcpkit_core::capability::{ServerCapabilities, ServerInfo};
     use mcpkit_core::error::McpError;
     use mcpkit_core::types::{
@@ -577,6 +468,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             r#"{"jsonrpc":"2.0","method":"ping","id":1}"#.to_string(),
         )
         .await;
@@ -595,6 +487,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             "invalid json".to_string(),
         )
         .await;
@@ -613,6 +506,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             r#"{"jsonrpc":"2.0","method":"ping","id":1}"#.to_string(),
         )
         .await;
@@ -631,6 +525,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             r#"{"jsonrpc":"2.0","method":"initialize","params":{},"id":1}"#.to_string(),
         )
         .await;
@@ -652,6 +547,7 @@ mod tests {
             Some(session_id.clone()),
             None,
             None,
+            None,
             r#"{"jsonrpc":"2.0","method":"ping","id":1}"#.to_string(),
         )
         .await;
@@ -671,6 +567,7 @@ mod tests {
             None,
             None,
             None,
+            None,
             r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#.to_string(),
         )
         .await;
@@ -678,6 +575,31 @@ mod tests {
         assert!(response.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_handle_mcp_post_sse_response() {
+        let state = Arc::new(
+            McpState::new(TestHandler)
+                .with_response_mode(mcpkit_server::service::ResponseMode::Sse),
+        );
+
+        let response = handle_mcp_post(
+            state,
+            Some("2025-11-25".to_string()),
+            None,
+            None,
+            Some("application/json, text/event-stream".to_string()),
+            None,
+            r#"{"jsonrpc":"2.0","method":"ping","id":1}"#.to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "text/event-stream"
+        );
+    }
+
     #[test]
     fn test_with_protocol_version_filter() {
         // Just verify the filter can be created
@@ -10,6 +10,7 @@
 //! - Session management with automatic cleanup
 //! - Protocol version validation
 //! - CORS support via Warp filters
+//! - Prometheus `/metrics` endpoint (`prometheus` feature)
 //!
 //! # Quick Start
 //!
@@ -44,7 +45,11 @@ mod session;
 mod state;
 
 pub use error::WarpError;
-pub use handler::{handle_mcp_post, handle_sse};
+#[cfg(feature = "prometheus")]
+pub use handler::handle_metrics;
+pub use handler::{
+    handle_liveness, handle_mcp_post, handle_readiness, handle_sse, handle_task_events,
+};
 pub use router::McpRouter;
 pub use session::{DEFAULT_SESSION_TIMEOUT, SessionManager, SessionStore};
 pub use state::McpState;
@@ -52,7 +57,7 @@ pub use state::McpState;
 /// Prelude module for convenient imports.
 pub mod prelude {
     pub use crate::error::WarpError;
-    pub use crate::handler::{handle_mcp_post, handle_sse};
+    pub use crate::handler::{handle_mcp_post, handle_sse, handle_task_events};
     pub use crate::router::McpRouter;
     pub use crate::session::{DEFAULT_SESSION_TIMEOUT, SessionManager, SessionStore};
     pub use crate::state::McpState;
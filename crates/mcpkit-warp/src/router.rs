@@ -1,10 +1,15 @@
 //! Router builder for MCP endpoints in Warp.
 
 use crate::handler::{
-    handle_mcp_post, handle_sse, with_origin, with_protocol_version, with_session_id,
+    handle_mcp_post, handle_sse, handle_task_events, with_accept, with_last_event_id, with_origin,
+    with_protocol_version, with_session_id,
 };
 use crate::state::{HasServerInfo, McpState};
-use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ToolHandler};
+use mcpkit_server::health::{HealthChecker, HealthProbes, LIVENESS_PATH, READINESS_PATH};
+use mcpkit_server::service::ResponseMode;
+use mcpkit_server::shutdown::DEFAULT_SHUTDOWN_TIMEOUT;
+use mcpkit_server::{PromptHandler, ResourceHandler, ServerHandler, ShutdownHandle, ToolHandler};
+use mcpkit_transport::TransportLimits;
 use mcpkit_transport::http::OriginValidator;
 use std::convert::Infallible;
 use std::net::SocketAddr;
@@ -33,8 +38,12 @@ use warp::Filter;
 pub struct McpRouter<H> {
     state: Arc<McpState<H>>,
     enable_cors: bool,
+    body_limit: u64,
 }
 
+/// Default limit on POST bodies, in bytes.
+const DEFAULT_BODY_LIMIT: u64 = 1024 * 1024;
+
 impl<H> McpRouter<H>
 where
     H: ServerHandler
@@ -51,6 +60,7 @@ where
         Self {
             state: Arc::new(McpState::new(handler)),
             enable_cors: false,
+            body_limit: DEFAULT_BODY_LIMIT,
         }
     }
 
@@ -61,6 +71,16 @@ where
         self
     }
 
+    /// Get a [`ShutdownHandle`] that stops [`serve`](Self::serve) gracefully.
+    ///
+    /// Once triggered, new requests get `503 Service Unavailable`, open SSE
+    /// streams end, and `serve` returns after in-flight requests finish or
+    /// [`DEFAULT_SHUTDOWN_TIMEOUT`] elapses.
+    #[must_use]
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.state.shutdown.clone()
+    }
+
     /// Restrict which browser `Origin`s are accepted, for DNS-rebinding
     /// protection.
     ///
@@ -117,6 +137,136 @@ where
         self
     }
 
+    /// Rate limit requests per session, per method, and per tool.
+    ///
+    /// Buckets are keyed by the `mcp-session-id` header. A request over a
+    /// quota gets a JSON-RPC error whose `data.retry_after_ms` says when to
+    /// retry. Disabled by default.
+    #[must_use]
+    pub fn with_rate_limit(mut self, config: mcpkit_server::RateLimitConfig) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.rate_limiter = Some(mcpkit_server::RateLimiter::new(config));
+        }
+        self
+    }
+
+    /// Check `tools/call` requests against a tool policy.
+    ///
+    /// The policy sees the `VerifiedUser` your auth middleware attached, and a
+    /// denied call gets a JSON-RPC error with code `-32011`. Disabled by
+    /// default.
+    #[must_use]
+    pub fn with_tool_authorizer(mut self, authorizer: mcpkit_server::ToolAuthorizer) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.tool_authorizer = Some(authorizer);
+        }
+        self
+    }
+
+    /// Write an audit record for each tool call and resource read.
+    ///
+    /// Records carry the `mcp-session-id` and the verified user's subject.
+    /// See [`AuditLogger`](mcpkit_server::AuditLogger) for sinks, sampling,
+    /// and redaction. Disabled by default.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: mcpkit_server::AuditLogger) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.audit_logger = Some(logger);
+        }
+        self
+    }
+
+    /// Trace tool, resource, and prompt invocations.
+    ///
+    /// Each call runs in an `mcp.tool`, `mcp.resource`, or `mcp.prompt` span
+    /// carrying the request and session ids, duration, and outcome. See
+    /// [`InvocationTracing`](mcpkit_server::InvocationTracing) for argument
+    /// capture. Arguments are not captured by default.
+    #[must_use]
+    pub fn with_invocation_tracing(mut self, tracing: mcpkit_server::InvocationTracing) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.invocation_tracing = tracing;
+        }
+        self
+    }
+
+    /// Record request counts, errors, and latency percentiles per method and
+    /// per tool into the layer's
+    /// [`ServerMetrics`](mcpkit_server::ServerMetrics).
+    ///
+    /// With [`MetricsLayer::expose_resource`](mcpkit_server::MetricsLayer::expose_resource),
+    /// clients can also read a JSON snapshot from the `metrics://server`
+    /// resource. Disabled by default.
+    #[must_use]
+    pub fn with_server_metrics(mut self, metrics: mcpkit_server::MetricsLayer) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.server_metrics = Some(metrics);
+        }
+        self
+    }
+
+    /// Choose how POSTed requests are answered when the client's `Accept`
+    /// header allows both JSON and SSE.
+    ///
+    /// With [`ResponseMode::Sse`], the response is a `text/event-stream`
+    /// carrying the notifications the handler sent (progress, logs) and then
+    /// the JSON-RPC response. Clients that accept only `application/json`
+    /// still get plain JSON. Defaults to [`ResponseMode::Json`].
+    #[must_use]
+    pub fn with_response_mode(mut self, mode: ResponseMode) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.response_mode = mode;
+        }
+        self
+    }
+
+    /// Apply [`TransportLimits`] to the MCP endpoints.
+    ///
+    /// Bodies over `max_message_size` (1 MB by default) are answered with
+    /// `413 Payload Too Large` and requests beyond `max_in_flight` with `503
+    /// Service Unavailable`. `read_timeout` is not applied.
+    #[must_use]
+    pub fn with_limits(mut self, limits: TransportLimits) -> Self {
+        self.body_limit = limits.max_message_size as u64;
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.limits = mcpkit_server::service::ServiceLimits::new(limits);
+        }
+        self
+    }
+
+    /// Record Prometheus metrics and serve them at `GET /metrics`.
+    ///
+    /// Exports request counts and latencies per JSON-RPC method, error counts
+    /// by JSON-RPC error code, open sessions, open SSE streams, and tool calls
+    /// and failures per tool. Requires the `prometheus` feature.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_metrics(self) -> Self {
+        let metrics =
+            mcpkit_transport::McpMetrics::new().expect("MCP metric names are valid and distinct");
+        self.with_prometheus(metrics)
+    }
+
+    /// Like [`with_metrics`](Self::with_metrics), recording into an existing
+    /// [`McpMetrics`](mcpkit_transport::McpMetrics), e.g. one shared with a
+    /// transport's `TelemetryLayer`.
+    #[cfg(feature = "prometheus")]
+    #[must_use]
+    pub fn with_prometheus(mut self, metrics: mcpkit_transport::McpMetrics) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.metrics = Some(metrics);
+        }
+        self
+    }
+
     /// Set the default task retention (milliseconds) for each session's task
     /// store, applied when a task-augmented `tools/call` omits a `ttl`. Pass
     /// `None` for unlimited retention.
@@ -129,6 +279,50 @@ where
         self
     }
 
+    /// Set session lifetimes, the session cap, and the SSE keep-alive
+    /// interval.
+    ///
+    /// A reaper task, started with the first SSE stream, ends streams idle
+    /// past `idle_timeout` or older than `max_age` and reports each eviction
+    /// to the config's `on_evict` callbacks. The idle timeout also applies to
+    /// HTTP sessions. See [`SessionConfig`](mcpkit_server::SessionConfig) for
+    /// the defaults.
+    #[must_use]
+    pub fn with_session_config(mut self, config: mcpkit_server::SessionConfig) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.sessions = state
+                .sessions
+                .clone()
+                .with_idle_timeout(config.idle_timeout);
+            state.sse_sessions = state.sse_sessions.clone().with_session_config(config);
+        }
+        self
+    }
+
+    /// Serve Kubernetes liveness and readiness probes at `/healthz` and
+    /// `/readyz`.
+    ///
+    /// `/healthz` answers `200` while the process responds. `/readyz`
+    /// answers `503` once shutdown begins. Add `?verbose` for a JSON report.
+    /// Use [`with_readiness_checks`](Self::with_readiness_checks) to make
+    /// readiness depend on your own components as well.
+    #[must_use]
+    pub fn with_health_checks(self) -> Self {
+        let checker = HealthChecker::new(&self.state.server_info.name);
+        self.with_readiness_checks(checker)
+    }
+
+    /// Like [`with_health_checks`](Self::with_health_checks), with `/readyz`
+    /// also running `checker`'s checks (e.g. that the database is reachable).
+    #[must_use]
+    pub fn with_readiness_checks(mut self, checker: HealthChecker) -> Self {
+        // The builder owns the only reference to the state at this point.
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.health = Some(HealthProbes::new(checker));
+        }
+        self
+    }
+
     fn set_origin_validator(&mut self, validator: OriginValidator) {
         // The builder owns the only reference to the state at this point, so
         // `get_mut` succeeds.
@@ -157,19 +351,31 @@ where
             .and(with_protocol_version())
             .and(with_session_id())
             .and(with_origin())
-            .and(warp::body::content_length_limit(1024 * 1024)) // 1MB limit
+            .and(with_accept())
+            .and(warp::body::content_length_limit(self.body_limit))
             .and(warp::body::bytes())
             .and_then(
                 |state: Arc<McpState<H>>,
                  version: Option<String>,
                  session_id: Option<String>,
                  origin: Option<String>,
+                 accept: Option<String>,
                  bytes: bytes::Bytes| async move {
                     let body = String::from_utf8_lossy(&bytes).to_string();
-                    handle_mcp_post(state, version, session_id, origin, None, body).await
+                    handle_mcp_post(state, version, session_id, origin, accept, None, body).await
                 },
             );
 
+        // GET /healthz and /readyz - Kubernetes probes
+        let health = health_routes(state.clone());
+
+        // GET /mcp/tasks/{task_id}/events - one task's progress as SSE
+        let task_events = task_events_route(state.clone());
+
+        // GET /metrics - Prometheus metrics
+        #[cfg(feature = "prometheus")]
+        let metrics = metrics_route(state.clone());
+
         // GET /mcp/sse - Server-Sent Events
         let sse_state = state;
         let mcp_sse = warp::path("mcp")
@@ -184,8 +390,12 @@ where
                 },
             );
 
+        let routes = mcp_post.or(mcp_sse).or(task_events).or(health);
+        #[cfg(feature = "prometheus")]
+        let routes = routes.or(metrics);
+
         // Combine routes with CORS
-        mcp_post.or(mcp_sse).with(
+        routes.with(
             warp::cors()
                 .allow_any_origin()
                 .allow_methods(vec!["GET", "POST", "OPTIONS"])
@@ -216,19 +426,31 @@ where
             .and(with_protocol_version())
             .and(with_session_id())
             .and(with_origin())
-            .and(warp::body::content_length_limit(1024 * 1024)) // 1MB limit
+            .and(with_accept())
+            .and(warp::body::content_length_limit(self.body_limit))
             .and(warp::body::bytes())
             .and_then(
                 |state: Arc<McpState<H>>,
                  version: Option<String>,
                  session_id: Option<String>,
                  origin: Option<String>,
+                 accept: Option<String>,
                  bytes: bytes::Bytes| async move {
                     let body = String::from_utf8_lossy(&bytes).to_string();
-                    handle_mcp_post(state, version, session_id, origin, None, body).await
+                    handle_mcp_post(state, version, session_id, origin, accept, None, body).await
                 },
             );
 
+        // GET /healthz and /readyz - Kubernetes probes
+        let health = health_routes(state.clone());
+
+        // GET /mcp/tasks/{task_id}/events - one task's progress as SSE
+        let task_events = task_events_route(state.clone());
+
+        // GET /metrics - Prometheus metrics
+        #[cfg(feature = "prometheus")]
+        let metrics = metrics_route(state.clone());
+
         // GET /mcp/sse - Server-Sent Events
         let sse_state = state;
         let mcp_sse = warp::path("mcp")
@@ -243,7 +465,10 @@ where
                 },
             );
 
-        mcp_post.or(mcp_sse)
+        let routes = mcp_post.or(mcp_sse).or(task_events).or(health);
+        #[cfg(feature = "prometheus")]
+        let routes = routes.or(metrics);
+        routes
     }
 
     /// Serve the MCP server on the given address.
@@ -257,9 +482,26 @@ where
     /// // warp pattern (now similar):
     /// McpRouter::new(handler).serve(([0, 0, 0, 0], 3000)).await;
     /// ```
+    ///
+    /// Stops gracefully once the [`shutdown_handle`](Self::shutdown_handle)
+    /// is triggered.
     pub async fn serve(self, addr: impl Into<SocketAddr>) {
+        let shutdown = self.shutdown_handle();
         let filter = self.into_filter();
-        warp::serve(filter).run(addr).await;
+        let addr: SocketAddr = addr.into();
+        let (_, server) = warp::serve(filter).bind_with_graceful_shutdown(addr, shutdown.wait());
+
+        // Bound how long open connections may hold the shutdown up.
+        let deadline = async {
+            shutdown.wait().await;
+            tokio::time::sleep(DEFAULT_SHUTDOWN_TIMEOUT).await;
+        };
+        tokio::select! {
+            () = server => {}
+            () = deadline => {
+                tracing::warn!("Shutdown timeout elapsed; dropping open connections");
+            }
+        }
     }
 }
 
@@ -270,6 +512,60 @@ fn with_state<H: Send + Sync + 'static>(
     warp::any().map(move || state.clone())
 }
 
+/// `GET /healthz` and `GET /readyz`, served by
+/// [`handle_liveness`](crate::handler::handle_liveness) and
+/// [`handle_readiness`](crate::handler::handle_readiness). Both reject unless
+/// health checks are enabled.
+fn health_routes<H: Send + Sync + 'static>(
+    state: Arc<McpState<H>>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    let query = warp::query::raw().or(warp::any().map(String::new)).unify();
+    let probe = |path: &'static str| {
+        warp::path(path.trim_start_matches('/'))
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and(query)
+    };
+    let liveness = probe(LIVENESS_PATH).and_then(crate::handler::handle_liveness::<H>);
+    let readiness = probe(READINESS_PATH).and_then(crate::handler::handle_readiness::<H>);
+    liveness.or(readiness).unify()
+}
+
+/// `GET /mcp/tasks/{task_id}/events`, served by
+/// [`handle_task_events`](crate::handler::handle_task_events).
+fn task_events_route<H: Send + Sync + 'static>(
+    state: Arc<McpState<H>>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    warp::path!("mcp" / "tasks" / String / "events")
+        .and(warp::get())
+        .and(with_state(state))
+        .and(with_session_id())
+        .and(with_origin())
+        .and(with_last_event_id())
+        .map(
+            |task_id: String,
+             state: Arc<McpState<H>>,
+             session_id: Option<String>,
+             origin: Option<String>,
+             last_event_id: Option<String>| {
+                handle_task_events(state, task_id, session_id, origin, last_event_id, None)
+            },
+        )
+}
+
+/// `GET /metrics`, served by [`handle_metrics`](crate::handler::handle_metrics).
+#[cfg(feature = "prometheus")]
+fn metrics_route<H: Send + Sync + 'static>(
+    state: Arc<McpState<H>>,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = warp::Rejection> + Clone {
+    warp::path("metrics")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state))
+        .and_then(crate::handler::handle_metrics::<H>)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,4 +693,46 @@ mod tests {
                 .is_allowed(Some("https://evil.example.com"))
         );
     }
+
+    #[tokio::test]
+    async fn task_events_stream_until_terminal_and_resume() {
+        let router = McpRouter::new(TestHandler);
+        let session_id = router.state.sessions.create();
+        let tasks = router.state.sessions.tasks(&session_id).unwrap();
+        let handle = tasks.create(None);
+        handle.record_progress(1.0, None, None).unwrap();
+        handle.complete(serde_json::json!({})).unwrap();
+        let path = format!("/mcp/tasks/{}/events", handle.id().as_str());
+        let filter = router.into_filter_without_cors();
+
+        let resp = warp::test::request()
+            .path(&path)
+            .header("mcp-session-id", &session_id)
+            .reply(&filter)
+            .await;
+        assert_eq!(resp.status(), 200);
+        let body = String::from_utf8(resp.body().to_vec()).unwrap();
+        let events: Vec<_> = body
+            .lines()
+            .filter_map(|line| line.strip_prefix("event:"))
+            .collect();
+        assert_eq!(events, ["status", "progress", "status"], "{body}");
+
+        let resp = warp::test::request()
+            .path(&path)
+            .header("mcp-session-id", &session_id)
+            .header("last-event-id", "2")
+            .reply(&filter)
+            .await;
+        let body = String::from_utf8(resp.body().to_vec()).unwrap();
+        assert_eq!(body.matches("event:").count(), 1, "{body}");
+        assert!(body.contains("id:3"), "{body}");
+
+        let resp = warp::test::request()
+            .path("/mcp/tasks/unknown/events")
+            .header("mcp-session-id", &session_id)
+            .reply(&filter)
+            .await;
+        assert_eq!(resp.status(), 404);
+    }
 }
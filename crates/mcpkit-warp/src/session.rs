@@ -4,7 +4,10 @@ use dashmap::DashMap;
 use mcpkit_core::auth::{SessionBindingError, VerifiedUser, check_session_binding};
 use mcpkit_core::capability::ClientCapabilities;
 use mcpkit_core::protocol_version::ProtocolVersion;
-use std::sync::Arc;
+use mcpkit_server::service::{SessionSnapshot, SessionStorage};
+use mcpkit_server::session::{EvictionReason, SessionConfig};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 use uuid::Uuid;
@@ -13,11 +16,18 @@ use uuid::Uuid;
 pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(3600);
 
 /// Session manager for tracking MCP client sessions.
+///
+/// SSE sessions are evicted according to a [`SessionConfig`]: by the reaper
+/// task (see [`Self::start_reaper`]) once idle or too old, and when a new
+/// SSE session needs room under the maximum session count. Evicting a
+/// session closes its channel, which ends its SSE stream.
 #[derive(Clone)]
 pub struct SessionStore {
     sessions: Arc<DashMap<String, SessionState>>,
-    sse_channels: Arc<DashMap<String, broadcast::Sender<String>>>,
+    sse_channels: Arc<DashMap<String, SseChannel>>,
     idle_timeout: Duration,
+    config: SessionConfig,
+    reaper_started: Arc<AtomicBool>,
     /// Default task retention (ms) applied to each session's task store; `None`
     /// means unlimited. Configure via `McpRouter::with_task_ttl`.
     pub(crate) default_task_ttl: Option<u64>,
@@ -36,6 +46,24 @@ struct SessionState {
     tasks: Arc<mcpkit_server::capability::tasks::TaskManager>,
 }
 
+/// An SSE session's channel and activity times.
+struct SseChannel {
+    tx: broadcast::Sender<String>,
+    created_at: Instant,
+    last_active: Instant,
+}
+
+impl SseChannel {
+    fn new(tx: broadcast::Sender<String>) -> Self {
+        let now = Instant::now();
+        Self {
+            tx,
+            created_at: now,
+            last_active: now,
+        }
+    }
+}
+
 impl Default for SessionStore {
     fn default() -> Self {
         Self::new()
@@ -50,6 +78,8 @@ impl SessionStore {
             sessions: Arc::new(DashMap::new()),
             sse_channels: Arc::new(DashMap::new()),
             idle_timeout: DEFAULT_SESSION_TIMEOUT,
+            config: SessionConfig::default(),
+            reaper_started: Arc::new(AtomicBool::new(false)),
             default_task_ttl: Some(mcpkit_server::capability::tasks::DEFAULT_TASK_TTL_MS),
         }
     }
@@ -62,6 +92,19 @@ impl SessionStore {
         self
     }
 
+    /// Set the lifetime settings for SSE sessions.
+    #[must_use]
+    pub fn with_session_config(mut self, config: SessionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The lifetime settings for SSE sessions.
+    #[must_use]
+    pub const fn session_config(&self) -> &SessionConfig {
+        &self.config
+    }
+
     /// Create a new session and return its ID.
     ///
     /// Sessions idle past the idle timeout are reaped first, so the store stays
@@ -102,6 +145,9 @@ impl SessionStore {
         if let Some(mut session) = self.sessions.get_mut(id) {
             session.last_seen = Instant::now();
         }
+        if let Some(mut channel) = self.sse_channels.get_mut(id) {
+            channel.last_active = Instant::now();
+        }
     }
 
     /// Touch a session, enforcing its user binding against the identity
@@ -160,19 +206,73 @@ impl SessionStore {
         self.sessions.contains_key(id)
     }
 
+    /// Get the number of active sessions.
+    #[must_use]
+    pub fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
     /// Get or create an SSE channel for a session.
+    ///
+    /// At the maximum session count, the least recently active SSE session
+    /// is evicted first.
     #[must_use]
     pub fn create_session(&self) -> (String, broadcast::Receiver<String>) {
+        if self.config.at_capacity(self.sse_channels.len()) {
+            let oldest = self
+                .sse_channels
+                .iter()
+                .min_by_key(|entry| entry.last_active)
+                .map(|entry| entry.key().clone());
+            if let Some(oldest) = oldest {
+                evict(
+                    &self.sse_channels,
+                    &self.sessions,
+                    &self.config,
+                    &oldest,
+                    EvictionReason::Capacity,
+                );
+            }
+        }
+
         let id = self.create();
         let (tx, rx) = broadcast::channel(100);
-        self.sse_channels.insert(id.clone(), tx);
+        self.sse_channels.insert(id.clone(), SseChannel::new(tx));
         (id, rx)
     }
 
-    /// Get a receiver for an existing SSE session.
+    /// Get a receiver for an existing SSE session, marking it active.
     #[must_use]
     pub fn get_receiver(&self, id: &str) -> Option<broadcast::Receiver<String>> {
-        self.sse_channels.get(id).map(|tx| tx.subscribe())
+        self.sse_channels.get_mut(id).map(|mut channel| {
+            channel.last_active = Instant::now();
+            channel.tx.subscribe()
+        })
+    }
+
+    /// Evict SSE sessions that are idle or past their maximum age, returning
+    /// how many were evicted.
+    #[must_use]
+    pub fn reap_expired(&self) -> usize {
+        reap(&self.sse_channels, &self.sessions, &self.config)
+    }
+
+    /// Start the background task that calls [`Self::reap_expired`] every
+    /// [`reap_interval`](SessionConfig::reap_interval).
+    ///
+    /// Only the first call on a store or its clones starts a task; the task
+    /// stops once the store and its clones are dropped. Must be called
+    /// within a Tokio runtime. The SSE handler calls this when it opens a
+    /// stream.
+    pub fn start_reaper(&self) {
+        if self.reaper_started.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        tokio::spawn(reap_periodically(
+            Arc::downgrade(&self.sse_channels),
+            Arc::downgrade(&self.sessions),
+            self.config.clone(),
+        ));
     }
 
     /// Remove sessions older than the given duration.
@@ -183,6 +283,55 @@ impl SessionStore {
     }
 }
 
+fn reap(
+    channels: &DashMap<String, SseChannel>,
+    sessions: &DashMap<String, SessionState>,
+    config: &SessionConfig,
+) -> usize {
+    let expired: Vec<(String, EvictionReason)> = channels
+        .iter()
+        .filter_map(|entry| {
+            config
+                .eviction_reason(entry.created_at, entry.last_active)
+                .map(|reason| (entry.key().clone(), reason))
+        })
+        .collect();
+    for (id, reason) in &expired {
+        evict(channels, sessions, config, id, *reason);
+    }
+    expired.len()
+}
+
+fn evict(
+    channels: &DashMap<String, SseChannel>,
+    sessions: &DashMap<String, SessionState>,
+    config: &SessionConfig,
+    id: &str,
+    reason: EvictionReason,
+) {
+    if channels.remove(id).is_some() {
+        sessions.remove(id);
+        config.notify_evicted(id, reason);
+    }
+}
+
+async fn reap_periodically(
+    channels: Weak<DashMap<String, SseChannel>>,
+    sessions: Weak<DashMap<String, SessionState>>,
+    config: SessionConfig,
+) {
+    loop {
+        tokio::time::sleep(config.reap_interval).await;
+        let (Some(channels), Some(sessions)) = (channels.upgrade(), sessions.upgrade()) else {
+            break;
+        };
+        let reaped = reap(&channels, &sessions, &config);
+        if reaped > 0 {
+            tracing::debug!(reaped, "Reaped expired SSE sessions");
+        }
+    }
+}
+
 /// Session manager trait for managing MCP sessions.
 pub trait SessionManager {
     /// Create a new session.
@@ -209,6 +358,37 @@ impl SessionManager for SessionStore {
     }
 }
 
+impl SessionStorage for SessionStore {
+    fn open_session(&self, user: Option<VerifiedUser>) -> String {
+        self.create_for_user(user)
+    }
+
+    fn resume_session(
+        &self,
+        id: &str,
+        user: Option<&VerifiedUser>,
+    ) -> Result<bool, SessionBindingError> {
+        self.touch_verified(id, user)
+    }
+
+    fn record_negotiated(
+        &self,
+        id: &str,
+        protocol_version: ProtocolVersion,
+        capabilities: Option<ClientCapabilities>,
+    ) {
+        self.set_negotiated(id, protocol_version, capabilities);
+    }
+
+    fn session_snapshot(&self, id: &str) -> Option<SessionSnapshot> {
+        self.sessions.get(id).map(|s| SessionSnapshot {
+            protocol_version: s.protocol_version,
+            client_capabilities: s.client_capabilities.clone(),
+            tasks: Some(s.tasks.clone()),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,9 +473,9 @@ mod tests {
         let (id, mut rx) = store.create_session();
 
         // Get the sender and send
-        let tx = store.sse_channels.get(&id).unwrap();
-        tx.send("test message".to_string()).unwrap();
-        drop(tx);
+        let channel = store.sse_channels.get(&id).unwrap();
+        channel.tx.send("test message".to_string()).unwrap();
+        drop(channel);
 
         // Receive the message
         let msg = rx.recv().await.unwrap();
@@ -347,4 +527,51 @@ mod tests {
         assert_ne!(id2, id3);
         assert_ne!(id1, id3);
     }
+
+    #[tokio::test]
+    async fn reaper_evicts_idle_sse_sessions_and_ends_their_streams() {
+        let evicted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&evicted);
+        let store = SessionStore::new().with_session_config(
+            SessionConfig::new()
+                .with_idle_timeout(Duration::from_millis(50))
+                .with_reap_interval(Duration::from_millis(10))
+                .on_evict(move |id, reason| {
+                    recorded.lock().unwrap().push((id.to_string(), reason));
+                }),
+        );
+
+        let (idle, mut idle_rx) = store.create_session();
+        let (active, _active_rx) = store.create_session();
+        store.start_reaper();
+        store.clone().start_reaper();
+
+        for _ in 0..10 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            store.touch(&active);
+        }
+
+        assert!(matches!(
+            idle_rx.recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+        assert!(!store.exists(&idle));
+        assert!(store.get_receiver(&active).is_some());
+        assert_eq!(*evicted.lock().unwrap(), vec![(idle, EvictionReason::Idle)]);
+    }
+
+    #[test]
+    fn full_store_evicts_least_recently_active_sse_session() {
+        let store =
+            SessionStore::new().with_session_config(SessionConfig::new().with_max_sessions(2));
+
+        let (first, _) = store.create_session();
+        let (second, _) = store.create_session();
+        store.touch(&first);
+        let (third, _) = store.create_session();
+
+        assert!(store.get_receiver(&first).is_some());
+        assert!(store.get_receiver(&second).is_none());
+        assert!(store.get_receiver(&third).is_some());
+    }
 }
@@ -123,6 +123,86 @@ async fn test_ping_request() {
     assert_eq!(response.status(), 200);
 }
 
+#[tokio::test]
+async fn test_with_limits_rejects_oversized_bodies() {
+    let filter = McpRouter::new(TestHandler)
+        .with_limits(mcpkit_transport::TransportLimits::new().with_max_message_size(16))
+        .into_filter();
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/mcp")
+        .header("content-type", "application/json")
+        .header("mcp-protocol-version", "2025-11-25")
+        .body(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+        .reply(&filter)
+        .await;
+
+    assert_eq!(response.status(), 413);
+}
+
+#[cfg(feature = "prometheus")]
+#[tokio::test]
+async fn test_with_metrics() {
+    let filter = McpRouter::new(TestHandler).with_metrics().into_filter();
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/mcp")
+        .header("content-type", "application/json")
+        .header("mcp-protocol-version", "2025-11-25")
+        .body(r#"{"jsonrpc":"2.0","method":"ping","id":1}"#)
+        .reply(&filter)
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let response = warp::test::request()
+        .method("GET")
+        .path("/metrics")
+        .reply(&filter)
+        .await;
+    assert_eq!(response.status(), 200);
+    let body = String::from_utf8(response.body().to_vec()).unwrap();
+    assert!(
+        body.contains(r#"mcp_requests_total{method="ping"} 1"#),
+        "{body}"
+    );
+    assert!(body.contains("mcp_active_sessions 1"), "{body}");
+}
+
+#[tokio::test]
+async fn test_health_checks() {
+    let unmounted = McpRouter::new(TestHandler).into_filter();
+    let response = warp::test::request()
+        .path("/healthz")
+        .reply(&unmounted)
+        .await;
+    assert_eq!(response.status(), 404);
+
+    let mut checker = mcpkit_server::HealthChecker::new("test-server");
+    checker.add_check("database", mcpkit_server::ComponentHealth::healthy);
+    let router = McpRouter::new(TestHandler).with_readiness_checks(checker);
+    let shutdown = router.shutdown_handle();
+    let filter = router.into_filter();
+
+    let response = warp::test::request().path("/healthz").reply(&filter).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body(), "ok");
+
+    let response = warp::test::request()
+        .path("/readyz?verbose")
+        .reply(&filter)
+        .await;
+    assert_eq!(response.status(), 200);
+    let report: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(report["components"]["database"]["status"], "healthy");
+
+    shutdown.shutdown();
+    let response = warp::test::request().path("/readyz").reply(&filter).await;
+    assert_eq!(response.status(), 503);
+    assert_eq!(response.body(), "not ready");
+}
+
 #[tokio::test]
 async fn test_initialize_request() {
     let filter = McpRouter::new(TestHandler).into_filter();
@@ -283,11 +283,11 @@ impl DatabaseServer {
         description = "Schema for a specific table",
         mime_type = "application/json"
     )]
-    async fn get_table_schema(&self, uri: &str) -> Result<ResourceContents, McpError> {
-        let table_name = uri
-            .strip_prefix("db://tables/")
-            .ok_or_else(|| McpError::invalid_request("Invalid URI format"))?;
-
+    async fn get_table_schema(
+        &self,
+        uri: &str,
+        table_name: &str,
+    ) -> Result<ResourceContents, McpError> {
         let tables = self.db.tables.read().await;
         let table = tables
             .iter()
@@ -305,12 +305,11 @@ impl DatabaseServer {
         description = "Sample rows from a table (first 10 rows)",
         mime_type = "application/json"
     )]
-    async fn get_table_sample(&self, uri: &str) -> Result<ResourceContents, McpError> {
-        let table_name = uri
-            .strip_prefix("db://tables/")
-            .and_then(|s| s.strip_suffix("/sample"))
-            .ok_or_else(|| McpError::invalid_request("Invalid URI format"))?;
-
+    async fn get_table_sample(
+        &self,
+        uri: &str,
+        table_name: &str,
+    ) -> Result<ResourceContents, McpError> {
         // Validate table exists
         let tables = self.db.tables.read().await;
         if !tables
@@ -5,7 +5,7 @@
 //!
 //! ## Features
 //!
-//! - Read and write files
+//! - Read and write files, including images
 //! - List directories
 //! - Search for files by pattern
 //! - Get file metadata
@@ -133,8 +133,7 @@ impl FilesystemServer {
 impl FilesystemServer {
     /// Read the contents of a file.
     ///
-    /// Returns the file contents as text. For binary files, consider using
-    /// base64 encoding on the client side.
+    /// Returns the file contents as text. Use `read_image` for images.
     #[tool(description = "Read the contents of a file", read_only = true)]
     async fn read_file(&self, path: String) -> ToolOutput {
         let resolved = match self.resolve_path(&path) {
@@ -148,6 +147,24 @@ impl FilesystemServer {
         }
     }
 
+    /// Read an image file.
+    ///
+    /// The MIME type is detected from the file, and files over 10 MiB are
+    /// refused.
+    #[tool(description = "Read an image file", read_only = true)]
+    async fn read_image(&self, path: String) -> ToolOutput {
+        let resolved = match self.resolve_path(&path) {
+            Ok(p) => p,
+            Err(e) => return ToolOutput::error(e),
+        };
+
+        match tokio::task::spawn_blocking(move || Content::image_from_path(resolved)).await {
+            Ok(Ok(image)) => ToolOutput::content(vec![image]),
+            Ok(Err(e)) => ToolOutput::error(format!("Failed to read image '{}': {}", path, e)),
+            Err(e) => ToolOutput::error(format!("Failed to read image '{}': {}", path, e)),
+        }
+    }
+
     /// Write content to a file.
     ///
     /// Creates the file if it doesn't exist, or overwrites if it does.
@@ -568,6 +585,32 @@ mod tests {
         assert!(escaped.is_err());
     }
 
+    #[tokio::test]
+    async fn test_read_image() {
+        let temp = tempdir().unwrap();
+        let root = temp.path().to_path_buf();
+        fs::write(
+            root.join("pixel.png"),
+            b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR",
+        )
+        .unwrap();
+        let server = Arc::new(FilesystemServer::new(root));
+
+        match FilesystemServer::read_image(&server, "pixel.png".to_string()).await {
+            ToolOutput::Success(r) => match &r.content[0] {
+                Content::Image(image) => assert_eq!(image.mime_type, "image/png"),
+                _ => panic!("Expected image content"),
+            },
+            ToolOutput::RecoverableError { message, .. } => panic!("Read failed: {}", message),
+        }
+
+        fs::write(server.allowed_root.join("notes.txt"), "hello").unwrap();
+        assert!(matches!(
+            FilesystemServer::read_image(&server, "notes.txt".to_string()).await,
+            ToolOutput::RecoverableError { .. }
+        ));
+    }
+
     #[tokio::test]
     async fn test_read_write_file() {
         let temp = tempdir().unwrap();
@@ -111,9 +111,7 @@ impl FullServer {
         description = "Get a specific configuration value",
         mime_type = "text/plain"
     )]
-    async fn get_config_value(&self, uri: &str) -> ResourceContents {
-        // Extract key from URI like "config://app/debug"
-        let key = uri.strip_prefix("config://app/").unwrap_or("");
+    async fn get_config_value(&self, uri: &str, key: &str) -> ResourceContents {
         let value = self
             .config
             .get(key)
@@ -52,9 +52,6 @@ fn demonstrate_client_config() {
 }
 
 /// Demonstrates gRPC server configuration options.
-///
-/// Note: The gRPC server implementation is currently a work-in-progress.
-/// This shows the intended API for when the server is fully implemented.
 fn demonstrate_server_config() {
     info!("=== gRPC Server Configuration ===");
 
@@ -64,8 +61,9 @@ fn demonstrate_server_config() {
 
     // Full server configuration
     let full_config = GrpcServerConfig::new("0.0.0.0:50051")
-        .with_tls()
+        .with_tls_identity("cert.pem contents", "key.pem contents")
         .max_concurrent_streams(200)
+        .max_connections(1000)
         .tcp_keepalive(Duration::from_secs(60))
         .http2_keepalive_interval(Duration::from_secs(30));
 
@@ -149,7 +147,7 @@ async fn demonstrate_server_api() {
     let server_handle = server.clone();
     let accept_task = tokio::spawn(async move {
         info!("Waiting for incoming connection...");
-        if let Some(transport) = server_handle.accept().await {
+        if let Ok(transport) = server_handle.accept().await {
             info!(
                 "Accepted connection from: {:?}",
                 transport.metadata().remote_addr
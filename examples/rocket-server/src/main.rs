@@ -246,7 +246,7 @@ async fn main() -> Result<(), rocket::Error> {
     // Build and launch Rocket
     let _ = rocket::build()
         .manage(state)
-        .mount("/", rocket::routes![mcp_post, mcp_sse])
+        .mount("/", rocket::routes![mcp_post, mcp_sse, mcp_task_events])
         .launch()
         .await?;
 
@@ -0,0 +1,62 @@
+//! Fuzz target for the newline-delimited framing layer.
+//!
+//! Feeds arbitrary bytes to `LineDecoder` in arbitrary chunk sizes and checks
+//! that it never panics, never buffers more than the cap, and produces the
+//! same frames as splitting the whole input at once.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use bytes::BytesMut;
+use libfuzzer_sys::fuzz_target;
+use mcpkit_transport::framing::{Frame, LineDecoder};
+
+#[derive(Debug, Arbitrary)]
+struct FuzzInput {
+    /// Maximum frame size (kept small so the cap is exercised).
+    max: u8,
+    /// Sizes of the chunks the input is delivered in.
+    chunks: Vec<u8>,
+    /// The raw byte stream.
+    data: Vec<u8>,
+}
+
+fn trimmed(line: &[u8]) -> Option<&[u8]> {
+    let start = line.iter().position(|b| !b.is_ascii_whitespace())?;
+    let end = line.iter().rposition(|b| !b.is_ascii_whitespace())? + 1;
+    Some(&line[start..end])
+}
+
+fuzz_target!(|input: FuzzInput| {
+    let max = usize::from(input.max).max(1);
+    let mut decoder = LineDecoder::new(max);
+    let mut buf = BytesMut::new();
+    let mut frames = Vec::new();
+
+    let mut rest = input.data.as_slice();
+    let mut sizes = input.chunks.iter().map(|&n| usize::from(n).max(1)).cycle();
+    while !rest.is_empty() {
+        let n = sizes.next().unwrap_or(rest.len()).min(rest.len());
+        buf.extend_from_slice(&rest[..n]);
+        rest = &rest[n..];
+        while let Some(frame) = decoder.decode(&mut buf) {
+            frames.push(frame);
+        }
+        assert!(buf.len() <= max, "buffered {} bytes past cap {max}", buf.len());
+    }
+    frames.extend(decoder.decode_eof(&mut buf));
+    assert!(buf.is_empty());
+
+    let expected: Vec<Frame> = input
+        .data
+        .split(|&b| b == b'\n')
+        .filter_map(|line| {
+            if line.len() > max {
+                Some(Frame::TooLarge { size: line.len() })
+            } else {
+                trimmed(line).map(|l| Frame::Line(bytes::Bytes::copy_from_slice(l)))
+            }
+        })
+        .collect();
+    assert_eq!(frames, expected);
+});
@@ -94,9 +94,10 @@ pub use mcpkit_transport::{Transport, TransportListener, TransportMetadata};
 
 // Re-export macros
 pub use mcpkit_macros::{
-    ToolInput, elicitation, mcp_client, mcp_server, on_connected, on_disconnected,
-    on_prompts_list_changed, on_resource_updated, on_resources_list_changed, on_task_progress,
-    on_tools_list_changed, prompt, resource, roots, sampling, tool,
+    ElicitForm, Elicitable, ToolInput, completion, elicitation, mcp_client, mcp_server,
+    on_connected, on_disconnected, on_prompts_list_changed, on_resource_updated,
+    on_resources_list_changed, on_task_progress, on_tools_list_changed, prompt, resource, roots,
+    sampling, tool,
 };
 
 pub mod prelude;
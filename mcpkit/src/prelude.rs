@@ -40,15 +40,18 @@
 //! - `#[tool]` - Tool attribute
 //! - `#[resource]` - Resource attribute
 //! - `#[prompt]` - Prompt attribute
+//! - `#[completion]` - Completion attribute
 //! - `#[derive(ToolInput)]` - Parameter struct derive
+//! - `#[derive(Elicitable)]` - Elicitation form derive
 
 // Core types
 pub use mcpkit_core::prelude::*;
 
 // Server types
 pub use mcpkit_server::{
-    CompletionHandler, Context, ContextData, LogLevel, PromptHandler, ResourceHandler, Server,
-    ServerBuilder, ServerHandler, TaskHandler, ToolHandler,
+    CompletionHandler, Context, ContextData, LogLevel, ProgressReporter, PromptHandler,
+    ResourceHandler, SamplingSession, Server, ServerBuilder, ServerHandler, TaskHandler,
+    ToolHandler,
 };
 
 // Transport types
@@ -56,7 +59,7 @@ pub use mcpkit_transport::{Transport, TransportListener, TransportMetadata};
 
 // Macros - these are automatically available at crate root
 pub use mcpkit_macros::{
-    ToolInput, elicitation, mcp_client, mcp_server, on_connected, on_disconnected,
-    on_prompts_list_changed, on_resource_updated, on_resources_list_changed, on_task_progress,
-    on_tools_list_changed, prompt, resource, roots, sampling, tool,
+    Elicitable, ToolInput, completion, elicitation, mcp_client, mcp_server, on_connected,
+    on_disconnected, on_prompts_list_changed, on_resource_updated, on_resources_list_changed,
+    on_task_progress, on_tools_list_changed, prompt, resource, roots, sampling, tool,
 };
@@ -416,3 +416,100 @@ async fn test_list_resource_templates() -> Result<(), Box<dyn std::error::Error>
     assert!(names.contains(&"API v2"));
     Ok(())
 }
+
+/// Serves `mem://whole` through the default `read_resource_range`, and
+/// `seek://data` by producing only the requested range.
+struct Chunked {
+    full_reads: std::sync::atomic::AtomicUsize,
+}
+
+const SEEKABLE_SIZE: u64 = 10;
+
+impl mcpkit_server::ServerHandler for Chunked {
+    fn server_info(&self) -> mcpkit::capability::ServerInfo {
+        mcpkit::capability::ServerInfo::new("chunked", "1.0.0")
+    }
+
+    fn capabilities(&self) -> ServerCapabilities {
+        ServerCapabilities::new().with_resources()
+    }
+}
+
+impl ResourceHandler for Chunked {
+    async fn list_resources(
+        &self,
+        _ctx: &Context<'_>,
+    ) -> Result<Vec<mcpkit::types::Resource>, mcpkit::error::McpError> {
+        Ok(vec![])
+    }
+
+    async fn read_resource(
+        &self,
+        uri: &str,
+        _ctx: &Context<'_>,
+    ) -> Result<Vec<ResourceContents>, mcpkit::error::McpError> {
+        self.full_reads
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(vec![ResourceContents::text(uri, "abcdefghijklmnopq")])
+    }
+
+    async fn read_resource_range(
+        &self,
+        uri: &str,
+        range: mcpkit::types::ByteRange,
+        ctx: &Context<'_>,
+    ) -> Result<ResourceContents, mcpkit::error::McpError> {
+        if uri != "seek://data" {
+            let contents = self.read_resource(uri, ctx).await?;
+            return Ok(contents[0].slice(range)?);
+        }
+        let end = (range.offset + range.length).min(SEEKABLE_SIZE);
+        let data: Vec<u8> = (range.offset..end).map(|i| b'0' + i as u8).collect();
+        Ok(ResourceContents::chunk(
+            uri,
+            &data,
+            None,
+            range.offset,
+            SEEKABLE_SIZE,
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_read_resource_streaming_in_chunks() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::TryStreamExt;
+    use std::sync::Arc;
+
+    let (client_transport, server_transport) = mcpkit_transport::MemoryTransport::pair();
+    let handler = Arc::new(Chunked {
+        full_reads: std::sync::atomic::AtomicUsize::new(0),
+    });
+    let server = mcpkit_server::ServerBuilder::new(Arc::clone(&handler))
+        .with_resources(Arc::clone(&handler))
+        .build();
+    tokio::spawn(server.serve(server_transport));
+    let client = mcpkit_client::ClientBuilder::new()
+        .resource_chunk_size(4)
+        .build(client_transport)
+        .await?;
+
+    let chunks: Vec<_> = client
+        .read_resource_streaming("seek://data")
+        .try_collect()
+        .await?;
+    let sizes: Vec<_> = chunks.iter().map(bytes::Bytes::len).collect();
+    assert_eq!(sizes, [4, 4, 2]);
+    assert_eq!(chunks.concat(), b"0123456789");
+    assert_eq!(
+        handler.full_reads.load(std::sync::atomic::Ordering::SeqCst),
+        0
+    );
+
+    let whole: Vec<_> = client
+        .read_resource_streaming("mem://whole")
+        .try_collect()
+        .await?;
+    assert_eq!(whole.len(), 5);
+    assert_eq!(whole.concat(), b"abcdefghijklmnopq");
+    Ok(())
+}